@@ -0,0 +1,512 @@
+// Common interface implemented by every audio capture backend
+//
+// Each OS-specific capture (Core Audio tap, WASAPI loopback, PulseAudio monitor, CPAL
+// inputs) is wrapped behind `AudioCaptureBackend`, so the stream layer picks an
+// implementation from `CaptureBackendType` instead of branching per platform.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use cpal::traits::{DeviceTrait, StreamTrait};
+use futures_util::{Stream, StreamExt};
+use log::{error, info};
+use serde::Serialize;
+
+use super::backend_config::{CaptureBackendType, CaptureSource};
+use crate::audio::devices::{get_device_and_config, AudioDevice};
+
+/// Called with interleaved f32 samples in the backend's `format()`
+pub type SampleCallback = Box<dyn FnMut(&[f32]) + Send + 'static>;
+
+/// Called when the underlying CPAL stream reports an error
+pub type ErrorCallback = Box<dyn Fn(cpal::StreamError) + Send + 'static>;
+
+/// Sample format delivered by a backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CaptureFormat {
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// Peak level above which a block is flagged as clipping (-1 dBFS)
+pub const CLIP_THRESHOLD: f32 = 0.891_251;
+
+/// Floor used when converting silence to dBFS
+pub const MIN_DBFS: f32 = -100.0;
+
+/// Input levels observed by a backend (linear, 0.0 to 1.0)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct CaptureLevels {
+    pub rms: f32,
+    pub peak: f32,
+    /// A sample reached -1 dBFS or above
+    pub clipping: bool,
+}
+
+impl CaptureLevels {
+    /// Compute levels for one block of samples
+    pub fn from_samples(samples: &[f32]) -> Self {
+        let mut meter = LevelMeter::default();
+        meter.push(samples);
+        meter.take()
+    }
+
+    pub fn rms_dbfs(&self) -> f32 {
+        to_dbfs(self.rms)
+    }
+
+    pub fn peak_dbfs(&self) -> f32 {
+        to_dbfs(self.peak)
+    }
+}
+
+/// Convert a linear amplitude to dBFS, clamped at `MIN_DBFS`
+pub fn to_dbfs(linear: f32) -> f32 {
+    if linear <= 0.0 {
+        return MIN_DBFS;
+    }
+    (20.0 * linear.log10()).max(MIN_DBFS)
+}
+
+/// Accumulates levels across blocks until read, so a meter polled every 100 ms sees the
+/// loudest peak of the whole window rather than only the last block
+///
+/// Backends don't meter in their callback; whoever drains the captured samples pushes them here.
+#[derive(Debug, Default)]
+pub struct LevelMeter {
+    sum_sq: f64,
+    count: usize,
+    peak: f32,
+}
+
+impl LevelMeter {
+    pub fn push(&mut self, samples: &[f32]) {
+        for &s in samples {
+            self.sum_sq += (s * s) as f64;
+            self.peak = self.peak.max(s.abs());
+        }
+        self.count += samples.len();
+    }
+
+    /// Levels since the last call; resets the window
+    pub fn take(&mut self) -> CaptureLevels {
+        if self.count == 0 {
+            return CaptureLevels::default();
+        }
+        let levels = CaptureLevels {
+            rms: ((self.sum_sq / self.count as f64).sqrt() as f32).min(1.0),
+            peak: self.peak.min(1.0),
+            clipping: self.peak >= CLIP_THRESHOLD,
+        };
+        *self = Self::default();
+        levels
+    }
+}
+
+/// Audio capture backend
+///
+/// The format is negotiated when the backend is created, so callers can size their
+/// processing before `start`. Pausing drops samples at the source without tearing
+/// down the OS stream, which keeps resume instant.
+pub trait AudioCaptureBackend: Send {
+    /// Which backend this is
+    fn backend_type(&self) -> CaptureBackendType;
+
+    /// Negotiated sample format
+    fn format(&self) -> CaptureFormat;
+
+    /// Start delivering samples to `on_samples`
+    fn start(&mut self, on_samples: SampleCallback, on_error: ErrorCallback) -> Result<()>;
+
+    /// Stop capture and release the OS stream
+    fn stop(&mut self) -> Result<()>;
+
+    /// Temporarily stop delivering samples
+    fn pause(&self);
+
+    /// Resume delivering samples
+    fn resume(&self);
+
+    fn is_paused(&self) -> bool;
+}
+
+/// Pause flag shared between a backend and its capture callback
+#[derive(Default)]
+struct BackendControl {
+    paused: AtomicBool,
+}
+
+impl BackendControl {
+    /// Wrap a sample callback so it honours pause
+    fn wrap(self: &Arc<Self>, mut on_samples: SampleCallback) -> SampleCallback {
+        let control = self.clone();
+        Box::new(move |data: &[f32]| {
+            if control.paused.load(Ordering::Relaxed) {
+                return;
+            }
+            on_samples(data);
+        })
+    }
+}
+
+/// Frames converted per pass on the integer sample paths of `CpalCaptureBackend`
+const CONVERT_FRAMES: usize = 1024;
+
+/// Convert integer samples to f32 in `scratch` and forward them a scratch buffer at a time;
+/// the scratch holds whole frames and is allocated with the stream, so the audio thread
+/// never allocates
+fn forward_converted<T: Copy>(
+    data: &[T],
+    scratch: &mut [f32],
+    to_f32: impl Fn(T) -> f32,
+    on_samples: &mut SampleCallback,
+) {
+    for chunk in data.chunks(scratch.len()) {
+        let converted = &mut scratch[..chunk.len()];
+        for (out, &sample) in converted.iter_mut().zip(chunk) {
+            *out = to_f32(sample);
+        }
+        on_samples(converted);
+    }
+}
+
+/// CPAL input stream backend (microphones, and system devices CPAL opens as inputs)
+pub struct CpalCaptureBackend {
+    backend_type: CaptureBackendType,
+    device: cpal::Device,
+    config: cpal::SupportedStreamConfig,
+    stream: Option<cpal::Stream>,
+    control: Arc<BackendControl>,
+}
+
+// SAFETY: While Stream doesn't implement Send, it is only created, played and dropped
+// through this backend, which is owned by a single AudioStream at a time
+unsafe impl Send for CpalCaptureBackend {}
+
+impl CpalCaptureBackend {
+    pub async fn open(device: &AudioDevice, backend_type: CaptureBackendType) -> Result<Self> {
+        let (device, config) = get_device_and_config(device).await?;
+        Ok(Self {
+            backend_type,
+            device,
+            config,
+            stream: None,
+            control: Arc::new(BackendControl::default()),
+        })
+    }
+
+    fn build_stream(&self, mut on_samples: SampleCallback, on_error: ErrorCallback) -> Result<cpal::Stream> {
+        let stream_config: cpal::StreamConfig = self.config.clone().into();
+        let mut scratch = vec![0.0f32; CONVERT_FRAMES * stream_config.channels.max(1) as usize];
+
+        let stream = match self.config.sample_format() {
+            cpal::SampleFormat::F32 => self.device.build_input_stream(
+                &stream_config,
+                move |data: &[f32], _: &cpal::InputCallbackInfo| on_samples(data),
+                on_error,
+                None,
+            )?,
+            cpal::SampleFormat::I16 => self.device.build_input_stream(
+                &stream_config,
+                move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                    forward_converted(data, &mut scratch, |sample| sample as f32 / i16::MAX as f32, &mut on_samples)
+                },
+                on_error,
+                None,
+            )?,
+            cpal::SampleFormat::I32 => self.device.build_input_stream(
+                &stream_config,
+                move |data: &[i32], _: &cpal::InputCallbackInfo| {
+                    forward_converted(data, &mut scratch, |sample| sample as f32 / i32::MAX as f32, &mut on_samples)
+                },
+                on_error,
+                None,
+            )?,
+            cpal::SampleFormat::I8 => self.device.build_input_stream(
+                &stream_config,
+                move |data: &[i8], _: &cpal::InputCallbackInfo| {
+                    forward_converted(data, &mut scratch, |sample| sample as f32 / i8::MAX as f32, &mut on_samples)
+                },
+                on_error,
+                None,
+            )?,
+            other => return Err(anyhow!("Unsupported sample format: {:?}", other)),
+        };
+
+        Ok(stream)
+    }
+}
+
+impl AudioCaptureBackend for CpalCaptureBackend {
+    fn backend_type(&self) -> CaptureBackendType {
+        self.backend_type
+    }
+
+    fn format(&self) -> CaptureFormat {
+        CaptureFormat {
+            sample_rate: self.config.sample_rate().0,
+            channels: self.config.channels(),
+        }
+    }
+
+    fn start(&mut self, on_samples: SampleCallback, on_error: ErrorCallback) -> Result<()> {
+        if self.stream.is_some() {
+            return Ok(());
+        }
+        let stream = self.build_stream(self.control.wrap(on_samples), on_error)?;
+        stream.play()?;
+        self.stream = Some(stream);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.stream.take();
+        Ok(())
+    }
+
+    fn pause(&self) {
+        self.control.paused.store(true, Ordering::Relaxed);
+    }
+
+    fn resume(&self) {
+        self.control.paused.store(false, Ordering::Relaxed);
+    }
+
+    fn is_paused(&self) -> bool {
+        self.control.paused.load(Ordering::Relaxed)
+    }
+}
+
+/// Backend wrapping a native mono sample stream (Core Audio tap, WASAPI loopback,
+/// PulseAudio monitor); samples are forwarded from a tokio task in fixed-size blocks
+pub struct SampleStreamBackend<S> {
+    backend_type: CaptureBackendType,
+    sample_rate: u32,
+    stream: Option<S>,
+    task: Option<tokio::task::JoinHandle<()>>,
+    control: Arc<BackendControl>,
+}
+
+/// Samples forwarded per callback by `SampleStreamBackend`
+const STREAM_FRAMES_PER_CHUNK: usize = 1024;
+
+impl<S> SampleStreamBackend<S>
+where
+    S: Stream<Item = f32> + Send + Unpin + 'static,
+{
+    pub fn new(backend_type: CaptureBackendType, stream: S, sample_rate: u32) -> Self {
+        Self {
+            backend_type,
+            sample_rate,
+            stream: Some(stream),
+            task: None,
+            control: Arc::new(BackendControl::default()),
+        }
+    }
+}
+
+impl<S> AudioCaptureBackend for SampleStreamBackend<S>
+where
+    S: Stream<Item = f32> + Send + Unpin + 'static,
+{
+    fn backend_type(&self) -> CaptureBackendType {
+        self.backend_type
+    }
+
+    fn format(&self) -> CaptureFormat {
+        CaptureFormat {
+            sample_rate: self.sample_rate,
+            channels: 1,
+        }
+    }
+
+    fn start(&mut self, on_samples: SampleCallback, _on_error: ErrorCallback) -> Result<()> {
+        let mut stream = self.stream.take()
+            .ok_or_else(|| anyhow!("{} stream already started", self.backend_type.name()))?;
+        let mut on_samples = self.control.wrap(on_samples);
+        let backend_name = self.backend_type.name();
+
+        self.task = Some(tokio::spawn(async move {
+            let mut buffer = Vec::with_capacity(STREAM_FRAMES_PER_CHUNK);
+            info!("✅ {} processing task started", backend_name);
+
+            while let Some(sample) = stream.next().await {
+                buffer.push(sample);
+                if buffer.len() >= STREAM_FRAMES_PER_CHUNK {
+                    on_samples(&buffer);
+                    buffer.clear();
+                }
+            }
+
+            // Process any remaining samples
+            if !buffer.is_empty() {
+                on_samples(&buffer);
+            }
+
+            info!("⚠️ {} processing task ended", backend_name);
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        // Aborting the task drops the native stream, which releases the OS resources
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.stream.take();
+        Ok(())
+    }
+
+    fn pause(&self) {
+        self.control.paused.store(true, Ordering::Relaxed);
+    }
+
+    fn resume(&self) {
+        self.control.paused.store(false, Ordering::Relaxed);
+    }
+
+    fn is_paused(&self) -> bool {
+        self.control.paused.load(Ordering::Relaxed)
+    }
+}
+
+impl<S> Drop for SampleStreamBackend<S> {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
+}
+
+/// Create the capture backend for a device
+///
+/// Native loopback backends only apply to system audio; everything else goes through
+/// CPAL, which is also the fallback when a backend is unavailable for the source.
+pub async fn create_capture_backend(
+    backend_type: CaptureBackendType,
+    source: CaptureSource,
+    device: &AudioDevice,
+) -> Result<Box<dyn AudioCaptureBackend>> {
+    let backend_type = if CaptureBackendType::available_for(source).contains(&backend_type) {
+        backend_type
+    } else {
+        let fallback = CaptureBackendType::default_for(source);
+        info!("Backend {:?} cannot capture {:?} audio, using {:?}", backend_type, source, fallback);
+        fallback
+    };
+
+    match backend_type {
+        #[cfg(target_os = "macos")]
+        CaptureBackendType::CoreAudio => {
+            let stream = super::CoreAudioCapture::new()
+                .and_then(|capture| capture.stream())
+                .map_err(|e| {
+                    error!("❌ Failed to create Core Audio capture: {}", e);
+                    anyhow!("Failed to create Core Audio capture: {}", e)
+                })?;
+            let sample_rate = stream.sample_rate();
+            Ok(Box::new(SampleStreamBackend::new(backend_type, stream, sample_rate)))
+        }
+        #[cfg(target_os = "windows")]
+        CaptureBackendType::Wasapi => {
+            let stream = super::WasapiLoopbackCapture::with_device_name(Some(&device.name))
+                .and_then(|capture| capture.stream())
+                .map_err(|e| {
+                    error!("❌ Failed to create WASAPI loopback capture: {}", e);
+                    anyhow!("Failed to create WASAPI loopback stream: {}", e)
+                })?;
+            let sample_rate = stream.sample_rate();
+            Ok(Box::new(SampleStreamBackend::new(backend_type, stream, sample_rate)))
+        }
+        #[cfg(target_os = "linux")]
+        CaptureBackendType::PulseMonitor => {
+            // Devices listed from pactl carry a " (System Audio)" suffix; anything else
+            // (e.g. an ALSA name) falls back to the default sink's monitor
+            let source_name = device.name.trim_end_matches(" (System Audio)");
+            let capture = if source_name.ends_with(".monitor") {
+                super::PulseMonitorCapture::with_source(source_name)
+            } else {
+                super::PulseMonitorCapture::new()
+            };
+            let stream = capture
+                .and_then(|capture| capture.stream())
+                .map_err(|e| {
+                    error!("❌ Failed to create monitor source capture: {}", e);
+                    anyhow!("Failed to create monitor source stream: {}", e)
+                })?;
+            let sample_rate = stream.sample_rate();
+            Ok(Box::new(SampleStreamBackend::new(backend_type, stream, sample_rate)))
+        }
+        CaptureBackendType::Cpal | CaptureBackendType::ScreenCaptureKit => {
+            Ok(Box::new(CpalCaptureBackend::open(device, backend_type).await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_levels_from_samples() {
+        let levels = CaptureLevels::from_samples(&[0.5, -0.5, 0.5, -0.5]);
+        assert!((levels.rms - 0.5).abs() < 1e-6);
+        assert!((levels.peak - 0.5).abs() < 1e-6);
+        assert!(!levels.clipping);
+        assert_eq!(CaptureLevels::from_samples(&[]), CaptureLevels::default());
+    }
+
+    #[test]
+    fn test_level_meter_holds_peak_and_flags_clipping() {
+        let mut meter = LevelMeter::default();
+        meter.push(&[0.95, 0.0]);
+        meter.push(&[0.1, 0.1]);
+        let levels = meter.take();
+        assert!((levels.peak - 0.95).abs() < 1e-6);
+        assert!(levels.clipping);
+        // Window resets after reading
+        assert_eq!(meter.take(), CaptureLevels::default());
+    }
+
+    #[test]
+    fn test_to_dbfs() {
+        assert!((to_dbfs(1.0)).abs() < 1e-6);
+        assert!((to_dbfs(CLIP_THRESHOLD) + 1.0).abs() < 1e-3);
+        assert_eq!(to_dbfs(0.0), MIN_DBFS);
+    }
+
+    #[test]
+    fn test_control_pause_drops_samples() {
+        let control = Arc::new(BackendControl::default());
+        let received = Arc::new(Mutex::new(0usize));
+        let counter = received.clone();
+        let mut callback = control.wrap(Box::new(move |data: &[f32]| {
+            *counter.lock().unwrap() += data.len();
+        }));
+
+        callback(&[0.1; 4]);
+        control.paused.store(true, Ordering::Relaxed);
+        callback(&[0.1; 4]);
+        control.paused.store(false, Ordering::Relaxed);
+        callback(&[0.1; 2]);
+
+        assert_eq!(*received.lock().unwrap(), 6);
+    }
+
+    #[test]
+    fn test_forward_converted_keeps_whole_frames() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let blocks = received.clone();
+        let mut callback: SampleCallback = Box::new(move |data: &[f32]| blocks.lock().unwrap().push(data.to_vec()));
+
+        // Two channels, a scratch of three frames
+        let mut scratch = vec![0.0f32; 6];
+        let data: Vec<i16> = vec![i16::MAX, 0, -i16::MAX, 0, i16::MAX, 0, 0, i16::MAX];
+        forward_converted(&data, &mut scratch, |sample| sample as f32 / i16::MAX as f32, &mut callback);
+
+        let blocks = received.lock().unwrap();
+        assert_eq!(blocks.iter().map(Vec::len).collect::<Vec<_>>(), vec![6, 2]);
+        assert_eq!(blocks.concat(), vec![1.0, 0.0, -1.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+    }
+}
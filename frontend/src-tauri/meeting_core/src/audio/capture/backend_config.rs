@@ -0,0 +1,395 @@
+// Backend configuration for audio capture (per source)
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use once_cell::sync::Lazy;
+use log::info;
+
+/// Available audio capture backends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureBackendType {
+    /// ScreenCaptureKit backend (macOS default)
+    /// Uses CPAL with ScreenCaptureKit host for system audio
+    ScreenCaptureKit,
+
+    /// Plain CPAL input stream (all platforms)
+    /// Used for microphones and for system devices CPAL can open as inputs
+    Cpal,
+
+    /// Core Audio backend (macOS only)
+    /// Uses direct Core Audio API with aggregate device + tap
+    #[cfg(target_os = "macos")]
+    CoreAudio,
+
+    /// WASAPI loopback backend (Windows only)
+    /// Opens a shared-mode capture stream on the render endpoint
+    #[cfg(target_os = "windows")]
+    Wasapi,
+
+    /// PulseAudio/PipeWire monitor source backend (Linux only)
+    /// Records the default sink's monitor through the sound server
+    #[cfg(target_os = "linux")]
+    PulseMonitor,
+}
+
+impl CaptureBackendType {
+    /// Get human-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            CaptureBackendType::ScreenCaptureKit => "ScreenCaptureKit",
+            CaptureBackendType::Cpal => "CPAL",
+            #[cfg(target_os = "macos")]
+            CaptureBackendType::CoreAudio => "Core Audio",
+            #[cfg(target_os = "windows")]
+            CaptureBackendType::Wasapi => "WASAPI Loopback",
+            #[cfg(target_os = "linux")]
+            CaptureBackendType::PulseMonitor => "PulseAudio/PipeWire Monitor",
+        }
+    }
+
+    /// Get description
+    pub fn description(&self) -> &'static str {
+        match self {
+            CaptureBackendType::ScreenCaptureKit => {
+                "Apple's ScreenCaptureKit framework - Higher level API with good compatibility"
+            }
+            CaptureBackendType::Cpal => {
+                "Cross-platform CPAL input stream - Works with any device the OS exposes as an input"
+            }
+            #[cfg(target_os = "macos")]
+            CaptureBackendType::CoreAudio => {
+                "Direct Core Audio API - Lower latency, more control over audio pipeline"
+            }
+            #[cfg(target_os = "windows")]
+            CaptureBackendType::Wasapi => {
+                "Windows Audio Session API loopback - Captures what the selected output device plays"
+            }
+            #[cfg(target_os = "linux")]
+            CaptureBackendType::PulseMonitor => {
+                "Sound server monitor source - Captures what the default sink plays (PulseAudio or PipeWire)"
+            }
+        }
+    }
+
+    /// Get backend from string
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "screencapturekit" => Some(CaptureBackendType::ScreenCaptureKit),
+            "cpal" => Some(CaptureBackendType::Cpal),
+            #[cfg(target_os = "macos")]
+            "coreaudio" | "core_audio" => Some(CaptureBackendType::CoreAudio),
+            #[cfg(target_os = "windows")]
+            "wasapi" => Some(CaptureBackendType::Wasapi),
+            #[cfg(target_os = "linux")]
+            "pulsemonitor" | "pulse_monitor" => Some(CaptureBackendType::PulseMonitor),
+            _ => None,
+        }
+    }
+
+    /// Convert to string (lowercase)
+    pub fn to_string(&self) -> String {
+        match self {
+            CaptureBackendType::ScreenCaptureKit => "screencapturekit".to_string(),
+            CaptureBackendType::Cpal => "cpal".to_string(),
+            #[cfg(target_os = "macos")]
+            CaptureBackendType::CoreAudio => "coreaudio".to_string(),
+            #[cfg(target_os = "windows")]
+            CaptureBackendType::Wasapi => "wasapi".to_string(),
+            #[cfg(target_os = "linux")]
+            CaptureBackendType::PulseMonitor => "pulsemonitor".to_string(),
+        }
+    }
+
+    /// Get all available backends for current platform
+    pub fn available_backends() -> Vec<Self> {
+        #[cfg(target_os = "macos")]
+        {
+            vec![CaptureBackendType::ScreenCaptureKit, CaptureBackendType::CoreAudio]
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            vec![CaptureBackendType::Wasapi, CaptureBackendType::ScreenCaptureKit]
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            vec![CaptureBackendType::PulseMonitor, CaptureBackendType::ScreenCaptureKit]
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        {
+            vec![CaptureBackendType::ScreenCaptureKit]
+        }
+    }
+
+    /// Get backends that can capture the given source
+    pub fn available_for(source: CaptureSource) -> Vec<Self> {
+        match source {
+            CaptureSource::Microphone => vec![CaptureBackendType::Cpal],
+            CaptureSource::System => Self::available_backends(),
+        }
+    }
+
+    /// Get default backend for the given source
+    pub fn default_for(source: CaptureSource) -> Self {
+        match source {
+            CaptureSource::Microphone => CaptureBackendType::Cpal,
+            CaptureSource::System => Self::default(),
+        }
+    }
+
+    /// Get default backend for current platform
+    pub fn default() -> Self {
+        #[cfg(target_os = "macos")]
+        return CaptureBackendType::CoreAudio;
+
+        #[cfg(target_os = "windows")]
+        return CaptureBackendType::Wasapi;
+
+        #[cfg(target_os = "linux")]
+        return CaptureBackendType::PulseMonitor;
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        return CaptureBackendType::ScreenCaptureKit;
+    }
+}
+
+impl Default for CaptureBackendType {
+    fn default() -> Self {
+        Self::default()
+    }
+}
+
+impl std::fmt::Display for CaptureBackendType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Audio source a backend is selected for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureSource {
+    Microphone,
+    System,
+}
+
+impl CaptureSource {
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "microphone" | "mic" => Some(CaptureSource::Microphone),
+            "system" => Some(CaptureSource::System),
+            _ => None,
+        }
+    }
+}
+
+/// Global backend configuration
+pub struct BackendConfig {
+    current_backend: RwLock<CaptureBackendType>,
+    microphone_backend: RwLock<CaptureBackendType>,
+}
+
+impl BackendConfig {
+    fn new() -> Self {
+        Self {
+            current_backend: RwLock::new(CaptureBackendType::default()),
+            microphone_backend: RwLock::new(CaptureBackendType::default_for(CaptureSource::Microphone)),
+        }
+    }
+
+    /// Get backend selected for a source
+    pub fn get_for(&self, source: CaptureSource) -> CaptureBackendType {
+        match source {
+            CaptureSource::Microphone => *self.microphone_backend.read().unwrap(),
+            CaptureSource::System => self.get(),
+        }
+    }
+
+    /// Select backend for a source, rejecting backends that cannot capture it
+    pub fn set_for(&self, source: CaptureSource, backend: CaptureBackendType) -> anyhow::Result<()> {
+        if !CaptureBackendType::available_for(source).contains(&backend) {
+            anyhow::bail!("Backend {} cannot capture {:?} audio on this platform", backend.name(), source);
+        }
+        match source {
+            CaptureSource::Microphone => {
+                info!("Switching microphone capture backend to: {:?}", backend);
+                *self.microphone_backend.write().unwrap() = backend;
+            }
+            CaptureSource::System => self.set(backend),
+        }
+        Ok(())
+    }
+
+    /// Get current backend
+    pub fn get(&self) -> CaptureBackendType {
+        *self.current_backend.read().unwrap()
+    }
+
+    /// Set current backend
+    pub fn set(&self, backend: CaptureBackendType) {
+        info!("Switching audio capture backend to: {:?}", backend);
+        *self.current_backend.write().unwrap() = backend;
+    }
+
+    /// Get available backends
+    pub fn available(&self) -> Vec<CaptureBackendType> {
+        CaptureBackendType::available_backends()
+    }
+
+    /// Reset to default
+    pub fn reset(&self) {
+        self.set(CaptureBackendType::default());
+        *self.microphone_backend.write().unwrap() = CaptureBackendType::default_for(CaptureSource::Microphone);
+    }
+}
+
+/// Global backend configuration instance
+pub static BACKEND_CONFIG: Lazy<Arc<BackendConfig>> = Lazy::new(|| {
+    Arc::new(BackendConfig::new())
+});
+
+/// Get current backend
+pub fn get_current_backend() -> CaptureBackendType {
+    BACKEND_CONFIG.get()
+}
+
+/// Set current backend
+pub fn set_current_backend(backend: CaptureBackendType) {
+    BACKEND_CONFIG.set(backend);
+}
+
+/// Get available backends
+pub fn get_available_backends() -> Vec<CaptureBackendType> {
+    BACKEND_CONFIG.available()
+}
+
+/// Get backend selected for a source
+pub fn get_backend_for(source: CaptureSource) -> CaptureBackendType {
+    BACKEND_CONFIG.get_for(source)
+}
+
+/// Select backend for a source
+pub fn set_backend_for(source: CaptureSource, backend: CaptureBackendType) -> anyhow::Result<()> {
+    BACKEND_CONFIG.set_for(source, backend)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_to_string() {
+        assert_eq!(CaptureBackendType::ScreenCaptureKit.to_string(), "screencapturekit");
+        #[cfg(target_os = "macos")]
+        assert_eq!(CaptureBackendType::CoreAudio.to_string(), "coreaudio");
+    }
+
+    #[test]
+    fn test_backend_from_string() {
+        assert_eq!(
+            CaptureBackendType::from_string("screencapturekit"),
+            Some(CaptureBackendType::ScreenCaptureKit)
+        );
+        #[cfg(target_os = "macos")]
+        {
+            assert_eq!(
+                CaptureBackendType::from_string("coreaudio"),
+                Some(CaptureBackendType::CoreAudio)
+            );
+            assert_eq!(
+                CaptureBackendType::from_string("core_audio"),
+                Some(CaptureBackendType::CoreAudio)
+            );
+        }
+    }
+
+    #[test]
+    fn test_available_backends() {
+        let backends = CaptureBackendType::available_backends();
+        assert!(backends.contains(&CaptureBackendType::ScreenCaptureKit));
+
+        #[cfg(target_os = "macos")]
+        assert!(backends.contains(&CaptureBackendType::CoreAudio));
+
+        #[cfg(target_os = "windows")]
+        assert!(backends.contains(&CaptureBackendType::Wasapi));
+
+        #[cfg(target_os = "linux")]
+        assert!(backends.contains(&CaptureBackendType::PulseMonitor));
+    }
+
+    #[test]
+    fn test_default_backend() {
+        #[cfg(target_os = "macos")]
+        assert_eq!(CaptureBackendType::default(), CaptureBackendType::CoreAudio);
+
+        #[cfg(target_os = "windows")]
+        assert_eq!(CaptureBackendType::default(), CaptureBackendType::Wasapi);
+
+        #[cfg(target_os = "linux")]
+        assert_eq!(CaptureBackendType::default(), CaptureBackendType::PulseMonitor);
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        assert_eq!(CaptureBackendType::default(), CaptureBackendType::ScreenCaptureKit);
+    }
+
+    #[test]
+    fn test_per_source_backend_selection() {
+        let config = BackendConfig::new();
+        assert_eq!(config.get_for(CaptureSource::Microphone), CaptureBackendType::Cpal);
+        assert_eq!(config.get_for(CaptureSource::System), CaptureBackendType::default());
+
+        // Microphones can only be captured through CPAL
+        assert!(config.set_for(CaptureSource::Microphone, CaptureBackendType::ScreenCaptureKit).is_err());
+        assert!(config.set_for(CaptureSource::System, CaptureBackendType::ScreenCaptureKit).is_ok());
+        assert_eq!(config.get_for(CaptureSource::System), CaptureBackendType::ScreenCaptureKit);
+    }
+
+    #[test]
+    fn test_capture_source_from_string() {
+        assert_eq!(CaptureSource::from_string("mic"), Some(CaptureSource::Microphone));
+        assert_eq!(CaptureSource::from_string("System"), Some(CaptureSource::System));
+        assert_eq!(CaptureSource::from_string("speakers"), None);
+    }
+
+    #[test]
+    fn test_backend_config() {
+        let config = BackendConfig::new();
+
+        // Should start with default
+        #[cfg(target_os = "macos")]
+        assert_eq!(config.get(), CaptureBackendType::CoreAudio);
+
+        #[cfg(target_os = "windows")]
+        assert_eq!(config.get(), CaptureBackendType::Wasapi);
+
+        #[cfg(target_os = "linux")]
+        assert_eq!(config.get(), CaptureBackendType::PulseMonitor);
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        assert_eq!(config.get(), CaptureBackendType::ScreenCaptureKit);
+
+        #[cfg(target_os = "macos")]
+        {
+            // Test setting CoreAudio
+            config.set(CaptureBackendType::CoreAudio);
+            assert_eq!(config.get(), CaptureBackendType::CoreAudio);
+        }
+
+        // Test reset
+        config.reset();
+        #[cfg(target_os = "macos")]
+        assert_eq!(config.get(), CaptureBackendType::CoreAudio);
+
+        #[cfg(target_os = "windows")]
+        assert_eq!(config.get(), CaptureBackendType::Wasapi);
+
+        #[cfg(target_os = "linux")]
+        assert_eq!(config.get(), CaptureBackendType::PulseMonitor);
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        assert_eq!(config.get(), CaptureBackendType::ScreenCaptureKit);
+    }
+}
\ No newline at end of file
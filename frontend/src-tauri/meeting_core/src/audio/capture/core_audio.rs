@@ -1,22 +1,72 @@
 // Core Audio implementation for macOS system audio capture
 
-#[cfg(target_os = "macos")]
 use std::pin::Pin;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::{Arc, Mutex};
-use std::task::{Context, Poll, Waker};
+use std::sync::RwLock;
+use std::task::{Context, Poll};
 use anyhow::Result;
 use futures_util::Stream;
+use log::info;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_os = "macos")]
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+#[cfg(target_os = "macos")]
+use std::sync::{Arc, Mutex};
+#[cfg(target_os = "macos")]
+use std::task::Waker;
+#[cfg(target_os = "macos")]
 use ringbuf::{
     traits::{Consumer, Producer, Split},
     HeapCons, HeapProd, HeapRb,
 };
-use log::{error, info, warn};
+#[cfg(target_os = "macos")]
+use log::{error, warn};
 
 #[cfg(target_os = "macos")]
 use cidre::{arc, av, cat, cf, core_audio as ca, os};
 
+/// Which processes the Core Audio tap records
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", content = "pids", rename_all = "snake_case")]
+pub enum TapTarget {
+    /// Whole system mix
+    #[default]
+    System,
+    /// Only these processes (e.g. Zoom, Teams, a browser)
+    Include(Vec<i32>),
+    /// Whole system mix minus these processes (e.g. music players)
+    Exclude(Vec<i32>),
+}
+
+/// Tap target used by the next `CoreAudioCapture::new()`
+static TAP_TARGET: Lazy<RwLock<TapTarget>> = Lazy::new(|| RwLock::new(TapTarget::default()));
+
+pub fn get_tap_target() -> TapTarget {
+    TAP_TARGET.read().map(|t| t.clone()).unwrap_or_default()
+}
+
+pub fn set_tap_target(target: TapTarget) {
+    info!("🎯 CoreAudio: Tap target set to {:?}", target);
+    if let Ok(mut current) = TAP_TARGET.write() {
+        *current = target;
+    }
+}
+
+/// A process known to Core Audio that can be tapped
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioProcessInfo {
+    pub pid: i32,
+    pub name: String,
+    pub bundle_id: Option<String>,
+    /// Path of the app bundle; the UI resolves the icon from it
+    pub bundle_path: Option<String>,
+    /// Whether the process is currently playing audio
+    pub is_playing: bool,
+}
+
 /// Waker state for async polling
+#[cfg(target_os = "macos")]
 struct WakerState {
     waker: Option<Waker>,
     has_data: bool,
@@ -85,10 +135,25 @@ impl CoreAudioCapture {
         // When using a tap, the tap provides all the audio we need
         // Including both the tap AND the device creates duplicate audio (echo issue)
 
-        // Create process tap with mono global tap, excluding no processes
-        // Note: Mono tap is more reliable for system audio capture on macOS
-        info!("🎙️ CoreAudio: Creating process tap (global mono tap)...");
-        let tap_desc = ca::TapDesc::with_mono_global_tap_excluding_processes(&cidre::ns::Array::new());
+        // Create process tap (mono is more reliable for system audio capture on macOS)
+        let target = get_tap_target();
+        info!("🎙️ CoreAudio: Creating process tap (target: {:?})...", target);
+        let tap_desc = match &target {
+            TapTarget::System => {
+                ca::TapDesc::with_mono_global_tap_excluding_processes(&cidre::ns::Array::new())
+            }
+            TapTarget::Include(pids) => {
+                let objects = process_objects_for_pids(pids)?;
+                if objects.is_empty() {
+                    return Err(anyhow::anyhow!("None of the selected processes ({:?}) are known to Core Audio", pids));
+                }
+                ca::TapDesc::with_mono_mixdown_of_processes(&process_id_array(&objects))
+            }
+            TapTarget::Exclude(pids) => {
+                let objects = process_objects_for_pids(pids)?;
+                ca::TapDesc::with_mono_global_tap_excluding_processes(&process_id_array(&objects))
+            }
+        };
         let tap = tap_desc.create_process_tap()
             .map_err(|e| {
                 error!("❌ CoreAudio: Failed to create process tap: {:?}", e);
@@ -295,6 +360,76 @@ impl CoreAudioCapture {
     }
 }
 
+/// Resolve PIDs to Core Audio process objects (PIDs without audio objects are skipped)
+#[cfg(target_os = "macos")]
+fn process_objects_for_pids(pids: &[i32]) -> Result<Vec<ca::Process>> {
+    let processes = ca::System::processes()
+        .map_err(|e| anyhow::anyhow!("Failed to list Core Audio processes: {:?}", e))?;
+
+    Ok(processes
+        .into_iter()
+        .filter(|p| p.pid().map(|pid| pids.contains(&pid)).unwrap_or(false))
+        .collect())
+}
+
+/// Build the NSArray of process object IDs expected by `CATapDescription`
+#[cfg(target_os = "macos")]
+fn process_id_array(processes: &[ca::Process]) -> arc::R<cidre::ns::Array<cidre::ns::Number>> {
+    let numbers: Vec<arc::R<cidre::ns::Number>> = processes
+        .iter()
+        .map(|p| cidre::ns::Number::with_u32(p.0 .0))
+        .collect();
+    let refs: Vec<&cidre::ns::Number> = numbers.iter().map(|n| n.as_ref()).collect();
+    cidre::ns::Array::from_slice(&refs)
+}
+
+/// List processes that Core Audio can tap
+#[cfg(target_os = "macos")]
+pub fn list_audio_processes() -> Result<Vec<AudioProcessInfo>> {
+    let processes = ca::System::processes()
+        .map_err(|e| anyhow::anyhow!("Failed to list Core Audio processes: {:?}", e))?;
+
+    let own_pid = std::process::id() as i32;
+    let mut result = Vec::new();
+    for process in processes {
+        let Ok(pid) = process.pid() else { continue };
+        if pid == own_pid {
+            continue;
+        }
+
+        let bundle_id = process.bundle_id().ok().map(|s| s.to_string()).filter(|s| !s.is_empty());
+        let running_app = cidre::ns::RunningApp::with_pid(pid);
+        let name = running_app
+            .as_ref()
+            .and_then(|app| app.localized_name())
+            .map(|s| s.to_string())
+            .or_else(|| bundle_id.clone())
+            .unwrap_or_else(|| format!("Process {}", pid));
+        let bundle_path = running_app
+            .as_ref()
+            .and_then(|app| app.bundle_url())
+            .and_then(|url| url.path())
+            .map(|s| s.to_string());
+
+        result.push(AudioProcessInfo {
+            pid,
+            name,
+            bundle_id,
+            bundle_path,
+            is_playing: process.is_running_output().unwrap_or(false),
+        });
+    }
+
+    // Apps currently producing audio first, then alphabetical
+    result.sort_by(|a, b| b.is_playing.cmp(&a.is_playing).then_with(|| a.name.cmp(&b.name)));
+    Ok(result)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn list_audio_processes() -> Result<Vec<AudioProcessInfo>> {
+    Err(anyhow::anyhow!("Per-application audio capture is only supported on macOS"))
+}
+
 /// Process audio data from the IO proc callback
 #[cfg(target_os = "macos")]
 fn process_audio_data(ctx: &mut AudioContext, data: &[f32]) {
@@ -421,6 +556,15 @@ impl Stream for CoreAudioStream {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_tap_target_serialization() {
+        let json = serde_json::to_string(&TapTarget::Include(vec![42])).unwrap();
+        assert_eq!(json, r#"{"mode":"include","pids":[42]}"#);
+
+        let parsed: TapTarget = serde_json::from_str(r#"{"mode":"system"}"#).unwrap();
+        assert_eq!(parsed, TapTarget::System);
+    }
+
     #[tokio::test]
     #[cfg(target_os = "macos")]
     #[ignore] // Only run manually as it requires audio hardware
@@ -0,0 +1,41 @@
+// Audio capture implementations module
+
+pub mod microphone;
+pub mod system;
+pub mod backend_config;
+pub mod backend;
+
+pub mod core_audio;
+
+pub mod wasapi;
+pub mod pulse_monitor;
+
+// Re-export capture functionality
+pub use system::{
+    SystemAudioCapture, SystemAudioStream,
+    start_system_audio_capture, list_system_audio_devices,
+    check_system_audio_permissions
+};
+
+#[cfg(target_os = "macos")]
+pub use core_audio::{CoreAudioCapture, CoreAudioStream};
+
+pub use core_audio::{
+    AudioProcessInfo, TapTarget, get_tap_target, set_tap_target, list_audio_processes
+};
+
+pub use wasapi::{WasapiLoopbackCapture, WasapiLoopbackStream};
+pub use pulse_monitor::{PulseMonitorCapture, PulseMonitorStream, MonitorSource, list_monitor_sources};
+
+// Re-export backend configuration
+pub use backend_config::{
+    CaptureBackendType, CaptureSource, BackendConfig, BACKEND_CONFIG,
+    get_current_backend, set_current_backend, get_available_backends,
+    get_backend_for, set_backend_for
+};
+
+// Re-export the common backend interface
+pub use backend::{
+    AudioCaptureBackend, CaptureFormat, CaptureLevels, CpalCaptureBackend, SampleStreamBackend,
+    LevelMeter, CLIP_THRESHOLD, create_capture_backend, to_dbfs
+};
\ No newline at end of file
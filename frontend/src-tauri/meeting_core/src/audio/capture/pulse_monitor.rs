@@ -0,0 +1,360 @@
+// PulseAudio/PipeWire monitor source capture for Linux system audio
+//
+// Every sink exposes a "<sink>.monitor" source carrying what the sink plays. PipeWire
+// provides the same sources through pipewire-pulse, so one implementation covers both.
+// Capture runs through `parec`, which avoids linking libpulse directly.
+
+use anyhow::{anyhow, Result};
+use futures_util::Stream;
+use std::task::{Context, Poll};
+
+#[cfg(target_os = "linux")]
+use std::io::Read;
+#[cfg(target_os = "linux")]
+use std::pin::Pin;
+#[cfg(target_os = "linux")]
+use std::process::{Child, Command, Stdio};
+#[cfg(target_os = "linux")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(target_os = "linux")]
+use std::sync::{Arc, Mutex};
+#[cfg(target_os = "linux")]
+use std::task::Waker;
+#[cfg(target_os = "linux")]
+use ringbuf::{
+    traits::{Consumer, Producer, Split},
+    HeapCons, HeapRb,
+};
+#[cfg(target_os = "linux")]
+use log::{error, info, warn};
+
+/// Sample rate requested from the sound server (it resamples for us)
+pub const MONITOR_SAMPLE_RATE: u32 = 48000;
+
+/// A monitor source as reported by `pactl list short sources`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorSource {
+    pub name: String,
+    pub driver: String,
+}
+
+/// Waker state for async polling
+#[cfg(target_os = "linux")]
+struct WakerState {
+    waker: Option<Waker>,
+    has_data: bool,
+}
+
+/// Monitor source capture bound to a sink's monitor
+#[cfg(target_os = "linux")]
+pub struct PulseMonitorCapture {
+    source: String,
+}
+
+/// Monitor stream producing mono f32 samples at `MONITOR_SAMPLE_RATE`
+#[cfg(target_os = "linux")]
+pub struct PulseMonitorStream {
+    consumer: HeapCons<f32>,
+    child: Child,
+    waker_state: Arc<Mutex<WakerState>>,
+    should_terminate: Arc<AtomicBool>,
+}
+
+#[cfg(target_os = "linux")]
+impl PulseMonitorCapture {
+    /// Create a capture on the default sink's monitor
+    pub fn new() -> Result<Self> {
+        let source = default_monitor_source()?;
+        info!("🔊 PulseMonitor: Using default sink monitor '{}'", source);
+        Ok(Self { source })
+    }
+
+    /// Create a capture on a specific monitor source (or a dedicated null sink's monitor)
+    pub fn with_source(source: &str) -> Result<Self> {
+        let sources = list_monitor_sources()?;
+        if !sources.iter().any(|s| s.name == source) {
+            warn!("⚠️ PulseMonitor: Monitor source '{}' not found, using default", source);
+            return Self::new();
+        }
+        Ok(Self { source: source.to_string() })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Start `parec` on the monitor source and create a stream
+    pub fn stream(self) -> Result<PulseMonitorStream> {
+        let mut child = Command::new("parec")
+            .arg(format!("--device={}", self.source))
+            .arg("--format=float32le")
+            .arg(format!("--rate={}", MONITOR_SAMPLE_RATE))
+            .arg("--channels=1")
+            .arg("--raw")
+            .arg("--latency-msec=20")
+            .arg("--client-name=Meetily")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| anyhow!("Failed to start parec (is pulseaudio-utils installed?): {}", e))?;
+
+        let mut stdout = child.stdout.take()
+            .ok_or_else(|| anyhow!("Failed to capture parec output"))?;
+
+        let rb = HeapRb::<f32>::new(1024 * 128);
+        let (mut producer, consumer) = rb.split();
+
+        let waker_state = Arc::new(Mutex::new(WakerState {
+            waker: None,
+            has_data: false,
+        }));
+        let should_terminate = Arc::new(AtomicBool::new(false));
+
+        let reader_waker = waker_state.clone();
+        let reader_terminate = should_terminate.clone();
+        let source = self.source.clone();
+        std::thread::Builder::new()
+            .name("pulse-monitor-reader".to_string())
+            .spawn(move || {
+                let mut bytes = vec![0u8; 4096];
+                let mut pending = Vec::with_capacity(4);
+                let mut samples = Vec::with_capacity(1024);
+
+                while !reader_terminate.load(Ordering::Acquire) {
+                    let n = match stdout.read(&mut bytes) {
+                        Ok(0) => break,
+                        Ok(n) => n,
+                        Err(e) => {
+                            error!("❌ PulseMonitor: Read from parec failed: {}", e);
+                            break;
+                        }
+                    };
+
+                    samples.clear();
+                    decode_f32le(&bytes[..n], &mut pending, &mut samples);
+                    if producer.push_slice(&samples) < samples.len() {
+                        warn!("PulseMonitor: Ring buffer full, dropping samples");
+                    }
+
+                    let waker = {
+                        let mut state = reader_waker.lock().unwrap();
+                        if !state.has_data {
+                            state.has_data = true;
+                            state.waker.take()
+                        } else {
+                            None
+                        }
+                    };
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+
+                reader_terminate.store(true, Ordering::Release);
+                if let Some(waker) = reader_waker.lock().unwrap().waker.take() {
+                    waker.wake();
+                }
+                info!("PulseMonitor: Reader for '{}' ended", source);
+            })?;
+
+        info!("✅ PulseMonitor: Capturing '{}' at {} Hz", self.source, MONITOR_SAMPLE_RATE);
+
+        Ok(PulseMonitorStream {
+            consumer,
+            child,
+            waker_state,
+            should_terminate,
+        })
+    }
+}
+
+/// Resolve the monitor source of the default sink
+#[cfg(target_os = "linux")]
+pub fn default_monitor_source() -> Result<String> {
+    let output = Command::new("pactl")
+        .arg("info")
+        .output()
+        .map_err(|e| anyhow!("Failed to run pactl (is PulseAudio/PipeWire running?): {}", e))?;
+
+    let info = String::from_utf8_lossy(&output.stdout);
+    parse_default_sink(&info)
+        .map(|sink| format!("{}.monitor", sink))
+        .ok_or_else(|| anyhow!("No default sink reported by pactl"))
+}
+
+/// List all monitor sources known to the sound server
+#[cfg(target_os = "linux")]
+pub fn list_monitor_sources() -> Result<Vec<MonitorSource>> {
+    let output = Command::new("pactl")
+        .args(["list", "short", "sources"])
+        .output()
+        .map_err(|e| anyhow!("Failed to run pactl (is PulseAudio/PipeWire running?): {}", e))?;
+
+    Ok(parse_short_sources(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Extract the default sink name from `pactl info` output
+pub fn parse_default_sink(info: &str) -> Option<String> {
+    info.lines()
+        .find_map(|line| line.trim().strip_prefix("Default Sink:"))
+        .map(|sink| sink.trim().to_string())
+        .filter(|sink| !sink.is_empty())
+}
+
+/// Parse `pactl list short sources`, keeping only monitor sources
+///
+/// Lines look like: `57\talsa_output.pci-0000_00_1f.3.analog-stereo.monitor\tPipeWire\ts32le 2ch 48000Hz\tSUSPENDED`
+pub fn parse_short_sources(output: &str) -> Vec<MonitorSource> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let _index = fields.next()?;
+            let name = fields.next()?.trim();
+            let driver = fields.next().unwrap_or("").trim();
+            name.ends_with(".monitor").then(|| MonitorSource {
+                name: name.to_string(),
+                driver: driver.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Decode little-endian f32 bytes, carrying partial samples across reads
+#[cfg(any(target_os = "linux", test))]
+fn decode_f32le(bytes: &[u8], pending: &mut Vec<u8>, out: &mut Vec<f32>) {
+    for &b in bytes {
+        pending.push(b);
+        if pending.len() == 4 {
+            out.push(f32::from_le_bytes([pending[0], pending[1], pending[2], pending[3]]));
+            pending.clear();
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl PulseMonitorStream {
+    pub fn sample_rate(&self) -> u32 {
+        MONITOR_SAMPLE_RATE
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Stream for PulseMonitorStream {
+    type Item = f32;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if let Some(sample) = self.consumer.try_pop() {
+            return Poll::Ready(Some(sample));
+        }
+
+        if self.should_terminate.load(Ordering::Acquire) {
+            return Poll::Ready(self.consumer.try_pop());
+        }
+
+        {
+            let mut state = self.waker_state.lock().unwrap();
+            state.has_data = false;
+            state.waker = Some(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for PulseMonitorStream {
+    fn drop(&mut self) {
+        info!("PulseMonitorStream dropped, stopping parec");
+        self.should_terminate.store(true, Ordering::Release);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// Stub implementations for non-Linux platforms
+#[cfg(not(target_os = "linux"))]
+pub struct PulseMonitorCapture;
+
+#[cfg(not(target_os = "linux"))]
+pub struct PulseMonitorStream;
+
+#[cfg(not(target_os = "linux"))]
+impl PulseMonitorCapture {
+    pub fn new() -> Result<Self> {
+        Err(anyhow!("PulseAudio monitor capture is only supported on Linux"))
+    }
+
+    pub fn with_source(_source: &str) -> Result<Self> {
+        Self::new()
+    }
+
+    pub fn stream(self) -> Result<PulseMonitorStream> {
+        Err(anyhow!("PulseAudio monitor capture is only supported on Linux"))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn list_monitor_sources() -> Result<Vec<MonitorSource>> {
+    Ok(Vec::new())
+}
+
+#[cfg(not(target_os = "linux"))]
+impl PulseMonitorStream {
+    pub fn sample_rate(&self) -> u32 {
+        0
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl Stream for PulseMonitorStream {
+    type Item = f32;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        Poll::Ready(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_default_sink() {
+        let info = "Server Name: PulseAudio (on PipeWire 1.0.5)\nDefault Sink: alsa_output.usb-headset.analog-stereo\nDefault Source: alsa_input.usb-headset.mono\n";
+        assert_eq!(
+            parse_default_sink(info),
+            Some("alsa_output.usb-headset.analog-stereo".to_string())
+        );
+        assert_eq!(parse_default_sink("Server Name: foo\n"), None);
+    }
+
+    #[test]
+    fn test_parse_short_sources_keeps_monitors() {
+        let output = "56\talsa_output.pci.analog-stereo.monitor\tPipeWire\ts32le 2ch 48000Hz\tSUSPENDED\n\
+                      57\talsa_input.pci.analog-stereo\tPipeWire\ts32le 2ch 48000Hz\tRUNNING\n\
+                      60\tmeetily_sink.monitor\tmodule-null-sink.c\tfloat32le 2ch 48000Hz\tIDLE\n";
+        let sources = parse_short_sources(output);
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].name, "alsa_output.pci.analog-stereo.monitor");
+        assert_eq!(sources[1].driver, "module-null-sink.c");
+    }
+
+    #[test]
+    fn test_decode_f32le_across_reads() {
+        let bytes: Vec<u8> = [0.5f32, -1.0f32].iter().flat_map(|f| f.to_le_bytes()).collect();
+        let mut pending = Vec::new();
+        let mut out = Vec::new();
+        decode_f32le(&bytes[..3], &mut pending, &mut out);
+        assert!(out.is_empty());
+        decode_f32le(&bytes[3..], &mut pending, &mut out);
+        assert_eq!(out, vec![0.5, -1.0]);
+        assert!(pending.is_empty());
+    }
+}
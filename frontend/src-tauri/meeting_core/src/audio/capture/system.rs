@@ -5,11 +5,15 @@ use anyhow::Result;
 use cpal::traits::{DeviceTrait, HostTrait};
 
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
 use futures_channel::mpsc;
 #[cfg(target_os = "macos")]
 use super::core_audio::CoreAudioCapture;
-#[cfg(target_os = "macos")]
+#[cfg(target_os = "windows")]
+use super::wasapi::WasapiLoopbackCapture;
+#[cfg(target_os = "linux")]
+use super::pulse_monitor::PulseMonitorCapture;
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
 use log::info;
 
 /// System audio capture using Core Audio tap (macOS) or CPAL (other platforms)
@@ -96,9 +100,108 @@ impl SystemAudioCapture {
             })
         }
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "windows")]
+        {
+            info!("Starting WASAPI loopback system capture (Windows)");
+            let loopback = WasapiLoopbackCapture::new()?.stream()?;
+            let sample_rate = loopback.sample_rate();
+
+            let (tx, rx) = mpsc::unbounded::<Vec<f32>>();
+            let (drop_tx, drop_rx) = std::sync::mpsc::channel::<()>();
+
+            // Spawn task to forward loopback samples in chunks
+            tokio::spawn(async move {
+                let mut stream = loopback;
+                let mut buffer = Vec::new();
+                let chunk_size = 1024;
+
+                loop {
+                    if drop_rx.try_recv().is_ok() {
+                        break;
+                    }
+
+                    match stream.next().await {
+                        Some(sample) => {
+                            buffer.push(sample);
+                            if buffer.len() >= chunk_size {
+                                if tx.unbounded_send(buffer.clone()).is_err() {
+                                    break;
+                                }
+                                buffer.clear();
+                            }
+                        }
+                        None => break,
+                    }
+                }
+
+                if !buffer.is_empty() {
+                    let _ = tx.unbounded_send(buffer);
+                }
+            });
+
+            let receiver = rx.map(futures_util::stream::iter).flatten();
+
+            info!("WASAPI loopback system capture started successfully");
+
+            Ok(SystemAudioStream {
+                drop_tx,
+                sample_rate,
+                receiver: Box::pin(receiver),
+            })
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            info!("Starting PulseAudio/PipeWire monitor capture (Linux)");
+            let monitor = PulseMonitorCapture::new()?.stream()?;
+            let sample_rate = monitor.sample_rate();
+
+            let (tx, rx) = mpsc::unbounded::<Vec<f32>>();
+            let (drop_tx, drop_rx) = std::sync::mpsc::channel::<()>();
+
+            // Spawn task to forward monitor samples in chunks
+            tokio::spawn(async move {
+                let mut stream = monitor;
+                let mut buffer = Vec::new();
+                let chunk_size = 1024;
+
+                loop {
+                    if drop_rx.try_recv().is_ok() {
+                        break;
+                    }
+
+                    match stream.next().await {
+                        Some(sample) => {
+                            buffer.push(sample);
+                            if buffer.len() >= chunk_size {
+                                if tx.unbounded_send(buffer.clone()).is_err() {
+                                    break;
+                                }
+                                buffer.clear();
+                            }
+                        }
+                        None => break,
+                    }
+                }
+
+                if !buffer.is_empty() {
+                    let _ = tx.unbounded_send(buffer);
+                }
+            });
+
+            let receiver = rx.map(futures_util::stream::iter).flatten();
+
+            info!("Monitor source capture started successfully");
+
+            Ok(SystemAudioStream {
+                drop_tx,
+                sample_rate,
+                receiver: Box::pin(receiver),
+            })
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
         {
-            // For non-macOS platforms, you would implement WASAPI/ALSA loopback here
             anyhow::bail!("System audio capture not yet implemented for this platform")
         }
     }
@@ -0,0 +1,395 @@
+// WASAPI loopback implementation for Windows system audio capture
+//
+// In shared mode WASAPI lets a capture stream be opened on a render (output) endpoint,
+// which yields the final mix the device is playing - i.e. Teams/Zoom/browser audio.
+// CPAL exposes this when an input stream is built on an output device of the WASAPI host.
+
+use anyhow::{anyhow, Result};
+use futures_util::Stream;
+use std::task::{Context, Poll};
+
+#[cfg(target_os = "windows")]
+use std::pin::Pin;
+#[cfg(target_os = "windows")]
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+#[cfg(target_os = "windows")]
+use std::sync::{Arc, Mutex};
+#[cfg(target_os = "windows")]
+use std::task::Waker;
+#[cfg(target_os = "windows")]
+use ringbuf::{
+    traits::{Consumer, Producer, Split},
+    HeapCons, HeapProd, HeapRb,
+};
+#[cfg(target_os = "windows")]
+use log::{error, info, warn};
+#[cfg(target_os = "windows")]
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+
+/// Waker state for async polling
+#[cfg(target_os = "windows")]
+struct WakerState {
+    waker: Option<Waker>,
+    has_data: bool,
+}
+
+/// WASAPI loopback capture bound to a render endpoint
+#[cfg(target_os = "windows")]
+pub struct WasapiLoopbackCapture {
+    device: cpal::Device,
+    device_name: String,
+    config: cpal::SupportedStreamConfig,
+}
+
+/// WASAPI loopback stream producing mono f32 samples
+#[cfg(target_os = "windows")]
+pub struct WasapiLoopbackStream {
+    consumer: HeapCons<f32>,
+    _stream: cpal::Stream,
+    waker_state: Arc<Mutex<WakerState>>,
+    sample_rate: u32,
+    should_terminate: Arc<AtomicBool>,
+}
+
+// SAFETY: cpal::Stream is only created and dropped through this wrapper; the WASAPI
+// stream itself runs on its own thread and is never touched across threads here
+#[cfg(target_os = "windows")]
+unsafe impl Send for WasapiLoopbackStream {}
+
+/// Capture callback context
+#[cfg(target_os = "windows")]
+struct LoopbackContext {
+    producer: HeapProd<f32>,
+    channels: usize,
+    mono_buffer: Vec<f32>,
+    waker_state: Arc<Mutex<WakerState>>,
+    consecutive_drops: Arc<AtomicU32>,
+    should_terminate: Arc<AtomicBool>,
+}
+
+#[cfg(target_os = "windows")]
+impl WasapiLoopbackCapture {
+    /// Create a loopback capture on the default render endpoint
+    pub fn new() -> Result<Self> {
+        Self::with_device_name(None)
+    }
+
+    /// Create a loopback capture on a named render endpoint, falling back to the default
+    pub fn with_device_name(device_name: Option<&str>) -> Result<Self> {
+        info!("🔊 WASAPI: Initializing loopback capture (device: {:?})", device_name);
+
+        let host = cpal::host_from_id(cpal::HostId::Wasapi)
+            .map_err(|e| anyhow!("Failed to create WASAPI host: {}", e))?;
+
+        let device = match device_name {
+            Some(wanted) => {
+                let base_name = strip_device_suffix(wanted);
+                let found = host
+                    .output_devices()?
+                    .find(|d| d.name().map(|n| n == base_name || n.contains(base_name)).unwrap_or(false));
+                match found {
+                    Some(device) => device,
+                    None => {
+                        warn!("⚠️ WASAPI: Output device '{}' not found, using default", wanted);
+                        host.default_output_device()
+                            .ok_or_else(|| anyhow!("No default output device available"))?
+                    }
+                }
+            }
+            None => host
+                .default_output_device()
+                .ok_or_else(|| anyhow!("No default output device available"))?,
+        };
+
+        let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+        let config = negotiate_shared_mode_format(&device)?;
+
+        info!("✅ WASAPI: Loopback device '{}' - {} Hz, {} channels, {:?}",
+              device_name, config.sample_rate().0, config.channels(), config.sample_format());
+
+        Ok(Self { device, device_name, config })
+    }
+
+    /// List render endpoints that can be used for loopback capture
+    pub fn list_devices() -> Result<Vec<String>> {
+        let host = cpal::host_from_id(cpal::HostId::Wasapi)
+            .map_err(|e| anyhow!("Failed to create WASAPI host: {}", e))?;
+        Ok(host.output_devices()?.filter_map(|d| d.name().ok()).collect())
+    }
+
+    pub fn device_name(&self) -> &str {
+        &self.device_name
+    }
+
+    /// Start capturing and create a stream
+    pub fn stream(self) -> Result<WasapiLoopbackStream> {
+        let sample_rate = self.config.sample_rate().0;
+        let channels = self.config.channels() as usize;
+
+        // Create ring buffer for lock-free audio transfer
+        let buffer_size = 1024 * 128;
+        let rb = HeapRb::<f32>::new(buffer_size);
+        let (producer, consumer) = rb.split();
+
+        let waker_state = Arc::new(Mutex::new(WakerState {
+            waker: None,
+            has_data: false,
+        }));
+        let should_terminate = Arc::new(AtomicBool::new(false));
+
+        let ctx = LoopbackContext {
+            producer,
+            channels,
+            mono_buffer: Vec::with_capacity(4096),
+            waker_state: waker_state.clone(),
+            consecutive_drops: Arc::new(AtomicU32::new(0)),
+            should_terminate: should_terminate.clone(),
+        };
+
+        let stream = build_loopback_stream(&self.device, &self.config, ctx)?;
+        stream.play()
+            .map_err(|e| anyhow!("Failed to start WASAPI loopback stream: {}", e))?;
+
+        info!("✅ WASAPI: Loopback stream started for '{}'", self.device_name);
+
+        Ok(WasapiLoopbackStream {
+            consumer,
+            _stream: stream,
+            waker_state,
+            sample_rate,
+            should_terminate,
+        })
+    }
+}
+
+/// Pick the stream format for shared-mode loopback
+///
+/// Shared mode must use the endpoint's mix format, which CPAL reports as the default
+/// output config. Only if that query fails do we fall back to the supported list.
+#[cfg(target_os = "windows")]
+fn negotiate_shared_mode_format(device: &cpal::Device) -> Result<cpal::SupportedStreamConfig> {
+    match device.default_output_config() {
+        Ok(config) => Ok(config),
+        Err(e) => {
+            warn!("⚠️ WASAPI: Failed to get mix format ({}), trying supported configs", e);
+            let configs: Vec<_> = device.supported_output_configs()?.collect();
+            configs
+                .iter()
+                .find(|c| c.sample_format() == cpal::SampleFormat::F32)
+                .or_else(|| configs.first())
+                .map(|c| c.with_max_sample_rate())
+                .ok_or_else(|| anyhow!("No supported loopback format for device"))
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn build_loopback_stream(
+    device: &cpal::Device,
+    config: &cpal::SupportedStreamConfig,
+    mut ctx: LoopbackContext,
+) -> Result<cpal::Stream> {
+    let stream_config: cpal::StreamConfig = config.clone().into();
+    let err_fn = |err: cpal::StreamError| error!("❌ WASAPI: Loopback stream error: {}", err);
+
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => device.build_input_stream(
+            &stream_config,
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                process_loopback_data(&mut ctx, data.iter().copied());
+            },
+            err_fn,
+            None,
+        )?,
+        cpal::SampleFormat::I16 => device.build_input_stream(
+            &stream_config,
+            move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                process_loopback_data(&mut ctx, data.iter().map(|&s| s as f32 / i16::MAX as f32));
+            },
+            err_fn,
+            None,
+        )?,
+        cpal::SampleFormat::I32 => device.build_input_stream(
+            &stream_config,
+            move |data: &[i32], _: &cpal::InputCallbackInfo| {
+                process_loopback_data(&mut ctx, data.iter().map(|&s| s as f32 / i32::MAX as f32));
+            },
+            err_fn,
+            None,
+        )?,
+        other => return Err(anyhow!("Unsupported loopback sample format: {:?}", other)),
+    };
+
+    Ok(stream)
+}
+
+/// Downmix interleaved frames to mono and push them to the ring buffer
+#[cfg(target_os = "windows")]
+fn process_loopback_data(ctx: &mut LoopbackContext, samples: impl Iterator<Item = f32>) {
+    ctx.mono_buffer.clear();
+    downmix_interleaved(samples, ctx.channels, &mut ctx.mono_buffer);
+
+    let pushed = ctx.producer.push_slice(&ctx.mono_buffer);
+    if pushed < ctx.mono_buffer.len() {
+        let consecutive = ctx.consecutive_drops.fetch_add(1, Ordering::AcqRel) + 1;
+        if consecutive > 10 {
+            ctx.should_terminate.store(true, Ordering::Release);
+            return;
+        }
+    } else {
+        ctx.consecutive_drops.store(0, Ordering::Release);
+    }
+
+    if pushed > 0 {
+        let should_wake = {
+            let mut waker_state = ctx.waker_state.lock().unwrap();
+            if !waker_state.has_data {
+                waker_state.has_data = true;
+                waker_state.waker.take()
+            } else {
+                None
+            }
+        };
+
+        if let Some(waker) = should_wake {
+            waker.wake();
+        }
+    }
+}
+
+/// Average each interleaved frame down to a single sample
+#[cfg(any(target_os = "windows", test))]
+fn downmix_interleaved(samples: impl Iterator<Item = f32>, channels: usize, out: &mut Vec<f32>) {
+    let channels = channels.max(1);
+    let mut sum = 0.0f32;
+    let mut n = 0usize;
+    for sample in samples {
+        sum += sample;
+        n += 1;
+        if n == channels {
+            out.push(sum / channels as f32);
+            sum = 0.0;
+            n = 0;
+        }
+    }
+}
+
+/// Strip the " (input)"/" (output)" suffix added by device enumeration
+#[cfg(any(target_os = "windows", test))]
+fn strip_device_suffix(name: &str) -> &str {
+    name.trim_end_matches(" (output)").trim_end_matches(" (input)")
+}
+
+#[cfg(target_os = "windows")]
+impl WasapiLoopbackStream {
+    /// Get sample rate of the negotiated mix format
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Stream for WasapiLoopbackStream {
+    type Item = f32;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if let Some(sample) = self.consumer.try_pop() {
+            return Poll::Ready(Some(sample));
+        }
+
+        if self.should_terminate.load(Ordering::Acquire) {
+            warn!("WASAPI loopback stream terminating due to buffer pressure");
+            return Poll::Ready(self.consumer.try_pop());
+        }
+
+        {
+            let mut state = self.waker_state.lock().unwrap();
+            state.has_data = false;
+            state.waker = Some(cx.waker().clone());
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for WasapiLoopbackStream {
+    fn drop(&mut self) {
+        info!("WasapiLoopbackStream dropped, signaling termination");
+        self.should_terminate.store(true, Ordering::Release);
+    }
+}
+
+// Stub implementations for non-Windows platforms
+#[cfg(not(target_os = "windows"))]
+pub struct WasapiLoopbackCapture;
+
+#[cfg(not(target_os = "windows"))]
+pub struct WasapiLoopbackStream;
+
+#[cfg(not(target_os = "windows"))]
+impl WasapiLoopbackCapture {
+    pub fn new() -> Result<Self> {
+        Err(anyhow!("WASAPI loopback is only supported on Windows"))
+    }
+
+    pub fn with_device_name(_device_name: Option<&str>) -> Result<Self> {
+        Self::new()
+    }
+
+    pub fn list_devices() -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    pub fn stream(self) -> Result<WasapiLoopbackStream> {
+        Err(anyhow!("WASAPI loopback is only supported on Windows"))
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+impl WasapiLoopbackStream {
+    pub fn sample_rate(&self) -> u32 {
+        0
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+impl Stream for WasapiLoopbackStream {
+    type Item = f32;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        Poll::Ready(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_downmix_stereo() {
+        let mut out = Vec::new();
+        downmix_interleaved([1.0, 0.0, 0.5, 0.5, -1.0, 1.0].into_iter(), 2, &mut out);
+        assert_eq!(out, vec![0.5, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_downmix_drops_partial_frame() {
+        let mut out = Vec::new();
+        downmix_interleaved([0.3, 0.3, 0.3, 0.9].into_iter(), 3, &mut out);
+        assert_eq!(out.len(), 1);
+        assert!((out[0] - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_strip_device_suffix() {
+        assert_eq!(strip_device_suffix("Speakers (Realtek) (output)"), "Speakers (Realtek)");
+        assert_eq!(strip_device_suffix("Headset"), "Headset");
+    }
+}
@@ -0,0 +1,204 @@
+// Rich device metadata for the frontend device picker
+
+use anyhow::Result;
+use cpal::traits::{DeviceTrait, HostTrait};
+use log::debug;
+use serde::Serialize;
+
+use super::configuration::{AudioDevice, DeviceType};
+use super::discovery::list_audio_devices;
+
+/// Sample rates offered in the picker when the device reports a supported range
+const COMMON_SAMPLE_RATES: [u32; 9] = [8000, 16000, 22050, 24000, 32000, 44100, 48000, 96000, 192000];
+
+/// How a device is connected to the machine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceTransport {
+    BuiltIn,
+    Usb,
+    Bluetooth,
+    /// Aggregate, loopback or other software devices
+    Virtual,
+    Unknown,
+}
+
+/// Device description returned to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioDeviceInfo {
+    /// Stable identifier, accepted by `parse_audio_device` (e.g. "USB Mic (input)")
+    pub id: String,
+    pub name: String,
+    pub device_type: DeviceType,
+    pub sample_rates: Vec<u32>,
+    pub channel_counts: Vec<u16>,
+    pub default_sample_rate: Option<u32>,
+    pub is_default: bool,
+    pub transport: DeviceTransport,
+}
+
+/// List all audio devices with their capabilities
+pub async fn list_audio_devices_detailed() -> Result<Vec<AudioDeviceInfo>> {
+    let devices = list_audio_devices().await?;
+    let host = cpal::default_host();
+
+    let default_input = host.default_input_device().and_then(|d| d.name().ok());
+    let default_output = host.default_output_device().and_then(|d| d.name().ok());
+
+    Ok(devices
+        .into_iter()
+        .map(|device| {
+            let is_default = match device.device_type {
+                DeviceType::Input => default_input.as_deref() == Some(device.name.as_str()),
+                DeviceType::Output => default_output.as_deref() == Some(device.name.as_str()),
+            };
+            describe_device(&host, device, is_default)
+        })
+        .collect())
+}
+
+fn describe_device(host: &cpal::Host, device: AudioDevice, is_default: bool) -> AudioDeviceInfo {
+    let mut sample_rates = Vec::new();
+    let mut channel_counts = Vec::new();
+    let mut default_sample_rate = None;
+
+    match find_cpal_device(host, &device) {
+        Some(cpal_device) => {
+            let (configs, default_config) = match device.device_type {
+                DeviceType::Input => (
+                    cpal_device.supported_input_configs().map(|c| c.collect::<Vec<_>>()),
+                    cpal_device.default_input_config().ok(),
+                ),
+                DeviceType::Output => (
+                    cpal_device.supported_output_configs().map(|c| c.collect::<Vec<_>>()),
+                    cpal_device.default_output_config().ok(),
+                ),
+            };
+
+            for range in configs.unwrap_or_default() {
+                let (min, max) = (range.min_sample_rate().0, range.max_sample_rate().0);
+                for rate in supported_rates(min, max) {
+                    if !sample_rates.contains(&rate) {
+                        sample_rates.push(rate);
+                    }
+                }
+                if !channel_counts.contains(&range.channels()) {
+                    channel_counts.push(range.channels());
+                }
+            }
+            sample_rates.sort_unstable();
+            channel_counts.sort_unstable();
+            default_sample_rate = default_config.map(|c| c.sample_rate().0);
+        }
+        None => debug!("No cpal device for '{}', capabilities unknown", device.name),
+    }
+
+    AudioDeviceInfo {
+        id: device.to_string(),
+        transport: detect_transport(&device.name),
+        name: device.name,
+        device_type: device.device_type,
+        sample_rates,
+        channel_counts,
+        default_sample_rate,
+        is_default,
+    }
+}
+
+fn find_cpal_device(host: &cpal::Host, device: &AudioDevice) -> Option<cpal::Device> {
+    let mut devices = match device.device_type {
+        DeviceType::Input => host.input_devices().ok()?,
+        DeviceType::Output => host.output_devices().ok()?,
+    };
+    devices.find(|d| d.name().map(|n| n == device.name).unwrap_or(false))
+}
+
+/// Common rates inside a supported range; the range bounds themselves when none match
+fn supported_rates(min: u32, max: u32) -> Vec<u32> {
+    let rates: Vec<u32> = COMMON_SAMPLE_RATES
+        .iter()
+        .copied()
+        .filter(|rate| (min..=max).contains(rate))
+        .collect();
+
+    if rates.is_empty() {
+        if min == max { vec![min] } else { vec![min, max] }
+    } else {
+        rates
+    }
+}
+
+/// Transport type from Core Audio on macOS, name heuristics elsewhere
+pub fn detect_transport(device_name: &str) -> DeviceTransport {
+    #[cfg(target_os = "macos")]
+    if let Some(transport) = detect_transport_macos(device_name) {
+        return transport;
+    }
+
+    transport_from_name(device_name)
+}
+
+#[cfg(target_os = "macos")]
+fn detect_transport_macos(device_name: &str) -> Option<DeviceTransport> {
+    use cidre::core_audio::hardware::System;
+    use cidre::core_audio::DeviceTransportType;
+
+    let devices = System::devices().ok()?;
+    let device = devices.iter().find(|d| {
+        d.name().ok().map(|n| n.to_string()).as_deref() == Some(device_name)
+    })?;
+
+    match device.transport_type().ok()? {
+        DeviceTransportType::BUILT_IN => Some(DeviceTransport::BuiltIn),
+        DeviceTransportType::USB => Some(DeviceTransport::Usb),
+        DeviceTransportType::BLUETOOTH | DeviceTransportType::BLUETOOTH_LE => Some(DeviceTransport::Bluetooth),
+        DeviceTransportType::AGGREGATE | DeviceTransportType::VIRTUAL => Some(DeviceTransport::Virtual),
+        _ => None,
+    }
+}
+
+fn transport_from_name(device_name: &str) -> DeviceTransport {
+    let name = device_name.to_lowercase();
+
+    if ["bluetooth", "airpods", "bluez", "hands-free", "wireless", "buds"]
+        .iter()
+        .any(|p| name.contains(p))
+    {
+        DeviceTransport::Bluetooth
+    } else if name.contains("usb") {
+        DeviceTransport::Usb
+    } else if ["blackhole", "loopback", "virtual", "aggregate", "monitor", "system audio", "stereo mix"]
+        .iter()
+        .any(|p| name.contains(p))
+    {
+        DeviceTransport::Virtual
+    } else if ["built-in", "builtin", "internal", "macbook", "microphone array", "hda intel", "realtek"]
+        .iter()
+        .any(|p| name.contains(p))
+    {
+        DeviceTransport::BuiltIn
+    } else {
+        DeviceTransport::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_rates_within_range() {
+        assert_eq!(supported_rates(44100, 48000), vec![44100, 48000]);
+        assert_eq!(supported_rates(48000, 48000), vec![48000]);
+        assert_eq!(supported_rates(11025, 11025), vec![11025]);
+    }
+
+    #[test]
+    fn test_transport_from_name() {
+        assert_eq!(transport_from_name("John's AirPods Pro"), DeviceTransport::Bluetooth);
+        assert_eq!(transport_from_name("Blue Yeti USB Microphone"), DeviceTransport::Usb);
+        assert_eq!(transport_from_name("MacBook Pro Microphone"), DeviceTransport::BuiltIn);
+        assert_eq!(transport_from_name("BlackHole 2ch"), DeviceTransport::Virtual);
+        assert_eq!(transport_from_name("Scarlett 2i2"), DeviceTransport::Unknown);
+    }
+}
@@ -7,11 +7,13 @@ pub mod speakers;
 pub mod configuration;
 pub mod platform;
 pub mod fallback;
+pub mod metadata;
 
 // Re-export all public functions to preserve existing API
 pub use discovery::{list_audio_devices, trigger_audio_permission};
 pub use microphone::{default_input_device, find_builtin_input_device};
 pub use speakers::{default_output_device, find_builtin_output_device};
+pub use metadata::{list_audio_devices_detailed, AudioDeviceInfo, DeviceTransport};
 pub use configuration::{get_device_and_config, parse_audio_device, AudioDevice, DeviceType, DeviceControl, AudioTranscriptionEngine, LAST_AUDIO_CAPTURE};
 
 // Re-export fallback functions (platform-specific)
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use cpal::traits::{DeviceTrait, HostTrait};
+use log::debug;
 
 use crate::audio::devices::configuration::{AudioDevice, DeviceType};
 
@@ -14,6 +15,25 @@ pub fn configure_linux_audio(host: &cpal::Host) -> Result<Vec<AudioDevice>> {
         }
     }
 
+    // Prefer monitor sources reported by the sound server (PulseAudio or PipeWire);
+    // the default sink's monitor comes first so it is picked as the default system device
+    match crate::audio::capture::list_monitor_sources() {
+        Ok(mut sources) if !sources.is_empty() => {
+            if let Ok(default_source) = crate::audio::capture::pulse_monitor::default_monitor_source() {
+                sources.sort_by_key(|s| s.name != default_source);
+            }
+            for source in sources {
+                devices.push(AudioDevice::new(
+                    format!("{} (System Audio)", source.name),
+                    DeviceType::Output
+                ));
+            }
+            return Ok(devices);
+        }
+        Ok(_) => debug!("No monitor sources reported by pactl, falling back to ALSA names"),
+        Err(e) => debug!("pactl unavailable ({}), falling back to ALSA names", e),
+    }
+
     // Add PulseAudio monitor sources for system audio
     if let Ok(pulse_host) = cpal::host_from_id(cpal::HostId::Alsa) {
         for device in pulse_host.input_devices()? {
@@ -0,0 +1,8 @@
+// audio/mod.rs
+//
+// Capture backends, device discovery and the transcription engine interface.
+
+pub mod capture;
+pub mod device_detection;
+pub mod devices;
+pub mod transcription;
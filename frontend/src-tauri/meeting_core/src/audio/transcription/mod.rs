@@ -0,0 +1,9 @@
+// audio/transcription/mod.rs
+//
+// The interface every transcription engine implements, and word timings.
+
+pub mod provider;
+pub mod words;
+
+pub use provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+pub use words::TranscriptWord;
@@ -3,6 +3,7 @@
 // Defines the unified TranscriptionProvider trait and common types for all
 // transcription engines (Whisper, Parakeet, future providers).
 
+use super::words::TranscriptWord;
 use async_trait::async_trait;
 
 // ============================================================================
@@ -43,6 +44,8 @@ pub struct TranscriptResult {
     pub text: String,
     pub confidence: Option<f32>, // None if provider doesn't support confidence scores
     pub is_partial: bool,
+    /// Word timings relative to the start of the audio; empty if the provider doesn't report them
+    pub words: Vec<TranscriptWord>,
 }
 
 /// Trait for transcription providers (Whisper, Parakeet, future providers)
@@ -70,4 +73,9 @@ pub trait TranscriptionProvider: Send + Sync {
 
     /// Get the provider name (for logging/debugging)
     fn provider_name(&self) -> &'static str;
+
+    /// Whether audio is sent off the machine (hosted APIs)
+    fn is_remote(&self) -> bool {
+        false
+    }
 }
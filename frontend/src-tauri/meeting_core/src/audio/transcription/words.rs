@@ -0,0 +1,102 @@
+// audio/transcription/words.rs
+//
+// Word-level timing and confidence.
+//
+// Engines report words relative to the audio they were given; the worker shifts them to
+// recording time before they are emitted and stored, so the frontend can seek playback
+// to a word and flag the ones the engine was unsure about.
+
+use serde::{Deserialize, Serialize};
+
+/// Words below this confidence are flagged for review
+pub const LOW_CONFIDENCE_THRESHOLD: f32 = 0.5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptWord {
+    pub text: String,
+    /// Seconds; relative to the transcribed audio, or to recording start once stored
+    pub start: f64,
+    pub end: f64,
+    pub confidence: Option<f32>,
+    #[serde(default)]
+    pub low_confidence: bool,
+}
+
+impl TranscriptWord {
+    pub fn new(text: String, start: f64, end: f64, confidence: Option<f32>) -> Self {
+        let low_confidence = confidence.is_some_and(|c| c < LOW_CONFIDENCE_THRESHOLD);
+        Self { text, start, end, confidence, low_confidence }
+    }
+}
+
+/// Shift word times by `offset` seconds
+pub fn offset_words(words: Vec<TranscriptWord>, offset: f64) -> Vec<TranscriptWord> {
+    words
+        .into_iter()
+        .map(|word| TranscriptWord { start: word.start + offset, end: word.end + offset, ..word })
+        .collect()
+}
+
+/// Merge sub-word tokens into words
+///
+/// Tokens are `(text, start, end, probability)`; a token starting with a space begins a
+/// new word, as in Whisper's BPE vocabulary. A word's confidence is its weakest token.
+pub fn words_from_tokens(tokens: &[(String, f64, f64, f32)]) -> Vec<TranscriptWord> {
+    let mut words: Vec<TranscriptWord> = Vec::new();
+    let mut current: Option<(String, f64, f64, f32)> = None;
+
+    for (text, start, end, probability) in tokens {
+        let starts_word = text.starts_with(' ') || current.is_none();
+        if starts_word {
+            if let Some((word, word_start, word_end, word_p)) = current.take() {
+                if !word.trim().is_empty() {
+                    words.push(TranscriptWord::new(word.trim().to_string(), word_start, word_end, Some(word_p)));
+                }
+            }
+            current = Some((text.clone(), *start, *end, *probability));
+        } else if let Some((word, _, word_end, word_p)) = current.as_mut() {
+            word.push_str(text);
+            *word_end = *end;
+            *word_p = word_p.min(*probability);
+        }
+    }
+
+    if let Some((word, start, end, probability)) = current {
+        if !word.trim().is_empty() {
+            words.push(TranscriptWord::new(word.trim().to_string(), start, end, Some(probability)));
+        }
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(text: &str, start: f64, end: f64, p: f32) -> (String, f64, f64, f32) {
+        (text.to_string(), start, end, p)
+    }
+
+    #[test]
+    fn test_tokens_merge_into_words() {
+        let words = words_from_tokens(&[
+            token(" Kuber", 0.0, 0.3, 0.9),
+            token("netes", 0.3, 0.6, 0.4),
+            token(" rocks", 0.7, 1.0, 0.95),
+            token(".", 1.0, 1.05, 0.99),
+        ]);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "Kubernetes");
+        assert_eq!((words[0].start, words[0].end), (0.0, 0.6));
+        assert!(words[0].low_confidence);
+        assert_eq!(words[1].text, "rocks.");
+        assert!(!words[1].low_confidence);
+    }
+
+    #[test]
+    fn test_offset_moves_to_recording_time() {
+        let words = offset_words(vec![TranscriptWord::new("hi".to_string(), 0.5, 0.8, None)], 10.0);
+        assert_eq!((words[0].start, words[0].end), (10.5, 10.8));
+        assert!(!words[0].low_confidence);
+    }
+}
@@ -0,0 +1,6 @@
+// database/mod.rs
+//
+// Rows of the meetings database. The connection and repositories stay in the app, which
+// owns the database file.
+
+pub mod models;
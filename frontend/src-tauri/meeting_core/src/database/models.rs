@@ -0,0 +1,463 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingModel {
+    pub id: String,
+    pub title: String,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+    pub folder_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct DateTimeUtc(pub DateTime<Utc>);
+
+impl From<NaiveDateTime> for DateTimeUtc {
+    fn from(naive: NaiveDateTime) -> Self {
+        DateTimeUtc(DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc))
+    }
+}
+
+// Renamed from TranscriptSegment to Transcript to match the table name
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Transcript {
+    pub id: String,
+    pub meeting_id: String,
+    pub transcript: String,
+    pub timestamp: String,
+    pub summary: Option<String>,
+    pub action_items: Option<String>,
+    pub key_points: Option<String>,
+    // Recording-relative timestamps for audio-transcript synchronization
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+    pub duration: Option<f64>,
+    pub translation: Option<String>,
+    // JSON array of word timings (see audio::transcription::words)
+    pub words: Option<String>,
+    pub speaker: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SummaryProcess {
+    pub meeting_id: String,
+    pub status: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub error: Option<String>,
+    pub result: Option<String>, // JSON
+    pub start_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub end_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub chunk_count: i64,
+    pub processing_time: f64,
+    pub metadata: Option<String>, // JSON
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptChunk {
+    pub meeting_id: String,
+    pub meeting_name: Option<String>,
+    pub transcript_text: String,
+    pub model: String,
+    pub model_name: String,
+    pub chunk_size: Option<i64>,
+    pub overlap: Option<i64>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Setting {
+    pub id: String,
+    pub provider: String,
+    pub model: String,
+    #[sqlx(rename = "whisperModel")]
+    #[serde(rename = "whisperModel")]
+    pub whisper_model: String,
+    #[sqlx(rename = "groqApiKey")]
+    #[serde(rename = "groqApiKey")]
+    pub groq_api_key: Option<String>,
+    #[sqlx(rename = "openaiApiKey")]
+    #[serde(rename = "openaiApiKey")]
+    pub openai_api_key: Option<String>,
+    #[sqlx(rename = "anthropicApiKey")]
+    #[serde(rename = "anthropicApiKey")]
+    pub anthropic_api_key: Option<String>,
+    #[sqlx(rename = "ollamaApiKey")]
+    #[serde(rename = "ollamaApiKey")]
+    pub ollama_api_key: Option<String>,
+    #[sqlx(rename = "openRouterApiKey")]
+    #[serde(rename = "openRouterApiKey")]
+    pub open_router_api_key: Option<String>,
+    #[sqlx(rename = "ollamaEndpoint")]
+    #[serde(rename = "ollamaEndpoint")]
+    pub ollama_endpoint: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptSetting {
+    pub id: String,
+    pub provider: String,
+    pub model: String,
+    #[sqlx(rename = "whisperApiKey")]
+    #[serde(rename = "whisperApiKey")]
+    pub whisper_api_key: Option<String>,
+    #[sqlx(rename = "deepgramApiKey")]
+    #[serde(rename = "deepgramApiKey")]
+    pub deepgram_api_key: Option<String>,
+    #[sqlx(rename = "elevenLabsApiKey")]
+    #[serde(rename = "elevenLabsApiKey")]
+    pub eleven_labs_api_key: Option<String>,
+    #[sqlx(rename = "groqApiKey")]
+    #[serde(rename = "groqApiKey")]
+    pub groq_api_key: Option<String>,
+    #[sqlx(rename = "openaiApiKey")]
+    #[serde(rename = "openaiApiKey")]
+    pub openai_api_key: Option<String>,
+    #[sqlx(rename = "assemblyAiApiKey")]
+    #[serde(rename = "assemblyAiApiKey")]
+    pub assembly_ai_api_key: Option<String>,
+}
+
+/// Model (and server address for local providers) last chosen for a summary provider
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SummaryProviderSetting {
+    pub provider: String,
+    pub model: String,
+    pub endpoint: Option<String>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub kind: String,
+    pub priority: i64,
+    pub status: String,
+    pub progress: f64,
+    pub message: Option<String>,
+    pub payload: String,        // JSON
+    pub result: Option<String>, // JSON
+    pub error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct GlossaryTerm {
+    pub id: String,
+    pub term: String,
+    pub aliases: String, // JSON array
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SpeakerProfile {
+    pub id: String,
+    pub name: String,
+    pub embedding: String, // JSON array
+    pub sample_count: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingSpeaker {
+    pub meeting_id: String,
+    pub label: String,
+    pub embedding: String, // JSON array
+    pub profile_id: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub id: String,
+    pub meeting_id: String,
+    pub text: String,
+    pub assignee: Option<String>,
+    pub due_date: Option<String>, // YYYY-MM-DD
+    pub source_segment: Option<String>,
+    pub completed: bool,
+    pub source: String, // "extracted" or "manual"
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct KeyPoint {
+    pub id: String,
+    pub meeting_id: String,
+    pub kind: String, // "decision", "open_question" or "risk"
+    pub text: String,
+    pub source_segments: String, // JSON array of transcript ids
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A moment of a meeting the user flagged as important
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingMarker {
+    pub id: String,
+    pub meeting_id: String,
+    pub audio_time: f64, // Seconds into the recording
+    pub label: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+/// A part of a meeting the user marked confidential; its audio is silence and it has no
+/// transcript
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ConfidentialRange {
+    pub id: String,
+    pub meeting_id: String,
+    pub audio_start: f64, // Seconds into the recording
+    pub audio_end: f64,
+    pub label: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+/// How many of one kind of item the redaction pass masked in a meeting's text
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RedactionAuditEntry {
+    pub id: String,
+    pub meeting_id: String,
+    pub target: String,   // 'summary' or 'export'
+    pub category: String, // 'email', 'phone', 'card_number', 'name' or 'profanity'
+    pub count: i64,
+    pub created_at: DateTimeUtc,
+}
+
+/// Tokens or audio sent to a hosted provider
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ProviderUsage {
+    pub id: String,
+    pub meeting_id: Option<String>,
+    /// The meeting's title, while it still exists
+    pub meeting_title: Option<String>,
+    pub kind: String,    // 'llm' or 'stt'
+    pub purpose: String, // 'summary', 'action_items', 'key_points', 'qa' or 'transcription'
+    pub provider: String,
+    pub model: String,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub audio_seconds: f64,
+    pub requests: i64,
+    pub estimated: bool, // Token counts the provider didn't report
+    pub created_at: DateTimeUtc,
+}
+
+/// A file or folder of a deleted meeting that is still to be removed
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct PendingDeletion {
+    pub path: String,
+    pub meeting_id: String,
+    pub sha256: Option<String>, // Exports are only deleted while unchanged
+    pub secure_wipe: bool,
+    pub created_at: DateTimeUtc,
+}
+
+/// A synced meeting's version last agreed with the sync location
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SyncState {
+    pub meeting_id: String,
+    pub base: String, // JSON of the synced meeting
+    pub remote_tag: Option<String>,
+    pub synced_at: DateTimeUtc,
+}
+
+/// A transcript segment edited differently on two devices
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SyncConflict {
+    pub id: String,
+    pub meeting_id: String,
+    pub segment_id: String,
+    pub kept_text: String,
+    pub other_text: String, // The edit the merge dropped
+    pub other_device: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SummaryTemplateRow {
+    pub id: String,
+    pub definition: String, // Template JSON
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SummaryVersion {
+    pub id: String,
+    pub meeting_id: String,
+    pub provider: String,
+    pub model: String,
+    pub template_id: String,
+    pub result: String, // JSON, same shape as summary_processes.result
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A transcript segment matching a full-text search
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptSearchHit {
+    pub transcript_id: String,
+    pub meeting_id: String,
+    pub meeting_title: String,
+    pub meeting_created_at: DateTimeUtc,
+    pub speaker: Option<String>,
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+    /// Text around the match, with matched terms wrapped in `<mark>`
+    pub snippet: String,
+    /// BM25 score; lower is more relevant
+    pub rank: f64,
+}
+
+/// A meeting whose summary matches a full-text search
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SummarySearchHit {
+    pub meeting_id: String,
+    pub meeting_title: String,
+    pub meeting_created_at: DateTimeUtc,
+    /// Text around the match, with matched terms wrapped in `<mark>`
+    pub snippet: String,
+    /// BM25 score; lower is more relevant
+    pub rank: f64,
+}
+
+/// A meeting row with its library fields
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LibraryMeeting {
+    pub id: String,
+    pub title: String,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+    pub folder_path: Option<String>,
+    pub archived_at: Option<DateTimeUtc>,
+    pub notes: Option<String>,
+    pub folder_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct Participant {
+    pub name: String,
+    pub email: Option<String>,
+}
+
+/// A library folder; folders nest through `parent_id`
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Folder {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+/// A named library filter query
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SavedFilter {
+    pub id: String,
+    pub name: String,
+    pub query: String,
+    pub created_at: DateTimeUtc,
+}
+
+/// A connected Notion workspace and where meetings go in it
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct NotionConnection {
+    pub id: String,
+    pub workspace_name: String,
+    pub database_id: Option<String>,
+    pub database_title: Option<String>,
+    pub title_property: Option<String>,
+    pub date_property: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+/// Where and whether summaries are posted to Slack
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SlackSettings {
+    pub enabled: bool,
+    pub mode: String, // "webhook" or "bot"
+    pub channel: Option<String>,
+    pub updated_at: DateTimeUtc,
+}
+
+/// A URL that receives meeting lifecycle events
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    pub events: String, // JSON array of event names
+    pub enabled: bool,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+/// One event sent to a webhook and the outcome of the latest attempt
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub webhook_id: String,
+    pub event: String,
+    pub payload: String,
+    pub status: String, // "pending", "delivered" or "failed"
+    pub attempts: i64,
+    pub response_status: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+/// SMTP server minutes are emailed through
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct EmailSettings {
+    pub host: String,
+    pub port: i64,
+    pub security: String, // "starttls", "tls" or "none"
+    pub username: Option<String>,
+    pub from_address: String,
+    pub from_name: Option<String>,
+    pub updated_at: DateTimeUtc,
+}
+
+/// A connected calendar
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CalendarAccount {
+    pub id: String,
+    pub provider: String, // "google", "outlook" or "caldav"
+    pub name: String,
+    pub url: Option<String>,
+    pub username: Option<String>,
+    pub created_at: DateTimeUtc,
+    pub last_synced_at: Option<DateTimeUtc>,
+}
+
+/// A synced calendar event
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CalendarEvent {
+    pub id: String,
+    pub account_id: String,
+    pub external_id: String,
+    pub title: String,
+    pub starts_at: DateTimeUtc,
+    pub ends_at: DateTimeUtc,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub attendees: String, // JSON array of participants
+    pub updated_at: DateTimeUtc,
+    /// Shared by the instances of a recurring event
+    pub series_id: Option<String>,
+}
+
+/// An event, or every instance of a recurring event, to record automatically
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CalendarRecordingRule {
+    pub id: String,
+    pub account_id: String,
+    pub target: String, // "event" or "series"
+    pub target_id: String, // the event's external_id, or the series_id
+    pub title: String,
+    pub created_at: DateTimeUtc,
+}
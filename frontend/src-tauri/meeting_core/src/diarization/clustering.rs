@@ -0,0 +1,98 @@
+/// Cosine similarity of two embeddings (0 when either is all zeros)
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+/// Mean of several embeddings
+pub fn centroid(embeddings: &[&[f32]]) -> Vec<f32> {
+    let Some(first) = embeddings.first() else {
+        return Vec::new();
+    };
+    let mut sum = vec![0.0; first.len()];
+    for embedding in embeddings {
+        for (total, value) in sum.iter_mut().zip(embedding.iter()) {
+            *total += value;
+        }
+    }
+    sum.iter_mut().for_each(|v| *v /= embeddings.len() as f32);
+    sum
+}
+
+/// Agglomerative clustering with average linkage
+///
+/// Clusters are merged while their average similarity is at least `threshold`, so the
+/// number of speakers doesn't have to be known up front. Returns a cluster index per
+/// embedding, numbered in order of first appearance.
+pub fn cluster_embeddings(embeddings: &[Vec<f32>], threshold: f32) -> Vec<usize> {
+    let n = embeddings.len();
+    let similarity: Vec<Vec<f32>> = (0..n)
+        .map(|i| (0..n).map(|j| cosine_similarity(&embeddings[i], &embeddings[j])).collect())
+        .collect();
+
+    let mut clusters: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    loop {
+        let mut best: Option<(usize, usize, f32)> = None;
+        for a in 0..clusters.len() {
+            for b in (a + 1)..clusters.len() {
+                let total: f32 = clusters[a]
+                    .iter()
+                    .flat_map(|&i| clusters[b].iter().map(move |&j| (i, j)))
+                    .map(|(i, j)| similarity[i][j])
+                    .sum();
+                let average = total / (clusters[a].len() * clusters[b].len()) as f32;
+                if average >= threshold && best.map_or(true, |(_, _, s)| average > s) {
+                    best = Some((a, b, average));
+                }
+            }
+        }
+        match best {
+            Some((a, b, _)) => {
+                let merged = clusters.remove(b);
+                clusters[a].extend(merged);
+            }
+            None => break,
+        }
+    }
+
+    // Number clusters by their earliest member
+    clusters.sort_by_key(|members| members.iter().copied().min());
+    let mut labels = vec![0; n];
+    for (label, members) in clusters.iter().enumerate() {
+        for &member in members {
+            labels[member] = label;
+        }
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similar_embeddings_share_a_cluster() {
+        let embeddings = vec![
+            vec![1.0, 0.1, 0.0],
+            vec![0.0, 1.0, 0.1],
+            vec![0.95, 0.15, 0.0],
+            vec![0.05, 0.9, 0.1],
+            vec![1.0, 0.0, 0.05],
+        ];
+        assert_eq!(cluster_embeddings(&embeddings, 0.7), vec![0, 1, 0, 1, 0]);
+        assert_eq!(cluster_embeddings(&embeddings, 0.9999), vec![0, 1, 2, 3, 4]);
+        assert!(cluster_embeddings(&[], 0.5).is_empty());
+    }
+
+    #[test]
+    fn test_cosine_and_centroid() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(centroid(&[&[1.0, 3.0], &[3.0, 5.0]]), vec![2.0, 4.0]);
+    }
+}
@@ -0,0 +1,111 @@
+use super::features::{compute_fbank, NUM_MEL_BINS};
+use anyhow::{anyhow, Result};
+use futures_util::StreamExt;
+use ndarray::Array3;
+use ort::execution_providers::CPUExecutionProvider;
+use ort::inputs;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::TensorRef;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+/// WeSpeaker ResNet34 trained on VoxCeleb (256-dim embeddings, ~25 MB)
+pub const EMBEDDING_MODEL_FILE: &str = "wespeaker_en_voxceleb_resnet34.onnx";
+const EMBEDDING_MODEL_URL: &str =
+    "https://github.com/k2-fsa/sherpa-onnx/releases/download/speaker-recongition-models/wespeaker_en_voxceleb_resnet34.onnx";
+
+/// Where the embedding model lives under the app's models directory
+pub fn model_path(models_dir: &Path) -> PathBuf {
+    models_dir.join("diarization").join(EMBEDDING_MODEL_FILE)
+}
+
+/// Download the embedding model, reporting progress as a percentage
+pub async fn download_model(models_dir: &Path, progress: impl Fn(u8)) -> Result<PathBuf> {
+    let path = model_path(models_dir);
+    if path.exists() {
+        return Ok(path);
+    }
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    log::info!("Downloading speaker embedding model from {}", EMBEDDING_MODEL_URL);
+    let response = reqwest::get(EMBEDDING_MODEL_URL)
+        .await
+        .map_err(|e| anyhow!("Failed to start download: {}", e))?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Download failed with status: {}", response.status()));
+    }
+
+    // Download next to the model and rename, so a partial file is never loaded
+    let partial_path = path.with_extension("onnx.part");
+    let total_size = response.content_length().unwrap_or(0);
+    let mut file = tokio::fs::File::create(&partial_path).await?;
+    let mut stream = response.bytes_stream();
+    let mut downloaded = 0u64;
+    let mut last_progress = 0u8;
+    progress(0);
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| anyhow!("Failed to read chunk: {}", e))?;
+        file.write_all(&chunk).await?;
+        downloaded += chunk.len() as u64;
+        if total_size > 0 {
+            let percent = ((downloaded as f64 / total_size as f64) * 100.0) as u8;
+            if percent > last_progress {
+                last_progress = percent;
+                progress(percent);
+            }
+        }
+    }
+    file.flush().await?;
+    drop(file);
+
+    tokio::fs::rename(&partial_path, &path).await?;
+    progress(100);
+    log::info!("Speaker embedding model saved to {}", path.display());
+    Ok(path)
+}
+
+/// Speaker embedding model: maps a stretch of speech to a voice fingerprint
+pub struct SpeakerEmbedder {
+    session: Session,
+    input_name: String,
+}
+
+impl SpeakerEmbedder {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(anyhow!("Speaker embedding model not found. Download it first."));
+        }
+        let threads = std::thread::available_parallelism().map(|n| n.get().min(4)).unwrap_or(2);
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_execution_providers(vec![CPUExecutionProvider::default().build()])?
+            .with_intra_threads(threads)?
+            .commit_from_file(path)?;
+        let input_name = session
+            .inputs
+            .first()
+            .map(|input| input.name.clone())
+            .ok_or_else(|| anyhow!("Speaker embedding model has no inputs"))?;
+        Ok(Self { session, input_name })
+    }
+
+    /// Embedding of 16kHz mono speech; None when the audio is too short to compute features
+    pub fn embed(&mut self, samples: &[f32]) -> Result<Option<Vec<f32>>> {
+        let features = compute_fbank(samples);
+        if features.is_empty() {
+            return Ok(None);
+        }
+
+        let frames = features.len();
+        let input = Array3::from_shape_vec((1, frames, NUM_MEL_BINS), features.into_iter().flatten().collect())?;
+        let outputs = self
+            .session
+            .run(inputs![self.input_name.as_str() => TensorRef::from_array_view(input.view())?])?;
+        let embedding = outputs[0].try_extract_array::<f32>()?;
+        Ok(Some(embedding.iter().copied().collect()))
+    }
+}
@@ -0,0 +1,132 @@
+use realfft::RealFftPlanner;
+
+/// Kaldi-style log mel filterbank features, as speaker embedding models are trained on
+///
+/// 16kHz input, 25ms frames every 10ms, Povey window, 80 mel bins, mean-normalized over
+/// time (CMN).
+pub const SAMPLE_RATE: usize = 16000;
+pub const NUM_MEL_BINS: usize = 80;
+const FRAME_LENGTH: usize = 400;
+const FRAME_SHIFT: usize = 160;
+const FFT_SIZE: usize = 512;
+const PREEMPHASIS: f32 = 0.97;
+const LOW_FREQ: f32 = 20.0;
+
+fn mel_scale(hz: f32) -> f32 {
+    1127.0 * (1.0 + hz / 700.0).ln()
+}
+
+/// Triangular filters over the FFT bins, one row per mel bin
+fn mel_filterbank() -> Vec<Vec<f32>> {
+    let num_bins = FFT_SIZE / 2;
+    let bin_hz = SAMPLE_RATE as f32 / FFT_SIZE as f32;
+    let mel_low = mel_scale(LOW_FREQ);
+    let mel_high = mel_scale(SAMPLE_RATE as f32 / 2.0);
+    let mel_delta = (mel_high - mel_low) / (NUM_MEL_BINS + 1) as f32;
+
+    (0..NUM_MEL_BINS)
+        .map(|m| {
+            let left = mel_low + m as f32 * mel_delta;
+            let center = left + mel_delta;
+            let right = center + mel_delta;
+            (0..num_bins)
+                .map(|bin| {
+                    let mel = mel_scale(bin as f32 * bin_hz);
+                    if mel > left && mel < right {
+                        if mel <= center {
+                            (mel - left) / (center - left)
+                        } else {
+                            (right - mel) / (right - center)
+                        }
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Features for 16kHz mono samples in [-1, 1]; one row of `NUM_MEL_BINS` per frame
+pub fn compute_fbank(samples: &[f32]) -> Vec<Vec<f32>> {
+    if samples.len() < FRAME_LENGTH {
+        return Vec::new();
+    }
+    let num_frames = 1 + (samples.len() - FRAME_LENGTH) / FRAME_SHIFT;
+
+    let filters = mel_filterbank();
+    let window: Vec<f32> = (0..FRAME_LENGTH)
+        .map(|i| {
+            let hann = 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (FRAME_LENGTH - 1) as f32).cos();
+            hann.powf(0.85)
+        })
+        .collect();
+    let fft = RealFftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+    let mut input = fft.make_input_vec();
+    let mut spectrum = fft.make_output_vec();
+
+    let mut features = Vec::with_capacity(num_frames);
+    for frame_index in 0..num_frames {
+        let start = frame_index * FRAME_SHIFT;
+        // Models expect int16-range samples, as Kaldi reads them from WAV
+        let mut frame: Vec<f32> = samples[start..start + FRAME_LENGTH].iter().map(|s| s * 32768.0).collect();
+
+        let mean = frame.iter().sum::<f32>() / FRAME_LENGTH as f32;
+        frame.iter_mut().for_each(|s| *s -= mean);
+        for i in (1..FRAME_LENGTH).rev() {
+            frame[i] -= PREEMPHASIS * frame[i - 1];
+        }
+        frame[0] -= PREEMPHASIS * frame[0];
+
+        input.fill(0.0);
+        for (i, sample) in frame.iter().enumerate() {
+            input[i] = sample * window[i];
+        }
+        if fft.process(&mut input, &mut spectrum).is_err() {
+            continue;
+        }
+
+        let power: Vec<f32> = spectrum.iter().take(FFT_SIZE / 2).map(|c| c.norm_sqr()).collect();
+        features.push(
+            filters
+                .iter()
+                .map(|filter| {
+                    let energy: f32 = filter.iter().zip(&power).map(|(w, p)| w * p).sum();
+                    energy.max(f32::EPSILON).ln()
+                })
+                .collect::<Vec<f32>>(),
+        );
+    }
+
+    // Cepstral mean normalization
+    if !features.is_empty() {
+        for bin in 0..NUM_MEL_BINS {
+            let mean = features.iter().map(|f| f[bin]).sum::<f32>() / features.len() as f32;
+            features.iter_mut().for_each(|f| f[bin] -= mean);
+        }
+    }
+    features
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fbank_frame_count_and_shape() {
+        assert!(compute_fbank(&[0.0; 100]).is_empty());
+
+        let one_second: Vec<f32> = (0..SAMPLE_RATE).map(|i| (i as f32 * 0.05).sin() * 0.3).collect();
+        let features = compute_fbank(&one_second);
+        assert_eq!(features.len(), 1 + (SAMPLE_RATE - FRAME_LENGTH) / FRAME_SHIFT);
+        assert!(features.iter().all(|f| f.len() == NUM_MEL_BINS));
+        assert!(features.iter().flatten().all(|v| v.is_finite()));
+    }
+
+    #[test]
+    fn test_filterbank_covers_every_bin() {
+        let filters = mel_filterbank();
+        assert_eq!(filters.len(), NUM_MEL_BINS);
+        assert!(filters.iter().all(|f| f.iter().any(|&w| w > 0.0)));
+    }
+}
@@ -0,0 +1,8 @@
+// diarization/mod.rs
+//
+// Filterbank features, the speaker embedding model and clustering of embeddings into
+// speakers. Turn detection and transcript labelling stay in the app.
+
+pub mod clustering;
+pub mod embedding;
+pub mod features;
@@ -0,0 +1,16 @@
+// meeting_core
+//
+// The parts of the recording pipeline that don't depend on Tauri: audio capture and device
+// discovery, transcription engine types and model catalogs, speaker diarization models,
+// summary providers and templates, and the stored data model. The app (`app_lib`), the
+// `meeting-minutes-cli` binary and tests build on it without a window or an `AppHandle`.
+//
+// Modules keep the paths they have in the app, which re-exports them in place, so
+// `crate::audio::devices` means the same thing on both sides of the split.
+
+pub mod audio;
+pub mod database;
+pub mod diarization;
+pub mod parakeet_engine;
+pub mod summary;
+pub mod whisper_engine;
@@ -0,0 +1,9 @@
+// parakeet_engine/mod.rs
+//
+// The Parakeet engine and its ONNX model; the app adds the Tauri commands.
+
+pub mod model;
+pub mod parakeet_engine;
+
+pub use model::{ParakeetError, ParakeetModel, TimestampedResult};
+pub use parakeet_engine::{ModelInfo, ModelStatus, ParakeetEngine, ParakeetEngineError, QuantizationType};
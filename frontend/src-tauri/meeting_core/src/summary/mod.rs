@@ -0,0 +1,8 @@
+// summary/mod.rs
+//
+// The interface every summary provider implements, and summary templates.
+
+pub mod provider;
+pub mod templates;
+
+pub use provider::{SummaryError, SummaryProvider, TokenUsage};
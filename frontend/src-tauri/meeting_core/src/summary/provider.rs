@@ -0,0 +1,248 @@
+// summary/provider.rs
+//
+// Defines the SummaryProvider trait and common types for all LLM backends used to
+// write meeting minutes (hosted APIs, Ollama, a local llama.cpp server).
+
+use async_trait::async_trait;
+use serde::Serialize;
+use std::time::Duration;
+use tracing::warn;
+
+// ============================================================================
+// SUMMARY PROVIDER TRAIT & ERROR TYPES
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub enum SummaryError {
+    MissingApiKey(String),
+    /// The request never got a response (connection refused, timeout, ...)
+    Request(String),
+    Api {
+        status: u16,
+        message: String,
+        /// Seconds the provider asked us to wait before retrying
+        retry_after: Option<u64>,
+    },
+    InvalidResponse(String),
+}
+
+impl SummaryError {
+    /// Rate limits, server errors and dropped connections are worth another attempt
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Request(_) => true,
+            Self::Api { status, .. } => *status == 408 || *status == 429 || *status >= 500,
+            Self::MissingApiKey(_) | Self::InvalidResponse(_) => false,
+        }
+    }
+}
+
+impl std::fmt::Display for SummaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingApiKey(provider) => write!(f, "Api key not found for {}", provider),
+            Self::Request(msg) => write!(f, "Failed to send request to LLM: {}", msg),
+            Self::Api { status, message, .. } => write!(f, "LLM API request failed ({}): {}", status, message),
+            Self::InvalidResponse(msg) => write!(f, "Failed to parse LLM response: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SummaryError {}
+
+/// Tokens consumed by one or more LLM calls
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Some counts were estimated because the provider didn't report usage
+    pub estimated: bool,
+}
+
+impl TokenUsage {
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+
+    pub fn add(&mut self, other: TokenUsage) {
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.estimated |= other.estimated;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CompletionRequest {
+    pub system_prompt: String,
+    pub user_prompt: String,
+    /// Upper bound on generated tokens; None leaves it to the provider
+    pub max_tokens: Option<u32>,
+}
+
+impl CompletionRequest {
+    pub fn new(system_prompt: impl Into<String>, user_prompt: impl Into<String>) -> Self {
+        Self { system_prompt: system_prompt.into(), user_prompt: user_prompt.into(), max_tokens: None }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Completion {
+    pub text: String,
+    pub usage: TokenUsage,
+}
+
+/// Trait for summary providers (OpenAI, Claude, Groq, OpenRouter, Ollama, llama.cpp)
+#[async_trait]
+pub trait SummaryProvider: Send + Sync {
+    /// Run one chat completion with a system and a user message
+    async fn complete(&self, request: &CompletionRequest) -> Result<Completion, SummaryError>;
+
+    /// Like `complete`, passing text to `on_delta` as it is generated
+    ///
+    /// Providers without streaming deliver the whole text as a single delta.
+    async fn complete_streaming(
+        &self,
+        request: &CompletionRequest,
+        on_delta: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<Completion, SummaryError> {
+        let completion = self.complete(request).await?;
+        on_delta(&completion.text);
+        Ok(completion)
+    }
+
+    /// Model the provider was configured with
+    fn model(&self) -> &str;
+
+    /// Get the provider name (for logging/debugging)
+    fn provider_name(&self) -> &'static str;
+
+    /// Whether the transcript is sent off the machine (hosted APIs)
+    fn is_remote(&self) -> bool {
+        true
+    }
+}
+
+// ============================================================================
+// RETRY WITH BACKOFF
+// ============================================================================
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: 4, initial_delay: Duration::from_secs(1), max_delay: Duration::from_secs(30) }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `attempt` (1-based): doubles each time, or what the provider asked for
+    pub fn delay(&self, attempt: u32, error: &SummaryError) -> Duration {
+        if let SummaryError::Api { retry_after: Some(seconds), .. } = error {
+            return Duration::from_secs(*seconds).min(self.max_delay);
+        }
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// `provider.complete`, retrying transient failures with exponential backoff
+pub async fn complete_with_retry(
+    provider: &dyn SummaryProvider,
+    request: &CompletionRequest,
+    policy: &RetryPolicy,
+) -> Result<Completion, SummaryError> {
+    let mut attempt = 1;
+    loop {
+        match provider.complete(request).await {
+            Ok(completion) => return Ok(completion),
+            Err(e) if e.is_retryable() && attempt < policy.max_attempts => {
+                let delay = policy.delay(attempt, &e);
+                warn!(
+                    "⚠️ {} request failed (attempt {}/{}): {}. Retrying in {:?}",
+                    provider.provider_name(),
+                    attempt,
+                    policy.max_attempts,
+                    e,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// `provider.complete_streaming` with retries
+///
+/// Only failures before any text was streamed are retried; a retry after that would
+/// repeat text the user has already seen.
+pub async fn stream_with_retry(
+    provider: &dyn SummaryProvider,
+    request: &CompletionRequest,
+    policy: &RetryPolicy,
+    on_delta: &mut (dyn FnMut(&str) + Send),
+) -> Result<Completion, SummaryError> {
+    let mut attempt = 1;
+    loop {
+        let mut streamed = false;
+        let mut forward = |delta: &str| {
+            streamed = true;
+            on_delta(delta);
+        };
+        let result = provider.complete_streaming(request, &mut forward).await;
+        match result {
+            Ok(completion) => return Ok(completion),
+            Err(e) if e.is_retryable() && !streamed && attempt < policy.max_attempts => {
+                let delay = policy.delay(attempt, &e);
+                warn!(
+                    "⚠️ {} stream failed (attempt {}/{}): {}. Retrying in {:?}",
+                    provider.provider_name(),
+                    attempt,
+                    policy.max_attempts,
+                    e,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_transient_errors_are_retried() {
+        let api = |status| SummaryError::Api { status, message: String::new(), retry_after: None };
+        assert!(api(429).is_retryable());
+        assert!(api(503).is_retryable());
+        assert!(!api(401).is_retryable());
+        assert!(SummaryError::Request("connection refused".into()).is_retryable());
+        assert!(!SummaryError::MissingApiKey("openai".into()).is_retryable());
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_honours_retry_after() {
+        let policy = RetryPolicy::default();
+        let error = SummaryError::Request(String::new());
+        assert_eq!(policy.delay(1, &error), Duration::from_secs(1));
+        assert_eq!(policy.delay(3, &error), Duration::from_secs(4));
+        assert_eq!(policy.delay(10, &error), Duration::from_secs(30));
+
+        let limited = SummaryError::Api { status: 429, message: String::new(), retry_after: Some(7) };
+        assert_eq!(policy.delay(1, &limited), Duration::from_secs(7));
+
+        let mut usage = TokenUsage { prompt_tokens: 10, completion_tokens: 5, estimated: false };
+        usage.add(TokenUsage { prompt_tokens: 1, completion_tokens: 2, estimated: true });
+        assert_eq!((usage.total_tokens(), usage.estimated), (18, true));
+    }
+}
@@ -4,10 +4,16 @@
 /// when custom templates are not available.
 
 /// Daily standup template for engineering/product teams
-pub const DAILY_STANDUP: &str = include_str!("../../../templates/daily_standup.json");
+pub const DAILY_STANDUP: &str = include_str!("../../../../templates/daily_standup.json");
 
 /// Standard meeting notes template
-pub const STANDARD_MEETING: &str = include_str!("../../../templates/standard_meeting.json");
+pub const STANDARD_MEETING: &str = include_str!("../../../../templates/standard_meeting.json");
+
+/// Manager/report one-on-one template
+pub const ONE_ON_ONE: &str = include_str!("../../../../templates/one_on_one.json");
+
+/// Sprint review (demo and stakeholder feedback) template
+pub const SPRINT_REVIEW: &str = include_str!("../../../../templates/sprint_review.json");
 
 /// Registry of all built-in templates
 ///
@@ -16,6 +22,8 @@ pub fn get_builtin_templates() -> Vec<(&'static str, &'static str)> {
     vec![
         ("daily_standup", DAILY_STANDUP),
         ("standard_meeting", STANDARD_MEETING),
+        ("one_on_one", ONE_ON_ONE),
+        ("sprint_review", SPRINT_REVIEW),
     ]
 }
 
@@ -30,13 +38,15 @@ pub fn get_builtin_template(id: &str) -> Option<&'static str> {
     match id {
         "daily_standup" => Some(DAILY_STANDUP),
         "standard_meeting" => Some(STANDARD_MEETING),
+        "one_on_one" => Some(ONE_ON_ONE),
+        "sprint_review" => Some(SPRINT_REVIEW),
         _ => None,
     }
 }
 
 /// List all built-in template identifiers
 pub fn list_builtin_template_ids() -> Vec<&'static str> {
-    vec!["daily_standup", "standard_meeting"]
+    vec!["daily_standup", "standard_meeting", "one_on_one", "sprint_review"]
 }
 
 #[cfg(test)]
@@ -60,6 +70,8 @@ mod tests {
     fn test_get_builtin_template() {
         assert!(get_builtin_template("daily_standup").is_some());
         assert!(get_builtin_template("standard_meeting").is_some());
+        assert!(get_builtin_template("one_on_one").is_some());
+        assert!(get_builtin_template("sprint_review").is_some());
         assert!(get_builtin_template("nonexistent").is_none());
     }
 }
@@ -0,0 +1,9 @@
+// summary/templates/mod.rs
+//
+// Template types and the built-in templates. Loading stored and custom templates stays in
+// the app, which knows where they are.
+
+pub mod defaults;
+pub mod types;
+
+pub use types::{Template, TemplateSection, TemplateVariables};
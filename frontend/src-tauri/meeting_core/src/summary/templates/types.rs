@@ -30,10 +30,60 @@ pub struct Template {
     /// Brief description of the template's purpose
     pub description: String,
 
+    /// Extra guidance for the LLM on how to write the summary (tone, focus, audience)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+
     /// List of sections in the template
     pub sections: Vec<TemplateSection>,
 }
 
+/// Meeting facts that can be referenced in a template's prompt and section instructions
+///
+/// Supported placeholders: `{title}`, `{date}`, `{participants}`, `{duration}` and `{agenda}`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateVariables {
+    pub title: Option<String>,
+    pub date: Option<String>,
+    pub participants: Vec<String>,
+    pub duration_seconds: Option<f64>,
+    /// From the linked calendar event's description
+    pub agenda: Option<String>,
+}
+
+impl TemplateVariables {
+    fn value(&self, name: &str) -> Option<String> {
+        match name {
+            "title" => self.title.clone(),
+            "date" => self.date.clone(),
+            "participants" => (!self.participants.is_empty()).then(|| self.participants.join(", ")),
+            "duration" => self.duration_seconds.map(|seconds| {
+                let minutes = (seconds / 60.0).round() as u64;
+                if minutes >= 60 {
+                    format!("{} h {} min", minutes / 60, minutes % 60)
+                } else {
+                    format!("{} min", minutes.max(1))
+                }
+            }),
+            "agenda" => self.agenda.clone(),
+            _ => None,
+        }
+    }
+
+    /// Replaces the placeholders in `text`; unknown values read as "unknown"
+    pub fn substitute(&self, text: &str) -> String {
+        ["title", "date", "participants", "duration", "agenda"]
+            .iter()
+            .fold(text.to_string(), |text, name| {
+                let placeholder = format!("{{{}}}", name);
+                if !text.contains(&placeholder) {
+                    return text;
+                }
+                text.replace(&placeholder, &self.value(name).unwrap_or_else(|| "unknown".to_string()))
+            })
+    }
+}
+
 impl Template {
     /// Validates the template structure
     pub fn validate(&self) -> Result<(), String> {
@@ -70,6 +120,16 @@ impl Template {
         Ok(())
     }
 
+    /// The template with its placeholders filled in for one meeting
+    pub fn render(&self, variables: &TemplateVariables) -> Template {
+        let mut template = self.clone();
+        template.prompt = template.prompt.map(|prompt| variables.substitute(&prompt));
+        for section in &mut template.sections {
+            section.instruction = variables.substitute(&section.instruction);
+        }
+        template
+    }
+
     /// Generates a clean markdown template structure
     pub fn to_markdown_structure(&self) -> String {
         let mut markdown = String::from("# <Add Title here>\n\n");
@@ -118,6 +178,7 @@ mod tests {
         let template = Template {
             name: "Test Template".to_string(),
             description: "A test template".to_string(),
+            prompt: None,
             sections: vec![
                 TemplateSection {
                     title: "Summary".to_string(),
@@ -137,6 +198,7 @@ mod tests {
         let template = Template {
             name: "".to_string(),
             description: "A test template".to_string(),
+            prompt: None,
             sections: vec![],
         };
 
@@ -148,6 +210,7 @@ mod tests {
         let template = Template {
             name: "Test".to_string(),
             description: "Test".to_string(),
+            prompt: None,
             sections: vec![
                 TemplateSection {
                     title: "Test".to_string(),
@@ -161,4 +224,34 @@ mod tests {
 
         assert!(template.validate().is_err());
     }
+
+    #[test]
+    fn test_render_substitutes_variables() {
+        let template = Template {
+            name: "1:1".to_string(),
+            description: "Test".to_string(),
+            prompt: Some("A {duration} one-on-one between {participants}.".to_string()),
+            sections: vec![
+                TemplateSection {
+                    title: "Date".to_string(),
+                    instruction: "Use {date}; keep {unknown} as is".to_string(),
+                    format: "string".to_string(),
+                    item_format: None,
+                    example_item_format: None,
+                },
+            ],
+        };
+        let variables = TemplateVariables {
+            title: None,
+            date: Some("2025-10-30".to_string()),
+            participants: vec!["Alice".to_string(), "Bob".to_string()],
+            duration_seconds: Some(3900.0),
+            agenda: None,
+        };
+
+        let rendered = template.render(&variables);
+        assert_eq!(rendered.prompt.as_deref(), Some("A 1 h 5 min one-on-one between Alice, Bob."));
+        assert_eq!(rendered.sections[0].instruction, "Use 2025-10-30; keep {unknown} as is");
+        assert_eq!(TemplateVariables::default().substitute("{participants}"), "unknown");
+    }
 }
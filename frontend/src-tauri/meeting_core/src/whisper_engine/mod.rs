@@ -0,0 +1,5 @@
+// whisper_engine/mod.rs
+//
+// The Whisper model catalog and download verification.
+
+pub mod models;
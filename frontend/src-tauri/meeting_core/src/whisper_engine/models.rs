@@ -0,0 +1,152 @@
+// Whisper model catalog and download verification
+//
+// All models come from the ggerganov/whisper.cpp repository on Hugging Face. Model files
+// are stored with Git LFS, so the repository reports each file's SHA-256 (the
+// `x-linked-etag` header of the un-redirected download URL). Downloads are hashed while
+// streaming and rejected on mismatch; the verified hash is kept next to the model as
+// `<file>.sha256` so the file can be re-verified later without network access.
+
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use sha2::{Digest, Sha256};
+
+const MODEL_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+pub struct ModelSpec {
+    pub name: &'static str,
+    pub filename: &'static str,
+    pub size_mb: u32,
+    pub accuracy: &'static str,
+    pub speed: &'static str,
+    pub description: &'static str,
+}
+
+impl ModelSpec {
+    pub fn download_url(&self) -> String {
+        format!("{}/{}", MODEL_BASE_URL, self.filename)
+    }
+}
+
+/// Models offered for download, smallest to largest within each precision
+pub const MODEL_CATALOG: &[ModelSpec] = &[
+    // Standard f16 models (full precision)
+    ModelSpec { name: "tiny", filename: "ggml-tiny.bin", size_mb: 39, accuracy: "Decent", speed: "Very Fast", description: "Fastest processing, good for real-time use" },
+    ModelSpec { name: "base", filename: "ggml-base.bin", size_mb: 142, accuracy: "Good", speed: "Fast", description: "Good balance of speed and accuracy" },
+    ModelSpec { name: "small", filename: "ggml-small.bin", size_mb: 466, accuracy: "Good", speed: "Medium", description: "Better accuracy, moderate speed" },
+    ModelSpec { name: "medium", filename: "ggml-medium.bin", size_mb: 1420, accuracy: "High", speed: "Slow", description: "High accuracy for professional use" },
+    ModelSpec { name: "large-v3-turbo", filename: "ggml-large-v3-turbo.bin", size_mb: 809, accuracy: "High", speed: "Medium", description: "Best accuracy with improved speed" },
+    ModelSpec { name: "large-v3", filename: "ggml-large-v3.bin", size_mb: 2870, accuracy: "High", speed: "Slow", description: "Best accuracy, latest large model" },
+
+    // Q5_0 quantized models (balanced speed/accuracy)
+    ModelSpec { name: "tiny-q5_0", filename: "ggml-tiny-q5_0.bin", size_mb: 26, accuracy: "Decent", speed: "Very Fast", description: "Quantized tiny model, ~50% faster processing" },
+    ModelSpec { name: "base-q5_0", filename: "ggml-base-q5_0.bin", size_mb: 85, accuracy: "Good", speed: "Fast", description: "Quantized base model, good speed/accuracy balance" },
+    ModelSpec { name: "small-q5_0", filename: "ggml-small-q5_0.bin", size_mb: 280, accuracy: "Good", speed: "Fast", description: "Quantized small model, faster than f16 version" },
+    ModelSpec { name: "medium-q5_0", filename: "ggml-medium-q5_0.bin", size_mb: 852, accuracy: "High", speed: "Medium", description: "Quantized medium model, professional quality" },
+    ModelSpec { name: "large-v3-turbo-q5_0", filename: "ggml-large-v3-turbo-q5_0.bin", size_mb: 574, accuracy: "High", speed: "Medium", description: "Quantized large model, best balance" },
+    ModelSpec { name: "large-v3-q5_0", filename: "ggml-large-v3-q5_0.bin", size_mb: 1050, accuracy: "High", speed: "Slow", description: "Quantized large model, high accuracy" },
+];
+
+pub fn find_model(name: &str) -> Option<&'static ModelSpec> {
+    MODEL_CATALOG.iter().find(|spec| spec.name == name)
+}
+
+fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// SHA-256 published for a model file, if the repository reports one
+pub async fn fetch_expected_sha256(url: &str) -> Option<String> {
+    // The LFS hash is only on the first response, before the redirect to the CDN
+    let client = Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .ok()?;
+    let response = client.head(url).send().await.ok()?;
+    let etag = response
+        .headers()
+        .get("x-linked-etag")
+        .or_else(|| response.headers().get("etag"))?
+        .to_str()
+        .ok()?
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .to_lowercase();
+
+    if is_sha256(&etag) {
+        Some(etag)
+    } else {
+        log::warn!("No SHA-256 published for {} (etag {})", url, etag);
+        None
+    }
+}
+
+/// Sidecar file holding the verified hash of a model
+pub fn checksum_path(model_path: &Path) -> PathBuf {
+    let mut name = model_path.as_os_str().to_owned();
+    name.push(".sha256");
+    PathBuf::from(name)
+}
+
+/// Incremental hasher fed while the model streams to disk
+pub struct DownloadHasher(Sha256);
+
+impl DownloadHasher {
+    pub fn new() -> Self {
+        Self(Sha256::new())
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    pub fn finish(self) -> String {
+        format!("{:x}", self.0.finalize())
+    }
+}
+
+/// Hash a model file on disk (off the async runtime; large models take seconds)
+pub async fn sha256_file(path: &Path) -> Result<String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<String> {
+        use std::io::Read;
+        let mut file = std::fs::File::open(&path)
+            .map_err(|e| anyhow!("Failed to open {}: {}", path.display(), e))?;
+        let mut hasher = DownloadHasher::new();
+        let mut buffer = vec![0u8; 1024 * 1024];
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+        Ok(hasher.finish())
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_urls_use_resolve_endpoint() {
+        for spec in MODEL_CATALOG {
+            assert!(spec.download_url().starts_with(MODEL_BASE_URL));
+            assert!(spec.download_url().ends_with(spec.filename));
+        }
+        assert!(find_model("large-v3-turbo-q5_0").is_some());
+        assert!(find_model("unknown").is_none());
+    }
+
+    #[test]
+    fn test_hasher_matches_known_digest() {
+        let mut hasher = DownloadHasher::new();
+        hasher.update(b"ab");
+        hasher.update(b"c");
+        assert_eq!(hasher.finish(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert!(is_sha256("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+        assert!(!is_sha256("not-a-hash"));
+    }
+}
@@ -0,0 +1,2 @@
+-- Add assemblyAiApiKey column to transcript_settings table
+ALTER TABLE transcript_settings ADD COLUMN assemblyAiApiKey TEXT;
@@ -0,0 +1,21 @@
+-- Migration: Add background job queue
+-- Transcription and summarization work is queued here so it survives restarts:
+--   - priority: 0 = live meeting, 1 = normal, 2 = imported file (lower runs first)
+--   - status: queued, running, completed, failed, cancelled
+--   - payload/result: JSON, shape depends on kind
+
+CREATE TABLE IF NOT EXISTS jobs (
+    id TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 1,
+    status TEXT NOT NULL DEFAULT 'queued',
+    progress REAL NOT NULL DEFAULT 0,
+    message TEXT,
+    payload TEXT NOT NULL,
+    result TEXT,
+    error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_jobs_status_priority ON jobs(status, priority, created_at);
@@ -0,0 +1,3 @@
+-- Migration: Add English translation to transcripts
+-- Filled when translation is enabled for a meeting; NULL otherwise
+ALTER TABLE transcripts ADD COLUMN translation TEXT;
@@ -0,0 +1,12 @@
+-- Migration: Add custom vocabulary (glossary)
+-- Domain terms (product names, acronyms, people) used to bias transcription:
+--   - term: correct spelling, e.g. "Kubernetes"
+--   - aliases: JSON array of common mis-transcriptions replaced by the term, e.g. ["cooper netties"]
+
+CREATE TABLE IF NOT EXISTS glossary_terms (
+    id TEXT PRIMARY KEY,
+    term TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    aliases TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,3 @@
+-- Migration: Add word-level timings to transcripts
+-- JSON array of {text, start, end, confidence, low_confidence}; NULL when the engine reports none
+ALTER TABLE transcripts ADD COLUMN words TEXT;
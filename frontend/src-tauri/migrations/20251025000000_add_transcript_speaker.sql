@@ -0,0 +1,3 @@
+-- Migration: Add speaker labels to transcripts
+-- Filled by diarization ("Speaker 1", ...); NULL until the meeting has been diarized
+ALTER TABLE transcripts ADD COLUMN speaker TEXT;
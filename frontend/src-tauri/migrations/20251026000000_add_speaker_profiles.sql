@@ -0,0 +1,26 @@
+-- Migration: Add speaker profiles for cross-meeting voice recognition
+--   - speaker_profiles: a named voice; embedding is a JSON array averaged over every
+--     meeting speaker assigned to the profile (sample_count of them)
+--   - meeting_speakers: the speakers diarization found in a meeting, with their voice
+--     embedding and the profile they were recognized as or assigned to
+
+CREATE TABLE IF NOT EXISTS speaker_profiles (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    embedding TEXT NOT NULL,
+    sample_count INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS meeting_speakers (
+    meeting_id TEXT NOT NULL,
+    label TEXT NOT NULL,
+    embedding TEXT NOT NULL,
+    profile_id TEXT,
+    PRIMARY KEY (meeting_id, label),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE,
+    FOREIGN KEY (profile_id) REFERENCES speaker_profiles(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_meeting_speakers_profile ON meeting_speakers(profile_id);
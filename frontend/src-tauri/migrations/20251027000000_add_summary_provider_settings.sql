@@ -0,0 +1,10 @@
+-- Migration: Remember the model (and server address) chosen for each summary provider,
+-- so switching providers doesn't lose the model picked for the previous one.
+-- API keys are not stored here; they live in the OS credential store.
+
+CREATE TABLE IF NOT EXISTS summary_provider_settings (
+    provider TEXT PRIMARY KEY,
+    model TEXT NOT NULL,
+    endpoint TEXT,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,22 @@
+-- Migration: Add action items extracted from meetings
+--   - source_segment: id of the transcript segment the item was found in (NULL for items
+--     added by hand)
+--   - due_date: YYYY-MM-DD, resolved against the meeting date when said as "by Friday"
+--   - source: 'extracted' items are replaced when extraction runs again; 'manual' items
+--     and items the user edited are kept
+
+CREATE TABLE IF NOT EXISTS action_items (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    text TEXT NOT NULL,
+    assignee TEXT,
+    due_date TEXT,
+    source_segment TEXT,
+    completed INTEGER NOT NULL DEFAULT 0,
+    source TEXT NOT NULL DEFAULT 'extracted',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_action_items_meeting ON action_items(meeting_id);
@@ -0,0 +1,15 @@
+-- Migration: Add structured minutes extracted from meetings
+--   - kind: 'decision', 'open_question' or 'risk'
+--   - source_segments: JSON array of the transcript segment ids the point was drawn from
+
+CREATE TABLE IF NOT EXISTS key_points (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    text TEXT NOT NULL,
+    source_segments TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_key_points_meeting ON key_points(meeting_id, kind);
@@ -0,0 +1,14 @@
+-- Migration: Add user-defined summary templates
+--   - summary_templates: templates created or edited in the app; definition is the template
+--     JSON (name, description, prompt, sections). A row with the id of a built-in template
+--     overrides it until deleted.
+--   - meetings.template_id: template selected for the meeting's summary
+
+CREATE TABLE IF NOT EXISTS summary_templates (
+    id TEXT PRIMARY KEY,
+    definition TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+ALTER TABLE meetings ADD COLUMN template_id TEXT;
@@ -0,0 +1,17 @@
+-- Migration: Keep every generated summary of a meeting
+--   - summary_versions: one row per completed summary generation, with the provider, model
+--     and template used, so outputs can be compared after regenerating. summary_processes
+--     keeps holding the current summary.
+
+CREATE TABLE IF NOT EXISTS summary_versions (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    template_id TEXT NOT NULL,
+    result TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_summary_versions_meeting ON summary_versions(meeting_id, created_at);
@@ -0,0 +1,17 @@
+-- Migration: Add transcript segment embeddings for meeting Q&A
+--   - vector: little-endian f32 values of the segment's embedding
+--   - text_hash: SHA-256 of the embedded text, so edited segments are embedded again
+--   - model: embedder that produced the vector; vectors of different models are never compared
+
+CREATE TABLE IF NOT EXISTS segment_embeddings (
+    transcript_id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    model TEXT NOT NULL,
+    text_hash TEXT NOT NULL,
+    vector BLOB NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (transcript_id) REFERENCES transcripts(id) ON DELETE CASCADE,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_segment_embeddings_meeting ON segment_embeddings(meeting_id, model);
@@ -0,0 +1,58 @@
+-- Migration: Full-text search over transcripts and summaries
+--   - transcripts_fts: external-content FTS5 index of the transcript text, kept in sync by
+--     triggers; its rowid is the transcripts rowid
+--   - summaries_fts: the markdown of each meeting's current summary
+
+CREATE VIRTUAL TABLE IF NOT EXISTS transcripts_fts USING fts5(
+    transcript,
+    content = 'transcripts',
+    content_rowid = 'rowid',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_insert AFTER INSERT ON transcripts BEGIN
+    INSERT INTO transcripts_fts (rowid, transcript) VALUES (new.rowid, new.transcript);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_delete AFTER DELETE ON transcripts BEGIN
+    INSERT INTO transcripts_fts (transcripts_fts, rowid, transcript) VALUES ('delete', old.rowid, old.transcript);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_update AFTER UPDATE OF transcript ON transcripts BEGIN
+    INSERT INTO transcripts_fts (transcripts_fts, rowid, transcript) VALUES ('delete', old.rowid, old.transcript);
+    INSERT INTO transcripts_fts (rowid, transcript) VALUES (new.rowid, new.transcript);
+END;
+
+INSERT INTO transcripts_fts (transcripts_fts) VALUES ('rebuild');
+
+CREATE VIRTUAL TABLE IF NOT EXISTS summaries_fts USING fts5(
+    summary,
+    meeting_id UNINDEXED,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_insert AFTER INSERT ON summary_processes
+WHEN new.result IS NOT NULL BEGIN
+    INSERT INTO summaries_fts (summary, meeting_id) VALUES (
+        CASE WHEN json_valid(new.result) THEN coalesce(json_extract(new.result, '$.markdown'), new.result) ELSE new.result END,
+        new.meeting_id
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_delete AFTER DELETE ON summary_processes BEGIN
+    DELETE FROM summaries_fts WHERE meeting_id = old.meeting_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_update AFTER UPDATE OF result ON summary_processes BEGIN
+    DELETE FROM summaries_fts WHERE meeting_id = old.meeting_id;
+    INSERT INTO summaries_fts (summary, meeting_id)
+    SELECT CASE WHEN json_valid(new.result) THEN coalesce(json_extract(new.result, '$.markdown'), new.result) ELSE new.result END,
+           new.meeting_id
+    WHERE new.result IS NOT NULL;
+END;
+
+INSERT INTO summaries_fts (summary, meeting_id)
+SELECT CASE WHEN json_valid(result) THEN coalesce(json_extract(result, '$.markdown'), result) ELSE result END,
+       meeting_id
+FROM summary_processes
+WHERE result IS NOT NULL;
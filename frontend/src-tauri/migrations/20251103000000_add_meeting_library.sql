@@ -0,0 +1,36 @@
+-- Migration: Meeting library metadata
+--   - meetings.archived_at: set when the meeting is archived; archived meetings are hidden
+--     from the library unless asked for
+--   - meetings.notes: free-form notes attached to the meeting
+--   - meeting_tags: free-form tags, unique per meeting regardless of case
+--   - meeting_participants: who attended, in the order they were entered
+--   - meeting_metadata: arbitrary key-value pairs set by the frontend
+
+ALTER TABLE meetings ADD COLUMN archived_at TEXT;
+ALTER TABLE meetings ADD COLUMN notes TEXT;
+
+CREATE TABLE IF NOT EXISTS meeting_tags (
+    meeting_id TEXT NOT NULL,
+    tag TEXT NOT NULL COLLATE NOCASE,
+    PRIMARY KEY (meeting_id, tag),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_meeting_tags_tag ON meeting_tags(tag);
+
+CREATE TABLE IF NOT EXISTS meeting_participants (
+    meeting_id TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    email TEXT,
+    PRIMARY KEY (meeting_id, position),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS meeting_metadata (
+    meeting_id TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (meeting_id, key),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
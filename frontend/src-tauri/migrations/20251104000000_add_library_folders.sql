@@ -0,0 +1,26 @@
+-- Migration: Folders and saved filters for the meeting library
+--   - folders: hierarchical; parent_id is NULL for top-level folders
+--   - meetings.folder_id: the folder a meeting is filed in, NULL when unfiled
+--   - saved_filters: named library queries such as "tag:standup last 30 days"; the query
+--     text is stored, so relative dates are evaluated each time the filter runs
+
+CREATE TABLE IF NOT EXISTS folders (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    parent_id TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (parent_id) REFERENCES folders(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_folders_parent ON folders(parent_id);
+
+ALTER TABLE meetings ADD COLUMN folder_id TEXT REFERENCES folders(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_meetings_folder ON meetings(folder_id);
+
+CREATE TABLE IF NOT EXISTS saved_filters (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    query TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
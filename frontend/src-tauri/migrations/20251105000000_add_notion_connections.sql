@@ -0,0 +1,16 @@
+-- Migration: Notion workspaces meetings can be exported to
+--   - id: the workspace id for OAuth connections, the integration's bot id for internal
+--     integration tokens; the token itself is kept in the OS credential store
+--   - database_id: the database new meeting pages are created in, chosen per workspace
+--   - title_property / date_property: the database's title column and the date column
+--     the meeting date is written to, looked up when the database is chosen
+
+CREATE TABLE IF NOT EXISTS notion_connections (
+    id TEXT PRIMARY KEY,
+    workspace_name TEXT NOT NULL,
+    database_id TEXT,
+    database_title TEXT,
+    title_property TEXT,
+    date_property TEXT,
+    created_at TEXT NOT NULL
+);
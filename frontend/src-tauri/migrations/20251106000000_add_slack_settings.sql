@@ -0,0 +1,15 @@
+-- Migration: Posting meeting summaries to Slack
+--   - slack_settings: a single row; mode 'webhook' posts to an incoming webhook, 'bot' posts
+--     with a bot token to `channel` (a channel id, or a user id for a direct message). The
+--     webhook URL or bot token is kept in the OS credential store
+--   - meetings.slack_opt_out: 1 for meetings that must not be posted automatically
+
+CREATE TABLE IF NOT EXISTS slack_settings (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    enabled INTEGER NOT NULL DEFAULT 0,
+    mode TEXT NOT NULL DEFAULT 'webhook',
+    channel TEXT,
+    updated_at TEXT NOT NULL
+);
+
+ALTER TABLE meetings ADD COLUMN slack_opt_out INTEGER NOT NULL DEFAULT 0;
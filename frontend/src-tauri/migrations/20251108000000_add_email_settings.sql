@@ -0,0 +1,14 @@
+-- Migration: SMTP server used to email meeting minutes
+--   - single row (id = 1); the password is kept in the OS credential store
+--   - security: 'starttls', 'tls' (implicit TLS, usually port 465) or 'none'
+
+CREATE TABLE IF NOT EXISTS email_settings (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    host TEXT NOT NULL,
+    port INTEGER NOT NULL DEFAULT 587,
+    security TEXT NOT NULL DEFAULT 'starttls',
+    username TEXT,
+    from_address TEXT NOT NULL,
+    from_name TEXT,
+    updated_at TEXT NOT NULL
+);
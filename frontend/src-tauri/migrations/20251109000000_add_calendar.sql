@@ -0,0 +1,36 @@
+-- Migration: Calendar accounts, their synced events, and the event a meeting was recorded for
+--   - calendar_accounts.provider: 'google', 'outlook' or 'caldav'; url and username are
+--     only set for CalDAV. OAuth tokens and CalDAV passwords are kept in the OS credential store
+--   - calendar_events: events in the sync window (yesterday to two weeks ahead), replaced on
+--     every sync; attendees is a JSON array of { name, email }
+--   - meetings.calendar_event_id: the linked event; kept when the event leaves the sync
+--     window, the meeting's title, participants and agenda metadata were copied from it
+
+CREATE TABLE IF NOT EXISTS calendar_accounts (
+    id TEXT PRIMARY KEY,
+    provider TEXT NOT NULL,
+    name TEXT NOT NULL,
+    url TEXT,
+    username TEXT,
+    created_at TEXT NOT NULL,
+    last_synced_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS calendar_events (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL,
+    external_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    starts_at TEXT NOT NULL,
+    ends_at TEXT NOT NULL,
+    description TEXT,
+    location TEXT,
+    attendees TEXT NOT NULL DEFAULT '[]',
+    updated_at TEXT NOT NULL,
+    UNIQUE (account_id, external_id),
+    FOREIGN KEY (account_id) REFERENCES calendar_accounts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_calendar_events_starts_at ON calendar_events(starts_at);
+
+ALTER TABLE meetings ADD COLUMN calendar_event_id TEXT;
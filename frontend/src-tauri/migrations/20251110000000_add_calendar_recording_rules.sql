@@ -0,0 +1,18 @@
+-- Migration: Calendar events and series recorded automatically
+--   - calendar_events.series_id: shared by the instances of a recurring event
+--   - calendar_recording_rules: "always record" marks; target is 'event' (target_id is the
+--     event's external_id) or 'series' (target_id is the series_id), so a series rule also
+--     covers instances synced later. title is the event title when the rule was made
+
+ALTER TABLE calendar_events ADD COLUMN series_id TEXT;
+
+CREATE TABLE IF NOT EXISTS calendar_recording_rules (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL,
+    target TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (account_id, target, target_id),
+    FOREIGN KEY (account_id) REFERENCES calendar_accounts(id) ON DELETE CASCADE
+);
@@ -0,0 +1,14 @@
+-- Migration: Highlight markers of a meeting
+--   - meeting_markers: moments the user flagged as important, during the recording (button
+--     or hotkey) or afterwards; audio_time is seconds into the recording, label an optional note
+
+CREATE TABLE IF NOT EXISTS meeting_markers (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    audio_time REAL NOT NULL,
+    label TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_meeting_markers_meeting ON meeting_markers(meeting_id, audio_time);
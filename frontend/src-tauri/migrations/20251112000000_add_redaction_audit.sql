@@ -0,0 +1,16 @@
+-- Migration: Redaction audit
+--   - redaction_audit: how much of each kind of personal data or profanity the redaction pass
+--     masked in a meeting's text before it went to a summary model ('summary') or out in an
+--     export ('export'); only counts are kept, never the masked text itself
+
+CREATE TABLE IF NOT EXISTS redaction_audit (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    target TEXT NOT NULL,
+    category TEXT NOT NULL,
+    count INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_redaction_audit_meeting ON redaction_audit(meeting_id, created_at);
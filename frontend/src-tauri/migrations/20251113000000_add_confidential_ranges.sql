@@ -0,0 +1,16 @@
+-- Migration: Confidential parts of a meeting
+--   - confidential_ranges: time ranges the user marked confidential, during the recording or
+--     afterwards; audio_start/audio_end are seconds into the recording. Their audio is silence
+--     and no transcript is kept for them, so each one is shown as a gap.
+
+CREATE TABLE IF NOT EXISTS confidential_ranges (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    audio_start REAL NOT NULL,
+    audio_end REAL NOT NULL,
+    label TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_confidential_ranges_meeting ON confidential_ranges(meeting_id, audio_start);
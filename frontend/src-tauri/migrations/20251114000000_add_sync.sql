@@ -0,0 +1,26 @@
+-- Migration: Sync between the user's devices
+--   - sync_state: for each synced meeting, the version last agreed with the sync location,
+--     used as the merge base, and the tag of the location's copy when it was read. Kept when
+--     the meeting is deleted, so the deletion reaches the other devices.
+--   - sync_conflicts: transcript segments edited differently on two devices; the edit the
+--     merge dropped is kept until the user picks a version
+
+CREATE TABLE IF NOT EXISTS sync_state (
+    meeting_id TEXT PRIMARY KEY,
+    base TEXT NOT NULL,  -- JSON, as synced
+    remote_tag TEXT,
+    synced_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS sync_conflicts (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    segment_id TEXT NOT NULL,
+    kept_text TEXT NOT NULL,
+    other_text TEXT NOT NULL,
+    other_device TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_sync_conflicts_meeting ON sync_conflicts(meeting_id);
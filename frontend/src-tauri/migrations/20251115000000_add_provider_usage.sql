@@ -0,0 +1,23 @@
+-- Migration: What was sent to hosted speech-to-text and summary providers
+--   - provider_usage: one row per LLM call (summary, extraction, Q&A) with its token counts,
+--     and per meeting for cloud transcription with the audio seconds sent. Local providers
+--     aren't recorded. Rows have no foreign key, so deleting a meeting doesn't erase what
+--     it cost.
+
+CREATE TABLE IF NOT EXISTS provider_usage (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT,
+    kind TEXT NOT NULL,     -- 'llm' or 'stt'
+    purpose TEXT NOT NULL,  -- 'summary', 'action_items', 'key_points', 'qa' or 'transcription'
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    prompt_tokens INTEGER NOT NULL DEFAULT 0,
+    completion_tokens INTEGER NOT NULL DEFAULT 0,
+    audio_seconds REAL NOT NULL DEFAULT 0,
+    requests INTEGER NOT NULL DEFAULT 1,
+    estimated BOOLEAN NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_provider_usage_meeting ON provider_usage(meeting_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_provider_usage_created ON provider_usage(created_at);
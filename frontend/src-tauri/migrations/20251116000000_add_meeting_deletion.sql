@@ -0,0 +1,26 @@
+-- Migration: Deleting a meeting everywhere its data lives
+--   - export_artifacts: files exported from a meeting, with the SHA-256 of what was written,
+--     so deleting the meeting can delete them too unless they have changed since
+--   - pending_deletions: files and folders of deleted meetings still to be removed. Rows are
+--     added in the transaction deleting the meeting and removed once the files are gone, so
+--     a deletion interrupted by a crash is finished on the next start.
+
+CREATE TABLE IF NOT EXISTS export_artifacts (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    path TEXT NOT NULL,
+    format TEXT NOT NULL,
+    sha256 TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_export_artifacts_meeting ON export_artifacts(meeting_id);
+
+CREATE TABLE IF NOT EXISTS pending_deletions (
+    path TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    sha256 TEXT,  -- Only deleted while the file still has this hash; NULL for meeting folders
+    secure_wipe BOOLEAN NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
@@ -72,8 +72,8 @@ impl AnalyticsClient {
 
     pub async fn identify(&self, user_id: String, properties: Option<HashMap<String, String>>) -> Result<(), String> {
         let client = match &self.client {
-            Some(client) => Arc::clone(client),
-            None => return Ok(()),
+            Some(client) if !crate::network::is_local_only() => Arc::clone(client),
+            _ => return Ok(()),
         };
 
         // Store user ID for future events
@@ -99,8 +99,8 @@ impl AnalyticsClient {
 
     pub async fn track_event(&self, event_name: &str, properties: Option<HashMap<String, String>>) -> Result<(), String> {
         let client = match &self.client {
-            Some(client) => Arc::clone(client),
-            None => return Ok(()),
+            Some(client) if !crate::network::is_local_only() => Arc::clone(client),
+            _ => return Ok(()),
         };
 
         let user_id = match self.user_id.lock().await.clone() {
@@ -399,8 +399,8 @@ impl AnalyticsClient {
 
     pub async fn set_user_properties(&self, properties: HashMap<String, String>) -> Result<(), String> {
         let client = match &self.client {
-            Some(client) => Arc::clone(client),
-            None => return Ok(()),
+            Some(client) if !crate::network::is_local_only() => Arc::clone(client),
+            _ => return Ok(()),
         };
 
         let user_id = match self.user_id.lock().await.clone() {
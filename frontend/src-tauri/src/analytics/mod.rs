@@ -1,5 +1,6 @@
 pub mod analytics;
 pub mod commands;
+pub mod talk_time;
 
 pub use analytics::*;
 // Don't re-export commands to avoid conflicts - lib.rs will import directly
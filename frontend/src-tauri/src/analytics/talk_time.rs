@@ -0,0 +1,235 @@
+use crate::database::models::Transcript;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::state::AppState;
+use serde::Serialize;
+
+/// Name used for segments diarization hasn't attributed to anyone
+pub const UNATTRIBUTED_SPEAKER: &str = "Unknown";
+/// A different speaker starting this long before the current one finished interrupts them
+const INTERRUPTION_OVERLAP_SECONDS: f64 = 0.3;
+/// Pauses up to this long don't end a monologue
+const MONOLOGUE_GAP_SECONDS: f64 = 2.0;
+
+/// A timed stretch of speech (seconds from recording start)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechSpan {
+    pub speaker: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpeakerTalkTime {
+    pub speaker: String,
+    pub talk_seconds: f64,
+    /// Fraction of all speech in the meeting (0.0 - 1.0)
+    pub share: f64,
+    pub turns: usize,
+    /// Times this speaker cut in while someone else was talking
+    pub interruptions: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Monologue {
+    pub speaker: String,
+    pub start: f64,
+    pub end: f64,
+    pub duration_seconds: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TalkTimeStats {
+    pub duration_seconds: f64,
+    /// Ordered by talk time, most talkative first
+    pub speakers: Vec<SpeakerTalkTime>,
+    pub interruptions: usize,
+    pub longest_monologue: Option<Monologue>,
+    /// Fraction of the meeting where nobody spoke (0.0 - 1.0)
+    pub silence_ratio: f64,
+}
+
+/// Spans of the transcript segments that have recording-relative times
+pub fn spans_from_transcripts(transcripts: &[Transcript]) -> Vec<SpeechSpan> {
+    transcripts
+        .iter()
+        .filter_map(|t| {
+            let start = t.audio_start_time?;
+            let end = t.audio_end_time.unwrap_or(start + t.duration.unwrap_or(0.0));
+            (end > start).then(|| SpeechSpan {
+                speaker: t.speaker.clone().unwrap_or_else(|| UNATTRIBUTED_SPEAKER.to_string()),
+                start,
+                end,
+            })
+        })
+        .collect()
+}
+
+/// Total length of the union of (start, end) ranges, so overlapping speech counts once
+fn covered_seconds(mut ranges: Vec<(f64, f64)>) -> f64 {
+    ranges.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut total = 0.0;
+    let mut current: Option<(f64, f64)> = None;
+    for (start, end) in ranges {
+        match current {
+            Some((_, current_end)) if start <= current_end => {
+                current = current.map(|(s, e)| (s, e.max(end)));
+            }
+            _ => {
+                if let Some((s, e)) = current {
+                    total += e - s;
+                }
+                current = Some((start, end));
+            }
+        }
+    }
+    total + current.map_or(0.0, |(s, e)| e - s)
+}
+
+pub fn compute_talk_time(spans: &[SpeechSpan]) -> TalkTimeStats {
+    let mut spans = spans.to_vec();
+    spans.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    // Times are relative to the start of the recording
+    let duration = spans.iter().map(|s| s.end).fold(0.0, f64::max);
+    let speech = covered_seconds(spans.iter().map(|s| (s.start, s.end)).collect());
+
+    let mut speakers: Vec<SpeakerTalkTime> = Vec::new();
+    for span in &spans {
+        if !speakers.iter().any(|s| s.speaker == span.speaker) {
+            let ranges = spans.iter().filter(|s| s.speaker == span.speaker).map(|s| (s.start, s.end)).collect();
+            let talk_seconds = covered_seconds(ranges);
+            speakers.push(SpeakerTalkTime {
+                speaker: span.speaker.clone(),
+                talk_seconds,
+                share: 0.0,
+                turns: 0,
+                interruptions: 0,
+            });
+        }
+    }
+    let total_talk: f64 = speakers.iter().map(|s| s.talk_seconds).sum();
+    for speaker in &mut speakers {
+        speaker.share = if total_talk > 0.0 { speaker.talk_seconds / total_talk } else { 0.0 };
+    }
+
+    // Turns, interruptions and monologues follow the speaker changes in time order
+    let mut interruptions = 0;
+    let mut longest_monologue: Option<Monologue> = None;
+    let mut current: Option<Monologue> = None;
+    let mut latest_end: Option<(String, f64)> = None;
+    for span in &spans {
+        let continues = current
+            .as_ref()
+            .is_some_and(|m| m.speaker == span.speaker && span.start - m.end <= MONOLOGUE_GAP_SECONDS);
+        if !continues {
+            if let Some(speaker) = speakers.iter_mut().find(|s| s.speaker == span.speaker) {
+                speaker.turns += 1;
+                let interrupting = latest_end.as_ref().is_some_and(|(other, end)| {
+                    other != &span.speaker && *end - span.start >= INTERRUPTION_OVERLAP_SECONDS
+                });
+                if interrupting && span.speaker != UNATTRIBUTED_SPEAKER {
+                    speaker.interruptions += 1;
+                    interruptions += 1;
+                }
+            }
+            if let Some(finished) = current.take() {
+                longest_monologue = longer(longest_monologue, finished);
+            }
+            current = Some(Monologue {
+                speaker: span.speaker.clone(),
+                start: span.start,
+                end: span.end,
+                duration_seconds: 0.0,
+            });
+        } else if let Some(monologue) = current.as_mut() {
+            monologue.end = monologue.end.max(span.end);
+        }
+        if latest_end.as_ref().map_or(true, |(_, end)| span.end >= *end) {
+            latest_end = Some((span.speaker.clone(), span.end));
+        }
+    }
+    if let Some(finished) = current {
+        longest_monologue = longer(longest_monologue, finished);
+    }
+
+    speakers.sort_by(|a, b| b.talk_seconds.total_cmp(&a.talk_seconds));
+    TalkTimeStats {
+        duration_seconds: duration,
+        speakers,
+        interruptions,
+        longest_monologue,
+        silence_ratio: if duration > 0.0 { ((duration - speech) / duration).clamp(0.0, 1.0) } else { 0.0 },
+    }
+}
+
+fn longer(best: Option<Monologue>, mut candidate: Monologue) -> Option<Monologue> {
+    candidate.duration_seconds = candidate.end - candidate.start;
+    if candidate.speaker == UNATTRIBUTED_SPEAKER {
+        return best;
+    }
+    match best {
+        Some(best) if best.duration_seconds >= candidate.duration_seconds => Some(best),
+        _ => Some(candidate),
+    }
+}
+
+/// Participation statistics of a meeting, for the talk-time chart
+#[tauri::command]
+pub async fn get_meeting_talk_time(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<TalkTimeStats, String> {
+    let transcripts = TranscriptsRepository::list_for_meeting(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcript: {}", e))?;
+    Ok(compute_talk_time(&spans_from_transcripts(&transcripts)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(speaker: &str, start: f64, end: f64) -> SpeechSpan {
+        SpeechSpan { speaker: speaker.to_string(), start, end }
+    }
+
+    #[test]
+    fn test_talk_time_shares_and_silence() {
+        let stats = compute_talk_time(&[
+            span("Alice", 0.0, 4.0),
+            span("Alice", 5.0, 10.0),
+            span("Bob", 12.0, 16.0),
+            span("Bob", 15.0, 17.0),
+            span("Alice", 18.0, 20.0),
+        ]);
+
+        assert_eq!(stats.duration_seconds, 20.0);
+        assert_eq!(stats.speakers[0].speaker, "Alice");
+        assert_eq!(stats.speakers[0].talk_seconds, 11.0);
+        assert_eq!(stats.speakers[0].turns, 2);
+        assert_eq!(stats.speakers[1].talk_seconds, 5.0);
+        assert!((stats.speakers[0].share - 11.0 / 16.0).abs() < 1e-9);
+        // Silent: 4-5, 10-12, 17-18
+        assert!((stats.silence_ratio - 4.0 / 20.0).abs() < 1e-9);
+
+        let monologue = stats.longest_monologue.unwrap();
+        assert_eq!((monologue.speaker.as_str(), monologue.start, monologue.end), ("Alice", 0.0, 10.0));
+        assert_eq!(stats.interruptions, 0);
+    }
+
+    #[test]
+    fn test_cutting_in_counts_as_interruption() {
+        let stats = compute_talk_time(&[
+            span("Alice", 0.0, 6.0),
+            span("Bob", 4.0, 8.0),
+            // Starts right as Bob finishes: not an interruption
+            span("Alice", 8.1, 9.0),
+            span(UNATTRIBUTED_SPEAKER, 8.5, 10.0),
+        ]);
+
+        assert_eq!(stats.interruptions, 1);
+        let bob = stats.speakers.iter().find(|s| s.speaker == "Bob").unwrap();
+        assert_eq!(bob.interruptions, 1);
+        assert!(compute_talk_time(&[]).longest_monologue.is_none());
+    }
+}
@@ -5,6 +5,7 @@ use tauri::{AppHandle, Runtime};
 use tauri_plugin_store::StoreExt;
 
 use crate::{
+    audio::transcription::{cloud_providers::is_cloud_provider, credentials, TranscriptWord},
     database::{
         models::MeetingModel,
         repositories::{
@@ -12,7 +13,9 @@ use crate::{
             transcript::TranscriptsRepository,
         },
     },
+    library::deletion::{self, DeleteOptions},
     state::AppState,
+    summary::credentials as summary_credentials,
 };
 
 // Hardcoded server URL
@@ -136,6 +139,14 @@ pub struct MeetingTranscript {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+    // Word timings for click-to-seek and low-confidence highlighting
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<TranscriptWord>,
+    // Speaker label from diarization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -168,6 +179,15 @@ pub struct TranscriptSegment {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    // English translation, when translation was enabled for the meeting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+    // Word timings from recording start, when the engine reports them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<TranscriptWord>,
+    // Speaker label, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -224,7 +244,7 @@ async fn make_api_request<R: Runtime, T: for<'de> Deserialize<'de>>(
     additional_headers: Option<HashMap<String, String>>,
     auth_token: Option<String>, // Pass auth token from frontend
 ) -> Result<T, String> {
-    let client = reqwest::Client::new();
+    let client = crate::network::client();
     let server_url = get_server_address(app).await?;
 
     let url = format!("{}{}", server_url, endpoint);
@@ -461,7 +481,7 @@ pub async fn api_get_model_config<R: Runtime>(
                 &config.whisper_model,
                 &config.ollama_endpoint
             );
-            match SettingsRepository::get_api_key(pool, &config.provider).await {
+            match summary_credentials::resolve_api_key(pool, &config.provider).await {
                 Ok(api_key) => {
                     log_info!("Successfully retrieved model config and API key.");
                     Ok(Some(ModelConfig {
@@ -526,12 +546,26 @@ pub async fn api_save_model_config<R: Runtime>(
         return Err(e.to_string());
     }
 
+    // Remember the model for this provider so switching back restores it
+    if let Err(e) = SettingsRepository::save_summary_provider_setting(pool, &provider, &model, None).await {
+        log_warn!("⚠️ Failed to remember model for provider {}: {}", &provider, e);
+    }
+
     if let Some(key) = api_key {
         if !key.is_empty() {
             log_info!("🔑 API key provided, saving...");
-            if let Err(e) = SettingsRepository::save_api_key(pool, &provider, &key).await {
-                log_error!("❌ Failed to save API key: {}", e);
-                return Err(e.to_string());
+            let stored = match summary_credentials::store_api_key(&provider, &key) {
+                Ok(()) => true,
+                Err(e) => {
+                    log_warn!("{}; keeping the key in the settings database", e);
+                    false
+                }
+            };
+            if !stored {
+                if let Err(e) = SettingsRepository::save_api_key(pool, &provider, &key).await {
+                    log_error!("❌ Failed to save API key: {}", e);
+                    return Err(e.to_string());
+                }
             }
         }
     }
@@ -553,7 +587,7 @@ pub async fn api_get_api_key<R: Runtime>(
         "api_get_api_key called (native) for provider '{}'",
         &provider
     );
-    match SettingsRepository::get_api_key(&state.db_manager.pool(), &provider).await {
+    match summary_credentials::resolve_api_key(&state.db_manager.pool(), &provider).await {
         Ok(key) => {
             log_info!(
                 "Successfully retrieved API key for provider '{}'.",
@@ -568,6 +602,22 @@ pub async fn api_get_api_key<R: Runtime>(
     }
 }
 
+/// Transcript API key from the system credential store, falling back to keys saved in
+/// the settings database by older versions
+async fn load_transcript_api_key(
+    pool: &sqlx::SqlitePool,
+    provider: &str,
+) -> std::result::Result<Option<String>, sqlx::Error> {
+    if is_cloud_provider(provider) {
+        match credentials::load_api_key(provider) {
+            Ok(Some(key)) => return Ok(Some(key)),
+            Ok(None) => {}
+            Err(e) => log_warn!("{}; trying the settings database", e),
+        }
+    }
+    SettingsRepository::get_transcript_api_key(pool, provider).await
+}
+
 #[tauri::command]
 pub async fn api_get_transcript_config<R: Runtime>(
     _app: AppHandle<R>,
@@ -584,7 +634,7 @@ pub async fn api_get_transcript_config<R: Runtime>(
                 &config.provider,
                 &config.model
             );
-            match SettingsRepository::get_transcript_api_key(pool, &config.provider).await {
+            match load_transcript_api_key(pool, &config.provider).await {
                 Ok(api_key) => {
                     log_info!("Successfully retrieved transcript config and API key.");
                     Ok(Some(TranscriptConfig {
@@ -641,6 +691,15 @@ pub async fn api_save_transcript_config<R: Runtime>(
     if let Some(key) = api_key {
         if !key.is_empty() {
             log_info!("API key provided, saving for transcript provider...");
+            if is_cloud_provider(&provider) {
+                match credentials::store_api_key(&provider, &key) {
+                    Ok(()) => {
+                        log_info!("Successfully saved transcript configuration.");
+                        return Ok(serde_json::json!({ "status": "success", "message": "Transcript configuration saved successfully" }));
+                    }
+                    Err(e) => log_warn!("{}; keeping the key in the settings database", e),
+                }
+            }
             if let Err(e) = SettingsRepository::save_transcript_api_key(pool, &provider, &key).await
             {
                 log_error!("Failed to save transcript API key: {}", e);
@@ -666,7 +725,7 @@ pub async fn api_get_transcript_api_key<R: Runtime>(
         "api_get_transcript_api_key called (native) for provider '{}'",
         &provider
     );
-    match SettingsRepository::get_transcript_api_key(&state.db_manager.pool(), &provider).await {
+    match load_transcript_api_key(&state.db_manager.pool(), &provider).await {
         Ok(key) => {
             log_info!(
                 "Successfully retrieved transcript API key for provider '{}'.",
@@ -696,6 +755,9 @@ pub async fn api_delete_api_key<R: Runtime>(
         "log_api_delete_api_key called (native) for provider '{}'",
         &provider
     );
+    if let Err(e) = summary_credentials::delete_api_key(&provider) {
+        log_warn!("{}", e);
+    }
     match SettingsRepository::delete_api_key(&state.db_manager.pool(), &provider).await {
         Ok(_) => {
             log_info!("Successfully deleted API key for provider '{}'.", &provider);
@@ -712,12 +774,15 @@ pub async fn api_delete_api_key<R: Runtime>(
     }
 }
 
+/// Deletes a meeting with its recording folder and the files exported from it; see
+/// `library::deletion` for what `options.secure_wipe` adds
 #[tauri::command]
 pub async fn api_delete_meeting<R: Runtime>(
     _app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
     meeting_id: String,
     auth_token: Option<String>,
+    options: Option<DeleteOptions>,
 ) -> Result<serde_json::Value, String> {
     log_info!(
         "api_delete_meeting called for meeting_id(native): {}, auth_token: {}",
@@ -727,15 +792,16 @@ pub async fn api_delete_meeting<R: Runtime>(
 
     let pool = state.db_manager.pool();
 
-    match MeetingsRepository::delete_meeting(pool, &meeting_id).await {
-        Ok(true) => {
+    match deletion::delete_meeting(pool, &meeting_id, options.unwrap_or_default()).await {
+        Ok(Some(report)) => {
             log_info!("Successfully deleted meeting {}", meeting_id);
             Ok(serde_json::json!({
                 "status": "success",
-                "message": "Meeting deleted successfully"
+                "message": "Meeting deleted successfully",
+                "report": report
             }))
         }
-        Ok(false) => {
+        Ok(None) => {
             log_warn!("Meeting not found or already deleted: {}", meeting_id);
             Err(format!(
                 "Meeting not found or could not be deleted: {}",
@@ -744,7 +810,7 @@ pub async fn api_delete_meeting<R: Runtime>(
         }
         Err(e) => {
             log_error!("Error deleting meeting {}: {}", meeting_id, e);
-            Err(format!("Failed to delete meeting: {}", e))
+            Err(e)
         }
     }
 }
@@ -780,6 +846,31 @@ pub async fn api_get_meeting<R: Runtime>(
     }
 }
 
+/// Exports a meeting's transcript with its English translation side by side (Markdown)
+#[tauri::command]
+pub async fn api_export_translated_transcript<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<String, String> {
+    log_info!("api_export_translated_transcript called for meeting_id: {}", meeting_id);
+    let pool = state.db_manager.pool();
+
+    match MeetingsRepository::get_meeting(pool, &meeting_id).await {
+        Ok(Some(meeting)) => {
+            if meeting.transcripts.iter().all(|t| t.translation.is_none()) {
+                return Err("This meeting has no translation".to_string());
+            }
+            Ok(crate::audio::transcription::format_side_by_side(&meeting.transcripts))
+        }
+        Ok(None) => Err(format!("Meeting not found: {}", meeting_id)),
+        Err(e) => {
+            log_error!("Error retrieving meeting {}: {}", meeting_id, e);
+            Err(format!("Failed to retrieve meeting: {}", e))
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn api_save_meeting_title<R: Runtime>(
     _app: AppHandle<R>,
@@ -812,7 +903,7 @@ pub async fn api_save_meeting_title<R: Runtime>(
 
 #[tauri::command]
 pub async fn api_save_transcript<R: Runtime>(
-    _app: AppHandle<R>,
+    app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
     meeting_title: String,
     transcripts: Vec<serde_json::Value>,
@@ -855,6 +946,7 @@ pub async fn api_save_transcript<R: Runtime>(
     }
 
     let pool = state.db_manager.pool();
+    let marker_folder = folder_path.clone();
 
     // Now, call the repository with the correctly typed data.
     match TranscriptsRepository::save_transcript(
@@ -870,6 +962,32 @@ pub async fn api_save_transcript<R: Runtime>(
                 "Successfully saved transcript and created meeting with id: {}",
                 meeting_id
             );
+            crate::usage::meter::flush(pool, Some(meeting_id.as_str())).await;
+            if let Some(folder) = marker_folder {
+                match crate::library::markers::import_recorded_markers(pool, &meeting_id, std::path::Path::new(&folder)).await {
+                    Ok(0) => {}
+                    Ok(count) => log_info!("Saved {} highlight marker(s) for meeting {}", count, meeting_id),
+                    Err(e) => log_warn!("Failed to save highlight markers for meeting {}: {}", meeting_id, e),
+                }
+                match crate::library::confidential::import_recorded_ranges(pool, &meeting_id, std::path::Path::new(&folder)).await {
+                    Ok(0) => {}
+                    Ok(count) => log_info!("Saved {} confidential part(s) for meeting {}", count, meeting_id),
+                    Err(e) => log_warn!("Failed to save confidential parts for meeting {}: {}", meeting_id, e),
+                }
+            }
+            if let Ok(models_dir) = crate::diarization::commands::models_dir(&app) {
+                crate::qa::index::queue_indexing(&app, pool, &models_dir, Some(meeting_id.clone())).await;
+            }
+            crate::plugins::hooks::transcript_ready(&app, pool, &meeting_id);
+            crate::integrations::webhooks::dispatch(
+                &app,
+                crate::integrations::webhooks::WebhookEvent::TranscriptReady,
+                serde_json::json!({
+                    "meeting_id": meeting_id,
+                    "title": meeting_title,
+                    "segments": transcripts_to_save.len(),
+                }),
+            );
             Ok(serde_json::json!({
                 "status": "success",
                 "message": "Transcript saved successfully",
@@ -966,7 +1084,7 @@ pub async fn test_backend_connection<R: Runtime>(
 ) -> Result<String, String> {
     log_debug!("Testing backend connection...");
 
-    let client = reqwest::Client::new();
+    let client = crate::network::client();
     let server_url = get_server_address(&app).await?;
 
     log_debug!("Testing connection to: {}", server_url);
@@ -1008,7 +1126,7 @@ pub async fn debug_backend_connection<R: Runtime>(app: AppHandle<R>) -> Result<S
     };
 
     // Test 2: Make a simple HTTP request to the backend
-    let client = reqwest::Client::new();
+    let client = crate::network::client();
     let test_url = format!("{}/docs", server_url); // Try the docs endpoint which should be public
 
     log_debug!("Testing connection to: {}", test_url);
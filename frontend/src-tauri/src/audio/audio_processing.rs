@@ -3,10 +3,11 @@ use chrono::Utc;
 use log::{debug, info, warn};
 use realfft::num_complex::{Complex32, ComplexFloat};
 use realfft::RealFftPlanner;
-use rubato::{
-    Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction,
-};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
 use nnnoiseless::DenoiseState;
 
 use super::encode::encode_single_audio; // Correct path to encode module
@@ -215,6 +216,139 @@ impl LoudnessNormalizer {
     }
 }
 
+/// Automatic gain control settings (microphone only)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AgcSettings {
+    /// When disabled the EBU R128 normalizer is used instead
+    pub enabled: bool,
+    /// Speech level the AGC steers toward
+    pub target_dbfs: f32,
+    /// Lowest gain applied, i.e. how much loud input may be turned down
+    pub min_gain_db: f32,
+    /// Highest gain applied, i.e. how much a quiet mic may be boosted
+    pub max_gain_db: f32,
+}
+
+impl Default for AgcSettings {
+    fn default() -> Self {
+        Self { enabled: true, target_dbfs: -20.0, min_gain_db: -6.0, max_gain_db: 24.0 }
+    }
+}
+
+impl AgcSettings {
+    /// Keep values in a usable range and min <= max
+    pub fn normalized(self) -> Self {
+        let min_gain_db = self.min_gain_db.clamp(-30.0, 0.0);
+        let max_gain_db = self.max_gain_db.clamp(0.0, 40.0);
+        Self {
+            enabled: self.enabled,
+            target_dbfs: self.target_dbfs.clamp(-40.0, -6.0),
+            min_gain_db,
+            max_gain_db,
+        }
+    }
+}
+
+static AGC_SETTINGS: Lazy<RwLock<AgcSettings>> = Lazy::new(|| RwLock::new(AgcSettings::default()));
+
+pub fn get_agc_settings() -> AgcSettings {
+    *AGC_SETTINGS.read().unwrap()
+}
+
+/// Applies to running captures on their next block
+pub fn set_agc_settings(settings: AgcSettings) {
+    let settings = settings.normalized();
+    *AGC_SETTINGS.write().unwrap() = settings;
+    info!("🎚️ AGC settings updated: {:?}", settings);
+}
+
+/// Automatic gain control for quiet (or hot) microphones
+///
+/// Unlike the EBU R128 normalizer, which converges on the loudness of the whole recording,
+/// the AGC follows the short-term speech level so a quiet USB mic is brought up within a
+/// couple of seconds. Gain drops quickly on loud input and rises slowly, is held during
+/// silence (so background noise is not pumped up) and is clamped to the configured limits.
+pub struct AutomaticGainControl {
+    block_size: usize,
+    level_db: Option<f32>,
+    gain_db: f32,
+    limiter: TruePeakLimiter,
+}
+
+impl AutomaticGainControl {
+    /// Blocks quieter than this are treated as silence
+    const NOISE_GATE_DBFS: f32 = -60.0;
+    /// Level tracking speed per 10ms block
+    const LEVEL_SMOOTHING: f32 = 0.2;
+    /// Gain reduction speed per block (fast attack)
+    const ATTACK: f32 = 0.3;
+    /// Maximum gain increase per second (slow release)
+    const RELEASE_DB_PER_SEC: f32 = 6.0;
+    const PEAK_LIMIT: f32 = 0.891_251; // -1 dBFS
+
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            block_size: (sample_rate as usize / 100).max(1),
+            level_db: None,
+            gain_db: 0.0,
+            limiter: TruePeakLimiter::new(sample_rate),
+        }
+    }
+
+    pub fn process(&mut self, samples: &[f32], settings: &AgcSettings) -> Vec<f32> {
+        let mut output = Vec::with_capacity(samples.len());
+        let release_per_block = Self::RELEASE_DB_PER_SEC / 100.0;
+
+        for block in samples.chunks(self.block_size) {
+            let rms = (block.iter().map(|s| s * s).sum::<f32>() / block.len() as f32).sqrt();
+            let block_db = 20.0 * rms.max(1e-10).log10();
+
+            if block_db > Self::NOISE_GATE_DBFS {
+                let level = match self.level_db {
+                    Some(level) => level + Self::LEVEL_SMOOTHING * (block_db - level),
+                    None => block_db,
+                };
+                self.level_db = Some(level);
+
+                let desired = (settings.target_dbfs - level).clamp(settings.min_gain_db, settings.max_gain_db);
+                if desired < self.gain_db {
+                    self.gain_db += Self::ATTACK * (desired - self.gain_db);
+                } else {
+                    self.gain_db = (self.gain_db + release_per_block).min(desired);
+                }
+            }
+            // Limits may have changed while silent
+            self.gain_db = self.gain_db.clamp(settings.min_gain_db, settings.max_gain_db);
+
+            let gain = 10_f32.powf(self.gain_db / 20.0);
+            output.extend(block.iter().map(|&s| self.limiter.process(s * gain, Self::PEAK_LIMIT)));
+        }
+
+        output
+    }
+
+    pub fn current_gain_db(&self) -> f32 {
+        self.gain_db
+    }
+}
+
+/// Runtime switch for microphone noise suppression, defaulting to `RNNOISE_APPLY_ENABLED`
+static NOISE_SUPPRESSION_ENABLED: AtomicBool =
+    AtomicBool::new(super::ffmpeg_mixer::RNNOISE_APPLY_ENABLED);
+
+pub fn is_noise_suppression_enabled() -> bool {
+    NOISE_SUPPRESSION_ENABLED.load(Ordering::SeqCst)
+}
+
+/// Takes effect on the next captured block, including during a recording
+pub fn set_noise_suppression_enabled(enabled: bool) {
+    NOISE_SUPPRESSION_ENABLED.store(enabled, Ordering::SeqCst);
+    info!("🔇 RNNoise noise suppression {}", if enabled { "enabled" } else { "disabled" });
+}
+
+/// RNNoise works on 16-bit sample magnitudes rather than [-1, 1]
+const RNNOISE_SCALE: f32 = 32768.0;
+
 /// RNNoise-based noise suppression processor
 ///
 /// Uses a recurrent neural network to suppress background noise while preserving speech.
@@ -283,7 +417,10 @@ impl NoiseSuppressionProcessor {
         // Process complete frames
         while self.frame_buffer.len() >= self.frame_size {
             // Extract one frame
-            let frame: Vec<f32> = self.frame_buffer.drain(0..self.frame_size).collect();
+            let frame: Vec<f32> = self.frame_buffer
+                .drain(0..self.frame_size)
+                .map(|s| s * RNNOISE_SCALE)
+                .collect();
 
             // RNNoise processes audio: separate input and output buffers
             let mut denoised_frame = vec![0.0f32; self.frame_size];
@@ -293,7 +430,7 @@ impl NoiseSuppressionProcessor {
             // Returns VAD probability (0.0-1.0), higher means more likely to be speech
             let _vad_prob = self.denoiser.process_frame(&mut denoised_frame, &frame);
 
-            output.extend_from_slice(&denoised_frame);
+            output.extend(denoised_frame.iter().map(|s| s / RNNOISE_SCALE));
         }
 
         // Return processed output without forcing length matching
@@ -316,7 +453,7 @@ impl NoiseSuppressionProcessor {
 
         // Pad the remaining samples to a full frame with zeros
         let remaining = self.frame_buffer.len();
-        let mut input_frame = self.frame_buffer.clone();
+        let mut input_frame: Vec<f32> = self.frame_buffer.iter().map(|s| s * RNNOISE_SCALE).collect();
         if input_frame.len() < self.frame_size {
             input_frame.resize(self.frame_size, 0.0);
         }
@@ -327,7 +464,7 @@ impl NoiseSuppressionProcessor {
 
         // Return only the original samples (without padding)
         output.truncate(remaining);
-        output
+        output.iter().map(|s| s / RNNOISE_SCALE).collect()
     }
 }
 
@@ -484,99 +621,10 @@ pub fn audio_to_mono(audio: &[f32], channels: u16) -> Vec<f32> {
     mono_samples
 }
 
-/// High-quality audio resampling with adaptive parameters based on sample rate ratio
-///
-/// This function automatically selects the best resampling parameters based on:
-/// - Sample rate ratio (upsampling vs downsampling)
-/// - Quality requirements (integer ratios get optimized paths)
-/// - Anti-aliasing needs
-///
-/// Supports all common sample rates: 8kHz, 16kHz, 24kHz, 44.1kHz, 48kHz, etc.
+/// High-quality one-shot resampling; see `super::resample` for the parameter selection
+/// and the streaming variant used for live capture
 pub fn resample(input: &[f32], from_sample_rate: u32, to_sample_rate: u32) -> Result<Vec<f32>> {
-    if input.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    // Fast path: No resampling needed
-    if from_sample_rate == to_sample_rate {
-        return Ok(input.to_vec());
-    }
-
-    let ratio = to_sample_rate as f64 / from_sample_rate as f64;
-
-    // Adaptive parameters based on sample rate ratio
-    let (sinc_len, interpolation_type, oversampling) = if ratio >= 2.0 {
-        // Large upsampling (e.g., 8kHz → 16kHz, 16kHz → 48kHz, 24kHz → 48kHz)
-        // Needs high quality to avoid artifacts
-        debug!("High-quality upsampling: {}Hz → {}Hz (ratio: {:.2}x)",
-               from_sample_rate, to_sample_rate, ratio);
-        (
-            512,                              // Longer sinc for smoother interpolation
-            SincInterpolationType::Cubic,     // Cubic for best quality
-            512,                              // Higher oversampling
-        )
-    } else if ratio >= 1.5 {
-        // Moderate upsampling (e.g., 32kHz → 48kHz)
-        debug!("Moderate upsampling: {}Hz → {}Hz (ratio: {:.2}x)",
-               from_sample_rate, to_sample_rate, ratio);
-        (
-            384,
-            SincInterpolationType::Cubic,
-            384,
-        )
-    } else if ratio > 1.0 {
-        // Small upsampling (e.g., 44.1kHz → 48kHz)
-        debug!("Small upsampling: {}Hz → {}Hz (ratio: {:.2}x)",
-               from_sample_rate, to_sample_rate, ratio);
-        (
-            256,
-            SincInterpolationType::Linear,
-            256,
-        )
-    } else if ratio <= 0.5 {
-        // Large downsampling (e.g., 48kHz → 16kHz, 48kHz → 8kHz)
-        // Needs strong anti-aliasing
-        debug!("Anti-aliased downsampling: {}Hz → {}Hz (ratio: {:.2}x)",
-               from_sample_rate, to_sample_rate, ratio);
-        (
-            512,                              // Longer sinc for anti-aliasing
-            SincInterpolationType::Cubic,     // Cubic for quality
-            512,
-        )
-    } else {
-        // Moderate downsampling (e.g., 48kHz → 24kHz, 48kHz → 32kHz)
-        debug!("Moderate downsampling: {}Hz → {}Hz (ratio: {:.2}x)",
-               from_sample_rate, to_sample_rate, ratio);
-        (
-            384,
-            SincInterpolationType::Linear,
-            384,
-        )
-    };
-
-    let params = SincInterpolationParameters {
-        sinc_len,
-        f_cutoff: 0.95,                      // Preserve most of the frequency content
-        interpolation: interpolation_type,
-        oversampling_factor: oversampling,
-        window: WindowFunction::BlackmanHarris2,  // Best window for audio
-    };
-
-    let mut resampler = SincFixedIn::<f32>::new(
-        ratio,
-        2.0,  // Maximum relative deviation
-        params,
-        input.len(),
-        1,    // Mono
-    )?;
-
-    let waves_in = vec![input.to_vec()];
-    let waves_out = resampler.process(&waves_in, None)?;
-
-    debug!("Resampling complete: {} samples → {} samples",
-           input.len(), waves_out[0].len());
-
-    Ok(waves_out.into_iter().next().unwrap())
+    super::resample::resample(input, from_sample_rate, to_sample_rate)
 }
 
 // Alias for compatibility with existing code
@@ -720,3 +768,91 @@ pub fn write_transcript_json_to_file(
 
     Ok(file_path.to_string_lossy().to_string())
 }
+
+#[tauri::command]
+pub async fn get_agc_settings_command() -> Result<AgcSettings, String> {
+    Ok(get_agc_settings())
+}
+
+#[tauri::command]
+pub async fn set_agc_settings_command(settings: AgcSettings) -> Result<AgcSettings, String> {
+    set_agc_settings(settings);
+    Ok(get_agc_settings())
+}
+
+#[tauri::command]
+pub async fn set_noise_suppression(enabled: bool) -> Result<(), String> {
+    set_noise_suppression_enabled(enabled);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_noise_suppression() -> Result<bool, String> {
+    Ok(is_noise_suppression_enabled())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(amplitude: f32, seconds: usize) -> Vec<f32> {
+        (0..48000 * seconds)
+            .map(|i| amplitude * (i as f32 * 2.0 * std::f32::consts::PI * 300.0 / 48000.0).sin())
+            .collect()
+    }
+
+    fn rms_dbfs(samples: &[f32]) -> f32 {
+        20.0 * (samples.iter().map(|x| x * x).sum::<f32>() / samples.len() as f32).sqrt().log10()
+    }
+
+    #[test]
+    fn test_agc_boosts_quiet_input_to_target() {
+        let settings = AgcSettings::default();
+        let mut agc = AutomaticGainControl::new(48000);
+        // Sine RMS is amplitude - 3 dB, so about -43 dBFS
+        let output = agc.process(&sine(0.01, 6), &settings);
+        let level = rms_dbfs(&output[output.len() - 48000..]);
+        assert!((level - settings.target_dbfs).abs() < 1.5, "level {:.1} dBFS", level);
+    }
+
+    #[test]
+    fn test_agc_respects_gain_limits() {
+        let settings = AgcSettings { max_gain_db: 10.0, ..AgcSettings::default() };
+        let mut agc = AutomaticGainControl::new(48000);
+        agc.process(&sine(0.003, 6), &settings);
+        assert!((agc.current_gain_db() - 10.0).abs() < 0.01);
+
+        let settings = AgcSettings { min_gain_db: -3.0, ..AgcSettings::default() };
+        let mut agc = AutomaticGainControl::new(48000);
+        agc.process(&sine(0.8, 2), &settings);
+        assert!((agc.current_gain_db() + 3.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_agc_holds_gain_during_silence() {
+        let settings = AgcSettings::default();
+        let mut agc = AutomaticGainControl::new(48000);
+        agc.process(&sine(0.05, 3), &settings);
+        let gain = agc.current_gain_db();
+        agc.process(&vec![0.0; 48000 * 3], &settings);
+        assert_eq!(agc.current_gain_db(), gain);
+    }
+
+    #[test]
+    fn test_noise_suppression_attenuates_noise() {
+        let mut processor = NoiseSuppressionProcessor::new(48000).unwrap();
+        // Deterministic white-ish noise at about -30 dBFS
+        let mut state = 1u32;
+        let noise: Vec<f32> = (0..48000 * 2)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 8) as f32 / (1u32 << 24) as f32 * 0.06 - 0.03
+            })
+            .collect();
+
+        let output: Vec<f32> = noise.chunks(480).flat_map(|c| processor.process(c)).collect();
+        let energy = |s: &[f32]| s.iter().map(|x| x * x).sum::<f32>() / s.len() as f32;
+        let tail = 48000;
+        assert!(energy(&output[output.len() - tail..]) < energy(&noise[noise.len() - tail..]) * 0.5);
+    }
+}
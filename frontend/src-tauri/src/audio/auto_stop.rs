@@ -0,0 +1,153 @@
+// Automatic stop after prolonged silence
+//
+// A recorder left running after a meeting ends keeps capturing (and saving) hours of
+// nothing. When enabled, a monitor watches how long it has been since the VAD last passed
+// speech to transcription; once that exceeds the configured number of minutes it asks the
+// UI to stop the recording through the regular stop flow (which saves the transcript and
+// generates the summary) and notifies the user.
+
+use std::sync::RwLock;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use super::recording_commands::RECORDING_MANAGER;
+use super::session;
+use crate::notifications::commands::NotificationManagerState;
+use crate::notifications::types::Notification;
+
+/// Emitted once when the silence limit is reached; the UI stops the recording
+pub const AUTO_STOP_EVENT: &str = "recording-auto-stop";
+
+/// How often the silence duration is checked
+const CHECK_INTERVAL_SECS: u64 = 10;
+
+const MAX_SILENCE_MINUTES: u32 = 240;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoStopSettings {
+    pub enabled: bool,
+    /// Minutes without detected speech before the recording is stopped
+    pub silence_minutes: u32,
+}
+
+impl Default for AutoStopSettings {
+    fn default() -> Self {
+        Self { enabled: false, silence_minutes: 15 }
+    }
+}
+
+impl AutoStopSettings {
+    pub fn normalized(self) -> Self {
+        Self { silence_minutes: self.silence_minutes.clamp(1, MAX_SILENCE_MINUTES), ..self }
+    }
+
+    /// Whether `silent_secs` of silence should end the recording
+    pub fn should_stop(&self, silent_secs: f64) -> bool {
+        self.enabled && silent_secs >= self.silence_minutes as f64 * 60.0
+    }
+}
+
+static AUTO_STOP_SETTINGS: Lazy<RwLock<AutoStopSettings>> = Lazy::new(|| RwLock::new(AutoStopSettings::default()));
+
+pub fn get_auto_stop_settings() -> AutoStopSettings {
+    *AUTO_STOP_SETTINGS.read().unwrap()
+}
+
+/// The watcher reads these on every check, so a recording in progress gets the new silence limit
+pub fn set_auto_stop_settings(settings: AutoStopSettings) -> AutoStopSettings {
+    let settings = settings.normalized();
+    *AUTO_STOP_SETTINGS.write().unwrap() = settings;
+    info!("⏹️ Auto-stop {} ({} min of silence)", if settings.enabled { "enabled" } else { "disabled" }, settings.silence_minutes);
+    settings
+}
+
+/// Seconds without detected speech in the active recording; `None` when paused or idle
+pub fn seconds_since_speech() -> Option<f64> {
+    let guard = RECORDING_MANAGER.lock().ok()?;
+    let manager = guard.as_ref().filter(|manager| !manager.is_paused())?;
+    manager.get_state().seconds_since_speech()
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AutoStopPayload {
+    silence_minutes: u32,
+    silent_seconds: f64,
+}
+
+/// Watch the active recording for prolonged silence
+pub fn spawn_auto_stop_monitor<R: Runtime>(app: AppHandle<R>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+
+        while session::phase().is_recording() {
+            interval.tick().await;
+
+            let silent_secs = match RECORDING_MANAGER.lock() {
+                Ok(guard) => match guard.as_ref() {
+                    Some(manager) if !manager.is_paused() => manager.get_state().seconds_since_speech(),
+                    Some(_) => continue,
+                    None => break,
+                },
+                Err(_) => break,
+            };
+
+            let settings = get_auto_stop_settings();
+            let Some(silent_secs) = silent_secs.filter(|&secs| settings.should_stop(secs)) else {
+                continue;
+            };
+
+            warn!("⏹️ No speech for {:.0}s, requesting automatic stop", silent_secs);
+            let payload = AutoStopPayload { silence_minutes: settings.silence_minutes, silent_seconds: silent_secs };
+            if let Err(e) = app.emit(AUTO_STOP_EVENT, payload) {
+                warn!("Failed to emit auto-stop event: {}", e);
+            }
+            notify(&app, settings.silence_minutes).await;
+            break;
+        }
+
+        info!("Auto-stop monitor stopped");
+    });
+}
+
+async fn notify<R: Runtime>(app: &AppHandle<R>, silence_minutes: u32) {
+    let Some(state) = app.try_state::<NotificationManagerState<R>>() else {
+        return;
+    };
+    let manager = state.read().await;
+    if let Some(manager) = manager.as_ref() {
+        if let Err(e) = manager.show_notification(Notification::recording_auto_stopped(silence_minutes)).await {
+            warn!("Failed to show auto-stop notification: {}", e);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_auto_stop_settings_command() -> Result<AutoStopSettings, String> {
+    Ok(get_auto_stop_settings())
+}
+
+#[tauri::command]
+pub async fn set_auto_stop_settings_command(settings: AutoStopSettings) -> Result<AutoStopSettings, String> {
+    Ok(set_auto_stop_settings(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_stop_only_when_enabled_and_past_limit() {
+        let settings = AutoStopSettings { enabled: true, silence_minutes: 10 };
+        assert!(!settings.should_stop(599.0));
+        assert!(settings.should_stop(600.0));
+        assert!(!AutoStopSettings { enabled: false, ..settings }.should_stop(10_000.0));
+    }
+
+    #[test]
+    fn test_normalized_clamps_minutes() {
+        assert_eq!(AutoStopSettings { enabled: true, silence_minutes: 0 }.normalized().silence_minutes, 1);
+        assert_eq!(AutoStopSettings { enabled: true, silence_minutes: 9999 }.normalized().silence_minutes, MAX_SILENCE_MINUTES);
+    }
+}
@@ -0,0 +1,168 @@
+// Bluetooth headset microphones and the hands-free profile
+//
+// A Bluetooth headset only sends microphone audio in its hands-free profile (HFP), which
+// carries 8 or 16kHz mono with heavy compression, and switches playback to the same low
+// quality for as long as the microphone is open. Transcripts of such audio are much worse.
+// When a recording's microphone is a Bluetooth headset the UI is told, and offered to record
+// from the built-in microphone instead; playback then stays on the headset in its
+// high-quality profile.
+//
+// Bluetooth devices with a high input rate (LE Audio, some dongles) aren't in HFP and are
+// left alone.
+
+use std::sync::Arc;
+use cpal::traits::{DeviceTrait, HostTrait};
+use log::{info, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use super::capture::CaptureSource;
+use super::device_preferences::{resolve_preferred_device, store_preferred_device};
+use super::devices::metadata::detect_transport;
+use super::devices::{default_input_device, find_builtin_input_device, AudioDevice, DeviceTransport};
+use super::recording_commands::{switch_microphone, RECORDING_MANAGER};
+use super::session;
+
+/// Emitted with a `HandsFreeWarning` when a recording starts on a hands-free microphone
+pub const HANDS_FREE_EVENT: &str = "bluetooth-hands-free";
+
+/// Highest input rate of the hands-free profile; wideband speech on some headsets uses 24kHz
+const MAX_HANDS_FREE_RATE: u32 = 24000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HandsFreeWarning {
+    pub microphone: String,
+    pub sample_rate: Option<u32>,
+    /// What to record from instead, when the computer has a microphone of its own
+    pub built_in_microphone: Option<String>,
+    pub message: String,
+}
+
+/// Whether a microphone puts its headset in the hands-free profile; an unknown rate counts
+fn is_hands_free(transport: DeviceTransport, sample_rate: Option<u32>) -> bool {
+    transport == DeviceTransport::Bluetooth && sample_rate.map_or(true, |rate| rate <= MAX_HANDS_FREE_RATE)
+}
+
+fn hands_free_warning(
+    microphone: &str,
+    transport: DeviceTransport,
+    sample_rate: Option<u32>,
+    built_in_microphone: Option<String>,
+) -> Option<HandsFreeWarning> {
+    if !is_hands_free(transport, sample_rate) {
+        return None;
+    }
+    let quality = match sample_rate {
+        Some(rate) => format!("{}kHz hands-free audio", rate / 1000),
+        None => "hands-free audio".to_string(),
+    };
+    let message = match &built_in_microphone {
+        Some(built_in) => format!(
+            "'{}' records {}, which transcribes poorly. Record from '{}' and keep listening on the headset.",
+            microphone, quality, built_in
+        ),
+        None => {
+            format!("'{}' records {}, which transcribes poorly. A wired microphone works better.", microphone, quality)
+        }
+    };
+    Some(HandsFreeWarning { microphone: microphone.to_string(), sample_rate, built_in_microphone, message })
+}
+
+fn input_sample_rate(name: &str) -> Option<u32> {
+    let host = cpal::default_host();
+    let device = host.input_devices().ok()?.find(|d| d.name().map(|n| n == name).unwrap_or(false))?;
+    device.default_input_config().ok().map(|config| config.sample_rate().0)
+}
+
+/// The warning for recording from `microphone`, if it is a hands-free headset
+pub fn check_microphone(microphone: &str) -> Option<HandsFreeWarning> {
+    let transport = detect_transport(microphone);
+    if transport != DeviceTransport::Bluetooth {
+        return None;
+    }
+    let built_in = find_builtin_input_device().ok().flatten().map(|device| device.name);
+    hands_free_warning(microphone, transport, input_sample_rate(microphone), built_in)
+}
+
+/// Warns the UI when the recording just started on a hands-free microphone
+pub fn spawn_hands_free_check<R: Runtime>(app: AppHandle<R>) {
+    tokio::spawn(async move {
+        let microphone = match RECORDING_MANAGER.lock() {
+            Ok(guard) => guard.as_ref().and_then(|manager| manager.get_state().get_microphone_device()),
+            Err(_) => None,
+        };
+        let Some(microphone) = microphone else {
+            return;
+        };
+        let Ok(Some(warning)) = tokio::task::spawn_blocking(move || check_microphone(&microphone.name)).await else {
+            return;
+        };
+        warn!("🎧 Recording from hands-free microphone '{}' ({:?} Hz)", warning.microphone, warning.sample_rate);
+        if let Err(e) = app.emit(HANDS_FREE_EVENT, &warning) {
+            warn!("Failed to emit {}: {}", HANDS_FREE_EVENT, e);
+        }
+    });
+}
+
+/// The warning for the microphone the next recording would use
+#[tauri::command]
+pub async fn check_bluetooth_microphone<R: Runtime>(app: AppHandle<R>) -> Result<Option<HandsFreeWarning>, String> {
+    let microphone = match resolve_preferred_device(&app, CaptureSource::Microphone).await {
+        Some(device) => device.name.clone(),
+        None => default_input_device().map_err(|e| format!("Failed to find a microphone: {}", e))?.name,
+    };
+    tokio::task::spawn_blocking(move || check_microphone(&microphone))
+        .await
+        .map_err(|e| format!("Failed to check the microphone: {}", e))
+}
+
+/// Records from the built-in microphone, moving a running recording over; `remember` keeps
+/// it as the preferred microphone for later recordings. Returns its name.
+#[tauri::command]
+pub async fn use_built_in_microphone<R: Runtime>(app: AppHandle<R>, remember: bool) -> Result<String, String> {
+    let device = find_builtin_input_device()
+        .map_err(|e| format!("Failed to list microphones: {}", e))?
+        .ok_or_else(|| "This computer has no built-in microphone".to_string())?;
+    let name = device.name.clone();
+
+    if remember {
+        store_preferred_device(&app, CaptureSource::Microphone, Some(device.to_string()))
+            .map_err(|e| format!("Failed to save preferred device: {}", e))?;
+    }
+
+    if session::phase().is_recording() {
+        switch_microphone(Arc::new(device))
+            .await
+            .map_err(|e| format!("Failed to switch to '{}': {}", name, e))?;
+    }
+
+    info!("🎤 Recording from built-in microphone '{}'", name);
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_hands_free() {
+        assert!(is_hands_free(DeviceTransport::Bluetooth, Some(16000)));
+        assert!(is_hands_free(DeviceTransport::Bluetooth, Some(8000)));
+        assert!(is_hands_free(DeviceTransport::Bluetooth, None));
+        assert!(!is_hands_free(DeviceTransport::Bluetooth, Some(48000)));
+        assert!(!is_hands_free(DeviceTransport::Usb, Some(16000)));
+    }
+
+    #[test]
+    fn test_hands_free_warning() {
+        let built_in = Some("MacBook Pro Microphone".to_string());
+        let warning = hands_free_warning("AirPods Pro", DeviceTransport::Bluetooth, Some(16000), built_in).unwrap();
+        assert_eq!(warning.built_in_microphone.as_deref(), Some("MacBook Pro Microphone"));
+        assert!(warning.message.contains("16kHz hands-free audio"));
+        assert!(warning.message.contains("Record from 'MacBook Pro Microphone'"));
+
+        let warning = hands_free_warning("Jabra Evolve", DeviceTransport::Bluetooth, Some(8000), None).unwrap();
+        assert!(warning.message.contains("A wired microphone works better"));
+        assert_eq!(hands_free_warning("Yeti", DeviceTransport::Usb, Some(48000), None), None);
+    }
+}
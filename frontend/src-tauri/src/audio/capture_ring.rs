@@ -0,0 +1,354 @@
+// Lock-free handoff from the capture callback to the processing thread
+//
+// The capture callback runs on the OS audio thread, which must hand back each buffer within a
+// few milliseconds. Mono conversion, resampling, noise suppression and AGC used to run inside
+// it, allocating a Vec per callback, and a slow moment there (a page fault, a busy CPU, a lock
+// held by the pipeline) made the OS drop the next buffer. Now the callback only copies its
+// samples into a ring buffer allocated up front, and a thread per stream drains the ring and
+// does the processing. The callback neither allocates, locks nor makes system calls; the
+// processing thread polls the ring at a short interval. Input levels for the meters are
+// measured on the processing thread as well.
+//
+// If the processing thread falls so far behind that the ring fills up, the frames that don't
+// fit are dropped and counted as an overrun. The counts go to the log and the diagnostics
+// bundle.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use ringbuf::traits::{Consumer, Observer, Producer, Split};
+use ringbuf::{HeapCons, HeapProd, HeapRb};
+use serde::Serialize;
+
+use super::capture::{CaptureLevels, LevelMeter};
+use super::recording_state::DeviceType;
+
+/// Audio the ring holds before the callback starts dropping frames
+const RING_SECS: f64 = 2.0;
+
+/// Most audio handed to the pipeline in one call
+const BLOCK_SECS: f64 = 0.02;
+
+/// How often the processing thread looks for new samples
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Counters shared between the callback and the processing thread
+#[derive(Debug, Default)]
+struct RingCounters {
+    captured_frames: AtomicU64,
+    fill_frames: AtomicU64,
+    overruns: AtomicU64,
+    dropped_frames: AtomicU64,
+    peak_fill_frames: AtomicU64,
+}
+
+/// Overrun counts of one stream's ring, for the diagnostics bundle
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CaptureRingStats {
+    pub device_name: String,
+    pub device_type: String,
+    pub capacity_ms: u64,
+    pub captured_frames: u64,
+    /// Callbacks that found the ring too full for all of their samples
+    pub overruns: u64,
+    pub dropped_frames: u64,
+    /// How full the ring was after the last callback, in percent of its capacity
+    pub fill_percent: f64,
+    /// Fullest the ring has been, in percent of its capacity
+    pub peak_fill_percent: f64,
+}
+
+struct RingEntry {
+    device_name: String,
+    device_type: DeviceType,
+    capacity_frames: usize,
+    sample_rate: u32,
+    counters: Arc<RingCounters>,
+    levels: Arc<Mutex<LevelMeter>>,
+}
+
+impl RingEntry {
+    fn stats(&self) -> CaptureRingStats {
+        let percent = |frames: u64| (frames as f64 * 1000.0 / self.capacity_frames.max(1) as f64).round() / 10.0;
+        CaptureRingStats {
+            device_name: self.device_name.clone(),
+            device_type: format!("{:?}", self.device_type),
+            capacity_ms: self.capacity_frames as u64 * 1000 / self.sample_rate.max(1) as u64,
+            captured_frames: self.counters.captured_frames.load(Ordering::Relaxed),
+            overruns: self.counters.overruns.load(Ordering::Relaxed),
+            dropped_frames: self.counters.dropped_frames.load(Ordering::Relaxed),
+            fill_percent: percent(self.counters.fill_frames.load(Ordering::Relaxed)),
+            peak_fill_percent: percent(self.counters.peak_fill_frames.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Rings of the current or last recording, one per device type
+static RINGS: Lazy<Mutex<Vec<Arc<RingEntry>>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Overrun counts of the streams of the current recording, or the last one
+pub fn recent_stats() -> Vec<CaptureRingStats> {
+    match RINGS.lock() {
+        Ok(rings) => rings.iter().map(|ring| ring.stats()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn register(entry: Arc<RingEntry>) {
+    if let Ok(mut rings) = RINGS.lock() {
+        rings.retain(|ring| ring.device_type != entry.device_type);
+        rings.push(entry);
+    }
+}
+
+/// The callback's end of the ring
+pub struct CaptureRingWriter {
+    producer: HeapProd<f32>,
+    channels: usize,
+    counters: Arc<RingCounters>,
+}
+
+impl CaptureRingWriter {
+    /// Copies a callback buffer into the ring; called on the audio thread
+    pub fn push(&mut self, data: &[f32]) {
+        // Whole frames only, so the processing thread never sees half a frame
+        let fits = self.producer.vacant_len() / self.channels * self.channels;
+        let pushed = self.producer.push_slice(&data[..data.len().min(fits)]);
+        self.counters.captured_frames.fetch_add((pushed / self.channels) as u64, Ordering::Relaxed);
+        if pushed < data.len() {
+            self.counters.overruns.fetch_add(1, Ordering::Relaxed);
+            self.counters.dropped_frames.fetch_add(((data.len() - pushed) / self.channels) as u64, Ordering::Relaxed);
+        }
+        let fill = (self.producer.occupied_len() / self.channels) as u64;
+        self.counters.fill_frames.store(fill, Ordering::Relaxed);
+        self.counters.peak_fill_frames.fetch_max(fill, Ordering::Relaxed);
+    }
+}
+
+/// The processing thread of one stream; stopping it processes what is left in the ring
+pub struct CaptureRing {
+    entry: Arc<RingEntry>,
+    running: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl CaptureRing {
+    /// Starts a processing thread calling `process` with the samples the writer receives; its
+    /// counts replace those of the previous ring of `device_type` in `recent_stats`
+    pub fn start<F>(
+        device_name: &str,
+        device_type: DeviceType,
+        sample_rate: u32,
+        channels: u16,
+        process: F,
+    ) -> std::io::Result<(Self, CaptureRingWriter)>
+    where
+        F: FnMut(&[f32]) + Send + 'static,
+    {
+        let (ring, writer) = Self::start_unlisted(device_name, device_type, sample_rate, channels, process)?;
+        register(ring.entry.clone());
+        Ok((ring, writer))
+    }
+
+    /// Like `start`, but left out of `recent_stats`; for captures that aren't recorded, such
+    /// as the level monitor's
+    pub fn start_unlisted<F>(
+        device_name: &str,
+        device_type: DeviceType,
+        sample_rate: u32,
+        channels: u16,
+        process: F,
+    ) -> std::io::Result<(Self, CaptureRingWriter)>
+    where
+        F: FnMut(&[f32]) + Send + 'static,
+    {
+        let capacity = ((sample_rate as f64 * RING_SECS) as usize).max(1);
+        let block = ((sample_rate as f64 * BLOCK_SECS) as usize).max(1);
+        Self::start_with_capacity(device_name, device_type, sample_rate, channels, capacity, block, process)
+    }
+
+    fn start_with_capacity<F>(
+        device_name: &str,
+        device_type: DeviceType,
+        sample_rate: u32,
+        channels: u16,
+        capacity_frames: usize,
+        block_frames: usize,
+        mut process: F,
+    ) -> std::io::Result<(Self, CaptureRingWriter)>
+    where
+        F: FnMut(&[f32]) + Send + 'static,
+    {
+        let channels = channels.max(1) as usize;
+        let (producer, consumer) = HeapRb::<f32>::new(capacity_frames * channels).split();
+        let counters = Arc::new(RingCounters::default());
+        let levels = Arc::new(Mutex::new(LevelMeter::default()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread_name = match device_type {
+            DeviceType::Microphone => "capture-microphone",
+            DeviceType::System => "capture-system",
+        };
+        let worker_running = running.clone();
+        let worker_levels = levels.clone();
+        let worker = std::thread::Builder::new().name(thread_name.to_string()).spawn(move || {
+            drain(consumer, vec![0.0; block_frames * channels], &worker_running, &mut |samples: &[f32]| {
+                if let Ok(mut meter) = worker_levels.lock() {
+                    meter.push(samples);
+                }
+                process(samples)
+            })
+        })?;
+
+        let entry = Arc::new(RingEntry {
+            device_name: device_name.to_string(),
+            device_type,
+            capacity_frames,
+            sample_rate,
+            counters: counters.clone(),
+            levels,
+        });
+        let writer = CaptureRingWriter { producer, channels, counters };
+        Ok((Self { entry, running, worker: Some(worker) }, writer))
+    }
+
+    /// Callbacks so far that lost samples because the ring was full
+    pub fn overruns(&self) -> u64 {
+        self.entry.counters.overruns.load(Ordering::Relaxed)
+    }
+
+    pub fn stats(&self) -> CaptureRingStats {
+        self.entry.stats()
+    }
+
+    /// Levels of the samples processed since the previous call
+    pub fn levels(&self) -> CaptureLevels {
+        self.entry.levels.lock().map(|mut meter| meter.take()).unwrap_or_default()
+    }
+
+    /// Processes the samples left in the ring and stops the thread; call after the capture
+    /// callback has stopped
+    pub fn stop(&mut self) {
+        let Some(worker) = self.worker.take() else {
+            return;
+        };
+        self.running.store(false, Ordering::Release);
+        worker.thread().unpark();
+        if worker.join().is_err() {
+            error!("❌ Capture processing thread for '{}' panicked", self.entry.device_name);
+        }
+
+        let dropped = self.entry.counters.dropped_frames.load(Ordering::Relaxed);
+        if dropped > 0 {
+            warn!(
+                "⚠️ Capture ring for '{}': {} overruns, {} frames dropped",
+                self.entry.device_name,
+                self.overruns(),
+                dropped
+            );
+        } else {
+            info!("Capture ring for '{}' stopped without overruns", self.entry.device_name);
+        }
+    }
+}
+
+impl Drop for CaptureRing {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn drain<F: FnMut(&[f32])>(mut consumer: HeapCons<f32>, mut block: Vec<f32>, running: &AtomicBool, process: &mut F) {
+    loop {
+        // Read before draining, so samples pushed just before the stop are still processed
+        let stopping = !running.load(Ordering::Acquire);
+        while consumer.occupied_len() > 0 {
+            let count = consumer.pop_slice(&mut block);
+            process(&block[..count]);
+        }
+        if stopping {
+            break;
+        }
+        std::thread::park_timeout(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_samples_arrive_in_order() {
+        let (sender, receiver) = mpsc::channel();
+        let (mut ring, mut writer) = CaptureRing::start_with_capacity(
+            "Test Mic",
+            DeviceType::Microphone,
+            48000,
+            2,
+            1000,
+            64,
+            move |samples: &[f32]| sender.send(samples.to_vec()).unwrap(),
+        )
+        .unwrap();
+
+        let input: Vec<f32> = (0..960).map(|i| i as f32).collect();
+        for buffer in input.chunks(96) {
+            writer.push(buffer);
+        }
+        ring.stop();
+
+        let blocks: Vec<Vec<f32>> = receiver.try_iter().collect();
+        assert!(blocks.iter().all(|block| block.len() % 2 == 0 && block.len() <= 128));
+        assert_eq!(blocks.concat(), input);
+        assert_eq!(ring.overruns(), 0);
+        // Metered on the processing thread; reading resets the window
+        assert!(ring.levels().clipping);
+        assert_eq!(ring.levels(), CaptureLevels::default());
+    }
+
+    #[test]
+    fn test_full_ring_counts_overruns() {
+        let gate = Arc::new(AtomicBool::new(false));
+        let worker_gate = gate.clone();
+        let (sender, receiver) = mpsc::channel();
+        let (mut ring, mut writer) = CaptureRing::start_with_capacity(
+            "Test System",
+            DeviceType::System,
+            48000,
+            2,
+            100,
+            100,
+            move |samples: &[f32]| {
+                // The processing thread is stuck until the test lets it go
+                while !worker_gate.load(Ordering::Acquire) {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                sender.send(samples.len()).unwrap();
+            },
+        )
+        .unwrap();
+
+        // 60 frames, then 60 more of which only 40 fit in the 100-frame ring (the worker may
+        // already hold the first block, leaving room for the rest)
+        writer.push(&[0.5; 120]);
+        std::thread::sleep(Duration::from_millis(50));
+        writer.push(&[0.5; 120]);
+        writer.push(&[0.5; 120]);
+        gate.store(true, Ordering::Release);
+        ring.stop();
+
+        let stats = ring.stats();
+        assert!(ring.overruns() >= 1);
+        assert_eq!(stats.overruns, ring.overruns());
+        let processed: usize = receiver.try_iter().sum();
+        assert_eq!(processed as u64 / 2 + stats.dropped_frames, 180);
+        assert_eq!(stats.captured_frames + stats.dropped_frames, 180);
+        assert_eq!(stats.peak_fill_percent, 100.0);
+        assert_eq!(stats.capacity_ms, 2);
+    }
+}
@@ -0,0 +1,163 @@
+// Channel layout of saved recordings
+//
+// Transcription always works on a mono downmix of microphone + system audio. The saved
+// file can be laid out differently:
+//
+// - `Mono`: the same mono mix that is transcribed (default, smallest files)
+// - `PreserveChannels`: stereo; system audio keeps its original stereo image and the
+//   (processed, mono) microphone sits in the center
+// - `SplitStereo`: microphone on the left channel, system audio on the right, so each
+//   side of the conversation can be reviewed or re-transcribed separately
+//
+// To preserve the stereo image without changing the mono pipeline, system capture also
+// carries the side signal (L - R) / 2 next to its mono mid signal (L + R) / 2, and the
+// two channels are rebuilt as mid ± side when the recording is mixed.
+
+use std::sync::RwLock;
+use log::info;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// How microphone and system audio are laid out in the saved file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelMixPolicy {
+    #[default]
+    Mono,
+    PreserveChannels,
+    SplitStereo,
+}
+
+impl ChannelMixPolicy {
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "mono" => Some(Self::Mono),
+            "preserve_channels" | "preserve" | "stereo" => Some(Self::PreserveChannels),
+            "split_stereo" | "split" => Some(Self::SplitStereo),
+            _ => None,
+        }
+    }
+
+    /// Channels of the saved file
+    pub fn recording_channels(&self) -> u16 {
+        match self {
+            Self::Mono => 1,
+            Self::PreserveChannels | Self::SplitStereo => 2,
+        }
+    }
+
+    /// Whether system capture needs to carry its stereo side signal
+    pub fn needs_stereo_side(&self) -> bool {
+        matches!(self, Self::PreserveChannels)
+    }
+}
+
+static CHANNEL_MIX_POLICY: Lazy<RwLock<ChannelMixPolicy>> = Lazy::new(|| RwLock::new(ChannelMixPolicy::default()));
+
+pub fn get_channel_mix_policy() -> ChannelMixPolicy {
+    *CHANNEL_MIX_POLICY.read().unwrap()
+}
+
+/// Applies to the next recording; one in progress keeps its layout
+pub fn set_channel_mix_policy(policy: ChannelMixPolicy) {
+    *CHANNEL_MIX_POLICY.write().unwrap() = policy;
+    info!("🎚️ Recording channel layout set to {:?}", policy);
+}
+
+/// Side signal (L - R) / 2 of interleaved audio; 0 for mono input
+pub fn stereo_side(data: &[f32], channels: u16) -> Vec<f32> {
+    if channels < 2 {
+        return vec![0.0; data.len()];
+    }
+    data.chunks(channels as usize)
+        .map(|frame| (frame[0] - frame.get(1).copied().unwrap_or(frame[0])) / 2.0)
+        .collect()
+}
+
+/// Build the audio for the saved file from one aligned window
+///
+/// `mixed` is the mono mix used for transcription; `system_side` is the system side
+/// signal aligned with `system` (zeros when the device is mono). Stereo output is
+/// interleaved L/R.
+pub fn mix_for_recording(
+    policy: ChannelMixPolicy,
+    mixed: &[f32],
+    mic: &[f32],
+    system: &[f32],
+    system_side: &[f32],
+) -> Vec<f32> {
+    match policy {
+        ChannelMixPolicy::Mono => mixed.to_vec(),
+        ChannelMixPolicy::PreserveChannels => {
+            let mut out = Vec::with_capacity(mic.len().max(system.len()) * 2);
+            for i in 0..mic.len().max(system.len()) {
+                let center = mic.get(i).copied().unwrap_or(0.0) + system.get(i).copied().unwrap_or(0.0);
+                let side = system_side.get(i).copied().unwrap_or(0.0);
+                out.push(soft_limit(center + side));
+                out.push(soft_limit(center - side));
+            }
+            out
+        }
+        ChannelMixPolicy::SplitStereo => {
+            let mut out = Vec::with_capacity(mic.len().max(system.len()) * 2);
+            for i in 0..mic.len().max(system.len()) {
+                out.push(soft_limit(mic.get(i).copied().unwrap_or(0.0)));
+                out.push(soft_limit(system.get(i).copied().unwrap_or(0.0)));
+            }
+            out
+        }
+    }
+}
+
+/// Same proportional scaling the mono mixer uses instead of hard clipping
+fn soft_limit(sample: f32) -> f32 {
+    let magnitude = sample.abs();
+    if magnitude > 1.0 {
+        sample / magnitude
+    } else {
+        sample
+    }
+}
+
+#[tauri::command]
+pub async fn get_channel_mix_policy_command() -> Result<ChannelMixPolicy, String> {
+    Ok(get_channel_mix_policy())
+}
+
+#[tauri::command]
+pub async fn set_channel_mix_policy_command(policy: String) -> Result<(), String> {
+    let policy = ChannelMixPolicy::from_string(&policy)
+        .ok_or_else(|| format!("Unknown channel layout: {}", policy))?;
+    set_channel_mix_policy(policy);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preserve_channels_rebuilds_stereo_image() {
+        // System audio hard-panned left: L=0.4, R=0.0
+        let interleaved = [0.4, 0.0, 0.4, 0.0];
+        let mid = super::super::audio_processing::audio_to_mono(&interleaved, 2);
+        let side = stereo_side(&interleaved, 2);
+
+        let out = mix_for_recording(ChannelMixPolicy::PreserveChannels, &mid, &[0.0, 0.0], &mid, &side);
+        assert_eq!(out, vec![0.4, 0.0, 0.4, 0.0]);
+    }
+
+    #[test]
+    fn test_split_stereo_separates_sources() {
+        let out = mix_for_recording(ChannelMixPolicy::SplitStereo, &[0.0; 2], &[0.1, 0.2], &[0.3, 0.4], &[0.0; 2]);
+        assert_eq!(out, vec![0.1, 0.3, 0.2, 0.4]);
+    }
+
+    #[test]
+    fn test_mono_passes_mix_through() {
+        let mixed = [0.5, -0.5];
+        assert_eq!(mix_for_recording(ChannelMixPolicy::Mono, &mixed, &[], &[], &[]), mixed.to_vec());
+        assert_eq!(ChannelMixPolicy::Mono.recording_channels(), 1);
+        assert_eq!(ChannelMixPolicy::from_string("split"), Some(ChannelMixPolicy::SplitStereo));
+    }
+}
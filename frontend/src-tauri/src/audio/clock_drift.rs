@@ -0,0 +1,282 @@
+// Clock drift between the microphone and system audio streams
+//
+// The two streams come from devices with their own clocks, so one delivers slightly more
+// samples per second than the other: tens of ppm between good devices, more with Bluetooth.
+// The mixer lines them up by sample count, and over a long meeting the system audio slides
+// away from the microphone - a third of a second an hour at 100 ppm - which puts remote
+// speech next to the wrong local speech.
+//
+// The microphone is the reference. The system stream's sample count, less the microphone's,
+// is fitted against time with a least-squares line; its slope is the drift. The system stream
+// is then corrected towards the fitted line by dropping or inserting single samples, spread
+// out and smoothed with their neighbours, so the correction isn't audible. A jump in the
+// difference (a stream that stalled or restarted) starts a new fit; the mixer already pads
+// those gaps.
+
+use log::info;
+
+use super::recording_state::DeviceType;
+
+/// Fitting points are taken once per second of microphone audio
+const OBSERVATION_SECS: f64 = 1.0;
+
+/// Seconds of fit before the drift is trusted
+const MIN_FIT_SECS: f64 = 60.0;
+
+/// A jump this large between two points is a stall or a restart, not drift
+const GAP_SECS: f64 = 0.25;
+
+/// Drift beyond this is a broken stream rather than a clock, and isn't followed
+const MAX_DRIFT_PPM: f64 = 5000.0;
+
+/// Most samples inserted or dropped per system sample; twice the largest drift, so a
+/// correction that started late catches up
+const MAX_EDITS_PER_SAMPLE: f64 = 2.0 * MAX_DRIFT_PPM / 1e6;
+
+const LOG_INTERVAL_SECS: f64 = 300.0;
+
+/// Running least-squares fit of the offset between the streams
+#[derive(Debug, Default)]
+struct DriftFit {
+    n: f64,
+    sx: f64,
+    sy: f64,
+    sxx: f64,
+    sxy: f64,
+    last_y: Option<f64>,
+    span: f64,
+}
+
+impl DriftFit {
+    fn add(&mut self, x: f64, y: f64) {
+        self.n += 1.0;
+        self.sx += x;
+        self.sy += y;
+        self.sxx += x * x;
+        self.sxy += x * y;
+        self.last_y = Some(y);
+        self.span = x;
+    }
+
+    /// Samples of offset gained per second
+    fn slope(&self) -> Option<f64> {
+        let variance = self.n * self.sxx - self.sx * self.sx;
+        (self.n >= 2.0 && variance > 0.0).then(|| (self.n * self.sxy - self.sx * self.sy) / variance)
+    }
+}
+
+/// Measures how fast the system stream runs against the microphone and corrects it
+#[derive(Debug)]
+pub struct DriftCompensator {
+    sample_rate: f64,
+    mic_samples: u64,
+    system_samples: u64,
+    /// Microphone and system sample counts where the current fit starts
+    segment_start: (u64, u64),
+    next_observation: u64,
+    fit: DriftFit,
+    /// Drift as a fraction (system samples per microphone sample, less one) once trusted
+    drift: Option<f64>,
+    /// Samples inserted (positive) or dropped in the current fit's segment
+    applied: i64,
+    corrected: u64,
+    next_log: u64,
+}
+
+impl DriftCompensator {
+    pub fn new(sample_rate: u32) -> Self {
+        let sample_rate = sample_rate.max(1) as f64;
+        Self {
+            sample_rate,
+            mic_samples: 0,
+            system_samples: 0,
+            segment_start: (0, 0),
+            next_observation: (sample_rate * OBSERVATION_SECS) as u64,
+            fit: DriftFit::default(),
+            drift: None,
+            applied: 0,
+            corrected: 0,
+            next_log: (sample_rate * LOG_INTERVAL_SECS) as u64,
+        }
+    }
+
+    /// Drift of the system stream against the microphone in ppm, once measured
+    pub fn drift_ppm(&self) -> Option<f64> {
+        self.drift.map(|drift| drift * 1e6)
+    }
+
+    /// Takes a block of either stream; system blocks come back corrected, along with their
+    /// stereo side signal
+    pub fn process(
+        &mut self,
+        device_type: &DeviceType,
+        samples: Vec<f32>,
+        side: Option<Vec<f32>>,
+    ) -> (Vec<f32>, Option<Vec<f32>>) {
+        match device_type {
+            DeviceType::Microphone => {
+                self.mic_samples += samples.len() as u64;
+                self.observe();
+                (samples, side)
+            }
+            DeviceType::System => {
+                self.system_samples += samples.len() as u64;
+                self.correct(samples, side)
+            }
+        }
+    }
+
+    fn observe(&mut self) {
+        while self.mic_samples >= self.next_observation {
+            self.next_observation += (self.sample_rate * OBSERVATION_SECS) as u64;
+
+            let mic = (self.mic_samples - self.segment_start.0) as f64;
+            let x = mic / self.sample_rate;
+            let y = (self.system_samples - self.segment_start.1) as f64 - mic;
+            let jumped = self.fit.last_y.is_some_and(|last| (y - last).abs() > GAP_SECS * self.sample_rate);
+            if jumped || self.system_samples == 0 {
+                // Nothing to fit yet, or the streams were interrupted; start over from here
+                self.segment_start = (self.mic_samples, self.system_samples);
+                self.fit = DriftFit::default();
+                self.applied = 0;
+                self.fit.add(0.0, 0.0);
+                continue;
+            }
+
+            self.fit.add(x, y);
+            if self.fit.span >= MIN_FIT_SECS {
+                if let Some(slope) = self.fit.slope() {
+                    let drift = slope / self.sample_rate;
+                    if drift.abs() * 1e6 <= MAX_DRIFT_PPM {
+                        self.drift = Some(drift);
+                    }
+                }
+            }
+
+            if self.mic_samples >= self.next_log {
+                self.next_log += (self.sample_rate * LOG_INTERVAL_SECS) as u64;
+                if let Some(ppm) = self.drift_ppm() {
+                    info!(
+                        "🕐 System audio drifts {:+.1} ppm from the microphone, {} samples corrected",
+                        ppm, self.corrected
+                    );
+                }
+            }
+        }
+    }
+
+    /// Inserts or drops system samples to follow the fitted drift since the segment started
+    fn correct(&mut self, samples: Vec<f32>, side: Option<Vec<f32>>) -> (Vec<f32>, Option<Vec<f32>>) {
+        let Some(drift) = self.drift else {
+            return (samples, side);
+        };
+        let elapsed = self.mic_samples.saturating_sub(self.segment_start.0) as f64;
+        let target = -(drift * elapsed).round() as i64;
+        let limit = ((samples.len() as f64 * MAX_EDITS_PER_SAMPLE).ceil() as i64).min(samples.len() as i64 / 2);
+        let edits = (target - self.applied).clamp(-limit, limit);
+        if edits == 0 {
+            return (samples, side);
+        }
+
+        self.applied += edits;
+        self.corrected += edits.unsigned_abs();
+        let side = side.map(|side| edit(side, edits));
+        (edit(samples, edits), side)
+    }
+}
+
+/// Inserts (`edits` > 0) or drops samples at evenly spread positions; an inserted sample is
+/// the mean of its neighbours, and a dropped one is folded into the sample after it
+fn edit(samples: Vec<f32>, edits: i64) -> Vec<f32> {
+    let count = edits.unsigned_abs() as usize;
+    if count == 0 || samples.len() < 2 {
+        return samples;
+    }
+    let positions: Vec<usize> = (1..=count).map(|k| k * samples.len() / (count + 1)).collect();
+    let mut out = Vec::with_capacity((samples.len() as i64 + edits).max(0) as usize);
+    let mut next = positions.iter().peekable();
+    let mut i = 0;
+    while i < samples.len() {
+        if next.peek() == Some(&&i) && i > 0 {
+            next.next();
+            if edits > 0 {
+                out.push((samples[i - 1] + samples[i]) / 2.0);
+                out.push(samples[i]);
+            } else if i + 1 < samples.len() {
+                out.push((samples[i] + samples[i + 1]) / 2.0);
+                i += 1;
+            }
+        } else {
+            out.push(samples[i]);
+        }
+        i += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48000;
+
+    /// Feeds `secs` of 10ms blocks with the system clock off by `ppm`; returns the system
+    /// samples coming out less the microphone samples
+    fn run(compensator: &mut DriftCompensator, secs: u32, ppm: f64, system_gap_secs: Option<(u32, u32)>) -> i64 {
+        let block = (RATE / 100) as usize;
+        let (mut mic_out, mut system_out, mut system_due) = (0i64, 0i64, 0.0f64);
+        for i in 0..secs * 100 {
+            let (samples, _) = compensator.process(&DeviceType::Microphone, vec![0.1; block], None);
+            mic_out += samples.len() as i64;
+
+            system_due += block as f64 * (1.0 + ppm / 1e6);
+            let len = system_due.floor() as usize;
+            system_due -= len as f64;
+            if system_gap_secs.is_some_and(|(from, to)| (from * 100..to * 100).contains(&i)) {
+                continue;
+            }
+            let (samples, side) = compensator.process(&DeviceType::System, vec![0.1; len], Some(vec![0.0; len]));
+            assert_eq!(side.unwrap().len(), samples.len());
+            system_out += samples.len() as i64;
+        }
+        system_out - mic_out
+    }
+
+    #[test]
+    fn test_drift_is_measured_and_corrected() {
+        let mut compensator = DriftCompensator::new(RATE);
+        // 200 ppm is 9.6 samples a second, 34560 in an hour
+        let offset = run(&mut compensator, 30 * 60, 200.0, None);
+        assert!((compensator.drift_ppm().unwrap() - 200.0).abs() < 1.0, "{:?}", compensator.drift_ppm());
+        assert!(offset.abs() < 500, "{} samples apart", offset);
+
+        let mut compensator = DriftCompensator::new(RATE);
+        let offset = run(&mut compensator, 30 * 60, -80.0, None);
+        assert!((compensator.drift_ppm().unwrap() + 80.0).abs() < 1.0);
+        assert!(offset.abs() < 500, "{} samples apart", offset);
+    }
+
+    #[test]
+    fn test_no_drift_no_edits() {
+        let mut compensator = DriftCompensator::new(RATE);
+        assert_eq!(run(&mut compensator, 10 * 60, 0.0, None), 0);
+        assert_eq!(compensator.corrected, 0);
+    }
+
+    #[test]
+    fn test_gap_starts_a_new_fit() {
+        let mut compensator = DriftCompensator::new(RATE);
+        // The system stream stalls for 3 seconds; the gap isn't mistaken for drift
+        let offset = run(&mut compensator, 20 * 60, 100.0, Some((300, 303)));
+        assert!((compensator.drift_ppm().unwrap() - 100.0).abs() < 2.0, "{:?}", compensator.drift_ppm());
+        assert!((offset + 3 * RATE as i64).abs() < 500, "{} samples apart", offset);
+    }
+
+    #[test]
+    fn test_edit() {
+        assert_eq!(edit(vec![0.0, 1.0, 2.0, 3.0], 1), vec![0.0, 1.0, 1.5, 2.0, 3.0]);
+        assert_eq!(edit(vec![0.0, 1.0, 2.0, 3.0], -1), vec![0.0, 1.0, 2.5]);
+        assert_eq!(edit(vec![0.0; 100], 3).len(), 103);
+        assert_eq!(edit(vec![0.0; 100], -3).len(), 97);
+    }
+}
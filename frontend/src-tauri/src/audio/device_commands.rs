@@ -0,0 +1,13 @@
+// Tauri commands for the device picker
+
+use tauri::command;
+
+use super::devices::{list_audio_devices_detailed, AudioDeviceInfo};
+
+/// List audio devices with sample rates, channel counts, default flag and transport type
+#[command]
+pub async fn list_audio_devices() -> Result<Vec<AudioDeviceInfo>, String> {
+    list_audio_devices_detailed()
+        .await
+        .map_err(|e| format!("Failed to list audio devices: {}", e))
+}
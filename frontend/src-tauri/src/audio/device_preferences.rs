@@ -0,0 +1,153 @@
+// Persisted microphone / system audio device choices
+//
+// Devices are stored by id (`AudioDevice` display form, e.g. "USB Mic (input)") in the
+// Tauri store so the selection survives restarts. A stored device that is no longer
+// present resolves to `None` and the recording falls back to the platform default.
+
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use super::capture::CaptureSource;
+use super::devices::{list_audio_devices, parse_audio_device, AudioDevice};
+
+const STORE_FILE: &str = "device_preferences.json";
+
+fn store_key(source: CaptureSource) -> &'static str {
+    match source {
+        CaptureSource::Microphone => "preferred_microphone",
+        CaptureSource::System => "preferred_system_audio",
+    }
+}
+
+/// Both stored preferences, as returned to the frontend
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DevicePreferences {
+    pub microphone: Option<String>,
+    pub system_audio: Option<String>,
+}
+
+/// Stored device id for a source, if any
+pub fn load_preferred_device<R: Runtime>(app: &AppHandle<R>, source: CaptureSource) -> Result<Option<String>> {
+    let store = app.store(STORE_FILE).map_err(|e| anyhow!("Failed to open {}: {}", STORE_FILE, e))?;
+    Ok(store
+        .get(store_key(source))
+        .and_then(|v| v.as_str().map(str::to_string))
+        .filter(|id| !id.is_empty()))
+}
+
+/// Store (or clear, with `None`) the preferred device for a source
+pub fn store_preferred_device<R: Runtime>(
+    app: &AppHandle<R>,
+    source: CaptureSource,
+    device_id: Option<String>,
+) -> Result<()> {
+    let store = app.store(STORE_FILE).map_err(|e| anyhow!("Failed to open {}: {}", STORE_FILE, e))?;
+
+    match device_id {
+        Some(id) => {
+            // Reject ids the recorder could never parse
+            parse_audio_device(&id)?;
+            info!("💾 Preferred {:?} device set to '{}'", source, id);
+            store.set(store_key(source), serde_json::Value::String(id));
+        }
+        None => {
+            info!("💾 Preferred {:?} device cleared", source);
+            store.delete(store_key(source));
+        }
+    }
+
+    store.save().map_err(|e| anyhow!("Failed to save {}: {}", STORE_FILE, e))
+}
+
+/// Resolve the stored device for a source against the devices currently present
+pub async fn resolve_preferred_device<R: Runtime>(
+    app: &AppHandle<R>,
+    source: CaptureSource,
+) -> Option<Arc<AudioDevice>> {
+    let id = match load_preferred_device(app, source) {
+        Ok(Some(id)) => id,
+        Ok(None) => return None,
+        Err(e) => {
+            warn!("⚠️ Could not read preferred {:?} device: {}", source, e);
+            return None;
+        }
+    };
+
+    let device = match parse_audio_device(&id) {
+        Ok(device) => device,
+        Err(e) => {
+            warn!("⚠️ Ignoring invalid preferred {:?} device '{}': {}", source, id, e);
+            return None;
+        }
+    };
+
+    let available = list_audio_devices().await.unwrap_or_default();
+    if is_device_present(&device, &available) {
+        info!("🎯 Using preferred {:?} device '{}'", source, device.name);
+        Some(Arc::new(device))
+    } else {
+        warn!("⚠️ Preferred {:?} device '{}' is not connected, falling back to default", source, device.name);
+        None
+    }
+}
+
+fn is_device_present(device: &AudioDevice, available: &[AudioDevice]) -> bool {
+    available.iter().any(|d| d == device)
+}
+
+#[tauri::command]
+pub async fn get_device_preferences<R: Runtime>(app: AppHandle<R>) -> Result<DevicePreferences, String> {
+    Ok(DevicePreferences {
+        microphone: load_preferred_device(&app, CaptureSource::Microphone).map_err(|e| e.to_string())?,
+        system_audio: load_preferred_device(&app, CaptureSource::System).map_err(|e| e.to_string())?,
+    })
+}
+
+#[tauri::command]
+pub async fn get_preferred_device<R: Runtime>(
+    app: AppHandle<R>,
+    source: String,
+) -> Result<Option<String>, String> {
+    let source = CaptureSource::from_string(&source)
+        .ok_or_else(|| format!("Invalid capture source: {}", source))?;
+    load_preferred_device(&app, source)
+        .map_err(|e| format!("Failed to read preferred device: {}", e))
+}
+
+#[tauri::command]
+pub async fn set_preferred_device<R: Runtime>(
+    app: AppHandle<R>,
+    source: String,
+    device_id: Option<String>,
+) -> Result<(), String> {
+    let source = CaptureSource::from_string(&source)
+        .ok_or_else(|| format!("Invalid capture source: {}", source))?;
+    store_preferred_device(&app, source, device_id)
+        .map_err(|e| format!("Failed to save preferred device: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::devices::DeviceType;
+
+    #[test]
+    fn test_preferred_device_presence_checks_type() {
+        let available = vec![
+            AudioDevice::new("USB Mic".to_string(), DeviceType::Input),
+            AudioDevice::new("Speakers".to_string(), DeviceType::Output),
+        ];
+        assert!(is_device_present(&parse_audio_device("USB Mic (input)").unwrap(), &available));
+        assert!(!is_device_present(&parse_audio_device("USB Mic (output)").unwrap(), &available));
+        assert!(!is_device_present(&parse_audio_device("AirPods (input)").unwrap(), &available));
+    }
+
+    #[test]
+    fn test_store_keys_are_distinct() {
+        assert_ne!(store_key(CaptureSource::Microphone), store_key(CaptureSource::System));
+    }
+}
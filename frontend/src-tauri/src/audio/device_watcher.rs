@@ -0,0 +1,220 @@
+// Audio device hot-plug watcher
+//
+// Watches the whole device list (not just the devices in use, which is what
+// `AudioDeviceMonitor` does) and the system default input/output. Changes are emitted to
+// the frontend as `audio-device-changed` events. When the active microphone disappears, or
+// the default input changes while the active microphone is gone, the running recording is
+// moved to the new default input without stopping the session.
+//
+// cpal exposes no device notifications, so the watcher diffs snapshots on a short poll.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use super::devices::{default_input_device, default_output_device, list_audio_devices, AudioDevice, DeviceType};
+
+/// Event name emitted to the frontend for every device change
+pub const DEVICE_CHANGED_EVENT: &str = "audio-device-changed";
+
+/// Event name emitted after the recording moved to another microphone
+pub const DEVICE_FAILOVER_EVENT: &str = "audio-device-failover";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1500);
+
+/// A single device change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeviceChange {
+    Added { name: String, device_type: DeviceType },
+    Removed { name: String, device_type: DeviceType },
+    DefaultInputChanged { previous: Option<String>, current: Option<String> },
+    DefaultOutputChanged { previous: Option<String>, current: Option<String> },
+}
+
+/// Point-in-time view of the audio devices
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceSnapshot {
+    pub devices: Vec<AudioDevice>,
+    pub default_input: Option<String>,
+    pub default_output: Option<String>,
+}
+
+impl DeviceSnapshot {
+    pub async fn capture() -> anyhow::Result<Self> {
+        Ok(Self {
+            devices: list_audio_devices().await?,
+            default_input: default_input_device().ok().map(|d| d.name),
+            default_output: default_output_device().ok().map(|d| d.name),
+        })
+    }
+
+    pub fn contains_input(&self, name: &str) -> bool {
+        self.devices.iter().any(|d| d.device_type == DeviceType::Input && d.name == name)
+    }
+}
+
+/// Changes between two snapshots, removals first so failover sees them before additions
+pub fn diff_snapshots(previous: &DeviceSnapshot, current: &DeviceSnapshot) -> Vec<DeviceChange> {
+    let before: HashSet<&AudioDevice> = previous.devices.iter().collect();
+    let after: HashSet<&AudioDevice> = current.devices.iter().collect();
+
+    let mut changes: Vec<DeviceChange> = previous
+        .devices
+        .iter()
+        .filter(|d| !after.contains(d))
+        .map(|d| DeviceChange::Removed { name: d.name.clone(), device_type: d.device_type.clone() })
+        .collect();
+
+    changes.extend(
+        current
+            .devices
+            .iter()
+            .filter(|d| !before.contains(d))
+            .map(|d| DeviceChange::Added { name: d.name.clone(), device_type: d.device_type.clone() }),
+    );
+
+    if previous.default_input != current.default_input {
+        changes.push(DeviceChange::DefaultInputChanged {
+            previous: previous.default_input.clone(),
+            current: current.default_input.clone(),
+        });
+    }
+    if previous.default_output != current.default_output {
+        changes.push(DeviceChange::DefaultOutputChanged {
+            previous: previous.default_output.clone(),
+            current: current.default_output.clone(),
+        });
+    }
+
+    changes
+}
+
+/// Microphone the recording should move to, if the active one is gone
+///
+/// Returns the current default input when the active microphone is no longer listed and
+/// the default is a different, present device.
+pub fn failover_target(active_microphone: &str, snapshot: &DeviceSnapshot) -> Option<String> {
+    if snapshot.contains_input(active_microphone) {
+        return None;
+    }
+    snapshot
+        .default_input
+        .as_ref()
+        .filter(|name| name.as_str() != active_microphone && snapshot.contains_input(name))
+        .cloned()
+}
+
+/// Background device watcher
+pub struct DeviceWatcher {
+    running: Arc<AtomicBool>,
+}
+
+impl DeviceWatcher {
+    fn new() -> Self {
+        Self { running: Arc::new(AtomicBool::new(false)) }
+    }
+
+    /// Start watching; calling again while running is a no-op
+    pub fn start<R: Runtime>(&self, app: AppHandle<R>) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            debug!("Device watcher already running");
+            return;
+        }
+
+        let running = self.running.clone();
+        tauri::async_runtime::spawn(async move {
+            info!("🎧 Device watcher started");
+            let mut previous = DeviceSnapshot::capture().await.unwrap_or_default();
+
+            while running.load(Ordering::SeqCst) {
+                tokio::time::sleep(POLL_INTERVAL).await;
+
+                let current = match DeviceSnapshot::capture().await {
+                    Ok(snapshot) => snapshot,
+                    Err(e) => {
+                        warn!("⚠️ Device watcher: failed to list devices: {}", e);
+                        continue;
+                    }
+                };
+
+                let changes = diff_snapshots(&previous, &current);
+                if !changes.is_empty() {
+                    for change in &changes {
+                        info!("🎧 Device change: {:?}", change);
+                        if let Err(e) = app.emit(DEVICE_CHANGED_EVENT, change) {
+                            warn!("Failed to emit {}: {}", DEVICE_CHANGED_EVENT, e);
+                        }
+                    }
+                    super::recording_commands::fail_over_microphone_if_needed(&app, &current).await;
+                }
+
+                previous = current;
+            }
+
+            info!("🎧 Device watcher stopped");
+        });
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+/// Global device watcher started at app setup
+pub static DEVICE_WATCHER: Lazy<DeviceWatcher> = Lazy::new(DeviceWatcher::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(name: &str) -> AudioDevice {
+        AudioDevice::new(name.to_string(), DeviceType::Input)
+    }
+
+    fn snapshot(devices: Vec<AudioDevice>, default_input: Option<&str>) -> DeviceSnapshot {
+        DeviceSnapshot {
+            devices,
+            default_input: default_input.map(str::to_string),
+            default_output: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_removal_addition_and_default_change() {
+        let before = snapshot(vec![input("AirPods"), input("MacBook Pro Microphone")], Some("AirPods"));
+        let after = snapshot(vec![input("MacBook Pro Microphone"), input("USB Mic")], Some("MacBook Pro Microphone"));
+
+        let changes = diff_snapshots(&before, &after);
+        assert_eq!(changes[0], DeviceChange::Removed { name: "AirPods".into(), device_type: DeviceType::Input });
+        assert_eq!(changes[1], DeviceChange::Added { name: "USB Mic".into(), device_type: DeviceType::Input });
+        assert!(matches!(changes[2], DeviceChange::DefaultInputChanged { .. }));
+        assert_eq!(changes.len(), 3);
+    }
+
+    #[test]
+    fn test_no_changes_for_identical_snapshots() {
+        let s = snapshot(vec![input("USB Mic")], Some("USB Mic"));
+        assert!(diff_snapshots(&s, &s.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_failover_target() {
+        let s = snapshot(vec![input("MacBook Pro Microphone")], Some("MacBook Pro Microphone"));
+        assert_eq!(failover_target("AirPods", &s), Some("MacBook Pro Microphone".to_string()));
+        // Active microphone still present: stay put
+        assert_eq!(failover_target("MacBook Pro Microphone", &s), None);
+        // Default not yet settled on a present device: wait for the next poll
+        let unsettled = snapshot(vec![input("USB Mic")], Some("AirPods"));
+        assert_eq!(failover_target("AirPods", &unsettled), None);
+    }
+}
@@ -8,6 +8,7 @@ use log::{info, warn};
 
 use super::devices::AudioDevice;
 use super::device_detection::{InputDeviceKind, calculate_buffer_timeout};
+use super::resample::ResamplePath;
 
 /// Log comprehensive device capabilities and detection results
 ///
@@ -258,6 +259,16 @@ pub fn log_mixer_status(
     info!("   Silence inserted:  {:.1}ms total", silence_inserted_ms);
 }
 
+/// Log the sample rate conversion a pipeline stage uses
+pub fn log_resample_path(stage: &str, path: &ResamplePath) {
+    if path.from_rate == path.to_rate {
+        info!("🔄 Resampling [{}]: {} Hz passthrough", stage, path.from_rate);
+    } else {
+        info!("🔄 Resampling [{}]: {} Hz → {} Hz (ratio {:.4}), {}, delay {} samples",
+              stage, path.from_rate, path.to_rate, path.ratio, path.method, path.delay_samples);
+    }
+}
+
 /// Log performance metrics summary
 pub fn log_performance_summary(
     total_chunks_processed: u64,
@@ -0,0 +1,229 @@
+// Free disk space before and during a recording
+//
+// Audio is written to the meeting folder as it's captured, so a disk that fills up mid-meeting
+// loses the rest of it. A recording isn't started when the recordings folder's disk is below
+// the stop threshold, and the user is warned when it's below the warning threshold. While
+// recording, a monitor checks the space left: it warns once when it runs low (again only after
+// space was freed and ran low again), and below the stop threshold asks the UI to stop and
+// save the recording before the disk is full.
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sysinfo::Disks;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use super::recording_commands::RECORDING_MANAGER;
+use super::session;
+use super::recording_preferences::{get_default_recordings_folder, load_recording_preferences};
+use crate::error::{AppError, ErrorCode};
+use crate::notifications::commands::notify_in_background;
+use crate::notifications::types::Notification;
+
+/// Emitted once when the disk is nearly full; the UI stops the recording
+pub const DISK_STOP_EVENT: &str = "recording-disk-stop";
+
+const CHECK_INTERVAL_SECS: u64 = 60;
+
+const MB: u64 = 1024 * 1024;
+/// Lowest stop threshold; the recording still has to be finalized and saved
+const MIN_STOP_MB: u64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskGuardSettings {
+    /// Below this many MB the user is warned; an hour of audio takes about 100 MB
+    pub warn_below_mb: u64,
+    /// Below this many MB recordings don't start, and one in progress is stopped
+    pub stop_below_mb: u64,
+    /// Stop a recording in progress at the stop threshold; otherwise only warn
+    pub auto_stop: bool,
+}
+
+impl Default for DiskGuardSettings {
+    fn default() -> Self {
+        Self { warn_below_mb: 1024, stop_below_mb: 200, auto_stop: true }
+    }
+}
+
+impl DiskGuardSettings {
+    pub fn normalized(self) -> Self {
+        let stop_below_mb = self.stop_below_mb.max(MIN_STOP_MB);
+        Self { stop_below_mb, warn_below_mb: self.warn_below_mb.max(stop_below_mb), ..self }
+    }
+
+    fn warn_bytes(&self) -> u64 {
+        self.warn_below_mb * MB
+    }
+
+    fn stop_bytes(&self) -> u64 {
+        self.stop_below_mb * MB
+    }
+}
+
+static DISK_GUARD_SETTINGS: Lazy<RwLock<DiskGuardSettings>> = Lazy::new(|| RwLock::new(DiskGuardSettings::default()));
+
+pub fn get_disk_guard_settings() -> DiskGuardSettings {
+    *DISK_GUARD_SETTINGS.read().unwrap()
+}
+
+/// A recording in progress is held to the new limits from the next disk check on
+pub fn set_disk_guard_settings(settings: DiskGuardSettings) -> DiskGuardSettings {
+    let settings = settings.normalized();
+    *DISK_GUARD_SETTINGS.write().unwrap() = settings;
+    info!(
+        "💾 Disk guard: warn below {} MB, {} below {} MB",
+        settings.warn_below_mb,
+        if settings.auto_stop { "stop" } else { "refuse to start" },
+        settings.stop_below_mb
+    );
+    settings
+}
+
+/// Bytes available on the disk holding `path`: the mounted disk with the longest mount point
+/// containing it
+fn available_on(disks: &[(PathBuf, u64)], path: &Path) -> Option<u64> {
+    disks
+        .iter()
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.components().count())
+        .map(|(_, available)| *available)
+}
+
+/// Bytes available on the disk holding `path`, if it's on a known disk
+pub fn available_space(path: &Path) -> Option<u64> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let disks: Vec<(PathBuf, u64)> = Disks::new_with_refreshed_list()
+        .list()
+        .iter()
+        .map(|disk| (disk.mount_point().to_path_buf(), disk.available_space()))
+        .collect();
+    available_on(&disks, &path)
+}
+
+/// Whether to warn now, given whether the user was already warned; updates `warned`.
+/// The warning is armed again once twice the threshold is free.
+fn should_warn(available: u64, threshold: u64, warned: &mut bool) -> bool {
+    if available < threshold && !*warned {
+        *warned = true;
+        return true;
+    }
+    if available >= 2 * threshold {
+        *warned = false;
+    }
+    false
+}
+
+/// The folder new recordings are saved to, and the bytes free on its disk if it's known
+pub async fn recordings_disk_space<R: Runtime>(app: &AppHandle<R>) -> (PathBuf, Option<u64>) {
+    let folder = match load_recording_preferences(app).await {
+        Ok(preferences) => preferences.save_folder,
+        Err(_) => get_default_recordings_folder(),
+    };
+    // The folder may not exist yet; its disk is the one of its closest existing ancestor
+    let available = folder.ancestors().find(|path| path.exists()).and_then(available_space);
+    (folder, available)
+}
+
+/// Refuses to start a recording on a nearly full disk, and warns when space is low
+pub async fn ensure_space_to_record<R: Runtime>(app: &AppHandle<R>) -> Result<(), AppError> {
+    let (folder, available) = recordings_disk_space(app).await;
+    let Some(available) = available else {
+        return Ok(());
+    };
+
+    let settings = get_disk_guard_settings();
+    if available < settings.stop_bytes() {
+        warn!("💾 Not starting: only {} MB left on the disk of {:?}", available / MB, folder);
+        return Err(AppError::new(
+            ErrorCode::DiskFull,
+            format!("Only {} MB of disk space left for the recording", available / MB),
+        ));
+    }
+    if available < settings.warn_bytes() {
+        warn!("💾 Starting with only {} MB left on the disk of {:?}", available / MB, folder);
+        notify_in_background(app, Notification::disk_space_low(available / MB));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DiskStopPayload {
+    available_mb: u64,
+    stop_below_mb: u64,
+}
+
+/// Watch the free space of the active recording's disk
+pub fn spawn_disk_space_monitor<R: Runtime>(app: AppHandle<R>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+        let mut warned = false;
+
+        while session::phase().is_recording() {
+            interval.tick().await;
+
+            let folder = match RECORDING_MANAGER.lock() {
+                Ok(guard) => match guard.as_ref() {
+                    Some(manager) => manager.get_meeting_folder().unwrap_or_else(get_default_recordings_folder),
+                    None => break,
+                },
+                Err(_) => break,
+            };
+
+            let Some(available) = available_space(&folder) else {
+                continue;
+            };
+            let settings = get_disk_guard_settings();
+            if settings.auto_stop && available < settings.stop_bytes() {
+                warn!("💾 Only {} MB left on the disk of {:?}, requesting stop", available / MB, folder);
+                let payload = DiskStopPayload { available_mb: available / MB, stop_below_mb: settings.stop_below_mb };
+                if let Err(e) = app.emit(DISK_STOP_EVENT, payload) {
+                    warn!("Failed to emit {}: {}", DISK_STOP_EVENT, e);
+                }
+                notify_in_background(&app, Notification::disk_space_stopped(available / MB));
+                break;
+            }
+            if should_warn(available, settings.warn_bytes(), &mut warned) {
+                warn!("💾 Only {} MB left on the disk of {:?}", available / MB, folder);
+                notify_in_background(&app, Notification::disk_space_low(available / MB));
+            }
+        }
+
+        info!("Disk space monitor stopped");
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_available_on_picks_deepest_mount() {
+        let disks = vec![(PathBuf::from("/"), 10 * MB), (PathBuf::from("/home"), 500 * MB)];
+        assert_eq!(available_on(&disks, Path::new("/home/me/meetily-recordings")), Some(500 * MB));
+        assert_eq!(available_on(&disks, Path::new("/tmp")), Some(10 * MB));
+        assert_eq!(available_on(&disks[1..], Path::new("/tmp")), None);
+    }
+
+    #[test]
+    fn test_should_warn_once_until_recovered() {
+        let threshold = 1024 * MB;
+        let mut warned = false;
+        assert!(!should_warn(5000 * MB, threshold, &mut warned));
+        assert!(should_warn(900 * MB, threshold, &mut warned));
+        assert!(!should_warn(800 * MB, threshold, &mut warned));
+        assert!(!should_warn(1500 * MB, threshold, &mut warned));
+        assert!(!should_warn(900 * MB, threshold, &mut warned));
+        assert!(!should_warn(3000 * MB, threshold, &mut warned));
+        assert!(should_warn(900 * MB, threshold, &mut warned));
+    }
+
+    #[test]
+    fn test_settings_normalized() {
+        let settings = DiskGuardSettings { warn_below_mb: 50, stop_below_mb: 10, auto_stop: true }.normalized();
+        assert_eq!(settings.stop_below_mb, MIN_STOP_MB);
+        assert_eq!(settings.warn_below_mb, MIN_STOP_MB);
+        assert_eq!(DiskGuardSettings::default().normalized(), DiskGuardSettings::default());
+    }
+}
@@ -0,0 +1,299 @@
+// Acoustic echo cancellation between system audio and the microphone
+//
+// On laptop speakers the microphone picks up the remote participants a second time, so
+// their speech would appear in the "me" channel and be transcribed twice. System audio is
+// exactly what the speakers play, which makes it a clean reference: an adaptive filter
+// learns the speaker-to-mic echo path and subtracts the estimated echo from the mic.
+//
+// The filter is a partitioned-block frequency-domain adaptive filter (overlap-save, the
+// same family as the Speex MDF canceller) covering about 170ms of echo tail at 48kHz.
+// Adaptation is frozen while the local user talks over the remote side (Geigel
+// double-talk detector) so the filter does not learn to cancel the user's own voice.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use log::{info, warn};
+use realfft::num_complex::Complex32;
+use realfft::{ComplexToReal, RealFftPlanner, RealToComplex};
+
+/// Samples per processing block (10ms at 48kHz)
+const BLOCK_SIZE: usize = 480;
+
+/// Filter partitions; tail length = BLOCK_SIZE * PARTITIONS samples
+const PARTITIONS: usize = 16;
+
+/// Adaptation step size
+const STEP_SIZE: f32 = 0.5;
+
+/// Smoothing of the reference power estimate
+const POWER_SMOOTHING: f32 = 0.9;
+
+/// Geigel threshold: mic peaks above this fraction of the recent reference peak mean double-talk
+const DOUBLE_TALK_THRESHOLD: f32 = 0.5;
+
+/// Reference quieter than this (peak) carries no echo worth learning from
+const MIN_REFERENCE_PEAK: f32 = 1e-4;
+
+static ECHO_CANCELLATION_ENABLED: AtomicBool = AtomicBool::new(true);
+
+pub fn is_echo_cancellation_enabled() -> bool {
+    ECHO_CANCELLATION_ENABLED.load(Ordering::SeqCst)
+}
+
+pub fn set_echo_cancellation_enabled(enabled: bool) {
+    ECHO_CANCELLATION_ENABLED.store(enabled, Ordering::SeqCst);
+    info!("🔁 Echo cancellation {}", if enabled { "enabled" } else { "disabled" });
+}
+
+/// Removes system-audio echo from the microphone signal
+///
+/// Output is delayed by one block (10ms) relative to the input so that arbitrary input
+/// lengths can be processed in whole blocks.
+pub struct EchoCanceller {
+    forward: Arc<dyn RealToComplex<f32>>,
+    inverse: Arc<dyn ComplexToReal<f32>>,
+    /// Frequency-domain filter weights, one set per partition
+    weights: Vec<Vec<Complex32>>,
+    /// Spectra of the most recent reference blocks, newest first
+    reference_spectra: VecDeque<Vec<Complex32>>,
+    /// Per-block reference peaks covering the filter tail, for double-talk detection
+    reference_peaks: VecDeque<f32>,
+    previous_reference: Vec<f32>,
+    power: Vec<f32>,
+    pending_mic: Vec<f32>,
+    pending_reference: Vec<f32>,
+    output: VecDeque<f32>,
+    time_buffer: Vec<f32>,
+    freq_buffer: Vec<Complex32>,
+    double_talk_blocks: u64,
+}
+
+impl EchoCanceller {
+    pub fn new() -> Self {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let forward = planner.plan_fft_forward(2 * BLOCK_SIZE);
+        let inverse = planner.plan_fft_inverse(2 * BLOCK_SIZE);
+        let bins = BLOCK_SIZE + 1;
+
+        Self {
+            time_buffer: forward.make_input_vec(),
+            freq_buffer: forward.make_output_vec(),
+            forward,
+            inverse,
+            weights: vec![vec![Complex32::default(); bins]; PARTITIONS],
+            reference_spectra: VecDeque::from(vec![vec![Complex32::default(); bins]; PARTITIONS]),
+            reference_peaks: VecDeque::from(vec![0.0; PARTITIONS]),
+            previous_reference: vec![0.0; BLOCK_SIZE],
+            power: vec![0.0; bins],
+            pending_mic: Vec::with_capacity(BLOCK_SIZE),
+            pending_reference: Vec::with_capacity(BLOCK_SIZE),
+            output: VecDeque::from(vec![0.0; BLOCK_SIZE]),
+            double_talk_blocks: 0,
+        }
+    }
+
+    /// Cancel echo of `reference` (system audio) from `mic`; returns `mic.len()` samples
+    pub fn process(&mut self, mic: &[f32], reference: &[f32]) -> Vec<f32> {
+        for (i, &sample) in mic.iter().enumerate() {
+            self.pending_mic.push(sample);
+            self.pending_reference.push(reference.get(i).copied().unwrap_or(0.0));
+
+            if self.pending_mic.len() == BLOCK_SIZE {
+                let mic_block = std::mem::take(&mut self.pending_mic);
+                let reference_block = std::mem::take(&mut self.pending_reference);
+                let cleaned = self.process_block(&mic_block, &reference_block);
+                self.output.extend(cleaned);
+                self.pending_mic = mic_block;
+                self.pending_mic.clear();
+                self.pending_reference = reference_block;
+                self.pending_reference.clear();
+            }
+        }
+
+        self.output.drain(..mic.len()).collect()
+    }
+
+    fn process_block(&mut self, mic: &[f32], reference: &[f32]) -> Vec<f32> {
+        let fft_len = (2 * BLOCK_SIZE) as f32;
+
+        // Spectrum of [previous block, current block] of the reference
+        self.time_buffer[..BLOCK_SIZE].copy_from_slice(&self.previous_reference);
+        self.time_buffer[BLOCK_SIZE..].copy_from_slice(reference);
+        self.previous_reference.copy_from_slice(reference);
+        let spectrum = self.forward_fft();
+
+        self.reference_spectra.pop_back();
+        self.reference_spectra.push_front(spectrum);
+        self.reference_peaks.pop_back();
+        self.reference_peaks.push_front(reference.iter().fold(0.0f32, |m, s| m.max(s.abs())));
+
+        // Echo estimate: sum of all partitions, last half of the circular convolution
+        let mut estimate = vec![Complex32::default(); BLOCK_SIZE + 1];
+        for (weights, spectrum) in self.weights.iter().zip(&self.reference_spectra) {
+            for ((acc, w), x) in estimate.iter_mut().zip(weights).zip(spectrum) {
+                *acc += w * x;
+            }
+        }
+        let echo = self.inverse_fft(estimate);
+        let error: Vec<f32> = mic
+            .iter()
+            .zip(&echo[BLOCK_SIZE..])
+            .map(|(d, y)| d - y / fft_len)
+            .collect();
+
+        let reference_peak = self.reference_peaks.iter().fold(0.0f32, |m, &p| m.max(p));
+        let mic_peak = mic.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        let double_talk = mic_peak > DOUBLE_TALK_THRESHOLD * reference_peak;
+        if double_talk {
+            self.double_talk_blocks += 1;
+        }
+
+        if reference_peak > MIN_REFERENCE_PEAK && !double_talk {
+            self.adapt(&error);
+        }
+
+        if error.iter().any(|s| !s.is_finite()) {
+            warn!("⚠️ Echo canceller diverged, resetting filter");
+            self.reset_filter();
+            return mic.to_vec();
+        }
+
+        error
+    }
+
+    /// Constrained NLMS update of every partition
+    fn adapt(&mut self, error: &[f32]) {
+        self.time_buffer[..BLOCK_SIZE].fill(0.0);
+        self.time_buffer[BLOCK_SIZE..].copy_from_slice(error);
+        let error_spectrum = self.forward_fft();
+
+        // Total reference power over the tail, smoothed per bin
+        for (bin, power) in self.power.iter_mut().enumerate() {
+            let current: f32 = self.reference_spectra.iter().map(|s| s[bin].norm_sqr()).sum();
+            *power = POWER_SMOOTHING * *power + (1.0 - POWER_SMOOTHING) * current;
+        }
+        let floor = self.power.iter().sum::<f32>() / self.power.len() as f32 * 1e-3 + 1e-9;
+
+        for p in 0..PARTITIONS {
+            let gradient: Vec<Complex32> = self.reference_spectra[p]
+                .iter()
+                .zip(&error_spectrum)
+                .zip(&self.power)
+                .map(|((x, e), power)| x.conj() * e / (power + floor))
+                .collect();
+
+            // Keep the filter causal: zero the second half of its impulse response
+            let mut impulse = self.inverse_fft(gradient);
+            impulse[BLOCK_SIZE..].fill(0.0);
+            self.time_buffer.copy_from_slice(&impulse);
+            let constrained = self.forward_fft();
+
+            let scale = STEP_SIZE / (2 * BLOCK_SIZE) as f32;
+            for (w, g) in self.weights[p].iter_mut().zip(constrained) {
+                *w += g * scale;
+            }
+        }
+    }
+
+    fn reset_filter(&mut self) {
+        for weights in &mut self.weights {
+            weights.fill(Complex32::default());
+        }
+        self.power.fill(0.0);
+    }
+
+    /// Forward FFT of `time_buffer` (which is used as scratch)
+    fn forward_fft(&mut self) -> Vec<Complex32> {
+        if let Err(e) = self.forward.process(&mut self.time_buffer, &mut self.freq_buffer) {
+            warn!("Echo canceller FFT failed: {}", e);
+        }
+        self.freq_buffer.clone()
+    }
+
+    /// Unnormalized inverse FFT
+    fn inverse_fft(&mut self, mut spectrum: Vec<Complex32>) -> Vec<f32> {
+        // A real signal has purely real DC and Nyquist bins
+        spectrum[0].im = 0.0;
+        spectrum[BLOCK_SIZE].im = 0.0;
+        let mut output = self.inverse.make_output_vec();
+        if let Err(e) = self.inverse.process(&mut spectrum, &mut output) {
+            warn!("Echo canceller inverse FFT failed: {}", e);
+        }
+        output
+    }
+
+    /// Blocks in which adaptation was frozen because both sides were talking
+    pub fn double_talk_blocks(&self) -> u64 {
+        self.double_talk_blocks
+    }
+}
+
+impl Default for EchoCanceller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[tauri::command]
+pub async fn set_echo_cancellation(enabled: bool) -> Result<(), String> {
+    set_echo_cancellation_enabled(enabled);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_echo_cancellation() -> Result<bool, String> {
+    Ok(is_echo_cancellation_enabled())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    fn energy(samples: &[f32]) -> f32 {
+        samples.iter().map(|s| s * s).sum::<f32>() / samples.len().max(1) as f32
+    }
+
+    #[test]
+    fn test_output_length_matches_input() {
+        let mut aec = EchoCanceller::new();
+        for len in [1, 100, 480, 777, 28800] {
+            assert_eq!(aec.process(&vec![0.1; len], &vec![0.0; len]).len(), len);
+        }
+    }
+
+    #[test]
+    fn test_cancels_delayed_echo() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let reference: Vec<f32> = (0..48000 * 6).map(|_| rng.gen_range(-0.3..0.3)).collect();
+
+        // Speaker-to-mic path: 2ms delay and 40% gain
+        let delay = 96;
+        let mic: Vec<f32> = (0..reference.len())
+            .map(|i| if i >= delay { 0.4 * reference[i - delay] } else { 0.0 })
+            .collect();
+
+        let mut aec = EchoCanceller::new();
+        let output: Vec<f32> = mic
+            .chunks(4800)
+            .zip(reference.chunks(4800))
+            .flat_map(|(m, r)| aec.process(m, r))
+            .collect();
+
+        // Compare the last second, after convergence (output lags by one block)
+        let tail = 48000;
+        let echo_energy = energy(&mic[mic.len() - tail - BLOCK_SIZE..mic.len() - BLOCK_SIZE]);
+        let residual_energy = energy(&output[output.len() - tail..]);
+        let erle_db = 10.0 * (echo_energy / residual_energy).log10();
+        assert!(erle_db > 20.0, "echo return loss enhancement only {:.1} dB", erle_db);
+    }
+
+    #[test]
+    fn test_passes_local_speech_without_reference() {
+        let mut aec = EchoCanceller::new();
+        let mic: Vec<f32> = (0..4800).map(|i| (i as f32 * 0.05).sin() * 0.5).collect();
+        let output = aec.process(&mic, &[]);
+        assert_eq!(&output[BLOCK_SIZE..], &mic[..mic.len() - BLOCK_SIZE]);
+    }
+}
@@ -1,13 +1,135 @@
 use super::ffmpeg::find_ffmpeg_path; // Correct path to encode module
 use super::AudioDevice;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use std::io::Write;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::{
     path::PathBuf,
     process::{Command, Stdio},
 };
 use tracing::{debug, error};
 
+/// Container/codec used for saved recordings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    /// Opus in Ogg, roughly 15 MB per hour of speech at 32 kbps
+    #[default]
+    Opus,
+    /// Lossless, typically half the size of WAV
+    Flac,
+    /// AAC-LC in MP4, the format used before encoding became configurable
+    Aac,
+    /// Uncompressed 16-bit PCM for maximum compatibility
+    Wav,
+}
+
+impl AudioFormat {
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "opus" | "ogg" => Some(Self::Opus),
+            "flac" => Some(Self::Flac),
+            "aac" | "mp4" | "m4a" => Some(Self::Aac),
+            "wav" => Some(Self::Wav),
+            _ => None,
+        }
+    }
+
+    /// File extension of the saved recording
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Opus => "opus",
+            Self::Flac => "flac",
+            Self::Aac => "mp4",
+            Self::Wav => "wav",
+        }
+    }
+
+    /// Whether the bitrate setting applies
+    pub fn is_lossy(&self) -> bool {
+        matches!(self, Self::Opus | Self::Aac)
+    }
+
+    pub fn default_bitrate_kbps(&self) -> u32 {
+        match self {
+            Self::Opus => 32,
+            Self::Aac => 192,
+            Self::Flac | Self::Wav => 0,
+        }
+    }
+
+    fn bitrate_range_kbps(&self) -> (u32, u32) {
+        match self {
+            Self::Opus => (6, 256),
+            Self::Aac => (32, 320),
+            Self::Flac | Self::Wav => (0, 0),
+        }
+    }
+
+    /// FFmpeg codec and muxer arguments
+    pub(super) fn ffmpeg_args(&self, bitrate_kbps: u32) -> Vec<String> {
+        let bitrate = format!("{}k", bitrate_kbps);
+        let args: Vec<&str> = match self {
+            Self::Opus => vec!["-c:a", "libopus", "-b:a", &bitrate, "-application", "voip", "-f", "opus"],
+            Self::Flac => vec!["-c:a", "flac", "-f", "flac"],
+            Self::Aac => vec![
+                "-c:a", "aac",
+                "-b:a", &bitrate,
+                "-profile:a", "aac_low", // Use AAC-LC profile for better compatibility
+                "-movflags", "+faststart", // Optimize for web streaming
+                "-f", "mp4",
+            ],
+            Self::Wav => vec!["-c:a", "pcm_s16le", "-f", "wav"],
+        };
+        args.into_iter().map(String::from).collect()
+    }
+}
+
+/// User-selected encoding for saved recordings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncodingSettings {
+    pub format: AudioFormat,
+    /// Target bitrate for lossy formats; ignored for FLAC and WAV
+    pub bitrate_kbps: u32,
+}
+
+impl Default for EncodingSettings {
+    fn default() -> Self {
+        Self::for_format(AudioFormat::default())
+    }
+}
+
+impl EncodingSettings {
+    pub fn for_format(format: AudioFormat) -> Self {
+        Self { format, bitrate_kbps: format.default_bitrate_kbps() }
+    }
+
+    /// Clamp the bitrate into the range the codec supports
+    pub fn normalized(self) -> Self {
+        let (min, max) = self.format.bitrate_range_kbps();
+        let bitrate_kbps = if self.format.is_lossy() {
+            self.bitrate_kbps.clamp(min, max)
+        } else {
+            0
+        };
+        Self { format: self.format, bitrate_kbps }
+    }
+}
+
+/// Encoding used for the next recording
+static ENCODING_SETTINGS: Lazy<RwLock<EncodingSettings>> =
+    Lazy::new(|| RwLock::new(EncodingSettings::default()));
+
+pub fn get_encoding_settings() -> EncodingSettings {
+    *ENCODING_SETTINGS.read().unwrap()
+}
+
+pub fn set_encoding_settings(settings: EncodingSettings) {
+    let settings = settings.normalized();
+    *ENCODING_SETTINGS.write().unwrap() = settings;
+}
+
 pub struct AudioInput {
     pub data: Arc<Vec<f32>>,
     pub sample_rate: u32,
@@ -15,11 +137,23 @@ pub struct AudioInput {
     pub device: Arc<AudioDevice>,
 }
 
+/// Encode as AAC in MP4 (192 kbps)
 pub fn encode_single_audio(
     data: &[u8],
     sample_rate: u32,
     channels: u16,
     output_path: &PathBuf,
+) -> anyhow::Result<()> {
+    encode_audio(data, sample_rate, channels, output_path, &EncodingSettings::for_format(AudioFormat::Aac))
+}
+
+/// Encode raw f32le samples with the given settings
+pub fn encode_audio(
+    data: &[u8],
+    sample_rate: u32,
+    channels: u16,
+    output_path: &PathBuf,
+    settings: &EncodingSettings,
 ) -> anyhow::Result<()> {
     debug!("Starting FFmpeg process for {} bytes of audio data", data.len());
 
@@ -44,18 +178,9 @@ pub fn encode_single_audio(
             &channels.to_string(),
             "-i",
             "pipe:0",
-            "-c:a",
-            "aac",
-            "-b:a",
-            "192k", // Increased from 64k for better audio quality (especially for speech)
-            "-profile:a",
-            "aac_low", // Use AAC-LC profile for better compatibility
-            "-movflags",
-            "+faststart", // Optimize for web streaming
-            "-f",
-            "mp4",
-            output_path.to_str().unwrap(),
         ])
+        .args(settings.format.ffmpeg_args(settings.bitrate_kbps))
+        .arg(output_path.to_str().unwrap())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
@@ -100,3 +225,25 @@ pub fn encode_single_audio(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_normalization() {
+        let opus = EncodingSettings { format: AudioFormat::Opus, bitrate_kbps: 1000 }.normalized();
+        assert_eq!(opus.bitrate_kbps, 256);
+
+        let flac = EncodingSettings { format: AudioFormat::Flac, bitrate_kbps: 128 }.normalized();
+        assert_eq!(flac.bitrate_kbps, 0);
+    }
+
+    #[test]
+    fn test_format_parsing_and_serialization() {
+        assert_eq!(AudioFormat::from_string("M4A"), Some(AudioFormat::Aac));
+        assert_eq!(AudioFormat::from_string("mp3"), None);
+        assert_eq!(serde_json::to_string(&AudioFormat::Opus).unwrap(), "\"opus\"");
+        assert_eq!(EncodingSettings::default().format.extension(), "opus");
+    }
+}
@@ -6,7 +6,8 @@ use ffmpeg_sidecar::{
 };
 use log::{debug, error};
 use once_cell::sync::Lazy;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use which::which;
 
 #[cfg(not(windows))]
@@ -196,3 +197,109 @@ fn get_ffmpeg_install_dir() -> Result<PathBuf, anyhow::Error> {
     // Your existing logic for other platforms
     sidecar_dir().map_err(|e| anyhow::anyhow!(e))
 }
+
+/// Decode any FFmpeg-readable file to mono f32 samples at `sample_rate`
+pub async fn decode_to_mono(path: &Path, sample_rate: u32) -> Result<Vec<f32>, anyhow::Error> {
+    decode_f32(path, sample_rate, &["-ac", "1"]).await
+}
+
+/// Decode a single channel of a multi-channel file (0 = left) to mono f32 samples
+pub async fn decode_channel_to_mono(path: &Path, sample_rate: u32, channel: u16) -> Result<Vec<f32>, anyhow::Error> {
+    decode_f32(path, sample_rate, &["-af", &format!("pan=mono|c0=c{}", channel)]).await
+}
+
+async fn decode_f32(path: &Path, sample_rate: u32, channel_args: &[&str]) -> Result<Vec<f32>, anyhow::Error> {
+    let ffmpeg_path = find_ffmpeg_path()
+        .ok_or_else(|| anyhow::anyhow!("FFmpeg not found. Please install FFmpeg to decode recordings."))?;
+    // FFmpeg can't read encrypted recordings; it gets a decrypted copy that lives as long as this call
+    let decrypted = crate::encryption::files::decrypted_copy(path)?;
+    let path = decrypted.as_ref().map_or(path, |copy| copy.path());
+
+    let mut command = tokio::process::Command::new(ffmpeg_path);
+    command
+        .arg("-i")
+        .arg(path)
+        .arg("-vn")
+        .args(channel_args)
+        .args(["-f", "f32le", "-ar", &sample_rate.to_string(), "pipe:1"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Callers may drop the future (e.g. a cancelled job); don't leave FFmpeg behind
+        .kill_on_drop(true);
+
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command.output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().last().unwrap_or("unknown error");
+        return Err(anyhow::anyhow!("FFmpeg could not decode {}: {}", path.display(), reason));
+    }
+
+    Ok(output
+        .stdout
+        .chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .collect())
+}
+
+/// Replace time ranges (seconds) of a recording with silence, in place. The file is
+/// re-encoded in its own format, so its length and every timestamp stay the same.
+pub async fn silence_ranges(path: &Path, ranges: &[(f64, f64)]) -> Result<(), anyhow::Error> {
+    if ranges.is_empty() {
+        return Ok(());
+    }
+    let ffmpeg_path = find_ffmpeg_path()
+        .ok_or_else(|| anyhow::anyhow!("FFmpeg not found. Please install FFmpeg to edit recordings."))?;
+    let extension = path.extension().map(|ext| ext.to_string_lossy().to_string()).unwrap_or_default();
+    let format = super::encode::AudioFormat::from_string(&extension)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported recording format: {}", extension))?;
+    let settings = super::encode::get_encoding_settings();
+    let bitrate_kbps =
+        if settings.format == format { settings.bitrate_kbps } else { format.default_bitrate_kbps() };
+
+    let decrypted = crate::encryption::files::decrypted_copy(path)?;
+    let input = decrypted.as_ref().map_or(path, |copy| copy.path());
+    let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let output_path = path.with_file_name(format!(".{}.silenced", name));
+    let enable = ranges
+        .iter()
+        .map(|(start, end)| format!("between(t,{:.3},{:.3})", start, end))
+        .collect::<Vec<_>>()
+        .join("+");
+
+    let mut command = tokio::process::Command::new(ffmpeg_path);
+    command
+        .arg("-y")
+        .arg("-i")
+        .arg(input)
+        .arg("-vn")
+        .args(["-af", &format!("volume=enable='{}':volume=0", enable)])
+        .args(format.ffmpeg_args(bitrate_kbps))
+        .arg(&output_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command.output().await?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&output_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().last().unwrap_or("unknown error");
+        return Err(anyhow::anyhow!("FFmpeg could not edit {}: {}", path.display(), reason));
+    }
+    std::fs::rename(&output_path, path)?;
+    Ok(())
+}
@@ -104,6 +104,24 @@ impl HardwareProfile {
         (false, GpuType::None)
     }
 
+    /// Every GPU compute API found on this machine, in order of preference
+    pub fn detected_gpu_types() -> Vec<GpuType> {
+        let mut types = Vec::new();
+        #[cfg(target_os = "macos")]
+        {
+            if Self::has_metal_support() {
+                types.push(GpuType::Metal);
+            }
+        }
+        if Self::has_cuda_support() {
+            types.push(GpuType::Cuda);
+        }
+        if Self::has_vulkan_support() {
+            types.push(GpuType::Vulkan);
+        }
+        types
+    }
+
     /// Detect available system memory in GB
     fn detect_memory_gb() -> u8 {
         // Simple memory detection - could be enhanced with system-specific calls
@@ -1,8 +1,9 @@
 use std::path::PathBuf;
 use anyhow::{Result, anyhow};
 use log::{info, warn, error};
-use super::encode::encode_single_audio;
+use super::encode::{encode_audio, get_encoding_settings, EncodingSettings};
 use super::recording_state::AudioChunk;
+use super::write_ahead::WriteAheadLog;
 
 #[cfg (target_os = "macos")]
 use super::ffmpeg::find_ffmpeg_path;
@@ -23,6 +24,12 @@ pub struct IncrementalAudioSaver {
     checkpoints_dir: PathBuf,
     meeting_folder: PathBuf,
     sample_rate: u32,
+    // 1 for mono, 2 for interleaved stereo
+    channels: u16,
+    // Snapshot of the encoding preference taken when recording started
+    encoding: EncodingSettings,
+    // Crash-safe raw audio covering the gap between checkpoints
+    wal: Option<WriteAheadLog>,
 }
 
 impl IncrementalAudioSaver {
@@ -31,7 +38,8 @@ impl IncrementalAudioSaver {
     /// # Arguments
     /// * `meeting_folder` - Path to the meeting folder (contains .checkpoints/)
     /// * `sample_rate` - Sample rate of audio (typically 48000)
-    pub fn new(meeting_folder: PathBuf, sample_rate: u32) -> Result<Self> {
+    /// * `channels` - Channels of the (interleaved) audio added to the saver
+    pub fn new(meeting_folder: PathBuf, sample_rate: u32, channels: u16) -> Result<Self> {
         let checkpoints_dir = meeting_folder.join(".checkpoints");
 
         // Verify checkpoints directory exists
@@ -39,19 +47,64 @@ impl IncrementalAudioSaver {
             return Err(anyhow!("Checkpoints directory does not exist: {}", checkpoints_dir.display()));
         }
 
+        let meeting_name = meeting_folder.file_name().map(|n| n.to_string_lossy().to_string());
+        let wal = match WriteAheadLog::create(&meeting_folder, meeting_name, sample_rate, channels) {
+            Ok(wal) => Some(wal),
+            Err(e) => {
+                warn!("⚠️ Failed to create write-ahead log, crash recovery disabled: {}", e);
+                None
+            }
+        };
+
         Ok(Self {
             checkpoint_buffer: Vec::new(),
-            checkpoint_interval_samples: sample_rate as usize * 30, // 30 seconds
+            checkpoint_interval_samples: sample_rate as usize * channels as usize * 30, // 30 seconds
             checkpoint_count: 0,
             checkpoints_dir,
             meeting_folder,
             sample_rate,
+            channels,
+            encoding: get_encoding_settings(),
+            wal,
+        })
+    }
+
+    /// Reopen an interrupted recording whose first `checkpoint_count` checkpoints exist
+    ///
+    /// Used by crash recovery; no write-ahead log is created since the audio being added
+    /// already comes from one.
+    pub fn resume_for_recovery(
+        meeting_folder: PathBuf,
+        sample_rate: u32,
+        channels: u16,
+        checkpoint_count: u32,
+        encoding: EncodingSettings,
+    ) -> Result<Self> {
+        let checkpoints_dir = meeting_folder.join(".checkpoints");
+        std::fs::create_dir_all(&checkpoints_dir)?;
+
+        Ok(Self {
+            checkpoint_buffer: Vec::new(),
+            checkpoint_interval_samples: sample_rate as usize * channels as usize * 30,
+            checkpoint_count,
+            checkpoints_dir,
+            meeting_folder,
+            sample_rate,
+            channels,
+            encoding,
+            wal: None,
         })
     }
 
     /// Add an audio chunk to the buffer
     /// Automatically saves a checkpoint when buffer reaches 30 seconds
     pub fn add_chunk(&mut self, chunk: AudioChunk) -> Result<()> {
+        if let Some(wal) = self.wal.as_mut() {
+            if let Err(e) = wal.append(&chunk.data) {
+                warn!("⚠️ Write-ahead log append failed: {}", e);
+            }
+        }
+
         let audio_data = AudioData {
             data: chunk.data,
             // sample_rate: chunk.sample_rate,
@@ -74,6 +127,17 @@ impl IncrementalAudioSaver {
         Ok(())
     }
 
+    /// Write whatever is buffered as its own checkpoint (e.g. when recording pauses),
+    /// so the audio on disk matches everything captured so far
+    pub fn flush_checkpoint(&mut self) -> Result<()> {
+        if self.checkpoint_buffer.is_empty() {
+            return Ok(());
+        }
+        self.save_checkpoint()?;
+        self.checkpoint_buffer.clear();
+        Ok(())
+    }
+
     /// Save current buffer as a checkpoint file
     fn save_checkpoint(&mut self) -> Result<()> {
         // Concatenate all chunks in buffer
@@ -89,20 +153,27 @@ impl IncrementalAudioSaver {
         }
 
         // Generate checkpoint filename
-        let checkpoint_path = self.checkpoints_dir
-            .join(format!("audio_chunk_{:03}.mp4", self.checkpoint_count));
+        let checkpoint_path = self.checkpoint_path(self.checkpoint_count);
 
         // Encode and save checkpoint
-        encode_single_audio(
+        encode_audio(
             bytemuck::cast_slice(&audio_data),
             self.sample_rate,
-            1,  // mono
-            &checkpoint_path
+            self.channels,
+            &checkpoint_path,
+            &self.encoding,
         )?;
 
-        let duration_seconds = audio_data.len() as f32 / self.sample_rate as f32;
+        let duration_seconds = audio_data.len() as f32 / (self.sample_rate as f32 * self.channels as f32);
         self.checkpoint_count += 1;
 
+        // The checkpoint is durable, so the raw audio it covers is no longer needed
+        if let Some(wal) = self.wal.as_mut() {
+            if let Err(e) = wal.commit_checkpoint(self.checkpoint_count) {
+                warn!("⚠️ Failed to commit checkpoint to write-ahead log: {}", e);
+            }
+        }
+
         info!("💾 Saved checkpoint {}: {:.2}s of audio ({} samples)",
               self.checkpoint_count,
               duration_seconds,
@@ -113,7 +184,7 @@ impl IncrementalAudioSaver {
 
     /// Finalize the recording: save final checkpoint, merge all checkpoints, cleanup
     ///
-    /// Returns the path to the final merged audio.mp4 file
+    /// Returns the path to the final merged audio file (`audio.<ext>` for the chosen format)
     pub async fn finalize(&mut self) -> Result<PathBuf> {
         info!("Finalizing incremental recording...");
 
@@ -129,7 +200,7 @@ impl IncrementalAudioSaver {
         }
 
         // Merge all checkpoints using FFmpeg concat
-        let final_audio_path = self.meeting_folder.join("audio.mp4");
+        let final_audio_path = self.meeting_folder.join(self.audio_file_name());
         self.merge_checkpoints(&final_audio_path).await?;
 
         // Clean up checkpoints directory
@@ -139,12 +210,18 @@ impl IncrementalAudioSaver {
             // Non-fatal - user can manually delete
         }
 
+        if let Some(wal) = self.wal.take() {
+            if let Err(e) = wal.finalize() {
+                warn!("Failed to remove write-ahead log: {}", e);
+            }
+        }
+
         info!("✅ Finalized recording: {}", final_audio_path.display());
 
         Ok(final_audio_path)
     }
 
-    /// Merge all checkpoint files into final audio.mp4 using FFmpeg concat
+    /// Merge all checkpoint files into the final audio file using FFmpeg concat
     /// Uses concat demuxer for fast merging without re-encoding
     async fn merge_checkpoints(&self, output: &PathBuf) -> Result<()> {
         info!("Merging {} checkpoints into final audio file...", self.checkpoint_count);
@@ -154,8 +231,7 @@ impl IncrementalAudioSaver {
         let mut list_content = String::new();
 
         for i in 0..self.checkpoint_count {
-            let checkpoint_path = self.checkpoints_dir
-                .join(format!("audio_chunk_{:03}.mp4", i));
+            let checkpoint_path = self.checkpoint_path(i);
 
             // Verify checkpoint exists
             if !checkpoint_path.exists() {
@@ -218,6 +294,20 @@ impl IncrementalAudioSaver {
         Ok(())
     }
 
+    /// Name of the final audio file inside the meeting folder
+    pub fn audio_file_name(&self) -> String {
+        format!("audio.{}", self.encoding.format.extension())
+    }
+
+    pub fn encoding(&self) -> EncodingSettings {
+        self.encoding
+    }
+
+    fn checkpoint_path(&self, index: u32) -> PathBuf {
+        self.checkpoints_dir
+            .join(format!("audio_chunk_{:03}.{}", index, self.encoding.format.extension()))
+    }
+
     /// Get the meeting folder path
     pub fn get_meeting_folder(&self) -> &PathBuf {
         &self.meeting_folder
@@ -245,7 +335,8 @@ mod tests {
 
         let mut saver = IncrementalAudioSaver::new(
             meeting_folder.clone(),
-            48000
+            48000,
+            1
         ).unwrap();
 
         // Add 60 seconds worth of audio (should create 2 checkpoints)
@@ -254,6 +345,7 @@ mod tests {
                 data: vec![0.5f32; 24000],  // 0.5s at 48kHz
                 sample_rate: 48000,
                 device_type: DeviceType::Microphone,
+                stereo_side: None,
             };
             saver.add_chunk(chunk).unwrap();
         }
@@ -278,7 +370,8 @@ mod tests {
 
         let mut saver = IncrementalAudioSaver::new(
             meeting_folder.clone(),
-            48000
+            48000,
+            1
         ).unwrap();
 
         // Try to finalize without adding any chunks
@@ -5,12 +5,11 @@ pub mod ffmpeg;
 pub mod vad;
 
 // Modularized device management
-pub mod devices;
-pub mod capture;
+pub use meeting_core::audio::{capture, devices};
 pub mod permissions;
 
 // NEW: Device detection and diagnostics for adaptive buffering
-pub mod device_detection;
+pub use meeting_core::audio::device_detection;
 pub mod diagnostics;
 pub mod ffmpeg_mixer;  // NEW: FFmpeg-style adaptive audio mixer
 
@@ -18,6 +17,8 @@ pub mod ffmpeg_mixer;  // NEW: FFmpeg-style adaptive audio mixer
 pub mod recording_state;
 pub mod pipeline;
 pub mod stream;
+pub mod capture_ring;     // Lock-free handoff from the capture callback to processing
+pub mod pipeline_stats;   // Dropped audio and queue depths per pipeline stage
 pub mod recording_manager;
 pub mod recording_commands;
 pub mod recording_preferences;
@@ -34,6 +35,25 @@ pub mod system_detector;
 pub mod system_audio_commands;
 pub mod device_monitor;  // NEW: Device disconnect/reconnect monitoring
 pub mod playback_monitor; // NEW: Playback device detection for BT warnings
+pub mod device_watcher;   // Hot-plug detection and microphone failover
+pub mod device_commands;  // Device picker commands
+pub mod device_preferences; // Persisted preferred microphone / system audio devices
+pub mod silence_watchdog; // No-signal / silent input detection during recording
+pub mod write_ahead;      // Crash-safe raw audio log and orphaned session detection
+pub mod recovery;         // Startup recovery of interrupted recordings
+pub mod preroll;          // Rolling pre-roll buffer captured before recording starts
+pub mod echo_cancellation; // Removes system audio echo from the microphone
+pub mod resample;         // Streaming and one-shot sample rate conversion
+pub mod clock_drift;      // Microphone / system audio clock drift compensation
+pub mod channel_mix;      // Mono / stereo layout of saved recordings
+pub mod auto_stop;        // Stop recordings left running after the meeting ended
+pub mod disk_space;       // Low disk space warnings while recording
+pub mod mute_detection;   // Muted or zero-volume microphone warnings while recording
+pub mod bluetooth_microphone; // Hands-free Bluetooth microphone warning and built-in switch
+pub mod session;          // Recording session state machine and its events
+pub mod playback;         // Transcript-synced playback of meeting recordings
+pub mod selftest;         // Audio → transcript pipeline self-test
+pub mod preflight;        // Readiness report shown before the first recording
 
 // Transcription module (provider abstraction, engine management, worker pool)
 pub mod transcription;
@@ -42,6 +62,7 @@ pub use devices::{
     default_input_device, default_output_device, get_device_and_config, list_audio_devices,
     parse_audio_device, trigger_audio_permission,
     AudioDevice, AudioTranscriptionEngine, DeviceControl, DeviceType,
+    AudioDeviceInfo, DeviceTransport, list_audio_devices_detailed,
     LAST_AUDIO_CAPTURE,
 };
 
@@ -84,15 +105,17 @@ pub use buffer_pool::{AudioBufferPool, PooledBuffer};
 pub use post_processor::{PostProcessor, PostProcessRequest, PostProcessResponse};
 pub use hardware_detector::{HardwareProfile, AdaptiveWhisperConfig, PerformanceTier, GpuType};
 pub use encode::{
-    encode_single_audio, AudioInput
+    encode_single_audio, encode_audio, AudioInput, AudioFormat, EncodingSettings,
+    get_encoding_settings, set_encoding_settings
 };
 pub use device_monitor::{AudioDeviceMonitor, DeviceEvent, DeviceMonitorType};
+pub use device_watcher::{DeviceChange, DeviceSnapshot, DeviceWatcher, DEVICE_WATCHER, DEVICE_CHANGED_EVENT};
 
 // Export device detection and diagnostics
 pub use device_detection::{InputDeviceKind, calculate_buffer_timeout};
 pub use diagnostics::{
     log_device_capabilities, log_detection_summary, log_buffer_health,
-    log_mixer_status, log_performance_summary
+    log_mixer_status, log_performance_summary, log_resample_path
 };
 
 // Export FFmpeg mixer
@@ -103,8 +126,11 @@ pub use vad::{extract_speech_16k};
 // Export permission functions
 pub use permissions::{
     check_microphone_permission, request_microphone_permission, ensure_microphone_permission,
-    init_microphone_permission,
-    check_microphone_permission_command, request_microphone_permission_command, 
+    init_microphone_permission, get_microphone_permission_status, MicrophonePermissionStatus,
+    system_audio_permission_status, SystemAudioPermissionStatus,
+    check_microphone_permission_command, request_microphone_permission_command,
+    get_microphone_permission_status_command,
+    PermissionMonitor, PermissionState, PERMISSION_MONITOR, get_permission_state_command,
     ensure_microphone_permission_command,
     check_screen_recording_permission_command, request_screen_recording_permission_command,
     trigger_system_audio_permission_command,
@@ -0,0 +1,262 @@
+// Muted microphone detection while recording
+//
+// Headsets with a mute button, a muted input in the system sound settings and an input volume
+// pulled down to zero all record whole meetings of silence. The silence watchdog only notices
+// that after seconds of zeros and can't tell why; this monitor asks the OS about the recording
+// microphone itself every few seconds, so the warning comes right away and says what to fix.
+// A mute switch that only exists in the headset's hardware isn't visible to the OS; the
+// watchdog still catches that one.
+//
+// The OS is asked through Core Audio on macOS, the endpoint volume of WASAPI on Windows, and
+// pactl (PulseAudio or PipeWire) on Linux.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use log::{debug, info};
+
+use super::recording_commands::RECORDING_MANAGER;
+use super::session;
+use super::silence_watchdog::{RecordingWarning, WarningReason};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Input volume at or below this records nothing usable
+const SILENT_VOLUME: f32 = 0.01;
+
+/// Whether the recording microphone was muted at the last check
+static INPUT_MUTED: AtomicBool = AtomicBool::new(false);
+
+/// Mute switch and volume of an input device, as the OS reports them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputMuteState {
+    pub muted: bool,
+    /// Scalar volume from 0 to 1, when the device has a volume control
+    pub volume: Option<f32>,
+}
+
+impl InputMuteState {
+    /// Why this device records silence, if it does
+    pub fn silenced(&self) -> Option<WarningReason> {
+        if self.muted {
+            Some(WarningReason::InputMuted)
+        } else if self.volume.is_some_and(|volume| volume <= SILENT_VOLUME) {
+            Some(WarningReason::InputVolumeZero)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether the recording microphone is muted in the OS, as of the last check
+pub fn is_input_muted() -> bool {
+    INPUT_MUTED.load(Ordering::Relaxed)
+}
+
+/// Raises a warning when the microphone becomes silenced, and again when that changes
+#[derive(Debug, Default)]
+struct MuteTracker {
+    warned: Option<WarningReason>,
+}
+
+impl MuteTracker {
+    fn observe(&mut self, state: InputMuteState) -> Option<WarningReason> {
+        match (state.silenced(), self.warned) {
+            (Some(reason), warned) if warned != Some(reason) => {
+                self.warned = Some(reason);
+                Some(reason)
+            }
+            (None, Some(_)) => {
+                self.warned = None;
+                Some(WarningReason::SignalRestored)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Mute state of the input device named `device_name`, or None when the OS doesn't say
+#[cfg(target_os = "macos")]
+pub fn input_mute_state(device_name: &str) -> Option<InputMuteState> {
+    use cidre::core_audio as ca;
+
+    const MUTE: ca::PropAddr = ca::PropAddr {
+        selector: ca::PropSelector(u32::from_be_bytes(*b"mute")),
+        scope: ca::PropScope::INPUT,
+        element: ca::PropElement::MAIN,
+    };
+    const VOLUME: ca::PropAddr = ca::PropAddr {
+        selector: ca::PropSelector(u32::from_be_bytes(*b"volm")),
+        scope: ca::PropScope::INPUT,
+        element: ca::PropElement::MAIN,
+    };
+
+    let devices = ca::System::devices().ok()?;
+    let device = devices
+        .iter()
+        .find(|d| d.name().ok().map(|n| n.to_string()).as_deref() == Some(device_name))?;
+    // Devices without the controls don't have the properties
+    let muted = device.prop::<u32>(&MUTE).ok();
+    let volume = device.prop::<f32>(&VOLUME).ok();
+    if muted.is_none() && volume.is_none() {
+        return None;
+    }
+    Some(InputMuteState { muted: muted.unwrap_or(0) != 0, volume })
+}
+
+/// Mute state of the input device named `device_name`, or None when the OS doesn't say
+#[cfg(target_os = "windows")]
+pub fn input_mute_state(device_name: &str) -> Option<InputMuteState> {
+    use windows::Win32::Devices::FunctionDiscovery::PKEY_Device_FriendlyName;
+    use windows::Win32::Media::Audio::Endpoints::IAudioEndpointVolume;
+    use windows::Win32::Media::Audio::{eCapture, IMMDeviceEnumerator, MMDeviceEnumerator, DEVICE_STATE_ACTIVE};
+    use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED, STGM_READ};
+
+    // SAFETY: plain COM calls on interfaces owned by this function; COM may already be
+    // initialized on this thread, which is fine either way
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL).ok()?;
+        let endpoints = enumerator.EnumAudioEndpoints(eCapture, DEVICE_STATE_ACTIVE).ok()?;
+        for i in 0..endpoints.GetCount().ok()? {
+            let Ok(endpoint) = endpoints.Item(i) else { continue };
+            let Ok(properties) = endpoint.OpenPropertyStore(STGM_READ) else { continue };
+            let Ok(name) = properties.GetValue(&PKEY_Device_FriendlyName) else { continue };
+            if name.to_string() != device_name {
+                continue;
+            }
+            let volume: IAudioEndpointVolume = endpoint.Activate(CLSCTX_ALL, None).ok()?;
+            return Some(InputMuteState {
+                muted: volume.GetMute().ok()?.as_bool(),
+                volume: volume.GetMasterVolumeLevelScalar().ok(),
+            });
+        }
+        None
+    }
+}
+
+/// Mute state of the input device, or None when the OS doesn't say
+///
+/// cpal only knows the ALSA name of the device, which records from the sound server's
+/// default source, so that source is the one asked about.
+#[cfg(target_os = "linux")]
+pub fn input_mute_state(_device_name: &str) -> Option<InputMuteState> {
+    use std::process::Command;
+
+    let pactl = |command: &str| {
+        let output = Command::new("pactl").args([command, "@DEFAULT_SOURCE@"]).output().ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let muted = parse_pactl_mute(&pactl("get-source-mute")?)?;
+    let volume = pactl("get-source-volume").and_then(|output| parse_pactl_volume(&output));
+    Some(InputMuteState { muted, volume })
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn input_mute_state(_device_name: &str) -> Option<InputMuteState> {
+    None
+}
+
+/// Parses `pactl get-source-mute`, e.g. "Mute: yes"
+#[cfg(any(target_os = "linux", test))]
+fn parse_pactl_mute(output: &str) -> Option<bool> {
+    match output.trim().strip_prefix("Mute:")?.trim() {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parses `pactl get-source-volume` into the loudest channel's volume, e.g.
+/// "Volume: front-left: 32768 /  50% / -18.06 dB,   front-right: 32768 /  50% / -18.06 dB"
+#[cfg(any(target_os = "linux", test))]
+fn parse_pactl_volume(output: &str) -> Option<f32> {
+    let line = output.lines().find_map(|line| line.trim().strip_prefix("Volume:"))?;
+    line.split('/')
+        .filter_map(|field| field.trim().strip_suffix('%'))
+        .filter_map(|percent| percent.trim().parse::<f32>().ok())
+        .map(|percent| percent / 100.0)
+        .reduce(f32::max)
+}
+
+/// Checks the recording microphone's mute state until the recording ends
+pub fn spawn_mute_monitor() {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        let mut tracker = MuteTracker::default();
+
+        while session::phase().is_recording() {
+            interval.tick().await;
+
+            let state = match RECORDING_MANAGER.lock() {
+                Ok(guard) => match guard.as_ref() {
+                    Some(manager) => manager.get_state().clone(),
+                    None => break,
+                },
+                Err(_) => break,
+            };
+            let Some(device) = state.get_microphone_device() else {
+                continue;
+            };
+
+            let name = device.name.clone();
+            let Ok(Some(mute_state)) = tokio::task::spawn_blocking(move || input_mute_state(&name)).await else {
+                continue;
+            };
+            INPUT_MUTED.store(mute_state.muted, Ordering::Relaxed);
+            debug!("🔇 '{}' muted: {}, volume: {:?}", device.name, mute_state.muted, mute_state.volume);
+
+            if let Some(reason) = tracker.observe(mute_state) {
+                state.report_warning(RecordingWarning {
+                    device_name: device.name.clone(),
+                    device_type: "Microphone".to_string(),
+                    reason,
+                    message: reason.user_message().to_string(),
+                    silent_for_secs: 0.0,
+                });
+            }
+        }
+
+        INPUT_MUTED.store(false, Ordering::Relaxed);
+        info!("Mute monitor stopped");
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silenced() {
+        let state = |muted, volume| InputMuteState { muted, volume };
+        assert_eq!(state(true, Some(0.8)).silenced(), Some(WarningReason::InputMuted));
+        assert_eq!(state(false, Some(0.0)).silenced(), Some(WarningReason::InputVolumeZero));
+        assert_eq!(state(false, Some(0.5)).silenced(), None);
+        assert_eq!(state(false, None).silenced(), None);
+    }
+
+    #[test]
+    fn test_tracker_warns_on_changes_only() {
+        let mut tracker = MuteTracker::default();
+        let muted = InputMuteState { muted: true, volume: Some(0.5) };
+        let unmuted = InputMuteState { muted: false, volume: Some(0.5) };
+        assert_eq!(tracker.observe(unmuted), None);
+        assert_eq!(tracker.observe(muted), Some(WarningReason::InputMuted));
+        assert_eq!(tracker.observe(muted), None);
+        let turned_down = InputMuteState { muted: false, volume: Some(0.0) };
+        assert_eq!(tracker.observe(turned_down), Some(WarningReason::InputVolumeZero));
+        assert_eq!(tracker.observe(unmuted), Some(WarningReason::SignalRestored));
+        assert_eq!(tracker.observe(unmuted), None);
+    }
+
+    #[test]
+    fn test_parse_pactl() {
+        assert_eq!(parse_pactl_mute("Mute: yes\n"), Some(true));
+        assert_eq!(parse_pactl_mute("Mute: no\n"), Some(false));
+        assert_eq!(parse_pactl_mute("Failure: No such entity\n"), None);
+
+        let volume = "Volume: front-left: 32768 /  50% / -18.06 dB,   front-right: 45875 /  70% / -9.29 dB\n";
+        assert_eq!(parse_pactl_volume(volume), Some(0.7));
+        assert_eq!(parse_pactl_volume("Volume: mono: 0 /   0% / -inf dB\n"), Some(0.0));
+        assert_eq!(parse_pactl_volume("Failure: No such entity\n"), None);
+    }
+}
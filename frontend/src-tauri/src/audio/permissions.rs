@@ -1,6 +1,15 @@
-// macOS audio permissions handling
+// Audio permissions handling: TCC on macOS, the microphone privacy settings on Windows, the
+// Flatpak or Snap sandbox on Linux
 use anyhow::Result;
 use log::{info, warn, error};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::error::{AppError, ErrorCode};
 
 #[cfg(target_os = "macos")]
 use std::process::Command;
@@ -17,17 +26,118 @@ static INIT_MICROPHONE_PERMISSION: Once = Once::new();
 /// When the app first attempts to create a Core Audio tap, macOS will automatically
 /// show a permission dialog to the user. If permission is denied, the tap will return
 /// silence (all zeros).
-///
-/// This function returns true because the actual permission prompt happens automatically
-/// when AudioHardwareCreateProcessTap is called by the cidre library.
 #[cfg(target_os = "macos")]
 pub fn check_screen_recording_permission() -> bool {
-    info!("ℹ️  Core Audio tap requires Audio Capture permission (macOS 14.4+)");
-    info!("📍 Permission dialog will appear automatically when recording starts");
-    info!("   If already granted: System Settings → Privacy & Security → Audio Capture");
+    let status = system_audio_permission_status();
+    match status {
+        SystemAudioPermissionStatus::Authorized => info!("✅ Audio Capture permission granted"),
+        SystemAudioPermissionStatus::Unknown => {
+            info!("🔐 Audio Capture permission not decided yet (the prompt appears when recording starts)")
+        }
+        _ => warn!("⚠️ Audio Capture permission denied - System Settings → Privacy & Security → Screen & System Audio Recording"),
+    }
+    status.allows_capture()
+}
+
+/// The TCC service behind the "System Audio Recording" permission of Core Audio taps
+#[cfg(target_os = "macos")]
+const AUDIO_CAPTURE_SERVICE: &str = "kTCCServiceAudioCapture";
+
+#[cfg(target_os = "macos")]
+const TCC_FRAMEWORK_PATH: &str = "/System/Library/PrivateFrameworks/TCC.framework/Versions/A/TCC";
+
+/// Asks TCC for the Audio Capture decision without prompting; None when TCC can't be asked
+///
+/// There is no public API for this permission, so `TCCAccessPreflight` is looked up at
+/// runtime; if a macOS release drops it the permission is just treated as unknown.
+#[cfg(target_os = "macos")]
+fn audio_capture_preflight() -> Option<i32> {
+    use std::ffi::{c_char, c_int, c_void, CString};
+
+    type Preflight = unsafe extern "C" fn(service: *const c_void, options: *const c_void) -> c_int;
+    const RTLD_LAZY: c_int = 0x1;
+    const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+    extern "C" {
+        fn dlopen(path: *const c_char, mode: c_int) -> *mut c_void;
+        fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    }
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFStringCreateWithCString(allocator: *const c_void, string: *const c_char, encoding: u32) -> *const c_void;
+        fn CFRelease(object: *const c_void);
+    }
+
+    static PREFLIGHT: Lazy<Option<Preflight>> = Lazy::new(|| {
+        let path = CString::new(TCC_FRAMEWORK_PATH).ok()?;
+        let symbol = CString::new("TCCAccessPreflight").ok()?;
+        // SAFETY: dlopen/dlsym with valid C strings; the symbol's signature is TCC's
+        unsafe {
+            let handle = dlopen(path.as_ptr(), RTLD_LAZY);
+            if handle.is_null() {
+                return None;
+            }
+            let function = dlsym(handle, symbol.as_ptr());
+            (!function.is_null()).then(|| std::mem::transmute::<*mut c_void, Preflight>(function))
+        }
+    });
+
+    let preflight = (*PREFLIGHT)?;
+    let service_name = CString::new(AUDIO_CAPTURE_SERVICE).ok()?;
+    // SAFETY: the CFString is created, passed and released here
+    unsafe {
+        let service = CFStringCreateWithCString(std::ptr::null(), service_name.as_ptr(), CF_STRING_ENCODING_UTF8);
+        if service.is_null() {
+            return None;
+        }
+        let result = preflight(service, std::ptr::null());
+        CFRelease(service);
+        Some(result)
+    }
+}
+
+/// State of the permission to capture system audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemAudioPermissionStatus {
+    Authorized,
+    /// Only the system settings can change this; captured system audio is silence
+    Denied,
+    /// Not decided yet, or the system couldn't be asked; macOS shows the prompt itself when
+    /// recording starts
+    Unknown,
+    /// The platform captures system audio without asking
+    NotRequired,
+}
+
+impl SystemAudioPermissionStatus {
+    /// Map a `TCCAccessPreflight` result (0 granted, 1 denied, 2 not decided) to a status
+    pub fn from_preflight(result: Option<i32>) -> Self {
+        match result {
+            Some(0) => SystemAudioPermissionStatus::Authorized,
+            Some(1) => SystemAudioPermissionStatus::Denied,
+            _ => SystemAudioPermissionStatus::Unknown,
+        }
+    }
 
-    // Always return true - the actual permission dialog is triggered by Core Audio API
-    true
+    pub fn is_granted(&self) -> bool {
+        matches!(self, SystemAudioPermissionStatus::Authorized | SystemAudioPermissionStatus::NotRequired)
+    }
+
+    /// Whether recording system audio can go ahead, possibly after a prompt
+    pub fn allows_capture(&self) -> bool {
+        *self != SystemAudioPermissionStatus::Denied
+    }
+}
+
+/// Silent variant of `check_screen_recording_permission` for polling
+#[cfg(target_os = "macos")]
+pub fn system_audio_permission_status() -> SystemAudioPermissionStatus {
+    SystemAudioPermissionStatus::from_preflight(audio_capture_preflight())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn system_audio_permission_status() -> SystemAudioPermissionStatus {
+    SystemAudioPermissionStatus::NotRequired
 }
 
 #[cfg(not(target_os = "macos"))]
@@ -35,27 +145,78 @@ pub fn check_screen_recording_permission() -> bool {
     true // Not required on other platforms
 }
 
+/// A pane of the Privacy & Security settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivacyPane {
+    Microphone,
+    /// "Screen & System Audio Recording", which holds the Audio Capture permission
+    SystemAudio,
+}
+
+impl PrivacyPane {
+    pub fn anchor(&self) -> &'static str {
+        match self {
+            PrivacyPane::Microphone => "Privacy_Microphone",
+            PrivacyPane::SystemAudio => "Privacy_ScreenCapture",
+        }
+    }
+}
+
+/// The major and minor version from `sw_vers -productVersion`, e.g. "14.4.1"
+pub fn parse_macos_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |minor| minor.parse().ok())?;
+    Some((major, minor))
+}
+
+/// The URL opening a privacy pane at `anchor`; System Settings (macOS 13 and later)
+/// has its own scheme, older System Preferences the security pane's
+pub fn privacy_settings_url(anchor: &str, version: Option<(u32, u32)>) -> String {
+    match version {
+        Some((major, _)) if major >= 13 => {
+            format!("x-apple.systempreferences:com.apple.settings.PrivacySecurity.extension?{}", anchor)
+        }
+        _ => format!("x-apple.systempreferences:com.apple.preference.security?{}", anchor),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn macos_version() -> Option<(u32, u32)> {
+    static VERSION: Lazy<Option<(u32, u32)>> = Lazy::new(|| {
+        let output = Command::new("sw_vers").arg("-productVersion").output().ok()?;
+        parse_macos_version(&String::from_utf8_lossy(&output.stdout))
+    });
+    *VERSION
+}
+
+/// Opens System Settings at a privacy pane, or at the one named by `anchor`
+#[cfg(target_os = "macos")]
+pub fn open_privacy_settings(anchor: &str) -> Result<()> {
+    let url = privacy_settings_url(anchor, macos_version());
+    Command::new("open")
+        .arg(&url)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| anyhow::anyhow!("Failed to open System Settings: {}", e))
+}
+
 /// Request Audio Capture permission from the user
-/// This will open System Settings to the Privacy & Security page
+///
+/// Opens System Settings at Screen & System Audio Recording. The permission monitor
+/// reports when it's granted, and the next recording uses it without a restart.
 #[cfg(target_os = "macos")]
 pub fn request_screen_recording_permission() -> Result<()> {
     info!("🔐 Opening System Settings for Audio Capture permission...");
 
-    // Open System Settings to Privacy & Security page
-    // Note: There's no direct URL for Audio Capture, so we open the main Privacy page
-    let result = Command::new("open")
-        .arg("x-apple.systempreferences:com.apple.preference.security")
-        .spawn();
-
-    match result {
-        Ok(_) => {
-            info!("✅ Opened System Settings - navigate to Privacy & Security → Audio Capture");
-            info!("👉 Please enable Audio Capture permission and restart the app");
+    match open_privacy_settings(PrivacyPane::SystemAudio.anchor()) {
+        Ok(()) => {
+            info!("✅ Opened System Settings → Privacy & Security → Screen & System Audio Recording");
             Ok(())
         }
         Err(e) => {
-            error!("❌ Failed to open System Settings: {}", e);
-            Err(anyhow::anyhow!("Failed to open System Settings: {}", e))
+            error!("❌ {}", e);
+            Err(e)
         }
     }
 }
@@ -79,7 +240,7 @@ pub fn ensure_screen_recording_permission() -> bool {
         return false;
     }
 
-    false // Permission will be granted after restart
+    false // The permission monitor reports when it's granted
 }
 
 /// Tauri command to check Screen Recording permission
@@ -90,9 +251,9 @@ pub async fn check_screen_recording_permission_command() -> bool {
 
 /// Tauri command to request Screen Recording permission
 #[tauri::command]
-pub async fn request_screen_recording_permission_command() -> Result<(), String> {
+pub async fn request_screen_recording_permission_command() -> Result<(), AppError> {
     request_screen_recording_permission()
-        .map_err(|e| e.to_string())
+        .map_err(|e| AppError::new(ErrorCode::PermissionDenied, e.to_string()))
 }
 
 /// Trigger system audio permission request programmatically
@@ -118,7 +279,7 @@ pub fn trigger_system_audio_permission() -> Result<()> {
                     let error_msg = e.to_string().to_lowercase();
                     if error_msg.contains("permission") || error_msg.contains("audio") {
                         info!("🔐 Audio Capture permission dialog should have appeared");
-                        info!("👉 Please grant Audio Capture permission and restart the app");
+                        info!("👉 Please grant Audio Capture permission");
                         Ok(()) // This is expected - we triggered the dialog
                     } else {
                         warn!("⚠️ Failed to create system audio stream: {}", e);
@@ -132,7 +293,7 @@ pub fn trigger_system_audio_permission() -> Result<()> {
             let error_msg = e.to_string().to_lowercase();
             if error_msg.contains("permission") || error_msg.contains("audio") {
                 info!("🔐 Audio Capture permission dialog should have appeared");
-                info!("👉 Please grant Audio Capture permission and restart the app");
+                info!("👉 Please grant Audio Capture permission");
                 Ok(()) // This is expected - we triggered the dialog
             } else {
                 warn!("⚠️ Failed to trigger Audio Capture permission: {}", e);
@@ -151,35 +312,290 @@ pub fn trigger_system_audio_permission() -> Result<()> {
 
 /// Tauri command to trigger system audio permission request
 #[tauri::command]
-pub async fn trigger_system_audio_permission_command() -> Result<(), String> {
+pub async fn trigger_system_audio_permission_command() -> Result<(), AppError> {
     trigger_system_audio_permission()
-        .map_err(|e| e.to_string())
+        .map_err(|e| AppError::new(ErrorCode::PermissionDenied, e.to_string()))
 }
 
-/// Check if the app has microphone permission
-/// This uses cpal to attempt to enumerate input devices, which triggers the permission dialog
+/// Microphone authorization state as reported by the OS privacy subsystem (TCC on macOS,
+/// the capability access settings on Windows)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MicrophonePermissionStatus {
+    /// User granted access
+    Authorized,
+    /// User explicitly denied access - only the system settings can change this
+    Denied,
+    /// User has not been asked yet - requesting will show the system prompt
+    NotDetermined,
+    /// Access is blocked by policy (parental controls, MDM)
+    Restricted,
+}
+
+impl MicrophonePermissionStatus {
+    /// Map a raw `AVAuthorizationStatus` value to a status
+    pub fn from_raw(raw: isize) -> Self {
+        match raw {
+            0 => MicrophonePermissionStatus::NotDetermined,
+            1 => MicrophonePermissionStatus::Restricted,
+            2 => MicrophonePermissionStatus::Denied,
+            3 => MicrophonePermissionStatus::Authorized,
+            other => {
+                warn!("Unknown AVAuthorizationStatus value {}, treating as NotDetermined", other);
+                MicrophonePermissionStatus::NotDetermined
+            }
+        }
+    }
+
+    pub fn is_granted(&self) -> bool {
+        *self == MicrophonePermissionStatus::Authorized
+    }
+
+    /// Whether asking the OS would show a prompt (as opposed to requiring System Settings)
+    pub fn can_request(&self) -> bool {
+        *self == MicrophonePermissionStatus::NotDetermined
+    }
+}
+
+/// Query the real microphone authorization state via AVFoundation
+///
+/// Unlike device enumeration, this reflects the TCC database: a default input device
+/// is still listed when the user has denied access, but the status will be `Denied`.
 #[cfg(target_os = "macos")]
-pub fn check_microphone_permission() -> bool {
-    use cpal::traits::HostTrait;
-    
-    info!("🎤 Checking microphone permission...");
-    
-    let host = cpal::default_host();
-    
-    // Try to get the default input device
-    match host.default_input_device() {
-        Some(_device) => {
-            info!("✅ Microphone permission granted - default input device available");
-            true
+pub fn get_microphone_permission_status() -> MicrophonePermissionStatus {
+    use cidre::av;
+
+    let media_type = av::MediaType::audio();
+    let status = av::CaptureDevice::authorization_status_for_media_type(media_type);
+    MicrophonePermissionStatus::from_raw(status as isize)
+}
+
+/// Where Windows keeps the microphone privacy switches: under HKLM the one for the whole
+/// device, under HKCU the user's, with a `NonPackaged` subkey for desktop apps like this one
+#[cfg(target_os = "windows")]
+const MICROPHONE_CONSENT_KEY: &str =
+    r"Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone";
+
+/// Group policy that can take the switches out of the user's hands
+#[cfg(target_os = "windows")]
+const APP_PRIVACY_POLICY_KEY: &str = r"SOFTWARE\Policies\Microsoft\Windows\AppPrivacy";
+
+/// Settings → Privacy & security → Microphone
+#[cfg(target_os = "windows")]
+const MICROPHONE_SETTINGS_URI: &str = "ms-settings:privacy-microphone";
+
+/// The microphone privacy settings of Windows 10/11, as stored in the registry
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WindowsMicrophoneConsent {
+    /// "Microphone access" for the whole device: "Allow" or "Deny"
+    pub device: Option<String>,
+    /// "Microphone access" for the current user
+    pub user: Option<String>,
+    /// "Let desktop apps access your microphone"
+    pub desktop_apps: Option<String>,
+    /// The `LetAppsAccessMicrophone` policy: 1 forces access on, 2 forces it off
+    pub policy: Option<u32>,
+}
+
+impl WindowsMicrophoneConsent {
+    /// Desktop apps are never prompted; they have access unless a switch is off. A missing
+    /// value is a switch never touched, which Windows treats as on.
+    pub fn status(&self) -> MicrophonePermissionStatus {
+        match self.policy {
+            Some(1) => return MicrophonePermissionStatus::Authorized,
+            Some(2) => return MicrophonePermissionStatus::Restricted,
+            _ => {}
         }
-        None => {
-            warn!("⚠️ No default microphone device available - permission may not be granted");
-            false
+        let denied = [&self.device, &self.user, &self.desktop_apps]
+            .iter()
+            .any(|value| value.as_deref().is_some_and(|value| value.eq_ignore_ascii_case("deny")));
+        if denied {
+            MicrophonePermissionStatus::Denied
+        } else {
+            MicrophonePermissionStatus::Authorized
         }
     }
 }
 
-#[cfg(not(target_os = "macos"))]
+#[cfg(target_os = "windows")]
+fn read_windows_microphone_consent() -> WindowsMicrophoneConsent {
+    use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+    use winreg::RegKey;
+
+    let value = |root: &RegKey, path: &str| -> Option<String> {
+        root.open_subkey(path).and_then(|key| key.get_value::<String, _>("Value")).ok()
+    };
+    let machine = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let user = RegKey::predef(HKEY_CURRENT_USER);
+    WindowsMicrophoneConsent {
+        device: value(&machine, MICROPHONE_CONSENT_KEY),
+        user: value(&user, MICROPHONE_CONSENT_KEY),
+        desktop_apps: value(&user, &format!(r"{}\NonPackaged", MICROPHONE_CONSENT_KEY)),
+        policy: machine
+            .open_subkey(APP_PRIVACY_POLICY_KEY)
+            .and_then(|key| key.get_value::<u32, _>("LetAppsAccessMicrophone"))
+            .ok(),
+    }
+}
+
+/// Query the microphone privacy settings
+///
+/// With access off, Windows still lists the microphones but records silence from them.
+#[cfg(target_os = "windows")]
+pub fn get_microphone_permission_status() -> MicrophonePermissionStatus {
+    read_windows_microphone_consent().status()
+}
+
+/// Where Flatpak describes the sandbox the app runs in
+#[cfg(target_os = "linux")]
+const FLATPAK_INFO_PATH: &str = "/.flatpak-info";
+
+/// `snapctl` is run at most this often, as the permission monitor polls every second
+#[cfg(target_os = "linux")]
+const SNAP_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The sandbox a Linux install runs in, which decides whether audio devices can be reached
+///
+/// Unsandboxed apps talk to PulseAudio or PipeWire directly. A Flatpak needs the PulseAudio
+/// socket (or the PipeWire one) in its permissions and a Snap the `audio-record` interface
+/// connected. The app can't grant either to itself, and the XDG Desktop Portal's Device
+/// interface only answers callers outside the sandbox, so the user is told what to run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinuxSandbox {
+    None,
+    Flatpak { app_id: String, audio: bool },
+    /// `audio_record` is None when `snapctl` couldn't tell
+    Snap { name: String, audio_record: Option<bool> },
+}
+
+impl LinuxSandbox {
+    pub fn microphone_status(&self) -> MicrophonePermissionStatus {
+        match self {
+            LinuxSandbox::Flatpak { audio: false, .. } | LinuxSandbox::Snap { audio_record: Some(false), .. } => {
+                MicrophonePermissionStatus::Denied
+            }
+            _ => MicrophonePermissionStatus::Authorized,
+        }
+    }
+
+    /// What the user can run to give the sandbox access to the microphone
+    pub fn remedy(&self) -> Option<String> {
+        match self {
+            LinuxSandbox::Flatpak { app_id, audio: false } => Some(format!(
+                "Meetily runs as a Flatpak without access to audio devices. Run `flatpak override --user \
+                 --socket=pulseaudio {}`, then restart Meetily.",
+                app_id
+            )),
+            LinuxSandbox::Snap { name, audio_record: Some(false) } => Some(format!(
+                "Meetily runs as a Snap without access to the microphone. Run `sudo snap connect {}:audio-record`.",
+                name
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a Flatpak's `/.flatpak-info`: its app id, and whether it may use the PulseAudio or
+/// PipeWire socket
+pub fn parse_flatpak_info(contents: &str) -> (Option<String>, bool) {
+    let mut section = "";
+    let mut app_id = None;
+    let mut audio = false;
+    for line in contents.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            section = name;
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let mut entries = value.split(';').map(|entry| entry.split(':').next().unwrap_or(entry).trim());
+        match (section, key.trim()) {
+            ("Application", "name") => app_id = Some(value.trim().to_string()),
+            ("Context", "sockets") => audio |= entries.any(|entry| entry == "pulseaudio"),
+            ("Context", "filesystems") => audio |= entries.any(|entry| entry == "xdg-run/pipewire-0"),
+            _ => {}
+        }
+    }
+    (app_id, audio)
+}
+
+#[cfg(target_os = "linux")]
+fn detect_linux_sandbox() -> LinuxSandbox {
+    if let Ok(contents) = std::fs::read_to_string(FLATPAK_INFO_PATH) {
+        let (app_id, audio) = parse_flatpak_info(&contents);
+        let app_id = app_id.or_else(|| std::env::var("FLATPAK_ID").ok()).unwrap_or_default();
+        return LinuxSandbox::Flatpak { app_id, audio };
+    }
+
+    if let Ok(name) = std::env::var("SNAP_NAME") {
+        // Exits 0 when connected and 1 when not; anything else means it couldn't tell
+        let audio_record = match std::process::Command::new("snapctl").args(["is-connected", "audio-record"]).status() {
+            Ok(status) => match status.code() {
+                Some(0) => Some(true),
+                Some(1) => Some(false),
+                _ => None,
+            },
+            Err(e) => {
+                warn!("Failed to run snapctl: {}", e);
+                None
+            }
+        };
+        return LinuxSandbox::Snap { name, audio_record };
+    }
+
+    LinuxSandbox::None
+}
+
+/// The sandbox the app runs in, checked again every few seconds for a Snap whose
+/// interfaces can be connected while it runs
+#[cfg(target_os = "linux")]
+pub fn linux_sandbox() -> LinuxSandbox {
+    static CACHED: Lazy<std::sync::Mutex<Option<(std::time::Instant, LinuxSandbox)>>> =
+        Lazy::new(|| std::sync::Mutex::new(None));
+
+    let mut cached = CACHED.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    match cached.as_ref() {
+        Some((_, sandbox @ (LinuxSandbox::None | LinuxSandbox::Flatpak { .. }))) => return sandbox.clone(),
+        Some((checked_at, sandbox)) if checked_at.elapsed() < SNAP_CHECK_INTERVAL => return sandbox.clone(),
+        _ => {}
+    }
+    let sandbox = detect_linux_sandbox();
+    if let Some(remedy) = sandbox.remedy() {
+        if cached.as_ref().map(|(_, previous)| previous) != Some(&sandbox) {
+            warn!("⚠️ {}", remedy);
+        }
+    }
+    *cached = Some((std::time::Instant::now(), sandbox.clone()));
+    sandbox
+}
+
+/// Query whether the sandbox, if any, lets the app reach the microphone
+#[cfg(target_os = "linux")]
+pub fn get_microphone_permission_status() -> MicrophonePermissionStatus {
+    linux_sandbox().microphone_status()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn get_microphone_permission_status() -> MicrophonePermissionStatus {
+    MicrophonePermissionStatus::Authorized // Not required on other platforms
+}
+
+/// Check if the app has microphone permission
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn check_microphone_permission() -> bool {
+    info!("🎤 Checking microphone permission...");
+
+    let status = get_microphone_permission_status();
+    if status.is_granted() {
+        info!("✅ Microphone permission granted");
+    } else {
+        warn!("⚠️ Microphone permission not granted: {:?}", status);
+    }
+    status.is_granted()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
 pub fn check_microphone_permission() -> bool {
     true // Not required on other platforms
 }
@@ -204,7 +620,33 @@ pub fn request_microphone_permission() -> Result<()> {
     }
 }
 
-#[cfg(not(target_os = "macos"))]
+/// Windows has no prompt for desktop apps, so this opens the microphone privacy settings
+/// unless access is already on
+#[cfg(target_os = "windows")]
+pub fn request_microphone_permission() -> Result<()> {
+    if get_microphone_permission_status().is_granted() {
+        return Ok(());
+    }
+
+    info!("🔐 Opening Settings → Privacy & security → Microphone...");
+    std::process::Command::new("explorer")
+        .arg(MICROPHONE_SETTINGS_URI)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| anyhow::anyhow!("Failed to open the microphone privacy settings: {}", e))
+}
+
+/// A sandboxed install can't be granted access from inside, so this fails with what the
+/// user can run instead
+#[cfg(target_os = "linux")]
+pub fn request_microphone_permission() -> Result<()> {
+    match linux_sandbox().remedy() {
+        Some(remedy) => Err(anyhow::anyhow!(remedy)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
 pub fn request_microphone_permission() -> Result<()> {
     Ok(()) // Not required on other platforms
 }
@@ -214,12 +656,22 @@ pub fn request_microphone_permission() -> Result<()> {
 /// Returns true if permission is already granted, false if it was just requested
 #[cfg(target_os = "macos")]
 pub fn ensure_microphone_permission() -> bool {
-    if check_microphone_permission() {
+    let status = get_microphone_permission_status();
+    if status.is_granted() {
         info!("✅ Microphone permission already granted");
         return true;
     }
+
+    if !status.can_request() {
+        // The system prompt is only shown once; after that the user must use System Settings
+        warn!("⚠️ Microphone permission is {:?} - grant it in System Settings → Privacy & Security → Microphone", status);
+        if let Err(e) = open_privacy_settings(PrivacyPane::Microphone.anchor()) {
+            error!("❌ {}", e);
+        }
+        return false;
+    }
     
-    info!("⚠️ Microphone permission not granted - requesting...");
+    info!("⚠️ Microphone permission not determined - requesting...");
     
     if let Err(e) = request_microphone_permission() {
         error!("❌ Failed to request microphone permission: {}", e);
@@ -237,7 +689,35 @@ pub fn ensure_microphone_permission() -> bool {
     granted
 }
 
-#[cfg(not(target_os = "macos"))]
+#[cfg(target_os = "windows")]
+pub fn ensure_microphone_permission() -> bool {
+    let status = get_microphone_permission_status();
+    if status.is_granted() {
+        return true;
+    }
+
+    warn!(
+        "⚠️ Microphone permission is {:?} - turn on microphone access in Settings → Privacy & security → Microphone",
+        status
+    );
+    if let Err(e) = request_microphone_permission() {
+        error!("❌ Failed to open the microphone privacy settings: {}", e);
+    }
+    false // The permission monitor reports when access is turned on
+}
+
+#[cfg(target_os = "linux")]
+pub fn ensure_microphone_permission() -> bool {
+    match linux_sandbox().remedy() {
+        Some(remedy) => {
+            warn!("⚠️ {}", remedy);
+            false
+        }
+        None => true,
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
 pub fn ensure_microphone_permission() -> bool {
     true // Not required on other platforms
 }
@@ -248,11 +728,17 @@ pub async fn check_microphone_permission_command() -> bool {
     check_microphone_permission()
 }
 
+/// Tauri command to get the detailed microphone authorization state
+#[tauri::command]
+pub async fn get_microphone_permission_status_command() -> MicrophonePermissionStatus {
+    get_microphone_permission_status()
+}
+
 /// Tauri command to request microphone permission
 #[tauri::command]
-pub async fn request_microphone_permission_command() -> Result<(), String> {
+pub async fn request_microphone_permission_command() -> Result<(), AppError> {
     request_microphone_permission()
-        .map_err(|e| e.to_string())
+        .map_err(|e| AppError::new(ErrorCode::PermissionDenied, e.to_string()))
 }
 
 /// Tauri command to ensure microphone permission (check and request if needed)
@@ -273,11 +759,17 @@ pub fn init_microphone_permission() {
             // Small delay to ensure app is fully initialized
             std::thread::sleep(std::time::Duration::from_millis(500));
             
-            if !check_microphone_permission() {
-                info!("🔐 Microphone permission not granted, requesting...");
-                let _ = request_microphone_permission();
-            } else {
-                info!("✅ Microphone permission already granted");
+            match get_microphone_permission_status() {
+                MicrophonePermissionStatus::Authorized => {
+                    info!("✅ Microphone permission already granted");
+                }
+                MicrophonePermissionStatus::NotDetermined => {
+                    info!("🔐 Microphone permission not determined, requesting...");
+                    let _ = request_microphone_permission();
+                }
+                status => {
+                    warn!("⚠️ Microphone permission is {:?}, user must enable it in System Settings", status);
+                }
             }
         });
     });
@@ -288,6 +780,115 @@ pub fn init_microphone_permission() {
     // Not required on other platforms
 }
 
+/// Snapshot of all permissions relevant to recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PermissionState {
+    pub mic: MicrophonePermissionStatus,
+    pub system_audio: SystemAudioPermissionStatus,
+}
+
+impl PermissionState {
+    pub fn current() -> Self {
+        Self {
+            mic: get_microphone_permission_status(),
+            system_audio: system_audio_permission_status(),
+        }
+    }
+
+    /// The permissions granted in `self` that weren't in `previous`
+    pub fn newly_granted(&self, previous: &PermissionState) -> Vec<&'static str> {
+        let mut granted = Vec::new();
+        if self.mic.is_granted() && !previous.mic.is_granted() {
+            granted.push("microphone");
+        }
+        if self.system_audio.is_granted() && !previous.system_audio.is_granted() {
+            granted.push("system_audio");
+        }
+        granted
+    }
+}
+
+/// Event emitted to the frontend whenever a permission changes
+pub const PERMISSION_STATE_CHANGED_EVENT: &str = "permission-state-changed";
+
+/// Event emitted when a permission flips to granted, with the permission's name
+/// ("microphone" or "system_audio")
+pub const PERMISSION_GRANTED_EVENT: &str = "permission-granted";
+
+/// How often TCC state is polled (there is no public change notification for it)
+const PERMISSION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Background watcher that emits `permission-state-changed` when the user grants or
+/// revokes access, so the UI can unlock recording without an app restart
+pub struct PermissionMonitor {
+    running: Arc<AtomicBool>,
+}
+
+impl PermissionMonitor {
+    pub fn new() -> Self {
+        Self {
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Start polling; the current state is emitted immediately so listeners start in sync
+    pub fn start<R: Runtime>(&self, app: AppHandle<R>) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return; // Already running
+        }
+
+        let running = self.running.clone();
+        tauri::async_runtime::spawn(async move {
+            info!("🔐 Permission monitor started");
+            let mut last: Option<PermissionState> = None;
+
+            while running.load(Ordering::SeqCst) {
+                let state = PermissionState::current();
+                if last != Some(state) {
+                    if let Some(previous) = last {
+                        info!("🔐 Permission state changed: {:?} -> {:?}", previous, state);
+                        for permission in state.newly_granted(&previous) {
+                            if let Err(e) = app.emit(PERMISSION_GRANTED_EVENT, permission) {
+                                error!("Failed to emit permission grant: {}", e);
+                            }
+                        }
+                    }
+                    if let Err(e) = app.emit(PERMISSION_STATE_CHANGED_EVENT, &state) {
+                        error!("Failed to emit permission state: {}", e);
+                    }
+                    last = Some(state);
+                }
+                tokio::time::sleep(PERMISSION_POLL_INTERVAL).await;
+            }
+
+            info!("🔐 Permission monitor stopped");
+        });
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for PermissionMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Global permission monitor instance
+pub static PERMISSION_MONITOR: Lazy<PermissionMonitor> = Lazy::new(PermissionMonitor::new);
+
+/// Tauri command to get the current permission snapshot
+#[tauri::command]
+pub async fn get_permission_state_command() -> PermissionState {
+    PermissionState::current()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,4 +904,133 @@ mod tests {
         let has_permission = check_microphone_permission();
         println!("Has Microphone permission: {}", has_permission);
     }
+
+    #[test]
+    fn test_microphone_status_from_raw() {
+        assert_eq!(MicrophonePermissionStatus::from_raw(0), MicrophonePermissionStatus::NotDetermined);
+        assert_eq!(MicrophonePermissionStatus::from_raw(1), MicrophonePermissionStatus::Restricted);
+        assert_eq!(MicrophonePermissionStatus::from_raw(2), MicrophonePermissionStatus::Denied);
+        assert_eq!(MicrophonePermissionStatus::from_raw(3), MicrophonePermissionStatus::Authorized);
+        assert_eq!(MicrophonePermissionStatus::from_raw(42), MicrophonePermissionStatus::NotDetermined);
+    }
+
+    #[test]
+    fn test_windows_microphone_consent() {
+        let allow = Some("Allow".to_string());
+        let deny = Some("Deny".to_string());
+        assert_eq!(WindowsMicrophoneConsent::default().status(), MicrophonePermissionStatus::Authorized);
+
+        let consent = WindowsMicrophoneConsent { device: allow.clone(), user: allow.clone(), ..Default::default() };
+        assert_eq!(consent.status(), MicrophonePermissionStatus::Authorized);
+        let desktop_apps_off = WindowsMicrophoneConsent { desktop_apps: deny.clone(), ..consent.clone() };
+        assert_eq!(desktop_apps_off.status(), MicrophonePermissionStatus::Denied);
+        let device_off = WindowsMicrophoneConsent { device: deny.clone(), ..consent.clone() };
+        assert_eq!(device_off.status(), MicrophonePermissionStatus::Denied);
+
+        // Group policy wins over the switches
+        assert_eq!(
+            WindowsMicrophoneConsent { policy: Some(2), ..consent }.status(),
+            MicrophonePermissionStatus::Restricted
+        );
+        assert_eq!(
+            WindowsMicrophoneConsent { policy: Some(1), ..desktop_apps_off }.status(),
+            MicrophonePermissionStatus::Authorized
+        );
+    }
+
+    #[test]
+    fn test_parse_flatpak_info() {
+        let info = "[Application]\nname=com.meetily.Meetily\nruntime=runtime/org.gnome.Platform/x86_64/46\n\n\
+                    [Context]\nshared=network;ipc;\nsockets=x11;wayland;pulseaudio;\n";
+        assert_eq!(parse_flatpak_info(info), (Some("com.meetily.Meetily".to_string()), true));
+
+        let no_audio = "[Application]\nname=com.meetily.Meetily\n[Context]\nsockets=wayland;\n";
+        assert_eq!(parse_flatpak_info(no_audio), (Some("com.meetily.Meetily".to_string()), false));
+
+        let pipewire = "[Context]\nsockets=wayland;\nfilesystems=xdg-run/pipewire-0:ro;home;\n";
+        assert_eq!(parse_flatpak_info(pipewire), (None, true));
+    }
+
+    #[test]
+    fn test_linux_sandbox_remedy() {
+        assert_eq!(LinuxSandbox::None.microphone_status(), MicrophonePermissionStatus::Authorized);
+        assert_eq!(LinuxSandbox::None.remedy(), None);
+
+        let flatpak = LinuxSandbox::Flatpak { app_id: "com.meetily.Meetily".to_string(), audio: false };
+        assert_eq!(flatpak.microphone_status(), MicrophonePermissionStatus::Denied);
+        assert!(flatpak.remedy().unwrap().contains("--socket=pulseaudio com.meetily.Meetily"));
+
+        // snapctl couldn't tell, so recording is left to try
+        let snap = LinuxSandbox::Snap { name: "meetily".to_string(), audio_record: None };
+        assert_eq!(snap.microphone_status(), MicrophonePermissionStatus::Authorized);
+        let snap = LinuxSandbox::Snap { name: "meetily".to_string(), audio_record: Some(false) };
+        assert!(snap.remedy().unwrap().contains("snap connect meetily:audio-record"));
+    }
+
+    #[test]
+    fn test_privacy_settings_url() {
+        assert_eq!(parse_macos_version("14.4.1\n"), Some((14, 4)));
+        assert_eq!(parse_macos_version("15"), Some((15, 0)));
+        assert_eq!(parse_macos_version(""), None);
+
+        let anchor = PrivacyPane::Microphone.anchor();
+        assert_eq!(
+            privacy_settings_url(anchor, Some((14, 4))),
+            "x-apple.systempreferences:com.apple.settings.PrivacySecurity.extension?Privacy_Microphone"
+        );
+        assert_eq!(
+            privacy_settings_url(PrivacyPane::SystemAudio.anchor(), Some((12, 6))),
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture"
+        );
+        assert!(privacy_settings_url(anchor, None).contains("com.apple.preference.security"));
+    }
+
+    #[test]
+    fn test_newly_granted() {
+        let denied = PermissionState {
+            mic: MicrophonePermissionStatus::Denied,
+            system_audio: SystemAudioPermissionStatus::Denied,
+        };
+        let granted = PermissionState {
+            mic: MicrophonePermissionStatus::Authorized,
+            system_audio: SystemAudioPermissionStatus::Authorized,
+        };
+        assert_eq!(granted.newly_granted(&denied), vec!["microphone", "system_audio"]);
+        assert!(denied.newly_granted(&granted).is_empty());
+        assert!(granted.newly_granted(&granted).is_empty());
+
+        // Answering the prompt counts as a grant too
+        let undecided = PermissionState { system_audio: SystemAudioPermissionStatus::Unknown, ..granted };
+        assert_eq!(granted.newly_granted(&undecided), vec!["system_audio"]);
+    }
+
+    #[test]
+    fn test_system_audio_status_from_preflight() {
+        assert_eq!(SystemAudioPermissionStatus::from_preflight(Some(0)), SystemAudioPermissionStatus::Authorized);
+        assert_eq!(SystemAudioPermissionStatus::from_preflight(Some(1)), SystemAudioPermissionStatus::Denied);
+        assert_eq!(SystemAudioPermissionStatus::from_preflight(Some(2)), SystemAudioPermissionStatus::Unknown);
+        assert_eq!(SystemAudioPermissionStatus::from_preflight(None), SystemAudioPermissionStatus::Unknown);
+
+        assert!(SystemAudioPermissionStatus::NotRequired.is_granted());
+        assert!(!SystemAudioPermissionStatus::Unknown.is_granted());
+        assert!(SystemAudioPermissionStatus::Unknown.allows_capture());
+        assert!(!SystemAudioPermissionStatus::Denied.allows_capture());
+        let json = serde_json::to_string(&SystemAudioPermissionStatus::NotRequired).unwrap();
+        assert_eq!(json, "\"not_required\"");
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_system_audio_not_required_elsewhere() {
+        assert_eq!(system_audio_permission_status(), SystemAudioPermissionStatus::NotRequired);
+        assert!(check_screen_recording_permission());
+    }
+
+    #[test]
+    fn test_microphone_status_serialization() {
+        let json = serde_json::to_string(&MicrophonePermissionStatus::NotDetermined).unwrap();
+        assert_eq!(json, "\"not_determined\"");
+        assert!(MicrophonePermissionStatus::Authorized.is_granted());
+        assert!(!MicrophonePermissionStatus::Denied.can_request());
+    }
 }
\ No newline at end of file
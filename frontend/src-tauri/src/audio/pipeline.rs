@@ -6,18 +6,29 @@ use anyhow::Result;
 use log::{debug, error, info, warn};
 use crate::{perf_debug, batch_audio_metric};
 use super::batch_processor::AudioMetricsBatcher;
-use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction};
 
 use super::devices::AudioDevice;
 use super::recording_state::{AudioChunk, AudioError, RecordingState, DeviceType};
-use super::audio_processing::{audio_to_mono, LoudnessNormalizer, NoiseSuppressionProcessor, HighPassFilter};
+use super::audio_processing::{
+    audio_to_mono, get_agc_settings, is_noise_suppression_enabled, AutomaticGainControl, LoudnessNormalizer,
+    NoiseSuppressionProcessor, HighPassFilter,
+};
 use super::vad::{ContinuousVadProcessor};
+use super::silence_watchdog::{RecordingWarning, SilenceWatchdog};
+use super::echo_cancellation::{is_echo_cancellation_enabled, EchoCanceller};
+use super::resample::StreamingResampler;
+use super::clock_drift::DriftCompensator;
+use super::pipeline_stats::{self, Stage};
+use super::channel_mix::{get_channel_mix_policy, mix_for_recording, stereo_side, ChannelMixPolicy};
+use super::transcription::streaming::{self, get_live_caption_settings, PartialScheduler, PartialWindow};
 
 /// Ring buffer for synchronized audio mixing
 /// Accumulates samples from mic and system streams until we have aligned windows
 struct AudioMixerRingBuffer {
     mic_buffer: VecDeque<f32>,
     system_buffer: VecDeque<f32>,
+    // Stereo side signal of system audio, kept sample-aligned with system_buffer
+    system_side_buffer: VecDeque<f32>,
     window_size_samples: usize,  // Fixed mixing window (e.g., 50ms)
     max_buffer_size: usize,  // Safety limit (e.g., 100ms)
 }
@@ -41,12 +52,15 @@ impl AudioMixerRingBuffer {
         Self {
             mic_buffer: VecDeque::with_capacity(max_buffer_size),
             system_buffer: VecDeque::with_capacity(max_buffer_size),
+            system_side_buffer: VecDeque::with_capacity(max_buffer_size),
             window_size_samples,
             max_buffer_size,
         }
     }
 
-    fn add_samples(&mut self, device_type: DeviceType, samples: Vec<f32>) {
+    fn add_samples(&mut self, device_type: DeviceType, samples: Vec<f32>, stereo_side: Option<Vec<f32>>) {
+        let received = samples.len() as u64;
+
         // Log buffer health periodically for diagnostics
         static mut SAMPLE_COUNTER: u64 = 0;
         unsafe {
@@ -59,7 +73,13 @@ impl AudioMixerRingBuffer {
 
         match device_type {
             DeviceType::Microphone => self.mic_buffer.extend(samples),
-            DeviceType::System => self.system_buffer.extend(samples),
+            DeviceType::System => {
+                // Mono devices (or layouts that don't need it) have no side signal
+                let mut side = stereo_side.unwrap_or_default();
+                side.resize(samples.len(), 0.0);
+                self.system_side_buffer.extend(side);
+                self.system_buffer.extend(samples);
+            }
         }
 
         // CRITICAL FIX: Add warnings before dropping samples
@@ -75,13 +95,28 @@ impl AudioMixerRingBuffer {
                   self.system_buffer.len() - self.max_buffer_size);
         }
 
+        let overflow = self.mic_buffer.len().saturating_sub(self.max_buffer_size)
+            + self.system_buffer.len().saturating_sub(self.max_buffer_size);
+        if overflow > 0 {
+            pipeline_stats::record_dropped(Stage::Mixer, overflow as u64);
+        }
+        // Only what survives the overflow goes on to be mixed
+        pipeline_stats::record_processed(Stage::Mixer, received.saturating_sub(overflow as u64));
+
         // Safety: prevent buffer overflow (keep only last 200ms)
         while self.mic_buffer.len() > self.max_buffer_size {
             self.mic_buffer.pop_front();
         }
         while self.system_buffer.len() > self.max_buffer_size {
             self.system_buffer.pop_front();
+            self.system_side_buffer.pop_front();
         }
+        self.report_depth();
+    }
+
+    /// Queue depth for the pipeline stats: the fuller of the two buffers
+    fn report_depth(&self) {
+        pipeline_stats::set_queue_depth(Stage::Mixer, self.mic_buffer.len().max(self.system_buffer.len()) as u64);
     }
 
     fn can_mix(&self) -> bool {
@@ -89,7 +124,7 @@ impl AudioMixerRingBuffer {
         self.system_buffer.len() >= self.window_size_samples
     }
 
-    fn extract_window(&mut self) -> Option<(Vec<f32>, Vec<f32>)> {
+    fn extract_window(&mut self) -> Option<(Vec<f32>, Vec<f32>, Vec<f32>)> {
         if !self.can_mix() {
             return None;
         }
@@ -117,6 +152,11 @@ impl AudioMixerRingBuffer {
             vec![0.0; self.window_size_samples]
         };
 
+        // Side window mirrors the system window
+        let side_len = self.window_size_samples.min(self.system_side_buffer.len());
+        let mut side_window: Vec<f32> = self.system_side_buffer.drain(..side_len).collect();
+        side_window.resize(self.window_size_samples, 0.0);
+
         // Extract system window (or pad with zeros if insufficient data)
         let sys_window = if self.system_buffer.len() >= self.window_size_samples {
             // Enough system data - drain window
@@ -136,8 +176,9 @@ impl AudioMixerRingBuffer {
             // No system data - return silence
             vec![0.0; self.window_size_samples]
         };
+        self.report_depth();
 
-        Some((mic_window, sys_window))
+        Some((mic_window, sys_window, side_window))
     }
 
 }
@@ -200,16 +241,20 @@ pub struct AudioCapture {
     device_type: DeviceType,
     recording_sender: Option<mpsc::UnboundedSender<AudioChunk>>,
     needs_resampling: bool,  // Flag if resampling is required
-    // CRITICAL FIX: Persistent resampler to preserve energy across chunks
-    resampler: Arc<std::sync::Mutex<Option<SincFixedIn<f32>>>>,
-    // Buffering for variable-size chunks → fixed-size resampler input
-    resampler_input_buffer: Arc<std::sync::Mutex<Vec<f32>>>,
-    resampler_chunk_size: usize,  // Fixed chunk size for resampler (512 samples)
+    // CRITICAL FIX: Persistent streaming resampler to preserve energy and timing across chunks
+    resampler: Arc<std::sync::Mutex<Option<StreamingResampler>>>,
+    // Stereo system audio: carry the side signal so the saved file can keep the stereo image
+    carry_stereo_side: bool,
+    side_resampler: Arc<std::sync::Mutex<Option<StreamingResampler>>>,
     // Audio enhancement processors (microphone only)
     noise_suppressor: Arc<std::sync::Mutex<Option<NoiseSuppressionProcessor>>>,
     high_pass_filter: Arc<std::sync::Mutex<Option<HighPassFilter>>>,
     // EBU R128 normalizer for microphone audio (per-device, stateful)
     normalizer: Arc<std::sync::Mutex<Option<LoudnessNormalizer>>>,
+    // Short-term automatic gain control for microphone audio (replaces the normalizer when enabled)
+    agc: Arc<std::sync::Mutex<Option<AutomaticGainControl>>>,
+    // Detects muted/denied inputs that deliver only silence
+    silence_watchdog: Arc<std::sync::Mutex<SilenceWatchdog>>,
     // Note: Using global recording timestamp for synchronization
 }
 
@@ -244,20 +289,6 @@ impl AudioCapture {
                 sample_rate, TARGET_SAMPLE_RATE
             );
 
-            // Log which resampling strategy will be used
-            let ratio = TARGET_SAMPLE_RATE as f64 / sample_rate as f64;
-            let strategy = if ratio >= 2.0 {
-                "High-quality upsampling (sinc_len=512, Cubic interpolation)"
-            } else if ratio >= 1.5 {
-                "Moderate upsampling (sinc_len=384, Cubic)"
-            } else if ratio > 1.0 {
-                "Small upsampling (sinc_len=256, Linear)"
-            } else if ratio <= 0.5 {
-                "Anti-aliased downsampling (sinc_len=512, Cubic)"
-            } else {
-                "Moderate downsampling (sinc_len=384, Linear)"
-            };
-            info!("   Resampling strategy: {}", strategy);
         } else {
             info!(
                 "✅ [{:?}] Audio device '{}' ({:?}) uses {} Hz (matches pipeline)",
@@ -268,22 +299,18 @@ impl AudioCapture {
         // Initialize audio enhancement processors for MICROPHONE ONLY
         // System audio doesn't need enhancement (already clean)
         let (noise_suppressor, high_pass_filter, normalizer) = if matches!(device_type, DeviceType::Microphone) {
-            // Initialize noise suppression (RNNoise) at 48kHz - CONDITIONAL based on flag
-            let ns = if super::ffmpeg_mixer::RNNOISE_APPLY_ENABLED {
-                match NoiseSuppressionProcessor::new(TARGET_SAMPLE_RATE) {
-                    Ok(processor) => {
-                        info!("✅ RNNoise noise suppression ENABLED for microphone '{}' (10-15 dB reduction)", device.name);
-                        Some(processor)
-                    }
-                    Err(e) => {
-                        warn!("⚠️ Failed to create noise suppressor: {}, continuing without noise suppression", e);
-                        None
-                    }
+            // Initialize noise suppression (RNNoise) at 48kHz - always created so it can be
+            // toggled at runtime; only applied while enabled
+            let ns = match NoiseSuppressionProcessor::new(TARGET_SAMPLE_RATE) {
+                Ok(processor) => {
+                    info!("✅ RNNoise noise suppression ready for microphone '{}' (currently {})",
+                          device.name, if is_noise_suppression_enabled() { "enabled" } else { "disabled" });
+                    Some(processor)
+                }
+                Err(e) => {
+                    warn!("⚠️ Failed to create noise suppressor: {}, continuing without noise suppression", e);
+                    None
                 }
-            } else {
-                info!("ℹ️ RNNoise noise suppression DISABLED for microphone '{}' (flag: RNNOISE_APPLY_ENABLED=false)", device.name);
-                info!("   Whisper handles noise well internally - RNNoise is optional");
-                None
             };
 
             // Initialize high-pass filter (removes rumble below 80 Hz)
@@ -312,46 +339,15 @@ impl AudioCapture {
             (None, None, None)
         };
 
-        // CRITICAL FIX: Initialize persistent resampler to preserve energy across chunks
-        // Creating a new resampler per chunk causes energy amplification and incorrect output sizes
-        // Use fixed chunk size of 512 samples with buffering for variable-size input
-        const RESAMPLER_CHUNK_SIZE: usize = 512;
-
+        // CRITICAL FIX: Persistent streaming resampler preserves energy across chunks
+        // Creating a new resampler per chunk causes energy amplification and incorrect output sizes;
+        // the streaming resampler buffers variable-size input (320, 512, 1024, ...) internally
         let resampler = if needs_resampling {
-            let ratio = TARGET_SAMPLE_RATE as f64 / sample_rate as f64;
-
-            // Adaptive parameters based on sample rate ratio (same logic as resample_audio)
-            let (sinc_len, interpolation_type, oversampling) = if ratio >= 2.0 {
-                (512, SincInterpolationType::Cubic, 512)
-            } else if ratio >= 1.5 {
-                (384, SincInterpolationType::Cubic, 384)
-            } else if ratio > 1.0 {
-                (256, SincInterpolationType::Linear, 256)
-            } else if ratio <= 0.5 {
-                (512, SincInterpolationType::Cubic, 512)
-            } else {
-                (384, SincInterpolationType::Linear, 384)
-            };
-
-            let params = SincInterpolationParameters {
-                sinc_len,
-                f_cutoff: 0.95,
-                interpolation: interpolation_type,
-                oversampling_factor: oversampling,
-                window: WindowFunction::BlackmanHarris2,
-            };
-
-            match SincFixedIn::<f32>::new(
-                ratio,
-                2.0,  // Maximum relative deviation
-                params,
-                RESAMPLER_CHUNK_SIZE,
-                1,    // Mono
-            ) {
+            match StreamingResampler::new(sample_rate, TARGET_SAMPLE_RATE) {
                 Ok(resampler) => {
-                    info!("✅ Persistent resampler initialized for '{}' ({}Hz → {}Hz, chunk_size={})",
-                          device.name, sample_rate, TARGET_SAMPLE_RATE, RESAMPLER_CHUNK_SIZE);
-                    info!("   Buffering enabled for variable-size chunks (e.g., 320, 512, 1024, etc.)");
+                    info!("✅ Persistent resampler initialized for '{}': {}Hz → {}Hz, {}",
+                          device.name, sample_rate, TARGET_SAMPLE_RATE, resampler.path().method);
+                    super::resample::register_path(Self::resample_stage(&device_type), resampler.path());
                     Some(resampler)
                 }
                 Err(e) => {
@@ -363,6 +359,20 @@ impl AudioCapture {
             None
         };
 
+        // Side signal gets its own resampler fed with the same block sizes, so its output stays
+        // sample-aligned with the mono signal
+        let carry_stereo_side = matches!(device_type, DeviceType::System)
+            && channels >= 2
+            && get_channel_mix_policy().needs_stereo_side();
+        let side_resampler = if carry_stereo_side && needs_resampling {
+            StreamingResampler::new(sample_rate, TARGET_SAMPLE_RATE).ok()
+        } else {
+            None
+        };
+        if carry_stereo_side {
+            info!("🎚️ Preserving stereo image of system audio '{}' in the recording", device.name);
+        }
+
         Self {
             device,
             state,
@@ -373,22 +383,96 @@ impl AudioCapture {
             recording_sender,
             needs_resampling,
             resampler: Arc::new(std::sync::Mutex::new(resampler)),
-            resampler_input_buffer: Arc::new(std::sync::Mutex::new(Vec::with_capacity(RESAMPLER_CHUNK_SIZE * 2))),
-            resampler_chunk_size: RESAMPLER_CHUNK_SIZE,
+            carry_stereo_side,
+            side_resampler: Arc::new(std::sync::Mutex::new(side_resampler)),
             noise_suppressor: Arc::new(std::sync::Mutex::new(noise_suppressor)),
             high_pass_filter: Arc::new(std::sync::Mutex::new(high_pass_filter)),
             normalizer: Arc::new(std::sync::Mutex::new(normalizer)),
+            agc: Arc::new(std::sync::Mutex::new(
+                matches!(device_type, DeviceType::Microphone).then(|| AutomaticGainControl::new(TARGET_SAMPLE_RATE)),
+            )),
+            silence_watchdog: Arc::new(std::sync::Mutex::new(SilenceWatchdog::new(device_type.clone(), sample_rate))),
             // Using global recording time for sync
         }
     }
 
+    /// Feed the raw (pre-enhancement) block to the silence watchdog
+    /// Bring the stereo side signal to 48kHz alongside the mono signal
+    fn resample_side(&self, side: Vec<f32>) -> Vec<f32> {
+        if !self.needs_resampling {
+            return side;
+        }
+        if let Ok(mut resampler_lock) = self.side_resampler.lock() {
+            if let Some(ref mut resampler) = *resampler_lock {
+                return resampler.process(&side).unwrap_or_default();
+            }
+        }
+        super::audio_processing::resample_audio(&side, self.sample_rate, 48000)
+    }
+
+    /// Diagnostics name of this capture's resampling stage
+    fn resample_stage(device_type: &DeviceType) -> &'static str {
+        match device_type {
+            DeviceType::Microphone => "microphone capture",
+            DeviceType::System => "system capture",
+        }
+    }
+
+    fn check_for_silence(&self, mono_data: &[f32]) {
+        if self.state.is_paused() || self.state.is_confidential() {
+            return;
+        }
+
+        let Ok(mut watchdog) = self.silence_watchdog.try_lock() else {
+            return;
+        };
+        if let Some(reason) = watchdog.observe(mono_data) {
+            self.state.report_warning(RecordingWarning {
+                device_name: self.device.name.clone(),
+                device_type: format!("{:?}", self.device_type),
+                reason,
+                message: reason.user_message().to_string(),
+                silent_for_secs: watchdog.silent_for().as_secs_f64(),
+            });
+        }
+    }
+
+    /// Feed buffered pre-roll audio ahead of the live stream
+    ///
+    /// `start_offset` places it on the recording timeline so that sources with less buffered
+    /// audio still end where live capture begins.
+    pub fn process_preroll(&self, data: &[f32], start_offset: f64) {
+        // 10ms blocks, like a typical device callback
+        let frame_len = self.channels.max(1) as usize;
+        let block_len = (self.sample_rate as usize / 100).max(1) * frame_len;
+
+        for (i, block) in data.chunks(block_len).enumerate() {
+            let timestamp = start_offset + (i * block_len / frame_len) as f64 / self.sample_rate as f64;
+            self.process_samples(block, Some(timestamp));
+        }
+    }
+
     /// Process audio data directly from callback
     pub fn process_audio_data(&self, data: &[f32]) {
+        self.process_samples(data, None);
+    }
+
+    fn process_samples(&self, data: &[f32], timestamp: Option<f64>) {
         // Check if still recording
         if !self.state.is_recording() {
             return;
         }
 
+        // A confidential part is kept as silence, so the timeline stays in step while nothing
+        // said is recorded or transcribed
+        let silenced;
+        let data = if self.state.is_confidential() {
+            silenced = vec![0.0; data.len()];
+            &silenced[..]
+        } else {
+            data
+        };
+
         // Convert to mono if needed
         let mut mono_data = if self.channels > 1 {
             audio_to_mono(data, self.channels)
@@ -396,6 +480,11 @@ impl AudioCapture {
             data.to_vec()
         };
 
+        self.check_for_silence(&mono_data);
+
+        // Resampled before the mono signal so an early return below leaves both in step
+        let side_data = self.carry_stereo_side.then(|| self.resample_side(stereo_side(data, self.channels)));
+
         // CRITICAL FIX: Resample to 48kHz if device uses different sample rate
         // This fixes Bluetooth devices (like Sony WH-1000XM4) that report 16kHz or 44.1kHz
         // Without this, audio is sped up 3x and VAD fails
@@ -415,39 +504,19 @@ impl AudioCapture {
             // Use persistent resampler with buffering to handle variable chunk sizes
             let mut resampled_output = Vec::new();
             let mut used_persistent_resampler = false;
-
-            if let Ok(mut buffer_lock) = self.resampler_input_buffer.lock() {
-                // Add new samples to buffer
-                buffer_lock.extend_from_slice(&mono_data);
-
-                // Process complete chunks through the resampler
-                if let Ok(mut resampler_lock) = self.resampler.lock() {
-                    if let Some(ref mut resampler) = *resampler_lock {
-                        used_persistent_resampler = true;
-
-                        // Process as many complete chunks as we have
-                        while buffer_lock.len() >= self.resampler_chunk_size {
-                            // Extract exactly chunk_size samples
-                            let chunk: Vec<f32> = buffer_lock.drain(0..self.resampler_chunk_size).collect();
-
-                            // Rubato expects input as Vec<Vec<f32>> (one Vec per channel)
-                            let waves_in = vec![chunk];
-
-                            match resampler.process(&waves_in, None) {
-                                Ok(mut waves_out) => {
-                                    if let Some(output) = waves_out.pop() {
-                                        resampled_output.extend_from_slice(&output);
-                                    }
-                                }
-                                Err(e) => {
-                                    warn!("⚠️ Persistent resampler processing failed: {}", e);
-                                    used_persistent_resampler = false;
-                                    break;
-                                }
-                            }
+            let mut drift_samples = 0.0;
+
+            if let Ok(mut resampler_lock) = self.resampler.lock() {
+                if let Some(ref mut resampler) = *resampler_lock {
+                    used_persistent_resampler = true;
+                    match resampler.process(&mono_data) {
+                        Ok(output) => resampled_output = output,
+                        Err(e) => {
+                            warn!("⚠️ Persistent resampler processing failed: {}", e);
+                            used_persistent_resampler = false;
                         }
-                        // Remaining samples in buffer will be processed in next iteration
                     }
+                    drift_samples = resampler.drift_samples();
                 }
             }
 
@@ -484,11 +553,10 @@ impl AudioCapture {
                 let ratio = TARGET_SAMPLE_RATE as f64 / self.sample_rate as f64;
                 let rms_preservation = if before_rms > 0.0 { (after_rms / before_rms) * 100.0 } else { 100.0 };
 
-                let buffer_size = if let Ok(buf) = self.resampler_input_buffer.lock() {
-                    buf.len()
-                } else {
-                    0
-                };
+                let buffer_size = self.resampler.lock()
+                    .ok()
+                    .and_then(|r| r.as_ref().map(|r| r.buffered_samples()))
+                    .unwrap_or(0);
 
                 info!(
                     "🔄 [{:?}] Persistent buffered resampler: {}Hz → {}Hz (ratio: {:.2}x)",
@@ -498,12 +566,13 @@ impl AudioCapture {
                     ratio
                 );
                 info!(
-                    "   Chunk {}: {} → {} samples, RMS preservation: {:.1}%, buffer: {}",
+                    "   Chunk {}: {} → {} samples, RMS preservation: {:.1}%, buffer: {}, drift: {:.1} samples",
                     chunk_id,
                     before_len,
                     after_len,
                     rms_preservation,
-                    buffer_size
+                    buffer_size,
+                    drift_samples
                 );
             }
         }
@@ -519,8 +588,19 @@ impl AudioCapture {
                 }
             }
 
-            // STEP 2: Apply RNNoise noise suppression (10-15 dB reduction) - CONDITIONAL
-            if super::ffmpeg_mixer::RNNOISE_APPLY_ENABLED {
+            // STEP 2: Apply RNNoise noise suppression (10-15 dB reduction) - toggled at runtime
+            if !is_noise_suppression_enabled() {
+                // Release the partial frame held from before it was switched off
+                if let Ok(mut ns_lock) = self.noise_suppressor.lock() {
+                    if let Some(ref mut suppressor) = *ns_lock {
+                        if suppressor.buffered_samples() > 0 {
+                            let mut flushed = suppressor.flush();
+                            flushed.extend_from_slice(&mono_data);
+                            mono_data = flushed;
+                        }
+                    }
+                }
+            } else {
                 if let Ok(mut ns_lock) = self.noise_suppressor.lock() {
                     if let Some(ref mut suppressor) = *ns_lock {
                         let before_len = mono_data.len();
@@ -555,8 +635,20 @@ impl AudioCapture {
                 }
             }
 
-            // STEP 3: Apply EBU R128 normalization (professional loudness standard)
-            if let Ok(mut normalizer_lock) = self.normalizer.lock() {
+            // STEP 3: Level the microphone - AGC when enabled, otherwise EBU R128 normalization
+            let agc_settings = get_agc_settings();
+            if agc_settings.enabled {
+                if let Ok(mut agc_lock) = self.agc.lock() {
+                    if let Some(ref mut agc) = *agc_lock {
+                        mono_data = agc.process(&mono_data, &agc_settings);
+
+                        let chunk_id = self.chunk_counter.load(std::sync::atomic::Ordering::SeqCst);
+                        if chunk_id % 200 == 0 {
+                            debug!("🎚️ AGC gain for chunk {}: {:.1} dB", chunk_id, agc.current_gain_db());
+                        }
+                    }
+                }
+            } else if let Ok(mut normalizer_lock) = self.normalizer.lock() {
                 if let Some(ref mut normalizer) = *normalizer_lock {
                     mono_data = normalizer.normalize_loudness(&mono_data);
 
@@ -603,7 +695,7 @@ impl AudioCapture {
         // }
 
         // Use global recording timestamp for proper synchronization
-        let timestamp = self.state.get_recording_duration().unwrap_or(0.0);
+        let timestamp = timestamp.unwrap_or_else(|| self.state.get_recording_duration().unwrap_or(0.0));
 
         // RAW AUDIO CHUNK: No gain applied - will be mixed and gained downstream
         // Use 48kHz if we resampled, otherwise use original rate
@@ -613,6 +705,7 @@ impl AudioCapture {
             timestamp,
             chunk_id,
             device_type: self.device_type.clone(),
+            stereo_side: side_data,
         };
 
         // NOTE: Raw audio is NOT sent to recording saver to prevent echo
@@ -622,6 +715,7 @@ impl AudioCapture {
 
         // Send to processing pipeline for transcription
         if let Err(e) = self.state.send_audio_chunk(audio_chunk) {
+            pipeline_stats::record_dropped(Stage::Resample, 1);
             // Check if this is the "pipeline not ready" error
             if e.to_string().contains("Audio pipeline not ready") {
                 // This is expected during initialization, just log it as debug
@@ -640,6 +734,8 @@ impl AudioCapture {
             };
             self.state.report_error(error);
         } else {
+            pipeline_stats::record_processed(Stage::Resample, 1);
+            pipeline_stats::record_enqueued(Stage::Resample);
             debug!("Sent audio chunk {} ({} samples)", chunk_id, data.len());
         }
     }
@@ -692,8 +788,16 @@ pub struct AudioPipeline {
     // PROFESSIONAL AUDIO MIXING: Ring buffer + RMS-based mixer
     ring_buffer: AudioMixerRingBuffer,
     mixer: ProfessionalAudioMixer,
+    // Keeps the system stream on the microphone's clock before the two are mixed
+    drift_compensator: DriftCompensator,
+    // Removes speaker echo of the system audio from the microphone before mixing
+    echo_canceller: EchoCanceller,
     // Recording sender for pre-mixed audio
     recording_sender_for_mixed: Option<mpsc::UnboundedSender<AudioChunk>>,
+    // Channel layout of the saved file (transcription always uses the mono mix)
+    channel_policy: ChannelMixPolicy,
+    // Paces partial transcriptions of the utterance in progress (live captions)
+    partial_scheduler: PartialScheduler,
 }
 
 impl AudioPipeline {
@@ -759,7 +863,11 @@ impl AudioPipeline {
             // Initialize professional audio mixing
             ring_buffer,
             mixer,
+            drift_compensator: DriftCompensator::new(sample_rate),
+            echo_canceller: EchoCanceller::new(),
             recording_sender_for_mixed: None,  // Will be set by manager
+            channel_policy: ChannelMixPolicy::default(),  // Will be set by manager
+            partial_scheduler: PartialScheduler::new(get_live_caption_settings()),
         }
     }
 
@@ -790,6 +898,7 @@ impl AudioPipeline {
                     // PERFORMANCE OPTIMIZATION: Eliminate per-chunk logging overhead
                     // Logging in hot paths causes severe performance degradation
                     self.processed_chunks += 1;
+                    pipeline_stats::record_dequeued(Stage::Resample);
 
                     // Smart batching: collect metrics instead of logging every chunk
                     if let Some(ref batcher) = self.metrics_batcher {
@@ -817,11 +926,21 @@ impl AudioPipeline {
                     // STEP 1: Add raw audio to ring buffer for mixing
                     // Microphone audio is already normalized at capture level (AudioCapture)
                     // System audio remains raw
-                    self.ring_buffer.add_samples(chunk.device_type.clone(), chunk.data);
+                    // Device clocks differ slightly; the system stream is corrected to the microphone's
+                    let (data, stereo_side) =
+                        self.drift_compensator.process(&chunk.device_type, chunk.data, chunk.stereo_side);
+                    self.ring_buffer.add_samples(chunk.device_type.clone(), data, stereo_side);
 
                     // STEP 2: Mix audio in fixed windows when both streams have sufficient data
                     while self.ring_buffer.can_mix() {
-                        if let Some((mic_window, sys_window)) = self.ring_buffer.extract_window() {
+                        if let Some((mic_window, sys_window, side_window)) = self.ring_buffer.extract_window() {
+                            // Strip remote voices picked up from the speakers so the mic carries only local speech
+                            let mic_window = if is_echo_cancellation_enabled() {
+                                self.echo_canceller.process(&mic_window, &sys_window)
+                            } else {
+                                mic_window
+                            };
+
                             // Simple mixing without aggressive ducking
                             let mixed_clean = self.mixer.mix_window(&mic_window, &sys_window);
 
@@ -847,12 +966,20 @@ impl AudioPipeline {
                                                 timestamp: segment.start_timestamp_ms / 1000.0,
                                                 chunk_id: self.chunk_id_counter,
                                                 device_type: DeviceType::Microphone,  // Mixed audio
+                                                stereo_side: None,
                                             };
 
+                                            self.state.mark_speech();
+                                            self.partial_scheduler.reset();
+                                            streaming::finish_utterance(self.chunk_id_counter);
+
                                             if let Err(e) = self.transcription_sender.send(transcription_chunk) {
                                                 warn!("Failed to send VAD segment: {}", e);
+                                                pipeline_stats::record_dropped(Stage::Vad, 1);
                                             } else {
                                                 self.chunk_id_counter += 1;
+                                                pipeline_stats::record_processed(Stage::Vad, 1);
+                                                pipeline_stats::record_enqueued(Stage::Transcription);
                                             }
                                         } else {
                                             debug!("⏭️ Dropping short VAD segment: {:.1}ms ({} samples < 800)",
@@ -862,17 +989,38 @@ impl AudioPipeline {
                                 }
                                 Err(e) => {
                                     warn!("⚠️ VAD error: {}", e);
+                                    pipeline_stats::record_dropped(Stage::Vad, 1);
+                                }
+                            }
+
+                            // Live captions: offer the utterance still in progress for a partial transcript
+                            if get_live_caption_settings().enabled {
+                                if let Some((speech, start_secs)) = self.vad_processor.speech_in_progress() {
+                                    if let Some(offset) = self.partial_scheduler.poll(speech) {
+                                        streaming::submit_partial(PartialWindow {
+                                            utterance_id: self.chunk_id_counter,
+                                            samples: speech[offset..].to_vec(),
+                                            audio_start_time: start_secs + offset as f64 / 16000.0,
+                                        });
+                                    }
                                 }
                             }
 
-                            // STEP 4: Send mixed audio for recording (WAV file)
+                            // STEP 4: Send mixed audio for recording in the chosen channel layout
                             if let Some(ref sender) = self.recording_sender_for_mixed {
                                 let recording_chunk = AudioChunk {
-                                    data: mixed_with_gain.clone(),
+                                    data: mix_for_recording(
+                                        self.channel_policy,
+                                        &mixed_with_gain,
+                                        &mic_window,
+                                        &sys_window,
+                                        &side_window,
+                                    ),
                                     sample_rate: self.sample_rate,
                                     timestamp: chunk.timestamp,
                                     chunk_id: self.chunk_id_counter,
                                     device_type: DeviceType::Microphone,  // Mixed audio
+                                    stereo_side: None,
                                 };
                                 let _ = sender.send(recording_chunk);
                             }
@@ -917,12 +1065,16 @@ impl AudioPipeline {
                             timestamp: segment.start_timestamp_ms / 1000.0,
                             chunk_id: self.chunk_id_counter,
                             device_type: DeviceType::Microphone,
+                            stereo_side: None,
                         };
 
                         if let Err(e) = self.transcription_sender.send(transcription_chunk) {
                             warn!("Failed to send final VAD segment: {}", e);
+                            pipeline_stats::record_dropped(Stage::Vad, 1);
                         } else {
                             self.chunk_id_counter += 1;
+                            pipeline_stats::record_processed(Stage::Vad, 1);
+                            pipeline_stats::record_enqueued(Stage::Transcription);
                         }
                     } else {
                         info!("⏭️ Skipping short final segment: {:.1}ms ({} samples < 800)",
@@ -935,6 +1087,12 @@ impl AudioPipeline {
             }
         }
 
+        let (analysed_secs, speech_secs) = self.vad_processor.activity_seconds();
+        if analysed_secs > 0.0 {
+            info!("🗣️ VAD gate: {:.1}s of {:.1}s was speech, {:.1}s of silence never reached transcription",
+                  speech_secs, analysed_secs, (analysed_secs - speech_secs).max(0.0));
+        }
+
         Ok(())
     }
 
@@ -962,6 +1120,7 @@ impl AudioPipelineManager {
         target_chunk_duration_ms: u32,
         sample_rate: u32,
         recording_sender: Option<mpsc::UnboundedSender<AudioChunk>>,
+        channel_policy: ChannelMixPolicy,
         mic_device_name: String,
         mic_device_kind: super::device_detection::InputDeviceKind,
         system_device_name: String,
@@ -972,6 +1131,9 @@ impl AudioPipelineManager {
         info!("   Microphone: '{}' ({:?})", mic_device_name, mic_device_kind);
         info!("   System Audio: '{}' ({:?})", system_device_name, system_device_kind);
 
+        // Stats count from the start of each recording
+        pipeline_stats::reset();
+
         // Create audio processing channel
         let (audio_sender, audio_receiver) = mpsc::unbounded_channel::<AudioChunk>();
 
@@ -994,6 +1156,7 @@ impl AudioPipelineManager {
         // CRITICAL FIX: Connect recording sender to receive pre-mixed audio
         // This ensures both mic AND system audio are captured in recordings
         pipeline.recording_sender_for_mixed = recording_sender;
+        pipeline.channel_policy = channel_policy;
 
         let handle = tokio::spawn(async move {
             pipeline.run().await
@@ -1039,6 +1202,7 @@ impl AudioPipelineManager {
                 timestamp: 0.0,
                 chunk_id: u64::MAX, // Special ID to indicate flush
                 device_type: super::recording_state::DeviceType::Microphone,
+                stereo_side: None,
             };
 
             if let Err(e) = sender.send(flush_chunk) {
@@ -1059,6 +1223,7 @@ impl AudioPipelineManager {
                         timestamp: 0.0,
                         chunk_id: u64::MAX - (i as u64),
                         device_type: super::recording_state::DeviceType::Microphone,
+                        stereo_side: None,
                     };
                     let _ = sender.send(additional_flush);
                 }
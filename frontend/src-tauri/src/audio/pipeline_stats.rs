@@ -0,0 +1,273 @@
+// Dropped audio and queue depths at each stage of the recording pipeline
+//
+// Audio goes from the capture ring (see capture_ring.rs) through per-stream processing
+// (mono, resampling, enhancement) into the pipeline's channel, then the mixer's buffers, the
+// VAD, and the transcription queue. Each stage counts what it passed on, what it dropped and
+// how much is waiting in front of the next stage, so a report of choppy audio or transcripts
+// running minutes behind shows where the time or the audio went. The counts start over with
+// each recording; the UI gets them every few seconds while recording and on request, and they
+// go into the diagnostics bundle.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use log::{error, info, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use super::capture_ring::{self, CaptureRingStats};
+use super::session;
+
+/// Emitted with `PipelineStats` while recording
+pub const PIPELINE_STATS_EVENT: &str = "pipeline-stats";
+
+const EVENT_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    /// Mono conversion, resampling and enhancement of each stream; its queue is the
+    /// pipeline's input channel
+    Resample,
+    /// Buffers lining up the two streams; its queue is the fuller buffer
+    Mixer,
+    /// Speech detection; windows it failed on count as dropped
+    Vad,
+    /// Speech segments waiting for or in transcription
+    Transcription,
+}
+
+impl Stage {
+    const ALL: [Stage; 4] = [Stage::Resample, Stage::Mixer, Stage::Vad, Stage::Transcription];
+
+    /// What this stage's counts count
+    fn unit(self) -> &'static str {
+        match self {
+            Stage::Resample => "chunks",
+            Stage::Mixer => "samples",
+            Stage::Vad | Stage::Transcription => "segments",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StageStats {
+    pub stage: Stage,
+    pub unit: &'static str,
+    pub processed: u64,
+    pub dropped: u64,
+    pub queue_depth: u64,
+    pub max_queue_depth: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PipelineStats {
+    /// Ring between each stream's capture callback and its processing, in frames
+    pub capture: Vec<CaptureRingStats>,
+    pub stages: Vec<StageStats>,
+}
+
+#[derive(Debug)]
+struct StageCounters {
+    processed: AtomicU64,
+    dropped: AtomicU64,
+    depth: AtomicU64,
+    max_depth: AtomicU64,
+}
+
+impl StageCounters {
+    const fn new() -> Self {
+        Self {
+            processed: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            depth: AtomicU64::new(0),
+            max_depth: AtomicU64::new(0),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PipelineCounters {
+    stages: [StageCounters; 4],
+}
+
+impl PipelineCounters {
+    const fn new() -> Self {
+        Self { stages: [StageCounters::new(), StageCounters::new(), StageCounters::new(), StageCounters::new()] }
+    }
+
+    fn stage(&self, stage: Stage) -> &StageCounters {
+        &self.stages[stage as usize]
+    }
+
+    fn processed(&self, stage: Stage, count: u64) {
+        self.stage(stage).processed.fetch_add(count, Ordering::Relaxed);
+    }
+
+    fn dropped(&self, stage: Stage, count: u64) {
+        self.stage(stage).dropped.fetch_add(count, Ordering::Relaxed);
+    }
+
+    fn set_depth(&self, stage: Stage, depth: u64) {
+        let counters = self.stage(stage);
+        counters.depth.store(depth, Ordering::Relaxed);
+        counters.max_depth.fetch_max(depth, Ordering::Relaxed);
+    }
+
+    fn enqueued(&self, stage: Stage) {
+        let counters = self.stage(stage);
+        let depth = counters.depth.fetch_add(1, Ordering::Relaxed) + 1;
+        counters.max_depth.fetch_max(depth, Ordering::Relaxed);
+    }
+
+    fn dequeued(&self, stage: Stage) {
+        // Saturating: a queue left over from the last recording may drain after a reset
+        let _ = self.stage(stage).depth.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |d| d.checked_sub(1));
+    }
+
+    fn reset(&self) {
+        for counters in &self.stages {
+            for counter in [&counters.processed, &counters.dropped, &counters.depth, &counters.max_depth] {
+                counter.store(0, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn stats(&self) -> Vec<StageStats> {
+        Stage::ALL
+            .iter()
+            .map(|&stage| {
+                let counters = self.stage(stage);
+                StageStats {
+                    stage,
+                    unit: stage.unit(),
+                    processed: counters.processed.load(Ordering::Relaxed),
+                    dropped: counters.dropped.load(Ordering::Relaxed),
+                    queue_depth: counters.depth.load(Ordering::Relaxed),
+                    max_queue_depth: counters.max_depth.load(Ordering::Relaxed),
+                }
+            })
+            .collect()
+    }
+}
+
+static COUNTERS: PipelineCounters = PipelineCounters::new();
+
+/// `count` units passed on to the next stage
+pub fn record_processed(stage: Stage, count: u64) {
+    COUNTERS.processed(stage, count);
+}
+
+/// `count` units lost at this stage
+pub fn record_dropped(stage: Stage, count: u64) {
+    COUNTERS.dropped(stage, count);
+}
+
+/// Current queue depth of a stage that measures it directly
+pub fn set_queue_depth(stage: Stage, depth: u64) {
+    COUNTERS.set_depth(stage, depth);
+}
+
+/// One unit joined the stage's queue
+pub fn record_enqueued(stage: Stage) {
+    COUNTERS.enqueued(stage);
+}
+
+/// One unit left the stage's queue
+pub fn record_dequeued(stage: Stage) {
+    COUNTERS.dequeued(stage);
+}
+
+/// Starts the counts over for a new recording
+pub fn reset() {
+    COUNTERS.reset();
+}
+
+pub fn current() -> PipelineStats {
+    PipelineStats { capture: capture_ring::recent_stats(), stages: COUNTERS.stats() }
+}
+
+fn log_summary(stats: &PipelineStats) {
+    for ring in &stats.capture {
+        info!(
+            "📊 Capture '{}': {} frames, {} dropped in {} overruns, peak fill {}%",
+            ring.device_name, ring.captured_frames, ring.dropped_frames, ring.overruns, ring.peak_fill_percent
+        );
+    }
+    for stage in &stats.stages {
+        let line = format!(
+            "📊 {:?}: {} {} processed, {} dropped, max queue {}",
+            stage.stage, stage.processed, stage.unit, stage.dropped, stage.max_queue_depth
+        );
+        if stage.dropped > 0 {
+            warn!("{}", line);
+        } else {
+            info!("{}", line);
+        }
+    }
+}
+
+/// Emits the stats every few seconds until the recording ends, then logs them
+pub fn spawn_pipeline_stats_events<R: Runtime>(app: AppHandle<R>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(EVENT_INTERVAL);
+
+        while session::phase().is_recording() {
+            interval.tick().await;
+            if let Err(e) = app.emit(PIPELINE_STATS_EVENT, current()) {
+                error!("Failed to emit {}: {}", PIPELINE_STATS_EVENT, e);
+                break;
+            }
+        }
+
+        log_summary(&current());
+    });
+}
+
+/// Drops and queue depths of the current recording, or the last one
+#[tauri::command]
+pub async fn pipeline_stats() -> PipelineStats {
+    current()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_depth() {
+        let counters = PipelineCounters::new();
+        counters.enqueued(Stage::Transcription);
+        counters.enqueued(Stage::Transcription);
+        counters.dequeued(Stage::Transcription);
+        counters.enqueued(Stage::Transcription);
+
+        let stats = &counters.stats()[3];
+        assert_eq!((stats.queue_depth, stats.max_queue_depth), (2, 2));
+
+        counters.reset();
+        counters.dequeued(Stage::Transcription);
+        let stats = &counters.stats()[3];
+        assert_eq!((stats.queue_depth, stats.max_queue_depth), (0, 0));
+    }
+
+    #[test]
+    fn test_counts_stay_with_their_stage() {
+        let counters = PipelineCounters::new();
+        counters.processed(Stage::Mixer, 4800);
+        counters.dropped(Stage::Mixer, 120);
+        counters.set_depth(Stage::Mixer, 9000);
+        counters.set_depth(Stage::Mixer, 3000);
+        counters.dropped(Stage::Vad, 1);
+
+        let stats = counters.stats();
+        let mixer = stats.iter().find(|stats| stats.stage == Stage::Mixer).unwrap();
+        assert_eq!(
+            (mixer.unit, mixer.processed, mixer.dropped, mixer.queue_depth, mixer.max_queue_depth),
+            ("samples", 4800, 120, 3000, 9000)
+        );
+        let vad = stats.iter().find(|stats| stats.stage == Stage::Vad).unwrap();
+        assert_eq!((vad.processed, vad.dropped), (0, 1));
+        assert_eq!(stats.iter().map(|stats| stats.dropped).sum::<u64>(), 121);
+    }
+}
@@ -0,0 +1,444 @@
+// Transcript-synced playback of meeting recordings
+//
+// The webview never receives the audio: the meeting's recording is decoded with FFmpeg
+// and played through the default output device from here. The frontend asks for a range
+// with `play_segment` (e.g. when a transcript line or word is clicked), can seek, pause
+// and change the speed, and follows along through `playback-position` events.
+//
+// Speed is changed by resampling, so the pitch follows the speed.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample, StreamConfig};
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use super::ffmpeg::decode_to_mono;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::state::AppState;
+
+/// Emitted while playing (and once after pausing) with the current `PlaybackStatus`
+pub const PLAYBACK_POSITION_EVENT: &str = "playback-position";
+/// Emitted when playback reaches the end of the requested range or of the recording
+pub const PLAYBACK_ENDED_EVENT: &str = "playback-ended";
+
+/// Recordings are decoded at this rate; the output callback converts to the device rate
+const PLAYBACK_SAMPLE_RATE: u32 = 48000;
+const POSITION_INTERVAL: Duration = Duration::from_millis(250);
+
+pub const MIN_PLAYBACK_SPEED: f32 = 0.75;
+pub const MAX_PLAYBACK_SPEED: f32 = 2.0;
+
+/// Kept across meetings, like a media player's speed setting
+static PLAYBACK_SPEED: AtomicU32 = AtomicU32::new(0x3f80_0000); // 1.0f32
+
+static PLAYER: Lazy<Mutex<Option<Player>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaybackStatus {
+    pub meeting_id: Option<String>,
+    pub position_ms: u64,
+    pub duration_ms: u64,
+    /// End of the range requested with `play_segment`, if any
+    pub end_ms: Option<u64>,
+    pub playing: bool,
+    pub speed: f32,
+}
+
+fn playback_speed() -> f32 {
+    f32::from_bits(PLAYBACK_SPEED.load(Ordering::Relaxed))
+}
+
+/// Decoded recording plus the play head, shared with the output callback
+struct Transport {
+    samples: Vec<f32>,
+    /// Fractional sample index (f64 bits)
+    position: AtomicU64,
+    /// Sample index playback stops at
+    end: AtomicU64,
+    playing: AtomicBool,
+    shutdown: AtomicBool,
+}
+
+impl Transport {
+    fn new(samples: Vec<f32>) -> Self {
+        let end = samples.len() as u64;
+        Self {
+            samples,
+            position: AtomicU64::new(0f64.to_bits()),
+            end: AtomicU64::new(end),
+            playing: AtomicBool::new(false),
+            shutdown: AtomicBool::new(false),
+        }
+    }
+
+    fn ms_to_sample(&self, ms: u64) -> u64 {
+        (ms * PLAYBACK_SAMPLE_RATE as u64 / 1000).min(self.samples.len() as u64)
+    }
+
+    fn sample_to_ms(sample: f64) -> u64 {
+        (sample * 1000.0 / PLAYBACK_SAMPLE_RATE as f64) as u64
+    }
+
+    fn position(&self) -> f64 {
+        f64::from_bits(self.position.load(Ordering::SeqCst))
+    }
+
+    fn seek(&self, ms: u64) {
+        let sample = self.ms_to_sample(ms);
+        self.position.store((sample as f64).to_bits(), Ordering::SeqCst);
+        // Seeking past the requested range plays on to the end of the recording
+        if sample >= self.end.load(Ordering::SeqCst) {
+            self.clear_range();
+        }
+    }
+
+    fn set_range_end(&self, end_ms: Option<u64>) {
+        match end_ms {
+            Some(ms) => self.end.store(self.ms_to_sample(ms), Ordering::SeqCst),
+            None => self.clear_range(),
+        }
+    }
+
+    fn clear_range(&self) {
+        self.end.store(self.samples.len() as u64, Ordering::SeqCst);
+    }
+
+    fn at_end(&self) -> bool {
+        self.position() >= self.end.load(Ordering::SeqCst) as f64
+    }
+
+    /// Fill `out` with mono samples, advancing `speed * step_scale` source samples per frame
+    fn fill(&self, out: &mut [f32], step_scale: f64) {
+        if !self.playing.load(Ordering::SeqCst) {
+            out.fill(0.0);
+            return;
+        }
+
+        let start_bits = self.position.load(Ordering::SeqCst);
+        let end = self.end.load(Ordering::SeqCst) as f64;
+        let step = playback_speed() as f64 * step_scale;
+        let mut position = f64::from_bits(start_bits);
+
+        for sample in out.iter_mut() {
+            if position >= end {
+                *sample = 0.0;
+                continue;
+            }
+            let index = position as usize;
+            let frac = (position - index as f64) as f32;
+            let current = self.samples[index];
+            let next = self.samples.get(index + 1).copied().unwrap_or(current);
+            *sample = current + (next - current) * frac;
+            position += step;
+        }
+
+        let position = position.min(end);
+        if position >= end {
+            self.playing.store(false, Ordering::SeqCst);
+        }
+        // A seek while this buffer was rendered wins over the advanced position
+        let _ = self.position.compare_exchange(start_bits, position.to_bits(), Ordering::SeqCst, Ordering::SeqCst);
+    }
+}
+
+struct Player {
+    meeting_id: String,
+    transport: Arc<Transport>,
+}
+
+impl Player {
+    fn status(&self) -> PlaybackStatus {
+        let transport = &self.transport;
+        let end = transport.end.load(Ordering::SeqCst);
+        PlaybackStatus {
+            meeting_id: Some(self.meeting_id.clone()),
+            position_ms: Transport::sample_to_ms(transport.position()),
+            duration_ms: Transport::sample_to_ms(transport.samples.len() as f64),
+            end_ms: (end < transport.samples.len() as u64).then(|| Transport::sample_to_ms(end as f64)),
+            playing: transport.playing.load(Ordering::SeqCst),
+            speed: playback_speed(),
+        }
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        // Stops the output thread, which owns the (non-Send) stream
+        self.transport.shutdown.store(true, Ordering::SeqCst);
+    }
+}
+
+fn idle_status() -> PlaybackStatus {
+    PlaybackStatus {
+        meeting_id: None,
+        position_ms: 0,
+        duration_ms: 0,
+        end_ms: None,
+        playing: false,
+        speed: playback_speed(),
+    }
+}
+
+/// The recording in a meeting folder: `audio.<ext>` for recorded and imported meetings
+pub(crate) fn find_recording(folder: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(folder)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .find(|path| path.is_file() && path.file_stem().is_some_and(|stem| stem == "audio"))
+}
+
+fn build_stream(transport: Arc<Transport>) -> Result<cpal::Stream> {
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| anyhow!("No audio output device available"))?;
+    let supported = device.default_output_config()?;
+    let step_scale = PLAYBACK_SAMPLE_RATE as f64 / supported.sample_rate().0 as f64;
+    let sample_format = supported.sample_format();
+    let config: StreamConfig = supported.into();
+
+    let stream = match sample_format {
+        SampleFormat::F32 => build_typed_stream::<f32>(&device, &config, transport, step_scale),
+        SampleFormat::I16 => build_typed_stream::<i16>(&device, &config, transport, step_scale),
+        SampleFormat::U16 => build_typed_stream::<u16>(&device, &config, transport, step_scale),
+        other => Err(anyhow!("Unsupported output sample format: {:?}", other)),
+    }?;
+    stream.play()?;
+    info!("🔊 Playback on '{}' ({} Hz, {} ch)", device.name().unwrap_or_default(), config.sample_rate.0, config.channels);
+    Ok(stream)
+}
+
+fn build_typed_stream<T: SizedSample + FromSample<f32>>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    transport: Arc<Transport>,
+    step_scale: f64,
+) -> Result<cpal::Stream> {
+    let channels = config.channels as usize;
+    let mut mono = Vec::new();
+    let stream = device.build_output_stream(
+        config,
+        move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+            mono.resize(data.len() / channels, 0.0);
+            transport.fill(&mut mono, step_scale);
+            for (frame, sample) in data.chunks_mut(channels).zip(&mono) {
+                frame.fill(T::from_sample(*sample));
+            }
+        },
+        |e| error!("❌ Playback stream error: {}", e),
+        None,
+    )?;
+    Ok(stream)
+}
+
+/// Open the output device on its own thread and report the position until shut down
+fn spawn_output_thread<R: Runtime>(
+    app: AppHandle<R>,
+    player_status: impl Fn() -> Option<PlaybackStatus> + Send + 'static,
+    transport: Arc<Transport>,
+) -> Result<()> {
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    std::thread::Builder::new()
+        .name("meeting-playback".to_string())
+        .spawn(move || {
+            let stream = match build_stream(transport.clone()) {
+                Ok(stream) => {
+                    let _ = ready_tx.send(Ok(()));
+                    stream
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+
+            let mut was_playing = false;
+            while !transport.shutdown.load(Ordering::SeqCst) {
+                let playing = transport.playing.load(Ordering::SeqCst);
+                if playing || was_playing {
+                    if let Some(status) = player_status() {
+                        if let Err(e) = app.emit(PLAYBACK_POSITION_EVENT, &status) {
+                            warn!("Failed to emit playback position: {}", e);
+                        }
+                        if was_playing && !playing && transport.at_end() {
+                            let _ = app.emit(PLAYBACK_ENDED_EVENT, &status);
+                        }
+                    }
+                }
+                was_playing = playing;
+                std::thread::sleep(POSITION_INTERVAL);
+            }
+            drop(stream);
+        })?;
+
+    ready_rx.recv().map_err(|_| anyhow!("Playback thread exited unexpectedly"))?
+}
+
+fn current_transport(meeting_id: &str) -> Option<Arc<Transport>> {
+    PLAYER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .filter(|player| player.meeting_id == meeting_id)
+        .map(|player| player.transport.clone())
+}
+
+/// Decode the meeting's recording and open the output; replaces any previous player
+async fn load_meeting<R: Runtime>(app: &AppHandle<R>, state: &AppState, meeting_id: &str) -> Result<Arc<Transport>> {
+    let folder = MeetingsRepository::get_folder_path(state.db_manager.pool(), meeting_id)
+        .await?
+        .ok_or_else(|| anyhow!("Meeting {} has no recording folder", meeting_id))?;
+    let recording = find_recording(Path::new(&folder))
+        .ok_or_else(|| anyhow!("No recording found in {}", folder))?;
+
+    info!("🔊 Loading {} for playback", recording.display());
+    let samples = decode_to_mono(&recording, PLAYBACK_SAMPLE_RATE).await?;
+    let transport = Arc::new(Transport::new(samples));
+
+    // Dropping the previous player shuts its output thread down
+    *PLAYER.lock().unwrap() = None;
+
+    let status_meeting = meeting_id.to_string();
+    let player_status = move || {
+        PLAYER
+            .lock()
+            .ok()?
+            .as_ref()
+            .filter(|player| player.meeting_id == status_meeting)
+            .map(Player::status)
+    };
+    spawn_output_thread(app.clone(), player_status, transport.clone())?;
+
+    *PLAYER.lock().unwrap() = Some(Player { meeting_id: meeting_id.to_string(), transport: transport.clone() });
+    Ok(transport)
+}
+
+fn with_player<F: FnOnce(&Player)>(action: F) -> Result<PlaybackStatus, String> {
+    let guard = PLAYER.lock().unwrap();
+    let player = guard.as_ref().ok_or_else(|| "Nothing is loaded for playback".to_string())?;
+    action(player);
+    Ok(player.status())
+}
+
+/// Play a meeting's recording from `start_ms`, stopping at `end_ms` if given
+#[tauri::command]
+pub async fn play_segment<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    start_ms: u64,
+    end_ms: Option<u64>,
+) -> Result<PlaybackStatus, String> {
+    if end_ms.is_some_and(|end| end <= start_ms) {
+        return Err("Playback range must end after it starts".to_string());
+    }
+
+    let transport = match current_transport(&meeting_id) {
+        Some(transport) => transport,
+        None => load_meeting(&app, &state, &meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load meeting audio: {}", e))?,
+    };
+
+    transport.set_range_end(end_ms);
+    transport.seek(start_ms);
+    transport.playing.store(true, Ordering::SeqCst);
+    with_player(|_| {})
+}
+
+#[tauri::command]
+pub async fn seek_playback(position_ms: u64) -> Result<PlaybackStatus, String> {
+    with_player(|player| player.transport.seek(position_ms))
+}
+
+#[tauri::command]
+pub async fn pause_playback() -> Result<PlaybackStatus, String> {
+    with_player(|player| player.transport.playing.store(false, Ordering::SeqCst))
+}
+
+/// Resume; at the end of a requested range, playback continues past it
+#[tauri::command]
+pub async fn resume_playback() -> Result<PlaybackStatus, String> {
+    with_player(|player| {
+        let transport = &player.transport;
+        if transport.at_end() {
+            transport.clear_range();
+        }
+        transport.playing.store(true, Ordering::SeqCst);
+    })
+}
+
+/// Stop playback and release the output device and decoded audio
+#[tauri::command]
+pub async fn stop_playback() -> Result<PlaybackStatus, String> {
+    *PLAYER.lock().unwrap() = None;
+    Ok(idle_status())
+}
+
+#[tauri::command]
+pub async fn set_playback_speed(speed: f32) -> Result<PlaybackStatus, String> {
+    if !(MIN_PLAYBACK_SPEED..=MAX_PLAYBACK_SPEED).contains(&speed) {
+        return Err(format!(
+            "Playback speed must be between {}x and {}x",
+            MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED
+        ));
+    }
+    PLAYBACK_SPEED.store(speed.to_bits(), Ordering::Relaxed);
+    info!("🔊 Playback speed set to {}x", speed);
+    Ok(PLAYER.lock().unwrap().as_ref().map(Player::status).unwrap_or_else(idle_status))
+}
+
+#[tauri::command]
+pub async fn get_playback_status() -> Result<PlaybackStatus, String> {
+    Ok(PLAYER.lock().unwrap().as_ref().map(Player::status).unwrap_or_else(idle_status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_fill_interpolates_and_stops_at_range_end() {
+        let transport = Transport::new((0..PLAYBACK_SAMPLE_RATE).map(|i| i as f32).collect());
+        transport.seek(0);
+        transport.end.store(4, Ordering::SeqCst);
+        transport.playing.store(true, Ordering::SeqCst);
+
+        // Device at twice the decode rate: half a source sample per frame
+        let mut out = [0.0f32; 10];
+        transport.fill(&mut out, 0.5);
+        assert_eq!(&out[..4], &[0.0, 0.5, 1.0, 1.5]);
+        assert_eq!(out[8], 0.0);
+        assert!(!transport.playing.load(Ordering::SeqCst));
+        assert!(transport.at_end());
+    }
+
+    #[test]
+    fn test_seek_past_range_clears_it() {
+        let transport = Transport::new(vec![0.0; PLAYBACK_SAMPLE_RATE as usize * 10]);
+        transport.set_range_end(Some(2000));
+        transport.seek(1000);
+        assert_eq!(transport.end.load(Ordering::SeqCst), PLAYBACK_SAMPLE_RATE as u64 * 2);
+
+        transport.seek(5000);
+        assert_eq!(transport.end.load(Ordering::SeqCst), transport.samples.len() as u64);
+        assert_eq!(Transport::sample_to_ms(transport.position()), 5000);
+    }
+
+    #[test]
+    fn test_find_recording_ignores_other_files() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("transcripts.json"), "{}").unwrap();
+        assert_eq!(find_recording(dir.path()), None);
+
+        std::fs::write(dir.path().join("audio.opus"), b"").unwrap();
+        assert_eq!(find_recording(dir.path()), Some(dir.path().join("audio.opus")));
+    }
+}
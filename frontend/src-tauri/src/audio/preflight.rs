@@ -0,0 +1,231 @@
+// Readiness report shown before the first recording
+//
+// One command answers "can I record now?" for the UI: the microphone and system audio
+// permissions, a microphone to record from, an output device for system audio to be captured
+// from, space for the recording, and a transcription model ready to use. Each item says what
+// is wrong and how to fix it; a failed item stops a recording from starting, a warning only
+// degrades it.
+
+use log::info;
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+
+use super::devices::{default_input_device, default_output_device};
+use super::disk_space::{get_disk_guard_settings, recordings_disk_space, DiskGuardSettings};
+use super::permissions::{
+    get_microphone_permission_status, system_audio_permission_status, MicrophonePermissionStatus,
+    SystemAudioPermissionStatus,
+};
+use super::transcription::engine::validate_transcription_model_ready;
+
+const MB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreflightStatus {
+    Ok,
+    /// Recording works, with something missing
+    Warn,
+    /// Recording can't start
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PreflightItem {
+    pub status: PreflightStatus,
+    pub detail: String,
+    /// What the user can do about it
+    pub fix: Option<String>,
+}
+
+impl PreflightItem {
+    fn ok(detail: impl Into<String>) -> Self {
+        Self { status: PreflightStatus::Ok, detail: detail.into(), fix: None }
+    }
+
+    fn problem(status: PreflightStatus, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { status, detail: detail.into(), fix: Some(fix.into()) }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PreflightReport {
+    pub microphone_permission: PreflightItem,
+    pub system_audio_permission: PreflightItem,
+    pub input_device: PreflightItem,
+    pub output_tap: PreflightItem,
+    pub disk_space: PreflightItem,
+    pub transcription_model: PreflightItem,
+    /// No item failed
+    pub ready: bool,
+}
+
+impl PreflightReport {
+    fn items(&self) -> [&PreflightItem; 6] {
+        [
+            &self.microphone_permission,
+            &self.system_audio_permission,
+            &self.input_device,
+            &self.output_tap,
+            &self.disk_space,
+            &self.transcription_model,
+        ]
+    }
+}
+
+/// Where the microphone permission is changed on this OS
+#[cfg(target_os = "macos")]
+fn microphone_settings() -> String {
+    "Allow Meetily in System Settings → Privacy & Security → Microphone".to_string()
+}
+
+#[cfg(target_os = "windows")]
+fn microphone_settings() -> String {
+    "Turn on microphone access for desktop apps in Settings → Privacy & security → Microphone".to_string()
+}
+
+#[cfg(target_os = "linux")]
+fn microphone_settings() -> String {
+    super::permissions::linux_sandbox()
+        .remedy()
+        .unwrap_or_else(|| "Check that the microphone isn't muted in the system sound settings".to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn microphone_settings() -> String {
+    "Check that the microphone isn't muted in the system sound settings".to_string()
+}
+
+fn microphone_permission_item(status: MicrophonePermissionStatus, fix: String) -> PreflightItem {
+    match status {
+        MicrophonePermissionStatus::Authorized => PreflightItem::ok("Microphone access allowed"),
+        MicrophonePermissionStatus::NotDetermined => PreflightItem::problem(
+            PreflightStatus::Warn,
+            "Microphone access not asked for yet",
+            "Allow access when asked as the recording starts",
+        ),
+        MicrophonePermissionStatus::Denied => {
+            PreflightItem::problem(PreflightStatus::Fail, "Microphone access denied", fix)
+        }
+        MicrophonePermissionStatus::Restricted => PreflightItem::problem(
+            PreflightStatus::Fail,
+            "Microphone access is blocked by a system policy",
+            "Ask your administrator to allow microphone access",
+        ),
+    }
+}
+
+fn system_audio_permission_item(status: SystemAudioPermissionStatus) -> PreflightItem {
+    match status {
+        SystemAudioPermissionStatus::Authorized => PreflightItem::ok("System audio capture allowed"),
+        SystemAudioPermissionStatus::NotRequired => PreflightItem::ok("System audio capture needs no permission here"),
+        SystemAudioPermissionStatus::Unknown => {
+            PreflightItem::ok("System audio capture not allowed yet; macOS asks when recording starts")
+        }
+        SystemAudioPermissionStatus::Denied => PreflightItem::problem(
+            PreflightStatus::Warn,
+            "System audio capture denied; only your microphone will be recorded",
+            "Allow Meetily in System Settings → Privacy & Security → Screen & System Audio Recording",
+        ),
+    }
+}
+
+fn disk_space_item(available: Option<u64>, settings: &DiskGuardSettings) -> PreflightItem {
+    let Some(available) = available else {
+        return PreflightItem::ok("Free space on the recordings disk unknown");
+    };
+    let detail = format!("{} MB free for recordings", available / MB);
+    if available < settings.stop_below_mb * MB {
+        PreflightItem::problem(PreflightStatus::Fail, detail, "Free up disk space or choose another recordings folder")
+    } else if available < settings.warn_below_mb * MB {
+        PreflightItem::problem(
+            PreflightStatus::Warn,
+            detail,
+            "An hour of audio takes about 100 MB; free up space for long meetings",
+        )
+    } else {
+        PreflightItem::ok(detail)
+    }
+}
+
+/// Checks everything a recording needs, without starting one
+///
+/// Readying the transcription model may load it, which takes a few seconds the first time.
+#[tauri::command]
+pub async fn preflight_check<R: Runtime>(app: AppHandle<R>) -> PreflightReport {
+    let microphone_permission = microphone_permission_item(get_microphone_permission_status(), microphone_settings());
+    let system_audio_permission = system_audio_permission_item(system_audio_permission_status());
+
+    let input_device = match default_input_device() {
+        Ok(device) => PreflightItem::ok(device.name),
+        Err(e) => PreflightItem::problem(
+            PreflightStatus::Fail,
+            format!("No microphone found: {}", e),
+            "Connect a microphone, or choose one in the system sound settings",
+        ),
+    };
+    let output_tap = match default_output_device() {
+        Ok(device) => PreflightItem::ok(device.name),
+        Err(e) => PreflightItem::problem(
+            PreflightStatus::Warn,
+            format!("No output device to capture system audio from: {}", e),
+            "Connect speakers or headphones to record the other participants",
+        ),
+    };
+
+    let (_, available) = recordings_disk_space(&app).await;
+    let disk_space = disk_space_item(available, &get_disk_guard_settings());
+
+    let transcription_model = match validate_transcription_model_ready(&app).await {
+        Ok(()) => PreflightItem::ok("Transcription model ready"),
+        Err(e) => PreflightItem::problem(PreflightStatus::Fail, e, "Download a transcription model in Settings"),
+    };
+
+    let mut report = PreflightReport {
+        microphone_permission,
+        system_audio_permission,
+        input_device,
+        output_tap,
+        disk_space,
+        transcription_model,
+        ready: false,
+    };
+    report.ready = report.items().iter().all(|item| item.status != PreflightStatus::Fail);
+    info!("🛫 Preflight: {}", if report.ready { "ready to record" } else { "not ready to record" });
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_microphone_permission_item() {
+        let status = |permission| microphone_permission_item(permission, "Open the settings".to_string()).status;
+        assert_eq!(status(MicrophonePermissionStatus::Authorized), PreflightStatus::Ok);
+        assert_eq!(status(MicrophonePermissionStatus::NotDetermined), PreflightStatus::Warn);
+        assert_eq!(status(MicrophonePermissionStatus::Restricted), PreflightStatus::Fail);
+        let denied = microphone_permission_item(MicrophonePermissionStatus::Denied, "Open the settings".to_string());
+        assert_eq!(denied.status, PreflightStatus::Fail);
+        assert_eq!(denied.fix.as_deref(), Some("Open the settings"));
+    }
+
+    #[test]
+    fn test_system_audio_permission_item() {
+        let status = |permission| system_audio_permission_item(permission).status;
+        assert_eq!(status(SystemAudioPermissionStatus::Authorized), PreflightStatus::Ok);
+        assert_eq!(status(SystemAudioPermissionStatus::NotRequired), PreflightStatus::Ok);
+        assert_eq!(status(SystemAudioPermissionStatus::Unknown), PreflightStatus::Ok);
+        assert_eq!(status(SystemAudioPermissionStatus::Denied), PreflightStatus::Warn);
+    }
+
+    #[test]
+    fn test_disk_space_item() {
+        let settings = DiskGuardSettings { warn_below_mb: 1024, stop_below_mb: 200, auto_stop: true };
+        assert_eq!(disk_space_item(Some(100 * MB), &settings).status, PreflightStatus::Fail);
+        assert_eq!(disk_space_item(Some(500 * MB), &settings).status, PreflightStatus::Warn);
+        let plenty = disk_space_item(Some(5000 * MB), &settings);
+        assert_eq!((plenty.status, plenty.detail.as_str()), (PreflightStatus::Ok, "5000 MB free for recordings"));
+        assert_eq!(disk_space_item(None, &settings).status, PreflightStatus::Ok);
+    }
+}
@@ -0,0 +1,298 @@
+// Pre-roll capture: keep the last N seconds of audio before recording starts
+//
+// When enabled, the microphone and system audio are captured into bounded in-memory ring
+// buffers while idle. Pressing Record stops the pre-roll capture (freeing the devices for
+// the real streams) and, if the user opted in, feeds the buffered audio through the
+// pipeline first so it ends up in the saved file and the transcript timeline.
+//
+// Nothing is written to disk while idle. Note that keeping the microphone open shows the
+// OS recording indicator, which is why pre-roll is off by default.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use anyhow::Result;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+
+use super::capture::{create_capture_backend, get_backend_for, AudioCaptureBackend, CaptureFormat, CaptureSource};
+use super::devices::AudioDevice;
+use super::recording_manager::RecordingManager;
+use super::recording_state::DeviceType;
+
+/// Upper bound for the pre-roll length
+pub const MAX_PREROLL_SECONDS: u32 = 120;
+
+/// Bounded ring buffer of interleaved samples
+#[derive(Debug)]
+pub struct PrerollBuffer {
+    samples: VecDeque<f32>,
+    capacity: usize,
+    channels: usize,
+}
+
+impl PrerollBuffer {
+    pub fn new(format: CaptureFormat, seconds: u32) -> Self {
+        let channels = format.channels.max(1) as usize;
+        let capacity = format.sample_rate as usize * seconds as usize * channels;
+        Self { samples: VecDeque::with_capacity(capacity), capacity, channels }
+    }
+
+    /// Append interleaved samples, dropping the oldest whole frames when full
+    pub fn push(&mut self, data: &[f32]) {
+        let data = if data.len() > self.capacity {
+            &data[data.len() - self.capacity..]
+        } else {
+            data
+        };
+
+        let overflow = (self.samples.len() + data.len()).saturating_sub(self.capacity);
+        let overflow = overflow.div_ceil(self.channels) * self.channels;
+        self.samples.drain(..overflow.min(self.samples.len()));
+        self.samples.extend(data);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn to_vec(&self) -> Vec<f32> {
+        self.samples.iter().copied().collect()
+    }
+}
+
+/// Buffered audio handed to the recording when it starts
+#[derive(Debug, Clone)]
+pub struct PrerollAudio {
+    pub device_name: String,
+    pub device_type: DeviceType,
+    pub format: CaptureFormat,
+    /// Interleaved samples in the device's native format
+    pub samples: Vec<f32>,
+}
+
+impl PrerollAudio {
+    pub fn duration_secs(&self) -> f64 {
+        let frames = self.samples.len() / self.format.channels.max(1) as usize;
+        frames as f64 / self.format.sample_rate.max(1) as f64
+    }
+}
+
+/// Pre-roll settings and buffered duration, for the settings UI
+#[derive(Debug, Clone, Serialize)]
+pub struct PrerollStatus {
+    pub seconds: u32,
+    pub include: bool,
+    pub active: bool,
+    pub buffered_seconds: f64,
+}
+
+struct PrerollSource {
+    device: Arc<AudioDevice>,
+    device_type: DeviceType,
+    format: CaptureFormat,
+    backend: Box<dyn AudioCaptureBackend>,
+    buffer: Arc<Mutex<PrerollBuffer>>,
+}
+
+impl PrerollSource {
+    fn snapshot(&self) -> PrerollAudio {
+        PrerollAudio {
+            device_name: self.device.name.clone(),
+            device_type: self.device_type.clone(),
+            format: self.format,
+            samples: self.buffer.lock().unwrap().to_vec(),
+        }
+    }
+}
+
+struct PrerollState {
+    seconds: u32,
+    include: bool,
+    sources: Vec<PrerollSource>,
+}
+
+static PREROLL: Lazy<Mutex<PrerollState>> = Lazy::new(|| {
+    Mutex::new(PrerollState { seconds: 0, include: true, sources: Vec::new() })
+});
+
+/// Change the pre-roll length; 0 disables it. Restarts the idle capture when not recording.
+pub async fn set_preroll_seconds<R: Runtime>(app: &AppHandle<R>, seconds: u32) -> Result<u32> {
+    let seconds = seconds.min(MAX_PREROLL_SECONDS);
+    stop_sources();
+    PREROLL.lock().unwrap().seconds = seconds;
+    info!("⏪ Pre-roll set to {}s", seconds);
+
+    if seconds > 0 && !super::recording_commands::is_recording().await {
+        start_sources(app, seconds).await?;
+    }
+    Ok(seconds)
+}
+
+/// Whether buffered pre-roll is included when recording starts
+pub fn set_include_preroll(include: bool) {
+    PREROLL.lock().unwrap().include = include;
+}
+
+pub fn get_preroll_status() -> PrerollStatus {
+    let state = PREROLL.lock().unwrap();
+    let buffered_seconds = state
+        .sources
+        .iter()
+        .map(|s| s.snapshot().duration_secs())
+        .fold(0.0, f64::max);
+    PrerollStatus {
+        seconds: state.seconds,
+        include: state.include,
+        active: !state.sources.is_empty(),
+        buffered_seconds,
+    }
+}
+
+/// Stop the idle capture and return the buffered audio if the user opted to include it
+///
+/// Must run before the recording streams open so the devices are free.
+pub fn take_preroll() -> Vec<PrerollAudio> {
+    let include = PREROLL.lock().unwrap().include;
+    let sources = std::mem::take(&mut PREROLL.lock().unwrap().sources);
+
+    let mut audio = Vec::new();
+    for mut source in sources {
+        if let Err(e) = source.backend.stop() {
+            warn!("Failed to stop pre-roll capture for '{}': {}", source.device.name, e);
+        }
+        let snapshot = source.snapshot();
+        if include && !snapshot.samples.is_empty() {
+            info!("⏪ Including {:.1}s of pre-roll from '{}'", snapshot.duration_secs(), snapshot.device_name);
+            audio.push(snapshot);
+        }
+    }
+    audio
+}
+
+/// Resume the idle capture after a recording ends
+pub fn rearm<R: Runtime>(app: AppHandle<R>) {
+    let seconds = PREROLL.lock().unwrap().seconds;
+    if seconds == 0 {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = start_sources(&app, seconds).await {
+            warn!("⚠️ Failed to restart pre-roll capture: {}", e);
+        }
+    });
+}
+
+async fn start_sources<R: Runtime>(app: &AppHandle<R>, seconds: u32) -> Result<()> {
+    let (default_microphone, default_system) = RecordingManager::default_recording_devices()?;
+    let microphone = super::device_preferences::resolve_preferred_device(app, CaptureSource::Microphone)
+        .await
+        .or(default_microphone);
+    let system = super::device_preferences::resolve_preferred_device(app, CaptureSource::System)
+        .await
+        .or(default_system);
+
+    let mut sources = Vec::new();
+    for (device, device_type, source) in [
+        (microphone, DeviceType::Microphone, CaptureSource::Microphone),
+        (system, DeviceType::System, CaptureSource::System),
+    ] {
+        let Some(device) = device else { continue };
+        match open_source(device.clone(), device_type, source, seconds).await {
+            Ok(source) => sources.push(source),
+            Err(e) => warn!("⚠️ Pre-roll: cannot capture '{}': {}", device.name, e),
+        }
+    }
+
+    if sources.is_empty() {
+        return Err(anyhow::anyhow!("No device could be opened for pre-roll capture"));
+    }
+
+    info!("⏪ Pre-roll capture running on {} device(s)", sources.len());
+    let replaced = std::mem::replace(&mut PREROLL.lock().unwrap().sources, sources);
+    for mut source in replaced {
+        let _ = source.backend.stop();
+    }
+    Ok(())
+}
+
+async fn open_source(
+    device: Arc<AudioDevice>,
+    device_type: DeviceType,
+    source: CaptureSource,
+    seconds: u32,
+) -> Result<PrerollSource> {
+    let mut backend = create_capture_backend(get_backend_for(source), source, &device).await?;
+    let format = backend.format();
+    let buffer = Arc::new(Mutex::new(PrerollBuffer::new(format, seconds)));
+
+    let sink = buffer.clone();
+    let device_name = device.name.clone();
+    backend.start(
+        Box::new(move |data: &[f32]| sink.lock().unwrap().push(data)),
+        Box::new(move |err| warn!("⚠️ Pre-roll stream error on '{}': {}", device_name, err)),
+    )?;
+
+    Ok(PrerollSource { device, device_type, format, backend, buffer })
+}
+
+fn stop_sources() {
+    let sources = std::mem::take(&mut PREROLL.lock().unwrap().sources);
+    for mut source in sources {
+        if let Err(e) = source.backend.stop() {
+            warn!("Failed to stop pre-roll capture for '{}': {}", source.device.name, e);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn set_preroll_seconds_command<R: Runtime>(app: AppHandle<R>, seconds: u32) -> Result<u32, String> {
+    set_preroll_seconds(&app, seconds)
+        .await
+        .map_err(|e| format!("Failed to enable pre-roll: {}", e))
+}
+
+#[tauri::command]
+pub async fn set_include_preroll_command(include: bool) -> Result<(), String> {
+    set_include_preroll(include);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_preroll_status_command() -> Result<PrerollStatus, String> {
+    Ok(get_preroll_status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_keeps_only_latest_frames() {
+        let format = CaptureFormat { sample_rate: 4, channels: 2 };
+        let mut buffer = PrerollBuffer::new(format, 1); // 8 samples
+
+        buffer.push(&[1.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+        buffer.push(&[4.0, 4.0, 5.0, 5.0]);
+        assert_eq!(buffer.to_vec(), vec![2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 5.0, 5.0]);
+
+        buffer.push(&[6.0; 20]);
+        assert_eq!(buffer.len(), 8);
+    }
+
+    #[test]
+    fn test_preroll_duration() {
+        let audio = PrerollAudio {
+            device_name: "Mic".into(),
+            device_type: DeviceType::Microphone,
+            format: CaptureFormat { sample_rate: 1000, channels: 2 },
+            samples: vec![0.0; 3000],
+        };
+        assert!((audio.duration_secs() - 1.5).abs() < 1e-9);
+    }
+}
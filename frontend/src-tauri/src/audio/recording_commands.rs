@@ -6,14 +6,17 @@
 use anyhow::Result;
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc, Mutex,
-};
+use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter, Manager, Runtime};
 use tokio::task::JoinHandle;
 
 use super::{parse_audio_device, RecordingManager, DeviceEvent, DeviceMonitorType};
+use super::session::{self, SessionPhase};
+use crate::error::{AppError, ErrorCode};
+use super::recording_state::AudioError;
+use super::silence_watchdog::WarningReason;
+use crate::notifications::commands::notify_in_background;
+use crate::notifications::types::Notification;
 
 // Import transcription modules
 use super::transcription::{
@@ -28,11 +31,8 @@ pub use super::transcription::TranscriptUpdate;
 // GLOBAL STATE
 // ============================================================================
 
-// Simple recording state tracking
-static IS_RECORDING: AtomicBool = AtomicBool::new(false);
-
 // Global recording manager and transcription task to keep them alive during recording
-static RECORDING_MANAGER: Mutex<Option<RecordingManager>> = Mutex::new(None);
+pub(super) static RECORDING_MANAGER: Mutex<Option<RecordingManager>> = Mutex::new(None);
 static TRANSCRIPTION_TASK: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
 
 // ============================================================================
@@ -56,7 +56,7 @@ pub struct TranscriptionStatus {
 // ============================================================================
 
 /// Start recording with default devices
-pub async fn start_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+pub async fn start_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), AppError> {
     start_recording_with_meeting_name(app, None).await
 }
 
@@ -64,7 +64,21 @@ pub async fn start_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String
 pub async fn start_recording_with_meeting_name<R: Runtime>(
     app: AppHandle<R>,
     meeting_name: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    session::begin(&app, meeting_name.clone())?;
+    crate::usage::meter::flush_unattributed(&app).await;
+    let result = arm_default_devices(app.clone(), meeting_name).await;
+    if let Err(e) = &result {
+        session::fail_arming(&app, &e.message);
+    }
+    result
+}
+
+/// Open the default devices and start capturing for an arming session
+async fn arm_default_devices<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_name: Option<String>,
+) -> Result<(), AppError> {
     info!(
         "Starting recording with default devices, meeting: {:?}",
         meeting_name
@@ -76,18 +90,11 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
         info!("🎤 Checking microphone permission before starting recording...");
         if !crate::audio::ensure_microphone_permission() {
             error!("❌ Microphone permission not granted");
-            return Err("Microphone permission is required to record audio. Please grant permission in System Settings > Privacy & Security > Microphone and restart the app.".to_string());
+            return Err(AppError::new(ErrorCode::PermissionDenied, "Microphone permission is required to record audio. Grant it in System Settings > Privacy & Security > Microphone, and recording can start as soon as it is allowed."));
         }
         info!("✅ Microphone permission verified");
     }
 
-    // Check if already recording
-    let current_recording_state = IS_RECORDING.load(Ordering::SeqCst);
-    info!("🔍 IS_RECORDING state check: {}", current_recording_state);
-    if current_recording_state {
-        return Err("Recording already in progress".to_string());
-    }
-
     // Validate that transcription models are available before starting recording
     info!("🔍 Validating transcription model availability before starting recording...");
     if let Err(validation_error) = transcription::validate_transcription_model_ready(&app).await {
@@ -100,10 +107,13 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
             "actionable": true
         }));
 
-        return Err(validation_error);
+        return Err(AppError::new(ErrorCode::ModelMissing, validation_error));
     }
     info!("✅ Transcription model validation passed");
 
+    // Don't start what can't be saved
+    super::disk_space::ensure_space_to_record(&app).await?;
+
     // Async-first approach - no more blocking operations!
     info!("🚀 Starting async recording initialization");
 
@@ -119,19 +129,36 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
             now.format("%Y-%m-%d_%H-%M-%S")
         )
     });
-    manager.set_meeting_name(Some(effective_meeting_name));
+    manager.set_meeting_name(Some(effective_meeting_name.clone()));
 
     // Set up error callback
     let app_for_error = app.clone();
     manager.set_error_callback(move |error| {
         let _ = app_for_error.emit("recording-error", error.user_message());
+        if matches!(error, AudioError::PermissionDenied) {
+            notify_in_background(&app_for_error, Notification::permission_problem(error.user_message()));
+        }
+    });
+
+    // Warn the user about muted or silent inputs while recording
+    let app_for_warning = app.clone();
+    manager.set_warning_callback(move |warning| {
+        let _ = app_for_warning.emit("recording-warning", warning);
+        // All-zero input is how a denied permission shows up on macOS, unless the microphone is muted
+        if warning.reason == WarningReason::NoSignal && !super::mute_detection::is_input_muted() {
+            notify_in_background(&app_for_warning, Notification::permission_problem(warning.reason.user_message()));
+        }
     });
 
-    // Start recording with default devices
+    // Start recording with the user's preferred devices, falling back to defaults
+    let preferred_microphone =
+        super::device_preferences::resolve_preferred_device(&app, super::capture::CaptureSource::Microphone).await;
+    let preferred_system =
+        super::device_preferences::resolve_preferred_device(&app, super::capture::CaptureSource::System).await;
     let transcription_receiver = manager
-        .start_recording_with_defaults()
+        .start_recording_with_preferred(preferred_microphone, preferred_system)
         .await
-        .map_err(|e| format!("Failed to start recording: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Failed to start recording"))?;
 
     // Store the manager globally to keep it alive
     {
@@ -139,10 +166,15 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
         *global_manager = Some(manager);
     }
 
-    // Set recording flag and reset speech detection flag
-    info!("🔍 Setting IS_RECORDING to true and resetting SPEECH_DETECTED_EMITTED");
-    IS_RECORDING.store(true, Ordering::SeqCst);
+    // Capture is running; reset speech detection for the new session
+    session::transition(&app, SessionPhase::Recording)?;
     reset_speech_detected_flag(); // Reset for new recording session
+    spawn_recording_level_events(app.clone());
+    super::auto_stop::spawn_auto_stop_monitor(app.clone());
+    super::disk_space::spawn_disk_space_monitor(app.clone());
+    super::mute_detection::spawn_mute_monitor();
+    super::bluetooth_microphone::spawn_hands_free_check(app.clone());
+    super::pipeline_stats::spawn_pipeline_stats_events(app.clone());
 
     // Start optimized parallel transcription task and store handle
     let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver);
@@ -170,6 +202,8 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    translation: None,
+                    words: update.words,
                 };
 
                 // Save to recording manager
@@ -189,7 +223,12 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
         "message": "Recording started successfully with parallel processing",
         "devices": ["Default Microphone", "Default System Audio"],
         "workers": 3
-    })).map_err(|e| e.to_string())?;
+    }))?;
+    crate::integrations::webhooks::dispatch(
+        &app,
+        crate::integrations::webhooks::WebhookEvent::RecordingStarted,
+        serde_json::json!({ "meeting_name": effective_meeting_name }),
+    );
 
     // Update tray menu to reflect recording state
     crate::tray::update_tray_menu(&app);
@@ -204,7 +243,7 @@ pub async fn start_recording_with_devices<R: Runtime>(
     app: AppHandle<R>,
     mic_device_name: Option<String>,
     system_device_name: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     start_recording_with_devices_and_meeting(app, mic_device_name, system_device_name, None).await
 }
 
@@ -214,7 +253,23 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
     mic_device_name: Option<String>,
     system_device_name: Option<String>,
     meeting_name: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    session::begin(&app, meeting_name.clone())?;
+    crate::usage::meter::flush_unattributed(&app).await;
+    let result = arm_with_devices(app.clone(), mic_device_name, system_device_name, meeting_name).await;
+    if let Err(e) = &result {
+        session::fail_arming(&app, &e.message);
+    }
+    result
+}
+
+/// Open the given devices and start capturing for an arming session
+async fn arm_with_devices<R: Runtime>(
+    app: AppHandle<R>,
+    mic_device_name: Option<String>,
+    system_device_name: Option<String>,
+    meeting_name: Option<String>,
+) -> Result<(), AppError> {
     info!(
         "Starting recording with specific devices: mic={:?}, system={:?}, meeting={:?}",
         mic_device_name, system_device_name, meeting_name
@@ -226,18 +281,11 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
         info!("🎤 Checking microphone permission before starting recording...");
         if !crate::audio::ensure_microphone_permission() {
             error!("❌ Microphone permission not granted");
-            return Err("Microphone permission is required to record audio. Please grant permission in System Settings > Privacy & Security > Microphone and restart the app.".to_string());
+            return Err(AppError::new(ErrorCode::PermissionDenied, "Microphone permission is required to record audio. Grant it in System Settings > Privacy & Security > Microphone, and recording can start as soon as it is allowed."));
         }
         info!("✅ Microphone permission verified");
     }
 
-    // Check if already recording
-    let current_recording_state = IS_RECORDING.load(Ordering::SeqCst);
-    info!("🔍 IS_RECORDING state check: {}", current_recording_state);
-    if current_recording_state {
-        return Err("Recording already in progress".to_string());
-    }
-
     // Validate that transcription models are available before starting recording
     info!("🔍 Validating transcription model availability before starting recording...");
     if let Err(validation_error) = transcription::validate_transcription_model_ready(&app).await {
@@ -250,14 +298,17 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
             "actionable": true
         }));
 
-        return Err(validation_error);
+        return Err(AppError::new(ErrorCode::ModelMissing, validation_error));
     }
     info!("✅ Transcription model validation passed");
 
+    // Don't start what can't be saved
+    super::disk_space::ensure_space_to_record(&app).await?;
+
     // Parse devices
     let mic_device = if let Some(ref name) = mic_device_name {
         Some(Arc::new(parse_audio_device(name).map_err(|e| {
-            format!("Invalid microphone device '{}': {}", name, e)
+            AppError::new(ErrorCode::DeviceNotFound, format!("Invalid microphone device '{}': {}", name, e))
         })?))
     } else {
         None
@@ -265,7 +316,7 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
 
     let system_device = if let Some(ref name) = system_device_name {
         Some(Arc::new(parse_audio_device(name).map_err(|e| {
-            format!("Invalid system device '{}': {}", name, e)
+            AppError::new(ErrorCode::DeviceNotFound, format!("Invalid system device '{}': {}", name, e))
         })?))
     } else {
         None
@@ -285,19 +336,32 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
             now.format("%Y-%m-%d_%H-%M-%S")
         )
     });
-    manager.set_meeting_name(Some(effective_meeting_name));
+    manager.set_meeting_name(Some(effective_meeting_name.clone()));
 
     // Set up error callback
     let app_for_error = app.clone();
     manager.set_error_callback(move |error| {
         let _ = app_for_error.emit("recording-error", error.user_message());
+        if matches!(error, AudioError::PermissionDenied) {
+            notify_in_background(&app_for_error, Notification::permission_problem(error.user_message()));
+        }
+    });
+
+    // Warn the user about muted or silent inputs while recording
+    let app_for_warning = app.clone();
+    manager.set_warning_callback(move |warning| {
+        let _ = app_for_warning.emit("recording-warning", warning);
+        // All-zero input is how a denied permission shows up on macOS, unless the microphone is muted
+        if warning.reason == WarningReason::NoSignal && !super::mute_detection::is_input_muted() {
+            notify_in_background(&app_for_warning, Notification::permission_problem(warning.reason.user_message()));
+        }
     });
 
     // Start recording with specified devices
     let transcription_receiver = manager
         .start_recording(mic_device, system_device)
         .await
-        .map_err(|e| format!("Failed to start recording: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Failed to start recording"))?;
 
     // Store the manager globally to keep it alive
     {
@@ -305,10 +369,15 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
         *global_manager = Some(manager);
     }
 
-    // Set recording flag and reset speech detection flag
-    info!("🔍 Setting IS_RECORDING to true and resetting SPEECH_DETECTED_EMITTED");
-    IS_RECORDING.store(true, Ordering::SeqCst);
+    // Capture is running; reset speech detection for the new session
+    session::transition(&app, SessionPhase::Recording)?;
     reset_speech_detected_flag(); // Reset for new recording session
+    spawn_recording_level_events(app.clone());
+    super::auto_stop::spawn_auto_stop_monitor(app.clone());
+    super::disk_space::spawn_disk_space_monitor(app.clone());
+    super::mute_detection::spawn_mute_monitor();
+    super::bluetooth_microphone::spawn_hands_free_check(app.clone());
+    super::pipeline_stats::spawn_pipeline_stats_events(app.clone());
 
     // Start optimized parallel transcription task and store handle
     let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver);
@@ -336,6 +405,8 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    translation: None,
+                    words: update.words,
                 };
 
                 // Save to recording manager
@@ -358,7 +429,12 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
             system_device_name.unwrap_or_else(|| "Default System Audio".to_string())
         ],
         "workers": 3
-    })).map_err(|e| e.to_string())?;
+    }))?;
+    crate::integrations::webhooks::dispatch(
+        &app,
+        crate::integrations::webhooks::WebhookEvent::RecordingStarted,
+        serde_json::json!({ "meeting_name": effective_meeting_name }),
+    );
 
     // Update tray menu to reflect recording state
     crate::tray::update_tray_menu(&app);
@@ -372,16 +448,17 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
 pub async fn stop_recording<R: Runtime>(
     app: AppHandle<R>,
     _args: RecordingArgs,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     info!(
         "🛑 Starting optimized recording shutdown - ensuring ALL transcript chunks are preserved"
     );
 
-    // Check if recording is active
-    if !IS_RECORDING.load(Ordering::SeqCst) {
+    // Only a running or paused recording can be stopped; a second stop while finalizing is a no-op
+    if !matches!(session::phase(), SessionPhase::Recording | SessionPhase::Paused) {
         info!("Recording was not active");
         return Ok(());
     }
+    session::transition(&app, SessionPhase::Finalizing)?;
 
     // Emit shutdown progress to frontend
     let _ = app.emit(
@@ -419,7 +496,9 @@ pub async fn stop_recording<R: Runtime>(
         }
         Err(e) => {
             error!("❌ Failed to stop audio streams: {}", e);
-            return Err(format!("Failed to stop audio streams: {}", e));
+            let message = format!("Failed to stop audio streams: {}", e);
+            session::fail(&app, &message);
+            return Err(message.into());
         }
     }
 
@@ -441,6 +520,7 @@ pub async fn stop_recording<R: Runtime>(
 
     if let Some(task_handle) = transcription_task {
         info!("⏳ Waiting for ALL transcription chunks to be processed (no timeout - preserving every chunk)");
+        crate::tray::update_tray_menu(&app);
 
         // Enhanced progress monitoring during shutdown
         let progress_app = app.clone();
@@ -700,9 +780,8 @@ pub async fn stop_recording<R: Runtime>(
         (None, None)
     };
 
-    // Set recording flag to false
-    info!("🔍 Setting IS_RECORDING to false");
-    IS_RECORDING.store(false, Ordering::SeqCst);
+    session::transition(&app, SessionPhase::Done)?;
+    super::preroll::rearm(app.clone());
 
     // Step 4.5: Prepare metadata for frontend (NO database save)
     // NOTE: We do NOT save to database here. The frontend will save after all transcripts are displayed.
@@ -732,6 +811,8 @@ pub async fn stop_recording<R: Runtime>(
         }),
     );
 
+    notify_in_background(&app, Notification::transcription_complete(folder_path_str.clone()));
+
     // Emit final stop event with folder_path and meeting_name for frontend to save
     app.emit(
         "recording-stopped",
@@ -740,8 +821,7 @@ pub async fn stop_recording<R: Runtime>(
             "folder_path": folder_path_str,
             "meeting_name": meeting_name_str
         }),
-    )
-    .map_err(|e| e.to_string())?;
+    )?;
 
     // Update tray menu to reflect stopped state
     crate::tray::update_tray_menu(&app);
@@ -750,43 +830,55 @@ pub async fn stop_recording<R: Runtime>(
     Ok(())
 }
 
-/// Check if recording is active
+/// Check if recording is active (including while a stopped recording is being finalized)
 pub async fn is_recording() -> bool {
-    IS_RECORDING.load(Ordering::SeqCst)
+    session::phase().is_recording()
+}
+
+/// Whether a stopped recording's remaining audio is still being transcribed and saved
+pub fn is_finishing_transcription() -> bool {
+    session::phase() == SessionPhase::Finalizing
+}
+
+/// Seconds recorded so far, pauses excluded; `None` when not recording
+pub fn active_recording_duration() -> Option<f64> {
+    let manager_guard = RECORDING_MANAGER.lock().ok()?;
+    manager_guard.as_ref()?.get_active_recording_duration()
 }
 
 /// Get recording statistics
 pub async fn get_transcription_status() -> TranscriptionStatus {
     TranscriptionStatus {
         chunks_in_queue: 0,
-        is_processing: IS_RECORDING.load(Ordering::SeqCst),
+        is_processing: session::phase().is_recording(),
         last_activity_ms: 0,
     }
 }
 
 /// Pause the current recording
 #[tauri::command]
-pub async fn pause_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+pub async fn pause_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), AppError> {
     info!("Pausing recording");
 
     // Check if currently recording
-    if !IS_RECORDING.load(Ordering::SeqCst) {
-        return Err("No recording is currently active".to_string());
+    if session::phase() != SessionPhase::Recording {
+        return Err(AppError::new(ErrorCode::NotRecording, "No recording is currently active"));
     }
 
     // Access the recording manager and pause it
     let manager_guard = RECORDING_MANAGER.lock().unwrap();
     if let Some(manager) = manager_guard.as_ref() {
-        manager.pause_recording().map_err(|e| e.to_string())?;
+        let marker = manager.pause_recording()?;
+        session::transition(&app, SessionPhase::Paused)?;
 
-        // Emit pause event to frontend
+        // Emit pause event to frontend (marker lets the transcript show the break)
         app.emit(
             "recording-paused",
             serde_json::json!({
-                "message": "Recording paused"
+                "message": "Recording paused",
+                "marker": marker
             }),
-        )
-        .map_err(|e| e.to_string())?;
+        )?;
 
         // Update tray menu to reflect paused state
         crate::tray::update_tray_menu(&app);
@@ -794,33 +886,34 @@ pub async fn pause_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String
         info!("Recording paused successfully");
         Ok(())
     } else {
-        Err("No recording manager found".to_string())
+        Err(AppError::internal("No recording manager found"))
     }
 }
 
 /// Resume the current recording
 #[tauri::command]
-pub async fn resume_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+pub async fn resume_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), AppError> {
     info!("Resuming recording");
 
-    // Check if currently recording
-    if !IS_RECORDING.load(Ordering::SeqCst) {
-        return Err("No recording is currently active".to_string());
+    // Check if currently paused
+    if session::phase() != SessionPhase::Paused {
+        return Err(AppError::new(ErrorCode::NotRecording, "No paused recording to resume"));
     }
 
     // Access the recording manager and resume it
     let manager_guard = RECORDING_MANAGER.lock().unwrap();
     if let Some(manager) = manager_guard.as_ref() {
-        manager.resume_recording().map_err(|e| e.to_string())?;
+        let marker = manager.resume_recording()?;
+        session::transition(&app, SessionPhase::Recording)?;
 
         // Emit resume event to frontend
         app.emit(
             "recording-resumed",
             serde_json::json!({
-                "message": "Recording resumed"
+                "message": "Recording resumed",
+                "marker": marker
             }),
-        )
-        .map_err(|e| e.to_string())?;
+        )?;
 
         // Update tray menu to reflect resumed state
         crate::tray::update_tray_menu(&app);
@@ -828,32 +921,72 @@ pub async fn resume_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), Strin
         info!("Recording resumed successfully");
         Ok(())
     } else {
-        Err("No recording manager found".to_string())
+        Err(AppError::internal("No recording manager found"))
+    }
+}
+
+/// Drop a highlight marker at the current moment of the recording
+#[tauri::command]
+pub async fn add_marker<R: Runtime>(
+    app: AppHandle<R>,
+    label: Option<String>,
+) -> Result<super::recording_saver::TimelineMarker, AppError> {
+    if !matches!(session::phase(), SessionPhase::Recording | SessionPhase::Paused) {
+        return Err(AppError::new(ErrorCode::NotRecording, "No recording is currently active"));
+    }
+
+    let marker = {
+        let manager_guard = RECORDING_MANAGER.lock().unwrap();
+        let manager = manager_guard.as_ref().ok_or("No recording manager found")?;
+        manager.add_highlight(label)?
+    };
+
+    // Let the transcript show the marker as it happens
+    app.emit("recording-marker", serde_json::json!({ "marker": marker }))?;
+    Ok(marker)
+}
+
+/// Start or end a confidential part of the recording; nothing said during it is recorded or
+/// transcribed, and the saved meeting shows it as a gap
+#[tauri::command]
+pub async fn set_recording_confidential<R: Runtime>(
+    app: AppHandle<R>,
+    confidential: bool,
+) -> Result<super::recording_saver::TimelineMarker, AppError> {
+    if !matches!(session::phase(), SessionPhase::Recording | SessionPhase::Paused) {
+        return Err(AppError::new(ErrorCode::NotRecording, "No recording is currently active"));
     }
+
+    let marker = {
+        let manager_guard = RECORDING_MANAGER.lock().unwrap();
+        let manager = manager_guard.as_ref().ok_or("No recording manager found")?;
+        manager.set_confidential(confidential)?
+    };
+
+    app.emit("recording-marker", serde_json::json!({ "marker": marker }))?;
+    Ok(marker)
 }
 
 /// Check if recording is currently paused
 #[tauri::command]
 pub async fn is_recording_paused() -> bool {
-    let manager_guard = RECORDING_MANAGER.lock().unwrap();
-    if let Some(manager) = manager_guard.as_ref() {
-        manager.is_paused()
-    } else {
-        false
-    }
+    session::phase() == SessionPhase::Paused
 }
 
 /// Get detailed recording state
 #[tauri::command]
 pub async fn get_recording_state() -> serde_json::Value {
-    let is_recording = IS_RECORDING.load(Ordering::SeqCst);
+    let session = session::current();
+    let is_recording = session.phase.is_recording();
     let manager_guard = RECORDING_MANAGER.lock().unwrap();
 
     if let Some(manager) = manager_guard.as_ref() {
         serde_json::json!({
+            "phase": session.phase,
             "is_recording": is_recording,
             "is_paused": manager.is_paused(),
             "is_active": manager.is_active(),
+            "is_confidential": manager.is_confidential(),
             "recording_duration": manager.get_recording_duration(),
             "active_duration": manager.get_active_recording_duration(),
             "total_pause_duration": manager.get_total_pause_duration(),
@@ -861,9 +994,11 @@ pub async fn get_recording_state() -> serde_json::Value {
         })
     } else {
         serde_json::json!({
+            "phase": session.phase,
             "is_recording": is_recording,
             "is_paused": false,
             "is_active": false,
+            "is_confidential": false,
             "recording_duration": null,
             "active_duration": null,
             "total_pause_duration": 0.0,
@@ -1019,6 +1154,134 @@ pub async fn get_active_audio_output() -> Result<super::playback_monitor::AudioO
         .map_err(|e| format!("Failed to get audio output info: {}", e))
 }
 
+/// Emit `audio-levels` for each active capture stream while recording
+fn spawn_recording_level_events<R: Runtime>(app: AppHandle<R>) {
+    use super::simple_level_monitor::{AudioLevelUpdate, AUDIO_LEVELS_EVENT, LEVEL_INTERVAL_MS};
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_millis(LEVEL_INTERVAL_MS));
+
+        while session::phase().is_recording() {
+            interval.tick().await;
+
+            let levels = match RECORDING_MANAGER.lock() {
+                Ok(guard) => match guard.as_ref() {
+                    Some(manager) if !manager.is_paused() => manager.stream_levels(),
+                    Some(_) => continue,
+                    None => break,
+                },
+                Err(_) => break,
+            };
+
+            if let Err(e) = app.emit(AUDIO_LEVELS_EVENT, &AudioLevelUpdate::now(levels)) {
+                error!("Failed to emit recording audio levels: {}", e);
+                break;
+            }
+        }
+
+        info!("Recording level events stopped");
+    });
+}
+
+/// One microphone switch at a time; held across the awaits of opening the new stream
+static MICROPHONE_SWITCH: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Moves the microphone stream of the active recording to `device`
+///
+/// Only the microphone stream is replaced: system audio keeps being captured throughout.
+/// `RECORDING_MANAGER` is only locked to hand out and take back the parts involved, never
+/// across an await.
+pub(super) async fn switch_microphone(device: Arc<super::AudioDevice>) -> Result<()> {
+    let _switch = MICROPHONE_SWITCH.lock().await;
+    info!("🔀 Switching microphone to '{}'", device.name);
+
+    let (state, mut monitor) = {
+        let mut manager_guard = RECORDING_MANAGER.lock().unwrap();
+        let manager = manager_guard.as_mut().ok_or_else(|| anyhow::anyhow!("Recording not active"))?;
+        manager.begin_microphone_failover()
+    };
+
+    let opened =
+        super::stream::AudioStream::create(device.clone(), state.clone(), super::recording_state::DeviceType::Microphone, None)
+            .await;
+    if let (Ok(_), Some(monitor)) = (&opened, monitor.as_mut()) {
+        // Watch the new microphone instead of the one that disappeared
+        monitor.stop_monitoring().await;
+        if let Err(e) = monitor.start_monitoring(Some(device.clone()), state.get_system_device()) {
+            warn!("Failed to restart device monitoring after failover: {}", e);
+        }
+    }
+
+    let (opened, error) = match opened {
+        Ok(stream) => (Some((device.clone(), stream)), None),
+        Err(e) => (None, Some(e)),
+    };
+    let (previous, orphan) = {
+        let mut manager_guard = RECORDING_MANAGER.lock().unwrap();
+        match manager_guard.as_mut() {
+            // The recording the switch started on
+            Some(manager) if Arc::ptr_eq(manager.get_state(), &state) => {
+                (manager.finish_microphone_failover(opened, monitor), None)
+            }
+            _ => (None, opened.map(|(_, stream)| stream)),
+        }
+    };
+
+    if let Some(stream) = previous {
+        if let Err(e) = stream.stop() {
+            warn!("Failed to stop the previous microphone stream: {}", e);
+        }
+    }
+    if let Some(stream) = orphan {
+        let _ = stream.stop();
+        return Err(anyhow::anyhow!("Recording stopped while switching microphones"));
+    }
+    if let Some(e) = error {
+        return Err(e);
+    }
+    info!("✅ Microphone switched to '{}'", device.name);
+    Ok(())
+}
+
+/// Move an active recording to the default input when its microphone has vanished
+///
+/// Called by the device watcher after every device change.
+pub async fn fail_over_microphone_if_needed<R: Runtime>(
+    app: &AppHandle<R>,
+    snapshot: &super::device_watcher::DeviceSnapshot,
+) {
+    if !session::phase().is_recording() {
+        return;
+    }
+
+    let active = {
+        let manager_guard = RECORDING_MANAGER.lock().unwrap();
+        manager_guard.as_ref().and_then(|m| m.get_state().get_microphone_device())
+    };
+    let Some(active) = active else {
+        return;
+    };
+    let Some(target) = super::device_watcher::failover_target(&active.name, snapshot) else {
+        return;
+    };
+
+    warn!("🔀 Active microphone '{}' disappeared, switching to default '{}'", active.name, target);
+    let device = Arc::new(super::AudioDevice::new(target.clone(), super::DeviceType::Input));
+
+    match switch_microphone(device).await {
+        Ok(()) => {
+            let _ = app.emit(super::device_watcher::DEVICE_FAILOVER_EVENT, serde_json::json!({
+                "previous": active.name,
+                "current": target,
+            }));
+        }
+        Err(e) => {
+            error!("❌ Microphone failover to '{}' failed: {}", target, e);
+            let _ = app.emit("recording-error", format!("Microphone disconnected and switching to '{}' failed", target));
+        }
+    }
+}
+
 /// Manually trigger device reconnection attempt
 /// Useful for UI "Retry" button
 #[tauri::command]
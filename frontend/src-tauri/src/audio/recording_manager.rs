@@ -12,8 +12,8 @@ use super::devices::get_safe_recording_devices_macos;
 use super::devices::{default_input_device, default_output_device};
 use super::recording_state::{RecordingState, AudioChunk, DeviceType as RecordingDeviceType};
 use super::pipeline::AudioPipelineManager;
-use super::stream::AudioStreamManager;
-use super::recording_saver::RecordingSaver;
+use super::stream::{AudioStream, AudioStreamManager};
+use super::recording_saver::{MarkerKind, RecordingSaver, TimelineMarker};
 use super::device_monitor::{AudioDeviceMonitor, DeviceEvent, DeviceMonitorType};
 
 /// Stream manager type enumeration
@@ -67,10 +67,23 @@ impl RecordingManager {
 
         // CRITICAL FIX: Create recording sender for pre-mixed audio from pipeline
         // Pipeline will mix mic + system audio professionally and send to this channel
+        // Snapshot the channel layout so the saver and the mixer agree for the whole recording
+        let channel_policy = super::channel_mix::get_channel_mix_policy();
+        self.recording_saver.set_channel_layout(channel_policy);
         let recording_sender = self.recording_saver.start_accumulation();
 
+        // Free the devices held by pre-roll capture and collect what it buffered
+        let preroll = super::preroll::take_preroll();
+        let preroll_seconds = preroll.iter().map(|p| p.duration_secs()).fold(0.0, f64::max);
+
         // Start recording state first
         self.state.start_recording()?;
+        if preroll_seconds > 0.0 {
+            // Live audio starts after the pre-roll on the timeline
+            self.state.backdate_start(std::time::Duration::from_secs_f64(preroll_seconds));
+            info!("⏪ Recording includes {:.1}s of pre-roll", preroll_seconds);
+        }
+        self.stream_manager.set_preroll(preroll, preroll_seconds);
 
         // Get device information for adaptive mixing
         // The pipeline uses device kind (Bluetooth vs Wired) to apply adaptive buffering:
@@ -105,6 +118,7 @@ impl RecordingManager {
             0, // Ignored - using dynamic sizing internally
             48000, // 48kHz sample rate
             Some(recording_sender), // CRITICAL: Pass recording sender to receive pre-mixed audio
+            channel_policy,
             mic_name,
             mic_kind,
             sys_name,
@@ -158,30 +172,48 @@ impl RecordingManager {
     /// User still hears audio via Bluetooth (playback), but recording captures
     /// via stable wired path for best quality.
     pub async fn start_recording_with_defaults(&mut self) -> Result<mpsc::UnboundedReceiver<AudioChunk>> {
+        self.start_recording_with_preferred(None, None).await
+    }
+
+    /// Start recording with the user's preferred devices, using defaults for any not given
+    ///
+    /// Preferred devices are expected to be already resolved against the current device
+    /// list (see `device_preferences::resolve_preferred_device`).
+    pub async fn start_recording_with_preferred(
+        &mut self,
+        preferred_microphone: Option<Arc<AudioDevice>>,
+        preferred_system: Option<Arc<AudioDevice>>,
+    ) -> Result<mpsc::UnboundedReceiver<AudioChunk>> {
+        let (default_microphone, default_system) = Self::default_recording_devices()?;
+
+        let microphone_device = preferred_microphone.or(default_microphone);
+        let system_device = preferred_system.or(default_system);
+
+        // Ensure at least microphone is available
+        if microphone_device.is_none() {
+            return Err(anyhow::anyhow!("❌ No microphone device available for recording"));
+        }
+
+        self.start_recording(microphone_device, system_device).await
+    }
+
+    /// Default microphone and system audio devices for this platform
+    pub(crate) fn default_recording_devices() -> Result<(Option<Arc<AudioDevice>>, Option<Arc<AudioDevice>>)> {
         #[cfg(target_os = "macos")]
         {
-            info!("🎙️ [macOS] Starting recording with smart device selection (Bluetooth override enabled)");
+            info!("🎙️ [macOS] Selecting recording devices with smart device selection (Bluetooth override enabled)");
 
             // Get safe recording devices with automatic Bluetooth fallback
             // This function handles all the detection and override logic for macOS
             let (microphone_device, system_device) = get_safe_recording_devices_macos()?;
 
             // Wrap in Arc for sharing across threads
-            let microphone_device = microphone_device.map(Arc::new);
-            let system_device = system_device.map(Arc::new);
-
-            // Ensure at least microphone is available
-            if microphone_device.is_none() {
-                return Err(anyhow::anyhow!("❌ No microphone device available for recording"));
-            }
-
-            // Start recording with selected devices
-            self.start_recording(microphone_device, system_device).await
+            Ok((microphone_device.map(Arc::new), system_device.map(Arc::new)))
         }
 
         #[cfg(not(target_os = "macos"))]
         {
-            info!("Starting recording with default devices");
+            info!("Selecting default recording devices");
 
             // Get default devices (no Bluetooth override on Windows/Linux)
             let microphone_device = match default_input_device() {
@@ -206,12 +238,7 @@ impl RecordingManager {
                 }
             };
 
-            // Ensure at least microphone is available
-            if microphone_device.is_none() {
-                return Err(anyhow::anyhow!("No microphone device available"));
-            }
-
-            self.start_recording(microphone_device, system_device).await
+            Ok((microphone_device, system_device))
         }
     }
 
@@ -225,6 +252,7 @@ impl RecordingManager {
         }
 
         // Stop recording state first
+        self.end_confidential();
         self.state.stop_recording();
 
         // Stop audio streams
@@ -246,6 +274,7 @@ impl RecordingManager {
         info!("🚀 Stopping recording streams with IMMEDIATE pipeline flush");
 
         // Stop recording state first
+        self.end_confidential();
         self.state.stop_recording();
 
         // Stop audio streams immediately
@@ -298,6 +327,7 @@ impl RecordingManager {
         info!("Recording duration before stop: {:?}s", recording_duration);
 
         // Stop recording state first
+        self.end_confidential();
         self.state.stop_recording();
 
         // Stop audio streams
@@ -339,15 +369,68 @@ impl RecordingManager {
     }
 
     /// Pause the current recording session
-    pub fn pause_recording(&self) -> Result<()> {
+    ///
+    /// Capture streams stop delivering samples, buffered audio is flushed to disk and a
+    /// marker is added to the transcript timeline.
+    pub fn pause_recording(&self) -> Result<TimelineMarker> {
         info!("Pausing recording");
-        self.state.pause_recording()
+        self.state.pause_recording()?;
+        self.stream_manager.pause_streams();
+        self.recording_saver.flush_audio();
+
+        let marker = TimelineMarker::new(MarkerKind::Paused, self.get_active_recording_duration().unwrap_or(0.0));
+        self.recording_saver.add_marker(marker.clone());
+        Ok(marker)
     }
 
     /// Resume the current recording session
-    pub fn resume_recording(&self) -> Result<()> {
+    pub fn resume_recording(&self) -> Result<TimelineMarker> {
         info!("Resuming recording");
-        self.state.resume_recording()
+        self.state.resume_recording()?;
+        self.stream_manager.resume_streams();
+
+        let marker = TimelineMarker::new(MarkerKind::Resumed, self.get_active_recording_duration().unwrap_or(0.0));
+        self.recording_saver.add_marker(marker.clone());
+        Ok(marker)
+    }
+
+    /// Flag the current moment as important, with an optional note
+    pub fn add_highlight(&self, label: Option<String>) -> Result<TimelineMarker> {
+        if !self.state.is_recording() {
+            return Err(anyhow::anyhow!("No recording is currently active"));
+        }
+        let mut marker = TimelineMarker::new(MarkerKind::Highlight, self.get_active_recording_duration().unwrap_or(0.0));
+        marker.label = label.map(|label| label.trim().to_string()).filter(|label| !label.is_empty());
+        self.recording_saver.add_marker(marker.clone());
+        Ok(marker)
+    }
+
+    /// Start or end a confidential part: until it ends, the recording and transcript get
+    /// silence instead of what's said
+    pub fn set_confidential(&self, confidential: bool) -> Result<TimelineMarker> {
+        self.state.set_confidential(confidential)?;
+        let kind = if confidential { MarkerKind::ConfidentialStart } else { MarkerKind::ConfidentialEnd };
+        let marker = TimelineMarker::new(kind, self.get_active_recording_duration().unwrap_or(0.0));
+        self.recording_saver.add_marker(marker.clone());
+        Ok(marker)
+    }
+
+    pub fn is_confidential(&self) -> bool {
+        self.state.is_confidential()
+    }
+
+    /// A confidential part still open when the recording stops ends with it
+    fn end_confidential(&self) {
+        if self.state.is_confidential() {
+            if let Err(e) = self.set_confidential(false) {
+                warn!("Failed to end the confidential part: {}", e);
+            }
+        }
+    }
+
+    /// Store the meeting language in the recording's metadata
+    pub fn set_language_info(&mut self, detected_language: Option<String>, language_override: Option<String>) {
+        self.recording_saver.set_language_info(detected_language, language_override);
     }
 
     /// Check if recording is currently paused
@@ -403,6 +486,14 @@ impl RecordingManager {
         self.state.set_error_callback(callback);
     }
 
+    /// Set the callback for non-fatal recording warnings (silence, no signal)
+    pub fn set_warning_callback<F>(&self, callback: F)
+    where
+        F: Fn(&super::silence_watchdog::RecordingWarning) + Send + Sync + 'static,
+    {
+        self.state.set_warning_callback(callback);
+    }
+
     /// Check if there's a fatal error
     pub fn has_fatal_error(&self) -> bool {
         self.state.has_fatal_error()
@@ -418,6 +509,11 @@ impl RecordingManager {
         self.recording_saver.add_transcript_segment(segment);
     }
 
+    /// Attach a translation to a saved transcript segment
+    pub fn set_segment_translation(&self, sequence_id: u64, translation: String) {
+        self.recording_saver.set_segment_translation(sequence_id, translation);
+    }
+
     /// Add a transcript chunk to be saved later (legacy method)
     pub fn add_transcript_chunk(&self, text: String) {
         self.recording_saver.add_transcript_chunk(text);
@@ -527,6 +623,34 @@ impl RecordingManager {
         }
     }
 
+    /// First step of moving the microphone stream to another device: hands out the device
+    /// monitor, so the caller can open the new stream and restart the monitor without
+    /// holding the manager's lock; `finish_microphone_failover` puts it back
+    pub fn begin_microphone_failover(&mut self) -> (Arc<RecordingState>, Option<AudioDeviceMonitor>) {
+        (self.state.clone(), self.device_monitor.take())
+    }
+
+    /// Swaps in the stream opened on the new microphone, if it opened, and returns the old
+    /// one for the caller to stop
+    ///
+    /// System audio, the pipeline, recording saver and transcription keep running, so the
+    /// session is not interrupted.
+    pub fn finish_microphone_failover(
+        &mut self,
+        opened: Option<(Arc<AudioDevice>, AudioStream)>,
+        monitor: Option<AudioDeviceMonitor>,
+    ) -> Option<AudioStream> {
+        self.device_monitor = monitor;
+        let (device, stream) = opened?;
+        let previous = self.stream_manager.replace_microphone_stream(device.clone(), stream);
+        self.state.stop_reconnecting();
+        self.recording_saver.set_device_info(
+            Some(device.name.clone()),
+            self.state.get_system_device().map(|d| d.name.clone()),
+        );
+        previous
+    }
+
     /// Handle a device disconnect event
     /// Pauses recording and attempts reconnection
     pub async fn handle_device_disconnect(&mut self, device_name: String, device_type: DeviceMonitorType) {
@@ -574,6 +698,21 @@ impl RecordingManager {
         self.state.is_reconnecting()
     }
 
+    /// Input levels of the active capture streams, for VU meters
+    pub fn stream_levels(&self) -> Vec<super::simple_level_monitor::AudioLevelData> {
+        self.stream_manager
+            .stream_levels()
+            .into_iter()
+            .map(|(device, device_type, levels)| {
+                let kind = match device_type {
+                    RecordingDeviceType::Microphone => "input",
+                    RecordingDeviceType::System => "output",
+                };
+                super::simple_level_monitor::AudioLevelData::new(&device.name, kind, levels)
+            })
+            .collect()
+    }
+
     /// Get reference to recording state for external access
     pub fn get_state(&self) -> &Arc<RecordingState> {
         &self.state
@@ -8,13 +8,42 @@ use log::error;
 use anyhow::Result;
 
 #[cfg(target_os = "macos")]
-use crate::audio::capture::AudioCaptureBackend;
+use crate::audio::capture::CaptureBackendType;
+use crate::audio::encode::{get_encoding_settings, set_encoding_settings, EncodingSettings};
+use crate::audio::channel_mix::{get_channel_mix_policy, set_channel_mix_policy, ChannelMixPolicy};
+use crate::audio::vad::{get_vad_sensitivity, set_vad_sensitivity, VadSensitivity};
+use crate::audio::auto_stop::{get_auto_stop_settings, set_auto_stop_settings, AutoStopSettings};
+use crate::audio::disk_space::{get_disk_guard_settings, set_disk_guard_settings, DiskGuardSettings};
+use crate::audio::transcription::streaming::{get_live_caption_settings, set_live_caption_settings, LiveCaptionSettings};
+use crate::whisper_engine::acceleration::{get_backend_preference, set_backend_preference, TranscriptionBackend};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RecordingPreferences {
     pub save_folder: PathBuf,
     pub auto_save: bool,
     pub file_format: String,
+    /// Codec and bitrate for saved recordings; `file_format` follows from it.
+    /// Left unchanged when a client does not send it.
+    #[serde(default)]
+    pub encoding: Option<EncodingSettings>,
+    /// Mono mix or stereo layout of saved recordings; left unchanged when not sent
+    #[serde(default)]
+    pub channel_mix: Option<ChannelMixPolicy>,
+    /// How readily speech is detected before transcription; left unchanged when not sent
+    #[serde(default)]
+    pub vad_sensitivity: Option<VadSensitivity>,
+    /// Stop after prolonged silence; left unchanged when not sent
+    #[serde(default)]
+    pub auto_stop: Option<AutoStopSettings>,
+    /// Free disk space thresholds for warning and stopping; left unchanged when not sent
+    #[serde(default)]
+    pub disk_guard: Option<DiskGuardSettings>,
+    /// Partial transcripts while speaking; left unchanged when not sent
+    #[serde(default)]
+    pub live_captions: Option<LiveCaptionSettings>,
+    /// GPU/CPU backend for local Whisper; left unchanged when not sent
+    #[serde(default)]
+    pub transcription_backend: Option<TranscriptionBackend>,
     #[cfg(target_os = "macos")]
     #[serde(default)]
     pub system_audio_backend: Option<String>,
@@ -25,15 +54,27 @@ impl Default for RecordingPreferences {
         Self {
             save_folder: get_default_recordings_folder(),
             auto_save: true,
-            file_format: "mp4".to_string(),
+            file_format: EncodingSettings::default().format.extension().to_string(),
+            encoding: Some(EncodingSettings::default()),
+            channel_mix: Some(ChannelMixPolicy::default()),
+            vad_sensitivity: Some(VadSensitivity::default()),
+            auto_stop: Some(AutoStopSettings::default()),
+            disk_guard: Some(DiskGuardSettings::default()),
+            live_captions: Some(LiveCaptionSettings::default()),
+            transcription_backend: Some(TranscriptionBackend::default()),
             #[cfg(target_os = "macos")]
             system_audio_backend: Some("coreaudio".to_string()),
         }
     }
 }
 
-/// Get the default recordings folder based on platform
+/// Get the default recordings folder based on platform and workspace
 pub fn get_default_recordings_folder() -> PathBuf {
+    // Workspaces other than the default one keep recordings in their own storage folder
+    if let Some(folder) = crate::workspaces::recordings_dir() {
+        return folder;
+    }
+
     #[cfg(target_os = "windows")]
     {
         // Windows: %USERPROFILE%\Music\meetily-recordings
@@ -93,7 +134,7 @@ pub async fn load_recording_preferences<R: Runtime>(
     // Try to load from Tauri store, fallback to defaults
     // For now, return defaults - can be enhanced to use tauri-plugin-store
     #[cfg(target_os = "macos")]
-    let prefs = {
+    let mut prefs = {
         let mut p = RecordingPreferences::default();
         let backend = crate::audio::capture::get_current_backend();
         p.system_audio_backend = Some(backend.to_string());
@@ -101,7 +142,17 @@ pub async fn load_recording_preferences<R: Runtime>(
     };
 
     #[cfg(not(target_os = "macos"))]
-    let prefs = RecordingPreferences::default();
+    let mut prefs = RecordingPreferences::default();
+
+    let encoding = get_encoding_settings();
+    prefs.file_format = encoding.format.extension().to_string();
+    prefs.encoding = Some(encoding);
+    prefs.channel_mix = Some(get_channel_mix_policy());
+    prefs.vad_sensitivity = Some(get_vad_sensitivity());
+    prefs.auto_stop = Some(get_auto_stop_settings());
+    prefs.disk_guard = Some(get_disk_guard_settings());
+    prefs.live_captions = Some(get_live_caption_settings());
+    prefs.transcription_backend = Some(get_backend_preference());
 
     info!("Loaded recording preferences: save_folder={:?}, auto_save={}, format={}",
           prefs.save_folder, prefs.auto_save, prefs.file_format);
@@ -117,10 +168,35 @@ pub async fn save_recording_preferences<R: Runtime>(
     info!("Saving recording preferences: save_folder={:?}, auto_save={}, format={}",
           preferences.save_folder, preferences.auto_save, preferences.file_format);
 
+    // Applies to the next recording; one in progress keeps its format
+    if let Some(encoding) = preferences.encoding {
+        set_encoding_settings(encoding);
+        info!("Recording encoding set to {:?}", get_encoding_settings());
+    }
+    if let Some(policy) = preferences.channel_mix {
+        set_channel_mix_policy(policy);
+    }
+    if let Some(sensitivity) = preferences.vad_sensitivity {
+        set_vad_sensitivity(sensitivity);
+    }
+    if let Some(auto_stop) = preferences.auto_stop {
+        set_auto_stop_settings(auto_stop);
+    }
+    if let Some(disk_guard) = preferences.disk_guard {
+        set_disk_guard_settings(disk_guard);
+    }
+    if let Some(live_captions) = preferences.live_captions {
+        set_live_caption_settings(live_captions);
+    }
+    // Applies the next time a model is loaded
+    if let Some(backend) = preferences.transcription_backend {
+        set_backend_preference(backend);
+    }
+
     // Save backend preference to global config
     #[cfg(target_os = "macos")]
     if let Some(backend_str) = &preferences.system_audio_backend {
-        if let Some(backend) = AudioCaptureBackend::from_string(backend_str) {
+        if let Some(backend) = CaptureBackendType::from_string(backend_str) {
             info!("Setting audio capture backend to: {:?}", backend);
             crate::audio::capture::set_current_backend(backend);
         }
@@ -224,8 +300,9 @@ pub async fn get_available_audio_backends() -> Result<Vec<String>, String> {
 
     #[cfg(not(target_os = "macos"))]
     {
-        // Only ScreenCaptureKit available on non-macOS
-        Ok(vec!["screencapturekit".to_string()])
+        // WASAPI loopback on Windows, ScreenCaptureKit placeholder elsewhere
+        let backends = crate::audio::capture::get_available_backends();
+        Ok(backends.iter().map(|b| b.to_string()).collect())
     }
 }
 
@@ -240,7 +317,7 @@ pub async fn get_current_audio_backend() -> Result<String, String> {
 
     #[cfg(not(target_os = "macos"))]
     {
-        Ok("screencapturekit".to_string())
+        Ok(crate::audio::capture::get_current_backend().to_string())
     }
 }
 
@@ -249,14 +326,14 @@ pub async fn get_current_audio_backend() -> Result<String, String> {
 pub async fn set_audio_backend(backend: String) -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
-        use crate::audio::capture::AudioCaptureBackend;
+        use crate::audio::capture::CaptureBackendType;
         use crate::audio::permissions::{check_screen_recording_permission, request_screen_recording_permission};
 
-        let backend_enum = AudioCaptureBackend::from_string(&backend)
+        let backend_enum = CaptureBackendType::from_string(&backend)
             .ok_or_else(|| format!("Invalid backend: {}", backend))?;
 
         // If switching to Core Audio, log information about Audio Capture permission
-        if backend_enum == AudioCaptureBackend::CoreAudio {
+        if backend_enum == CaptureBackendType::CoreAudio {
             info!("🔐 Core Audio backend requires Audio Capture permission (macOS 14.4+)");
             info!("📍 Permission dialog will appear automatically when recording starts");
 
@@ -272,8 +349,8 @@ pub async fn set_audio_backend(backend: String) -> Result<(), String> {
                 return Err(
                     "Core Audio requires Audio Capture permission. \
                     The permission dialog will appear when you start recording. \
-                    If already denied, enable it in System Settings → Privacy & Security → Audio Capture, \
-                    then restart the app.".to_string()
+                    If already denied, enable it in System Settings → Privacy & Security → Audio Capture; \
+                    recording can start as soon as it is allowed.".to_string()
                 );
             }
 
@@ -287,9 +364,13 @@ pub async fn set_audio_backend(backend: String) -> Result<(), String> {
 
     #[cfg(not(target_os = "macos"))]
     {
-        if backend != "screencapturekit" {
-            return Err(format!("Backend {} not available on this platform", backend));
-        }
+        use crate::audio::capture::CaptureBackendType;
+
+        let backend_enum = CaptureBackendType::from_string(&backend)
+            .ok_or_else(|| format!("Backend {} not available on this platform", backend))?;
+
+        info!("Setting audio backend to: {:?}", backend_enum);
+        crate::audio::capture::set_current_backend(backend_enum);
         Ok(())
     }
 }
@@ -302,28 +383,93 @@ pub struct BackendInfo {
     pub description: String,
 }
 
+/// Backends available for one capture source, with the current selection
+#[derive(Serialize)]
+pub struct SourceBackends {
+    pub source: crate::audio::capture::CaptureSource,
+    pub selected: String,
+    pub backends: Vec<BackendInfo>,
+}
+
+/// List capture backends per source (microphone and system audio)
+#[tauri::command]
+pub async fn list_capture_backends() -> Result<Vec<SourceBackends>, String> {
+    use crate::audio::capture::{get_backend_for, CaptureBackendType, CaptureSource};
+
+    Ok([CaptureSource::Microphone, CaptureSource::System]
+        .into_iter()
+        .map(|source| SourceBackends {
+            source,
+            selected: get_backend_for(source).to_string(),
+            backends: CaptureBackendType::available_for(source)
+                .into_iter()
+                .map(|backend| BackendInfo {
+                    id: backend.to_string(),
+                    name: backend.name().to_string(),
+                    description: backend.description().to_string(),
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// Choose the capture backend for one source; applies to the next recording
+#[tauri::command]
+pub async fn set_capture_backend(source: String, backend: String) -> Result<(), String> {
+    use crate::audio::capture::{set_backend_for, CaptureBackendType, CaptureSource};
+
+    let source_enum = CaptureSource::from_string(&source)
+        .ok_or_else(|| format!("Invalid capture source: {}", source))?;
+    let backend_enum = CaptureBackendType::from_string(&backend)
+        .ok_or_else(|| format!("Invalid backend: {}", backend))?;
+
+    set_backend_for(source_enum, backend_enum).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_audio_backend_info() -> Result<Vec<BackendInfo>, String> {
     #[cfg(target_os = "macos")]
     {
-        use crate::audio::capture::AudioCaptureBackend;
+        use crate::audio::capture::CaptureBackendType;
 
         let backends = vec![
             BackendInfo {
-                id: AudioCaptureBackend::ScreenCaptureKit.to_string(),
-                name: AudioCaptureBackend::ScreenCaptureKit.name().to_string(),
-                description: AudioCaptureBackend::ScreenCaptureKit.description().to_string(),
+                id: CaptureBackendType::ScreenCaptureKit.to_string(),
+                name: CaptureBackendType::ScreenCaptureKit.name().to_string(),
+                description: CaptureBackendType::ScreenCaptureKit.description().to_string(),
             },
             BackendInfo {
-                id: AudioCaptureBackend::CoreAudio.to_string(),
-                name: AudioCaptureBackend::CoreAudio.name().to_string(),
-                description: AudioCaptureBackend::CoreAudio.description().to_string(),
+                id: CaptureBackendType::CoreAudio.to_string(),
+                name: CaptureBackendType::CoreAudio.name().to_string(),
+                description: CaptureBackendType::CoreAudio.description().to_string(),
             },
         ];
         Ok(backends)
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "windows")]
+    {
+        use crate::audio::capture::CaptureBackendType;
+
+        Ok(vec![BackendInfo {
+            id: CaptureBackendType::Wasapi.to_string(),
+            name: CaptureBackendType::Wasapi.name().to_string(),
+            description: CaptureBackendType::Wasapi.description().to_string(),
+        }])
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use crate::audio::capture::CaptureBackendType;
+
+        Ok(vec![BackendInfo {
+            id: CaptureBackendType::PulseMonitor.to_string(),
+            name: CaptureBackendType::PulseMonitor.name().to_string(),
+            description: CaptureBackendType::PulseMonitor.description().to_string(),
+        }])
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     {
         Ok(vec![BackendInfo {
             id: "screencapturekit".to_string(),
@@ -7,10 +7,12 @@ use tokio::sync::mpsc;
 use serde::{Serialize, Deserialize};
 use std::path::PathBuf;
 
+use super::channel_mix::ChannelMixPolicy;
 use super::recording_state::AudioChunk;
 use super::recording_preferences::load_recording_preferences;
 use super::audio_processing::create_meeting_folder;
 use super::incremental_saver::IncrementalAudioSaver;
+use super::transcription::words::TranscriptWord;
 
 /// Structured transcript segment for JSON export
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +25,60 @@ pub struct TranscriptSegment {
     pub display_time: String,   // Formatted time for display like "[02:15]"
     pub confidence: f32,
     pub sequence_id: u64,
+    /// English translation, when translation was enabled for the meeting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+    /// Word timings from recording start, when the engine reports them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<TranscriptWord>,
+}
+
+/// Kind of event marked on the transcript timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkerKind {
+    Paused,
+    Resumed,
+    /// A moment the user flagged as important
+    Highlight,
+    /// Start of a part the user marked confidential; its audio is silence
+    ConfidentialStart,
+    ConfidentialEnd,
+}
+
+/// A point on the transcript timeline: a pause, a resume, a highlight or the bounds of a
+/// confidential part
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineMarker {
+    pub kind: MarkerKind,
+    pub audio_time: f64,        // Seconds into the recorded audio (pauses excluded)
+    pub display_time: String,   // Formatted time for display like "[02:15]"
+    pub wall_time: String,      // RFC 3339 wall-clock time
+    /// The user's note on a highlight
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl TimelineMarker {
+    pub fn new(kind: MarkerKind, audio_time: f64) -> Self {
+        let total = audio_time.max(0.0) as u64;
+        Self {
+            kind,
+            audio_time,
+            display_time: format!("[{:02}:{:02}]", total / 60, total % 60),
+            wall_time: chrono::Utc::now().to_rfc3339(),
+            label: None,
+        }
+    }
+}
+
+/// The markers saved in a meeting folder's transcripts.json
+pub fn load_markers(folder: &std::path::Path) -> Result<Vec<TimelineMarker>> {
+    let json: serde_json::Value = serde_json::from_str(&crate::encryption::files::read_to_string(&folder.join("transcripts.json"))?)?;
+    Ok(match json.get("markers") {
+        Some(markers) => serde_json::from_value(markers.clone())?,
+        None => Vec::new(),
+    })
 }
 
 /// Meeting metadata structure
@@ -38,7 +94,22 @@ pub struct MeetingMetadata {
     pub audio_file: String,
     pub transcript_file: String,
     pub sample_rate: u32,
+    #[serde(default = "default_channels")]
+    pub channels: u16,
+    /// How microphone and system audio were laid out across `channels`
+    #[serde(default)]
+    pub channel_layout: Option<ChannelMixPolicy>,
     pub status: String,  // "recording", "completed", "error"
+    /// Language detected from the opening speech
+    #[serde(default)]
+    pub detected_language: Option<String>,
+    /// Language forced for this meeting
+    #[serde(default)]
+    pub language_override: Option<String>,
+}
+
+fn default_channels() -> u16 {
+    1
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,8 +125,11 @@ pub struct RecordingSaver {
     meeting_name: Option<String>,
     metadata: Option<MeetingMetadata>,
     transcript_segments: Arc<Mutex<Vec<TranscriptSegment>>>,
+    markers: Arc<Mutex<Vec<TimelineMarker>>>,
     chunk_receiver: Option<mpsc::UnboundedReceiver<AudioChunk>>,
     is_saving: Arc<Mutex<bool>>,
+    channels: u16,
+    channel_layout: ChannelMixPolicy,
 }
 
 impl RecordingSaver {
@@ -66,8 +140,11 @@ impl RecordingSaver {
             meeting_name: None,
             metadata: None,
             transcript_segments: Arc::new(Mutex::new(Vec::new())),
+            markers: Arc::new(Mutex::new(Vec::new())),
             chunk_receiver: None,
             is_saving: Arc::new(Mutex::new(false)),
+            channels: 1,
+            channel_layout: ChannelMixPolicy::default(),
         }
     }
 
@@ -76,6 +153,12 @@ impl RecordingSaver {
         self.meeting_name = name;
     }
 
+    /// Channel layout of the saved file (interleaved when stereo); must be set before accumulation starts
+    pub fn set_channel_layout(&mut self, policy: ChannelMixPolicy) {
+        self.channel_layout = policy;
+        self.channels = policy.recording_channels().max(1);
+    }
+
     /// Set device information in metadata
     pub fn set_device_info(&mut self, mic_name: Option<String>, sys_name: Option<String>) {
         if let Some(ref mut metadata) = self.metadata {
@@ -92,13 +175,29 @@ impl RecordingSaver {
         }
     }
 
+    /// Record the meeting's detected and forced language in metadata
+    pub fn set_language_info(&mut self, detected_language: Option<String>, language_override: Option<String>) {
+        if let Some(ref mut metadata) = self.metadata {
+            metadata.detected_language = detected_language;
+            metadata.language_override = language_override;
+
+            if let Some(folder) = &self.meeting_folder {
+                let metadata_clone = metadata.clone();
+                if let Err(e) = self.write_metadata(folder, &metadata_clone) {
+                    warn!("Failed to update metadata with language: {}", e);
+                }
+            }
+        }
+    }
+
     /// Add or update a structured transcript segment (upserts based on sequence_id)
     /// Also saves incrementally to disk
     pub fn add_transcript_segment(&self, segment: TranscriptSegment) {
         if let Ok(mut segments) = self.transcript_segments.lock() {
             // Check if segment with same sequence_id exists (update it)
             if let Some(existing) = segments.iter_mut().find(|s| s.sequence_id == segment.sequence_id) {
-                *existing = segment.clone();
+                let translation = segment.translation.clone().or_else(|| existing.translation.take());
+                *existing = TranscriptSegment { translation, ..segment.clone() };
                 info!("Updated transcript segment {} (seq: {}) - total segments: {}",
                       segment.id, segment.sequence_id, segments.len());
             } else {
@@ -119,6 +218,37 @@ impl RecordingSaver {
         }
     }
 
+    /// Record a timeline marker and save it with the transcript
+    pub fn add_marker(&self, marker: TimelineMarker) {
+        info!("📍 Timeline marker {:?} at {}", marker.kind, marker.display_time);
+        if let Ok(mut markers) = self.markers.lock() {
+            markers.push(marker);
+        }
+
+        if let Some(folder) = &self.meeting_folder {
+            if let Err(e) = self.write_transcripts_json(folder) {
+                warn!("Failed to write transcript markers: {}", e);
+            }
+        }
+    }
+
+    pub fn get_markers(&self) -> Vec<TimelineMarker> {
+        self.markers.lock().map(|m| m.clone()).unwrap_or_default()
+    }
+
+    /// Flush buffered audio to a checkpoint in the background
+    ///
+    /// Called on pause so a crash or long break never loses audio captured before it.
+    pub fn flush_audio(&self) {
+        if let Some(saver) = self.incremental_saver.clone() {
+            tokio::spawn(async move {
+                if let Err(e) = saver.lock().await.flush_checkpoint() {
+                    error!("Failed to flush audio checkpoint: {}", e);
+                }
+            });
+        }
+    }
+
     /// Legacy method for backward compatibility - converts text to basic segment
     pub fn add_transcript_chunk(&self, text: String) {
         let segment = TranscriptSegment {
@@ -130,10 +260,34 @@ impl RecordingSaver {
             display_time: "[00:00]".to_string(),
             confidence: 1.0,
             sequence_id: 0,
+            translation: None,
+            words: Vec::new(),
         };
         self.add_transcript_segment(segment);
     }
 
+    /// Attach a translation to an already saved segment
+    pub fn set_segment_translation(&self, sequence_id: u64, translation: String) {
+        let updated = match self.transcript_segments.lock() {
+            Ok(mut segments) => match segments.iter_mut().find(|s| s.sequence_id == sequence_id) {
+                Some(segment) => {
+                    segment.translation = Some(translation);
+                    true
+                }
+                None => false,
+            },
+            Err(_) => false,
+        };
+
+        if updated {
+            if let Some(folder) = &self.meeting_folder {
+                if let Err(e) = self.write_transcripts_json(folder) {
+                    warn!("Failed to write translated transcript update: {}", e);
+                }
+            }
+        }
+    }
+
     /// Start accumulation with incremental saving
     pub fn start_accumulation(&mut self) -> mpsc::UnboundedSender<AudioChunk> {
         info!("Initializing incremental audio saver for recording");
@@ -205,7 +359,8 @@ impl RecordingSaver {
         let meeting_folder = create_meeting_folder(&base_folder, meeting_name)?;
 
         // Initialize incremental saver
-        let incremental_saver = IncrementalAudioSaver::new(meeting_folder.clone(), 48000)?;
+        let incremental_saver = IncrementalAudioSaver::new(meeting_folder.clone(), 48000, self.channels)?;
+        let audio_file = incremental_saver.audio_file_name();
 
         // Create initial metadata
         let metadata = MeetingMetadata {
@@ -219,10 +374,14 @@ impl RecordingSaver {
                 microphone: None,  // Could be enhanced to store actual device names
                 system_audio: None,
             },
-            audio_file: "audio.mp4".to_string(),
+            audio_file,
             transcript_file: "transcripts.json".to_string(),
             sample_rate: 48000,
+            channels: self.channels,
+            channel_layout: Some(self.channel_layout),
             status: "recording".to_string(),
+            detected_language: None,
+            language_override: crate::audio::transcription::language::get_meeting_language().language_override,
         };
 
         // Write initial metadata.json
@@ -262,10 +421,13 @@ impl RecordingSaver {
         let transcript_path = folder.join("transcripts.json");
         let temp_path = folder.join(".transcripts.json.tmp");
 
+        let markers_clone = self.get_markers();
+
         // Create JSON structure
         let json = serde_json::json!({
             "version": "1.0",
             "segments": segments_clone,
+            "markers": markers_clone,
             "last_updated": chrono::Utc::now().to_rfc3339(),
             "total_segments": segments_clone.len()
         });
@@ -349,7 +511,7 @@ impl RecordingSaver {
             return Ok(None);
         }
 
-        // Finalize incremental saver (merge checkpoints into final audio.mp4)
+        // Finalize incremental saver (merge checkpoints into the final audio file)
         let final_audio_path = if let Some(saver_arc) = &self.incremental_saver {
             let mut saver = saver_arc.lock().await;
             match saver.finalize().await {
@@ -406,6 +568,12 @@ impl RecordingSaver {
             info!("✅ Metadata updated with duration: {:?}s", metadata.duration_seconds);
         }
 
+        if let Some(folder) = &self.meeting_folder {
+            if let Err(e) = crate::encryption::encrypt_meeting_folder(folder) {
+                error!("❌ Failed to encrypt the recording: {}", e);
+            }
+        }
+
         // Emit save event with audio and transcript paths
         let save_event = serde_json::json!({
             "audio_file": final_audio_path.to_string_lossy(),
@@ -6,6 +6,7 @@ use anyhow::Result;
 
 use super::devices::AudioDevice;
 use super::buffer_pool::AudioBufferPool;
+use super::silence_watchdog::RecordingWarning;
 
 /// Device type for audio chunks
 #[derive(Debug, Clone, PartialEq)]
@@ -22,6 +23,9 @@ pub struct AudioChunk {
     pub timestamp: f64,
     pub chunk_id: u64,
     pub device_type: DeviceType,
+    /// Stereo side signal (L - R) / 2 aligned with `data`; only set for stereo system
+    /// audio when the saved file preserves the stereo image
+    pub stereo_side: Option<Vec<f32>>,
 }
 
 /// Processed audio chunk (post-VAD) for recording
@@ -83,6 +87,14 @@ impl AudioError {
     }
 }
 
+impl std::fmt::Display for AudioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.user_message())
+    }
+}
+
+impl std::error::Error for AudioError {}
+
 /// Recording statistics
 #[derive(Debug, Default)]
 pub struct RecordingStats {
@@ -97,6 +109,8 @@ pub struct RecordingState {
     is_recording: AtomicBool,
     is_paused: AtomicBool,
     is_reconnecting: AtomicBool,  // NEW: Attempting to reconnect to device
+    // Audio is replaced with silence while the user marks the meeting confidential
+    is_confidential: AtomicBool,
 
     // Audio devices
     microphone_device: Mutex<Option<Arc<AudioDevice>>>,
@@ -115,6 +129,7 @@ pub struct RecordingState {
     recoverable_error_count: AtomicU32,
     last_error: Mutex<Option<AudioError>>,
     error_callback: Mutex<Option<Box<dyn Fn(&AudioError) + Send + Sync>>>,
+    warning_callback: Mutex<Option<Box<dyn Fn(&RecordingWarning) + Send + Sync>>>,
 
     // Statistics
     stats: Mutex<RecordingStats>,
@@ -124,6 +139,8 @@ pub struct RecordingState {
     // Pause time tracking
     pause_start: Mutex<Option<Instant>>,
     total_pause_duration: Mutex<std::time::Duration>,
+    // Last time the VAD passed speech on to transcription (recording start until then)
+    last_speech: Mutex<Option<Instant>>,
 }
 
 impl RecordingState {
@@ -132,6 +149,7 @@ impl RecordingState {
             is_recording: AtomicBool::new(false),
             is_paused: AtomicBool::new(false),
             is_reconnecting: AtomicBool::new(false),
+            is_confidential: AtomicBool::new(false),
             microphone_device: Mutex::new(None),
             system_device: Mutex::new(None),
             disconnected_device: Mutex::new(None),
@@ -141,26 +159,40 @@ impl RecordingState {
             recoverable_error_count: AtomicU32::new(0),
             last_error: Mutex::new(None),
             error_callback: Mutex::new(None),
+            warning_callback: Mutex::new(None),
             stats: Mutex::new(RecordingStats::default()),
             recording_start: Mutex::new(None),
             pause_start: Mutex::new(None),
             total_pause_duration: Mutex::new(std::time::Duration::ZERO),
+            last_speech: Mutex::new(None),
         })
     }
 
     // Recording control
     pub fn start_recording(&self) -> Result<()> {
         self.is_recording.store(true, Ordering::SeqCst);
+        self.is_confidential.store(false, Ordering::SeqCst);
         *self.recording_start.lock().unwrap() = Some(Instant::now());
+        *self.last_speech.lock().unwrap() = Some(Instant::now());
         self.error_count.store(0, Ordering::SeqCst);
         self.recoverable_error_count.store(0, Ordering::SeqCst);
         *self.last_error.lock().unwrap() = None;
         Ok(())
     }
 
+    /// Move the recording start back so buffered pre-roll audio gets timestamps from 0
+    pub fn backdate_start(&self, by: std::time::Duration) {
+        if let Some(start) = self.recording_start.lock().unwrap().as_mut() {
+            if let Some(earlier) = start.checked_sub(by) {
+                *start = earlier;
+            }
+        }
+    }
+
     pub fn stop_recording(&self) {
         self.is_recording.store(false, Ordering::SeqCst);
         self.is_paused.store(false, Ordering::SeqCst);
+        self.is_confidential.store(false, Ordering::SeqCst);
         // Clear pause tracking when stopping
         *self.pause_start.lock().unwrap() = None;
         // CRITICAL: Clear audio sender to close the pipeline channel
@@ -197,10 +229,22 @@ impl RecordingState {
             log::info!("Recording resumed after pause of {:.2}s", pause_duration.as_secs_f64());
         }
 
+        // Time spent paused doesn't count as silence
+        self.mark_speech();
         self.is_paused.store(false, Ordering::SeqCst);
         Ok(())
     }
 
+    /// Speech was detected just now
+    pub fn mark_speech(&self) {
+        *self.last_speech.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Seconds since speech was last detected (or since the recording started)
+    pub fn seconds_since_speech(&self) -> Option<f64> {
+        self.last_speech.lock().unwrap().map(|t| t.elapsed().as_secs_f64())
+    }
+
     pub fn is_recording(&self) -> bool {
         self.is_recording.load(Ordering::SeqCst)
     }
@@ -213,6 +257,26 @@ impl RecordingState {
         self.is_recording() && !self.is_paused()
     }
 
+    /// Start or end a confidential stretch; fails if it's already started or ended
+    pub fn set_confidential(&self, confidential: bool) -> Result<()> {
+        if !self.is_recording() {
+            return Err(anyhow::anyhow!("No recording is currently active"));
+        }
+        if self.is_confidential.swap(confidential, Ordering::SeqCst) == confidential {
+            return Err(anyhow::anyhow!(if confidential {
+                "A confidential part is already being marked"
+            } else {
+                "No confidential part is being marked"
+            }));
+        }
+        log::info!("Confidential part {}", if confidential { "started" } else { "ended" });
+        Ok(())
+    }
+
+    pub fn is_confidential(&self) -> bool {
+        self.is_confidential.load(Ordering::SeqCst)
+    }
+
     // Reconnection state management
     pub fn start_reconnecting(&self, device: Arc<AudioDevice>, device_type: DeviceType) {
         self.is_reconnecting.store(true, Ordering::SeqCst);
@@ -284,6 +348,21 @@ impl RecordingState {
         *self.error_callback.lock().unwrap() = Some(Box::new(callback));
     }
 
+    pub fn set_warning_callback<F>(&self, callback: F)
+    where
+        F: Fn(&RecordingWarning) + Send + Sync + 'static,
+    {
+        *self.warning_callback.lock().unwrap() = Some(Box::new(callback));
+    }
+
+    /// Report a non-fatal condition the user should know about (e.g. no input signal)
+    pub fn report_warning(&self, warning: RecordingWarning) {
+        log::warn!("⚠️ Recording warning for '{}': {:?}", warning.device_name, warning.reason);
+        if let Some(callback) = self.warning_callback.lock().unwrap().as_ref() {
+            callback(&warning);
+        }
+    }
+
     pub fn report_error(&self, error: AudioError) {
         let count = self.error_count.fetch_add(1, Ordering::SeqCst) + 1;
 
@@ -396,6 +475,7 @@ impl RecordingState {
         *self.audio_sender.lock().unwrap() = None;
         *self.last_error.lock().unwrap() = None;
         *self.error_callback.lock().unwrap() = None;
+        *self.warning_callback.lock().unwrap() = None;
         *self.stats.lock().unwrap() = RecordingStats::default();
         *self.recording_start.lock().unwrap() = None;
         *self.pause_start.lock().unwrap() = None;
@@ -414,6 +494,7 @@ impl Default for RecordingState {
             is_recording: AtomicBool::new(false),
             is_paused: AtomicBool::new(false),
             is_reconnecting: AtomicBool::new(false),
+            is_confidential: AtomicBool::new(false),
             microphone_device: Mutex::new(None),
             system_device: Mutex::new(None),
             disconnected_device: Mutex::new(None),
@@ -423,10 +504,12 @@ impl Default for RecordingState {
             recoverable_error_count: AtomicU32::new(0),
             last_error: Mutex::new(None),
             error_callback: Mutex::new(None),
+            warning_callback: Mutex::new(None),
             stats: Mutex::new(RecordingStats::default()),
             recording_start: Mutex::new(None),
             pause_start: Mutex::new(None),
             total_pause_duration: Mutex::new(std::time::Duration::ZERO),
+            last_speech: Mutex::new(None),
         }
     }
 }
@@ -440,4 +523,63 @@ impl Clone for RecordingStats {
             last_activity: self.last_activity,
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Moves the start of the current pause back, as if it began `by` ago
+    fn backdate_pause(state: &RecordingState, by: Duration) {
+        let mut pause_start = state.pause_start.lock().unwrap();
+        *pause_start = pause_start.and_then(|start| start.checked_sub(by));
+    }
+
+    #[test]
+    fn test_pause_resume_stop() {
+        let state = RecordingState::new();
+        assert!(state.pause_recording().is_err());
+
+        state.start_recording().unwrap();
+        assert!(state.resume_recording().is_err());
+        state.pause_recording().unwrap();
+        assert!(state.is_recording() && state.is_paused() && !state.is_active());
+        assert!(state.pause_recording().is_err());
+        assert!(state.get_current_pause_duration().is_some());
+
+        state.resume_recording().unwrap();
+        assert!(state.is_active());
+        assert!(state.get_current_pause_duration().is_none());
+
+        state.stop_recording();
+        assert!(!state.is_recording() && !state.is_paused());
+        assert!(state.pause_recording().is_err());
+        assert!(state.resume_recording().is_err());
+    }
+
+    #[test]
+    fn test_active_duration_excludes_pauses() {
+        let state = RecordingState::new();
+        state.start_recording().unwrap();
+        state.backdate_start(Duration::from_secs(10));
+
+        state.pause_recording().unwrap();
+        backdate_pause(&state, Duration::from_secs(4));
+        // Doesn't grow while paused
+        let paused = state.get_active_recording_duration().unwrap();
+        assert!((paused - 6.0).abs() < 0.5, "active while paused: {}", paused);
+
+        state.resume_recording().unwrap();
+        assert!((state.get_total_pause_duration() - 4.0).abs() < 0.5);
+        let active = state.get_active_recording_duration().unwrap();
+        assert!((active - 6.0).abs() < 0.5, "active after resuming: {}", active);
+        assert!((state.get_recording_duration().unwrap() - 10.0).abs() < 0.5);
+
+        state.pause_recording().unwrap();
+        backdate_pause(&state, Duration::from_secs(2));
+        state.resume_recording().unwrap();
+        assert!((state.get_total_pause_duration() - 6.0).abs() < 0.5);
+        let active = state.get_active_recording_duration().unwrap();
+        assert!((active - 4.0).abs() < 0.5, "active after two pauses: {}", active);
+    }
+}
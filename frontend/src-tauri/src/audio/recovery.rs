@@ -0,0 +1,353 @@
+// Recovery of recordings interrupted by a crash, force quit or sleep
+//
+// On launch every meeting folder with an unfinalized write-ahead log is salvaged: the
+// pending PCM chunks (including a torn last chunk the manifest never saw) are rebuilt
+// into a valid WAV next to the log, so nothing else can be lost. The frontend lists the
+// sessions with `list_orphaned_recordings` when it mounts (the scan may finish before it
+// listens; `recording-recovery-available` only tells it to list them again) and can then
+// recover (merge checkpoints + salvaged audio into the final audio file, save it as a
+// meeting and queue its transcription) or dismiss each session.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Result};
+use log::{error, info, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use super::encode::{get_encoding_settings, AudioFormat, EncodingSettings};
+use super::incremental_saver::IncrementalAudioSaver;
+use super::recording_state::{AudioChunk, DeviceType};
+use super::write_ahead::{find_orphaned_sessions, read_chunk, OrphanedSession, WalChunk, WalManifest, WAL_DIR};
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::jobs::{self, JobPayload, JobPriority};
+use crate::state::AppState;
+
+/// Event emitted at startup when interrupted recordings were found
+pub const RECOVERY_AVAILABLE_EVENT: &str = "recording-recovery-available";
+
+/// Rebuilt audio for the part of a session not covered by checkpoints
+const SALVAGED_FILE: &str = "salvaged.wav";
+
+/// An interrupted recording ready to be recovered
+#[derive(Debug, Clone, Serialize)]
+pub struct RecoverableRecording {
+    #[serde(flatten)]
+    pub session: OrphanedSession,
+    /// Local time the recording started, e.g. "14:02"
+    pub started_at_local: String,
+    /// Approximate recovered length in seconds
+    pub recoverable_seconds: f64,
+}
+
+/// A recovered recording, saved as a meeting whose transcription is queued
+#[derive(Debug, Clone, Serialize)]
+pub struct RecoveredMeeting {
+    pub meeting_id: String,
+    pub audio_path: String,
+    pub job_id: String,
+}
+
+/// Scan for interrupted recordings, salvage them and notify the frontend
+pub fn scan_on_startup<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let recordings_folder = super::recording_preferences::get_default_recordings_folder();
+        let recordings = tauri::async_runtime::spawn_blocking(move || scan_recordings_folder(&recordings_folder))
+            .await
+            .unwrap_or_default();
+
+        if recordings.is_empty() {
+            return;
+        }
+
+        info!("🩹 Found {} interrupted recording(s)", recordings.len());
+        if let Err(e) = app.emit(RECOVERY_AVAILABLE_EVENT, &recordings) {
+            error!("Failed to emit {}: {}", RECOVERY_AVAILABLE_EVENT, e);
+        }
+    });
+}
+
+/// Find and salvage every interrupted recording under `recordings_folder`
+pub fn scan_recordings_folder(recordings_folder: &Path) -> Vec<RecoverableRecording> {
+    find_orphaned_sessions(recordings_folder)
+        .into_iter()
+        .filter_map(|session| match salvage_session(&session.meeting_folder) {
+            Ok(salvaged_seconds) => {
+                let checkpoint_seconds = session.committed_checkpoints as f64 * 30.0;
+                Some(RecoverableRecording {
+                    started_at_local: chrono::DateTime::parse_from_rfc3339(&session.started_at)
+                        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M").to_string())
+                        .unwrap_or_default(),
+                    recoverable_seconds: checkpoint_seconds + salvaged_seconds,
+                    session,
+                })
+            }
+            Err(e) => {
+                warn!("⚠️ Could not salvage {}: {}", session.meeting_folder.display(), e);
+                None
+            }
+        })
+        .filter(|r| r.recoverable_seconds > 0.0)
+        .collect()
+}
+
+/// Rebuild the pending PCM chunks of a session into `.wal/salvaged.wav`
+///
+/// Returns the salvaged duration in seconds. Safe to run repeatedly.
+pub fn salvage_session(meeting_folder: &Path) -> Result<f64> {
+    let wal_dir = meeting_folder.join(WAL_DIR);
+    let manifest = WalManifest::load(&wal_dir)?;
+
+    let samples = collect_pending_samples(&wal_dir, &manifest)?;
+    if samples.is_empty() {
+        return Ok(0.0);
+    }
+
+    write_wav_f32(&wal_dir.join(SALVAGED_FILE), &samples, manifest.sample_rate, manifest.channels)?;
+    let seconds = samples.len() as f64 / (manifest.sample_rate.max(1) as f64 * manifest.channels.max(1) as f64);
+    info!("🩹 Salvaged {:.1}s of audio for {}", seconds, meeting_folder.display());
+    Ok(seconds)
+}
+
+/// Listed chunks in order, then any chunk files written after the last manifest update;
+/// chunks a committed checkpoint covers are left out
+fn collect_pending_samples(wal_dir: &Path, manifest: &WalManifest) -> Result<Vec<f32>> {
+    let mut chunks = manifest.chunks.clone();
+    let last_index = chunks.iter().map(|c| c.index).chain(manifest.last_committed_chunk).max();
+
+    let mut unlisted: Vec<WalChunk> = fs::read_dir(wal_dir)?
+        .flatten()
+        .filter_map(|entry| {
+            let file = entry.file_name().to_string_lossy().to_string();
+            let index = file.strip_prefix("chunk_")?.strip_suffix(".pcm")?.parse::<u64>().ok()?;
+            let newer = last_index.map_or(true, |last| index > last);
+            newer.then_some(WalChunk { index, file, samples: 0 })
+        })
+        .collect();
+    unlisted.sort_by_key(|c| c.index);
+    chunks.extend(unlisted);
+
+    let mut samples = Vec::new();
+    for chunk in &chunks {
+        match read_chunk(wal_dir, chunk) {
+            Ok(data) => samples.extend(data),
+            Err(e) => warn!("⚠️ Skipping unreadable chunk {}: {}", chunk.file, e),
+        }
+    }
+    Ok(samples)
+}
+
+/// Merge checkpoints and salvaged audio into the final audio file and close the session
+pub async fn recover_session(meeting_folder: &Path) -> Result<PathBuf> {
+    let wal_dir = meeting_folder.join(WAL_DIR);
+    let manifest = WalManifest::load(&wal_dir)?;
+    if manifest.finalized {
+        return Err(anyhow!("Recording was already finalized"));
+    }
+
+    // Make sure the salvaged WAV reflects everything on disk
+    salvage_session(meeting_folder)?;
+    let salvaged = read_wav_f32(&wal_dir.join(SALVAGED_FILE)).unwrap_or_default();
+
+    let mut saver = IncrementalAudioSaver::resume_for_recovery(
+        meeting_folder.to_path_buf(),
+        manifest.sample_rate,
+        manifest.channels,
+        manifest.committed_checkpoints,
+        checkpoint_encoding(meeting_folder),
+    )?;
+    if !salvaged.is_empty() {
+        saver.add_chunk(AudioChunk {
+            data: salvaged,
+            sample_rate: manifest.sample_rate,
+            timestamp: 0.0,
+            chunk_id: 0,
+            device_type: DeviceType::Microphone,
+            stereo_side: None,
+        })?;
+    }
+
+    let audio_path = saver.finalize().await?;
+    update_metadata(meeting_folder, |metadata| {
+        metadata["status"] = serde_json::Value::String("recovered".to_string());
+        metadata["completed_at"] = serde_json::Value::String(chrono::Utc::now().to_rfc3339());
+        if let Some(file_name) = audio_path.file_name() {
+            metadata["audio_file"] = serde_json::Value::String(file_name.to_string_lossy().to_string());
+        }
+    });
+    fs::remove_dir_all(&wal_dir)?;
+
+    info!("✅ Recovered interrupted recording: {}", audio_path.display());
+    Ok(audio_path)
+}
+
+/// Stop offering a session for recovery without touching its audio
+pub fn dismiss_session(meeting_folder: &Path) -> Result<()> {
+    let wal_dir = meeting_folder.join(WAL_DIR);
+    let mut manifest = WalManifest::load(&wal_dir)?;
+    manifest.finalized = true;
+    manifest.save(&wal_dir)?;
+    info!("Dismissed recovery for {}", meeting_folder.display());
+    Ok(())
+}
+
+/// Encoding the existing checkpoints were written with, so the merge can stream-copy them
+fn checkpoint_encoding(meeting_folder: &Path) -> EncodingSettings {
+    let first_checkpoint = fs::read_dir(meeting_folder.join(".checkpoints"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.file_stem().is_some_and(|stem| stem.to_string_lossy().starts_with("audio_chunk_")));
+
+    first_checkpoint
+        .and_then(|path| path.extension().and_then(|ext| AudioFormat::from_string(&ext.to_string_lossy())))
+        .map(EncodingSettings::for_format)
+        .unwrap_or_else(get_encoding_settings)
+}
+
+fn update_metadata(meeting_folder: &Path, update: impl FnOnce(&mut serde_json::Value)) {
+    let path = meeting_folder.join("metadata.json");
+    let Ok(content) = fs::read_to_string(&path) else {
+        return;
+    };
+    let Ok(mut metadata) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return;
+    };
+    update(&mut metadata);
+    if let Ok(json) = serde_json::to_string_pretty(&metadata) {
+        if let Err(e) = fs::write(&path, json) {
+            warn!("Failed to update metadata for recovered recording: {}", e);
+        }
+    }
+}
+
+/// Write interleaved 32-bit float PCM as a WAV file
+fn write_wav_f32(path: &Path, samples: &[f32], sample_rate: u32, channels: u16) -> Result<()> {
+    let data_len = (samples.len() * 4) as u32;
+    let block_align = 4 * channels.max(1);
+    let mut file = fs::File::create(path)?;
+
+    file.write_all(b"RIFF")?;
+    file.write_all(&(36 + data_len).to_le_bytes())?;
+    file.write_all(b"WAVE")?;
+    file.write_all(b"fmt ")?;
+    file.write_all(&16u32.to_le_bytes())?;
+    file.write_all(&3u16.to_le_bytes())?; // IEEE float
+    file.write_all(&channels.max(1).to_le_bytes())?;
+    file.write_all(&sample_rate.to_le_bytes())?;
+    file.write_all(&(sample_rate * block_align as u32).to_le_bytes())?; // byte rate
+    file.write_all(&block_align.to_le_bytes())?;
+    file.write_all(&32u16.to_le_bytes())?; // bits per sample
+    file.write_all(b"data")?;
+    file.write_all(&data_len.to_le_bytes())?;
+    file.write_all(bytemuck::cast_slice(samples))?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Read back a WAV written by `write_wav_f32`
+fn read_wav_f32(path: &Path) -> Result<Vec<f32>> {
+    let bytes = fs::read(path)?;
+    if bytes.len() < 44 || &bytes[0..4] != b"RIFF" || &bytes[36..40] != b"data" {
+        return Err(anyhow!("Not a salvaged WAV file: {}", path.display()));
+    }
+    Ok(bytes[44..]
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
+
+/// Recover the audio, save it as a meeting and queue its transcription
+#[tauri::command]
+pub async fn recover_interrupted_recording<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_folder: String,
+) -> Result<RecoveredMeeting, String> {
+    let folder = Path::new(&meeting_folder);
+    let manifest = WalManifest::load(&folder.join(WAL_DIR)).map_err(|e| format!("Failed to recover recording: {}", e))?;
+    let audio_path = recover_session(folder).await.map_err(|e| format!("Failed to recover recording: {}", e))?;
+
+    let pool = state.db_manager.pool();
+    let title = manifest.meeting_name.unwrap_or_else(|| "Recovered recording".to_string());
+    let meeting_id = TranscriptsRepository::save_transcript(pool, &title, &[], Some(meeting_folder.clone()))
+        .await
+        .map_err(|e| format!("Failed to save recovered meeting: {}", e))?;
+    update_metadata(folder, |metadata| metadata["meeting_id"] = serde_json::Value::String(meeting_id.clone()));
+
+    let audio_path = audio_path.to_string_lossy().to_string();
+    let payload = JobPayload::RecoveredRecording { meeting_id: meeting_id.clone(), path: audio_path.clone() };
+    let job = jobs::enqueue(&app, pool, payload, JobPriority::Normal)
+        .await
+        .map_err(|e| format!("Failed to queue transcription of the recovered recording: {}", e))?;
+
+    info!("🩹 Saved recovered recording as meeting {}", meeting_id);
+    Ok(RecoveredMeeting { meeting_id, audio_path, job_id: job.id })
+}
+
+#[tauri::command]
+pub async fn dismiss_interrupted_recording(meeting_folder: String) -> Result<(), String> {
+    dismiss_session(Path::new(&meeting_folder))
+        .map_err(|e| format!("Failed to dismiss recording: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::write_ahead::WriteAheadLog;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_wav_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("test.wav");
+        write_wav_f32(&path, &[0.5, -0.25, 0.0], 48000, 1).unwrap();
+        assert_eq!(read_wav_f32(&path).unwrap(), vec![0.5, -0.25, 0.0]);
+    }
+
+    #[test]
+    fn test_salvage_includes_unsealed_chunk() {
+        let temp_dir = tempdir().unwrap();
+        let mut wal = WriteAheadLog::create(temp_dir.path(), None, 100, 1).unwrap();
+        wal.append(&vec![0.1f32; 650]).unwrap();
+        drop(wal); // 500 samples sealed, 150 only in the open chunk
+
+        let seconds = salvage_session(temp_dir.path()).unwrap();
+        assert!((seconds - 6.5).abs() < 1e-9);
+        assert!(temp_dir.path().join(WAL_DIR).join(SALVAGED_FILE).exists());
+    }
+
+    #[test]
+    fn test_salvage_skips_chunks_covered_by_a_checkpoint() {
+        let temp_dir = tempdir().unwrap();
+        let wal_dir = temp_dir.path().join(WAL_DIR);
+        let mut wal = WriteAheadLog::create(temp_dir.path(), None, 100, 1).unwrap();
+        wal.append(&vec![0.1f32; 700]).unwrap();
+        wal.commit_checkpoint(1).unwrap();
+        drop(wal);
+
+        // A chunk whose deletion failed after the checkpoint covered it
+        fs::write(wal_dir.join("chunk_00001.pcm"), bytemuck::cast_slice(&[0.1f32; 200])).unwrap();
+        let manifest = WalManifest::load(&wal_dir).unwrap();
+        assert!(manifest.chunks.is_empty());
+        assert!(collect_pending_samples(&wal_dir, &manifest).unwrap().is_empty());
+        assert_eq!(salvage_session(temp_dir.path()).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_dismissed_session_is_no_longer_orphaned() {
+        let recordings = tempdir().unwrap();
+        let folder = recordings.path().join("Interrupted");
+        fs::create_dir_all(&folder).unwrap();
+        let mut wal = WriteAheadLog::create(&folder, None, 100, 1).unwrap();
+        wal.append(&vec![0.1f32; 500]).unwrap();
+        drop(wal);
+
+        assert_eq!(scan_recordings_folder(recordings.path()).len(), 1);
+        dismiss_session(&folder).unwrap();
+        assert!(scan_recordings_folder(recordings.path()).is_empty());
+        assert!(!folder.join(WAL_DIR).join(".manifest.json.tmp").exists());
+    }
+}
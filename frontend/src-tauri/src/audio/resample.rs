@@ -0,0 +1,282 @@
+// Sample rate conversion
+//
+// Devices deliver 8k/16k/44.1k/48k/96k audio; the pipeline mixes at 48kHz and the VAD and
+// speech models want 16kHz. All conversions go through rubato's windowed-sinc resampler
+// with parameters picked from the conversion ratio:
+//
+// - `resample` converts a complete buffer in one call
+// - `StreamingResampler` converts a live stream delivered in arbitrary block sizes while
+//   keeping filter state between blocks, so there are no seams, gain jumps or drift
+//
+// The resampling path each stage actually uses is recorded for diagnostics.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use anyhow::Result;
+use log::{debug, info};
+use once_cell::sync::Lazy;
+use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction};
+use serde::Serialize;
+
+/// Input frames per rubato call for streaming conversion
+const STREAM_CHUNK_SIZE: usize = 512;
+
+/// Description of one conversion, for diagnostics
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResamplePath {
+    pub from_rate: u32,
+    pub to_rate: u32,
+    pub ratio: f64,
+    /// "passthrough" or the sinc quality tier used
+    pub method: String,
+    /// Latency added by the filter, in output samples
+    pub delay_samples: usize,
+}
+
+impl ResamplePath {
+    fn passthrough(rate: u32) -> Self {
+        Self { from_rate: rate, to_rate: rate, ratio: 1.0, method: "passthrough".to_string(), delay_samples: 0 }
+    }
+}
+
+/// Sinc parameters for a conversion ratio (output rate / input rate)
+fn sinc_parameters(ratio: f64) -> (SincInterpolationParameters, &'static str) {
+    let (sinc_len, interpolation, oversampling_factor, tier) = if ratio >= 2.0 {
+        // Large upsampling (e.g. 8kHz → 16kHz, 16kHz → 48kHz): long sinc to avoid artifacts
+        (512, SincInterpolationType::Cubic, 512, "high-quality upsampling")
+    } else if ratio >= 1.5 {
+        // Moderate upsampling (e.g. 32kHz → 48kHz)
+        (384, SincInterpolationType::Cubic, 384, "moderate upsampling")
+    } else if ratio > 1.0 {
+        // Small upsampling (e.g. 44.1kHz → 48kHz)
+        (256, SincInterpolationType::Linear, 256, "small upsampling")
+    } else if ratio <= 0.5 {
+        // Large downsampling (e.g. 48kHz → 16kHz): strong anti-aliasing
+        (512, SincInterpolationType::Cubic, 512, "anti-aliased downsampling")
+    } else {
+        // Moderate downsampling (e.g. 48kHz → 32kHz)
+        (384, SincInterpolationType::Linear, 384, "moderate downsampling")
+    };
+
+    let params = SincInterpolationParameters {
+        sinc_len,
+        f_cutoff: 0.95, // Preserve most of the frequency content
+        interpolation,
+        oversampling_factor,
+        window: WindowFunction::BlackmanHarris2, // Best window for audio
+    };
+    (params, tier)
+}
+
+/// Convert a complete mono buffer
+pub fn resample(input: &[f32], from_rate: u32, to_rate: u32) -> Result<Vec<f32>> {
+    if input.is_empty() {
+        return Ok(Vec::new());
+    }
+    if from_rate == to_rate {
+        return Ok(input.to_vec());
+    }
+
+    let ratio = to_rate as f64 / from_rate as f64;
+    let (params, tier) = sinc_parameters(ratio);
+    debug!("Resampling {} samples: {}Hz → {}Hz ({})", input.len(), from_rate, to_rate, tier);
+
+    let mut resampler = SincFixedIn::<f32>::new(ratio, 2.0, params, input.len(), 1)?;
+    let waves_out = resampler.process(&[input], None)?;
+    Ok(waves_out.into_iter().next().unwrap_or_default())
+}
+
+/// Stateful mono resampler for audio arriving in arbitrary block sizes
+pub struct StreamingResampler {
+    inner: Option<SincFixedIn<f32>>,
+    path: ResamplePath,
+    pending: Vec<f32>,
+    samples_in: u64,
+    samples_out: u64,
+}
+
+impl StreamingResampler {
+    pub fn new(from_rate: u32, to_rate: u32) -> Result<Self> {
+        if from_rate == to_rate {
+            return Ok(Self {
+                inner: None,
+                path: ResamplePath::passthrough(from_rate),
+                pending: Vec::new(),
+                samples_in: 0,
+                samples_out: 0,
+            });
+        }
+
+        let ratio = to_rate as f64 / from_rate as f64;
+        let (params, tier) = sinc_parameters(ratio);
+        let inner = SincFixedIn::<f32>::new(ratio, 2.0, params, STREAM_CHUNK_SIZE, 1)?;
+        let path = ResamplePath {
+            from_rate,
+            to_rate,
+            ratio,
+            method: format!("sinc ({})", tier),
+            delay_samples: inner.output_delay(),
+        };
+
+        Ok(Self {
+            inner: Some(inner),
+            path,
+            pending: Vec::with_capacity(STREAM_CHUNK_SIZE * 2),
+            samples_in: 0,
+            samples_out: 0,
+        })
+    }
+
+    /// Convert the next block; input is buffered until a full chunk is available
+    pub fn process(&mut self, samples: &[f32]) -> Result<Vec<f32>> {
+        self.samples_in += samples.len() as u64;
+        let Some(inner) = self.inner.as_mut() else {
+            self.samples_out += samples.len() as u64;
+            return Ok(samples.to_vec());
+        };
+
+        self.pending.extend_from_slice(samples);
+        let mut output = Vec::with_capacity((samples.len() as f64 * self.path.ratio) as usize + 16);
+        let mut consumed = 0;
+        while self.pending.len() - consumed >= STREAM_CHUNK_SIZE {
+            let chunk = &self.pending[consumed..consumed + STREAM_CHUNK_SIZE];
+            let mut waves_out = inner.process(&[chunk], None)?;
+            output.append(&mut waves_out[0]);
+            consumed += STREAM_CHUNK_SIZE;
+        }
+        self.pending.drain(..consumed);
+
+        self.samples_out += output.len() as u64;
+        Ok(output)
+    }
+
+    /// Convert whatever is still buffered, including the filter's tail
+    pub fn flush(&mut self) -> Result<Vec<f32>> {
+        let Some(inner) = self.inner.as_mut() else {
+            return Ok(Vec::new());
+        };
+
+        let mut output = Vec::new();
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            output.append(&mut inner.process_partial(Some(&[pending]), None)?[0]);
+        }
+        output.append(&mut inner.process_partial::<Vec<f32>>(None, None)?[0]);
+
+        self.samples_out += output.len() as u64;
+        Ok(output)
+    }
+
+    pub fn path(&self) -> &ResamplePath {
+        &self.path
+    }
+
+    pub fn is_passthrough(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Input samples waiting for a full chunk
+    pub fn buffered_samples(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Output produced minus output expected for the input consumed so far
+    ///
+    /// Stays within a sample or two for a healthy stream; a growing value means the
+    /// timeline is drifting.
+    pub fn drift_samples(&self) -> f64 {
+        let consumed = self.samples_in - self.pending.len() as u64;
+        self.samples_out as f64 - consumed as f64 * self.path.ratio
+    }
+}
+
+/// Resampling path used by each pipeline stage, keyed by stage name
+static ACTIVE_PATHS: Lazy<Mutex<BTreeMap<String, ResamplePath>>> = Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+/// Record the path a stage uses and log it
+pub fn register_path(stage: &str, path: &ResamplePath) {
+    super::diagnostics::log_resample_path(stage, path);
+    ACTIVE_PATHS.lock().unwrap().insert(stage.to_string(), path.clone());
+}
+
+/// Recording stopped; no conversion is active any more
+pub fn clear_paths() {
+    ACTIVE_PATHS.lock().unwrap().clear();
+}
+
+/// Stage name and resampling path currently in use
+#[derive(Debug, Clone, Serialize)]
+pub struct StageResamplePath {
+    pub stage: String,
+    #[serde(flatten)]
+    pub path: ResamplePath,
+}
+
+#[tauri::command]
+pub async fn get_resampling_diagnostics() -> Result<Vec<StageResamplePath>, String> {
+    let paths = ACTIVE_PATHS.lock().unwrap();
+    info!("Reporting {} active resampling path(s)", paths.len());
+    Ok(paths
+        .iter()
+        .map(|(stage, path)| StageResamplePath { stage: stage.clone(), path: path.clone() })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frequency: f32, rate: u32, seconds: f32) -> Vec<f32> {
+        (0..(rate as f32 * seconds) as usize)
+            .map(|i| 0.5 * (2.0 * std::f32::consts::PI * frequency * i as f32 / rate as f32).sin())
+            .collect()
+    }
+
+    fn zero_crossings(samples: &[f32]) -> usize {
+        samples.windows(2).filter(|w| (w[0] < 0.0) != (w[1] < 0.0)).count()
+    }
+
+    #[test]
+    fn test_streaming_has_no_drift_with_irregular_blocks() {
+        for (from, to) in [(44100, 16000), (48000, 16000), (96000, 48000), (16000, 48000), (44100, 48000)] {
+            let input = sine(440.0, from, 10.0);
+            let mut resampler = StreamingResampler::new(from, to).unwrap();
+
+            let mut output = Vec::new();
+            let mut offset = 0;
+            for size in [1, 333, 480, 1024, 7, 4096].iter().cycle() {
+                if offset >= input.len() {
+                    break;
+                }
+                let end = (offset + size).min(input.len());
+                output.extend(resampler.process(&input[offset..end]).unwrap());
+                assert!(resampler.drift_samples().abs() < 2.0, "{}→{} drifted", from, to);
+                offset = end;
+            }
+            output.extend(resampler.flush().unwrap());
+
+            // Everything in comes out, plus the filter delay
+            let expected = input.len() as f64 * to as f64 / from as f64;
+            let produced = output.len() as f64 - resampler.path().delay_samples as f64;
+            assert!((produced - expected).abs() <= 2.0 + to as f64 / from as f64 * STREAM_CHUNK_SIZE as f64,
+                    "{}→{}: produced {} expected {}", from, to, produced, expected);
+        }
+    }
+
+    #[test]
+    fn test_preserves_frequency() {
+        let input = sine(1000.0, 48000, 2.0);
+        let output = resample(&input, 48000, 16000).unwrap();
+        // 1kHz has 2000 zero crossings per second
+        let crossings_per_second = zero_crossings(&output) as f64 / (output.len() as f64 / 16000.0);
+        assert!((crossings_per_second - 2000.0).abs() < 20.0);
+    }
+
+    #[test]
+    fn test_passthrough() {
+        let mut resampler = StreamingResampler::new(16000, 16000).unwrap();
+        assert!(resampler.is_passthrough());
+        assert_eq!(resampler.process(&[0.1, 0.2]).unwrap(), vec![0.1, 0.2]);
+        assert_eq!(resampler.path().method, "passthrough");
+    }
+}
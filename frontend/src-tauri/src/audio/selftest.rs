@@ -0,0 +1,476 @@
+// Audio → transcript pipeline self-test
+//
+// Runs each stage of the recording pipeline on known input and reports which ones work, so
+// support can tell a broken machine from a broken meeting. The offline checks use a test tone
+// generated here: level metering, resampling to 16kHz, and the VAD on silence. Then the
+// default microphone is opened while the tone plays on the default output, and the user reads
+// `SELFTEST_PHRASE` aloud; that capture goes through the VAD and the configured transcription
+// engine, and the transcript is compared with the phrase.
+
+use std::f32::consts::PI;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample, StreamConfig};
+use log::{error, info, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use super::capture::{create_capture_backend, get_backend_for, CaptureLevels, CaptureSource};
+use super::devices::default_input_device;
+use super::resample::resample;
+use super::session::{self, SessionPhase};
+use super::transcription::engine::{get_or_init_transcription_engine, validate_transcription_model_ready};
+use super::vad::extract_speech_16k;
+use crate::error::{AppError, ErrorCode};
+
+/// Emitted with the `SelfTestStage` being run, so the UI can ask the user to speak
+pub const SELFTEST_EVENT: &str = "pipeline-selftest";
+
+/// What the user reads aloud during the speech capture
+pub const SELFTEST_PHRASE: &str = "The quick brown fox jumps over the lazy dog";
+
+const TONE_HZ: f32 = 1000.0;
+/// Peak amplitude; a sine's RMS is 1/√2 of it, about -15 dBFS
+const TONE_AMPLITUDE: f32 = 0.25;
+const TONE_DURATION: Duration = Duration::from_millis(1500);
+const SPEECH_DURATION: Duration = Duration::from_secs(5);
+const PIPELINE_RATE: u32 = 48000;
+const SPEECH_RATE: u32 = 16000;
+
+/// Peak below which the microphone delivers digital silence
+const SILENT_PEAK: f32 = 0.0001;
+/// RMS below which speech is likely too quiet to transcribe
+const QUIET_DBFS: f32 = -55.0;
+/// Share of the captured energy at the tone frequency for the tone to count as heard
+const TONE_HEARD_RATIO: f32 = 0.2;
+/// Share of the phrase's words the transcript must contain
+const MIN_WORD_MATCH: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTestStage {
+    Offline,
+    /// Playing the tone while capturing
+    Tone,
+    /// Capturing while the user reads the phrase
+    Speech,
+    Transcription,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but something may get in the way of good transcripts
+    Warn,
+    Fail,
+    /// Not run because an earlier check failed
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl SelfTestCheck {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name, status, detail: detail.into() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    /// No check failed
+    pub passed: bool,
+    pub checks: Vec<SelfTestCheck>,
+    pub input_device: Option<String>,
+    pub phrase: &'static str,
+    pub transcript: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// `duration` of a sine at `freq`, sampled at `rate`
+fn tone(freq: f32, rate: u32, duration: Duration, amplitude: f32) -> Vec<f32> {
+    let len = (rate as f64 * duration.as_secs_f64()) as usize;
+    (0..len).map(|i| amplitude * (2.0 * PI * freq * i as f32 / rate as f32).sin()).collect()
+}
+
+/// Share of the signal's energy at `freq` (Goertzel); 1.0 for a pure tone at that frequency
+fn tone_ratio(samples: &[f32], rate: u32, freq: f32) -> f32 {
+    let energy: f64 = samples.iter().map(|&x| (x as f64) * (x as f64)).sum();
+    if samples.is_empty() || energy == 0.0 {
+        return 0.0;
+    }
+    let coeff = 2.0 * (2.0 * std::f64::consts::PI * freq as f64 / rate as f64).cos();
+    let (mut s1, mut s2) = (0.0f64, 0.0f64);
+    for &x in samples {
+        let s0 = x as f64 + coeff * s1 - s2;
+        s2 = s1;
+        s1 = s0;
+    }
+    let power = s1 * s1 + s2 * s2 - coeff * s1 * s2;
+    (2.0 * power / (samples.len() as f64 * energy)) as f32
+}
+
+fn downmix(interleaved: &[f32], channels: u16) -> Vec<f32> {
+    let channels = channels.max(1) as usize;
+    interleaved.chunks(channels).map(|frame| frame.iter().sum::<f32>() / frame.len() as f32).collect()
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Share of the words of `expected` that were heard
+fn word_match(expected: &str, heard: &str) -> f32 {
+    let expected = words(expected);
+    let heard = words(heard);
+    if expected.is_empty() {
+        return 0.0;
+    }
+    let found = expected.iter().filter(|word| heard.contains(word)).count();
+    found as f32 / expected.len() as f32
+}
+
+fn check_level_metering(tone: &[f32]) -> SelfTestCheck {
+    let measured = CaptureLevels::from_samples(tone).rms_dbfs();
+    let expected = 20.0 * (TONE_AMPLITUDE / 2f32.sqrt()).log10();
+    if (measured - expected).abs() <= 0.5 {
+        SelfTestCheck::new("level_metering", CheckStatus::Pass, format!("Test tone measured at {:.1} dBFS", measured))
+    } else {
+        SelfTestCheck::new(
+            "level_metering",
+            CheckStatus::Fail,
+            format!("Test tone measured at {:.1} dBFS, expected {:.1} dBFS", measured, expected),
+        )
+    }
+}
+
+fn check_resampling(tone: &[f32]) -> SelfTestCheck {
+    let resampled = match resample(tone, PIPELINE_RATE, SPEECH_RATE) {
+        Ok(resampled) => resampled,
+        Err(e) => return SelfTestCheck::new("resampling", CheckStatus::Fail, format!("Resampling failed: {}", e)),
+    };
+    let expected_len = tone.len() as f32 * SPEECH_RATE as f32 / PIPELINE_RATE as f32;
+    let len_error = (resampled.len() as f32 - expected_len).abs() / expected_len;
+    // The filter's delay and edges are left out of the comparison
+    let middle = &resampled[resampled.len() / 4..resampled.len() * 3 / 4];
+    let gain_db = CaptureLevels::from_samples(middle).rms_dbfs() - CaptureLevels::from_samples(tone).rms_dbfs();
+    let ratio = tone_ratio(middle, SPEECH_RATE, TONE_HZ);
+
+    if len_error > 0.05 || gain_db.abs() > 1.0 || ratio < 0.9 {
+        SelfTestCheck::new(
+            "resampling",
+            CheckStatus::Fail,
+            format!(
+                "48 kHz → 16 kHz: {} samples (expected {:.0}), gain {:+.1} dB, {:.0}% of the tone kept",
+                resampled.len(),
+                expected_len,
+                gain_db,
+                ratio * 100.0
+            ),
+        )
+    } else {
+        SelfTestCheck::new("resampling", CheckStatus::Pass, format!("48 kHz → 16 kHz with {:+.2} dB gain", gain_db))
+    }
+}
+
+fn check_vad_silence() -> SelfTestCheck {
+    let silence = vec![0.0f32; SPEECH_RATE as usize * 2];
+    match extract_speech_16k(&silence) {
+        Ok(speech) if speech.is_empty() => SelfTestCheck::new("vad_silence", CheckStatus::Pass, "No speech found in silence"),
+        Ok(speech) => SelfTestCheck::new(
+            "vad_silence",
+            CheckStatus::Fail,
+            format!("Found {:.1}s of speech in silence", speech.len() as f32 / SPEECH_RATE as f32),
+        ),
+        Err(e) => SelfTestCheck::new("vad_silence", CheckStatus::Fail, format!("Voice detection failed: {}", e)),
+    }
+}
+
+fn check_microphone_level(samples: &[f32]) -> SelfTestCheck {
+    let levels = CaptureLevels::from_samples(samples);
+    if levels.peak < SILENT_PEAK {
+        SelfTestCheck::new(
+            "microphone_level",
+            CheckStatus::Fail,
+            "The microphone delivered only silence; it may be muted or blocked in the system's privacy settings",
+        )
+    } else if levels.rms_dbfs() < QUIET_DBFS {
+        SelfTestCheck::new(
+            "microphone_level",
+            CheckStatus::Warn,
+            format!("Very quiet input ({:.1} dBFS); check the input volume", levels.rms_dbfs()),
+        )
+    } else if levels.clipping {
+        SelfTestCheck::new(
+            "microphone_level",
+            CheckStatus::Warn,
+            format!("Input is clipping (peak {:.1} dBFS); lower the input volume", levels.peak_dbfs()),
+        )
+    } else {
+        SelfTestCheck::new(
+            "microphone_level",
+            CheckStatus::Pass,
+            format!("{:.1} dBFS RMS, {:.1} dBFS peak", levels.rms_dbfs(), levels.peak_dbfs()),
+        )
+    }
+}
+
+fn check_tone_playback(tone_capture: &[f32], played: &Result<()>) -> SelfTestCheck {
+    if let Err(e) = played {
+        return SelfTestCheck::new("tone_playback", CheckStatus::Warn, format!("Could not play the test tone: {}", e));
+    }
+    let ratio = tone_ratio(tone_capture, SPEECH_RATE, TONE_HZ);
+    if ratio >= TONE_HEARD_RATIO {
+        SelfTestCheck::new("tone_playback", CheckStatus::Pass, "The microphone picked up the test tone")
+    } else {
+        SelfTestCheck::new(
+            "tone_playback",
+            CheckStatus::Warn,
+            "The microphone didn't pick up the test tone; expected with headphones",
+        )
+    }
+}
+
+fn sine_stream<T: SizedSample + FromSample<f32>>(device: &cpal::Device, config: &StreamConfig) -> Result<cpal::Stream> {
+    let channels = config.channels as usize;
+    let rate = config.sample_rate.0 as f32;
+    let mut phase = 0.0f32;
+    let stream = device.build_output_stream(
+        config,
+        move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+            for frame in data.chunks_mut(channels) {
+                frame.fill(T::from_sample(TONE_AMPLITUDE * (2.0 * PI * phase).sin()));
+                phase = (phase + TONE_HZ / rate).fract();
+            }
+        },
+        |e| error!("❌ Self-test tone stream error: {}", e),
+        None,
+    )?;
+    Ok(stream)
+}
+
+/// Plays the test tone on the default output for `TONE_DURATION`; blocks until done
+fn play_tone() -> Result<()> {
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| anyhow!("No audio output device available"))?;
+    let supported = device.default_output_config()?;
+    let sample_format = supported.sample_format();
+    let config: StreamConfig = supported.into();
+    let stream = match sample_format {
+        SampleFormat::F32 => sine_stream::<f32>(&device, &config),
+        SampleFormat::I16 => sine_stream::<i16>(&device, &config),
+        SampleFormat::U16 => sine_stream::<u16>(&device, &config),
+        other => Err(anyhow!("Unsupported output sample format: {:?}", other)),
+    }?;
+    stream.play()?;
+    std::thread::sleep(TONE_DURATION);
+    Ok(())
+}
+
+fn emit_stage<R: Runtime>(app: &AppHandle<R>, stage: SelfTestStage) {
+    if let Err(e) = app.emit(SELFTEST_EVENT, stage) {
+        warn!("Failed to emit {}: {}", SELFTEST_EVENT, e);
+    }
+}
+
+/// Captured microphone audio at 16kHz, split into the tone and speech parts
+struct Capture {
+    device: String,
+    tone: Vec<f32>,
+    speech: Vec<f32>,
+    played: Result<()>,
+}
+
+async fn capture<R: Runtime>(app: &AppHandle<R>) -> Result<Capture> {
+    let device = default_input_device()?;
+    let source = CaptureSource::Microphone;
+    let mut backend = create_capture_backend(get_backend_for(source), source, &device).await?;
+    let format = backend.format();
+
+    let captured = Arc::new(Mutex::new(Vec::<f32>::new()));
+    let sink = captured.clone();
+    backend.start(
+        Box::new(move |data: &[f32]| {
+            if let Ok(mut samples) = sink.lock() {
+                samples.extend_from_slice(data);
+            }
+        }),
+        Box::new(|e| warn!("⚠️ Self-test capture error: {}", e)),
+    )?;
+
+    emit_stage(app, SelfTestStage::Tone);
+    let played = tokio::task::spawn_blocking(play_tone)
+        .await
+        .unwrap_or_else(|e| Err(anyhow!("Tone playback panicked: {}", e)));
+    let tone_end = captured.lock().unwrap().len();
+
+    emit_stage(app, SelfTestStage::Speech);
+    tokio::time::sleep(SPEECH_DURATION).await;
+    if let Err(e) = backend.stop() {
+        warn!("Failed to stop self-test capture: {}", e);
+    }
+
+    let samples = std::mem::take(&mut *captured.lock().unwrap());
+    let (tone, speech) = samples.split_at(tone_end.min(samples.len()));
+    let to_16k = |interleaved: &[f32]| resample(&downmix(interleaved, format.channels), format.sample_rate, SPEECH_RATE);
+    Ok(Capture { device: device.name.clone(), tone: to_16k(tone)?, speech: to_16k(speech)?, played })
+}
+
+async fn check_transcription<R: Runtime>(app: &AppHandle<R>, speech: Vec<f32>) -> (SelfTestCheck, Option<String>) {
+    let engine = match validate_transcription_model_ready(app).await {
+        Ok(()) => get_or_init_transcription_engine(app).await,
+        Err(e) => Err(e),
+    };
+    let engine = match engine {
+        Ok(engine) => engine,
+        Err(e) => return (SelfTestCheck::new("transcription", CheckStatus::Fail, e), None),
+    };
+
+    let text = match engine.transcribe(speech, None).await {
+        Ok(result) => result.text,
+        Err(e) => {
+            return (
+                SelfTestCheck::new("transcription", CheckStatus::Fail, format!("{} failed: {}", engine.provider_name(), e)),
+                None,
+            )
+        }
+    };
+
+    let matched = word_match(SELFTEST_PHRASE, &text);
+    let check = if text.is_empty() {
+        SelfTestCheck::new("transcription", CheckStatus::Fail, format!("{} returned no text", engine.provider_name()))
+    } else if matched >= MIN_WORD_MATCH {
+        SelfTestCheck::new(
+            "transcription",
+            CheckStatus::Pass,
+            format!("{} recognized {:.0}% of the phrase", engine.provider_name(), matched * 100.0),
+        )
+    } else {
+        SelfTestCheck::new(
+            "transcription",
+            CheckStatus::Warn,
+            format!("{} recognized only {:.0}% of the phrase", engine.provider_name(), matched * 100.0),
+        )
+    };
+    (check, Some(text))
+}
+
+/// Runs the pipeline self-test; takes about 10 seconds, during which the user reads
+/// `SELFTEST_PHRASE` once the `speech` stage is emitted
+#[tauri::command]
+pub async fn run_pipeline_selftest<R: Runtime>(app: AppHandle<R>) -> Result<SelfTestReport, AppError> {
+    let phase = session::phase();
+    if phase.is_recording() || phase == SessionPhase::Arming {
+        return Err(AppError::new(ErrorCode::AlreadyRecording, "Stop the recording before running the self-test"));
+    }
+
+    info!("🩺 Running pipeline self-test");
+    let started = Instant::now();
+    emit_stage(&app, SelfTestStage::Offline);
+
+    let test_tone = tone(TONE_HZ, PIPELINE_RATE, TONE_DURATION, TONE_AMPLITUDE);
+    let mut checks = vec![check_level_metering(&test_tone), check_resampling(&test_tone), check_vad_silence()];
+    let mut input_device = None;
+    let mut transcript = None;
+
+    match capture(&app).await {
+        Ok(capture) => {
+            input_device = Some(capture.device);
+            let microphone = check_microphone_level(&capture.speech);
+            let silent = microphone.status == CheckStatus::Fail;
+            checks.push(microphone);
+            checks.push(check_tone_playback(&capture.tone, &capture.played));
+
+            let speech = if silent { Ok(Vec::new()) } else { extract_speech_16k(&capture.speech) };
+            match speech {
+                Ok(speech) if !speech.is_empty() => {
+                    checks.push(SelfTestCheck::new(
+                        "vad_speech",
+                        CheckStatus::Pass,
+                        format!("Found {:.1}s of speech", speech.len() as f32 / SPEECH_RATE as f32),
+                    ));
+                    emit_stage(&app, SelfTestStage::Transcription);
+                    let (check, text) = check_transcription(&app, speech).await;
+                    checks.push(check);
+                    transcript = text;
+                }
+                Ok(_) => {
+                    checks.push(SelfTestCheck::new("vad_speech", CheckStatus::Fail, "No speech found while reading the phrase"));
+                    checks.push(SelfTestCheck::new("transcription", CheckStatus::Skipped, "No speech to transcribe"));
+                }
+                Err(e) => {
+                    checks.push(SelfTestCheck::new("vad_speech", CheckStatus::Fail, format!("Voice detection failed: {}", e)));
+                    checks.push(SelfTestCheck::new("transcription", CheckStatus::Skipped, "No speech to transcribe"));
+                }
+            }
+        }
+        Err(e) => {
+            checks.push(SelfTestCheck::new("microphone_level", CheckStatus::Fail, format!("Could not capture from the microphone: {}", e)));
+            for name in ["tone_playback", "vad_speech", "transcription"] {
+                checks.push(SelfTestCheck::new(name, CheckStatus::Skipped, "Needs microphone capture"));
+            }
+        }
+    }
+
+    let report = SelfTestReport {
+        passed: checks.iter().all(|check| check.status != CheckStatus::Fail),
+        checks,
+        input_device,
+        phrase: SELFTEST_PHRASE,
+        transcript,
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    info!("🩺 Pipeline self-test {}", if report.passed { "passed" } else { "failed" });
+    if report.passed {
+        // It doubles as the onboarding's test recording
+        if let Err(e) = crate::onboarding::mark_completed(&app, crate::onboarding::OnboardingStep::TestRecording) {
+            warn!("Failed to record the onboarding test recording: {}", e);
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tone_ratio() {
+        let pure = tone(TONE_HZ, SPEECH_RATE, Duration::from_secs(1), 0.5);
+        assert!((tone_ratio(&pure, SPEECH_RATE, TONE_HZ) - 1.0).abs() < 0.01);
+        let other = tone(440.0, SPEECH_RATE, Duration::from_secs(1), 0.5);
+        assert!(tone_ratio(&other, SPEECH_RATE, TONE_HZ) < 0.01);
+        assert_eq!(tone_ratio(&[0.0; 100], SPEECH_RATE, TONE_HZ), 0.0);
+    }
+
+    #[test]
+    fn test_downmix() {
+        assert_eq!(downmix(&[0.2, 0.4, -1.0, 1.0], 2), vec![0.3, 0.0]);
+        assert_eq!(downmix(&[0.5, 0.25], 1), vec![0.5, 0.25]);
+    }
+
+    #[test]
+    fn test_word_match() {
+        assert_eq!(word_match(SELFTEST_PHRASE, "The quick brown fox jumps over the lazy dog."), 1.0);
+        assert!((word_match(SELFTEST_PHRASE, "quick brown box") - 2.0 / 9.0).abs() < 1e-6);
+        assert_eq!(word_match(SELFTEST_PHRASE, ""), 0.0);
+    }
+
+    #[test]
+    fn test_level_metering_on_tone() {
+        let test_tone = tone(TONE_HZ, PIPELINE_RATE, TONE_DURATION, TONE_AMPLITUDE);
+        assert_eq!(check_level_metering(&test_tone).status, CheckStatus::Pass);
+    }
+}
@@ -0,0 +1,181 @@
+// Recording session state machine
+//
+// The one place that knows what the recorder is doing. A session goes
+// Idle → Arming → Recording ⇄ Paused → Finalizing → Done, or to Error when starting or
+// stopping fails; a new session can begin from Idle, Done or Error. Every transition is
+// checked against that graph and emitted as a `recording-session` event, so the UI follows
+// the backend instead of keeping its own idea of the recording state.
+
+use std::sync::Mutex;
+use chrono::Utc;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::error::{AppError, ErrorCode};
+
+/// Emitted on every transition with a `SessionTransition`
+pub const SESSION_EVENT: &str = "recording-session";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionPhase {
+    /// No recording since the app started
+    Idle,
+    /// Checking models and opening the capture devices
+    Arming,
+    Recording,
+    Paused,
+    /// Capture stopped; the remaining audio is transcribed and the recording saved
+    Finalizing,
+    /// The last recording was saved
+    Done,
+    /// The last recording failed to start or to stop cleanly
+    Error,
+}
+
+impl SessionPhase {
+    /// Whether the graph has an edge from `self` to `to`
+    pub fn can_become(self, to: SessionPhase) -> bool {
+        use SessionPhase::*;
+        matches!(
+            (self, to),
+            (Idle | Done | Error, Arming)
+                | (Arming, Recording | Error)
+                | (Recording, Paused | Finalizing | Error)
+                | (Paused, Recording | Finalizing | Error)
+                | (Finalizing, Done | Error)
+        )
+    }
+
+    /// Capture is open: from the first recorded sample until the recording is saved
+    pub fn is_recording(self) -> bool {
+        matches!(self, SessionPhase::Recording | SessionPhase::Paused | SessionPhase::Finalizing)
+    }
+}
+
+/// The current session, as returned by `get_recording_session`
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingSession {
+    pub phase: SessionPhase,
+    pub meeting_name: Option<String>,
+    /// When the session entered its phase (RFC 3339)
+    pub since: String,
+    /// Why the session failed, in the Error phase
+    pub error: Option<String>,
+}
+
+/// Payload of the `recording-session` event
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionTransition {
+    pub from: SessionPhase,
+    pub to: SessionPhase,
+    pub meeting_name: Option<String>,
+    pub error: Option<String>,
+    pub at: String,
+}
+
+static SESSION: Lazy<Mutex<RecordingSession>> = Lazy::new(|| {
+    Mutex::new(RecordingSession { phase: SessionPhase::Idle, meeting_name: None, since: Utc::now().to_rfc3339(), error: None })
+});
+
+pub fn current() -> RecordingSession {
+    SESSION.lock().unwrap().clone()
+}
+
+pub fn phase() -> SessionPhase {
+    SESSION.lock().unwrap().phase
+}
+
+/// Moves the session to `to`, checking and applying the transition under one lock
+fn apply<R: Runtime>(
+    app: &AppHandle<R>,
+    to: SessionPhase,
+    meeting_name: Option<Option<String>>,
+    error: Option<String>,
+) -> Result<SessionTransition, String> {
+    let transition = {
+        let mut session = SESSION.lock().unwrap();
+        let from = session.phase;
+        if !from.can_become(to) {
+            return Err(format!("Cannot go from {:?} to {:?}", from, to));
+        }
+        if let Some(meeting_name) = meeting_name {
+            session.meeting_name = meeting_name;
+        }
+        session.phase = to;
+        session.since = Utc::now().to_rfc3339();
+        session.error = error;
+        SessionTransition {
+            from,
+            to,
+            meeting_name: session.meeting_name.clone(),
+            error: session.error.clone(),
+            at: session.since.clone(),
+        }
+    };
+
+    info!("🎙️ Recording session: {:?} → {:?}", transition.from, transition.to);
+    if let Err(e) = app.emit(SESSION_EVENT, &transition) {
+        warn!("Failed to emit {}: {}", SESSION_EVENT, e);
+    }
+    Ok(transition)
+}
+
+/// Starts arming a new session; refused while another one is in progress
+pub fn begin<R: Runtime>(app: &AppHandle<R>, meeting_name: Option<String>) -> Result<(), AppError> {
+    apply(app, SessionPhase::Arming, Some(meeting_name), None)
+        .map(|_| ())
+        .map_err(|_| AppError::new(ErrorCode::AlreadyRecording, "Recording already in progress"))
+}
+
+pub fn transition<R: Runtime>(app: &AppHandle<R>, to: SessionPhase) -> Result<(), String> {
+    apply(app, to, None, None).map(|_| ())
+}
+
+/// Ends the session in the Error phase
+pub fn fail<R: Runtime>(app: &AppHandle<R>, error: &str) {
+    if let Err(e) = apply(app, SessionPhase::Error, None, Some(error.to_string())) {
+        warn!("Recording session error not recorded ({}): {}", e, error);
+    }
+}
+
+/// Fails the session if it never got past arming; a start that failed after capture began
+/// leaves the session as it is, since the recorder is running
+pub fn fail_arming<R: Runtime>(app: &AppHandle<R>, error: &str) {
+    if phase() == SessionPhase::Arming {
+        fail(app, error);
+    }
+}
+
+#[tauri::command]
+pub async fn get_recording_session() -> Result<RecordingSession, String> {
+    Ok(current())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use SessionPhase::*;
+
+    #[test]
+    fn test_transitions() {
+        let path = [Idle, Arming, Recording, Paused, Recording, Finalizing, Done, Arming, Error, Arming];
+        for pair in path.windows(2) {
+            assert!(pair[0].can_become(pair[1]), "{:?} → {:?}", pair[0], pair[1]);
+        }
+        assert!(!Idle.can_become(Recording));
+        assert!(!Recording.can_become(Arming));
+        assert!(!Finalizing.can_become(Finalizing));
+        assert!(!Done.can_become(Paused));
+        assert!(!Paused.can_become(Done));
+    }
+
+    #[test]
+    fn test_is_recording() {
+        assert!(Finalizing.is_recording());
+        assert!(!Arming.is_recording());
+        assert!(!Done.is_recording());
+    }
+}
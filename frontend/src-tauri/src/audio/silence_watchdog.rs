@@ -0,0 +1,171 @@
+// Silence / no-signal watchdog for capture streams
+//
+// A muted microphone, a revoked permission or a Core Audio tap that was silently denied
+// all deliver a stream of zeros instead of failing. The watchdog tracks how long a stream
+// has produced no usable signal and raises a warning once, within seconds, so the user
+// is not left with an empty recording.
+
+use std::time::Duration;
+use serde::Serialize;
+
+use super::recording_state::DeviceType;
+
+/// Samples with absolute value at or below this count as digital silence
+const ZERO_EPSILON: f32 = 1e-7;
+
+/// Block RMS below this is treated as near-silent (about -70 dBFS)
+const NEAR_SILENT_RMS: f32 = 3.2e-4;
+
+/// All-zero input for this long means no signal at all
+const NO_SIGNAL_AFTER: Duration = Duration::from_secs(5);
+
+/// Near-silent microphone input for this long is worth a warning
+const MIC_SILENT_AFTER: Duration = Duration::from_secs(20);
+
+/// Why a recording warning was raised
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningReason {
+    /// The stream delivers only zeros (muted device, denied permission or tap)
+    NoSignal,
+    /// The microphone picks up almost nothing
+    SilentInput,
+    /// The OS reports the microphone as muted
+    InputMuted,
+    /// The microphone's input volume is turned all the way down
+    InputVolumeZero,
+    /// Signal returned after a warning
+    SignalRestored,
+}
+
+impl WarningReason {
+    pub fn user_message(&self) -> &'static str {
+        match self {
+            WarningReason::NoSignal => "No audio is being received. The device may be muted or access may have been denied.",
+            WarningReason::SilentInput => "The microphone is picking up almost no sound. Check that it is not muted.",
+            WarningReason::InputMuted => "The microphone is muted. Unmute it to be heard in the recording.",
+            WarningReason::InputVolumeZero => {
+                "The microphone's input volume is at zero. Turn it up in the system sound settings."
+            }
+            WarningReason::SignalRestored => "Audio input is working again.",
+        }
+    }
+}
+
+/// Payload of the `recording-warning` event
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingWarning {
+    pub device_name: String,
+    pub device_type: String,
+    pub reason: WarningReason,
+    pub message: String,
+    pub silent_for_secs: f64,
+}
+
+/// Per-stream silence tracker fed with raw capture blocks
+#[derive(Debug)]
+pub struct SilenceWatchdog {
+    device_type: DeviceType,
+    sample_rate: u32,
+    zero_samples: u64,
+    quiet_samples: u64,
+    warned: Option<WarningReason>,
+}
+
+impl SilenceWatchdog {
+    pub fn new(device_type: DeviceType, sample_rate: u32) -> Self {
+        Self {
+            device_type,
+            sample_rate: sample_rate.max(1),
+            zero_samples: 0,
+            quiet_samples: 0,
+            warned: None,
+        }
+    }
+
+    /// Feed one mono block; returns a reason when a warning (or recovery) should be raised
+    pub fn observe(&mut self, samples: &[f32]) -> Option<WarningReason> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let all_zero = samples.iter().all(|s| s.abs() <= ZERO_EPSILON);
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+
+        if all_zero {
+            self.zero_samples += samples.len() as u64;
+            self.quiet_samples += samples.len() as u64;
+        } else if rms < NEAR_SILENT_RMS {
+            self.zero_samples = 0;
+            self.quiet_samples += samples.len() as u64;
+        } else {
+            self.zero_samples = 0;
+            self.quiet_samples = 0;
+            // Only announce recovery if something was wrong
+            return self.warned.take().map(|_| WarningReason::SignalRestored);
+        }
+
+        if self.warned == Some(WarningReason::NoSignal) {
+            return None;
+        }
+
+        if self.duration(self.zero_samples) >= NO_SIGNAL_AFTER {
+            self.warned = Some(WarningReason::NoSignal);
+            return self.warned;
+        }
+
+        // Quiet system audio just means nobody is talking on the call
+        if self.warned.is_none()
+            && matches!(self.device_type, DeviceType::Microphone)
+            && self.duration(self.quiet_samples) >= MIC_SILENT_AFTER
+        {
+            self.warned = Some(WarningReason::SilentInput);
+            return self.warned;
+        }
+
+        None
+    }
+
+    /// How long the stream has been without usable signal
+    pub fn silent_for(&self) -> Duration {
+        self.duration(self.quiet_samples)
+    }
+
+    fn duration(&self, samples: u64) -> Duration {
+        Duration::from_secs_f64(samples as f64 / self.sample_rate as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 1000;
+
+    fn feed(watchdog: &mut SilenceWatchdog, value: f32, seconds: u32) -> Vec<WarningReason> {
+        (0..seconds * 10)
+            .filter_map(|_| watchdog.observe(&vec![value; (RATE / 10) as usize]))
+            .collect()
+    }
+
+    #[test]
+    fn test_all_zero_input_warns_once() {
+        let mut watchdog = SilenceWatchdog::new(DeviceType::System, RATE);
+        assert!(feed(&mut watchdog, 0.0, 4).is_empty());
+        assert_eq!(feed(&mut watchdog, 0.0, 30), vec![WarningReason::NoSignal]);
+    }
+
+    #[test]
+    fn test_quiet_microphone_warns_and_recovers() {
+        let mut watchdog = SilenceWatchdog::new(DeviceType::Microphone, RATE);
+        assert_eq!(feed(&mut watchdog, 1e-4, 25), vec![WarningReason::SilentInput]);
+        assert_eq!(feed(&mut watchdog, 0.1, 1), vec![WarningReason::SignalRestored]);
+        assert!(feed(&mut watchdog, 0.1, 5).is_empty());
+    }
+
+    #[test]
+    fn test_quiet_system_audio_is_not_a_warning() {
+        let mut watchdog = SilenceWatchdog::new(DeviceType::System, RATE);
+        assert!(feed(&mut watchdog, 1e-4, 60).is_empty());
+    }
+}
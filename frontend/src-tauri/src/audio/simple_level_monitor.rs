@@ -1,16 +1,50 @@
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Runtime};
 use anyhow::Result;
-use log::{error, info};
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
 use serde::Serialize;
 
+use super::capture::{create_capture_backend, get_backend_for, AudioCaptureBackend, CaptureLevels, CaptureSource};
+use super::capture_ring::CaptureRing;
+use super::devices::{list_audio_devices, parse_audio_device, AudioDevice, DeviceType};
+use super::recording_state::DeviceType as StreamType;
+
+/// Event carrying `AudioLevelUpdate` payloads
+pub const AUDIO_LEVELS_EVENT: &str = "audio-levels";
+
+/// Interval between level events
+pub const LEVEL_INTERVAL_MS: u64 = 100;
+
+/// RMS above which a device counts as picking up sound (about -50 dBFS)
+const ACTIVE_RMS_THRESHOLD: f32 = 0.003;
+
 #[derive(Debug, Serialize, Clone)]
 pub struct AudioLevelData {
     pub device_name: String,
     pub device_type: String, // "input" or "output"
     pub rms_level: f32,     // RMS level (0.0 to 1.0)
     pub peak_level: f32,    // Peak level (0.0 to 1.0)
+    pub rms_db: f32,        // RMS level in dBFS
+    pub peak_db: f32,       // Peak level in dBFS
     pub is_active: bool,    // Whether audio is being detected
+    pub clipping: bool,     // Peak reached -1 dBFS
+}
+
+impl AudioLevelData {
+    pub fn new(device_name: &str, device_type: &str, levels: CaptureLevels) -> Self {
+        Self {
+            device_name: device_name.to_string(),
+            device_type: device_type.to_string(),
+            rms_level: levels.rms,
+            peak_level: levels.peak,
+            rms_db: levels.rms_dbfs(),
+            peak_db: levels.peak_dbfs(),
+            is_active: levels.rms > ACTIVE_RMS_THRESHOLD,
+            clipping: levels.clipping,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -19,55 +53,112 @@ pub struct AudioLevelUpdate {
     pub levels: Vec<AudioLevelData>,
 }
 
+impl AudioLevelUpdate {
+    pub fn now(levels: Vec<AudioLevelData>) -> Self {
+        Self {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            levels,
+        }
+    }
+}
+
+/// A capture opened only to meter levels (nothing is recorded); the ring's thread does
+/// the metering
+struct MeteredDevice {
+    device: AudioDevice,
+    backend: Box<dyn AudioCaptureBackend>,
+    ring: CaptureRing,
+}
+
 // Simple global monitoring state
 static IS_MONITORING: AtomicBool = AtomicBool::new(false);
+static METERED_DEVICES: Lazy<Mutex<Vec<MeteredDevice>>> = Lazy::new(|| Mutex::new(Vec::new()));
 
 /// Start audio level monitoring for specified devices
+///
+/// Opens each device through its selected capture backend and emits real RMS/peak levels,
+/// so the user can check the microphone before the meeting starts.
 pub async fn start_monitoring<R: Runtime>(
     app_handle: AppHandle<R>,
     device_names: Vec<String>,
 ) -> Result<()> {
-    info!("Starting simplified audio level monitoring for devices: {:?}", device_names);
+    info!("Starting audio level monitoring for devices: {:?}", device_names);
 
     // Stop any existing monitoring
-    IS_MONITORING.store(false, Ordering::SeqCst);
+    stop_monitoring().await?;
 
     // Wait a bit for any existing tasks to stop
-    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    tokio::time::sleep(tokio::time::Duration::from_millis(LEVEL_INTERVAL_MS)).await;
+
+    let available = list_audio_devices().await.unwrap_or_default();
+    let mut metered = Vec::new();
+    for name in &device_names {
+        let device = resolve_device(name, &available);
+        let (source, stream_type) = match device.device_type {
+            DeviceType::Input => (CaptureSource::Microphone, StreamType::Microphone),
+            DeviceType::Output => (CaptureSource::System, StreamType::System),
+        };
+
+        let mut backend = match create_capture_backend(get_backend_for(source), source, &device).await {
+            Ok(backend) => backend,
+            Err(e) => {
+                warn!("⚠️ Level monitor: cannot open '{}': {}", device.name, e);
+                continue;
+            }
+        };
+
+        let format = backend.format();
+        let (ring, mut writer) =
+            match CaptureRing::start_unlisted(&device.name, stream_type, format.sample_rate, format.channels, |_: &[f32]| {}) {
+                Ok(ring) => ring,
+                Err(e) => {
+                    warn!("⚠️ Level monitor: cannot start metering '{}': {}", device.name, e);
+                    continue;
+                }
+            };
+
+        let device_name = device.name.clone();
+        if let Err(e) = backend.start(
+            Box::new(move |data: &[f32]| writer.push(data)),
+            Box::new(move |err| warn!("⚠️ Level monitor stream error on '{}': {}", device_name, err)),
+        ) {
+            warn!("⚠️ Level monitor: cannot start '{}': {}", device.name, e);
+            continue;
+        }
+
+        metered.push(MeteredDevice { device, backend, ring });
+    }
 
-    // Start new monitoring
+    if metered.is_empty() {
+        return Err(anyhow::anyhow!("None of the requested devices could be opened for level monitoring"));
+    }
+
+    *METERED_DEVICES.lock().unwrap() = metered;
     IS_MONITORING.store(true, Ordering::SeqCst);
 
-    // For now, create fake level data to test the UI
-    let app_handle_clone = app_handle.clone();
     tokio::spawn(async move {
-        let mut counter: f32 = 0.0;
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(LEVEL_INTERVAL_MS));
 
         while IS_MONITORING.load(Ordering::SeqCst) {
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-
-            counter += 0.1;
-            let fake_level = (counter.sin().abs() * 0.8) as f32; // Simulate varying levels
-
-            let levels: Vec<AudioLevelData> = device_names.iter().map(|name| {
-                AudioLevelData {
-                    device_name: name.clone(),
-                    device_type: "input".to_string(),
-                    rms_level: fake_level,
-                    peak_level: fake_level * 1.2,
-                    is_active: fake_level > 0.1,
-                }
-            }).collect();
-
-            let update = AudioLevelUpdate {
-                timestamp: std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis() as u64,
-                levels,
-            };
-
-            if let Err(e) = app_handle_clone.emit("audio-levels", &update) {
+            interval.tick().await;
+
+            let levels: Vec<AudioLevelData> = METERED_DEVICES
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|m| {
+                    let kind = match m.device.device_type {
+                        DeviceType::Input => "input",
+                        DeviceType::Output => "output",
+                    };
+                    AudioLevelData::new(&m.device.name, kind, m.ring.levels())
+                })
+                .collect();
+
+            if let Err(e) = app_handle.emit(AUDIO_LEVELS_EVENT, &AudioLevelUpdate::now(levels)) {
                 error!("Failed to emit audio levels: {}", e);
                 break;
             }
@@ -81,12 +172,57 @@ pub async fn start_monitoring<R: Runtime>(
 
 /// Stop audio level monitoring
 pub async fn stop_monitoring() -> Result<()> {
-    info!("Stopping simplified audio level monitoring");
+    info!("Stopping audio level monitoring");
     IS_MONITORING.store(false, Ordering::SeqCst);
+
+    let metered = std::mem::take(&mut *METERED_DEVICES.lock().unwrap());
+    for mut m in metered {
+        if let Err(e) = m.backend.stop() {
+            warn!("Failed to stop level monitor for '{}': {}", m.device.name, e);
+        }
+        m.ring.stop();
+    }
     Ok(())
 }
 
 /// Check if currently monitoring
 pub fn is_monitoring() -> bool {
     IS_MONITORING.load(Ordering::SeqCst)
-}
\ No newline at end of file
+}
+
+/// Accept either a device id ("Name (input)") or a bare device name
+fn resolve_device(name: &str, available: &[AudioDevice]) -> AudioDevice {
+    if let Ok(device) = parse_audio_device(name) {
+        return device;
+    }
+    available
+        .iter()
+        .find(|d| d.name == name)
+        .cloned()
+        .unwrap_or_else(|| AudioDevice::new(name.to_string(), DeviceType::Input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_data_from_capture_levels() {
+        let data = AudioLevelData::new("USB Mic", "input", CaptureLevels::from_samples(&[0.95, -0.2]));
+        assert!(data.clipping);
+        assert!(data.is_active);
+        assert!(data.peak_db > -1.0 && data.peak_db < 0.0);
+
+        let silent = AudioLevelData::new("USB Mic", "input", CaptureLevels::default());
+        assert!(!silent.is_active);
+        assert_eq!(silent.rms_db, super::super::capture::to_dbfs(0.0));
+    }
+
+    #[test]
+    fn test_resolve_device_prefers_listed_type() {
+        let available = vec![AudioDevice::new("BlackHole 2ch".to_string(), DeviceType::Output)];
+        assert_eq!(resolve_device("BlackHole 2ch", &available).device_type, DeviceType::Output);
+        assert_eq!(resolve_device("USB Mic (input)", &available).name, "USB Mic");
+        assert_eq!(resolve_device("Unknown", &available).device_type, DeviceType::Input);
+    }
+}
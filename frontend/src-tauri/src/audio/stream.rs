@@ -1,42 +1,26 @@
 use std::sync::Arc;
 use anyhow::Result;
-use cpal::traits::{DeviceTrait, StreamTrait};
-use cpal::{Device, Stream, SupportedStreamConfig};
 use log::{error, info, warn};
 use tokio::sync::mpsc;
 
-use super::devices::{AudioDevice, get_device_and_config};
+use super::capture_ring::CaptureRing;
+use super::devices::AudioDevice;
 use super::pipeline::AudioCapture;
+use super::preroll::PrerollAudio;
 use super::recording_state::{RecordingState, DeviceType};
-use super::capture::{AudioCaptureBackend, get_current_backend};
-
-#[cfg(target_os = "macos")]
-use super::capture::CoreAudioCapture;
-
-/// Stream backend implementation
-pub enum StreamBackend {
-    /// CPAL-based stream (ScreenCaptureKit or default)
-    Cpal(Stream),
-    /// Core Audio direct implementation (macOS only)
-    #[cfg(target_os = "macos")]
-    CoreAudio {
-        task: Option<tokio::task::JoinHandle<()>>,
-    },
-}
-
-// SAFETY: While Stream doesn't implement Send, we ensure it's only accessed
-// from the same thread context by using spawn_blocking for operations that cross thread boundaries
-unsafe impl Send for StreamBackend {}
+use super::capture::{
+    create_capture_backend, get_backend_for, AudioCaptureBackend, CaptureBackendType,
+    CaptureLevels, CaptureSource,
+};
 
 /// Simplified audio stream wrapper with multi-backend support
 pub struct AudioStream {
     device: Arc<AudioDevice>,
-    backend: StreamBackend,
+    backend: Box<dyn AudioCaptureBackend>,
+    // Processing thread fed by the capture callback
+    ring: CaptureRing,
 }
 
-// SAFETY: AudioStream contains StreamBackend which we've marked as Send
-unsafe impl Send for AudioStream {}
-
 impl AudioStream {
     /// Create a new audio stream for the given device
     pub async fn create(
@@ -45,318 +29,164 @@ impl AudioStream {
         device_type: DeviceType,
         recording_sender: Option<mpsc::UnboundedSender<super::recording_state::AudioChunk>>,
     ) -> Result<Self> {
-        // Get current backend from global config
-        let backend_type = get_current_backend();
-        Self::create_with_backend(device, state, device_type, recording_sender, backend_type).await
-    }
-
-    /// Create a new audio stream with explicit backend selection
-    pub async fn create_with_backend(
-        device: Arc<AudioDevice>,
-        state: Arc<RecordingState>,
-        device_type: DeviceType,
-        recording_sender: Option<mpsc::UnboundedSender<super::recording_state::AudioChunk>>,
-        backend_type: AudioCaptureBackend,
-    ) -> Result<Self> {
-        info!("🎵 Stream: Creating audio stream for device: {} with backend: {:?}, device_type: {:?}",
-              device.name, backend_type, device_type);
-
-        // For system audio devices, use the selected backend
-        // For microphone devices, always use CPAL
-        #[cfg(target_os = "macos")]
-        let use_core_audio = device_type == DeviceType::System
-            && backend_type == AudioCaptureBackend::CoreAudio;
-
-        #[cfg(not(target_os = "macos"))]
-        let use_core_audio = false;
-
-        #[cfg(target_os = "macos")]
-        info!("🎵 Stream: use_core_audio = {}, device_type == System: {}, backend == CoreAudio: {}",
-              use_core_audio,
-              device_type == DeviceType::System,
-              backend_type == AudioCaptureBackend::CoreAudio);
-
-        #[cfg(not(target_os = "macos"))]
-        info!("🎵 Stream: use_core_audio = {}, device_type == System: {}",
-              use_core_audio,
-              device_type == DeviceType::System);
-
-        #[cfg(target_os = "macos")]
-        if use_core_audio {
-            info!("🎵 Stream: Using Core Audio backend (cidre) for system audio");
-            return Self::create_core_audio_stream(device, state, device_type, recording_sender).await;
-        }
-
-        // Default path: use CPAL
-        #[cfg(target_os = "macos")]
-        let backend_name = if backend_type == AudioCaptureBackend::ScreenCaptureKit {
-            "ScreenCaptureKit"
-        } else {
-            "CPAL (default)"
-        };
-
-        #[cfg(not(target_os = "macos"))]
-        let backend_name = "CPAL";
-
-        info!("🎵 Stream: Using CPAL backend ({}) for device: {}", backend_name, device.name);
-        Self::create_cpal_stream(device, state, device_type, recording_sender).await
+        // Get the backend selected for this source from global config
+        let backend_type = get_backend_for(capture_source(&device_type));
+        Self::create_with_backend(device, state, device_type, recording_sender, backend_type, None).await
     }
 
-    /// Create a CPAL-based stream (ScreenCaptureKit on macOS)
-    async fn create_cpal_stream(
+    /// Create a stream that first replays buffered pre-roll audio at `offset` seconds
+    pub async fn create_with_preroll(
         device: Arc<AudioDevice>,
         state: Arc<RecordingState>,
         device_type: DeviceType,
         recording_sender: Option<mpsc::UnboundedSender<super::recording_state::AudioChunk>>,
+        preroll: Option<(PrerollAudio, f64)>,
     ) -> Result<Self> {
-        info!("Creating CPAL stream for device: {}", device.name);
-
-        // Get the underlying cpal device and config
-        let (cpal_device, config) = get_device_and_config(&device).await?;
-
-        info!("Audio config - Sample rate: {}, Channels: {}, Format: {:?}",
-              config.sample_rate().0, config.channels(), config.sample_format());
-
-        // Create audio capture processor
-        let capture = AudioCapture::new(
-            device.clone(),
-            state.clone(),
-            config.sample_rate().0,
-            config.channels(),
-            device_type,
-            recording_sender,
-        );
-
-        // Build the appropriate stream based on sample format
-        let stream = Self::build_stream(&cpal_device, &config, capture.clone())?;
-
-        // Start the stream
-        stream.play()?;
-        info!("CPAL stream started for device: {}", device.name);
-
-        Ok(Self {
-            device,
-            backend: StreamBackend::Cpal(stream),
-        })
+        let backend_type = get_backend_for(capture_source(&device_type));
+        Self::create_with_backend(device, state, device_type, recording_sender, backend_type, preroll).await
     }
 
-    /// Create a Core Audio stream (macOS only)
-    #[cfg(target_os = "macos")]
-    async fn create_core_audio_stream(
+    /// Create a new audio stream with explicit backend selection
+    pub async fn create_with_backend(
         device: Arc<AudioDevice>,
         state: Arc<RecordingState>,
         device_type: DeviceType,
         recording_sender: Option<mpsc::UnboundedSender<super::recording_state::AudioChunk>>,
+        backend_type: CaptureBackendType,
+        preroll: Option<(PrerollAudio, f64)>,
     ) -> Result<Self> {
-        info!("🔊 Stream: Creating Core Audio stream for device: {}", device.name);
-
-        // Create Core Audio capture
-        info!("🔊 Stream: Calling CoreAudioCapture::new()...");
-        let capture_impl = CoreAudioCapture::new()
-            .map_err(|e| {
-                error!("❌ Stream: CoreAudioCapture::new() failed: {}", e);
-                anyhow::anyhow!("Failed to create Core Audio capture: {}", e)
-            })?;
+        info!("🎵 Stream: Creating audio stream for device: {} with backend: {:?}, device_type: {:?}",
+              device.name, backend_type, device_type);
 
-        info!("✅ Stream: CoreAudioCapture created, calling stream()...");
-        let core_stream = capture_impl.stream()
+        let mut backend = create_capture_backend(backend_type, capture_source(&device_type), &device)
+            .await
             .map_err(|e| {
-                error!("❌ Stream: capture_impl.stream() failed: {}", e);
-                anyhow::anyhow!("Failed to create Core Audio stream: {}", e)
+                error!("❌ Stream: Failed to create {:?} backend for {}: {}", backend_type, device.name, e);
+                e
             })?;
 
-        let sample_rate = core_stream.sample_rate();
-        info!("✅ Stream: Core Audio stream created with sample rate: {} Hz", sample_rate);
+        let format = backend.format();
+        info!("Audio config - Backend: {}, Sample rate: {}, Channels: {}",
+              backend.backend_type().name(), format.sample_rate, format.channels);
 
         // Create audio capture processor for pipeline integration
-        // CRITICAL: Core Audio tap is MONO (with_mono_global_tap_excluding_processes)
         let capture = AudioCapture::new(
             device.clone(),
             state.clone(),
-            sample_rate,
-            1, // Core Audio tap is MONO (not stereo!)
-            device_type,
+            format.sample_rate,
+            format.channels,
+            device_type.clone(),
             recording_sender,
         );
 
-        // Spawn task to process Core Audio stream samples
-        // The stream needs to be polled continuously to produce samples
-        let device_name = device.name.clone();
-        info!("🔊 Stream: Spawning tokio task to poll Core Audio stream...");
-        let task = tokio::spawn({
-            let capture = capture.clone();
-            let mut stream = core_stream;
-
-            async move {
-                use futures_util::StreamExt;
-
-                let mut buffer = Vec::new();
-                let mut frame_count = 0;
-                let frames_per_chunk = 1024; // Process in chunks of 1024 samples
-
-                info!("✅ Stream: Core Audio processing task started for {}", device_name);
-
-                let mut _sample_count = 0u64;
-                while let Some(sample) = stream.next().await {
-                    _sample_count += 1;
-                    // if _sample_count % 48000 == 0 {
-                    //     info!("📊 Stream: Received {} samples from Core Audio stream", _sample_count);
-                    // }
-
-                    buffer.push(sample);
-                    frame_count += 1;
-
-                    // Process when we have enough samples
-                    if frame_count >= frames_per_chunk {
-                        capture.process_audio_data(&buffer);
-                        buffer.clear();
-                        frame_count = 0;
-                    }
-                }
-
-                // Process any remaining samples
-                if !buffer.is_empty() {
-                    capture.process_audio_data(&buffer);
-                }
-
-                info!("⚠️ Stream: Core Audio processing task ended for {}", device_name);
+        if let Some((preroll, offset)) = preroll {
+            if preroll.format == format {
+                capture.process_preroll(&preroll.samples, offset);
+            } else {
+                warn!("⚠️ Pre-roll for '{}' has a different format ({:?} vs {:?}), dropping it",
+                      device.name, preroll.format, format);
             }
-        });
+        }
 
-        info!("✅ Stream: Core Audio stream fully initialized for device: {}", device.name);
+        // The callback only fills the ring; the pipeline runs on the ring's thread
+        let error_capture = capture.clone();
+        let (ring, mut writer) = CaptureRing::start(
+            &device.name,
+            device_type,
+            format.sample_rate,
+            format.channels,
+            move |data: &[f32]| capture.process_audio_data(data),
+        )?;
+        backend.start(
+            Box::new(move |data: &[f32]| writer.push(data)),
+            Box::new(move |err| error_capture.handle_stream_error(err)),
+        )?;
+
+        info!("✅ Stream: {} stream started for device: {}", backend.backend_type().name(), device.name);
+
+        Ok(Self { device, backend, ring })
+    }
 
-        Ok(Self {
-            device: device.clone(),
-            backend: StreamBackend::CoreAudio {
-                task: Some(task),
-            },
-        })
+    /// Get device info
+    pub fn device(&self) -> &AudioDevice {
+        &self.device
     }
 
-    /// Build stream based on sample format
-    fn build_stream(
-        device: &Device,
-        config: &SupportedStreamConfig,
-        capture: AudioCapture,
-    ) -> Result<Stream> {
-        let config_copy = config.clone();
-
-        let stream = match config.sample_format() {
-            cpal::SampleFormat::F32 => {
-                let capture_clone = capture.clone();
-                device.build_input_stream(
-                    &config_copy.into(),
-                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                        capture.process_audio_data(data);
-                    },
-                    move |err| {
-                        capture_clone.handle_stream_error(err);
-                    },
-                    None,
-                )?
-            }
-            cpal::SampleFormat::I16 => {
-                let capture_clone = capture.clone();
-                device.build_input_stream(
-                    &config_copy.into(),
-                    move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                        let f32_data: Vec<f32> = data.iter()
-                            .map(|&sample| sample as f32 / i16::MAX as f32)
-                            .collect();
-                        capture.process_audio_data(&f32_data);
-                    },
-                    move |err| {
-                        capture_clone.handle_stream_error(err);
-                    },
-                    None,
-                )?
-            }
-            cpal::SampleFormat::I32 => {
-                let capture_clone = capture.clone();
-                device.build_input_stream(
-                    &config_copy.into(),
-                    move |data: &[i32], _: &cpal::InputCallbackInfo| {
-                        let f32_data: Vec<f32> = data.iter()
-                            .map(|&sample| sample as f32 / i32::MAX as f32)
-                            .collect();
-                        capture.process_audio_data(&f32_data);
-                    },
-                    move |err| {
-                        capture_clone.handle_stream_error(err);
-                    },
-                    None,
-                )?
-            }
-            cpal::SampleFormat::I8 => {
-                let capture_clone = capture.clone();
-                device.build_input_stream(
-                    &config_copy.into(),
-                    move |data: &[i8], _: &cpal::InputCallbackInfo| {
-                        let f32_data: Vec<f32> = data.iter()
-                            .map(|&sample| sample as f32 / i8::MAX as f32)
-                            .collect();
-                        capture.process_audio_data(&f32_data);
-                    },
-                    move |err| {
-                        capture_clone.handle_stream_error(err);
-                    },
-                    None,
-                )?
-            }
-            _ => {
-                return Err(anyhow::anyhow!("Unsupported sample format: {:?}", config.sample_format()));
-            }
-        };
+    /// Backend this stream captures through
+    pub fn backend_type(&self) -> CaptureBackendType {
+        self.backend.backend_type()
+    }
 
-        Ok(stream)
+    /// Input levels measured by the processing thread since the previous call
+    pub fn levels(&self) -> CaptureLevels {
+        self.ring.levels()
     }
 
-    /// Get device info
-    pub fn device(&self) -> &AudioDevice {
-        &self.device
+    /// Stop delivering samples without closing the device
+    pub fn pause(&self) {
+        self.backend.pause();
+    }
+
+    pub fn resume(&self) {
+        self.backend.resume();
     }
 
     /// Stop the stream
-    pub fn stop(self) -> Result<()> {
+    pub fn stop(mut self) -> Result<()> {
         info!("Stopping audio stream for device: {}", self.device.name);
-
-        match self.backend {
-            StreamBackend::Cpal(stream) => {
-                drop(stream);
-            }
-            #[cfg(target_os = "macos")]
-            StreamBackend::CoreAudio { task } => {
-                // Abort the processing task (which will drop the stream)
-                if let Some(task_handle) = task {
-                    task_handle.abort();
-                }
-            }
-        }
-
+        self.backend.stop()?;
+        self.ring.stop();
         info!("Audio stream stopped for device: {}", self.device.name);
         Ok(())
     }
 }
 
+/// Map recording device type to the capture source used for backend selection
+fn capture_source(device_type: &DeviceType) -> CaptureSource {
+    match device_type {
+        DeviceType::Microphone => CaptureSource::Microphone,
+        DeviceType::System => CaptureSource::System,
+    }
+}
+
 /// Audio stream manager for handling multiple streams
 pub struct AudioStreamManager {
     microphone_stream: Option<AudioStream>,
     system_stream: Option<AudioStream>,
     state: Arc<RecordingState>,
+    // Buffered audio replayed ahead of the first streams, with its total length in seconds
+    preroll: Vec<PrerollAudio>,
+    preroll_seconds: f64,
 }
 
-// SAFETY: AudioStreamManager contains AudioStream which we've marked as Send
-unsafe impl Send for AudioStreamManager {}
-
 impl AudioStreamManager {
     pub fn new(state: Arc<RecordingState>) -> Self {
         Self {
             microphone_stream: None,
             system_stream: None,
             state,
+            preroll: Vec::new(),
+            preroll_seconds: 0.0,
         }
     }
 
+    /// Replay this pre-roll audio when the next streams start
+    pub fn set_preroll(&mut self, preroll: Vec<PrerollAudio>, total_seconds: f64) {
+        self.preroll = preroll;
+        self.preroll_seconds = total_seconds;
+    }
+
+    /// Pre-roll for the device, with its start offset on the recording timeline
+    fn take_preroll_for(&mut self, device: &AudioDevice, device_type: &DeviceType) -> Option<(PrerollAudio, f64)> {
+        let index = self
+            .preroll
+            .iter()
+            .position(|p| p.device_name == device.name && &p.device_type == device_type)?;
+        let preroll = self.preroll.remove(index);
+        let offset = (self.preroll_seconds - preroll.duration_secs()).max(0.0);
+        Some((preroll, offset))
+    }
+
     /// Start audio streams for the given devices
     pub async fn start_streams(
         &mut self,
@@ -364,14 +194,14 @@ impl AudioStreamManager {
         system_device: Option<Arc<AudioDevice>>,
         recording_sender: Option<mpsc::UnboundedSender<super::recording_state::AudioChunk>>,
     ) -> Result<()> {
-        use super::capture::get_current_backend;
-        let backend = get_current_backend();
-        info!("🎙️ Starting audio streams with backend: {:?}", backend);
+        let backend = get_backend_for(CaptureSource::System);
+        info!("🎙️ Starting audio streams with system backend: {:?}", backend);
 
         // Start microphone stream
         if let Some(mic_device) = microphone_device {
-            info!("🎤 Creating microphone stream: {} (always uses CPAL)", mic_device.name);
-            match AudioStream::create(mic_device.clone(), self.state.clone(), DeviceType::Microphone, recording_sender.clone()).await {
+            info!("🎤 Creating microphone stream: {} (backend: {:?})", mic_device.name, get_backend_for(CaptureSource::Microphone));
+            let preroll = self.take_preroll_for(&mic_device, &DeviceType::Microphone);
+            match AudioStream::create_with_preroll(mic_device.clone(), self.state.clone(), DeviceType::Microphone, recording_sender.clone(), preroll).await {
                 Ok(stream) => {
                     self.state.set_microphone_device(mic_device);
                     self.microphone_stream = Some(stream);
@@ -389,7 +219,8 @@ impl AudioStreamManager {
         // Start system audio stream
         if let Some(sys_device) = system_device {
             info!("🔊 Creating system audio stream: {} (backend: {:?})", sys_device.name, backend);
-            match AudioStream::create(sys_device.clone(), self.state.clone(), DeviceType::System, recording_sender.clone()).await {
+            let preroll = self.take_preroll_for(&sys_device, &DeviceType::System);
+            match AudioStream::create_with_preroll(sys_device.clone(), self.state.clone(), DeviceType::System, recording_sender.clone(), preroll).await {
                 Ok(stream) => {
                     self.state.set_system_device(sys_device);
                     self.system_stream = Some(stream);
@@ -404,6 +235,9 @@ impl AudioStreamManager {
             info!("ℹ️ No system device specified, skipping system audio stream");
         }
 
+        // Pre-roll only applies to the first start, not to restarts during failover
+        self.preroll.clear();
+
         // Ensure at least one stream was created
         if self.microphone_stream.is_none() && self.system_stream.is_none() {
             return Err(anyhow::anyhow!("No audio streams could be created"));
@@ -412,9 +246,17 @@ impl AudioStreamManager {
         Ok(())
     }
 
+    /// Makes `stream` on `device` the microphone stream and returns the one it replaces,
+    /// leaving the system audio stream running
+    pub fn replace_microphone_stream(&mut self, device: Arc<AudioDevice>, stream: AudioStream) -> Option<AudioStream> {
+        self.state.set_microphone_device(device);
+        self.microphone_stream.replace(stream)
+    }
+
     /// Stop all audio streams
     pub fn stop_streams(&mut self) -> Result<()> {
         info!("Stopping all audio streams");
+        super::resample::clear_paths();
 
         let mut errors = Vec::new();
 
@@ -454,6 +296,31 @@ impl AudioStreamManager {
         count
     }
 
+    /// Suspend sample delivery on every stream (devices stay open for instant resume)
+    pub fn pause_streams(&self) {
+        for stream in self.microphone_stream.iter().chain(self.system_stream.iter()) {
+            stream.pause();
+        }
+    }
+
+    pub fn resume_streams(&self) {
+        for stream in self.microphone_stream.iter().chain(self.system_stream.iter()) {
+            stream.resume();
+        }
+    }
+
+    /// Levels of every active stream since the previous call
+    pub fn stream_levels(&self) -> Vec<(&AudioDevice, DeviceType, CaptureLevels)> {
+        let mut levels = Vec::with_capacity(2);
+        if let Some(ref stream) = self.microphone_stream {
+            levels.push((stream.device(), DeviceType::Microphone, stream.levels()));
+        }
+        if let Some(ref stream) = self.system_stream {
+            levels.push((stream.device(), DeviceType::System, stream.levels()));
+        }
+        levels
+    }
+
     /// Check if any streams are active
     pub fn has_active_streams(&self) -> bool {
         self.microphone_stream.is_some() || self.system_stream.is_some()
@@ -3,6 +3,7 @@ use crate::audio::{
     start_system_audio_capture, list_system_audio_devices, check_system_audio_permissions,
     SystemAudioDetector, SystemAudioEvent, new_system_audio_callback
 };
+use crate::audio::capture::{self, AudioProcessInfo, TapTarget};
 use std::sync::{Arc, Mutex};
 use anyhow::Result;
 
@@ -96,6 +97,44 @@ pub async fn get_system_audio_monitoring_status(
     Ok(detector_guard.is_some())
 }
 
+/// List processes that can be tapped individually (macOS only)
+#[command]
+pub async fn list_audio_processes() -> Result<Vec<AudioProcessInfo>, String> {
+    capture::list_audio_processes()
+        .map_err(|e| format!("Failed to list audio processes: {}", e))
+}
+
+/// Record only one application's audio, or the whole system mix when `pid` is None
+///
+/// Takes effect the next time system audio capture starts.
+#[command]
+pub async fn set_tap_target(pid: Option<i32>) -> Result<TapTarget, String> {
+    let target = match pid {
+        Some(pid) => TapTarget::Include(vec![pid]),
+        None => TapTarget::System,
+    };
+    capture::set_tap_target(target.clone());
+    Ok(target)
+}
+
+/// Record the whole system mix except the given applications (e.g. music players)
+#[command]
+pub async fn set_tap_excluded_processes(pids: Vec<i32>) -> Result<TapTarget, String> {
+    let target = if pids.is_empty() {
+        TapTarget::System
+    } else {
+        TapTarget::Exclude(pids)
+    };
+    capture::set_tap_target(target.clone());
+    Ok(target)
+}
+
+/// Current system audio tap target
+#[command]
+pub async fn get_tap_target() -> TapTarget {
+    capture::get_tap_target()
+}
+
 /// Initialize the system audio detector state in Tauri app
 pub fn init_system_audio_state() -> SystemAudioDetectorState {
     Arc::new(Mutex::new(None))
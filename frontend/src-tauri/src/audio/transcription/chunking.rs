@@ -0,0 +1,236 @@
+// audio/transcription/chunking.rs
+//
+// Overlapping windows for speech longer than Whisper's 30 second window.
+//
+// Whisper decodes 30 seconds at a time. Given a longer VAD segment it moves on by whole
+// windows (timestamps are off, see whisper_engine.rs), so a word spoken across the 30 second
+// mark comes out cut in two, twice, or not at all. Long segments are transcribed here in 30
+// second windows that overlap by 5 seconds instead; each boundary word is then heard whole by
+// at least one window. The windows' words are joined where they agree: the longest run of
+// words both transcribed inside the overlap, kept once. When they don't agree on at least two
+// words, the overlap is split at its middle by word timings.
+
+use std::future::Future;
+use std::ops::Range;
+
+use super::words::{offset_words, TranscriptWord};
+
+const SAMPLE_RATE: usize = 16000;
+
+pub const WINDOW_SECS: usize = 30;
+
+pub const OVERLAP_SECS: usize = 5;
+
+/// Fewest matching words taken as agreement; a single "the" or "and" matches by chance
+const MIN_MATCH_WORDS: usize = 2;
+
+/// Words starting this close to the overlap still count as in it, since word timings are
+/// only as good as Whisper's token timestamps
+const TIMING_SLACK_SECS: f64 = 0.5;
+
+/// Whether samples at 16kHz are too long for one window
+pub fn needs_windows(samples: usize) -> bool {
+    samples > WINDOW_SECS * SAMPLE_RATE
+}
+
+/// Sample ranges of the overlapping windows covering `samples` at 16kHz
+pub fn windows(samples: usize) -> Vec<Range<usize>> {
+    let window = WINDOW_SECS * SAMPLE_RATE;
+    let step = (WINDOW_SECS - OVERLAP_SECS) * SAMPLE_RATE;
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    loop {
+        ranges.push(start..samples.min(start + window));
+        start += step;
+        if start + OVERLAP_SECS * SAMPLE_RATE >= samples {
+            return ranges;
+        }
+    }
+}
+
+/// Lowercase with punctuation trimmed, so "Okay," in one window matches "okay" in the next
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()
+}
+
+/// Longest run of equal words in `a` and `b`: (start in a, start in b, length)
+fn longest_common_run(a: &[String], b: &[String]) -> (usize, usize, usize) {
+    let mut best = (0, 0, 0);
+    // lengths[j + 1] is the run ending at a[i] and b[j]
+    let mut lengths = vec![0usize; b.len() + 1];
+    for (i, word) in a.iter().enumerate() {
+        for j in (0..b.len()).rev() {
+            lengths[j + 1] = if !word.is_empty() && *word == b[j] { lengths[j] + 1 } else { 0 };
+            if lengths[j + 1] > best.2 {
+                best = (i + 1 - lengths[j + 1], j + 1 - lengths[j + 1], lengths[j + 1]);
+            }
+        }
+    }
+    best
+}
+
+/// Appends the words of the next window, timed from the chunk start, to `merged`; the two
+/// overlap from `overlap.start` to `overlap.end` seconds
+fn merge_window(merged: &mut Vec<TranscriptWord>, next: Vec<TranscriptWord>, overlap: Range<f64>) {
+    let tail_start = merged
+        .iter()
+        .position(|word| word.end > overlap.start - TIMING_SLACK_SECS)
+        .unwrap_or(merged.len());
+    let head_len = next.iter().take_while(|word| word.start < overlap.end + TIMING_SLACK_SECS).count();
+
+    let tail: Vec<String> = merged[tail_start..].iter().map(|word| normalize(&word.text)).collect();
+    let head: Vec<String> = next[..head_len].iter().map(|word| normalize(&word.text)).collect();
+    let (in_tail, in_head, len) = longest_common_run(&tail, &head);
+
+    if len >= MIN_MATCH_WORDS {
+        // Both heard these words; anything either window heard around them at its edge is cut off
+        merged.truncate(tail_start + in_tail + len);
+        merged.extend(next.into_iter().skip(in_head + len));
+    } else {
+        let middle = (overlap.start + overlap.end) / 2.0;
+        merged.retain(|word| word.start < middle);
+        merged.extend(next.into_iter().filter(|word| word.start >= middle));
+    }
+}
+
+/// Words with times spread evenly over `duration`, for a window transcribed without them
+fn spread_words(text: &str, duration: f64) -> Vec<TranscriptWord> {
+    let parts: Vec<&str> = text.split_whitespace().collect();
+    let step = duration / parts.len().max(1) as f64;
+    parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| TranscriptWord::new(part.to_string(), i as f64 * step, (i + 1) as f64 * step, None))
+        .collect()
+}
+
+/// A chunk transcribed window by window
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowedTranscript {
+    pub text: String,
+    /// Mean over the windows
+    pub confidence: f32,
+    /// Relative to the chunk; empty when a window came back without word timings
+    pub words: Vec<TranscriptWord>,
+}
+
+/// Transcribes 16kHz `samples` in overlapping windows with `transcribe`, which returns the
+/// text, confidence and words of one window
+pub async fn transcribe_in_windows<F, Fut, E>(samples: &[f32], mut transcribe: F) -> Result<WindowedTranscript, E>
+where
+    F: FnMut(Vec<f32>) -> Fut,
+    Fut: Future<Output = Result<(String, f32, Vec<TranscriptWord>), E>>,
+{
+    let ranges = windows(samples.len());
+    let mut merged: Vec<TranscriptWord> = Vec::new();
+    let mut previous_end = 0.0;
+    let mut confidence = 0.0;
+    let mut timed = true;
+
+    for range in &ranges {
+        let start = range.start as f64 / SAMPLE_RATE as f64;
+        let end = range.end as f64 / SAMPLE_RATE as f64;
+        let (text, window_confidence, words) = transcribe(samples[range.clone()].to_vec()).await?;
+        confidence += window_confidence;
+
+        let words = if words.is_empty() && !text.trim().is_empty() {
+            timed = false;
+            spread_words(&text, end - start)
+        } else {
+            words
+        };
+        merge_window(&mut merged, offset_words(words, start), start..previous_end);
+        previous_end = end;
+    }
+
+    let text = merged.iter().map(|word| word.text.as_str()).collect::<Vec<_>>().join(" ");
+    Ok(WindowedTranscript {
+        text,
+        confidence: confidence / ranges.len() as f32,
+        words: if timed { merged } else { Vec::new() },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: usize = SAMPLE_RATE;
+
+    /// Words of `text` one second apart from `start`
+    fn words(text: &str, start: f64) -> Vec<TranscriptWord> {
+        text.split_whitespace()
+            .enumerate()
+            .map(|(i, word)| TranscriptWord::new(word.to_string(), start + i as f64, start + i as f64 + 0.8, Some(0.9)))
+            .collect()
+    }
+
+    fn text(words: &[TranscriptWord]) -> String {
+        words.iter().map(|word| word.text.as_str()).collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn test_windows() {
+        assert!(!needs_windows(30 * SECOND));
+        assert_eq!(windows(20 * SECOND), vec![0..20 * SECOND]);
+        assert_eq!(windows(32 * SECOND), vec![0..30 * SECOND, 25 * SECOND..32 * SECOND]);
+        assert_eq!(
+            windows(70 * SECOND),
+            vec![0..30 * SECOND, 25 * SECOND..55 * SECOND, 50 * SECOND..70 * SECOND]
+        );
+        // A last window that would only repeat the overlap is left out
+        assert_eq!(windows(55 * SECOND), vec![0..30 * SECOND, 25 * SECOND..55 * SECOND]);
+    }
+
+    #[test]
+    fn test_merge_keeps_overlap_once() {
+        // The first window ends mid-word; the second starts mid-word
+        let mut merged = words("so the budget for next quarter is fine and we can hi", 20.0);
+        let next = words("ter is fine and we can hire two more people", 26.0);
+        merge_window(&mut merged, next, 25.0..30.0);
+        assert_eq!(text(&merged), "so the budget for next quarter is fine and we can hire two more people");
+    }
+
+    #[test]
+    fn test_merge_ignores_case_and_punctuation() {
+        let mut merged = words("we agreed. Okay, let's", 26.0);
+        let next = words("okay let's move on", 27.0);
+        merge_window(&mut merged, next, 25.0..30.0);
+        assert_eq!(text(&merged), "we agreed. Okay, let's move on");
+    }
+
+    #[test]
+    fn test_merge_without_agreement_splits_at_the_middle() {
+        let mut merged = words("one two three four five", 25.0);
+        let next = words("uno dos tres cuatro cinco seis", 25.0);
+        merge_window(&mut merged, next, 25.0..30.0);
+        assert_eq!(text(&merged), "one two three cuatro cinco seis");
+    }
+
+    #[test]
+    fn test_longest_common_run() {
+        let split = |text: &str| text.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(longest_common_run(&split("a b c d"), &split("x c d e")), (2, 1, 2));
+        assert_eq!(longest_common_run(&split("a b"), &split("c d")).2, 0);
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_in_windows() {
+        // 40 seconds of audio whose sample values are their second, "spoken" as one word a second
+        let samples: Vec<f32> = (0..40 * SECOND).map(|i| (i / SECOND) as f32).collect();
+        let transcribe = |window: Vec<f32>| async move {
+            let first = window[0] as usize;
+            let seconds = window.len() / SECOND;
+            let text = (first..first + seconds).map(|s| format!("w{}", s)).collect::<Vec<_>>().join(" ");
+            let words = words(&text, 0.0);
+            Ok::<_, String>((text, 0.8, words))
+        };
+
+        let transcript = transcribe_in_windows(&samples, transcribe).await.unwrap();
+        let expected = (0..40).map(|s| format!("w{}", s)).collect::<Vec<_>>().join(" ");
+        assert_eq!(transcript.text, expected);
+        assert_eq!(transcript.words.len(), 40);
+        assert_eq!(transcript.words[39].start, 39.0);
+        assert_eq!(transcript.confidence, 0.8);
+    }
+}
@@ -0,0 +1,501 @@
+// audio/transcription/cloud_providers.rs
+//
+// Hosted speech-to-text providers (OpenAI Whisper API, Deepgram, AssemblyAI).
+//
+// Each VAD segment is encoded as 16kHz 16-bit WAV and sent to the provider's REST API.
+// These trade privacy (audio leaves the machine) for accuracy or speed on machines that
+// can't run large local models.
+
+use super::provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+use super::words::TranscriptWord;
+use crate::network::GuardedClient;
+use async_trait::async_trait;
+use log::{debug, warn};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SAMPLE_RATE: u32 = 16000;
+
+/// Longest a single segment may take, including AssemblyAI's queue
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+const ASSEMBLYAI_POLL_INTERVAL: Duration = Duration::from_millis(750);
+
+/// Encode 16kHz mono samples as 16-bit PCM WAV
+pub fn encode_wav_16k(samples: &[f32]) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for &sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        wav.extend_from_slice(&value.to_le_bytes());
+    }
+    wav
+}
+
+/// Language hint to send, or None for automatic detection
+fn language_code(language: Option<String>) -> Option<String> {
+    language.filter(|lang| !lang.is_empty() && lang != "auto" && lang != "auto-translate")
+}
+
+/// Providers live as long as the transcription engine, so their client follows local-only
+/// mode changes
+fn http_client() -> GuardedClient {
+    GuardedClient::new(|builder| builder.timeout(REQUEST_TIMEOUT))
+        // A default client would skip the local-only guard
+        .expect("Failed to create the HTTP client")
+}
+
+/// Length of 16kHz samples, as metered for usage
+fn audio_seconds(samples: &[f32]) -> f64 {
+    samples.len() as f64 / SAMPLE_RATE as f64
+}
+
+fn request_failed(provider: &str, e: impl std::fmt::Display) -> TranscriptionError {
+    TranscriptionError::EngineFailed(format!("{} request failed: {}", provider, e))
+}
+
+/// Turn a non-success response into an error that includes the provider's message
+async fn check_status(provider: &str, response: reqwest::Response) -> Result<reqwest::Response, TranscriptionError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(TranscriptionError::EngineFailed(format!(
+        "{} returned {}: {}",
+        provider,
+        status,
+        body.chars().take(300).collect::<String>()
+    )))
+}
+
+// ============================================================================
+// OPENAI WHISPER API
+// ============================================================================
+
+pub struct OpenAiWhisperProvider {
+    client: GuardedClient,
+    api_key: String,
+    model: String,
+}
+
+impl OpenAiWhisperProvider {
+    pub const DEFAULT_MODEL: &'static str = "whisper-1";
+
+    pub fn new(api_key: String, model: String) -> Self {
+        Self { client: http_client(), api_key, model }
+    }
+}
+
+#[derive(Deserialize)]
+struct OpenAiTranscription {
+    text: String,
+}
+
+#[async_trait]
+impl TranscriptionProvider for OpenAiWhisperProvider {
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        let file = reqwest::multipart::Part::bytes(encode_wav_16k(&audio))
+            .file_name("segment.wav")
+            .mime_str("audio/wav")
+            .map_err(|e| request_failed("OpenAI", e))?;
+        let mut form = reqwest::multipart::Form::new()
+            .part("file", file)
+            .text("model", self.model.clone())
+            .text("response_format", "json");
+        if let Some(lang) = language_code(language) {
+            form = form.text("language", lang);
+        }
+        if let Some(prompt) = super::glossary::initial_prompt() {
+            form = form.text("prompt", prompt);
+        }
+
+        let response = self
+            .client
+            .get()
+            .post("https://api.openai.com/v1/audio/transcriptions")
+            .bearer_auth(&self.api_key)
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| request_failed("OpenAI", e))?;
+        let result: OpenAiTranscription = check_status("OpenAI", response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| request_failed("OpenAI", e))?;
+        crate::usage::meter::record_audio("openai", &self.model, audio_seconds(&audio));
+
+        Ok(TranscriptResult {
+            text: result.text.trim().to_string(),
+            confidence: None,
+            is_partial: false,
+            words: Vec::new(),
+        })
+    }
+
+    async fn is_model_loaded(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "OpenAI Whisper API"
+    }
+
+    fn is_remote(&self) -> bool {
+        true
+    }
+}
+
+// ============================================================================
+// DEEPGRAM
+// ============================================================================
+
+pub struct DeepgramProvider {
+    client: GuardedClient,
+    api_key: String,
+    model: String,
+}
+
+impl DeepgramProvider {
+    pub const DEFAULT_MODEL: &'static str = "nova-2";
+
+    pub fn new(api_key: String, model: String) -> Self {
+        Self { client: http_client(), api_key, model }
+    }
+}
+
+#[derive(Deserialize)]
+struct DeepgramResponse {
+    results: DeepgramResults,
+}
+
+#[derive(Deserialize)]
+struct DeepgramResults {
+    channels: Vec<DeepgramChannel>,
+}
+
+#[derive(Deserialize)]
+struct DeepgramChannel {
+    alternatives: Vec<DeepgramAlternative>,
+}
+
+#[derive(Deserialize)]
+struct DeepgramAlternative {
+    transcript: String,
+    confidence: f32,
+    #[serde(default)]
+    words: Vec<DeepgramWord>,
+}
+
+#[derive(Deserialize)]
+struct DeepgramWord {
+    word: String,
+    punctuated_word: Option<String>,
+    start: f64,
+    end: f64,
+    confidence: f32,
+}
+
+#[async_trait]
+impl TranscriptionProvider for DeepgramProvider {
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        let mut query = vec![("model", self.model.clone()), ("smart_format", "true".to_string())];
+        match language_code(language) {
+            Some(lang) => query.push(("language", lang)),
+            None => query.push(("detect_language", "true".to_string())),
+        }
+        for term in super::glossary::glossary_terms() {
+            query.push(("keywords", term));
+        }
+
+        let response = self
+            .client
+            .get()
+            .post("https://api.deepgram.com/v1/listen")
+            .header("Authorization", format!("Token {}", self.api_key))
+            .header("Content-Type", "audio/wav")
+            .query(&query)
+            .body(encode_wav_16k(&audio))
+            .send()
+            .await
+            .map_err(|e| request_failed("Deepgram", e))?;
+        let result: DeepgramResponse = check_status("Deepgram", response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| request_failed("Deepgram", e))?;
+        crate::usage::meter::record_audio("deepgram", &self.model, audio_seconds(&audio));
+
+        let best = result
+            .results
+            .channels
+            .into_iter()
+            .next()
+            .and_then(|channel| channel.alternatives.into_iter().next());
+        Ok(match best {
+            Some(alternative) => TranscriptResult {
+                text: alternative.transcript.trim().to_string(),
+                confidence: Some(alternative.confidence),
+                is_partial: false,
+                words: alternative
+                    .words
+                    .into_iter()
+                    .map(|w| TranscriptWord::new(w.punctuated_word.unwrap_or(w.word), w.start, w.end, Some(w.confidence)))
+                    .collect(),
+            },
+            None => TranscriptResult { text: String::new(), confidence: None, is_partial: false, words: Vec::new() },
+        })
+    }
+
+    async fn is_model_loaded(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Deepgram"
+    }
+
+    fn is_remote(&self) -> bool {
+        true
+    }
+}
+
+// ============================================================================
+// ASSEMBLYAI
+// ============================================================================
+
+pub struct AssemblyAiProvider {
+    client: GuardedClient,
+    api_key: String,
+    model: String,
+}
+
+impl AssemblyAiProvider {
+    pub const DEFAULT_MODEL: &'static str = "best";
+    const BASE_URL: &'static str = "https://api.assemblyai.com/v2";
+
+    pub fn new(api_key: String, model: String) -> Self {
+        Self { client: http_client(), api_key, model }
+    }
+}
+
+#[derive(Deserialize)]
+struct AssemblyAiUpload {
+    upload_url: String,
+}
+
+#[derive(Deserialize)]
+struct AssemblyAiTranscript {
+    id: String,
+    status: String,
+    text: Option<String>,
+    confidence: Option<f32>,
+    error: Option<String>,
+    words: Option<Vec<AssemblyAiWord>>,
+}
+
+/// Times are in milliseconds
+#[derive(Deserialize)]
+struct AssemblyAiWord {
+    text: String,
+    start: u64,
+    end: u64,
+    confidence: f32,
+}
+
+#[async_trait]
+impl TranscriptionProvider for AssemblyAiProvider {
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        // AssemblyAI transcribes uploaded files asynchronously: upload, submit, then poll
+        let response = self
+            .client
+            .get()
+            .post(format!("{}/upload", Self::BASE_URL))
+            .header("authorization", &self.api_key)
+            .body(encode_wav_16k(&audio))
+            .send()
+            .await
+            .map_err(|e| request_failed("AssemblyAI", e))?;
+        let upload: AssemblyAiUpload = check_status("AssemblyAI", response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| request_failed("AssemblyAI", e))?;
+
+        let mut request = serde_json::json!({
+            "audio_url": upload.upload_url,
+            "speech_model": self.model,
+        });
+        match language_code(language) {
+            Some(lang) => request["language_code"] = serde_json::json!(lang),
+            None => request["language_detection"] = serde_json::json!(true),
+        }
+        let terms = super::glossary::glossary_terms();
+        if !terms.is_empty() {
+            request["word_boost"] = serde_json::json!(terms);
+        }
+
+        let response = self
+            .client
+            .get()
+            .post(format!("{}/transcript", Self::BASE_URL))
+            .header("authorization", &self.api_key)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| request_failed("AssemblyAI", e))?;
+        let mut transcript: AssemblyAiTranscript = check_status("AssemblyAI", response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| request_failed("AssemblyAI", e))?;
+
+        let started = std::time::Instant::now();
+        loop {
+            match transcript.status.as_str() {
+                "completed" => break,
+                "error" => {
+                    return Err(TranscriptionError::EngineFailed(format!(
+                        "AssemblyAI transcription failed: {}",
+                        transcript.error.unwrap_or_default()
+                    )))
+                }
+                status => debug!("AssemblyAI transcript {} is {}", transcript.id, status),
+            }
+            if started.elapsed() > REQUEST_TIMEOUT {
+                warn!("AssemblyAI transcript {} timed out", transcript.id);
+                return Err(TranscriptionError::EngineFailed("AssemblyAI transcription timed out".to_string()));
+            }
+
+            tokio::time::sleep(ASSEMBLYAI_POLL_INTERVAL).await;
+            let response = self
+                .client
+                .get()
+                .get(format!("{}/transcript/{}", Self::BASE_URL, transcript.id))
+                .header("authorization", &self.api_key)
+                .send()
+                .await
+                .map_err(|e| request_failed("AssemblyAI", e))?;
+            transcript = check_status("AssemblyAI", response)
+                .await?
+                .json()
+                .await
+                .map_err(|e| request_failed("AssemblyAI", e))?;
+        }
+        crate::usage::meter::record_audio("assemblyAI", &self.model, audio_seconds(&audio));
+
+        Ok(TranscriptResult {
+            text: transcript.text.unwrap_or_default().trim().to_string(),
+            confidence: transcript.confidence,
+            is_partial: false,
+            words: transcript
+                .words
+                .unwrap_or_default()
+                .into_iter()
+                .map(|w| TranscriptWord::new(w.text, w.start as f64 / 1000.0, w.end as f64 / 1000.0, Some(w.confidence)))
+                .collect(),
+        })
+    }
+
+    async fn is_model_loaded(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "AssemblyAI"
+    }
+
+    fn is_remote(&self) -> bool {
+        true
+    }
+}
+
+/// Provider ids (as stored in transcript settings) that transcribe in the cloud
+pub const CLOUD_PROVIDER_IDS: &[&str] = &["openai", "deepgram", "assemblyAI"];
+
+pub fn is_cloud_provider(provider: &str) -> bool {
+    CLOUD_PROVIDER_IDS.contains(&provider)
+}
+
+/// Create a hosted provider; an empty model selects the provider's default
+pub fn create_cloud_provider(
+    provider: &str,
+    model: &str,
+    api_key: Option<String>,
+) -> Result<Arc<dyn TranscriptionProvider>, String> {
+    crate::network::ensure_online(&format!("Cloud transcription with {}", provider))?;
+    let api_key = api_key
+        .filter(|key| !key.trim().is_empty())
+        .ok_or_else(|| format!("No API key configured for transcription provider '{}'", provider))?;
+    let model_or = |default: &str| if model.is_empty() { default.to_string() } else { model.to_string() };
+
+    match provider {
+        "openai" => Ok(Arc::new(OpenAiWhisperProvider::new(api_key, model_or(OpenAiWhisperProvider::DEFAULT_MODEL)))),
+        "deepgram" => Ok(Arc::new(DeepgramProvider::new(api_key, model_or(DeepgramProvider::DEFAULT_MODEL)))),
+        "assemblyAI" => Ok(Arc::new(AssemblyAiProvider::new(api_key, model_or(AssemblyAiProvider::DEFAULT_MODEL)))),
+        other => Err(format!("Unknown cloud transcription provider: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wav_encoding_header_and_samples() {
+        let wav = encode_wav_16k(&[0.0, 1.0, -1.0]);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 6);
+        assert_eq!(i16::from_le_bytes([wav[46], wav[47]]), i16::MAX);
+        assert_eq!(i16::from_le_bytes([wav[48], wav[49]]), -i16::MAX);
+    }
+
+    #[test]
+    fn test_create_requires_api_key() {
+        assert!(create_cloud_provider("deepgram", "", None).is_err());
+        assert!(create_cloud_provider("deepgram", "", Some("  ".to_string())).is_err());
+        assert!(create_cloud_provider("deepgram", "", Some("key".to_string())).is_ok());
+        assert!(language_code(Some("auto".to_string())).is_none());
+    }
+}
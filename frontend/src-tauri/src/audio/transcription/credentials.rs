@@ -0,0 +1,33 @@
+// audio/transcription/credentials.rs
+//
+// API keys for hosted transcription providers, kept in the OS credential store
+// (macOS Keychain, Windows Credential Manager, Secret Service on Linux) instead of the
+// settings database. Keys saved by older versions still live in the database and are
+// used as a fallback until the user saves them again.
+
+use crate::secrets;
+use anyhow::Result;
+use log::info;
+
+const SERVICE: &str = "com.meetily.ai.transcription";
+
+/// Each workspace keeps its own transcription keys
+fn service() -> String {
+    crate::workspaces::scoped_service(SERVICE)
+}
+
+/// Store (or replace) the API key for a provider
+pub fn store_api_key(provider: &str, api_key: &str) -> Result<()> {
+    secrets::store(&service(), provider, api_key)?;
+    info!("🔐 Stored transcription API key for {} in the system credential store", provider);
+    Ok(())
+}
+
+/// API key for a provider, or None when none is stored
+pub fn load_api_key(provider: &str) -> Result<Option<String>> {
+    secrets::load(&service(), provider)
+}
+
+pub fn delete_api_key(provider: &str) -> Result<()> {
+    secrets::delete(&service(), provider)
+}
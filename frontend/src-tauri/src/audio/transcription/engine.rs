@@ -2,9 +2,12 @@
 //
 // TranscriptionEngine enum and model initialization/validation logic.
 
-use super::provider::TranscriptionProvider;
+use super::provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+use super::cloud_providers::{create_cloud_provider, is_cloud_provider};
 use log::{info, warn};
-use std::sync::Arc;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
 use tauri::{AppHandle, Manager, Runtime};
 
 // ============================================================================
@@ -12,6 +15,7 @@ use tauri::{AppHandle, Manager, Runtime};
 // ============================================================================
 
 // Transcription engine abstraction to support multiple providers
+#[derive(Clone)]
 pub enum TranscriptionEngine {
     Whisper(Arc<crate::whisper_engine::WhisperEngine>),  // Direct access (backward compat)
     Parakeet(Arc<crate::parakeet_engine::ParakeetEngine>), // Direct access (backward compat)
@@ -37,6 +41,57 @@ impl TranscriptionEngine {
         }
     }
 
+    /// Transcribe 16kHz mono audio without emitting any events
+    pub async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        let result = match self {
+            Self::Whisper(engine) => engine
+                .transcribe_audio_with_words(audio, language)
+                .await
+                .map(|(text, confidence, is_partial, words)| TranscriptResult {
+                    text: text.trim().to_string(),
+                    confidence: Some(confidence),
+                    is_partial,
+                    words,
+                })
+                .map_err(|e| TranscriptionError::EngineFailed(e.to_string())),
+            Self::Parakeet(engine) => engine
+                .transcribe_audio(audio)
+                .await
+                .map(|text| TranscriptResult {
+                    text: text.trim().to_string(),
+                    confidence: None,
+                    is_partial: false,
+                    words: Vec::new(),
+                })
+                .map_err(|e| TranscriptionError::EngineFailed(e.to_string())),
+            Self::Provider(provider) => provider.transcribe(audio, language).await,
+        };
+        result.map(|result| TranscriptResult { text: super::glossary::apply_aliases(&result.text), ..result })
+    }
+
+    /// Detect the spoken language, for engines that support it
+    ///
+    /// Only local Whisper does; Parakeet is English-only and hosted providers detect the
+    /// language server-side on every request.
+    pub async fn detect_language(&self, audio: &[f32]) -> Option<anyhow::Result<(String, f32)>> {
+        match self {
+            Self::Whisper(engine) => Some(engine.detect_language(audio).await),
+            Self::Parakeet(_) | Self::Provider(_) => None,
+        }
+    }
+
+    /// Whether audio is sent to a hosted API
+    pub fn is_remote(&self) -> bool {
+        match self {
+            Self::Whisper(_) | Self::Parakeet(_) => false,
+            Self::Provider(provider) => provider.is_remote(),
+        }
+    }
+
     /// Get the provider name for logging
     pub fn provider_name(&self) -> &str {
         match self {
@@ -51,10 +106,38 @@ impl TranscriptionEngine {
 // MODEL VALIDATION AND INITIALIZATION
 // ============================================================================
 
-/// Validate that transcription models (Whisper or Parakeet) are ready before starting recording
-pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
-    // Check transcript configuration to determine which engine to validate
-    let config = match crate::api::api::api_get_transcript_config(
+/// Provider chosen for the next meeting, overriding the saved transcript settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeetingProviderSelection {
+    pub provider: String,
+    /// Empty selects the provider's default model
+    #[serde(default)]
+    pub model: String,
+}
+
+static MEETING_PROVIDER: Lazy<RwLock<Option<MeetingProviderSelection>>> = Lazy::new(|| RwLock::new(None));
+
+pub fn get_meeting_provider() -> Option<MeetingProviderSelection> {
+    MEETING_PROVIDER.read().unwrap().clone()
+}
+
+/// Applies to the next recording; None returns to the saved transcript settings
+pub fn set_meeting_provider(selection: Option<MeetingProviderSelection>) {
+    match &selection {
+        Some(s) => info!("📝 Next meeting will be transcribed with {} ({})", s.provider, if s.model.is_empty() { "default model" } else { &s.model }),
+        None => info!("📝 Next meeting will use the saved transcription provider"),
+    }
+    *MEETING_PROVIDER.write().unwrap() = selection;
+}
+
+/// The meeting ended; later meetings use the saved settings again
+pub fn clear_meeting_provider() {
+    MEETING_PROVIDER.write().unwrap().take();
+}
+
+/// Saved transcript settings with the per-meeting selection applied
+async fn effective_transcript_config<R: Runtime>(app: &AppHandle<R>) -> crate::api::api::TranscriptConfig {
+    let saved = match crate::api::api::api_get_transcript_config(
         app.clone(),
         app.clone().state(),
         None,
@@ -63,7 +146,7 @@ pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>)
     {
         Ok(Some(config)) => {
             info!(
-                "📝 Found transcript config - provider: {}, model: {}",
+                "📝 Transcript config - provider: {}, model: {}",
                 config.provider, config.model
             );
             config
@@ -86,6 +169,30 @@ pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>)
         }
     };
 
+    let Some(selection) = get_meeting_provider() else {
+        return saved;
+    };
+    if selection.provider == saved.provider && selection.model.is_empty() {
+        return saved;
+    }
+
+    info!("📝 Using per-meeting provider {} instead of {}", selection.provider, saved.provider);
+    let api_key = if is_cloud_provider(&selection.provider) {
+        crate::api::api::api_get_transcript_api_key(app.clone(), app.clone().state(), selection.provider.clone(), None)
+            .await
+            .ok()
+            .filter(|key| !key.is_empty())
+    } else {
+        None
+    };
+    crate::api::api::TranscriptConfig { provider: selection.provider, model: selection.model, api_key }
+}
+
+/// Validate that transcription models (Whisper or Parakeet) are ready before starting recording
+pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    // Check transcript configuration to determine which engine to validate
+    let config = effective_transcript_config(app).await;
+
     // Validate based on provider
     match config.provider.as_str() {
         "localWhisper" => {
@@ -135,10 +242,24 @@ pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>)
                 }
             }
         }
+        provider if is_cloud_provider(provider) => {
+            crate::network::ensure_online(&format!("Cloud transcription with {}", provider))?;
+            info!("🔍 Validating {} API key...", provider);
+            match config.api_key.as_deref().map(str::trim) {
+                Some(key) if !key.is_empty() => {
+                    info!("✅ {} is configured for cloud transcription", provider);
+                    Ok(())
+                }
+                _ => Err(format!(
+                    "No API key configured for {}. Add one in transcription settings or choose a local model.",
+                    provider
+                )),
+            }
+        }
         other => {
             warn!("❌ Unsupported transcription provider for local recording: {}", other);
             Err(format!(
-                "Provider '{}' is not supported for local transcription. Please select 'localWhisper' or 'parakeet'.",
+                "Provider '{}' is not supported for local transcription. Please select 'localWhisper', 'parakeet', 'openai', 'deepgram' or 'assemblyAI'.",
                 other
             ))
         }
@@ -150,37 +271,7 @@ pub async fn get_or_init_transcription_engine<R: Runtime>(
     app: &AppHandle<R>,
 ) -> Result<TranscriptionEngine, String> {
     // Get provider configuration from API
-    let config = match crate::api::api::api_get_transcript_config(
-        app.clone(),
-        app.clone().state(),
-        None,
-    )
-    .await
-    {
-        Ok(Some(config)) => {
-            info!(
-                "📝 Transcript config - provider: {}, model: {}",
-                config.provider, config.model
-            );
-            config
-        }
-        Ok(None) => {
-            info!("📝 No transcript config found, defaulting to localWhisper");
-            crate::api::api::TranscriptConfig {
-                provider: "localWhisper".to_string(),
-                model: "large-v3".to_string(),
-                api_key: None,
-            }
-        }
-        Err(e) => {
-            warn!("⚠️ Failed to get transcript config: {}, defaulting to localWhisper", e);
-            crate::api::api::TranscriptConfig {
-                provider: "localWhisper".to_string(),
-                model: "large-v3".to_string(),
-                api_key: None,
-            }
-        }
-    };
+    let config = effective_transcript_config(app).await;
 
     // Initialize the appropriate engine based on provider
     match config.provider.as_str() {
@@ -212,6 +303,11 @@ pub async fn get_or_init_transcription_engine<R: Runtime>(
                 }
             }
         }
+        provider if is_cloud_provider(provider) => {
+            info!("☁️ Initializing {} cloud transcription (model: {})", provider, config.model);
+            let cloud = create_cloud_provider(provider, &config.model, config.api_key)?;
+            Ok(TranscriptionEngine::Provider(cloud))
+        }
         "localWhisper" | _ => {
             info!("🎤 Initializing Whisper transcription engine");
             let whisper_engine = get_or_init_whisper(app).await?;
@@ -441,3 +537,44 @@ pub async fn get_or_init_whisper<R: Runtime>(
 
     Ok(engine)
 }
+
+// ============================================================================
+// PROVIDER SELECTION COMMANDS
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionProviderInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    /// Audio never leaves the machine
+    pub local: bool,
+    pub default_model: &'static str,
+}
+
+#[tauri::command]
+pub async fn list_transcription_providers() -> Result<Vec<TranscriptionProviderInfo>, String> {
+    use super::cloud_providers::{AssemblyAiProvider, DeepgramProvider, OpenAiWhisperProvider};
+    Ok(vec![
+        TranscriptionProviderInfo { id: "localWhisper", name: "Whisper (local)", local: true, default_model: "large-v3" },
+        TranscriptionProviderInfo { id: "parakeet", name: "Parakeet (local)", local: true, default_model: "parakeet-tdt-0.6b-v3-int8" },
+        TranscriptionProviderInfo { id: "openai", name: "OpenAI Whisper API", local: false, default_model: OpenAiWhisperProvider::DEFAULT_MODEL },
+        TranscriptionProviderInfo { id: "deepgram", name: "Deepgram", local: false, default_model: DeepgramProvider::DEFAULT_MODEL },
+        TranscriptionProviderInfo { id: "assemblyAI", name: "AssemblyAI", local: false, default_model: AssemblyAiProvider::DEFAULT_MODEL },
+    ])
+}
+
+#[tauri::command]
+pub async fn get_meeting_transcription_provider() -> Result<Option<MeetingProviderSelection>, String> {
+    Ok(get_meeting_provider())
+}
+
+#[tauri::command]
+pub async fn set_meeting_transcription_provider(selection: Option<MeetingProviderSelection>) -> Result<(), String> {
+    if let Some(s) = &selection {
+        if !matches!(s.provider.as_str(), "localWhisper" | "parakeet") && !is_cloud_provider(&s.provider) {
+            return Err(format!("Unknown transcription provider: {}", s.provider));
+        }
+    }
+    set_meeting_provider(selection);
+    Ok(())
+}
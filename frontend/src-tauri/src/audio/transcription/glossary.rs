@@ -0,0 +1,223 @@
+// audio/transcription/glossary.rs
+//
+// Custom vocabulary for domain terms (product names, acronyms, people).
+//
+// Terms are stored in the `glossary_terms` table and cached here. They bias transcription
+// in two ways:
+// - Whisper gets them as its initial prompt, and hosted providers as their keyword/boost
+//   list, which makes the correct spelling far more likely.
+// - Known mis-transcriptions ("aliases", e.g. "cooper netties") are replaced with the
+//   term after transcription, for the cases biasing alone doesn't fix.
+//
+// The cache is refreshed from the database whenever a live or batch transcription starts
+// and after every change made through the commands below.
+
+use crate::database::models::GlossaryTerm;
+use crate::database::repositories::glossary::GlossaryRepository;
+use crate::state::AppState;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Whisper only uses the last ~224 prompt tokens; stay well within that
+const MAX_PROMPT_CHARS: usize = 600;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    pub id: String,
+    pub term: String,
+    pub aliases: Vec<String>,
+}
+
+impl From<GlossaryTerm> for GlossaryEntry {
+    fn from(row: GlossaryTerm) -> Self {
+        Self {
+            aliases: serde_json::from_str(&row.aliases).unwrap_or_default(),
+            id: row.id,
+            term: row.term,
+        }
+    }
+}
+
+struct CompiledGlossary {
+    entries: Vec<GlossaryEntry>,
+    prompt: Option<String>,
+    replacements: Vec<(Regex, String)>,
+}
+
+impl CompiledGlossary {
+    fn new(entries: Vec<GlossaryEntry>) -> Self {
+        let terms: Vec<&str> = entries.iter().map(|e| e.term.as_str()).collect();
+        let replacements = entries
+            .iter()
+            .flat_map(|entry| {
+                entry.aliases.iter().filter_map(move |alias| {
+                    let pattern = format!(r"(?i)\b{}\b", regex::escape(alias.trim()));
+                    Regex::new(&pattern).ok().map(|re| (re, entry.term.clone()))
+                })
+            })
+            .collect();
+
+        Self { prompt: build_prompt(&terms), replacements, entries }
+    }
+
+    fn apply(&self, text: &str) -> String {
+        self.replacements
+            .iter()
+            .fold(text.to_string(), |text, (pattern, term)| pattern.replace_all(&text, term.as_str()).into_owned())
+    }
+}
+
+static GLOSSARY: Lazy<RwLock<CompiledGlossary>> = Lazy::new(|| RwLock::new(CompiledGlossary::new(Vec::new())));
+
+/// Comma-separated terms; terms that no longer fit are left out
+fn build_prompt(terms: &[&str]) -> Option<String> {
+    let mut prompt = String::new();
+    for term in terms {
+        let separator = if prompt.is_empty() { "" } else { ", " };
+        if prompt.len() + separator.len() + term.len() > MAX_PROMPT_CHARS {
+            continue;
+        }
+        prompt.push_str(separator);
+        prompt.push_str(term);
+    }
+    (!prompt.is_empty()).then(|| format!("{}.", prompt))
+}
+
+/// Initial prompt for Whisper, if any terms are defined
+pub fn initial_prompt() -> Option<String> {
+    GLOSSARY.read().unwrap().prompt.clone()
+}
+
+/// Terms for providers that take a keyword/boost list
+pub fn glossary_terms() -> Vec<String> {
+    GLOSSARY.read().unwrap().entries.iter().map(|e| e.term.clone()).collect()
+}
+
+/// Replace known mis-transcriptions with their glossary term
+pub fn apply_aliases(text: &str) -> String {
+    GLOSSARY.read().unwrap().apply(text)
+}
+
+fn set_entries(entries: Vec<GlossaryEntry>) {
+    *GLOSSARY.write().unwrap() = CompiledGlossary::new(entries);
+}
+
+/// Reload the glossary from the database
+pub async fn refresh_glossary<R: Runtime>(app: &AppHandle<R>) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    match GlossaryRepository::list(state.db_manager.pool()).await {
+        Ok(rows) => {
+            let entries: Vec<GlossaryEntry> = rows.into_iter().map(GlossaryEntry::from).collect();
+            if !entries.is_empty() {
+                info!("📖 Loaded {} glossary term(s) for transcription", entries.len());
+            }
+            set_entries(entries);
+        }
+        Err(e) => warn!("Failed to load glossary: {}", e),
+    }
+}
+
+fn normalize_term(term: &str, aliases: Option<Vec<String>>) -> Result<(String, String), String> {
+    let term = term.trim();
+    if term.is_empty() {
+        return Err("Glossary term cannot be empty".to_string());
+    }
+    let aliases: Vec<String> = aliases
+        .unwrap_or_default()
+        .into_iter()
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty() && !a.eq_ignore_ascii_case(term))
+        .collect();
+    let aliases_json = serde_json::to_string(&aliases).map_err(|e| e.to_string())?;
+    Ok((term.to_string(), aliases_json))
+}
+
+#[tauri::command]
+pub async fn list_glossary_terms(state: tauri::State<'_, AppState>) -> Result<Vec<GlossaryEntry>, String> {
+    GlossaryRepository::list(state.db_manager.pool())
+        .await
+        .map(|rows| rows.into_iter().map(GlossaryEntry::from).collect())
+        .map_err(|e| format!("Failed to list glossary terms: {}", e))
+}
+
+#[tauri::command]
+pub async fn add_glossary_term<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    term: String,
+    aliases: Option<Vec<String>>,
+) -> Result<GlossaryEntry, String> {
+    let (term, aliases) = normalize_term(&term, aliases)?;
+    let row = GlossaryRepository::insert(state.db_manager.pool(), &term, &aliases)
+        .await
+        .map_err(|e| format!("Failed to add glossary term '{}': {}", term, e))?;
+    refresh_glossary(&app).await;
+    Ok(row.into())
+}
+
+#[tauri::command]
+pub async fn update_glossary_term<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: String,
+    term: String,
+    aliases: Option<Vec<String>>,
+) -> Result<GlossaryEntry, String> {
+    let (term, aliases) = normalize_term(&term, aliases)?;
+    let row = GlossaryRepository::update(state.db_manager.pool(), &id, &term, &aliases)
+        .await
+        .map_err(|e| format!("Failed to update glossary term: {}", e))?
+        .ok_or_else(|| format!("Glossary term not found: {}", id))?;
+    refresh_glossary(&app).await;
+    Ok(row.into())
+}
+
+#[tauri::command]
+pub async fn delete_glossary_term<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: String,
+) -> Result<bool, String> {
+    let deleted = GlossaryRepository::delete(state.db_manager.pool(), &id)
+        .await
+        .map_err(|e| format!("Failed to delete glossary term: {}", e))?;
+    refresh_glossary(&app).await;
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(term: &str, aliases: &[&str]) -> GlossaryEntry {
+        GlossaryEntry {
+            id: term.to_lowercase(),
+            term: term.to_string(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_prompt_lists_terms_within_limit() {
+        assert_eq!(build_prompt(&["Kubernetes", "Meetily"]), Some("Kubernetes, Meetily.".to_string()));
+        assert_eq!(build_prompt(&[]), None);
+
+        let long_term = "x".repeat(400);
+        let prompt = build_prompt(&[&long_term, &long_term, "Kafka"]).unwrap();
+        assert!(prompt.len() <= MAX_PROMPT_CHARS + 1);
+        assert!(prompt.ends_with("Kafka."));
+    }
+
+    #[test]
+    fn test_aliases_replace_whole_words_case_insensitively() {
+        let glossary = CompiledGlossary::new(vec![entry("Kubernetes", &["cooper netties"]), entry("gRPC", &["g rpc"])]);
+        assert_eq!(glossary.apply("We moved to Cooper Netties and G RPC."), "We moved to Kubernetes and gRPC.");
+        assert_eq!(glossary.apply("cooper nettiesque"), "cooper nettiesque");
+    }
+}
@@ -0,0 +1,233 @@
+// audio/transcription/language.rs
+//
+// Meeting language: auto-detection and per-meeting override.
+//
+// With the language preference on "auto", Whisper guesses the language of every segment
+// independently, and short segments from multilingual speakers are often guessed wrong.
+// Instead, the first ~30 seconds of speech of a meeting are collected and run through
+// Whisper's language detection once; when the result is confident, it is used for every
+// later segment of the meeting. `set_meeting_language` forces a language for the
+// current (or next) meeting and takes precedence over both detection and the global
+// preference. Both are written to the meeting's metadata.json.
+
+use super::engine::TranscriptionEngine;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
+use tauri::{AppHandle, Emitter, Runtime};
+
+pub const LANGUAGE_DETECTED_EVENT: &str = "language-detected";
+
+/// Speech collected before running detection (Whisper looks at 30s at most)
+const DETECTION_SAMPLES: usize = 16000 * 30;
+
+/// Detections below this probability are reported but not applied
+const MIN_DETECTION_PROBABILITY: f32 = 0.5;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DetectedLanguage {
+    pub language: String,
+    pub probability: f32,
+    /// Whether later segments are transcribed with this language
+    pub applied: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MeetingLanguage {
+    pub language_override: Option<String>,
+    pub detected: Option<DetectedLanguage>,
+    /// Language segments are currently transcribed with (None = per-segment auto)
+    pub effective: Option<String>,
+}
+
+#[derive(Default)]
+struct LanguageState {
+    language_override: Option<String>,
+    detected: Option<DetectedLanguage>,
+}
+
+static LANGUAGE_STATE: Lazy<RwLock<LanguageState>> = Lazy::new(|| RwLock::new(LanguageState::default()));
+
+static DETECTION_BUFFER: Lazy<Mutex<Vec<f32>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static DETECTION_STARTED: AtomicBool = AtomicBool::new(false);
+
+fn is_auto(language: Option<&str>) -> bool {
+    matches!(language, None | Some("auto"))
+}
+
+/// Override first, then a confident detection when the preference is "auto"
+///
+/// "auto-translate" is left alone: it asks Whisper to translate whatever it hears.
+fn resolve_language(
+    language_override: Option<&str>,
+    detected: Option<&DetectedLanguage>,
+    preference: Option<&str>,
+) -> Option<String> {
+    if let Some(language) = language_override {
+        return Some(language.to_string());
+    }
+    match detected {
+        Some(detected) if detected.applied && is_auto(preference) => Some(detected.language.clone()),
+        _ => preference.map(str::to_string),
+    }
+}
+
+/// Language to transcribe the current meeting's segments with
+pub fn effective_language() -> Option<String> {
+    let state = LANGUAGE_STATE.read().unwrap();
+    let preference = crate::get_language_preference_internal();
+    resolve_language(state.language_override.as_deref(), state.detected.as_ref(), preference.as_deref())
+}
+
+pub fn get_meeting_language() -> MeetingLanguage {
+    let state = LANGUAGE_STATE.read().unwrap();
+    let preference = crate::get_language_preference_internal();
+    MeetingLanguage {
+        language_override: state.language_override.clone(),
+        detected: state.detected.clone(),
+        effective: resolve_language(state.language_override.as_deref(), state.detected.as_ref(), preference.as_deref()),
+    }
+}
+
+/// A recording session started: forget the previous meeting's detection
+///
+/// An override set before the recording started is kept for this meeting.
+pub fn begin_meeting() {
+    LANGUAGE_STATE.write().unwrap().detected = None;
+    DETECTION_BUFFER.lock().unwrap().clear();
+    DETECTION_STARTED.store(false, Ordering::SeqCst);
+    sync_metadata();
+}
+
+/// The recording session ended; the override only applied to that meeting
+pub fn end_meeting() {
+    let mut state = LANGUAGE_STATE.write().unwrap();
+    state.language_override = None;
+    state.detected = None;
+    DETECTION_BUFFER.lock().unwrap().clear();
+}
+
+fn sync_metadata() {
+    let (detected, language_override) = {
+        let state = LANGUAGE_STATE.read().unwrap();
+        (
+            state.detected.as_ref().filter(|d| d.applied).map(|d| d.language.clone()),
+            state.language_override.clone(),
+        )
+    };
+    if let Ok(mut manager) = crate::audio::recording_commands::RECORDING_MANAGER.lock() {
+        if let Some(manager) = manager.as_mut() {
+            manager.set_language_info(detected, language_override);
+        }
+    }
+}
+
+/// Feed speech of the current meeting; detection runs once enough has been collected
+pub fn observe_speech<R: Runtime>(app: &AppHandle<R>, engine: &TranscriptionEngine, samples: &[f32]) {
+    if DETECTION_STARTED.load(Ordering::SeqCst) {
+        return;
+    }
+    {
+        let state = LANGUAGE_STATE.read().unwrap();
+        let preference = crate::get_language_preference_internal();
+        if state.language_override.is_some() || !is_auto(preference.as_deref()) {
+            return;
+        }
+    }
+    if !matches!(engine, TranscriptionEngine::Whisper(_)) {
+        return;
+    }
+
+    let window = {
+        let mut buffer = DETECTION_BUFFER.lock().unwrap();
+        let needed = DETECTION_SAMPLES.saturating_sub(buffer.len());
+        buffer.extend_from_slice(&samples[..samples.len().min(needed)]);
+        if buffer.len() < DETECTION_SAMPLES || DETECTION_STARTED.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        std::mem::take(&mut *buffer)
+    };
+
+    let app = app.clone();
+    let engine = engine.clone();
+    tokio::spawn(async move {
+        let (language, probability) = match engine.detect_language(&window).await {
+            Some(Ok(result)) => result,
+            Some(Err(e)) => {
+                warn!("⚠️ Language detection failed: {}", e);
+                return;
+            }
+            None => return,
+        };
+
+        let detected = DetectedLanguage {
+            language,
+            probability,
+            applied: probability >= MIN_DETECTION_PROBABILITY,
+        };
+        info!(
+            "🌐 Detected meeting language '{}' (p={:.2}){}",
+            detected.language,
+            detected.probability,
+            if detected.applied { "" } else { ", too uncertain to apply" }
+        );
+
+        LANGUAGE_STATE.write().unwrap().detected = Some(detected.clone());
+        sync_metadata();
+        if let Err(e) = app.emit(LANGUAGE_DETECTED_EVENT, &detected) {
+            warn!("Failed to emit language-detected: {}", e);
+        }
+    });
+}
+
+/// Force the language of the current (or next) meeting; None or "auto" clears the override
+#[tauri::command]
+pub async fn set_meeting_language(language: Option<String>) -> Result<MeetingLanguage, String> {
+    let language = language
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty() && l != "auto");
+
+    if let Some(code) = &language {
+        if whisper_rs::get_lang_id(code).is_none() {
+            return Err(format!("Unknown language code: {}", code));
+        }
+    }
+
+    info!("🌐 Meeting language override set to {:?}", language);
+    LANGUAGE_STATE.write().unwrap().language_override = language;
+    sync_metadata();
+    Ok(get_meeting_language())
+}
+
+#[tauri::command]
+pub async fn get_meeting_language_command() -> Result<MeetingLanguage, String> {
+    Ok(get_meeting_language())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(language: &str, applied: bool) -> DetectedLanguage {
+        DetectedLanguage { language: language.to_string(), probability: 0.9, applied }
+    }
+
+    #[test]
+    fn test_override_wins_over_detection_and_preference() {
+        let de = detected("de", true);
+        assert_eq!(resolve_language(Some("fr"), Some(&de), Some("en")), Some("fr".to_string()));
+        assert_eq!(resolve_language(Some("fr"), None, Some("auto")), Some("fr".to_string()));
+    }
+
+    #[test]
+    fn test_detection_only_replaces_auto() {
+        let de = detected("de", true);
+        assert_eq!(resolve_language(None, Some(&de), Some("auto")), Some("de".to_string()));
+        assert_eq!(resolve_language(None, Some(&de), None), Some("de".to_string()));
+        assert_eq!(resolve_language(None, Some(&de), Some("en")), Some("en".to_string()));
+        assert_eq!(resolve_language(None, Some(&de), Some("auto-translate")), Some("auto-translate".to_string()));
+        assert_eq!(resolve_language(None, Some(&detected("de", false)), Some("auto")), Some("auto".to_string()));
+    }
+}
@@ -2,24 +2,37 @@
 //
 // Transcription module: Provider abstraction, engine management, and worker pool.
 
-pub mod provider;
+pub use meeting_core::audio::transcription::{provider, words};
 pub mod whisper_provider;
 pub mod parakeet_provider;
+pub mod cloud_providers;
+pub mod credentials;
 pub mod engine;
 pub mod worker;
+pub mod chunking;
+pub mod streaming;
+pub mod language;
+pub mod translation;
+pub mod glossary;
 
 // Re-export commonly used types
 pub use provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
 pub use whisper_provider::WhisperProvider;
 pub use parakeet_provider::ParakeetProvider;
+pub use cloud_providers::{AssemblyAiProvider, DeepgramProvider, OpenAiWhisperProvider};
 pub use engine::{
     TranscriptionEngine,
     validate_transcription_model_ready,
     get_or_init_transcription_engine,
-    get_or_init_whisper
+    get_or_init_whisper,
+    MeetingProviderSelection
 };
 pub use worker::{
     start_transcription_task,
     reset_speech_detected_flag,
     TranscriptUpdate
 };
+pub use streaming::{LiveCaptionSettings, PartialScheduler, PartialWindow};
+pub use language::{effective_language, DetectedLanguage, MeetingLanguage};
+pub use translation::{format_side_by_side, TranslatedSegment};
+pub use words::TranscriptWord;
@@ -0,0 +1,280 @@
+// audio/transcription/streaming.rs
+//
+// Live captions while the meeting is running.
+//
+// Final transcripts only arrive once the VAD closes a speech segment, which for a long
+// utterance can be many seconds after the words were spoken. While an utterance is still
+// in progress, the pipeline periodically hands the most recent window of its speech to a
+// dedicated partial worker, which transcribes it and emits `transcript-partial`. When the
+// segment is closed and transcribed by the regular worker, `transcript-final` replaces
+// the caption for that utterance.
+//
+// Partial windows are offered through a single-slot channel: while one is being
+// transcribed, newer windows are dropped instead of queueing up, so captions degrade to a
+// lower refresh rate on slow hardware without ever delaying final transcripts.
+//
+// Both events are also broadcast to caption clients outside the app window, such as the
+// local API's WebSocket.
+
+use super::engine::TranscriptionEngine;
+use super::worker::TranscriptUpdate;
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use tauri::{AppHandle, Emitter, Runtime};
+use tokio::sync::{broadcast, mpsc};
+
+pub const TRANSCRIPT_PARTIAL_EVENT: &str = "transcript-partial";
+pub const TRANSCRIPT_FINAL_EVENT: &str = "transcript-final";
+
+const SAMPLE_RATE: usize = 16000;
+
+/// Shortest speech worth a partial transcription (0.5s)
+const MIN_PARTIAL_SAMPLES: usize = SAMPLE_RATE / 2;
+
+/// Captions kept for a slow client; one further behind skips the oldest
+const CAPTION_BACKLOG: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveCaptionSettings {
+    pub enabled: bool,
+    /// How much new speech triggers the next partial transcription
+    pub interval_ms: u32,
+    /// Longest stretch of the current utterance transcribed per partial
+    pub window_ms: u32,
+}
+
+impl Default for LiveCaptionSettings {
+    fn default() -> Self {
+        Self { enabled: true, interval_ms: 1000, window_ms: 8000 }
+    }
+}
+
+impl LiveCaptionSettings {
+    pub fn normalized(self) -> Self {
+        Self {
+            interval_ms: self.interval_ms.clamp(250, 5000),
+            window_ms: self.window_ms.clamp(2000, 30000),
+            ..self
+        }
+    }
+}
+
+static LIVE_CAPTION_SETTINGS: Lazy<RwLock<LiveCaptionSettings>> =
+    Lazy::new(|| RwLock::new(LiveCaptionSettings::default()));
+
+pub fn get_live_caption_settings() -> LiveCaptionSettings {
+    *LIVE_CAPTION_SETTINGS.read().unwrap()
+}
+
+/// Turning captions on or off applies to a recording in progress; the timing applies from the next one
+pub fn set_live_caption_settings(settings: LiveCaptionSettings) -> LiveCaptionSettings {
+    let settings = settings.normalized();
+    *LIVE_CAPTION_SETTINGS.write().unwrap() = settings;
+    info!(
+        "💬 Live captions {} (every {}ms, window {}ms)",
+        if settings.enabled { "enabled" } else { "disabled" },
+        settings.interval_ms,
+        settings.window_ms
+    );
+    settings
+}
+
+/// Recent speech of an utterance that has not been closed by the VAD yet
+#[derive(Debug, Clone)]
+pub struct PartialWindow {
+    /// chunk_id the utterance will carry when it is sent as a final segment
+    pub utterance_id: u64,
+    pub samples: Vec<f32>,
+    /// Seconds from recording start
+    pub audio_start_time: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PartialTranscript {
+    pub utterance_id: u64,
+    pub text: String,
+    pub audio_start_time: f64,
+    pub audio_end_time: f64,
+}
+
+/// Closes the live caption of an utterance
+///
+/// `update` is the transcript also sent as `transcript-update`, or None when the segment
+/// produced no usable text and the caption should simply be cleared.
+#[derive(Debug, Clone, Serialize)]
+pub struct FinalTranscript {
+    pub utterance_id: u64,
+    pub update: Option<TranscriptUpdate>,
+}
+
+/// A caption change, as broadcast to clients outside the app window
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CaptionEvent {
+    Partial(PartialTranscript),
+    Final(FinalTranscript),
+}
+
+static CAPTIONS: Lazy<broadcast::Sender<CaptionEvent>> = Lazy::new(|| broadcast::channel(CAPTION_BACKLOG).0);
+
+/// Receives every caption change from now on
+pub fn subscribe_captions() -> broadcast::Receiver<CaptionEvent> {
+    CAPTIONS.subscribe()
+}
+
+/// Decides when the growing speech of the current utterance is due for a partial
+pub struct PartialScheduler {
+    interval_samples: usize,
+    window_samples: usize,
+    last_len: usize,
+}
+
+impl PartialScheduler {
+    pub fn new(settings: LiveCaptionSettings) -> Self {
+        Self {
+            interval_samples: settings.interval_ms as usize * SAMPLE_RATE / 1000,
+            window_samples: settings.window_ms as usize * SAMPLE_RATE / 1000,
+            last_len: 0,
+        }
+    }
+
+    /// Offset into `speech` of the window to transcribe, if one is due
+    pub fn poll(&mut self, speech: &[f32]) -> Option<usize> {
+        if speech.len() < self.last_len {
+            // A new utterance started since the last poll
+            self.last_len = 0;
+        }
+        if speech.len() < MIN_PARTIAL_SAMPLES || speech.len() < self.last_len + self.interval_samples {
+            return None;
+        }
+        self.last_len = speech.len();
+        Some(speech.len().saturating_sub(self.window_samples))
+    }
+
+    /// The utterance was closed
+    pub fn reset(&mut self) {
+        self.last_len = 0;
+    }
+}
+
+static PARTIAL_SENDER: Lazy<Mutex<Option<mpsc::Sender<PartialWindow>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Number of utterances already closed; partials for them are stale
+static FINALIZED_UTTERANCES: AtomicU64 = AtomicU64::new(0);
+
+fn is_finalized(utterance_id: u64) -> bool {
+    utterance_id < FINALIZED_UTTERANCES.load(Ordering::SeqCst)
+}
+
+/// Offer a window for partial transcription; dropped if the worker is busy
+pub fn submit_partial(window: PartialWindow) {
+    if let Some(sender) = PARTIAL_SENDER.lock().unwrap().as_ref() {
+        if sender.try_send(window).is_err() {
+            debug!("Partial transcription busy, skipping window");
+        }
+    }
+}
+
+/// The pipeline sent the utterance as a final segment
+pub fn finish_utterance(utterance_id: u64) {
+    FINALIZED_UTTERANCES.fetch_max(utterance_id + 1, Ordering::SeqCst);
+}
+
+/// Emit `transcript-final` for a segment processed by the transcription worker
+pub fn emit_final<R: Runtime>(app: &AppHandle<R>, utterance_id: u64, update: Option<TranscriptUpdate>) {
+    finish_utterance(utterance_id);
+    let payload = FinalTranscript { utterance_id, update };
+    if let Err(e) = app.emit(TRANSCRIPT_FINAL_EVENT, &payload) {
+        warn!("Failed to emit transcript-final: {}", e);
+    }
+    // Nobody listening is fine
+    let _ = CAPTIONS.send(CaptionEvent::Final(payload));
+}
+
+/// Start the partial transcription worker for a recording session
+///
+/// Runs until `stop_partial_transcription` is called at the end of the session.
+pub fn start_partial_transcription<R: Runtime>(app: AppHandle<R>, engine: TranscriptionEngine) {
+    let (sender, mut receiver) = mpsc::channel::<PartialWindow>(1);
+    *PARTIAL_SENDER.lock().unwrap() = Some(sender);
+    FINALIZED_UTTERANCES.store(0, Ordering::SeqCst);
+
+    tokio::spawn(async move {
+        info!("💬 Live caption worker started ({})", engine.provider_name());
+
+        while let Some(window) = receiver.recv().await {
+            if is_finalized(window.utterance_id) || !get_live_caption_settings().enabled {
+                continue;
+            }
+
+            let duration = window.samples.len() as f64 / SAMPLE_RATE as f64;
+            let language = super::language::effective_language();
+            let text = match engine.transcribe(window.samples, language).await {
+                Ok(result) => result.text,
+                Err(e) => {
+                    debug!("Partial transcription failed: {}", e);
+                    continue;
+                }
+            };
+
+            // The final transcript may have landed while this window was transcribed
+            if text.is_empty() || is_finalized(window.utterance_id) {
+                continue;
+            }
+
+            let partial = PartialTranscript {
+                utterance_id: window.utterance_id,
+                text,
+                audio_start_time: window.audio_start_time,
+                audio_end_time: window.audio_start_time + duration,
+            };
+            if let Err(e) = app.emit(TRANSCRIPT_PARTIAL_EVENT, &partial) {
+                warn!("Failed to emit transcript-partial: {}", e);
+            }
+            let _ = CAPTIONS.send(CaptionEvent::Partial(partial));
+        }
+
+        info!("💬 Live caption worker stopped");
+    });
+}
+
+/// Stop accepting partial windows; the worker exits once its channel closes
+pub fn stop_partial_transcription() {
+    PARTIAL_SENDER.lock().unwrap().take();
+}
+
+#[tauri::command]
+pub async fn get_live_caption_settings_command() -> Result<LiveCaptionSettings, String> {
+    Ok(get_live_caption_settings())
+}
+
+#[tauri::command]
+pub async fn set_live_caption_settings_command(settings: LiveCaptionSettings) -> Result<LiveCaptionSettings, String> {
+    Ok(set_live_caption_settings(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduler_waits_for_interval_and_limits_window() {
+        let settings = LiveCaptionSettings { enabled: true, interval_ms: 1000, window_ms: 2000 };
+        let mut scheduler = PartialScheduler::new(settings);
+
+        assert_eq!(scheduler.poll(&vec![0.0; 4000]), None); // below minimum
+        assert_eq!(scheduler.poll(&vec![0.0; 16000]), Some(0));
+        assert_eq!(scheduler.poll(&vec![0.0; 24000]), None); // only 0.5s new
+        assert_eq!(scheduler.poll(&vec![0.0; 48000]), Some(48000 - 32000));
+    }
+
+    #[test]
+    fn test_scheduler_restarts_for_new_utterance() {
+        let mut scheduler = PartialScheduler::new(LiveCaptionSettings::default());
+        assert!(scheduler.poll(&vec![0.0; 32000]).is_some());
+        assert!(scheduler.poll(&vec![0.0; 16000]).is_some());
+    }
+}
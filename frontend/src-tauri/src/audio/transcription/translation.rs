@@ -0,0 +1,148 @@
+// audio/transcription/translation.rs
+//
+// English translation of non-English meetings.
+//
+// When translation is enabled for a meeting, every final segment is run a second time
+// through Whisper's translate task and the result is emitted as `transcript-translation`
+// with the segment's sequence_id, so the frontend can show it next to the original. The
+// translation is kept with the segment in transcripts.json and saved to the database
+// alongside the original text. Only local Whisper can translate; with other engines the
+// setting is accepted but has no effect until a Whisper model is used.
+
+use super::engine::TranscriptionEngine;
+use crate::api::MeetingTranscript;
+use log::{info, warn};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Runtime};
+
+pub const TRANSCRIPT_TRANSLATION_EVENT: &str = "transcript-translation";
+
+/// Whisper can only translate into English
+pub const TRANSLATION_TARGET: &str = "en";
+
+/// Translation for the current (or next) meeting
+static MEETING_TRANSLATION: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranslatedSegment {
+    pub sequence_id: u64,
+    pub text: String,
+    pub source_language: Option<String>,
+    pub target_language: String,
+}
+
+pub fn is_translation_enabled() -> bool {
+    MEETING_TRANSLATION.load(Ordering::SeqCst)
+}
+
+/// Translation only applies to the meeting it was enabled for
+pub fn end_meeting() {
+    MEETING_TRANSLATION.store(false, Ordering::SeqCst);
+}
+
+/// Segments already in the target language need no translation
+fn needs_translation(source_language: Option<&str>) -> bool {
+    !matches!(source_language, Some(TRANSLATION_TARGET) | Some("auto-translate"))
+}
+
+/// Translate a final segment and emit it; returns the translation, if any
+pub async fn translate_segment<R: Runtime>(
+    app: &AppHandle<R>,
+    engine: &TranscriptionEngine,
+    audio_16k: Vec<f32>,
+    sequence_id: u64,
+) -> Option<String> {
+    let source_language = super::language::effective_language();
+    if !needs_translation(source_language.as_deref()) {
+        return None;
+    }
+
+    let TranscriptionEngine::Whisper(whisper_engine) = engine else {
+        return None;
+    };
+
+    let text = match whisper_engine.translate_audio(audio_16k, source_language.clone()).await {
+        Ok(text) if !text.trim().is_empty() => text.trim().to_string(),
+        Ok(_) => return None,
+        Err(e) => {
+            warn!("⚠️ Translation failed for segment {}: {}", sequence_id, e);
+            return None;
+        }
+    };
+
+    if let Ok(manager) = crate::audio::recording_commands::RECORDING_MANAGER.lock() {
+        if let Some(manager) = manager.as_ref() {
+            manager.set_segment_translation(sequence_id, text.clone());
+        }
+    }
+
+    let payload = TranslatedSegment {
+        sequence_id,
+        text: text.clone(),
+        source_language,
+        target_language: TRANSLATION_TARGET.to_string(),
+    };
+    if let Err(e) = app.emit(TRANSCRIPT_TRANSLATION_EVENT, &payload) {
+        warn!("Failed to emit transcript-translation: {}", e);
+    }
+    Some(text)
+}
+
+/// Original and translation side by side as a Markdown table
+pub fn format_side_by_side(transcripts: &[MeetingTranscript]) -> String {
+    let escape = |text: &str| text.replace('|', "\\|").replace('\n', " ");
+    let mut output = String::from("| Time | Original | English |\n| --- | --- | --- |\n");
+    for transcript in transcripts {
+        output.push_str(&format!(
+            "| {} | {} | {} |\n",
+            transcript.timestamp,
+            escape(&transcript.text),
+            escape(transcript.translation.as_deref().unwrap_or("")),
+        ));
+    }
+    output
+}
+
+/// Enable or disable English translation for the current (or next) meeting
+#[tauri::command]
+pub async fn set_meeting_translation(enabled: bool) -> Result<bool, String> {
+    MEETING_TRANSLATION.store(enabled, Ordering::SeqCst);
+    info!("🌐 Meeting translation {}", if enabled { "enabled" } else { "disabled" });
+    Ok(enabled)
+}
+
+#[tauri::command]
+pub async fn get_meeting_translation() -> Result<bool, String> {
+    Ok(is_translation_enabled())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_segments_are_not_translated() {
+        assert!(!needs_translation(Some("en")));
+        assert!(!needs_translation(Some("auto-translate")));
+        assert!(needs_translation(Some("de")));
+        assert!(needs_translation(None));
+    }
+
+    #[test]
+    fn test_side_by_side_escapes_table_cells() {
+        let transcripts = vec![MeetingTranscript {
+            id: "t1".to_string(),
+            text: "Hallo | zusammen".to_string(),
+            timestamp: "10:00:01".to_string(),
+            audio_start_time: None,
+            audio_end_time: None,
+            duration: None,
+            translation: Some("Hello everyone".to_string()),
+            words: Vec::new(),
+            speaker: None,
+        }];
+        let table = format_side_by_side(&transcripts);
+        assert!(table.contains("| 10:00:01 | Hallo \\| zusammen | Hello everyone |"));
+    }
+}
@@ -4,6 +4,7 @@
 
 use super::engine::TranscriptionEngine;
 use super::provider::TranscriptionError;
+use super::words::{offset_words, TranscriptWord};
 use crate::audio::AudioChunk;
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
@@ -36,6 +37,21 @@ pub struct TranscriptUpdate {
     pub audio_start_time: f64, // Seconds from recording start (e.g., 125.3)
     pub audio_end_time: f64,   // Seconds from recording start (e.g., 128.6)
     pub duration: f64,          // Segment duration in seconds (e.g., 3.3)
+    /// Word timings from recording start, for click-to-seek and low-confidence flags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<TranscriptWord>,
+}
+
+/// Counts a segment leaving the transcription queue, transcribed or lost
+fn finish_stats(transcribed: bool) {
+    use crate::audio::pipeline_stats::{self, Stage};
+
+    pipeline_stats::record_dequeued(Stage::Transcription);
+    if transcribed {
+        pipeline_stats::record_processed(Stage::Transcription, 1);
+    } else {
+        pipeline_stats::record_dropped(Stage::Transcription, 1);
+    }
 }
 
 // NOTE: get_transcript_history and get_recording_meeting_name functions
@@ -63,6 +79,15 @@ pub fn start_transcription_task<R: Runtime>(
             }
         };
 
+        super::language::begin_meeting();
+        super::glossary::refresh_glossary(&app).await;
+
+        // Live captions transcribe in-progress speech alongside the final segments
+        // (local engines only: hosted APIs would be billed for every partial window)
+        if !transcription_engine.is_remote() {
+            super::streaming::start_partial_transcription(app.clone(), transcription_engine.clone());
+        }
+
         // Create parallel workers for faster processing while preserving ALL chunks
         const NUM_WORKERS: usize = 1; // Serial processing ensures transcripts emit in chronological order
         let (work_sender, work_receiver) = tokio::sync::mpsc::unbounded_channel::<AudioChunk>();
@@ -136,11 +161,24 @@ pub fn start_transcription_task<R: Runtime>(
                             if !engine_clone.is_model_loaded().await {
                                 warn!("⚠️ Worker {}: Model unloaded, but continuing to preserve chunk {}", worker_id, chunk.chunk_id);
                                 // Still count as completed even if we can't process
+                                super::streaming::emit_final(&app_clone, chunk.chunk_id, None);
                                 chunks_completed_clone.fetch_add(1, Ordering::SeqCst);
+                                finish_stats(false);
                                 continue;
                             }
 
+                            let chunk_id = chunk.chunk_id;
                             let chunk_timestamp = chunk.timestamp;
+                            // Keep the audio for the translation pass, if the meeting wants one
+                            let translation_audio = super::translation::is_translation_enabled().then(|| {
+                                if chunk.sample_rate != 16000 {
+                                    crate::audio::audio_processing::resample_audio(&chunk.data, chunk.sample_rate, 16000)
+                                } else {
+                                    chunk.data.clone()
+                                }
+                            });
+                            let mut final_update = None;
+                            let mut transcribed = true;
                             let chunk_duration = chunk.data.len() as f64 / chunk.sample_rate as f64;
 
                             // Transcribe with provider-agnostic approach
@@ -151,7 +189,8 @@ pub fn start_transcription_task<R: Runtime>(
                             )
                             .await
                             {
-                                Ok((transcript, confidence_opt, is_partial)) => {
+                                Ok((transcript, confidence_opt, is_partial, words)) => {
+                                    let transcript = super::glossary::apply_aliases(&transcript);
                                     // Provider-aware confidence threshold
                                     let confidence_threshold = match &engine_clone {
                                         TranscriptionEngine::Whisper(_) | TranscriptionEngine::Provider(_) => 0.3,
@@ -217,6 +256,7 @@ pub fn start_transcription_task<R: Runtime>(
                                             audio_start_time,
                                             audio_end_time,
                                             duration: chunk_duration,
+                                            words: offset_words(words, chunk_timestamp),
                                         };
 
                                         if let Err(e) = app_clone.emit("transcript-update", &update)
@@ -226,6 +266,7 @@ pub fn start_transcription_task<R: Runtime>(
                                                 worker_id, e
                                             );
                                         }
+                                        final_update = Some(update);
                                         // PERFORMANCE: Removed verbose logging of every emission
                                     } else if !transcript.trim().is_empty() && should_log_this_chunk
                                     {
@@ -241,25 +282,38 @@ pub fn start_transcription_task<R: Runtime>(
                                         TranscriptionError::AudioTooShort { .. } => {
                                             // Skip silently, this is expected for very short chunks
                                             info!("Worker {}: {}", worker_id, e);
+                                            super::streaming::emit_final(&app_clone, chunk_id, None);
                                             chunks_completed_clone.fetch_add(1, Ordering::SeqCst);
+                                            finish_stats(true);
                                             continue;
                                         }
                                         TranscriptionError::ModelNotLoaded => {
                                             warn!("Worker {}: Model unloaded during transcription", worker_id);
+                                            super::streaming::emit_final(&app_clone, chunk_id, None);
                                             chunks_completed_clone.fetch_add(1, Ordering::SeqCst);
+                                            finish_stats(false);
                                             continue;
                                         }
                                         _ => {
                                             warn!("Worker {}: Transcription failed: {}", worker_id, e);
+                                            transcribed = false;
                                             let _ = app_clone.emit("transcription-warning", e.to_string());
                                         }
                                     }
                                 }
                             }
 
+                            let translate_sequence = final_update.as_ref().map(|update| update.sequence_id);
+                            super::streaming::emit_final(&app_clone, chunk_id, final_update);
+
+                            if let (Some(audio), Some(sequence_id)) = (translation_audio, translate_sequence) {
+                                super::translation::translate_segment(&app_clone, &engine_clone, audio, sequence_id).await;
+                            }
+
                             // Mark chunk as completed
                             let completed =
                                 chunks_completed_clone.fetch_add(1, Ordering::SeqCst) + 1;
+                            finish_stats(transcribed);
                             let queued = chunks_queued_clone.load(Ordering::SeqCst);
 
                             // PERFORMANCE: Only log progress every 5th chunk to reduce I/O overhead
@@ -337,6 +391,7 @@ pub fn start_transcription_task<R: Runtime>(
 
         // Signal that input is finished
         input_finished.store(true, Ordering::SeqCst);
+        super::streaming::stop_partial_transcription();
         drop(work_sender); // Close the channel to signal workers
 
         let total_chunks_queued = chunks_queued.load(Ordering::SeqCst);
@@ -399,17 +454,20 @@ pub fn start_transcription_task<R: Runtime>(
             }
         }
 
+        super::engine::clear_meeting_provider();
+        super::language::end_meeting();
+        super::translation::end_meeting();
         info!("✅ Parallel transcription task completed - all workers finished, ready for model unload");
     })
 }
 
 /// Transcribe audio chunk using the appropriate provider (Whisper, Parakeet, or trait-based)
-/// Returns: (text, confidence Option, is_partial)
+/// Returns: (text, confidence Option, is_partial, words relative to the chunk)
 async fn transcribe_chunk_with_provider<R: Runtime>(
     engine: &TranscriptionEngine,
     chunk: AudioChunk,
     app: &AppHandle<R>,
-) -> std::result::Result<(String, Option<f32>, bool), TranscriptionError> {
+) -> std::result::Result<(String, Option<f32>, bool, Vec<TranscriptWord>), TranscriptionError> {
     // Convert to 16kHz mono for transcription
     let transcription_data = if chunk.sample_rate != 16000 {
         crate::audio::audio_processing::resample_audio(&chunk.data, chunk.sample_rate, 16000)
@@ -445,17 +503,36 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
     // Transcribe using the appropriate engine (with improved error handling)
     match engine {
         TranscriptionEngine::Whisper(whisper_engine) => {
-            // Get language preference from global state
-            let language = crate::get_language_preference_internal();
+            // Meeting language: override, detected language, or the global preference
+            super::language::observe_speech(app, engine, &speech_samples);
+            let language = super::language::effective_language();
 
-            match whisper_engine
-                .transcribe_audio_with_confidence(speech_samples, language)
+            // Longer than one Whisper window: overlapping windows so boundary words survive
+            let result = if super::chunking::needs_windows(speech_samples.len()) {
+                info!(
+                    "Chunk {} is {:.0}s long, transcribing in overlapping windows",
+                    chunk.chunk_id,
+                    speech_samples.len() as f64 / 16000.0
+                );
+                super::chunking::transcribe_in_windows(&speech_samples, |window| {
+                    let language = language.clone();
+                    async move {
+                        let (text, confidence, _, words) =
+                            whisper_engine.transcribe_audio_with_words(window, language).await?;
+                        Ok::<_, anyhow::Error>((text, confidence, words))
+                    }
+                })
                 .await
-            {
-                Ok((text, confidence, is_partial)) => {
+                .map(|transcript| (transcript.text, transcript.confidence, false, transcript.words))
+            } else {
+                whisper_engine.transcribe_audio_with_words(speech_samples, language).await
+            };
+
+            match result {
+                Ok((text, confidence, is_partial, words)) => {
                     let cleaned_text = text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), Some(confidence), is_partial));
+                        return Ok((String::new(), Some(confidence), is_partial, Vec::new()));
                     }
 
                     info!(
@@ -463,7 +540,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                         chunk.chunk_id, cleaned_text, confidence, is_partial
                     );
 
-                    Ok((cleaned_text, Some(confidence), is_partial))
+                    Ok((cleaned_text, Some(confidence), is_partial, words))
                 }
                 Err(e) => {
                     error!(
@@ -490,7 +567,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                 Ok(text) => {
                     let cleaned_text = text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), None, false));
+                        return Ok((String::new(), None, false, Vec::new()));
                     }
 
                     info!(
@@ -499,7 +576,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                     );
 
                     // Parakeet doesn't provide confidence or partial results
-                    Ok((cleaned_text, None, false))
+                    Ok((cleaned_text, None, false, Vec::new()))
                 }
                 Err(e) => {
                     error!(
@@ -523,13 +600,13 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
         }
         TranscriptionEngine::Provider(provider) => {
             // NEW: Trait-based provider (clean, unified interface)
-            let language = crate::get_language_preference_internal();
+            let language = super::language::effective_language();
 
             match provider.transcribe(speech_samples, language).await {
                 Ok(result) => {
                     let cleaned_text = result.text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), result.confidence, result.is_partial));
+                        return Ok((String::new(), result.confidence, result.is_partial, Vec::new()));
                     }
 
                     let confidence_str = match result.confidence {
@@ -546,7 +623,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                         result.is_partial
                     );
 
-                    Ok((cleaned_text, result.confidence, result.is_partial))
+                    Ok((cleaned_text, result.confidence, result.is_partial, result.words))
                 }
                 Err(e) => {
                     error!(
@@ -1,8 +1,59 @@
 use anyhow::{anyhow, Result};
 use silero_rs::{VadConfig, VadSession, VadTransition};
 use log::{debug, info};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::sync::RwLock;
 use std::time::Duration;
+use super::resample::StreamingResampler;
+
+/// How readily the VAD treats audio as speech
+///
+/// Only speech segments reach the transcription engine, so this trades skipped silence
+/// (and CPU) against the risk of missing quiet or distant speakers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VadSensitivity {
+    /// Noisy rooms: only clear speech opens a segment
+    Low,
+    #[default]
+    Medium,
+    /// Quiet or distant speakers
+    High,
+}
+
+impl VadSensitivity {
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "low" => Some(Self::Low),
+            "medium" | "balanced" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            _ => None,
+        }
+    }
+
+    /// Silero (positive, negative) speech probability thresholds
+    pub fn thresholds(&self) -> (f32, f32) {
+        match self {
+            Self::Low => (0.65, 0.50),
+            Self::Medium => (0.50, 0.35), // Silero defaults - good for continuous speech
+            Self::High => (0.35, 0.20),
+        }
+    }
+}
+
+static VAD_SENSITIVITY: Lazy<RwLock<VadSensitivity>> = Lazy::new(|| RwLock::new(VadSensitivity::default()));
+
+pub fn get_vad_sensitivity() -> VadSensitivity {
+    *VAD_SENSITIVITY.read().unwrap()
+}
+
+/// Applies to the next recording
+pub fn set_vad_sensitivity(sensitivity: VadSensitivity) {
+    *VAD_SENSITIVITY.write().unwrap() = sensitivity;
+    info!("🗣️ VAD sensitivity set to {:?}", sensitivity);
+}
 
 /// Represents a complete speech segment detected by VAD
 #[derive(Debug, Clone)]
@@ -18,12 +69,15 @@ pub struct ContinuousVadProcessor {
     session: VadSession,
     chunk_size: usize,
     sample_rate: u32,
+    resampler: StreamingResampler,
     buffer: Vec<f32>,
     speech_segments: VecDeque<SpeechSegment>,
     current_speech: Vec<f32>,
     in_speech: bool,
     processed_samples: usize,
     speech_start_sample: usize,
+    // Samples (16kHz) handed out as speech, to report how much silence was skipped
+    speech_samples_emitted: usize,
     // State tracking for smart logging
     last_logged_state: bool,
 }
@@ -39,9 +93,11 @@ impl ContinuousVadProcessor {
 
         // CONTINUOUS SPEECH FIX: Tuned for capturing complete 5+ second utterances
         // Previous: 0.55/0.40 with 400ms redemption was fragmenting speech into 40ms segments
-        // New: More lenient thresholds + longer redemption for continuous speech
-        config.positive_speech_threshold = 0.50;  // Silero default - good for continuous speech
-        config.negative_speech_threshold = 0.35;  // Silero default - allows natural pauses
+        // Thresholds come from the user's sensitivity setting (Medium = Silero defaults)
+        let sensitivity = get_vad_sensitivity();
+        let (positive_threshold, negative_threshold) = sensitivity.thresholds();
+        config.positive_speech_threshold = positive_threshold;
+        config.negative_speech_threshold = negative_threshold;
 
         // CRITICAL FIX: Removed redemption_time capping to support long continuous speech
         // Previous: capped at 400ms, causing VAD to fragment 5-second speech into 40ms segments
@@ -55,8 +111,8 @@ impl ContinuousVadProcessor {
         // New: 250ms ensures segments are substantial enough for Whisper (>100ms requirement)
         config.min_speech_time = Duration::from_millis(250);  // Prevent tiny fragments
 
-        debug!("Creating VAD session with: sample_rate={}Hz, redemption={}ms, min_speech={}ms, input_rate={}Hz",
-               VAD_SAMPLE_RATE, redemption_time_ms, 250, input_sample_rate);
+        debug!("Creating VAD session with: sample_rate={}Hz, redemption={}ms, min_speech={}ms, input_rate={}Hz, sensitivity={:?}",
+               VAD_SAMPLE_RATE, redemption_time_ms, 250, input_sample_rate, sensitivity);
 
         let session = VadSession::new(config)
             .map_err(|e| anyhow!("Failed to create VAD session: {:?}", e))?;
@@ -64,6 +120,9 @@ impl ContinuousVadProcessor {
         // VAD uses 30ms chunks at 16kHz (480 samples)
         let vad_chunk_size = (VAD_SAMPLE_RATE as f32 * 0.03) as usize; // 480 samples
 
+        let resampler = StreamingResampler::new(input_sample_rate, VAD_SAMPLE_RATE)?;
+        super::resample::register_path("vad", resampler.path());
+
         info!("VAD processor created: input={}Hz, vad={}Hz, chunk_size={} samples",
               input_sample_rate, VAD_SAMPLE_RATE, vad_chunk_size);
 
@@ -71,12 +130,14 @@ impl ContinuousVadProcessor {
             session,
             chunk_size: vad_chunk_size,
             sample_rate: input_sample_rate, // Store original for timestamp calculations
+            resampler,
             buffer: Vec::with_capacity(vad_chunk_size * 2),
             speech_segments: VecDeque::new(),
             current_speech: Vec::new(),
             in_speech: false,
             processed_samples: 0,
             speech_start_sample: 0,
+            speech_samples_emitted: 0,
             // Initialize state tracking
             last_logged_state: false,
         })
@@ -85,12 +146,8 @@ impl ContinuousVadProcessor {
     /// Process incoming audio samples and return any complete speech segments
     /// Handles resampling from input sample rate to 16kHz for VAD processing
     pub fn process_audio(&mut self, samples: &[f32]) -> Result<Vec<SpeechSegment>> {
-        // Resample to 16kHz if needed
-        let resampled_audio = if self.sample_rate == 16000 {
-            samples.to_vec()
-        } else {
-            self.resample_to_16k(samples)?
-        };
+        // Resample to 16kHz if needed (passthrough when the input already is)
+        let resampled_audio = self.resampler.process(samples)?;
 
         self.buffer.extend_from_slice(&resampled_audio);
         let mut completed_segments = Vec::new();
@@ -102,6 +159,7 @@ impl ContinuousVadProcessor {
 
             // Extract any completed speech segments
             while let Some(segment) = self.speech_segments.pop_front() {
+                self.speech_samples_emitted += segment.samples.len();
                 completed_segments.push(segment);
             }
         }
@@ -109,60 +167,14 @@ impl ContinuousVadProcessor {
         Ok(completed_segments)
     }
 
-    /// Improved resampling from input sample rate to 16kHz with anti-aliasing
-    /// Uses linear interpolation and basic low-pass filtering for better quality
-    fn resample_to_16k(&self, samples: &[f32]) -> Result<Vec<f32>> {
-        if self.sample_rate == 16000 {
-            return Ok(samples.to_vec());
-        }
-
-        // Calculate downsampling ratio
-        let ratio = self.sample_rate as f64 / 16000.0;
-        let output_len = (samples.len() as f64 / ratio) as usize;
-        let mut resampled = Vec::with_capacity(output_len);
-
-        // Apply simple low-pass filter before downsampling to reduce aliasing
-        let cutoff_freq = 0.4; // Normalized frequency (0.4 * Nyquist)
-        let mut filtered_samples = Vec::with_capacity(samples.len());
-        
-        // Simple moving average filter (basic low-pass)
-        let filter_size = (self.sample_rate as f64 / (cutoff_freq * self.sample_rate as f64)) as usize;
-        let filter_size = std::cmp::max(1, std::cmp::min(filter_size, 5)); // Limit filter size
-        
-        for i in 0..samples.len() {
-            let start = if i >= filter_size { i - filter_size } else { 0 };
-            let end = std::cmp::min(i + filter_size + 1, samples.len());
-            let sum: f32 = samples[start..end].iter().sum();
-            filtered_samples.push(sum / (end - start) as f32);
-        }
-
-        // Linear interpolation downsampling
-        for i in 0..output_len {
-            let source_pos = i as f64 * ratio;
-            let source_index = source_pos as usize;
-            let fraction = source_pos - source_index as f64;
-            
-            if source_index + 1 < filtered_samples.len() {
-                // Linear interpolation
-                let sample1 = filtered_samples[source_index];
-                let sample2 = filtered_samples[source_index + 1];
-                let interpolated = sample1 + (sample2 - sample1) * fraction as f32;
-                resampled.push(interpolated);
-            } else if source_index < filtered_samples.len() {
-                resampled.push(filtered_samples[source_index]);
-            }
-        }
-
-        debug!("Resampled from {} samples ({}Hz) to {} samples (16kHz) with anti-aliasing",
-               samples.len(), self.sample_rate, resampled.len());
-
-        Ok(resampled)
-    }
-
     /// Flush any remaining audio and return final speech segments
     pub fn flush(&mut self) -> Result<Vec<SpeechSegment>> {
         let mut completed_segments = Vec::new();
 
+        // Drain the resampler's tail into the buffer
+        let tail = self.resampler.flush()?;
+        self.buffer.extend_from_slice(&tail);
+
         // Process any remaining buffered audio
         if !self.buffer.is_empty() {
             let remaining = self.buffer.clone();
@@ -196,12 +208,27 @@ impl ContinuousVadProcessor {
 
         // Extract all remaining segments
         while let Some(segment) = self.speech_segments.pop_front() {
+            self.speech_samples_emitted += segment.samples.len();
             completed_segments.push(segment);
         }
 
         Ok(completed_segments)
     }
 
+    /// Speech of the utterance still in progress (16kHz) and its start in seconds
+    pub fn speech_in_progress(&self) -> Option<(&[f32], f64)> {
+        if !self.in_speech || self.current_speech.is_empty() {
+            return None;
+        }
+        let start_secs = (self.processed_samples - self.current_speech.len()) as f64 / 16000.0;
+        Some((&self.current_speech, start_secs))
+    }
+
+    /// Seconds of audio analysed vs. seconds passed on as speech
+    pub fn activity_seconds(&self) -> (f64, f64) {
+        (self.processed_samples as f64 / 16000.0, self.speech_samples_emitted as f64 / 16000.0)
+    }
+
     fn process_chunk(&mut self, chunk: &[f32]) -> Result<()> {
         let transitions = self.session.process(chunk)
             .map_err(|e| anyhow!("VAD processing failed: {}", e))?;
@@ -317,3 +344,35 @@ pub fn get_speech_chunks(samples_mono_16k: &[f32], redemption_time_ms: u32) -> R
 }
 
  
+
+#[tauri::command]
+pub async fn get_vad_sensitivity_command() -> Result<VadSensitivity, String> {
+    Ok(get_vad_sensitivity())
+}
+
+#[tauri::command]
+pub async fn set_vad_sensitivity_command(sensitivity: String) -> Result<(), String> {
+    let sensitivity = VadSensitivity::from_string(&sensitivity)
+        .ok_or_else(|| format!("Unknown VAD sensitivity: {}", sensitivity))?;
+    set_vad_sensitivity(sensitivity);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_higher_sensitivity_lowers_thresholds() {
+        let (low, _) = VadSensitivity::Low.thresholds();
+        let (medium, _) = VadSensitivity::Medium.thresholds();
+        let (high, _) = VadSensitivity::High.thresholds();
+        assert!(low > medium && medium > high);
+
+        for sensitivity in [VadSensitivity::Low, VadSensitivity::Medium, VadSensitivity::High] {
+            let (positive, negative) = sensitivity.thresholds();
+            assert!(negative < positive, "{:?} needs hysteresis", sensitivity);
+        }
+        assert_eq!(VadSensitivity::from_string("balanced"), Some(VadSensitivity::Medium));
+    }
+}
@@ -0,0 +1,320 @@
+// Write-ahead audio log for crash-safe recording
+//
+// The incremental saver only encodes a checkpoint every 30 seconds, so a crash (or the
+// machine going to sleep and never waking the app) could lose up to 30 seconds of audio
+// plus everything that never got merged. The write-ahead log closes that gap:
+//
+// - Mixed audio is appended to small raw PCM chunk files (f32 LE, interleaved) every few seconds
+// - `manifest.json` lists the chunks and how many encoded checkpoints already exist
+// - Once a checkpoint is encoded, the PCM chunks it covers are deleted
+// - A clean finalize marks the manifest finalized and removes the log
+//
+// Any meeting folder whose log is not finalized is an orphaned session that can be
+// rebuilt from its checkpoints plus the pending PCM chunks.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Result};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+
+/// Directory inside the meeting folder holding the log
+pub const WAL_DIR: &str = ".wal";
+
+/// Manifest file name inside `WAL_DIR`
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+const MANIFEST_VERSION: u32 = 1;
+
+/// Seconds of audio per PCM chunk file
+const CHUNK_SECONDS: u32 = 5;
+
+/// One raw PCM chunk on disk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalChunk {
+    pub index: u64,
+    pub file: String,
+    pub samples: u64,
+}
+
+/// Index of everything written for a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalManifest {
+    pub version: u32,
+    pub meeting_name: Option<String>,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub started_at: String,
+    pub updated_at: String,
+    /// Encoded checkpoints (`.checkpoints/audio_chunk_NNN.<ext>`) that are complete
+    pub committed_checkpoints: u32,
+    /// PCM chunks written after the last committed checkpoint
+    pub chunks: Vec<WalChunk>,
+    /// Index of the last PCM chunk a committed checkpoint covers; chunk files up to it are
+    /// never salvaged, even if deleting them failed
+    #[serde(default)]
+    pub last_committed_chunk: Option<u64>,
+    pub finalized: bool,
+}
+
+impl WalManifest {
+    /// Seconds of audio covered by the pending PCM chunks
+    pub fn pending_seconds(&self) -> f64 {
+        let samples: u64 = self.chunks.iter().map(|c| c.samples).sum();
+        samples as f64 / (self.sample_rate.max(1) as f64 * self.channels.max(1) as f64)
+    }
+
+    pub fn load(wal_dir: &Path) -> Result<Self> {
+        let content = fs::read_to_string(wal_dir.join(MANIFEST_FILE))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Replace the manifest in `wal_dir` through a temporary file, so a crash never leaves
+    /// it half written
+    pub fn save(&self, wal_dir: &Path) -> Result<()> {
+        let temp_path = wal_dir.join(".manifest.json.tmp");
+        let mut file = File::create(&temp_path)?;
+        file.write_all(serde_json::to_string_pretty(self)?.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, wal_dir.join(MANIFEST_FILE))?; // Atomic
+        Ok(())
+    }
+}
+
+/// Append-only PCM log for one recording session
+pub struct WriteAheadLog {
+    dir: PathBuf,
+    manifest: WalManifest,
+    current: Option<(File, WalChunk)>,
+    next_index: u64,
+    chunk_samples: u64,
+}
+
+impl WriteAheadLog {
+    /// Create the log in `<meeting_folder>/.wal` for interleaved audio with `channels` channels
+    pub fn create(meeting_folder: &Path, meeting_name: Option<String>, sample_rate: u32, channels: u16) -> Result<Self> {
+        let dir = meeting_folder.join(WAL_DIR);
+        fs::create_dir_all(&dir)?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let log = Self {
+            dir,
+            manifest: WalManifest {
+                version: MANIFEST_VERSION,
+                meeting_name,
+                sample_rate,
+                channels,
+                started_at: now.clone(),
+                updated_at: now,
+                committed_checkpoints: 0,
+                chunks: Vec::new(),
+                last_committed_chunk: None,
+                finalized: false,
+            },
+            current: None,
+            next_index: 0,
+            chunk_samples: sample_rate as u64 * channels.max(1) as u64 * CHUNK_SECONDS as u64,
+        };
+        log.write_manifest()?;
+
+        info!("📝 Write-ahead log created at {}", log.dir.display());
+        Ok(log)
+    }
+
+    /// Append interleaved samples, rolling to a new chunk file when the current one is full
+    pub fn append(&mut self, samples: &[f32]) -> Result<()> {
+        let mut remaining = samples;
+        while !remaining.is_empty() {
+            if self.current.is_none() {
+                self.open_chunk()?;
+            }
+
+            let (file, chunk) = self.current.as_mut().unwrap();
+            let room = (self.chunk_samples - chunk.samples) as usize;
+            let (now, later) = remaining.split_at(room.min(remaining.len()));
+
+            file.write_all(bytemuck::cast_slice(now))?;
+            chunk.samples += now.len() as u64;
+            remaining = later;
+
+            if chunk.samples >= self.chunk_samples {
+                self.seal_chunk()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Make the open chunk durable and record it in the manifest
+    pub fn seal_chunk(&mut self) -> Result<()> {
+        if let Some((file, chunk)) = self.current.take() {
+            file.sync_data()?;
+            if chunk.samples > 0 {
+                debug!("WAL: sealed {} ({} samples)", chunk.file, chunk.samples);
+                self.manifest.chunks.push(chunk);
+                self.write_manifest()?;
+            } else {
+                let _ = fs::remove_file(self.dir.join(&chunk.file));
+            }
+        }
+        Ok(())
+    }
+
+    /// An encoded checkpoint now covers everything logged so far; drop those chunks
+    pub fn commit_checkpoint(&mut self, checkpoint_count: u32) -> Result<()> {
+        self.seal_chunk()?;
+
+        for chunk in self.manifest.chunks.drain(..) {
+            if let Err(e) = fs::remove_file(self.dir.join(&chunk.file)) {
+                warn!("WAL: failed to remove {}: {}", chunk.file, e);
+            }
+        }
+        self.manifest.committed_checkpoints = checkpoint_count;
+        // Every chunk opened so far is sealed now, so the checkpoint covers all of them
+        self.manifest.last_committed_chunk = self.next_index.checked_sub(1);
+        self.write_manifest()
+    }
+
+    /// Recording was finalized cleanly: mark it and remove the log
+    pub fn finalize(mut self) -> Result<()> {
+        self.seal_chunk()?;
+        self.manifest.finalized = true;
+        self.write_manifest()?;
+        fs::remove_dir_all(&self.dir)?;
+        info!("📝 Write-ahead log finalized and removed");
+        Ok(())
+    }
+
+    pub fn manifest(&self) -> &WalManifest {
+        &self.manifest
+    }
+
+    fn open_chunk(&mut self) -> Result<()> {
+        let file_name = format!("chunk_{:05}.pcm", self.next_index);
+        let file = File::create(self.dir.join(&file_name))?;
+        self.current = Some((file, WalChunk { index: self.next_index, file: file_name, samples: 0 }));
+        self.next_index += 1;
+        Ok(())
+    }
+
+    fn write_manifest(&self) -> Result<()> {
+        let mut manifest = self.manifest.clone();
+        manifest.updated_at = chrono::Utc::now().to_rfc3339();
+        manifest.save(&self.dir)
+    }
+}
+
+/// Read a sealed PCM chunk, ignoring a torn trailing sample
+pub fn read_chunk(wal_dir: &Path, chunk: &WalChunk) -> Result<Vec<f32>> {
+    let bytes = fs::read(wal_dir.join(&chunk.file))
+        .map_err(|e| anyhow!("Failed to read WAL chunk {}: {}", chunk.file, e))?;
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
+
+/// A recording whose app exited before it was finalized
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanedSession {
+    pub meeting_folder: PathBuf,
+    pub meeting_name: Option<String>,
+    pub started_at: String,
+    pub last_written_at: String,
+    pub committed_checkpoints: u32,
+    pub pending_seconds: f64,
+}
+
+/// Find meeting folders under `recordings_folder` with an unfinalized write-ahead log
+pub fn find_orphaned_sessions(recordings_folder: &Path) -> Vec<OrphanedSession> {
+    let Ok(entries) = fs::read_dir(recordings_folder) else {
+        return Vec::new();
+    };
+
+    let mut sessions: Vec<OrphanedSession> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|folder| {
+            let manifest = WalManifest::load(&folder.join(WAL_DIR)).ok()?;
+            if manifest.finalized {
+                return None;
+            }
+            Some(OrphanedSession {
+                meeting_name: manifest.meeting_name.clone(),
+                started_at: manifest.started_at.clone(),
+                last_written_at: manifest.updated_at.clone(),
+                committed_checkpoints: manifest.committed_checkpoints,
+                pending_seconds: manifest.pending_seconds(),
+                meeting_folder: folder,
+            })
+        })
+        .collect();
+
+    sessions.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    sessions
+}
+
+/// List interrupted recordings the user can recover
+#[tauri::command]
+pub async fn list_orphaned_recordings() -> Result<Vec<OrphanedSession>, String> {
+    let recordings_folder = super::recording_preferences::get_default_recordings_folder();
+    Ok(find_orphaned_sessions(&recordings_folder))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_append_rolls_chunks_and_updates_manifest() {
+        let temp_dir = tempdir().unwrap();
+        let mut wal = WriteAheadLog::create(temp_dir.path(), Some("Standup".into()), 100, 1).unwrap();
+
+        // 100 Hz * 5 s = 500 samples per chunk
+        wal.append(&vec![0.25f32; 1200]).unwrap();
+        assert_eq!(wal.manifest().chunks.len(), 2);
+
+        wal.seal_chunk().unwrap();
+        let manifest = WalManifest::load(&temp_dir.path().join(WAL_DIR)).unwrap();
+        assert_eq!(manifest.chunks.iter().map(|c| c.samples).sum::<u64>(), 1200);
+        assert!((manifest.pending_seconds() - 12.0).abs() < 1e-9);
+
+        let samples = read_chunk(&temp_dir.path().join(WAL_DIR), &manifest.chunks[2]).unwrap();
+        assert_eq!(samples, vec![0.25f32; 200]);
+    }
+
+    #[test]
+    fn test_commit_checkpoint_prunes_chunks() {
+        let temp_dir = tempdir().unwrap();
+        let mut wal = WriteAheadLog::create(temp_dir.path(), None, 100, 1).unwrap();
+        wal.append(&vec![0.1f32; 700]).unwrap();
+        wal.commit_checkpoint(1).unwrap();
+
+        assert!(wal.manifest().chunks.is_empty());
+        assert_eq!(wal.manifest().committed_checkpoints, 1);
+        assert_eq!(wal.manifest().last_committed_chunk, Some(1));
+        assert!(!temp_dir.path().join(WAL_DIR).join("chunk_00000.pcm").exists());
+    }
+
+    #[test]
+    fn test_orphan_detection() {
+        let recordings = tempdir().unwrap();
+        let crashed = recordings.path().join("Crashed_2025-01-01_10-00");
+        let finished = recordings.path().join("Finished_2025-01-01_09-00");
+        fs::create_dir_all(&crashed).unwrap();
+        fs::create_dir_all(&finished).unwrap();
+
+        let mut wal = WriteAheadLog::create(&crashed, Some("Crashed".into()), 100, 1).unwrap();
+        wal.append(&vec![0.1f32; 600]).unwrap();
+        drop(wal); // Simulated crash: never finalized
+
+        WriteAheadLog::create(&finished, Some("Finished".into()), 100, 1).unwrap().finalize().unwrap();
+
+        let orphans = find_orphaned_sessions(recordings.path());
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].meeting_name.as_deref(), Some("Crashed"));
+        assert!((orphans[0].pending_seconds - 5.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,421 @@
+// backup/archive.rs
+//
+// Exporting the database as a zip for another computer, and restoring from an export or a
+// snapshot. An export holds a copy of the database and `manifest.json`, which lists each
+// meeting's recording files with their sizes and where the recordings folder was. Recordings
+// aren't in the zip; they're copied over separately, and restoring relinks meetings whose
+// folder isn't where it was to the same place under this computer's recordings folder.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{SqliteConnection, SqlitePool};
+use tauri::{AppHandle, Runtime};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::{attach_statement, integrity_problems, is_plain_database, pending_restore_path};
+use crate::audio::recording_preferences::get_default_recordings_folder;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::workspaces::storage::rebase;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const DATABASE_ENTRY: &str = "meeting_minutes.sqlite";
+/// Bumped when the manifest changes in a way older versions can't read
+const MANIFEST_FORMAT: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingFile {
+    pub name: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingRecordings {
+    pub meeting_id: String,
+    pub folder: PathBuf,
+    pub files: Vec<RecordingFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: u32,
+    pub app_version: String,
+    pub created_at: DateTime<Utc>,
+    /// Whether the database is encrypted; it only opens with this computer's key then
+    pub encrypted: bool,
+    pub database_sha256: String,
+    /// The recordings folder on the computer the export was made on
+    pub recordings_root: PathBuf,
+    pub meetings: Vec<MeetingRecordings>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RestoreReport {
+    pub meetings: i64,
+    /// Meetings whose recording folder was found under this computer's recordings folder
+    pub relinked: usize,
+    /// Meetings whose recording folder wasn't found; they open without audio
+    pub missing_recordings: usize,
+    /// Recording files the manifest lists that are missing or have a different size
+    pub mismatched_files: usize,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Copies `reader` into `target`, returning the SHA-256 of what was copied
+fn copy_hashed(reader: &mut dyn Read, target: &Path) -> Result<String, String> {
+    let mut file = File::create(target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = reader.read(&mut buffer).map_err(|e| format!("Failed to read the backup: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        file.write_all(&buffer[..read]).map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+    }
+    file.sync_all().map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+    Ok(to_hex(&hasher.finalize()))
+}
+
+fn list_recording_files(folder: &Path) -> Vec<RecordingFile> {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return Vec::new();
+    };
+    let mut files: Vec<RecordingFile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|metadata| metadata.is_file())?;
+            Some(RecordingFile { name: entry.file_name().to_string_lossy().into_owned(), bytes: metadata.len() })
+        })
+        .collect();
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    files
+}
+
+/// How many of `files` are missing from `folder` or have a different size there
+fn count_mismatched(folder: &Path, files: &[RecordingFile]) -> usize {
+    files
+        .iter()
+        .filter(|file| {
+            let bytes = std::fs::metadata(folder.join(&file.name)).ok().map(|metadata| metadata.len());
+            bytes != Some(file.bytes)
+        })
+        .count()
+}
+
+fn write_archive(target: &Path, database: &Path, manifest: &Manifest) -> Result<(), String> {
+    let file = File::create(target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated).large_file(true);
+
+    zip.start_file(MANIFEST_FILE, options).map_err(|e| format!("Failed to add the manifest: {}", e))?;
+    let json = serde_json::to_vec_pretty(manifest).map_err(|e| e.to_string())?;
+    zip.write_all(&json).map_err(|e| format!("Failed to add the manifest: {}", e))?;
+
+    zip.start_file(DATABASE_ENTRY, options).map_err(|e| format!("Failed to add the database: {}", e))?;
+    let mut source = File::open(database).map_err(|e| format!("Failed to open {}: {}", database.display(), e))?;
+    std::io::copy(&mut source, &mut zip).map_err(|e| format!("Failed to add the database: {}", e))?;
+    zip.finish().map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+    Ok(())
+}
+
+/// Writes an export of the open workspace to `target`
+pub async fn export<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, target: &Path) -> Result<Manifest, String> {
+    let dir = super::backups_dir(app)?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let copy = dir.join(format!("export-{}.tmp", uuid::Uuid::new_v4()));
+    super::copy_database(pool, &copy).await?;
+
+    let result = async {
+        let database_sha256 = {
+            let mut file = File::open(&copy).map_err(|e| format!("Failed to open {}: {}", copy.display(), e))?;
+            let mut hasher = Sha256::new();
+            std::io::copy(&mut file, &mut hasher).map_err(|e| format!("Failed to read {}: {}", copy.display(), e))?;
+            to_hex(&hasher.finalize())
+        };
+        let meetings = MeetingsRepository::get_meetings(pool)
+            .await
+            .map_err(|e| format!("Failed to load meetings: {}", e))?
+            .into_iter()
+            .filter_map(|meeting| {
+                let folder = PathBuf::from(meeting.folder_path?);
+                let files = list_recording_files(&folder);
+                Some(MeetingRecordings { meeting_id: meeting.id, folder, files })
+            })
+            .collect();
+        let manifest = Manifest {
+            format: MANIFEST_FORMAT,
+            app_version: app.package_info().version.to_string(),
+            created_at: Utc::now(),
+            encrypted: crate::encryption::active_key().is_some(),
+            database_sha256,
+            recordings_root: get_default_recordings_folder(),
+            meetings,
+        };
+
+        let target = target.to_path_buf();
+        let copy = copy.clone();
+        let written = manifest.clone();
+        tokio::task::spawn_blocking(move || write_archive(&target, &copy, &written))
+            .await
+            .map_err(|e| format!("Failed to write the export: {}", e))??;
+        Ok::<_, String>(manifest)
+    }
+    .await;
+
+    let _ = std::fs::remove_file(&copy);
+    match &result {
+        Ok(_) => info!("💾 Exported the database to {:?}", target),
+        Err(_) => {
+            let _ = std::fs::remove_file(target);
+        }
+    }
+    result
+}
+
+/// Extracts the database of the export at `archive` to `target` and checks it against the
+/// manifest
+fn extract(archive: &Path, target: &Path) -> Result<Manifest, String> {
+    let file = File::open(archive).map_err(|e| format!("Failed to open {}: {}", archive.display(), e))?;
+    let mut zip = ZipArchive::new(file).map_err(|e| format!("{} isn't a backup: {}", archive.display(), e))?;
+
+    let manifest: Manifest = {
+        let entry = zip
+            .by_name(MANIFEST_FILE)
+            .map_err(|_| format!("{} isn't a backup: it has no manifest", archive.display()))?;
+        serde_json::from_reader(entry).map_err(|e| format!("The backup's manifest can't be read: {}", e))?
+    };
+    if manifest.format > MANIFEST_FORMAT {
+        return Err(format!("This backup was made by a newer version of the app ({})", manifest.app_version));
+    }
+
+    let mut entry = zip
+        .by_name(DATABASE_ENTRY)
+        .map_err(|_| format!("{} isn't a backup: it has no database", archive.display()))?;
+    let sha256 = copy_hashed(&mut entry, target)?;
+    if sha256 != manifest.database_sha256 {
+        let _ = std::fs::remove_file(target);
+        return Err("The backup's database is damaged: its checksum doesn't match".to_string());
+    }
+    Ok(manifest)
+}
+
+/// Where the restored database has each meeting's recordings: where they were when they're
+/// still there, otherwise the same place under `recordings` when they've been copied there
+fn relink(folder: &Path, old_root: &Path, recordings: &Path) -> Option<PathBuf> {
+    if folder.is_dir() {
+        return Some(folder.to_path_buf());
+    }
+    rebase(folder, old_root, recordings).filter(|moved| moved.is_dir())
+}
+
+/// Checks the attached database `restored`, then exports it into `pending` encrypted like the
+/// open database, with its meetings relinked to their recordings
+async fn prepare_pending(
+    conn: &mut SqliteConnection,
+    manifest: Option<&Manifest>,
+    recordings: &Path,
+) -> Result<RestoreReport, String> {
+    let problems = integrity_problems(conn, "restored").await?;
+    if !problems.is_empty() {
+        return Err(format!("The backup is damaged: {}", problems.join("; ")));
+    }
+    let (open_version, restored_version): (Option<i64>, Option<i64>) = sqlx::query_as(
+        "SELECT (SELECT MAX(version) FROM main._sqlx_migrations WHERE success = 1),
+                (SELECT MAX(version) FROM restored._sqlx_migrations WHERE success = 1)",
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| format!("The backup can't be read: {}", e))?;
+    if restored_version > open_version {
+        return Err("This backup was made by a newer version of the app".to_string());
+    }
+
+    sqlx::query("SELECT sqlcipher_export('pending', 'restored')")
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to copy the backup: {}", e))?;
+
+    let meetings = sqlx::query_scalar("SELECT COUNT(*) FROM pending.meetings")
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to read the backup: {}", e))?;
+    let mut report = RestoreReport { meetings, ..Default::default() };
+    let folders: Vec<(String, String)> =
+        sqlx::query_as("SELECT id, folder_path FROM pending.meetings WHERE folder_path IS NOT NULL")
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to read the backup: {}", e))?;
+
+    // A snapshot was taken on this computer, so its folders are relative to this recordings folder
+    let old_root = manifest.map_or(recordings, |manifest| manifest.recordings_root.as_path());
+    for (meeting_id, folder) in folders {
+        let folder = PathBuf::from(folder);
+        let Some(found) = relink(&folder, old_root, recordings) else {
+            report.missing_recordings += 1;
+            continue;
+        };
+        let listed = manifest.and_then(|manifest| manifest.meetings.iter().find(|m| m.meeting_id == meeting_id));
+        if let Some(listed) = listed {
+            report.mismatched_files += count_mismatched(&found, &listed.files);
+        }
+        if found != folder {
+            sqlx::query("UPDATE pending.meetings SET folder_path = ? WHERE id = ?")
+                .bind(found.to_string_lossy().to_string())
+                .bind(&meeting_id)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to relink recordings: {}", e))?;
+            report.relinked += 1;
+        }
+    }
+
+    let problems = integrity_problems(conn, "pending").await?;
+    if !problems.is_empty() {
+        return Err(format!("The restored database failed its integrity check: {}", problems.join("; ")));
+    }
+    Ok(report)
+}
+
+/// Checks the export or snapshot at `source` and prepares it to replace the open database
+/// when the app restarts
+pub async fn restore<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    source: &Path,
+) -> Result<RestoreReport, String> {
+    let db_path = crate::workspaces::database_path(app)?;
+    let pending = pending_restore_path(&db_path);
+    let _ = std::fs::remove_file(&pending);
+
+    let is_archive = source.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("zip"));
+    let extracted = db_path.with_file_name(format!("restore-{}.tmp", uuid::Uuid::new_v4()));
+    let (database, manifest) = if is_archive {
+        let archive = source.to_path_buf();
+        let target = extracted.clone();
+        let manifest = tokio::task::spawn_blocking(move || extract(&archive, &target))
+            .await
+            .map_err(|e| format!("Failed to read the backup: {}", e))??;
+        (extracted.clone(), Some(manifest))
+    } else {
+        (source.to_path_buf(), None)
+    };
+
+    let result = async {
+        let key = if is_plain_database(&database)? {
+            None
+        } else {
+            Some(crate::encryption::current_key().ok_or_else(|| {
+                "This backup is encrypted and this computer doesn't have its key; \
+                 turn encryption off before exporting to move meetings to another computer"
+                    .to_string()
+            })?)
+        };
+
+        let mut conn = pool.acquire().await.map_err(|e| format!("Failed to open the database: {}", e))?;
+        sqlx::query(&attach_statement(&database, "restored", key.as_ref()))
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to open the backup: {}", e))?;
+        let attached = sqlx::query(&attach_statement(&pending, "pending", crate::encryption::active_key().as_ref()))
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to prepare the restore: {}", e));
+        let report = match attached {
+            Ok(_) => {
+                let report = prepare_pending(&mut conn, manifest.as_ref(), &get_default_recordings_folder()).await;
+                let _ = sqlx::query("DETACH DATABASE pending").execute(&mut *conn).await;
+                report
+            }
+            Err(e) => Err(e),
+        };
+        let _ = sqlx::query("DETACH DATABASE restored").execute(&mut *conn).await;
+        report
+    }
+    .await
+    .map_err(|e| {
+        if e.contains("file is not a database") {
+            "The backup can't be opened; it may be encrypted with a different key".to_string()
+        } else {
+            e
+        }
+    });
+
+    let _ = std::fs::remove_file(&extracted);
+    if result.is_err() {
+        let _ = std::fs::remove_file(&pending);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relink() {
+        let dir = tempfile::tempdir().unwrap();
+        let recordings = dir.path().join("recordings");
+        std::fs::create_dir_all(recordings.join("Standup")).unwrap();
+        let old_root = Path::new("/Users/someone/Movies/meetily-recordings");
+
+        assert_eq!(relink(&old_root.join("Standup"), old_root, &recordings), Some(recordings.join("Standup")));
+        assert_eq!(relink(&old_root.join("Retro"), old_root, &recordings), None);
+        assert_eq!(relink(&recordings.join("Standup"), old_root, &recordings), Some(recordings.join("Standup")));
+        assert_eq!(relink(Path::new("/elsewhere/Standup"), old_root, &recordings), None);
+    }
+
+    #[test]
+    fn test_recording_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("audio.mp4"), [0u8; 100]).unwrap();
+        std::fs::write(dir.path().join("transcripts.json"), "{}").unwrap();
+        std::fs::create_dir(dir.path().join(".checkpoints")).unwrap();
+
+        let files = list_recording_files(dir.path());
+        assert_eq!(files.iter().map(|file| file.name.as_str()).collect::<Vec<_>>(), ["audio.mp4", "transcripts.json"]);
+        assert_eq!(count_mismatched(dir.path(), &files), 0);
+
+        std::fs::write(dir.path().join("audio.mp4"), [0u8; 50]).unwrap();
+        std::fs::remove_file(dir.path().join("transcripts.json")).unwrap();
+        assert_eq!(count_mismatched(dir.path(), &files), 2);
+    }
+
+    #[test]
+    fn test_archive_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = dir.path().join("copy.sqlite");
+        std::fs::write(&database, b"SQLite format 3\0 and the rest").unwrap();
+        let mut manifest = Manifest {
+            format: MANIFEST_FORMAT,
+            app_version: "0.1.0".to_string(),
+            created_at: Utc::now(),
+            encrypted: false,
+            database_sha256: to_hex(&Sha256::digest(std::fs::read(&database).unwrap())),
+            recordings_root: PathBuf::from("/recordings"),
+            meetings: Vec::new(),
+        };
+
+        let archive = dir.path().join("backup.zip");
+        write_archive(&archive, &database, &manifest).unwrap();
+        let extracted = dir.path().join("extracted.sqlite");
+        assert_eq!(extract(&archive, &extracted).unwrap().recordings_root, manifest.recordings_root);
+        assert_eq!(std::fs::read(&extracted).unwrap(), std::fs::read(&database).unwrap());
+        assert!(is_plain_database(&extracted).unwrap());
+
+        manifest.database_sha256 = "0".repeat(64);
+        write_archive(&archive, &database, &manifest).unwrap();
+        assert!(extract(&archive, &extracted).is_err());
+        assert!(!extracted.exists());
+    }
+}
@@ -0,0 +1,97 @@
+// backup/commands.rs
+//
+// Tauri commands for database snapshots, exports and restoring. Restoring prepares the
+// backup while the app runs, then restarts it so the database is swapped before it opens.
+
+use std::path::Path;
+
+use log::info;
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+
+use super::archive::{self, RestoreReport};
+use super::{backups_dir, list_snapshots, load_settings, save_settings, take_snapshot, BackupSettings, Snapshot};
+use crate::audio::session;
+use crate::state::AppState;
+use crate::utils::restart_soon;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportSummary {
+    pub path: String,
+    pub meetings: usize,
+    /// The export only opens on a computer with this one's encryption key
+    pub encrypted: bool,
+}
+
+#[tauri::command]
+pub async fn get_backup_settings<R: Runtime>(app: AppHandle<R>) -> Result<BackupSettings, String> {
+    Ok(load_settings(&app))
+}
+
+#[tauri::command]
+pub async fn set_backup_settings<R: Runtime>(
+    app: AppHandle<R>,
+    settings: BackupSettings,
+) -> Result<BackupSettings, String> {
+    let settings = settings.normalized();
+    save_settings(&app, &settings)?;
+    info!("💾 Backup settings set to {:?}", settings);
+    Ok(settings)
+}
+
+/// The open workspace's snapshots, newest first
+#[tauri::command]
+pub async fn list_backups<R: Runtime>(app: AppHandle<R>) -> Result<Vec<Snapshot>, String> {
+    Ok(list_snapshots(&backups_dir(&app)?))
+}
+
+#[tauri::command]
+pub async fn create_backup<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Snapshot, String> {
+    take_snapshot(&app, state.db_manager.pool()).await
+}
+
+/// Writes the database and a manifest of the recordings to a zip at `path`
+#[tauri::command]
+pub async fn export_backup<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    path: String,
+) -> Result<ExportSummary, String> {
+    let path = Path::new(path.trim());
+    if !path.is_absolute() {
+        return Err(format!("The export must be saved to a full path: {}", path.display()));
+    }
+    let manifest = archive::export(&app, state.db_manager.pool(), path).await?;
+    Ok(ExportSummary {
+        path: path.to_string_lossy().into_owned(),
+        meetings: manifest.meetings.len(),
+        encrypted: manifest.encrypted,
+    })
+}
+
+/// Replaces the database with the export or snapshot at `path`, then restarts the app. The
+/// database it replaces is kept as a snapshot.
+#[tauri::command]
+pub async fn restore_backup<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    path: String,
+) -> Result<RestoreReport, String> {
+    if session::phase().is_recording() {
+        return Err("A backup can't be restored while recording".to_string());
+    }
+    if crate::encryption::commands::get_encryption_status(app.clone()).await?.restart_pending {
+        return Err("Restart the app to finish switching encryption before restoring a backup".to_string());
+    }
+    let report = archive::restore(&app, state.db_manager.pool(), Path::new(path.trim())).await?;
+
+    info!(
+        "💾 Restoring {} meetings ({} relinked, {} without recordings), restarting",
+        report.meetings, report.relinked, report.missing_recordings
+    );
+    restart_soon(app);
+    Ok(report)
+}
@@ -0,0 +1,319 @@
+/// Backup module - snapshots of the meeting database, and backups to restore from
+///
+/// Snapshots are consistent copies of the open database, taken in the background (daily by
+/// default) into `backups/` next to it, keeping the newest few. An export is a zip of such a
+/// copy with a manifest of each meeting's recording files, for moving to another computer:
+/// the recordings folder is copied over separately and restoring relinks the meetings to it.
+/// Both are encrypted like the database when encryption is on.
+///
+/// Restoring checks the backup and prepares it next to the database, then restarts the app;
+/// the database is swapped before it opens again, and the one it replaces is kept as a
+/// snapshot.
+///
+/// This module contains:
+/// - Export archives and restoring (`archive`)
+/// - Tauri commands for frontend integration
+
+pub mod archive;
+pub mod commands;
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::encryption::database::key_pragma;
+use crate::state::AppState;
+
+const STORE_FILE: &str = "backup.json";
+const STORE_KEY: &str = "backup_settings";
+
+pub const BACKUPS_DIR: &str = "backups";
+const SNAPSHOT_PREFIX: &str = "meeting_minutes-";
+const SNAPSHOT_EXTENSION: &str = ".sqlite";
+const SNAPSHOT_TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// A restored database waiting to replace the open one on the next start
+const PENDING_RESTORE_FILE: &str = "meeting_minutes.restore.sqlite";
+
+/// The first bytes of every plain SQLite file; encrypted ones start with random bytes
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// A snapshot copies the whole database, so the first one waits until long after startup
+const FIRST_RUN_DELAY: Duration = Duration::from_secs(10 * 60);
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const MAX_INTERVAL_HOURS: u32 = 24 * 30;
+const MAX_KEEP: u32 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupSettings {
+    /// Whether snapshots are taken in the background
+    pub enabled: bool,
+    pub interval_hours: u32,
+    /// Snapshots kept; older ones are deleted
+    pub keep: u32,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self { enabled: true, interval_hours: 24, keep: 7 }
+    }
+}
+
+impl BackupSettings {
+    fn normalized(self) -> Self {
+        Self {
+            enabled: self.enabled,
+            interval_hours: self.interval_hours.clamp(1, MAX_INTERVAL_HOURS),
+            keep: self.keep.clamp(1, MAX_KEEP),
+        }
+    }
+}
+
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> BackupSettings {
+    let stored = match app.store(STORE_FILE) {
+        Ok(store) => store.get(STORE_KEY),
+        Err(e) => {
+            warn!("Failed to open {}: {}", STORE_FILE, e);
+            None
+        }
+    };
+    stored.and_then(|value| serde_json::from_value(value).ok()).unwrap_or_default()
+}
+
+fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: &BackupSettings) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    let value = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+    store.set(STORE_KEY, value);
+    store.save().map_err(|e| format!("Failed to save {}: {}", STORE_FILE, e))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+    pub bytes: u64,
+}
+
+/// Where the open workspace's snapshots are kept
+pub fn backups_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    Ok(crate::workspaces::data_dir(app)?.join(BACKUPS_DIR))
+}
+
+fn snapshot_name(time: DateTime<Utc>) -> String {
+    format!("{}{}{}", SNAPSHOT_PREFIX, time.format(SNAPSHOT_TIME_FORMAT), SNAPSHOT_EXTENSION)
+}
+
+/// When a snapshot was taken, from its file name
+fn snapshot_time(name: &str) -> Option<DateTime<Utc>> {
+    let time = name.strip_prefix(SNAPSHOT_PREFIX)?.strip_suffix(SNAPSHOT_EXTENSION)?;
+    NaiveDateTime::parse_from_str(time, SNAPSHOT_TIME_FORMAT).ok().map(|time| time.and_utc())
+}
+
+/// The snapshots in `dir`, newest first
+pub fn list_snapshots(dir: &Path) -> Vec<Snapshot> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<Snapshot> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let created_at = snapshot_time(&entry.file_name().to_string_lossy())?;
+            let bytes = entry.metadata().ok()?.len();
+            Some(Snapshot { path: entry.path(), created_at, bytes })
+        })
+        .collect();
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.created_at));
+    snapshots
+}
+
+/// The snapshots beyond the newest `keep`, given newest first
+fn expired(snapshots: &[Snapshot], keep: u32) -> &[Snapshot] {
+    snapshots.get(keep as usize..).unwrap_or(&[])
+}
+
+fn is_due(latest: Option<DateTime<Utc>>, interval_hours: u32, now: DateTime<Utc>) -> bool {
+    match latest {
+        Some(latest) => now - latest >= chrono::Duration::hours(interval_hours as i64),
+        None => true,
+    }
+}
+
+/// Whether the file at `path` is a plain SQLite database rather than an encrypted one
+pub fn is_plain_database(path: &Path) -> Result<bool, String> {
+    let mut header = [0u8; 16];
+    let mut file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    file.read_exact(&mut header).map_err(|e| format!("{} isn't a database: {}", path.display(), e))?;
+    Ok(&header == SQLITE_HEADER)
+}
+
+/// The problems `PRAGMA integrity_check` finds in the attached database `schema`
+async fn integrity_problems(conn: &mut sqlx::SqliteConnection, schema: &str) -> Result<Vec<String>, String> {
+    Ok(sqlx::query_scalar::<_, String>(&format!("PRAGMA {}.integrity_check", schema))
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to check the database copy: {}", e))?
+        .into_iter()
+        .filter(|line| line != "ok")
+        .collect())
+}
+
+fn attach_statement(path: &Path, schema: &str, key: Option<&crate::encryption::EncryptionKey>) -> String {
+    format!(
+        "ATTACH DATABASE '{}' AS {} KEY {}",
+        path.to_string_lossy().replace('\'', "''"),
+        schema,
+        key_pragma(key)
+    )
+}
+
+/// Copies the open database to `target`, encrypted like it, and checks the copy
+pub async fn copy_database(pool: &SqlitePool, target: &Path) -> Result<(), String> {
+    let mut conn = pool.acquire().await.map_err(|e| format!("Failed to open the database: {}", e))?;
+    sqlx::query(&attach_statement(target, "snapshot", crate::encryption::active_key().as_ref()))
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    let copied = match sqlx::query("SELECT sqlcipher_export('snapshot')").execute(&mut *conn).await {
+        Ok(_) => integrity_problems(&mut conn, "snapshot").await,
+        Err(e) => Err(format!("Failed to copy the database: {}", e)),
+    };
+    let _ = sqlx::query("DETACH DATABASE snapshot").execute(&mut *conn).await;
+
+    let result = match copied {
+        Ok(problems) if problems.is_empty() => Ok(()),
+        Ok(problems) => Err(format!("The database copy failed its integrity check: {}", problems.join("; "))),
+        Err(e) => Err(e),
+    };
+    if result.is_err() {
+        let _ = std::fs::remove_file(target);
+    }
+    result
+}
+
+/// Takes a snapshot now and deletes the ones beyond the number to keep
+pub async fn take_snapshot<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool) -> Result<Snapshot, String> {
+    let dir = backups_dir(app)?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let created_at = Utc::now();
+    let path = dir.join(snapshot_name(created_at));
+    copy_database(pool, &path).await?;
+    let bytes = std::fs::metadata(&path).map(|metadata| metadata.len()).unwrap_or(0);
+
+    for old in expired(&list_snapshots(&dir), load_settings(app).keep) {
+        if let Err(e) = std::fs::remove_file(&old.path) {
+            warn!("Failed to delete old snapshot {:?}: {}", old.path, e);
+        }
+    }
+    info!("💾 Database snapshot saved to {:?}", path);
+    Ok(Snapshot { path, created_at, bytes })
+}
+
+/// Takes a snapshot whenever the newest is older than the interval
+pub fn start_backup_scheduler<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_RUN_DELAY).await;
+        let pool = AppState::wait_for_pool(&app).await;
+        loop {
+            let settings = load_settings(&app);
+            if settings.enabled {
+                let latest = backups_dir(&app)
+                    .map(|dir| list_snapshots(&dir).first().map(|snapshot| snapshot.created_at))
+                    .unwrap_or(None);
+                if is_due(latest, settings.interval_hours, Utc::now()) {
+                    if let Err(e) = take_snapshot(&app, &pool).await {
+                        warn!("💾 Database snapshot failed: {}", e);
+                    }
+                }
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+fn pending_restore_path(db_path: &Path) -> PathBuf {
+    db_path.with_file_name(PENDING_RESTORE_FILE)
+}
+
+/// Puts a restored database in place of the one at `db_path`, which is kept as a snapshot;
+/// called before the database opens. Returns whether there was one.
+pub fn complete_pending_restore(db_path: &Path) -> Result<bool, String> {
+    let pending = pending_restore_path(db_path);
+    if !pending.exists() {
+        return Ok(false);
+    }
+
+    if db_path.exists() {
+        let dir = db_path.with_file_name(BACKUPS_DIR);
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let kept = dir.join(snapshot_name(Utc::now()));
+        // The write-ahead log goes with it, so the kept copy opens with everything in it
+        for suffix in ["", "-wal", "-shm"] {
+            let from = PathBuf::from(format!("{}{}", db_path.to_string_lossy(), suffix));
+            if from.exists() {
+                let to = PathBuf::from(format!("{}{}", kept.to_string_lossy(), suffix));
+                std::fs::rename(&from, &to).map_err(|e| format!("Failed to move {:?} aside: {}", from, e))?;
+            }
+        }
+        info!("💾 Kept the replaced database as {:?}", kept);
+    }
+    std::fs::rename(&pending, db_path).map_err(|e| format!("Failed to move {:?} into place: {}", pending, e))?;
+    info!("💾 Restored the database from a backup");
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_snapshot_names() {
+        let time = Utc.with_ymd_and_hms(2025, 11, 16, 9, 30, 5).unwrap();
+        let name = snapshot_name(time);
+        assert_eq!(name, "meeting_minutes-20251116-093005.sqlite");
+        assert_eq!(snapshot_time(&name), Some(time));
+        assert_eq!(snapshot_time("meeting_minutes.sqlite"), None);
+        assert_eq!(snapshot_time("meeting_minutes-20251116-093005.sqlite-wal"), None);
+    }
+
+    #[test]
+    fn test_schedule_and_pruning() {
+        let now = Utc.with_ymd_and_hms(2025, 11, 16, 12, 0, 0).unwrap();
+        assert!(is_due(None, 24, now));
+        assert!(!is_due(Some(now - chrono::Duration::hours(23)), 24, now));
+        assert!(is_due(Some(now - chrono::Duration::hours(24)), 24, now));
+
+        let snapshots: Vec<Snapshot> = (0..5)
+            .map(|age| Snapshot { path: PathBuf::from(format!("{}", age)), created_at: now, bytes: 0 })
+            .collect();
+        assert_eq!(expired(&snapshots, 3).len(), 2);
+        assert_eq!(expired(&snapshots, 3)[0].path, PathBuf::from("3"));
+        assert!(expired(&snapshots, 7).is_empty());
+    }
+
+    #[test]
+    fn test_complete_pending_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("meeting_minutes.sqlite");
+        std::fs::write(&db, "current").unwrap();
+        std::fs::write(dir.path().join("meeting_minutes.sqlite-wal"), "wal").unwrap();
+        assert!(!complete_pending_restore(&db).unwrap());
+
+        std::fs::write(pending_restore_path(&db), "restored").unwrap();
+        assert!(complete_pending_restore(&db).unwrap());
+        assert_eq!(std::fs::read_to_string(&db).unwrap(), "restored");
+        assert!(!dir.path().join("meeting_minutes.sqlite-wal").exists());
+
+        let kept = list_snapshots(&dir.path().join(BACKUPS_DIR));
+        assert_eq!(kept.len(), 1);
+        assert_eq!(std::fs::read_to_string(&kept[0].path).unwrap(), "current");
+        assert!(kept[0].path.with_file_name(format!("{}-wal", snapshot_name(kept[0].created_at))).exists());
+    }
+}
@@ -0,0 +1,262 @@
+// meeting-minutes-cli
+//
+// Records or transcribes meetings from the terminal and prints Markdown minutes, for
+// servers and automation. Uses the app's pipeline through `app_lib::headless`, and the
+// Whisper models the app downloaded. See `docs/cli.md`.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use app_lib::audio::transcription::cloud_providers::encode_wav_16k;
+use app_lib::headless::{self, SummaryOptions};
+use app_lib::jobs::file_transcription::FileTranscriptSegment;
+use app_lib::summary::templates::TemplateVariables;
+use app_lib::summary::LLMProvider;
+use app_lib::whisper_engine::ModelStatus;
+use chrono::{Local, Utc};
+use clap::{Args, Parser, Subcommand};
+
+/// Record, transcribe and summarize meetings without the app window
+#[derive(Parser)]
+#[command(name = "meeting-minutes-cli", version)]
+struct Cli {
+    /// Log progress details to stderr
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Transcribe an audio or video file and print the minutes
+    Transcribe {
+        /// Any file FFmpeg can read
+        file: PathBuf,
+
+        #[command(flatten)]
+        minutes: MinutesArgs,
+    },
+    /// Record a microphone until Ctrl-C (or for --duration) and print the minutes
+    Record {
+        /// Microphone to record, as `devices` lists it; the default microphone otherwise
+        #[arg(long)]
+        device: Option<String>,
+
+        /// Stop after this many seconds
+        #[arg(long)]
+        duration: Option<u64>,
+
+        /// Also save the recording as a 16kHz WAV file
+        #[arg(long, value_name = "PATH")]
+        save_audio: Option<PathBuf>,
+
+        #[command(flatten)]
+        minutes: MinutesArgs,
+    },
+    /// List the microphones that can be recorded
+    Devices,
+    /// List the Whisper models and whether they're downloaded
+    Models {
+        /// Where the models are; the app's models folder by default
+        #[arg(long)]
+        models_dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+struct MinutesArgs {
+    /// Whisper model to transcribe with; download it in the app first
+    #[arg(long, default_value = "large-v3-turbo")]
+    whisper_model: String,
+
+    /// Where the Whisper and built-in summary models are; the app's models folder by default
+    #[arg(long)]
+    models_dir: Option<PathBuf>,
+
+    /// Spoken language code, e.g. "en"; detected when left out
+    #[arg(long)]
+    language: Option<String>,
+
+    /// Only transcribe; leave out the summary
+    #[arg(long)]
+    no_summary: bool,
+
+    /// Summary provider: ollama, llama-cpp, builtin, openai, claude, groq or openrouter
+    #[arg(long, default_value = "ollama")]
+    provider: String,
+
+    /// Summary model; the provider's default when left out
+    #[arg(long)]
+    llm_model: Option<String>,
+
+    /// API key for hosted providers; MEETILY_API_KEY or the key saved in the app otherwise
+    #[arg(long)]
+    api_key: Option<String>,
+
+    /// Server address for Ollama or llama.cpp
+    #[arg(long)]
+    endpoint: Option<String>,
+
+    /// Summary template, e.g. standard_meeting, daily_standup, one_on_one
+    #[arg(long, default_value = "standard_meeting")]
+    template: String,
+
+    /// Extra context for the summary, such as who attended
+    #[arg(long)]
+    context: Option<String>,
+
+    /// Meeting title; taken from the summary when left out
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Write the minutes to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+fn progress(done: usize, total: usize) {
+    eprint!("\rTranscribing: {}/{} segments", done, total);
+    if done == total {
+        eprintln!();
+    }
+}
+
+/// Waits for Ctrl-C, or until `duration` seconds have passed
+async fn stop_signal(duration: Option<u64>) {
+    let timeout = async {
+        match duration {
+            Some(seconds) => tokio::time::sleep(Duration::from_secs(seconds)).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = timeout => {}
+    }
+}
+
+async fn write_minutes(args: &MinutesArgs, transcript: &[FileTranscriptSegment], duration: f64) -> Result<(), String> {
+    let summary = if args.no_summary {
+        None
+    } else {
+        let options = SummaryOptions {
+            provider: LLMProvider::from_str(&args.provider)?,
+            model: args.llm_model.clone(),
+            api_key: args.api_key.clone().or_else(|| std::env::var("MEETILY_API_KEY").ok()),
+            endpoint: args.endpoint.clone(),
+            template_id: args.template.clone(),
+            custom_prompt: args.context.clone().unwrap_or_default(),
+        };
+        let variables = TemplateVariables {
+            title: args.title.clone(),
+            date: Some(Local::now().format("%B %-d, %Y").to_string()),
+            duration_seconds: Some(duration),
+            ..Default::default()
+        };
+        eprintln!("Summarizing with {}...", options.provider.display_name());
+        Some(headless::summarize(&options, transcript, &variables, &mut |_: &str| {}).await?)
+    };
+
+    let minutes = headless::render_minutes(args.title.clone(), Utc::now(), summary, transcript);
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, minutes).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            eprintln!("Minutes written to {}", path.display());
+        }
+        None => {
+            let mut stdout = std::io::stdout();
+            stdout.write_all(minutes.as_bytes()).map_err(|e| format!("Failed to write minutes: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+async fn load_whisper(args: &MinutesArgs) -> Result<app_lib::whisper_engine::WhisperEngine, String> {
+    let models_dir = match &args.models_dir {
+        Some(dir) => dir.clone(),
+        None => headless::default_models_dir().map_err(|e| e.to_string())?,
+    };
+    app_lib::summary::builtin_llm::set_models_dir(models_dir.clone());
+    headless::load_whisper(models_dir, &args.whisper_model)
+        .await
+        .map_err(|e| format!("Failed to load Whisper model {}: {}", args.whisper_model, e))
+}
+
+async fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Transcribe { file, minutes } => {
+            let engine = load_whisper(&minutes).await?;
+            let transcript = headless::transcribe_file(&engine, &file, minutes.language.clone(), progress)
+                .await
+                .map_err(|e| e.to_string())?;
+            if transcript.segments.is_empty() {
+                return Err(format!("No speech detected in {}", file.display()));
+            }
+            write_minutes(&minutes, &transcript.segments, transcript.duration).await
+        }
+        Command::Record { device, duration, save_audio, minutes } => {
+            // Loaded first, so a missing model is reported before anything is recorded
+            let engine = load_whisper(&minutes).await?;
+            eprintln!("Recording, press Ctrl-C to stop...");
+            let samples = headless::record_microphone(device.as_deref(), stop_signal(duration))
+                .await
+                .map_err(|e| format!("Recording failed: {}", e))?;
+            let seconds = samples.len() as f64 / 16000.0;
+            eprintln!("Recorded {:.0}s", seconds);
+            if let Some(path) = &save_audio {
+                std::fs::write(path, encode_wav_16k(&samples))
+                    .map_err(|e| format!("Failed to save {}: {}", path.display(), e))?;
+            }
+            let transcript = headless::transcribe_samples(&engine, samples, minutes.language.clone(), progress)
+                .await
+                .map_err(|e| e.to_string())?;
+            if transcript.is_empty() {
+                return Err("No speech detected in the recording".to_string());
+            }
+            write_minutes(&minutes, &transcript, seconds).await
+        }
+        Command::Devices => {
+            for name in headless::list_microphones().await.map_err(|e| e.to_string())? {
+                println!("{}", name);
+            }
+            Ok(())
+        }
+        Command::Models { models_dir } => {
+            let models_dir = match models_dir {
+                Some(dir) => dir,
+                None => headless::default_models_dir().map_err(|e| e.to_string())?,
+            };
+            let engine = app_lib::whisper_engine::WhisperEngine::new_with_models_dir(Some(models_dir))
+                .map_err(|e| e.to_string())?;
+            for model in engine.discover_models().await.map_err(|e| e.to_string())? {
+                let status = match model.status {
+                    ModelStatus::Available => "downloaded",
+                    ModelStatus::Missing => "not downloaded",
+                    ModelStatus::Downloading { .. } => "downloading",
+                    ModelStatus::Error(_) | ModelStatus::Corrupted { .. } => "broken",
+                };
+                println!("{:<24} {:>6} MB  {}", model.name, model.size_mb, status);
+            }
+            Ok(())
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let level = if cli.verbose { "info" } else { "warn" };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(level)).init();
+
+    match run(cli.command).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// calendar/caldav.rs
+//
+// Events of a CalDAV calendar (iCloud, Fastmail, Nextcloud, ...), read with a
+// calendar-query REPORT. The server expands recurring events into the instances within the
+// requested range, and returns each as iCalendar data inside the XML response.
+
+use super::{ical, FetchedEvent};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static CALENDAR_DATA: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<(?:[A-Za-z0-9]+:)?calendar-data[^>]*>(.*?)</(?:[A-Za-z0-9]+:)?calendar-data>").unwrap());
+
+fn ical_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn query_body(from: DateTime<Utc>, to: DateTime<Utc>) -> String {
+    let (start, end) = (ical_time(from), ical_time(to));
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<C:calendar-query xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:prop>
+    <C:calendar-data>
+      <C:expand start="{start}" end="{end}"/>
+    </C:calendar-data>
+  </D:prop>
+  <C:filter>
+    <C:comp-filter name="VCALENDAR">
+      <C:comp-filter name="VEVENT">
+        <C:time-range start="{start}" end="{end}"/>
+      </C:comp-filter>
+    </C:comp-filter>
+  </C:filter>
+</C:calendar-query>"#
+    )
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&amp;", "&")
+}
+
+/// The iCalendar documents in a multistatus response
+fn calendar_data(xml: &str) -> Vec<String> {
+    CALENDAR_DATA
+        .captures_iter(xml)
+        .map(|captures| {
+            let data = captures[1].trim();
+            match data.strip_prefix("<![CDATA[").and_then(|data| data.strip_suffix("]]>")) {
+                Some(cdata) => cdata.to_string(),
+                None => unescape_xml(data),
+            }
+        })
+        .collect()
+}
+
+/// Events between `from` and `to`; `url` is the calendar collection, not the account
+pub async fn list_events(
+    url: &str,
+    username: &str,
+    password: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<FetchedEvent>, String> {
+    let method = reqwest::Method::from_bytes(b"REPORT").map_err(|e| e.to_string())?;
+    let response = crate::network::client()
+        .request(method, url)
+        .basic_auth(username, Some(password))
+        .header("Depth", "1")
+        .header("Content-Type", "application/xml; charset=utf-8")
+        .body(query_body(from, to))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach the CalDAV server: {}", e))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err("The CalDAV server rejected the username or password".to_string());
+    }
+    if !status.is_success() {
+        return Err(format!("CalDAV request failed ({}); check that the URL points to a calendar", status));
+    }
+    let xml = response.text().await.map_err(|e| format!("Failed to read the CalDAV response: {}", e))?;
+    Ok(calendar_data(&xml).iter().flat_map(|ics| ical::parse_events(ics)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calendar_data() {
+        let xml = r#"<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+  <d:response><d:propstat><d:prop>
+    <cal:calendar-data>BEGIN:VCALENDAR&#13;
+SUMMARY:R&amp;D &lt;sync&gt;&#13;
+END:VCALENDAR</cal:calendar-data>
+  </d:prop></d:propstat></d:response>
+  <d:response><d:propstat><d:prop>
+    <calendar-data xmlns="urn:ietf:params:xml:ns:caldav"><![CDATA[BEGIN:VCALENDAR
+END:VCALENDAR]]></calendar-data>
+  </d:prop></d:propstat></d:response>
+</d:multistatus>"#;
+        let data = calendar_data(xml);
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0], "BEGIN:VCALENDAR\r\nSUMMARY:R&D <sync>\r\nEND:VCALENDAR");
+        assert_eq!(data[1], "BEGIN:VCALENDAR\nEND:VCALENDAR");
+    }
+}
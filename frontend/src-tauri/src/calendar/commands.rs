@@ -0,0 +1,217 @@
+// calendar/commands.rs
+//
+// Tauri commands for connecting calendars, browsing events and linking them to meetings.
+
+use super::{caldav, credential_key, oauth, sync, CalendarProvider};
+use crate::database::models::{CalendarAccount, CalendarEvent, CalendarRecordingRule};
+use crate::database::repositories::calendar::CalendarRepository;
+use crate::integrations::credentials;
+use crate::state::AppState;
+use chrono::{DateTime, Duration, Utc};
+use tracing::{info, warn};
+
+fn parse_provider(provider: &str) -> Result<CalendarProvider, String> {
+    CalendarProvider::from_string(provider).ok_or_else(|| format!("Unknown calendar provider: {}", provider))
+}
+
+fn parse_time(time: Option<String>, default: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    match time {
+        Some(time) => DateTime::parse_from_rfc3339(&time)
+            .map(|time| time.with_timezone(&Utc))
+            .map_err(|e| format!("Invalid time {}: {}", time, e)),
+        None => Ok(default),
+    }
+}
+
+/// The provider's address to open for connecting a Google or Outlook calendar
+#[tauri::command]
+pub async fn get_calendar_oauth_url(
+    provider: String,
+    client_id: String,
+    redirect_uri: String,
+    state: String,
+) -> Result<String, String> {
+    oauth::authorize_url(parse_provider(&provider)?, &client_id, &redirect_uri, &state)
+}
+
+/// Finishes OAuth with the code the provider redirected back with, then syncs the calendar
+#[tauri::command]
+pub async fn connect_calendar_oauth(
+    state: tauri::State<'_, AppState>,
+    provider: String,
+    code: String,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+    name: Option<String>,
+) -> Result<CalendarAccount, String> {
+    let provider = parse_provider(&provider)?;
+    let token = oauth::exchange_code(provider, &code, &client_id, &client_secret, &redirect_uri).await?;
+    let pool = state.db_manager.pool();
+    let name = name.filter(|name| !name.trim().is_empty()).unwrap_or_else(|| match provider {
+        CalendarProvider::Google => "Google Calendar".to_string(),
+        _ => "Outlook".to_string(),
+    });
+    let account = CalendarRepository::create_account(pool, provider.as_str(), &name, None, None)
+        .await
+        .map_err(|e| format!("Failed to save calendar account: {}", e))?;
+    let json = serde_json::to_string(&token).map_err(|e| e.to_string())?;
+    credentials::store_token(&credential_key(&account.id), &json).map_err(|e| e.to_string())?;
+    info!("Connected {} calendar {}", provider.as_str(), account.name);
+    if let Err(e) = sync::sync_account(pool, &account).await {
+        warn!("Initial sync of calendar {} failed: {}", account.name, e);
+    }
+    Ok(account)
+}
+
+/// Connects a CalDAV calendar; the credentials are checked by reading its events
+#[tauri::command]
+pub async fn connect_caldav(
+    state: tauri::State<'_, AppState>,
+    url: String,
+    username: String,
+    password: String,
+    name: Option<String>,
+) -> Result<CalendarAccount, String> {
+    let url = url.trim();
+    url::Url::parse(url).map_err(|e| format!("Invalid CalDAV URL: {}", e))?;
+    let (from, to) = sync::sync_window();
+    let events = caldav::list_events(url, &username, &password, from, to).await?;
+
+    let pool = state.db_manager.pool();
+    let name = name.filter(|name| !name.trim().is_empty()).unwrap_or_else(|| "Calendar".to_string());
+    let account = CalendarRepository::create_account(pool, CalendarProvider::Caldav.as_str(), &name, Some(url), Some(&username))
+        .await
+        .map_err(|e| format!("Failed to save calendar account: {}", e))?;
+    credentials::store_token(&credential_key(&account.id), &password).map_err(|e| e.to_string())?;
+    CalendarRepository::replace_events(pool, &account.id, &events, from, to)
+        .await
+        .map_err(|e| format!("Failed to save calendar events: {}", e))?;
+    info!("Connected CalDAV calendar {} ({} events)", account.name, events.len());
+    Ok(account)
+}
+
+#[tauri::command]
+pub async fn list_calendar_accounts(state: tauri::State<'_, AppState>) -> Result<Vec<CalendarAccount>, String> {
+    CalendarRepository::list_accounts(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load calendar accounts: {}", e))
+}
+
+#[tauri::command]
+pub async fn disconnect_calendar(state: tauri::State<'_, AppState>, account_id: String) -> Result<bool, String> {
+    credentials::delete_token(&credential_key(&account_id)).map_err(|e| e.to_string())?;
+    CalendarRepository::delete_account(state.db_manager.pool(), &account_id)
+        .await
+        .map_err(|e| format!("Failed to remove calendar account: {}", e))
+}
+
+/// Syncs every connected calendar now; returns the number of events in the sync window
+#[tauri::command]
+pub async fn sync_calendars(state: tauri::State<'_, AppState>) -> Result<usize, String> {
+    sync::sync_all(state.db_manager.pool()).await
+}
+
+/// Events between `from` and `to` (RFC 3339); defaults to now until two weeks ahead
+#[tauri::command]
+pub async fn list_calendar_events(
+    state: tauri::State<'_, AppState>,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<Vec<CalendarEvent>, String> {
+    let now = Utc::now();
+    let from = parse_time(from, now)?;
+    let to = parse_time(to, now + Duration::days(14))?;
+    CalendarRepository::list_events(state.db_manager.pool(), from, to)
+        .await
+        .map_err(|e| format!("Failed to load calendar events: {}", e))
+}
+
+/// The event under way, or starting within ten minutes, to offer when a recording starts
+#[tauri::command]
+pub async fn get_current_calendar_event(state: tauri::State<'_, AppState>) -> Result<Option<CalendarEvent>, String> {
+    let now = Utc::now();
+    let events = CalendarRepository::list_events(state.db_manager.pool(), now, now + Duration::minutes(10))
+        .await
+        .map_err(|e| format!("Failed to load calendar events: {}", e))?;
+    // All-day events are rarely what is being recorded
+    Ok(events
+        .into_iter()
+        .filter(|event| event.ends_at.0 - event.starts_at.0 < Duration::hours(12))
+        .max_by_key(|event| event.starts_at.0))
+}
+
+/// Links a meeting to an event, filling in its title, participants and agenda
+#[tauri::command]
+pub async fn link_meeting_to_calendar_event(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    event_id: String,
+    overwrite: Option<bool>,
+) -> Result<(), String> {
+    let pool = state.db_manager.pool();
+    let event = CalendarRepository::get_event(pool, &event_id)
+        .await
+        .map_err(|e| format!("Failed to load calendar event: {}", e))?
+        .ok_or_else(|| format!("No calendar event with id {}", event_id))?;
+    sync::link_meeting(pool, &meeting_id, &event, overwrite.unwrap_or(false)).await
+}
+
+/// Removes the link; the details copied from the event stay with the meeting
+#[tauri::command]
+pub async fn unlink_meeting_calendar_event(state: tauri::State<'_, AppState>, meeting_id: String) -> Result<bool, String> {
+    CalendarRepository::set_meeting_event(state.db_manager.pool(), &meeting_id, None)
+        .await
+        .map_err(|e| format!("Failed to unlink meeting: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_meeting_calendar_event(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Option<CalendarEvent>, String> {
+    CalendarRepository::meeting_event(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load calendar event: {}", e))
+}
+
+#[tauri::command]
+pub async fn list_calendar_recording_rules(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<CalendarRecordingRule>, String> {
+    CalendarRepository::list_recording_rules(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load recording rules: {}", e))
+}
+
+/// Marks an event, or with `whole_series` every instance of its recurring series, to be
+/// recorded automatically
+#[tauri::command]
+pub async fn add_calendar_recording_rule(
+    state: tauri::State<'_, AppState>,
+    event_id: String,
+    whole_series: bool,
+) -> Result<CalendarRecordingRule, String> {
+    let pool = state.db_manager.pool();
+    let event = CalendarRepository::get_event(pool, &event_id)
+        .await
+        .map_err(|e| format!("Failed to load calendar event: {}", e))?
+        .ok_or_else(|| format!("No calendar event with id {}", event_id))?;
+    let (target, target_id) = match (whole_series, &event.series_id) {
+        (true, Some(series_id)) => ("series", series_id),
+        (true, None) => return Err(format!("{} is not a recurring event", event.title)),
+        (false, _) => ("event", &event.external_id),
+    };
+    let rule = CalendarRepository::add_recording_rule(pool, &event.account_id, target, target_id, &event.title)
+        .await
+        .map_err(|e| format!("Failed to save recording rule: {}", e))?;
+    info!("📅 Will record {} {} automatically", target, event.title);
+    Ok(rule)
+}
+
+#[tauri::command]
+pub async fn delete_calendar_recording_rule(state: tauri::State<'_, AppState>, rule_id: String) -> Result<bool, String> {
+    CalendarRepository::delete_recording_rule(state.db_manager.pool(), &rule_id)
+        .await
+        .map_err(|e| format!("Failed to delete recording rule: {}", e))
+}
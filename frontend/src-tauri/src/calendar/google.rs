@@ -0,0 +1,124 @@
+// calendar/google.rs
+//
+// Events of the primary Google calendar, through the Calendar API v3.
+
+use super::FetchedEvent;
+use crate::database::models::Participant;
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use serde_json::Value;
+
+const EVENTS_URL: &str = "https://www.googleapis.com/calendar/v3/calendars/primary/events";
+
+/// Events between `from` and `to`, recurring events expanded into their instances
+pub async fn list_events(access_token: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<FetchedEvent>, String> {
+    let client = crate::network::client();
+    let mut events = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let mut query = vec![
+            ("singleEvents", "true".to_string()),
+            ("orderBy", "startTime".to_string()),
+            ("maxResults", "250".to_string()),
+            ("timeMin", from.to_rfc3339()),
+            ("timeMax", to.to_rfc3339()),
+        ];
+        if let Some(token) = page_token.take() {
+            query.push(("pageToken", token));
+        }
+        let response = client
+            .get(EVENTS_URL)
+            .bearer_auth(access_token)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Google Calendar: {}", e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Google Calendar request failed ({}): {}", status, body));
+        }
+        let page: Value = response.json().await.map_err(|e| format!("Unexpected response from Google Calendar: {}", e))?;
+        events.extend(parse_events(&page));
+        match page["nextPageToken"].as_str() {
+            Some(token) => page_token = Some(token.to_string()),
+            None => return Ok(events),
+        }
+    }
+}
+
+/// `start`/`end` objects hold `dateTime`, or `date` for all-day events
+fn parse_time(time: &Value) -> Option<DateTime<Utc>> {
+    if let Some(date_time) = time["dateTime"].as_str() {
+        return DateTime::parse_from_rfc3339(date_time).ok().map(|t| t.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(time["date"].as_str()?, "%Y-%m-%d").ok()?;
+    Local.from_local_datetime(&date.and_hms_opt(0, 0, 0)?).earliest().map(|t| t.with_timezone(&Utc))
+}
+
+fn parse_events(page: &Value) -> Vec<FetchedEvent> {
+    page["items"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|item| item["status"] != "cancelled")
+        .filter_map(|item| {
+            let mut attendees: Vec<Participant> = Vec::new();
+            for person in std::iter::once(&item["organizer"]).chain(item["attendees"].as_array().into_iter().flatten()) {
+                let Some(email) = person["email"].as_str() else {
+                    continue;
+                };
+                // Rooms and other resources are listed as attendees too
+                if person["resource"] == true || attendees.iter().any(|a| a.email.as_deref() == Some(email)) {
+                    continue;
+                }
+                let name = person["displayName"].as_str().unwrap_or(email).to_string();
+                attendees.push(Participant { name, email: Some(email.to_string()) });
+            }
+            Some(FetchedEvent {
+                external_id: item["id"].as_str()?.to_string(),
+                series_id: item["recurringEventId"].as_str().map(str::to_string),
+                title: item["summary"].as_str().unwrap_or("Untitled event").to_string(),
+                starts_at: parse_time(&item["start"])?,
+                ends_at: parse_time(&item["end"])?,
+                description: item["description"].as_str().map(str::to_string),
+                location: item["location"].as_str().map(str::to_string),
+                attendees,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_events() {
+        let page = json!({
+            "items": [
+                {
+                    "id": "evt1_20251110T090000Z",
+                    "recurringEventId": "evt1",
+                    "summary": "Design review",
+                    "description": "Agenda: mockups",
+                    "start": { "dateTime": "2025-11-10T10:00:00+01:00" },
+                    "end": { "dateTime": "2025-11-10T11:00:00+01:00" },
+                    "organizer": { "email": "ana@example.com", "displayName": "Ana" },
+                    "attendees": [
+                        { "email": "ana@example.com", "displayName": "Ana" },
+                        { "email": "room-1@resource.calendar.google.com", "resource": true },
+                        { "email": "bo@example.com" }
+                    ]
+                },
+                { "id": "evt2", "status": "cancelled", "start": {}, "end": {} }
+            ]
+        });
+        let events = parse_events(&page);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].starts_at.to_rfc3339(), "2025-11-10T09:00:00+00:00");
+        assert_eq!(events[0].series_id.as_deref(), Some("evt1"));
+        let names: Vec<&str> = events[0].attendees.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["Ana", "bo@example.com"]);
+    }
+}
@@ -0,0 +1,255 @@
+// calendar/ical.rs
+//
+// Reads the events of an iCalendar (RFC 5545) document, as returned by CalDAV servers.
+//
+// Only what a meeting needs is read: title, times, description, location and attendees.
+// Times with a TZID are read in the computer's time zone, which is right for the common
+// case of events in the user's own zone; recurring events arrive expanded from the server.
+
+use super::FetchedEvent;
+use crate::database::models::Participant;
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::collections::HashMap;
+
+/// A content line: name, parameters and value
+struct Property {
+    name: String,
+    params: HashMap<String, String>,
+    value: String,
+}
+
+/// Joins folded lines (continuations start with a space or tab)
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        let line = line.trim_end_matches('\r');
+        match (line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    // The value starts at the first colon outside a quoted parameter
+    let mut quoted = false;
+    let split = line.char_indices().find(|(_, c)| {
+        if *c == '"' {
+            quoted = !quoted;
+        }
+        *c == ':' && !quoted
+    })?;
+    let (head, value) = (&line[..split.0], &line[split.0 + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.to_uppercase();
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.to_uppercase(), value.trim_matches('"').to_string()))
+        .collect();
+    Some(Property { name, params, value: value.to_string() })
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => {}
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// DATE-TIME or DATE value; the bool is true for all-day dates
+fn parse_time(property: &Property) -> Option<(DateTime<Utc>, bool)> {
+    let value = property.value.trim();
+    if property.params.get("VALUE").map(String::as_str) == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        let local = Local.from_local_datetime(&date.and_hms_opt(0, 0, 0)?).earliest()?;
+        return Some((local.with_timezone(&Utc), true));
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((Utc.from_utc_datetime(&naive), false));
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Some((Local.from_local_datetime(&naive).earliest()?.with_timezone(&Utc), false))
+}
+
+/// A DURATION value such as `PT1H30M` or `P1D`
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim().trim_start_matches('+');
+    let rest = value.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match unit {
+                    'W' => Duration::weeks(n),
+                    'D' => Duration::days(n),
+                    'H' => Duration::hours(n),
+                    'M' => Duration::minutes(n),
+                    'S' => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(total)
+}
+
+fn participant(property: &Property) -> Option<Participant> {
+    let value = property.value.trim();
+    let email = value
+        .strip_prefix("mailto:")
+        .or_else(|| value.strip_prefix("MAILTO:"))
+        .filter(|email| email.contains('@'))
+        .map(str::to_string);
+    let name = property.params.get("CN").cloned().filter(|name| !name.trim().is_empty()).or_else(|| email.clone())?;
+    Some(Participant { name, email })
+}
+
+fn build_event(properties: &[Property]) -> Option<FetchedEvent> {
+    let get = |name: &str| properties.iter().find(|p| p.name == name);
+    if get("STATUS").is_some_and(|status| status.value.eq_ignore_ascii_case("CANCELLED")) {
+        return None;
+    }
+    let uid = get("UID")?.value.clone();
+    let (starts_at, all_day) = parse_time(get("DTSTART")?)?;
+    let ends_at = match (get("DTEND").and_then(parse_time), get("DURATION").and_then(|d| parse_duration(&d.value))) {
+        (Some((end, _)), _) => end,
+        (None, Some(duration)) => starts_at + duration,
+        (None, None) if all_day => starts_at + Duration::days(1),
+        (None, None) => starts_at,
+    };
+    // Instances of a recurring event share the UID
+    let (external_id, series_id) = match get("RECURRENCE-ID") {
+        Some(recurrence) => (format!("{}/{}", uid, recurrence.value), Some(uid)),
+        None if get("RRULE").is_some() => (uid.clone(), Some(uid)),
+        None => (uid, None),
+    };
+
+    let mut attendees: Vec<Participant> = Vec::new();
+    for property in properties.iter().filter(|p| p.name == "ORGANIZER" || p.name == "ATTENDEE") {
+        if let Some(participant) = participant(property) {
+            let duplicate = attendees.iter().any(|a| a.email.is_some() && a.email == participant.email);
+            if !duplicate {
+                attendees.push(participant);
+            }
+        }
+    }
+    let text = |name: &str| get(name).map(|p| unescape(&p.value)).filter(|text| !text.trim().is_empty());
+    Some(FetchedEvent {
+        external_id,
+        series_id,
+        title: text("SUMMARY").unwrap_or_else(|| "Untitled event".to_string()),
+        starts_at,
+        ends_at,
+        description: text("DESCRIPTION"),
+        location: text("LOCATION"),
+        attendees,
+    })
+}
+
+/// The events of an iCalendar document; cancelled events are left out
+pub fn parse_events(ics: &str) -> Vec<FetchedEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<Vec<Property>> = None;
+    // Alarms nested in an event have properties of their own
+    let mut nested = 0;
+    for line in unfold(ics) {
+        let Some(property) = parse_property(&line) else {
+            continue;
+        };
+        match (property.name.as_str(), property.value.to_uppercase().as_str()) {
+            ("BEGIN", "VEVENT") => current = Some(Vec::new()),
+            ("END", "VEVENT") => {
+                if let Some(event) = current.take().as_deref().and_then(build_event) {
+                    events.push(event);
+                }
+            }
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", _) if current.is_some() => nested -= 1,
+            _ if nested == 0 => {
+                if let Some(properties) = current.as_mut() {
+                    properties.push(property);
+                }
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICS: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+UID:abc-123\r\n\
+DTSTART:20251110T090000Z\r\n\
+DTEND:20251110T093000Z\r\n\
+SUMMARY:Weekly sync\\, team\r\n\
+DESCRIPTION:1. Roadmap\\n2. Hiring upd\r\n \
+ate\r\n\
+ORGANIZER;CN=Ana Lima:mailto:ana@example.com\r\n\
+ATTENDEE;CN=\"Lima, Ana\";ROLE=REQ-PARTICIPANT:mailto:ana@example.com\r\n\
+ATTENDEE;CN=Bo:mailto:bo@example.com\r\n\
+BEGIN:VALARM\r\n\
+DESCRIPTION:Reminder\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:gone\r\n\
+STATUS:CANCELLED\r\n\
+DTSTART:20251110T100000Z\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:series\r\n\
+RECURRENCE-ID:20251111T080000Z\r\n\
+DTSTART:20251111T080000Z\r\n\
+DURATION:PT1H15M\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_parse_events() {
+        let events = parse_events(ICS);
+        assert_eq!(events.len(), 2);
+        let sync = &events[0];
+        assert_eq!(sync.title, "Weekly sync, team");
+        assert_eq!(sync.description.as_deref(), Some("1. Roadmap\n2. Hiring update"));
+        assert_eq!(sync.starts_at.to_rfc3339(), "2025-11-10T09:00:00+00:00");
+        assert_eq!((sync.ends_at - sync.starts_at).num_minutes(), 30);
+        let names: Vec<&str> = sync.attendees.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["Ana Lima", "Bo"]);
+
+        assert_eq!(sync.series_id, None);
+
+        assert_eq!(events[1].external_id, "series/20251111T080000Z");
+        assert_eq!(events[1].series_id.as_deref(), Some("series"));
+        assert_eq!(events[1].title, "Untitled event");
+        assert_eq!((events[1].ends_at - events[1].starts_at).num_minutes(), 75);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("P1DT2H"), Some(Duration::hours(26)));
+        assert_eq!(parse_duration("PT45M"), Some(Duration::minutes(45)));
+        assert_eq!(parse_duration("1H"), None);
+    }
+}
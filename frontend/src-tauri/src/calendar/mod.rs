@@ -0,0 +1,75 @@
+/// Calendar module - upcoming events as context for meetings
+///
+/// Google and Outlook calendars are connected through OAuth, other calendars (iCloud,
+/// Fastmail, Nextcloud, ...) through CalDAV. Events from yesterday to two weeks ahead are
+/// synced periodically. Linking a meeting to an event copies the event's title, attendees
+/// and agenda into the meeting, and the agenda is given to the model when summarizing.
+///
+/// This module contains:
+/// - iCalendar parsing for CalDAV (`ical`)
+/// - OAuth tokens shared by Google and Outlook (`oauth`)
+/// - The providers' event APIs (`google`, `outlook`, `caldav`)
+/// - Syncing and linking events to meetings (`sync`)
+/// - Recording events marked "always record" (`scheduler`)
+/// - Tauri commands for frontend integration
+
+pub mod caldav;
+pub mod commands;
+pub mod google;
+pub mod ical;
+pub mod oauth;
+pub mod outlook;
+pub mod scheduler;
+pub mod sync;
+
+use crate::database::models::Participant;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CalendarProvider {
+    Google,
+    Outlook,
+    Caldav,
+}
+
+impl CalendarProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Google => "google",
+            Self::Outlook => "outlook",
+            Self::Caldav => "caldav",
+        }
+    }
+
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "google" => Some(Self::Google),
+            "outlook" => Some(Self::Outlook),
+            "caldav" => Some(Self::Caldav),
+            _ => None,
+        }
+    }
+}
+
+/// An event as read from a provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchedEvent {
+    /// The provider's id; stays the same when the event is edited
+    pub external_id: String,
+    /// Shared by the instances of a recurring event
+    pub series_id: Option<String>,
+    pub title: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// The invitation text, used as the agenda
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub attendees: Vec<Participant>,
+}
+
+/// Credential store key of an account's OAuth token or CalDAV password
+pub fn credential_key(account_id: &str) -> String {
+    format!("calendar:{}", account_id)
+}
@@ -0,0 +1,161 @@
+// calendar/oauth.rs
+//
+// OAuth authorization-code flow for Google and Outlook calendars.
+//
+// Access tokens last about an hour, so the refresh token is stored along with them, and
+// with the client id and secret the app was registered with, which refreshing requires.
+// The whole token is kept as JSON in the OS credential store.
+
+use super::CalendarProvider;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: Option<i64>,
+}
+
+struct Endpoints {
+    authorize: &'static str,
+    token: &'static str,
+    scope: &'static str,
+}
+
+fn endpoints(provider: CalendarProvider) -> Result<Endpoints, String> {
+    match provider {
+        CalendarProvider::Google => Ok(Endpoints {
+            authorize: "https://accounts.google.com/o/oauth2/v2/auth",
+            token: "https://oauth2.googleapis.com/token",
+            scope: "https://www.googleapis.com/auth/calendar.readonly",
+        }),
+        CalendarProvider::Outlook => Ok(Endpoints {
+            authorize: "https://login.microsoftonline.com/common/oauth2/v2.0/authorize",
+            token: "https://login.microsoftonline.com/common/oauth2/v2.0/token",
+            scope: "offline_access Calendars.Read",
+        }),
+        CalendarProvider::Caldav => Err("CalDAV calendars connect with a username and password".to_string()),
+    }
+}
+
+/// The address to send the user to for connecting a calendar
+pub fn authorize_url(provider: CalendarProvider, client_id: &str, redirect_uri: &str, state: &str) -> Result<String, String> {
+    let endpoints = endpoints(provider)?;
+    let mut url = url::Url::parse(endpoints.authorize).map_err(|e| e.to_string())?;
+    url.query_pairs_mut()
+        .append_pair("client_id", client_id)
+        .append_pair("response_type", "code")
+        .append_pair("redirect_uri", redirect_uri)
+        .append_pair("scope", endpoints.scope)
+        .append_pair("state", state);
+    if provider == CalendarProvider::Google {
+        // Without these Google issues no refresh token
+        url.query_pairs_mut().append_pair("access_type", "offline").append_pair("prompt", "consent");
+    }
+    Ok(url.to_string())
+}
+
+async fn request_token(provider: CalendarProvider, form: &[(&str, &str)]) -> Result<TokenResponse, String> {
+    let endpoints = endpoints(provider)?;
+    let response = crate::network::client()
+        .post(endpoints.token)
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach {}: {}", provider.as_str(), e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("{} rejected the authorization ({}): {}", provider.as_str(), status, body));
+    }
+    response.json().await.map_err(|e| format!("Unexpected token response: {}", e))
+}
+
+fn expires_at(expires_in: Option<i64>) -> DateTime<Utc> {
+    // A minute early, so a token doesn't expire mid-request
+    Utc::now() + Duration::seconds(expires_in.unwrap_or(3600) - 60)
+}
+
+/// Trades the code from the redirect for tokens
+pub async fn exchange_code(
+    provider: CalendarProvider,
+    code: &str,
+    client_id: &str,
+    client_secret: &str,
+    redirect_uri: &str,
+) -> Result<OAuthToken, String> {
+    let token = request_token(
+        provider,
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("redirect_uri", redirect_uri),
+        ],
+    )
+    .await?;
+    Ok(OAuthToken {
+        access_token: token.access_token,
+        refresh_token: token.refresh_token,
+        expires_at: expires_at(token.expires_in),
+        client_id: client_id.to_string(),
+        client_secret: client_secret.to_string(),
+    })
+}
+
+impl OAuthToken {
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+
+    /// A new access token; providers may also rotate the refresh token
+    pub async fn refresh(&self, provider: CalendarProvider) -> Result<OAuthToken, String> {
+        let refresh_token = self
+            .refresh_token
+            .as_deref()
+            .ok_or("The calendar authorization expired; connect the calendar again")?;
+        let token = request_token(
+            provider,
+            &[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+                ("client_id", &self.client_id),
+                ("client_secret", &self.client_secret),
+            ],
+        )
+        .await?;
+        Ok(OAuthToken {
+            access_token: token.access_token,
+            refresh_token: token.refresh_token.or_else(|| self.refresh_token.clone()),
+            expires_at: expires_at(token.expires_in),
+            client_id: self.client_id.clone(),
+            client_secret: self.client_secret.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authorize_url() {
+        let url = authorize_url(CalendarProvider::Google, "client", "http://localhost:1420/calendar", "xyz").unwrap();
+        assert!(url.starts_with("https://accounts.google.com/o/oauth2/v2/auth?client_id=client&response_type=code"));
+        assert!(url.contains("access_type=offline"));
+        let url = authorize_url(CalendarProvider::Outlook, "client", "http://localhost:1420/calendar", "xyz").unwrap();
+        assert!(url.contains("scope=offline_access+Calendars.Read"));
+        assert!(authorize_url(CalendarProvider::Caldav, "client", "http://localhost", "xyz").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+// calendar/outlook.rs
+//
+// Events of the default Outlook / Microsoft 365 calendar, through Microsoft Graph.
+
+use super::FetchedEvent;
+use crate::database::models::Participant;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use serde_json::Value;
+
+const CALENDAR_VIEW_URL: &str = "https://graph.microsoft.com/v1.0/me/calendarView";
+
+/// Events between `from` and `to`; the calendar view expands recurring events
+pub async fn list_events(access_token: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<FetchedEvent>, String> {
+    let client = crate::network::client();
+    let mut url = url::Url::parse(CALENDAR_VIEW_URL).map_err(|e| e.to_string())?;
+    url.query_pairs_mut()
+        .append_pair("startDateTime", &from.to_rfc3339())
+        .append_pair("endDateTime", &to.to_rfc3339())
+        .append_pair("$top", "100");
+    let mut next = Some(url.to_string());
+    let mut events = Vec::new();
+    while let Some(url) = next.take() {
+        let response = client
+            .get(&url)
+            .bearer_auth(access_token)
+            // Times in UTC and bodies as plain text rather than HTML
+            .header("Prefer", "outlook.timezone=\"UTC\", outlook.body-content-type=\"text\"")
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Outlook: {}", e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Outlook calendar request failed ({}): {}", status, body));
+        }
+        let page: Value = response.json().await.map_err(|e| format!("Unexpected response from Outlook: {}", e))?;
+        events.extend(parse_events(&page));
+        next = page["@odata.nextLink"].as_str().map(str::to_string);
+    }
+    Ok(events)
+}
+
+/// `dateTime` without an offset, in the zone asked for with the Prefer header
+fn parse_time(time: &Value) -> Option<DateTime<Utc>> {
+    let naive = NaiveDateTime::parse_from_str(time["dateTime"].as_str()?, "%Y-%m-%dT%H:%M:%S%.f").ok()?;
+    Some(Utc.from_utc_datetime(&naive))
+}
+
+fn participant(person: &Value) -> Option<Participant> {
+    let address = person["emailAddress"]["address"].as_str()?;
+    let name = person["emailAddress"]["name"].as_str().filter(|name| !name.is_empty()).unwrap_or(address);
+    Some(Participant { name: name.to_string(), email: Some(address.to_string()) })
+}
+
+fn parse_events(page: &Value) -> Vec<FetchedEvent> {
+    page["value"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|item| item["isCancelled"] != true)
+        .filter_map(|item| {
+            let mut attendees: Vec<Participant> = Vec::new();
+            let people = std::iter::once(&item["organizer"]).chain(
+                item["attendees"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    // Rooms and equipment are attendees of type "resource"
+                    .filter(|person| person["type"] != "resource"),
+            );
+            for participant in people.filter_map(participant) {
+                if !attendees.iter().any(|a| a.email == participant.email) {
+                    attendees.push(participant);
+                }
+            }
+            let description = item["body"]["content"]
+                .as_str()
+                .or_else(|| item["bodyPreview"].as_str())
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .map(str::to_string);
+            Some(FetchedEvent {
+                external_id: item["id"].as_str()?.to_string(),
+                series_id: item["seriesMasterId"].as_str().map(str::to_string),
+                title: item["subject"].as_str().filter(|s| !s.is_empty()).unwrap_or("Untitled event").to_string(),
+                starts_at: parse_time(&item["start"])?,
+                ends_at: parse_time(&item["end"])?,
+                description,
+                location: item["location"]["displayName"].as_str().filter(|l| !l.is_empty()).map(str::to_string),
+                attendees,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_events() {
+        let page = json!({
+            "value": [
+                {
+                    "id": "AAMk1",
+                    "subject": "Budget",
+                    "body": { "contentType": "text", "content": "  Q1 numbers\r\n" },
+                    "start": { "dateTime": "2025-11-10T09:00:00.0000000", "timeZone": "UTC" },
+                    "end": { "dateTime": "2025-11-10T09:45:00.0000000", "timeZone": "UTC" },
+                    "location": { "displayName": "" },
+                    "organizer": { "emailAddress": { "name": "Ana", "address": "ana@example.com" } },
+                    "attendees": [
+                        { "type": "required", "emailAddress": { "name": "Ana", "address": "ana@example.com" } },
+                        { "type": "resource", "emailAddress": { "name": "Room 1", "address": "room1@example.com" } },
+                        { "type": "optional", "emailAddress": { "name": "", "address": "bo@example.com" } }
+                    ]
+                },
+                { "id": "AAMk2", "isCancelled": true }
+            ]
+        });
+        let events = parse_events(&page);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].description.as_deref(), Some("Q1 numbers"));
+        assert_eq!(events[0].location, None);
+        assert_eq!(events[0].series_id, None);
+        assert_eq!((events[0].ends_at - events[0].starts_at).num_minutes(), 45);
+        let names: Vec<&str> = events[0].attendees.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["Ana", "bo@example.com"]);
+    }
+}
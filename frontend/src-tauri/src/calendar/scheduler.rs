@@ -0,0 +1,178 @@
+// calendar/scheduler.rs
+//
+// Records the events marked "always record". Capture starts when such an event starts, with
+// the meeting named after it. When the event ends, or nobody has spoken for a while, the UI
+// is asked to stop the recording through the regular stop flow, which saves the transcript.
+
+use crate::audio::{auto_stop, recording_commands};
+use crate::database::models::CalendarEvent;
+use crate::database::repositories::calendar::CalendarRepository;
+use crate::notifications::commands::{show_recording_started_notification, NotificationManagerState};
+use crate::state::AppState;
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tracing::{info, warn};
+
+/// Emitted when a scheduled recording starts; the UI links the saved meeting to the event
+pub const SCHEDULED_START_EVENT: &str = "calendar-recording-started";
+/// Emitted when a scheduled recording should end; the UI stops the recording
+pub const SCHEDULED_STOP_EVENT: &str = "calendar-recording-stop";
+
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+/// Minutes without speech after which a scheduled recording stops before the event ends
+const SILENCE_MINUTES: f64 = 10.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledRecordingPayload {
+    pub event_id: String,
+    pub title: String,
+    /// Why the recording is being stopped: "ended" or "silence"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// The scheduled recording in progress, if the current recording is one
+static ACTIVE: Lazy<Mutex<Option<CalendarEvent>>> = Lazy::new(|| Mutex::new(None));
+/// Events already started, so one the user stopped early isn't started again
+static STARTED: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// The first event under way that hasn't been recorded yet; events about to end are skipped
+fn due<'a>(events: &'a [CalendarEvent], now: DateTime<Utc>, started: &HashSet<String>) -> Option<&'a CalendarEvent> {
+    events
+        .iter()
+        .find(|event| event.starts_at.0 <= now && now < event.ends_at.0 - Duration::minutes(1) && !started.contains(&event.id))
+}
+
+/// Why a scheduled recording should stop now, if it should
+fn stop_reason(now: DateTime<Utc>, ends_at: DateTime<Utc>, silent_secs: Option<f64>) -> Option<&'static str> {
+    if now >= ends_at {
+        Some("ended")
+    } else if silent_secs.is_some_and(|secs| secs >= SILENCE_MINUTES * 60.0) {
+        Some("silence")
+    } else {
+        None
+    }
+}
+
+/// Checks every 30 seconds for scheduled recordings to start or stop
+pub fn start_recording_scheduler<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let pool = AppState::wait_for_pool(&app).await;
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            check(&app, &pool).await;
+        }
+    });
+}
+
+async fn check<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool) {
+    let now = Utc::now();
+    if recording_commands::is_recording().await {
+        let active = ACTIVE.lock().unwrap().clone();
+        let Some(event) = active else {
+            // The user is recording something else
+            return;
+        };
+        if let Some(reason) = stop_reason(now, event.ends_at.0, auto_stop::seconds_since_speech()) {
+            info!("📅 Stopping the scheduled recording of {} ({})", event.title, reason);
+            let payload = ScheduledRecordingPayload {
+                event_id: event.id.clone(),
+                title: event.title.clone(),
+                reason: Some(reason.to_string()),
+            };
+            if let Err(e) = app.emit(SCHEDULED_STOP_EVENT, payload) {
+                warn!("Failed to emit {}: {}", SCHEDULED_STOP_EVENT, e);
+            }
+            *ACTIVE.lock().unwrap() = None;
+        }
+        return;
+    }
+    *ACTIVE.lock().unwrap() = None;
+
+    let events = match CalendarRepository::list_scheduled_events(pool, now, now).await {
+        Ok(events) => events,
+        Err(e) => {
+            warn!("Failed to load scheduled recordings: {}", e);
+            return;
+        }
+    };
+    let event = {
+        let started = STARTED.lock().unwrap();
+        due(&events, now, &started).cloned()
+    };
+    let Some(event) = event else {
+        return;
+    };
+    STARTED.lock().unwrap().insert(event.id.clone());
+    match start(app, &event).await {
+        Ok(()) => *ACTIVE.lock().unwrap() = Some(event),
+        Err(e) => warn!("Failed to start the scheduled recording of {}: {}", event.title, e),
+    }
+}
+
+async fn start<R: Runtime>(app: &AppHandle<R>, event: &CalendarEvent) -> Result<(), String> {
+    info!("📅 Starting the scheduled recording of {}", event.title);
+    recording_commands::start_recording_with_meeting_name(app.clone(), Some(event.title.clone())).await?;
+    crate::tray::update_tray_menu(app);
+
+    let payload = ScheduledRecordingPayload { event_id: event.id.clone(), title: event.title.clone(), reason: None };
+    if let Err(e) = app.emit(SCHEDULED_START_EVENT, payload) {
+        warn!("Failed to emit {}: {}", SCHEDULED_START_EVENT, e);
+    }
+    if let Some(state) = app.try_state::<NotificationManagerState<R>>() {
+        if let Err(e) = show_recording_started_notification(app, &state, Some(event.title.clone())).await {
+            warn!("Failed to show recording started notification: {}", e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::DateTimeUtc;
+
+    fn event(id: &str, starts_at: DateTime<Utc>, minutes: i64) -> CalendarEvent {
+        CalendarEvent {
+            id: id.to_string(),
+            account_id: "calendar-1".to_string(),
+            external_id: id.to_string(),
+            title: id.to_string(),
+            starts_at: DateTimeUtc(starts_at),
+            ends_at: DateTimeUtc(starts_at + Duration::minutes(minutes)),
+            description: None,
+            location: None,
+            attendees: "[]".to_string(),
+            updated_at: DateTimeUtc(starts_at),
+            series_id: None,
+        }
+    }
+
+    #[test]
+    fn test_due() {
+        let now = Utc::now();
+        let events = vec![
+            event("ending", now - Duration::minutes(29), 30),
+            event("standup", now - Duration::minutes(2), 15),
+            event("later", now + Duration::minutes(5), 30),
+        ];
+        let mut started = HashSet::new();
+        assert_eq!(due(&events, now, &started).map(|e| e.id.as_str()), Some("standup"));
+        started.insert("standup".to_string());
+        assert!(due(&events, now, &started).is_none());
+    }
+
+    #[test]
+    fn test_stop_reason() {
+        let now = Utc::now();
+        assert_eq!(stop_reason(now, now, Some(0.0)), Some("ended"));
+        assert_eq!(stop_reason(now, now + Duration::minutes(5), Some(600.0)), Some("silence"));
+        assert_eq!(stop_reason(now, now + Duration::minutes(5), Some(30.0)), None);
+        assert_eq!(stop_reason(now, now + Duration::minutes(5), None), None);
+    }
+}
@@ -0,0 +1,160 @@
+// calendar/sync.rs
+//
+// Keeps the synced events current and copies an event's details into a linked meeting.
+
+use super::oauth::OAuthToken;
+use super::{caldav, credential_key, google, outlook, CalendarProvider, FetchedEvent};
+use crate::database::models::{CalendarAccount, CalendarEvent, Participant};
+use crate::database::repositories::calendar::CalendarRepository;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::meeting_library::MeetingLibraryRepository;
+use crate::integrations::credentials;
+use crate::state::AppState;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Runtime};
+use tracing::{info, warn};
+
+const SYNC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+/// Metadata keys the linked event's details are stored under
+pub const AGENDA_KEY: &str = "agenda";
+pub const LOCATION_KEY: &str = "location";
+
+/// The range of events kept: yesterday's, for meetings recorded late, to two weeks ahead
+pub fn sync_window() -> (DateTime<Utc>, DateTime<Utc>) {
+    let now = Utc::now();
+    (now - Duration::days(1), now + Duration::days(14))
+}
+
+/// A current access token for an OAuth account, refreshed and saved when expired
+async fn access_token(account: &CalendarAccount, provider: CalendarProvider) -> Result<String, String> {
+    let stored = credentials::load_token(&credential_key(&account.id))
+        .map_err(|e| e.to_string())?
+        .ok_or("The calendar authorization is missing; connect the calendar again")?;
+    let token: OAuthToken = serde_json::from_str(&stored).map_err(|e| format!("Invalid stored calendar token: {}", e))?;
+    if !token.is_expired() {
+        return Ok(token.access_token);
+    }
+    let token = token.refresh(provider).await?;
+    let json = serde_json::to_string(&token).map_err(|e| e.to_string())?;
+    credentials::store_token(&credential_key(&account.id), &json).map_err(|e| e.to_string())?;
+    Ok(token.access_token)
+}
+
+/// Reads an account's events in the window from its provider
+pub async fn fetch_events(account: &CalendarAccount, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<FetchedEvent>, String> {
+    let provider = CalendarProvider::from_string(&account.provider)
+        .ok_or_else(|| format!("Unknown calendar provider: {}", account.provider))?;
+    if provider != CalendarProvider::Caldav {
+        crate::network::ensure_online(&format!("Syncing {} calendars", provider.as_str()))?;
+    }
+    match provider {
+        CalendarProvider::Google => google::list_events(&access_token(account, provider).await?, from, to).await,
+        CalendarProvider::Outlook => outlook::list_events(&access_token(account, provider).await?, from, to).await,
+        CalendarProvider::Caldav => {
+            let url = account.url.as_deref().ok_or("The CalDAV calendar has no URL")?;
+            let password = credentials::load_token(&credential_key(&account.id))
+                .map_err(|e| e.to_string())?
+                .ok_or("The CalDAV password is missing; connect the calendar again")?;
+            caldav::list_events(url, account.username.as_deref().unwrap_or_default(), &password, from, to).await
+        }
+    }
+}
+
+/// Syncs one account; returns the number of events in the window
+pub async fn sync_account(pool: &SqlitePool, account: &CalendarAccount) -> Result<usize, String> {
+    let (from, to) = sync_window();
+    let events = fetch_events(account, from, to).await?;
+    CalendarRepository::replace_events(pool, &account.id, &events, from, to)
+        .await
+        .map_err(|e| format!("Failed to save calendar events: {}", e))?;
+    Ok(events.len())
+}
+
+/// Syncs every account; one failing account doesn't stop the others
+pub async fn sync_all(pool: &SqlitePool) -> Result<usize, String> {
+    let accounts = CalendarRepository::list_accounts(pool)
+        .await
+        .map_err(|e| format!("Failed to load calendar accounts: {}", e))?;
+    let mut total = 0;
+    let mut errors = Vec::new();
+    for account in &accounts {
+        match sync_account(pool, account).await {
+            Ok(count) => total += count,
+            Err(e) => {
+                warn!("Failed to sync calendar {}: {}", account.name, e);
+                errors.push(format!("{}: {}", account.name, e));
+            }
+        }
+    }
+    if !errors.is_empty() && errors.len() == accounts.len() {
+        return Err(errors.join("; "));
+    }
+    Ok(total)
+}
+
+/// Syncs calendars now and then every 15 minutes
+pub fn start_calendar_sync<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let pool = AppState::wait_for_pool(&app).await;
+        loop {
+            match sync_all(&pool).await {
+                Ok(count) if count > 0 => info!("📅 Synced {} calendar event(s)", count),
+                Ok(_) => {}
+                Err(e) => warn!("Calendar sync failed: {}", e),
+            }
+            tokio::time::sleep(SYNC_INTERVAL).await;
+        }
+    });
+}
+
+pub fn attendees(event: &CalendarEvent) -> Vec<Participant> {
+    serde_json::from_str(&event.attendees).unwrap_or_default()
+}
+
+/// Links a meeting to an event and copies the event's title, attendees and agenda into it
+///
+/// Participants and the title the user already set are kept unless `overwrite` is set.
+pub async fn link_meeting(pool: &SqlitePool, meeting_id: &str, event: &CalendarEvent, overwrite: bool) -> Result<(), String> {
+    let meeting = MeetingLibraryRepository::get(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("No meeting found with id {}", meeting_id))?;
+    CalendarRepository::set_meeting_event(pool, meeting_id, Some(&event.id))
+        .await
+        .map_err(|e| format!("Failed to link meeting: {}", e))?;
+
+    // Recordings are named "Meeting <date>" until the user renames them
+    if overwrite || meeting.title.starts_with("Meeting ") || meeting.title.trim().is_empty() {
+        MeetingsRepository::update_meeting_title(pool, meeting_id, &event.title)
+            .await
+            .map_err(|e| format!("Failed to update meeting title: {}", e))?;
+    }
+    let participants = MeetingLibraryRepository::list_participants(pool, Some(meeting_id))
+        .await
+        .map_err(|e| format!("Failed to load participants: {}", e))?;
+    let event_attendees = attendees(event);
+    if (overwrite || participants.is_empty()) && !event_attendees.is_empty() {
+        MeetingLibraryRepository::set_participants(pool, meeting_id, &event_attendees)
+            .await
+            .map_err(|e| format!("Failed to save participants: {}", e))?;
+    }
+    for (key, value) in [(AGENDA_KEY, &event.description), (LOCATION_KEY, &event.location)] {
+        MeetingLibraryRepository::set_metadata(pool, meeting_id, key, value.as_deref())
+            .await
+            .map_err(|e| format!("Failed to save meeting metadata: {}", e))?;
+    }
+    info!("📅 Linked meeting {} to calendar event {}", meeting_id, event.id);
+    Ok(())
+}
+
+/// The agenda copied from the linked event, for the summary prompt
+pub async fn meeting_agenda(pool: &SqlitePool, meeting_id: &str) -> Option<String> {
+    MeetingLibraryRepository::list_metadata(pool, Some(meeting_id))
+        .await
+        .ok()?
+        .into_iter()
+        .find(|(_, key, _)| key == AGENDA_KEY)
+        .map(|(_, _, value)| value)
+        .filter(|agenda| !agenda.trim().is_empty())
+}
@@ -3,6 +3,7 @@ use serde::Serialize;
 use std::path::PathBuf;
 use tauri::{AppHandle, Emitter, Manager};
 
+use super::integrity::{self, IntegrityReport};
 use super::manager::DatabaseManager;
 use crate::state::AppState;
 
@@ -175,13 +176,18 @@ pub async fn initialize_fresh_database(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Check the database file and its foreign keys
+#[tauri::command]
+pub async fn check_database_integrity(state: tauri::State<'_, AppState>) -> Result<IntegrityReport, String> {
+    integrity::check_integrity(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to check database integrity: {}", e))
+}
+
 /// Get the database directory path
 #[tauri::command]
 pub async fn get_database_directory(app: AppHandle) -> Result<String, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let app_data_dir = crate::workspaces::data_dir(&app)?;
 
     Ok(app_data_dir.to_string_lossy().to_string())
 }
@@ -189,10 +195,7 @@ pub async fn get_database_directory(app: AppHandle) -> Result<String, String> {
 /// Open the database folder in the system file explorer
 #[tauri::command]
 pub async fn open_database_folder(app: AppHandle) -> Result<(), String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let app_data_dir = crate::workspaces::data_dir(&app)?;
 
     // Ensure directory exists before trying to open it
     if !app_data_dir.exists() {
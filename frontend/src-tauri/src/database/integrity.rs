@@ -0,0 +1,224 @@
+// database/integrity.rs
+//
+// Schema migrations and integrity checks, run every time the database is opened
+//
+// Migrations live in `migrations/` and are embedded at compile time; sqlx records the
+// applied ones in `_sqlx_migrations`, so each runs exactly once per database. After
+// migrating, SQLite's own consistency check and a foreign key check are run, and any
+// problem found is logged rather than blocking startup.
+//
+// The database holds meetings and everything recorded about them: transcripts, summaries,
+// speakers, markers, action items and summary settings. Two kinds of JSON files stay outside
+// it on purpose:
+// - `transcripts.json` in each meeting folder is a copy of the meeting's transcript written
+//   next to its recording, so a folder copied elsewhere is still readable on its own; the
+//   database rows are what the app reads
+// - The plugin store files (`workspaces.json`, `encryption.json`, `onboarding.json` and the
+//   other device preferences) are read before the database is opened or to decide how to
+//   open it, e.g. which workspace's database and whether it needs a key
+
+use serde::Serialize;
+use sqlx::{migrate::MigrateError, FromRow, SqlitePool};
+
+/// Rows reported per check, so a badly damaged file doesn't flood the log
+const MAX_REPORTED_PROBLEMS: i64 = 100;
+
+/// A row whose foreign key points at a row that doesn't exist
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct ForeignKeyViolation {
+    pub table: String,
+    pub rowid: Option<i64>,
+    pub parent: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityReport {
+    /// No problems found by either check
+    pub ok: bool,
+    /// Latest applied migration
+    pub schema_version: Option<i64>,
+    /// Problems reported by `PRAGMA quick_check`
+    pub errors: Vec<String>,
+    pub foreign_key_violations: Vec<ForeignKeyViolation>,
+}
+
+/// Applies the migrations the database doesn't have yet
+pub async fn migrate(pool: &SqlitePool) -> Result<(), MigrateError> {
+    sqlx::migrate!("./migrations").run(pool).await
+}
+
+/// A migrated database in memory, for tests
+#[cfg(test)]
+pub async fn memory_pool() -> SqlitePool {
+    // Every connection to `:memory:` opens a database of its own, so the pool keeps just one
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to open an in-memory database");
+    migrate(&pool).await.expect("Failed to migrate the in-memory database");
+    pool
+}
+
+/// `memory_pool` holding one meeting, `m1`, for tests of what's stored per meeting
+#[cfg(test)]
+pub async fn pool_with_meeting() -> SqlitePool {
+    let pool = memory_pool().await;
+    sqlx::query("INSERT INTO meetings (id, title, created_at, updated_at) VALUES ('m1', 'Planning', ?, ?)")
+        .bind(chrono::Utc::now())
+        .bind(chrono::Utc::now())
+        .execute(&pool)
+        .await
+        .expect("Failed to insert the test meeting");
+    pool
+}
+
+/// SQLITE_CORRUPT or SQLITE_NOTADB, including their extended codes
+fn is_corruption(code: Option<&str>) -> bool {
+    code.and_then(|code| code.parse::<i32>().ok()).is_some_and(|code| matches!(code & 0xff, 11 | 26))
+}
+
+/// Checks the database file and its foreign keys without changing anything
+pub async fn check_integrity(pool: &SqlitePool) -> Result<IntegrityReport, sqlx::Error> {
+    let errors: Vec<String> =
+        match sqlx::query_scalar::<_, String>(&format!("PRAGMA quick_check({})", MAX_REPORTED_PROBLEMS))
+            .fetch_all(pool)
+            .await
+        {
+            Ok(lines) => lines.into_iter().filter(|line| line != "ok").collect(),
+            // Damage bad enough that SQLite can't walk the file fails the check itself
+            Err(sqlx::Error::Database(e)) if is_corruption(e.code().as_deref()) => {
+                return Ok(IntegrityReport {
+                    ok: false,
+                    schema_version: None,
+                    errors: vec![e.message().to_string()],
+                    foreign_key_violations: Vec::new(),
+                });
+            }
+            Err(e) => return Err(e),
+        };
+
+    let foreign_key_violations = sqlx::query_as::<_, ForeignKeyViolation>(&format!(
+        "SELECT \"table\", rowid, parent FROM pragma_foreign_key_check LIMIT {}",
+        MAX_REPORTED_PROBLEMS
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    let schema_version: Option<i64> =
+        sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1")
+            .fetch_one(pool)
+            .await?;
+
+    Ok(IntegrityReport {
+        ok: errors.is_empty() && foreign_key_violations.is_empty(),
+        schema_version,
+        errors,
+        foreign_key_violations,
+    })
+}
+
+/// Runs `check_integrity` and logs what it finds
+pub async fn log_integrity(pool: &SqlitePool) {
+    match check_integrity(pool).await {
+        Ok(report) if report.ok => {
+            log::info!("Database integrity check passed (schema version {:?})", report.schema_version);
+        }
+        Ok(report) => {
+            for error in &report.errors {
+                log::error!("Database integrity problem: {}", error);
+            }
+            for violation in &report.foreign_key_violations {
+                log::warn!(
+                    "Row {:?} of {} references a missing {} row",
+                    violation.rowid,
+                    violation.table,
+                    violation.parent
+                );
+            }
+        }
+        Err(e) => log::error!("Failed to check database integrity: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqliteConnectOptions;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn test_healthy_database_passes() {
+        let pool = memory_pool().await;
+        let report = check_integrity(&pool).await.unwrap();
+        assert!(report.ok, "{:?}", report);
+        assert!(report.schema_version.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_dangling_foreign_key_reported() {
+        let pool = memory_pool().await;
+        // The pool's single connection, so the pragma applies to the insert
+        sqlx::query("PRAGMA foreign_keys = OFF").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO transcripts (id, meeting_id, transcript, timestamp) VALUES ('t1', 'gone', 'hi', '0')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let report = check_integrity(&pool).await.unwrap();
+        assert!(!report.ok);
+        assert_eq!(report.foreign_key_violations.len(), 1);
+        assert_eq!(report.foreign_key_violations[0].table, "transcripts");
+        assert_eq!(report.foreign_key_violations[0].parent, "meetings");
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_database_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("meeting_minutes.sqlite");
+        let options = SqliteConnectOptions::from_str(path.to_str().unwrap()).unwrap().create_if_missing(true);
+        let pool = SqlitePool::connect_with(options.clone()).await.unwrap();
+        migrate(&pool).await.unwrap();
+        let mut transaction = pool.begin().await.unwrap();
+        sqlx::query("INSERT INTO meetings (id, title, created_at, updated_at) VALUES ('m1', 'Planning', 'x', 'x')")
+            .execute(&mut *transaction)
+            .await
+            .unwrap();
+        for i in 0..300 {
+            sqlx::query("INSERT INTO transcripts (id, meeting_id, transcript, timestamp) VALUES (?, 'm1', ?, '00:00')")
+                .bind(format!("t{}", i))
+                .bind("word ".repeat(400))
+                .execute(&mut *transaction)
+                .await
+                .unwrap();
+        }
+        transaction.commit().await.unwrap();
+        pool.close().await;
+
+        // Scribble over a page of transcript rows, past the schema at the start of the file
+        let mut bytes = std::fs::read(&path).unwrap();
+        let page_size = u16::from_be_bytes([bytes[16], bytes[17]]) as usize;
+        let page = bytes.len() / page_size / 2;
+        for byte in &mut bytes[page * page_size + 100..(page + 1) * page_size] {
+            *byte = 0xA5;
+        }
+        std::fs::write(&path, bytes).unwrap();
+
+        let pool = SqlitePool::connect_with(options).await.unwrap();
+        let report = check_integrity(&pool).await.unwrap();
+        assert!(!report.ok);
+        assert!(!report.errors.is_empty());
+    }
+
+    #[test]
+    fn test_corruption_codes() {
+        assert!(is_corruption(Some("11")));
+        // SQLITE_CORRUPT_INDEX
+        assert!(is_corruption(Some("779")));
+        assert!(is_corruption(Some("26")));
+        assert!(!is_corruption(Some("5")));
+        assert!(!is_corruption(None));
+    }
+}
@@ -1,6 +1,10 @@
-use sqlx::{migrate::MigrateDatabase, Result, Sqlite, SqlitePool, Transaction};
+use super::integrity;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{Result, Sqlite, SqlitePool, Transaction};
+use std::borrow::Cow;
 use std::fs;
 use std::path::Path;
+use std::str::FromStr;
 use tauri::Manager;
 
 pub struct DatabaseManager {
@@ -25,13 +29,19 @@ impl DatabaseManager {
                 fs::copy(backend_db_path, tauri_db_path).map_err(|e| sqlx::Error::Io(e))?;
             } else {
                 log::info!("Creating database at {}", tauri_db_path);
-                Sqlite::create_database(tauri_db_path).await?;
             }
         }
 
-        let pool = SqlitePool::connect(tauri_db_path).await?;
+        // Created on connect rather than beforehand, so an encrypted one gets its key first
+        let mut options = SqliteConnectOptions::from_str(tauri_db_path)?.create_if_missing(true);
+        if let Some(key) = crate::encryption::active_key() {
+            options = options.pragma("key", Cow::Owned(crate::encryption::database::key_pragma(Some(&key))));
+        }
+        let pool = SqlitePool::connect_with(options).await?;
 
-        sqlx::migrate!("./migrations").run(&pool).await?;
+        integrity::migrate(&pool).await?;
+        integrity::log_integrity(&pool).await;
+        crate::secrets::migration::move_legacy_keys(&pool).await;
 
         Ok(DatabaseManager { pool })
     }
@@ -41,11 +51,9 @@ impl DatabaseManager {
     // the current app dir, So the system detects legacy db and copy it and starts with that data
     // (Newly created .sqlite with the copied content from .db)
     pub async fn new_from_app_handle(app_handle: &tauri::AppHandle) -> Result<Self> {
-        // Resolve the app's data directory
-        let app_data_dir = app_handle
-            .path()
-            .app_data_dir()
-            .expect("failed to get app data dir");
+        // Resolve the active workspace's data directory
+        let app_data_dir = crate::workspaces::data_dir(app_handle)
+            .map_err(|e| sqlx::Error::Configuration(e.into()))?;
         if !app_data_dir.exists() {
             fs::create_dir_all(&app_data_dir).map_err(|e| sqlx::Error::Io(e))?;
         }
@@ -63,17 +71,23 @@ impl DatabaseManager {
         log::info!("Tauri DB path: {}", tauri_db_path);
         log::info!("Legacy backend DB path: {}", backend_db_path);
 
+        crate::backup::complete_pending_restore(Path::new(&tauri_db_path))
+            .map_err(|e| sqlx::Error::Configuration(e.into()))?;
+        crate::encryption::unlock(app_handle, Path::new(&tauri_db_path))
+            .map_err(|e| sqlx::Error::Configuration(e.into()))?;
+
         Self::new(&tauri_db_path, &backend_db_path).await
     }
 
-    /// Check if this is the first launch (sqlite database doesn't exist yet)
+    /// Check if this is the first launch (sqlite database doesn't exist yet). A workspace
+    /// added later starts with an empty database instead.
     pub async fn is_first_launch(app_handle: &tauri::AppHandle) -> Result<bool> {
-        let app_data_dir = app_handle
-            .path()
-            .app_data_dir()
-            .expect("failed to get app data dir");
-
-        let tauri_db_path = app_data_dir.join("meeting_minutes.sqlite");
+        if !crate::workspaces::active().is_default() {
+            return Ok(false);
+        }
+        // The default workspace's storage may have been moved out of the app data folder
+        let tauri_db_path = crate::workspaces::database_path(app_handle)
+            .map_err(|e| sqlx::Error::Configuration(e.into()))?;
 
         Ok(!tauri_db_path.exists())
     }
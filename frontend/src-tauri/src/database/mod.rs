@@ -1,5 +1,6 @@
 pub mod commands;
+pub mod integrity;
 pub mod manager;
-pub mod models;
+pub use meeting_core::database::models;
 pub mod repositories;
 pub mod setup;
@@ -1,115 +0,0 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
-use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
-
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
-pub struct MeetingModel {
-    pub id: String,
-    pub title: String,
-    pub created_at: DateTimeUtc,
-    pub updated_at: DateTimeUtc,
-    pub folder_path: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(transparent)]
-pub struct DateTimeUtc(pub DateTime<Utc>);
-
-impl From<NaiveDateTime> for DateTimeUtc {
-    fn from(naive: NaiveDateTime) -> Self {
-        DateTimeUtc(DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc))
-    }
-}
-
-// Renamed from TranscriptSegment to Transcript to match the table name
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
-pub struct Transcript {
-    pub id: String,
-    pub meeting_id: String,
-    pub transcript: String,
-    pub timestamp: String,
-    pub summary: Option<String>,
-    pub action_items: Option<String>,
-    pub key_points: Option<String>,
-    // Recording-relative timestamps for audio-transcript synchronization
-    pub audio_start_time: Option<f64>,
-    pub audio_end_time: Option<f64>,
-    pub duration: Option<f64>,
-}
-
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
-pub struct SummaryProcess {
-    pub meeting_id: String,
-    pub status: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-    pub error: Option<String>,
-    pub result: Option<String>, // JSON
-    pub start_time: Option<chrono::DateTime<chrono::Utc>>,
-    pub end_time: Option<chrono::DateTime<chrono::Utc>>,
-    pub chunk_count: i64,
-    pub processing_time: f64,
-    pub metadata: Option<String>, // JSON
-}
-
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
-pub struct TranscriptChunk {
-    pub meeting_id: String,
-    pub meeting_name: Option<String>,
-    pub transcript_text: String,
-    pub model: String,
-    pub model_name: String,
-    pub chunk_size: Option<i64>,
-    pub overlap: Option<i64>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-}
-
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
-pub struct Setting {
-    pub id: String,
-    pub provider: String,
-    pub model: String,
-    #[sqlx(rename = "whisperModel")]
-    #[serde(rename = "whisperModel")]
-    pub whisper_model: String,
-    #[sqlx(rename = "groqApiKey")]
-    #[serde(rename = "groqApiKey")]
-    pub groq_api_key: Option<String>,
-    #[sqlx(rename = "openaiApiKey")]
-    #[serde(rename = "openaiApiKey")]
-    pub openai_api_key: Option<String>,
-    #[sqlx(rename = "anthropicApiKey")]
-    #[serde(rename = "anthropicApiKey")]
-    pub anthropic_api_key: Option<String>,
-    #[sqlx(rename = "ollamaApiKey")]
-    #[serde(rename = "ollamaApiKey")]
-    pub ollama_api_key: Option<String>,
-    #[sqlx(rename = "openRouterApiKey")]
-    #[serde(rename = "openRouterApiKey")]
-    pub open_router_api_key: Option<String>,
-    #[sqlx(rename = "ollamaEndpoint")]
-    #[serde(rename = "ollamaEndpoint")]
-    pub ollama_endpoint: Option<String>,
-}
-
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
-pub struct TranscriptSetting {
-    pub id: String,
-    pub provider: String,
-    pub model: String,
-    #[sqlx(rename = "whisperApiKey")]
-    #[serde(rename = "whisperApiKey")]
-    pub whisper_api_key: Option<String>,
-    #[sqlx(rename = "deepgramApiKey")]
-    #[serde(rename = "deepgramApiKey")]
-    pub deepgram_api_key: Option<String>,
-    #[sqlx(rename = "elevenLabsApiKey")]
-    #[serde(rename = "elevenLabsApiKey")]
-    pub eleven_labs_api_key: Option<String>,
-    #[sqlx(rename = "groqApiKey")]
-    #[serde(rename = "groqApiKey")]
-    pub groq_api_key: Option<String>,
-    #[sqlx(rename = "openaiApiKey")]
-    #[serde(rename = "openaiApiKey")]
-    pub openai_api_key: Option<String>,
-}
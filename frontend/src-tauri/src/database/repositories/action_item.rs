@@ -0,0 +1,156 @@
+use crate::database::models::ActionItem;
+use chrono::Utc;
+use sqlx::{Connection, SqlitePool};
+use uuid::Uuid;
+
+/// Fields of an action item before it is stored
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewActionItem {
+    pub text: String,
+    pub assignee: Option<String>,
+    pub due_date: Option<String>,
+    pub source_segment: Option<String>,
+}
+
+pub struct ActionItemsRepository;
+
+impl ActionItemsRepository {
+    pub async fn list_for_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<ActionItem>, sqlx::Error> {
+        sqlx::query_as::<_, ActionItem>("SELECT * FROM action_items WHERE meeting_id = ? ORDER BY created_at, rowid")
+            .bind(meeting_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<ActionItem>, sqlx::Error> {
+        sqlx::query_as::<_, ActionItem>("SELECT * FROM action_items WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Adds an item the user wrote themselves
+    pub async fn insert(pool: &SqlitePool, meeting_id: &str, item: &NewActionItem) -> Result<ActionItem, sqlx::Error> {
+        let id = format!("action-{}", Uuid::new_v4());
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO action_items (id, meeting_id, text, assignee, due_date, source_segment, completed, source, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, 0, 'manual', ?, ?)",
+        )
+        .bind(&id)
+        .bind(meeting_id)
+        .bind(&item.text)
+        .bind(&item.assignee)
+        .bind(&item.due_date)
+        .bind(&item.source_segment)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Self::get(pool, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Edits an item; edited items count as the user's and survive re-extraction
+    pub async fn update(
+        pool: &SqlitePool,
+        id: &str,
+        text: &str,
+        assignee: Option<&str>,
+        due_date: Option<&str>,
+        completed: bool,
+    ) -> Result<Option<ActionItem>, sqlx::Error> {
+        let rows = sqlx::query(
+            "UPDATE action_items SET text = ?, assignee = ?, due_date = ?, completed = ?, source = 'manual', updated_at = ? WHERE id = ?",
+        )
+        .bind(text)
+        .bind(assignee)
+        .bind(due_date)
+        .bind(completed)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+        if rows == 0 {
+            return Ok(None);
+        }
+        Self::get(pool, id).await
+    }
+
+    pub async fn set_completed(pool: &SqlitePool, id: &str, completed: bool) -> Result<Option<ActionItem>, sqlx::Error> {
+        let rows = sqlx::query("UPDATE action_items SET completed = ?, updated_at = ? WHERE id = ?")
+            .bind(completed)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+
+        if rows == 0 {
+            return Ok(None);
+        }
+        Self::get(pool, id).await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("DELETE FROM action_items WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    /// Replaces the extracted items of a meeting with a new extraction
+    ///
+    /// Items the user added, edited or completed are kept, and new items with the same
+    /// text as a kept one are skipped.
+    pub async fn replace_extracted(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        items: &[NewActionItem],
+    ) -> Result<Vec<ActionItem>, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query("DELETE FROM action_items WHERE meeting_id = ? AND source = 'extracted' AND completed = 0")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        let kept: Vec<String> = sqlx::query_scalar("SELECT text FROM action_items WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_all(&mut *transaction)
+            .await?;
+        let mut seen: Vec<String> = kept.iter().map(|text| text.trim().to_lowercase()).collect();
+
+        let now = Utc::now();
+        for item in items {
+            let key = item.text.trim().to_lowercase();
+            if seen.contains(&key) {
+                continue;
+            }
+            seen.push(key);
+
+            sqlx::query(
+                "INSERT INTO action_items (id, meeting_id, text, assignee, due_date, source_segment, completed, source, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, 0, 'extracted', ?, ?)",
+            )
+            .bind(format!("action-{}", Uuid::new_v4()))
+            .bind(meeting_id)
+            .bind(&item.text)
+            .bind(&item.assignee)
+            .bind(&item.due_date)
+            .bind(&item.source_segment)
+            .bind(now)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Self::list_for_meeting(pool, meeting_id).await
+    }
+}
@@ -0,0 +1,231 @@
+use crate::calendar::FetchedEvent;
+use crate::database::models::{CalendarAccount, CalendarEvent, CalendarRecordingRule};
+use chrono::{DateTime, Utc};
+use sqlx::{Connection, SqlitePool};
+use uuid::Uuid;
+
+pub struct CalendarRepository;
+
+impl CalendarRepository {
+    pub async fn list_accounts(pool: &SqlitePool) -> Result<Vec<CalendarAccount>, sqlx::Error> {
+        sqlx::query_as::<_, CalendarAccount>("SELECT * FROM calendar_accounts ORDER BY name COLLATE NOCASE")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get_account(pool: &SqlitePool, id: &str) -> Result<Option<CalendarAccount>, sqlx::Error> {
+        sqlx::query_as::<_, CalendarAccount>("SELECT * FROM calendar_accounts WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn create_account(
+        pool: &SqlitePool,
+        provider: &str,
+        name: &str,
+        url: Option<&str>,
+        username: Option<&str>,
+    ) -> Result<CalendarAccount, sqlx::Error> {
+        let id = format!("calendar-{}", Uuid::new_v4());
+        sqlx::query("INSERT INTO calendar_accounts (id, provider, name, url, username, created_at) VALUES (?, ?, ?, ?, ?, ?)")
+            .bind(&id)
+            .bind(provider)
+            .bind(name)
+            .bind(url)
+            .bind(username)
+            .bind(Utc::now())
+            .execute(pool)
+            .await?;
+        Self::get_account(pool, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Removes an account with its events and recording rules; meetings keep their copied details
+    pub async fn delete_account(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        for table in ["calendar_events", "calendar_recording_rules"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE account_id = ?", table))
+                .bind(id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        let rows = sqlx::query("DELETE FROM calendar_accounts WHERE id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected();
+        transaction.commit().await?;
+        Ok(rows > 0)
+    }
+
+    /// Replaces an account's events between `from` and `to` with the ones just fetched
+    pub async fn replace_events(
+        pool: &SqlitePool,
+        account_id: &str,
+        events: &[FetchedEvent],
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        // Events that were cancelled or moved out of the window
+        let external_ids = serde_json::to_string(&events.iter().map(|e| &e.external_id).collect::<Vec<_>>())
+            .unwrap_or_else(|_| "[]".to_string());
+        sqlx::query(
+            "DELETE FROM calendar_events
+             WHERE account_id = ? AND ends_at >= ? AND starts_at <= ?
+               AND external_id NOT IN (SELECT value FROM json_each(?))",
+        )
+        .bind(account_id)
+        .bind(from)
+        .bind(to)
+        .bind(&external_ids)
+        .execute(&mut *transaction)
+        .await?;
+        for event in events {
+            sqlx::query(
+                "INSERT INTO calendar_events
+                     (id, account_id, external_id, series_id, title, starts_at, ends_at, description, location, attendees, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 ON CONFLICT(account_id, external_id) DO UPDATE SET
+                     series_id = excluded.series_id, title = excluded.title, starts_at = excluded.starts_at,
+                     ends_at = excluded.ends_at, description = excluded.description, location = excluded.location,
+                     attendees = excluded.attendees, updated_at = excluded.updated_at",
+            )
+            .bind(format!("event-{}", Uuid::new_v4()))
+            .bind(account_id)
+            .bind(&event.external_id)
+            .bind(&event.series_id)
+            .bind(&event.title)
+            .bind(event.starts_at)
+            .bind(event.ends_at)
+            .bind(&event.description)
+            .bind(&event.location)
+            .bind(serde_json::to_string(&event.attendees).unwrap_or_else(|_| "[]".to_string()))
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        sqlx::query("UPDATE calendar_accounts SET last_synced_at = ? WHERE id = ?")
+            .bind(now)
+            .bind(account_id)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await
+    }
+
+    /// Events overlapping `from`..`to`, earliest first
+    pub async fn list_events(
+        pool: &SqlitePool,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<CalendarEvent>, sqlx::Error> {
+        sqlx::query_as::<_, CalendarEvent>(
+            "SELECT * FROM calendar_events WHERE ends_at >= ? AND starts_at <= ? ORDER BY starts_at, title",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_event(pool: &SqlitePool, id: &str) -> Result<Option<CalendarEvent>, sqlx::Error> {
+        sqlx::query_as::<_, CalendarEvent>("SELECT * FROM calendar_events WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Sets or clears the event a meeting is linked to
+    pub async fn set_meeting_event(pool: &SqlitePool, meeting_id: &str, event_id: Option<&str>) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("UPDATE meetings SET calendar_event_id = ? WHERE id = ?")
+            .bind(event_id)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    pub async fn meeting_event(pool: &SqlitePool, meeting_id: &str) -> Result<Option<CalendarEvent>, sqlx::Error> {
+        sqlx::query_as::<_, CalendarEvent>(
+            "SELECT e.* FROM calendar_events e JOIN meetings m ON m.calendar_event_id = e.id WHERE m.id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn list_recording_rules(pool: &SqlitePool) -> Result<Vec<CalendarRecordingRule>, sqlx::Error> {
+        sqlx::query_as::<_, CalendarRecordingRule>(
+            "SELECT * FROM calendar_recording_rules ORDER BY title COLLATE NOCASE",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Marks an event or series to be recorded; marking it again returns the existing rule
+    pub async fn add_recording_rule(
+        pool: &SqlitePool,
+        account_id: &str,
+        target: &str,
+        target_id: &str,
+        title: &str,
+    ) -> Result<CalendarRecordingRule, sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO calendar_recording_rules (id, account_id, target, target_id, title, created_at)
+             VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT(account_id, target, target_id) DO NOTHING",
+        )
+        .bind(format!("record-{}", Uuid::new_v4()))
+        .bind(account_id)
+        .bind(target)
+        .bind(target_id)
+        .bind(title)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        sqlx::query_as::<_, CalendarRecordingRule>(
+            "SELECT * FROM calendar_recording_rules WHERE account_id = ? AND target = ? AND target_id = ?",
+        )
+        .bind(account_id)
+        .bind(target)
+        .bind(target_id)
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete_recording_rule(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("DELETE FROM calendar_recording_rules WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    /// Events overlapping `from`..`to` that a rule marks for recording, earliest first
+    pub async fn list_scheduled_events(
+        pool: &SqlitePool,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<CalendarEvent>, sqlx::Error> {
+        sqlx::query_as::<_, CalendarEvent>(
+            "SELECT e.* FROM calendar_events e
+             WHERE e.ends_at >= ? AND e.starts_at <= ?
+               AND EXISTS (
+                   SELECT 1 FROM calendar_recording_rules r
+                   WHERE r.account_id = e.account_id
+                     AND ((r.target = 'event' AND r.target_id = e.external_id)
+                       OR (r.target = 'series' AND r.target_id = e.series_id))
+               )
+             ORDER BY e.starts_at, e.title",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(pool)
+        .await
+    }
+}
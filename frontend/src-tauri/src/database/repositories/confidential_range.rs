@@ -0,0 +1,46 @@
+use crate::database::models::ConfidentialRange;
+use chrono::Utc;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub struct ConfidentialRangesRepository;
+
+impl ConfidentialRangesRepository {
+    pub async fn list_for_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<ConfidentialRange>, sqlx::Error> {
+        sqlx::query_as::<_, ConfidentialRange>(
+            "SELECT * FROM confidential_ranges WHERE meeting_id = ? ORDER BY audio_start",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<ConfidentialRange>, sqlx::Error> {
+        sqlx::query_as::<_, ConfidentialRange>("SELECT * FROM confidential_ranges WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        audio_start: f64,
+        audio_end: f64,
+        label: Option<&str>,
+    ) -> Result<ConfidentialRange, sqlx::Error> {
+        let id = format!("confidential-{}", Uuid::new_v4());
+        sqlx::query(
+            "INSERT INTO confidential_ranges (id, meeting_id, audio_start, audio_end, label, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(meeting_id)
+        .bind(audio_start)
+        .bind(audio_end)
+        .bind(label)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Self::get(pool, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+}
@@ -0,0 +1,174 @@
+use crate::database::models::PendingDeletion;
+use crate::database::repositories::meeting::delete_meeting_with_transaction;
+use chrono::Utc;
+use sqlx::{Connection, SqliteConnection, SqlitePool};
+
+/// Search indexes holding text of deleted rows until their segments are merged
+const FTS_TABLES: [&str; 2] = ["transcripts_fts", "summaries_fts"];
+
+pub struct DeletionsRepository;
+
+impl DeletionsRepository {
+    /// Deletes a meeting's rows and queues its files for removal, in one transaction; returns
+    /// everything queued for the meeting, or None when there is no such meeting
+    ///
+    /// The meeting's folder is queued unless another meeting uses it too, and its exports
+    /// unless `keep_exports`. With `secure_wipe`, SQLite overwrites the deleted rows, the search
+    /// indexes are merged so they no longer hold the deleted text, and the write-ahead log is
+    /// emptied.
+    pub async fn delete_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        keep_exports: bool,
+        secure_wipe: bool,
+    ) -> Result<Option<Vec<PendingDeletion>>, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        if !secure_wipe {
+            return delete_and_queue(&mut conn, meeting_id, keep_exports, false).await;
+        }
+
+        let (secure_before,): (i64,) = sqlx::query_as("PRAGMA secure_delete").fetch_one(&mut *conn).await?;
+        sqlx::query("PRAGMA secure_delete = ON").execute(&mut *conn).await?;
+        let queued = delete_and_wipe(&mut conn, meeting_id, keep_exports).await;
+        // Restored whatever happened, since the connection goes back to the pool
+        let restored = sqlx::query(&format!("PRAGMA secure_delete = {}", secure_before))
+            .execute(&mut *conn)
+            .await;
+        let queued = queued?;
+        restored?;
+        Ok(queued)
+    }
+
+    /// Files and folders of deleted meetings not removed yet
+    pub async fn pending(pool: &SqlitePool) -> Result<Vec<PendingDeletion>, sqlx::Error> {
+        sqlx::query_as::<_, PendingDeletion>("SELECT * FROM pending_deletions ORDER BY created_at")
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Drops `path` from the queue once it's removed, or was left alone on purpose
+    pub async fn finish(pool: &SqlitePool, path: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM pending_deletions WHERE path = ?")
+            .bind(path)
+            .execute(pool)
+            .await
+            .map(|_| ())
+    }
+}
+
+async fn delete_and_wipe(
+    conn: &mut SqliteConnection,
+    meeting_id: &str,
+    keep_exports: bool,
+) -> Result<Option<Vec<PendingDeletion>>, sqlx::Error> {
+    let queued = delete_and_queue(conn, meeting_id, keep_exports, true).await?;
+    if queued.is_some() {
+        for table in FTS_TABLES {
+            sqlx::query(&format!("INSERT INTO {0}({0}) VALUES('optimize')", table))
+                .execute(&mut *conn)
+                .await?;
+        }
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)").execute(&mut *conn).await?;
+    }
+    Ok(queued)
+}
+
+async fn delete_and_queue(
+    conn: &mut SqliteConnection,
+    meeting_id: &str,
+    keep_exports: bool,
+    secure_wipe: bool,
+) -> Result<Option<Vec<PendingDeletion>>, sqlx::Error> {
+    let mut transaction = conn.begin().await?;
+
+    let folder: Option<(Option<String>,)> = sqlx::query_as("SELECT folder_path FROM meetings WHERE id = ?")
+        .bind(meeting_id)
+        .fetch_optional(&mut *transaction)
+        .await?;
+    let Some((folder,)) = folder else {
+        return Ok(None);
+    };
+
+    let now = Utc::now();
+    if let Some(folder) = folder.filter(|folder| !folder.trim().is_empty()) {
+        let shared: Option<(i64,)> = sqlx::query_as("SELECT 1 FROM meetings WHERE folder_path = ? AND id != ? LIMIT 1")
+            .bind(&folder)
+            .bind(meeting_id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+        if shared.is_none() {
+            sqlx::query(
+                "INSERT OR IGNORE INTO pending_deletions (path, meeting_id, sha256, secure_wipe, created_at)
+                 VALUES (?, ?, NULL, ?, ?)",
+            )
+            .bind(&folder)
+            .bind(meeting_id)
+            .bind(secure_wipe)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+    }
+    if !keep_exports {
+        sqlx::query(
+            "INSERT OR IGNORE INTO pending_deletions (path, meeting_id, sha256, secure_wipe, created_at)
+             SELECT path, meeting_id, sha256, ?, ? FROM export_artifacts WHERE meeting_id = ?",
+        )
+        .bind(secure_wipe)
+        .bind(now)
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    if !delete_meeting_with_transaction(&mut transaction, meeting_id).await? {
+        transaction.rollback().await?;
+        return Ok(None);
+    }
+    transaction.commit().await?;
+
+    sqlx::query_as::<_, PendingDeletion>("SELECT * FROM pending_deletions WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::integrity::pool_with_meeting;
+
+    async fn secure_delete(pool: &SqlitePool) -> i64 {
+        sqlx::query_scalar("PRAGMA secure_delete").fetch_one(pool).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_secure_wipe_restores_secure_delete() {
+        let pool = pool_with_meeting().await;
+        let before = secure_delete(&pool).await;
+        let queued = DeletionsRepository::delete_meeting(&pool, "m1", false, true).await.unwrap();
+        assert!(queued.is_some());
+        assert_eq!(secure_delete(&pool).await, before);
+        assert!(DeletionsRepository::delete_meeting(&pool, "m1", false, true).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_wipe_restores_secure_delete() {
+        let pool = pool_with_meeting().await;
+        // A plain table in place of the summary index, so merging it fails
+        for statement in [
+            "DROP TRIGGER summaries_fts_insert",
+            "DROP TRIGGER summaries_fts_delete",
+            "DROP TRIGGER summaries_fts_update",
+            "DROP TABLE summaries_fts",
+            "CREATE TABLE summaries_fts (summary TEXT)",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+
+        let before = secure_delete(&pool).await;
+        assert!(DeletionsRepository::delete_meeting(&pool, "m1", false, true).await.is_err());
+        assert_eq!(secure_delete(&pool).await, before);
+    }
+}
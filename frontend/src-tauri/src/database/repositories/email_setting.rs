@@ -0,0 +1,48 @@
+use crate::database::models::EmailSettings;
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+pub struct EmailSettingsRepository;
+
+impl EmailSettingsRepository {
+    pub async fn get(pool: &SqlitePool) -> Result<Option<EmailSettings>, sqlx::Error> {
+        sqlx::query_as::<_, EmailSettings>(
+            "SELECT host, port, security, username, from_address, from_name, updated_at FROM email_settings WHERE id = 1",
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn save(
+        pool: &SqlitePool,
+        host: &str,
+        port: i64,
+        security: &str,
+        username: Option<&str>,
+        from_address: &str,
+        from_name: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO email_settings (id, host, port, security, username, from_address, from_name, updated_at)
+             VALUES (1, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET host = excluded.host, port = excluded.port, security = excluded.security,
+                 username = excluded.username, from_address = excluded.from_address,
+                 from_name = excluded.from_name, updated_at = excluded.updated_at",
+        )
+        .bind(host)
+        .bind(port)
+        .bind(security)
+        .bind(username)
+        .bind(from_address)
+        .bind(from_name)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("DELETE FROM email_settings WHERE id = 1").execute(pool).await?.rows_affected();
+        Ok(rows > 0)
+    }
+}
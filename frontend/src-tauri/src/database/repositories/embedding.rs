@@ -0,0 +1,109 @@
+use chrono::Utc;
+use sqlx::{Connection, FromRow, SqlitePool};
+
+/// A stored vector, without the segment text
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredVector {
+    pub transcript_id: String,
+    pub meeting_id: String,
+    pub vector: Vec<u8>,
+}
+
+/// The fields of a transcript segment needed to cite it
+#[derive(Debug, Clone, FromRow)]
+pub struct SegmentText {
+    pub id: String,
+    pub meeting_id: String,
+    pub transcript: String,
+    pub speaker: Option<String>,
+    pub audio_start_time: Option<f64>,
+}
+
+/// A segment embedding to store
+#[derive(Debug, Clone)]
+pub struct NewEmbedding {
+    pub transcript_id: String,
+    pub text_hash: String,
+    pub vector: Vec<u8>,
+}
+
+pub struct EmbeddingsRepository;
+
+impl EmbeddingsRepository {
+    /// (transcript id, text hash) of a meeting's segments already embedded with `model`
+    pub async fn hashes_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        model: &str,
+    ) -> Result<Vec<(String, String)>, sqlx::Error> {
+        sqlx::query_as("SELECT transcript_id, text_hash FROM segment_embeddings WHERE meeting_id = ? AND model = ?")
+            .bind(meeting_id)
+            .bind(model)
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn save(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        model: &str,
+        embeddings: &[NewEmbedding],
+    ) -> Result<(), sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        let now = Utc::now();
+        for embedding in embeddings {
+            sqlx::query(
+                "INSERT INTO segment_embeddings (transcript_id, meeting_id, model, text_hash, vector, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?)
+                 ON CONFLICT(transcript_id) DO UPDATE SET
+                    model = excluded.model, text_hash = excluded.text_hash,
+                    vector = excluded.vector, created_at = excluded.created_at",
+            )
+            .bind(&embedding.transcript_id)
+            .bind(meeting_id)
+            .bind(model)
+            .bind(&embedding.text_hash)
+            .bind(&embedding.vector)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await
+    }
+
+    /// Vectors made with `model` across all meetings
+    pub async fn list_vectors(pool: &SqlitePool, model: &str) -> Result<Vec<StoredVector>, sqlx::Error> {
+        sqlx::query_as::<_, StoredVector>(
+            "SELECT transcript_id, meeting_id, vector FROM segment_embeddings WHERE model = ?",
+        )
+        .bind(model)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The transcript segments with the given ids; ids that no longer exist are skipped
+    pub async fn segment_texts(pool: &SqlitePool, ids: &[String]) -> Result<Vec<SegmentText>, sqlx::Error> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let sql = format!(
+            "SELECT id, meeting_id, transcript, speaker, audio_start_time FROM transcripts WHERE id IN ({})",
+            placeholders
+        );
+        let mut query = sqlx::query_as::<_, SegmentText>(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        query.fetch_all(pool).await
+    }
+
+    pub async fn delete_for_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<u64, sqlx::Error> {
+        Ok(sqlx::query("DELETE FROM segment_embeddings WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(pool)
+            .await?
+            .rows_affected())
+    }
+}
@@ -0,0 +1,35 @@
+use chrono::Utc;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub struct ExportArtifactsRepository;
+
+impl ExportArtifactsRepository {
+    /// Records a file exported from a meeting, replacing an earlier export to the same path
+    pub async fn record(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        path: &str,
+        format: &str,
+        sha256: &str,
+    ) -> Result<(), sqlx::Error> {
+        let mut transaction = pool.begin().await?;
+        sqlx::query("DELETE FROM export_artifacts WHERE meeting_id = ? AND path = ?")
+            .bind(meeting_id)
+            .bind(path)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query(
+            "INSERT INTO export_artifacts (id, meeting_id, path, format, sha256, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(format!("export-{}", Uuid::new_v4()))
+        .bind(meeting_id)
+        .bind(path)
+        .bind(format)
+        .bind(sha256)
+        .bind(Utc::now())
+        .execute(&mut *transaction)
+        .await?;
+        transaction.commit().await
+    }
+}
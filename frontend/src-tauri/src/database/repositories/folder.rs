@@ -0,0 +1,101 @@
+use crate::database::models::Folder;
+use chrono::Utc;
+use sqlx::{Connection, SqlitePool};
+use uuid::Uuid;
+
+pub struct FoldersRepository;
+
+impl FoldersRepository {
+    pub async fn list(pool: &SqlitePool) -> Result<Vec<Folder>, sqlx::Error> {
+        sqlx::query_as::<_, Folder>("SELECT * FROM folders ORDER BY name COLLATE NOCASE")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<Folder>, sqlx::Error> {
+        sqlx::query_as::<_, Folder>("SELECT * FROM folders WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// (folder id, meetings filed directly in it)
+    pub async fn meeting_counts(pool: &SqlitePool) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        sqlx::query_as("SELECT folder_id, COUNT(*) FROM meetings WHERE folder_id IS NOT NULL GROUP BY folder_id")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn create(pool: &SqlitePool, name: &str, parent_id: Option<&str>) -> Result<Folder, sqlx::Error> {
+        let id = format!("folder-{}", Uuid::new_v4());
+        sqlx::query("INSERT INTO folders (id, name, parent_id, created_at) VALUES (?, ?, ?, ?)")
+            .bind(&id)
+            .bind(name)
+            .bind(parent_id)
+            .bind(Utc::now())
+            .execute(pool)
+            .await?;
+        Self::get(pool, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn rename(pool: &SqlitePool, id: &str, name: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("UPDATE folders SET name = ? WHERE id = ?")
+            .bind(name)
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    pub async fn set_parent(pool: &SqlitePool, id: &str, parent_id: Option<&str>) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("UPDATE folders SET parent_id = ? WHERE id = ?")
+            .bind(parent_id)
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    /// Deletes a folder; its subfolders and meetings move up to its parent
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        let Some(parent_id) = sqlx::query_scalar::<_, Option<String>>("SELECT parent_id FROM folders WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *transaction)
+            .await?
+        else {
+            return Ok(false);
+        };
+        sqlx::query("UPDATE folders SET parent_id = ? WHERE parent_id = ?")
+            .bind(&parent_id)
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("UPDATE meetings SET folder_id = ? WHERE folder_id = ?")
+            .bind(&parent_id)
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM folders WHERE id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(true)
+    }
+
+    /// Files a meeting in a folder; None leaves it unfiled
+    pub async fn move_meeting(pool: &SqlitePool, meeting_id: &str, folder_id: Option<&str>) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("UPDATE meetings SET folder_id = ?, updated_at = ? WHERE id = ?")
+            .bind(folder_id)
+            .bind(Utc::now())
+            .bind(meeting_id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+}
@@ -0,0 +1,67 @@
+use crate::database::models::GlossaryTerm;
+use chrono::Utc;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub struct GlossaryRepository;
+
+impl GlossaryRepository {
+    pub async fn list(pool: &SqlitePool) -> Result<Vec<GlossaryTerm>, sqlx::Error> {
+        sqlx::query_as::<_, GlossaryTerm>("SELECT * FROM glossary_terms ORDER BY term COLLATE NOCASE")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<GlossaryTerm>, sqlx::Error> {
+        sqlx::query_as::<_, GlossaryTerm>("SELECT * FROM glossary_terms WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// `aliases` is a JSON array of strings
+    pub async fn insert(pool: &SqlitePool, term: &str, aliases: &str) -> Result<GlossaryTerm, sqlx::Error> {
+        let id = format!("term-{}", Uuid::new_v4());
+        let now = Utc::now();
+        sqlx::query("INSERT INTO glossary_terms (id, term, aliases, created_at, updated_at) VALUES (?, ?, ?, ?, ?)")
+            .bind(&id)
+            .bind(term)
+            .bind(aliases)
+            .bind(now)
+            .bind(now)
+            .execute(pool)
+            .await?;
+
+        Self::get(pool, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: &str,
+        term: &str,
+        aliases: &str,
+    ) -> Result<Option<GlossaryTerm>, sqlx::Error> {
+        let rows = sqlx::query("UPDATE glossary_terms SET term = ?, aliases = ?, updated_at = ? WHERE id = ?")
+            .bind(term)
+            .bind(aliases)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+
+        if rows == 0 {
+            return Ok(None);
+        }
+        Self::get(pool, id).await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("DELETE FROM glossary_terms WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::database::models::Job;
+use chrono::Utc;
+use sqlx::SqlitePool;
+use tracing::info as log_info;
+
+pub struct JobsRepository;
+
+impl JobsRepository {
+    pub async fn insert(
+        pool: &SqlitePool,
+        id: &str,
+        kind: &str,
+        priority: i64,
+        payload: &str,
+    ) -> Result<Job, sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO jobs (id, kind, priority, status, progress, payload, created_at, updated_at)
+             VALUES (?, ?, ?, 'queued', 0, ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(kind)
+        .bind(priority)
+        .bind(payload)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        log_info!("Queued {} job {} (priority {})", kind, id, priority);
+        Self::get(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<Job>, sqlx::Error> {
+        sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Jobs ordered as the queue will run them; finished jobs last, newest first
+    pub async fn list(pool: &SqlitePool, include_finished: bool) -> Result<Vec<Job>, sqlx::Error> {
+        let filter = if include_finished {
+            ""
+        } else {
+            "WHERE status IN ('queued', 'running')"
+        };
+        let query = format!(
+            "SELECT * FROM jobs {}
+             ORDER BY CASE status WHEN 'running' THEN 0 WHEN 'queued' THEN 1 ELSE 2 END,
+                      CASE WHEN status IN ('running', 'queued') THEN priority ELSE 0 END,
+                      CASE WHEN status IN ('running', 'queued') THEN created_at END ASC,
+                      updated_at DESC",
+            filter
+        );
+        sqlx::query_as::<_, Job>(&query).fetch_all(pool).await
+    }
+
+    /// Claims the next queued job whose priority is within the given range
+    pub async fn claim_next(
+        pool: &SqlitePool,
+        min_priority: i64,
+        max_priority: i64,
+    ) -> Result<Option<Job>, sqlx::Error> {
+        let mut transaction = pool.begin().await?;
+
+        let job = sqlx::query_as::<_, Job>(
+            "SELECT * FROM jobs WHERE status = 'queued' AND priority BETWEEN ? AND ?
+             ORDER BY priority ASC, created_at ASC LIMIT 1",
+        )
+        .bind(min_priority)
+        .bind(max_priority)
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        let Some(mut job) = job else {
+            transaction.rollback().await?;
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        sqlx::query("UPDATE jobs SET status = 'running', updated_at = ? WHERE id = ?")
+            .bind(now)
+            .bind(&job.id)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+
+        job.status = "running".to_string();
+        job.updated_at = now;
+        Ok(Some(job))
+    }
+
+    pub async fn update_progress(
+        pool: &SqlitePool,
+        id: &str,
+        progress: f64,
+        message: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE jobs SET progress = ?, message = ?, updated_at = ? WHERE id = ? AND status = 'running'")
+            .bind(progress)
+            .bind(message)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Moves a job to a final state; a job that is already finished is left untouched
+    pub async fn finish(
+        pool: &SqlitePool,
+        id: &str,
+        status: &str,
+        result: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let progress_update = if status == "completed" { ", progress = 1" } else { "" };
+        let query = format!(
+            "UPDATE jobs SET status = ?, result = ?, error = ?, updated_at = ?{}
+             WHERE id = ? AND status IN ('queued', 'running')",
+            progress_update
+        );
+        let rows = sqlx::query(&query)
+            .bind(status)
+            .bind(result)
+            .bind(error)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    /// Jobs interrupted by a shutdown go back to the queue
+    pub async fn requeue_interrupted(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let rows = sqlx::query("UPDATE jobs SET status = 'queued', progress = 0, message = NULL, updated_at = ? WHERE status = 'running'")
+            .bind(Utc::now())
+            .execute(pool)
+            .await?
+            .rows_affected();
+        if rows > 0 {
+            log_info!("Requeued {} interrupted job(s)", rows);
+        }
+        Ok(rows)
+    }
+}
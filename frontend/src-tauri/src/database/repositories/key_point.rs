@@ -0,0 +1,66 @@
+use crate::database::models::KeyPoint;
+use chrono::Utc;
+use sqlx::{Connection, SqlitePool};
+use uuid::Uuid;
+
+/// Fields of a key point before it is stored
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewKeyPoint {
+    pub kind: String,
+    pub text: String,
+    /// JSON array of transcript ids
+    pub source_segments: String,
+}
+
+pub struct KeyPointsRepository;
+
+impl KeyPointsRepository {
+    pub async fn list_for_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<KeyPoint>, sqlx::Error> {
+        sqlx::query_as::<_, KeyPoint>("SELECT * FROM key_points WHERE meeting_id = ? ORDER BY kind, rowid")
+            .bind(meeting_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("DELETE FROM key_points WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    /// Replaces all key points of a meeting with a new extraction
+    pub async fn replace_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        points: &[NewKeyPoint],
+    ) -> Result<Vec<KeyPoint>, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query("DELETE FROM key_points WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        let now = Utc::now();
+        for point in points {
+            sqlx::query(
+                "INSERT INTO key_points (id, meeting_id, kind, text, source_segments, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(format!("point-{}", Uuid::new_v4()))
+            .bind(meeting_id)
+            .bind(&point.kind)
+            .bind(&point.text)
+            .bind(&point.source_segments)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Self::list_for_meeting(pool, meeting_id).await
+    }
+}
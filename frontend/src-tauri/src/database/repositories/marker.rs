@@ -0,0 +1,62 @@
+use crate::database::models::MeetingMarker;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub struct MarkersRepository;
+
+impl MarkersRepository {
+    pub async fn list_for_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<MeetingMarker>, sqlx::Error> {
+        sqlx::query_as::<_, MeetingMarker>("SELECT * FROM meeting_markers WHERE meeting_id = ? ORDER BY audio_time")
+            .bind(meeting_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<MeetingMarker>, sqlx::Error> {
+        sqlx::query_as::<_, MeetingMarker>("SELECT * FROM meeting_markers WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// `created_at` is when the moment was flagged, which for markers dropped during the
+    /// recording is before the meeting was saved
+    pub async fn create(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        audio_time: f64,
+        label: Option<&str>,
+        created_at: DateTime<Utc>,
+    ) -> Result<MeetingMarker, sqlx::Error> {
+        let id = format!("marker-{}", Uuid::new_v4());
+        sqlx::query("INSERT INTO meeting_markers (id, meeting_id, audio_time, label, created_at) VALUES (?, ?, ?, ?, ?)")
+            .bind(&id)
+            .bind(meeting_id)
+            .bind(audio_time)
+            .bind(label)
+            .bind(created_at)
+            .execute(pool)
+            .await?;
+        Self::get(pool, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn update_label(pool: &SqlitePool, id: &str, label: Option<&str>) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("UPDATE meeting_markers SET label = ? WHERE id = ?")
+            .bind(label)
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("DELETE FROM meeting_markers WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+}
@@ -15,6 +15,22 @@ impl MeetingsRepository {
         Ok(meetings)
     }
 
+    /// The most recently created meeting
+    pub async fn get_latest_meeting(pool: &SqlitePool) -> Result<Option<MeetingModel>, sqlx::Error> {
+        sqlx::query_as::<_, MeetingModel>("SELECT * FROM meetings ORDER BY created_at DESC LIMIT 1")
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Folder holding the meeting's audio and transcript files, if it was recorded or imported
+    pub async fn get_folder_path(pool: &SqlitePool, meeting_id: &str) -> Result<Option<String>, SqlxError> {
+        let folder: Option<(Option<String>,)> = sqlx::query_as("SELECT folder_path FROM meetings WHERE id = ?")
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(folder.and_then(|(path,)| path))
+    }
+
     pub async fn delete_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<bool, SqlxError> {
         if meeting_id.trim().is_empty() {
             return Err(SqlxError::Protocol(
@@ -92,6 +108,13 @@ impl MeetingsRepository {
                     audio_start_time: t.audio_start_time,
                     audio_end_time: t.audio_end_time,
                     duration: t.duration,
+                    translation: t.translation,
+                    words: t
+                        .words
+                        .as_deref()
+                        .and_then(|words| serde_json::from_str(words).ok())
+                        .unwrap_or_default(),
+                    speaker: t.speaker,
                 })
                 .collect::<Vec<_>>();
 
@@ -171,9 +194,32 @@ impl MeetingsRepository {
         transaction.commit().await?;
         Ok(true)
     }
+
+    /// Template selected for the meeting's summary, if any
+    pub async fn get_template_id(pool: &SqlitePool, meeting_id: &str) -> Result<Option<String>, SqlxError> {
+        let template_id: Option<Option<String>> = sqlx::query_scalar("SELECT template_id FROM meetings WHERE id = ?")
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(template_id.flatten())
+    }
+
+    pub async fn set_template_id(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        template_id: Option<&str>,
+    ) -> Result<bool, SqlxError> {
+        let rows = sqlx::query("UPDATE meetings SET template_id = ? WHERE id = ?")
+            .bind(template_id)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
 }
 
-async fn delete_meeting_with_transaction(
+pub(crate) async fn delete_meeting_with_transaction(
     transaction: &mut SqliteConnection,
     meeting_id: &str,
 ) -> Result<bool, SqlxError> {
@@ -207,7 +253,55 @@ async fn delete_meeting_with_transaction(
         .execute(&mut *transaction)
         .await?;
 
-    // 4. Finally, delete the meeting
+    // 4. Delete from meeting_speakers
+    sqlx::query("DELETE FROM meeting_speakers WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 5. Delete from action_items
+    sqlx::query("DELETE FROM action_items WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 6. Delete from key_points
+    sqlx::query("DELETE FROM key_points WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 7. Delete from summary_versions
+    sqlx::query("DELETE FROM summary_versions WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 8. Delete from segment_embeddings
+    sqlx::query("DELETE FROM segment_embeddings WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 9. Delete library tags, participants, metadata, highlight markers, confidential ranges,
+    //    redaction audit, sync conflicts and the record of exports
+    for table in [
+        "meeting_tags",
+        "meeting_participants",
+        "meeting_metadata",
+        "meeting_markers",
+        "confidential_ranges",
+        "redaction_audit",
+        "sync_conflicts",
+        "export_artifacts",
+    ] {
+        sqlx::query(&format!("DELETE FROM {} WHERE meeting_id = ?", table))
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+    }
+
+    // 10. Finally, delete the meeting
     let result = sqlx::query("DELETE FROM meetings WHERE id = ?")
         .bind(meeting_id)
         .execute(&mut *transaction)
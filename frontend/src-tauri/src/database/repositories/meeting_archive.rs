@@ -0,0 +1,143 @@
+use crate::export::archive::MeetingArchive;
+use chrono::Utc;
+use sqlx::{Connection, SqlitePool};
+use std::collections::HashMap;
+use std::path::Path;
+use uuid::Uuid;
+
+pub struct MeetingArchivesRepository;
+
+impl MeetingArchivesRepository {
+    /// Stores an archived meeting as a new meeting and returns its id
+    ///
+    /// Every row gets a new id, so importing the same archive twice yields two meetings.
+    /// Segment references of action items and key points are mapped to the new segment ids.
+    /// Speaker profiles are not imported; segments keep their speaker names.
+    pub async fn import(pool: &SqlitePool, archive: &MeetingArchive) -> Result<String, sqlx::Error> {
+        let meeting = &archive.meeting;
+        let meeting_id = format!("meeting-{}", Uuid::new_v4());
+        // The recording only comes along when this machine has it at the same place
+        let folder_path = archive
+            .audio
+            .as_ref()
+            .map(|audio| audio.folder_path.clone())
+            .filter(|folder| Path::new(folder).is_dir());
+
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query(
+            "INSERT INTO meetings (id, title, created_at, updated_at, folder_path, archived_at, notes) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&meeting_id)
+        .bind(&meeting.title)
+        .bind(meeting.created_at)
+        .bind(meeting.updated_at)
+        .bind(&folder_path)
+        .bind(meeting.archived_at)
+        .bind(&meeting.notes)
+        .execute(&mut *transaction)
+        .await?;
+
+        let mut segment_ids = HashMap::new();
+        for segment in &archive.segments {
+            let transcript_id = format!("transcript-{}", Uuid::new_v4());
+            sqlx::query(
+                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, translation, words, speaker)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&transcript_id)
+            .bind(&meeting_id)
+            .bind(&segment.text)
+            .bind(&segment.timestamp)
+            .bind(segment.start)
+            .bind(segment.end)
+            .bind(segment.duration)
+            .bind(&segment.translation)
+            .bind((!segment.words.is_empty()).then(|| serde_json::to_string(&segment.words).ok()).flatten())
+            .bind(&segment.speaker)
+            .execute(&mut *transaction)
+            .await?;
+            segment_ids.insert(segment.id.as_str(), transcript_id);
+        }
+
+        for tag in &meeting.tags {
+            sqlx::query("INSERT OR IGNORE INTO meeting_tags (meeting_id, tag) VALUES (?, ?)")
+                .bind(&meeting_id)
+                .bind(tag)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        for (position, participant) in meeting.participants.iter().enumerate() {
+            sqlx::query("INSERT INTO meeting_participants (meeting_id, position, name, email) VALUES (?, ?, ?, ?)")
+                .bind(&meeting_id)
+                .bind(position as i64)
+                .bind(&participant.name)
+                .bind(&participant.email)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        for (key, value) in &meeting.metadata {
+            sqlx::query("INSERT INTO meeting_metadata (meeting_id, key, value) VALUES (?, ?, ?)")
+                .bind(&meeting_id)
+                .bind(key)
+                .bind(value)
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        let now = Utc::now();
+        if let Some(summary) = &archive.summary {
+            let result = match &summary.result {
+                serde_json::Value::String(text) => text.clone(),
+                result => result.to_string(),
+            };
+            sqlx::query(
+                "INSERT INTO summary_processes (meeting_id, status, created_at, updated_at, result) VALUES (?, 'completed', ?, ?, ?)",
+            )
+            .bind(&meeting_id)
+            .bind(now)
+            .bind(now)
+            .bind(result)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        for item in &archive.action_items {
+            sqlx::query(
+                "INSERT INTO action_items (id, meeting_id, text, assignee, due_date, source_segment, completed, source, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(format!("action-{}", Uuid::new_v4()))
+            .bind(&meeting_id)
+            .bind(&item.text)
+            .bind(&item.assignee)
+            .bind(&item.due_date)
+            .bind(item.source_segment.as_deref().and_then(|id| segment_ids.get(id)))
+            .bind(item.completed)
+            .bind(&item.source)
+            .bind(now)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        for point in &archive.key_points {
+            let sources: Vec<&String> = point.source_segments.iter().filter_map(|id| segment_ids.get(id.as_str())).collect();
+            sqlx::query(
+                "INSERT INTO key_points (id, meeting_id, kind, text, source_segments, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(format!("point-{}", Uuid::new_v4()))
+            .bind(&meeting_id)
+            .bind(&point.kind)
+            .bind(&point.text)
+            .bind(serde_json::to_string(&sources).unwrap_or_else(|_| "[]".to_string()))
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(meeting_id)
+    }
+}
@@ -0,0 +1,259 @@
+use crate::database::models::{LibraryMeeting, Participant};
+use crate::library::filter::{ArchivedFilter, LibraryFilter};
+use chrono::Utc;
+use sqlx::{Connection, QueryBuilder, Sqlite, SqlitePool};
+use uuid::Uuid;
+
+const LIBRARY_COLUMNS: &str = "id, title, created_at, updated_at, folder_path, archived_at, notes, folder_id";
+
+/// `value` as a LIKE pattern matching it anywhere, with wildcards in it taken literally
+fn contains_pattern(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+pub struct MeetingLibraryRepository;
+
+impl MeetingLibraryRepository {
+    /// Creates an empty meeting, e.g. for notes taken without a recording
+    pub async fn create(pool: &SqlitePool, title: &str) -> Result<String, sqlx::Error> {
+        let meeting_id = format!("meeting-{}", Uuid::new_v4());
+        let now = Utc::now();
+        sqlx::query("INSERT INTO meetings (id, title, created_at, updated_at) VALUES (?, ?, ?, ?)")
+            .bind(&meeting_id)
+            .bind(title)
+            .bind(now)
+            .bind(now)
+            .execute(pool)
+            .await?;
+        Ok(meeting_id)
+    }
+
+    pub async fn get(pool: &SqlitePool, meeting_id: &str) -> Result<Option<LibraryMeeting>, sqlx::Error> {
+        sqlx::query_as::<_, LibraryMeeting>(&format!("SELECT {} FROM meetings WHERE id = ?", LIBRARY_COLUMNS))
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Newest first
+    pub async fn list(pool: &SqlitePool, include_archived: bool) -> Result<Vec<LibraryMeeting>, sqlx::Error> {
+        sqlx::query_as::<_, LibraryMeeting>(&format!(
+            "SELECT {} FROM meetings WHERE ? OR archived_at IS NULL ORDER BY created_at DESC",
+            LIBRARY_COLUMNS
+        ))
+        .bind(include_archived)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Meetings matching a library filter, newest first
+    pub async fn query(pool: &SqlitePool, filter: &LibraryFilter) -> Result<Vec<LibraryMeeting>, sqlx::Error> {
+        let mut query: QueryBuilder<Sqlite> =
+            QueryBuilder::new(format!("SELECT {} FROM meetings WHERE 1 = 1", LIBRARY_COLUMNS));
+        match filter.archived {
+            ArchivedFilter::Exclude => {
+                query.push(" AND archived_at IS NULL");
+            }
+            ArchivedFilter::Only => {
+                query.push(" AND archived_at IS NOT NULL");
+            }
+            ArchivedFilter::Include => {}
+        }
+        for tag in &filter.tags {
+            query.push(" AND EXISTS (SELECT 1 FROM meeting_tags t WHERE t.meeting_id = meetings.id AND t.tag = ");
+            query.push_bind(tag).push(")");
+        }
+        for tag in &filter.excluded_tags {
+            query.push(" AND NOT EXISTS (SELECT 1 FROM meeting_tags t WHERE t.meeting_id = meetings.id AND t.tag = ");
+            query.push_bind(tag).push(")");
+        }
+        if let Some(folder) = &filter.folder {
+            // The named folder and every folder below it
+            query.push(" AND folder_id IN (WITH RECURSIVE tree(id) AS (SELECT id FROM folders WHERE name = ");
+            query.push_bind(folder);
+            query.push(" COLLATE NOCASE UNION SELECT f.id FROM folders f JOIN tree ON f.parent_id = tree.id)");
+            query.push(" SELECT id FROM tree)");
+        }
+        if let Some(participant) = &filter.participant {
+            let pattern = contains_pattern(participant);
+            query.push(" AND EXISTS (SELECT 1 FROM meeting_participants p WHERE p.meeting_id = meetings.id");
+            query.push(" AND (p.name LIKE ");
+            query.push_bind(pattern.clone()).push(" ESCAPE '\\' OR p.email LIKE ");
+            query.push_bind(pattern).push(" ESCAPE '\\'))");
+        }
+        if let Some(after) = filter.after {
+            query.push(" AND created_at >= ").push_bind(after);
+        }
+        if let Some(before) = filter.before {
+            query.push(" AND created_at < ").push_bind(before);
+        }
+        for word in &filter.title_words {
+            query.push(" AND title LIKE ").push_bind(contains_pattern(word)).push(" ESCAPE '\\'");
+        }
+        query.push(" ORDER BY created_at DESC");
+        query.build_query_as::<LibraryMeeting>().fetch_all(pool).await
+    }
+
+    pub async fn set_archived(pool: &SqlitePool, meeting_id: &str, archived: bool) -> Result<bool, sqlx::Error> {
+        let now = Utc::now();
+        let rows = sqlx::query(
+            "UPDATE meetings SET archived_at = CASE WHEN ? THEN coalesce(archived_at, ?) END, updated_at = ? WHERE id = ?",
+        )
+        .bind(archived)
+        .bind(now)
+        .bind(now)
+        .bind(meeting_id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+        Ok(rows > 0)
+    }
+
+    pub async fn set_notes(pool: &SqlitePool, meeting_id: &str, notes: Option<&str>) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("UPDATE meetings SET notes = ?, updated_at = ? WHERE id = ?")
+            .bind(notes)
+            .bind(Utc::now())
+            .bind(meeting_id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    /// (meeting id, tag) of every meeting, or of one
+    pub async fn list_tags(pool: &SqlitePool, meeting_id: Option<&str>) -> Result<Vec<(String, String)>, sqlx::Error> {
+        sqlx::query_as("SELECT meeting_id, tag FROM meeting_tags WHERE ? IS NULL OR meeting_id = ? ORDER BY tag")
+            .bind(meeting_id)
+            .bind(meeting_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Every tag in use with the number of meetings carrying it
+    pub async fn tag_counts(pool: &SqlitePool) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        sqlx::query_as("SELECT tag, COUNT(*) FROM meeting_tags GROUP BY tag ORDER BY tag")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn set_tags(pool: &SqlitePool, meeting_id: &str, tags: &[String]) -> Result<(), sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        sqlx::query("DELETE FROM meeting_tags WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        for tag in tags {
+            sqlx::query("INSERT OR IGNORE INTO meeting_tags (meeting_id, tag) VALUES (?, ?)")
+                .bind(meeting_id)
+                .bind(tag)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        transaction.commit().await
+    }
+
+    pub async fn add_tag(pool: &SqlitePool, meeting_id: &str, tag: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT OR IGNORE INTO meeting_tags (meeting_id, tag) VALUES (?, ?)")
+            .bind(meeting_id)
+            .bind(tag)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn remove_tag(pool: &SqlitePool, meeting_id: &str, tag: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("DELETE FROM meeting_tags WHERE meeting_id = ? AND tag = ?")
+            .bind(meeting_id)
+            .bind(tag)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    /// (meeting id, participant) of every meeting, or of one, in the order entered
+    pub async fn list_participants(
+        pool: &SqlitePool,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<(String, Participant)>, sqlx::Error> {
+        let rows: Vec<(String, String, Option<String>)> = sqlx::query_as(
+            "SELECT meeting_id, name, email FROM meeting_participants
+             WHERE ? IS NULL OR meeting_id = ? ORDER BY meeting_id, position",
+        )
+        .bind(meeting_id)
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(meeting_id, name, email)| (meeting_id, Participant { name, email }))
+            .collect())
+    }
+
+    pub async fn set_participants(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        participants: &[Participant],
+    ) -> Result<(), sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        sqlx::query("DELETE FROM meeting_participants WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        for (position, participant) in participants.iter().enumerate() {
+            sqlx::query("INSERT INTO meeting_participants (meeting_id, position, name, email) VALUES (?, ?, ?, ?)")
+                .bind(meeting_id)
+                .bind(position as i64)
+                .bind(&participant.name)
+                .bind(&participant.email)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        transaction.commit().await
+    }
+
+    /// (meeting id, key, value) of every meeting, or of one
+    pub async fn list_metadata(
+        pool: &SqlitePool,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<(String, String, String)>, sqlx::Error> {
+        sqlx::query_as("SELECT meeting_id, key, value FROM meeting_metadata WHERE ? IS NULL OR meeting_id = ? ORDER BY key")
+            .bind(meeting_id)
+            .bind(meeting_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Sets a metadata value; None removes the key
+    pub async fn set_metadata(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        key: &str,
+        value: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        match value {
+            Some(value) => {
+                sqlx::query(
+                    "INSERT INTO meeting_metadata (meeting_id, key, value) VALUES (?, ?, ?)
+                     ON CONFLICT(meeting_id, key) DO UPDATE SET value = excluded.value",
+                )
+                .bind(meeting_id)
+                .bind(key)
+                .bind(value)
+                .execute(pool)
+                .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM meeting_metadata WHERE meeting_id = ? AND key = ?")
+                    .bind(meeting_id)
+                    .bind(key)
+                    .execute(pool)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
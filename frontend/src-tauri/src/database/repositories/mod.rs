@@ -1,5 +1,30 @@
+pub mod action_item;
+pub mod calendar;
+pub mod confidential_range;
+pub mod deletion;
+pub mod email_setting;
+pub mod embedding;
+pub mod export_artifact;
+pub mod folder;
+pub mod glossary;
+pub mod job;
+pub mod key_point;
+pub mod marker;
 pub mod meeting;
+pub mod meeting_archive;
+pub mod meeting_library;
+pub mod notion_connection;
+pub mod redaction_audit;
+pub mod saved_filter;
 pub mod setting;
+pub mod slack_setting;
+pub mod search;
+pub mod speaker;
 pub mod summary;
+pub mod summary_version;
+pub mod sync;
+pub mod template;
 pub mod transcript;
 pub mod transcript_chunk;
+pub mod usage;
+pub mod webhook;
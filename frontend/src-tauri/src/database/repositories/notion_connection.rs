@@ -0,0 +1,65 @@
+use crate::database::models::NotionConnection;
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+pub struct NotionConnectionsRepository;
+
+impl NotionConnectionsRepository {
+    pub async fn list(pool: &SqlitePool) -> Result<Vec<NotionConnection>, sqlx::Error> {
+        sqlx::query_as::<_, NotionConnection>("SELECT * FROM notion_connections ORDER BY workspace_name COLLATE NOCASE")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<NotionConnection>, sqlx::Error> {
+        sqlx::query_as::<_, NotionConnection>("SELECT * FROM notion_connections WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Adds a workspace; reconnecting one keeps its target database
+    pub async fn upsert(pool: &SqlitePool, id: &str, workspace_name: &str) -> Result<NotionConnection, sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO notion_connections (id, workspace_name, created_at) VALUES (?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET workspace_name = excluded.workspace_name",
+        )
+        .bind(id)
+        .bind(workspace_name)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Self::get(pool, id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn set_database(
+        pool: &SqlitePool,
+        id: &str,
+        database_id: &str,
+        database_title: &str,
+        title_property: &str,
+        date_property: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query(
+            "UPDATE notion_connections SET database_id = ?, database_title = ?, title_property = ?, date_property = ? WHERE id = ?",
+        )
+        .bind(database_id)
+        .bind(database_title)
+        .bind(title_property)
+        .bind(date_property)
+        .bind(id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+        Ok(rows > 0)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("DELETE FROM notion_connections WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+}
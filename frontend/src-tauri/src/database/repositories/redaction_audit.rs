@@ -0,0 +1,42 @@
+use crate::database::models::RedactionAuditEntry;
+use chrono::Utc;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub struct RedactionAuditRepository;
+
+impl RedactionAuditRepository {
+    pub async fn list_for_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<RedactionAuditEntry>, sqlx::Error> {
+        sqlx::query_as::<_, RedactionAuditEntry>(
+            "SELECT * FROM redaction_audit WHERE meeting_id = ? ORDER BY created_at DESC, category",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Records one pass over a meeting's text: a row per category with what was masked
+    pub async fn record(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        target: &str,
+        counts: &[(&str, usize)],
+    ) -> Result<(), sqlx::Error> {
+        let created_at = Utc::now();
+        let mut transaction = pool.begin().await?;
+        for (category, count) in counts {
+            sqlx::query(
+                "INSERT INTO redaction_audit (id, meeting_id, target, category, count, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(format!("redaction-{}", Uuid::new_v4()))
+            .bind(meeting_id)
+            .bind(target)
+            .bind(category)
+            .bind(*count as i64)
+            .bind(created_at)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await
+    }
+}
@@ -0,0 +1,46 @@
+use crate::database::models::SavedFilter;
+use chrono::Utc;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub struct SavedFiltersRepository;
+
+impl SavedFiltersRepository {
+    pub async fn list(pool: &SqlitePool) -> Result<Vec<SavedFilter>, sqlx::Error> {
+        sqlx::query_as::<_, SavedFilter>("SELECT * FROM saved_filters ORDER BY name COLLATE NOCASE")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<SavedFilter>, sqlx::Error> {
+        sqlx::query_as::<_, SavedFilter>("SELECT * FROM saved_filters WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Creates a filter, or updates it when `id` is given
+    pub async fn save(pool: &SqlitePool, id: Option<&str>, name: &str, query: &str) -> Result<SavedFilter, sqlx::Error> {
+        let id = id.map(str::to_string).unwrap_or_else(|| format!("filter-{}", Uuid::new_v4()));
+        sqlx::query(
+            "INSERT INTO saved_filters (id, name, query, created_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET name = excluded.name, query = excluded.query",
+        )
+        .bind(&id)
+        .bind(name)
+        .bind(query)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Self::get(pool, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("DELETE FROM saved_filters WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+}
@@ -0,0 +1,91 @@
+use crate::database::models::{SummarySearchHit, TranscriptSearchHit};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+/// Restricts search hits; unset fields don't filter
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilters {
+    pub meeting_id: Option<String>,
+    /// Matched case-insensitively; summaries have no speaker and are skipped when set
+    pub speaker: Option<String>,
+    /// Meetings created at or after
+    pub from: Option<DateTime<Utc>>,
+    /// Meetings created before
+    pub to: Option<DateTime<Utc>>,
+}
+
+pub struct SearchRepository;
+
+impl SearchRepository {
+    /// Transcript segments matching an FTS5 `MATCH` expression, most relevant first
+    pub async fn search_transcripts(
+        pool: &SqlitePool,
+        match_expression: &str,
+        filters: &SearchFilters,
+        limit: usize,
+    ) -> Result<Vec<TranscriptSearchHit>, sqlx::Error> {
+        sqlx::query_as::<_, TranscriptSearchHit>(
+            "SELECT t.id AS transcript_id, t.meeting_id, m.title AS meeting_title, m.created_at AS meeting_created_at,
+                    t.speaker, t.audio_start_time, t.audio_end_time,
+                    snippet(transcripts_fts, 0, '<mark>', '</mark>', '…', 16) AS snippet,
+                    bm25(transcripts_fts) AS rank
+             FROM transcripts_fts
+             JOIN transcripts t ON t.rowid = transcripts_fts.rowid
+             JOIN meetings m ON m.id = t.meeting_id
+             WHERE transcripts_fts MATCH ?
+               AND (? IS NULL OR t.meeting_id = ?)
+               AND (? IS NULL OR t.speaker = ? COLLATE NOCASE)
+               AND (? IS NULL OR m.created_at >= ?)
+               AND (? IS NULL OR m.created_at < ?)
+             ORDER BY rank
+             LIMIT ?",
+        )
+        .bind(match_expression)
+        .bind(&filters.meeting_id)
+        .bind(&filters.meeting_id)
+        .bind(&filters.speaker)
+        .bind(&filters.speaker)
+        .bind(filters.from)
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(filters.to)
+        .bind(limit as i64)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Meetings whose current summary matches an FTS5 `MATCH` expression, most relevant first
+    pub async fn search_summaries(
+        pool: &SqlitePool,
+        match_expression: &str,
+        filters: &SearchFilters,
+        limit: usize,
+    ) -> Result<Vec<SummarySearchHit>, sqlx::Error> {
+        if filters.speaker.is_some() {
+            return Ok(Vec::new());
+        }
+        sqlx::query_as::<_, SummarySearchHit>(
+            "SELECT summaries_fts.meeting_id, m.title AS meeting_title, m.created_at AS meeting_created_at,
+                    snippet(summaries_fts, 0, '<mark>', '</mark>', '…', 16) AS snippet,
+                    bm25(summaries_fts) AS rank
+             FROM summaries_fts
+             JOIN meetings m ON m.id = summaries_fts.meeting_id
+             WHERE summaries_fts MATCH ?
+               AND (? IS NULL OR summaries_fts.meeting_id = ?)
+               AND (? IS NULL OR m.created_at >= ?)
+               AND (? IS NULL OR m.created_at < ?)
+             ORDER BY rank
+             LIMIT ?",
+        )
+        .bind(match_expression)
+        .bind(&filters.meeting_id)
+        .bind(&filters.meeting_id)
+        .bind(filters.from)
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(filters.to)
+        .bind(limit as i64)
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -1,4 +1,4 @@
-use crate::database::models::{Setting, TranscriptSetting};
+use crate::database::models::{Setting, SummaryProviderSetting, TranscriptSetting};
 use sqlx::SqlitePool;
 
 #[derive(serde::Deserialize, Debug)]
@@ -23,7 +23,7 @@ pub struct SaveTranscriptConfigRequest {
 
 pub struct SettingsRepository;
 
-// Transcript providers: localWhisper, deepgram, elevenLabs, groq, openai
+// Transcript providers: localWhisper, parakeet, deepgram, elevenLabs, groq, openai, assemblyAI
 // Summary providers: openai, claude, ollama, groq, added openrouter
 // NOTE: Handle data exclusion in the higher layer as this is database abstraction layer(using SELECT *)
 
@@ -123,6 +123,51 @@ impl SettingsRepository {
         Ok(api_key)
     }
 
+    pub async fn get_summary_provider_settings(
+        pool: &SqlitePool,
+    ) -> std::result::Result<Vec<SummaryProviderSetting>, sqlx::Error> {
+        sqlx::query_as::<_, SummaryProviderSetting>("SELECT * FROM summary_provider_settings ORDER BY provider")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get_summary_provider_setting(
+        pool: &SqlitePool,
+        provider: &str,
+    ) -> std::result::Result<Option<SummaryProviderSetting>, sqlx::Error> {
+        sqlx::query_as::<_, SummaryProviderSetting>("SELECT * FROM summary_provider_settings WHERE provider = ?")
+            .bind(provider)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Remembers the model for a provider; a None endpoint keeps the saved one
+    pub async fn save_summary_provider_setting(
+        pool: &SqlitePool,
+        provider: &str,
+        model: &str,
+        endpoint: Option<&str>,
+    ) -> std::result::Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO summary_provider_settings (provider, model, endpoint, updated_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(provider) DO UPDATE SET
+                model = excluded.model,
+                endpoint = COALESCE(excluded.endpoint, summary_provider_settings.endpoint),
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(provider)
+        .bind(model)
+        .bind(endpoint)
+        .bind(chrono::Utc::now())
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn get_transcript_config(
         pool: &SqlitePool,
     ) -> std::result::Result<Option<TranscriptSetting>, sqlx::Error> {
@@ -170,6 +215,7 @@ impl SettingsRepository {
             "elevenLabs" => "elevenLabsApiKey",
             "groq" => "groqApiKey",
             "openai" => "openaiApiKey",
+            "assemblyAI" => "assemblyAiApiKey",
             _ => {
                 return Err(sqlx::Error::Protocol(
                     format!("Invalid provider: {}", provider).into(),
@@ -202,6 +248,7 @@ impl SettingsRepository {
             "elevenLabs" => "elevenLabsApiKey",
             "groq" => "groqApiKey",
             "openai" => "openaiApiKey",
+            "assemblyAI" => "assemblyAiApiKey",
             _ => {
                 return Err(sqlx::Error::Protocol(
                     format!("Invalid provider: {}", provider).into(),
@@ -242,4 +289,32 @@ impl SettingsRepository {
 
         Ok(())
     }
+
+    pub async fn delete_transcript_api_key(
+        pool: &SqlitePool,
+        provider: &str,
+    ) -> std::result::Result<(), sqlx::Error> {
+        let api_key_column = match provider {
+            "localWhisper" => "whisperApiKey",
+            "parakeet" => return Ok(()), // Parakeet doesn't need an API key
+            "deepgram" => "deepgramApiKey",
+            "elevenLabs" => "elevenLabsApiKey",
+            "groq" => "groqApiKey",
+            "openai" => "openaiApiKey",
+            "assemblyAI" => "assemblyAiApiKey",
+            _ => {
+                return Err(sqlx::Error::Protocol(
+                    format!("Invalid provider: {}", provider).into(),
+                ))
+            }
+        };
+
+        let query = format!(
+            "UPDATE transcript_settings SET {} = NULL WHERE id = '1'",
+            api_key_column
+        );
+        sqlx::query(&query).execute(pool).await?;
+
+        Ok(())
+    }
 }
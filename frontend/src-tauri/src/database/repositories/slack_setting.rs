@@ -0,0 +1,46 @@
+use crate::database::models::SlackSettings;
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+pub struct SlackSettingsRepository;
+
+impl SlackSettingsRepository {
+    pub async fn get(pool: &SqlitePool) -> Result<Option<SlackSettings>, sqlx::Error> {
+        sqlx::query_as::<_, SlackSettings>("SELECT enabled, mode, channel, updated_at FROM slack_settings WHERE id = 1")
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn save(pool: &SqlitePool, enabled: bool, mode: &str, channel: Option<&str>) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO slack_settings (id, enabled, mode, channel, updated_at) VALUES (1, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET enabled = excluded.enabled, mode = excluded.mode,
+                 channel = excluded.channel, updated_at = excluded.updated_at",
+        )
+        .bind(enabled)
+        .bind(mode)
+        .bind(channel)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn is_opted_out(pool: &SqlitePool, meeting_id: &str) -> Result<bool, sqlx::Error> {
+        let opted_out: Option<bool> = sqlx::query_scalar("SELECT slack_opt_out FROM meetings WHERE id = ?")
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(opted_out.unwrap_or(false))
+    }
+
+    pub async fn set_opt_out(pool: &SqlitePool, meeting_id: &str, opt_out: bool) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("UPDATE meetings SET slack_opt_out = ? WHERE id = ?")
+            .bind(opt_out)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+}
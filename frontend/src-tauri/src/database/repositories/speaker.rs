@@ -0,0 +1,234 @@
+use crate::database::models::{MeetingSpeaker, SpeakerProfile};
+use chrono::Utc;
+use sqlx::{Connection, SqlitePool};
+use uuid::Uuid;
+
+pub struct SpeakerProfilesRepository;
+
+impl SpeakerProfilesRepository {
+    pub async fn list(pool: &SqlitePool) -> Result<Vec<SpeakerProfile>, sqlx::Error> {
+        sqlx::query_as::<_, SpeakerProfile>("SELECT * FROM speaker_profiles ORDER BY name COLLATE NOCASE")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<SpeakerProfile>, sqlx::Error> {
+        sqlx::query_as::<_, SpeakerProfile>("SELECT * FROM speaker_profiles WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn find_by_name(pool: &SqlitePool, name: &str) -> Result<Option<SpeakerProfile>, sqlx::Error> {
+        sqlx::query_as::<_, SpeakerProfile>("SELECT * FROM speaker_profiles WHERE name = ? COLLATE NOCASE")
+            .bind(name)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// `embedding` is a JSON array of floats
+    pub async fn insert(pool: &SqlitePool, name: &str, embedding: &str) -> Result<SpeakerProfile, sqlx::Error> {
+        let id = format!("speaker-{}", Uuid::new_v4());
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO speaker_profiles (id, name, embedding, sample_count, created_at, updated_at) VALUES (?, ?, ?, 1, ?, ?)",
+        )
+        .bind(&id)
+        .bind(name)
+        .bind(embedding)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Self::get(pool, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn update_embedding(
+        pool: &SqlitePool,
+        id: &str,
+        embedding: &str,
+        sample_count: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE speaker_profiles SET embedding = ?, sample_count = ?, updated_at = ? WHERE id = ?")
+            .bind(embedding)
+            .bind(sample_count)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Renames the profile and the transcript labels of every meeting it was assigned in
+    pub async fn rename(pool: &SqlitePool, id: &str, name: &str) -> Result<bool, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        let old_name: Option<(String,)> = sqlx::query_as("SELECT name FROM speaker_profiles WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+        let Some((old_name,)) = old_name else {
+            return Ok(false);
+        };
+
+        sqlx::query("UPDATE speaker_profiles SET name = ?, updated_at = ? WHERE id = ?")
+            .bind(name)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query(
+            "UPDATE transcripts SET speaker = ?
+             WHERE speaker = ? AND meeting_id IN (SELECT meeting_id FROM meeting_speakers WHERE profile_id = ?)",
+        )
+        .bind(name)
+        .bind(&old_name)
+        .bind(id)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+        Ok(true)
+    }
+
+    /// Folds `source` into `target`: meetings move over, transcripts take the target's name
+    ///
+    /// `embedding` and `sample_count` are the combined values for the target.
+    pub async fn merge(
+        pool: &SqlitePool,
+        source: &SpeakerProfile,
+        target: &SpeakerProfile,
+        embedding: &str,
+        sample_count: i64,
+    ) -> Result<(), sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query(
+            "UPDATE transcripts SET speaker = ?
+             WHERE speaker = ? AND meeting_id IN (SELECT meeting_id FROM meeting_speakers WHERE profile_id = ?)",
+        )
+        .bind(&target.name)
+        .bind(&source.name)
+        .bind(&source.id)
+        .execute(&mut *transaction)
+        .await?;
+        sqlx::query("UPDATE meeting_speakers SET profile_id = ? WHERE profile_id = ?")
+            .bind(&target.id)
+            .bind(&source.id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("UPDATE speaker_profiles SET embedding = ?, sample_count = ?, updated_at = ? WHERE id = ?")
+            .bind(embedding)
+            .bind(sample_count)
+            .bind(Utc::now())
+            .bind(&target.id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM speaker_profiles WHERE id = ?")
+            .bind(&source.id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Deletes the profile; its meetings fall back to their diarization labels
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        let name: Option<(String,)> = sqlx::query_as("SELECT name FROM speaker_profiles WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+        let Some((name,)) = name else {
+            return Ok(false);
+        };
+
+        sqlx::query(
+            "UPDATE transcripts SET speaker = (
+                 SELECT ms.label FROM meeting_speakers ms
+                 WHERE ms.meeting_id = transcripts.meeting_id AND ms.profile_id = ?
+             )
+             WHERE speaker = ? AND meeting_id IN (SELECT meeting_id FROM meeting_speakers WHERE profile_id = ?)",
+        )
+        .bind(id)
+        .bind(&name)
+        .bind(id)
+        .execute(&mut *transaction)
+        .await?;
+        sqlx::query("UPDATE meeting_speakers SET profile_id = NULL WHERE profile_id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM speaker_profiles WHERE id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(true)
+    }
+
+    pub async fn list_meeting_speakers(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<MeetingSpeaker>, sqlx::Error> {
+        sqlx::query_as::<_, MeetingSpeaker>("SELECT * FROM meeting_speakers WHERE meeting_id = ? ORDER BY label")
+            .bind(meeting_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Replaces the speakers stored for a meeting (diarization was run again)
+    pub async fn replace_meeting_speakers(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        speakers: &[MeetingSpeaker],
+    ) -> Result<(), sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        sqlx::query("DELETE FROM meeting_speakers WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        for speaker in speakers {
+            sqlx::query("INSERT INTO meeting_speakers (meeting_id, label, embedding, profile_id) VALUES (?, ?, ?, ?)")
+                .bind(meeting_id)
+                .bind(&speaker.label)
+                .bind(&speaker.embedding)
+                .bind(&speaker.profile_id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Links a meeting speaker to a profile and relabels its transcript segments
+    pub async fn assign_meeting_speaker(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        label: &str,
+        current_name: &str,
+        profile: &SpeakerProfile,
+    ) -> Result<(), sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        sqlx::query("UPDATE meeting_speakers SET profile_id = ? WHERE meeting_id = ? AND label = ?")
+            .bind(&profile.id)
+            .bind(meeting_id)
+            .bind(label)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("UPDATE transcripts SET speaker = ? WHERE meeting_id = ? AND speaker = ?")
+            .bind(&profile.name)
+            .bind(meeting_id)
+            .bind(current_name)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
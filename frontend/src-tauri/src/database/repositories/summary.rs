@@ -141,6 +141,58 @@ impl SummaryProcessesRepository {
         Ok(())
     }
 
+    /// Makes an earlier summary the current one
+    pub async fn restore_result(pool: &SqlitePool, meeting_id: &str, result: &str) -> Result<bool, sqlx::Error> {
+        let now = Utc::now();
+        let rows = sqlx::query(
+            r#"
+            UPDATE summary_processes
+            SET status = 'completed', result = ?, error = NULL, updated_at = ?
+            WHERE meeting_id = ?
+            "#,
+        )
+        .bind(result)
+        .bind(now)
+        .bind(meeting_id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+        Ok(rows > 0)
+    }
+
+    /// Puts back a process as read before `create_or_reset_process`, or removes the one it
+    /// created when there was none
+    pub async fn put_back_process(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        previous: Option<&SummaryProcess>,
+    ) -> Result<(), sqlx::Error> {
+        let Some(previous) = previous else {
+            sqlx::query("DELETE FROM summary_processes WHERE meeting_id = ?")
+                .bind(meeting_id)
+                .execute(pool)
+                .await?;
+            return Ok(());
+        };
+        sqlx::query(
+            r#"
+            UPDATE summary_processes
+            SET status = ?, result = ?, error = ?, updated_at = ?, start_time = ?, end_time = ?
+            WHERE meeting_id = ?
+            "#,
+        )
+        .bind(&previous.status)
+        .bind(&previous.result)
+        .bind(&previous.error)
+        .bind(previous.updated_at)
+        .bind(previous.start_time)
+        .bind(previous.end_time)
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn update_process_failed(
         pool: &SqlitePool,
         meeting_id: &str,
@@ -163,3 +215,42 @@ impl SummaryProcessesRepository {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::integrity::pool_with_meeting;
+
+    #[tokio::test]
+    async fn test_restore_result() {
+        let pool = pool_with_meeting().await;
+        assert!(!SummaryProcessesRepository::restore_result(&pool, "m1", r#"{"markdown":"old"}"#).await.unwrap());
+
+        SummaryProcessesRepository::create_or_reset_process(&pool, "m1").await.unwrap();
+        SummaryProcessesRepository::update_process_failed(&pool, "m1", "model crashed").await.unwrap();
+        assert!(SummaryProcessesRepository::restore_result(&pool, "m1", r#"{"markdown":"old"}"#).await.unwrap());
+
+        let process = SummaryProcessesRepository::get_summary_data(&pool, "m1").await.unwrap().unwrap();
+        assert_eq!(process.status, "completed");
+        assert_eq!(process.result.as_deref(), Some(r#"{"markdown":"old"}"#));
+        assert_eq!(process.error, None);
+    }
+
+    #[tokio::test]
+    async fn test_put_back_process() {
+        let pool = pool_with_meeting().await;
+        SummaryProcessesRepository::create_or_reset_process(&pool, "m1").await.unwrap();
+        SummaryProcessesRepository::put_back_process(&pool, "m1", None).await.unwrap();
+        assert!(SummaryProcessesRepository::get_summary_data(&pool, "m1").await.unwrap().is_none());
+
+        SummaryProcessesRepository::create_or_reset_process(&pool, "m1").await.unwrap();
+        SummaryProcessesRepository::restore_result(&pool, "m1", r#"{"markdown":"kept"}"#).await.unwrap();
+        let previous = SummaryProcessesRepository::get_summary_data(&pool, "m1").await.unwrap();
+        SummaryProcessesRepository::create_or_reset_process(&pool, "m1").await.unwrap();
+        SummaryProcessesRepository::put_back_process(&pool, "m1", previous.as_ref()).await.unwrap();
+
+        let process = SummaryProcessesRepository::get_summary_data(&pool, "m1").await.unwrap().unwrap();
+        assert_eq!(process.status, "completed");
+        assert_eq!(process.result.as_deref(), Some(r#"{"markdown":"kept"}"#));
+    }
+}
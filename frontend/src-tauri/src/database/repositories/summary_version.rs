@@ -0,0 +1,119 @@
+use crate::database::models::SummaryVersion;
+use chrono::Utc;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub struct SummaryVersionsRepository;
+
+impl SummaryVersionsRepository {
+    /// Newest first
+    pub async fn list_for_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<SummaryVersion>, sqlx::Error> {
+        sqlx::query_as::<_, SummaryVersion>(
+            "SELECT * FROM summary_versions WHERE meeting_id = ? ORDER BY created_at DESC, rowid DESC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<SummaryVersion>, sqlx::Error> {
+        sqlx::query_as::<_, SummaryVersion>("SELECT * FROM summary_versions WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn insert(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        provider: &str,
+        model: &str,
+        template_id: &str,
+        result: &Value,
+    ) -> Result<String, sqlx::Error> {
+        let id = format!("summary-{}", Uuid::new_v4());
+        let result_str = serde_json::to_string(result)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize result: {}", e)))?;
+        sqlx::query(
+            "INSERT INTO summary_versions (id, meeting_id, provider, model, template_id, result, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(meeting_id)
+        .bind(provider)
+        .bind(model)
+        .bind(template_id)
+        .bind(result_str)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Ok(id)
+    }
+
+    /// Whether the meeting already has a version with this result; compared as JSON, since
+    /// the same summary may have been serialized with other spacing or key order
+    pub async fn contains_result(pool: &SqlitePool, meeting_id: &str, result: &Value) -> Result<bool, sqlx::Error> {
+        let stored: Vec<String> = sqlx::query_scalar("SELECT result FROM summary_versions WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_all(pool)
+            .await?;
+        Ok(stored.iter().any(|text| serde_json::from_str::<Value>(text).is_ok_and(|value| value == *result)))
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("DELETE FROM summary_versions WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::integrity::pool_with_meeting;
+    use serde_json::json;
+
+    async fn insert(pool: &SqlitePool, provider: &str, model: &str, result: &Value) -> String {
+        SummaryVersionsRepository::insert(pool, "m1", provider, model, "standup", result).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_versions_listed_newest_first() {
+        let pool = pool_with_meeting().await;
+        let first = insert(&pool, "ollama", "llama3", &json!({"markdown": "a"})).await;
+        let second = insert(&pool, "openai", "gpt-4o", &json!({"markdown": "b"})).await;
+
+        let versions = SummaryVersionsRepository::list_for_meeting(&pool, "m1").await.unwrap();
+        assert_eq!(versions.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(), vec![second.as_str(), first.as_str()]);
+        assert!(SummaryVersionsRepository::list_for_meeting(&pool, "m2").await.unwrap().is_empty());
+
+        let version = SummaryVersionsRepository::get(&pool, &first).await.unwrap().unwrap();
+        assert_eq!((version.provider.as_str(), version.model.as_str()), ("ollama", "llama3"));
+        assert_eq!(serde_json::from_str::<Value>(&version.result).unwrap(), json!({"markdown": "a"}));
+    }
+
+    #[tokio::test]
+    async fn test_delete_version() {
+        let pool = pool_with_meeting().await;
+        let id = insert(&pool, "ollama", "llama3", &json!({})).await;
+        assert!(SummaryVersionsRepository::delete(&pool, &id).await.unwrap());
+        assert!(!SummaryVersionsRepository::delete(&pool, &id).await.unwrap());
+        assert!(SummaryVersionsRepository::get(&pool, &id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_contains_result_ignores_formatting() {
+        let pool = pool_with_meeting().await;
+        let result = json!({"markdown": "# Notes", "usage": {"provider": "ollama"}});
+        insert(&pool, "ollama", "llama3", &result).await;
+
+        let reordered = r#"{ "usage": { "provider": "ollama" }, "markdown": "# Notes" }"#;
+        let reordered: Value = serde_json::from_str(reordered).unwrap();
+        assert!(SummaryVersionsRepository::contains_result(&pool, "m1", &reordered).await.unwrap());
+        let other = json!({"markdown": "# Other"});
+        assert!(!SummaryVersionsRepository::contains_result(&pool, "m1", &other).await.unwrap());
+    }
+}
@@ -0,0 +1,227 @@
+use crate::database::models::{DateTimeUtc, SyncConflict, SyncState};
+use crate::sync::merge::{SyncedMeeting, SyncedSegment};
+use chrono::Utc;
+use sqlx::{Connection, SqlitePool};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub struct SyncRepository;
+
+impl SyncRepository {
+    pub async fn list_states(pool: &SqlitePool) -> Result<Vec<SyncState>, sqlx::Error> {
+        sqlx::query_as::<_, SyncState>("SELECT * FROM sync_state").fetch_all(pool).await
+    }
+
+    pub async fn save_state(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        base: &str,
+        remote_tag: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO sync_state (meeting_id, base, remote_tag, synced_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT(meeting_id) DO UPDATE SET base = excluded.base, remote_tag = excluded.remote_tag, synced_at = excluded.synced_at",
+        )
+        .bind(meeting_id)
+        .bind(base)
+        .bind(remote_tag)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_meeting_ids(pool: &SqlitePool) -> Result<Vec<String>, sqlx::Error> {
+        let rows: Vec<(String,)> = sqlx::query_as("SELECT id FROM meetings").fetch_all(pool).await?;
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// A meeting as it's synced: its title, notes, transcript segments and completed summary
+    pub async fn load_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<Option<SyncedMeeting>, sqlx::Error> {
+        let meeting: Option<(String, String, Option<String>, DateTimeUtc, DateTimeUtc)> =
+            sqlx::query_as("SELECT id, title, notes, created_at, updated_at FROM meetings WHERE id = ?")
+                .bind(meeting_id)
+                .fetch_optional(pool)
+                .await?;
+        let Some((id, title, notes, created_at, updated_at)) = meeting else {
+            return Ok(None);
+        };
+
+        let rows: Vec<(String, String, String, Option<f64>, Option<f64>, Option<f64>, Option<String>, Option<String>)> =
+            sqlx::query_as(
+                "SELECT id, transcript, timestamp, audio_start_time, audio_end_time, duration, speaker, translation
+                 FROM transcripts WHERE meeting_id = ? ORDER BY audio_start_time, timestamp",
+            )
+            .bind(meeting_id)
+            .fetch_all(pool)
+            .await?;
+        let segments = rows
+            .into_iter()
+            .map(|(id, text, timestamp, start, end, duration, speaker, translation)| SyncedSegment {
+                id,
+                text,
+                timestamp,
+                start,
+                end,
+                duration,
+                speaker,
+                translation,
+            })
+            .collect();
+
+        let summary: Option<(Option<String>,)> =
+            sqlx::query_as("SELECT result FROM summary_processes WHERE meeting_id = ? AND status = 'completed'")
+                .bind(meeting_id)
+                .fetch_optional(pool)
+                .await?;
+
+        Ok(Some(SyncedMeeting {
+            id,
+            title,
+            notes,
+            created_at: created_at.0,
+            updated_at: updated_at.0,
+            deleted: false,
+            segments,
+            summary: summary.and_then(|(result,)| result),
+        }))
+    }
+
+    /// Makes the stored meeting match a merged version, creating it when it came from another
+    /// device. Word timings are kept for segments whose text is unchanged.
+    pub async fn apply(pool: &SqlitePool, meeting: &SyncedMeeting) -> Result<(), sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query(
+            "INSERT INTO meetings (id, title, created_at, updated_at, notes) VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET title = excluded.title, notes = excluded.notes, updated_at = excluded.updated_at",
+        )
+        .bind(&meeting.id)
+        .bind(&meeting.title)
+        .bind(meeting.created_at)
+        .bind(meeting.updated_at)
+        .bind(&meeting.notes)
+        .execute(&mut *transaction)
+        .await?;
+
+        let kept: HashSet<&str> = meeting.segments.iter().map(|segment| segment.id.as_str()).collect();
+        let existing: Vec<(String,)> = sqlx::query_as("SELECT id FROM transcripts WHERE meeting_id = ?")
+            .bind(&meeting.id)
+            .fetch_all(&mut *transaction)
+            .await?;
+        for (id,) in existing.into_iter().filter(|(id,)| !kept.contains(id.as_str())) {
+            sqlx::query("DELETE FROM segment_embeddings WHERE transcript_id = ?")
+                .bind(&id)
+                .execute(&mut *transaction)
+                .await?;
+            sqlx::query("DELETE FROM transcripts WHERE id = ?").bind(&id).execute(&mut *transaction).await?;
+        }
+
+        for segment in &meeting.segments {
+            sqlx::query(
+                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, translation, speaker)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 ON CONFLICT(id) DO UPDATE SET
+                    words = CASE WHEN transcripts.transcript = excluded.transcript THEN transcripts.words ELSE NULL END,
+                    transcript = excluded.transcript,
+                    timestamp = excluded.timestamp,
+                    audio_start_time = excluded.audio_start_time,
+                    audio_end_time = excluded.audio_end_time,
+                    duration = excluded.duration,
+                    translation = excluded.translation,
+                    speaker = excluded.speaker",
+            )
+            .bind(&segment.id)
+            .bind(&meeting.id)
+            .bind(&segment.text)
+            .bind(&segment.timestamp)
+            .bind(segment.start)
+            .bind(segment.end)
+            .bind(segment.duration)
+            .bind(&segment.translation)
+            .bind(&segment.speaker)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        if let Some(summary) = &meeting.summary {
+            let now = Utc::now();
+            sqlx::query(
+                "INSERT INTO summary_processes (meeting_id, status, created_at, updated_at, result) VALUES (?, 'completed', ?, ?, ?)
+                 ON CONFLICT(meeting_id) DO UPDATE SET status = 'completed', result = excluded.result, error = NULL, updated_at = excluded.updated_at",
+            )
+            .bind(&meeting.id)
+            .bind(now)
+            .bind(now)
+            .bind(summary)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await
+    }
+
+    pub async fn record_conflict(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        segment_id: &str,
+        kept_text: &str,
+        other_text: &str,
+        other_device: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO sync_conflicts (id, meeting_id, segment_id, kept_text, other_text, other_device, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(format!("conflict-{}", Uuid::new_v4()))
+        .bind(meeting_id)
+        .bind(segment_id)
+        .bind(kept_text)
+        .bind(other_text)
+        .bind(other_device)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Unresolved conflicts, all meetings' or one meeting's
+    pub async fn list_conflicts(pool: &SqlitePool, meeting_id: Option<&str>) -> Result<Vec<SyncConflict>, sqlx::Error> {
+        sqlx::query_as::<_, SyncConflict>(
+            "SELECT * FROM sync_conflicts WHERE ? IS NULL OR meeting_id = ? ORDER BY created_at DESC",
+        )
+        .bind(meeting_id)
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_conflict(pool: &SqlitePool, id: &str) -> Result<Option<SyncConflict>, sqlx::Error> {
+        sqlx::query_as::<_, SyncConflict>("SELECT * FROM sync_conflicts WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Closes a conflict, setting the segment's text to `text` when given. The meeting's
+    /// `updated_at` moves forward so the choice wins on the other devices too.
+    pub async fn resolve_conflict(pool: &SqlitePool, conflict: &SyncConflict, text: Option<&str>) -> Result<(), sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        if let Some(text) = text {
+            sqlx::query("UPDATE transcripts SET transcript = ?, words = NULL WHERE id = ? AND meeting_id = ?")
+                .bind(text)
+                .bind(&conflict.segment_id)
+                .bind(&conflict.meeting_id)
+                .execute(&mut *transaction)
+                .await?;
+            sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+                .bind(Utc::now())
+                .bind(&conflict.meeting_id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        sqlx::query("DELETE FROM sync_conflicts WHERE id = ?").bind(&conflict.id).execute(&mut *transaction).await?;
+        transaction.commit().await
+    }
+}
@@ -0,0 +1,38 @@
+use crate::database::models::SummaryTemplateRow;
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+pub struct TemplatesRepository;
+
+impl TemplatesRepository {
+    pub async fn list(pool: &SqlitePool) -> Result<Vec<SummaryTemplateRow>, sqlx::Error> {
+        sqlx::query_as::<_, SummaryTemplateRow>("SELECT * FROM summary_templates ORDER BY id")
+            .fetch_all(pool)
+            .await
+    }
+
+    /// `definition` is the template JSON
+    pub async fn save(pool: &SqlitePool, id: &str, definition: &str) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO summary_templates (id, definition, created_at, updated_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET definition = excluded.definition, updated_at = excluded.updated_at",
+        )
+        .bind(id)
+        .bind(definition)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("DELETE FROM summary_templates WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+}
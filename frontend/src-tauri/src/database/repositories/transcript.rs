@@ -1,6 +1,7 @@
 use crate::api::{TranscriptSearchResult, TranscriptSegment};
+use crate::database::models::Transcript;
 use chrono::Utc;
-use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use sqlx::{Connection, Error as SqlxError, SqliteConnection, SqlitePool};
 use tracing::{error, info};
 use uuid::Uuid;
 
@@ -45,22 +46,7 @@ impl TranscriptsRepository {
 
         // 2. Save each transcript segment with audio timing fields
         for segment in transcripts {
-            let transcript_id = format!("transcript-{}", Uuid::new_v4());
-            let result = sqlx::query(
-                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
-                 VALUES (?, ?, ?, ?, ?, ?, ?)"
-            )
-            .bind(&transcript_id)
-            .bind(&meeting_id)
-            .bind(&segment.text)
-            .bind(&segment.timestamp)
-            .bind(segment.audio_start_time)
-            .bind(segment.audio_end_time)
-            .bind(segment.duration)
-            .execute(&mut *transaction)
-            .await;
-
-            if let Err(e) = result {
+            if let Err(e) = Self::insert_segment(&mut transaction, &meeting_id, segment).await {
                 error!(
                     "Failed to save transcript segment for meeting {}: {}",
                     meeting_id, e
@@ -82,6 +68,119 @@ impl TranscriptsRepository {
         Ok(meeting_id)
     }
 
+    /// Adds transcript segments to an existing meeting in one transaction.
+    pub async fn add_segments(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        transcripts: &[TranscriptSegment],
+    ) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        for segment in transcripts {
+            Self::insert_segment(&mut transaction, meeting_id, segment).await?;
+        }
+        transaction.commit().await?;
+        info!("Added {} transcript segments to meeting {}", transcripts.len(), meeting_id);
+        Ok(())
+    }
+
+    /// Inserts one segment with its audio timing fields under a new id.
+    async fn insert_segment(
+        conn: &mut SqliteConnection,
+        meeting_id: &str,
+        segment: &TranscriptSegment,
+    ) -> Result<(), SqlxError> {
+        let transcript_id = format!("transcript-{}", Uuid::new_v4());
+        sqlx::query(
+            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, translation, words, speaker)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&transcript_id)
+        .bind(meeting_id)
+        .bind(&segment.text)
+        .bind(&segment.timestamp)
+        .bind(segment.audio_start_time)
+        .bind(segment.audio_end_time)
+        .bind(segment.duration)
+        .bind(&segment.translation)
+        .bind(
+            (!segment.words.is_empty())
+                .then(|| serde_json::to_string(&segment.words).ok())
+                .flatten(),
+        )
+        .bind(&segment.speaker)
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
+
+    /// All transcript segments of a meeting in recording order.
+    pub async fn list_for_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<Transcript>, SqlxError> {
+        sqlx::query_as::<_, Transcript>(
+            "SELECT * FROM transcripts WHERE meeting_id = ? ORDER BY audio_start_time, timestamp",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Sets the speaker label of each (transcript id, speaker) pair in one transaction.
+    pub async fn set_speakers(pool: &SqlitePool, speakers: &[(String, Option<String>)]) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        for (id, speaker) in speakers {
+            sqlx::query("UPDATE transcripts SET speaker = ? WHERE id = ?")
+                .bind(speaker)
+                .bind(id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Replaces the text of each (transcript id, text) pair of a meeting in one transaction.
+    /// Word timings no longer match the new text, so they're dropped.
+    pub async fn set_texts(pool: &SqlitePool, meeting_id: &str, texts: &[(String, String)]) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        for (id, text) in texts {
+            sqlx::query("UPDATE transcripts SET transcript = ?, words = NULL WHERE id = ? AND meeting_id = ?")
+                .bind(text)
+                .bind(id)
+                .bind(meeting_id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Deletes the segments of a meeting that overlap `start..end` (seconds), with their
+    /// embeddings, in one transaction. Returns how many segments were deleted.
+    pub async fn delete_in_range(pool: &SqlitePool, meeting_id: &str, start: f64, end: f64) -> Result<u64, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        let overlapping = "SELECT id FROM transcripts WHERE meeting_id = ? AND audio_start_time < ? AND audio_end_time > ?";
+        sqlx::query(&format!("DELETE FROM segment_embeddings WHERE transcript_id IN ({})", overlapping))
+            .bind(meeting_id)
+            .bind(end)
+            .bind(start)
+            .execute(&mut *transaction)
+            .await?;
+        let deleted = sqlx::query(
+            "DELETE FROM transcripts WHERE meeting_id = ? AND audio_start_time < ? AND audio_end_time > ?",
+        )
+        .bind(meeting_id)
+        .bind(end)
+        .bind(start)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected();
+        transaction.commit().await?;
+        Ok(deleted)
+    }
+
     /// Searches for a query string within the transcripts.
     /// It returns a list of matching transcripts with context.
     pub async fn search_transcripts(
@@ -1,5 +1,6 @@
 // src/database/repo/transcript_chunks.rs
 
+use crate::database::models::TranscriptChunk;
 use chrono::Utc;
 use log::info as log_info;
 use sqlx::SqlitePool;
@@ -46,4 +47,22 @@ impl TranscriptChunksRepository {
 
         Ok(())
     }
+
+    /// The transcript text and settings last used to summarize a meeting
+    pub async fn get(pool: &SqlitePool, meeting_id: &str) -> Result<Option<TranscriptChunk>, sqlx::Error> {
+        sqlx::query_as::<_, TranscriptChunk>("SELECT * FROM transcript_chunks WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Forgets the transcript text last summarized, so the next summary is built from the
+    /// segments
+    pub async fn delete(pool: &SqlitePool, meeting_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM transcript_chunks WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,67 @@
+use crate::database::models::ProviderUsage;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// A usage row to record; `kind` is "llm" or "stt"
+#[derive(Debug, Clone, Default)]
+pub struct NewProviderUsage<'a> {
+    pub meeting_id: Option<&'a str>,
+    pub kind: &'a str,
+    pub purpose: &'a str,
+    pub provider: &'a str,
+    pub model: &'a str,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub audio_seconds: f64,
+    pub requests: u64,
+    pub estimated: bool,
+}
+
+pub struct UsageRepository;
+
+impl UsageRepository {
+    pub async fn record(pool: &SqlitePool, usage: &NewProviderUsage<'_>) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO provider_usage
+             (id, meeting_id, kind, purpose, provider, model, prompt_tokens, completion_tokens, audio_seconds, requests, estimated, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(format!("usage-{}", Uuid::new_v4()))
+        .bind(usage.meeting_id)
+        .bind(usage.kind)
+        .bind(usage.purpose)
+        .bind(usage.provider)
+        .bind(usage.model)
+        .bind(usage.prompt_tokens as i64)
+        .bind(usage.completion_tokens as i64)
+        .bind(usage.audio_seconds)
+        .bind(usage.requests as i64)
+        .bind(usage.estimated)
+        .bind(Utc::now())
+        .execute(pool)
+        .await
+        .map(|_| ())
+    }
+
+    /// Usage newest first, for one meeting or all, optionally only since a time
+    pub async fn list(
+        pool: &SqlitePool,
+        meeting_id: Option<&str>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<ProviderUsage>, sqlx::Error> {
+        sqlx::query_as::<_, ProviderUsage>(
+            "SELECT u.*, m.title AS meeting_title
+             FROM provider_usage u
+             LEFT JOIN meetings m ON m.id = u.meeting_id
+             WHERE (? IS NULL OR u.meeting_id = ?) AND (? IS NULL OR u.created_at >= ?)
+             ORDER BY u.created_at DESC",
+        )
+        .bind(meeting_id)
+        .bind(meeting_id)
+        .bind(since)
+        .bind(since)
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,180 @@
+use crate::database::models::{Webhook, WebhookDelivery};
+use chrono::Utc;
+use sqlx::{Connection, SqlitePool};
+use uuid::Uuid;
+
+pub struct WebhooksRepository;
+
+impl WebhooksRepository {
+    pub async fn list(pool: &SqlitePool) -> Result<Vec<Webhook>, sqlx::Error> {
+        sqlx::query_as::<_, Webhook>("SELECT * FROM webhooks ORDER BY created_at")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<Webhook>, sqlx::Error> {
+        sqlx::query_as::<_, Webhook>("SELECT * FROM webhooks WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Enabled webhooks that receive `event`
+    pub async fn list_subscribed(pool: &SqlitePool, event: &str) -> Result<Vec<Webhook>, sqlx::Error> {
+        sqlx::query_as::<_, Webhook>(
+            "SELECT * FROM webhooks
+             WHERE enabled = 1 AND EXISTS (SELECT 1 FROM json_each(webhooks.events) WHERE value = ?)",
+        )
+        .bind(event)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, url: &str, events: &[String]) -> Result<Webhook, sqlx::Error> {
+        let id = format!("webhook-{}", Uuid::new_v4());
+        let now = Utc::now();
+        sqlx::query("INSERT INTO webhooks (id, url, events, enabled, created_at, updated_at) VALUES (?, ?, ?, 1, ?, ?)")
+            .bind(&id)
+            .bind(url)
+            .bind(serde_json::to_string(events).unwrap_or_else(|_| "[]".to_string()))
+            .bind(now)
+            .bind(now)
+            .execute(pool)
+            .await?;
+        Self::get(pool, &id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: &str,
+        url: &str,
+        events: &[String],
+        enabled: bool,
+    ) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("UPDATE webhooks SET url = ?, events = ?, enabled = ?, updated_at = ? WHERE id = ?")
+            .bind(url)
+            .bind(serde_json::to_string(events).unwrap_or_else(|_| "[]".to_string()))
+            .bind(enabled)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+        sqlx::query("DELETE FROM webhook_deliveries WHERE webhook_id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+        let rows = sqlx::query("DELETE FROM webhooks WHERE id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected();
+        transaction.commit().await?;
+        Ok(rows > 0)
+    }
+
+    pub async fn create_delivery(
+        pool: &SqlitePool,
+        id: &str,
+        webhook_id: &str,
+        event: &str,
+        payload: &str,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO webhook_deliveries (id, webhook_id, event, payload, status, attempts, created_at, updated_at)
+             VALUES (?, ?, ?, ?, 'pending', 0, ?, ?)",
+        )
+        .bind(id)
+        .bind(webhook_id)
+        .bind(event)
+        .bind(payload)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_delivery(pool: &SqlitePool, id: &str) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>("SELECT * FROM webhook_deliveries WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Latest deliveries first, of one webhook or of all
+    pub async fn list_deliveries(
+        pool: &SqlitePool,
+        webhook_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>(
+            "SELECT * FROM webhook_deliveries WHERE (? IS NULL OR webhook_id = ?) ORDER BY created_at DESC LIMIT ?",
+        )
+        .bind(webhook_id)
+        .bind(webhook_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Records the outcome of an attempt; `status` stays `pending` while retries remain
+    pub async fn record_attempt(
+        pool: &SqlitePool,
+        id: &str,
+        status: &str,
+        response_status: Option<i64>,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE webhook_deliveries SET status = ?, attempts = attempts + 1, response_status = ?, error = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(status)
+        .bind(response_status)
+        .bind(error)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Puts a delivery back in the pending state for another round of attempts
+    pub async fn reset_delivery(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let rows = sqlx::query("UPDATE webhook_deliveries SET status = 'pending', attempts = 0, updated_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+        Ok(rows > 0)
+    }
+
+    /// Deliveries a previous session stopped retrying when the app quit
+    pub async fn list_pending(pool: &SqlitePool) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>("SELECT * FROM webhook_deliveries WHERE status = 'pending' ORDER BY created_at")
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Keeps the newest `keep` deliveries
+    pub async fn prune_deliveries(pool: &SqlitePool, keep: i64) -> Result<u64, sqlx::Error> {
+        let rows = sqlx::query(
+            "DELETE FROM webhook_deliveries WHERE id NOT IN
+             (SELECT id FROM webhook_deliveries ORDER BY created_at DESC LIMIT ?)",
+        )
+        .bind(keep)
+        .execute(pool)
+        .await?
+        .rows_affected();
+        Ok(rows)
+    }
+}
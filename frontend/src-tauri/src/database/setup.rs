@@ -32,6 +32,15 @@ pub async fn initialize_database_on_startup(app: &AppHandle) -> Result<(), Strin
 
         app.manage(AppState { db_manager });
         info!("Database initialized successfully");
+
+        // Only now that the moved database has opened is its old copy no longer needed
+        crate::workspaces::storage::delete_old_copy(app);
+
+        // Files of meetings deleted just before the app last closed
+        let pool = app.state::<AppState>().db_manager.pool().clone();
+        tauri::async_runtime::spawn(async move {
+            crate::library::deletion::finish_pending(&pool).await;
+        });
     }
 
     Ok(())
@@ -0,0 +1,145 @@
+// diagnostics/commands.rs
+//
+// Builds the diagnostics bundle users attach to bug reports: a zip of the app and OS versions,
+// the audio pipeline configuration, the audio devices, the models in use, the permission
+// states, and the recent logs and errors. Nothing is collected without the user's consent, and
+// every file is scrubbed of personal data first. API keys are never read.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use log::{info, warn};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sysinfo::System;
+use tauri::{AppHandle, Manager, Runtime};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::log_capture;
+use super::scrub::Scrubber;
+use crate::audio::pipeline_stats;
+use crate::audio::devices::metadata::list_audio_devices_detailed;
+use crate::audio::permissions::PermissionState;
+use crate::audio::recording_preferences::load_recording_preferences;
+use crate::audio::session;
+use crate::database::repositories::setting::SettingsRepository;
+use crate::state::AppState;
+
+fn pretty<T: Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|e| format!("Failed to serialize: {}", e))
+}
+
+fn system_info<R: Runtime>(app: &AppHandle<R>) -> Value {
+    let mut system = System::new();
+    system.refresh_memory();
+    json!({
+        "app_version": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "os_version": System::long_os_version(),
+        "kernel_version": System::kernel_version(),
+        "cpu_count": std::thread::available_parallelism().map(|n| n.get()).ok(),
+        "total_memory_mb": system.total_memory() / (1024 * 1024),
+        "generated_at": Local::now().to_rfc3339(),
+    })
+}
+
+async fn audio_info<R: Runtime>(app: &AppHandle<R>) -> Value {
+    let preferences = match load_recording_preferences(app).await {
+        Ok(preferences) => serde_json::to_value(preferences).unwrap_or(Value::Null),
+        Err(e) => json!({ "error": format!("Failed to load preferences: {}", e) }),
+    };
+    json!({
+        "recording_preferences": preferences,
+        "session": session::current(),
+        "pipeline": pipeline_stats::current(),
+    })
+}
+
+async fn devices_info() -> Value {
+    match list_audio_devices_detailed().await {
+        Ok(devices) => serde_json::to_value(devices).unwrap_or(Value::Null),
+        Err(e) => json!({ "error": format!("Failed to list audio devices: {}", e) }),
+    }
+}
+
+/// Providers and model names only; the settings rows also hold API keys
+async fn models_info(state: &AppState) -> Value {
+    let pool = state.db_manager.pool();
+    let summary = match SettingsRepository::get_model_config(pool).await {
+        Ok(Some(setting)) => json!({ "provider": setting.provider, "model": setting.model, "whisper_model": setting.whisper_model }),
+        Ok(None) => Value::Null,
+        Err(e) => json!({ "error": format!("Failed to load model config: {}", e) }),
+    };
+    let transcript = match SettingsRepository::get_transcript_config(pool).await {
+        Ok(Some(setting)) => json!({ "provider": setting.provider, "model": setting.model }),
+        Ok(None) => Value::Null,
+        Err(e) => json!({ "error": format!("Failed to load transcript config: {}", e) }),
+    };
+    json!({
+        "summary": summary,
+        "transcription": transcript,
+        "loaded_whisper_model": crate::whisper_engine::commands::whisper_get_current_model().await.ok().flatten(),
+        "loaded_parakeet_model": crate::parakeet_engine::commands::parakeet_get_current_model().await.ok().flatten(),
+    })
+}
+
+fn write_bundle(path: &Path, files: &[(&str, String)]) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("Failed to create diagnostics bundle: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, contents) in files {
+        zip.start_file(*name, options)
+            .map_err(|e| format!("Failed to add {} to diagnostics bundle: {}", name, e))?;
+        zip.write_all(contents.as_bytes())
+            .map_err(|e| format!("Failed to add {} to diagnostics bundle: {}", name, e))?;
+    }
+    zip.finish().map_err(|e| format!("Failed to write diagnostics bundle: {}", e))?;
+    Ok(())
+}
+
+/// Writes a diagnostics zip to the app data folder and returns its path.
+///
+/// `consent` must be true: the user has to agree to the collection each time.
+#[tauri::command]
+pub async fn generate_diagnostics<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    consent: bool,
+) -> Result<String, String> {
+    if !consent {
+        return Err("Diagnostics are only collected with your consent".to_string());
+    }
+
+    let scrubber = Scrubber::for_current_user();
+    let files = [
+        ("system.json", pretty(&system_info(&app))),
+        ("audio.json", pretty(&audio_info(&app).await)),
+        ("devices.json", pretty(&devices_info().await)),
+        ("models.json", pretty(&models_info(&state).await)),
+        ("permissions.json", pretty(&PermissionState::current())),
+        ("logs.txt", log_capture::recent_lines().join("\n")),
+        ("errors.txt", log_capture::recent_problems().join("\n")),
+    ]
+    .map(|(name, contents)| (name, scrubber.scrub(&contents)));
+
+    let folder: PathBuf = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("diagnostics");
+    std::fs::create_dir_all(&folder).map_err(|e| format!("Failed to create diagnostics folder: {}", e))?;
+    let path = folder.join(format!("meetily-diagnostics-{}.zip", Local::now().format("%Y%m%d-%H%M%S")));
+
+    if let Err(e) = write_bundle(&path, &files) {
+        warn!("{}", e);
+        let _ = std::fs::remove_file(&path);
+        return Err(e);
+    }
+
+    info!("🩺 Diagnostics bundle written to {:?}", path);
+    Ok(path.to_string_lossy().into_owned())
+}
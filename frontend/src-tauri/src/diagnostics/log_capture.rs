@@ -0,0 +1,87 @@
+// diagnostics/log_capture.rs
+//
+// The app logs to stderr only, which users can't get at after a problem. This logger wraps
+// env_logger and also keeps the latest lines in memory, with the recent warnings and errors
+// kept apart so a busy log doesn't push them out before a diagnostics bundle is made.
+
+use chrono::Local;
+use log::{Level, Log, Metadata, Record};
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+const MAX_LINES: usize = 5000;
+const MAX_PROBLEMS: usize = 200;
+
+struct Buffer {
+    lines: VecDeque<String>,
+    problems: VecDeque<String>,
+}
+
+static BUFFER: Lazy<Mutex<Buffer>> = Lazy::new(|| {
+    Mutex::new(Buffer {
+        lines: VecDeque::with_capacity(MAX_LINES),
+        problems: VecDeque::with_capacity(MAX_PROBLEMS),
+    })
+});
+
+fn push(queue: &mut VecDeque<String>, line: String, max: usize) {
+    if queue.len() == max {
+        queue.pop_front();
+    }
+    queue.push_back(line);
+}
+
+struct CapturingLogger {
+    inner: env_logger::Logger,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.inner.matches(record) {
+            return;
+        }
+        self.inner.log(record);
+
+        let line = format!(
+            "{} {:<5} {}: {}",
+            Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        if let Ok(mut buffer) = BUFFER.lock() {
+            if record.level() <= Level::Warn {
+                push(&mut buffer.problems, line.clone(), MAX_PROBLEMS);
+            }
+            push(&mut buffer.lines, line, MAX_LINES);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Sets up logging to stderr, filtered by `RUST_LOG`, with the recent lines kept for diagnostics
+pub fn init() {
+    let inner = env_logger::Builder::from_default_env().build();
+    let max_level = inner.filter();
+    if log::set_boxed_logger(Box::new(CapturingLogger { inner })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// The latest log lines, oldest first
+pub fn recent_lines() -> Vec<String> {
+    BUFFER.lock().map(|buffer| buffer.lines.iter().cloned().collect()).unwrap_or_default()
+}
+
+/// The latest warnings and errors, oldest first
+pub fn recent_problems() -> Vec<String> {
+    BUFFER.lock().map(|buffer| buffer.problems.iter().cloned().collect()).unwrap_or_default()
+}
@@ -0,0 +1,14 @@
+/// Diagnostics module - bundles for bug reports
+///
+/// Logs go to stderr, where users can't find them; the app keeps the latest lines in memory
+/// instead, and `generate_diagnostics` zips them together with the audio, device, model and
+/// permission state once the user agrees.
+///
+/// This module contains:
+/// - The logger that keeps recent lines and errors (`log_capture`)
+/// - Removal of personal data from the bundle (`scrub`)
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+pub mod log_capture;
+pub mod scrub;
@@ -0,0 +1,101 @@
+// diagnostics/scrub.rs
+//
+// Removes personal data from everything that goes into a diagnostics bundle: the home folder
+// and user name in paths, email addresses, and API keys or tokens that ended up in a log line.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static EMAIL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").expect("Invalid regex pattern"));
+
+static BEARER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bbearer\s+[A-Za-z0-9._~+/=-]+").expect("Invalid regex pattern"));
+
+/// `api_key=...`, `"token": "..."` and the like
+static SECRET_FIELD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\b((?:api[_-]?key|access[_-]?token|token|secret|password)["']?\s*[:=]\s*["']?)[^\s"',&}]+"#)
+        .expect("Invalid regex pattern")
+});
+
+/// Provider keys recognizable on their own (OpenAI/Anthropic, Groq, OpenRouter)
+static PROVIDER_KEY: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:sk|gsk)[-_][A-Za-z0-9_-]{16,}").expect("Invalid regex pattern"));
+
+pub struct Scrubber {
+    home: Option<String>,
+    user: Option<Regex>,
+}
+
+impl Scrubber {
+    pub fn new(home: Option<String>, user: Option<String>) -> Self {
+        Self {
+            home: home.filter(|home| home.len() > 1),
+            // Short names like "me" would match inside ordinary words
+            user: user
+                .filter(|user| user.chars().count() >= 3)
+                .and_then(|user| Regex::new(&format!(r"(?i)\b{}\b", regex::escape(&user))).ok()),
+        }
+    }
+
+    /// For the current user
+    pub fn for_current_user() -> Self {
+        let home = dirs::home_dir().map(|home| home.to_string_lossy().into_owned());
+        let user = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok();
+        Self::new(home, user)
+    }
+
+    pub fn scrub(&self, text: &str) -> String {
+        let mut text = match &self.home {
+            Some(home) => text.replace(home.as_str(), "~"),
+            None => text.to_string(),
+        };
+        text = EMAIL.replace_all(&text, "<email>").into_owned();
+        text = BEARER.replace_all(&text, "Bearer <redacted>").into_owned();
+        text = SECRET_FIELD.replace_all(&text, "${1}<redacted>").into_owned();
+        text = PROVIDER_KEY.replace_all(&text, "<redacted>").into_owned();
+        if let Some(user) = &self.user {
+            text = user.replace_all(&text, "<user>").into_owned();
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scrubber() -> Scrubber {
+        Scrubber::new(Some("/home/alice".to_string()), Some("alice".to_string()))
+    }
+
+    #[test]
+    fn test_scrub_paths_and_user() {
+        let scrubber = scrubber();
+        assert_eq!(
+            scrubber.scrub("Saving to /home/alice/meetily-recordings/Standup"),
+            "Saving to ~/meetily-recordings/Standup"
+        );
+        assert_eq!(scrubber.scrub("Device: Alice's AirPods"), "Device: <user>'s AirPods");
+        assert_eq!(scrubber.scrub("Malice in the logs"), "Malice in the logs");
+    }
+
+    #[test]
+    fn test_scrub_secrets() {
+        let scrubber = scrubber();
+        assert_eq!(scrubber.scrub("Invite sent to bob.smith@example.com"), "Invite sent to <email>");
+        assert_eq!(
+            scrubber.scrub("Authorization: Bearer eyJhbGciOi.abc-123"),
+            "Authorization: Bearer <redacted>"
+        );
+        assert_eq!(scrubber.scrub(r#"{"api_key": "abc123", "model": "x"}"#), r#"{"api_key": "<redacted>", "model": "x"}"#);
+        assert_eq!(scrubber.scrub("GET /v1?token=xyz&lang=en"), "GET /v1?token=<redacted>&lang=en");
+        assert_eq!(scrubber.scrub("key sk-proj-abcdefghijklmnopqrstuv"), "key <redacted>");
+    }
+
+    #[test]
+    fn test_short_user_name_kept() {
+        let scrubber = Scrubber::new(None, Some("me".to_string()));
+        assert_eq!(scrubber.scrub("meeting with me"), "meeting with me");
+    }
+}
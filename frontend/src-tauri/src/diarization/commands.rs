@@ -0,0 +1,60 @@
+use super::embedding;
+use crate::database::models::Job;
+use crate::jobs::{queue, JobPayload, JobPriority};
+use crate::state::AppState;
+use log::info;
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+pub const MODEL_DOWNLOAD_PROGRESS_EVENT: &str = "diarization-model-download-progress";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiarizationModelStatus {
+    pub downloaded: bool,
+    pub path: String,
+}
+
+/// Same models directory the transcription engines use
+pub fn models_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("models"))
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_diarization_model_status<R: Runtime>(app: AppHandle<R>) -> Result<DiarizationModelStatus, String> {
+    let path = embedding::model_path(&models_dir(&app)?);
+    Ok(DiarizationModelStatus { downloaded: path.exists(), path: path.to_string_lossy().to_string() })
+}
+
+#[tauri::command]
+pub async fn download_diarization_model<R: Runtime>(app: AppHandle<R>) -> Result<DiarizationModelStatus, String> {
+    let models_dir = models_dir(&app)?;
+    let progress_app = app.clone();
+    let path = embedding::download_model(&models_dir, move |progress| {
+        let _ = progress_app.emit(MODEL_DOWNLOAD_PROGRESS_EVENT, serde_json::json!({ "progress": progress }));
+    })
+    .await
+    .map_err(|e| format!("Failed to download speaker model: {}", e))?;
+    Ok(DiarizationModelStatus { downloaded: true, path: path.to_string_lossy().to_string() })
+}
+
+/// Queues speaker diarization of a saved meeting; the transcript is labelled when it completes
+#[tauri::command]
+pub async fn diarize_meeting<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Job, String> {
+    if !embedding::model_path(&models_dir(&app)?).exists() {
+        return Err("Speaker model not downloaded. Download it before identifying speakers.".to_string());
+    }
+
+    info!("diarize_meeting called for {}", meeting_id);
+    let pool = state.db_manager.pool().clone();
+    queue::enqueue(&app, &pool, JobPayload::Diarization { meeting_id }, JobPriority::Normal)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,28 @@
+/// Diarization module - who spoke when
+///
+/// A saved meeting's recording is split into short speech windows, each window is turned
+/// into a voice embedding with a WeSpeaker ONNX model, and the embeddings are clustered
+/// into speakers. Transcript segments then take the speaker they overlap most and are
+/// labelled "Speaker 1", "Speaker 2", ... in order of first appearance. Diarization runs
+/// as a background job once the model has been downloaded.
+///
+/// Split-stereo recordings keep the microphone on its own channel: everything said there
+/// is labelled "Me" and only the system-audio channel is clustered.
+///
+/// Naming a meeting's speaker enrolls their voice in a speaker profile; later meetings
+/// label speakers whose voice matches a profile with its name instead.
+///
+/// This module contains:
+/// - Filterbank features (`features`, in `meeting_core`)
+/// - Speaker embedding model and its download (`embedding`, in `meeting_core`)
+/// - Agglomerative clustering of embeddings (`clustering`, in `meeting_core`)
+/// - Turn detection and transcript labelling (`pipeline`)
+/// - Speaker profiles and voice recognition across meetings (`profiles`)
+/// - Tauri commands for frontend integration
+
+pub use meeting_core::diarization::{clustering, embedding, features};
+pub mod commands;
+pub mod pipeline;
+pub mod profiles;
+
+pub use pipeline::{assign_speakers, speaker_label, Diarization, SpeakerTurn, MEETING_DIARIZED_EVENT};
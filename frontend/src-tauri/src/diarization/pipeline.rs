@@ -0,0 +1,350 @@
+use super::clustering::{centroid, cluster_embeddings};
+use super::embedding::{model_path, SpeakerEmbedder};
+use super::features::SAMPLE_RATE;
+use super::profiles::{embedding_json, parse_embedding, recognize};
+use crate::audio::channel_mix::ChannelMixPolicy;
+use crate::audio::ffmpeg::{decode_channel_to_mono, decode_to_mono};
+use crate::audio::playback::find_recording;
+use crate::audio::recording_saver::MeetingMetadata;
+use crate::audio::vad::get_speech_chunks;
+use crate::database::models::MeetingSpeaker;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::speaker::SpeakerProfilesRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::jobs::JobContext;
+use anyhow::{anyhow, Result};
+use log::info;
+use serde::Serialize;
+use std::path::Path;
+use tauri::{Emitter, Runtime};
+
+pub const MEETING_DIARIZED_EVENT: &str = "meeting-diarized";
+
+/// Windows are short enough to rarely span a change of speaker
+const WINDOW_SECONDS: f64 = 3.0;
+/// Shorter speech gives unreliable embeddings and is left to the neighbouring turns
+const MIN_WINDOW_SECONDS: f64 = 0.5;
+/// Average cosine similarity above which windows are considered the same voice
+pub const SAME_SPEAKER_THRESHOLD: f32 = 0.5;
+/// Same-speaker turns closer than this are merged
+const MERGE_GAP_SECONDS: f64 = 0.5;
+const VAD_REDEMPTION_MS: u32 = 400;
+/// Label of the microphone speaker in split-stereo recordings
+pub const LOCAL_SPEAKER_LABEL: &str = "Me";
+/// Microphone windows averaged into the local speaker's voice embedding
+const LOCAL_EMBEDDING_WINDOWS: usize = 10;
+
+/// A stretch of the recording attributed to one speaker (seconds from recording start)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpeakerTurn {
+    pub speaker: usize,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Diarization {
+    pub turns: Vec<SpeakerTurn>,
+    /// Average voice embedding per speaker, indexed like `SpeakerTurn::speaker`
+    pub centroids: Vec<Vec<f32>>,
+    /// Display label per speaker, indexed like `SpeakerTurn::speaker`
+    pub labels: Vec<String>,
+}
+
+/// Display label for a speaker index
+pub fn speaker_label(speaker: usize) -> String {
+    format!("Speaker {}", speaker + 1)
+}
+
+/// Cut speech ranges into windows of at most `WINDOW_SECONDS`, dropping very short ones
+fn speech_windows(ranges: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut windows = Vec::new();
+    for &(start, end) in ranges {
+        let mut window_start = start;
+        while end - window_start >= MIN_WINDOW_SECONDS {
+            let window_end = (window_start + WINDOW_SECONDS).min(end);
+            // Fold a short tail into the last window instead of dropping it
+            let window_end = if end - window_end < MIN_WINDOW_SECONDS { end } else { window_end };
+            windows.push((window_start, window_end));
+            window_start = window_end;
+        }
+    }
+    windows
+}
+
+fn merge_turns(mut turns: Vec<SpeakerTurn>) -> Vec<SpeakerTurn> {
+    turns.sort_by(|a, b| a.start.total_cmp(&b.start));
+    let mut merged: Vec<SpeakerTurn> = Vec::new();
+    for turn in turns {
+        match merged.last_mut() {
+            Some(last) if last.speaker == turn.speaker && turn.start - last.end <= MERGE_GAP_SECONDS => {
+                last.end = last.end.max(turn.end);
+            }
+            _ => merged.push(turn),
+        }
+    }
+    merged
+}
+
+fn speech_ranges(samples: &[f32]) -> Result<Vec<(f64, f64)>> {
+    Ok(get_speech_chunks(samples, VAD_REDEMPTION_MS)?
+        .iter()
+        .map(|segment| (segment.start_timestamp_ms / 1000.0, segment.end_timestamp_ms / 1000.0))
+        .collect())
+}
+
+fn window_samples(samples: &[f32], start: f64, end: f64) -> &[f32] {
+    let from = ((start * SAMPLE_RATE as f64) as usize).min(samples.len());
+    let to = ((end * SAMPLE_RATE as f64) as usize).min(samples.len());
+    &samples[from..to]
+}
+
+/// Speaker turns of 16kHz mono audio
+pub fn diarize(embedder: &mut SpeakerEmbedder, samples: &[f32], threshold: f32) -> Result<Diarization> {
+    let speech = speech_ranges(samples)?;
+
+    let mut windows = Vec::new();
+    let mut embeddings = Vec::new();
+    for (start, end) in speech_windows(&speech) {
+        if let Some(embedding) = embedder.embed(window_samples(samples, start, end))? {
+            windows.push((start, end));
+            embeddings.push(embedding);
+        }
+    }
+
+    let labels = cluster_embeddings(&embeddings, threshold);
+    let speakers = labels.iter().max().map_or(0, |max| max + 1);
+    let centroids: Vec<Vec<f32>> = (0..speakers)
+        .map(|speaker| {
+            let members: Vec<&[f32]> = labels
+                .iter()
+                .zip(&embeddings)
+                .filter(|(label, _)| **label == speaker)
+                .map(|(_, embedding)| embedding.as_slice())
+                .collect();
+            centroid(&members)
+        })
+        .collect();
+    let turns = windows
+        .into_iter()
+        .zip(labels)
+        .map(|((start, end), speaker)| SpeakerTurn { speaker, start, end })
+        .collect();
+
+    let speaker_labels = (0..centroids.len()).map(speaker_label).collect();
+
+    Ok(Diarization { turns: merge_turns(turns), centroids, labels: speaker_labels })
+}
+
+/// Puts the local user in front of the remote speakers as speaker 0
+fn with_local_speaker(remote: Diarization, local_speech: &[(f64, f64)], local_centroid: Vec<f32>) -> Diarization {
+    let mut turns: Vec<SpeakerTurn> = local_speech
+        .iter()
+        .map(|&(start, end)| SpeakerTurn { speaker: 0, start, end })
+        .collect();
+    turns.extend(remote.turns.into_iter().map(|turn| SpeakerTurn { speaker: turn.speaker + 1, ..turn }));
+
+    let mut centroids = vec![local_centroid];
+    centroids.extend(remote.centroids);
+    let mut labels = vec![LOCAL_SPEAKER_LABEL.to_string()];
+    labels.extend(remote.labels);
+
+    Diarization { turns: merge_turns(turns), centroids, labels }
+}
+
+/// Speaker turns of a split-stereo recording (16kHz mono per channel)
+///
+/// Everything on the microphone channel is the local user; only the system-audio
+/// channel, where the remote participants are, is clustered.
+pub fn diarize_split_channels(
+    embedder: &mut SpeakerEmbedder,
+    microphone: &[f32],
+    system: &[f32],
+    threshold: f32,
+) -> Result<Diarization> {
+    let remote = diarize(embedder, system, threshold)?;
+    let local_speech = speech_ranges(microphone)?;
+    if local_speech.is_empty() {
+        return Ok(remote);
+    }
+
+    // A handful of windows spread over the meeting is enough for a voice embedding
+    let windows = speech_windows(&local_speech);
+    let step = (windows.len() / LOCAL_EMBEDDING_WINDOWS).max(1);
+    let mut embeddings = Vec::new();
+    for &(start, end) in windows.iter().step_by(step).take(LOCAL_EMBEDDING_WINDOWS) {
+        if let Some(embedding) = embedder.embed(window_samples(microphone, start, end))? {
+            embeddings.push(embedding);
+        }
+    }
+    let members: Vec<&[f32]> = embeddings.iter().map(Vec::as_slice).collect();
+
+    Ok(with_local_speaker(remote, &local_speech, centroid(&members)))
+}
+
+/// Layout of the meeting's recording, from the metadata.json written next to it
+fn recording_layout(folder: &Path) -> Option<ChannelMixPolicy> {
+    let content = std::fs::read_to_string(folder.join("metadata.json")).ok()?;
+    serde_json::from_str::<MeetingMetadata>(&content).ok()?.channel_layout
+}
+
+/// Speaker with the most overlap for each (start, end) range; None where nobody spoke
+pub fn assign_speakers(turns: &[SpeakerTurn], ranges: &[(f64, f64)]) -> Vec<Option<usize>> {
+    ranges
+        .iter()
+        .map(|&(start, end)| {
+            let mut overlap_by_speaker: Vec<(usize, f64)> = Vec::new();
+            for turn in turns {
+                let overlap = end.min(turn.end) - start.max(turn.start);
+                if overlap <= 0.0 {
+                    continue;
+                }
+                match overlap_by_speaker.iter_mut().find(|(speaker, _)| *speaker == turn.speaker) {
+                    Some((_, total)) => *total += overlap,
+                    None => overlap_by_speaker.push((turn.speaker, overlap)),
+                }
+            }
+            overlap_by_speaker
+                .into_iter()
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(speaker, _)| speaker)
+        })
+        .collect()
+}
+
+/// Diarize a saved meeting and label its transcript segments
+///
+/// Speakers whose voice matches an enrolled profile are labelled with the profile's name.
+pub async fn diarize_meeting<R: Runtime>(
+    context: &JobContext<R>,
+    models_dir: &Path,
+    meeting_id: &str,
+) -> Result<serde_json::Value> {
+    let folder = MeetingsRepository::get_folder_path(&context.pool, meeting_id)
+        .await?
+        .ok_or_else(|| anyhow!("Meeting {} has no recording folder", meeting_id))?;
+    let recording = find_recording(Path::new(&folder)).ok_or_else(|| anyhow!("No recording found in {}", folder))?;
+    let split_channels = recording_layout(Path::new(&folder)) == Some(ChannelMixPolicy::SplitStereo);
+
+    context.progress(0.0, "Decoding audio").await;
+    let channels = if split_channels {
+        info!("🗣️ Split-stereo recording: microphone is {}, diarizing system audio only", LOCAL_SPEAKER_LABEL);
+        let microphone = decode_channel_to_mono(&recording, SAMPLE_RATE as u32, 0).await?;
+        let system = decode_channel_to_mono(&recording, SAMPLE_RATE as u32, 1).await?;
+        (system, Some(microphone))
+    } else {
+        (decode_to_mono(&recording, SAMPLE_RATE as u32).await?, None)
+    };
+
+    context.progress(0.1, "Identifying speakers").await;
+    let model = model_path(models_dir);
+    let diarization = tokio::task::spawn_blocking(move || {
+        let mut embedder = SpeakerEmbedder::load(&model)?;
+        match channels {
+            (system, Some(microphone)) => {
+                diarize_split_channels(&mut embedder, &microphone, &system, SAME_SPEAKER_THRESHOLD)
+            }
+            (samples, None) => diarize(&mut embedder, &samples, SAME_SPEAKER_THRESHOLD),
+        }
+    })
+    .await??;
+
+    context.progress(0.9, "Labelling transcript").await;
+    let profiles = SpeakerProfilesRepository::list(&context.pool).await?;
+    let profile_embeddings: Vec<Vec<f32>> = profiles.iter().map(|p| parse_embedding(&p.embedding)).collect();
+    let recognized = recognize(&diarization.centroids, &profile_embeddings);
+    let names: Vec<String> = recognized
+        .iter()
+        .enumerate()
+        .map(|(speaker, profile)| profile.map_or_else(|| diarization.labels[speaker].clone(), |p| profiles[p].name.clone()))
+        .collect();
+    let meeting_speakers: Vec<MeetingSpeaker> = diarization
+        .centroids
+        .iter()
+        .zip(&recognized)
+        .enumerate()
+        .map(|(speaker, (centroid, profile))| MeetingSpeaker {
+            meeting_id: meeting_id.to_string(),
+            label: diarization.labels[speaker].clone(),
+            embedding: embedding_json(centroid),
+            profile_id: profile.map(|p| profiles[p].id.clone()),
+        })
+        .collect();
+    SpeakerProfilesRepository::replace_meeting_speakers(&context.pool, meeting_id, &meeting_speakers).await?;
+
+    let transcripts = TranscriptsRepository::list_for_meeting(&context.pool, meeting_id).await?;
+    let ranges: Vec<(f64, f64)> = transcripts
+        .iter()
+        .map(|t| {
+            let start = t.audio_start_time.unwrap_or(0.0);
+            (start, t.audio_end_time.unwrap_or(start))
+        })
+        .collect();
+    let labels: Vec<(String, Option<String>)> = transcripts
+        .iter()
+        .zip(assign_speakers(&diarization.turns, &ranges))
+        .map(|(transcript, speaker)| (transcript.id.clone(), speaker.map(|s| names[s].clone())))
+        .collect();
+    let labelled = labels.iter().filter(|(_, speaker)| speaker.is_some()).count();
+    TranscriptsRepository::set_speakers(&context.pool, &labels).await?;
+
+    let speakers = diarization.centroids.len();
+    let recognized_count = recognized.iter().filter(|p| p.is_some()).count();
+    info!(
+        "🗣️ Diarized meeting {}: {} speaker(s) ({} recognized), {} of {} segments labelled",
+        meeting_id, speakers, recognized_count, labelled, labels.len()
+    );
+    let result = serde_json::json!({
+        "meeting_id": meeting_id,
+        "speakers": speakers,
+        "split_channels": split_channels,
+        "names": names,
+        "labelled_segments": labelled,
+        "turns": diarization.turns,
+    });
+    let _ = context.app.emit(MEETING_DIARIZED_EVENT, &result);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(speaker: usize, start: f64, end: f64) -> SpeakerTurn {
+        SpeakerTurn { speaker, start, end }
+    }
+
+    #[test]
+    fn test_windows_split_long_speech_and_drop_blips() {
+        assert_eq!(speech_windows(&[(0.0, 7.2)]), vec![(0.0, 3.0), (3.0, 6.0), (6.0, 7.2)]);
+        assert_eq!(speech_windows(&[(0.0, 6.3)]), vec![(0.0, 3.0), (3.0, 6.3)]);
+        assert!(speech_windows(&[(10.0, 10.3)]).is_empty());
+    }
+
+    #[test]
+    fn test_segments_take_speaker_with_most_overlap() {
+        let turns = merge_turns(vec![turn(0, 0.0, 3.0), turn(0, 3.2, 5.0), turn(1, 5.0, 9.0)]);
+        assert_eq!(turns, vec![turn(0, 0.0, 5.0), turn(1, 5.0, 9.0)]);
+
+        let speakers = assign_speakers(&turns, &[(0.5, 2.0), (4.0, 8.0), (20.0, 21.0)]);
+        assert_eq!(speakers, vec![Some(0), Some(1), None]);
+        assert_eq!(speaker_label(1), "Speaker 2");
+    }
+
+    #[test]
+    fn test_local_speaker_comes_first_in_split_recordings() {
+        let remote = Diarization {
+            turns: vec![turn(0, 2.0, 4.0), turn(1, 6.0, 8.0)],
+            centroids: vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+            labels: vec![speaker_label(0), speaker_label(1)],
+        };
+        let diarization = with_local_speaker(remote, &[(0.0, 1.5), (4.2, 5.5)], vec![0.5, 0.5]);
+
+        assert_eq!(diarization.labels, vec!["Me", "Speaker 1", "Speaker 2"]);
+        assert_eq!(diarization.centroids[0], vec![0.5, 0.5]);
+        assert_eq!(
+            diarization.turns,
+            vec![turn(0, 0.0, 1.5), turn(1, 2.0, 4.0), turn(0, 4.2, 5.5), turn(2, 6.0, 8.0)]
+        );
+    }
+}
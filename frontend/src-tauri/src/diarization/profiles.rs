@@ -0,0 +1,272 @@
+use super::clustering::cosine_similarity;
+use crate::database::models::SpeakerProfile;
+use crate::database::repositories::speaker::SpeakerProfilesRepository;
+use crate::state::AppState;
+use log::info;
+use serde::Serialize;
+
+/// Similarity a meeting speaker needs to a profile's voice to be recognized as them
+///
+/// Higher than the clustering threshold: a wrong name is worse than "Speaker 2".
+pub const RECOGNITION_THRESHOLD: f32 = 0.6;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpeakerProfileInfo {
+    pub id: String,
+    pub name: String,
+    /// Meeting speakers the voice embedding was averaged over
+    pub sample_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<SpeakerProfile> for SpeakerProfileInfo {
+    fn from(profile: SpeakerProfile) -> Self {
+        Self {
+            id: profile.id,
+            name: profile.name,
+            sample_count: profile.sample_count,
+            created_at: profile.created_at.to_rfc3339(),
+            updated_at: profile.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeetingSpeakerInfo {
+    /// Diarization label, e.g. "Speaker 2"
+    pub label: String,
+    /// Name shown in the transcript: the profile's name, or the label
+    pub name: String,
+    pub profile_id: Option<String>,
+}
+
+pub fn parse_embedding(json: &str) -> Vec<f32> {
+    serde_json::from_str(json).unwrap_or_default()
+}
+
+pub fn embedding_json(embedding: &[f32]) -> String {
+    serde_json::to_string(embedding).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Average of two embeddings weighted by how many samples each stands for
+pub fn combine_embeddings(a: &[f32], a_count: i64, b: &[f32], b_count: i64) -> Vec<f32> {
+    if a.len() != b.len() {
+        return if a_count >= b_count { a.to_vec() } else { b.to_vec() };
+    }
+    let total = (a_count + b_count).max(1) as f32;
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x * a_count as f32 + y * b_count as f32) / total)
+        .collect()
+}
+
+/// Profile index recognized for each meeting speaker; every profile is used at most once
+pub fn recognize(centroids: &[Vec<f32>], profiles: &[Vec<f32>]) -> Vec<Option<usize>> {
+    let mut candidates: Vec<(usize, usize, f32)> = centroids
+        .iter()
+        .enumerate()
+        .flat_map(|(speaker, centroid)| {
+            profiles
+                .iter()
+                .enumerate()
+                .map(move |(profile, embedding)| (speaker, profile, cosine_similarity(centroid, embedding)))
+        })
+        .filter(|(_, _, similarity)| *similarity >= RECOGNITION_THRESHOLD)
+        .collect();
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let mut matches = vec![None; centroids.len()];
+    let mut used = vec![false; profiles.len()];
+    for (speaker, profile, _) in candidates {
+        if matches[speaker].is_none() && !used[profile] {
+            matches[speaker] = Some(profile);
+            used[profile] = true;
+        }
+    }
+    matches
+}
+
+fn normalize_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Speaker name cannot be empty".to_string());
+    }
+    Ok(name.to_string())
+}
+
+#[tauri::command]
+pub async fn list_speaker_profiles(state: tauri::State<'_, AppState>) -> Result<Vec<SpeakerProfileInfo>, String> {
+    SpeakerProfilesRepository::list(state.db_manager.pool())
+        .await
+        .map(|profiles| profiles.into_iter().map(SpeakerProfileInfo::from).collect())
+        .map_err(|e| format!("Failed to list speaker profiles: {}", e))
+}
+
+#[tauri::command]
+pub async fn list_meeting_speakers(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<MeetingSpeakerInfo>, String> {
+    let pool = state.db_manager.pool();
+    let speakers = SpeakerProfilesRepository::list_meeting_speakers(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to list meeting speakers: {}", e))?;
+    let profiles = SpeakerProfilesRepository::list(pool)
+        .await
+        .map_err(|e| format!("Failed to list speaker profiles: {}", e))?;
+
+    Ok(speakers
+        .into_iter()
+        .map(|speaker| {
+            let name = speaker
+                .profile_id
+                .as_ref()
+                .and_then(|id| profiles.iter().find(|p| &p.id == id))
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| speaker.label.clone());
+            MeetingSpeakerInfo { label: speaker.label, name, profile_id: speaker.profile_id }
+        })
+        .collect())
+}
+
+/// Names a meeting's speaker ("Speaker 1 = Alice") and enrolls their voice
+///
+/// The voice is added to the profile with that name, which is created if needed, so
+/// later meetings recognize the speaker automatically.
+#[tauri::command]
+pub async fn label_meeting_speaker(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    label: String,
+    name: String,
+) -> Result<SpeakerProfileInfo, String> {
+    let name = normalize_name(&name)?;
+    let pool = state.db_manager.pool();
+    let speaker = SpeakerProfilesRepository::list_meeting_speakers(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting speakers: {}", e))?
+        .into_iter()
+        .find(|s| s.label == label)
+        .ok_or_else(|| format!("Speaker '{}' not found in this meeting", label))?;
+
+    let current_profile = match &speaker.profile_id {
+        Some(id) => SpeakerProfilesRepository::get(pool, id).await.map_err(|e| e.to_string())?,
+        None => None,
+    };
+    let current_name = current_profile.as_ref().map(|p| p.name.clone()).unwrap_or_else(|| label.clone());
+
+    let profile = match SpeakerProfilesRepository::find_by_name(pool, &name).await.map_err(|e| e.to_string())? {
+        Some(profile) if speaker.profile_id.as_deref() == Some(profile.id.as_str()) => profile,
+        Some(profile) => {
+            let embedding = combine_embeddings(
+                &parse_embedding(&profile.embedding),
+                profile.sample_count,
+                &parse_embedding(&speaker.embedding),
+                1,
+            );
+            SpeakerProfilesRepository::update_embedding(pool, &profile.id, &embedding_json(&embedding), profile.sample_count + 1)
+                .await
+                .map_err(|e| format!("Failed to update speaker profile: {}", e))?;
+            profile
+        }
+        None => SpeakerProfilesRepository::insert(pool, &name, &speaker.embedding)
+            .await
+            .map_err(|e| format!("Failed to create speaker profile: {}", e))?,
+    };
+
+    SpeakerProfilesRepository::assign_meeting_speaker(pool, &meeting_id, &label, &current_name, &profile)
+        .await
+        .map_err(|e| format!("Failed to label speaker: {}", e))?;
+    info!("🗣️ {} in meeting {} labelled as '{}'", label, meeting_id, profile.name);
+
+    SpeakerProfilesRepository::get(pool, &profile.id)
+        .await
+        .map_err(|e| e.to_string())?
+        .map(SpeakerProfileInfo::from)
+        .ok_or_else(|| "Speaker profile disappeared".to_string())
+}
+
+#[tauri::command]
+pub async fn rename_speaker_profile(
+    state: tauri::State<'_, AppState>,
+    id: String,
+    name: String,
+) -> Result<SpeakerProfileInfo, String> {
+    let name = normalize_name(&name)?;
+    let pool = state.db_manager.pool();
+    if !SpeakerProfilesRepository::rename(pool, &id, &name)
+        .await
+        .map_err(|e| format!("Failed to rename speaker profile: {}", e))?
+    {
+        return Err(format!("Speaker profile not found: {}", id));
+    }
+    SpeakerProfilesRepository::get(pool, &id)
+        .await
+        .map_err(|e| e.to_string())?
+        .map(SpeakerProfileInfo::from)
+        .ok_or_else(|| format!("Speaker profile not found: {}", id))
+}
+
+/// Merges two profiles of the same person; `source` is removed
+#[tauri::command]
+pub async fn merge_speaker_profiles(
+    state: tauri::State<'_, AppState>,
+    source_id: String,
+    target_id: String,
+) -> Result<SpeakerProfileInfo, String> {
+    if source_id == target_id {
+        return Err("Cannot merge a speaker profile into itself".to_string());
+    }
+    let pool = state.db_manager.pool();
+    let load = |id: String| async move {
+        SpeakerProfilesRepository::get(pool, &id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Speaker profile not found: {}", id))
+    };
+    let source = load(source_id).await?;
+    let target = load(target_id.clone()).await?;
+
+    let embedding = combine_embeddings(
+        &parse_embedding(&target.embedding),
+        target.sample_count,
+        &parse_embedding(&source.embedding),
+        source.sample_count,
+    );
+    SpeakerProfilesRepository::merge(pool, &source, &target, &embedding_json(&embedding), target.sample_count + source.sample_count)
+        .await
+        .map_err(|e| format!("Failed to merge speaker profiles: {}", e))?;
+    info!("🗣️ Merged speaker profile '{}' into '{}'", source.name, target.name);
+
+    load(target_id).await.map(SpeakerProfileInfo::from)
+}
+
+#[tauri::command]
+pub async fn delete_speaker_profile(state: tauri::State<'_, AppState>, id: String) -> Result<bool, String> {
+    SpeakerProfilesRepository::delete(state.db_manager.pool(), &id)
+        .await
+        .map_err(|e| format!("Failed to delete speaker profile: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_each_profile_is_recognized_once() {
+        let alice = vec![1.0, 0.0, 0.0];
+        let bob = vec![0.0, 1.0, 0.0];
+        let centroids = vec![vec![0.9, 0.1, 0.0], vec![0.95, 0.05, 0.0], vec![0.1, 0.9, 0.1], vec![0.0, 0.0, 1.0]];
+        // The closer of the two Alice-like speakers gets the name
+        assert_eq!(recognize(&centroids, &[alice, bob]), vec![None, Some(0), Some(1), None]);
+        assert_eq!(recognize(&centroids, &[]), vec![None; 4]);
+    }
+
+    #[test]
+    fn test_combined_embedding_is_weighted() {
+        assert_eq!(combine_embeddings(&[1.0, 0.0], 3, &[0.0, 1.0], 1), vec![0.75, 0.25]);
+        assert_eq!(combine_embeddings(&[1.0], 1, &[0.0, 1.0], 2), vec![0.0, 1.0]);
+        assert_eq!(parse_embedding(&embedding_json(&[0.5, -1.0])), vec![0.5, -1.0]);
+    }
+}
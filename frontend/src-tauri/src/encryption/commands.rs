@@ -0,0 +1,146 @@
+// encryption/commands.rs
+//
+// Tauri commands switching at-rest encryption on and off. Both prepare everything while the
+// app runs, then restart it so the database is swapped before it's opened again.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+use log::{error, info};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Runtime};
+
+use super::{database, files, EncryptionKey};
+use crate::audio::session;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::state::AppState;
+use crate::utils::restart_soon;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EncryptionStatus {
+    /// Whether the database and meeting files are encrypted
+    pub enabled: bool,
+    /// Encryption was switched on or off and the app hasn't restarted yet
+    pub restart_pending: bool,
+}
+
+#[tauri::command]
+pub async fn get_encryption_status<R: Runtime>(app: AppHandle<R>) -> Result<EncryptionStatus, String> {
+    let enabled = super::ENABLED.load(Ordering::SeqCst);
+    Ok(EncryptionStatus { enabled, restart_pending: super::is_enabled(&app) != enabled })
+}
+
+fn db_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    crate::workspaces::database_path(app)
+}
+
+async fn meeting_folders(pool: &SqlitePool) -> Result<Vec<PathBuf>, String> {
+    let meetings = MeetingsRepository::get_meetings(pool)
+        .await
+        .map_err(|e| format!("Failed to load meetings: {}", e))?;
+    Ok(meetings
+        .into_iter()
+        .filter_map(|meeting| meeting.folder_path.map(PathBuf::from))
+        .filter(|folder| folder.is_dir())
+        .collect())
+}
+
+/// Only the open workspace's database is switched, and the others couldn't be opened with the
+/// wrong key afterwards, so encryption is switched before adding workspaces; ones added later
+/// are created to match
+fn ensure_idle<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    if session::phase().is_recording() {
+        return Err("Encryption can't be switched while recording".to_string());
+    }
+    if crate::workspaces::has_others(app) {
+        return Err("Encryption can't be switched while there are other workspaces".to_string());
+    }
+    Ok(())
+}
+
+/// Applies `transform` to the files of every folder; on failure, undoes it with `undo`
+fn transform_all(
+    folders: &[PathBuf],
+    key: &EncryptionKey,
+    transform: fn(&EncryptionKey, &Path) -> anyhow::Result<()>,
+    undo: fn(&EncryptionKey, &Path) -> anyhow::Result<()>,
+) -> Result<(), String> {
+    for (done, folder) in folders.iter().enumerate() {
+        let result = super::meeting_files(folder).iter().try_for_each(|path| transform(key, path));
+        if let Err(e) = result {
+            for folder in &folders[..=done] {
+                for path in super::meeting_files(folder) {
+                    if let Err(e) = undo(key, &path) {
+                        error!("Failed to restore {:?}: {}", path, e);
+                    }
+                }
+            }
+            return Err(format!("Failed to process {:?}: {}", folder, e));
+        }
+    }
+    Ok(())
+}
+
+/// Encrypts the database and every meeting's files, then restarts the app
+#[tauri::command]
+pub async fn enable_encryption<R: Runtime>(app: AppHandle<R>, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    ensure_idle(&app)?;
+    if super::active_key().is_some() {
+        return Err("Encryption is already enabled".to_string());
+    }
+    let pool = state.db_manager.pool();
+    let db_path = db_path(&app)?;
+
+    let key = super::load_or_create_key().map_err(|e| e.to_string())?;
+    // Files encrypted from here on must stay readable until the restart
+    *super::KEY.write().unwrap() = Some(key);
+
+    let folders = meeting_folders(pool).await?;
+    transform_all(&folders, &key, files::encrypt_file, files::decrypt_file)?;
+    // Exported last, so changes made while the files were encrypted aren't lost on restart
+    if let Err(e) = database::export(pool, &db_path, Some(&key)).await {
+        let _ = transform_all(&folders, &key, files::decrypt_file, files::encrypt_file);
+        return Err(format!("{:#}", e));
+    }
+    if let Err(e) = super::set_enabled(&app, true) {
+        let _ = transform_all(&folders, &key, files::decrypt_file, files::encrypt_file);
+        database::discard_export(&db_path, true);
+        return Err(e);
+    }
+
+    info!("🔐 Encrypted {} meeting folders, restarting to encrypt the database", folders.len());
+    restart_soon(app);
+    Ok(())
+}
+
+/// Decrypts the database and every meeting's files, then restarts the app
+#[tauri::command]
+pub async fn disable_encryption<R: Runtime>(app: AppHandle<R>, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    ensure_idle(&app)?;
+    let Some(key) = super::active_key() else {
+        return Err("Encryption is not enabled".to_string());
+    };
+    let pool = state.db_manager.pool();
+    let db_path = db_path(&app)?;
+
+    let folders = meeting_folders(pool).await?;
+    transform_all(&folders, &key, files::decrypt_file, files::encrypt_file)?;
+    // Exported last, so changes made while the files were decrypted aren't lost on restart
+    if let Err(e) = database::export(pool, &db_path, None).await {
+        let _ = transform_all(&folders, &key, files::encrypt_file, files::decrypt_file);
+        return Err(format!("{:#}", e));
+    }
+    // Until the restart, new recordings stay plain
+    super::ENABLED.store(false, Ordering::SeqCst);
+    if let Err(e) = super::set_enabled(&app, false) {
+        super::ENABLED.store(true, Ordering::SeqCst);
+        let _ = transform_all(&folders, &key, files::encrypt_file, files::decrypt_file);
+        database::discard_export(&db_path, false);
+        return Err(e);
+    }
+
+    info!("🔓 Decrypted {} meeting folders, restarting to decrypt the database", folders.len());
+    restart_soon(app);
+    Ok(())
+}
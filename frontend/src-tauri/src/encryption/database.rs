@@ -0,0 +1,102 @@
+// encryption/database.rs
+//
+// Switching the database between plain and SQLCipher-encrypted. SQLCipher can't change a
+// database's key in place from plain text, so its content is exported into a file next to it
+// with the new key while the app runs, and that file replaces the database on the next start,
+// before anything opens it.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use log::info;
+use sqlx::SqlitePool;
+
+use super::{to_hex, EncryptionKey};
+
+/// The export waiting to replace the database once encryption is switched on or off
+fn pending_path(db_path: &Path, encrypted: bool) -> PathBuf {
+    db_path.with_extension(if encrypted { "encrypted.sqlite" } else { "decrypted.sqlite" })
+}
+
+/// The value of SQLCipher's `key` pragma: the raw key, or the empty key of a plain database
+pub fn key_pragma(key: Option<&EncryptionKey>) -> String {
+    match key {
+        Some(key) => format!("\"x'{}'\"", to_hex(key)),
+        None => "''".to_string(),
+    }
+}
+
+/// Exports the database into its pending file, encrypted with `key` or plain without one
+pub async fn export(pool: &SqlitePool, db_path: &Path, key: Option<&EncryptionKey>) -> Result<()> {
+    let target = pending_path(db_path, key.is_some());
+    if target.exists() {
+        std::fs::remove_file(&target).with_context(|| format!("Failed to remove {:?}", target))?;
+    }
+
+    let mut conn = pool.acquire().await?;
+    let attach = format!(
+        "ATTACH DATABASE '{}' AS export KEY {}",
+        target.to_string_lossy().replace('\'', "''"),
+        key_pragma(key)
+    );
+    sqlx::query(&attach).execute(&mut *conn).await.context("Failed to create the export")?;
+    let exported = sqlx::query("SELECT sqlcipher_export('export')").execute(&mut *conn).await;
+    sqlx::query("DETACH DATABASE export").execute(&mut *conn).await?;
+    if let Err(e) = exported {
+        let _ = std::fs::remove_file(&target);
+        return Err(e).context("Failed to export the database");
+    }
+
+    info!("🔐 Exported the database to {:?}", target);
+    Ok(())
+}
+
+/// Removes the export of a switch that was undone before restarting
+pub fn discard_export(db_path: &Path, encrypted: bool) {
+    let _ = std::fs::remove_file(pending_path(db_path, encrypted));
+}
+
+/// Puts the export matching `enabled` in place of the database; returns whether there was one
+pub fn complete_pending_switch(db_path: &Path, enabled: bool) -> Result<bool> {
+    discard_export(db_path, !enabled);
+    let pending = pending_path(db_path, enabled);
+    if !pending.exists() {
+        return Ok(false);
+    }
+
+    let name = db_path.to_string_lossy();
+    for stale in [db_path.to_path_buf(), PathBuf::from(format!("{}-wal", name)), PathBuf::from(format!("{}-shm", name))] {
+        if stale.exists() {
+            std::fs::remove_file(&stale).with_context(|| format!("Failed to remove {:?}", stale))?;
+        }
+    }
+    std::fs::rename(&pending, db_path).with_context(|| format!("Failed to move {:?} into place", pending))?;
+    info!("🔐 Database is now {}", if enabled { "encrypted" } else { "plain" });
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complete_pending_switch() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("meeting_minutes.sqlite");
+        std::fs::write(&db, "plain").unwrap();
+        std::fs::write(dir.path().join("meeting_minutes.sqlite-wal"), "wal").unwrap();
+        assert!(!complete_pending_switch(&db, true).unwrap());
+
+        std::fs::write(pending_path(&db, true), "encrypted").unwrap();
+        std::fs::write(pending_path(&db, false), "stale").unwrap();
+        assert!(complete_pending_switch(&db, true).unwrap());
+        assert_eq!(std::fs::read_to_string(&db).unwrap(), "encrypted");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_key_pragma() {
+        assert_eq!(key_pragma(None), "''");
+        assert_eq!(key_pragma(Some(&[0xab; 32])), format!("\"x'{}'\"", "ab".repeat(32)));
+    }
+}
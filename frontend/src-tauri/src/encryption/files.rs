@@ -0,0 +1,250 @@
+// encryption/files.rs
+//
+// Encrypted meeting files. A file is encrypted in place, keeping its name, so everything
+// that finds recordings by name keeps working; readers go through `read` or
+// `decrypted_copy`, which pass plain files through unchanged.
+//
+// Format: `MAGIC`, a random 7-byte nonce prefix, then the content in chunks of `CHUNK_SIZE`
+// bytes, each sealed with AES-256-GCM. A chunk's nonce is the prefix, its index and a flag set
+// on the last chunk, so chunks can't be reordered, dropped or truncated without failing
+// authentication, and recordings of any length are processed without loading them whole.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{anyhow, Context, Result};
+use rand::RngCore;
+
+use super::EncryptionKey;
+
+const MAGIC: &[u8; 6] = b"MMENC\x01";
+const PREFIX_LEN: usize = 7;
+const CHUNK_SIZE: usize = 64 * 1024;
+const TAG_LEN: usize = 16;
+
+fn nonce(prefix: &[u8; PREFIX_LEN], index: u32, last: bool) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[..PREFIX_LEN].copy_from_slice(prefix);
+    nonce[PREFIX_LEN..11].copy_from_slice(&index.to_be_bytes());
+    nonce[11] = last as u8;
+    nonce
+}
+
+/// Reads until `buf` is full or the input ends; returns the bytes read
+fn read_up_to(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+pub fn encrypt(key: &EncryptionKey, input: &mut impl Read, output: &mut impl Write) -> Result<()> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let mut prefix = [0u8; PREFIX_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut prefix);
+    output.write_all(MAGIC)?;
+    output.write_all(&prefix)?;
+
+    let mut current = vec![0u8; CHUNK_SIZE];
+    let mut next = vec![0u8; CHUNK_SIZE];
+    let mut current_len = read_up_to(input, &mut current)?;
+    let mut index: u32 = 0;
+    loop {
+        // A chunk is the last one when nothing follows it
+        let next_len = if current_len == CHUNK_SIZE { read_up_to(input, &mut next)? } else { 0 };
+        let last = next_len == 0;
+        let sealed = cipher
+            .encrypt(Nonce::from_slice(&nonce(&prefix, index, last)), &current[..current_len])
+            .map_err(|_| anyhow!("Encryption failed"))?;
+        output.write_all(&sealed)?;
+        if last {
+            return Ok(());
+        }
+        std::mem::swap(&mut current, &mut next);
+        current_len = next_len;
+        index = index.checked_add(1).ok_or_else(|| anyhow!("File too large to encrypt"))?;
+    }
+}
+
+pub fn decrypt(key: &EncryptionKey, input: &mut impl Read, output: &mut impl Write) -> Result<()> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let mut header = [0u8; MAGIC.len() + PREFIX_LEN];
+    if read_up_to(input, &mut header)? < header.len() || &header[..MAGIC.len()] != MAGIC {
+        return Err(anyhow!("Not an encrypted file"));
+    }
+    let mut prefix = [0u8; PREFIX_LEN];
+    prefix.copy_from_slice(&header[MAGIC.len()..]);
+
+    let mut current = vec![0u8; CHUNK_SIZE + TAG_LEN];
+    let mut next = vec![0u8; CHUNK_SIZE + TAG_LEN];
+    let mut current_len = read_up_to(input, &mut current)?;
+    let mut index: u32 = 0;
+    loop {
+        let next_len = if current_len == current.len() { read_up_to(input, &mut next)? } else { 0 };
+        let last = next_len == 0;
+        let plain = cipher
+            .decrypt(Nonce::from_slice(&nonce(&prefix, index, last)), &current[..current_len])
+            .map_err(|_| anyhow!("Decryption failed: wrong key or damaged file"))?;
+        output.write_all(&plain)?;
+        if last {
+            return Ok(());
+        }
+        std::mem::swap(&mut current, &mut next);
+        current_len = next_len;
+        index = index.checked_add(1).ok_or_else(|| anyhow!("Encrypted file too large"))?;
+    }
+}
+
+pub fn is_encrypted(path: &Path) -> bool {
+    let mut magic = [0u8; MAGIC.len()];
+    File::open(path)
+        .and_then(|mut file| read_up_to(&mut file, &mut magic))
+        .is_ok_and(|len| len == MAGIC.len() && &magic == MAGIC)
+}
+
+/// Replaces `path` with the output of `transform`, through a temporary file next to it
+fn rewrite(path: &Path, transform: impl FnOnce(&mut BufReader<File>, &mut BufWriter<File>) -> Result<()>) -> Result<()> {
+    let name = path.file_name().ok_or_else(|| anyhow!("Not a file: {:?}", path))?;
+    let temp = path.with_file_name(format!(".{}.tmp", name.to_string_lossy()));
+    let result = (|| -> Result<()> {
+        let mut input = BufReader::new(File::open(path)?);
+        let mut output = BufWriter::new(File::create(&temp)?);
+        transform(&mut input, &mut output)?;
+        output.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        std::fs::rename(&temp, path)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    result.with_context(|| format!("Failed to rewrite {:?}", path))
+}
+
+/// Encrypts a file in place; files already encrypted are left alone
+pub fn encrypt_file(key: &EncryptionKey, path: &Path) -> Result<()> {
+    if is_encrypted(path) {
+        return Ok(());
+    }
+    rewrite(path, |input, output| encrypt(key, input, output))
+}
+
+/// Decrypts a file in place; plain files are left alone
+pub fn decrypt_file(key: &EncryptionKey, path: &Path) -> Result<()> {
+    if !is_encrypted(path) {
+        return Ok(());
+    }
+    rewrite(path, |input, output| decrypt(key, input, output))
+}
+
+/// The content of a meeting file, decrypted if needed
+pub fn read(path: &Path) -> Result<Vec<u8>> {
+    if !is_encrypted(path) {
+        return Ok(std::fs::read(path)?);
+    }
+    let key = super::current_key().ok_or_else(|| anyhow!("{:?} is encrypted and encryption is locked", path))?;
+    let mut plain = Vec::new();
+    decrypt(&key, &mut BufReader::new(File::open(path)?), &mut plain)?;
+    Ok(plain)
+}
+
+pub fn read_to_string(path: &Path) -> Result<String> {
+    Ok(String::from_utf8(read(path)?)?)
+}
+
+/// A decrypted temporary copy of an encrypted file, deleted when dropped
+pub struct DecryptedCopy {
+    path: PathBuf,
+}
+
+impl DecryptedCopy {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for DecryptedCopy {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// For tools that need a seekable file (FFmpeg): `None` when `path` isn't encrypted. The copy
+/// is plain text on disk while it exists, so keep it only as long as the tool runs.
+pub fn decrypted_copy(path: &Path) -> Result<Option<DecryptedCopy>> {
+    if !is_encrypted(path) {
+        return Ok(None);
+    }
+    let key = super::current_key().ok_or_else(|| anyhow!("{:?} is encrypted and encryption is locked", path))?;
+    let extension = path.extension().map(|ext| format!(".{}", ext.to_string_lossy())).unwrap_or_default();
+    let copy = DecryptedCopy {
+        path: std::env::temp_dir().join(format!("meetily-{}{}", uuid::Uuid::new_v4(), extension)),
+    };
+    let mut output = BufWriter::new(File::create(copy.path())?);
+    decrypt(&key, &mut BufReader::new(File::open(path)?), &mut output)?;
+    output.flush()?;
+    Ok(Some(copy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: EncryptionKey = [7u8; 32];
+
+    fn round_trip(len: usize) {
+        let plain: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+        let mut sealed = Vec::new();
+        encrypt(&KEY, &mut plain.as_slice(), &mut sealed).unwrap();
+        let chunks = len.div_ceil(CHUNK_SIZE).max(1);
+        assert_eq!(sealed.len(), MAGIC.len() + PREFIX_LEN + len + chunks * TAG_LEN);
+
+        let mut opened = Vec::new();
+        decrypt(&KEY, &mut sealed.as_slice(), &mut opened).unwrap();
+        assert_eq!(opened, plain);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for len in [0, 1, CHUNK_SIZE - 1, CHUNK_SIZE, CHUNK_SIZE + 1, 3 * CHUNK_SIZE + 17] {
+            round_trip(len);
+        }
+    }
+
+    #[test]
+    fn test_tampering_detected() {
+        let plain = vec![1u8; 2 * CHUNK_SIZE + 5];
+        let mut sealed = Vec::new();
+        encrypt(&KEY, &mut plain.as_slice(), &mut sealed).unwrap();
+
+        let mut flipped = sealed.clone();
+        flipped[MAGIC.len() + PREFIX_LEN + 10] ^= 1;
+        assert!(decrypt(&KEY, &mut flipped.as_slice(), &mut Vec::new()).is_err());
+
+        // Dropping the last chunk makes the one before it look last, which fails its nonce
+        let truncated = &sealed[..MAGIC.len() + PREFIX_LEN + 2 * (CHUNK_SIZE + TAG_LEN)];
+        assert!(decrypt(&KEY, &mut &truncated[..], &mut Vec::new()).is_err());
+
+        assert!(decrypt(&[8u8; 32], &mut sealed.as_slice(), &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_file_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("transcripts.json");
+        std::fs::write(&path, "{\"segments\":[]}").unwrap();
+
+        encrypt_file(&KEY, &path).unwrap();
+        assert!(is_encrypted(&path));
+        encrypt_file(&KEY, &path).unwrap();
+        decrypt_file(&KEY, &path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"segments\":[]}");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
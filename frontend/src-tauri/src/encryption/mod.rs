@@ -0,0 +1,175 @@
+/// Encryption module - at-rest encryption of meeting content
+///
+/// Off by default. Once enabled, the database is encrypted with SQLCipher, and each meeting's
+/// recording and transcript file with AES-256-GCM, all with one random key kept in the OS
+/// credential store (macOS Keychain, Windows Credential Manager, Secret Service on Linux).
+/// The key is loaded when the app starts, before the database opens, so nothing changes for
+/// the user.
+///
+/// A meeting's files are encrypted when it is saved, not while it is recorded. Until then the
+/// crash-safe audio log (`.wal`), the audio checkpoints (`.checkpoints`) and `transcripts.json`
+/// sit in the meeting folder as plaintext, because recovery and the ffmpeg merge read them
+/// directly. After a crash they stay plaintext until the recording is recovered, and a
+/// dismissed recovery leaves them as they are. Everything else is ciphertext on disk.
+///
+/// This module contains:
+/// - Encrypted meeting files (`files`)
+/// - Encrypting and decrypting the database (`database`)
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+pub mod database;
+pub mod files;
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::secrets;
+
+pub type EncryptionKey = [u8; 32];
+
+const SERVICE: &str = "com.meetily.ai.encryption";
+const ACCOUNT: &str = "master-key";
+
+/// Whether encryption is on has to be known before the database opens, so it isn't a setting
+const STORE_FILE: &str = "encryption.json";
+const STORE_KEY: &str = "enabled";
+
+/// The transcript written next to each recording
+const TRANSCRIPT_FILE: &str = "transcripts.json";
+
+/// The stored key, loaded at startup. It's kept while encryption is off if switching it on or
+/// off was interrupted, so files already encrypted can still be read.
+static KEY: Lazy<RwLock<Option<EncryptionKey>>> = Lazy::new(|| RwLock::new(None));
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The key for reading encrypted files
+pub fn current_key() -> Option<EncryptionKey> {
+    *KEY.read().unwrap()
+}
+
+/// The key for the database and new files; `None` while encryption is off
+pub fn active_key() -> Option<EncryptionKey> {
+    current_key().filter(|_| ENABLED.load(Ordering::SeqCst))
+}
+
+pub(crate) fn to_hex(key: &EncryptionKey) -> String {
+    key.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub(crate) fn from_hex(hex: &str) -> Option<EncryptionKey> {
+    let hex = hex.trim();
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut key = [0u8; 32];
+    for (byte, pair) in key.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(key)
+}
+
+fn load_key() -> Result<Option<EncryptionKey>> {
+    match secrets::load(SERVICE, ACCOUNT)? {
+        Some(hex) => from_hex(&hex).map(Some).ok_or_else(|| anyhow!("The stored encryption key is damaged")),
+        None => Ok(None),
+    }
+}
+
+/// The stored key, or a new one when there's none
+fn load_or_create_key() -> Result<EncryptionKey> {
+    if let Some(key) = load_key()? {
+        return Ok(key);
+    }
+    let mut key = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut key);
+    secrets::store(SERVICE, ACCOUNT, &to_hex(&key))?;
+    info!("🔐 Created an encryption key in the system credential store");
+    Ok(key)
+}
+
+fn delete_key() -> Result<()> {
+    secrets::delete(SERVICE, ACCOUNT)
+}
+
+pub fn is_enabled<R: Runtime>(app: &AppHandle<R>) -> bool {
+    match app.store(STORE_FILE) {
+        Ok(store) => store.get(STORE_KEY).and_then(|value| value.as_bool()).unwrap_or(false),
+        Err(e) => {
+            warn!("Failed to open {}: {}", STORE_FILE, e);
+            false
+        }
+    }
+}
+
+fn set_enabled<R: Runtime>(app: &AppHandle<R>, enabled: bool) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    store.set(STORE_KEY, enabled);
+    store.save().map_err(|e| format!("Failed to save {}: {}", STORE_FILE, e))
+}
+
+/// Called before the database opens: finishes switching encryption on or off, and loads the
+/// key when it's on. Fails when the database is encrypted and its key can't be read, since
+/// nothing could be opened then.
+pub fn unlock<R: Runtime>(app: &AppHandle<R>, db_path: &Path) -> Result<()> {
+    let enabled = is_enabled(app);
+    let switched = database::complete_pending_switch(db_path, enabled)?;
+
+    let key = if enabled {
+        let key = load_key()?.ok_or_else(|| anyhow!("Encryption is enabled but its key is missing from the credential store"))?;
+        info!("🔐 Encryption unlocked");
+        Some(key)
+    } else if switched {
+        // Every file was decrypted before the switch, so the key isn't needed anymore
+        delete_key()?;
+        info!("🔓 Encryption turned off and its key deleted");
+        None
+    } else {
+        load_key().unwrap_or_else(|e| {
+            warn!("{}", e);
+            None
+        })
+    };
+    *KEY.write().unwrap() = key;
+    ENABLED.store(enabled, Ordering::SeqCst);
+    Ok(())
+}
+
+/// The files of a meeting folder that hold meeting content
+fn meeting_files(folder: &Path) -> Vec<std::path::PathBuf> {
+    crate::audio::playback::find_recording(folder)
+        .into_iter()
+        .chain(Some(folder.join(TRANSCRIPT_FILE)).filter(|path| path.is_file()))
+        .collect()
+}
+
+/// Encrypts a meeting's recording and transcript; does nothing while encryption is off
+pub fn encrypt_meeting_folder(folder: &Path) -> Result<()> {
+    let Some(key) = active_key() else {
+        return Ok(());
+    };
+    for path in meeting_files(folder) {
+        files::encrypt_file(&key, &path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_round_trip() {
+        let key: EncryptionKey = std::array::from_fn(|i| (i * 8) as u8);
+        assert_eq!(from_hex(&to_hex(&key)), Some(key));
+        assert_eq!(from_hex(&to_hex(&key)[2..]), None);
+        assert_eq!(from_hex(&"zz".repeat(32)), None);
+    }
+}
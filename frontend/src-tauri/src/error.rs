@@ -0,0 +1,255 @@
+// error.rs
+//
+// Errors returned to the frontend by the Tauri commands. Each carries a code the UI can
+// branch on instead of matching message text, whether retrying can succeed without restarting
+// the app, and a hint telling the user what to do about it.
+//
+// Only the commands the UI branches on return it: starting, stopping, pausing and resuming a
+// recording, the permission requests and the self-test. The rest keep returning plain strings,
+// which the UI only ever shows as text.
+//
+// Codes come from typed errors. `AudioError`, `TranscriptionError`, I/O and cpal errors map to
+// a code directly, and an `anyhow::Error` takes the code of the first of those in its chain. A
+// bare string carries no code and is `Internal`.
+
+use crate::audio::recording_state::AudioError;
+use crate::audio::transcription::provider::TranscriptionError;
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ErrorCode {
+    /// The OS refused access to the microphone or system audio
+    PermissionDenied,
+    /// Another application holds the audio device
+    DeviceBusy,
+    /// The audio device is disconnected or doesn't exist
+    DeviceNotFound,
+    /// No transcription model is downloaded or it failed to load
+    ModelMissing,
+    /// No space left to write the recording
+    DiskFull,
+    AlreadyRecording,
+    NotRecording,
+    /// Anything else; the message is all there is
+    Internal,
+}
+
+impl ErrorCode {
+    /// Whether trying again can succeed once the hint is followed, without restarting the app
+    pub fn recoverable(self) -> bool {
+        !matches!(self, ErrorCode::Internal)
+    }
+
+    pub fn hint(self) -> Option<&'static str> {
+        match self {
+            ErrorCode::PermissionDenied => Some(
+                "Allow Meetily to use the microphone (and screen recording for system audio) in your system's privacy settings; recording can start as soon as access is granted.",
+            ),
+            ErrorCode::DeviceBusy => Some("Close other applications using the audio device, or choose another device."),
+            ErrorCode::DeviceNotFound => Some("Check that the device is connected, or choose another device."),
+            ErrorCode::ModelMissing => Some("Download a transcription model in Settings."),
+            ErrorCode::DiskFull => Some("Free up disk space, or choose a recordings folder on another disk."),
+            ErrorCode::AlreadyRecording | ErrorCode::NotRecording | ErrorCode::Internal => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub recoverable: bool,
+    pub hint: Option<String>,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            recoverable: code.recoverable(),
+            hint: code.hint().map(str::to_string),
+        }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    /// Keeps the code, with `context` in front of the message, e.g. "Failed to start recording: ..."
+    pub fn context(mut self, context: &str) -> Self {
+        self.message = format!("{}: {}", context, self.message);
+        self
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<AudioError> for AppError {
+    fn from(error: AudioError) -> Self {
+        Self::new(audio_error_code(&error), error.user_message())
+    }
+}
+
+impl From<TranscriptionError> for AppError {
+    fn from(error: TranscriptionError) -> Self {
+        Self::new(transcription_error_code(&error), error.to_string())
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        Self::new(io_error_code(&error), error.to_string())
+    }
+}
+
+impl From<cpal::BuildStreamError> for AppError {
+    fn from(error: cpal::BuildStreamError) -> Self {
+        Self::new(build_stream_error_code(&error), error.to_string())
+    }
+}
+
+impl From<cpal::PlayStreamError> for AppError {
+    fn from(error: cpal::PlayStreamError) -> Self {
+        Self::new(play_stream_error_code(&error), error.to_string())
+    }
+}
+
+impl From<anyhow::Error> for AppError {
+    fn from(error: anyhow::Error) -> Self {
+        let code = error.chain().find_map(typed_error_code).unwrap_or(ErrorCode::Internal);
+        Self::new(code, format!("{:#}", error))
+    }
+}
+
+/// The code for one link of an error chain, if it's one of the typed errors
+fn typed_error_code(cause: &(dyn std::error::Error + 'static)) -> Option<ErrorCode> {
+    if let Some(error) = cause.downcast_ref::<AppError>() {
+        Some(error.code)
+    } else if let Some(error) = cause.downcast_ref::<AudioError>() {
+        Some(audio_error_code(error))
+    } else if let Some(error) = cause.downcast_ref::<TranscriptionError>() {
+        Some(transcription_error_code(error))
+    } else if let Some(error) = cause.downcast_ref::<std::io::Error>() {
+        Some(io_error_code(error))
+    } else if let Some(error) = cause.downcast_ref::<cpal::BuildStreamError>() {
+        Some(build_stream_error_code(error))
+    } else {
+        cause.downcast_ref::<cpal::PlayStreamError>().map(play_stream_error_code)
+    }
+}
+
+fn audio_error_code(error: &AudioError) -> ErrorCode {
+    match error {
+        AudioError::PermissionDenied => ErrorCode::PermissionDenied,
+        AudioError::DeviceDisconnected => ErrorCode::DeviceNotFound,
+        _ => ErrorCode::Internal,
+    }
+}
+
+fn transcription_error_code(error: &TranscriptionError) -> ErrorCode {
+    match error {
+        TranscriptionError::ModelNotLoaded => ErrorCode::ModelMissing,
+        _ => ErrorCode::Internal,
+    }
+}
+
+/// `ErrorKind::StorageFull` is newer than our minimum toolchain, so a full disk is told apart
+/// by its OS error code
+fn io_error_code(error: &std::io::Error) -> ErrorCode {
+    #[cfg(unix)]
+    const DISK_FULL: &[i32] = &[28]; // ENOSPC
+    #[cfg(windows)]
+    const DISK_FULL: &[i32] = &[39, 112]; // ERROR_HANDLE_DISK_FULL, ERROR_DISK_FULL
+    #[cfg(not(any(unix, windows)))]
+    const DISK_FULL: &[i32] = &[];
+
+    if error.kind() == std::io::ErrorKind::PermissionDenied {
+        ErrorCode::PermissionDenied
+    } else if error.raw_os_error().is_some_and(|code| DISK_FULL.contains(&code)) {
+        ErrorCode::DiskFull
+    } else {
+        ErrorCode::Internal
+    }
+}
+
+fn build_stream_error_code(error: &cpal::BuildStreamError) -> ErrorCode {
+    match error {
+        cpal::BuildStreamError::DeviceNotAvailable => ErrorCode::DeviceNotFound,
+        _ => ErrorCode::Internal,
+    }
+}
+
+fn play_stream_error_code(error: &cpal::PlayStreamError) -> ErrorCode {
+    match error {
+        cpal::PlayStreamError::DeviceNotAvailable => ErrorCode::DeviceNotFound,
+        _ => ErrorCode::Internal,
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(error: tauri::Error) -> Self {
+        Self::internal(error.to_string())
+    }
+}
+
+/// For callers that still pass errors around as strings
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        error.message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_from_typed_errors() {
+        assert_eq!(AppError::from(AudioError::PermissionDenied).code, ErrorCode::PermissionDenied);
+        assert_eq!(AppError::from(AudioError::DeviceDisconnected).code, ErrorCode::DeviceNotFound);
+        assert_eq!(AppError::from(AudioError::StreamFailed).code, ErrorCode::Internal);
+        assert_eq!(AppError::from(TranscriptionError::ModelNotLoaded).code, ErrorCode::ModelMissing);
+        assert_eq!(AppError::from(std::io::Error::from(std::io::ErrorKind::PermissionDenied)).code, ErrorCode::PermissionDenied);
+        #[cfg(unix)]
+        assert_eq!(AppError::from(std::io::Error::from_raw_os_error(28)).code, ErrorCode::DiskFull);
+        assert_eq!(AppError::from(cpal::BuildStreamError::DeviceNotAvailable).code, ErrorCode::DeviceNotFound);
+    }
+
+    #[test]
+    fn test_message_text_is_not_a_code() {
+        // Only the type says what went wrong; wording that looks like a permission problem doesn't
+        assert_eq!(AppError::from("Microphone permission denied").code, ErrorCode::Internal);
+        assert_eq!(AppError::from(anyhow::anyhow!("Microphone permission denied")).code, ErrorCode::Internal);
+    }
+
+    #[test]
+    fn test_code_from_anyhow_chain() {
+        let error = anyhow::Error::new(AudioError::PermissionDenied).context("Failed to open microphone");
+        let error = AppError::from(error).context("Failed to start recording");
+        assert_eq!(error.code, ErrorCode::PermissionDenied);
+        assert_eq!(error.message, "Failed to start recording: Failed to open microphone: Microphone permission denied");
+        assert!(error.recoverable);
+        assert!(error.hint.is_some());
+        assert_eq!(String::from(error), "Failed to start recording: Failed to open microphone: Microphone permission denied");
+    }
+}
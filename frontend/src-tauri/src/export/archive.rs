@@ -0,0 +1,330 @@
+// export/archive.rs
+//
+// A complete meeting as one JSON document, for moving meetings between machines or
+// processing them with other tools. The format is described by
+// docs/meeting-archive.schema.json; bump ARCHIVE_VERSION when it changes incompatibly.
+//
+// A file holds one archive, a JSON array of them, or one archive per line (JSONL).
+// Audio is referenced, not embedded: the archive records where the recording lived.
+
+use crate::audio::transcription::TranscriptWord;
+use crate::database::models::{ActionItem, KeyPoint, Participant};
+use crate::database::repositories::action_item::ActionItemsRepository;
+use crate::database::repositories::key_point::KeyPointsRepository;
+use crate::database::repositories::meeting_library::MeetingLibraryRepository;
+use crate::database::repositories::speaker::SpeakerProfilesRepository;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+pub const ARCHIVE_FORMAT: &str = "meetily.meeting";
+pub const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingArchive {
+    /// Always `meetily.meeting`
+    pub format: String,
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub meeting: ArchivedMeeting,
+    pub audio: Option<AudioRef>,
+    #[serde(default)]
+    pub speakers: Vec<ArchivedSpeaker>,
+    #[serde(default)]
+    pub segments: Vec<ArchivedSegment>,
+    pub summary: Option<ArchivedSummary>,
+    #[serde(default)]
+    pub action_items: Vec<ArchivedActionItem>,
+    #[serde(default)]
+    pub key_points: Vec<ArchivedKeyPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedMeeting {
+    /// Id on the exporting machine; imports get a new one
+    pub id: String,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub participants: Vec<Participant>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Where the recording was stored on the exporting machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioRef {
+    pub folder_path: String,
+    /// File name inside the folder, from the folder's metadata.json
+    pub file: Option<String>,
+    pub duration_seconds: Option<f64>,
+}
+
+/// A diarized speaker and the profile it was matched to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSpeaker {
+    pub label: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSegment {
+    pub id: String,
+    pub text: String,
+    /// Wall-clock time the segment was captured, as stored
+    pub timestamp: String,
+    /// Seconds into the recording
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    pub duration: Option<f64>,
+    pub speaker: Option<String>,
+    pub translation: Option<String>,
+    #[serde(default)]
+    pub words: Vec<TranscriptWord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSummary {
+    pub markdown: Option<String>,
+    /// The stored summary result as is, including the editor's blocks
+    pub result: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedActionItem {
+    pub text: String,
+    pub assignee: Option<String>,
+    /// YYYY-MM-DD
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub completed: bool,
+    /// `extracted` or `manual`
+    pub source: String,
+    /// Id of the segment the item came from
+    pub source_segment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedKeyPoint {
+    /// `decision`, `open_question` or `risk`
+    pub kind: String,
+    pub text: String,
+    /// Ids of the segments the point was drawn from
+    #[serde(default)]
+    pub source_segments: Vec<String>,
+}
+
+impl From<ActionItem> for ArchivedActionItem {
+    fn from(item: ActionItem) -> Self {
+        Self {
+            text: item.text,
+            assignee: item.assignee,
+            due_date: item.due_date,
+            completed: item.completed,
+            source: item.source,
+            source_segment: item.source_segment,
+        }
+    }
+}
+
+impl From<KeyPoint> for ArchivedKeyPoint {
+    fn from(point: KeyPoint) -> Self {
+        Self {
+            kind: point.kind,
+            text: point.text,
+            source_segments: serde_json::from_str(&point.source_segments).unwrap_or_default(),
+        }
+    }
+}
+
+/// The recording's file name and duration from the metadata.json next to it
+fn audio_ref(folder_path: &str) -> AudioRef {
+    let metadata: Option<serde_json::Value> = std::fs::read_to_string(Path::new(folder_path).join("metadata.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    AudioRef {
+        folder_path: folder_path.to_string(),
+        file: metadata.as_ref().and_then(|m| m["audio_file"].as_str()).map(str::to_string),
+        duration_seconds: metadata.as_ref().and_then(|m| m["duration_seconds"].as_f64()),
+    }
+}
+
+impl MeetingArchive {
+    pub async fn load(pool: &SqlitePool, meeting_id: &str) -> Result<Self, String> {
+        let meeting = MeetingLibraryRepository::get(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load meeting: {}", e))?
+            .ok_or_else(|| format!("No meeting found with id {}", meeting_id))?;
+        let tags = MeetingLibraryRepository::list_tags(pool, Some(meeting_id))
+            .await
+            .map_err(|e| format!("Failed to load tags: {}", e))?;
+        let participants = MeetingLibraryRepository::list_participants(pool, Some(meeting_id))
+            .await
+            .map_err(|e| format!("Failed to load participants: {}", e))?;
+        let metadata = MeetingLibraryRepository::list_metadata(pool, Some(meeting_id))
+            .await
+            .map_err(|e| format!("Failed to load metadata: {}", e))?;
+
+        let profiles: HashMap<String, String> = SpeakerProfilesRepository::list(pool)
+            .await
+            .map_err(|e| format!("Failed to load speaker profiles: {}", e))?
+            .into_iter()
+            .map(|profile| (profile.id, profile.name))
+            .collect();
+        let speakers = SpeakerProfilesRepository::list_meeting_speakers(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load speakers: {}", e))?
+            .into_iter()
+            .map(|speaker| ArchivedSpeaker {
+                name: speaker.profile_id.and_then(|id| profiles.get(&id).cloned()),
+                label: speaker.label,
+            })
+            .collect();
+
+        let segments = TranscriptsRepository::list_for_meeting(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load transcript: {}", e))?
+            .into_iter()
+            .map(|t| ArchivedSegment {
+                words: t.words.as_deref().and_then(|words| serde_json::from_str(words).ok()).unwrap_or_default(),
+                id: t.id,
+                text: t.transcript,
+                timestamp: t.timestamp,
+                start: t.audio_start_time,
+                end: t.audio_end_time,
+                duration: t.duration,
+                speaker: t.speaker,
+                translation: t.translation,
+            })
+            .collect();
+
+        let summary = SummaryProcessesRepository::get_summary_data(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load summary: {}", e))?
+            .and_then(|process| process.result)
+            .map(|result| {
+                let result = serde_json::from_str(&result).unwrap_or(serde_json::Value::String(result));
+                ArchivedSummary { markdown: result["markdown"].as_str().map(str::to_string), result }
+            });
+        let action_items = ActionItemsRepository::list_for_meeting(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load action items: {}", e))?
+            .into_iter()
+            .map(ArchivedActionItem::from)
+            .collect();
+        let key_points = KeyPointsRepository::list_for_meeting(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load key points: {}", e))?
+            .into_iter()
+            .map(ArchivedKeyPoint::from)
+            .collect();
+
+        Ok(Self {
+            format: ARCHIVE_FORMAT.to_string(),
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            audio: meeting.folder_path.as_deref().map(audio_ref),
+            meeting: ArchivedMeeting {
+                id: meeting.id,
+                title: meeting.title,
+                created_at: meeting.created_at.0,
+                updated_at: meeting.updated_at.0,
+                archived_at: meeting.archived_at.map(|at| at.0),
+                notes: meeting.notes,
+                tags: tags.into_iter().map(|(_, tag)| tag).collect(),
+                participants: participants.into_iter().map(|(_, participant)| participant).collect(),
+                metadata: metadata.into_iter().map(|(_, key, value)| (key, value)).collect(),
+            },
+            speakers,
+            segments,
+            summary,
+            action_items,
+            key_points,
+        })
+    }
+}
+
+fn check_version(archive: MeetingArchive) -> Result<MeetingArchive, String> {
+    if archive.format != ARCHIVE_FORMAT {
+        return Err(format!("Not a meeting archive: format is \"{}\"", archive.format));
+    }
+    if archive.version > ARCHIVE_VERSION {
+        return Err(format!(
+            "Meeting archive version {} is newer than this app supports ({}); update the app to import it",
+            archive.version, ARCHIVE_VERSION
+        ));
+    }
+    Ok(archive)
+}
+
+/// Reads the archives in a file: one archive, an array of them, or one per line
+pub fn parse_archives(content: &str) -> Result<Vec<MeetingArchive>, String> {
+    let content = content.trim_start_matches('\u{feff}').trim();
+    let archives: Vec<MeetingArchive> = if content.starts_with('[') {
+        serde_json::from_str(content).map_err(|e| format!("Invalid meeting archive: {}", e))?
+    } else if let Ok(archive) = serde_json::from_str::<MeetingArchive>(content) {
+        vec![archive]
+    } else {
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| serde_json::from_str(line).map_err(|e| format!("Invalid meeting archive on line {}: {}", i + 1, e)))
+            .collect::<Result<_, _>>()?
+    };
+    if archives.is_empty() {
+        return Err("The file contains no meetings".to_string());
+    }
+    archives.into_iter().map(check_version).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive_json(version: u32) -> String {
+        serde_json::json!({
+            "format": ARCHIVE_FORMAT,
+            "version": version,
+            "exported_at": "2025-11-05T10:00:00Z",
+            "meeting": {
+                "id": "meeting-1",
+                "title": "Planning",
+                "created_at": "2025-11-04T09:00:00Z",
+                "updated_at": "2025-11-04T10:00:00Z",
+                "archived_at": null,
+                "notes": null
+            },
+            "audio": null,
+            "summary": null
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_parse_archives_accepts_object_array_and_lines() {
+        let one = archive_json(1);
+        assert_eq!(parse_archives(&one).unwrap().len(), 1);
+        assert_eq!(parse_archives(&format!("[{},{}]", one, one)).unwrap().len(), 2);
+        let archives = parse_archives(&format!("{}\n\n{}\n", one, one)).unwrap();
+        assert_eq!(archives.len(), 2);
+        assert_eq!(archives[0].meeting.title, "Planning");
+        assert!(archives[0].segments.is_empty());
+    }
+
+    #[test]
+    fn test_parse_archives_rejects_newer_versions() {
+        assert!(parse_archives(&archive_json(ARCHIVE_VERSION + 1)).unwrap_err().contains("newer"));
+        assert!(parse_archives(&format!("{}\nnot json", archive_json(1))).unwrap_err().contains("line 2"));
+        assert!(parse_archives("").is_err());
+    }
+}
@@ -0,0 +1,160 @@
+// export/commands.rs
+//
+// Tauri commands for exporting meetings.
+
+use crate::database::repositories::export_artifact::ExportArtifactsRepository;
+use crate::database::repositories::meeting_archive::MeetingArchivesRepository;
+use crate::diarization::commands::models_dir;
+use crate::export::archive::{parse_archives, MeetingArchive};
+use crate::export::document::{ExportDocument, ExportSection, ExportTemplate};
+use crate::export::docx::render_docx;
+use crate::export::markdown::render_markdown;
+use crate::export::pdf::render_pdf;
+use crate::export::subtitles::{build_cues, render_srt, render_vtt};
+use crate::library::deletion::sha256_hex;
+use crate::qa::index::queue_indexing;
+use crate::state::AppState;
+use serde::Deserialize;
+use tauri::{AppHandle, Runtime};
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Markdown,
+    Pdf,
+    Docx,
+    /// SubRip captions of the transcript
+    Srt,
+    /// WebVTT captions of the transcript
+    Vtt,
+    /// The whole meeting as a meeting archive, for `import_meetings`
+    Json,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExportOptions {
+    /// Sections in the order to write them; all of them when omitted
+    pub sections: Option<Vec<ExportSection>>,
+    /// Header and footer; a footer with the title and date when omitted
+    pub template: Option<ExportTemplate>,
+    /// Name the speaker of each caption; defaults to true
+    pub speaker_labels: Option<bool>,
+}
+
+/// Writes a meeting to `path` as Markdown, PDF, DOCX or captions; returns the path written
+///
+/// Captions (SRT, VTT) contain the transcript only and JSON the whole meeting; sections and
+/// template only apply to the document formats.
+#[tauri::command]
+pub async fn export_meeting(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    format: ExportFormat,
+    path: String,
+    options: Option<ExportOptions>,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    let sections = options.sections.unwrap_or_else(|| ExportSection::ALL.to_vec());
+    if sections.is_empty() {
+        return Err("Select at least one section to export".to_string());
+    }
+    let template = options.template.unwrap_or_default();
+
+    let pool = state.db_manager.pool();
+    let bytes = match format {
+        ExportFormat::Json => {
+            let archive = MeetingArchive::load(pool, &meeting_id).await?;
+            serde_json::to_vec_pretty(&archive).map_err(|e| format!("Failed to serialize meeting: {}", e))?
+        }
+        ExportFormat::Srt | ExportFormat::Vtt => {
+            let document = ExportDocument::load(pool, &meeting_id).await?;
+            let cues = build_cues(&document.transcript);
+            if cues.is_empty() {
+                return Err("This meeting has no timed transcript to make captions from".to_string());
+            }
+            let speaker_labels = options.speaker_labels.unwrap_or(true);
+            match format {
+                ExportFormat::Srt => render_srt(&cues, speaker_labels).into_bytes(),
+                _ => render_vtt(&cues, speaker_labels).into_bytes(),
+            }
+        }
+        ExportFormat::Markdown | ExportFormat::Pdf | ExportFormat::Docx => {
+            let document = ExportDocument::load(pool, &meeting_id).await?;
+            match format {
+                ExportFormat::Markdown => render_markdown(&document, &sections, &template).into_bytes(),
+                ExportFormat::Pdf => render_pdf(&document, &sections, &template)?,
+                _ => render_docx(&document, &sections, &template)?,
+            }
+        }
+    };
+    let sha256 = sha256_hex(&bytes);
+    tokio::fs::write(&path, bytes)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    info!("Exported meeting {} as {:?} to {}", meeting_id, format, path);
+
+    // So deleting the meeting can delete the export too
+    let format_name = format!("{:?}", format).to_lowercase();
+    if let Err(e) = ExportArtifactsRepository::record(pool, &meeting_id, &path, &format_name, &sha256).await {
+        warn!("Failed to record the export of meeting {}: {}", meeting_id, e);
+    }
+    Ok(path)
+}
+
+/// Writes meeting archives to `path` as JSON Lines, one meeting per line; returns how many
+#[tauri::command]
+pub async fn export_meetings_jsonl(
+    state: tauri::State<'_, AppState>,
+    meeting_ids: Vec<String>,
+    path: String,
+) -> Result<usize, String> {
+    if meeting_ids.is_empty() {
+        return Err("Select at least one meeting to export".to_string());
+    }
+    let pool = state.db_manager.pool();
+    let mut lines = String::new();
+    for meeting_id in &meeting_ids {
+        let archive = MeetingArchive::load(pool, meeting_id).await?;
+        let line = serde_json::to_string(&archive).map_err(|e| format!("Failed to serialize meeting: {}", e))?;
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+    tokio::fs::write(&path, lines)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    info!("Exported {} meeting(s) as JSON Lines to {}", meeting_ids.len(), path);
+    Ok(meeting_ids.len())
+}
+
+/// Imports the meetings of a JSON or JSON Lines archive as new meetings; returns their ids
+///
+/// Nothing is imported when any meeting in the file cannot be read.
+#[tauri::command]
+pub async fn import_meetings<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    path: String,
+) -> Result<Vec<String>, String> {
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let archives = parse_archives(&content)?;
+
+    let pool = state.db_manager.pool();
+    let mut meeting_ids = Vec::with_capacity(archives.len());
+    for archive in &archives {
+        let meeting_id = MeetingArchivesRepository::import(pool, archive)
+            .await
+            .map_err(|e| format!("Failed to import \"{}\": {}", archive.meeting.title, e))?;
+        meeting_ids.push(meeting_id);
+    }
+    info!("Imported {} meeting(s) from {}", meeting_ids.len(), path);
+
+    if let Ok(models_dir) = models_dir(&app) {
+        for meeting_id in &meeting_ids {
+            queue_indexing(&app, pool, &models_dir, Some(meeting_id.clone())).await;
+        }
+    }
+    Ok(meeting_ids)
+}
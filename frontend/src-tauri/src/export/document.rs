@@ -0,0 +1,329 @@
+// export/document.rs
+//
+// The contents of an exported meeting, independent of the output format.
+
+use crate::audio::transcription::TranscriptWord;
+use crate::database::models::ActionItem;
+use crate::database::models::ConfidentialRange;
+use crate::database::repositories::action_item::ActionItemsRepository;
+use crate::database::repositories::confidential_range::ConfidentialRangesRepository;
+use crate::database::repositories::meeting_library::MeetingLibraryRepository;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::redaction::{self, RedactionTarget};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportSection {
+    Summary,
+    ActionItems,
+    Transcript,
+}
+
+impl ExportSection {
+    pub const ALL: [ExportSection; 3] = [ExportSection::Summary, ExportSection::ActionItems, ExportSection::Transcript];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            ExportSection::Summary => "Summary",
+            ExportSection::ActionItems => "Action Items",
+            ExportSection::Transcript => "Transcript",
+        }
+    }
+}
+
+/// Header and footer lines
+///
+/// `{title}`, `{date}` and `{page}` are replaced; Markdown and DOCX have no page numbers,
+/// so `{page}` is left empty there.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportTemplate {
+    pub header: Option<String>,
+    pub footer: Option<String>,
+}
+
+impl Default for ExportTemplate {
+    fn default() -> Self {
+        Self { header: None, footer: Some("{title} · {date}".to_string()) }
+    }
+}
+
+/// A transcript segment as exported
+#[derive(Debug, Clone)]
+pub struct TranscriptLine {
+    /// Seconds into the recording
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    pub speaker: Option<String>,
+    pub text: String,
+    /// Word timings, when the engine reported them
+    pub words: Vec<TranscriptWord>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportDocument {
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    /// Summary markdown, if the meeting has been summarized
+    pub summary: Option<String>,
+    pub action_items: Vec<ActionItem>,
+    pub transcript: Vec<TranscriptLine>,
+}
+
+impl ExportDocument {
+    pub async fn load(pool: &SqlitePool, meeting_id: &str) -> Result<Self, String> {
+        let meeting = MeetingLibraryRepository::get(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load meeting: {}", e))?
+            .ok_or_else(|| format!("No meeting found with id {}", meeting_id))?;
+        let summary = SummaryProcessesRepository::get_summary_data(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load summary: {}", e))?
+            .and_then(|process| process.result)
+            .and_then(|result| summary_markdown(&result));
+        let action_items = ActionItemsRepository::list_for_meeting(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load action items: {}", e))?;
+        let mut transcript: Vec<TranscriptLine> = TranscriptsRepository::list_for_meeting(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load transcript: {}", e))?
+            .into_iter()
+            .filter(|t| !t.transcript.trim().is_empty())
+            .map(|t| TranscriptLine {
+                start: t.audio_start_time,
+                end: t.audio_end_time,
+                speaker: t.speaker,
+                text: t.transcript.trim().to_string(),
+                words: t.words.as_deref().and_then(|words| serde_json::from_str(words).ok()).unwrap_or_default(),
+            })
+            .collect();
+        let confidential = ConfidentialRangesRepository::list_for_meeting(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load confidential parts: {}", e))?;
+        insert_confidential_gaps(&mut transcript, &confidential);
+        let mut document =
+            Self { title: meeting.title, created_at: meeting.created_at.0, summary, action_items, transcript };
+        document.redact(pool, meeting_id).await?;
+        Ok(document)
+    }
+
+    /// Masks what the redaction settings ask for. Word timings of a changed line are dropped,
+    /// since they still hold the original words, and with name redaction speakers are numbered.
+    async fn redact(&mut self, pool: &SqlitePool, meeting_id: &str) -> Result<(), String> {
+        let settings = redaction::get_settings();
+        if !settings.enabled {
+            return Ok(());
+        }
+        if settings.names {
+            redaction::number_speakers(self.transcript.iter_mut().filter_map(|line| line.speaker.as_mut()));
+        }
+
+        let mut texts = vec![self.title.clone()];
+        texts.extend(self.summary.clone());
+        for item in &self.action_items {
+            texts.push(item.text.clone());
+            texts.push(item.assignee.clone().unwrap_or_default());
+        }
+        texts.extend(self.transcript.iter().map(|line| line.text.clone()));
+        let mut redacted =
+            redaction::redact_for_meeting(pool, meeting_id, RedactionTarget::Export, texts).await?.into_iter();
+        let mut next = || redacted.next().unwrap_or_default();
+
+        self.title = next();
+        if let Some(summary) = self.summary.as_mut() {
+            *summary = next();
+        }
+        for item in &mut self.action_items {
+            item.text = next();
+            item.assignee = Some(next()).filter(|assignee| !assignee.is_empty());
+        }
+        for line in &mut self.transcript {
+            let text = next();
+            if text != line.text {
+                line.text = text;
+                line.words.clear();
+            }
+        }
+        Ok(())
+    }
+
+    /// The meeting date as shown in exports, in local time
+    pub fn date(&self) -> String {
+        self.created_at.with_timezone(&Local).format("%B %-d, %Y %H:%M").to_string()
+    }
+
+    /// Fills in a header or footer template
+    pub fn fill(&self, template: &str, page: Option<usize>) -> String {
+        template
+            .replace("{title}", &self.title)
+            .replace("{date}", &self.date())
+            .replace("{page}", &page.map(|page| page.to_string()).unwrap_or_default())
+            .trim()
+            .to_string()
+    }
+}
+
+/// The markdown of a stored summary result: `{ "markdown": ... }` or the text itself
+pub fn summary_markdown(result: &str) -> Option<String> {
+    let markdown = match serde_json::from_str::<serde_json::Value>(result) {
+        Ok(serde_json::Value::Object(object)) => object.get("markdown")?.as_str()?.to_string(),
+        _ => result.to_string(),
+    };
+    let markdown = markdown.trim();
+    (!markdown.is_empty()).then(|| markdown.to_string())
+}
+
+/// An action item as one line of text, e.g. `Send the deck (Alice, due 2025-11-03)`
+pub fn action_item_text(item: &ActionItem) -> String {
+    let details: Vec<String> = item
+        .assignee
+        .iter()
+        .cloned()
+        .chain(item.due_date.iter().map(|due| format!("due {}", due)))
+        .collect();
+    if details.is_empty() {
+        item.text.clone()
+    } else {
+        format!("{} ({})", item.text, details.join(", "))
+    }
+}
+
+/// A block of summary markdown, for formats that lay text out themselves
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// Level 1 for `#`
+    Heading(usize, String),
+    Bullet(String),
+    Paragraph(String),
+}
+
+/// Drops the emphasis, code and link markup of a line of markdown
+pub fn plain_text(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' | '`' => {}
+            '_' if plain.is_empty() || plain.ends_with(' ') || chars.peek().map_or(true, |next| *next == ' ') => {}
+            // `[label](url)` keeps the label
+            ']' if chars.peek() == Some(&'(') => {
+                for next in chars.by_ref() {
+                    if next == ')' {
+                        break;
+                    }
+                }
+            }
+            '[' if is_link(chars.clone()) => {}
+            c => plain.push(c),
+        }
+    }
+    plain
+}
+
+/// Whether the text after a `[` closes it as a link label, so `[name]` alone is kept
+fn is_link(mut rest: impl Iterator<Item = char>) -> bool {
+    rest.any(|c| c == ']') && rest.next() == Some('(')
+}
+
+/// Splits markdown into headings, bullets and paragraphs; consecutive lines form one paragraph
+pub fn parse_markdown(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let flush = |paragraph: &mut Vec<String>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(plain_text(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+    };
+
+    for line in markdown.lines() {
+        let line = line.trim();
+        let hashes = line.chars().take_while(|c| *c == '#').count();
+        if line.is_empty() || line == "---" {
+            flush(&mut paragraph, &mut blocks);
+        } else if hashes > 0 && line[hashes..].starts_with(' ') {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Heading(hashes, plain_text(line[hashes..].trim())));
+        } else if let Some(item) = ["- ", "* ", "+ "].iter().find_map(|marker| line.strip_prefix(marker)) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Bullet(plain_text(item.trim())));
+        } else if let Some(item) = numbered_item(line) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Bullet(plain_text(item)));
+        } else if !line.starts_with('|') || !line.trim_matches(|c| c == '|' || c == '-' || c == ':' || c == ' ').is_empty() {
+            // Table rows read as text; their separator rows are dropped
+            paragraph.push(line.to_string());
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// The text of a `1. item` line
+fn numbered_item(line: &str) -> Option<&str> {
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    line[digits..].strip_prefix(". ").map(str::trim)
+}
+
+/// Puts a line marking each confidential part where it was cut from the transcript
+fn insert_confidential_gaps(transcript: &mut Vec<TranscriptLine>, ranges: &[ConfidentialRange]) {
+    for range in ranges {
+        let index = transcript
+            .iter()
+            .position(|line| line.start.is_some_and(|start| start >= range.audio_start))
+            .unwrap_or(transcript.len());
+        let gap = TranscriptLine {
+            start: Some(range.audio_start),
+            end: Some(range.audio_end),
+            speaker: None,
+            text: crate::library::confidential::GAP_TEXT.to_string(),
+            words: Vec::new(),
+        };
+        transcript.insert(index, gap);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_markdown() {
+        let markdown = "# Weekly sync\n\nWe **agreed** on the\n[plan](https://example.com).\n\n## Next\n- Ship _it_\n2. Review\n|---|---|\n| a | b |";
+        assert_eq!(
+            parse_markdown(markdown),
+            vec![
+                Block::Heading(1, "Weekly sync".to_string()),
+                Block::Paragraph("We agreed on the plan.".to_string()),
+                Block::Heading(2, "Next".to_string()),
+                Block::Bullet("Ship it".to_string()),
+                Block::Bullet("Review".to_string()),
+                Block::Paragraph("| a | b |".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plain_text_keeps_snake_case() {
+        assert_eq!(plain_text("rename `user_id` in user_profile"), "rename user_id in user_profile");
+    }
+
+    #[test]
+    fn test_plain_text_links() {
+        assert_eq!(plain_text("See [the docs](https://example.com) today"), "See the docs today");
+        assert_eq!(plain_text("[name] asked for [the deck](https://example.com)"), "[name] asked for the deck");
+    }
+
+    #[test]
+    fn test_summary_markdown() {
+        assert_eq!(summary_markdown(r##"{"markdown":"# Notes","summary_json":[]}"##).as_deref(), Some("# Notes"));
+        assert_eq!(summary_markdown("Plain summary\n").as_deref(), Some("Plain summary"));
+        assert_eq!(summary_markdown(r#"{"summary_json":[]}"#), None);
+    }
+}
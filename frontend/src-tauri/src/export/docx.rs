@@ -0,0 +1,90 @@
+// export/docx.rs
+//
+// Renders an exported meeting as a Word document.
+
+use crate::export::document::{action_item_text, parse_markdown, Block, ExportDocument, ExportSection, ExportTemplate};
+use crate::utils::format_timestamp;
+use docx_rs::{Docx, Footer, Header, Paragraph, Run};
+use std::io::Cursor;
+
+/// Font sizes are in half-points
+const BODY_SIZE: usize = 21;
+const SMALL_SIZE: usize = 16;
+
+fn heading(level: usize, text: &str) -> Paragraph {
+    let size = match level {
+        1 => 36,
+        2 => 28,
+        _ => 24,
+    };
+    Paragraph::new().add_run(Run::new().add_text(text).bold().size(size))
+}
+
+fn paragraph(text: &str) -> Paragraph {
+    Paragraph::new().add_run(Run::new().add_text(text).size(BODY_SIZE))
+}
+
+fn bullet(marker: &str, text: &str) -> Paragraph {
+    Paragraph::new().add_run(Run::new().add_text(format!("{} {}", marker, text)).size(BODY_SIZE))
+}
+
+fn small(text: &str) -> Paragraph {
+    Paragraph::new().add_run(Run::new().add_text(text).size(SMALL_SIZE))
+}
+
+pub fn render_docx(document: &ExportDocument, sections: &[ExportSection], template: &ExportTemplate) -> Result<Vec<u8>, String> {
+    let mut docx = Docx::new();
+    if let Some(header) = &template.header {
+        docx = docx.header(Header::new().add_paragraph(small(&document.fill(header, None))));
+    }
+    if let Some(footer) = &template.footer {
+        docx = docx.footer(Footer::new().add_paragraph(small(&document.fill(footer, None))));
+    }
+
+    docx = docx.add_paragraph(heading(1, &document.title)).add_paragraph(paragraph(&document.date()));
+    for section in sections {
+        docx = docx.add_paragraph(heading(2, section.title()));
+        match section {
+            ExportSection::Summary => match &document.summary {
+                Some(summary) => {
+                    for block in parse_markdown(summary) {
+                        docx = docx.add_paragraph(match block {
+                            Block::Heading(level, text) => heading(level + 2, &text),
+                            Block::Bullet(text) => bullet("\u{2022}", &text),
+                            Block::Paragraph(text) => paragraph(&text),
+                        });
+                    }
+                }
+                None => docx = docx.add_paragraph(paragraph("No summary yet.")),
+            },
+            ExportSection::ActionItems => {
+                if document.action_items.is_empty() {
+                    docx = docx.add_paragraph(paragraph("No action items."));
+                }
+                for item in &document.action_items {
+                    let marker = if item.completed { "\u{2611}" } else { "\u{2610}" };
+                    docx = docx.add_paragraph(bullet(marker, &action_item_text(item)));
+                }
+            }
+            ExportSection::Transcript => {
+                if document.transcript.is_empty() {
+                    docx = docx.add_paragraph(paragraph("No transcript."));
+                }
+                for line in &document.transcript {
+                    let mut segment = Paragraph::new();
+                    if let Some(start) = line.start {
+                        segment = segment.add_run(Run::new().add_text(format!("{}  ", format_timestamp(start))).size(SMALL_SIZE));
+                    }
+                    if let Some(speaker) = &line.speaker {
+                        segment = segment.add_run(Run::new().add_text(format!("{}: ", speaker)).bold().size(BODY_SIZE));
+                    }
+                    docx = docx.add_paragraph(segment.add_run(Run::new().add_text(&line.text).size(BODY_SIZE)));
+                }
+            }
+        }
+    }
+
+    let mut buffer = Cursor::new(Vec::new());
+    docx.build().pack(&mut buffer).map_err(|e| format!("Failed to write DOCX: {}", e))?;
+    Ok(buffer.into_inner())
+}
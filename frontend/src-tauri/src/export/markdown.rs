@@ -0,0 +1,73 @@
+// export/markdown.rs
+//
+// Renders an exported meeting as Markdown.
+
+use crate::export::document::{action_item_text, ExportDocument, ExportSection, ExportTemplate};
+use crate::utils::format_timestamp;
+
+/// Moves every heading one level down, so the summary's headings sit below its section heading
+fn demote_headings(markdown: &str) -> String {
+    markdown
+        .lines()
+        .map(|line| if line.starts_with('#') { format!("#{}", line) } else { line.to_string() })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn render_markdown(document: &ExportDocument, sections: &[ExportSection], template: &ExportTemplate) -> String {
+    let mut out = String::new();
+    if let Some(header) = template.header.as_deref().map(|header| document.fill(header, None)).filter(|h| !h.is_empty()) {
+        out.push_str(&format!("_{}_\n\n", header));
+    }
+    out.push_str(&format!("# {}\n\n", document.title));
+    out.push_str(&format!("**Date:** {}\n", document.date()));
+
+    for section in sections {
+        out.push_str(&format!("\n## {}\n\n", section.title()));
+        match section {
+            ExportSection::Summary => match &document.summary {
+                Some(summary) => out.push_str(&format!("{}\n", demote_headings(summary))),
+                None => out.push_str("_No summary yet._\n"),
+            },
+            ExportSection::ActionItems => {
+                if document.action_items.is_empty() {
+                    out.push_str("_No action items._\n");
+                }
+                for item in &document.action_items {
+                    let check = if item.completed { "x" } else { " " };
+                    out.push_str(&format!("- [{}] {}\n", check, action_item_text(item)));
+                }
+            }
+            ExportSection::Transcript => {
+                if document.transcript.is_empty() {
+                    out.push_str("_No transcript._\n");
+                }
+                for line in &document.transcript {
+                    let mut prefix = String::new();
+                    if let Some(start) = line.start {
+                        prefix.push_str(&format!("`{}` ", format_timestamp(start)));
+                    }
+                    if let Some(speaker) = &line.speaker {
+                        prefix.push_str(&format!("**{}:** ", speaker));
+                    }
+                    out.push_str(&format!("{}{}\n\n", prefix, line.text));
+                }
+            }
+        }
+    }
+
+    if let Some(footer) = template.footer.as_deref().map(|footer| document.fill(footer, None)).filter(|f| !f.is_empty()) {
+        out.push_str(&format!("\n---\n\n_{}_\n", footer));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demote_headings() {
+        assert_eq!(demote_headings("# Title\ntext #1\n## Part"), "## Title\ntext #1\n### Part");
+    }
+}
@@ -0,0 +1,24 @@
+/// Export module - meetings as Markdown, PDF and Word documents, and captions
+///
+/// A meeting's summary, action items and transcript are gathered into one document and
+/// rendered to the chosen format. Callers pick which sections to include and can set a
+/// header and footer template, e.g. `{title} · {date}` or `Page {page}`. The transcript
+/// can also be exported as SRT or WebVTT captions to go with the recording, and whole
+/// meetings as JSON archives that `import_meetings` reads back on another machine.
+///
+/// This module contains:
+/// - The JSON meeting archive format (`archive`), see docs/meeting-archive.schema.json
+/// - Loading a meeting and parsing its summary markdown into blocks (`document`)
+/// - Markdown rendering (`markdown`)
+/// - PDF rendering with the built-in PDF fonts (`pdf`)
+/// - DOCX rendering (`docx`)
+/// - SRT and WebVTT captions (`subtitles`)
+/// - Tauri commands for frontend integration
+
+pub mod archive;
+pub mod commands;
+pub mod document;
+pub mod docx;
+pub mod markdown;
+pub mod pdf;
+pub mod subtitles;
@@ -0,0 +1,240 @@
+// export/pdf.rs
+//
+// Renders an exported meeting as an A4 PDF.
+//
+// Text is set in the PDF's built-in Helvetica, so no font files need to ship with the app.
+// Those fonts only cover Latin-1; other characters are replaced (see `latin1`).
+
+use crate::export::document::{action_item_text, parse_markdown, Block, ExportDocument, ExportSection, ExportTemplate};
+use crate::utils::format_timestamp;
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference};
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const BODY_SIZE: f32 = 10.5;
+const SMALL_SIZE: f32 = 8.0;
+const MM_PER_POINT: f32 = 0.3528;
+/// Average Helvetica glyph width as a fraction of the font size
+const AVERAGE_GLYPH_WIDTH: f32 = 0.5;
+/// Room for a bullet or checkbox before the text
+const BULLET_INDENT: f32 = 8.0;
+
+/// `text` with the characters the built-in fonts lack swapped for close Latin-1 ones
+fn latin1(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\u{2018}' | '\u{2019}' => out.push('\''),
+            '\u{201C}' | '\u{201D}' => out.push('"'),
+            '\u{2013}' | '\u{2014}' => out.push('-'),
+            '\u{2026}' => out.push_str("..."),
+            '\u{2022}' => out.push('\u{B7}'),
+            c if (c as u32) < 0x100 => out.push(c),
+            _ => out.push('?'),
+        }
+    }
+    out
+}
+
+/// Breaks `text` into lines of at most `max_chars`, at spaces where possible
+fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        // Words longer than a line are split
+        while word.len() > max_chars {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word.drain(..max_chars).collect());
+        }
+        let word: String = word.into_iter().collect();
+        if word.is_empty() {
+            continue;
+        }
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Lays text out top to bottom, starting a new page when one is full
+struct Writer<'a> {
+    pdf: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    /// Distance of the next line's baseline from the bottom of the page
+    y: f32,
+    page: usize,
+    document: &'a ExportDocument,
+    template: &'a ExportTemplate,
+}
+
+impl<'a> Writer<'a> {
+    fn new(document: &'a ExportDocument, template: &'a ExportTemplate) -> Result<Self, String> {
+        let (pdf, page, layer) = PdfDocument::new(latin1(&document.title), Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        let regular = pdf
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(|e| format!("Failed to load PDF font: {}", e))?;
+        let bold = pdf
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(|e| format!("Failed to load PDF font: {}", e))?;
+        let layer = pdf.get_page(page).get_layer(layer);
+        let writer = Self { pdf, layer, regular, bold, y: PAGE_HEIGHT - MARGIN, page: 1, document, template };
+        writer.decorate_page();
+        Ok(writer)
+    }
+
+    /// Header and footer of the current page
+    fn decorate_page(&self) {
+        let small = SMALL_SIZE * MM_PER_POINT;
+        if let Some(header) = &self.template.header {
+            let header = self.document.fill(header, Some(self.page));
+            self.layer.use_text(latin1(&header), SMALL_SIZE, Mm(MARGIN), Mm(PAGE_HEIGHT - MARGIN / 2.0 - small), &self.regular);
+        }
+        if let Some(footer) = &self.template.footer {
+            let footer = self.document.fill(footer, Some(self.page));
+            self.layer.use_text(latin1(&footer), SMALL_SIZE, Mm(MARGIN), Mm(MARGIN / 2.0), &self.regular);
+        }
+    }
+
+    fn new_page(&mut self) {
+        let (page, layer) = self.pdf.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        self.layer = self.pdf.get_page(page).get_layer(layer);
+        self.page += 1;
+        self.y = PAGE_HEIGHT - MARGIN;
+        self.decorate_page();
+    }
+
+    /// A wrapped run of text; `indent` in mm from the left margin
+    fn text(&mut self, text: &str, size: f32, bold: bool, indent: f32) {
+        let line_height = size * MM_PER_POINT * 1.4;
+        let width = PAGE_WIDTH - 2.0 * MARGIN - indent;
+        let max_chars = (width / (size * MM_PER_POINT * AVERAGE_GLYPH_WIDTH)) as usize;
+        let font = if bold { self.bold.clone() } else { self.regular.clone() };
+        for line in wrap_text(&latin1(text), max_chars.max(10)) {
+            if self.y - line_height < MARGIN {
+                self.new_page();
+            }
+            self.y -= line_height;
+            self.layer.use_text(line, size, Mm(MARGIN + indent), Mm(self.y), &font);
+        }
+    }
+
+    fn space(&mut self, mm: f32) {
+        self.y -= mm;
+    }
+
+    fn heading(&mut self, level: usize, text: &str) {
+        let size = match level {
+            1 => 18.0,
+            2 => 14.0,
+            _ => 12.0,
+        };
+        // Keep a heading together with at least a few lines of what follows
+        if self.y - 4.0 * size * MM_PER_POINT < MARGIN {
+            self.new_page();
+        }
+        self.space(size * MM_PER_POINT * 0.6);
+        self.text(text, size, true, 0.0);
+        self.space(1.5);
+    }
+
+    fn bullet(&mut self, marker: &str, text: &str) {
+        let line_height = BODY_SIZE * MM_PER_POINT * 1.4;
+        if self.y - line_height < MARGIN {
+            self.new_page();
+        }
+        let font = self.regular.clone();
+        self.layer.use_text(marker, BODY_SIZE, Mm(MARGIN), Mm(self.y - line_height), &font);
+        self.text(text, BODY_SIZE, false, BULLET_INDENT);
+    }
+
+    fn finish(self) -> Result<Vec<u8>, String> {
+        self.pdf.save_to_bytes().map_err(|e| format!("Failed to write PDF: {}", e))
+    }
+}
+
+pub fn render_pdf(document: &ExportDocument, sections: &[ExportSection], template: &ExportTemplate) -> Result<Vec<u8>, String> {
+    let mut writer = Writer::new(document, template)?;
+    writer.heading(1, &document.title);
+    writer.text(&document.date(), BODY_SIZE, false, 0.0);
+
+    for section in sections {
+        writer.space(4.0);
+        writer.heading(2, section.title());
+        match section {
+            ExportSection::Summary => match &document.summary {
+                Some(summary) => {
+                    for block in parse_markdown(summary) {
+                        match block {
+                            Block::Heading(level, text) => writer.heading(level + 2, &text),
+                            Block::Bullet(text) => writer.bullet("\u{B7}", &text),
+                            Block::Paragraph(text) => {
+                                writer.text(&text, BODY_SIZE, false, 0.0);
+                                writer.space(2.0);
+                            }
+                        }
+                    }
+                }
+                None => writer.text("No summary yet.", BODY_SIZE, false, 0.0),
+            },
+            ExportSection::ActionItems => {
+                if document.action_items.is_empty() {
+                    writer.text("No action items.", BODY_SIZE, false, 0.0);
+                }
+                for item in &document.action_items {
+                    writer.bullet(if item.completed { "[x]" } else { "[ ]" }, &action_item_text(item));
+                }
+            }
+            ExportSection::Transcript => {
+                if document.transcript.is_empty() {
+                    writer.text("No transcript.", BODY_SIZE, false, 0.0);
+                }
+                for line in &document.transcript {
+                    let mut label = Vec::new();
+                    if let Some(start) = line.start {
+                        label.push(format_timestamp(start));
+                    }
+                    if let Some(speaker) = &line.speaker {
+                        label.push(speaker.clone());
+                    }
+                    if !label.is_empty() {
+                        writer.text(&label.join("  "), SMALL_SIZE, true, 0.0);
+                    }
+                    writer.text(&line.text, BODY_SIZE, false, 0.0);
+                    writer.space(2.0);
+                }
+            }
+        }
+    }
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(wrap_text("the quick brown fox", 10), vec!["the quick", "brown fox"]);
+        assert_eq!(wrap_text("abcdefghijkl xy", 5), vec!["abcde", "fghij", "kl xy"]);
+        assert!(wrap_text("   ", 5).is_empty());
+    }
+
+    #[test]
+    fn test_latin1() {
+        assert_eq!(latin1("It\u{2019}s \u{201C}done\u{201D} \u{2014} caf\u{e9} \u{2713}"), "It's \"done\" - caf\u{e9} ?");
+    }
+}
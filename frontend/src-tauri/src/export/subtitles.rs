@@ -0,0 +1,218 @@
+// export/subtitles.rs
+//
+// SRT and WebVTT captions from a meeting's transcript.
+//
+// With word timings a segment is split into short cues that follow the speech; without
+// them each segment becomes one cue spanning its recording timestamps. Segments that were
+// never placed on the recording's timeline (no start time) are left out.
+
+use crate::audio::transcription::TranscriptWord;
+use crate::export::document::TranscriptLine;
+
+/// Characters per caption line, and lines per cue, as common subtitle guidelines suggest
+const LINE_CHARS: usize = 42;
+const MAX_LINES: usize = 2;
+/// Longest a word-timed cue stays on screen, in seconds
+const MAX_CUE_SECONDS: f64 = 6.0;
+/// Reading speed used when a segment has no end time, in characters per second
+const READING_CHARS_PER_SECOND: f64 = 15.0;
+const MIN_CUE_SECONDS: f64 = 1.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    /// Seconds into the recording
+    pub start: f64,
+    pub end: f64,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+/// Groups words into cues that fit on screen, breaking after sentences where possible
+fn word_cues(words: &[TranscriptWord], speaker: Option<&str>) -> Vec<Cue> {
+    let mut cues = Vec::new();
+    let mut current: Vec<&TranscriptWord> = Vec::new();
+    let flush = |current: &mut Vec<&TranscriptWord>, cues: &mut Vec<Cue>| {
+        if let (Some(first), Some(last)) = (current.first(), current.last()) {
+            cues.push(Cue {
+                start: first.start,
+                end: last.end,
+                speaker: speaker.map(str::to_string),
+                text: current.iter().map(|word| word.text.as_str()).collect::<Vec<_>>().join(" "),
+            });
+        }
+        current.clear();
+    };
+
+    for word in words.iter().filter(|word| !word.text.trim().is_empty()) {
+        if let Some(first) = current.first() {
+            let chars: usize = current.iter().map(|w| w.text.chars().count() + 1).sum::<usize>() + word.text.chars().count();
+            if chars > LINE_CHARS * MAX_LINES || word.end - first.start > MAX_CUE_SECONDS {
+                flush(&mut current, &mut cues);
+            }
+        }
+        current.push(word);
+        // A sentence ending well into a cue closes it
+        let chars: usize = current.iter().map(|w| w.text.chars().count() + 1).sum();
+        if word.text.ends_with(['.', '?', '!']) && chars > LINE_CHARS / 2 {
+            flush(&mut current, &mut cues);
+        }
+    }
+    flush(&mut current, &mut cues);
+    cues
+}
+
+/// Cues for the whole transcript, in time order and without overlaps
+pub fn build_cues(lines: &[TranscriptLine]) -> Vec<Cue> {
+    let mut timed: Vec<&TranscriptLine> = lines.iter().filter(|line| line.start.is_some()).collect();
+    timed.sort_by(|a, b| a.start.unwrap_or(0.0).total_cmp(&b.start.unwrap_or(0.0)));
+
+    let mut cues = Vec::new();
+    for line in timed {
+        let start = line.start.unwrap_or(0.0);
+        if line.words.is_empty() {
+            let estimated = start + (line.text.chars().count() as f64 / READING_CHARS_PER_SECOND).max(MIN_CUE_SECONDS);
+            cues.push(Cue {
+                start,
+                end: line.end.filter(|end| *end > start).unwrap_or(estimated),
+                speaker: line.speaker.clone(),
+                text: line.text.clone(),
+            });
+        } else {
+            cues.extend(word_cues(&line.words, line.speaker.as_deref()));
+        }
+    }
+
+    // A cue ends at the latest when the next one starts
+    for i in 1..cues.len() {
+        let next_start = cues[i].start;
+        let cue = &mut cues[i - 1];
+        if cue.end > next_start {
+            cue.end = next_start.max(cue.start);
+        }
+    }
+    cues.retain(|cue| cue.end > cue.start);
+    cues
+}
+
+/// Breaks cue text into at most two balanced lines
+fn caption_lines(text: &str) -> String {
+    if text.chars().count() <= LINE_CHARS {
+        return text.to_string();
+    }
+    let middle = text.len() / 2;
+    let split = text
+        .match_indices(' ')
+        .map(|(i, _)| i)
+        .min_by_key(|i| i.abs_diff(middle));
+    match split {
+        Some(i) => format!("{}\n{}", &text[..i], &text[i + 1..]),
+        None => text.to_string(),
+    }
+}
+
+/// `hh:mm:ss` and milliseconds joined by `separator`
+fn timestamp(seconds: f64, separator: char) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    let (hours, minutes, secs, millis) = (millis / 3_600_000, millis / 60_000 % 60, millis / 1000 % 60, millis % 1000);
+    format!("{:02}:{:02}:{:02}{}{:03}", hours, minutes, secs, separator, millis)
+}
+
+/// SubRip; speakers are written before the text
+pub fn render_srt(cues: &[Cue], speaker_labels: bool) -> String {
+    let mut out = String::new();
+    for (i, cue) in cues.iter().enumerate() {
+        let text = match (&cue.speaker, speaker_labels) {
+            (Some(speaker), true) => format!("{}: {}", speaker, cue.text),
+            _ => cue.text.clone(),
+        };
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            timestamp(cue.start, ','),
+            timestamp(cue.end, ','),
+            caption_lines(&text)
+        ));
+    }
+    out
+}
+
+/// Text as WebVTT cue text, where `&` and `<` start markup
+fn escape_vtt(text: &str) -> String {
+    // `&` first, or the entities added for `<` and `>` would be escaped again
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// WebVTT; speakers become voice tags, which players can show or style
+pub fn render_vtt(cues: &[Cue], speaker_labels: bool) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for cue in cues {
+        let text = caption_lines(&escape_vtt(&cue.text));
+        let text = match (&cue.speaker, speaker_labels) {
+            (Some(speaker), true) => format!("<v {}>{}", escape_vtt(speaker), text),
+            _ => text,
+        };
+        out.push_str(&format!("{} --> {}\n{}\n\n", timestamp(cue.start, '.'), timestamp(cue.end, '.'), text));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start: f64, end: f64) -> TranscriptWord {
+        TranscriptWord::new(text.to_string(), start, end, None)
+    }
+
+    fn line(start: f64, end: f64, text: &str, words: Vec<TranscriptWord>) -> TranscriptLine {
+        TranscriptLine { start: Some(start), end: Some(end), speaker: Some("Alice".to_string()), text: text.to_string(), words }
+    }
+
+    #[test]
+    fn test_cues_from_words_break_after_sentences() {
+        let words = vec![
+            word("We", 0.0, 0.2),
+            word("shipped", 0.2, 0.6),
+            word("the", 0.6, 0.7),
+            word("release", 0.7, 1.1),
+            word("yesterday.", 1.1, 1.8),
+            word("Next", 2.0, 2.3),
+            word("week", 2.3, 2.6),
+        ];
+        let cues = build_cues(&[line(0.0, 3.0, "", words)]);
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].text, "We shipped the release yesterday.");
+        assert_eq!((cues[1].start, cues[1].end), (2.0, 2.6));
+    }
+
+    #[test]
+    fn test_segment_cues_do_not_overlap() {
+        let mut untimed = line(0.0, 0.0, "lost", Vec::new());
+        untimed.start = None;
+        let cues = build_cues(&[line(4.0, 9.0, "Second", Vec::new()), line(0.0, 5.0, "First", Vec::new()), untimed]);
+        assert_eq!(cues.iter().map(|c| (c.start, c.end)).collect::<Vec<_>>(), vec![(0.0, 4.0), (4.0, 9.0)]);
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let cues = vec![Cue { start: 3661.5, end: 3663.0, speaker: Some("Bob".to_string()), text: "Hi <all>".to_string() }];
+        assert_eq!(render_srt(&cues, true), "1\n01:01:01,500 --> 01:01:03,000\nBob: Hi <all>\n\n");
+        assert_eq!(render_vtt(&cues, true), "WEBVTT\n\n01:01:01.500 --> 01:01:03.000\n<v Bob>Hi &lt;all&gt;\n\n");
+        assert_eq!(render_vtt(&cues, false), "WEBVTT\n\n01:01:01.500 --> 01:01:03.000\nHi &lt;all&gt;\n\n");
+    }
+
+    #[test]
+    fn test_vtt_escapes_ampersands() {
+        let cues = vec![Cue { start: 0.0, end: 1.0, speaker: Some("R&D".to_string()), text: "Q&A at 3 < 4 &lt;".to_string() }];
+        assert_eq!(
+            render_vtt(&cues, true),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:01.000\n<v R&amp;D>Q&amp;A at 3 &lt; 4 &amp;lt;\n\n"
+        );
+    }
+
+    #[test]
+    fn test_caption_lines_split_near_middle() {
+        let text = "this caption is long enough that it needs two lines";
+        assert_eq!(caption_lines(text), "this caption is long enough\nthat it needs two lines");
+    }
+}
@@ -0,0 +1,272 @@
+/// Headless module - the recording pipeline without the app window
+///
+/// Records from a microphone, transcribes audio files and turns transcripts into Markdown
+/// minutes using the same building blocks as the app: the capture backends, the VAD, the
+/// Whisper engine, the summary providers and templates, and the Markdown export. Nothing
+/// here needs a running Tauri app, so it backs the `meeting-minutes-cli` binary for servers
+/// and automation.
+///
+/// Whisper models are shared with the app; API keys come from the caller or, failing that,
+/// from the keys the app saved for the default workspace.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+
+use crate::audio::capture::{create_capture_backend, get_backend_for, CaptureSource};
+use crate::audio::devices::{default_input_device, list_audio_devices, AudioDevice, DeviceType};
+use crate::audio::ffmpeg::decode_to_mono;
+use crate::audio::resample::resample;
+use crate::audio::transcription::words::offset_words;
+use crate::audio::vad::get_speech_chunks;
+use crate::export::document::{ExportDocument, ExportSection, ExportTemplate, TranscriptLine};
+use crate::export::markdown::render_markdown;
+use crate::jobs::file_transcription::{FileTranscript, FileTranscriptSegment};
+use crate::summary::{builtin_llm, credentials};
+use crate::summary::llm_client::{create_provider, LLMProvider};
+use crate::summary::processor::{extract_meeting_name_from_markdown, generate_meeting_summary, ChunkingOptions};
+use crate::summary::service::SummaryService;
+use crate::summary::templates::TemplateVariables;
+use crate::whisper_engine::WhisperEngine;
+
+const SAMPLE_RATE: u32 = 16000;
+
+/// Same redemption time the live pipeline uses for its segments
+const VAD_REDEMPTION_MS: u32 = 400;
+
+/// The app's identifier, which names its data folder
+const APP_IDENTIFIER: &str = "com.meetily.ai";
+
+/// Where the app keeps its Whisper models
+pub fn default_models_dir() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().ok_or_else(|| anyhow!("Could not find the system data directory"))?;
+    Ok(data_dir.join(APP_IDENTIFIER).join("models"))
+}
+
+/// A Whisper engine with `model` loaded, e.g. "large-v3-turbo"
+pub async fn load_whisper(models_dir: PathBuf, model: &str) -> Result<WhisperEngine> {
+    let engine = WhisperEngine::new_with_models_dir(Some(models_dir))?;
+    engine.discover_models().await?;
+    engine.load_model(model).await?;
+    Ok(engine)
+}
+
+/// Transcribe 16kHz mono samples, calling `on_progress` with (done, total) speech segments
+pub async fn transcribe_samples(
+    engine: &WhisperEngine,
+    samples: Vec<f32>,
+    language: Option<String>,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<Vec<FileTranscriptSegment>> {
+    let segments = tokio::task::spawn_blocking(move || get_speech_chunks(&samples, VAD_REDEMPTION_MS)).await??;
+    let total = segments.len();
+    let mut transcript = Vec::new();
+    for (index, segment) in segments.into_iter().enumerate() {
+        let start = segment.start_timestamp_ms / 1000.0;
+        let (text, _, _, words) = engine
+            .transcribe_audio_with_words(segment.samples, language.clone())
+            .await
+            .map_err(|e| anyhow!("Transcription failed at {:.1}s: {}", start, e))?;
+        let text = text.trim().to_string();
+        if !text.is_empty() {
+            transcript.push(FileTranscriptSegment {
+                text,
+                audio_start_time: start,
+                audio_end_time: segment.end_timestamp_ms / 1000.0,
+                words: offset_words(words, start),
+            });
+        }
+        on_progress(index + 1, total);
+    }
+    Ok(transcript)
+}
+
+/// Decode and transcribe any file FFmpeg can read
+pub async fn transcribe_file(
+    engine: &WhisperEngine,
+    path: &Path,
+    language: Option<String>,
+    on_progress: impl FnMut(usize, usize),
+) -> Result<FileTranscript> {
+    if !path.exists() {
+        return Err(anyhow!("File not found: {}", path.display()));
+    }
+    let samples = decode_to_mono(path, SAMPLE_RATE).await?;
+    let duration = samples.len() as f64 / SAMPLE_RATE as f64;
+    info!("📂 Decoded {} ({:.1}s of audio)", path.display(), duration);
+    let segments = transcribe_samples(engine, samples, language, on_progress).await?;
+    Ok(FileTranscript { path: path.display().to_string(), duration, segments })
+}
+
+/// Names of the microphones that can be recorded
+pub async fn list_microphones() -> Result<Vec<String>> {
+    let devices = list_audio_devices().await?;
+    Ok(devices.into_iter().filter(|device| device.device_type == DeviceType::Input).map(|device| device.name).collect())
+}
+
+/// Record a microphone until `stop` completes; returns 16kHz mono samples
+///
+/// `device` is a microphone name as `list_microphones` gives it; the default microphone is
+/// used without one.
+pub async fn record_microphone(device: Option<&str>, stop: impl Future<Output = ()>) -> Result<Vec<f32>> {
+    let device = match device {
+        Some(name) => AudioDevice::new(name.to_string(), DeviceType::Input),
+        None => default_input_device()?,
+    };
+    let source = CaptureSource::Microphone;
+    let mut backend = create_capture_backend(get_backend_for(source), source, &device).await?;
+    let format = backend.format();
+
+    let captured = Arc::new(Mutex::new(Vec::<f32>::new()));
+    let sink = captured.clone();
+    backend.start(
+        Box::new(move |data: &[f32]| {
+            if let Ok(mut samples) = sink.lock() {
+                samples.extend_from_slice(data);
+            }
+        }),
+        Box::new(|e| warn!("⚠️ Capture error: {}", e)),
+    )?;
+    info!("🎙️ Recording from {} ({}Hz, {} channels)", device.name, format.sample_rate, format.channels);
+
+    stop.await;
+    if let Err(e) = backend.stop() {
+        warn!("Failed to stop capture: {}", e);
+    }
+
+    let interleaved = std::mem::take(&mut *captured.lock().unwrap());
+    let channels = format.channels.max(1) as usize;
+    let mono: Vec<f32> =
+        interleaved.chunks(channels).map(|frame| frame.iter().sum::<f32>() / frame.len() as f32).collect();
+    resample(&mono, format.sample_rate, SAMPLE_RATE)
+}
+
+/// Which model writes the summary, and how
+#[derive(Debug, Clone)]
+pub struct SummaryOptions {
+    pub provider: LLMProvider,
+    /// The provider's default model when `None`
+    pub model: Option<String>,
+    /// Looked up in the credential store when `None`
+    pub api_key: Option<String>,
+    /// Server address for Ollama and llama.cpp
+    pub endpoint: Option<String>,
+    pub template_id: String,
+    pub custom_prompt: String,
+}
+
+/// Summarize a transcript into Markdown, streaming the report to `on_delta` as it's written
+pub async fn summarize(
+    options: &SummaryOptions,
+    transcript: &[FileTranscriptSegment],
+    variables: &TemplateVariables,
+    on_delta: &mut (dyn FnMut(&str) + Send),
+) -> Result<String, String> {
+    let text = transcript.iter().map(|segment| segment.text.as_str()).collect::<Vec<_>>().join("\n");
+    if text.trim().is_empty() {
+        return Err("The transcript is empty".to_string());
+    }
+    let provider = options.provider;
+    let model = options.model.clone().unwrap_or_else(|| provider.default_model().to_string());
+    let api_key = match &options.api_key {
+        Some(key) => key.clone(),
+        None => credentials::load_api_key(provider.id()).map_err(|e| e.to_string())?.unwrap_or_default(),
+    };
+    let builtin_endpoint = match provider {
+        LLMProvider::Builtin => Some(
+            builtin_llm::ensure_server(&model).await.map_err(|e| format!("Failed to start the built-in model: {}", e))?,
+        ),
+        _ => None,
+    };
+    let endpoint = builtin_endpoint.as_deref().or(options.endpoint.as_deref());
+    if provider == LLMProvider::Ollama {
+        if let Err(e) = crate::ollama::lifecycle::ensure_running(endpoint).await {
+            warn!("{}", e);
+        }
+    }
+
+    let client = crate::network::client();
+    let summary_provider = create_provider(&client, provider, &model, &api_key, endpoint).map_err(|e| e.to_string())?;
+    let token_threshold = SummaryService::token_threshold(provider, &model, endpoint).await;
+    let summary = generate_meeting_summary(
+        summary_provider.as_ref(),
+        &text,
+        &options.custom_prompt,
+        &options.template_id,
+        variables,
+        token_threshold,
+        &ChunkingOptions::default(),
+        on_delta,
+    )
+    .await;
+    // The CLI exits after one summary, and the server would outlive it
+    builtin_llm::stop();
+    let (markdown, _, _, report) = summary?;
+    if let Some(note) = report.note() {
+        warn!("{}", note);
+    }
+    Ok(markdown)
+}
+
+/// Markdown minutes: the summary, when there is one, followed by the transcript
+///
+/// Without a `title`, the summary's own heading is used, then "Meeting".
+pub fn render_minutes(
+    title: Option<String>,
+    created_at: DateTime<Utc>,
+    summary: Option<String>,
+    transcript: &[FileTranscriptSegment],
+) -> String {
+    let title = title
+        .or_else(|| summary.as_deref().and_then(extract_meeting_name_from_markdown))
+        .unwrap_or_else(|| "Meeting".to_string());
+    let sections: &[ExportSection] = match summary {
+        Some(_) => &[ExportSection::Summary, ExportSection::Transcript],
+        None => &[ExportSection::Transcript],
+    };
+    let document = ExportDocument {
+        title,
+        created_at,
+        summary,
+        action_items: Vec::new(),
+        transcript: transcript
+            .iter()
+            .map(|segment| TranscriptLine {
+                start: Some(segment.audio_start_time),
+                end: Some(segment.audio_end_time),
+                speaker: None,
+                text: segment.text.clone(),
+                words: segment.words.clone(),
+            })
+            .collect(),
+    };
+    render_markdown(&document, sections, &ExportTemplate { header: None, footer: None })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start: f64) -> FileTranscriptSegment {
+        FileTranscriptSegment { text: text.to_string(), audio_start_time: start, audio_end_time: start + 2.0, words: Vec::new() }
+    }
+
+    #[test]
+    fn test_render_minutes() {
+        let transcript = vec![segment("Let's ship on Friday.", 5.0), segment("Agreed.", 65.0)];
+        let summary = "# Release planning\n\n## Decisions\n- Ship on Friday".to_string();
+        let minutes = render_minutes(None, Utc::now(), Some(summary), &transcript);
+        assert!(minutes.starts_with("# Release planning\n"));
+        assert!(minutes.contains("## Summary\n\n## Release planning\n\n### Decisions\n- Ship on Friday"));
+        assert!(minutes.contains("`00:00:05` Let's ship on Friday."));
+        assert!(minutes.contains("`00:01:05` Agreed."));
+
+        let transcript_only = render_minutes(Some("Standup".to_string()), Utc::now(), None, &transcript);
+        assert!(transcript_only.starts_with("# Standup\n"));
+        assert!(!transcript_only.contains("## Summary"));
+    }
+}
@@ -0,0 +1,77 @@
+// hotkeys/commands.rs
+//
+// Tauri commands for reading, checking and changing the global shortcuts.
+
+use super::{apply, canonical, conflicts, duplicates, load_settings, save_settings, HotkeyAction, HotkeyConflict, HotkeySettings};
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+/// The shortcuts and the ones that couldn't be registered
+#[derive(Debug, Clone, Serialize)]
+pub struct HotkeyStatus {
+    pub settings: HotkeySettings,
+    pub conflicts: Vec<HotkeyConflict>,
+}
+
+#[tauri::command]
+pub async fn get_hotkey_settings<R: Runtime>(app: AppHandle<R>) -> Result<HotkeyStatus, String> {
+    Ok(HotkeyStatus { settings: load_settings(&app), conflicts: conflicts() })
+}
+
+/// Saves and registers the shortcuts; a shortcut used for two actions is refused, one taken
+/// by another application is saved and reported in `conflicts`
+#[tauri::command]
+pub async fn save_hotkey_settings<R: Runtime>(app: AppHandle<R>, settings: HotkeySettings) -> Result<HotkeyStatus, String> {
+    for (_, shortcut) in settings.bindings() {
+        canonical(shortcut)?;
+    }
+    if let Some(conflict) = duplicates(&settings).into_iter().next() {
+        return Err(format!("{} for \"{}\": {}", conflict.shortcut, conflict.action.label(), conflict.reason));
+    }
+    save_settings(&app, &settings)?;
+    let conflicts = apply(&app, &settings);
+    Ok(HotkeyStatus { settings, conflicts })
+}
+
+#[tauri::command]
+pub async fn reset_hotkey_settings<R: Runtime>(app: AppHandle<R>) -> Result<HotkeyStatus, String> {
+    let settings = HotkeySettings::default();
+    save_settings(&app, &settings)?;
+    let conflicts = apply(&app, &settings);
+    Ok(HotkeyStatus { settings, conflicts })
+}
+
+/// Why `shortcut` can't be used for `action`, or `None` when it's free
+#[tauri::command]
+pub async fn check_hotkey<R: Runtime>(
+    app: AppHandle<R>,
+    action: HotkeyAction,
+    shortcut: String,
+) -> Result<Option<String>, String> {
+    let wanted = canonical(&shortcut)?;
+    let settings = load_settings(&app);
+    for (other, other_shortcut) in settings.bindings() {
+        if canonical(other_shortcut).ok().as_deref() != Some(wanted.as_str()) {
+            continue;
+        }
+        if other == action && !conflicts().iter().any(|conflict| conflict.action == action) {
+            // Its current shortcut, registered fine
+            return Ok(None);
+        }
+        if other != action {
+            return Ok(Some(format!("Already used for \"{}\"", other.label())));
+        }
+    }
+
+    // Another application holds it if it can't be registered
+    let parsed: Shortcut = shortcut.trim().parse().map_err(|e| format!("Not a valid shortcut: {}", e))?;
+    let global_shortcut = app.global_shortcut();
+    match global_shortcut.register(parsed) {
+        Ok(()) => {
+            let _ = global_shortcut.unregister(parsed);
+            Ok(None)
+        }
+        Err(e) => Ok(Some(format!("Already used by another application or the system ({})", e))),
+    }
+}
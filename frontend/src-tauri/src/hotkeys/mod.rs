@@ -0,0 +1,295 @@
+/// Hotkeys module - global keyboard shortcuts for recording
+///
+/// Shortcuts are registered with the OS, so they work while another application (the
+/// meeting itself, usually) is focused. Each action has at most one shortcut, stored in the
+/// Tauri store; a shortcut used twice, or already taken by another application, is reported
+/// as a conflict and left unregistered.
+///
+/// This module contains:
+/// - The actions and their settings
+/// - Registration and dispatch of pressed shortcuts
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "hotkeys.json";
+const STORE_KEY: &str = "hotkeys";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyAction {
+    /// Starts a recording, or stops the one in progress
+    ToggleRecording,
+    /// Pauses the recording, or resumes it when paused
+    PauseRecording,
+    /// Drops a highlight marker at the current moment
+    AddMarker,
+}
+
+impl HotkeyAction {
+    pub const ALL: [HotkeyAction; 3] = [Self::ToggleRecording, Self::PauseRecording, Self::AddMarker];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ToggleRecording => "Start/stop recording",
+            Self::PauseRecording => "Pause/resume recording",
+            Self::AddMarker => "Add highlight marker",
+        }
+    }
+}
+
+/// The shortcut of each action, in accelerator form such as "CmdOrCtrl+Alt+R"; `None` turns
+/// the shortcut off
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotkeySettings {
+    pub toggle_recording: Option<String>,
+    pub pause_recording: Option<String>,
+    pub add_marker: Option<String>,
+}
+
+impl Default for HotkeySettings {
+    fn default() -> Self {
+        Self {
+            toggle_recording: Some("CmdOrCtrl+Alt+R".to_string()),
+            pause_recording: Some("CmdOrCtrl+Alt+P".to_string()),
+            add_marker: Some("CmdOrCtrl+Alt+M".to_string()),
+        }
+    }
+}
+
+impl HotkeySettings {
+    pub fn shortcut(&self, action: HotkeyAction) -> Option<&str> {
+        let shortcut = match action {
+            HotkeyAction::ToggleRecording => &self.toggle_recording,
+            HotkeyAction::PauseRecording => &self.pause_recording,
+            HotkeyAction::AddMarker => &self.add_marker,
+        };
+        shortcut.as_deref().map(str::trim).filter(|s| !s.is_empty())
+    }
+
+    /// The actions that have a shortcut
+    pub fn bindings(&self) -> Vec<(HotkeyAction, &str)> {
+        HotkeyAction::ALL
+            .iter()
+            .filter_map(|&action| self.shortcut(action).map(|shortcut| (action, shortcut)))
+            .collect()
+    }
+}
+
+/// A shortcut that couldn't be registered
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HotkeyConflict {
+    pub action: HotkeyAction,
+    pub shortcut: String,
+    pub reason: String,
+}
+
+/// Actions of the shortcuts registered now, by shortcut id
+static REGISTERED: Lazy<RwLock<HashMap<u32, HotkeyAction>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+/// Conflicts found the last time the shortcuts were registered
+static CONFLICTS: Lazy<RwLock<Vec<HotkeyConflict>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+fn modifier(token: &str) -> Option<&'static str> {
+    match token.to_lowercase().as_str() {
+        "ctrl" | "control" => Some("Ctrl"),
+        "alt" | "option" => Some("Alt"),
+        "shift" => Some("Shift"),
+        "cmd" | "command" | "super" | "meta" | "win" => Some("Super"),
+        "cmdorctrl" | "cmdorcontrol" | "commandorctrl" | "commandorcontrol" => {
+            Some(if cfg!(target_os = "macos") { "Super" } else { "Ctrl" })
+        }
+        _ => None,
+    }
+}
+
+/// The shortcut in a canonical form for comparing, e.g. "Ctrl+Shift+R" for "shift+CmdOrCtrl+r"
+/// on Windows; shortcuts without a modifier are refused, except for function keys, as they
+/// would capture normal typing
+pub fn canonical(shortcut: &str) -> Result<String, String> {
+    let mut modifiers: Vec<&str> = Vec::new();
+    let mut key: Option<String> = None;
+    for token in shortcut.split('+').map(str::trim) {
+        if token.is_empty() {
+            return Err(format!("\"{}\" is not a valid shortcut", shortcut));
+        }
+        match modifier(token) {
+            Some(modifier) if !modifiers.contains(&modifier) => modifiers.push(modifier),
+            Some(_) => {}
+            None if key.is_some() => return Err(format!("\"{}\" has more than one key", shortcut)),
+            None => key = Some(token.to_uppercase()),
+        }
+    }
+    let key = key.ok_or_else(|| format!("\"{}\" needs a key besides the modifiers", shortcut))?;
+    let function_key = key.strip_prefix('F').is_some_and(|n| n.parse::<u8>().is_ok_and(|n| (1..=24).contains(&n)));
+    if modifiers.is_empty() && !function_key {
+        return Err(format!("\"{}\" needs a modifier such as Ctrl or Alt", shortcut));
+    }
+    modifiers.sort_by_key(|modifier| ["Ctrl", "Alt", "Shift", "Super"].iter().position(|m| m == modifier));
+    modifiers.push(&key);
+    Ok(modifiers.join("+"))
+}
+
+/// Actions whose shortcut is already used by an earlier action
+pub fn duplicates(settings: &HotkeySettings) -> Vec<HotkeyConflict> {
+    let mut seen: Vec<(HotkeyAction, String)> = Vec::new();
+    let mut conflicts = Vec::new();
+    for (action, shortcut) in settings.bindings() {
+        let Ok(canonical) = canonical(shortcut) else {
+            continue;
+        };
+        match seen.iter().find(|(_, other)| *other == canonical) {
+            Some((other, _)) => conflicts.push(HotkeyConflict {
+                action,
+                shortcut: shortcut.to_string(),
+                reason: format!("Also used for \"{}\"", other.label()),
+            }),
+            None => seen.push((action, canonical)),
+        }
+    }
+    conflicts
+}
+
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> HotkeySettings {
+    let stored = match app.store(STORE_FILE) {
+        Ok(store) => store.get(STORE_KEY),
+        Err(e) => {
+            warn!("Failed to open {}: {}", STORE_FILE, e);
+            None
+        }
+    };
+    stored.and_then(|value| serde_json::from_value(value).ok()).unwrap_or_default()
+}
+
+pub fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: &HotkeySettings) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    let value = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+    store.set(STORE_KEY, value);
+    store.save().map_err(|e| format!("Failed to save {}: {}", STORE_FILE, e))
+}
+
+pub fn conflicts() -> Vec<HotkeyConflict> {
+    CONFLICTS.read().unwrap().clone()
+}
+
+/// Replaces the registered shortcuts with `settings`; returns the ones that couldn't be registered
+pub fn apply<R: Runtime>(app: &AppHandle<R>, settings: &HotkeySettings) -> Vec<HotkeyConflict> {
+    let global_shortcut = app.global_shortcut();
+    let mut registered = REGISTERED.write().unwrap();
+    registered.clear();
+    if let Err(e) = global_shortcut.unregister_all() {
+        warn!("Failed to unregister hotkeys: {}", e);
+    }
+
+    let mut conflicts = duplicates(settings);
+    for (action, text) in settings.bindings() {
+        if conflicts.iter().any(|conflict| conflict.action == action) {
+            continue;
+        }
+        let conflict = |reason: String| HotkeyConflict { action, shortcut: text.to_string(), reason };
+        let shortcut = match canonical(text).and_then(|_| text.parse::<Shortcut>().map_err(|e| e.to_string())) {
+            Ok(shortcut) => shortcut,
+            Err(e) => {
+                conflicts.push(conflict(format!("Not a valid shortcut: {}", e)));
+                continue;
+            }
+        };
+        match global_shortcut.register(shortcut) {
+            Ok(()) => {
+                registered.insert(shortcut.id(), action);
+            }
+            Err(e) => conflicts.push(conflict(format!("Already used by another application or the system ({})", e))),
+        }
+    }
+
+    for conflict in &conflicts {
+        warn!("⌨️ Hotkey {} for {:?} not registered: {}", conflict.shortcut, conflict.action, conflict.reason);
+    }
+    info!("⌨️ Registered {} hotkey(s)", registered.len());
+    *CONFLICTS.write().unwrap() = conflicts.clone();
+    conflicts
+}
+
+/// Registers the saved shortcuts at startup
+pub fn register_saved<R: Runtime>(app: &AppHandle<R>) {
+    let settings = load_settings(app);
+    apply(app, &settings);
+}
+
+/// The global shortcut plugin's handler for every registered shortcut
+pub fn handle_shortcut<R: Runtime>(app: &AppHandle<R>, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let Some(action) = REGISTERED.read().unwrap().get(&shortcut.id()).copied() else {
+        return;
+    };
+    info!("⌨️ Hotkey pressed: {}", action.label());
+    match action {
+        HotkeyAction::ToggleRecording => crate::tray::toggle_recording_handler(app),
+        HotkeyAction::PauseRecording => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if !crate::audio::recording_commands::is_recording().await {
+                    return;
+                }
+                if crate::audio::recording_commands::is_recording_paused().await {
+                    crate::tray::resume_recording_handler(&app);
+                } else {
+                    crate::tray::pause_recording_handler(&app);
+                }
+            });
+        }
+        HotkeyAction::AddMarker => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if crate::audio::recording_commands::is_recording().await {
+                    if let Err(e) = crate::audio::recording_commands::add_marker(app, None).await {
+                        warn!("Failed to add marker: {}", e);
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical() {
+        assert_eq!(canonical("shift + ctrl + r"), Ok("Ctrl+Shift+R".to_string()));
+        assert_eq!(canonical("Option+Command+m"), Ok("Alt+Super+M".to_string()));
+        let cmd_or_ctrl = if cfg!(target_os = "macos") { "Super+R" } else { "Ctrl+R" };
+        assert_eq!(canonical("CmdOrCtrl+R"), Ok(cmd_or_ctrl.to_string()));
+        assert_eq!(canonical("F9"), Ok("F9".to_string()));
+        assert!(canonical("R").is_err());
+        assert!(canonical("Ctrl+Shift").is_err());
+        assert!(canonical("Ctrl+R+T").is_err());
+        assert!(canonical("Ctrl++").is_err());
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let settings = HotkeySettings {
+            toggle_recording: Some("Ctrl+Alt+R".to_string()),
+            pause_recording: Some("alt+ctrl+r".to_string()),
+            add_marker: None,
+        };
+        let conflicts = duplicates(&settings);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].action, HotkeyAction::PauseRecording);
+        assert!(duplicates(&HotkeySettings::default()).is_empty());
+        assert_eq!(settings.bindings().len(), 2);
+    }
+}
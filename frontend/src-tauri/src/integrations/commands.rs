@@ -0,0 +1,457 @@
+// integrations/commands.rs
+//
+// Tauri commands for connecting services and sending meetings to them.
+
+use crate::database::models::{EmailSettings, NotionConnection, SlackSettings, Webhook, WebhookDelivery};
+use crate::database::repositories::email_setting::EmailSettingsRepository;
+use crate::database::repositories::meeting_library::MeetingLibraryRepository;
+use crate::database::repositories::notion_connection::NotionConnectionsRepository;
+use crate::database::repositories::slack_setting::SlackSettingsRepository;
+use crate::database::repositories::webhook::WebhooksRepository;
+use crate::export::document::ExportDocument;
+use crate::integrations::credentials;
+use crate::integrations::email::{self, EmailDraft};
+use crate::integrations::notion::{self, DatabaseProperties, NotionClient, NotionDatabase, NotionWorkspace};
+use crate::integrations::{slack, webhooks};
+use crate::state::AppState;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tracing::info;
+
+fn notion_token_key(connection_id: &str) -> String {
+    format!("notion:{}", connection_id)
+}
+
+async fn save_notion_workspace(pool: &SqlitePool, workspace: NotionWorkspace) -> Result<NotionConnection, String> {
+    credentials::store_token(&notion_token_key(&workspace.id), &workspace.access_token).map_err(|e| e.to_string())?;
+    let connection = NotionConnectionsRepository::upsert(pool, &workspace.id, &workspace.name)
+        .await
+        .map_err(|e| format!("Failed to save Notion connection: {}", e))?;
+    info!("Connected Notion workspace {}", connection.workspace_name);
+    Ok(connection)
+}
+
+/// The connection and a client for it; without an id, the only connected workspace
+async fn notion_connection(pool: &SqlitePool, connection_id: Option<&str>) -> Result<(NotionConnection, NotionClient), String> {
+    crate::network::ensure_online("Notion")?;
+    let connection = match connection_id {
+        Some(id) => NotionConnectionsRepository::get(pool, id)
+            .await
+            .map_err(|e| format!("Failed to load Notion connection: {}", e))?
+            .ok_or_else(|| format!("No Notion connection with id {}", id))?,
+        None => {
+            let mut connections = NotionConnectionsRepository::list(pool)
+                .await
+                .map_err(|e| format!("Failed to load Notion connections: {}", e))?;
+            match connections.len() {
+                0 => return Err("Connect a Notion workspace first".to_string()),
+                1 => connections.remove(0),
+                _ => return Err("Choose which Notion workspace to use".to_string()),
+            }
+        }
+    };
+    let token = credentials::load_token(&notion_token_key(&connection.id))
+        .map_err(|e| e.to_string())?
+        .ok_or("The Notion token is missing; connect the workspace again")?;
+    Ok((connection, NotionClient::new(token)))
+}
+
+/// The Notion address to open for connecting a workspace through OAuth
+#[tauri::command]
+pub async fn get_notion_oauth_url(client_id: String, redirect_uri: String, state: String) -> Result<String, String> {
+    Ok(notion::oauth_url(&client_id, &redirect_uri, &state))
+}
+
+/// Finishes OAuth with the code Notion redirected back with
+#[tauri::command]
+pub async fn connect_notion_oauth(
+    state: tauri::State<'_, AppState>,
+    code: String,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+) -> Result<NotionConnection, String> {
+    let workspace = notion::exchange_code(&code, &client_id, &client_secret, &redirect_uri).await?;
+    save_notion_workspace(state.db_manager.pool(), workspace).await
+}
+
+/// Connects with an internal integration token (`secret_...` / `ntn_...`)
+#[tauri::command]
+pub async fn connect_notion_token(state: tauri::State<'_, AppState>, token: String) -> Result<NotionConnection, String> {
+    let token = token.trim();
+    if token.is_empty() {
+        return Err("Notion token cannot be empty".to_string());
+    }
+    let workspace = NotionClient::new(token.to_string()).token_workspace().await?;
+    save_notion_workspace(state.db_manager.pool(), workspace).await
+}
+
+#[tauri::command]
+pub async fn list_notion_connections(state: tauri::State<'_, AppState>) -> Result<Vec<NotionConnection>, String> {
+    NotionConnectionsRepository::list(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load Notion connections: {}", e))
+}
+
+#[tauri::command]
+pub async fn disconnect_notion(state: tauri::State<'_, AppState>, connection_id: String) -> Result<bool, String> {
+    credentials::delete_token(&notion_token_key(&connection_id)).map_err(|e| e.to_string())?;
+    NotionConnectionsRepository::delete(state.db_manager.pool(), &connection_id)
+        .await
+        .map_err(|e| format!("Failed to remove Notion connection: {}", e))
+}
+
+/// Databases of the workspace that have been shared with the integration
+#[tauri::command]
+pub async fn list_notion_databases(
+    state: tauri::State<'_, AppState>,
+    connection_id: String,
+) -> Result<Vec<NotionDatabase>, String> {
+    let (_, client) = notion_connection(state.db_manager.pool(), Some(&connection_id)).await?;
+    client.list_databases().await
+}
+
+/// Chooses the database new meeting pages are created in
+#[tauri::command]
+pub async fn set_notion_database(
+    state: tauri::State<'_, AppState>,
+    connection_id: String,
+    database_id: String,
+) -> Result<NotionConnection, String> {
+    let pool = state.db_manager.pool();
+    let (connection, client) = notion_connection(pool, Some(&connection_id)).await?;
+    let (title, properties) = client.database(&database_id).await?;
+    NotionConnectionsRepository::set_database(
+        pool,
+        &connection.id,
+        &database_id,
+        &title,
+        &properties.title,
+        properties.date.as_deref(),
+    )
+    .await
+    .map_err(|e| format!("Failed to save Notion database: {}", e))?;
+    NotionConnectionsRepository::get(pool, &connection.id)
+        .await
+        .map_err(|e| format!("Failed to load Notion connection: {}", e))?
+        .ok_or_else(|| format!("No Notion connection with id {}", connection.id))
+}
+
+/// Creates a Notion page for the meeting; returns the page URL
+#[tauri::command]
+pub async fn export_to_notion(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    connection_id: Option<String>,
+) -> Result<String, String> {
+    let pool = state.db_manager.pool();
+    let (connection, client) = notion_connection(pool, connection_id.as_deref()).await?;
+    let (Some(database_id), Some(title)) = (&connection.database_id, &connection.title_property) else {
+        return Err(format!("Choose a database in {} to export meetings to", connection.workspace_name));
+    };
+    let properties = DatabaseProperties { title: title.clone(), date: connection.date_property.clone() };
+
+    let document = ExportDocument::load(pool, &meeting_id).await?;
+    let url = client
+        .create_page(
+            database_id,
+            notion::page_properties(&document, &properties),
+            notion::page_blocks(&document),
+            notion::transcript_blocks(&document),
+        )
+        .await?;
+    info!("Exported meeting {} to Notion", meeting_id);
+    Ok(url)
+}
+
+#[derive(Debug, Serialize)]
+pub struct SlackSettingsResponse {
+    #[serde(flatten)]
+    pub settings: SlackSettings,
+    /// Whether a webhook URL or bot token is stored for the mode
+    pub has_credential: bool,
+}
+
+#[tauri::command]
+pub async fn get_slack_settings(state: tauri::State<'_, AppState>) -> Result<Option<SlackSettingsResponse>, String> {
+    let settings = SlackSettingsRepository::get(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load Slack settings: {}", e))?;
+    let Some(settings) = settings else {
+        return Ok(None);
+    };
+    let has_credential = credentials::load_token(&slack::credential_key(&settings.mode))
+        .map_err(|e| e.to_string())?
+        .is_some();
+    Ok(Some(SlackSettingsResponse { settings, has_credential }))
+}
+
+/// Saves where summaries are posted; `secret` is the webhook URL or bot token, kept as is when None
+#[tauri::command]
+pub async fn save_slack_settings(
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+    mode: String,
+    channel: Option<String>,
+    secret: Option<String>,
+) -> Result<(), String> {
+    if let Some(secret) = secret.as_deref().map(str::trim).filter(|secret| !secret.is_empty()) {
+        slack::validate_secret(&mode, secret)?;
+        credentials::store_token(&slack::credential_key(&mode), secret).map_err(|e| e.to_string())?;
+    } else if mode != slack::MODE_WEBHOOK && mode != slack::MODE_BOT {
+        return Err(format!("Unknown Slack mode: {}", mode));
+    }
+    let channel = channel.as_deref().map(str::trim).filter(|channel| !channel.is_empty());
+    if mode == slack::MODE_BOT && enabled && channel.is_none() {
+        return Err("Choose a Slack channel to post to".to_string());
+    }
+    SlackSettingsRepository::save(state.db_manager.pool(), enabled, &mode, channel)
+        .await
+        .map_err(|e| format!("Failed to save Slack settings: {}", e))
+}
+
+/// Removes the stored webhook URL and bot token and turns posting off
+#[tauri::command]
+pub async fn disconnect_slack(state: tauri::State<'_, AppState>) -> Result<(), String> {
+    for mode in [slack::MODE_WEBHOOK, slack::MODE_BOT] {
+        credentials::delete_token(&slack::credential_key(mode)).map_err(|e| e.to_string())?;
+    }
+    let pool = state.db_manager.pool();
+    if let Some(settings) = SlackSettingsRepository::get(pool)
+        .await
+        .map_err(|e| format!("Failed to load Slack settings: {}", e))?
+    {
+        SlackSettingsRepository::save(pool, false, &settings.mode, settings.channel.as_deref())
+            .await
+            .map_err(|e| format!("Failed to save Slack settings: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Sends a short message to check the saved settings
+#[tauri::command]
+pub async fn test_slack(state: tauri::State<'_, AppState>) -> Result<(), String> {
+    let settings = SlackSettingsRepository::get(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load Slack settings: {}", e))?
+        .ok_or("Slack is not set up")?;
+    slack::post(&settings, &serde_json::json!({ "text": "Meetily is connected. Meeting summaries will be posted here." })).await
+}
+
+/// Keeps a meeting's summary out of Slack, or lets it be posted again
+#[tauri::command]
+pub async fn set_meeting_slack_opt_out(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    opt_out: bool,
+) -> Result<bool, String> {
+    SlackSettingsRepository::set_opt_out(state.db_manager.pool(), &meeting_id, opt_out)
+        .await
+        .map_err(|e| format!("Failed to update meeting: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_meeting_slack_opt_out(state: tauri::State<'_, AppState>, meeting_id: String) -> Result<bool, String> {
+    SlackSettingsRepository::is_opted_out(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))
+}
+
+/// Posts a meeting now, regardless of the automatic posting setting and opt-out
+#[tauri::command]
+pub async fn post_meeting_to_slack(state: tauri::State<'_, AppState>, meeting_id: String) -> Result<(), String> {
+    slack::post_meeting(state.db_manager.pool(), &meeting_id).await
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookRegistration {
+    pub webhook: Webhook,
+    /// Shown once; receivers use it to check the X-Meetily-Signature header
+    pub secret: String,
+}
+
+#[tauri::command]
+pub async fn list_webhooks(state: tauri::State<'_, AppState>) -> Result<Vec<Webhook>, String> {
+    WebhooksRepository::list(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load webhooks: {}", e))
+}
+
+/// Registers a URL for the given events (`recording-started`, `transcript-ready`, `summary-ready`)
+#[tauri::command]
+pub async fn register_webhook(
+    state: tauri::State<'_, AppState>,
+    url: String,
+    events: Vec<String>,
+) -> Result<WebhookRegistration, String> {
+    let url = url.trim();
+    webhooks::validate(url, &events)?;
+    let webhook = WebhooksRepository::create(state.db_manager.pool(), url, &events)
+        .await
+        .map_err(|e| format!("Failed to save webhook: {}", e))?;
+    let secret = webhooks::generate_secret();
+    credentials::store_token(&webhooks::secret_key(&webhook.id), &secret).map_err(|e| e.to_string())?;
+    info!("Registered webhook {} for {}", webhook.id, events.join(", "));
+    Ok(WebhookRegistration { webhook, secret })
+}
+
+#[tauri::command]
+pub async fn update_webhook(
+    state: tauri::State<'_, AppState>,
+    webhook_id: String,
+    url: String,
+    events: Vec<String>,
+    enabled: bool,
+) -> Result<bool, String> {
+    let url = url.trim();
+    webhooks::validate(url, &events)?;
+    WebhooksRepository::update(state.db_manager.pool(), &webhook_id, url, &events, enabled)
+        .await
+        .map_err(|e| format!("Failed to update webhook: {}", e))
+}
+
+/// Replaces a webhook's signing secret; returns the new one
+#[tauri::command]
+pub async fn rotate_webhook_secret(state: tauri::State<'_, AppState>, webhook_id: String) -> Result<String, String> {
+    WebhooksRepository::get(state.db_manager.pool(), &webhook_id)
+        .await
+        .map_err(|e| format!("Failed to load webhook: {}", e))?
+        .ok_or_else(|| format!("No webhook with id {}", webhook_id))?;
+    let secret = webhooks::generate_secret();
+    credentials::store_token(&webhooks::secret_key(&webhook_id), &secret).map_err(|e| e.to_string())?;
+    Ok(secret)
+}
+
+/// Removes a webhook along with its delivery log
+#[tauri::command]
+pub async fn delete_webhook(state: tauri::State<'_, AppState>, webhook_id: String) -> Result<bool, String> {
+    credentials::delete_token(&webhooks::secret_key(&webhook_id)).map_err(|e| e.to_string())?;
+    WebhooksRepository::delete(state.db_manager.pool(), &webhook_id)
+        .await
+        .map_err(|e| format!("Failed to delete webhook: {}", e))
+}
+
+/// The delivery log, newest first; of one webhook or of all
+#[tauri::command]
+pub async fn list_webhook_deliveries(
+    state: tauri::State<'_, AppState>,
+    webhook_id: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<WebhookDelivery>, String> {
+    WebhooksRepository::list_deliveries(state.db_manager.pool(), webhook_id.as_deref(), limit.unwrap_or(100).clamp(1, 1000))
+        .await
+        .map_err(|e| format!("Failed to load webhook deliveries: {}", e))
+}
+
+/// Sends a logged delivery again with the same body, retrying as for a new event
+#[tauri::command]
+pub async fn redeliver_webhook(state: tauri::State<'_, AppState>, delivery_id: String) -> Result<bool, String> {
+    let pool = state.db_manager.pool();
+    let reset = WebhooksRepository::reset_delivery(pool, &delivery_id)
+        .await
+        .map_err(|e| format!("Failed to update webhook delivery: {}", e))?;
+    if reset {
+        tauri::async_runtime::spawn(webhooks::deliver(pool.clone(), delivery_id));
+    }
+    Ok(reset)
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmailSettingsResponse {
+    #[serde(flatten)]
+    pub settings: EmailSettings,
+    pub has_password: bool,
+}
+
+#[tauri::command]
+pub async fn get_email_settings(state: tauri::State<'_, AppState>) -> Result<Option<EmailSettingsResponse>, String> {
+    let settings = EmailSettingsRepository::get(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load email settings: {}", e))?;
+    let Some(settings) = settings else {
+        return Ok(None);
+    };
+    let has_password = credentials::load_token(email::PASSWORD_KEY).map_err(|e| e.to_string())?.is_some();
+    Ok(Some(EmailSettingsResponse { settings, has_password }))
+}
+
+/// Saves the SMTP server; the password is kept as is when None
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn save_email_settings(
+    state: tauri::State<'_, AppState>,
+    host: String,
+    port: i64,
+    security: String,
+    username: Option<String>,
+    from_address: String,
+    from_name: Option<String>,
+    password: Option<String>,
+) -> Result<(), String> {
+    let host = host.trim();
+    if host.is_empty() {
+        return Err("SMTP server cannot be empty".to_string());
+    }
+    if !(1..=65535).contains(&port) {
+        return Err(format!("Invalid SMTP port: {}", port));
+    }
+    if !email::SECURITY_MODES.contains(&security.as_str()) {
+        return Err(format!("Unknown SMTP security mode: {}", security));
+    }
+    if !from_address.contains('@') {
+        return Err(format!("Invalid sender address: {}", from_address));
+    }
+    if let Some(password) = password.filter(|password| !password.is_empty()) {
+        credentials::store_token(email::PASSWORD_KEY, &password).map_err(|e| e.to_string())?;
+    }
+    let username = username.as_deref().map(str::trim).filter(|username| !username.is_empty());
+    let from_name = from_name.as_deref().map(str::trim).filter(|name| !name.is_empty());
+    EmailSettingsRepository::save(state.db_manager.pool(), host, port, &security, username, from_address.trim(), from_name)
+        .await
+        .map_err(|e| format!("Failed to save email settings: {}", e))
+}
+
+#[tauri::command]
+pub async fn delete_email_settings(state: tauri::State<'_, AppState>) -> Result<bool, String> {
+    credentials::delete_token(email::PASSWORD_KEY).map_err(|e| e.to_string())?;
+    EmailSettingsRepository::delete(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to delete email settings: {}", e))
+}
+
+/// The minutes as an email to the meeting's participants, for review before sending
+#[tauri::command]
+pub async fn preview_meeting_email(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    include_transcript: Option<bool>,
+) -> Result<EmailDraft, String> {
+    let pool = state.db_manager.pool();
+    let document = ExportDocument::load(pool, &meeting_id).await?;
+    let participants: Vec<_> = MeetingLibraryRepository::list_participants(pool, Some(&meeting_id))
+        .await
+        .map_err(|e| format!("Failed to load participants: {}", e))?
+        .into_iter()
+        .map(|(_, participant)| participant)
+        .collect();
+    Ok(email::compose(&document, &participants, include_transcript.unwrap_or(false)))
+}
+
+/// Sends a reviewed draft through the SMTP server
+#[tauri::command]
+pub async fn send_meeting_email(state: tauri::State<'_, AppState>, draft: EmailDraft) -> Result<(), String> {
+    let settings = EmailSettingsRepository::get(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load email settings: {}", e))?
+        .ok_or("Set up an SMTP server first, or open the email in your mail app")?;
+    let password = credentials::load_token(email::PASSWORD_KEY).map_err(|e| e.to_string())?;
+    email::send(&settings, password, &draft).await?;
+    info!("Emailed \"{}\" to {} recipient(s)", draft.subject, draft.recipients.len());
+    Ok(())
+}
+
+/// Hands a reviewed draft to the default mail app instead of sending it
+#[tauri::command]
+pub async fn open_meeting_email_in_mail_app(draft: EmailDraft) -> Result<(), String> {
+    email::open_in_mail_app(&draft)
+}
@@ -0,0 +1,31 @@
+// integrations/credentials.rs
+//
+// Tokens of connected services, kept in the OS credential store like the summary
+// providers' API keys. Each token is stored under a key such as `notion:<workspace id>`.
+
+use crate::secrets;
+use anyhow::Result;
+use log::info;
+
+const SERVICE: &str = "com.meetily.ai.integrations";
+
+/// Tokens are kept per workspace
+fn service() -> String {
+    crate::workspaces::scoped_service(SERVICE)
+}
+
+/// Store (or replace) a token
+pub fn store_token(key: &str, token: &str) -> Result<()> {
+    secrets::store(&service(), key, token)?;
+    info!("🔐 Stored {} token in the system credential store", key);
+    Ok(())
+}
+
+/// The token stored under `key`, or None
+pub fn load_token(key: &str) -> Result<Option<String>> {
+    secrets::load(&service(), key)
+}
+
+pub fn delete_token(key: &str) -> Result<()> {
+    secrets::delete(&service(), key)
+}
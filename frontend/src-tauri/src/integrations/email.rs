@@ -0,0 +1,215 @@
+// integrations/email.rs
+//
+// Emailing the minutes of a meeting to its participants.
+//
+// A draft is composed first so the user can review and edit the recipients, subject and
+// text before anything is sent. The draft is then either sent through the configured SMTP
+// server, or handed to the default mail app as a `mailto:` link.
+
+use crate::database::models::{EmailSettings, Participant};
+use crate::export::document::{parse_markdown, Block, ExportDocument, ExportSection, ExportTemplate};
+use crate::export::markdown::render_markdown;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::{Deserialize, Serialize};
+
+pub const PASSWORD_KEY: &str = "smtp:password";
+pub const SECURITY_MODES: [&str; 3] = ["starttls", "tls", "none"];
+/// Mail apps and Windows truncate or reject longer `mailto:` links
+const MAX_MAILTO_CHARS: usize = 2000;
+
+/// An email ready for review; the frontend may change any field before sending
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailDraft {
+    pub recipients: Vec<Participant>,
+    pub subject: String,
+    /// Plain text body, Markdown formatted
+    pub text: String,
+    /// HTML body; generated from `text` when None, e.g. after the text was edited
+    pub html: Option<String>,
+    /// Participants left out because they have no email address
+    #[serde(default)]
+    pub missing: Vec<String>,
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// HTML for a Markdown body: headings, bullet lists and paragraphs
+pub fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::new();
+    // Task list checkboxes would otherwise lose their brackets
+    let markdown = markdown.replace("- [ ] ", "- ☐ ").replace("- [x] ", "- ☑ ");
+    let mut in_list = false;
+    for block in parse_markdown(&markdown) {
+        let is_bullet = matches!(block, Block::Bullet(_));
+        if in_list && !is_bullet {
+            html.push_str("</ul>\n");
+        } else if !in_list && is_bullet {
+            html.push_str("<ul>\n");
+        }
+        in_list = is_bullet;
+        match block {
+            Block::Heading(level, text) => {
+                let level = level.clamp(1, 4);
+                html.push_str(&format!("<h{}>{}</h{}>\n", level, escape_html(&text), level));
+            }
+            Block::Bullet(text) => html.push_str(&format!("<li>{}</li>\n", escape_html(&text))),
+            Block::Paragraph(text) => html.push_str(&format!("<p>{}</p>\n", escape_html(&text))),
+        }
+    }
+    if in_list {
+        html.push_str("</ul>\n");
+    }
+    format!("<!DOCTYPE html>\n<html><body style=\"font-family: sans-serif; line-height: 1.5\">\n{}</body></html>\n", html)
+}
+
+/// The minutes as an email to the participants that have an address
+pub fn compose(document: &ExportDocument, participants: &[Participant], include_transcript: bool) -> EmailDraft {
+    let mut sections = vec![ExportSection::Summary, ExportSection::ActionItems];
+    if include_transcript {
+        sections.push(ExportSection::Transcript);
+    }
+    let text = render_markdown(document, &sections, &ExportTemplate { header: None, footer: None });
+
+    let (recipients, missing): (Vec<&Participant>, Vec<&Participant>) = participants
+        .iter()
+        .partition(|participant| participant.email.as_deref().is_some_and(|email| email.contains('@')));
+    EmailDraft {
+        recipients: recipients.into_iter().cloned().collect(),
+        subject: format!("Minutes: {} ({})", document.title, document.date()),
+        html: Some(markdown_to_html(&text)),
+        text,
+        missing: missing.into_iter().map(|participant| participant.name.clone()).collect(),
+    }
+}
+
+fn mailbox(name: Option<&str>, address: &str) -> Result<Mailbox, String> {
+    let address = address.trim().parse().map_err(|e| format!("Invalid email address {}: {}", address, e))?;
+    Ok(Mailbox::new(name.map(str::to_string).filter(|name| !name.is_empty()), address))
+}
+
+/// Sends a draft through the SMTP server
+pub async fn send(settings: &EmailSettings, password: Option<String>, draft: &EmailDraft) -> Result<(), String> {
+    if draft.recipients.is_empty() {
+        return Err("Add at least one recipient".to_string());
+    }
+    let mut builder = Message::builder()
+        .from(mailbox(settings.from_name.as_deref(), &settings.from_address)?)
+        .subject(draft.subject.clone());
+    for recipient in &draft.recipients {
+        let address = recipient.email.as_deref().ok_or_else(|| format!("{} has no email address", recipient.name))?;
+        builder = builder.to(mailbox(Some(&recipient.name), address)?);
+    }
+    let html = draft.html.clone().unwrap_or_else(|| markdown_to_html(&draft.text));
+    let message = builder
+        .multipart(MultiPart::alternative_plain_html(draft.text.clone(), html))
+        .map_err(|e| format!("Failed to build email: {}", e))?;
+
+    crate::network::check_host(&settings.host).await?;
+    let transport = match settings.security.as_str() {
+        "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&settings.host),
+        "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&settings.host),
+        "none" => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&settings.host)),
+        security => return Err(format!("Unknown SMTP security mode: {}", security)),
+    }
+    .map_err(|e| format!("Invalid SMTP server {}: {}", settings.host, e))?;
+    let mut transport = transport.port(settings.port as u16);
+    if let (Some(username), Some(password)) = (settings.username.as_deref(), password) {
+        transport = transport.credentials(Credentials::new(username.to_string(), password));
+    }
+    transport
+        .build()
+        .send(message)
+        .await
+        .map_err(|e| format!("Failed to send email: {}", e))?;
+    Ok(())
+}
+
+/// Percent-encodes everything but unreserved characters, as `mailto:` links need
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// A `mailto:` link for the draft; long bodies are cut to what mail apps accept
+pub fn mailto_url(draft: &EmailDraft) -> String {
+    let to: Vec<String> = draft
+        .recipients
+        .iter()
+        .filter_map(|recipient| recipient.email.as_deref())
+        .map(|email| percent_encode(email.trim()).replace("%40", "@"))
+        .collect();
+    let prefix = format!("mailto:{}?subject={}&body=", to.join(","), percent_encode(&draft.subject));
+    let mut body = String::new();
+    for c in draft.text.chars() {
+        let encoded = percent_encode(&c.to_string());
+        if prefix.len() + body.len() + encoded.len() > MAX_MAILTO_CHARS {
+            body.push_str(&percent_encode("…"));
+            break;
+        }
+        body.push_str(&encoded);
+    }
+    format!("{}{}", prefix, body)
+}
+
+/// Opens the draft in the default mail app
+pub fn open_in_mail_app(draft: &EmailDraft) -> Result<(), String> {
+    let url = mailto_url(draft);
+    #[cfg(target_os = "macos")]
+    let result = std::process::Command::new("open").arg(&url).spawn();
+    #[cfg(target_os = "windows")]
+    let result = std::process::Command::new("rundll32").args(["url.dll,FileProtocolHandler", &url]).spawn();
+    #[cfg(target_os = "linux")]
+    let result = std::process::Command::new("xdg-open").arg(&url).spawn();
+    result.map(|_| ()).map_err(|e| format!("Failed to open the mail app: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_html_groups_bullets() {
+        let html = markdown_to_html("## Decisions\n- Ship <v2>\n- [x] Hire\n\nDone & dusted.");
+        assert!(html.contains("<h2>Decisions</h2>\n<ul>\n<li>Ship &lt;v2&gt;</li>\n<li>☑ Hire</li>\n</ul>\n<p>Done &amp; dusted.</p>"));
+    }
+
+    #[test]
+    fn test_mailto_url() {
+        let draft = EmailDraft {
+            recipients: vec![
+                Participant { name: "Ana".to_string(), email: Some("ana@example.com".to_string()) },
+                Participant { name: "Bo".to_string(), email: Some("bo+team@example.com".to_string()) },
+            ],
+            subject: "Minutes: Q&A".to_string(),
+            text: "Hi all,\nsee below".to_string(),
+            html: None,
+            missing: Vec::new(),
+        };
+        assert_eq!(
+            mailto_url(&draft),
+            "mailto:ana@example.com,bo%2Bteam@example.com?subject=Minutes%3A%20Q%26A&body=Hi%20all%2C%0Asee%20below"
+        );
+    }
+
+    #[test]
+    fn test_mailto_url_truncates_long_bodies() {
+        let draft = EmailDraft {
+            recipients: Vec::new(),
+            subject: "Minutes".to_string(),
+            text: "word ".repeat(1000),
+            html: None,
+            missing: Vec::new(),
+        };
+        let url = mailto_url(&draft);
+        assert!(url.len() <= MAX_MAILTO_CHARS + 9);
+        assert!(url.ends_with("%E2%80%A6"));
+    }
+}
@@ -0,0 +1,19 @@
+/// Integrations module - sending meetings to other services
+///
+/// Connected services keep their tokens in the OS credential store; only the connection
+/// details (workspace name, target database) are stored in the database.
+///
+/// This module contains:
+/// - Token storage in the OS credential store (`credentials`)
+/// - Emailing minutes to participants over SMTP or through the mail app (`email`)
+/// - The Notion client and meeting pages (`notion`)
+/// - Posting summaries to Slack after summarization (`slack`)
+/// - Signed webhooks for recording, transcript and summary events (`webhooks`)
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+pub mod credentials;
+pub mod email;
+pub mod notion;
+pub mod slack;
+pub mod webhooks;
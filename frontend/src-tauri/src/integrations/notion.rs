@@ -0,0 +1,321 @@
+// integrations/notion.rs
+//
+// Notion API client and the page a meeting becomes: the summary, action items as to-do
+// checkboxes, and the transcript folded into a toggle.
+//
+// Notion accepts at most 100 blocks per request and 2000 characters per text run, so long
+// meetings are created in several requests and long paragraphs split into several runs.
+
+use crate::export::document::{action_item_text, parse_markdown, Block, ExportDocument};
+use crate::utils::format_timestamp;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+const API_BASE: &str = "https://api.notion.com/v1";
+const NOTION_VERSION: &str = "2022-06-28";
+const MAX_BLOCKS_PER_REQUEST: usize = 100;
+const MAX_TEXT_CHARS: usize = 2000;
+
+/// Workspace a token grants access to
+#[derive(Debug, Clone)]
+pub struct NotionWorkspace {
+    pub id: String,
+    pub name: String,
+    pub access_token: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotionDatabase {
+    pub id: String,
+    pub title: String,
+}
+
+/// Columns of a database that meeting pages fill in
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseProperties {
+    pub title: String,
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OAuthTokenResponse {
+    access_token: String,
+    workspace_id: String,
+    workspace_name: Option<String>,
+}
+
+/// The address to send the user to for connecting a workspace
+pub fn oauth_url(client_id: &str, redirect_uri: &str, state: &str) -> String {
+    let mut url = url::Url::parse(&format!("{}/oauth/authorize", API_BASE)).expect("valid Notion URL");
+    url.query_pairs_mut()
+        .append_pair("client_id", client_id)
+        .append_pair("response_type", "code")
+        .append_pair("owner", "user")
+        .append_pair("redirect_uri", redirect_uri)
+        .append_pair("state", state);
+    url.to_string()
+}
+
+/// Trades the code from the OAuth redirect for an access token
+pub async fn exchange_code(
+    code: &str,
+    client_id: &str,
+    client_secret: &str,
+    redirect_uri: &str,
+) -> Result<NotionWorkspace, String> {
+    let response = crate::network::client()
+        .post(format!("{}/oauth/token", API_BASE))
+        .basic_auth(client_id, Some(client_secret))
+        .json(&json!({ "grant_type": "authorization_code", "code": code, "redirect_uri": redirect_uri }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Notion: {}", e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Notion rejected the authorization ({}): {}", status, body));
+    }
+    let token: OAuthTokenResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Unexpected response from Notion: {}", e))?;
+    Ok(NotionWorkspace {
+        name: token.workspace_name.unwrap_or_else(|| "Notion".to_string()),
+        id: token.workspace_id,
+        access_token: token.access_token,
+    })
+}
+
+pub struct NotionClient {
+    http: reqwest::Client,
+    token: String,
+}
+
+impl NotionClient {
+    pub fn new(token: String) -> Self {
+        Self { http: crate::network::client(), token }
+    }
+
+    async fn request(&self, method: reqwest::Method, path: &str, body: Option<Value>) -> Result<Value, String> {
+        let mut request = self
+            .http
+            .request(method, format!("{}{}", API_BASE, path))
+            .bearer_auth(&self.token)
+            .header("Notion-Version", NOTION_VERSION);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await.map_err(|e| format!("Failed to reach Notion: {}", e))?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+        if !status.is_success() {
+            let message = body["message"].as_str().unwrap_or("unknown error");
+            return Err(format!("Notion request failed ({}): {}", status, message));
+        }
+        Ok(body)
+    }
+
+    /// The workspace an internal integration token belongs to, keyed by the integration's bot id
+    pub async fn token_workspace(&self) -> Result<NotionWorkspace, String> {
+        let me = self.request(reqwest::Method::GET, "/users/me", None).await?;
+        Ok(NotionWorkspace {
+            id: me["id"].as_str().ok_or("Unexpected response from Notion")?.to_string(),
+            name: me["bot"]["workspace_name"].as_str().unwrap_or("Notion").to_string(),
+            access_token: self.token.clone(),
+        })
+    }
+
+    /// Databases shared with the integration
+    pub async fn list_databases(&self) -> Result<Vec<NotionDatabase>, String> {
+        let body = json!({ "filter": { "property": "object", "value": "database" }, "page_size": 100 });
+        let result = self.request(reqwest::Method::POST, "/search", Some(body)).await?;
+        Ok(result["results"]
+            .as_array()
+            .map(|databases| {
+                databases
+                    .iter()
+                    .filter_map(|database| {
+                        Some(NotionDatabase { id: database["id"].as_str()?.to_string(), title: plain_title(&database["title"]) })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// A database's title and the columns meeting pages fill in
+    pub async fn database(&self, database_id: &str) -> Result<(String, DatabaseProperties), String> {
+        let database = self.request(reqwest::Method::GET, &format!("/databases/{}", database_id), None).await?;
+        let properties = database_properties(&database["properties"])
+            .ok_or("The database has no title column")?;
+        Ok((plain_title(&database["title"]), properties))
+    }
+
+    /// Creates the page and returns its URL
+    pub async fn create_page(&self, database_id: &str, properties: Value, blocks: Vec<Value>, transcript: Vec<Value>) -> Result<String, String> {
+        let mut blocks = blocks.into_iter();
+        let first: Vec<Value> = blocks.by_ref().take(MAX_BLOCKS_PER_REQUEST).collect();
+        let page = self
+            .request(
+                reqwest::Method::POST,
+                "/pages",
+                Some(json!({ "parent": { "database_id": database_id }, "properties": properties, "children": first })),
+            )
+            .await?;
+        let page_id = page["id"].as_str().ok_or("Unexpected response from Notion")?.to_string();
+        self.append(&page_id, blocks.collect()).await?;
+
+        if !transcript.is_empty() {
+            let mut transcript = transcript.into_iter();
+            let first: Vec<Value> = transcript.by_ref().take(MAX_BLOCKS_PER_REQUEST).collect();
+            let toggle = json!({ "object": "block", "type": "toggle", "toggle": { "rich_text": rich_text("Transcript"), "children": first } });
+            let appended = self.append(&page_id, vec![toggle]).await?;
+            let toggle_id = appended.first().cloned().ok_or("Unexpected response from Notion")?;
+            self.append(&toggle_id, transcript.collect()).await?;
+        }
+        Ok(page["url"].as_str().unwrap_or_default().to_string())
+    }
+
+    /// Appends blocks in batches; returns the ids of the blocks created
+    async fn append(&self, block_id: &str, blocks: Vec<Value>) -> Result<Vec<String>, String> {
+        let mut ids = Vec::new();
+        for batch in blocks.chunks(MAX_BLOCKS_PER_REQUEST) {
+            let result = self
+                .request(reqwest::Method::PATCH, &format!("/blocks/{}/children", block_id), Some(json!({ "children": batch })))
+                .await?;
+            ids.extend(
+                result["results"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|block| block["id"].as_str().map(str::to_string)),
+            );
+        }
+        Ok(ids)
+    }
+}
+
+/// Text of a Notion rich text array
+fn plain_title(title: &Value) -> String {
+    title
+        .as_array()
+        .map(|parts| parts.iter().filter_map(|part| part["plain_text"].as_str()).collect::<String>())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| "Untitled".to_string())
+}
+
+/// The title column and the first date column of a database
+fn database_properties(properties: &Value) -> Option<DatabaseProperties> {
+    let properties = properties.as_object()?;
+    let named = |kind: &str| {
+        let mut names: Vec<&String> = properties.iter().filter(|(_, p)| p["type"] == kind).map(|(name, _)| name).collect();
+        names.sort();
+        names.first().map(|name| name.to_string())
+    };
+    Some(DatabaseProperties { title: named("title")?, date: named("date") })
+}
+
+/// Rich text runs of at most MAX_TEXT_CHARS characters each
+fn rich_text(text: &str) -> Value {
+    let chars: Vec<char> = text.chars().collect();
+    let runs: Vec<Value> = chars
+        .chunks(MAX_TEXT_CHARS)
+        .map(|chunk| json!({ "type": "text", "text": { "content": chunk.iter().collect::<String>() } }))
+        .collect();
+    Value::Array(runs)
+}
+
+fn block(kind: &str, text: &str) -> Value {
+    let mut block = json!({ "object": "block", "type": kind });
+    block[kind] = json!({ "rich_text": rich_text(text) });
+    block
+}
+
+/// Page properties: the title, and the meeting date when the database has a date column
+pub fn page_properties(document: &ExportDocument, properties: &DatabaseProperties) -> Value {
+    let mut values = serde_json::Map::new();
+    values.insert(properties.title.clone(), json!({ "title": rich_text(&document.title) }));
+    if let Some(date) = &properties.date {
+        values.insert(date.clone(), json!({ "date": { "start": document.created_at.to_rfc3339() } }));
+    }
+    Value::Object(values)
+}
+
+/// The summary and action items
+pub fn page_blocks(document: &ExportDocument) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    if let Some(summary) = &document.summary {
+        blocks.push(block("heading_2", "Summary"));
+        for part in parse_markdown(summary) {
+            blocks.push(match part {
+                // The page title already names the meeting; summary headings go below "Summary"
+                Block::Heading(level, text) => block(if level <= 2 { "heading_3" } else { "paragraph" }, &text),
+                Block::Bullet(text) => block("bulleted_list_item", &text),
+                Block::Paragraph(text) => block("paragraph", &text),
+            });
+        }
+    }
+    if !document.action_items.is_empty() {
+        blocks.push(block("heading_2", "Action Items"));
+        for item in &document.action_items {
+            blocks.push(json!({
+                "object": "block",
+                "type": "to_do",
+                "to_do": { "rich_text": rich_text(&action_item_text(item)), "checked": item.completed },
+            }));
+        }
+    }
+    blocks
+}
+
+/// One paragraph per segment, for the transcript toggle
+pub fn transcript_blocks(document: &ExportDocument) -> Vec<Value> {
+    document
+        .transcript
+        .iter()
+        .map(|line| {
+            let mut prefix = Vec::new();
+            if let Some(start) = line.start {
+                prefix.push(format!("[{}]", format_timestamp(start)));
+            }
+            if let Some(speaker) = &line.speaker {
+                prefix.push(format!("{}:", speaker));
+            }
+            prefix.push(line.text.clone());
+            block("paragraph", &prefix.join(" "))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rich_text_splits_long_text() {
+        let text = "a".repeat(MAX_TEXT_CHARS + 5);
+        let runs = rich_text(&text);
+        assert_eq!(runs.as_array().unwrap().len(), 2);
+        assert_eq!(runs[1]["text"]["content"], "aaaaa");
+    }
+
+    #[test]
+    fn test_database_properties() {
+        let properties = json!({
+            "Name": { "type": "title" },
+            "When": { "type": "date" },
+            "Tags": { "type": "multi_select" },
+        });
+        assert_eq!(
+            database_properties(&properties),
+            Some(DatabaseProperties { title: "Name".to_string(), date: Some("When".to_string()) })
+        );
+        assert_eq!(database_properties(&json!({ "Tags": { "type": "multi_select" } })), None);
+    }
+
+    #[test]
+    fn test_oauth_url() {
+        let url = oauth_url("client", "http://localhost:1420/notion", "xyz");
+        assert!(url.starts_with("https://api.notion.com/v1/oauth/authorize?client_id=client&response_type=code"));
+        assert!(url.contains("redirect_uri=http%3A%2F%2Flocalhost%3A1420%2Fnotion"));
+    }
+}
@@ -0,0 +1,211 @@
+// integrations/slack.rs
+//
+// Posts a meeting's summary and action items to Slack once summarization completes.
+//
+// Two ways to post are supported: an incoming webhook, which is tied to one channel, or a
+// bot token with a channel id (or a user id, which Slack delivers as a direct message).
+// Posting runs as a job so a failed post shows up in the job list and can be retried.
+
+use crate::database::models::SlackSettings;
+use crate::database::repositories::slack_setting::SlackSettingsRepository;
+use crate::export::document::{action_item_text, parse_markdown, Block, ExportDocument};
+use crate::integrations::credentials;
+use crate::jobs::{self, JobPayload, JobPriority};
+use tracing::{info, warn};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Runtime};
+
+pub const MODE_WEBHOOK: &str = "webhook";
+pub const MODE_BOT: &str = "bot";
+const WEBHOOK_PREFIX: &str = "https://hooks.slack.com/";
+const POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+/// Slack rejects section blocks with more text than this
+const MAX_SECTION_CHARS: usize = 3000;
+const MAX_HEADER_CHARS: usize = 150;
+
+/// Credential store key of the webhook URL or bot token for a mode
+pub fn credential_key(mode: &str) -> String {
+    format!("slack:{}", mode)
+}
+
+/// Checks a webhook URL or bot token before it is stored
+pub fn validate_secret(mode: &str, secret: &str) -> Result<(), String> {
+    match mode {
+        MODE_WEBHOOK if !secret.starts_with(WEBHOOK_PREFIX) => {
+            Err(format!("A Slack webhook URL starts with {}", WEBHOOK_PREFIX))
+        }
+        MODE_BOT if !secret.starts_with("xoxb-") => Err("A Slack bot token starts with xoxb-".to_string()),
+        MODE_WEBHOOK | MODE_BOT => Ok(()),
+        _ => Err(format!("Unknown Slack mode: {}", mode)),
+    }
+}
+
+/// `text` with the characters Slack treats as markup escaped
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// The summary markdown in Slack's mrkdwn flavor
+fn summary_mrkdwn(markdown: &str) -> String {
+    parse_markdown(markdown)
+        .into_iter()
+        .map(|block| match block {
+            Block::Heading(_, text) => format!("*{}*", escape(&text)),
+            Block::Bullet(text) => format!("• {}", escape(&text)),
+            Block::Paragraph(text) => escape(&text),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Section blocks holding `text`, split between lines to stay under Slack's limit
+fn sections(text: &str) -> Vec<Value> {
+    let mut chunks: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line: String = line.chars().take(MAX_SECTION_CHARS).collect();
+        match chunks.last_mut() {
+            Some(chunk) if chunk.chars().count() + 1 + line.chars().count() <= MAX_SECTION_CHARS => {
+                chunk.push('\n');
+                chunk.push_str(&line);
+            }
+            _ => chunks.push(line),
+        }
+    }
+    chunks
+        .into_iter()
+        .filter(|chunk| !chunk.trim().is_empty())
+        .map(|chunk| json!({ "type": "section", "text": { "type": "mrkdwn", "text": chunk } }))
+        .collect()
+}
+
+/// The message for a meeting: title, summary and action items
+pub fn meeting_message(document: &ExportDocument) -> Value {
+    let title: String = document.title.chars().take(MAX_HEADER_CHARS).collect();
+    let mut blocks = vec![
+        json!({ "type": "header", "text": { "type": "plain_text", "text": title } }),
+        json!({ "type": "context", "elements": [{ "type": "mrkdwn", "text": escape(&document.date()) }] }),
+    ];
+    match &document.summary {
+        Some(summary) => blocks.extend(sections(&summary_mrkdwn(summary))),
+        None => blocks.extend(sections("_No summary._")),
+    }
+    if !document.action_items.is_empty() {
+        blocks.push(json!({ "type": "divider" }));
+        let items: Vec<String> = document
+            .action_items
+            .iter()
+            .map(|item| format!("{} {}", if item.completed { "☑" } else { "☐" }, escape(&action_item_text(item))))
+            .collect();
+        blocks.extend(sections(&format!("*Action items*\n{}", items.join("\n"))));
+    }
+    // Notifications and clients without block support show the plain text
+    json!({ "text": format!("Meeting summary: {}", document.title), "blocks": blocks })
+}
+
+/// Sends a message with the configured webhook or bot token
+pub async fn post(settings: &SlackSettings, message: &Value) -> Result<(), String> {
+    let secret = credentials::load_token(&credential_key(&settings.mode))
+        .map_err(|e| e.to_string())?
+        .ok_or("Slack is not connected; add a webhook URL or bot token first")?;
+    let client = crate::network::client();
+    match settings.mode.as_str() {
+        MODE_WEBHOOK => {
+            let response = client
+                .post(&secret)
+                .json(message)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to reach Slack: {}", e))?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(format!("Slack rejected the message ({}): {}", status, body));
+            }
+        }
+        MODE_BOT => {
+            let channel = settings.channel.as_deref().ok_or("Choose a Slack channel to post to")?;
+            let mut message = message.clone();
+            message["channel"] = json!(channel);
+            let response: Value = client
+                .post(POST_MESSAGE_URL)
+                .bearer_auth(&secret)
+                .json(&message)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to reach Slack: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("Unexpected response from Slack: {}", e))?;
+            // The Web API answers 200 with ok: false on errors
+            if response["ok"] != json!(true) {
+                return Err(format!("Slack rejected the message: {}", response["error"].as_str().unwrap_or("unknown error")));
+            }
+        }
+        mode => return Err(format!("Unknown Slack mode: {}", mode)),
+    }
+    Ok(())
+}
+
+/// Posts a meeting with the saved settings
+pub async fn post_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<(), String> {
+    let settings = SlackSettingsRepository::get(pool)
+        .await
+        .map_err(|e| format!("Failed to load Slack settings: {}", e))?
+        .ok_or("Slack is not set up")?;
+    let document = ExportDocument::load(pool, meeting_id).await?;
+    post(&settings, &meeting_message(&document)).await?;
+    info!("Posted meeting {} to Slack", meeting_id);
+    Ok(())
+}
+
+/// Queues a post of a freshly summarized meeting, unless Slack is off or the meeting opted out
+pub async fn queue_post<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, meeting_id: &str) {
+    let enabled = match SlackSettingsRepository::get(pool).await {
+        Ok(settings) => settings.is_some_and(|settings| settings.enabled),
+        Err(e) => {
+            warn!("Failed to load Slack settings: {}", e);
+            false
+        }
+    };
+    if !enabled || SlackSettingsRepository::is_opted_out(pool, meeting_id).await.unwrap_or(true) {
+        return;
+    }
+    if crate::network::is_local_only() {
+        info!("Local-only mode is on, so meeting {} isn't posted to Slack", meeting_id);
+        return;
+    }
+    let payload = JobPayload::SlackPost { meeting_id: meeting_id.to_string() };
+    if let Err(e) = jobs::enqueue(app, pool, payload, JobPriority::Normal).await {
+        warn!("Failed to queue Slack post for {}: {}", meeting_id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_mrkdwn() {
+        assert_eq!(
+            summary_mrkdwn("## Decisions\n- Ship **v2** <today>\n\nR&D owns it."),
+            "*Decisions*\n• Ship v2 &lt;today&gt;\nR&amp;D owns it."
+        );
+    }
+
+    #[test]
+    fn test_sections_stay_under_limit() {
+        let line = "x".repeat(2000);
+        let blocks = sections(&format!("{}\n{}\n{}", line, line, "y".repeat(MAX_SECTION_CHARS + 10)));
+        let lengths: Vec<usize> = blocks.iter().map(|b| b["text"]["text"].as_str().unwrap().chars().count()).collect();
+        assert_eq!(lengths, vec![2000, 2000, MAX_SECTION_CHARS]);
+    }
+
+    #[test]
+    fn test_validate_secret() {
+        assert!(validate_secret(MODE_WEBHOOK, "https://hooks.slack.com/services/T/B/x").is_ok());
+        assert!(validate_secret(MODE_WEBHOOK, "https://example.com/hook").is_err());
+        assert!(validate_secret(MODE_BOT, "xoxb-123").is_ok());
+        assert!(validate_secret("email", "x").is_err());
+    }
+}
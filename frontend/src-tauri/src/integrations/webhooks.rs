@@ -0,0 +1,293 @@
+// integrations/webhooks.rs
+//
+// Outgoing webhooks: registered URLs receive a signed JSON POST when a recording starts,
+// a transcript is saved, or a summary is ready.
+//
+// Each event sent to a webhook is a row in `webhook_deliveries`, updated after every
+// attempt. Failed attempts are retried with growing delays; deliveries the app was still
+// retrying when it quit are picked up again on the next launch. The request body is stored
+// so a delivery can be sent again unchanged. See docs/webhooks.md for verifying signatures.
+
+use crate::database::models::WebhookDelivery;
+use crate::database::repositories::webhook::WebhooksRepository;
+use crate::integrations::credentials;
+use crate::state::AppState;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use sqlx::SqlitePool;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Waits before the second, third, ... attempt
+const RETRY_DELAYS: [Duration; 4] = [
+    Duration::from_secs(10),
+    Duration::from_secs(60),
+    Duration::from_secs(5 * 60),
+    Duration::from_secs(30 * 60),
+];
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+/// Deliveries kept in the log
+const MAX_LOGGED_DELIVERIES: i64 = 1000;
+/// Response text kept with a failed attempt
+const MAX_ERROR_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookEvent {
+    RecordingStarted,
+    TranscriptReady,
+    SummaryReady,
+}
+
+impl WebhookEvent {
+    pub const ALL: [Self; 3] = [Self::RecordingStarted, Self::TranscriptReady, Self::SummaryReady];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RecordingStarted => "recording-started",
+            Self::TranscriptReady => "transcript-ready",
+            Self::SummaryReady => "summary-ready",
+        }
+    }
+
+    pub fn from_string(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|event| event.as_str() == s)
+    }
+}
+
+/// Credential store key of a webhook's signing secret
+pub fn secret_key(webhook_id: &str) -> String {
+    format!("webhook:{}", webhook_id)
+}
+
+/// A new random signing secret
+pub fn generate_secret() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    format!("whsec_{}", to_hex(&bytes))
+}
+
+/// Checks a webhook URL and event names before they are saved
+pub fn validate(url: &str, events: &[String]) -> Result<(), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+    // Deliveries carry meeting content, so plain http is only for receivers on this machine
+    let local = matches!(parsed.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+    if parsed.scheme() != "https" && !(parsed.scheme() == "http" && local) {
+        return Err("Webhook URLs must use https".to_string());
+    }
+    if events.is_empty() {
+        return Err("Choose at least one event".to_string());
+    }
+    if let Some(unknown) = events.iter().find(|event| WebhookEvent::from_string(event).is_none()) {
+        return Err(format!("Unknown webhook event: {}", unknown));
+    }
+    Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hmac_hex(secret: &str, message: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    to_hex(&mac.finalize().into_bytes())
+}
+
+/// The `X-Meetily-Signature` header: HMAC-SHA256 of `{timestamp}.{body}`
+pub fn signature(secret: &str, timestamp: i64, body: &str) -> String {
+    format!("t={},v1={}", timestamp, hmac_hex(secret, format!("{}.{}", timestamp, body).as_bytes()))
+}
+
+/// The request body sent for an event
+pub fn envelope(delivery_id: &str, event: WebhookEvent, data: &Value) -> Value {
+    json!({
+        "id": delivery_id,
+        "event": event.as_str(),
+        "created_at": Utc::now().to_rfc3339(),
+        "data": data,
+    })
+}
+
+/// Whether a failed response is worth another attempt; other client errors won't change
+fn retryable(status: u16) -> bool {
+    !(400..500).contains(&status) || status == 408 || status == 429
+}
+
+/// Outcome of one attempt: the response status, and an error unless it was delivered
+async fn attempt(delivery: &WebhookDelivery, url: &str, secret: &str) -> (Option<u16>, Option<String>) {
+    let response = crate::network::client()
+        .post(url)
+        .timeout(REQUEST_TIMEOUT)
+        .header("Content-Type", "application/json")
+        .header("User-Agent", "Meetily-Webhooks")
+        .header("X-Meetily-Event", &delivery.event)
+        .header("X-Meetily-Delivery", &delivery.id)
+        .header("X-Meetily-Signature", signature(secret, Utc::now().timestamp(), &delivery.payload))
+        .body(delivery.payload.clone())
+        .send()
+        .await;
+    match response {
+        Ok(response) if response.status().is_success() => (Some(response.status().as_u16()), None),
+        Ok(response) => {
+            let status = response.status();
+            let body: String = response.text().await.unwrap_or_default().chars().take(MAX_ERROR_CHARS).collect();
+            (Some(status.as_u16()), Some(format!("{}: {}", status, body)))
+        }
+        Err(e) => (None, Some(format!("Failed to reach {}: {}", url, e))),
+    }
+}
+
+/// Attempts a delivery until it succeeds or the retries run out
+pub async fn deliver(pool: SqlitePool, delivery_id: String) {
+    loop {
+        let delivery = match WebhooksRepository::get_delivery(&pool, &delivery_id).await {
+            Ok(Some(delivery)) if delivery.status == "pending" => delivery,
+            Ok(_) => return,
+            Err(e) => {
+                warn!("Failed to load webhook delivery {}: {}", delivery_id, e);
+                return;
+            }
+        };
+        let webhook = match WebhooksRepository::get(&pool, &delivery.webhook_id).await {
+            Ok(Some(webhook)) => webhook,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("Failed to load webhook {}: {}", delivery.webhook_id, e);
+                return;
+            }
+        };
+
+        let (response_status, error) = match credentials::load_token(&secret_key(&webhook.id)) {
+            Ok(Some(secret)) => attempt(&delivery, &webhook.url, &secret).await,
+            Ok(None) => (None, Some("The signing secret is missing; register the webhook again".to_string())),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        let retry = delivery.attempts < RETRY_DELAYS.len() as i64
+            && webhook.enabled
+            && match response_status {
+                Some(status) => retryable(status),
+                None => true,
+            };
+        let status = match (&error, retry) {
+            (None, _) => "delivered",
+            (Some(_), true) => "pending",
+            (Some(_), false) => "failed",
+        };
+        if let Err(e) =
+            WebhooksRepository::record_attempt(&pool, &delivery.id, status, response_status.map(i64::from), error.as_deref())
+                .await
+        {
+            warn!("Failed to record webhook delivery {}: {}", delivery.id, e);
+            return;
+        }
+        match error {
+            None => {
+                info!("Delivered {} to webhook {}", delivery.event, webhook.id);
+                return;
+            }
+            Some(error) if retry => {
+                let delay = RETRY_DELAYS[delivery.attempts as usize];
+                warn!("Webhook delivery {} failed, retrying in {:?}: {}", delivery.id, delay, error);
+                tokio::time::sleep(delay).await;
+            }
+            Some(error) => {
+                warn!("Webhook delivery {} failed: {}", delivery.id, error);
+                return;
+            }
+        }
+    }
+}
+
+async fn send_event(pool: SqlitePool, event: WebhookEvent, data: Value) {
+    let webhooks = match WebhooksRepository::list_subscribed(&pool, event.as_str()).await {
+        Ok(webhooks) => webhooks,
+        Err(e) => {
+            warn!("Failed to load webhooks: {}", e);
+            return;
+        }
+    };
+    if webhooks.is_empty() {
+        return;
+    }
+    for webhook in webhooks {
+        let delivery_id = format!("delivery-{}", Uuid::new_v4());
+        let payload = envelope(&delivery_id, event, &data).to_string();
+        if let Err(e) = WebhooksRepository::create_delivery(&pool, &delivery_id, &webhook.id, event.as_str(), &payload).await {
+            warn!("Failed to queue {} for webhook {}: {}", event.as_str(), webhook.id, e);
+            continue;
+        }
+        tauri::async_runtime::spawn(deliver(pool.clone(), delivery_id));
+    }
+    if let Err(e) = WebhooksRepository::prune_deliveries(&pool, MAX_LOGGED_DELIVERIES).await {
+        warn!("Failed to prune webhook deliveries: {}", e);
+    }
+}
+
+/// Sends an event to every webhook subscribed to it, in the background
+pub fn dispatch<R: Runtime>(app: &AppHandle<R>, event: WebhookEvent, data: Value) {
+    // Before onboarding there is no database and no webhooks
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let pool = state.db_manager.pool().clone();
+    tauri::async_runtime::spawn(send_event(pool, event, data));
+}
+
+/// Resumes deliveries that were still being retried when the app last quit
+pub fn resume_pending<R: Runtime>(app: &AppHandle<R>) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let pool = state.db_manager.pool().clone();
+    tauri::async_runtime::spawn(async move {
+        match WebhooksRepository::list_pending(&pool).await {
+            Ok(deliveries) => {
+                for delivery in deliveries {
+                    tauri::async_runtime::spawn(deliver(pool.clone(), delivery.id));
+                }
+            }
+            Err(e) => warn!("Failed to load pending webhook deliveries: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_matches_rfc_4231() {
+        assert_eq!(
+            hmac_hex("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert!(signature("Jefe", 1700000000, "{}").starts_with("t=1700000000,v1="));
+    }
+
+    #[test]
+    fn test_validate() {
+        let events = vec!["summary-ready".to_string()];
+        assert!(validate("https://example.com/hooks/meetily", &events).is_ok());
+        assert!(validate("ftp://example.com", &events).is_err());
+        assert!(validate("http://example.com/hooks/meetily", &events).is_err());
+        assert!(validate("http://localhost:8080/hooks", &events).is_ok());
+        assert!(validate("http://127.0.0.1/hooks", &events).is_ok());
+        assert!(validate("http://[::1]:9000/hooks", &events).is_ok());
+        assert!(validate("https://example.com", &[]).is_err());
+        assert!(validate("https://example.com", &["meeting-deleted".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_retryable() {
+        assert!(retryable(500));
+        assert!(retryable(429));
+        assert!(!retryable(404));
+        assert_eq!(WebhookEvent::from_string("transcript-ready"), Some(WebhookEvent::TranscriptReady));
+    }
+}
@@ -0,0 +1,98 @@
+use super::media_import::{supported_extension, ImportSummaryOptions, SUPPORTED_EXTENSIONS};
+use super::{queue, JobPayload, JobPriority};
+use crate::database::models::Job;
+use crate::database::repositories::job::JobsRepository;
+use crate::state::AppState;
+use log::info as log_info;
+use tauri::{AppHandle, Runtime};
+
+/// Queues transcription of an existing recording
+///
+/// Runs at import priority unless another priority is given.
+#[tauri::command]
+pub async fn enqueue_file_transcription<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    path: String,
+    language: Option<String>,
+    priority: Option<String>,
+) -> Result<Job, String> {
+    let priority = match priority {
+        Some(p) => JobPriority::from_string(&p).ok_or_else(|| format!("Unknown job priority: {}", p))?,
+        None => JobPriority::Import,
+    };
+    if !std::path::Path::new(&path).exists() {
+        return Err(format!("File not found: {}", path));
+    }
+
+    log_info!("enqueue_file_transcription called for {}", path);
+    let pool = state.db_manager.pool().clone();
+    queue::enqueue(&app, &pool, JobPayload::FileTranscription { path, language }, priority)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Imports a WAV/MP3/M4A/MP4/WEBM recording as a new meeting
+///
+/// Queued at import priority; the job result holds the new meeting id. When `summary` is
+/// given, a summary job is queued for the meeting once the transcript is saved.
+#[tauri::command]
+pub async fn import_media<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    path: String,
+    title: Option<String>,
+    language: Option<String>,
+    summary: Option<ImportSummaryOptions>,
+) -> Result<Job, String> {
+    let file_path = std::path::Path::new(&path);
+    if !file_path.exists() {
+        return Err(format!("File not found: {}", path));
+    }
+    if supported_extension(file_path).is_none() {
+        return Err(format!("Unsupported file type. Supported: {}", SUPPORTED_EXTENSIONS.join(", ")));
+    }
+
+    let title = title
+        .filter(|t| !t.trim().is_empty())
+        .or_else(|| file_path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "Imported recording".to_string());
+
+    log_info!("import_media called for {} as '{}'", path, title);
+    let pool = state.db_manager.pool().clone();
+    queue::enqueue(&app, &pool, JobPayload::MediaImport { path, title, language, summary }, JobPriority::Import)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Lists queued and running jobs in execution order, plus finished ones if requested
+#[tauri::command]
+pub async fn list_jobs(
+    state: tauri::State<'_, AppState>,
+    include_finished: Option<bool>,
+) -> Result<Vec<Job>, String> {
+    let pool = state.db_manager.pool();
+    JobsRepository::list(pool, include_finished.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to list jobs: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_job(state: tauri::State<'_, AppState>, job_id: String) -> Result<Option<Job>, String> {
+    let pool = state.db_manager.pool();
+    JobsRepository::get(pool, &job_id)
+        .await
+        .map_err(|e| format!("Failed to load job: {}", e))
+}
+
+/// Cancels a queued or running job; returns false if it had already finished
+#[tauri::command]
+pub async fn cancel_job<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    job_id: String,
+) -> Result<bool, String> {
+    log_info!("cancel_job called for {}", job_id);
+    let pool = state.db_manager.pool().clone();
+    queue::cancel(&app, &pool, &job_id).await.map_err(|e| e.to_string())
+}
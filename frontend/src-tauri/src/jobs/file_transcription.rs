@@ -0,0 +1,119 @@
+// jobs/file_transcription.rs
+//
+// Transcription of an existing audio or video file. FFmpeg decodes the file to 16kHz
+// mono f32, the VAD splits it into speech segments, and each segment goes through the
+// configured transcription engine. While a recording is active the job pauses between
+// segments so it never competes with live transcription for the engine.
+
+use super::JobContext;
+use crate::audio::ffmpeg::decode_to_mono;
+use crate::audio::transcription::get_or_init_transcription_engine;
+use crate::audio::transcription::words::{offset_words, TranscriptWord};
+use crate::audio::vad::get_speech_chunks;
+use anyhow::{anyhow, Result};
+use log::{debug, info};
+use serde::Serialize;
+use std::path::Path;
+use std::time::Duration;
+use tauri::Runtime;
+
+const SAMPLE_RATE: u32 = 16000;
+
+/// Same redemption time the live pipeline uses for its segments
+const VAD_REDEMPTION_MS: u32 = 400;
+
+const RECORDING_WAIT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileTranscriptSegment {
+    pub text: String,
+    /// Seconds from the start of the file
+    pub audio_start_time: f64,
+    pub audio_end_time: f64,
+    /// Word timings from the start of the file
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<TranscriptWord>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileTranscript {
+    pub path: String,
+    pub duration: f64,
+    pub segments: Vec<FileTranscriptSegment>,
+}
+
+pub async fn transcribe_file<R: Runtime>(
+    context: &JobContext<R>,
+    path: &str,
+    language: Option<String>,
+) -> Result<serde_json::Value> {
+    let transcript = transcribe_to_segments(context, path, language, 1.0).await?;
+    crate::usage::meter::flush(&context.pool, None).await;
+    Ok(serde_json::to_value(transcript)?)
+}
+
+/// Transcribe a file, reporting progress from 0 up to `progress_scale`
+pub async fn transcribe_to_segments<R: Runtime>(
+    context: &JobContext<R>,
+    path: &str,
+    language: Option<String>,
+    progress_scale: f64,
+) -> Result<FileTranscript> {
+    let file_path = Path::new(path);
+    if !file_path.exists() {
+        return Err(anyhow!("File not found: {}", path));
+    }
+
+    context.progress(0.0, "Decoding audio").await;
+    let samples = decode_to_mono(file_path, SAMPLE_RATE).await?;
+    let duration = samples.len() as f64 / SAMPLE_RATE as f64;
+    info!("📂 Decoded {} ({:.1}s of audio)", path, duration);
+
+    context.progress(0.05 * progress_scale, "Detecting speech").await;
+    let segments = tokio::task::spawn_blocking(move || get_speech_chunks(&samples, VAD_REDEMPTION_MS)).await??;
+    if segments.is_empty() {
+        return Err(anyhow!("No speech detected in {}", path));
+    }
+
+    let engine = get_or_init_transcription_engine(&context.app)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let language = language.or_else(crate::get_language_preference_internal);
+    crate::audio::transcription::glossary::refresh_glossary(&context.app).await;
+
+    let total = segments.len();
+    let mut transcript = Vec::new();
+    for (index, segment) in segments.into_iter().enumerate() {
+        if crate::audio::is_recording().await {
+            let progress = (0.05 + 0.95 * index as f64 / total as f64) * progress_scale;
+            context.progress(progress, "Paused while recording").await;
+            while crate::audio::is_recording().await {
+                tokio::time::sleep(RECORDING_WAIT).await;
+            }
+        }
+
+        let result = engine
+            .transcribe(segment.samples, language.clone())
+            .await
+            .map_err(|e| anyhow!("Transcription failed at {:.1}s: {}", segment.start_timestamp_ms / 1000.0, e))?;
+
+        let text = result.text.trim().to_string();
+        if !text.is_empty() {
+            transcript.push(FileTranscriptSegment {
+                text,
+                audio_start_time: segment.start_timestamp_ms / 1000.0,
+                audio_end_time: segment.end_timestamp_ms / 1000.0,
+                words: offset_words(result.words, segment.start_timestamp_ms / 1000.0),
+            });
+        }
+
+        let done = index + 1;
+        debug!("Transcribed segment {}/{} of {}", done, total, path);
+        let progress = (0.05 + 0.95 * done as f64 / total as f64) * progress_scale;
+        context
+            .progress(progress, &format!("Transcribed {} of {} segments", done, total))
+            .await;
+    }
+
+    Ok(FileTranscript { path: path.to_string(), duration, segments: transcript })
+}
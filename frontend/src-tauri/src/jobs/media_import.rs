@@ -0,0 +1,260 @@
+// jobs/media_import.rs
+//
+// Import of recordings made outside the app. The file is copied into a new meeting
+// folder next to regular recordings, transcribed like any other file, and saved as a
+// meeting so it shows up in the meeting list with playback. A summary can be queued
+// right away with the same settings the summary view uses.
+//
+// A recording recovered after a crash already has its meeting folder and meeting; its
+// audio is transcribed the same way and the segments added to that meeting.
+
+use super::file_transcription::{transcribe_to_segments, FileTranscriptSegment};
+use super::{queue, JobContext, JobPayload, JobPriority};
+use crate::api::TranscriptSegment;
+use crate::audio::audio_processing::create_meeting_folder;
+use crate::audio::recording_preferences::get_default_recordings_folder;
+use crate::audio::recording_saver::{DeviceInfo, MeetingMetadata};
+use crate::database::repositories::{
+    summary::SummaryProcessesRepository, transcript::TranscriptsRepository,
+    transcript_chunk::TranscriptChunksRepository,
+};
+use crate::summary::processor::ChunkingOptions;
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::Runtime;
+
+pub const SUPPORTED_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "mp4", "webm"];
+
+/// Share of the job's progress spent on transcription; the rest is saving
+const TRANSCRIPTION_PROGRESS: f64 = 0.9;
+
+/// Summary to generate once the import is saved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportSummaryOptions {
+    pub model: String,
+    pub model_name: String,
+    pub template_id: Option<String>,
+    pub custom_prompt: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaImportResult {
+    pub meeting_id: String,
+    pub folder_path: String,
+    pub duration: f64,
+    pub segment_count: usize,
+    pub summary_job_id: Option<String>,
+}
+
+/// Lowercase extension if the file type can be imported
+pub fn supported_extension(path: &Path) -> Option<String> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    SUPPORTED_EXTENSIONS.contains(&extension.as_str()).then_some(extension)
+}
+
+/// Offset into the file as HH:MM:SS, used as the segment timestamp
+fn format_offset(seconds: f64) -> String {
+    let total = seconds.max(0.0).floor() as u64;
+    format!("{:02}:{:02}:{:02}", total / 3600, (total / 60) % 60, total % 60)
+}
+
+fn to_transcript_segments(segments: &[FileTranscriptSegment]) -> Vec<TranscriptSegment> {
+    segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| TranscriptSegment {
+            id: format!("import-{}", index),
+            text: segment.text.clone(),
+            timestamp: format_offset(segment.audio_start_time),
+            audio_start_time: Some(segment.audio_start_time),
+            audio_end_time: Some(segment.audio_end_time),
+            duration: Some(segment.audio_end_time - segment.audio_start_time),
+            translation: None,
+            words: segment.words.clone(),
+            speaker: None,
+        })
+        .collect()
+}
+
+pub async fn import_media_file<R: Runtime>(
+    context: &JobContext<R>,
+    path: &str,
+    title: &str,
+    language: Option<String>,
+    summary: Option<ImportSummaryOptions>,
+) -> Result<serde_json::Value> {
+    let source = Path::new(path);
+    let extension = supported_extension(source)
+        .ok_or_else(|| anyhow!("Unsupported file type. Supported: {}", SUPPORTED_EXTENSIONS.join(", ")))?;
+
+    let transcript = transcribe_to_segments(context, path, language.clone(), TRANSCRIPTION_PROGRESS).await?;
+    if transcript.segments.is_empty() {
+        return Err(anyhow!("No speech could be transcribed from {}", path));
+    }
+
+    context.progress(TRANSCRIPTION_PROGRESS, "Saving meeting").await;
+
+    // Keep a copy with the meeting so playback works even if the original moves
+    let meeting_folder = create_meeting_folder(&get_default_recordings_folder(), title)?;
+    let audio_file = format!("audio.{}", extension);
+    tokio::fs::copy(source, meeting_folder.join(&audio_file))
+        .await
+        .map_err(|e| anyhow!("Failed to copy {} into the meeting folder: {}", path, e))?;
+
+    let folder_path = meeting_folder.to_string_lossy().to_string();
+    let segments = to_transcript_segments(&transcript.segments);
+    let meeting_id = TranscriptsRepository::save_transcript(&context.pool, title, &segments, Some(folder_path.clone()))
+        .await
+        .map_err(|e| anyhow!("Failed to save imported meeting: {}", e))?;
+    crate::usage::meter::flush(&context.pool, Some(meeting_id.as_str())).await;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let metadata = MeetingMetadata {
+        version: "1.0".to_string(),
+        meeting_id: Some(meeting_id.clone()),
+        meeting_name: Some(title.to_string()),
+        created_at: now.clone(),
+        completed_at: Some(now),
+        duration_seconds: Some(transcript.duration),
+        devices: DeviceInfo { microphone: None, system_audio: None },
+        audio_file,
+        transcript_file: "transcripts.json".to_string(),
+        sample_rate: 16000,
+        channels: 1,
+        channel_layout: None,
+        status: "completed".to_string(),
+        detected_language: None,
+        language_override: language,
+    };
+    if let Err(e) = std::fs::write(meeting_folder.join("metadata.json"), serde_json::to_string_pretty(&metadata)?) {
+        warn!("Failed to write metadata.json for imported meeting {}: {}", meeting_id, e);
+    }
+    if let Err(e) = std::fs::write(meeting_folder.join("transcripts.json"), serde_json::to_string_pretty(&segments)?) {
+        warn!("Failed to write transcripts.json for imported meeting {}: {}", meeting_id, e);
+    }
+    if let Err(e) = crate::encryption::encrypt_meeting_folder(&meeting_folder) {
+        warn!("Failed to encrypt imported meeting {}: {}", meeting_id, e);
+    }
+
+    info!("📂 Imported {} as meeting {} ({} segments)", path, meeting_id, segments.len());
+    if let Ok(models_dir) = crate::diarization::commands::models_dir(&context.app) {
+        crate::qa::index::queue_indexing(&context.app, &context.pool, &models_dir, Some(meeting_id.clone())).await;
+    }
+    crate::plugins::hooks::transcript_ready(&context.app, &context.pool, &meeting_id);
+    crate::integrations::webhooks::dispatch(
+        &context.app,
+        crate::integrations::webhooks::WebhookEvent::TranscriptReady,
+        serde_json::json!({ "meeting_id": meeting_id, "title": title, "segments": segments.len() }),
+    );
+
+    let summary_job_id = match summary {
+        Some(options) => Some(queue_summary(context, &meeting_id, &segments, options).await?),
+        None => None,
+    };
+
+    Ok(serde_json::to_value(MediaImportResult {
+        meeting_id,
+        folder_path,
+        duration: transcript.duration,
+        segment_count: segments.len(),
+        summary_job_id,
+    })?)
+}
+
+/// Transcribe the audio of a recovered recording into its meeting
+pub async fn transcribe_recovered_recording<R: Runtime>(
+    context: &JobContext<R>,
+    meeting_id: &str,
+    path: &str,
+) -> Result<serde_json::Value> {
+    let transcript = transcribe_to_segments(context, path, None, TRANSCRIPTION_PROGRESS).await?;
+    context.progress(TRANSCRIPTION_PROGRESS, "Saving transcript").await;
+
+    let segments = to_transcript_segments(&transcript.segments);
+    TranscriptsRepository::add_segments(&context.pool, meeting_id, &segments)
+        .await
+        .map_err(|e| anyhow!("Failed to save recovered transcript: {}", e))?;
+    crate::usage::meter::flush(&context.pool, Some(meeting_id)).await;
+
+    if let Some(meeting_folder) = Path::new(path).parent() {
+        if let Err(e) = std::fs::write(meeting_folder.join("transcripts.json"), serde_json::to_string_pretty(&segments)?) {
+            warn!("Failed to write transcripts.json for recovered meeting {}: {}", meeting_id, e);
+        }
+        if let Err(e) = crate::encryption::encrypt_meeting_folder(meeting_folder) {
+            warn!("Failed to encrypt recovered meeting {}: {}", meeting_id, e);
+        }
+    }
+
+    info!("🩹 Transcribed recovered recording {} ({} segments)", meeting_id, segments.len());
+    if let Ok(models_dir) = crate::diarization::commands::models_dir(&context.app) {
+        crate::qa::index::queue_indexing(&context.app, &context.pool, &models_dir, Some(meeting_id.to_string())).await;
+    }
+    crate::plugins::hooks::transcript_ready(&context.app, &context.pool, meeting_id);
+
+    Ok(serde_json::json!({ "meeting_id": meeting_id, "duration": transcript.duration, "segment_count": segments.len() }))
+}
+
+/// Queue a summary for the imported meeting, as api_process_transcript would
+async fn queue_summary<R: Runtime>(
+    context: &JobContext<R>,
+    meeting_id: &str,
+    segments: &[TranscriptSegment],
+    options: ImportSummaryOptions,
+) -> Result<String> {
+    let text = segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    SummaryProcessesRepository::create_or_reset_process(&context.pool, meeting_id).await?;
+    TranscriptChunksRepository::save_transcript_data(
+        &context.pool,
+        meeting_id,
+        &text,
+        &options.model,
+        &options.model_name,
+        40000,
+        1000,
+    )
+    .await?;
+
+    let payload = JobPayload::Summary {
+        meeting_id: meeting_id.to_string(),
+        text,
+        model: options.model,
+        model_name: options.model_name,
+        custom_prompt: options.custom_prompt.unwrap_or_default(),
+        template_id: options.template_id.unwrap_or_else(|| "daily_standup".to_string()),
+        chunking: ChunkingOptions::default(),
+    };
+    let job = queue::enqueue(&context.app, &context.pool, payload, JobPriority::Import).await?;
+    Ok(job.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_extension_is_case_insensitive() {
+        assert_eq!(supported_extension(Path::new("/tmp/Call.M4A")), Some("m4a".to_string()));
+        assert_eq!(supported_extension(Path::new("/tmp/screen.webm")), Some("webm".to_string()));
+        assert_eq!(supported_extension(Path::new("/tmp/notes.txt")), None);
+        assert_eq!(supported_extension(Path::new("/tmp/noextension")), None);
+    }
+
+    #[test]
+    fn test_segments_use_file_offsets() {
+        let segments = to_transcript_segments(&[FileTranscriptSegment {
+            text: "hello".to_string(),
+            audio_start_time: 3725.4,
+            audio_end_time: 3727.9,
+            words: Vec::new(),
+        }]);
+        assert_eq!(segments[0].timestamp, "01:02:05");
+        assert!((segments[0].duration.unwrap() - 2.5).abs() < 1e-9);
+    }
+}
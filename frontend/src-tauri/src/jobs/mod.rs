@@ -0,0 +1,167 @@
+/// Jobs module - background queue for transcription and summarization work
+///
+/// Jobs are persisted in the `jobs` table so queued and interrupted work resumes after a
+/// restart. Two lanes drain the queue: one runs live-meeting jobs only, the other runs
+/// everything else in priority order. Work for the meeting in progress is therefore never
+/// stuck behind a batch of imported recordings, and imported files additionally pause
+/// while a recording is active so they don't compete with live transcription.
+///
+/// This module contains:
+/// - Queue and workers (`queue`)
+/// - Transcription of audio files (`file_transcription`)
+/// - Importing recordings made elsewhere, or recovered after a crash, as meetings (`media_import`)
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+pub mod file_transcription;
+pub mod media_import;
+pub mod queue;
+
+use crate::summary::processor::ChunkingOptions;
+use serde::{Deserialize, Serialize};
+
+pub use commands::{
+    __cmd__cancel_job, __cmd__enqueue_file_transcription, __cmd__get_job, __cmd__import_media,
+    __cmd__list_jobs, cancel_job, enqueue_file_transcription, get_job, import_media, list_jobs,
+};
+pub use media_import::ImportSummaryOptions;
+pub use queue::{cancel, enqueue, start_job_workers, JobContext, JOB_PROGRESS_EVENT, JOB_UPDATED_EVENT};
+
+/// Lower values run first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobPriority {
+    /// Work for the meeting being recorded or just finished
+    Live = 0,
+    Normal = 1,
+    /// Imported recordings
+    Import = 2,
+}
+
+impl JobPriority {
+    pub fn as_i64(self) -> i64 {
+        self as i64
+    }
+
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "live" => Some(Self::Live),
+            "normal" => Some(Self::Normal),
+            "import" => Some(Self::Import),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// What a job does; stored as JSON in `jobs.payload`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JobPayload {
+    Summary {
+        meeting_id: String,
+        text: String,
+        model: String,
+        model_name: String,
+        custom_prompt: String,
+        template_id: String,
+        #[serde(default)]
+        chunking: ChunkingOptions,
+    },
+    FileTranscription {
+        path: String,
+        language: Option<String>,
+    },
+    MediaImport {
+        path: String,
+        title: String,
+        language: Option<String>,
+        summary: Option<media_import::ImportSummaryOptions>,
+    },
+    /// Transcribes the audio of a recording recovered after a crash into its meeting
+    RecoveredRecording {
+        meeting_id: String,
+        path: String,
+    },
+    Diarization {
+        meeting_id: String,
+    },
+    ActionItems {
+        meeting_id: String,
+        model: String,
+        model_name: String,
+    },
+    KeyPoints {
+        meeting_id: String,
+        model: String,
+        model_name: String,
+    },
+    /// Embeds transcript segments for semantic search; all meetings when None
+    SemanticIndex {
+        meeting_id: Option<String>,
+    },
+    /// Posts the summary and action items to the configured Slack channel
+    SlackPost {
+        meeting_id: String,
+    },
+}
+
+impl JobPayload {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Summary { .. } => "summary",
+            Self::FileTranscription { .. } => "file_transcription",
+            Self::MediaImport { .. } => "media_import",
+            Self::RecoveredRecording { .. } => "recovered_recording",
+            Self::Diarization { .. } => "diarization",
+            Self::ActionItems { .. } => "action_items",
+            Self::KeyPoints { .. } => "key_points",
+            Self::SemanticIndex { .. } => "semantic_index",
+            Self::SlackPost { .. } => "slack_post",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_orders_live_first() {
+        let mut priorities = vec![JobPriority::Import, JobPriority::Live, JobPriority::Normal];
+        priorities.sort();
+        assert_eq!(priorities, vec![JobPriority::Live, JobPriority::Normal, JobPriority::Import]);
+        assert_eq!(JobPriority::from_string("Import"), Some(JobPriority::Import));
+        assert_eq!(JobPriority::from_string("urgent"), None);
+    }
+
+    #[test]
+    fn test_payload_round_trip_keeps_kind() {
+        let payload = JobPayload::FileTranscription { path: "/tmp/call.m4a".to_string(), language: None };
+        let json = serde_json::to_string(&payload).unwrap();
+        assert!(json.contains("\"kind\":\"file_transcription\""));
+        assert_eq!(serde_json::from_str::<JobPayload>(&json).unwrap(), payload);
+        assert_eq!(payload.kind(), "file_transcription");
+    }
+}
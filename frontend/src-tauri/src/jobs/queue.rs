@@ -0,0 +1,296 @@
+use super::{file_transcription, media_import, JobPayload, JobPriority, JobStatus};
+use crate::database::models::Job;
+use crate::database::repositories::{job::JobsRepository, summary::SummaryProcessesRepository};
+use crate::qa;
+use crate::state::AppState;
+use crate::summary::{action_items, key_points};
+use crate::summary::llm_client::LLMProvider;
+use crate::summary::service::SummaryService;
+use anyhow::{anyhow, Result};
+use log::{error as log_error, info as log_info, warn as log_warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
+use tokio::sync::Notify;
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+
+/// Full job row, emitted whenever a job changes state
+pub const JOB_UPDATED_EVENT: &str = "job-updated";
+pub const JOB_PROGRESS_EVENT: &str = "job-progress";
+
+/// Fallback poll in case a wakeup is missed between claiming and waiting
+const IDLE_POLL: Duration = Duration::from_secs(5);
+
+static JOB_NOTIFY: Lazy<Notify> = Lazy::new(Notify::new);
+
+/// Abort handles of the jobs currently executing, by job id
+static RUNNING_JOBS: Lazy<Mutex<HashMap<String, AbortHandle>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobProgress {
+    pub id: String,
+    pub progress: f64,
+    pub message: Option<String>,
+}
+
+/// Handed to a running job to report progress
+pub struct JobContext<R: Runtime> {
+    pub app: AppHandle<R>,
+    pub pool: SqlitePool,
+    pub id: String,
+}
+
+impl<R: Runtime> JobContext<R> {
+    /// `progress` is a fraction between 0 and 1
+    pub async fn progress(&self, progress: f64, message: &str) {
+        let progress = progress.clamp(0.0, 1.0);
+        if let Err(e) = JobsRepository::update_progress(&self.pool, &self.id, progress, Some(message)).await {
+            log_warn!("Failed to save progress for job {}: {}", self.id, e);
+        }
+        let payload = JobProgress { id: self.id.clone(), progress, message: Some(message.to_string()) };
+        if let Err(e) = self.app.emit(JOB_PROGRESS_EVENT, &payload) {
+            log_warn!("Failed to emit job-progress: {}", e);
+        }
+    }
+}
+
+async fn emit_job_updated<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, id: &str) {
+    match JobsRepository::get(pool, id).await {
+        Ok(Some(job)) => {
+            if let Err(e) = app.emit(JOB_UPDATED_EVENT, &job) {
+                log_warn!("Failed to emit job-updated: {}", e);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => log_warn!("Failed to load job {}: {}", id, e),
+    }
+}
+
+/// Add a job to the queue and wake the workers
+pub async fn enqueue<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    payload: JobPayload,
+    priority: JobPriority,
+) -> Result<Job> {
+    let id = format!("job-{}", Uuid::new_v4());
+    let payload_json = serde_json::to_string(&payload)?;
+    let job = JobsRepository::insert(pool, &id, payload.kind(), priority.as_i64(), &payload_json)
+        .await
+        .map_err(|e| anyhow!("Failed to queue job: {}", e))?;
+
+    log_info!("📥 Queued {} job {} ({:?} priority)", job.kind, job.id, priority);
+    if let Err(e) = app.emit(JOB_UPDATED_EVENT, &job) {
+        log_warn!("Failed to emit job-updated: {}", e);
+    }
+    JOB_NOTIFY.notify_waiters();
+    Ok(job)
+}
+
+/// Cancel a queued or running job; returns false if it had already finished
+pub async fn cancel<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, id: &str) -> Result<bool> {
+    let job = JobsRepository::get(pool, id)
+        .await?
+        .ok_or_else(|| anyhow!("Job not found: {}", id))?;
+
+    let cancelled = JobsRepository::finish(pool, id, JobStatus::Cancelled.as_str(), None, None).await?;
+    if !cancelled {
+        return Ok(false);
+    }
+
+    if let Some(handle) = RUNNING_JOBS.lock().unwrap().remove(id) {
+        handle.abort();
+    }
+
+    // Summaries also track their state per meeting; don't leave it "processing"
+    if let Ok(JobPayload::Summary { meeting_id, .. }) = serde_json::from_str::<JobPayload>(&job.payload) {
+        if let Err(e) = SummaryProcessesRepository::update_process_failed(pool, &meeting_id, "Cancelled by user").await {
+            log_warn!("Failed to mark summary for {} as cancelled: {}", meeting_id, e);
+        }
+        SummaryService::emit_done(app, &meeting_id, "cancelled", None);
+    }
+
+    log_info!("🛑 Cancelled job {}", id);
+    emit_job_updated(app, pool, id).await;
+    Ok(true)
+}
+
+/// Start the queue workers once the database is available
+///
+/// Jobs left running by a previous session are put back in the queue first.
+pub fn start_job_workers<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let pool = AppState::wait_for_pool(&app).await;
+
+        if let Err(e) = JobsRepository::requeue_interrupted(&pool).await {
+            log_error!("Failed to requeue interrupted jobs: {}", e);
+        }
+
+        let live = JobPriority::Live.as_i64();
+        tokio::spawn(run_lane(app.clone(), pool.clone(), "live", live, live));
+        tokio::spawn(run_lane(app, pool, "background", JobPriority::Normal.as_i64(), JobPriority::Import.as_i64()));
+    });
+}
+
+async fn run_lane<R: Runtime>(app: AppHandle<R>, pool: SqlitePool, lane: &'static str, min_priority: i64, max_priority: i64) {
+    log_info!("🧵 Job worker '{}' started", lane);
+    loop {
+        match JobsRepository::claim_next(&pool, min_priority, max_priority).await {
+            Ok(Some(job)) => run_job(&app, &pool, job).await,
+            Ok(None) => {
+                tokio::select! {
+                    _ = JOB_NOTIFY.notified() => {}
+                    _ = tokio::time::sleep(IDLE_POLL) => {}
+                }
+            }
+            Err(e) => {
+                log_error!("Job worker '{}' failed to read the queue: {}", lane, e);
+                tokio::time::sleep(IDLE_POLL).await;
+            }
+        }
+    }
+}
+
+async fn run_job<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, job: Job) {
+    log_info!("▶️ Running {} job {}", job.kind, job.id);
+    emit_job_updated(app, pool, &job.id).await;
+
+    let payload = match serde_json::from_str::<JobPayload>(&job.payload) {
+        Ok(payload) => payload,
+        Err(e) => {
+            let error = format!("Invalid job payload: {}", e);
+            let _ = JobsRepository::finish(pool, &job.id, JobStatus::Failed.as_str(), None, Some(&error)).await;
+            emit_job_updated(app, pool, &job.id).await;
+            return;
+        }
+    };
+
+    let context = JobContext { app: app.clone(), pool: pool.clone(), id: job.id.clone() };
+    let handle = tokio::spawn(execute(context, payload));
+    let abort_handle = handle.abort_handle();
+    RUNNING_JOBS.lock().unwrap().insert(job.id.clone(), abort_handle.clone());
+
+    // cancel() may have run between claiming the job and registering its handle
+    if let Ok(Some(current)) = JobsRepository::get(pool, &job.id).await {
+        if current.status == JobStatus::Cancelled.as_str() {
+            abort_handle.abort();
+        }
+    }
+
+    let outcome = handle.await;
+    RUNNING_JOBS.lock().unwrap().remove(&job.id);
+
+    let finished = match outcome {
+        Ok(Ok(result)) => {
+            let result = result.map(|value| value.to_string());
+            log_info!("✅ Job {} completed", job.id);
+            JobsRepository::finish(pool, &job.id, JobStatus::Completed.as_str(), result.as_deref(), None).await
+        }
+        Ok(Err(e)) => {
+            log_error!("❌ Job {} failed: {}", job.id, e);
+            JobsRepository::finish(pool, &job.id, JobStatus::Failed.as_str(), None, Some(&e.to_string())).await
+        }
+        // Status was already set by cancel()
+        Err(e) if e.is_cancelled() => Ok(false),
+        Err(e) => {
+            log_error!("❌ Job {} panicked: {}", job.id, e);
+            JobsRepository::finish(pool, &job.id, JobStatus::Failed.as_str(), None, Some("Job crashed unexpectedly")).await
+        }
+    };
+
+    match finished {
+        Ok(true) => emit_job_updated(app, pool, &job.id).await,
+        Ok(false) => {}
+        Err(e) => log_error!("Failed to save result of job {}: {}", job.id, e),
+    }
+}
+
+async fn execute<R: Runtime>(context: JobContext<R>, payload: JobPayload) -> Result<Option<serde_json::Value>> {
+    match payload {
+        JobPayload::Summary { meeting_id, text, model, model_name, custom_prompt, template_id, chunking } => {
+            context.progress(0.0, "Generating summary").await;
+            SummaryService::process_transcript_background(
+                context.app.clone(),
+                context.pool.clone(),
+                meeting_id.clone(),
+                text,
+                model,
+                model_name,
+                custom_prompt,
+                template_id,
+                chunking,
+            )
+            .await;
+
+            // The service records its outcome on the meeting's summary process
+            match SummaryProcessesRepository::get_summary_data(&context.pool, &meeting_id).await? {
+                Some(process) if process.status == "failed" => {
+                    Err(anyhow!(process.error.unwrap_or_else(|| "Summary generation failed".to_string())))
+                }
+                _ => Ok(None),
+            }
+        }
+        JobPayload::FileTranscription { path, language } => {
+            file_transcription::transcribe_file(&context, &path, language).await.map(Some)
+        }
+        JobPayload::MediaImport { path, title, language, summary } => {
+            media_import::import_media_file(&context, &path, &title, language, summary).await.map(Some)
+        }
+        JobPayload::RecoveredRecording { meeting_id, path } => {
+            media_import::transcribe_recovered_recording(&context, &meeting_id, &path).await.map(Some)
+        }
+        JobPayload::Diarization { meeting_id } => {
+            let models_dir = crate::diarization::commands::models_dir(&context.app).map_err(|e| anyhow!(e))?;
+            crate::diarization::pipeline::diarize_meeting(&context, &models_dir, &meeting_id).await.map(Some)
+        }
+        JobPayload::ActionItems { meeting_id, model, model_name } => {
+            context.progress(0.0, "Extracting action items").await;
+            let provider = LLMProvider::from_str(&model).map_err(|e| anyhow!(e))?;
+            let (provider, model_name) = SummaryService::choose_provider(&context.pool, provider, &model_name).await;
+            let (provider, token_threshold) = SummaryService::resolve_provider(&context.pool, provider, &model_name)
+                .await
+                .map_err(|e| anyhow!(e))?;
+            let items = action_items::extract_action_items(&context.pool, provider.as_ref(), token_threshold, &meeting_id)
+                .await
+                .map_err(|e| anyhow!(e))?;
+            action_items::emit_updated(&context.app, &meeting_id);
+            Ok(Some(serde_json::json!({ "action_items": items.len() })))
+        }
+        JobPayload::KeyPoints { meeting_id, model, model_name } => {
+            context.progress(0.0, "Extracting decisions, open questions and risks").await;
+            let provider = LLMProvider::from_str(&model).map_err(|e| anyhow!(e))?;
+            let (provider, model_name) = SummaryService::choose_provider(&context.pool, provider, &model_name).await;
+            let (provider, token_threshold) = SummaryService::resolve_provider(&context.pool, provider, &model_name)
+                .await
+                .map_err(|e| anyhow!(e))?;
+            let points = key_points::extract_key_points(&context.pool, provider.as_ref(), token_threshold, &meeting_id)
+                .await
+                .map_err(|e| anyhow!(e))?;
+            key_points::emit_updated(&context.app, &meeting_id);
+            Ok(Some(serde_json::json!({ "key_points": points.len() })))
+        }
+        JobPayload::SemanticIndex { meeting_id } => {
+            context.progress(0.0, "Indexing transcripts for search").await;
+            let models_dir = crate::diarization::commands::models_dir(&context.app).map_err(|e| anyhow!(e))?;
+            let embedder = qa::embeddings::configured_embedder(&context.pool, &models_dir)
+                .await
+                .map_err(|e| anyhow!(e))?;
+            let embedded = match meeting_id {
+                Some(meeting_id) => qa::index::index_meeting(&context.pool, embedder.as_ref(), &meeting_id).await,
+                None => qa::index::index_all_meetings(&context.pool, embedder.as_ref()).await,
+            }
+            .map_err(|e| anyhow!(e))?;
+            Ok(Some(serde_json::json!({ "embedded_segments": embedded })))
+        }
+        JobPayload::SlackPost { meeting_id } => {
+            context.progress(0.0, "Posting to Slack").await;
+            crate::integrations::slack::post_meeting(&context.pool, &meeting_id).await.map_err(|e| anyhow!(e))?;
+            Ok(None)
+        }
+    }
+}
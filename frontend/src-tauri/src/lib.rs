@@ -1,5 +1,4 @@
 use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex as StdMutex;
 // Removed unused import
 
@@ -38,27 +37,49 @@ pub(crate) use perf_trace;
 pub mod analytics;
 pub mod api;
 pub mod audio;
+pub mod backup;
+pub mod calendar;
 pub mod console_utils;
 pub mod database;
+pub mod error;
+pub mod diagnostics;
+pub mod diarization;
+pub mod encryption;
+pub mod export;
+pub mod headless;
+pub mod hotkeys;
+pub mod integrations;
+pub mod jobs;
+pub mod library;
+pub mod local_api;
+pub mod network;
 pub mod notifications;
 pub mod ollama;
+pub mod onboarding;
 pub mod openrouter;
 pub mod parakeet_engine;
+pub mod plugins;
+pub mod qa;
+pub mod redaction;
+pub mod search;
+pub mod secrets;
 pub mod state;
 pub mod summary;
+pub mod sync;
 pub mod tray;
+pub mod usage;
 pub mod utils;
 pub mod whisper_engine;
+pub mod workspaces;
 
 use audio::{list_audio_devices, AudioDevice};
+use error::{AppError, ErrorCode};
 use log::{error as log_error, info as log_info};
 use notifications::commands::NotificationManagerState;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager, Runtime};
 use tokio::sync::RwLock;
 
-static RECORDING_FLAG: AtomicBool = AtomicBool::new(false);
-
 // Global language preference storage (default to "auto-translate" for automatic translation to English)
 static LANGUAGE_PREFERENCE: std::sync::LazyLock<StdMutex<String>> =
     std::sync::LazyLock::new(|| StdMutex::new("auto-translate".to_string()));
@@ -81,7 +102,7 @@ async fn start_recording<R: Runtime>(
     mic_device_name: Option<String>,
     system_device_name: Option<String>,
     meeting_name: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     log_info!("🔥 CALLED start_recording with meeting: {:?}", meeting_name);
     log_info!(
         "📋 Backend received parameters - mic: {:?}, system: {:?}, meeting: {:?}",
@@ -91,7 +112,7 @@ async fn start_recording<R: Runtime>(
     );
 
     if is_recording().await {
-        return Err("Recording already in progress".to_string());
+        return Err(AppError::new(ErrorCode::AlreadyRecording, "Recording already in progress"));
     }
 
     // Call the actual audio recording system with meeting name
@@ -104,7 +125,6 @@ async fn start_recording<R: Runtime>(
     .await
     {
         Ok(_) => {
-            RECORDING_FLAG.store(true, Ordering::SeqCst);
             tray::update_tray_menu(&app);
 
             log_info!("Recording started successfully");
@@ -131,13 +151,13 @@ async fn start_recording<R: Runtime>(
         }
         Err(e) => {
             log_error!("Failed to start audio recording: {}", e);
-            Err(format!("Failed to start recording: {}", e))
+            Err(e.context("Failed to start recording"))
         }
     }
 }
 
 #[tauri::command]
-async fn stop_recording<R: Runtime>(app: AppHandle<R>, args: RecordingArgs) -> Result<(), String> {
+async fn stop_recording<R: Runtime>(app: AppHandle<R>, args: RecordingArgs) -> Result<(), AppError> {
     log_info!("Attempting to stop recording...");
 
     // Check the actual audio recording system state instead of the flag
@@ -156,7 +176,6 @@ async fn stop_recording<R: Runtime>(app: AppHandle<R>, args: RecordingArgs) -> R
     .await
     {
         Ok(_) => {
-            RECORDING_FLAG.store(false, Ordering::SeqCst);
             tray::update_tray_menu(&app);
 
             // Create the save directory if it doesn't exist
@@ -164,9 +183,9 @@ async fn stop_recording<R: Runtime>(app: AppHandle<R>, args: RecordingArgs) -> R
                 if !parent.exists() {
                     log_info!("Creating directory: {:?}", parent);
                     if let Err(e) = std::fs::create_dir_all(parent) {
-                        let err_msg = format!("Failed to create save directory: {}", e);
-                        log_error!("{}", err_msg);
-                        return Err(err_msg);
+                        let error = AppError::from(e).context("Failed to create save directory");
+                        log_error!("{}", error);
+                        return Err(error);
                     }
                 }
             }
@@ -192,10 +211,8 @@ async fn stop_recording<R: Runtime>(app: AppHandle<R>, args: RecordingArgs) -> R
         }
         Err(e) => {
             log_error!("Failed to stop audio recording: {}", e);
-            // Still update the flag even if stopping failed
-            RECORDING_FLAG.store(false, Ordering::SeqCst);
             tray::update_tray_menu(&app);
-            Err(format!("Failed to stop recording: {}", e))
+            Err(e.context("Failed to stop recording"))
         }
     }
 }
@@ -216,7 +233,7 @@ fn get_transcription_status() -> TranscriptionStatus {
 
 #[tauri::command]
 fn read_audio_file(file_path: String) -> Result<Vec<u8>, String> {
-    match std::fs::read(&file_path) {
+    match encryption::files::read(std::path::Path::new(&file_path)) {
         Ok(data) => Ok(data),
         Err(e) => Err(format!("Failed to read audio file: {}", e)),
     }
@@ -288,7 +305,7 @@ async fn start_recording_with_devices<R: Runtime>(
     app: AppHandle<R>,
     mic_device_name: Option<String>,
     system_device_name: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     start_recording_with_devices_and_meeting(app, mic_device_name, system_device_name, None).await
 }
 
@@ -298,7 +315,7 @@ async fn start_recording_with_devices_and_meeting<R: Runtime>(
     mic_device_name: Option<String>,
     system_device_name: Option<String>,
     meeting_name: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     log_info!("🚀 CALLED start_recording_with_devices_and_meeting - Mic: {:?}, System: {:?}, Meeting: {:?}",
              mic_device_name, system_device_name, meeting_name);
 
@@ -393,6 +410,11 @@ pub fn run() {
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(hotkeys::handle_shortcut)
+                .build(),
+        )
         .manage(whisper_engine::parallel_commands::ParallelProcessorState::new())
         .manage(Arc::new(RwLock::new(
             None::<notifications::manager::NotificationManager<tauri::Wry>>,
@@ -401,6 +423,11 @@ pub fn run() {
         .setup(|_app| {
             log::info!("Application setup complete");
 
+            // Pick the workspace first; everything below reads its database and recordings
+            workspaces::init(_app.handle());
+            // Before anything that might go online
+            network::init(_app.handle());
+
             // Initialize system tray
             if let Err(e) = tray::create_tray(_app.handle()) {
                 log::error!("Failed to create system tray: {}", e);
@@ -452,17 +479,42 @@ pub fn run() {
                 }
             });
 
+            // The built-in summary model lives with the other models
+            match diarization::commands::models_dir(_app.handle()) {
+                Ok(dir) => summary::builtin_llm::set_models_dir(dir),
+                Err(e) => log::warn!("Built-in summary model unavailable: {}", e),
+            }
+
             // Initialize microphone permission on startup
             // This will request permission early so it's ready when recording starts
             audio::init_microphone_permission();
             log::info!("Microphone permission initialization triggered");
 
+            // Watch for permission grants/revocations so the UI updates without a restart
+            audio::PERMISSION_MONITOR.start(_app.handle().clone());
+            audio::DEVICE_WATCHER.start(_app.handle().clone());
+            audio::recovery::scan_on_startup(_app.handle().clone());
+
             // Initialize database (handles first launch detection and conditional setup)
             tauri::async_runtime::block_on(async {
                 database::setup::initialize_database_on_startup(&_app.handle()).await
             })
             .expect("Failed to initialize database");
 
+            // Start background job workers (waits for the database on first launch)
+            jobs::start_job_workers(_app.handle().clone());
+            integrations::webhooks::resume_pending(_app.handle());
+            calendar::sync::start_calendar_sync(_app.handle().clone());
+            calendar::scheduler::start_recording_scheduler(_app.handle().clone());
+            library::retention::start_retention_janitor(_app.handle().clone());
+            backup::start_backup_scheduler(_app.handle().clone());
+            sync::start_sync_worker(_app.handle().clone());
+            local_api::start_local_api(_app.handle().clone());
+            redaction::init(_app.handle());
+
+            // Register the global shortcuts
+            hotkeys::register_saved(_app.handle());
+
             // Initialize bundled templates directory for dynamic template discovery
             log::info!("Initializing bundled templates directory...");
             if let Ok(resource_path) = _app.handle().path().resource_dir() {
@@ -507,6 +559,7 @@ pub fn run() {
             analytics::commands::track_analytics_enabled,
             analytics::commands::track_analytics_disabled,
             analytics::commands::track_analytics_transparency_viewed,
+            analytics::talk_time::get_meeting_talk_time,
             whisper_engine::commands::whisper_init,
             whisper_engine::commands::whisper_get_available_models,
             whisper_engine::commands::whisper_load_model,
@@ -519,6 +572,11 @@ pub fn run() {
             whisper_engine::commands::whisper_download_model,
             whisper_engine::commands::whisper_cancel_download,
             whisper_engine::commands::whisper_delete_corrupted_model,
+            whisper_engine::commands::whisper_delete_model,
+            whisper_engine::commands::whisper_verify_model,
+            whisper_engine::commands::whisper_switch_model,
+            whisper_engine::acceleration::get_transcription_backend_diagnostics,
+            whisper_engine::acceleration::set_transcription_backend,
             // Parakeet engine commands
             parakeet_engine::commands::parakeet_init,
             parakeet_engine::commands::parakeet_get_available_models,
@@ -546,6 +604,58 @@ pub fn run() {
             whisper_engine::parallel_commands::prepare_audio_chunks,
             whisper_engine::parallel_commands::test_parallel_processing_setup,
             get_audio_devices,
+            audio::device_commands::list_audio_devices,
+            audio::device_preferences::get_device_preferences,
+            audio::device_preferences::get_preferred_device,
+            audio::device_preferences::set_preferred_device,
+            audio::write_ahead::list_orphaned_recordings,
+            audio::recovery::recover_interrupted_recording,
+            audio::recovery::dismiss_interrupted_recording,
+            audio::preroll::set_preroll_seconds_command,
+            audio::preroll::set_include_preroll_command,
+            audio::preroll::get_preroll_status_command,
+            audio::echo_cancellation::set_echo_cancellation,
+            audio::echo_cancellation::get_echo_cancellation,
+            audio::resample::get_resampling_diagnostics,
+            audio::channel_mix::get_channel_mix_policy_command,
+            audio::channel_mix::set_channel_mix_policy_command,
+            audio::vad::get_vad_sensitivity_command,
+            audio::vad::set_vad_sensitivity_command,
+            audio::auto_stop::get_auto_stop_settings_command,
+            audio::auto_stop::set_auto_stop_settings_command,
+            audio::playback::play_segment,
+            audio::playback::seek_playback,
+            audio::playback::pause_playback,
+            audio::playback::resume_playback,
+            audio::playback::stop_playback,
+            audio::playback::set_playback_speed,
+            audio::playback::get_playback_status,
+            diarization::commands::get_diarization_model_status,
+            diarization::commands::download_diarization_model,
+            diarization::commands::diarize_meeting,
+            diarization::profiles::list_speaker_profiles,
+            diarization::profiles::list_meeting_speakers,
+            diarization::profiles::label_meeting_speaker,
+            diarization::profiles::rename_speaker_profile,
+            diarization::profiles::merge_speaker_profiles,
+            diarization::profiles::delete_speaker_profile,
+            audio::transcription::streaming::get_live_caption_settings_command,
+            audio::transcription::streaming::set_live_caption_settings_command,
+            audio::transcription::language::set_meeting_language,
+            audio::transcription::language::get_meeting_language_command,
+            audio::transcription::translation::set_meeting_translation,
+            audio::transcription::translation::get_meeting_translation,
+            audio::transcription::glossary::list_glossary_terms,
+            audio::transcription::glossary::add_glossary_term,
+            audio::transcription::glossary::update_glossary_term,
+            audio::transcription::glossary::delete_glossary_term,
+            audio::transcription::engine::list_transcription_providers,
+            audio::transcription::engine::get_meeting_transcription_provider,
+            audio::transcription::engine::set_meeting_transcription_provider,
+            audio::audio_processing::set_noise_suppression,
+            audio::audio_processing::get_noise_suppression,
+            audio::audio_processing::get_agc_settings_command,
+            audio::audio_processing::set_agc_settings_command,
             start_recording_with_devices,
             start_recording_with_devices_and_meeting,
             start_audio_level_monitoring,
@@ -554,8 +664,12 @@ pub fn run() {
             // Recording pause/resume commands
             audio::recording_commands::pause_recording,
             audio::recording_commands::resume_recording,
+            audio::recording_commands::add_marker,
+            audio::recording_commands::set_recording_confidential,
             audio::recording_commands::is_recording_paused,
             audio::recording_commands::get_recording_state,
+            audio::session::get_recording_session,
+            audio::selftest::run_pipeline_selftest,
             audio::recording_commands::get_meeting_folder_path,
             // Reload sync commands (retrieve transcript history and meeting name)
             audio::recording_commands::get_transcript_history,
@@ -573,6 +687,9 @@ pub fn run() {
             ollama::pull_ollama_model,
             ollama::delete_ollama_model,
             ollama::get_ollama_model_context,
+            ollama::lifecycle::get_ollama_status,
+            ollama::lifecycle::check_ollama_health,
+            ollama::lifecycle::start_ollama,
             api::api_get_meetings,
             api::api_search_transcripts,
             api::api_get_profile,
@@ -588,6 +705,7 @@ pub fn run() {
             api::api_get_transcript_api_key,
             api::api_delete_meeting,
             api::api_get_meeting,
+            api::api_export_translated_transcript,
             api::api_save_meeting_title,
             api::api_save_transcript,
             api::open_meeting_folder,
@@ -596,12 +714,203 @@ pub fn run() {
             api::open_external_url,
             // Summary commands
             summary::api_process_transcript,
+            jobs::enqueue_file_transcription,
+            jobs::import_media,
+            jobs::list_jobs,
+            jobs::get_job,
+            jobs::cancel_job,
             summary::api_get_summary,
             summary::api_save_meeting_summary,
+            summary::api_cancel_summary,
+            summary::regenerate_summary,
+            summary::list_summary_versions,
+            summary::restore_summary_version,
+            summary::delete_summary_version,
             // Template commands
             summary::api_list_templates,
             summary::api_get_template_details,
             summary::api_validate_template,
+            summary::api_get_template,
+            summary::api_save_template,
+            summary::api_delete_template,
+            summary::api_set_meeting_template,
+            summary::api_get_meeting_template,
+            // Summary provider commands
+            summary::provider_commands::list_summary_providers,
+            summary::provider_commands::set_summary_provider_model,
+            summary::provider_commands::save_summary_api_key,
+            summary::provider_commands::delete_summary_api_key,
+            summary::provider_commands::get_builtin_llm_status,
+            summary::provider_commands::download_builtin_llm_model,
+            // Action item commands
+            summary::action_items::list_action_items,
+            summary::action_items::create_action_item,
+            summary::action_items::update_action_item,
+            summary::action_items::set_action_item_completed,
+            summary::action_items::delete_action_item,
+            summary::action_items::extract_meeting_action_items,
+            // Key point (decisions, open questions, risks) commands
+            summary::key_points::list_key_points,
+            summary::key_points::delete_key_point,
+            summary::key_points::extract_meeting_key_points,
+            // Meeting Q&A commands
+            qa::commands::ask_meeting,
+            qa::commands::ask_all_meetings,
+            qa::commands::semantic_search,
+            qa::commands::get_embedding_model_status,
+            qa::commands::download_embedding_model,
+            qa::commands::rebuild_semantic_index,
+            // Full-text search commands
+            search::commands::search_meetings,
+            // Export commands
+            export::commands::export_meeting,
+            export::commands::export_meetings_jsonl,
+            export::commands::import_meetings,
+            // Notion integration commands
+            integrations::commands::get_notion_oauth_url,
+            integrations::commands::connect_notion_oauth,
+            integrations::commands::connect_notion_token,
+            integrations::commands::list_notion_connections,
+            integrations::commands::disconnect_notion,
+            integrations::commands::list_notion_databases,
+            integrations::commands::set_notion_database,
+            integrations::commands::export_to_notion,
+            // Slack integration commands
+            integrations::commands::get_slack_settings,
+            integrations::commands::save_slack_settings,
+            integrations::commands::disconnect_slack,
+            integrations::commands::test_slack,
+            integrations::commands::set_meeting_slack_opt_out,
+            integrations::commands::get_meeting_slack_opt_out,
+            integrations::commands::post_meeting_to_slack,
+            // Webhook commands
+            integrations::commands::list_webhooks,
+            integrations::commands::register_webhook,
+            integrations::commands::update_webhook,
+            integrations::commands::rotate_webhook_secret,
+            integrations::commands::delete_webhook,
+            integrations::commands::list_webhook_deliveries,
+            integrations::commands::redeliver_webhook,
+            // Email commands
+            integrations::commands::get_email_settings,
+            integrations::commands::save_email_settings,
+            integrations::commands::delete_email_settings,
+            integrations::commands::preview_meeting_email,
+            integrations::commands::send_meeting_email,
+            integrations::commands::open_meeting_email_in_mail_app,
+            // Calendar commands
+            calendar::commands::get_calendar_oauth_url,
+            calendar::commands::connect_calendar_oauth,
+            calendar::commands::connect_caldav,
+            calendar::commands::list_calendar_accounts,
+            calendar::commands::disconnect_calendar,
+            calendar::commands::sync_calendars,
+            calendar::commands::list_calendar_events,
+            calendar::commands::get_current_calendar_event,
+            calendar::commands::link_meeting_to_calendar_event,
+            calendar::commands::unlink_meeting_calendar_event,
+            calendar::commands::get_meeting_calendar_event,
+            calendar::commands::list_calendar_recording_rules,
+            calendar::commands::add_calendar_recording_rule,
+            calendar::commands::delete_calendar_recording_rule,
+            // Hotkey commands
+            hotkeys::commands::get_hotkey_settings,
+            hotkeys::commands::save_hotkey_settings,
+            hotkeys::commands::reset_hotkey_settings,
+            hotkeys::commands::check_hotkey,
+            onboarding::commands::get_onboarding_status,
+            onboarding::commands::skip_onboarding_step,
+            onboarding::commands::reset_onboarding,
+            // Highlight marker commands
+            library::markers::list_meeting_markers,
+            library::markers::add_meeting_marker,
+            library::markers::update_meeting_marker,
+            library::markers::delete_meeting_marker,
+            library::confidential::list_confidential_ranges,
+            library::confidential::mark_confidential_range,
+            // Retention policy commands
+            library::retention::get_retention_policy,
+            library::retention::set_retention_policy,
+            library::retention::run_retention_now,
+            // At-rest encryption commands
+            encryption::commands::get_encryption_status,
+            encryption::commands::enable_encryption,
+            encryption::commands::disable_encryption,
+            // Workspace commands
+            workspaces::commands::list_workspaces,
+            workspaces::commands::create_workspace,
+            workspaces::commands::rename_workspace,
+            workspaces::commands::remove_workspace,
+            workspaces::commands::switch_workspace,
+            workspaces::commands::get_storage_locations,
+            workspaces::commands::plan_storage_move,
+            workspaces::commands::move_storage,
+            // Backup commands
+            backup::commands::get_backup_settings,
+            backup::commands::set_backup_settings,
+            backup::commands::list_backups,
+            backup::commands::create_backup,
+            backup::commands::export_backup,
+            backup::commands::restore_backup,
+            // Device sync commands
+            sync::commands::get_sync_settings,
+            sync::commands::set_sync_settings,
+            sync::commands::get_sync_key,
+            sync::commands::create_sync_key,
+            sync::commands::set_sync_key,
+            sync::commands::sync_now,
+            sync::commands::list_sync_conflicts,
+            sync::commands::resolve_sync_conflict,
+            // Local API commands
+            local_api::commands::get_local_api_status,
+            local_api::commands::set_local_api_settings,
+            local_api::commands::regenerate_local_api_token,
+            // Plugin commands
+            plugins::commands::list_plugins,
+            plugins::commands::set_plugin_enabled,
+            plugins::commands::open_plugins_folder,
+            // Redaction commands
+            redaction::commands::get_redaction_settings,
+            redaction::commands::set_redaction_settings,
+            redaction::commands::get_ner_model_status,
+            redaction::commands::download_ner_model,
+            redaction::commands::preview_redaction,
+            redaction::commands::list_redaction_audit,
+            // Local-only mode commands
+            network::commands::get_network_status,
+            network::commands::set_local_only,
+            // Usage commands
+            usage::commands::get_usage_report,
+            // Credential commands
+            secrets::commands::list_credentials,
+            secrets::commands::get_credential,
+            secrets::commands::set_credential,
+            secrets::commands::test_credential,
+            secrets::commands::delete_credential,
+            // Meeting library commands
+            library::commands::create_meeting,
+            library::commands::get_meeting_info,
+            library::commands::list_library_meetings,
+            library::commands::rename_meeting,
+            library::commands::set_meeting_archived,
+            library::commands::set_meeting_notes,
+            library::commands::set_meeting_tags,
+            library::commands::add_meeting_tag,
+            library::commands::remove_meeting_tag,
+            library::commands::list_meeting_tags,
+            library::commands::set_meeting_participants,
+            library::commands::set_meeting_metadata,
+            library::commands::query_library,
+            library::commands::list_saved_filters,
+            library::commands::save_filter,
+            library::commands::delete_saved_filter,
+            library::commands::run_saved_filter,
+            library::folders::list_folders,
+            library::folders::create_folder,
+            library::folders::rename_folder,
+            library::folders::move_folder,
+            library::folders::delete_folder,
+            library::folders::move_meeting_to_folder,
             openrouter::get_openrouter_models,
             audio::recording_preferences::get_recording_preferences,
             audio::recording_preferences::set_recording_preferences,
@@ -612,6 +921,8 @@ pub fn run() {
             audio::recording_preferences::get_current_audio_backend,
             audio::recording_preferences::set_audio_backend,
             audio::recording_preferences::get_audio_backend_info,
+            audio::recording_preferences::list_capture_backends,
+            audio::recording_preferences::set_capture_backend,
             // Language preference commands
             get_language_preference,
             set_language_preference,
@@ -630,6 +941,8 @@ pub fn run() {
             notifications::commands::initialize_notification_manager_manual,
             notifications::commands::test_notification_with_auto_consent,
             notifications::commands::get_notification_stats,
+            // Diagnostics commands
+            diagnostics::commands::generate_diagnostics,
             // System audio capture commands
             audio::system_audio_commands::start_system_audio_capture_command,
             audio::system_audio_commands::list_system_audio_devices_command,
@@ -637,14 +950,24 @@ pub fn run() {
             audio::system_audio_commands::start_system_audio_monitoring,
             audio::system_audio_commands::stop_system_audio_monitoring,
             audio::system_audio_commands::get_system_audio_monitoring_status,
+            audio::system_audio_commands::list_audio_processes,
+            audio::system_audio_commands::set_tap_target,
+            audio::system_audio_commands::set_tap_excluded_processes,
+            audio::system_audio_commands::get_tap_target,
             // Screen Recording permission commands
             audio::permissions::check_screen_recording_permission_command,
             audio::permissions::request_screen_recording_permission_command,
             // audio::permissions::trigger_system_audio_permission_command,
             // Microphone permission commands
             audio::permissions::check_microphone_permission_command,
+            audio::permissions::get_microphone_permission_status_command,
+            audio::permissions::get_permission_state_command,
             audio::permissions::request_microphone_permission_command,
             audio::permissions::ensure_microphone_permission_command,
+            audio::preflight::preflight_check,
+            audio::bluetooth_microphone::check_bluetooth_microphone,
+            audio::bluetooth_microphone::use_built_in_microphone,
+            audio::pipeline_stats::pipeline_stats,
             // Database import commands
             database::commands::check_first_launch,
             database::commands::select_legacy_database_path,
@@ -652,6 +975,7 @@ pub fn run() {
             database::commands::check_homebrew_database,
             database::commands::import_and_initialize_database,
             database::commands::initialize_fresh_database,
+            database::commands::check_database_integrity,
             // Database and Models path commands
             database::commands::get_database_directory,
             database::commands::open_database_folder,
@@ -660,6 +984,12 @@ pub fn run() {
             #[cfg(target_os = "macos")]
             utils::open_system_settings,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            // Don't leave the built-in summary model's server running
+            if let tauri::RunEvent::Exit = event {
+                summary::builtin_llm::stop();
+            }
+        });
 }
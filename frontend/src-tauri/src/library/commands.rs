@@ -0,0 +1,340 @@
+// library/commands.rs
+//
+// Tauri commands for the meeting library.
+
+use crate::database::models::{DateTimeUtc, LibraryMeeting, Participant, SavedFilter};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::meeting_library::MeetingLibraryRepository;
+use crate::database::repositories::saved_filter::SavedFiltersRepository;
+use crate::library::fields::{normalize_participants, normalize_tag, normalize_tags, validate_metadata_key};
+use crate::library::filter::parse_filter;
+use chrono::Utc;
+use crate::state::AppState;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap};
+use tracing::info;
+
+/// A meeting as the library view shows it
+#[derive(Debug, Clone, Serialize)]
+pub struct MeetingInfo {
+    pub id: String,
+    pub title: String,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+    pub folder_path: Option<String>,
+    pub folder_id: Option<String>,
+    pub archived: bool,
+    pub archived_at: Option<DateTimeUtc>,
+    pub notes: Option<String>,
+    pub tags: Vec<String>,
+    pub participants: Vec<Participant>,
+    pub metadata: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub meetings: i64,
+}
+
+impl MeetingInfo {
+    fn new(meeting: LibraryMeeting) -> Self {
+        Self {
+            id: meeting.id,
+            title: meeting.title,
+            created_at: meeting.created_at,
+            updated_at: meeting.updated_at,
+            folder_path: meeting.folder_path,
+            folder_id: meeting.folder_id,
+            archived: meeting.archived_at.is_some(),
+            archived_at: meeting.archived_at,
+            notes: meeting.notes,
+            tags: Vec::new(),
+            participants: Vec::new(),
+            metadata: BTreeMap::new(),
+        }
+    }
+}
+
+/// Fills in the tags, participants and metadata of `meetings`
+///
+/// With a single meeting only its rows are loaded; otherwise everything is loaded at once
+/// rather than one query per meeting.
+async fn with_details(pool: &SqlitePool, meetings: Vec<LibraryMeeting>) -> Result<Vec<MeetingInfo>, String> {
+    let only = match meetings.as_slice() {
+        [meeting] => Some(meeting.id.clone()),
+        _ => None,
+    };
+    let tags = MeetingLibraryRepository::list_tags(pool, only.as_deref())
+        .await
+        .map_err(|e| format!("Failed to load tags: {}", e))?;
+    let participants = MeetingLibraryRepository::list_participants(pool, only.as_deref())
+        .await
+        .map_err(|e| format!("Failed to load participants: {}", e))?;
+    let metadata = MeetingLibraryRepository::list_metadata(pool, only.as_deref())
+        .await
+        .map_err(|e| format!("Failed to load metadata: {}", e))?;
+
+    let mut infos: Vec<MeetingInfo> = meetings.into_iter().map(MeetingInfo::new).collect();
+    let positions: HashMap<String, usize> = infos.iter().enumerate().map(|(i, info)| (info.id.clone(), i)).collect();
+    for (meeting_id, tag) in tags {
+        if let Some(&i) = positions.get(&meeting_id) {
+            infos[i].tags.push(tag);
+        }
+    }
+    for (meeting_id, participant) in participants {
+        if let Some(&i) = positions.get(&meeting_id) {
+            infos[i].participants.push(participant);
+        }
+    }
+    for (meeting_id, key, value) in metadata {
+        if let Some(&i) = positions.get(&meeting_id) {
+            infos[i].metadata.insert(key, value);
+        }
+    }
+    Ok(infos)
+}
+
+async fn meeting_info(pool: &SqlitePool, meeting_id: &str) -> Result<MeetingInfo, String> {
+    let meeting = MeetingLibraryRepository::get(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("No meeting found with id {}", meeting_id))?;
+    with_details(pool, vec![meeting])
+        .await?
+        .pop()
+        .ok_or_else(|| format!("No meeting found with id {}", meeting_id))
+}
+
+/// Creates an empty meeting, e.g. to take notes without recording
+#[tauri::command]
+pub async fn create_meeting(state: tauri::State<'_, AppState>, title: String) -> Result<MeetingInfo, String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Meeting title cannot be empty".to_string());
+    }
+    let pool = state.db_manager.pool();
+    let meeting_id = MeetingLibraryRepository::create(pool, title)
+        .await
+        .map_err(|e| format!("Failed to create meeting: {}", e))?;
+    info!("Created meeting {}", meeting_id);
+    meeting_info(pool, &meeting_id).await
+}
+
+#[tauri::command]
+pub async fn get_meeting_info(state: tauri::State<'_, AppState>, meeting_id: String) -> Result<MeetingInfo, String> {
+    meeting_info(state.db_manager.pool(), &meeting_id).await
+}
+
+/// Every meeting with its library fields, newest first; archived ones only when asked for
+#[tauri::command]
+pub async fn list_library_meetings(
+    state: tauri::State<'_, AppState>,
+    include_archived: Option<bool>,
+) -> Result<Vec<MeetingInfo>, String> {
+    let pool = state.db_manager.pool();
+    let meetings = MeetingLibraryRepository::list(pool, include_archived.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to list meetings: {}", e))?;
+    with_details(pool, meetings).await
+}
+
+#[tauri::command]
+pub async fn rename_meeting(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    title: String,
+) -> Result<MeetingInfo, String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Meeting title cannot be empty".to_string());
+    }
+    let pool = state.db_manager.pool();
+    if !MeetingsRepository::update_meeting_name(pool, &meeting_id, title)
+        .await
+        .map_err(|e| format!("Failed to rename meeting: {}", e))?
+    {
+        return Err(format!("No meeting found with id {}", meeting_id));
+    }
+    meeting_info(pool, &meeting_id).await
+}
+
+#[tauri::command]
+pub async fn set_meeting_archived(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    archived: bool,
+) -> Result<MeetingInfo, String> {
+    let pool = state.db_manager.pool();
+    if !MeetingLibraryRepository::set_archived(pool, &meeting_id, archived)
+        .await
+        .map_err(|e| format!("Failed to archive meeting: {}", e))?
+    {
+        return Err(format!("No meeting found with id {}", meeting_id));
+    }
+    meeting_info(pool, &meeting_id).await
+}
+
+/// Replaces the meeting's notes; empty notes clear them
+#[tauri::command]
+pub async fn set_meeting_notes(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    notes: Option<String>,
+) -> Result<MeetingInfo, String> {
+    let pool = state.db_manager.pool();
+    let notes = notes.filter(|notes| !notes.trim().is_empty());
+    if !MeetingLibraryRepository::set_notes(pool, &meeting_id, notes.as_deref())
+        .await
+        .map_err(|e| format!("Failed to save notes: {}", e))?
+    {
+        return Err(format!("No meeting found with id {}", meeting_id));
+    }
+    meeting_info(pool, &meeting_id).await
+}
+
+/// Replaces all tags of a meeting; returns the tags as stored
+#[tauri::command]
+pub async fn set_meeting_tags(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    tags: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let pool = state.db_manager.pool();
+    let meeting = meeting_info(pool, &meeting_id).await?;
+    MeetingLibraryRepository::set_tags(pool, &meeting.id, &normalize_tags(&tags))
+        .await
+        .map_err(|e| format!("Failed to save tags: {}", e))?;
+    Ok(meeting_info(pool, &meeting_id).await?.tags)
+}
+
+#[tauri::command]
+pub async fn add_meeting_tag(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    tag: String,
+) -> Result<Vec<String>, String> {
+    let tag = normalize_tag(&tag).ok_or_else(|| "Tag cannot be empty".to_string())?;
+    let pool = state.db_manager.pool();
+    let meeting = meeting_info(pool, &meeting_id).await?;
+    MeetingLibraryRepository::add_tag(pool, &meeting.id, &tag)
+        .await
+        .map_err(|e| format!("Failed to add tag: {}", e))?;
+    Ok(meeting_info(pool, &meeting_id).await?.tags)
+}
+
+#[tauri::command]
+pub async fn remove_meeting_tag(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    tag: String,
+) -> Result<Vec<String>, String> {
+    let pool = state.db_manager.pool();
+    MeetingLibraryRepository::remove_tag(pool, &meeting_id, tag.trim().trim_start_matches('#'))
+        .await
+        .map_err(|e| format!("Failed to remove tag: {}", e))?;
+    Ok(meeting_info(pool, &meeting_id).await?.tags)
+}
+
+/// Every tag in use, with how many meetings carry it
+#[tauri::command]
+pub async fn list_meeting_tags(state: tauri::State<'_, AppState>) -> Result<Vec<TagCount>, String> {
+    Ok(MeetingLibraryRepository::tag_counts(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to list tags: {}", e))?
+        .into_iter()
+        .map(|(tag, meetings)| TagCount { tag, meetings })
+        .collect())
+}
+
+/// Replaces who attended the meeting; returns the participants as stored
+#[tauri::command]
+pub async fn set_meeting_participants(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    participants: Vec<Participant>,
+) -> Result<Vec<Participant>, String> {
+    let pool = state.db_manager.pool();
+    let meeting = meeting_info(pool, &meeting_id).await?;
+    let participants = normalize_participants(participants);
+    MeetingLibraryRepository::set_participants(pool, &meeting.id, &participants)
+        .await
+        .map_err(|e| format!("Failed to save participants: {}", e))?;
+    Ok(participants)
+}
+
+/// Sets one metadata value of a meeting; None removes the key. Returns all metadata
+#[tauri::command]
+pub async fn set_meeting_metadata(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    key: String,
+    value: Option<String>,
+) -> Result<BTreeMap<String, String>, String> {
+    let key = validate_metadata_key(&key)?;
+    let pool = state.db_manager.pool();
+    let meeting = meeting_info(pool, &meeting_id).await?;
+    MeetingLibraryRepository::set_metadata(pool, &meeting.id, &key, value.as_deref())
+        .await
+        .map_err(|e| format!("Failed to save metadata: {}", e))?;
+    Ok(meeting_info(pool, &meeting_id).await?.metadata)
+}
+
+/// Meetings matching a filter query such as `tag:standup last 30 days`, newest first
+#[tauri::command]
+pub async fn query_library(state: tauri::State<'_, AppState>, query: String) -> Result<Vec<MeetingInfo>, String> {
+    let filter = parse_filter(&query, Utc::now())?;
+    let pool = state.db_manager.pool();
+    let meetings = MeetingLibraryRepository::query(pool, &filter)
+        .await
+        .map_err(|e| format!("Failed to filter meetings: {}", e))?;
+    with_details(pool, meetings).await
+}
+
+#[tauri::command]
+pub async fn list_saved_filters(state: tauri::State<'_, AppState>) -> Result<Vec<SavedFilter>, String> {
+    SavedFiltersRepository::list(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to list saved filters: {}", e))
+}
+
+/// Saves a filter query under a name; updates the filter when `id` is given
+#[tauri::command]
+pub async fn save_filter(
+    state: tauri::State<'_, AppState>,
+    id: Option<String>,
+    name: String,
+    query: String,
+) -> Result<SavedFilter, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Filter name cannot be empty".to_string());
+    }
+    // Reject queries that would fail every time the filter runs
+    parse_filter(&query, Utc::now())?;
+    SavedFiltersRepository::save(state.db_manager.pool(), id.as_deref(), name, query.trim())
+        .await
+        .map_err(|e| format!("Failed to save filter: {}", e))
+}
+
+#[tauri::command]
+pub async fn delete_saved_filter(state: tauri::State<'_, AppState>, id: String) -> Result<bool, String> {
+    SavedFiltersRepository::delete(state.db_manager.pool(), &id)
+        .await
+        .map_err(|e| format!("Failed to delete saved filter: {}", e))
+}
+
+#[tauri::command]
+pub async fn run_saved_filter(state: tauri::State<'_, AppState>, id: String) -> Result<Vec<MeetingInfo>, String> {
+    let pool = state.db_manager.pool();
+    let saved = SavedFiltersRepository::get(pool, &id)
+        .await
+        .map_err(|e| format!("Failed to load saved filter: {}", e))?
+        .ok_or_else(|| format!("No saved filter found with id {}", id))?;
+    let filter = parse_filter(&saved.query, Utc::now())?;
+    let meetings = MeetingLibraryRepository::query(pool, &filter)
+        .await
+        .map_err(|e| format!("Failed to filter meetings: {}", e))?;
+    with_details(pool, meetings).await
+}
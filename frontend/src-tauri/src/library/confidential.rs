@@ -0,0 +1,237 @@
+// library/confidential.rs
+//
+// Confidential parts of meetings. A part marked during the recording is recorded as silence,
+// so nothing said in it is ever saved or transcribed; the range is copied from the meeting
+// folder when the transcript is saved. A part marked afterwards has its transcript segments
+// deleted and its audio silenced in the saved recording. Either way the range stays on the
+// meeting as a visible gap, and summaries and exports only see what's left around it.
+
+use crate::audio::ffmpeg::silence_ranges;
+use crate::audio::playback::find_recording;
+use crate::audio::recording_saver::{load_markers, MarkerKind, MeetingMetadata, TimelineMarker};
+use crate::database::models::ConfidentialRange;
+use crate::database::repositories::confidential_range::ConfidentialRangesRepository;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::database::repositories::transcript_chunk::TranscriptChunksRepository;
+use crate::state::AppState;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::path::Path;
+use log::{info, warn};
+
+const MAX_LABEL_CHARS: usize = 200;
+const TRANSCRIPT_FILE: &str = "transcripts.json";
+
+/// What exports show in place of a confidential part
+pub const GAP_TEXT: &str = "[Confidential part removed]";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfidentialRemoval {
+    pub range: ConfidentialRange,
+    /// Transcript segments deleted because they overlapped the range
+    pub removed_segments: u64,
+    /// Whether the saved recording was silenced; false when the meeting has none
+    pub audio_silenced: bool,
+    /// The meeting was summarized before, so its summary may still mention the part
+    pub summary_outdated: bool,
+}
+
+/// The confidential parts of a recording's timeline: from each start marker to the next end
+/// marker, or to `recording_end` for one still open when the recording was cut short
+pub fn recorded_ranges(markers: &[TimelineMarker], recording_end: f64) -> Vec<(f64, f64)> {
+    let mut ranges = Vec::new();
+    let mut open: Option<f64> = None;
+    for marker in markers {
+        match marker.kind {
+            MarkerKind::ConfidentialStart => {
+                open.get_or_insert(marker.audio_time);
+            }
+            MarkerKind::ConfidentialEnd => {
+                if let Some(start) = open.take() {
+                    ranges.push((start, marker.audio_time.max(start)));
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = open {
+        ranges.push((start, recording_end.max(start)));
+    }
+    ranges
+}
+
+/// Copies the confidential parts marked during the recording from the meeting folder
+pub async fn import_recorded_ranges(pool: &SqlitePool, meeting_id: &str, folder: &Path) -> Result<usize, String> {
+    let markers = load_markers(folder).map_err(|e| format!("Failed to read markers: {}", e))?;
+    let last_marker = markers.iter().map(|marker| marker.audio_time).fold(0.0, f64::max);
+    let recording_end = std::fs::read_to_string(folder.join("metadata.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str::<MeetingMetadata>(&json).ok())
+        .and_then(|metadata| metadata.duration_seconds)
+        .unwrap_or(last_marker);
+
+    let ranges = recorded_ranges(&markers, recording_end);
+    for (start, end) in &ranges {
+        ConfidentialRangesRepository::create(pool, meeting_id, *start, *end, None)
+            .await
+            .map_err(|e| format!("Failed to save confidential part: {}", e))?;
+    }
+    Ok(ranges.len())
+}
+
+/// Drops the segments overlapping `start..end` from the meeting folder's transcript file and
+/// records the range there too
+fn remove_from_transcript_file(folder: &Path, start: f64, end: f64) -> Result<(), String> {
+    let path = folder.join(TRANSCRIPT_FILE);
+    if !path.is_file() {
+        return Ok(());
+    }
+    let content = crate::encryption::files::read_to_string(&path).map_err(|e| format!("Failed to read transcript: {}", e))?;
+    let mut json: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse transcript: {}", e))?;
+
+    if let Some(segments) = json.get_mut("segments").and_then(|segments| segments.as_array_mut()) {
+        segments.retain(|segment| {
+            let time = |key: &str| segment.get(key).and_then(|value| value.as_f64());
+            !matches!((time("audio_start_time"), time("audio_end_time")), (Some(s), Some(e)) if s < end && e > start)
+        });
+        let total = segments.len();
+        json["total_segments"] = total.into();
+    }
+    if let Some(markers) = json.get_mut("markers").and_then(|markers| markers.as_array_mut()) {
+        for marker in [TimelineMarker::new(MarkerKind::ConfidentialStart, start), TimelineMarker::new(MarkerKind::ConfidentialEnd, end)] {
+            markers.push(serde_json::to_value(marker).map_err(|e| e.to_string())?);
+        }
+    }
+
+    let temp_path = folder.join(format!(".{}.tmp", TRANSCRIPT_FILE));
+    let json_string = serde_json::to_string_pretty(&json).map_err(|e| e.to_string())?;
+    std::fs::write(&temp_path, json_string).map_err(|e| format!("Failed to write transcript: {}", e))?;
+    std::fs::rename(&temp_path, &path).map_err(|e| format!("Failed to write transcript: {}", e))
+}
+
+/// Marks `start..end` of a saved meeting confidential: its transcript is deleted from the
+/// database and the meeting folder, and its audio silenced in the recording
+pub async fn mark_range(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    start: f64,
+    end: f64,
+    label: Option<&str>,
+) -> Result<ConfidentialRemoval, String> {
+    let removed_segments = TranscriptsRepository::delete_in_range(pool, meeting_id, start, end)
+        .await
+        .map_err(|e| format!("Failed to delete transcript segments: {}", e))?;
+    // The text last summarized still holds the deleted segments
+    TranscriptChunksRepository::delete(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to clear summarized transcript: {}", e))?;
+
+    let folder = MeetingsRepository::get_folder_path(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?;
+    let mut audio_silenced = false;
+    if let Some(folder) = folder.as_deref().map(Path::new).filter(|folder| folder.is_dir()) {
+        if let Err(e) = remove_from_transcript_file(folder, start, end) {
+            warn!("Confidential part of {} left in {}: {}", meeting_id, TRANSCRIPT_FILE, e);
+        }
+        if let Some(recording) = find_recording(folder) {
+            silence_ranges(&recording, &[(start, end)])
+                .await
+                .map_err(|e| format!("Failed to silence the recording: {}", e))?;
+            audio_silenced = true;
+        }
+        crate::encryption::encrypt_meeting_folder(folder).map_err(|e| format!("Failed to encrypt meeting files: {}", e))?;
+    }
+
+    let range = ConfidentialRangesRepository::create(pool, meeting_id, start, end, label)
+        .await
+        .map_err(|e| format!("Failed to save confidential part: {}", e))?;
+    let summary_outdated = SummaryProcessesRepository::get_summary_data(pool, meeting_id)
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|process| process.result.is_some());
+    info!(
+        "🔒 Marked {:.1}s-{:.1}s of meeting {} confidential: {} segment(s) removed, audio silenced: {}",
+        start, end, meeting_id, removed_segments, audio_silenced
+    );
+    Ok(ConfidentialRemoval { range, removed_segments, audio_silenced, summary_outdated })
+}
+
+#[tauri::command]
+pub async fn list_confidential_ranges(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<ConfidentialRange>, String> {
+    ConfidentialRangesRepository::list_for_meeting(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load confidential parts: {}", e))
+}
+
+/// Marks a part of a saved meeting confidential; this can't be undone
+#[tauri::command]
+pub async fn mark_confidential_range(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    start: f64,
+    end: f64,
+    label: Option<String>,
+) -> Result<ConfidentialRemoval, String> {
+    if !start.is_finite() || !end.is_finite() || start < 0.0 || end <= start {
+        return Err(format!("Invalid confidential range: {}-{}", start, end));
+    }
+    let label = label
+        .map(|label| label.trim().chars().take(MAX_LABEL_CHARS).collect::<String>())
+        .filter(|label| !label.is_empty());
+    mark_range(state.db_manager.pool(), &meeting_id, start, end, label.as_deref()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(kind: MarkerKind, audio_time: f64) -> TimelineMarker {
+        TimelineMarker::new(kind, audio_time)
+    }
+
+    #[test]
+    fn test_recorded_ranges() {
+        let markers = vec![
+            marker(MarkerKind::Highlight, 5.0),
+            marker(MarkerKind::ConfidentialStart, 10.0),
+            marker(MarkerKind::Paused, 12.0),
+            marker(MarkerKind::ConfidentialEnd, 20.0),
+            marker(MarkerKind::ConfidentialEnd, 25.0),
+            marker(MarkerKind::ConfidentialStart, 30.0),
+        ];
+        assert_eq!(recorded_ranges(&markers, 42.0), vec![(10.0, 20.0), (30.0, 42.0)]);
+        assert_eq!(recorded_ranges(&markers[..2], 8.0), vec![(10.0, 10.0)]);
+        assert!(recorded_ranges(&markers[..1], 42.0).is_empty());
+    }
+
+    #[test]
+    fn test_remove_from_transcript_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let segment = |id: &str, start: f64, end: f64| {
+            serde_json::json!({ "id": id, "text": id, "audio_start_time": start, "audio_end_time": end })
+        };
+        let json = serde_json::json!({
+            "segments": [segment("a", 0.0, 5.0), segment("b", 5.0, 10.0), segment("c", 10.0, 15.0)],
+            "markers": [],
+            "total_segments": 3
+        });
+        std::fs::write(dir.path().join(TRANSCRIPT_FILE), json.to_string()).unwrap();
+
+        remove_from_transcript_file(dir.path(), 6.0, 10.0).unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join(TRANSCRIPT_FILE)).unwrap()).unwrap();
+        let ids: Vec<&str> = saved["segments"].as_array().unwrap().iter().map(|s| s["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, ["a", "c"]);
+        assert_eq!(saved["total_segments"], 2);
+        assert_eq!(saved["markers"][0]["kind"], "confidential_start");
+        assert_eq!(saved["markers"][1]["audio_time"], 10.0);
+    }
+}
@@ -0,0 +1,274 @@
+// library/deletion.rs
+//
+// Deleting a meeting everywhere its data lives. Its rows go in one transaction: transcript,
+// summaries, notes and embeddings, and through their triggers the search index entries. The
+// same transaction queues the meeting's folder (recording, capture leftovers, transcript
+// files) and the files exported from it, which are removed right after; a deletion cut short
+// by a crash is finished on the next start. The meeting is dropped from the Q&A index too.
+//
+// A secure wipe also overwrites each file with zeros before deleting it and has SQLite zero
+// the deleted rows. SSDs and copy-on-write file systems may keep the old blocks elsewhere,
+// and snapshots taken earlier still hold the meeting, so it is a best effort.
+
+use crate::database::models::PendingDeletion;
+use crate::database::repositories::deletion::DeletionsRepository;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+
+const WIPE_BUFFER_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct DeleteOptions {
+    /// Overwrite the files before deleting them
+    #[serde(default)]
+    pub secure_wipe: bool,
+    /// Leave the files exported from the meeting
+    #[serde(default)]
+    pub keep_exports: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DeletionReport {
+    pub files_deleted: usize,
+    pub bytes_deleted: u64,
+    pub exports_deleted: usize,
+    /// Exports left alone because they changed after being exported
+    pub exports_changed: usize,
+    pub secure_wipe: bool,
+    /// Files that couldn't be removed; they are tried again on the next start
+    pub errors: Vec<String>,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// The hash recorded for an export, to tell later whether the file changed
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    to_hex(&Sha256::digest(bytes))
+}
+
+fn file_sha256(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; WIPE_BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// Overwrites a file with zeros and flushes it to disk; returns its size
+fn overwrite(path: &Path) -> std::io::Result<u64> {
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    let size = file.metadata()?.len();
+    let zeros = vec![0u8; WIPE_BUFFER_SIZE];
+    let mut left = size;
+    while left > 0 {
+        let chunk = left.min(WIPE_BUFFER_SIZE as u64) as usize;
+        file.write_all(&zeros[..chunk])?;
+        left -= chunk as u64;
+    }
+    file.sync_all()?;
+    Ok(size)
+}
+
+/// Deletes a file or a folder and everything in it, overwriting each file first when
+/// `secure`; returns the files and bytes deleted. Links are removed, not followed.
+fn remove(path: &Path, secure: bool) -> Result<(usize, u64), String> {
+    let metadata = std::fs::symlink_metadata(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if metadata.is_dir() {
+        let entries = std::fs::read_dir(path).map_err(|e| format!("Failed to list {}: {}", path.display(), e))?;
+        let (mut files, mut bytes) = (0, 0);
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to list {}: {}", path.display(), e))?;
+            let (entry_files, entry_bytes) = remove(&entry.path(), secure)?;
+            files += entry_files;
+            bytes += entry_bytes;
+        }
+        std::fs::remove_dir(path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+        return Ok((files, bytes));
+    }
+
+    if secure && metadata.is_file() {
+        overwrite(path).map_err(|e| format!("Failed to overwrite {}: {}", path.display(), e))?;
+    }
+    std::fs::remove_file(path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+    Ok((1, metadata.len()))
+}
+
+/// Removes one queued path; exports that changed since they were written are left
+fn remove_pending(item: &PendingDeletion, report: &mut DeletionReport) -> Result<(), String> {
+    let path = Path::new(&item.path);
+    if std::fs::symlink_metadata(path).is_err() {
+        return Ok(());
+    }
+    if let Some(sha256) = &item.sha256 {
+        if !path.is_file() || file_sha256(path)? != *sha256 {
+            report.exports_changed += 1;
+            return Ok(());
+        }
+    }
+    let (files, bytes) = remove(path, item.secure_wipe)?;
+    report.files_deleted += files;
+    report.bytes_deleted += bytes;
+    if item.sha256.is_some() {
+        report.exports_deleted += 1;
+    }
+    Ok(())
+}
+
+async fn remove_files(pool: &SqlitePool, pending: Vec<PendingDeletion>, report: &mut DeletionReport) {
+    for item in pending {
+        let removed = tokio::task::spawn_blocking(move || {
+            let mut removed = DeletionReport::default();
+            let result = remove_pending(&item, &mut removed);
+            (item, removed, result)
+        })
+        .await;
+        let (item, removed, result) = match removed {
+            Ok(removed) => removed,
+            Err(e) => {
+                report.errors.push(format!("Failed to delete files: {}", e));
+                continue;
+            }
+        };
+
+        report.files_deleted += removed.files_deleted;
+        report.bytes_deleted += removed.bytes_deleted;
+        report.exports_deleted += removed.exports_deleted;
+        report.exports_changed += removed.exports_changed;
+        match result {
+            Ok(()) => {
+                if let Err(e) = DeletionsRepository::finish(pool, &item.path).await {
+                    report.errors.push(format!("Failed to update the deletion queue: {}", e));
+                }
+            }
+            Err(e) => report.errors.push(e),
+        }
+    }
+}
+
+/// Deletes a meeting, its files and its exports; None when there is no such meeting
+pub async fn delete_meeting(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    options: DeleteOptions,
+) -> Result<Option<DeletionReport>, String> {
+    if meeting_id.trim().is_empty() {
+        return Err("meeting_id cannot be empty".to_string());
+    }
+    let Some(pending) = DeletionsRepository::delete_meeting(pool, meeting_id, options.keep_exports, options.secure_wipe)
+        .await
+        .map_err(|e| format!("Failed to delete meeting {}: {}", meeting_id, e))?
+    else {
+        return Ok(None);
+    };
+    crate::qa::vector_index::remove_meeting(meeting_id);
+
+    let mut report = DeletionReport { secure_wipe: options.secure_wipe, ..Default::default() };
+    remove_files(pool, pending, &mut report).await;
+    info!(
+        "🗑️ Deleted meeting {}: {} files, {} exports{}",
+        meeting_id,
+        report.files_deleted,
+        report.exports_deleted,
+        if options.secure_wipe { ", wiped" } else { "" }
+    );
+    for error in &report.errors {
+        warn!("🗑️ Deleting meeting {}: {}", meeting_id, error);
+    }
+    Ok(Some(report))
+}
+
+/// Removes files of meetings whose deletion was cut short when the app last closed
+pub async fn finish_pending(pool: &SqlitePool) {
+    let pending = match DeletionsRepository::pending(pool).await {
+        Ok(pending) if pending.is_empty() => return,
+        Ok(pending) => pending,
+        Err(e) => {
+            warn!("Failed to load the deletion queue: {}", e);
+            return;
+        }
+    };
+    let mut report = DeletionReport::default();
+    remove_files(pool, pending, &mut report).await;
+    info!("🗑️ Finished deleting {} files of deleted meetings", report.files_deleted);
+    for error in &report.errors {
+        warn!("🗑️ {}", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::database::models::DateTimeUtc;
+    use tempfile::tempdir;
+
+    fn pending(path: &Path, sha256: Option<String>, secure_wipe: bool) -> PendingDeletion {
+        PendingDeletion {
+            path: path.to_string_lossy().into_owned(),
+            meeting_id: "meeting-1".to_string(),
+            sha256,
+            secure_wipe,
+            created_at: DateTimeUtc(Utc::now()),
+        }
+    }
+
+    #[test]
+    fn test_overwrite_zeroes_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audio.mp4");
+        std::fs::write(&path, vec![7u8; WIPE_BUFFER_SIZE + 10]).unwrap();
+
+        assert_eq!(overwrite(&path).unwrap(), WIPE_BUFFER_SIZE as u64 + 10);
+        let wiped = std::fs::read(&path).unwrap();
+        assert_eq!(wiped.len(), WIPE_BUFFER_SIZE + 10);
+        assert!(wiped.iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn test_remove_folder() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("Meeting");
+        std::fs::create_dir_all(folder.join(".checkpoints")).unwrap();
+        std::fs::write(folder.join("audio.mp4"), [1u8; 1000]).unwrap();
+        std::fs::write(folder.join("transcripts.json"), "{}").unwrap();
+        std::fs::write(folder.join(".checkpoints").join("audio_chunk_000.mp4"), [1u8; 500]).unwrap();
+
+        let mut report = DeletionReport::default();
+        remove_pending(&pending(&folder, None, true), &mut report).unwrap();
+        assert!(!folder.exists());
+        assert_eq!((report.files_deleted, report.bytes_deleted), (3, 1502));
+
+        // Already gone
+        remove_pending(&pending(&folder, None, true), &mut report).unwrap();
+        assert_eq!(report.files_deleted, 3);
+    }
+
+    #[test]
+    fn test_changed_exports_are_kept() {
+        let dir = tempdir().unwrap();
+        let unchanged = dir.path().join("minutes.md");
+        let changed = dir.path().join("minutes.pdf");
+        std::fs::write(&unchanged, "# Minutes").unwrap();
+        std::fs::write(&changed, "edited since").unwrap();
+
+        let mut report = DeletionReport::default();
+        remove_pending(&pending(&unchanged, Some(sha256_hex(b"# Minutes")), false), &mut report).unwrap();
+        remove_pending(&pending(&changed, Some(sha256_hex(b"as exported")), false), &mut report).unwrap();
+        assert!(!unchanged.exists());
+        assert!(changed.exists());
+        assert_eq!((report.exports_deleted, report.exports_changed), (1, 1));
+    }
+}
@@ -0,0 +1,91 @@
+// library/fields.rs
+//
+// Cleans up what users type into the library fields before it is stored.
+
+use crate::database::models::Participant;
+
+const MAX_TAG_CHARS: usize = 64;
+const MAX_METADATA_KEY_CHARS: usize = 64;
+
+/// A tag without surrounding space, a leading `#` or repeated inner spaces; None if empty
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#');
+    let tag: String = tag.split_whitespace().collect::<Vec<_>>().join(" ");
+    if tag.is_empty() {
+        return None;
+    }
+    Some(tag.chars().take(MAX_TAG_CHARS).collect())
+}
+
+/// Normalized tags without empties and case-insensitive duplicates, first spelling kept
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.iter().filter_map(|tag| normalize_tag(tag)) {
+        if !normalized.iter().any(|existing| existing.eq_ignore_ascii_case(&tag)) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// Participants with trimmed names, without nameless entries and repeated names
+pub fn normalize_participants(participants: Vec<Participant>) -> Vec<Participant> {
+    let mut normalized: Vec<Participant> = Vec::new();
+    for participant in participants {
+        let name = participant.name.trim().to_string();
+        if name.is_empty() || normalized.iter().any(|p| p.name.eq_ignore_ascii_case(&name)) {
+            continue;
+        }
+        let email = participant.email.map(|email| email.trim().to_string()).filter(|email| !email.is_empty());
+        normalized.push(Participant { name, email });
+    }
+    normalized
+}
+
+pub fn validate_metadata_key(key: &str) -> Result<String, String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Metadata key cannot be empty".to_string());
+    }
+    if key.chars().count() > MAX_METADATA_KEY_CHARS {
+        return Err(format!("Metadata key is longer than {} characters", MAX_METADATA_KEY_CHARS));
+    }
+    Ok(key.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_are_cleaned_and_deduplicated() {
+        let tags = vec![
+            " #standup ".to_string(),
+            "Standup".to_string(),
+            "customer   call".to_string(),
+            "  ".to_string(),
+            "#".to_string(),
+        ];
+        assert_eq!(normalize_tags(&tags), vec!["standup", "customer call"]);
+        assert_eq!(normalize_tag(&"x".repeat(100)).unwrap().len(), MAX_TAG_CHARS);
+    }
+
+    #[test]
+    fn test_participants_are_cleaned() {
+        let participants = vec![
+            Participant { name: " Alice ".to_string(), email: Some(" alice@example.com ".to_string()) },
+            Participant { name: "alice".to_string(), email: None },
+            Participant { name: "Bob".to_string(), email: Some("".to_string()) },
+            Participant { name: " ".to_string(), email: None },
+        ];
+        assert_eq!(
+            normalize_participants(participants),
+            vec![
+                Participant { name: "Alice".to_string(), email: Some("alice@example.com".to_string()) },
+                Participant { name: "Bob".to_string(), email: None },
+            ]
+        );
+        assert!(validate_metadata_key(" ").is_err());
+        assert_eq!(validate_metadata_key(" crm_id ").unwrap(), "crm_id");
+    }
+}
@@ -0,0 +1,173 @@
+// library/filter.rs
+//
+// The query language of library filters, e.g. `tag:standup last 30 days`.
+//
+// Supported terms:
+// - `tag:standup`, `tag:"customer call"`: meetings with the tag; `-tag:x` excludes it
+// - `folder:Clients`: meetings filed in the folder or any folder below it
+// - `participant:alice` (or `with:alice`): a participant whose name or email contains it
+// - `after:2025-09-01`, `before:2025-10-01`: by creation date (`before` includes the day)
+// - `last 30 days`, `last 2 weeks`, `last 3 months`, `last:30d`: created recently
+// - `is:archived` only archived meetings, `is:any` archived or not (default: not archived)
+// - anything else: words the title must contain
+//
+// Relative dates are resolved when the filter runs, so a saved filter keeps meaning
+// "the last 30 days".
+
+use crate::search::query::parse_date_bound;
+use chrono::{DateTime, Duration, Utc};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArchivedFilter {
+    #[default]
+    Exclude,
+    Only,
+    Include,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LibraryFilter {
+    pub tags: Vec<String>,
+    pub excluded_tags: Vec<String>,
+    pub folder: Option<String>,
+    pub participant: Option<String>,
+    /// Created at or after
+    pub after: Option<DateTime<Utc>>,
+    /// Created before
+    pub before: Option<DateTime<Utc>>,
+    pub archived: ArchivedFilter,
+    pub title_words: Vec<String>,
+}
+
+/// Splits on whitespace outside double quotes and drops the quotes
+fn tokenize(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in query.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Days in `count` of `unit` (day, week, month; singular, plural or abbreviated)
+///
+/// A month counts as 30 days.
+fn period_days(count: i64, unit: &str) -> Option<i64> {
+    let days_per_unit = match unit.to_lowercase().trim_end_matches('s') {
+        "d" | "day" => 1,
+        "w" | "week" => 7,
+        "m" | "month" => 30,
+        _ => return None,
+    };
+    Some(count * days_per_unit)
+}
+
+/// `30d`, `2w`, `3m`
+fn compact_period_days(value: &str) -> Option<i64> {
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let count = value[..split].parse().ok()?;
+    period_days(count, &value[split..])
+}
+
+/// Parses a filter query; `now` anchors relative dates
+pub fn parse_filter(query: &str, now: DateTime<Utc>) -> Result<LibraryFilter, String> {
+    let mut filter = LibraryFilter::default();
+    let tokens = tokenize(query);
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = &tokens[i];
+        i += 1;
+
+        if token.eq_ignore_ascii_case("last") {
+            let count = tokens.get(i).and_then(|count| count.parse::<i64>().ok());
+            let days = count.zip(tokens.get(i + 1)).and_then(|(count, unit)| period_days(count, unit));
+            let days = days.ok_or_else(|| "Expected a period after \"last\", e.g. \"last 30 days\"".to_string())?;
+            filter.after = Some(now - Duration::days(days));
+            i += 2;
+            continue;
+        }
+
+        let (negated, term) = match token.strip_prefix('-') {
+            Some(rest) if rest.contains(':') => (true, rest),
+            _ => (false, token.as_str()),
+        };
+        let Some((key, value)) = term.split_once(':') else {
+            filter.title_words.push(token.clone());
+            continue;
+        };
+        let value = value.trim();
+        match (key.to_lowercase().as_str(), negated) {
+            ("tag", false) => filter.tags.push(value.trim_start_matches('#').to_string()),
+            ("tag", true) => filter.excluded_tags.push(value.trim_start_matches('#').to_string()),
+            ("folder", false) => filter.folder = Some(value.to_string()),
+            ("participant" | "with", false) => filter.participant = Some(value.to_string()),
+            ("after", false) => filter.after = Some(parse_date_bound(value, false)?),
+            ("before", false) => filter.before = Some(parse_date_bound(value, true)?),
+            ("last", false) => {
+                let days = compact_period_days(value).ok_or_else(|| format!("Invalid period: {}", value))?;
+                filter.after = Some(now - Duration::days(days));
+            }
+            ("is", false) => {
+                filter.archived = match value.to_lowercase().as_str() {
+                    "archived" => ArchivedFilter::Only,
+                    "any" | "all" => ArchivedFilter::Include,
+                    "active" => ArchivedFilter::Exclude,
+                    _ => return Err(format!("Unknown filter is:{}", value)),
+                }
+            }
+            (_, true) => return Err(format!("Only tags can be excluded, not {}", key)),
+            // A colon in a title word ("Q3:"), not a filter
+            _ => filter.title_words.push(token.clone()),
+        }
+    }
+    filter.tags.retain(|tag| !tag.is_empty());
+    filter.excluded_tags.retain(|tag| !tag.is_empty());
+    Ok(filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-11-01T12:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_filter_terms() {
+        let filter = parse_filter(r#"tag:standup -tag:#skip folder:"Client work" with:alice last 30 days Q3: plan"#, now())
+            .unwrap();
+        assert_eq!(filter.tags, vec!["standup"]);
+        assert_eq!(filter.excluded_tags, vec!["skip"]);
+        assert_eq!(filter.folder.as_deref(), Some("Client work"));
+        assert_eq!(filter.participant.as_deref(), Some("alice"));
+        assert_eq!(filter.after, Some(now() - Duration::days(30)));
+        assert_eq!(filter.archived, ArchivedFilter::Exclude);
+        assert_eq!(filter.title_words, vec!["Q3:", "plan"]);
+    }
+
+    #[test]
+    fn test_parse_filter_dates_and_errors() {
+        let filter = parse_filter("last:2w is:archived before:2025-10-01", now()).unwrap();
+        assert_eq!(filter.after, Some(now() - Duration::days(14)));
+        assert_eq!(filter.before.unwrap().to_rfc3339(), "2025-10-02T00:00:00+00:00");
+        assert_eq!(filter.archived, ArchivedFilter::Only);
+
+        assert!(parse_filter("last week", now()).is_err());
+        assert!(parse_filter("is:deleted", now()).is_err());
+        assert!(parse_filter("-folder:x", now()).is_err());
+        assert_eq!(parse_filter("", now()).unwrap(), LibraryFilter::default());
+    }
+}
@@ -0,0 +1,184 @@
+// library/folders.rs
+//
+// Hierarchical folders to file meetings in. A meeting is in at most one folder; deleting
+// a folder moves its subfolders and meetings up to its parent.
+
+use crate::database::models::{DateTimeUtc, Folder};
+use crate::database::repositories::folder::FoldersRepository;
+use crate::state::AppState;
+use serde::Serialize;
+use std::collections::HashMap;
+
+const MAX_FOLDER_NAME_CHARS: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderInfo {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    pub created_at: DateTimeUtc,
+    /// Meetings filed directly in the folder
+    pub meeting_count: i64,
+}
+
+fn folder_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Folder name cannot be empty".to_string());
+    }
+    Ok(name.chars().take(MAX_FOLDER_NAME_CHARS).collect())
+}
+
+/// Whether making `parent_id` the parent of `folder_id` would put the folder inside itself
+pub fn creates_cycle(folders: &[Folder], folder_id: &str, parent_id: &str) -> bool {
+    let parents: HashMap<&str, Option<&str>> =
+        folders.iter().map(|folder| (folder.id.as_str(), folder.parent_id.as_deref())).collect();
+    let mut current = Some(parent_id);
+    // Bounded walk, so a cycle already in the data can't hang us
+    for _ in 0..=folders.len() {
+        match current {
+            Some(id) if id == folder_id => return true,
+            Some(id) => current = parents.get(id).copied().flatten(),
+            None => return false,
+        }
+    }
+    true
+}
+
+async fn require_folder(pool: &sqlx::SqlitePool, folder_id: &str) -> Result<Folder, String> {
+    FoldersRepository::get(pool, folder_id)
+        .await
+        .map_err(|e| format!("Failed to load folder: {}", e))?
+        .ok_or_else(|| format!("No folder found with id {}", folder_id))
+}
+
+/// All folders, flat; the frontend builds the tree from `parent_id`
+#[tauri::command]
+pub async fn list_folders(state: tauri::State<'_, AppState>) -> Result<Vec<FolderInfo>, String> {
+    let pool = state.db_manager.pool();
+    let folders = FoldersRepository::list(pool)
+        .await
+        .map_err(|e| format!("Failed to list folders: {}", e))?;
+    let counts: HashMap<String, i64> = FoldersRepository::meeting_counts(pool)
+        .await
+        .map_err(|e| format!("Failed to count meetings: {}", e))?
+        .into_iter()
+        .collect();
+    Ok(folders
+        .into_iter()
+        .map(|folder| FolderInfo {
+            meeting_count: counts.get(&folder.id).copied().unwrap_or(0),
+            id: folder.id,
+            name: folder.name,
+            parent_id: folder.parent_id,
+            created_at: folder.created_at,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn create_folder(
+    state: tauri::State<'_, AppState>,
+    name: String,
+    parent_id: Option<String>,
+) -> Result<Folder, String> {
+    let name = folder_name(&name)?;
+    let pool = state.db_manager.pool();
+    if let Some(parent_id) = parent_id.as_deref() {
+        require_folder(pool, parent_id).await?;
+    }
+    FoldersRepository::create(pool, &name, parent_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to create folder: {}", e))
+}
+
+#[tauri::command]
+pub async fn rename_folder(
+    state: tauri::State<'_, AppState>,
+    folder_id: String,
+    name: String,
+) -> Result<Folder, String> {
+    let name = folder_name(&name)?;
+    let pool = state.db_manager.pool();
+    FoldersRepository::rename(pool, &folder_id, &name)
+        .await
+        .map_err(|e| format!("Failed to rename folder: {}", e))?;
+    require_folder(pool, &folder_id).await
+}
+
+/// Moves a folder under another one; None makes it a top-level folder
+#[tauri::command]
+pub async fn move_folder(
+    state: tauri::State<'_, AppState>,
+    folder_id: String,
+    parent_id: Option<String>,
+) -> Result<Folder, String> {
+    let pool = state.db_manager.pool();
+    require_folder(pool, &folder_id).await?;
+    if let Some(parent_id) = parent_id.as_deref() {
+        let folders = FoldersRepository::list(pool)
+            .await
+            .map_err(|e| format!("Failed to list folders: {}", e))?;
+        if !folders.iter().any(|folder| folder.id == parent_id) {
+            return Err(format!("No folder found with id {}", parent_id));
+        }
+        if creates_cycle(&folders, &folder_id, parent_id) {
+            return Err("A folder cannot be moved into itself or one of its subfolders".to_string());
+        }
+    }
+    FoldersRepository::set_parent(pool, &folder_id, parent_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to move folder: {}", e))?;
+    require_folder(pool, &folder_id).await
+}
+
+/// Deletes a folder; its subfolders and meetings move up to its parent
+#[tauri::command]
+pub async fn delete_folder(state: tauri::State<'_, AppState>, folder_id: String) -> Result<bool, String> {
+    FoldersRepository::delete(state.db_manager.pool(), &folder_id)
+        .await
+        .map_err(|e| format!("Failed to delete folder: {}", e))
+}
+
+/// Files a meeting in a folder; None takes it out of its folder
+#[tauri::command]
+pub async fn move_meeting_to_folder(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    folder_id: Option<String>,
+) -> Result<(), String> {
+    let pool = state.db_manager.pool();
+    if let Some(folder_id) = folder_id.as_deref() {
+        require_folder(pool, folder_id).await?;
+    }
+    if !FoldersRepository::move_meeting(pool, &meeting_id, folder_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to move meeting: {}", e))?
+    {
+        return Err(format!("No meeting found with id {}", meeting_id));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folder(id: &str, parent_id: Option<&str>) -> Folder {
+        Folder {
+            id: id.to_string(),
+            name: id.to_string(),
+            parent_id: parent_id.map(str::to_string),
+            created_at: DateTimeUtc(chrono::Utc::now()),
+        }
+    }
+
+    #[test]
+    fn test_creates_cycle() {
+        let folders = vec![folder("a", None), folder("b", Some("a")), folder("c", Some("b")), folder("d", None)];
+        assert!(creates_cycle(&folders, "a", "c"));
+        assert!(creates_cycle(&folders, "b", "b"));
+        assert!(!creates_cycle(&folders, "c", "a"));
+        assert!(!creates_cycle(&folders, "a", "d"));
+    }
+}
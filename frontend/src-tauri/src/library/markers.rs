@@ -0,0 +1,199 @@
+// library/markers.rs
+//
+// Highlight markers of saved meetings. Markers dropped during a recording are copied from
+// the meeting folder when the transcript is saved, and more can be added afterwards as
+// bookmarks. When summarizing, they're given to the model as the moments the user flagged
+// as important, with what was being said at the time.
+
+use crate::audio::recording_saver::{load_markers, MarkerKind};
+use crate::database::models::{MeetingMarker, Transcript};
+use crate::database::repositories::marker::MarkersRepository;
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::path::Path;
+
+const MAX_LABEL_CHARS: usize = 200;
+const MAX_EXCERPT_CHARS: usize = 300;
+/// Seconds of speech before a marker that count as its context; the user usually reacts
+/// to something just said
+const CONTEXT_BEFORE_SECS: f64 = 20.0;
+const CONTEXT_AFTER_SECS: f64 = 5.0;
+
+fn marker_label(label: Option<String>) -> Option<String> {
+    label
+        .map(|label| label.trim().chars().take(MAX_LABEL_CHARS).collect::<String>())
+        .filter(|label| !label.is_empty())
+}
+
+fn timestamp(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    if total >= 3600 {
+        format!("[{}:{:02}:{:02}]", total / 3600, total / 60 % 60, total % 60)
+    } else {
+        format!("[{:02}:{:02}]", total / 60, total % 60)
+    }
+}
+
+/// What was said around `audio_time`
+fn excerpt(transcripts: &[Transcript], audio_time: f64) -> Option<String> {
+    let text = transcripts
+        .iter()
+        .filter(|t| match (t.audio_start_time, t.audio_end_time) {
+            (Some(start), Some(end)) => {
+                end >= audio_time - CONTEXT_BEFORE_SECS && start <= audio_time + CONTEXT_AFTER_SECS
+            }
+            _ => false,
+        })
+        .map(|t| t.transcript.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    let mut chars = text.chars();
+    let mut excerpt: String = chars.by_ref().take(MAX_EXCERPT_CHARS).collect();
+    if chars.next().is_some() {
+        excerpt.push('…');
+    }
+    Some(excerpt)
+}
+
+/// The flagged moments for the summary prompt, or `None` when there are none
+pub fn flagged_moments(markers: &[MeetingMarker], transcripts: &[Transcript]) -> Option<String> {
+    if markers.is_empty() {
+        return None;
+    }
+    let mut lines = vec!["Moments the user flagged as important during the meeting:".to_string()];
+    for marker in markers {
+        let mut line = format!("- {}", timestamp(marker.audio_time));
+        if let Some(label) = &marker.label {
+            line.push_str(&format!(" {}", label));
+        }
+        if let Some(excerpt) = excerpt(transcripts, marker.audio_time) {
+            line.push_str(&format!(" (said at the time: \"{}\")", excerpt));
+        }
+        lines.push(line);
+    }
+    Some(lines.join("\n"))
+}
+
+/// Copies the highlights dropped during the recording from the meeting folder
+pub async fn import_recorded_markers(pool: &SqlitePool, meeting_id: &str, folder: &Path) -> Result<usize, String> {
+    let markers = load_markers(folder).map_err(|e| format!("Failed to read markers: {}", e))?;
+    let mut count = 0;
+    for marker in markers.into_iter().filter(|marker| marker.kind == MarkerKind::Highlight) {
+        let created_at = DateTime::parse_from_rfc3339(&marker.wall_time)
+            .map(|time| time.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+        MarkersRepository::create(pool, meeting_id, marker.audio_time, marker.label.as_deref(), created_at)
+            .await
+            .map_err(|e| format!("Failed to save marker: {}", e))?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[tauri::command]
+pub async fn list_meeting_markers(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<MeetingMarker>, String> {
+    MarkersRepository::list_for_meeting(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load markers: {}", e))
+}
+
+/// Bookmarks a moment of a saved meeting
+#[tauri::command]
+pub async fn add_meeting_marker(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    audio_time: f64,
+    label: Option<String>,
+) -> Result<MeetingMarker, String> {
+    if !audio_time.is_finite() || audio_time < 0.0 {
+        return Err(format!("Invalid marker time: {}", audio_time));
+    }
+    MarkersRepository::create(state.db_manager.pool(), &meeting_id, audio_time, marker_label(label).as_deref(), Utc::now())
+        .await
+        .map_err(|e| format!("Failed to save marker: {}", e))
+}
+
+#[tauri::command]
+pub async fn update_meeting_marker(
+    state: tauri::State<'_, AppState>,
+    marker_id: String,
+    label: Option<String>,
+) -> Result<bool, String> {
+    MarkersRepository::update_label(state.db_manager.pool(), &marker_id, marker_label(label).as_deref())
+        .await
+        .map_err(|e| format!("Failed to update marker: {}", e))
+}
+
+#[tauri::command]
+pub async fn delete_meeting_marker(state: tauri::State<'_, AppState>, marker_id: String) -> Result<bool, String> {
+    MarkersRepository::delete(state.db_manager.pool(), &marker_id)
+        .await
+        .map_err(|e| format!("Failed to delete marker: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::DateTimeUtc;
+
+    fn transcript(text: &str, start: f64, end: f64) -> Transcript {
+        Transcript {
+            id: format!("t-{}", start),
+            meeting_id: "meeting-1".to_string(),
+            transcript: text.to_string(),
+            timestamp: String::new(),
+            summary: None,
+            action_items: None,
+            key_points: None,
+            audio_start_time: Some(start),
+            audio_end_time: Some(end),
+            duration: Some(end - start),
+            translation: None,
+            words: None,
+            speaker: None,
+        }
+    }
+
+    fn marker(audio_time: f64, label: Option<&str>) -> MeetingMarker {
+        MeetingMarker {
+            id: "marker-1".to_string(),
+            meeting_id: "meeting-1".to_string(),
+            audio_time,
+            label: label.map(str::to_string),
+            created_at: DateTimeUtc(Utc::now()),
+        }
+    }
+
+    #[test]
+    fn test_flagged_moments() {
+        let transcripts = vec![
+            transcript("Welcome everyone.", 0.0, 4.0),
+            transcript("We ship on Friday.", 100.0, 104.0),
+            transcript("Any questions?", 106.0, 108.0),
+        ];
+        let markers = vec![marker(105.0, Some("Deadline")), marker(3725.0, None)];
+        let prompt = flagged_moments(&markers, &transcripts).unwrap();
+        assert_eq!(
+            prompt,
+            "Moments the user flagged as important during the meeting:\n\
+             - [01:45] Deadline (said at the time: \"We ship on Friday. Any questions?\")\n\
+             - [1:02:05]"
+        );
+        assert_eq!(flagged_moments(&[], &transcripts), None);
+    }
+
+    #[test]
+    fn test_marker_label() {
+        assert_eq!(marker_label(Some("  ".to_string())), None);
+        assert_eq!(marker_label(Some(" Budget ".to_string())).as_deref(), Some("Budget"));
+        assert_eq!(marker_label(Some("x".repeat(500))).map(|l| l.len()), Some(MAX_LABEL_CHARS));
+    }
+}
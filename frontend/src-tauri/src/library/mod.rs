@@ -0,0 +1,27 @@
+/// Library module - organizing the saved meetings
+///
+/// Meetings can be created without a recording, renamed, archived, tagged, given a list
+/// of participants and notes, and carry arbitrary key-value metadata for the frontend.
+/// Deleting one removes its data from every store it lives in, optionally wiping it.
+///
+/// Meetings are filed in hierarchical folders and found with filter queries such as
+/// `tag:standup last 30 days`, which can be saved under a name.
+///
+/// This module contains:
+/// - Confidential parts of meetings, and removing them after the fact (`confidential`)
+/// - Deleting meetings with their files and exports (`deletion`)
+/// - Normalization of user-entered tags, participants and metadata keys (`fields`)
+/// - The filter query language (`filter`)
+/// - Folders and their commands (`folders`)
+/// - Highlight markers, and the flagged moments given to the summarizer (`markers`)
+/// - The retention policy and the janitor applying it (`retention`)
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+pub mod confidential;
+pub mod deletion;
+pub mod fields;
+pub mod filter;
+pub mod folders;
+pub mod markers;
+pub mod retention;
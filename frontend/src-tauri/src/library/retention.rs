@@ -0,0 +1,285 @@
+// library/retention.rs
+//
+// Retention policy for saved meetings. Recorded audio takes far more space than anything
+// else, so it can be deleted a number of days after the meeting while the transcript, summary
+// and notes are kept; whole meetings can be deleted after a number of days too, leaving the
+// files exported from them. Both are off by default, so everything is kept forever. The
+// policy is stored in the Tauri store and applied by a janitor task a few minutes after
+// startup and every six hours, or on demand.
+
+use crate::audio::session;
+use crate::audio::write_ahead::WAL_DIR;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::library::deletion::{self, DeleteOptions};
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "retention.json";
+const STORE_KEY: &str = "retention_policy";
+
+/// Deleting waits until startup is over and an interrupted recording has been offered for recovery
+const FIRST_RUN_DELAY: Duration = Duration::from_secs(5 * 60);
+const RUN_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const MAX_DAYS: u32 = 3650;
+
+/// Capture leftovers kept next to the saved recording until it's finalized
+const CHECKPOINTS_DIR: &str = ".checkpoints";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Days after the meeting when its recorded audio is deleted; `None` keeps it
+    pub delete_audio_after_days: Option<u32>,
+    /// Days after the meeting when the whole meeting is deleted; `None` keeps it
+    pub delete_meetings_after_days: Option<u32>,
+}
+
+impl RetentionPolicy {
+    fn normalized(self) -> Self {
+        let clamp = |days: Option<u32>| days.map(|days| days.clamp(1, MAX_DAYS));
+        Self {
+            delete_audio_after_days: clamp(self.delete_audio_after_days),
+            delete_meetings_after_days: clamp(self.delete_meetings_after_days),
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.delete_audio_after_days.is_some() || self.delete_meetings_after_days.is_some()
+    }
+}
+
+/// What the policy does with a meeting created at `created_at`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expiry {
+    Keep,
+    DeleteAudio,
+    DeleteMeeting,
+}
+
+fn expiry(policy: &RetentionPolicy, created_at: DateTime<Utc>, now: DateTime<Utc>) -> Expiry {
+    let age_days = (now - created_at).num_days();
+    let past = |days: Option<u32>| days.is_some_and(|days| age_days >= days as i64);
+    if past(policy.delete_meetings_after_days) {
+        Expiry::DeleteMeeting
+    } else if past(policy.delete_audio_after_days) {
+        Expiry::DeleteAudio
+    } else {
+        Expiry::Keep
+    }
+}
+
+/// The recorded audio in a meeting folder: the saved recording and capture leftovers
+fn audio_paths(folder: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            if path.is_dir() {
+                path.file_name().is_some_and(|name| name == CHECKPOINTS_DIR || name == WAL_DIR)
+            } else {
+                path.file_stem().is_some_and(|stem| stem == "audio")
+            }
+        })
+        .collect()
+}
+
+fn size_of(path: &Path) -> u64 {
+    if path.is_dir() {
+        std::fs::read_dir(path)
+            .map(|entries| entries.filter_map(|entry| entry.ok()).map(|entry| size_of(&entry.path())).sum())
+            .unwrap_or(0)
+    } else {
+        std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0)
+    }
+}
+
+/// Deletes the recorded audio in `folder`; returns the bytes freed
+fn delete_audio(folder: &Path) -> Result<u64, String> {
+    let mut freed = 0;
+    for path in audio_paths(folder) {
+        let size = size_of(&path);
+        let removed = if path.is_dir() { std::fs::remove_dir_all(&path) } else { std::fs::remove_file(&path) };
+        removed.map_err(|e| format!("Failed to delete {:?}: {}", path, e))?;
+        freed += size;
+    }
+    Ok(freed)
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RetentionReport {
+    /// Meetings whose audio was deleted
+    pub audio_deleted: usize,
+    pub meetings_deleted: usize,
+    pub bytes_freed: u64,
+    pub errors: Vec<String>,
+}
+
+pub fn load_policy<R: Runtime>(app: &AppHandle<R>) -> RetentionPolicy {
+    let stored = match app.store(STORE_FILE) {
+        Ok(store) => store.get(STORE_KEY),
+        Err(e) => {
+            warn!("Failed to open {}: {}", STORE_FILE, e);
+            None
+        }
+    };
+    stored.and_then(|value| serde_json::from_value(value).ok()).unwrap_or_default()
+}
+
+fn save_policy<R: Runtime>(app: &AppHandle<R>, policy: &RetentionPolicy) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    let value = serde_json::to_value(policy).map_err(|e| e.to_string())?;
+    store.set(STORE_KEY, value);
+    store.save().map_err(|e| format!("Failed to save {}: {}", STORE_FILE, e))
+}
+
+/// Applies `policy` to every saved meeting
+pub async fn apply_policy(pool: &SqlitePool, policy: &RetentionPolicy) -> Result<RetentionReport, String> {
+    let mut report = RetentionReport::default();
+    if !policy.is_active() {
+        return Ok(report);
+    }
+
+    let meetings = MeetingsRepository::get_meetings(pool)
+        .await
+        .map_err(|e| format!("Failed to load meetings: {}", e))?;
+    let now = Utc::now();
+    for meeting in meetings {
+        let expiry = expiry(policy, meeting.created_at.0, now);
+        if expiry == Expiry::Keep {
+            continue;
+        }
+
+        if expiry == Expiry::DeleteMeeting {
+            // Exports were made to keep a copy, which retention shouldn't take away
+            let options = DeleteOptions { keep_exports: true, ..DeleteOptions::default() };
+            match deletion::delete_meeting(pool, &meeting.id, options).await {
+                Ok(Some(deleted)) => {
+                    report.meetings_deleted += 1;
+                    report.bytes_freed += deleted.bytes_deleted;
+                    report.errors.extend(deleted.errors);
+                }
+                Ok(None) => {}
+                Err(e) => report.errors.push(e),
+            }
+            continue;
+        }
+
+        if let Some(folder) = meeting.folder_path.as_deref().map(Path::new) {
+            match delete_audio(folder) {
+                Ok(0) => {}
+                Ok(freed) => {
+                    report.bytes_freed += freed;
+                    report.audio_deleted += 1;
+                }
+                Err(e) => report.errors.push(e),
+            }
+        }
+    }
+
+    info!(
+        "🧹 Retention: deleted audio of {} meetings and {} meetings, freed {} MB",
+        report.audio_deleted,
+        report.meetings_deleted,
+        report.bytes_freed / (1024 * 1024)
+    );
+    for error in &report.errors {
+        warn!("🧹 Retention: {}", error);
+    }
+    Ok(report)
+}
+
+/// Applies the stored policy every few hours; skipped while recording
+pub fn start_retention_janitor<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_RUN_DELAY).await;
+        let pool = AppState::wait_for_pool(&app).await;
+        loop {
+            let policy = load_policy(&app);
+            if policy.is_active() {
+                if session::phase().is_recording() {
+                    info!("🧹 Retention postponed while recording");
+                } else if let Err(e) = apply_policy(&pool, &policy).await {
+                    warn!("🧹 Retention failed: {}", e);
+                }
+            }
+            tokio::time::sleep(RUN_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_retention_policy<R: Runtime>(app: AppHandle<R>) -> Result<RetentionPolicy, String> {
+    Ok(load_policy(&app))
+}
+
+/// Saves the policy; it's applied on the janitor's next run
+#[tauri::command]
+pub async fn set_retention_policy<R: Runtime>(app: AppHandle<R>, policy: RetentionPolicy) -> Result<RetentionPolicy, String> {
+    let policy = policy.normalized();
+    save_policy(&app, &policy)?;
+    info!("🧹 Retention policy set to {:?}", policy);
+    Ok(policy)
+}
+
+/// Applies the stored policy now
+#[tauri::command]
+pub async fn run_retention_now<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<RetentionReport, String> {
+    if session::phase().is_recording() {
+        return Err("Retention can't run while recording".to_string());
+    }
+    apply_policy(state.db_manager.pool(), &load_policy(&app)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_expiry() {
+        let now = Utc::now();
+        let policy = RetentionPolicy { delete_audio_after_days: Some(30), delete_meetings_after_days: Some(365) };
+        assert_eq!(expiry(&policy, now - ChronoDuration::days(10), now), Expiry::Keep);
+        assert_eq!(expiry(&policy, now - ChronoDuration::days(30), now), Expiry::DeleteAudio);
+        assert_eq!(expiry(&policy, now - ChronoDuration::days(400), now), Expiry::DeleteMeeting);
+        assert_eq!(expiry(&RetentionPolicy::default(), now - ChronoDuration::days(4000), now), Expiry::Keep);
+    }
+
+    #[test]
+    fn test_normalized() {
+        let policy = RetentionPolicy { delete_audio_after_days: Some(0), delete_meetings_after_days: Some(100_000) };
+        assert_eq!(
+            policy.normalized(),
+            RetentionPolicy { delete_audio_after_days: Some(1), delete_meetings_after_days: Some(MAX_DAYS) }
+        );
+    }
+
+    #[test]
+    fn test_delete_audio_keeps_transcripts() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("audio.mp4"), [0u8; 1000]).unwrap();
+        std::fs::write(dir.path().join("transcripts.json"), "{}").unwrap();
+        std::fs::write(dir.path().join("metadata.json"), "{}").unwrap();
+        std::fs::create_dir(dir.path().join(CHECKPOINTS_DIR)).unwrap();
+        std::fs::write(dir.path().join(CHECKPOINTS_DIR).join("audio_chunk_000.mp4"), [0u8; 500]).unwrap();
+
+        assert_eq!(delete_audio(dir.path()).unwrap(), 1500);
+        assert!(!dir.path().join("audio.mp4").exists());
+        assert!(!dir.path().join(CHECKPOINTS_DIR).exists());
+        assert!(dir.path().join("transcripts.json").exists());
+        assert!(dir.path().join("metadata.json").exists());
+        assert_eq!(delete_audio(dir.path()).unwrap(), 0);
+    }
+}
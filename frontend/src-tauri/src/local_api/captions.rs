@@ -0,0 +1,52 @@
+// local_api/captions.rs
+//
+// Live captions over WebSocket, for OBS overlays or a second screen. Each caption change
+// during a recording is sent as a JSON text frame: `{"type": "partial", ...}` while an
+// utterance is being spoken, then `{"type": "final", ...}` once it's transcribed (with
+// `update` null when it produced no text, which clears the caption). Browsers can't send
+// headers when opening a WebSocket, so the token may be given as `?token=` instead.
+//
+// `/captions` serves a ready-made overlay page that connects to the stream.
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::{Html, Response};
+use log::debug;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::audio::transcription::streaming::subscribe_captions;
+
+const OVERLAY_PAGE: &str = include_str!("captions.html");
+
+pub(super) async fn captions(upgrade: WebSocketUpgrade) -> Response {
+    upgrade.on_upgrade(stream)
+}
+
+/// Static; it reads the token from its own address
+pub(super) async fn overlay() -> Html<&'static str> {
+    Html(OVERLAY_PAGE)
+}
+
+async fn stream(mut socket: WebSocket) {
+    let mut captions = subscribe_captions();
+    loop {
+        tokio::select! {
+            caption = captions.recv() => match caption {
+                Ok(caption) => {
+                    let Ok(json) = serde_json::to_string(&caption) else {
+                        continue;
+                    };
+                    if socket.send(Message::Text(json)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => debug!("🔌 Caption client fell behind, skipped {} captions", skipped),
+                Err(RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Pings are answered by the server; nothing else is expected
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// local_api/commands.rs
+//
+// Tauri commands for turning the local API on and off and managing its token. Saving the
+// settings restarts the server, so a new port or token applies at once.
+
+use log::info;
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+
+use super::{load_or_create_token, load_settings, regenerate_token, restart, running_port, save_settings, LocalApiSettings};
+use crate::secrets;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalApiStatus {
+    pub settings: LocalApiSettings,
+    /// Created the first time the API is turned on
+    pub token: Option<String>,
+    /// The port the server listens on; `None` while it's stopped
+    pub running_port: Option<u16>,
+}
+
+fn status<R: Runtime>(app: &AppHandle<R>) -> Result<LocalApiStatus, String> {
+    Ok(LocalApiStatus {
+        settings: load_settings(app),
+        token: secrets::load(super::SERVICE, super::TOKEN_ACCOUNT).map_err(|e| e.to_string())?,
+        running_port: running_port(),
+    })
+}
+
+#[tauri::command]
+pub async fn get_local_api_status<R: Runtime>(app: AppHandle<R>) -> Result<LocalApiStatus, String> {
+    status(&app)
+}
+
+/// Saves the settings and restarts the server with them
+#[tauri::command]
+pub async fn set_local_api_settings<R: Runtime>(
+    app: AppHandle<R>,
+    settings: LocalApiSettings,
+) -> Result<LocalApiStatus, String> {
+    if settings.port < 1024 {
+        return Err("Choose a port from 1024 to 65535".to_string());
+    }
+    if settings.enabled {
+        load_or_create_token()?;
+    }
+    save_settings(&app, &settings)?;
+    info!("🔌 Local API settings saved: {:?}", settings);
+    restart(&app).await?;
+    status(&app)
+}
+
+/// Replaces the token; scripts using the old one stop working
+#[tauri::command]
+pub async fn regenerate_local_api_token<R: Runtime>(app: AppHandle<R>) -> Result<LocalApiStatus, String> {
+    regenerate_token()?;
+    info!("🔌 Local API token regenerated");
+    restart(&app).await?;
+    status(&app)
+}
@@ -0,0 +1,179 @@
+/// Local API module - scripting the app over HTTP
+///
+/// Off by default. Once enabled, an HTTP server on 127.0.0.1 lists meetings, returns their
+/// transcripts and summaries, searches them, and starts and stops recordings, so the app can
+/// be driven from shell scripts, CI jobs or launchers such as Raycast. Every request needs the
+/// API token as a bearer token; the token is random, kept in the OS credential store and can
+/// be regenerated at any time. The server only answers requests addressed to localhost, so
+/// web pages can't reach it through DNS rebinding. See `docs/local-api.md`.
+///
+/// This module contains:
+/// - The routes and their handlers (`routes`)
+/// - The live caption WebSocket and its overlay page (`captions`)
+/// - Tauri commands for frontend integration
+
+pub mod captions;
+pub mod commands;
+pub mod routes;
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+use crate::secrets;
+
+const STORE_FILE: &str = "local_api.json";
+const STORE_KEY: &str = "local_api_settings";
+
+const SERVICE: &str = "com.meetily.ai.local-api";
+const TOKEN_ACCOUNT: &str = "token";
+const TOKEN_PREFIX: &str = "mlt_";
+
+pub const DEFAULT_PORT: u16 = 5170;
+
+/// A stopped server releases its port shortly after
+const BIND_ATTEMPTS: u32 = 10;
+const BIND_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocalApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for LocalApiSettings {
+    fn default() -> Self {
+        Self { enabled: false, port: DEFAULT_PORT }
+    }
+}
+
+/// The server while it runs, with what stops it
+struct RunningServer {
+    port: u16,
+    shutdown: oneshot::Sender<()>,
+}
+
+static SERVER: Lazy<Mutex<Option<RunningServer>>> = Lazy::new(|| Mutex::new(None));
+
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> LocalApiSettings {
+    let stored = match app.store(STORE_FILE) {
+        Ok(store) => store.get(STORE_KEY),
+        Err(e) => {
+            warn!("Failed to open {}: {}", STORE_FILE, e);
+            None
+        }
+    };
+    stored.and_then(|value| serde_json::from_value(value).ok()).unwrap_or_default()
+}
+
+fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: &LocalApiSettings) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    store.set(STORE_KEY, serde_json::to_value(settings).map_err(|e| e.to_string())?);
+    store.save().map_err(|e| format!("Failed to save {}: {}", STORE_FILE, e))
+}
+
+fn new_token() -> String {
+    let mut bytes = [0u8; 24];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}{}", TOKEN_PREFIX, hex)
+}
+
+/// The stored token, or a new one when there's none
+fn load_or_create_token() -> Result<String, String> {
+    if let Some(token) = secrets::load(SERVICE, TOKEN_ACCOUNT).map_err(|e| e.to_string())? {
+        return Ok(token);
+    }
+    regenerate_token()
+}
+
+fn regenerate_token() -> Result<String, String> {
+    let token = new_token();
+    secrets::store(SERVICE, TOKEN_ACCOUNT, &token).map_err(|e| e.to_string())?;
+    Ok(token)
+}
+
+/// The port the server listens on, `None` while it's stopped
+pub fn running_port() -> Option<u16> {
+    SERVER.lock().unwrap().as_ref().map(|server| server.port)
+}
+
+fn stop() {
+    if let Some(server) = SERVER.lock().unwrap().take() {
+        let _ = server.shutdown.send(());
+        info!("🔌 Local API stopped");
+    }
+}
+
+async fn bind(address: SocketAddr) -> std::io::Result<TcpListener> {
+    let mut attempts = 1;
+    loop {
+        match TcpListener::bind(address).await {
+            Err(e) if e.kind() == std::io::ErrorKind::AddrInUse && attempts < BIND_ATTEMPTS => {
+                attempts += 1;
+                tokio::time::sleep(BIND_RETRY_DELAY).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Stops the server and starts it again with the stored settings and token, when enabled
+pub async fn restart<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    stop();
+    let settings = load_settings(app);
+    if !settings.enabled {
+        return Ok(());
+    }
+    let token = load_or_create_token()?;
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.port));
+    let listener = bind(address)
+        .await
+        .map_err(|e| format!("Failed to listen on port {}: {}", settings.port, e))?;
+
+    let (shutdown, stopped) = oneshot::channel::<()>();
+    let router = routes::router(app.clone(), token);
+    tauri::async_runtime::spawn(async move {
+        let server = axum::serve(listener, router).with_graceful_shutdown(async {
+            let _ = stopped.await;
+        });
+        if let Err(e) = server.await {
+            warn!("🔌 Local API server failed: {}", e);
+        }
+    });
+    *SERVER.lock().unwrap() = Some(RunningServer { port: settings.port, shutdown });
+    info!("🔌 Local API listening on http://{}", address);
+    Ok(())
+}
+
+/// Starts the server at startup when it's enabled
+pub fn start_local_api<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = restart(&app).await {
+            warn!("🔌 Local API not started: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_token() {
+        let token = new_token();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.len(), TOKEN_PREFIX.len() + 48);
+        assert_ne!(token, new_token());
+    }
+}
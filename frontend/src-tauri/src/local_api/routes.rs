@@ -0,0 +1,296 @@
+// local_api/routes.rs
+//
+// The local API's routes. Responses are JSON; errors are `{"error": "..."}` with a 4xx or 5xx
+// status. Recordings are stopped through the same flow as the tray's Stop button, so the
+// transcript is saved as usual. Live captions are a WebSocket; see `captions`.
+
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Manager, Runtime};
+
+use super::captions;
+use crate::api::api::MeetingDetails;
+use crate::audio::recording_commands;
+use crate::audio::session::{self, RecordingSession, SessionPhase};
+use crate::database::models::MeetingModel;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::search::commands::{search, SearchRequest, SearchResults};
+use crate::state::AppState;
+
+struct ApiContext<R: Runtime> {
+    app: AppHandle<R>,
+    token: String,
+}
+
+// Derived, it would need `R: Clone`
+impl<R: Runtime> Clone for ApiContext<R> {
+    fn clone(&self) -> Self {
+        Self { app: self.app.clone(), token: self.token.clone() }
+    }
+}
+
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+
+    fn internal(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+const CAPTIONS_PATH: &str = "/v1/captions";
+
+pub(super) fn router<R: Runtime>(app: AppHandle<R>, token: String) -> Router {
+    let context = ApiContext { app, token };
+    Router::new()
+        .route("/v1/status", get(status::<R>))
+        .route("/v1/meetings", get(list_meetings::<R>))
+        .route("/v1/meetings/:id", get(get_meeting::<R>))
+        .route("/v1/meetings/:id/summary", get(get_summary::<R>))
+        .route("/v1/search", get(search_meetings::<R>))
+        .route("/v1/recording/start", post(start_recording::<R>))
+        .route("/v1/recording/stop", post(stop_recording::<R>))
+        .route(CAPTIONS_PATH, get(captions::captions))
+        .route_layer(middleware::from_fn_with_state(context.clone(), authenticate::<R>))
+        // The overlay page holds no data; the stream it opens is authenticated
+        .route("/captions", get(captions::overlay))
+        .with_state(context)
+}
+
+/// Whether the request was addressed to this machine; anything else reached the server
+/// through a name that resolves here, e.g. a web page rebinding its own domain
+fn is_local_host(host: &str) -> bool {
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => host,
+    };
+    matches!(name.to_ascii_lowercase().as_str(), "localhost" | "127.0.0.1" | "[::1]")
+}
+
+/// Compares in constant time, so the token can't be guessed from response times
+fn tokens_match(given: &str, token: &str) -> bool {
+    given.len() == token.len() && given.bytes().zip(token.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Browser WebSockets can't set headers, so the captions stream also takes the token as a
+/// query parameter; everywhere else it would end up in logs and browser history
+fn accepts_query_token(path: &str, upgrade: Option<&str>) -> bool {
+    path == CAPTIONS_PATH && upgrade.is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+}
+
+/// The `token` query parameter
+fn query_token(query: Option<&str>) -> Option<String> {
+    url::form_urlencoded::parse(query?.as_bytes())
+        .find(|(name, _)| name == "token")
+        .map(|(_, value)| value.into_owned())
+}
+
+fn check_request(
+    host: Option<&str>,
+    authorization: Option<&str>,
+    path: &str,
+    upgrade: Option<&str>,
+    query: Option<&str>,
+    token: &str,
+) -> Result<(), ApiError> {
+    if !host.is_some_and(is_local_host) {
+        return Err(ApiError::new(StatusCode::FORBIDDEN, "Requests must be addressed to localhost"));
+    }
+    let given = match authorization.and_then(|value| value.strip_prefix("Bearer ")) {
+        Some(given) => Some(given.trim().to_string()),
+        None if accepts_query_token(path, upgrade) => query_token(query),
+        None => None,
+    };
+    match given {
+        Some(given) if tokens_match(&given, token) => Ok(()),
+        _ => Err(ApiError::new(StatusCode::UNAUTHORIZED, "Missing or invalid API token")),
+    }
+}
+
+async fn authenticate<R: Runtime>(State(context): State<ApiContext<R>>, request: Request, next: Next) -> Response {
+    let checked = {
+        let header_value = |name| request.headers().get(name).and_then(|value| value.to_str().ok());
+        check_request(
+            header_value(header::HOST),
+            header_value(header::AUTHORIZATION),
+            request.uri().path(),
+            header_value(header::UPGRADE),
+            request.uri().query(),
+            &context.token,
+        )
+    };
+    match checked {
+        Ok(()) => next.run(request).await,
+        Err(e) => e.into_response(),
+    }
+}
+
+/// The open workspace's database; unavailable until onboarding has set it up
+fn pool<R: Runtime>(app: &AppHandle<R>) -> Result<SqlitePool, ApiError> {
+    app.try_state::<AppState>()
+        .map(|state| state.db_manager.pool().clone())
+        .ok_or_else(|| ApiError::new(StatusCode::SERVICE_UNAVAILABLE, "The app isn't set up yet"))
+}
+
+#[derive(Debug, Serialize)]
+struct StatusResponse {
+    version: String,
+    workspace: String,
+    recording: RecordingSession,
+}
+
+async fn status<R: Runtime>(State(context): State<ApiContext<R>>) -> ApiResult<StatusResponse> {
+    Ok(Json(StatusResponse {
+        version: context.app.package_info().version.to_string(),
+        workspace: crate::workspaces::active().name,
+        recording: session::current(),
+    }))
+}
+
+async fn list_meetings<R: Runtime>(State(context): State<ApiContext<R>>) -> ApiResult<Vec<MeetingModel>> {
+    MeetingsRepository::get_meetings(&pool(&context.app)?)
+        .await
+        .map(Json)
+        .map_err(|e| ApiError::internal(format!("Failed to list meetings: {}", e)))
+}
+
+async fn get_meeting<R: Runtime>(State(context): State<ApiContext<R>>, Path(id): Path<String>) -> ApiResult<MeetingDetails> {
+    match MeetingsRepository::get_meeting(&pool(&context.app)?, &id).await {
+        Ok(Some(meeting)) => Ok(Json(meeting)),
+        Ok(None) => Err(ApiError::new(StatusCode::NOT_FOUND, format!("Meeting not found: {}", id))),
+        Err(e) => Err(ApiError::internal(format!("Failed to retrieve meeting: {}", e))),
+    }
+}
+
+async fn get_summary<R: Runtime>(State(context): State<ApiContext<R>>, Path(id): Path<String>) -> ApiResult<Value> {
+    let process = SummaryProcessesRepository::get_summary_data(&pool(&context.app)?, &id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to retrieve summary: {}", e)))?
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, format!("No summary for meeting {}", id)))?;
+    // The stored result is JSON text; it's returned as JSON
+    let result = process.result.map(|result| serde_json::from_str(&result).unwrap_or(Value::String(result)));
+    Ok(Json(json!({
+        "meeting_id": process.meeting_id,
+        "status": process.status,
+        "updated_at": process.updated_at,
+        "error": process.error,
+        "result": result,
+    })))
+}
+
+async fn search_meetings<R: Runtime>(
+    State(context): State<ApiContext<R>>,
+    Query(request): Query<SearchRequest>,
+) -> ApiResult<SearchResults> {
+    search(&pool(&context.app)?, request).await.map(Json).map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e))
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct StartRecordingRequest {
+    meeting_name: Option<String>,
+}
+
+async fn start_recording<R: Runtime>(
+    State(context): State<ApiContext<R>>,
+    body: Option<Json<StartRecordingRequest>>,
+) -> Result<(StatusCode, Json<RecordingSession>), ApiError> {
+    if matches!(session::phase(), SessionPhase::Arming) || session::phase().is_recording() {
+        return Err(ApiError::new(StatusCode::CONFLICT, "A recording is already in progress"));
+    }
+    let meeting_name = body.and_then(|Json(body)| body.meeting_name).filter(|name| !name.trim().is_empty());
+    recording_commands::start_recording_with_meeting_name(context.app.clone(), meeting_name)
+        .await
+        .map_err(|e| ApiError::internal(e.message))?;
+    crate::tray::update_tray_menu(&context.app);
+    Ok((StatusCode::ACCEPTED, Json(session::current())))
+}
+
+async fn stop_recording<R: Runtime>(State(context): State<ApiContext<R>>) -> Result<(StatusCode, Json<RecordingSession>), ApiError> {
+    if !matches!(session::phase(), SessionPhase::Recording | SessionPhase::Paused) {
+        return Err(ApiError::new(StatusCode::CONFLICT, "No recording is in progress"));
+    }
+    crate::tray::stop_recording_handler(&context.app);
+    Ok((StatusCode::ACCEPTED, Json(session::current())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_local_host() {
+        assert!(is_local_host("localhost:5170"));
+        assert!(is_local_host("127.0.0.1:5170"));
+        assert!(is_local_host("LOCALHOST"));
+        assert!(is_local_host("[::1]:5170"));
+        assert!(!is_local_host("attacker.example:5170"));
+        assert!(!is_local_host("localhost.attacker.example"));
+    }
+
+    /// A request for `path` with only the headers and query given
+    fn check(
+        path: &str,
+        authorization: Option<&str>,
+        upgrade: Option<&str>,
+        query: Option<&str>,
+    ) -> Result<(), ApiError> {
+        check_request(Some("localhost:5170"), authorization, path, upgrade, query, "mlt_secret")
+    }
+
+    #[test]
+    fn test_check_request() {
+        let token = "mlt_secret";
+        assert!(check("/v1/status", Some("Bearer mlt_secret"), None, None).is_ok());
+        let unauthorized = check("/v1/status", Some("Bearer mlt_secreT"), None, None).unwrap_err();
+        assert_eq!(unauthorized.status, StatusCode::UNAUTHORIZED);
+        assert_eq!(check("/v1/status", None, None, None).unwrap_err().status, StatusCode::UNAUTHORIZED);
+        let rebound = check_request(Some("evil.example"), Some("Bearer mlt_secret"), "/v1/status", None, None, token);
+        assert_eq!(rebound.unwrap_err().status, StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_check_request_query_token() {
+        let websocket = Some("websocket");
+        assert!(check(CAPTIONS_PATH, None, websocket, Some("lines=3&token=mlt_secret")).is_ok());
+        let wrong = check(CAPTIONS_PATH, None, websocket, Some("token=mlt_other")).unwrap_err();
+        assert_eq!(wrong.status, StatusCode::UNAUTHORIZED);
+        let missing = check(CAPTIONS_PATH, None, websocket, Some("lines=3")).unwrap_err();
+        assert_eq!(missing.status, StatusCode::UNAUTHORIZED);
+        // A header that's present wins over the query
+        let header_first = check(CAPTIONS_PATH, Some("Bearer mlt_other"), websocket, Some("token=mlt_secret"));
+        assert_eq!(header_first.unwrap_err().status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_rest_routes_reject_query_token() {
+        let rest = check("/v1/meetings", None, None, Some("token=mlt_secret")).unwrap_err();
+        assert_eq!(rest.status, StatusCode::UNAUTHORIZED);
+        // Only the WebSocket upgrade takes it, not a plain GET of the same path
+        let plain = check(CAPTIONS_PATH, None, None, Some("token=mlt_secret")).unwrap_err();
+        assert_eq!(plain.status, StatusCode::UNAUTHORIZED);
+        assert!(check("/v1/meetings", Some("Bearer mlt_secret"), None, Some("token=mlt_other")).is_ok());
+    }
+}
@@ -4,11 +4,10 @@
 )]
 
 use log;
-use env_logger;
 
 fn main() {
     std::env::set_var("RUST_LOG", "info");
-    env_logger::init();
+    app_lib::diagnostics::log_capture::init();
 
     // Async logger will be initialized lazily when first needed (after Tauri runtime starts)
     log::info!("Starting application...");
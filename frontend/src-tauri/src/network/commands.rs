@@ -0,0 +1,109 @@
+// network/commands.rs
+//
+// Tauri commands for local-only mode, with the configured features it turns off so the
+// settings can say what stops working before and after it's switched on.
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Emitter, Runtime};
+use tracing::info;
+
+use super::{is_local_only, save_local_only};
+use crate::audio::transcription::cloud_providers::is_cloud_provider;
+use crate::database::repositories::calendar::CalendarRepository;
+use crate::database::repositories::notion_connection::NotionConnectionsRepository;
+use crate::database::repositories::setting::SettingsRepository;
+use crate::database::repositories::slack_setting::SlackSettingsRepository;
+use crate::state::AppState;
+use crate::summary::builtin_llm;
+use crate::summary::llm_client::LLMProvider;
+
+pub const NETWORK_MODE_CHANGED_EVENT: &str = "network-mode-changed";
+
+/// A configured feature that doesn't work, or works differently, in local-only mode
+#[derive(Debug, Clone, Serialize)]
+pub struct AffectedFeature {
+    pub feature: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkStatus {
+    pub local_only: bool,
+    pub affected: Vec<AffectedFeature>,
+}
+
+fn affected(feature: &str, detail: String) -> AffectedFeature {
+    AffectedFeature { feature: feature.to_string(), detail }
+}
+
+async fn affected_features(pool: &SqlitePool) -> Vec<AffectedFeature> {
+    let mut features = Vec::new();
+
+    if let Ok(Some(config)) = SettingsRepository::get_transcript_config(pool).await {
+        if is_cloud_provider(&config.provider) {
+            features.push(affected(
+                "Transcription",
+                format!("Recording needs a local model instead of {}; choose Whisper or Parakeet", config.provider),
+            ));
+        }
+    }
+
+    if let Ok(Some(config)) = SettingsRepository::get_model_config(pool).await {
+        if let Ok(provider) = LLMProvider::from_str(&config.provider) {
+            if !provider.is_local() {
+                let detail = match builtin_llm::ready_model() {
+                    Some(model) => {
+                        format!("Summaries use the built-in model {} instead of {}", model, provider.display_name())
+                    }
+                    None => format!(
+                        "Summaries with {} can't run; choose Ollama, llama.cpp or the built-in model",
+                        provider.display_name()
+                    ),
+                };
+                features.push(affected("Summaries", detail));
+            }
+        }
+    }
+
+    if SlackSettingsRepository::get(pool).await.ok().flatten().is_some_and(|settings| settings.enabled) {
+        features.push(affected("Slack", "Summaries aren't posted to Slack".to_string()));
+    }
+    if NotionConnectionsRepository::list(pool).await.is_ok_and(|connections| !connections.is_empty()) {
+        features.push(affected("Notion", "Meetings can't be sent to Notion".to_string()));
+    }
+    let hosted_calendars = CalendarRepository::list_accounts(pool)
+        .await
+        .map(|accounts| accounts.iter().filter(|account| account.provider != "caldav").count())
+        .unwrap_or(0);
+    if hosted_calendars > 0 {
+        features.push(affected("Calendars", "Google and Outlook calendars aren't synced".to_string()));
+    }
+    features.push(affected(
+        "Everything else",
+        "Webhooks, email, CalDAV, sync and Ollama only reach this computer or the local network".to_string(),
+    ));
+    features
+}
+
+async fn status(pool: &SqlitePool) -> NetworkStatus {
+    NetworkStatus { local_only: is_local_only(), affected: affected_features(pool).await }
+}
+
+#[tauri::command]
+pub async fn get_network_status(state: tauri::State<'_, AppState>) -> Result<NetworkStatus, String> {
+    Ok(status(state.db_manager.pool()).await)
+}
+
+#[tauri::command]
+pub async fn set_local_only<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<NetworkStatus, String> {
+    save_local_only(&app, enabled)?;
+    info!("Local-only mode {}", if enabled { "on" } else { "off" });
+    let status = status(state.db_manager.pool()).await;
+    let _ = app.emit(NETWORK_MODE_CHANGED_EVENT, &status);
+    Ok(status)
+}
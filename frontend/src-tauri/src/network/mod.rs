@@ -0,0 +1,226 @@
+/// Network module - the local-only switch and the HTTP clients that enforce it
+///
+/// With local-only mode on, nothing about a meeting leaves this computer or the local
+/// network: hosted transcription and summary providers, Slack, Notion, Google and Outlook
+/// calendars and analytics are off, and Ollama, llama.cpp, the built-in model, webhooks,
+/// CalDAV and sync keep working when they point at a local address. Downloading models
+/// stays allowed, since it sends nothing about the user's meetings.
+///
+/// Features that always talk to a third party check `ensure_online` up front so the user
+/// gets a clear message. The enforcement itself is in the HTTP clients made by `client` and
+/// `client_builder`: their DNS resolver refuses, while the mode is on, any host name that
+/// doesn't resolve to a loopback, private or link-local address, and clients made while it's
+/// on also refuse IP addresses outside those ranges and skip the system proxy. Both checks
+/// only happen when a connection is made, so a client kept for longer than one task is a
+/// `GuardedClient`, which is rebuilt with a fresh connection pool whenever the mode changes.
+///
+/// This module contains:
+/// - Tauri commands for frontend integration, including the features the mode turns off
+
+pub mod commands;
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use hyper::client::connect::dns::Name;
+use log::warn;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "network.json";
+const STORE_KEY: &str = "local_only";
+
+/// Requests to addresses outside the local network go to this proxy, which never resolves
+const BLOCKED_HOST: &str = "blocked.local-only.invalid";
+const BLOCKED_PROXY: &str = "http://blocked.local-only.invalid";
+
+static LOCAL_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Bumped whenever the mode changes, so `GuardedClient`s know to rebuild
+static POLICY_VERSION: AtomicU64 = AtomicU64::new(0);
+
+/// Loads the stored mode; called once at startup
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let stored = match app.store(STORE_FILE) {
+        Ok(store) => store.get(STORE_KEY),
+        Err(e) => {
+            warn!("Failed to open {}: {}", STORE_FILE, e);
+            None
+        }
+    };
+    LOCAL_ONLY.store(stored.and_then(|value| value.as_bool()).unwrap_or(false), Ordering::SeqCst);
+    POLICY_VERSION.fetch_add(1, Ordering::SeqCst);
+}
+
+pub fn is_local_only() -> bool {
+    LOCAL_ONLY.load(Ordering::SeqCst)
+}
+
+fn save_local_only<R: Runtime>(app: &AppHandle<R>, enabled: bool) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    store.set(STORE_KEY, enabled);
+    store.save().map_err(|e| format!("Failed to save {}: {}", STORE_FILE, e))?;
+    LOCAL_ONLY.store(enabled, Ordering::SeqCst);
+    POLICY_VERSION.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+/// An error for the user when local-only mode keeps `feature` from working
+pub fn ensure_online(feature: &str) -> Result<(), String> {
+    if is_local_only() {
+        return Err(format!("{} isn't available while local-only mode is on", feature));
+    }
+    Ok(())
+}
+
+/// Loopback, private and link-local addresses
+pub fn is_local_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_loopback() || v4.is_private() || v4.is_link_local() || v4.is_unspecified(),
+        IpAddr::V6(v6) => {
+            let first = v6.segments()[0];
+            v6.is_loopback()
+                || v6.is_unspecified()
+                || (first & 0xfe00) == 0xfc00 // unique local
+                || (first & 0xffc0) == 0xfe80 // link-local
+                || v6.to_ipv4_mapped().is_some_and(|v4| is_local_ip(IpAddr::V4(v4)))
+        }
+    }
+}
+
+/// A URL host as an IP address, including IPv6 in brackets
+fn host_ip(host: &str) -> Option<IpAddr> {
+    host.trim_start_matches('[').trim_end_matches(']').parse().ok()
+}
+
+fn blocked_message(host: &str) -> String {
+    format!("Local-only mode is on, so {} isn't contacted", host)
+}
+
+/// Resolves `host`, keeping only local addresses while local-only mode is on
+async fn resolve_allowed(host: &str, port: u16) -> Result<Vec<SocketAddr>, String> {
+    if host == BLOCKED_HOST {
+        return Err("Local-only mode is on, so addresses outside the local network aren't contacted".to_string());
+    }
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .collect();
+    if !is_local_only() {
+        return Ok(addrs);
+    }
+    let local: Vec<SocketAddr> = addrs.into_iter().filter(|addr| is_local_ip(addr.ip())).collect();
+    if local.is_empty() {
+        return Err(blocked_message(host));
+    }
+    Ok(local)
+}
+
+/// For connections made without `client`, such as SMTP: an error when local-only mode is on
+/// and `host` isn't on the local network
+pub async fn check_host(host: &str) -> Result<(), String> {
+    if !is_local_only() {
+        return Ok(());
+    }
+    match host_ip(host) {
+        Some(ip) if is_local_ip(ip) => Ok(()),
+        Some(_) => Err(blocked_message(host)),
+        None => resolve_allowed(host, 0).await.map(|_| ()),
+    }
+}
+
+struct GuardedResolver;
+
+impl Resolve for GuardedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs = resolve_allowed(&host, 0).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// A client builder with the local-only guard, for clients that need other options
+pub fn client_builder() -> reqwest::ClientBuilder {
+    let builder = reqwest::Client::builder().dns_resolver(Arc::new(GuardedResolver));
+    if !is_local_only() {
+        return builder;
+    }
+    // IP addresses skip the resolver; a proxy of our own also keeps the system proxy out
+    builder.proxy(reqwest::Proxy::custom(|url| {
+        url.host_str().and_then(host_ip).filter(|ip| !is_local_ip(*ip)).map(|_| BLOCKED_PROXY)
+    }))
+}
+
+/// An HTTP client with the local-only guard
+pub fn client() -> reqwest::Client {
+    client_builder().build().expect("Failed to create the HTTP client")
+}
+
+/// A long-lived client that follows local-only mode changes
+///
+/// Connections already pooled when the mode is turned on would otherwise keep reaching
+/// hosts the resolver now refuses, and a client built while it was off has no IP proxy.
+pub struct GuardedClient {
+    configure: fn(reqwest::ClientBuilder) -> reqwest::ClientBuilder,
+    /// The client and the policy version it was built under
+    current: Mutex<(u64, reqwest::Client)>,
+}
+
+impl GuardedClient {
+    /// `configure` sets the options beyond the guard, such as a timeout
+    pub fn new(configure: fn(reqwest::ClientBuilder) -> reqwest::ClientBuilder) -> Result<Self, reqwest::Error> {
+        let version = POLICY_VERSION.load(Ordering::SeqCst);
+        let client = configure(client_builder()).build()?;
+        Ok(Self { configure, current: Mutex::new((version, client)) })
+    }
+
+    /// The client for the current mode; cheap to call per request
+    pub fn get(&self) -> reqwest::Client {
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        // Read before building, so a change made meanwhile triggers another rebuild
+        let version = POLICY_VERSION.load(Ordering::SeqCst);
+        if current.0 != version {
+            let client = (self.configure)(client_builder())
+                .build()
+                // Keeping the old client would keep its pooled connections
+                .expect("Failed to create the HTTP client");
+            *current = (version, client);
+        }
+        current.1.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_addresses() {
+        let local = ["127.0.0.1", "10.0.0.5", "192.168.1.20", "172.16.4.1", "169.254.1.1", "[::1]", "[fd12::1]", "[fe80::1]"];
+        for host in local {
+            assert!(host_ip(host).is_some_and(is_local_ip), "{} should be local", host);
+        }
+        for host in ["8.8.8.8", "172.32.0.1", "[2606:4700::1111]", "[::ffff:1.1.1.1]"] {
+            assert!(!host_ip(host).is_some_and(is_local_ip), "{} shouldn't be local", host);
+        }
+        assert_eq!(host_ip("api.openai.com"), None);
+    }
+
+    #[test]
+    fn test_guarded_client_rebuilt_after_mode_change() {
+        let guarded = GuardedClient::new(|builder| builder).unwrap();
+        let built_under = || guarded.current.lock().unwrap().0;
+        let before = built_under();
+        guarded.get();
+        assert_eq!(built_under(), before);
+
+        POLICY_VERSION.fetch_add(1, Ordering::SeqCst);
+        guarded.get();
+        assert_eq!(built_under(), POLICY_VERSION.load(Ordering::SeqCst));
+        assert_ne!(built_under(), before);
+    }
+}
@@ -6,7 +6,7 @@ use crate::notifications::{
 
 use anyhow::Result;
 use log::{info as log_info, error as log_error};
-use tauri::{State, AppHandle, Runtime, Wry};
+use tauri::{State, AppHandle, Manager, Runtime, Wry};
 use tauri_plugin_notification::NotificationExt;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -456,4 +456,21 @@ pub async fn show_system_error_notification(
         log_error!("Cannot show system error notification: manager not initialized");
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Show a notification from a background task or a capture callback (internal use);
+/// skipped when the manager isn't initialized, the type's own preference decides the rest
+pub fn notify_in_background<R: Runtime>(app_handle: &AppHandle<R>, notification: Notification) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let Some(manager_state) = app_handle.try_state::<NotificationManagerState<R>>() else {
+            return;
+        };
+        let manager_lock = manager_state.read().await;
+        if let Some(manager) = manager_lock.as_ref() {
+            if let Err(e) = manager.show_notification(notification).await {
+                log_error!("Failed to show notification: {}", e);
+            }
+        }
+    });
+}
@@ -297,9 +297,14 @@ impl<R: Runtime> NotificationManager<R> {
             NotificationType::RecordingStopped => settings.notification_preferences.show_recording_stopped,
             NotificationType::RecordingPaused => settings.notification_preferences.show_recording_paused,
             NotificationType::RecordingResumed => settings.notification_preferences.show_recording_resumed,
+            NotificationType::RecordingAutoStopped(_) => true, // The user didn't stop it, so always tell them
             NotificationType::TranscriptionComplete => settings.notification_preferences.show_transcription_complete,
+            NotificationType::SummaryReady => settings.notification_preferences.show_summary_ready,
+            NotificationType::DiskSpaceLow(_) => settings.notification_preferences.show_disk_space_low,
+            NotificationType::PermissionProblem(_) => settings.notification_preferences.show_permission_problems,
             NotificationType::MeetingReminder(_) => settings.notification_preferences.show_meeting_reminders,
             NotificationType::SystemError(_) => settings.notification_preferences.show_system_errors,
+            NotificationType::PluginMessage => true, // Only plugins the user turned on send these
             NotificationType::Test => true, // Always show test notifications
         }
     }
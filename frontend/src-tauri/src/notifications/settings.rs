@@ -52,6 +52,18 @@ pub struct NotificationPreferences {
     /// Show transcription complete notifications
     pub show_transcription_complete: bool,
 
+    /// Show summary ready notifications
+    #[serde(default = "enabled")]
+    pub show_summary_ready: bool,
+
+    /// Show low disk space warnings while recording
+    #[serde(default = "enabled")]
+    pub show_disk_space_low: bool,
+
+    /// Show warnings about lost device access while recording
+    #[serde(default = "enabled")]
+    pub show_permission_problems: bool,
+
     /// Show meeting reminder notifications
     pub show_meeting_reminders: bool,
 
@@ -62,6 +74,11 @@ pub struct NotificationPreferences {
     pub meeting_reminder_minutes: Vec<u64>,
 }
 
+// Settings files saved before a preference existed keep it turned on
+fn enabled() -> bool {
+    true
+}
+
 impl Default for NotificationSettings {
     fn default() -> Self {
         Self {
@@ -86,6 +103,9 @@ impl Default for NotificationPreferences {
             show_recording_paused: true,
             show_recording_resumed: true,
             show_transcription_complete: true,
+            show_summary_ready: true,
+            show_disk_space_low: true,
+            show_permission_problems: true,
             show_meeting_reminders: true,
             show_system_errors: true,
             meeting_reminder_minutes: vec![15, 5], // 15 minutes and 5 minutes before
@@ -19,9 +19,14 @@ pub enum NotificationType {
     RecordingStopped,
     RecordingPaused,
     RecordingResumed,
+    RecordingAutoStopped(u32), // Minutes of silence
     TranscriptionComplete,
+    SummaryReady,
+    DiskSpaceLow(u64), // Megabytes left
+    PermissionProblem(String),
     MeetingReminder(u64), // Duration in minutes
     SystemError(String),
+    PluginMessage,
     Test, // For testing notifications
 }
 
@@ -154,6 +159,16 @@ impl Notification {
         .with_timeout(NotificationTimeout::Seconds(3))
     }
 
+    pub fn recording_auto_stopped(silence_minutes: u32) -> Self {
+        Notification::new(
+            "Meetily",
+            format!("No speech for {} minutes - stopping and saving the recording", silence_minutes),
+            NotificationType::RecordingAutoStopped(silence_minutes)
+        )
+        .with_priority(NotificationPriority::High)
+        .with_timeout(NotificationTimeout::Seconds(10))
+    }
+
     pub fn transcription_complete(file_path: Option<String>) -> Self {
         let body = match file_path {
             Some(path) => format!("Transcription completed and saved to: {}", path),
@@ -165,6 +180,48 @@ impl Notification {
             .with_timeout(NotificationTimeout::Seconds(5))
     }
 
+    pub fn summary_ready(meeting_title: Option<String>) -> Self {
+        let body = match meeting_title {
+            Some(title) => format!("The summary of '{}' is ready", title),
+            None => "The meeting summary is ready".to_string(),
+        };
+
+        Notification::new("Meetily", body, NotificationType::SummaryReady)
+            .with_priority(NotificationPriority::Normal)
+            .with_timeout(NotificationTimeout::Seconds(5))
+    }
+
+    pub fn disk_space_low(available_mb: u64) -> Self {
+        Notification::new(
+            "Meetily",
+            format!("Only {} MB of disk space left for the recording. Free up space to keep recording.", available_mb),
+            NotificationType::DiskSpaceLow(available_mb)
+        )
+        .with_priority(NotificationPriority::High)
+        .with_timeout(NotificationTimeout::Seconds(10))
+    }
+
+    pub fn disk_space_stopped(available_mb: u64) -> Self {
+        Notification::new(
+            "Meetily",
+            format!("Only {} MB of disk space left - stopping and saving the recording", available_mb),
+            NotificationType::DiskSpaceLow(available_mb)
+        )
+        .with_priority(NotificationPriority::Critical)
+        .with_timeout(NotificationTimeout::Seconds(10))
+    }
+
+    pub fn permission_problem(message: impl Into<String>) -> Self {
+        let message = message.into();
+        Notification::new(
+            "Meetily",
+            format!("{} Check the app's microphone and screen recording permissions.", message),
+            NotificationType::PermissionProblem(message)
+        )
+        .with_priority(NotificationPriority::High)
+        .with_timeout(NotificationTimeout::Seconds(10))
+    }
+
     pub fn meeting_reminder(minutes_until: u64, meeting_title: Option<String>) -> Self {
         let body = match meeting_title {
             Some(title) => format!("Meeting '{}' starts in {} minutes", title, minutes_until),
@@ -176,6 +233,12 @@ impl Notification {
             .with_timeout(NotificationTimeout::Seconds(10))
     }
 
+    pub fn plugin_message(plugin_name: impl Into<String>, message: impl Into<String>) -> Self {
+        Notification::new(plugin_name, message, NotificationType::PluginMessage)
+            .with_priority(NotificationPriority::Normal)
+            .with_timeout(NotificationTimeout::Seconds(5))
+    }
+
     pub fn system_error(error: impl Into<String>) -> Self {
         let error_string = error.into();
         Notification::new(
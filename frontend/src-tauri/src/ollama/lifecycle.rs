@@ -0,0 +1,217 @@
+// ollama/lifecycle.rs
+//
+// Finding, checking and starting a local Ollama, so summaries with local models work without
+// opening a terminal. A server the app starts is `ollama serve` on the configured port; it
+// keeps running after the app quits, like one started from Ollama's own app.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Instant;
+
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tauri::command;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration};
+
+use crate::summary::llm_client::DEFAULT_OLLAMA_ENDPOINT;
+
+const EXECUTABLE_NAME: &str = if cfg!(windows) { "ollama.exe" } else { "ollama" };
+
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(3);
+/// How long a starting server gets to answer
+const START_TIMEOUT: Duration = Duration::from_secs(20);
+const START_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Held while a server starts, so two summaries don't both start one
+static STARTING: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaHealth {
+    pub reachable: bool,
+    /// The server's version, e.g. "0.5.7"
+    pub version: Option<String>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaStatus {
+    /// Where the `ollama` program is, when it's installed on this computer
+    pub binary_path: Option<String>,
+    /// Whether the endpoint is on this computer, so the app can start a server for it
+    pub local: bool,
+    pub health: OllamaHealth,
+}
+
+#[derive(Deserialize)]
+struct VersionResponse {
+    version: String,
+}
+
+fn base_url(endpoint: Option<&str>) -> &str {
+    match endpoint.map(str::trim) {
+        Some(endpoint) if !endpoint.is_empty() => endpoint.trim_end_matches('/'),
+        _ => DEFAULT_OLLAMA_ENDPOINT,
+    }
+}
+
+/// The `OLLAMA_HOST` a local server for `endpoint` listens on; `None` for other computers
+fn serve_host(endpoint: Option<&str>) -> Option<String> {
+    let url = url::Url::parse(base_url(endpoint)).ok()?;
+    match url.host_str()? {
+        "localhost" | "127.0.0.1" | "[::1]" => Some(format!("127.0.0.1:{}", url.port_or_known_default()?)),
+        _ => None,
+    }
+}
+
+/// The installed `ollama` program: on the PATH or where the installers put it
+pub fn find_ollama_binary() -> Option<PathBuf> {
+    if let Ok(path) = which::which(EXECUTABLE_NAME) {
+        return Some(path);
+    }
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    #[cfg(target_os = "macos")]
+    {
+        candidates.push(PathBuf::from("/Applications/Ollama.app/Contents/Resources/ollama"));
+        candidates.push(PathBuf::from("/opt/homebrew/bin/ollama"));
+        candidates.push(PathBuf::from("/usr/local/bin/ollama"));
+    }
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(local) = dirs::data_local_dir() {
+            candidates.push(local.join("Programs").join("Ollama").join(EXECUTABLE_NAME));
+        }
+    }
+    #[cfg(target_os = "linux")]
+    {
+        candidates.push(PathBuf::from("/usr/local/bin/ollama"));
+        candidates.push(PathBuf::from("/usr/bin/ollama"));
+    }
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+/// Asks the server for its version
+pub async fn check_health(endpoint: Option<&str>) -> OllamaHealth {
+    let url = format!("{}/api/version", base_url(endpoint));
+    let started = Instant::now();
+    let response = crate::network::client().get(&url).timeout(HEALTH_TIMEOUT).send().await;
+    let unreachable = |error: String| OllamaHealth { reachable: false, version: None, latency_ms: None, error: Some(error) };
+    match response {
+        Ok(response) if response.status().is_success() => {
+            let latency_ms = started.elapsed().as_millis() as u64;
+            let version = response.json::<VersionResponse>().await.ok().map(|body| body.version);
+            OllamaHealth { reachable: true, version, latency_ms: Some(latency_ms), error: None }
+        }
+        Ok(response) => unreachable(format!("Ollama answered with HTTP {}", response.status())),
+        Err(e) if e.is_connect() => unreachable(format!("Ollama isn't running at {}", base_url(endpoint))),
+        Err(e) if e.is_timeout() => unreachable(format!("Ollama at {} didn't answer in time", base_url(endpoint))),
+        Err(e) => unreachable(format!("Failed to reach Ollama: {}", e)),
+    }
+}
+
+pub async fn status(endpoint: Option<&str>) -> OllamaStatus {
+    OllamaStatus {
+        binary_path: find_ollama_binary().map(|path| path.to_string_lossy().to_string()),
+        local: serve_host(endpoint).is_some(),
+        health: check_health(endpoint).await,
+    }
+}
+
+/// Starts `ollama serve` for a local endpoint unless a server already answers there, and
+/// waits until it does
+pub async fn ensure_running(endpoint: Option<&str>) -> Result<OllamaHealth, String> {
+    let _starting = STARTING.lock().await;
+    let health = check_health(endpoint).await;
+    if health.reachable {
+        return Ok(health);
+    }
+    let host = serve_host(endpoint)
+        .ok_or_else(|| format!("Ollama isn't reachable at {} and runs on another computer", base_url(endpoint)))?;
+    let binary = find_ollama_binary().ok_or("Ollama isn't installed. Download it from https://ollama.com/download")?;
+
+    let mut command = tokio::process::Command::new(&binary);
+    command.arg("serve").env("OLLAMA_HOST", &host).stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::piped());
+
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = command.spawn().map_err(|e| format!("Failed to start {}: {}", binary.display(), e))?;
+    info!("🦙 Starting Ollama on {}", host);
+
+    // The server's log, and the reason when it exits early (e.g. the port is taken)
+    let last_line = std::sync::Arc::new(std::sync::Mutex::new(None::<String>));
+    if let Some(stderr) = child.stderr.take() {
+        let last_line = last_line.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                debug!("[ollama] {}", line);
+                *last_line.lock().unwrap() = Some(line);
+            }
+        });
+    }
+
+    let deadline = Instant::now() + START_TIMEOUT;
+    loop {
+        sleep(START_POLL_INTERVAL).await;
+        if let Ok(Some(exit)) = child.try_wait() {
+            let reason = last_line.lock().unwrap().clone().unwrap_or_else(|| exit.to_string());
+            return Err(format!("Ollama stopped while starting: {}", reason));
+        }
+        let health = check_health(endpoint).await;
+        if health.reachable {
+            info!("🦙 Ollama {} is running on {}", health.version.as_deref().unwrap_or("(unknown version)"), host);
+            // Reaps the server once it exits
+            tauri::async_runtime::spawn(async move {
+                match child.wait().await {
+                    Ok(exit) => info!("🦙 Ollama exited: {}", exit),
+                    Err(e) => warn!("Failed to wait for Ollama: {}", e),
+                }
+            });
+            return Ok(health);
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill().await;
+            return Err(format!("Ollama didn't start within {}s", START_TIMEOUT.as_secs()));
+        }
+    }
+}
+
+/// Whether Ollama is installed and whether it answers at `endpoint`
+#[command]
+pub async fn get_ollama_status(endpoint: Option<String>) -> Result<OllamaStatus, String> {
+    Ok(status(endpoint.as_deref()).await)
+}
+
+#[command]
+pub async fn check_ollama_health(endpoint: Option<String>) -> Result<OllamaHealth, String> {
+    Ok(check_health(endpoint.as_deref()).await)
+}
+
+/// Starts Ollama for a local endpoint when it isn't running yet
+#[command]
+pub async fn start_ollama(endpoint: Option<String>) -> Result<OllamaStatus, String> {
+    ensure_running(endpoint.as_deref()).await?;
+    Ok(status(endpoint.as_deref()).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serve_host() {
+        assert_eq!(serve_host(None), Some("127.0.0.1:11434".to_string()));
+        assert_eq!(serve_host(Some("")), Some("127.0.0.1:11434".to_string()));
+        assert_eq!(serve_host(Some("http://127.0.0.1:11500/")), Some("127.0.0.1:11500".to_string()));
+        assert_eq!(serve_host(Some("http://localhost")), Some("127.0.0.1:80".to_string()));
+        assert_eq!(serve_host(Some("http://192.168.1.20:11434")), None);
+        assert_eq!(serve_host(Some("not a url")), None);
+    }
+}
@@ -3,7 +3,6 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use serde::{Deserialize, Serialize};
-use reqwest::Client;
 use regex::Regex;
 use once_cell::sync::Lazy;
 
@@ -141,7 +140,7 @@ async fn fetch_model_info(
     model_name: &str,
     endpoint: Option<&str>,
 ) -> Result<ModelMetadata, String> {
-    let client = Client::new();
+    let client = crate::network::client();
     let base_url = endpoint.unwrap_or("http://localhost:11434");
     let url = format!("{}/api/show", base_url);
 
@@ -1,5 +1,6 @@
 pub mod ollama;
 pub mod commands;
+pub mod lifecycle;
 pub mod metadata;
 
 pub use ollama::*;
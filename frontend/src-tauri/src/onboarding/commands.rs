@@ -0,0 +1,30 @@
+// onboarding/commands.rs
+//
+// Tauri commands that drive the first-run setup.
+
+use super::{load_progress, next_step, save_progress, OnboardingProgress, OnboardingStatus, OnboardingStep};
+use tauri::{AppHandle, Runtime};
+
+/// The step the setup is at; the steps the system already satisfies are completed first
+#[tauri::command]
+pub async fn get_onboarding_status<R: Runtime>(app: AppHandle<R>) -> Result<OnboardingStatus, String> {
+    next_step(&app).await
+}
+
+#[tauri::command]
+pub async fn skip_onboarding_step<R: Runtime>(
+    app: AppHandle<R>,
+    step: OnboardingStep,
+) -> Result<OnboardingStatus, String> {
+    let mut progress = load_progress(&app);
+    progress.skip(step)?;
+    save_progress(&app, &progress)?;
+    next_step(&app).await
+}
+
+/// Starts the setup over, e.g. from the settings
+#[tauri::command]
+pub async fn reset_onboarding<R: Runtime>(app: AppHandle<R>) -> Result<OnboardingStatus, String> {
+    save_progress(&app, &OnboardingProgress::default())?;
+    next_step(&app).await
+}
@@ -0,0 +1,230 @@
+/// Onboarding module - the first-run setup, one step at a time
+///
+/// The steps run in a fixed order: microphone permission, system audio permission, a
+/// transcription model, then a test recording. `next_step()` finds the first one not done
+/// yet, checking the system as it goes, so a permission granted in the system settings or a
+/// model downloaded elsewhere moves the setup along by itself. Progress is kept in the Tauri
+/// store and the setup resumes where it stopped after a restart.
+///
+/// System audio and the test recording can be skipped; recording needs the other two.
+///
+/// This module contains:
+/// - The steps and the saved progress
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::audio::permissions::{
+    get_microphone_permission_status, system_audio_permission_status, MicrophonePermissionStatus,
+};
+use crate::audio::transcription::engine::validate_transcription_model_ready;
+
+const STORE_FILE: &str = "onboarding.json";
+const STORE_KEY: &str = "onboarding_progress";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    MicrophonePermission,
+    SystemAudioPermission,
+    ModelDownload,
+    /// The pipeline self-test, recording the user reading a phrase
+    TestRecording,
+    Done,
+}
+
+impl OnboardingStep {
+    /// The steps in the order they are taken
+    pub const ORDER: [OnboardingStep; 4] =
+        [Self::MicrophonePermission, Self::SystemAudioPermission, Self::ModelDownload, Self::TestRecording];
+
+    /// Whether recording works without it
+    pub fn can_skip(&self) -> bool {
+        matches!(self, Self::SystemAudioPermission | Self::TestRecording)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OnboardingProgress {
+    pub completed: Vec<OnboardingStep>,
+    pub skipped: Vec<OnboardingStep>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl OnboardingProgress {
+    /// The first step neither completed nor skipped
+    pub fn pending_step(&self) -> OnboardingStep {
+        if self.finished_at.is_some() {
+            return OnboardingStep::Done;
+        }
+        OnboardingStep::ORDER
+            .into_iter()
+            .find(|step| !self.completed.contains(step) && !self.skipped.contains(step))
+            .unwrap_or(OnboardingStep::Done)
+    }
+
+    pub fn complete(&mut self, step: OnboardingStep) {
+        if step == OnboardingStep::Done || self.completed.contains(&step) {
+            return;
+        }
+        self.skipped.retain(|skipped| *skipped != step);
+        self.completed.push(step);
+        self.finish_if_done();
+    }
+
+    pub fn skip(&mut self, step: OnboardingStep) -> Result<(), String> {
+        if !step.can_skip() {
+            return Err(format!("The {:?} step can't be skipped; recording needs it", step));
+        }
+        if !self.completed.contains(&step) && !self.skipped.contains(&step) {
+            self.skipped.push(step);
+        }
+        self.finish_if_done();
+        Ok(())
+    }
+
+    fn finish_if_done(&mut self) {
+        if self.finished_at.is_none() && self.pending_step() == OnboardingStep::Done {
+            self.finished_at = Some(Utc::now());
+        }
+    }
+}
+
+/// Where the setup stands, and why the current step isn't done yet
+#[derive(Debug, Clone, Serialize)]
+pub struct OnboardingStatus {
+    pub step: OnboardingStep,
+    pub can_skip: bool,
+    pub problem: Option<String>,
+    pub progress: OnboardingProgress,
+}
+
+pub fn load_progress<R: Runtime>(app: &AppHandle<R>) -> OnboardingProgress {
+    let stored = match app.store(STORE_FILE) {
+        Ok(store) => store.get(STORE_KEY),
+        Err(e) => {
+            warn!("Failed to open {}: {}", STORE_FILE, e);
+            None
+        }
+    };
+    stored.and_then(|value| serde_json::from_value(value).ok()).unwrap_or_default()
+}
+
+fn save_progress<R: Runtime>(app: &AppHandle<R>, progress: &OnboardingProgress) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    let value = serde_json::to_value(progress).map_err(|e| e.to_string())?;
+    store.set(STORE_KEY, value);
+    store.save().map_err(|e| format!("Failed to save {}: {}", STORE_FILE, e))
+}
+
+/// Whether the system already satisfies `step`; the test recording only completes when
+/// the self-test passes
+async fn check_step<R: Runtime>(app: &AppHandle<R>, step: OnboardingStep) -> Result<(), String> {
+    match step {
+        OnboardingStep::MicrophonePermission => match get_microphone_permission_status() {
+            MicrophonePermissionStatus::Authorized => Ok(()),
+            MicrophonePermissionStatus::NotDetermined => Err("Microphone access not asked for yet".to_string()),
+            MicrophonePermissionStatus::Denied => Err("Microphone access denied".to_string()),
+            MicrophonePermissionStatus::Restricted => {
+                Err("Microphone access is blocked by a system policy".to_string())
+            }
+        },
+        // An undecided permission is asked for by macOS when recording starts
+        OnboardingStep::SystemAudioPermission if system_audio_permission_status().allows_capture() => Ok(()),
+        OnboardingStep::SystemAudioPermission => Err("System audio capture not allowed".to_string()),
+        OnboardingStep::ModelDownload => validate_transcription_model_ready(app).await,
+        OnboardingStep::TestRecording => Err("Test recording not made yet".to_string()),
+        OnboardingStep::Done => Ok(()),
+    }
+}
+
+/// Completes the steps the system already satisfies and returns the first one left
+pub async fn next_step<R: Runtime>(app: &AppHandle<R>) -> Result<OnboardingStatus, String> {
+    let mut progress = load_progress(app);
+    let before = progress.clone();
+    let mut problem = None;
+    loop {
+        let step = progress.pending_step();
+        if step == OnboardingStep::Done {
+            break;
+        }
+        match check_step(app, step).await {
+            Ok(()) => progress.complete(step),
+            Err(e) => {
+                problem = Some(e);
+                break;
+            }
+        }
+    }
+
+    if progress != before {
+        save_progress(app, &progress)?;
+    }
+    let step = progress.pending_step();
+    if step == OnboardingStep::Done && before.finished_at.is_none() {
+        info!("🚀 Onboarding finished");
+    }
+    Ok(OnboardingStatus { step, can_skip: step.can_skip(), problem, progress })
+}
+
+/// Records a step done outside `next_step()`, such as a passing self-test
+pub fn mark_completed<R: Runtime>(app: &AppHandle<R>, step: OnboardingStep) -> Result<(), String> {
+    let mut progress = load_progress(app);
+    if progress.completed.contains(&step) {
+        return Ok(());
+    }
+    progress.complete(step);
+    save_progress(app, &progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_in_order() {
+        let mut progress = OnboardingProgress::default();
+        assert_eq!(progress.pending_step(), OnboardingStep::MicrophonePermission);
+        progress.complete(OnboardingStep::MicrophonePermission);
+        assert_eq!(progress.pending_step(), OnboardingStep::SystemAudioPermission);
+        progress.skip(OnboardingStep::SystemAudioPermission).unwrap();
+        assert_eq!(progress.pending_step(), OnboardingStep::ModelDownload);
+        assert!(progress.skip(OnboardingStep::ModelDownload).is_err());
+        progress.complete(OnboardingStep::ModelDownload);
+        assert_eq!(progress.pending_step(), OnboardingStep::TestRecording);
+        assert!(progress.finished_at.is_none());
+
+        progress.complete(OnboardingStep::TestRecording);
+        assert_eq!(progress.pending_step(), OnboardingStep::Done);
+        assert!(progress.finished_at.is_some());
+    }
+
+    #[test]
+    fn test_completing_a_skipped_step() {
+        let mut progress = OnboardingProgress::default();
+        progress.skip(OnboardingStep::SystemAudioPermission).unwrap();
+        progress.complete(OnboardingStep::SystemAudioPermission);
+        progress.complete(OnboardingStep::SystemAudioPermission);
+        assert_eq!(progress.completed, vec![OnboardingStep::SystemAudioPermission]);
+        assert!(progress.skipped.is_empty());
+        // Steps before it are still pending
+        assert_eq!(progress.pending_step(), OnboardingStep::MicrophonePermission);
+    }
+
+    #[test]
+    fn test_progress_resumes_from_store() {
+        let saved =
+            serde_json::json!({ "completed": ["microphone_permission"], "skipped": ["system_audio_permission"] });
+        let progress: OnboardingProgress = serde_json::from_value(saved).unwrap();
+        assert_eq!(progress.pending_step(), OnboardingStep::ModelDownload);
+        let empty: OnboardingProgress = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(empty, OnboardingProgress::default());
+    }
+}
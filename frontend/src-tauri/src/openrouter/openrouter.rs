@@ -1,6 +1,5 @@
 use serde::{Deserialize, Serialize};
 use tauri::command;
-use reqwest::blocking::Client;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OpenRouterModel {
@@ -39,11 +38,13 @@ struct OpenRouterResponse {
 }
 
 #[command]
-pub fn get_openrouter_models() -> Result<Vec<OpenRouterModel>, String> {
-    let client = Client::new();
+pub async fn get_openrouter_models() -> Result<Vec<OpenRouterModel>, String> {
+    crate::network::ensure_online("OpenRouter's model list")?;
+    let client = crate::network::client();
     let response = client
         .get("https://openrouter.ai/api/v1/models")
         .send()
+        .await
         .map_err(|e| format!("Failed to make HTTP request: {}", e))?;
 
     if !response.status().is_success() {
@@ -52,6 +53,7 @@ pub fn get_openrouter_models() -> Result<Vec<OpenRouterModel>, String> {
 
     let api_response: OpenRouterResponse = response
         .json()
+        .await
         .map_err(|e| format!("Failed to parse JSON response: {}", e))?;
 
     let models = api_response
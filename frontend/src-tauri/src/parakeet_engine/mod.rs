@@ -13,12 +13,11 @@
 //!
 //! # Module Structure
 //!
-//! - `parakeet_engine`: Main engine implementation
-//! - `model`: ONNX model wrapper and inference logic
+//! - `parakeet_engine`: Main engine implementation (in `meeting_core`)
+//! - `model`: ONNX model wrapper and inference logic (in `meeting_core`)
 //! - `commands`: Tauri command interface for frontend integration
 
-pub mod parakeet_engine;
-pub mod model;
+pub use meeting_core::parakeet_engine::{model, parakeet_engine};
 pub mod commands;
 
 pub use parakeet_engine::{ParakeetEngine, ParakeetEngineError, QuantizationType, ModelInfo, ModelStatus};
@@ -0,0 +1,70 @@
+// plugins/commands.rs
+//
+// Tauri commands for listing installed plugins and turning them on and off. Installing a
+// plugin is copying its folder into the plugins folder.
+
+use log::info;
+use tauri::{AppHandle, Runtime};
+
+use super::{list, load_enabled, plugins_dir, save_enabled, InstalledPlugin};
+
+#[tauri::command]
+pub async fn list_plugins<R: Runtime>(app: AppHandle<R>) -> Result<Vec<InstalledPlugin>, String> {
+    list(&app)
+}
+
+/// Turns a plugin on or off; the change applies from the next event
+#[tauri::command]
+pub async fn set_plugin_enabled<R: Runtime>(
+    app: AppHandle<R>,
+    plugin_id: String,
+    enabled: bool,
+) -> Result<Vec<InstalledPlugin>, String> {
+    let installed = list(&app)?;
+    if enabled && !installed.iter().any(|plugin| plugin.manifest.as_ref().is_some_and(|manifest| manifest.id == plugin_id)) {
+        return Err(format!("No plugin with id {} is installed", plugin_id));
+    }
+    let mut ids = load_enabled(&app);
+    ids.retain(|id| id != &plugin_id);
+    if enabled {
+        ids.push(plugin_id.clone());
+    }
+    save_enabled(&app, &ids)?;
+    info!("🧩 Plugin {} turned {}", plugin_id, if enabled { "on" } else { "off" });
+    list(&app)
+}
+
+/// Open the plugins folder in the system file explorer
+#[tauri::command]
+pub async fn open_plugins_folder<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    let dir = plugins_dir(&app)?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let folder_path = dir.to_string_lossy().to_string();
+
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg(&folder_path)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(&folder_path)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        std::process::Command::new("xdg-open")
+            .arg(&folder_path)
+            .spawn()
+            .map_err(|e| format!("Failed to open folder: {}", e))?;
+    }
+
+    info!("Opened plugins folder: {}", folder_path);
+    Ok(())
+}
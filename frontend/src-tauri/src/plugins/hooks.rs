@@ -0,0 +1,127 @@
+// plugins/hooks.rs
+//
+// Where plugins are called. Plugins subscribed to an event run one after another in folder
+// name order, each seeing the changes the ones before it made. A plugin that fails is
+// logged and skipped; it never stops the transcript or summary from being saved.
+
+use log::{info, warn};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use super::host::{self, PluginReply};
+use super::{subscribed, PluginEvent};
+use crate::database::models::Transcript;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::notifications::commands::notify_in_background;
+use crate::notifications::types::Notification;
+
+/// Tells the frontend a plugin changed a meeting's title or transcript
+pub const MEETING_CHANGED_EVENT: &str = "plugin-changed-meeting";
+
+fn transcript_json(transcripts: &[Transcript]) -> Value {
+    transcripts
+        .iter()
+        .map(|segment| {
+            json!({
+                "id": segment.id,
+                "start": segment.audio_start_time,
+                "end": segment.audio_end_time,
+                "speaker": segment.speaker,
+                "text": segment.transcript,
+            })
+        })
+        .collect()
+}
+
+/// The meeting's title and transcript as plugins receive them
+async fn meeting_params(pool: &SqlitePool, meeting_id: &str) -> Result<Value, String> {
+    let title = match MeetingsRepository::get_meeting(pool, meeting_id).await {
+        Ok(Some(meeting)) => meeting.title,
+        Ok(None) => return Err(format!("No meeting found with id {}", meeting_id)),
+        Err(e) => return Err(format!("Failed to load meeting {}: {}", meeting_id, e)),
+    };
+    let transcripts = TranscriptsRepository::list_for_meeting(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcript of {}: {}", meeting_id, e))?;
+    Ok(json!({ "meeting_id": meeting_id, "title": title, "transcript": transcript_json(&transcripts) }))
+}
+
+/// Saves the title and transcript changes of a reply; returns whether anything changed
+async fn apply_meeting_changes(pool: &SqlitePool, meeting_id: &str, plugin_id: &str, reply: &PluginReply) -> bool {
+    let mut changed = false;
+    if let Some(title) = reply.title.as_deref().map(str::trim).filter(|title| !title.is_empty()) {
+        match MeetingsRepository::update_meeting_name(pool, meeting_id, title).await {
+            Ok(updated) => changed |= updated,
+            Err(e) => warn!("Failed to rename meeting {} for plugin {}: {}", meeting_id, plugin_id, e),
+        }
+    }
+    if !reply.segments.is_empty() {
+        let texts: Vec<(String, String)> =
+            reply.segments.iter().map(|segment| (segment.id.clone(), segment.text.clone())).collect();
+        match TranscriptsRepository::set_texts(pool, meeting_id, &texts).await {
+            Ok(()) => changed = true,
+            Err(e) => warn!("Failed to save transcript changes of plugin {}: {}", plugin_id, e),
+        }
+    }
+    changed
+}
+
+/// Runs the plugins for `event`. With a `summary`, plugins receive it and may replace it.
+async fn run<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    event: PluginEvent,
+    mut summary: Option<&mut String>,
+) {
+    let mut changed = false;
+    for (dir, manifest) in subscribed(app, event) {
+        let mut params = match meeting_params(pool, meeting_id).await {
+            Ok(params) => params,
+            Err(e) => {
+                warn!("Plugins not run for {}: {}", meeting_id, e);
+                return;
+            }
+        };
+        if let Some(summary) = summary.as_deref() {
+            params["summary"] = Value::String(summary.clone());
+        }
+        let reply = match host::call(&dir, &manifest, event, &params).await {
+            Ok(reply) => reply,
+            Err(e) => {
+                warn!("🧩 Plugin {} failed on {} for {}: {}", manifest.id, event.as_str(), meeting_id, e);
+                continue;
+            }
+        };
+        info!("🧩 Plugin {} handled {} for {}", manifest.id, event.as_str(), meeting_id);
+        changed |= apply_meeting_changes(pool, meeting_id, &manifest.id, &reply).await;
+        if let (Some(summary), Some(replacement)) = (summary.as_deref_mut(), &reply.summary) {
+            *summary = replacement.clone();
+        }
+        if let Some(message) = reply.message.as_deref().filter(|message| !message.trim().is_empty()) {
+            notify_in_background(app, Notification::plugin_message(&manifest.name, message));
+        }
+    }
+    if changed {
+        if let Err(e) = app.emit(MEETING_CHANGED_EVENT, json!({ "meeting_id": meeting_id })) {
+            warn!("Failed to emit {}: {}", MEETING_CHANGED_EVENT, e);
+        }
+    }
+}
+
+/// Runs the `transcript-ready` plugins for a saved meeting, in the background
+pub fn transcript_ready<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, meeting_id: &str) {
+    let (app, pool, meeting_id) = (app.clone(), pool.clone(), meeting_id.to_string());
+    tauri::async_runtime::spawn(async move {
+        run(&app, &pool, &meeting_id, PluginEvent::TranscriptReady, None).await;
+    });
+}
+
+/// Runs the `summary-ready` plugins on a new summary before it's saved; returns the summary
+/// with their changes
+pub async fn summary_ready<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, meeting_id: &str, mut markdown: String) -> String {
+    run(app, pool, meeting_id, PluginEvent::SummaryReady, Some(&mut markdown)).await;
+    markdown
+}
@@ -0,0 +1,198 @@
+// plugins/host.rs
+//
+// Runs a plugin for one event. The plugin is started in its own folder and gets a single
+// JSON-RPC 2.0 request as one line on stdin, which is then closed. It answers with one line
+// on stdout: a response with the same id, whose result says what the app should change.
+// Before that it may send `log` notifications, which go to the app's log. Anything else on
+// stdout is ignored, stderr is logged, and a plugin that hasn't answered within its timeout
+// is stopped.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use super::{PluginEvent, PluginManifest};
+
+const REQUEST_ID: u64 = 1;
+
+/// Changes a plugin asks for; every field is optional
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginReply {
+    /// New meeting title
+    pub title: Option<String>,
+    /// New text for transcript segments, by segment id (`transcript-ready` only)
+    pub segments: Vec<SegmentText>,
+    /// Replacement summary Markdown (`summary-ready` only)
+    pub summary: Option<String>,
+    /// Shown to the user as a notification
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SegmentText {
+    pub id: String,
+    pub text: String,
+}
+
+/// A line a plugin wrote to stdout
+#[derive(Debug, PartialEq)]
+enum Message {
+    Reply(Result<PluginReply, String>),
+    Log(String),
+    Other,
+}
+
+fn request_line(event: PluginEvent, params: &Value) -> String {
+    let request = json!({ "jsonrpc": "2.0", "id": REQUEST_ID, "method": event.as_str(), "params": params });
+    format!("{}\n", request)
+}
+
+fn parse_message(line: &str) -> Message {
+    let Ok(message) = serde_json::from_str::<Value>(line) else {
+        return Message::Other;
+    };
+    if message.get("id").and_then(Value::as_u64) == Some(REQUEST_ID) {
+        if let Some(error) = message.get("error") {
+            let text = error.get("message").and_then(Value::as_str).map(str::to_string);
+            return Message::Reply(Err(text.unwrap_or_else(|| error.to_string())));
+        }
+        let reply = match message.get("result") {
+            None | Some(Value::Null) => Ok(PluginReply::default()),
+            Some(result) => {
+                serde_json::from_value(result.clone()).map_err(|e| format!("The plugin's reply is invalid: {}", e))
+            }
+        };
+        return Message::Reply(reply);
+    }
+    if message.get("method").and_then(Value::as_str) == Some("log") {
+        let text = message.pointer("/params/message").and_then(Value::as_str).unwrap_or_default();
+        return Message::Log(text.to_string());
+    }
+    Message::Other
+}
+
+/// Sends `event` to a plugin and waits for its reply
+pub async fn call(dir: &Path, manifest: &PluginManifest, event: PluginEvent, params: &Value) -> Result<PluginReply, String> {
+    let mut command = tokio::process::Command::new(manifest.program(dir));
+    command
+        .args(&manifest.args)
+        .current_dir(dir)
+        .env("MEETILY_PLUGIN_ID", &manifest.id)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = command.spawn().map_err(|e| format!("Failed to start {}: {}", manifest.command, e))?;
+
+    // Written alongside reading, so a plugin that answers before reading everything can't
+    // leave both sides waiting on full pipes
+    let mut stdin = child.stdin.take().ok_or("The plugin's stdin is unavailable")?;
+    let request = request_line(event, params);
+    tauri::async_runtime::spawn(async move {
+        // A plugin that doesn't need the request may exit without reading it
+        let _ = stdin.write_all(request.as_bytes()).await;
+    });
+
+    let id = manifest.id.clone();
+    let stderr = child.stderr.take().ok_or("The plugin's stderr is unavailable")?;
+    let stderr_task = tauri::async_runtime::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        let mut last = None;
+        while let Ok(Some(line)) = lines.next_line().await {
+            debug!("[plugin {}] {}", id, line);
+            last = Some(line);
+        }
+        last
+    });
+
+    let stdout = child.stdout.take().ok_or("The plugin's stdout is unavailable")?;
+    let read_reply = async {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Some(line) = lines.next_line().await.map_err(|e| format!("Failed to read the plugin's output: {}", e))? {
+            match parse_message(&line) {
+                Message::Reply(reply) => return Ok(Some(reply)),
+                Message::Log(text) => info!("[plugin {}] {}", manifest.id, text),
+                Message::Other => {}
+            }
+        }
+        Ok::<_, String>(None)
+    };
+
+    let timeout = Duration::from_secs(manifest.timeout_seconds());
+    let reply = match tokio::time::timeout(timeout, read_reply).await {
+        Ok(Ok(Some(reply))) => reply,
+        Ok(Ok(None)) => {
+            let _ = child.wait().await;
+            let reason = stderr_task.await.ok().flatten();
+            return Err(match reason {
+                Some(reason) => format!("The plugin exited without replying: {}", reason),
+                None => "The plugin exited without replying".to_string(),
+            });
+        }
+        Ok(Err(e)) => Err(e),
+        Err(_) => {
+            let _ = child.kill().await;
+            return Err(format!("The plugin didn't reply within {}s", timeout.as_secs()));
+        }
+    };
+
+    // The reply is all the app needs; give the plugin a moment to finish up, then stop it
+    if tokio::time::timeout(Duration::from_secs(5), child.wait()).await.is_err() {
+        if let Err(e) = child.kill().await {
+            warn!("Failed to stop plugin {}: {}", manifest.id, e);
+        }
+    }
+    reply
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_line() {
+        let line = request_line(PluginEvent::SummaryReady, &json!({ "meeting_id": "meeting-1" }));
+        assert!(line.ends_with('\n'));
+        let request: Value = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(request["method"], "summary-ready");
+        assert_eq!(request["id"], REQUEST_ID);
+        assert_eq!(request["params"]["meeting_id"], "meeting-1");
+    }
+
+    #[test]
+    fn test_parse_message() {
+        let reply = parse_message(r#"{"jsonrpc":"2.0","id":1,"result":{"title":"Sprint review","message":"Created 2 tickets"}}"#);
+        assert_eq!(
+            reply,
+            Message::Reply(Ok(PluginReply {
+                title: Some("Sprint review".to_string()),
+                message: Some("Created 2 tickets".to_string()),
+                ..Default::default()
+            }))
+        );
+        assert_eq!(parse_message(r#"{"jsonrpc":"2.0","id":1,"result":null}"#), Message::Reply(Ok(PluginReply::default())));
+        assert_eq!(
+            parse_message(r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"Jira is down"}}"#),
+            Message::Reply(Err("Jira is down".to_string()))
+        );
+        assert_eq!(
+            parse_message(r#"{"jsonrpc":"2.0","method":"log","params":{"message":"Connecting"}}"#),
+            Message::Log("Connecting".to_string())
+        );
+        assert_eq!(parse_message("Loading config..."), Message::Other);
+        assert_eq!(parse_message(r#"{"jsonrpc":"2.0","id":7,"result":{}}"#), Message::Other);
+    }
+}
@@ -0,0 +1,217 @@
+/// Plugins module - running the user's own programs when transcripts and summaries are ready
+///
+/// A plugin is a folder in the app's `plugins` folder with a `plugin.json` manifest naming
+/// the program to run and the events it wants. When one of those events happens, the app
+/// starts the program, sends it the meeting as a JSON-RPC request on stdin and reads the
+/// reply from stdout. Plugins can act on the meeting themselves (create Jira tickets, file
+/// notes) and can also hand back changes for the app to make: a new title, corrected
+/// transcript text or a rewritten summary. Plugins run arbitrary programs, so each one stays
+/// off until it's turned on in the settings. See `docs/plugins.md` for the protocol.
+///
+/// This module contains:
+/// - Starting plugins and talking JSON-RPC to them (`host`)
+/// - The hooks the transcript and summary code call (`hooks`)
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+pub mod hooks;
+pub mod host;
+
+use std::path::{Path, PathBuf};
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "plugins.json";
+const ENABLED_KEY: &str = "enabled_plugins";
+
+pub const MANIFEST_FILE: &str = "plugin.json";
+
+/// How long a plugin may take to answer when its manifest doesn't say
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 60;
+const MAX_TIMEOUT_SECONDS: u64 = 15 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PluginEvent {
+    TranscriptReady,
+    SummaryReady,
+}
+
+impl PluginEvent {
+    pub const ALL: [Self; 2] = [Self::TranscriptReady, Self::SummaryReady];
+
+    /// The JSON-RPC method a plugin receives the event as
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TranscriptReady => "transcript-ready",
+            Self::SummaryReady => "summary-ready",
+        }
+    }
+}
+
+/// A plugin's `plugin.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// Letters, digits, `-` and `_`
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The program to run; a relative path is looked up in the plugin's folder first, then
+    /// on the PATH (e.g. `python3`)
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub events: Vec<PluginEvent>,
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+}
+
+impl PluginManifest {
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let manifest: Self = serde_json::from_str(json).map_err(|e| format!("Invalid {}: {}", MANIFEST_FILE, e))?;
+        if manifest.id.is_empty()
+            || !manifest.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!("Invalid plugin id '{}': use letters, digits, '-' and '_'", manifest.id));
+        }
+        if manifest.name.trim().is_empty() {
+            return Err("The plugin needs a name".to_string());
+        }
+        if manifest.command.trim().is_empty() {
+            return Err("The plugin needs a command to run".to_string());
+        }
+        if manifest.events.is_empty() {
+            return Err("The plugin doesn't subscribe to any events".to_string());
+        }
+        Ok(manifest)
+    }
+
+    pub fn timeout_seconds(&self) -> u64 {
+        self.timeout_seconds.unwrap_or(DEFAULT_TIMEOUT_SECONDS).clamp(1, MAX_TIMEOUT_SECONDS)
+    }
+
+    /// The program to start: `command` in the plugin's folder when it's there
+    pub fn program(&self, dir: &Path) -> PathBuf {
+        let local = dir.join(&self.command);
+        if Path::new(&self.command).is_relative() && local.is_file() {
+            local
+        } else {
+            PathBuf::from(&self.command)
+        }
+    }
+}
+
+/// A plugin found in the plugins folder
+#[derive(Debug, Clone, Serialize)]
+pub struct InstalledPlugin {
+    /// The plugin's folder
+    pub dir: PathBuf,
+    pub manifest: Option<PluginManifest>,
+    /// Why the manifest couldn't be read
+    pub error: Option<String>,
+    pub enabled: bool,
+}
+
+impl InstalledPlugin {
+    fn subscribes_to(&self, event: PluginEvent) -> bool {
+        self.enabled && self.manifest.as_ref().is_some_and(|manifest| manifest.events.contains(&event))
+    }
+}
+
+/// The folder plugins are installed in, shared by all workspaces
+pub fn plugins_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    Ok(dir.join("plugins"))
+}
+
+pub(crate) fn load_enabled<R: Runtime>(app: &AppHandle<R>) -> Vec<String> {
+    let stored = match app.store(STORE_FILE) {
+        Ok(store) => store.get(ENABLED_KEY),
+        Err(e) => {
+            warn!("Failed to open {}: {}", STORE_FILE, e);
+            None
+        }
+    };
+    stored.and_then(|value| serde_json::from_value(value).ok()).unwrap_or_default()
+}
+
+pub(crate) fn save_enabled<R: Runtime>(app: &AppHandle<R>, enabled: &[String]) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    store.set(ENABLED_KEY, serde_json::to_value(enabled).map_err(|e| e.to_string())?);
+    store.save().map_err(|e| format!("Failed to save {}: {}", STORE_FILE, e))
+}
+
+/// Every folder in `dir` with a manifest, sorted by folder name
+fn discover(dir: &Path, enabled: &[String]) -> Vec<InstalledPlugin> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut folders: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(MANIFEST_FILE).is_file())
+        .collect();
+    folders.sort();
+    folders
+        .into_iter()
+        .map(|dir| {
+            let manifest = std::fs::read_to_string(dir.join(MANIFEST_FILE))
+                .map_err(|e| format!("Failed to read {}: {}", MANIFEST_FILE, e))
+                .and_then(|json| PluginManifest::parse(&json));
+            match manifest {
+                Ok(manifest) => {
+                    let enabled = enabled.contains(&manifest.id);
+                    InstalledPlugin { dir, manifest: Some(manifest), error: None, enabled }
+                }
+                Err(error) => InstalledPlugin { dir, manifest: None, error: Some(error), enabled: false },
+            }
+        })
+        .collect()
+}
+
+/// The installed plugins, with whether each is turned on
+pub fn list<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<InstalledPlugin>, String> {
+    Ok(discover(&plugins_dir(app)?, &load_enabled(app)))
+}
+
+/// The turned-on plugins that want `event`, in folder name order
+pub fn subscribed<R: Runtime>(app: &AppHandle<R>, event: PluginEvent) -> Vec<(PathBuf, PluginManifest)> {
+    let plugins = match list(app) {
+        Ok(plugins) => plugins,
+        Err(e) => {
+            warn!("Failed to list plugins: {}", e);
+            return Vec::new();
+        }
+    };
+    plugins
+        .into_iter()
+        .filter(|plugin| plugin.subscribes_to(event))
+        .filter_map(|plugin| plugin.manifest.map(|manifest| (plugin.dir, manifest)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = PluginManifest::parse(
+            r#"{"id": "jira-tickets", "name": "Jira tickets", "command": "python3", "args": ["main.py"],
+                "events": ["summary-ready"]}"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.events, vec![PluginEvent::SummaryReady]);
+        assert_eq!(manifest.timeout_seconds(), DEFAULT_TIMEOUT_SECONDS);
+        assert_eq!(manifest.program(Path::new("/nonexistent")), PathBuf::from("python3"));
+
+        assert!(PluginManifest::parse(r#"{"id": "../x", "name": "X", "command": "x", "events": ["summary-ready"]}"#).is_err());
+        assert!(PluginManifest::parse(r#"{"id": "x", "name": "X", "command": "x", "events": []}"#).is_err());
+        assert!(PluginManifest::parse(r#"{"id": "x", "name": "X", "command": "x", "events": ["meeting-deleted"]}"#).is_err());
+    }
+}
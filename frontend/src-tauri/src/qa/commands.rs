@@ -0,0 +1,189 @@
+// qa/commands.rs
+//
+// Tauri commands for asking questions about meetings.
+
+use crate::database::models::Job;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::diarization::commands::models_dir;
+use crate::jobs::{self, JobPayload, JobPriority};
+use crate::qa::answer::{answer_question, QaAnswer};
+use crate::qa::embeddings::configured_embedder;
+use crate::qa::index::{index_all_meetings, index_meeting, queue_indexing, retrieve, RetrievedSegment};
+use crate::qa::local_embedder;
+use crate::state::AppState;
+use crate::summary::extraction::extraction_model;
+use crate::summary::{LLMProvider, SummaryService};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Runtime};
+use tracing::info;
+
+pub const EMBEDDING_MODEL_DOWNLOAD_PROGRESS_EVENT: &str = "embedding-model-download-progress";
+
+/// Excerpts given to the model per question
+const DEFAULT_TOP_K: usize = 8;
+const MAX_TOP_K: usize = 30;
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingModelStatus {
+    pub model: String,
+    pub downloaded: bool,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SemanticSearchHit {
+    #[serde(flatten)]
+    pub segment: RetrievedSegment,
+    pub meeting_title: Option<String>,
+}
+
+async fn meeting_titles(pool: &SqlitePool) -> Result<HashMap<String, String>, String> {
+    Ok(MeetingsRepository::get_meetings(pool)
+        .await
+        .map_err(|e| format!("Failed to list meetings: {}", e))?
+        .into_iter()
+        .map(|meeting| (meeting.id, meeting.title))
+        .collect())
+}
+
+async fn ask(
+    pool: &SqlitePool,
+    models_dir: &Path,
+    question: String,
+    meeting_id: Option<String>,
+    top_k: Option<usize>,
+    model: Option<String>,
+    model_name: Option<String>,
+) -> Result<QaAnswer, String> {
+    if question.trim().is_empty() {
+        return Err("The question is empty".to_string());
+    }
+    let embedder = configured_embedder(pool, models_dir).await?;
+    match meeting_id.as_deref() {
+        Some(meeting_id) => index_meeting(pool, embedder.as_ref(), meeting_id).await?,
+        None => index_all_meetings(pool, embedder.as_ref()).await?,
+    };
+
+    let k = top_k.unwrap_or(DEFAULT_TOP_K).clamp(1, MAX_TOP_K);
+    let segments = retrieve(pool, embedder.as_ref(), &question, meeting_id.as_deref(), k).await?;
+    let meeting_titles = meeting_titles(pool).await?;
+
+    let (model, model_name) = extraction_model(pool, model, model_name).await?;
+    let provider = LLMProvider::from_str(&model)?;
+    let (provider, model_name) = SummaryService::choose_provider(pool, provider, &model_name).await;
+    let (provider, _) = SummaryService::resolve_provider(pool, provider, &model_name).await?;
+
+    info!(
+        "💬 Answering question over {} with {} excerpt(s) and {}",
+        meeting_id.as_deref().unwrap_or("all meetings"),
+        segments.len(),
+        provider.model()
+    );
+    let answer = answer_question(provider.as_ref(), &question, &segments, &meeting_titles).await?;
+    crate::usage::record_llm(pool, meeting_id.as_deref(), "qa", provider.as_ref(), &answer.usage).await;
+    Ok(answer)
+}
+
+/// Answers a question about one meeting, citing the transcript segments used
+///
+/// Without a model the saved summary model answers.
+#[tauri::command]
+pub async fn ask_meeting<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    question: String,
+    top_k: Option<usize>,
+    model: Option<String>,
+    model_name: Option<String>,
+) -> Result<QaAnswer, String> {
+    ask(state.db_manager.pool(), &models_dir(&app)?, question, Some(meeting_id), top_k, model, model_name).await
+}
+
+/// Answers a question from every meeting, citing the transcript segments used
+#[tauri::command]
+pub async fn ask_all_meetings<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    question: String,
+    top_k: Option<usize>,
+    model: Option<String>,
+    model_name: Option<String>,
+) -> Result<QaAnswer, String> {
+    ask(state.db_manager.pool(), &models_dir(&app)?, question, None, top_k, model, model_name).await
+}
+
+/// Segments closest in meaning to `query`, in one meeting or across the archive
+///
+/// Only segments indexed already are searched; new meetings are indexed in the background.
+#[tauri::command]
+pub async fn semantic_search<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    query: String,
+    meeting_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<SemanticSearchHit>, String> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let pool = state.db_manager.pool();
+    let embedder = configured_embedder(pool, &models_dir(&app)?).await?;
+    let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, MAX_SEARCH_LIMIT);
+    let segments = retrieve(pool, embedder.as_ref(), &query, meeting_id.as_deref(), limit).await?;
+    let titles = meeting_titles(pool).await?;
+    Ok(segments
+        .into_iter()
+        .map(|segment| SemanticSearchHit { meeting_title: titles.get(&segment.meeting_id).cloned(), segment })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_embedding_model_status<R: Runtime>(app: AppHandle<R>) -> Result<EmbeddingModelStatus, String> {
+    let models_dir = models_dir(&app)?;
+    Ok(EmbeddingModelStatus {
+        model: local_embedder::LOCAL_EMBEDDING_MODEL.to_string(),
+        downloaded: local_embedder::is_downloaded(&models_dir),
+        path: local_embedder::model_dir(&models_dir).to_string_lossy().to_string(),
+    })
+}
+
+/// Downloads the on-device embedding model and queues indexing of every meeting with it
+#[tauri::command]
+pub async fn download_embedding_model<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<EmbeddingModelStatus, String> {
+    let models_dir = models_dir(&app)?;
+    let progress_app = app.clone();
+    let path = local_embedder::download_model(&models_dir, move |progress| {
+        let _ = progress_app.emit(EMBEDDING_MODEL_DOWNLOAD_PROGRESS_EVENT, serde_json::json!({ "progress": progress }));
+    })
+    .await
+    .map_err(|e| format!("Failed to download embedding model: {}", e))?;
+
+    queue_indexing(&app, state.db_manager.pool(), &models_dir, None).await;
+    Ok(EmbeddingModelStatus {
+        model: local_embedder::LOCAL_EMBEDDING_MODEL.to_string(),
+        downloaded: true,
+        path: path.to_string_lossy().to_string(),
+    })
+}
+
+/// Queues embedding of every meeting's segments that aren't indexed yet
+#[tauri::command]
+pub async fn rebuild_semantic_index<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Job, String> {
+    info!("rebuild_semantic_index called");
+    let pool = state.db_manager.pool().clone();
+    jobs::enqueue(&app, &pool, JobPayload::SemanticIndex { meeting_id: None }, JobPriority::Normal)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,186 @@
+// qa/embeddings.rs
+//
+// Text embeddings for retrieval. The on-device model (`local_embedder`) is used once it
+// has been downloaded; otherwise any server with an OpenAI-compatible `/v1/embeddings`
+// endpoint works: Ollama (e.g. `nomic-embed-text`), a llama.cpp server started with
+// `--embeddings`, or OpenAI itself.
+
+use crate::database::repositories::setting::SettingsRepository;
+use crate::qa::local_embedder::{self, LocalEmbedder};
+use crate::summary::credentials;
+use crate::summary::llm_client::{LLMProvider, DEFAULT_OLLAMA_ENDPOINT};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::Path;
+
+pub const DEFAULT_OLLAMA_EMBEDDING_MODEL: &str = "nomic-embed-text";
+pub const DEFAULT_OPENAI_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+
+/// Texts sent per request
+const EMBEDDING_BATCH_SIZE: usize = 32;
+
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// One vector per text, in order
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String>;
+
+    /// Identifies the vector space; stored with every vector
+    fn model_id(&self) -> String;
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+pub struct OpenAiCompatibleEmbedder {
+    client: Client,
+    provider: LLMProvider,
+    url: String,
+    api_key: String,
+    model: String,
+}
+
+impl OpenAiCompatibleEmbedder {
+    pub fn new(provider: LLMProvider, base_url: &str, api_key: &str, model: &str) -> Self {
+        Self {
+            client: crate::network::client(),
+            provider,
+            url: format!("{}/v1/embeddings", base_url.trim_end_matches('/')),
+            api_key: api_key.to_string(),
+            model: model.to_string(),
+        }
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let mut request = self.client.post(&self.url).json(&EmbeddingRequest { model: &self.model, input: texts });
+        if !self.api_key.is_empty() {
+            request = request.bearer_auth(&self.api_key);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach the {} embedding endpoint: {}", self.provider.display_name(), e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let message = response.text().await.unwrap_or_default();
+            return Err(format!("Embedding request failed ({}): {}", status, message));
+        }
+        let mut body: EmbeddingResponse =
+            response.json().await.map_err(|e| format!("Failed to parse embedding response: {}", e))?;
+        if body.data.len() != texts.len() {
+            return Err(format!("Expected {} embeddings, got {}", texts.len(), body.data.len()));
+        }
+        body.data.sort_by_key(|data| data.index);
+        Ok(body.data.into_iter().map(|data| data.embedding).collect())
+    }
+}
+
+#[async_trait]
+impl Embedder for OpenAiCompatibleEmbedder {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(EMBEDDING_BATCH_SIZE) {
+            vectors.extend(self.embed_batch(batch).await?);
+        }
+        Ok(vectors)
+    }
+
+    fn model_id(&self) -> String {
+        format!("{}:{}", self.provider.id(), self.model)
+    }
+}
+
+/// The on-device model when downloaded, otherwise the one matching the summary provider
+///
+/// OpenAI users embed with OpenAI; everyone else with the local Ollama server, so the
+/// transcript stays on the machine.
+pub async fn configured_embedder(pool: &SqlitePool, models_dir: &Path) -> Result<Box<dyn Embedder>, String> {
+    if local_embedder::is_downloaded(models_dir) {
+        let models_dir = models_dir.to_path_buf();
+        let embedder = tokio::task::spawn_blocking(move || LocalEmbedder::shared(&models_dir))
+            .await
+            .map_err(|e| format!("Failed to load embedding model: {}", e))?
+            .map_err(|e| format!("Failed to load embedding model: {}", e))?;
+        return Ok(Box::new(embedder));
+    }
+
+    let config = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to load model config: {}", e))?;
+
+    if !crate::network::is_local_only() && config.as_ref().is_some_and(|c| c.provider.eq_ignore_ascii_case("openai")) {
+        let api_key = credentials::resolve_api_key(pool, LLMProvider::OpenAI.id())
+            .await
+            .map_err(|e| e.to_string())?
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| "OpenAI API key not found".to_string())?;
+        return Ok(Box::new(OpenAiCompatibleEmbedder::new(
+            LLMProvider::OpenAI,
+            "https://api.openai.com",
+            &api_key,
+            DEFAULT_OPENAI_EMBEDDING_MODEL,
+        )));
+    }
+
+    let endpoint = config
+        .and_then(|c| c.ollama_endpoint)
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_OLLAMA_ENDPOINT.to_string());
+    Ok(Box::new(OpenAiCompatibleEmbedder::new(LLMProvider::Ollama, &endpoint, "", DEFAULT_OLLAMA_EMBEDDING_MODEL)))
+}
+
+/// Vectors are stored as little-endian f32 bytes
+pub fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|value| value.to_le_bytes()).collect()
+}
+
+pub fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_roundtrip_and_similarity() {
+        let vector = vec![0.5, -1.25, 3.0];
+        assert_eq!(decode_vector(&encode_vector(&vector)), vector);
+
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 1.0]), 0.0);
+    }
+}
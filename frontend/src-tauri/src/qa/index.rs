@@ -0,0 +1,186 @@
+// qa/index.rs
+//
+// Embeds transcript segments and finds the ones closest to a question.
+//
+// New meetings are embedded by a background job when the local embedding model is
+// installed. Indexing is also lazy: before a meeting is searched, any segment without a
+// vector from the current embedder (or whose text changed since) is embedded and stored.
+// Search runs against the in-memory vector index (`vector_index`).
+
+use crate::database::repositories::{
+    embedding::{EmbeddingsRepository, NewEmbedding},
+    meeting::MeetingsRepository,
+    transcript::TranscriptsRepository,
+};
+use crate::jobs::{self, JobPayload, JobPriority};
+use crate::qa::embeddings::{encode_vector, Embedder};
+use crate::qa::{local_embedder, vector_index};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::{AppHandle, Runtime};
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RetrievedSegment {
+    pub transcript_id: String,
+    pub meeting_id: String,
+    pub text: String,
+    pub speaker: Option<String>,
+    /// Seconds into the recording
+    pub start_time: Option<f64>,
+    pub score: f32,
+}
+
+/// What gets embedded for a segment; the speaker helps questions like "what did Bob say"
+pub fn segment_text(speaker: Option<&str>, text: &str) -> String {
+    match speaker {
+        Some(speaker) => format!("{}: {}", speaker, text.trim()),
+        None => text.trim().to_string(),
+    }
+}
+
+fn text_hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+/// Embeds the meeting's segments that have no up-to-date vector; returns how many
+pub async fn index_meeting(pool: &SqlitePool, embedder: &dyn Embedder, meeting_id: &str) -> Result<usize, String> {
+    let model = embedder.model_id();
+    let existing: HashMap<String, String> = EmbeddingsRepository::hashes_for_meeting(pool, meeting_id, &model)
+        .await
+        .map_err(|e| format!("Failed to load embeddings: {}", e))?
+        .into_iter()
+        .collect();
+    let transcripts = TranscriptsRepository::list_for_meeting(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcript: {}", e))?;
+
+    let pending: Vec<(String, String, String)> = transcripts
+        .iter()
+        .filter(|t| !t.transcript.trim().is_empty())
+        .map(|t| {
+            let text = segment_text(t.speaker.as_deref(), &t.transcript);
+            let hash = text_hash(&text);
+            (t.id.clone(), text, hash)
+        })
+        .filter(|(id, _, hash)| existing.get(id) != Some(hash))
+        .collect();
+    if pending.is_empty() {
+        return Ok(0);
+    }
+
+    let texts: Vec<String> = pending.iter().map(|(_, text, _)| text.clone()).collect();
+    let vectors = embedder.embed(&texts).await?;
+    if vectors.len() != pending.len() {
+        return Err(format!("Expected {} embeddings, got {}", pending.len(), vectors.len()));
+    }
+    let embeddings: Vec<NewEmbedding> = pending
+        .iter()
+        .zip(&vectors)
+        .map(|((transcript_id, _, text_hash), vector)| NewEmbedding {
+            transcript_id: transcript_id.clone(),
+            text_hash: text_hash.clone(),
+            vector: encode_vector(vector),
+        })
+        .collect();
+    EmbeddingsRepository::save(pool, meeting_id, &model, &embeddings)
+        .await
+        .map_err(|e| format!("Failed to save embeddings: {}", e))?;
+    let indexed: Vec<(String, Vec<f32>)> =
+        pending.into_iter().map(|(transcript_id, _, _)| transcript_id).zip(vectors).collect();
+    vector_index::upsert(&model, meeting_id, &indexed);
+
+    info!("🧬 Embedded {} segment(s) of meeting {} with {}", embeddings.len(), meeting_id, model);
+    Ok(embeddings.len())
+}
+
+/// Brings every meeting's embeddings up to date; meetings that fail are skipped
+pub async fn index_all_meetings(pool: &SqlitePool, embedder: &dyn Embedder) -> Result<usize, String> {
+    let meetings = MeetingsRepository::get_meetings(pool)
+        .await
+        .map_err(|e| format!("Failed to list meetings: {}", e))?;
+    let mut embedded = 0;
+    for meeting in meetings {
+        match index_meeting(pool, embedder, &meeting.id).await {
+            Ok(count) => embedded += count,
+            Err(e) => warn!("Failed to index meeting {}: {}", meeting.id, e),
+        }
+    }
+    Ok(embedded)
+}
+
+/// Segments most relevant to `question`, in one meeting or across all of them
+pub async fn retrieve(
+    pool: &SqlitePool,
+    embedder: &dyn Embedder,
+    question: &str,
+    meeting_id: Option<&str>,
+    k: usize,
+) -> Result<Vec<RetrievedSegment>, String> {
+    let model = embedder.model_id();
+    vector_index::ensure_loaded(pool, &model).await?;
+    let query = embedder
+        .embed(&[question.to_string()])
+        .await?
+        .pop()
+        .ok_or_else(|| "The embedder returned no vector for the question".to_string())?;
+
+    let hits = vector_index::search(&model, &query, meeting_id, k);
+    let ids: Vec<String> = hits.iter().map(|hit| hit.transcript_id.clone()).collect();
+    let mut texts: HashMap<String, _> = EmbeddingsRepository::segment_texts(pool, &ids)
+        .await
+        .map_err(|e| format!("Failed to load transcript: {}", e))?
+        .into_iter()
+        .map(|segment| (segment.id.clone(), segment))
+        .collect();
+
+    // Keep the ranking; segments deleted since they were embedded drop out
+    Ok(hits
+        .into_iter()
+        .filter_map(|hit| {
+            let segment = texts.remove(&hit.transcript_id)?;
+            Some(RetrievedSegment {
+                transcript_id: hit.transcript_id,
+                meeting_id: segment.meeting_id,
+                text: segment.transcript,
+                speaker: segment.speaker,
+                start_time: segment.audio_start_time,
+                score: hit.score,
+            })
+        })
+        .collect())
+}
+
+/// Queues background embedding of a meeting (or of all meetings when None)
+///
+/// Only done when the local embedding model is installed, so transcripts are never sent
+/// to a server without the user asking a question.
+pub async fn queue_indexing<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    models_dir: &Path,
+    meeting_id: Option<String>,
+) {
+    if !local_embedder::is_downloaded(models_dir) {
+        return;
+    }
+    if let Err(e) = jobs::enqueue(app, pool, JobPayload::SemanticIndex { meeting_id }, JobPriority::Normal).await {
+        warn!("Failed to queue semantic indexing: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_text_hash_changes_with_speaker() {
+        assert_eq!(segment_text(Some("Alice"), " Ship it "), "Alice: Ship it");
+        assert_eq!(segment_text(None, "Ship it"), "Ship it");
+        assert_ne!(text_hash("Alice: Ship it"), text_hash("Ship it"));
+        assert_eq!(text_hash("x").len(), 64);
+    }
+}
@@ -0,0 +1,230 @@
+// qa/local_embedder.rs
+//
+// On-device text embeddings with all-MiniLM-L6-v2 run through ONNX Runtime, so the
+// meeting archive can be indexed without any server. The model (~90 MB) and its
+// tokenizer are downloaded on request into the app's models directory.
+
+use crate::qa::embeddings::Embedder;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use ndarray::Array2;
+use once_cell::sync::Lazy;
+use ort::execution_providers::CPUExecutionProvider;
+use ort::inputs;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::TensorRef;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokenizers::{Encoding, PaddingParams, Tokenizer, TruncationParams};
+use tokio::io::AsyncWriteExt;
+
+pub const LOCAL_EMBEDDING_MODEL: &str = "all-MiniLM-L6-v2";
+const MODEL_FILE: &str = "model.onnx";
+const TOKENIZER_FILE: &str = "tokenizer.json";
+const MODEL_BASE_URL: &str = "https://huggingface.co/sentence-transformers/all-MiniLM-L6-v2/resolve/main";
+
+/// The model was trained on sequences up to this many tokens
+const MAX_SEQUENCE_TOKENS: usize = 256;
+/// Texts run through the model at once
+const BATCH_SIZE: usize = 16;
+
+/// The loaded model, shared by every search and indexing job
+static LOADED: Lazy<Mutex<Option<LocalEmbedder>>> = Lazy::new(|| Mutex::new(None));
+
+/// Where the model lives under the app's models directory
+pub fn model_dir(models_dir: &Path) -> PathBuf {
+    models_dir.join("embeddings").join(LOCAL_EMBEDDING_MODEL)
+}
+
+pub fn is_downloaded(models_dir: &Path) -> bool {
+    let dir = model_dir(models_dir);
+    dir.join(MODEL_FILE).exists() && dir.join(TOKENIZER_FILE).exists()
+}
+
+/// Download the model and its tokenizer, reporting progress of the model file as a percentage
+pub async fn download_model(models_dir: &Path, progress: impl Fn(u8)) -> Result<PathBuf> {
+    let dir = model_dir(models_dir);
+    tokio::fs::create_dir_all(&dir).await?;
+    download_file(&format!("{}/{}", MODEL_BASE_URL, TOKENIZER_FILE), &dir.join(TOKENIZER_FILE), |_| {}).await?;
+    download_file(&format!("{}/onnx/{}", MODEL_BASE_URL, MODEL_FILE), &dir.join(MODEL_FILE), progress).await?;
+    log::info!("Embedding model saved to {}", dir.display());
+    Ok(dir)
+}
+
+pub(crate) async fn download_file(url: &str, path: &Path, progress: impl Fn(u8)) -> Result<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    log::info!("Downloading {}", url);
+    let response = reqwest::get(url)
+        .await
+        .map_err(|e| anyhow!("Failed to start download: {}", e))?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Download failed with status: {}", response.status()));
+    }
+
+    // Download next to the file and rename, so a partial file is never loaded
+    let partial_path = path.with_extension("part");
+    let total_size = response.content_length().unwrap_or(0);
+    let mut file = tokio::fs::File::create(&partial_path).await?;
+    let mut stream = response.bytes_stream();
+    let mut downloaded = 0u64;
+    let mut last_progress = 0u8;
+    progress(0);
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| anyhow!("Failed to read chunk: {}", e))?;
+        file.write_all(&chunk).await?;
+        downloaded += chunk.len() as u64;
+        if total_size > 0 {
+            let percent = ((downloaded as f64 / total_size as f64) * 100.0) as u8;
+            if percent > last_progress {
+                last_progress = percent;
+                progress(percent);
+            }
+        }
+    }
+    file.flush().await?;
+    drop(file);
+
+    tokio::fs::rename(&partial_path, path).await?;
+    progress(100);
+    Ok(())
+}
+
+/// Sentence embedding model; cheap to clone
+#[derive(Clone)]
+pub struct LocalEmbedder {
+    session: Arc<Mutex<Session>>,
+    tokenizer: Arc<Tokenizer>,
+}
+
+impl LocalEmbedder {
+    pub fn load(dir: &Path) -> Result<Self> {
+        let (model_path, tokenizer_path) = (dir.join(MODEL_FILE), dir.join(TOKENIZER_FILE));
+        if !model_path.exists() || !tokenizer_path.exists() {
+            return Err(anyhow!("Embedding model not found. Download it first."));
+        }
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path).map_err(|e| anyhow!("Failed to load tokenizer: {}", e))?;
+        tokenizer
+            .with_truncation(Some(TruncationParams { max_length: MAX_SEQUENCE_TOKENS, ..Default::default() }))
+            .map_err(|e| anyhow!("Failed to configure tokenizer: {}", e))?;
+        tokenizer.with_padding(Some(PaddingParams::default()));
+
+        let threads = std::thread::available_parallelism().map(|n| n.get().min(4)).unwrap_or(2);
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_execution_providers(vec![CPUExecutionProvider::default().build()])?
+            .with_intra_threads(threads)?
+            .commit_from_file(&model_path)?;
+        Ok(Self { session: Arc::new(Mutex::new(session)), tokenizer: Arc::new(tokenizer) })
+    }
+
+    /// The model loaded once and kept for the lifetime of the app
+    pub fn shared(models_dir: &Path) -> Result<Self> {
+        let mut loaded = LOADED.lock().unwrap();
+        if let Some(embedder) = loaded.as_ref() {
+            return Ok(embedder.clone());
+        }
+        let embedder = Self::load(&model_dir(models_dir))?;
+        *loaded = Some(embedder.clone());
+        Ok(embedder)
+    }
+
+    /// Unit-length embeddings of a batch of texts
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let encodings = self
+            .tokenizer
+            .encode_batch(texts.to_vec(), true)
+            .map_err(|e| anyhow!("Failed to tokenize: {}", e))?;
+        let (batch, length) = (encodings.len(), encodings.first().map_or(0, |e| e.get_ids().len()));
+        if length == 0 {
+            return Ok(vec![Vec::new(); batch]);
+        }
+
+        let column = |values: fn(&Encoding) -> &[u32]| -> Result<Array2<i64>> {
+            let flat = encodings.iter().flat_map(|e| values(e).iter().map(|&v| v as i64)).collect();
+            Ok(Array2::from_shape_vec((batch, length), flat)?)
+        };
+        let input_ids = column(Encoding::get_ids)?;
+        let attention_mask = column(Encoding::get_attention_mask)?;
+        let token_type_ids = column(Encoding::get_type_ids)?;
+
+        let mut session = self.session.lock().unwrap();
+        let outputs = session.run(inputs![
+            "input_ids" => TensorRef::from_array_view(input_ids.view())?,
+            "attention_mask" => TensorRef::from_array_view(attention_mask.view())?,
+            "token_type_ids" => TensorRef::from_array_view(token_type_ids.view())?
+        ])?;
+        // Token embeddings: [batch, length, hidden]
+        let hidden_states = outputs[0].try_extract_array::<f32>()?;
+        let hidden = hidden_states.shape().get(2).copied().ok_or_else(|| anyhow!("Unexpected model output shape"))?;
+        let values: Vec<f32> = hidden_states.iter().copied().collect();
+
+        Ok(encodings
+            .iter()
+            .enumerate()
+            .map(|(i, encoding)| {
+                let tokens = &values[i * length * hidden..(i + 1) * length * hidden];
+                mean_pool(tokens, encoding.get_attention_mask(), hidden)
+            })
+            .collect())
+    }
+}
+
+/// Averages the token embeddings of the non-padding tokens and scales to unit length
+fn mean_pool(tokens: &[f32], attention_mask: &[u32], hidden: usize) -> Vec<f32> {
+    let mut pooled = vec![0.0f32; hidden];
+    let mut count = 0.0f32;
+    for (token, &mask) in tokens.chunks_exact(hidden).zip(attention_mask) {
+        if mask == 0 {
+            continue;
+        }
+        count += 1.0;
+        for (sum, value) in pooled.iter_mut().zip(token) {
+            *sum += value;
+        }
+    }
+    let norm = pooled.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if count > 0.0 && norm > 0.0 {
+        pooled.iter_mut().for_each(|x| *x /= norm);
+    }
+    pooled
+}
+
+#[async_trait]
+impl Embedder for LocalEmbedder {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(BATCH_SIZE) {
+            let embedder = self.clone();
+            let batch = batch.to_vec();
+            let embedded = tokio::task::spawn_blocking(move || embedder.embed_batch(&batch))
+                .await
+                .map_err(|e| format!("Embedding task failed: {}", e))?
+                .map_err(|e| format!("Failed to embed text: {}", e))?;
+            vectors.extend(embedded);
+        }
+        Ok(vectors)
+    }
+
+    fn model_id(&self) -> String {
+        format!("local:{}", LOCAL_EMBEDDING_MODEL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mean_pool_skips_padding() {
+        let tokens = [1.0, 0.0, 3.0, 0.0, 100.0, 100.0];
+        let pooled = mean_pool(&tokens, &[1, 1, 0], 2);
+        assert_eq!(pooled, vec![1.0, 0.0]);
+        assert_eq!(mean_pool(&tokens, &[0, 0, 0], 2), vec![0.0, 0.0]);
+    }
+}
@@ -0,0 +1,23 @@
+/// Q&A module - chat with your meetings
+///
+/// Transcript segments are embedded and the vectors stored in the database. A question is
+/// embedded the same way, the closest segments of one meeting (or of all of them) are
+/// retrieved, and the summary model answers from those segments, citing the ones it used.
+///
+/// New meetings are indexed in the background once the on-device embedding model has been
+/// downloaded, so semantic search covers the whole archive.
+///
+/// This module contains:
+/// - Embedding clients and vector helpers (`embeddings`)
+/// - On-device embedding model run with ONNX Runtime (`local_embedder`)
+/// - Indexing of segments and retrieval (`index`)
+/// - In-memory vector index over all meetings (`vector_index`)
+/// - Answering with citations (`answer`)
+/// - Tauri commands for frontend integration
+
+pub mod answer;
+pub mod commands;
+pub mod embeddings;
+pub mod index;
+pub mod local_embedder;
+pub mod vector_index;
@@ -0,0 +1,153 @@
+// qa/vector_index.rs
+//
+// In-memory vector index over the stored segment embeddings.
+//
+// The vectors of an embedding model are loaded from the database the first time that model
+// is searched, normalized once, and kept in memory; segments embedded afterwards are added
+// as they are saved. A search is then a dot product per segment, which stays well under a
+// frame for archives of hundreds of meetings and needs no extra native dependency.
+
+use crate::database::repositories::embedding::EmbeddingsRepository;
+use crate::qa::embeddings::decode_vector;
+use once_cell::sync::Lazy;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tracing::info;
+
+/// Segments scoring below this are too unrelated to help answer anything
+const MIN_SIMILARITY: f32 = 0.2;
+
+#[derive(Debug, Clone)]
+struct Entry {
+    meeting_id: String,
+    /// Unit length, so the dot product is the cosine similarity
+    vector: Vec<f32>,
+}
+
+/// A segment close to the query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit {
+    pub transcript_id: String,
+    pub meeting_id: String,
+    pub score: f32,
+}
+
+/// Entries by transcript id, by embedding model
+static INDEX: Lazy<RwLock<HashMap<String, HashMap<String, Entry>>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// `vector` scaled to unit length; None for the zero vector
+fn normalize(vector: &[f32]) -> Option<Vec<f32>> {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    (norm > 0.0).then(|| vector.iter().map(|x| x / norm).collect())
+}
+
+/// Loads the vectors of `model` from the database unless they are in memory already
+pub async fn ensure_loaded(pool: &SqlitePool, model: &str) -> Result<(), String> {
+    if INDEX.read().unwrap().contains_key(model) {
+        return Ok(());
+    }
+    let stored = EmbeddingsRepository::list_vectors(pool, model)
+        .await
+        .map_err(|e| format!("Failed to load embeddings: {}", e))?;
+    let entries: HashMap<String, Entry> = stored
+        .into_iter()
+        .filter_map(|row| {
+            let vector = normalize(&decode_vector(&row.vector))?;
+            Some((row.transcript_id, Entry { meeting_id: row.meeting_id, vector }))
+        })
+        .collect();
+    info!("🧬 Loaded {} vector(s) of {} into the search index", entries.len(), model);
+    // Another search may have loaded the model meanwhile; keep what it (and later saves) added
+    INDEX.write().unwrap().entry(model.to_string()).or_insert(entries);
+    Ok(())
+}
+
+/// Adds or replaces segment vectors of a meeting
+///
+/// Models not loaded yet are skipped; they read the new vectors from the database when
+/// they are first searched.
+pub fn upsert(model: &str, meeting_id: &str, vectors: &[(String, Vec<f32>)]) {
+    let mut index = INDEX.write().unwrap();
+    let Some(entries) = index.get_mut(model) else {
+        return;
+    };
+    for (transcript_id, vector) in vectors {
+        if let Some(vector) = normalize(vector) {
+            entries.insert(transcript_id.clone(), Entry { meeting_id: meeting_id.to_string(), vector });
+        }
+    }
+}
+
+/// Forgets a deleted meeting's vectors for every model
+pub fn remove_meeting(meeting_id: &str) {
+    for entries in INDEX.write().unwrap().values_mut() {
+        entries.retain(|_, entry| entry.meeting_id != meeting_id);
+    }
+}
+
+/// The `k` segments most similar to `query`, best first, in one meeting or all of them
+pub fn search(model: &str, query: &[f32], meeting_id: Option<&str>, k: usize) -> Vec<Hit> {
+    let Some(query) = normalize(query) else {
+        return Vec::new();
+    };
+    let index = INDEX.read().unwrap();
+    let Some(entries) = index.get(model) else {
+        return Vec::new();
+    };
+    top_k(&query, entries, meeting_id, k)
+}
+
+fn top_k(query: &[f32], entries: &HashMap<String, Entry>, meeting_id: Option<&str>, k: usize) -> Vec<Hit> {
+    let mut hits: Vec<Hit> = entries
+        .iter()
+        .filter(|(_, entry)| meeting_id.map_or(true, |id| entry.meeting_id == id))
+        .filter(|(_, entry)| entry.vector.len() == query.len())
+        .filter_map(|(transcript_id, entry)| {
+            let score: f32 = entry.vector.iter().zip(query).map(|(a, b)| a * b).sum();
+            (score >= MIN_SIMILARITY).then(|| Hit {
+                transcript_id: transcript_id.clone(),
+                meeting_id: entry.meeting_id.clone(),
+                score,
+            })
+        })
+        .collect();
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    hits.truncate(k);
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(items: &[(&str, &str, &[f32])]) -> HashMap<String, Entry> {
+        items
+            .iter()
+            .map(|(id, meeting_id, vector)| {
+                (id.to_string(), Entry { meeting_id: meeting_id.to_string(), vector: normalize(vector).unwrap() })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_top_k_ranks_and_drops_unrelated() {
+        let entries = entries(&[
+            ("far", "m1", &[0.0, 1.0]),
+            ("close", "m1", &[0.9, 0.1]),
+            ("exact", "m2", &[2.0, 0.0]),
+            ("near", "m1", &[0.7, 0.7]),
+        ]);
+        let ids = |hits: Vec<Hit>| hits.into_iter().map(|hit| hit.transcript_id).collect::<Vec<_>>();
+
+        assert_eq!(ids(top_k(&[1.0, 0.0], &entries, None, 2)), vec!["exact", "close"]);
+        assert_eq!(ids(top_k(&[1.0, 0.0], &entries, Some("m1"), 5)), vec!["close", "near"]);
+        assert!(top_k(&[1.0, 0.0, 0.0], &entries, None, 5).is_empty());
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(&[3.0, 4.0]), Some(vec![0.6, 0.8]));
+        assert_eq!(normalize(&[0.0, 0.0]), None);
+    }
+}
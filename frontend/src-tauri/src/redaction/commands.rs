@@ -0,0 +1,85 @@
+// redaction/commands.rs
+//
+// Tauri commands for the redaction settings, the name detection model and the audit.
+
+use super::{ner, redact, save_settings, RedactionCounts, RedactionSettings};
+use crate::database::models::RedactionAuditEntry;
+use crate::database::repositories::redaction_audit::RedactionAuditRepository;
+use crate::diarization::commands::models_dir;
+use crate::state::AppState;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+use tracing::info;
+
+pub const NER_MODEL_DOWNLOAD_PROGRESS_EVENT: &str = "ner-model-download-progress";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NerModelStatus {
+    pub model: String,
+    pub downloaded: bool,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RedactionPreview {
+    pub text: String,
+    pub counts: RedactionCounts,
+}
+
+#[tauri::command]
+pub async fn get_redaction_settings() -> Result<RedactionSettings, String> {
+    Ok(super::get_settings())
+}
+
+#[tauri::command]
+pub async fn set_redaction_settings<R: Runtime>(
+    app: AppHandle<R>,
+    settings: RedactionSettings,
+) -> Result<RedactionSettings, String> {
+    if settings.names && !ner::is_downloaded(&models_dir(&app)?) {
+        return Err("Download the name detection model before turning on name redaction".to_string());
+    }
+    save_settings(&app, &settings)?;
+    info!("Redaction settings updated: {:?}", settings);
+    Ok(settings)
+}
+
+#[tauri::command]
+pub async fn get_ner_model_status<R: Runtime>(app: AppHandle<R>) -> Result<NerModelStatus, String> {
+    let models_dir = models_dir(&app)?;
+    Ok(NerModelStatus {
+        model: ner::NER_MODEL.to_string(),
+        downloaded: ner::is_downloaded(&models_dir),
+        path: ner::model_dir(&models_dir).to_string_lossy().to_string(),
+    })
+}
+
+#[tauri::command]
+pub async fn download_ner_model<R: Runtime>(app: AppHandle<R>) -> Result<NerModelStatus, String> {
+    let models_dir = models_dir(&app)?;
+    let progress_app = app.clone();
+    let path = ner::download_model(&models_dir, move |progress| {
+        let _ = progress_app.emit(NER_MODEL_DOWNLOAD_PROGRESS_EVENT, serde_json::json!({ "progress": progress }));
+    })
+    .await
+    .map_err(|e| format!("Failed to download name detection model: {}", e))?;
+    Ok(NerModelStatus { model: ner::NER_MODEL.to_string(), downloaded: true, path: path.to_string_lossy().to_string() })
+}
+
+/// Redacts `text` with the given settings, for trying them out before they're saved
+#[tauri::command]
+pub async fn preview_redaction(text: String, settings: RedactionSettings) -> Result<RedactionPreview, String> {
+    let (mut texts, counts) = redact(vec![text], settings).await?;
+    Ok(RedactionPreview { text: texts.pop().unwrap_or_default(), counts })
+}
+
+/// What each redaction pass over the meeting masked, newest first
+#[tauri::command]
+pub async fn list_redaction_audit(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<RedactionAuditEntry>, String> {
+    RedactionAuditRepository::list_for_meeting(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load redaction audit: {}", e))
+}
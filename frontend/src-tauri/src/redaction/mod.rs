@@ -0,0 +1,243 @@
+/// Redaction module - masking personal data and profanity in meeting text
+///
+/// Off by default. Once enabled, a meeting's transcript is redacted before it's sent to a
+/// summary model, and its summary, action items and transcript before they're exported or
+/// shared through an integration. Email addresses, phone numbers and payment card numbers are
+/// found with regular expressions; people's names with a named-entity recognition model that
+/// runs on the device; profanity with a word list. What's stored is never changed, and each
+/// pass records how much of each kind it masked, but not what, in the redaction audit.
+///
+/// Full-fidelity meeting archives (`.meetily` files) aren't redacted, since they exist to
+/// move a meeting to another installation unchanged.
+///
+/// This module contains:
+/// - Regular expressions and word lists (`patterns`)
+/// - Name detection with the NER model (`ner`)
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+pub mod ner;
+pub mod patterns;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::database::repositories::redaction_audit::RedactionAuditRepository;
+use ner::NerModel;
+use patterns::Span;
+
+const STORE_FILE: &str = "redaction.json";
+const STORE_KEY: &str = "redaction_settings";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionSettings {
+    pub enabled: bool,
+    pub emails: bool,
+    pub phone_numbers: bool,
+    pub card_numbers: bool,
+    /// People's names; needs the NER model
+    pub names: bool,
+    pub profanity: bool,
+}
+
+impl Default for RedactionSettings {
+    fn default() -> Self {
+        Self { enabled: false, emails: true, phone_numbers: true, card_numbers: true, names: false, profanity: false }
+    }
+}
+
+/// A kind of item the redaction pass masks; where matches overlap, the earlier kind wins
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Email,
+    CardNumber,
+    Phone,
+    Name,
+    Profanity,
+}
+
+impl Category {
+    pub fn id(self) -> &'static str {
+        match self {
+            Category::Email => "email",
+            Category::CardNumber => "card_number",
+            Category::Phone => "phone",
+            Category::Name => "name",
+            Category::Profanity => "profanity",
+        }
+    }
+
+    /// What a masked item is replaced with; profanity keeps its first letter so the sentence
+    /// still reads
+    pub fn replacement(self, original: &str) -> String {
+        match self {
+            Category::Email => "[email]".to_string(),
+            Category::CardNumber => "[card number]".to_string(),
+            Category::Phone => "[phone]".to_string(),
+            Category::Name => "[name]".to_string(),
+            Category::Profanity => {
+                let mut chars = original.chars();
+                let first = chars.next().map(String::from).unwrap_or_default();
+                first + &"*".repeat(chars.count())
+            }
+        }
+    }
+}
+
+/// How many items of each category were masked
+pub type RedactionCounts = BTreeMap<Category, usize>;
+
+/// Where redacted text is going, as recorded in the audit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionTarget {
+    Summary,
+    Export,
+}
+
+impl RedactionTarget {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RedactionTarget::Summary => "summary",
+            RedactionTarget::Export => "export",
+        }
+    }
+}
+
+static SETTINGS: Lazy<RwLock<RedactionSettings>> = Lazy::new(|| RwLock::new(RedactionSettings::default()));
+static MODELS_DIR: Lazy<RwLock<Option<PathBuf>>> = Lazy::new(|| RwLock::new(None));
+
+/// Loads the stored settings; called once at startup
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    *MODELS_DIR.write().unwrap() = crate::diarization::commands::models_dir(app).ok();
+    *SETTINGS.write().unwrap() = load_settings(app);
+}
+
+pub fn get_settings() -> RedactionSettings {
+    *SETTINGS.read().unwrap()
+}
+
+fn load_settings<R: Runtime>(app: &AppHandle<R>) -> RedactionSettings {
+    let stored = match app.store(STORE_FILE) {
+        Ok(store) => store.get(STORE_KEY),
+        Err(e) => {
+            warn!("Failed to open {}: {}", STORE_FILE, e);
+            None
+        }
+    };
+    stored.and_then(|value| serde_json::from_value(value).ok()).unwrap_or_default()
+}
+
+fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: &RedactionSettings) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    let value = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+    store.set(STORE_KEY, value);
+    store.save().map_err(|e| format!("Failed to save {}: {}", STORE_FILE, e))?;
+    *SETTINGS.write().unwrap() = *settings;
+    Ok(())
+}
+
+/// Replaces speaker names with `Speaker 1`, `Speaker 2`... in order of first appearance, for
+/// when names are redacted
+pub fn number_speakers<'a>(speakers: impl IntoIterator<Item = &'a mut String>) {
+    let mut seen: Vec<String> = Vec::new();
+    for speaker in speakers {
+        let number = match seen.iter().position(|known| known == speaker) {
+            Some(index) => index + 1,
+            None => {
+                seen.push(speaker.clone());
+                seen.len()
+            }
+        };
+        *speaker = format!("Speaker {}", number);
+    }
+}
+
+/// The NER model, when `settings` ask for names
+fn name_model(settings: &RedactionSettings) -> Result<Option<NerModel>, String> {
+    if !settings.names {
+        return Ok(None);
+    }
+    let models_dir = MODELS_DIR.read().unwrap().clone().ok_or("The models directory is unavailable")?;
+    if !ner::is_downloaded(&models_dir) {
+        return Err("Name redaction is on but its model isn't downloaded".to_string());
+    }
+    NerModel::shared(&models_dir)
+        .map(Some)
+        .map_err(|e| format!("Failed to load the name detection model: {}", e))
+}
+
+/// Masks what `settings` ask for in each text, whether or not redaction is enabled
+pub async fn redact(texts: Vec<String>, settings: RedactionSettings) -> Result<(Vec<String>, RedactionCounts), String> {
+    let names = name_model(&settings)?;
+    tokio::task::spawn_blocking(move || -> Result<(Vec<String>, RedactionCounts), String> {
+        let name_spans = match &names {
+            Some(model) => model.find_names(&texts).map_err(|e| format!("Failed to detect names: {}", e))?,
+            None => vec![Vec::new(); texts.len()],
+        };
+        let mut counts = RedactionCounts::new();
+        let redacted = texts
+            .iter()
+            .zip(name_spans)
+            .map(|(text, names)| {
+                let mut spans = patterns::find(text, &settings);
+                spans.extend(names.into_iter().map(|(start, end)| Span { start, end, category: Category::Name }));
+                patterns::apply(text, spans, &mut counts)
+            })
+            .collect();
+        Ok((redacted, counts))
+    })
+    .await
+    .map_err(|e| format!("Redaction task failed: {}", e))?
+}
+
+/// Redacts a meeting's texts on their way to `target` and records the pass in the audit;
+/// returns them unchanged when redaction is off. Fails rather than let text through
+/// unredacted.
+pub async fn redact_for_meeting(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    target: RedactionTarget,
+    texts: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let settings = get_settings();
+    if !settings.enabled {
+        return Ok(texts);
+    }
+    let (texts, counts) = redact(texts, settings).await?;
+    if !counts.is_empty() {
+        let rows: Vec<(&str, usize)> = counts.iter().map(|(category, count)| (category.id(), *count)).collect();
+        info!("🕶️ Redacted {:?} from meeting {} for {}", rows, meeting_id, target.as_str());
+        if let Err(e) = RedactionAuditRepository::record(pool, meeting_id, target.as_str(), &rows).await {
+            warn!("Failed to record redaction audit: {}", e);
+        }
+    }
+    Ok(texts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_speakers() {
+        let mut speakers = vec!["Alice".to_string(), "Bob".to_string(), "Alice".to_string(), "Speaker 1".to_string()];
+        number_speakers(speakers.iter_mut());
+        assert_eq!(speakers, ["Speaker 1", "Speaker 2", "Speaker 1", "Speaker 3"]);
+    }
+
+    #[test]
+    fn test_profanity_replacement_keeps_length() {
+        assert_eq!(Category::Profanity.replacement("Shitty"), "S*****");
+        assert_eq!(Category::Email.replacement("a@b.io"), "[email]");
+    }
+}
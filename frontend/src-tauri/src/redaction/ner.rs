@@ -0,0 +1,281 @@
+// redaction/ner.rs
+//
+// Finds people's names with bert-base-NER, a named-entity recognition model run through ONNX
+// Runtime, since no pattern can. The quantized model (~110 MB), its tokenizer and its label
+// set are downloaded on request into the app's models directory.
+
+use crate::qa::local_embedder::download_file;
+use anyhow::{anyhow, Result};
+use ndarray::Array2;
+use once_cell::sync::Lazy;
+use ort::execution_providers::CPUExecutionProvider;
+use ort::inputs;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::TensorRef;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokenizers::{Encoding, PaddingParams, Tokenizer, TruncationParams};
+
+pub const NER_MODEL: &str = "bert-base-NER";
+const MODEL_FILE: &str = "model_quantized.onnx";
+const TOKENIZER_FILE: &str = "tokenizer.json";
+const CONFIG_FILE: &str = "config.json";
+const MODEL_BASE_URL: &str = "https://huggingface.co/Xenova/bert-base-NER/resolve/main";
+
+const MAX_SEQUENCE_TOKENS: usize = 512;
+/// Texts are tagged in pieces of at most this many bytes, which stay under the token limit
+const CHUNK_BYTES: usize = 1200;
+/// Pieces run through the model at once
+const BATCH_SIZE: usize = 8;
+
+/// The loaded model, shared by every redaction pass
+static LOADED: Lazy<Mutex<Option<NerModel>>> = Lazy::new(|| Mutex::new(None));
+
+/// Where the model lives under the app's models directory
+pub fn model_dir(models_dir: &Path) -> PathBuf {
+    models_dir.join("ner").join(NER_MODEL)
+}
+
+pub fn is_downloaded(models_dir: &Path) -> bool {
+    let dir = model_dir(models_dir);
+    [MODEL_FILE, TOKENIZER_FILE, CONFIG_FILE].iter().all(|file| dir.join(file).exists())
+}
+
+/// Download the model, its tokenizer and config, reporting progress of the model file as a
+/// percentage
+pub async fn download_model(models_dir: &Path, progress: impl Fn(u8)) -> Result<PathBuf> {
+    let dir = model_dir(models_dir);
+    tokio::fs::create_dir_all(&dir).await?;
+    download_file(&format!("{}/{}", MODEL_BASE_URL, CONFIG_FILE), &dir.join(CONFIG_FILE), |_| {}).await?;
+    download_file(&format!("{}/{}", MODEL_BASE_URL, TOKENIZER_FILE), &dir.join(TOKENIZER_FILE), |_| {}).await?;
+    download_file(&format!("{}/onnx/{}", MODEL_BASE_URL, MODEL_FILE), &dir.join(MODEL_FILE), progress).await?;
+    log::info!("Name detection model saved to {}", dir.display());
+    Ok(dir)
+}
+
+/// Token classification model; cheap to clone
+#[derive(Clone)]
+pub struct NerModel {
+    session: Arc<Mutex<Session>>,
+    tokenizer: Arc<Tokenizer>,
+    /// Tag names by class index, e.g. `B-PER`
+    labels: Arc<Vec<String>>,
+}
+
+impl NerModel {
+    pub fn load(dir: &Path) -> Result<Self> {
+        let (model_path, tokenizer_path, config_path) =
+            (dir.join(MODEL_FILE), dir.join(TOKENIZER_FILE), dir.join(CONFIG_FILE));
+        if !model_path.exists() || !tokenizer_path.exists() || !config_path.exists() {
+            return Err(anyhow!("Name detection model not found. Download it first."));
+        }
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path).map_err(|e| anyhow!("Failed to load tokenizer: {}", e))?;
+        tokenizer
+            .with_truncation(Some(TruncationParams { max_length: MAX_SEQUENCE_TOKENS, ..Default::default() }))
+            .map_err(|e| anyhow!("Failed to configure tokenizer: {}", e))?;
+        tokenizer.with_padding(Some(PaddingParams::default()));
+        let labels = load_labels(&config_path)?;
+
+        let threads = std::thread::available_parallelism().map(|n| n.get().min(4)).unwrap_or(2);
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_execution_providers(vec![CPUExecutionProvider::default().build()])?
+            .with_intra_threads(threads)?
+            .commit_from_file(&model_path)?;
+        Ok(Self { session: Arc::new(Mutex::new(session)), tokenizer: Arc::new(tokenizer), labels: Arc::new(labels) })
+    }
+
+    /// The model loaded once and kept for the lifetime of the app
+    pub fn shared(models_dir: &Path) -> Result<Self> {
+        let mut loaded = LOADED.lock().unwrap();
+        if let Some(model) = loaded.as_ref() {
+            return Ok(model.clone());
+        }
+        let model = Self::load(&model_dir(models_dir))?;
+        *loaded = Some(model.clone());
+        Ok(model)
+    }
+
+    /// Byte ranges of people's names in each text
+    pub fn find_names(&self, texts: &[String]) -> Result<Vec<Vec<(usize, usize)>>> {
+        let chunks: Vec<(usize, usize, &str)> = texts
+            .iter()
+            .enumerate()
+            .flat_map(|(index, text)| {
+                split_chunks(text, CHUNK_BYTES).into_iter().map(move |(offset, chunk)| (index, offset, chunk))
+            })
+            .filter(|(_, _, chunk)| !chunk.trim().is_empty())
+            .collect();
+
+        let mut names = vec![Vec::new(); texts.len()];
+        for batch in chunks.chunks(BATCH_SIZE) {
+            let found = self.tag_batch(&batch.iter().map(|(_, _, chunk)| *chunk).collect::<Vec<_>>())?;
+            for (&(index, offset, _), spans) in batch.iter().zip(found) {
+                names[index].extend(spans.into_iter().map(|(start, end)| (start + offset, end + offset)));
+            }
+        }
+        Ok(names)
+    }
+
+    /// Byte ranges of people's names in each of a batch of short texts
+    fn tag_batch(&self, chunks: &[&str]) -> Result<Vec<Vec<(usize, usize)>>> {
+        let encodings = self
+            .tokenizer
+            .encode_batch(chunks.to_vec(), true)
+            .map_err(|e| anyhow!("Failed to tokenize: {}", e))?;
+        let (batch, length) = (encodings.len(), encodings.first().map_or(0, |e| e.get_ids().len()));
+        if length == 0 {
+            return Ok(vec![Vec::new(); batch]);
+        }
+
+        let column = |values: fn(&Encoding) -> &[u32]| -> Result<Array2<i64>> {
+            let flat = encodings.iter().flat_map(|e| values(e).iter().map(|&v| v as i64)).collect();
+            Ok(Array2::from_shape_vec((batch, length), flat)?)
+        };
+        let input_ids = column(Encoding::get_ids)?;
+        let attention_mask = column(Encoding::get_attention_mask)?;
+        let token_type_ids = column(Encoding::get_type_ids)?;
+
+        let mut session = self.session.lock().unwrap();
+        let outputs = session.run(inputs![
+            "input_ids" => TensorRef::from_array_view(input_ids.view())?,
+            "attention_mask" => TensorRef::from_array_view(attention_mask.view())?,
+            "token_type_ids" => TensorRef::from_array_view(token_type_ids.view())?
+        ])?;
+        // Class scores: [batch, length, classes]
+        let logits = outputs[0].try_extract_array::<f32>()?;
+        let classes = logits.shape().get(2).copied().ok_or_else(|| anyhow!("Unexpected model output shape"))?;
+        let values: Vec<f32> = logits.iter().copied().collect();
+
+        Ok(encodings
+            .iter()
+            .zip(chunks)
+            .enumerate()
+            .map(|(i, (encoding, chunk))| {
+                let tags: Vec<&str> = values[i * length * classes..(i + 1) * length * classes]
+                    .chunks_exact(classes)
+                    .map(|scores| self.labels.get(argmax(scores)).map_or("O", String::as_str))
+                    .collect();
+                person_spans(chunk, &tags, encoding.get_offsets())
+            })
+            .collect())
+    }
+}
+
+/// The tag names from the model's `config.json`, by class index
+fn load_labels(config_path: &Path) -> Result<Vec<String>> {
+    let config: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(config_path)?)?;
+    let id2label = config["id2label"].as_object().ok_or_else(|| anyhow!("The model config has no labels"))?;
+    let mut labels = vec![String::new(); id2label.len()];
+    for (id, label) in id2label {
+        let slot = id.parse::<usize>().ok().and_then(|id| labels.get_mut(id));
+        let slot = slot.ok_or_else(|| anyhow!("Unexpected label id in the model config: {}", id))?;
+        *slot = label.as_str().unwrap_or_default().to_string();
+    }
+    Ok(labels)
+}
+
+fn argmax(scores: &[f32]) -> usize {
+    scores
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map_or(0, |(index, _)| index)
+}
+
+/// Pieces of `text` of at most `max_len` bytes, broken at whitespace, with their offsets
+fn split_chunks(text: &str, max_len: usize) -> Vec<(usize, &str)> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while text.len() - start > max_len {
+        let mut end = start + max_len;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        // A single word longer than a piece is cut wherever the piece ends
+        if let Some(space) = text[start..end].rfind(char::is_whitespace).filter(|&space| space > 0) {
+            end = start + space;
+        }
+        chunks.push((start, &text[start..end]));
+        start = end;
+    }
+    chunks.push((start, &text[start..]));
+    chunks
+}
+
+/// Merges the tokens tagged as part of a person's name into byte ranges of `text`. Ranges are
+/// widened to whole words, since the model may tag only some pieces of a word.
+fn person_spans(text: &str, tags: &[&str], offsets: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    let mut in_name = false;
+    for (tag, &(start, end)) in tags.iter().zip(offsets) {
+        // Special and padding tokens
+        if start == end {
+            continue;
+        }
+        if !tag.ends_with("-PER") {
+            in_name = false;
+            continue;
+        }
+        let (start, end) = widen_to_words(text, start, end);
+        match spans.last_mut() {
+            Some(last) if start <= last.1 || (in_name && text[last.1..start].trim().is_empty()) => {
+                last.1 = last.1.max(end);
+            }
+            _ => spans.push((start, end)),
+        }
+        in_name = true;
+    }
+    spans
+}
+
+fn widen_to_words(text: &str, mut start: usize, mut end: usize) -> (usize, usize) {
+    while let Some(c) = text[..start].chars().next_back().filter(|c| c.is_alphanumeric()) {
+        start -= c.len_utf8();
+    }
+    while let Some(c) = text[end..].chars().next().filter(|c| c.is_alphanumeric()) {
+        end += c.len_utf8();
+    }
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_person_spans_merge_and_widen() {
+        let text = "Thanks Alice Smith, and Joanna's team.";
+        // [CLS] Thanks Alice Smith , and Jo ##anna ' s team . [SEP]
+        let offsets = [
+            (0, 0), (0, 6), (7, 12), (13, 18), (18, 19), (20, 23), (24, 26), (26, 30), (30, 31), (31, 32), (33, 37), (37, 38), (0, 0),
+        ];
+        let tags = ["O", "O", "B-PER", "I-PER", "O", "O", "B-PER", "O", "O", "O", "O", "O", "O"];
+        let spans = person_spans(text, &tags, &offsets);
+        assert_eq!(spans, vec![(7, 18), (24, 30)]);
+        assert_eq!(&text[spans[0].0..spans[0].1], "Alice Smith");
+        assert_eq!(&text[spans[1].0..spans[1].1], "Joanna");
+    }
+
+    #[test]
+    fn test_person_spans_keep_separate_names_apart() {
+        let text = "Bob and Carol";
+        let offsets = [(0, 3), (4, 7), (8, 13)];
+        assert_eq!(person_spans(text, &["B-PER", "O", "B-PER"], &offsets), vec![(0, 3), (8, 13)]);
+    }
+
+    #[test]
+    fn test_split_chunks() {
+        let text = "one two three four five";
+        let chunks = split_chunks(text, 10);
+        assert!(chunks.iter().all(|(_, chunk)| chunk.len() <= 10));
+        assert_eq!(chunks.iter().map(|(_, chunk)| *chunk).collect::<String>(), text);
+        for (offset, chunk) in chunks {
+            assert_eq!(&text[offset..offset + chunk.len()], chunk);
+        }
+        assert_eq!(split_chunks("short", 10), vec![(0, "short")]);
+        assert!(split_chunks("ééééé", 3).iter().all(|(_, chunk)| !chunk.is_empty()));
+    }
+}
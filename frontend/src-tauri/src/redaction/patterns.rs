@@ -0,0 +1,219 @@
+// redaction/patterns.rs
+//
+// The pattern half of the redaction pass: email addresses, phone numbers, payment card
+// numbers and profanity, and replacing whatever was found. Transcripts are speech, so numbers
+// come grouped with whatever spaces, dots or dashes the speech engine put between them.
+
+use super::{Category, RedactionCounts, RedactionSettings};
+use once_cell::sync::Lazy;
+use regex::{Match, Regex};
+use std::cmp::Reverse;
+
+static EMAIL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}\b").unwrap());
+
+/// 13 to 19 digits, optionally grouped by spaces or dashes
+static CARD_NUMBER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d(?:[ -]?\d){12,18}\b").unwrap());
+
+/// Groups of digits with an optional country code and area code in parentheses
+static PHONE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{1,4}\)[ .-]?)?\d{2,4}(?:[ .-]?\d{2,4}){1,4}").unwrap()
+});
+
+/// A local number without an area code, e.g. 555-0142
+static LOCAL_PHONE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\d{3}[ .-]\d{4}$").unwrap());
+
+/// ISO dates read like digit groups too
+static ISO_DATE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d{4}-\d{2}-\d{2}").unwrap());
+
+static PROFANITY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:\w*fuck\w*|bullshit\w*|shit(?:s|ty|ting|head|heads|hole|holes)?|bitch(?:es|y|ing)?|assholes?|bastards?|dickheads?|cunts?|goddamn(?:ed|it)?|wankers?|twats?)\b",
+    )
+    .unwrap()
+});
+
+/// Phone numbers have at least this many digits, except for local ones
+const MIN_PHONE_DIGITS: usize = 9;
+/// The longest international numbers (E.164)
+const MAX_PHONE_DIGITS: usize = 15;
+
+/// A byte range of a text to mask
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub category: Category,
+}
+
+impl Span {
+    fn of(found: Match, category: Category) -> Self {
+        Self { start: found.start(), end: found.end(), category }
+    }
+}
+
+/// Everything in `text` the settings ask for, except names; spans may overlap
+pub fn find(text: &str, settings: &RedactionSettings) -> Vec<Span> {
+    let mut spans = Vec::new();
+    if settings.emails {
+        spans.extend(EMAIL.find_iter(text).map(|found| Span::of(found, Category::Email)));
+    }
+    if settings.card_numbers {
+        spans.extend(
+            CARD_NUMBER
+                .find_iter(text)
+                .filter(|found| passes_luhn(found.as_str()))
+                .map(|found| Span::of(found, Category::CardNumber)),
+        );
+    }
+    if settings.phone_numbers {
+        spans.extend(
+            PHONE
+                .find_iter(text)
+                .filter(|found| is_phone_number(text, found))
+                .map(|found| Span::of(found, Category::Phone)),
+        );
+    }
+    if settings.profanity {
+        spans.extend(PROFANITY.find_iter(text).map(|found| Span::of(found, Category::Profanity)));
+    }
+    spans
+}
+
+/// The Luhn checksum every payment card number satisfies
+fn passes_luhn(number: &str) -> bool {
+    let digits: Vec<u32> = number.chars().filter_map(|c| c.to_digit(10)).collect();
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &digit)| match (i % 2 == 1, digit * 2) {
+            (true, doubled) if doubled > 9 => doubled - 9,
+            (true, doubled) => doubled,
+            (false, _) => digit,
+        })
+        .sum();
+    !digits.is_empty() && sum % 10 == 0
+}
+
+fn is_phone_number(text: &str, found: &Match) -> bool {
+    // Part of a longer word or code, like an order number
+    let before = text[..found.start()].chars().next_back();
+    let after = text[found.end()..].chars().next();
+    if before.is_some_and(|c| c.is_alphanumeric() || c == '+') || after.is_some_and(char::is_alphanumeric) {
+        return false;
+    }
+    if ISO_DATE.is_match(found.as_str()) {
+        return false;
+    }
+    let digits = found.as_str().chars().filter(char::is_ascii_digit).count();
+    (MIN_PHONE_DIGITS..=MAX_PHONE_DIGITS).contains(&digits) || LOCAL_PHONE.is_match(found.as_str())
+}
+
+/// Replaces the spans in `text`, counting each by category. Of overlapping spans, the one
+/// that starts first wins; from the same place, the longer one.
+pub fn apply(text: &str, mut spans: Vec<Span>, counts: &mut RedactionCounts) -> String {
+    spans.sort_by_key(|span| (span.start, Reverse(span.end), span.category));
+    let mut redacted = String::with_capacity(text.len());
+    let mut cursor = 0;
+    for span in spans {
+        if span.start < cursor || span.start >= span.end {
+            continue;
+        }
+        redacted.push_str(&text[cursor..span.start]);
+        redacted.push_str(&span.category.replacement(&text[span.start..span.end]));
+        *counts.entry(span.category).or_default() += 1;
+        cursor = span.end;
+    }
+    redacted.push_str(&text[cursor..]);
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all() -> RedactionSettings {
+        RedactionSettings {
+            enabled: true,
+            emails: true,
+            phone_numbers: true,
+            card_numbers: true,
+            names: false,
+            profanity: true,
+        }
+    }
+
+    fn redact(text: &str) -> (String, RedactionCounts) {
+        let mut counts = RedactionCounts::new();
+        let redacted = apply(text, find(text, &all()), &mut counts);
+        (redacted, counts)
+    }
+
+    #[test]
+    fn test_emails() {
+        let (redacted, counts) = redact("Send it to jane.doe+notes@mail.example.co.uk, thanks");
+        assert_eq!(redacted, "Send it to [email], thanks");
+        assert_eq!(counts[&Category::Email], 1);
+        assert_eq!(redact("Meet @ noon in room 4.2").0, "Meet @ noon in room 4.2");
+    }
+
+    #[test]
+    fn test_card_numbers_need_a_valid_checksum() {
+        assert_eq!(redact("Card 4111 1111 1111 1111 expires soon").0, "Card [card number] expires soon");
+        assert_eq!(redact("Card 4111-1111-1111-1111.").0, "Card [card number].");
+        let text = "Ticket 4111 1111 1111 1112 is open";
+        assert_eq!(redact(text).0, text);
+    }
+
+    #[test]
+    fn test_phone_numbers() {
+        assert_eq!(redact("Call +1 (555) 123-4567 today").0, "Call [phone] today");
+        assert_eq!(redact("Call 555 123 4567.").0, "Call [phone].");
+        assert_eq!(redact("Extension 555-0142 works").0, "Extension [phone] works");
+        assert_eq!(redact("Call +44 20 7946 0958").0, "Call [phone]");
+    }
+
+    #[test]
+    fn test_numbers_that_arent_phone_numbers() {
+        for text in [
+            "We shipped 2025-11-03 10 items",
+            "Revenue was 1.250.000 last year",
+            "Order AB123456789 is late",
+            "Meet at 10:30 in room 1204",
+            "Version 12.3.4",
+        ] {
+            assert_eq!(redact(text).0, text);
+        }
+    }
+
+    #[test]
+    fn test_profanity_keeps_first_letter() {
+        let (redacted, counts) = redact("That was bullshit, total fucking mess. Shitake? No: shiitake");
+        assert_eq!(redacted, "That was b*******, total f****** mess. Shitake? No: shiitake");
+        assert_eq!(counts[&Category::Profanity], 2);
+        assert_eq!(redact("Classic assessment").0, "Classic assessment");
+    }
+
+    #[test]
+    fn test_disabled_categories_are_left_alone() {
+        let settings = RedactionSettings { profanity: false, emails: false, ..all() };
+        let text = "Damn, shit, write to a@b.io";
+        assert!(find(text, &settings).is_empty());
+    }
+
+    #[test]
+    fn test_overlapping_spans() {
+        let text = "Reach Jane Doe at jane@example.com";
+        let spans = vec![
+            Span { start: 6, end: 14, category: Category::Name },
+            Span { start: 11, end: 14, category: Category::Name },
+            Span { start: 18, end: 34, category: Category::Email },
+            Span { start: 18, end: 22, category: Category::Name },
+        ];
+        let mut counts = RedactionCounts::new();
+        assert_eq!(apply(text, spans, &mut counts), "Reach [name] at [email]");
+        assert_eq!(counts[&Category::Name], 1);
+        assert_eq!(counts[&Category::Email], 1);
+    }
+}
@@ -0,0 +1,73 @@
+// search/commands.rs
+//
+// Tauri commands for full-text search over the meeting archive.
+
+use crate::database::models::{SummarySearchHit, TranscriptSearchHit};
+use crate::database::repositories::search::{SearchFilters, SearchRepository};
+use crate::search::query::{match_expression, parse_date_bound};
+use crate::state::AppState;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tracing::info;
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SearchRequest {
+    /// Words, "exact phrases", prefix* terms, OR and -exclusions
+    pub query: String,
+    pub meeting_id: Option<String>,
+    /// Only segments spoken by this speaker; summaries are skipped
+    pub speaker: Option<String>,
+    /// `YYYY-MM-DD` or RFC 3339; meetings created on or after
+    pub from: Option<String>,
+    /// `YYYY-MM-DD` (inclusive) or RFC 3339; meetings created before
+    pub to: Option<String>,
+    /// Also search summaries; defaults to true
+    pub include_summaries: Option<bool>,
+    /// Hits per kind
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SearchResults {
+    /// Segments with the recording timestamps to jump to
+    pub transcripts: Vec<TranscriptSearchHit>,
+    pub summaries: Vec<SummarySearchHit>,
+}
+
+/// Full-text search over transcripts and summaries, most relevant first
+#[tauri::command]
+pub async fn search_meetings(
+    state: tauri::State<'_, AppState>,
+    request: SearchRequest,
+) -> Result<SearchResults, String> {
+    search(state.db_manager.pool(), request).await
+}
+
+pub async fn search(pool: &SqlitePool, request: SearchRequest) -> Result<SearchResults, String> {
+    let Some(expression) = match_expression(&request.query) else {
+        return Ok(SearchResults::default());
+    };
+    let filters = SearchFilters {
+        meeting_id: request.meeting_id,
+        speaker: request.speaker.filter(|speaker| !speaker.trim().is_empty()),
+        from: request.from.as_deref().map(|from| parse_date_bound(from, false)).transpose()?,
+        to: request.to.as_deref().map(|to| parse_date_bound(to, true)).transpose()?,
+    };
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    info!("search_meetings called with {}", expression);
+    let transcripts = SearchRepository::search_transcripts(pool, &expression, &filters, limit)
+        .await
+        .map_err(|e| format!("Failed to search transcripts: {}", e))?;
+    let summaries = if request.include_summaries.unwrap_or(true) {
+        SearchRepository::search_summaries(pool, &expression, &filters, limit)
+            .await
+            .map_err(|e| format!("Failed to search summaries: {}", e))?
+    } else {
+        Vec::new()
+    };
+    Ok(SearchResults { transcripts, summaries })
+}
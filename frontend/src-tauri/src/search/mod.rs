@@ -0,0 +1,12 @@
+/// Search module - full-text search over the meeting archive
+///
+/// Transcripts and summaries are indexed with SQLite FTS5; triggers keep the indexes in
+/// sync as meetings are saved, edited and deleted. Transcript hits carry the segment's
+/// recording timestamps, so the frontend can jump straight to that moment in the audio.
+///
+/// This module contains:
+/// - Parsing of the query syntax into FTS5 expressions (`query`)
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+pub mod query;
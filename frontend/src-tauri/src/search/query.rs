@@ -0,0 +1,143 @@
+// search/query.rs
+//
+// Turns what the user typed into an FTS5 `MATCH` expression.
+//
+// Supported syntax:
+// - `pricing plan`: both words, anywhere in the text
+// - `"annual plan"`: the exact phrase
+// - `price*`: words starting with "price"
+// - `pricing OR budget`: either word
+// - `-cancelled`: leave out text containing the word
+//
+// Every word and phrase is quoted before it reaches FTS5, so punctuation and FTS5 keywords
+// in a query never cause a syntax error.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Term { text: String, prefix: bool, negated: bool },
+    Or,
+}
+
+fn tokenize(query: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let negated = c == '-';
+        if negated {
+            chars.next();
+        }
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            let text: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            tokens.push(Token::Term { text, prefix: false, negated });
+            continue;
+        }
+        let mut word = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() || c == '"' {
+                break;
+            }
+            word.push(c);
+            chars.next();
+        }
+        if word == "OR" && !negated {
+            tokens.push(Token::Or);
+        } else {
+            let prefix = word.ends_with('*');
+            let text = word.trim_end_matches('*').to_string();
+            tokens.push(Token::Term { text, prefix, negated });
+        }
+    }
+    tokens
+}
+
+fn quote(text: &str, prefix: bool) -> String {
+    let quoted = format!("\"{}\"", text.replace('"', "\"\""));
+    if prefix {
+        format!("{}*", quoted)
+    } else {
+        quoted
+    }
+}
+
+/// The FTS5 expression for a search query; None when nothing searchable is left
+///
+/// Queries made only of exclusions are not searchable, FTS5 needs something to match.
+pub fn match_expression(query: &str) -> Option<String> {
+    let mut positive: Vec<String> = Vec::new();
+    let mut negative: Vec<String> = Vec::new();
+    let mut pending_or = false;
+    for token in tokenize(query) {
+        match token {
+            Token::Or => pending_or = !positive.is_empty(),
+            Token::Term { text, .. } if text.trim().is_empty() => {}
+            Token::Term { text, prefix, negated: true } => negative.push(quote(text.trim(), prefix)),
+            Token::Term { text, prefix, negated: false } => {
+                if std::mem::take(&mut pending_or) {
+                    positive.push("OR".to_string());
+                }
+                positive.push(quote(text.trim(), prefix));
+            }
+        }
+    }
+    if positive.is_empty() {
+        return None;
+    }
+    let mut expression = format!("({})", positive.join(" "));
+    for term in negative {
+        expression.push_str(" NOT ");
+        expression.push_str(&term);
+    }
+    Some(expression)
+}
+
+/// Parses a date range bound: an RFC 3339 timestamp or a `YYYY-MM-DD` day
+///
+/// A day as the end of the range includes that whole day.
+pub fn parse_date_bound(value: &str, end: bool) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    let day = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| format!("Invalid date: {}", value))?;
+    let start = day
+        .and_hms_opt(0, 0, 0)
+        .map(|start| start.and_utc())
+        .ok_or_else(|| format!("Invalid date: {}", value))?;
+    Ok(if end { start + Duration::days(1) } else { start })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_expression() {
+        assert_eq!(match_expression("pricing plan").as_deref(), Some(r#"("pricing" "plan")"#));
+        assert_eq!(
+            match_expression(r#""annual plan" price* -cancelled"#).as_deref(),
+            Some(r#"("annual plan" "price"*) NOT "cancelled""#)
+        );
+        assert_eq!(match_expression("pricing OR budget").as_deref(), Some(r#"("pricing" OR "budget")"#));
+        assert_eq!(match_expression("OR NOT AND(").as_deref(), Some(r#"("NOT" "AND(")"#));
+        assert_eq!(match_expression("-cancelled"), None);
+        assert_eq!(match_expression("  \"\" "), None);
+    }
+
+    #[test]
+    fn test_date_bounds() {
+        assert_eq!(parse_date_bound("2025-10-01", false).unwrap().to_rfc3339(), "2025-10-01T00:00:00+00:00");
+        assert_eq!(parse_date_bound("2025-10-01", true).unwrap().to_rfc3339(), "2025-10-02T00:00:00+00:00");
+        assert_eq!(
+            parse_date_bound("2025-10-01T12:00:00+02:00", true).unwrap().to_rfc3339(),
+            "2025-10-01T10:00:00+00:00"
+        );
+        assert!(parse_date_bound("yesterday", false).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+// secrets/commands.rs
+//
+// Tauri commands for the credentials section of the settings. Secrets go in but never come
+// back out: every credential is returned masked.
+
+use log::info;
+
+use super::credential::{self, Credential, CredentialInfo};
+use super::verify;
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn list_credentials(state: tauri::State<'_, AppState>) -> Result<Vec<CredentialInfo>, String> {
+    let pool = state.db_manager.pool();
+    let mut credentials = Vec::new();
+    for credential in credential::all(pool).await? {
+        credentials.push(credential.info(pool).await);
+    }
+    Ok(credentials)
+}
+
+#[tauri::command]
+pub async fn get_credential(state: tauri::State<'_, AppState>, id: String) -> Result<CredentialInfo, String> {
+    Ok(Credential::parse(&id)?.info(state.db_manager.pool()).await)
+}
+
+/// Stores (or replaces) a credential in the system credential store
+#[tauri::command]
+pub async fn set_credential(
+    state: tauri::State<'_, AppState>,
+    id: String,
+    secret: String,
+) -> Result<CredentialInfo, String> {
+    let credential = Credential::parse(&id)?;
+    let secret = secret.trim();
+    if secret.is_empty() {
+        return Err(format!("{} cannot be empty", credential.label()));
+    }
+    let pool = state.db_manager.pool();
+    credential.store(pool, secret).await?;
+    Ok(credential.info(pool).await)
+}
+
+/// Checks `secret` with its service before it's saved, or the stored one without it
+#[tauri::command]
+pub async fn test_credential(
+    state: tauri::State<'_, AppState>,
+    id: String,
+    secret: Option<String>,
+) -> Result<String, String> {
+    let credential = Credential::parse(&id)?;
+    let secret = match secret.map(|secret| secret.trim().to_string()).filter(|secret| !secret.is_empty()) {
+        Some(secret) => secret,
+        None => credential
+            .load(state.db_manager.pool())
+            .await
+            .map(|(secret, _)| secret)
+            .ok_or_else(|| format!("No {} is saved", credential.label()))?,
+    };
+    verify::verify(&credential, &secret).await
+}
+
+#[tauri::command]
+pub async fn delete_credential(state: tauri::State<'_, AppState>, id: String) -> Result<(), String> {
+    let credential = Credential::parse(&id)?;
+    credential.delete(state.db_manager.pool()).await?;
+    info!("🔐 Deleted {}", credential.label());
+    Ok(())
+}
@@ -0,0 +1,221 @@
+// secrets/credential.rs
+//
+// The credentials the settings view manages, named by ids such as `summary:openai`,
+// `transcription:deepgram`, `slack:bot` or `notion:<connection id>`. Each one is kept by the
+// module that uses it; this maps an id to that module's storage.
+
+use log::warn;
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use crate::audio::transcription::cloud_providers::CLOUD_PROVIDER_IDS;
+use crate::audio::transcription::credentials as transcription_credentials;
+use crate::database::repositories::notion_connection::NotionConnectionsRepository;
+use crate::database::repositories::setting::SettingsRepository;
+use crate::integrations::{credentials as integration_credentials, slack};
+use crate::summary::credentials as summary_credentials;
+use crate::summary::llm_client::LLMProvider;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Credential {
+    /// API key of a hosted summary provider
+    Summary(LLMProvider),
+    /// API key of a hosted transcription provider
+    Transcription(&'static str),
+    /// Webhook URL or bot token, by Slack mode
+    Slack(&'static str),
+    /// Token of a connected Notion workspace, by connection id
+    Notion(String),
+}
+
+/// Where a stored credential was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialSource {
+    Keychain,
+    /// Saved by an older version and not moved yet, because the credential store failed
+    SettingsDatabase,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialInfo {
+    pub id: String,
+    pub label: String,
+    /// The secret with most of it hidden; `None` when nothing is stored
+    pub masked: Option<String>,
+    pub source: Option<CredentialSource>,
+}
+
+impl Credential {
+    pub fn parse(id: &str) -> Result<Self, String> {
+        let (kind, name) = id.split_once(':').ok_or_else(|| format!("Unknown credential: {}", id))?;
+        match kind {
+            "summary" => {
+                let provider = LLMProvider::from_str(name)?;
+                if !provider.requires_api_key() {
+                    return Err(format!("{} doesn't use an API key", provider.display_name()));
+                }
+                Ok(Self::Summary(provider))
+            }
+            "transcription" => CLOUD_PROVIDER_IDS
+                .iter()
+                .copied()
+                .find(|provider| *provider == name)
+                .map(Self::Transcription)
+                .ok_or_else(|| format!("Unknown transcription provider: {}", name)),
+            "slack" => [slack::MODE_WEBHOOK, slack::MODE_BOT]
+                .into_iter()
+                .find(|mode| *mode == name)
+                .map(Self::Slack)
+                .ok_or_else(|| format!("Unknown Slack mode: {}", name)),
+            "notion" if !name.is_empty() => Ok(Self::Notion(name.to_string())),
+            _ => Err(format!("Unknown credential: {}", id)),
+        }
+    }
+
+    pub fn id(&self) -> String {
+        match self {
+            Self::Summary(provider) => format!("summary:{}", provider.id()),
+            Self::Transcription(provider) => format!("transcription:{}", provider),
+            // Integration credentials are already stored under ids of this form
+            Self::Slack(mode) => slack::credential_key(mode),
+            Self::Notion(connection_id) => format!("notion:{}", connection_id),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Summary(provider) => format!("{} (summaries)", provider.display_name()),
+            Self::Transcription(provider) => format!("{} (transcription)", transcription_label(provider)),
+            Self::Slack(mode) if *mode == slack::MODE_BOT => "Slack bot token".to_string(),
+            Self::Slack(_) => "Slack webhook URL".to_string(),
+            Self::Notion(_) => "Notion workspace".to_string(),
+        }
+    }
+
+    /// The stored secret and where it was found
+    pub async fn load(&self, pool: &SqlitePool) -> Option<(String, CredentialSource)> {
+        let stored = match self {
+            Self::Summary(provider) => summary_credentials::load_api_key(provider.id()),
+            Self::Transcription(provider) => transcription_credentials::load_api_key(provider),
+            Self::Slack(_) | Self::Notion(_) => integration_credentials::load_token(&self.id()),
+        };
+        match stored {
+            Ok(Some(secret)) if !secret.is_empty() => return Some((secret, CredentialSource::Keychain)),
+            Ok(_) => {}
+            Err(e) => warn!("{}; trying the settings database", e),
+        }
+
+        // Keys older versions saved in the settings database
+        let legacy = match self {
+            Self::Summary(provider) => SettingsRepository::get_api_key(pool, provider.id()).await,
+            Self::Transcription(provider) => SettingsRepository::get_transcript_api_key(pool, provider).await,
+            Self::Slack(_) | Self::Notion(_) => Ok(None),
+        };
+        legacy
+            .ok()
+            .flatten()
+            .filter(|secret| !secret.is_empty())
+            .map(|secret| (secret, CredentialSource::SettingsDatabase))
+    }
+
+    /// Stores the secret in the credential store and removes any database copy; unlike the
+    /// older settings commands, never falls back to the database
+    pub async fn store(&self, pool: &SqlitePool, secret: &str) -> Result<(), String> {
+        match self {
+            Self::Summary(provider) => summary_credentials::store_api_key(provider.id(), secret),
+            Self::Transcription(provider) => transcription_credentials::store_api_key(provider, secret),
+            Self::Slack(mode) => {
+                slack::validate_secret(mode, secret)?;
+                integration_credentials::store_token(&self.id(), secret)
+            }
+            Self::Notion(_) => return Err("Connect the Notion workspace again to replace its token".to_string()),
+        }
+        .map_err(|e| e.to_string())?;
+        self.clear_database_copy(pool).await
+    }
+
+    pub async fn delete(&self, pool: &SqlitePool) -> Result<(), String> {
+        match self {
+            Self::Summary(provider) => summary_credentials::delete_api_key(provider.id()),
+            Self::Transcription(provider) => transcription_credentials::delete_api_key(provider),
+            Self::Slack(_) | Self::Notion(_) => integration_credentials::delete_token(&self.id()),
+        }
+        .map_err(|e| e.to_string())?;
+        self.clear_database_copy(pool).await?;
+
+        // A workspace without its token is useless, so it's disconnected
+        if let Self::Notion(connection_id) = self {
+            NotionConnectionsRepository::delete(pool, connection_id)
+                .await
+                .map_err(|e| format!("Failed to remove Notion connection: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Removes the copy an older version saved in the settings database
+    pub async fn clear_database_copy(&self, pool: &SqlitePool) -> Result<(), String> {
+        let cleared = match self {
+            Self::Summary(provider) => SettingsRepository::delete_api_key(pool, provider.id()).await,
+            Self::Transcription(provider) => SettingsRepository::delete_transcript_api_key(pool, provider).await,
+            Self::Slack(_) | Self::Notion(_) => Ok(()),
+        };
+        cleared.map_err(|e| format!("Failed to clear the database copy of the key: {}", e))
+    }
+
+    pub async fn info(&self, pool: &SqlitePool) -> CredentialInfo {
+        let stored = self.load(pool).await;
+        CredentialInfo {
+            id: self.id(),
+            label: self.label(),
+            masked: stored.as_ref().map(|(secret, _)| super::mask(secret)),
+            source: stored.map(|(_, source)| source),
+        }
+    }
+}
+
+fn transcription_label(provider: &str) -> &str {
+    match provider {
+        "openai" => "OpenAI",
+        "deepgram" => "Deepgram",
+        "assemblyAI" => "AssemblyAI",
+        other => other,
+    }
+}
+
+/// The API keys of hosted summary and transcription providers
+pub fn provider_keys() -> impl Iterator<Item = Credential> {
+    LLMProvider::ALL
+        .into_iter()
+        .filter(LLMProvider::requires_api_key)
+        .map(Credential::Summary)
+        .chain(CLOUD_PROVIDER_IDS.iter().copied().map(Credential::Transcription))
+}
+
+/// Every credential the app can hold, with Notion's for each connected workspace
+pub async fn all(pool: &SqlitePool) -> Result<Vec<Credential>, String> {
+    let notion = NotionConnectionsRepository::list(pool)
+        .await
+        .map_err(|e| format!("Failed to load Notion connections: {}", e))?;
+    Ok(provider_keys()
+        .chain([Credential::Slack(slack::MODE_WEBHOOK), Credential::Slack(slack::MODE_BOT)])
+        .chain(notion.into_iter().map(|connection| Credential::Notion(connection.id)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        for id in ["summary:openai", "transcription:deepgram", "transcription:assemblyAI", "slack:bot", "notion:abc-123"] {
+            assert_eq!(Credential::parse(id).unwrap().id(), id);
+        }
+        assert!(Credential::parse("summary:ollama").is_err());
+        assert!(Credential::parse("transcription:parakeet").is_err());
+        assert!(Credential::parse("slack:email").is_err());
+        assert!(Credential::parse("notion:").is_err());
+        assert!(Credential::parse("openai").is_err());
+    }
+}
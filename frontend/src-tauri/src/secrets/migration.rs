@@ -0,0 +1,36 @@
+// secrets/migration.rs
+//
+// Older versions saved provider API keys in plaintext columns of the settings database.
+// Each time the database opens, any such key is moved into the credential store and its
+// column cleared. A key that can't be stored stays where it is and is still used, so a
+// missing or locked credential store never loses one.
+
+use log::{info, warn};
+use sqlx::SqlitePool;
+
+use super::credential::{provider_keys, CredentialSource};
+
+/// Moves the API keys left in the settings database into the credential store
+pub async fn move_legacy_keys(pool: &SqlitePool) {
+    let mut moved = 0;
+    for credential in provider_keys() {
+        let secret = match credential.load(pool).await {
+            Some((secret, CredentialSource::SettingsDatabase)) => secret,
+            // A copy left next to a key that's already in the store is out of date
+            Some((_, CredentialSource::Keychain)) => {
+                if let Err(e) = credential.clear_database_copy(pool).await {
+                    warn!("🔐 {}", e);
+                }
+                continue;
+            }
+            None => continue,
+        };
+        match credential.store(pool, &secret).await {
+            Ok(()) => moved += 1,
+            Err(e) => warn!("🔐 Keeping the {} key in the settings database: {}", credential.id(), e),
+        }
+    }
+    if moved > 0 {
+        info!("🔐 Moved {} API keys from the settings database to the system credential store", moved);
+    }
+}
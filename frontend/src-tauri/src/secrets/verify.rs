@@ -0,0 +1,77 @@
+// secrets/verify.rs
+//
+// Checks a credential with the cheapest authenticated request its service offers, such as
+// listing models or reading the account. Nothing is created, posted or billed.
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use super::credential::Credential;
+use crate::integrations::notion::NotionClient;
+use crate::integrations::slack;
+use crate::summary::llm_client::LLMProvider;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+const SLACK_AUTH_TEST_URL: &str = "https://slack.com/api/auth.test";
+
+/// A short description of what the credential gives access to, or why it doesn't work
+pub async fn verify(credential: &Credential, secret: &str) -> Result<String, String> {
+    let client = crate::network::client_builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let request = match credential {
+        Credential::Summary(LLMProvider::OpenAI) | Credential::Transcription("openai") => {
+            client.get("https://api.openai.com/v1/models").bearer_auth(secret)
+        }
+        Credential::Summary(LLMProvider::Claude) => client
+            .get("https://api.anthropic.com/v1/models")
+            .header("x-api-key", secret)
+            .header("anthropic-version", "2023-06-01"),
+        Credential::Summary(LLMProvider::Groq) => client.get("https://api.groq.com/openai/v1/models").bearer_auth(secret),
+        Credential::Summary(LLMProvider::OpenRouter) => client.get("https://openrouter.ai/api/v1/key").bearer_auth(secret),
+        Credential::Summary(provider) => return Err(format!("{} doesn't use an API key", provider.display_name())),
+        Credential::Transcription("deepgram") => client
+            .get("https://api.deepgram.com/v1/projects")
+            .header("Authorization", format!("Token {}", secret)),
+        Credential::Transcription("assemblyAI") => client
+            .get("https://api.assemblyai.com/v2/transcript?limit=1")
+            .header("Authorization", secret),
+        Credential::Transcription(provider) => return Err(format!("Keys for {} can't be checked", provider)),
+        Credential::Slack(mode) if *mode == slack::MODE_BOT => return verify_slack_bot(&client, secret).await,
+        Credential::Slack(mode) => {
+            slack::validate_secret(mode, secret)?;
+            return Ok("The webhook URL looks right; only sending a test message to Slack checks it".to_string());
+        }
+        Credential::Notion(_) => {
+            let workspace = NotionClient::new(secret.to_string()).token_workspace().await?;
+            return Ok(format!("Connected to {}", workspace.name));
+        }
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach {}: {}", credential.label(), e))?;
+    match response.status() {
+        status if status.is_success() => Ok("The key works".to_string()),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err("The key was rejected".to_string()),
+        status => Err(format!("Unexpected response ({}); try again later", status)),
+    }
+}
+
+async fn verify_slack_bot(client: &reqwest::Client, token: &str) -> Result<String, String> {
+    let response = client
+        .post(SLACK_AUTH_TEST_URL)
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Slack: {}", e))?;
+    let body: Value = response.json().await.map_err(|e| format!("Unexpected response from Slack: {}", e))?;
+    if body["ok"].as_bool() != Some(true) {
+        return Err(format!("Slack rejected the token: {}", body["error"].as_str().unwrap_or("unknown error")));
+    }
+    Ok(format!("Connected to {}", body["team"].as_str().unwrap_or("Slack")))
+}
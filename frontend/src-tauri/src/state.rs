@@ -1,5 +1,28 @@
+use std::time::Duration;
+
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Manager, Runtime};
+
 use crate::database::manager::DatabaseManager;
 
+/// How often `AppState::wait_for_pool` checks whether the database has been set up
+const DATABASE_POLL: Duration = Duration::from_secs(2);
+
 pub struct AppState {
     pub db_manager: DatabaseManager,
 }
+
+impl AppState {
+    /// The database pool, once there is one
+    ///
+    /// On first launch the database is only set up after onboarding, so background workers
+    /// started with the app wait here before their first run.
+    pub async fn wait_for_pool<R: Runtime>(app: &AppHandle<R>) -> SqlitePool {
+        loop {
+            if let Some(state) = app.try_state::<AppState>() {
+                return state.db_manager.pool().clone();
+            }
+            tokio::time::sleep(DATABASE_POLL).await;
+        }
+    }
+}
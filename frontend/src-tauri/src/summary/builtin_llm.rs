@@ -0,0 +1,308 @@
+// summary/builtin_llm.rs
+//
+// The built-in summary model: a small quantized model run by a llama.cpp `llama-server`
+// that ships with the app, so summaries work without Ollama or an API key. The server is a
+// separate program rather than linked in, because llama.cpp and whisper.cpp each carry
+// their own copy of ggml. It's started on a free local port the first time a summary needs
+// it, talked to through the same OpenAI-compatible client as an external llama.cpp server,
+// and stopped when the app exits.
+//
+// Builds that don't bundle `llama-server` use one found on the PATH; without either, the
+// built-in provider reports that it's unavailable.
+
+use std::net::{Ipv4Addr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+use futures_util::StreamExt;
+use log::{debug, info, warn};
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Child;
+use tokio::time::{sleep, Duration};
+
+const EXECUTABLE_NAME: &str = if cfg!(windows) { "llama-server.exe" } else { "llama-server" };
+
+/// Context window the server is started with; the models below support at least this
+const CONTEXT_SIZE: usize = 8192;
+/// Transcript tokens per request, leaving room for the prompt and the summary
+pub const TOKEN_THRESHOLD: usize = CONTEXT_SIZE - 2500;
+
+/// Loading a model from a slow disk can take a while
+const START_TIMEOUT: Duration = Duration::from_secs(120);
+const START_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct BuiltinModel {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub size_mb: u32,
+    #[serde(skip)]
+    file: &'static str,
+    #[serde(skip)]
+    url: &'static str,
+}
+
+/// Models the built-in provider can download, the default first
+pub const BUILTIN_MODELS: [BuiltinModel; 2] = [
+    BuiltinModel {
+        id: "qwen2.5-1.5b-instruct",
+        name: "Qwen2.5 1.5B Instruct (Q4_K_M)",
+        size_mb: 1120,
+        file: "qwen2.5-1.5b-instruct-q4_k_m.gguf",
+        url: "https://huggingface.co/Qwen/Qwen2.5-1.5B-Instruct-GGUF/resolve/main/qwen2.5-1.5b-instruct-q4_k_m.gguf",
+    },
+    BuiltinModel {
+        id: "qwen2.5-3b-instruct",
+        name: "Qwen2.5 3B Instruct (Q4_K_M)",
+        size_mb: 2100,
+        file: "qwen2.5-3b-instruct-q4_k_m.gguf",
+        url: "https://huggingface.co/Qwen/Qwen2.5-3B-Instruct-GGUF/resolve/main/qwen2.5-3b-instruct-q4_k_m.gguf",
+    },
+];
+
+pub fn find_model(id: &str) -> Option<&'static BuiltinModel> {
+    BUILTIN_MODELS.iter().find(|model| model.id == id)
+}
+
+/// The app's models directory, set at startup (or by the CLI)
+static MODELS_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+pub fn set_models_dir(dir: PathBuf) {
+    let _ = MODELS_DIR.set(dir);
+}
+
+/// Where a model's file goes under the models directory
+pub fn model_path(models_dir: &Path, model: &BuiltinModel) -> PathBuf {
+    models_dir.join("llm").join(model.file)
+}
+
+fn models_dir() -> Result<&'static PathBuf> {
+    MODELS_DIR.get().ok_or_else(|| anyhow!("The models directory isn't set yet"))
+}
+
+/// `llama-server` next to the app's executable, where the bundle puts it, or on the PATH
+pub fn find_runtime() -> Option<PathBuf> {
+    let bundled = std::env::current_exe().ok()?.parent()?.join(EXECUTABLE_NAME);
+    if bundled.is_file() {
+        return Some(bundled);
+    }
+    which::which(EXECUTABLE_NAME).ok()
+}
+
+/// The model a summary can use right now: the first downloaded one, when the runtime is there
+pub fn ready_model() -> Option<&'static str> {
+    find_runtime()?;
+    let dir = models_dir().ok()?;
+    BUILTIN_MODELS.iter().find(|model| model_path(dir, model).exists()).map(|model| model.id)
+}
+
+/// Downloads a model, reporting progress as a percentage
+pub async fn download_model(model: &BuiltinModel, progress: impl Fn(u8)) -> Result<PathBuf> {
+    let path = model_path(models_dir()?, model);
+    if path.exists() {
+        return Ok(path);
+    }
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    info!("Downloading built-in summary model from {}", model.url);
+    let response = reqwest::get(model.url).await.map_err(|e| anyhow!("Failed to start download: {}", e))?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Download failed with status: {}", response.status()));
+    }
+
+    // Download next to the model and rename, so a partial file is never loaded
+    let partial_path = path.with_extension("gguf.part");
+    let total_size = response.content_length().unwrap_or(0);
+    let mut file = tokio::fs::File::create(&partial_path).await?;
+    let mut stream = response.bytes_stream();
+    let mut downloaded = 0u64;
+    let mut last_progress = 0u8;
+    progress(0);
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| anyhow!("Failed to read chunk: {}", e))?;
+        file.write_all(&chunk).await?;
+        downloaded += chunk.len() as u64;
+        if total_size > 0 {
+            let percent = ((downloaded as f64 / total_size as f64) * 100.0) as u8;
+            if percent > last_progress {
+                last_progress = percent;
+                progress(percent);
+            }
+        }
+    }
+    file.flush().await?;
+    drop(file);
+
+    tokio::fs::rename(&partial_path, &path).await?;
+    progress(100);
+    info!("Built-in summary model saved to {}", path.display());
+    Ok(path)
+}
+
+/// The running server and the model it serves
+struct RunningServer {
+    model_id: &'static str,
+    port: u16,
+    child: Child,
+}
+
+static SERVER: Lazy<Mutex<Option<RunningServer>>> = Lazy::new(|| Mutex::new(None));
+/// Held while a server starts, so two summaries don't both start one
+static STARTING: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+fn free_port() -> Result<u16> {
+    Ok(TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?.local_addr()?.port())
+}
+
+/// The endpoint of a running server for `model_id`, if there is one
+fn running_endpoint(model_id: &str) -> Option<String> {
+    let mut server = SERVER.lock().unwrap();
+    let running = server.as_mut()?;
+    let alive = matches!(running.child.try_wait(), Ok(None));
+    if alive && running.model_id == model_id {
+        return Some(format!("http://127.0.0.1:{}", running.port));
+    }
+    if !alive {
+        *server = None;
+    }
+    None
+}
+
+async fn wait_until_ready(endpoint: &str, child: &mut Child, last_line: &Mutex<Option<String>>) -> Result<()> {
+    let client = crate::network::client();
+    let deadline = Instant::now() + START_TIMEOUT;
+    loop {
+        sleep(START_POLL_INTERVAL).await;
+        if let Ok(Some(exit)) = child.try_wait() {
+            let reason = last_line.lock().unwrap().clone().unwrap_or_else(|| exit.to_string());
+            return Err(anyhow!("llama-server stopped while starting: {}", reason));
+        }
+        // 503 while the model is still loading
+        let health = client.get(format!("{}/health", endpoint)).timeout(Duration::from_secs(2)).send().await;
+        if health.is_ok_and(|response| response.status().is_success()) {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(anyhow!("llama-server didn't load the model within {}s", START_TIMEOUT.as_secs()));
+        }
+    }
+}
+
+/// Starts the server for `model_id` unless it's already running; returns its endpoint
+pub async fn ensure_server(model_id: &str) -> Result<String> {
+    let model = find_model(model_id).ok_or_else(|| anyhow!("Unknown built-in model: {}", model_id))?;
+    let _starting = STARTING.lock().await;
+    if let Some(endpoint) = running_endpoint(model.id) {
+        return Ok(endpoint);
+    }
+    stop();
+
+    let runtime = find_runtime().ok_or_else(|| anyhow!("This build doesn't include the built-in summary model runtime"))?;
+    let path = model_path(models_dir()?, model);
+    if !path.exists() {
+        return Err(anyhow!("The built-in model {} isn't downloaded yet", model.name));
+    }
+    let port = free_port()?;
+
+    let mut command = tokio::process::Command::new(&runtime);
+    command
+        .arg("--model")
+        .arg(&path)
+        .args(["--host", "127.0.0.1", "--port", &port.to_string(), "--ctx-size", &CONTEXT_SIZE.to_string()])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = command.spawn().map_err(|e| anyhow!("Failed to start {}: {}", runtime.display(), e))?;
+    info!("🧠 Starting built-in summary model {} on port {}", model.id, port);
+
+    // The server's log, and the reason when it exits early
+    let last_line = Arc::new(Mutex::new(None::<String>));
+    if let Some(stderr) = child.stderr.take() {
+        let last_line = last_line.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                debug!("[llama-server] {}", line);
+                *last_line.lock().unwrap() = Some(line);
+            }
+        });
+    }
+
+    let endpoint = format!("http://127.0.0.1:{}", port);
+    if let Err(e) = wait_until_ready(&endpoint, &mut child, &last_line).await {
+        let _ = child.kill().await;
+        return Err(e);
+    }
+    info!("🧠 Built-in summary model {} is ready", model.id);
+    *SERVER.lock().unwrap() = Some(RunningServer { model_id: model.id, port, child });
+    Ok(endpoint)
+}
+
+/// Stops the server, e.g. when the app exits
+pub fn stop() {
+    if let Some(mut server) = SERVER.lock().unwrap().take() {
+        if let Err(e) = server.child.start_kill() {
+            warn!("Failed to stop llama-server: {}", e);
+        }
+        info!("🧠 Built-in summary model stopped");
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BuiltinModelStatus {
+    #[serde(flatten)]
+    pub model: BuiltinModel,
+    pub downloaded: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BuiltinLlmStatus {
+    /// Whether `llama-server` is bundled or on the PATH
+    pub runtime_available: bool,
+    pub models: Vec<BuiltinModelStatus>,
+}
+
+pub fn status() -> BuiltinLlmStatus {
+    let dir = models_dir().ok();
+    BuiltinLlmStatus {
+        runtime_available: find_runtime().is_some(),
+        models: BUILTIN_MODELS
+            .iter()
+            .map(|model| BuiltinModelStatus {
+                model: *model,
+                downloaded: dir.is_some_and(|dir| model_path(dir, model).exists()),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog() {
+        assert_eq!(find_model("qwen2.5-1.5b-instruct").map(|model| model.id), Some(BUILTIN_MODELS[0].id));
+        assert!(find_model("llama3.2:latest").is_none());
+        for model in BUILTIN_MODELS {
+            assert!(model.url.ends_with(model.file));
+            assert_eq!(model_path(Path::new("/models"), &model), Path::new("/models/llm").join(model.file));
+        }
+    }
+}
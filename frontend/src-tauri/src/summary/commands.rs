@@ -1,11 +1,16 @@
+use crate::database::models::Job;
 use crate::database::repositories::{
-    meeting::MeetingsRepository, summary::SummaryProcessesRepository,
-    transcript_chunk::TranscriptChunksRepository,
+    job::JobsRepository, meeting::MeetingsRepository, setting::SettingsRepository,
+    summary::SummaryProcessesRepository, summary_version::SummaryVersionsRepository,
+    transcript::TranscriptsRepository, transcript_chunk::TranscriptChunksRepository,
 };
+use crate::jobs::{self, JobPayload, JobPriority};
 use crate::state::AppState;
-use crate::summary::service::SummaryService;
+use crate::summary::llm_client::LLMProvider;
+use crate::summary::processor::ChunkingOptions;
 use log::{error as log_error, info as log_info, warn as log_warn};
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 use tauri::{AppHandle, Runtime};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,6 +31,46 @@ pub struct ProcessTranscriptResponse {
     pub process_id: String,
 }
 
+/// A previously generated summary of a meeting
+#[derive(Debug, Serialize)]
+pub struct SummaryVersionResponse {
+    pub id: String,
+    pub meeting_id: String,
+    pub provider: String,
+    pub model: String,
+    pub template_id: String,
+    pub created_at: String,
+    pub data: Option<serde_json::Value>,
+}
+
+/// Resets the meeting's summary process and queues the job that fills it; when queueing
+/// fails, the process is put back so the current summary isn't lost
+async fn reset_and_enqueue<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    payload: JobPayload,
+    priority: JobPriority,
+) -> Result<Job, String> {
+    let previous = SummaryProcessesRepository::get_summary_data(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary process: {}", e))?;
+    SummaryProcessesRepository::create_or_reset_process(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to initialize process: {}", e))?;
+    match jobs::enqueue(app, pool, payload, priority).await {
+        Ok(job) => Ok(job),
+        Err(e) => {
+            if let Err(restore_error) =
+                SummaryProcessesRepository::put_back_process(pool, meeting_id, previous.as_ref()).await
+            {
+                log_error!("Failed to put back the summary of {}: {}", meeting_id, restore_error);
+            }
+            Err(format!("Failed to queue summary generation: {}", e))
+        }
+    }
+}
+
 /// Saves a meeting summary (Native SQLx implementation)
 ///
 /// Expected format: { "markdown": "...", "summary_json": [...BlockNote blocks...] }
@@ -165,7 +210,9 @@ pub async fn api_get_summary<R: Runtime>(
 
 /// Processes transcript and generates summary (Native SQLx implementation)
 ///
-/// Spawns a background task and returns immediately with process_id
+/// Queues the summary as a live-priority job and returns immediately with process_id.
+/// Transcripts longer than the model's context are summarized in windows of
+/// `chunk_size` tokens overlapping by `overlap` tokens, both capped to fit the model.
 #[tauri::command]
 pub async fn api_process_transcript<R: Runtime>(
     app: AppHandle<R>,
@@ -191,14 +238,20 @@ pub async fn api_process_transcript<R: Runtime>(
 
     let pool = state.db_manager.pool().clone();
     let final_prompt = custom_prompt.unwrap_or_else(|| "".to_string());
-    let final_template_id = template_id.unwrap_or_else(|| "daily_standup".to_string());
-
-    // Create or reset the process entry in the database
-    SummaryProcessesRepository::create_or_reset_process(&pool, &m_id)
-        .await
-        .map_err(|e| format!("Failed to initialize process: {}", e))?;
-
-    log_info!("✓ Summary process initialized for meeting_id: {}", &m_id);
+    // An explicit template becomes the meeting's selection; otherwise use the selection
+    let final_template_id = match template_id {
+        Some(template_id) => {
+            if let Err(e) = MeetingsRepository::set_template_id(&pool, &m_id, Some(&template_id)).await {
+                log_warn!("Failed to remember template for {}: {}", &m_id, e);
+            }
+            template_id
+        }
+        None => MeetingsRepository::get_template_id(&pool, &m_id)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "daily_standup".to_string()),
+    };
 
     // Save transcript chunks data (matching Python backend behavior)
     let chunk_size = _chunk_size.unwrap_or(40000);
@@ -218,26 +271,242 @@ pub async fn api_process_transcript<R: Runtime>(
 
     log_info!("✓ Transcript chunks saved for meeting_id: {}", &m_id);
 
-    // Spawn background task for actual processing
-    let meeting_id_clone = m_id.clone();
-    tauri::async_runtime::spawn(async move {
-        SummaryService::process_transcript_background(
-            app,
-            pool,
-            meeting_id_clone.clone(),
-            text,
-            model,
-            model_name,
-            final_prompt,
-            final_template_id,
-        )
-        .await;
-    });
+    // Queue the actual processing; live jobs never wait behind imported files
+    let payload = JobPayload::Summary {
+        meeting_id: m_id.clone(),
+        text,
+        model,
+        model_name,
+        custom_prompt: final_prompt,
+        template_id: final_template_id,
+        chunking: ChunkingOptions {
+            chunk_tokens: usize::try_from(chunk_size).ok(),
+            overlap_tokens: usize::try_from(overlap).ok(),
+        },
+    };
+    let job = reset_and_enqueue(&app, &pool, &m_id, payload, JobPriority::Live).await?;
 
-    log_info!("🚀 Background task spawned for meeting_id: {}", &m_id);
+    log_info!("🚀 Summary job {} queued for meeting_id: {}", job.id, &m_id);
 
     Ok(ProcessTranscriptResponse {
         message: "Summary generation started".to_string(),
         process_id: m_id,
     })
 }
+
+/// Stops summary generation for a meeting, keeping nothing of the partial output
+///
+/// Returns false when no summary was being generated.
+#[tauri::command]
+pub async fn api_cancel_summary<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<bool, String> {
+    log_info!("api_cancel_summary called for meeting_id: {}", meeting_id);
+    let pool = state.db_manager.pool().clone();
+    let active = JobsRepository::list(&pool, false)
+        .await
+        .map_err(|e| format!("Failed to list jobs: {}", e))?;
+
+    let job = active.into_iter().find(|job| {
+        matches!(
+            serde_json::from_str::<JobPayload>(&job.payload),
+            Ok(JobPayload::Summary { meeting_id: ref id, .. }) if *id == meeting_id
+        )
+    });
+    match job {
+        Some(job) => jobs::cancel(&app, &pool, &job.id).await.map_err(|e| e.to_string()),
+        None => Ok(false),
+    }
+}
+
+/// Summarizes a meeting again from its stored transcript, e.g. with another model or template
+///
+/// Nothing is re-transcribed. The current summary stays in the meeting's version history
+/// (see `list_summary_versions`), so outputs of different models can be compared.
+/// Without `model_name` the model chosen for the provider (or its default) is used, and
+/// without `template_id` the meeting's selected template.
+#[tauri::command]
+pub async fn regenerate_summary<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    provider: String,
+    model_name: Option<String>,
+    template_id: Option<String>,
+    custom_prompt: Option<String>,
+) -> Result<ProcessTranscriptResponse, String> {
+    log_info!("regenerate_summary called for meeting_id: {} with {}", meeting_id, provider);
+    let pool = state.db_manager.pool().clone();
+    let llm_provider = LLMProvider::from_str(&provider)?;
+
+    let model_name = match model_name.map(|m| m.trim().to_string()).filter(|m| !m.is_empty()) {
+        Some(model_name) => model_name,
+        None => SettingsRepository::get_summary_provider_setting(&pool, llm_provider.id())
+            .await
+            .ok()
+            .flatten()
+            .map(|setting| setting.model)
+            .unwrap_or_else(|| llm_provider.default_model().to_string()),
+    };
+
+    // Reuse the transcript text last summarized; older meetings only have segments
+    let stored = TranscriptChunksRepository::get(&pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcript: {}", e))?;
+    let previous_model = stored.as_ref().map(|chunk| (chunk.model.clone(), chunk.model_name.clone()));
+    let (text, chunk_size, overlap) = match stored {
+        Some(chunk) => (
+            chunk.transcript_text,
+            chunk.chunk_size.and_then(|size| i32::try_from(size).ok()).unwrap_or(40000),
+            chunk.overlap.and_then(|overlap| i32::try_from(overlap).ok()).unwrap_or(1000),
+        ),
+        None => {
+            let segments = TranscriptsRepository::list_for_meeting(&pool, &meeting_id)
+                .await
+                .map_err(|e| format!("Failed to load transcript: {}", e))?;
+            let text = segments
+                .iter()
+                .map(|segment| segment.transcript.trim())
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+            (text, 40000, 1000)
+        }
+    };
+    if text.trim().is_empty() {
+        return Err("The meeting has no transcript to summarize".to_string());
+    }
+
+    // Resetting the process clears the current summary; make sure the history has it,
+    // including edits made to it and summaries from before versions were kept
+    if let Ok(Some(process)) = SummaryProcessesRepository::get_summary_data(&pool, &meeting_id).await {
+        if let Some(result) = process.result.filter(|_| process.status.eq_ignore_ascii_case("completed")) {
+            match serde_json::from_str::<serde_json::Value>(&result) {
+                Ok(value) => {
+                    let kept = SummaryVersionsRepository::contains_result(&pool, &meeting_id, &value)
+                        .await
+                        .map_err(|e| format!("Failed to load summary versions: {}", e))?;
+                    if !kept {
+                        let (provider, model) =
+                            previous_model.unwrap_or_else(|| ("unknown".to_string(), "unknown".to_string()));
+                        let previous_template = MeetingsRepository::get_template_id(&pool, &meeting_id)
+                            .await
+                            .ok()
+                            .flatten()
+                            .unwrap_or_else(|| "unknown".to_string());
+                        SummaryVersionsRepository::insert(&pool, &meeting_id, &provider, &model, &previous_template, &value)
+                            .await
+                            .map_err(|e| format!("Failed to keep the current summary: {}", e))?;
+                    }
+                }
+                Err(e) => log_warn!("Current summary of {} is not valid JSON, not keeping it: {}", meeting_id, e),
+            }
+        }
+    }
+
+    let template_id = match template_id {
+        Some(template_id) => {
+            if let Err(e) = MeetingsRepository::set_template_id(&pool, &meeting_id, Some(&template_id)).await {
+                log_warn!("Failed to remember template for {}: {}", meeting_id, e);
+            }
+            template_id
+        }
+        None => MeetingsRepository::get_template_id(&pool, &meeting_id)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "daily_standup".to_string()),
+    };
+
+    TranscriptChunksRepository::save_transcript_data(
+        &pool,
+        &meeting_id,
+        &text,
+        llm_provider.id(),
+        &model_name,
+        chunk_size,
+        overlap,
+    )
+    .await
+    .map_err(|e| format!("Failed to save transcript data: {}", e))?;
+
+    let payload = JobPayload::Summary {
+        meeting_id: meeting_id.clone(),
+        text,
+        model: llm_provider.id().to_string(),
+        model_name: model_name.clone(),
+        custom_prompt: custom_prompt.unwrap_or_default(),
+        template_id: template_id.clone(),
+        chunking: ChunkingOptions {
+            chunk_tokens: usize::try_from(chunk_size).ok(),
+            overlap_tokens: usize::try_from(overlap).ok(),
+        },
+    };
+    let job = reset_and_enqueue(&app, &pool, &meeting_id, payload, JobPriority::Normal).await?;
+
+    log_info!(
+        "🔁 Regeneration job {} queued for meeting_id: {} ({} / {}, template {})",
+        job.id,
+        meeting_id,
+        llm_provider.id(),
+        model_name,
+        template_id
+    );
+    Ok(ProcessTranscriptResponse {
+        message: "Summary regeneration started".to_string(),
+        process_id: meeting_id,
+    })
+}
+
+/// All summaries generated for a meeting, newest first
+#[tauri::command]
+pub async fn list_summary_versions(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<SummaryVersionResponse>, String> {
+    let versions = SummaryVersionsRepository::list_for_meeting(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to list summary versions: {}", e))?;
+    Ok(versions
+        .into_iter()
+        .map(|version| SummaryVersionResponse {
+            data: serde_json::from_str(&version.result).ok(),
+            id: version.id,
+            meeting_id: version.meeting_id,
+            provider: version.provider,
+            model: version.model,
+            template_id: version.template_id,
+            created_at: version.created_at.to_rfc3339(),
+        })
+        .collect())
+}
+
+/// Makes an earlier summary version the meeting's current summary
+#[tauri::command]
+pub async fn restore_summary_version(
+    state: tauri::State<'_, AppState>,
+    version_id: String,
+) -> Result<(), String> {
+    let pool = state.db_manager.pool();
+    let version = SummaryVersionsRepository::get(pool, &version_id)
+        .await
+        .map_err(|e| format!("Failed to load summary version: {}", e))?
+        .ok_or_else(|| format!("Summary version not found: {}", version_id))?;
+    let restored = SummaryProcessesRepository::restore_result(pool, &version.meeting_id, &version.result)
+        .await
+        .map_err(|e| format!("Failed to restore summary: {}", e))?;
+    if !restored {
+        return Err(format!("No summary to replace for meeting {}", version.meeting_id));
+    }
+    log_info!("⏪ Restored summary version {} for meeting_id: {}", version_id, version.meeting_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_summary_version(state: tauri::State<'_, AppState>, version_id: String) -> Result<bool, String> {
+    SummaryVersionsRepository::delete(state.db_manager.pool(), &version_id)
+        .await
+        .map_err(|e| format!("Failed to delete summary version: {}", e))
+}
@@ -0,0 +1,49 @@
+// summary/credentials.rs
+//
+// API keys for hosted summary providers, kept in the OS credential store (macOS
+// Keychain, Windows Credential Manager, Secret Service on Linux) instead of the settings
+// database. Keys saved by older versions still live in the database and are used as a
+// fallback until the user saves them again.
+
+use crate::database::repositories::setting::SettingsRepository;
+use crate::secrets;
+use anyhow::Result;
+use log::{info, warn};
+use sqlx::SqlitePool;
+
+const SERVICE: &str = "com.meetily.ai.summary";
+
+/// Other workspaces' keys are under their own service names
+fn service() -> String {
+    crate::workspaces::scoped_service(SERVICE)
+}
+
+/// Store (or replace) the API key for a provider
+pub fn store_api_key(provider: &str, api_key: &str) -> Result<()> {
+    secrets::store(&service(), provider, api_key)?;
+    info!("🔐 Stored summary API key for {} in the system credential store", provider);
+    Ok(())
+}
+
+/// API key for a provider, or None when none is stored
+pub fn load_api_key(provider: &str) -> Result<Option<String>> {
+    secrets::load(&service(), provider)
+}
+
+pub fn delete_api_key(provider: &str) -> Result<()> {
+    secrets::delete(&service(), provider)
+}
+
+/// Summary API key from the credential store, falling back to the settings database
+pub async fn resolve_api_key(pool: &SqlitePool, provider: &str) -> std::result::Result<Option<String>, sqlx::Error> {
+    match load_api_key(provider) {
+        Ok(Some(key)) => return Ok(Some(key)),
+        Ok(None) => {}
+        Err(e) => warn!("{}; trying the settings database", e),
+    }
+    // Local providers have no key column
+    match SettingsRepository::get_api_key(pool, provider).await {
+        Err(sqlx::Error::Protocol(_)) => Ok(None),
+        result => result,
+    }
+}
@@ -0,0 +1,220 @@
+// summary/extraction.rs
+//
+// Shared plumbing for structured extraction passes (action items, key points).
+//
+// The transcript is given to the model as numbered segments ("[12] Alice: ..."), and the
+// model answers with a JSON array whose records name the segment they came from, so every
+// extracted record can be linked back to the transcript. Meetings that don't fit the model
+// are split into windows of whole segments and the records of all windows are combined.
+
+use crate::database::models::Transcript;
+use crate::database::repositories::{setting::SettingsRepository, transcript::TranscriptsRepository};
+use crate::redaction::{self, RedactionTarget};
+use crate::summary::processor::rough_token_count;
+use crate::summary::provider::{complete_with_retry, CompletionRequest, RetryPolicy, SummaryProvider, TokenUsage};
+use serde::de::DeserializeOwned;
+use sqlx::SqlitePool;
+use std::ops::Range;
+use tracing::{info, warn};
+
+/// Room left in each window for the instructions and the model's answer
+const EXTRACTION_OVERHEAD_TOKENS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub id: String,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+impl From<Transcript> for Segment {
+    fn from(row: Transcript) -> Self {
+        Self { id: row.id, speaker: row.speaker, text: row.transcript }
+    }
+}
+
+/// The non-empty transcript segments of a meeting in recording order, redacted as the
+/// redaction settings ask since they're sent to a model
+pub async fn load_segments(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<Segment>, String> {
+    let mut segments: Vec<Segment> = TranscriptsRepository::list_for_meeting(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcript: {}", e))?
+        .into_iter()
+        .map(Segment::from)
+        .filter(|segment| !segment.text.trim().is_empty())
+        .collect();
+    if segments.is_empty() {
+        return Err("The meeting has no transcript to extract from".to_string());
+    }
+
+    let settings = redaction::get_settings();
+    if settings.enabled {
+        if settings.names {
+            redaction::number_speakers(segments.iter_mut().filter_map(|segment| segment.speaker.as_mut()));
+        }
+        let texts = segments.iter().map(|segment| segment.text.clone()).collect();
+        let redacted = redaction::redact_for_meeting(pool, meeting_id, RedactionTarget::Summary, texts).await?;
+        for (segment, text) in segments.iter_mut().zip(redacted) {
+            segment.text = text;
+        }
+    }
+    Ok(segments)
+}
+
+/// The (provider, model) to extract with: the one given, or the saved summary model
+pub async fn extraction_model(
+    pool: &SqlitePool,
+    model: Option<String>,
+    model_name: Option<String>,
+) -> Result<(String, String), String> {
+    if let (Some(model), Some(model_name)) = (model, model_name) {
+        return Ok((model, model_name));
+    }
+    let config = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to load model config: {}", e))?
+        .ok_or_else(|| "No summary model configured".to_string())?;
+    Ok((config.provider, config.model))
+}
+
+/// One numbered transcript line; `number` is 1-based
+pub fn format_segment(number: usize, segment: &Segment) -> String {
+    match segment.speaker.as_deref() {
+        Some(speaker) => format!("[{}] {}: {}", number, speaker, segment.text.trim()),
+        None => format!("[{}] {}", number, segment.text.trim()),
+    }
+}
+
+/// Splits the segments into consecutive windows of at most `budget_tokens`
+///
+/// A segment longer than the budget gets a window of its own.
+pub fn segment_windows(segments: &[Segment], budget_tokens: usize) -> Vec<Range<usize>> {
+    let mut windows = Vec::new();
+    let mut start = 0;
+    let mut tokens = 0;
+    for (i, segment) in segments.iter().enumerate() {
+        let segment_tokens = rough_token_count(&format_segment(i + 1, segment)) + 1;
+        if i > start && tokens + segment_tokens > budget_tokens {
+            windows.push(start..i);
+            start = i;
+            tokens = 0;
+        }
+        tokens += segment_tokens;
+    }
+    if start < segments.len() {
+        windows.push(start..segments.len());
+    }
+    windows
+}
+
+/// Id of the segment a record points at by its 1-based number
+pub fn segment_id(segments: &[Segment], number: Option<usize>) -> Option<String> {
+    number
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|i| segments.get(i))
+        .map(|segment| segment.id.clone())
+}
+
+/// The records of the JSON array in a model reply
+///
+/// Models like to wrap JSON in code fences or add a sentence around it, so the outermost
+/// `[...]` is used. Records that don't match `T` are dropped rather than failing the rest.
+pub fn parse_json_array<T: DeserializeOwned>(reply: &str) -> Vec<T> {
+    let (Some(start), Some(end)) = (reply.find('['), reply.rfind(']')) else {
+        return Vec::new();
+    };
+    if end < start {
+        return Vec::new();
+    }
+    let Ok(values) = serde_json::from_str::<Vec<serde_json::Value>>(&reply[start..=end]) else {
+        return Vec::new();
+    };
+    values.into_iter().filter_map(|value| serde_json::from_value(value).ok()).collect()
+}
+
+/// Runs an extraction prompt over the whole transcript, one window at a time
+///
+/// `instructions` describe the records wanted and the JSON shape; the numbered transcript
+/// window is appended to them. Fails only if no window could be processed.
+pub async fn extract_records<T: DeserializeOwned>(
+    provider: &dyn SummaryProvider,
+    system_prompt: &str,
+    instructions: &str,
+    segments: &[Segment],
+    token_threshold: usize,
+) -> Result<(Vec<T>, TokenUsage), String> {
+    let budget = token_threshold.saturating_sub(EXTRACTION_OVERHEAD_TOKENS).max(500);
+    let windows = segment_windows(segments, budget);
+    let mut records = Vec::new();
+    let mut usage = TokenUsage::default();
+    let mut failures = 0;
+
+    for (i, window) in windows.iter().enumerate() {
+        let transcript = window
+            .clone()
+            .map(|index| format_segment(index + 1, &segments[index]))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let user_prompt = format!("{}\n\n<transcript>\n{}\n</transcript>", instructions, transcript);
+        let request = CompletionRequest::new(system_prompt, user_prompt);
+
+        match complete_with_retry(provider, &request, &RetryPolicy::default()).await {
+            Ok(completion) => {
+                usage.add(completion.usage);
+                let parsed: Vec<T> = parse_json_array(&completion.text);
+                info!("✓ Extraction window {}/{}: {} record(s)", i + 1, windows.len(), parsed.len());
+                records.extend(parsed);
+            }
+            Err(e) => {
+                warn!("⚠️ Extraction window {}/{} failed: {}", i + 1, windows.len(), e);
+                failures += 1;
+            }
+        }
+    }
+
+    if !windows.is_empty() && failures == windows.len() {
+        return Err("Extraction failed: no part of the transcript could be processed".to_string());
+    }
+    Ok((records, usage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Record {
+        text: String,
+    }
+
+    fn segment(text: &str) -> Segment {
+        Segment { id: format!("t-{}", text.len()), speaker: Some("Alice".to_string()), text: text.to_string() }
+    }
+
+    #[test]
+    fn test_json_array_in_fenced_reply() {
+        let reply = "Here you go:\n```json\n[{\"text\": \"Send deck\"}, {\"oops\": 1}, {\"text\": \"Book room\"}]\n```";
+        let records: Vec<Record> = parse_json_array(reply);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].text, "Book room");
+
+        assert!(parse_json_array::<Record>("No action items.").is_empty());
+        assert!(parse_json_array::<Record>("] nothing [").is_empty());
+    }
+
+    #[test]
+    fn test_windows_keep_whole_segments() {
+        let segments: Vec<Segment> = (0..10).map(|_| segment(&"word ".repeat(20))).collect();
+        let windows = segment_windows(&segments, 80);
+        assert!(windows.len() > 1);
+        assert_eq!(windows.first().unwrap().start, 0);
+        assert_eq!(windows.last().unwrap().end, 10);
+        assert!(windows.windows(2).all(|pair| pair[0].end == pair[1].start));
+
+        assert_eq!(segment_windows(&segments[..1], 5), vec![0..1]);
+        assert_eq!(segment_id(&segments, Some(1)), Some(segments[0].id.clone()));
+        assert_eq!(segment_id(&segments, Some(0)), None);
+        assert_eq!(segment_id(&segments, Some(11)), None);
+    }
+}
@@ -1,7 +1,16 @@
+use crate::summary::processor::rough_token_count;
+use crate::summary::provider::{Completion, CompletionRequest, SummaryError, SummaryProvider, TokenUsage};
+use crate::summary::streaming::{parse_anthropic_event, parse_openai_event, AnthropicEvent, SseParser};
+use async_trait::async_trait;
+use futures_util::StreamExt;
 use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
+pub const DEFAULT_OLLAMA_ENDPOINT: &str = "http://localhost:11434";
+/// Where `llama-server` listens by default
+pub const DEFAULT_LLAMA_CPP_ENDPOINT: &str = "http://localhost:8080";
+
 // Generic structure for OpenAI-compatible API chat messages
 #[derive(Debug, Serialize)]
 pub struct ChatMessage {
@@ -14,12 +23,20 @@ pub struct ChatMessage {
 pub struct ChatRequest {
     pub model: String,
     pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<serde_json::Value>,
 }
 
 // Generic structure for OpenAI-compatible API chat responses
 #[derive(Deserialize, Debug)]
 pub struct ChatResponse {
     pub choices: Vec<Choice>,
+    #[serde(default)]
+    pub usage: Option<ChatUsage>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -32,6 +49,12 @@ pub struct MessageContent {
     pub content: String,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct ChatUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
 // Claude-specific request structure
 #[derive(Debug, Serialize)]
 pub struct ClaudeRequest {
@@ -39,12 +62,16 @@ pub struct ClaudeRequest {
     pub max_tokens: u32,
     pub system: String,
     pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub stream: bool,
 }
 
 // Claude-specific response structure
 #[derive(Deserialize, Debug)]
 pub struct ClaudeChatResponse {
     pub content: Vec<ClaudeChatContent>,
+    #[serde(default)]
+    pub usage: Option<ClaudeUsage>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -52,17 +79,37 @@ pub struct ClaudeChatContent {
     pub text: String,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct ClaudeUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
 /// LLM Provider enumeration for multi-provider support
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LLMProvider {
     OpenAI,
     Claude,
     Groq,
     Ollama,
     OpenRouter,
+    /// A local `llama-server` (llama.cpp) with its OpenAI-compatible API
+    LlamaCpp,
+    /// The app's own llama.cpp runtime with a downloaded model (`builtin_llm`)
+    Builtin,
 }
 
 impl LLMProvider {
+    pub const ALL: [LLMProvider; 7] = [
+        Self::Builtin,
+        Self::Ollama,
+        Self::LlamaCpp,
+        Self::OpenAI,
+        Self::Claude,
+        Self::Groq,
+        Self::OpenRouter,
+    ];
+
     /// Parse provider from string (case-insensitive)
     pub fn from_str(s: &str) -> Result<Self, String> {
         match s.to_lowercase().as_str() {
@@ -71,177 +118,368 @@ impl LLMProvider {
             "groq" => Ok(Self::Groq),
             "ollama" => Ok(Self::Ollama),
             "openrouter" => Ok(Self::OpenRouter),
+            "llamacpp" | "llama.cpp" | "llama-cpp" => Ok(Self::LlamaCpp),
+            "builtin" => Ok(Self::Builtin),
             _ => Err(format!("Unsupported LLM provider: {}", s)),
         }
     }
+
+    /// Identifier used in settings and commands
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::OpenAI => "openai",
+            Self::Claude => "claude",
+            Self::Groq => "groq",
+            Self::Ollama => "ollama",
+            Self::OpenRouter => "openrouter",
+            Self::LlamaCpp => "llamacpp",
+            Self::Builtin => "builtin",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::OpenAI => "OpenAI",
+            Self::Claude => "Claude",
+            Self::Groq => "Groq",
+            Self::Ollama => "Ollama",
+            Self::OpenRouter => "OpenRouter",
+            Self::LlamaCpp => "llama.cpp",
+            Self::Builtin => "Built-in (llama.cpp)",
+        }
+    }
+
+    /// The transcript never leaves the machine
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::Ollama | Self::LlamaCpp | Self::Builtin)
+    }
+
+    pub fn requires_api_key(&self) -> bool {
+        !self.is_local()
+    }
+
+    pub fn default_model(&self) -> &'static str {
+        match self {
+            Self::OpenAI => "gpt-4o-2024-11-20",
+            Self::Claude => "claude-3-5-sonnet-latest",
+            Self::Groq => "llama-3.3-70b-versatile",
+            Self::Ollama => "llama3.2:latest",
+            Self::OpenRouter => "openai/gpt-4o",
+            // llama-server serves whichever model it was started with
+            Self::LlamaCpp => "default",
+            Self::Builtin => "qwen2.5-1.5b-instruct",
+        }
+    }
 }
 
-/// Generates a summary using the specified LLM provider
-///
-/// # Arguments
-/// * `client` - Reqwest HTTP client (reused for performance)
-/// * `provider` - The LLM provider to use
-/// * `model_name` - The specific model to use (e.g., "gpt-4", "claude-3-opus")
-/// * `api_key` - API key for the provider (not needed for Ollama)
-/// * `system_prompt` - System instructions for the LLM
-/// * `user_prompt` - User query/content to process
-/// * `ollama_endpoint` - Optional custom Ollama endpoint (defaults to localhost:11434)
+/// Creates the provider for a summary
 ///
-/// # Returns
-/// The generated summary text or an error message
-pub async fn generate_summary(
+/// `endpoint` is the server address for Ollama, llama.cpp and the built-in model; hosted
+/// providers ignore it.
+pub fn create_provider(
     client: &Client,
-    provider: &LLMProvider,
+    provider: LLMProvider,
     model_name: &str,
     api_key: &str,
-    system_prompt: &str,
-    user_prompt: &str,
-    ollama_endpoint: Option<&str>,
-) -> Result<String, String> {
-    let (api_url, mut headers) = match provider {
-        LLMProvider::OpenAI => (
-            "https://api.openai.com/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::Groq => (
-            "https://api.groq.com/openai/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::OpenRouter => (
-            "https://openrouter.ai/api/v1/chat/completions".to_string(),
-            header::HeaderMap::new(),
-        ),
-        LLMProvider::Ollama => {
-            let host = ollama_endpoint
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| "http://localhost:11434".to_string());
-            (
-                format!("{}/v1/chat/completions", host),
-                header::HeaderMap::new(),
-            )
-        }
+    endpoint: Option<&str>,
+) -> Result<Box<dyn SummaryProvider>, SummaryError> {
+    if provider.requires_api_key() && api_key.is_empty() {
+        return Err(SummaryError::MissingApiKey(provider.id().to_string()));
+    }
+    let host = |default: &str| endpoint.filter(|e| !e.is_empty()).unwrap_or(default).trim_end_matches('/').to_string();
+
+    let chat_url = match provider {
         LLMProvider::Claude => {
-            let mut header_map = header::HeaderMap::new();
-            header_map.insert(
-                "x-api-key",
-                api_key
-                    .parse()
-                    .map_err(|_| "Invalid API key format".to_string())?,
-            );
-            header_map.insert(
-                "anthropic-version",
-                "2023-06-01"
-                    .parse()
-                    .map_err(|_| "Invalid anthropic version".to_string())?,
-            );
-            ("https://api.anthropic.com/v1/messages".to_string(), header_map)
+            return Ok(Box::new(AnthropicProvider {
+                client: client.clone(),
+                api_key: api_key.to_string(),
+                model: model_name.to_string(),
+            }))
+        }
+        LLMProvider::OpenAI => "https://api.openai.com/v1/chat/completions".to_string(),
+        LLMProvider::Groq => "https://api.groq.com/openai/v1/chat/completions".to_string(),
+        LLMProvider::OpenRouter => "https://openrouter.ai/api/v1/chat/completions".to_string(),
+        LLMProvider::Ollama => format!("{}/v1/chat/completions", host(DEFAULT_OLLAMA_ENDPOINT)),
+        LLMProvider::LlamaCpp | LLMProvider::Builtin => {
+            format!("{}/v1/chat/completions", host(DEFAULT_LLAMA_CPP_ENDPOINT))
         }
     };
+    Ok(Box::new(OpenAiCompatibleProvider {
+        client: client.clone(),
+        kind: provider,
+        chat_url,
+        api_key: api_key.to_string(),
+        model: model_name.to_string(),
+    }))
+}
+
+/// Map a failed HTTP response to an error, keeping the provider's message and Retry-After
+async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, SummaryError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let retry_after = response
+        .headers()
+        .get(header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok());
+    let message = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+    Err(SummaryError::Api { status: status.as_u16(), message, retry_after })
+}
+
+/// Usage for providers that don't report it
+fn estimated_usage(request: &CompletionRequest, text: &str) -> TokenUsage {
+    TokenUsage {
+        prompt_tokens: (rough_token_count(&request.system_prompt) + rough_token_count(&request.user_prompt)) as u64,
+        completion_tokens: rough_token_count(text) as u64,
+        estimated: true,
+    }
+}
 
-    // Add authorization header for non-Claude providers
-    if provider != &LLMProvider::Claude {
-        headers.insert(
-            header::AUTHORIZATION,
-            format!("Bearer {}", api_key)
-                .parse()
-                .map_err(|_| "Invalid authorization header".to_string())?,
-        );
-    }
-    headers.insert(
-        header::CONTENT_TYPE,
-        "application/json"
-            .parse()
-            .map_err(|_| "Invalid content type".to_string())?,
-    );
-
-    // Build request body based on provider
-    let request_body = if provider != &LLMProvider::Claude {
-        serde_json::json!(ChatRequest {
-            model: model_name.to_string(),
+// ============================================================================
+// OPENAI-COMPATIBLE (OpenAI, Groq, OpenRouter, Ollama, llama.cpp, built-in)
+// ============================================================================
+
+pub struct OpenAiCompatibleProvider {
+    client: Client,
+    kind: LLMProvider,
+    chat_url: String,
+    api_key: String,
+    model: String,
+}
+
+impl OpenAiCompatibleProvider {
+    async fn send(&self, request: &CompletionRequest, stream: bool) -> Result<reqwest::Response, SummaryError> {
+        let body = ChatRequest {
+            model: self.model.clone(),
             messages: vec![
-                ChatMessage {
-                    role: "system".to_string(),
-                    content: system_prompt.to_string(),
-                },
-                ChatMessage {
-                    role: "user".to_string(),
-                    content: user_prompt.to_string(),
-                }
+                ChatMessage { role: "system".to_string(), content: request.system_prompt.clone() },
+                ChatMessage { role: "user".to_string(), content: request.user_prompt.clone() },
             ],
-        })
-    } else {
-        serde_json::json!(ClaudeRequest {
-            system: system_prompt.to_string(),
-            model: model_name.to_string(),
-            max_tokens: 2048,
-            messages: vec![ChatMessage {
-                role: "user".to_string(),
-                content: user_prompt.to_string(),
-            }]
-        })
-    };
+            max_tokens: request.max_tokens,
+            stream,
+            // OpenAI only reports usage of a stream when asked to
+            stream_options: (stream && self.kind == LLMProvider::OpenAI)
+                .then(|| serde_json::json!({ "include_usage": true })),
+        };
+
+        info!("🐞 LLM Request to {}: model={}, stream={}", self.provider_name(), self.model, stream);
+
+        let mut http_request = self.client.post(&self.chat_url).json(&body);
+        if !self.api_key.is_empty() {
+            http_request = http_request.bearer_auth(&self.api_key);
+        }
+        let response = http_request.send().await.map_err(|e| SummaryError::Request(e.to_string()))?;
+        check_status(response).await
+    }
+}
 
-    info!("🐞 LLM Request to {}: model={}", provider_name(provider), model_name);
+#[async_trait]
+impl SummaryProvider for OpenAiCompatibleProvider {
+    async fn complete(&self, request: &CompletionRequest) -> Result<Completion, SummaryError> {
+        let chat_response = self
+            .send(request, false)
+            .await?
+            .json::<ChatResponse>()
+            .await
+            .map_err(|e| SummaryError::InvalidResponse(e.to_string()))?;
+
+        info!("🐞 LLM Response received from {}", self.provider_name());
+
+        let text = chat_response
+            .choices
+            .first()
+            .ok_or_else(|| SummaryError::InvalidResponse("No content in LLM response".to_string()))?
+            .message
+            .content
+            .trim()
+            .to_string();
+        let usage = match chat_response.usage {
+            Some(usage) => TokenUsage {
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                estimated: false,
+            },
+            None => estimated_usage(request, &text),
+        };
+        Ok(Completion { text, usage })
+    }
+
+    async fn complete_streaming(
+        &self,
+        request: &CompletionRequest,
+        on_delta: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<Completion, SummaryError> {
+        let mut stream = self.send(request, true).await?.bytes_stream();
+        let mut parser = SseParser::default();
+        let mut text = String::new();
+        let mut usage = None;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| SummaryError::Request(e.to_string()))?;
+            for data in parser.feed(&chunk) {
+                let (delta, chunk_usage) = parse_openai_event(&data);
+                if let Some(delta) = delta {
+                    on_delta(&delta);
+                    text.push_str(&delta);
+                }
+                if chunk_usage.is_some() {
+                    usage = chunk_usage;
+                }
+            }
+        }
+
+        info!("🐞 LLM stream finished from {}", self.provider_name());
+        let usage = usage.unwrap_or_else(|| estimated_usage(request, &text));
+        Ok(Completion { text: text.trim().to_string(), usage })
+    }
 
-    // Send request
-    let response = client
-        .post(api_url)
-        .headers(headers)
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.kind.display_name()
+    }
 
-    if !response.status().is_success() {
-        let error_body = response
-            .text()
+    fn is_remote(&self) -> bool {
+        !self.kind.is_local()
+    }
+}
+
+// ============================================================================
+// ANTHROPIC (Claude)
+// ============================================================================
+
+pub struct AnthropicProvider {
+    client: Client,
+    api_key: String,
+    model: String,
+}
+
+impl AnthropicProvider {
+    /// The messages API requires an explicit limit
+    const DEFAULT_MAX_TOKENS: u32 = 2048;
+
+    async fn send(&self, request: &CompletionRequest, stream: bool) -> Result<reqwest::Response, SummaryError> {
+        let body = ClaudeRequest {
+            system: request.system_prompt.clone(),
+            model: self.model.clone(),
+            max_tokens: request.max_tokens.unwrap_or(Self::DEFAULT_MAX_TOKENS),
+            messages: vec![ChatMessage { role: "user".to_string(), content: request.user_prompt.clone() }],
+            stream,
+        };
+
+        info!("🐞 LLM Request to Claude: model={}, stream={}", self.model, stream);
+
+        let response = self
+            .client
+            .post("https://api.anthropic.com/v1/messages")
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .json(&body)
+            .send()
             .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("LLM API request failed: {}", error_body));
+            .map_err(|e| SummaryError::Request(e.to_string()))?;
+        check_status(response).await
     }
+}
 
-    // Parse response based on provider
-    if provider == &LLMProvider::Claude {
-        let chat_response = response
+#[async_trait]
+impl SummaryProvider for AnthropicProvider {
+    async fn complete(&self, request: &CompletionRequest) -> Result<Completion, SummaryError> {
+        let chat_response = self
+            .send(request, false)
+            .await?
             .json::<ClaudeChatResponse>()
             .await
-            .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+            .map_err(|e| SummaryError::InvalidResponse(e.to_string()))?;
 
         info!("🐞 LLM Response received from Claude");
 
-        let content = chat_response
+        let text = chat_response
             .content
-            .get(0)
-            .ok_or("No content in LLM response")?
+            .first()
+            .ok_or_else(|| SummaryError::InvalidResponse("No content in LLM response".to_string()))?
             .text
-            .trim();
-        Ok(content.to_string())
-    } else {
-        let chat_response = response
-            .json::<ChatResponse>()
-            .await
-            .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+            .trim()
+            .to_string();
+        let usage = match chat_response.usage {
+            Some(usage) => TokenUsage {
+                prompt_tokens: usage.input_tokens,
+                completion_tokens: usage.output_tokens,
+                estimated: false,
+            },
+            None => estimated_usage(request, &text),
+        };
+        Ok(Completion { text, usage })
+    }
 
-        info!("🐞 LLM Response received from {}", provider_name(provider));
+    async fn complete_streaming(
+        &self,
+        request: &CompletionRequest,
+        on_delta: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<Completion, SummaryError> {
+        let mut stream = self.send(request, true).await?.bytes_stream();
+        let mut parser = SseParser::default();
+        let mut text = String::new();
+        let mut usage = TokenUsage::default();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| SummaryError::Request(e.to_string()))?;
+            for data in parser.feed(&chunk) {
+                match parse_anthropic_event(&data) {
+                    AnthropicEvent::Text(delta) => {
+                        on_delta(&delta);
+                        text.push_str(&delta);
+                    }
+                    AnthropicEvent::InputTokens(tokens) => usage.prompt_tokens = tokens,
+                    AnthropicEvent::OutputTokens(tokens) => usage.completion_tokens = tokens,
+                    // e.g. "overloaded_error" in the middle of a stream
+                    AnthropicEvent::Error(message) => {
+                        return Err(SummaryError::Api { status: 529, message, retry_after: None })
+                    }
+                    AnthropicEvent::Other => {}
+                }
+            }
+        }
 
-        let content = chat_response
-            .choices
-            .get(0)
-            .ok_or("No content in LLM response")?
-            .message
-            .content
-            .trim();
-        Ok(content.to_string())
+        info!("🐞 LLM stream finished from Claude");
+        Ok(Completion { text: text.trim().to_string(), usage })
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Claude"
     }
 }
 
-/// Helper function to get provider name for logging
-fn provider_name(provider: &LLMProvider) -> &str {
-    match provider {
-        LLMProvider::OpenAI => "OpenAI",
-        LLMProvider::Claude => "Claude",
-        LLMProvider::Groq => "Groq",
-        LLMProvider::Ollama => "Ollama",
-        LLMProvider::OpenRouter => "OpenRouter",
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_ids_round_trip() {
+        for provider in LLMProvider::ALL {
+            assert_eq!(LLMProvider::from_str(provider.id()), Ok(provider));
+        }
+        assert_eq!(LLMProvider::from_str("llama.cpp"), Ok(LLMProvider::LlamaCpp));
+        assert!(LLMProvider::from_str("bard").is_err());
+    }
+
+    #[test]
+    fn test_hosted_providers_need_a_key() {
+        let client = crate::network::client();
+        assert!(matches!(
+            create_provider(&client, LLMProvider::OpenAI, "gpt-4o", "", None),
+            Err(SummaryError::MissingApiKey(_))
+        ));
+        let local = create_provider(&client, LLMProvider::LlamaCpp, "default", "", Some("http://127.0.0.1:9000/")).unwrap();
+        assert!(!local.is_remote());
+        assert_eq!(local.provider_name(), "llama.cpp");
+        let builtin = create_provider(&client, LLMProvider::Builtin, "qwen2.5-1.5b-instruct", "", Some("http://127.0.0.1:9001")).unwrap();
+        assert!(!builtin.is_remote());
     }
 }
@@ -1,33 +1,56 @@
 /// Summary module - handles all meeting summary generation functionality
 ///
 /// This module contains:
-/// - LLM client for communicating with various AI providers (OpenAI, Claude, Groq, Ollama, OpenRouter)
+/// - Summary provider trait with retry/backoff and token usage (`provider`)
+/// - LLM clients for the providers (OpenAI, Claude, Groq, Ollama, OpenRouter, llama.cpp)
+/// - The built-in model, run by a bundled llama.cpp server (`builtin_llm`)
+/// - API keys in the OS credential store (`credentials`)
 /// - Processor for chunking transcripts and generating summaries
+/// - Token-budget aware prompts: condensing and reporting what didn't fit (`prompt_builder`)
 /// - Service layer for orchestrating summary generation
+/// - Structured extraction of action items, decisions, open questions and risks linked to
+///   transcript segments (`extraction`, `action_items`, `key_points`)
+/// - Streaming of the report to the frontend as it is generated (`streaming`)
 /// - Templates for structured meeting summary generation
 /// - Tauri commands for frontend integration
 
+pub mod action_items;
+pub mod builtin_llm;
 pub mod commands;
+pub mod credentials;
+pub mod extraction;
+pub mod key_points;
 pub mod llm_client;
 pub mod processor;
+pub mod prompt_builder;
+pub use meeting_core::summary::provider;
+pub mod provider_commands;
 pub mod service;
+pub mod streaming;
 pub mod template_commands;
 pub mod templates;
 
 // Re-export Tauri commands (with their generated __cmd__ variants)
 pub use commands::{
-    __cmd__api_get_summary, __cmd__api_process_transcript, __cmd__api_save_meeting_summary,
-    api_get_summary, api_process_transcript, api_save_meeting_summary,
+    __cmd__api_cancel_summary, __cmd__api_get_summary, __cmd__api_process_transcript,
+    __cmd__api_save_meeting_summary, __cmd__delete_summary_version, __cmd__list_summary_versions,
+    __cmd__regenerate_summary, __cmd__restore_summary_version, api_cancel_summary, api_get_summary,
+    api_process_transcript, api_save_meeting_summary, delete_summary_version, list_summary_versions,
+    regenerate_summary, restore_summary_version,
 };
 
 // Re-export template commands
 pub use template_commands::{
-    __cmd__api_get_template_details, __cmd__api_list_templates, __cmd__api_validate_template,
-    api_get_template_details, api_list_templates, api_validate_template,
+    __cmd__api_delete_template, __cmd__api_get_meeting_template, __cmd__api_get_template,
+    __cmd__api_get_template_details, __cmd__api_list_templates, __cmd__api_save_template,
+    __cmd__api_set_meeting_template, __cmd__api_validate_template, api_delete_template,
+    api_get_meeting_template, api_get_template, api_get_template_details, api_list_templates,
+    api_save_template, api_set_meeting_template, api_validate_template,
 };
 
 // Re-export commonly used items
 pub use llm_client::LLMProvider;
+pub use provider::{SummaryError, SummaryProvider, TokenUsage};
 pub use processor::{
     chunk_text, clean_llm_markdown_output, extract_meeting_name_from_markdown,
     generate_meeting_summary, rough_token_count,
@@ -1,8 +1,11 @@
-use crate::summary::llm_client::{generate_summary, LLMProvider};
-use crate::summary::templates;
+use crate::summary::provider::{
+    complete_with_retry, stream_with_retry, CompletionRequest, RetryPolicy, SummaryProvider, TokenUsage,
+};
+use crate::summary::prompt_builder::{condense, InputReport, PromptBuilder};
+use crate::summary::templates::{self, TemplateVariables};
 use regex::Regex;
-use reqwest::Client;
-use tracing::{error, info};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
 
 /// Rough token count estimation (4 characters ≈ 1 token)
 pub fn rough_token_count(s: &str) -> usize {
@@ -24,14 +27,16 @@ pub fn chunk_text(text: &str, chunk_size_tokens: usize, overlap_tokens: usize) -
         chunk_size_tokens, overlap_tokens
     );
 
-    if text.is_empty() || chunk_size_tokens == 0 {
+    // Convert token-based sizes to character-based sizes (4 chars ≈ 1 token)
+    chunk_text_chars(text, chunk_size_tokens * 4, overlap_tokens * 4)
+}
+
+/// Like `chunk_text`, with the window and overlap in characters
+pub fn chunk_text_chars(text: &str, chunk_size_chars: usize, overlap_chars: usize) -> Vec<String> {
+    if text.is_empty() || chunk_size_chars == 0 {
         return vec![];
     }
 
-    // Convert token-based sizes to character-based sizes (4 chars ≈ 1 token)
-    let chunk_size_chars = chunk_size_tokens * 4;
-    let overlap_chars = overlap_tokens * 4;
-
     let chars: Vec<char> = text.chars().collect();
     let total_chars = chars.len();
 
@@ -117,136 +122,185 @@ pub fn extract_meeting_name_from_markdown(markdown: &str) -> Option<String> {
         .map(|line| line.trim_start_matches("# ").trim().to_string())
 }
 
+/// Tokens reserved for the instructions around each map or reduce prompt
+const PROMPT_OVERHEAD_TOKENS: usize = 300;
+const DEFAULT_OVERLAP_TOKENS: usize = 100;
+
+/// Window size and overlap for map-reduce summarization
+///
+/// Unset values follow the model's context; sizes larger than the context are capped.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChunkingOptions {
+    pub chunk_tokens: Option<usize>,
+    pub overlap_tokens: Option<usize>,
+}
+
+impl ChunkingOptions {
+    /// (chunk, overlap) in tokens for a model that fits `token_threshold` tokens
+    pub fn resolve(&self, token_threshold: usize) -> (usize, usize) {
+        let max_chunk = token_threshold.saturating_sub(PROMPT_OVERHEAD_TOKENS).max(1);
+        let chunk = self.chunk_tokens.filter(|&c| c > 0).map_or(max_chunk, |c| c.min(max_chunk));
+        // Overlap only has to carry context across the cut
+        let overlap = self.overlap_tokens.unwrap_or(DEFAULT_OVERLAP_TOKENS).min(chunk / 4);
+        (chunk, overlap)
+    }
+}
+
+/// Groups consecutive partial summaries so each group fits `budget_tokens`
+///
+/// Always makes progress: if no two neighbours fit together they are paired anyway.
+pub fn reduce_groups(summaries: &[String], budget_tokens: usize) -> Vec<Vec<String>> {
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    let mut group_tokens = 0;
+    for summary in summaries {
+        let tokens = rough_token_count(summary);
+        match groups.last_mut() {
+            Some(group) if group_tokens + tokens <= budget_tokens => {
+                group.push(summary.clone());
+                group_tokens += tokens;
+            }
+            _ => {
+                groups.push(vec![summary.clone()]);
+                group_tokens = tokens;
+            }
+        }
+    }
+    if summaries.len() > 1 && groups.len() == summaries.len() {
+        return summaries.chunks(2).map(|pair| pair.to_vec()).collect();
+    }
+    groups
+}
+
+/// Summarizes overlapping transcript windows, then merges the partial summaries in
+/// rounds until one remains
+///
+/// Returns the merged summary and the number of windows summarized; windows that failed
+/// are added to `report`.
+async fn map_reduce(
+    provider: &dyn SummaryProvider,
+    builder: &PromptBuilder,
+    text: &str,
+    token_threshold: usize,
+    chunking: &ChunkingOptions,
+    usage: &mut TokenUsage,
+    report: &mut InputReport,
+) -> Result<(String, i64), String> {
+    let (chunk_tokens, overlap_tokens) = chunking.resolve(token_threshold);
+    let chunks = builder.chunk(text, chunk_tokens, overlap_tokens);
+    let num_chunks = chunks.len();
+    report.chunks = num_chunks;
+    info!("Split transcript into {} chunks", num_chunks);
+
+    let mut chunk_summaries = Vec::new();
+    let system_prompt_chunk = "You are an expert meeting summarizer.";
+    for (i, chunk) in chunks.iter().enumerate() {
+        info!("⏲️ Processing chunk {}/{}", i + 1, num_chunks);
+        let user_prompt_chunk = format!(
+            "Provide a concise but comprehensive summary of the following transcript chunk (part {} of {} of the meeting). Capture all key points, decisions, action items, and mentioned individuals.\n\n<transcript_chunk>\n{}\n</transcript_chunk>",
+            i + 1,
+            num_chunks,
+            chunk
+        );
+
+        match generate_summary(provider, system_prompt_chunk, &user_prompt_chunk, usage).await {
+            Ok(summary) => {
+                chunk_summaries.push(summary);
+                info!("✓ Chunk {}/{} processed successfully", i + 1, num_chunks);
+            }
+            Err(e) => {
+                error!("⚠️ Failed processing chunk {}/{}: {}", i + 1, num_chunks, e);
+                report.skipped_chunks.push(i + 1);
+            }
+        }
+    }
+
+    if chunk_summaries.is_empty() {
+        return Err("Multi-level summarization failed: No chunks were processed successfully.".to_string());
+    }
+    let successful_chunk_count = chunk_summaries.len() as i64;
+    info!("Successfully processed {} out of {} chunks", successful_chunk_count, num_chunks);
+
+    let system_prompt_combine = "You are an expert at synthesizing meeting summaries.";
+    let budget = token_threshold.saturating_sub(PROMPT_OVERHEAD_TOKENS).max(1);
+    let mut round = 1;
+    while chunk_summaries.len() > 1 {
+        let groups = reduce_groups(&chunk_summaries, budget);
+        info!(
+            "Combining {} chunk summaries into {} (round {})",
+            chunk_summaries.len(),
+            groups.len(),
+            round
+        );
+        let mut merged = Vec::with_capacity(groups.len());
+        for group in groups {
+            if group.len() == 1 {
+                merged.extend(group);
+                continue;
+            }
+            let user_prompt_combine = format!(
+                "The following are consecutive summaries of a meeting. Combine them into a single, coherent, and detailed narrative summary that retains all important details, organized logically.\n\n<summaries>\n{}\n</summaries>",
+                group.join("\n---\n")
+            );
+            merged.push(generate_summary(provider, system_prompt_combine, &user_prompt_combine, usage).await?);
+        }
+        chunk_summaries = merged;
+        round += 1;
+    }
+
+    Ok((chunk_summaries.remove(0), successful_chunk_count))
+}
+
+/// Runs one completion with retries, adding its token usage to `usage`
+async fn generate_summary(
+    provider: &dyn SummaryProvider,
+    system_prompt: &str,
+    user_prompt: &str,
+    usage: &mut TokenUsage,
+) -> Result<String, String> {
+    let request = CompletionRequest::new(system_prompt, user_prompt);
+    let completion = complete_with_retry(provider, &request, &RetryPolicy::default())
+        .await
+        .map_err(|e| e.to_string())?;
+    usage.add(completion.usage);
+    Ok(completion.text)
+}
+
 /// Generates a complete meeting summary with conditional chunking strategy
 ///
 /// # Arguments
-/// * `client` - Reqwest HTTP client
-/// * `provider` - LLM provider to use
-/// * `model_name` - Specific model name
-/// * `api_key` - API key for the provider
+/// * `provider` - LLM provider to use, configured with its model
 /// * `text` - Full transcript text to summarize
 /// * `custom_prompt` - Optional user-provided context
 /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
+/// * `variables` - Meeting facts for the template's `{placeholders}`
 /// * `token_threshold` - Token limit for single-pass processing (default 4000)
-/// * `ollama_endpoint` - Optional custom Ollama endpoint
+/// * `chunking` - Window size and overlap for transcripts over the threshold
+/// * `on_delta` - Receives the final report's text as it is generated
 ///
 /// # Returns
-/// Tuple of (final_summary_markdown, number_of_chunks_processed)
+/// Tuple of (final_summary_markdown, number_of_chunks_processed, token_usage, input_report).
+/// When part of the transcript had to be left out, the markdown ends with a note saying so.
 pub async fn generate_meeting_summary(
-    client: &Client,
-    provider: &LLMProvider,
-    model_name: &str,
-    api_key: &str,
+    provider: &dyn SummaryProvider,
     text: &str,
     custom_prompt: &str,
     template_id: &str,
+    variables: &TemplateVariables,
     token_threshold: usize,
-    ollama_endpoint: Option<&str>,
-) -> Result<(String, i64), String> {
+    chunking: &ChunkingOptions,
+    on_delta: &mut (dyn FnMut(&str) + Send),
+) -> Result<(String, i64, TokenUsage, InputReport), String> {
     info!(
-        "Starting summary generation with provider: {:?}, model: {}",
-        provider, model_name
+        "Starting summary generation with provider: {}, model: {}",
+        provider.provider_name(),
+        provider.model()
     );
-
-    let total_tokens = rough_token_count(text);
-    info!("Transcript length: {} tokens", total_tokens);
-
-    let content_to_summarize: String;
-    let successful_chunk_count: i64;
-
-    // Strategy: Use single-pass for cloud providers or short transcripts
-    // Use multi-level chunking for Ollama with long transcripts
-    if provider != &LLMProvider::Ollama || total_tokens < token_threshold {
-        info!(
-            "Using single-pass summarization (tokens: {}, threshold: {})",
-            total_tokens, token_threshold
-        );
-        content_to_summarize = text.to_string();
-        successful_chunk_count = 1;
-    } else {
-        info!(
-            "Using multi-level summarization (tokens: {} exceeds threshold: {})",
-            total_tokens, token_threshold
-        );
-
-        // Reserve 300 tokens for prompt overhead
-        let chunks = chunk_text(text, token_threshold - 300, 100);
-        let num_chunks = chunks.len();
-        info!("Split transcript into {} chunks", num_chunks);
-
-        let mut chunk_summaries = Vec::new();
-        let system_prompt_chunk = "You are an expert meeting summarizer.";
-        let user_prompt_template_chunk = "Provide a concise but comprehensive summary of the following transcript chunk. Capture all key points, decisions, action items, and mentioned individuals.\n\n<transcript_chunk>\n{}\n</transcript_chunk>";
-
-        for (i, chunk) in chunks.iter().enumerate() {
-            info!("⏲️ Processing chunk {}/{}", i + 1, num_chunks);
-            let user_prompt_chunk = user_prompt_template_chunk.replace("{}", chunk.as_str());
-
-            match generate_summary(
-                client,
-                provider,
-                model_name,
-                api_key,
-                system_prompt_chunk,
-                &user_prompt_chunk,
-                ollama_endpoint,
-            )
-            .await
-            {
-                Ok(summary) => {
-                    chunk_summaries.push(summary);
-                    info!("✓ Chunk {}/{} processed successfully", i + 1, num_chunks);
-                }
-                Err(e) => {
-                    error!("⚠️ Failed processing chunk {}/{}: {}", i + 1, num_chunks, e);
-                }
-            }
-        }
-
-        if chunk_summaries.is_empty() {
-            return Err(
-                "Multi-level summarization failed: No chunks were processed successfully."
-                    .to_string(),
-            );
-        }
-
-        successful_chunk_count = chunk_summaries.len() as i64;
-        info!(
-            "Successfully processed {} out of {} chunks",
-            successful_chunk_count, num_chunks
-        );
-
-        // Combine chunk summaries if multiple chunks
-        content_to_summarize = if chunk_summaries.len() > 1 {
-            info!(
-                "Combining {} chunk summaries into cohesive summary",
-                chunk_summaries.len()
-            );
-            let combined_text = chunk_summaries.join("\n---\n");
-            let system_prompt_combine = "You are an expert at synthesizing meeting summaries.";
-            let user_prompt_combine_template = "The following are consecutive summaries of a meeting. Combine them into a single, coherent, and detailed narrative summary that retains all important details, organized logically.\n\n<summaries>\n{}\n</summaries>";
-
-            let user_prompt_combine = user_prompt_combine_template.replace("{}", &combined_text);
-            generate_summary(
-                client,
-                provider,
-                model_name,
-                api_key,
-                system_prompt_combine,
-                &user_prompt_combine,
-                ollama_endpoint,
-            )
-            .await?
-        } else {
-            chunk_summaries.remove(0)
-        };
-    }
-
-    info!("Generating final markdown report with template: {}", template_id);
+    let mut usage = TokenUsage::default();
 
     // Load the template using the provided template_id
     let template = templates::get_template(template_id)
-        .map_err(|e| format!("Failed to load template '{}': {}", template_id, e))?;
+        .map_err(|e| format!("Failed to load template '{}': {}", template_id, e))?
+        .render(variables);
 
     // Generate markdown structure and section instructions using template methods
     let clean_template_markdown = template.to_markdown_structure();
@@ -272,36 +326,117 @@ pub async fn generate_meeting_summary(
 "#,
         section_instructions, clean_template_markdown
     );
+    let final_system_prompt = match template.prompt.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(prompt) => format!("{}\n**TEMPLATE GUIDANCE:**\n{}\n", final_system_prompt, prompt),
+        None => final_system_prompt,
+    };
+
+    let user_context = if custom_prompt.is_empty() {
+        String::new()
+    } else {
+        format!("\n\nUser Provided Context:\n\n<user_context>\n{}\n</user_context>", custom_prompt)
+    };
+
+    // What the transcript can take up of the final prompt, counted for this model
+    let builder = PromptBuilder::new(provider.model(), token_threshold);
+    let room = builder.room(&[&final_system_prompt, &user_context, "<transcript_chunks>\n</transcript_chunks>"]);
+    let mut report = InputReport { original_tokens: builder.count(text), chunks: 1, ..Default::default() };
+    report.tokens = report.original_tokens;
+    info!("Transcript length: {} tokens, room in the prompt: {} tokens", report.original_tokens, room);
+
+    // Strategy: single pass when the transcript fits the model's context, if need be after
+    // condensing it, otherwise map-reduce: summarize overlapping windows, then merge the
+    // partial summaries
+    let text = if report.original_tokens <= room {
+        text.to_string()
+    } else {
+        let (condensed, fillers, repeated) = condense(text);
+        report.tokens = builder.count(&condensed);
+        report.fillers_removed = fillers;
+        report.repeated_lines_removed = repeated;
+        info!(
+            "Condensed transcript to {} tokens ({} filler words, {} repeated lines removed)",
+            report.tokens, fillers, repeated
+        );
+        condensed
+    };
+    let (content_to_summarize, successful_chunk_count) = if report.tokens <= room {
+        info!(
+            "Using single-pass summarization (tokens: {}, threshold: {})",
+            report.tokens, room
+        );
+        (text, 1)
+    } else {
+        info!(
+            "Using map-reduce summarization (tokens: {} exceeds threshold: {})",
+            report.tokens, room
+        );
+        map_reduce(provider, &builder, &text, token_threshold, chunking, &mut usage, &mut report).await?
+    };
+
+    // Merged partial summaries normally fit; if they don't, the cut is reported
+    let (content_to_summarize, truncated_tokens) = builder.fit(&content_to_summarize, room);
+    report.truncated_tokens = truncated_tokens;
+    if let Some(note) = report.note() {
+        warn!("⚠️ {}", note);
+    }
+
+    info!("Generating final markdown report with template: {}", template_id);
 
-    let mut final_user_prompt = format!(
+    let final_user_prompt = format!(
         r#"
 <transcript_chunks>
 {}
 </transcript_chunks>
-"#,
-        content_to_summarize
+{}"#,
+        content_to_summarize, user_context
     );
 
-    if !custom_prompt.is_empty() {
-        final_user_prompt.push_str("\n\nUser Provided Context:\n\n<user_context>\n");
-        final_user_prompt.push_str(custom_prompt);
-        final_user_prompt.push_str("\n</user_context>");
+    // The final report is streamed so it can be read while it is written
+    let request = CompletionRequest::new(final_system_prompt, final_user_prompt);
+    let completion = stream_with_retry(provider, &request, &RetryPolicy::default(), on_delta)
+        .await
+        .map_err(|e| e.to_string())?;
+    usage.add(completion.usage);
+    let raw_markdown = completion.text;
+
+    // Clean the output
+    let mut final_markdown = clean_llm_markdown_output(&raw_markdown);
+    if let Some(note) = report.note() {
+        let note = format!("\n\n> **Note:** {}", note);
+        on_delta(&note);
+        final_markdown.push_str(&note);
     }
 
-    let raw_markdown = generate_summary(
-        client,
-        provider,
-        model_name,
-        api_key,
-        &final_system_prompt,
-        &final_user_prompt,
-        ollama_endpoint,
-    )
-    .await?;
+    info!(
+        "Summary generation completed successfully ({} tokens{})",
+        usage.total_tokens(),
+        if usage.estimated { ", estimated" } else { "" }
+    );
+    Ok((final_markdown, successful_chunk_count, usage, report))
+}
 
-    // Clean the output
-    let final_markdown = clean_llm_markdown_output(&raw_markdown);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunking_is_capped_by_the_model_context() {
+        assert_eq!(ChunkingOptions::default().resolve(4000), (3700, 100));
+        let legacy = ChunkingOptions { chunk_tokens: Some(40000), overlap_tokens: Some(1000) };
+        assert_eq!(legacy.resolve(4000), (3700, 925));
+        assert_eq!(legacy.resolve(100000), (40000, 1000));
+    }
 
-    info!("Summary generation completed successfully");
-    Ok((final_markdown, successful_chunk_count))
+    #[test]
+    fn test_reduce_groups_fit_budget_and_make_progress() {
+        let summary = |tokens: usize| "x".repeat(tokens * 4);
+        let summaries = vec![summary(40), summary(40), summary(40), summary(90)];
+        let groups = reduce_groups(&summaries, 100);
+        assert_eq!(groups.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 1, 1]);
+
+        // Nothing fits together: pair up anyway so the reduction terminates
+        let large = vec![summary(80), summary(80), summary(80)];
+        assert_eq!(reduce_groups(&large, 100).iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 1]);
+    }
 }
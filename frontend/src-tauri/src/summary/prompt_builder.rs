@@ -0,0 +1,279 @@
+// summary/prompt_builder.rs
+//
+// Fits transcript text into the selected model's context. Tokens are counted with an
+// estimate for the model's tokenizer family; a transcript that doesn't fit is first
+// condensed (filler words and repeated lines removed, which also catches Whisper's
+// "Thank you." loops), then split for map-reduce. Whatever still had to be left out is
+// recorded in an `InputReport`, so a summary says so instead of quietly missing the end.
+
+use serde::Serialize;
+
+use crate::summary::processor::chunk_text_chars;
+
+/// Average characters per token of a tokenizer family, matched on the model name's start
+/// (after any `vendor/` prefix); newer, larger vocabularies pack more text per token
+const MODEL_FAMILIES: &[(&str, f64)] = &[
+    ("gpt-4o", 4.2),
+    ("gpt-4.1", 4.2),
+    ("gpt-5", 4.2),
+    ("o1", 4.2),
+    ("o3", 4.2),
+    ("o4", 4.2),
+    ("gpt-4", 4.0),
+    ("gpt-3.5", 4.0),
+    ("claude", 3.5),
+    ("llama3", 4.0),
+    ("llama-3", 4.0),
+    ("gemma", 4.0),
+    ("qwen", 3.8),
+    ("deepseek", 3.8),
+    ("phi", 3.6),
+    ("llama2", 3.4),
+    ("llama-2", 3.4),
+    ("mistral", 3.4),
+    ("mixtral", 3.4),
+];
+
+/// Unknown models are counted conservatively, so their prompts err on the short side
+const DEFAULT_CHARS_PER_TOKEN: f64 = 3.5;
+/// Non-Latin scripts (CJK above all) take far more tokens per character
+const NON_ASCII_TOKENS_PER_CHAR: f64 = 0.75;
+
+/// Words that carry nothing for a summary when they stand alone
+const FILLERS: &[&str] = &["um", "umm", "uh", "uhh", "uhm", "erm", "er", "ah", "hmm", "mm", "mhm", "uh-huh"];
+
+/// Estimates token counts for one model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenCounter {
+    chars_per_token: f64,
+}
+
+impl TokenCounter {
+    pub fn for_model(model: &str) -> Self {
+        let model = model.to_lowercase();
+        let name = model.rsplit('/').next().unwrap_or(&model);
+        let chars_per_token = MODEL_FAMILIES
+            .iter()
+            .find(|(prefix, _)| name.starts_with(prefix))
+            .map_or(DEFAULT_CHARS_PER_TOKEN, |(_, chars_per_token)| *chars_per_token);
+        Self { chars_per_token }
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        text.chars().map(|c| self.char_tokens(c)).sum::<f64>().ceil() as usize
+    }
+
+    fn char_tokens(&self, c: char) -> f64 {
+        if c.is_ascii() {
+            1.0 / self.chars_per_token
+        } else {
+            NON_ASCII_TOKENS_PER_CHAR
+        }
+    }
+
+    /// How many characters of `text` make up about `tokens` tokens
+    fn chars_for(&self, tokens: usize, text: &str) -> usize {
+        let chars = text.chars().count();
+        let total = self.count(text).max(1);
+        (tokens as f64 * chars as f64 / total as f64) as usize
+    }
+}
+
+/// What happened to the transcript on its way into the prompt
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct InputReport {
+    /// Transcript tokens before condensing, for the selected model
+    pub original_tokens: usize,
+    /// Transcript tokens after condensing
+    pub tokens: usize,
+    pub fillers_removed: usize,
+    pub repeated_lines_removed: usize,
+    /// Map-reduce windows; 1 for a single pass
+    pub chunks: usize,
+    /// 1-based windows that couldn't be summarized and aren't covered
+    pub skipped_chunks: Vec<usize>,
+    /// Tokens cut from the end of the final prompt to fit the context
+    pub truncated_tokens: usize,
+}
+
+impl InputReport {
+    /// Whether part of the transcript isn't reflected in the summary
+    pub fn is_lossy(&self) -> bool {
+        !self.skipped_chunks.is_empty() || self.truncated_tokens > 0
+    }
+
+    /// A sentence for the reader of the summary when something was left out
+    pub fn note(&self) -> Option<String> {
+        let mut notes = Vec::new();
+        if !self.skipped_chunks.is_empty() {
+            let parts = self.skipped_chunks.iter().map(usize::to_string).collect::<Vec<_>>().join(", ");
+            let end = if self.skipped_chunks.last() == Some(&self.chunks) { ", including the end of the meeting" } else { "" };
+            notes.push(format!(
+                "Part{} {} of {} of the transcript couldn't be summarized and {} not covered{}.",
+                if self.skipped_chunks.len() == 1 { "" } else { "s" },
+                parts,
+                self.chunks,
+                if self.skipped_chunks.len() == 1 { "is" } else { "are" },
+                end
+            ));
+        }
+        if self.truncated_tokens > 0 {
+            notes.push(format!(
+                "The input was too long for the model, so its last ~{} tokens were left out.",
+                self.truncated_tokens
+            ));
+        }
+        (!notes.is_empty()).then(|| notes.join(" "))
+    }
+}
+
+/// Builds summary prompts that fit the model's context
+#[derive(Debug, Clone, Copy)]
+pub struct PromptBuilder {
+    counter: TokenCounter,
+    context_tokens: usize,
+}
+
+impl PromptBuilder {
+    /// `context_tokens` is how much the model takes in one request
+    pub fn new(model: &str, context_tokens: usize) -> Self {
+        Self { counter: TokenCounter::for_model(model), context_tokens }
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        self.counter.count(text)
+    }
+
+    /// Tokens left for the transcript next to the fixed parts of a prompt
+    pub fn room(&self, fixed: &[&str]) -> usize {
+        let fixed: usize = fixed.iter().map(|part| self.count(part)).sum();
+        self.context_tokens.saturating_sub(fixed)
+    }
+
+    /// Windows of about `chunk_tokens` model tokens, overlapping by `overlap_tokens`
+    pub fn chunk(&self, text: &str, chunk_tokens: usize, overlap_tokens: usize) -> Vec<String> {
+        chunk_text_chars(text, self.counter.chars_for(chunk_tokens, text), self.counter.chars_for(overlap_tokens, text))
+    }
+
+    /// `text` cut at a line (or word) boundary to fit `room` tokens, with the tokens left out
+    pub fn fit(&self, text: &str, room: usize) -> (String, usize) {
+        let total = self.count(text);
+        if total <= room {
+            return (text.to_string(), 0);
+        }
+        let mut end = 0;
+        let mut tokens = 0.0;
+        for (i, c) in text.char_indices() {
+            tokens += self.counter.char_tokens(c);
+            if tokens.ceil() as usize > room {
+                break;
+            }
+            end = i + c.len_utf8();
+        }
+        let kept = &text[..end];
+        let cut = kept.rfind('\n').or_else(|| kept.rfind(char::is_whitespace)).unwrap_or(end);
+        let kept = kept[..cut].trim_end().to_string();
+        let dropped = total.saturating_sub(self.count(&kept));
+        (kept, dropped)
+    }
+}
+
+/// Removes filler words and lines that repeat the one before, returning the condensed text
+/// and the (fillers, lines) removed
+pub fn condense(text: &str) -> (String, usize, usize) {
+    let mut lines: Vec<String> = Vec::new();
+    let mut last_key = String::new();
+    let mut fillers = 0;
+    let mut repeated = 0;
+
+    for line in text.lines() {
+        let mut words = Vec::new();
+        for word in line.split_whitespace() {
+            let bare = word.trim_matches(|c: char| c.is_ascii_punctuation() && c != '-').to_lowercase();
+            if FILLERS.contains(&bare.as_str()) {
+                fillers += 1;
+                // Keep the sentence's end when the filler carried it
+                let end = word.chars().last().filter(|c| ".?!".contains(*c));
+                if let (Some(previous), Some(end)) = (words.last_mut(), end) {
+                    let previous: &mut String = previous;
+                    if previous.ends_with(',') {
+                        previous.pop();
+                    }
+                    if !previous.ends_with(['.', '?', '!']) {
+                        previous.push(end);
+                    }
+                }
+                continue;
+            }
+            words.push(word.to_string());
+        }
+        let condensed = words.join(" ");
+        if condensed.is_empty() {
+            continue;
+        }
+        let key: String = condensed.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect();
+        if !key.is_empty() && key == last_key {
+            repeated += 1;
+            continue;
+        }
+        last_key = key;
+        lines.push(condensed);
+    }
+    (lines.join("\n"), fillers, repeated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_counter_by_family() {
+        assert_eq!(TokenCounter::for_model("gpt-4o-2024-11-20").chars_per_token, 4.2);
+        assert_eq!(TokenCounter::for_model("openai/gpt-4-turbo").chars_per_token, 4.0);
+        assert_eq!(TokenCounter::for_model("llama3.2:latest").chars_per_token, 4.0);
+        assert_eq!(TokenCounter::for_model("Mistral:7b").chars_per_token, 3.4);
+        assert_eq!(TokenCounter::for_model("something-new").chars_per_token, DEFAULT_CHARS_PER_TOKEN);
+
+        let counter = TokenCounter::for_model("claude-3-5-sonnet-latest");
+        assert_eq!(counter.count("abcdefg"), 2);
+        assert_eq!(counter.count("会议纪要"), 3);
+        assert_eq!(counter.count(""), 0);
+    }
+
+    #[test]
+    fn test_condense() {
+        let text = "Um, so we ship on Friday, uh.\nThank you.\nthank you!\nThank you.\nUh\nRight, let's move on.";
+        let (condensed, fillers, repeated) = condense(text);
+        assert_eq!(condensed, "so we ship on Friday.\nThank you.\nRight, let's move on.");
+        assert_eq!((fillers, repeated), (3, 2));
+
+        let (unchanged, fillers, repeated) = condense("Errors are up.\nUmbrella policy is next.");
+        assert_eq!(unchanged, "Errors are up.\nUmbrella policy is next.");
+        assert_eq!((fillers, repeated), (0, 0));
+    }
+
+    #[test]
+    fn test_fit_cuts_at_a_line_and_reports_the_rest() {
+        let builder = PromptBuilder::new("gpt-4", 1000);
+        let text = "first line here\nsecond line here\nthird line here";
+        assert_eq!(builder.fit(text, 100), (text.to_string(), 0));
+
+        let (kept, dropped) = builder.fit(text, 9);
+        assert_eq!(kept, "first line here\nsecond line here");
+        assert_eq!(dropped, builder.count(text) - builder.count(&kept));
+        assert_eq!(builder.room(&["x".repeat(400).as_str()]), 900);
+    }
+
+    #[test]
+    fn test_report_note() {
+        assert_eq!(InputReport { chunks: 1, ..Default::default() }.note(), None);
+        let report = InputReport { chunks: 9, skipped_chunks: vec![3, 9], ..Default::default() };
+        assert!(report.is_lossy());
+        assert_eq!(
+            report.note().unwrap(),
+            "Parts 3, 9 of 9 of the transcript couldn't be summarized and are not covered, including the end of the meeting."
+        );
+        let truncated = InputReport { chunks: 4, truncated_tokens: 1200, ..Default::default() };
+        assert_eq!(truncated.note().unwrap(), "The input was too long for the model, so its last ~1200 tokens were left out.");
+    }
+}
@@ -0,0 +1,145 @@
+use crate::database::repositories::setting::SettingsRepository;
+use crate::state::AppState;
+use crate::summary::builtin_llm::{self, BuiltinLlmStatus};
+use crate::summary::credentials;
+use crate::summary::llm_client::LLMProvider;
+use log::{info as log_info, warn as log_warn};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+pub const BUILTIN_MODEL_DOWNLOAD_PROGRESS_EVENT: &str = "builtin-llm-download-progress";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SummaryProviderInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    /// The transcript never leaves the machine
+    pub local: bool,
+    pub requires_api_key: bool,
+    pub has_api_key: bool,
+    pub default_model: &'static str,
+    /// Model chosen for this provider, or the default
+    pub model: String,
+    /// Server address for local providers
+    pub endpoint: Option<String>,
+}
+
+#[tauri::command]
+pub async fn list_summary_providers(state: tauri::State<'_, AppState>) -> Result<Vec<SummaryProviderInfo>, String> {
+    let pool = state.db_manager.pool();
+    let saved = SettingsRepository::get_summary_provider_settings(pool)
+        .await
+        .map_err(|e| format!("Failed to load summary provider settings: {}", e))?;
+
+    let mut providers = Vec::new();
+    for provider in LLMProvider::ALL {
+        let setting = saved.iter().find(|s| s.provider == provider.id());
+        let has_api_key = provider.requires_api_key()
+            && credentials::resolve_api_key(pool, provider.id())
+                .await
+                .ok()
+                .flatten()
+                .is_some_and(|key| !key.is_empty());
+        providers.push(SummaryProviderInfo {
+            id: provider.id(),
+            name: provider.display_name(),
+            local: provider.is_local(),
+            requires_api_key: provider.requires_api_key(),
+            has_api_key,
+            default_model: provider.default_model(),
+            model: setting.map_or_else(|| provider.default_model().to_string(), |s| s.model.clone()),
+            endpoint: setting.and_then(|s| s.endpoint.clone()),
+        });
+    }
+    Ok(providers)
+}
+
+/// Remembers the model (and server address) to use with a provider
+#[tauri::command]
+pub async fn set_summary_provider_model(
+    state: tauri::State<'_, AppState>,
+    provider: String,
+    model: String,
+    endpoint: Option<String>,
+) -> Result<(), String> {
+    let provider = LLMProvider::from_str(&provider)?;
+    let model = model.trim();
+    if model.is_empty() {
+        return Err("Model name cannot be empty".to_string());
+    }
+    SettingsRepository::save_summary_provider_setting(
+        state.db_manager.pool(),
+        provider.id(),
+        model,
+        endpoint.as_deref().map(str::trim).filter(|e| !e.is_empty()),
+    )
+    .await
+    .map_err(|e| format!("Failed to save summary provider settings: {}", e))?;
+    log_info!("🤖 Summary model for {} set to {}", provider.display_name(), model);
+    Ok(())
+}
+
+/// Stores a provider's API key in the system credential store
+#[tauri::command]
+pub async fn save_summary_api_key(
+    state: tauri::State<'_, AppState>,
+    provider: String,
+    api_key: String,
+) -> Result<(), String> {
+    let provider = LLMProvider::from_str(&provider)?;
+    if !provider.requires_api_key() {
+        return Err(format!("{} doesn't use an API key", provider.display_name()));
+    }
+    let api_key = api_key.trim();
+    if api_key.is_empty() {
+        return Err("API key cannot be empty".to_string());
+    }
+    match credentials::store_api_key(provider.id(), api_key) {
+        Ok(()) => {
+            // Don't leave an older copy in the database
+            if let Err(e) = SettingsRepository::delete_api_key(state.db_manager.pool(), provider.id()).await {
+                log_warn!("Failed to clear database copy of the {} API key: {}", provider.id(), e);
+            }
+            Ok(())
+        }
+        Err(e) => {
+            log_warn!("{}; keeping the key in the settings database", e);
+            SettingsRepository::save_api_key(state.db_manager.pool(), provider.id(), api_key)
+                .await
+                .map_err(|e| format!("Failed to save API key: {}", e))
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn delete_summary_api_key(state: tauri::State<'_, AppState>, provider: String) -> Result<(), String> {
+    let provider = LLMProvider::from_str(&provider)?;
+    credentials::delete_api_key(provider.id()).map_err(|e| e.to_string())?;
+    if provider.requires_api_key() {
+        SettingsRepository::delete_api_key(state.db_manager.pool(), provider.id())
+            .await
+            .map_err(|e| format!("Failed to delete API key: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Whether the built-in model's runtime is available and which models are downloaded
+#[tauri::command]
+pub async fn get_builtin_llm_status() -> Result<BuiltinLlmStatus, String> {
+    Ok(builtin_llm::status())
+}
+
+#[tauri::command]
+pub async fn download_builtin_llm_model<R: Runtime>(app: AppHandle<R>, model_id: String) -> Result<BuiltinLlmStatus, String> {
+    let model = builtin_llm::find_model(&model_id).ok_or_else(|| format!("Unknown built-in model: {}", model_id))?;
+    let progress_app = app.clone();
+    builtin_llm::download_model(model, move |progress| {
+        let _ = progress_app.emit(
+            BUILTIN_MODEL_DOWNLOAD_PROGRESS_EVENT,
+            serde_json::json!({ "model_id": model.id, "progress": progress }),
+        );
+    })
+    .await
+    .map_err(|e| format!("Failed to download {}: {}", model.name, e))?;
+    Ok(builtin_llm::status())
+}
@@ -1,12 +1,23 @@
 use crate::database::repositories::{
-    meeting::MeetingsRepository, setting::SettingsRepository, summary::SummaryProcessesRepository,
+    marker::MarkersRepository, meeting::MeetingsRepository, meeting_library::MeetingLibraryRepository,
+    setting::SettingsRepository, summary::SummaryProcessesRepository, summary_version::SummaryVersionsRepository,
+    transcript::TranscriptsRepository,
 };
-use crate::summary::llm_client::LLMProvider;
-use crate::summary::processor::{extract_meeting_name_from_markdown, generate_meeting_summary};
+use crate::library::markers::flagged_moments;
+use crate::notifications::commands::notify_in_background;
+use crate::notifications::types::Notification;
+use crate::redaction::{self, RedactionTarget};
+use crate::summary::{action_items, builtin_llm, key_points};
+use crate::summary::credentials;
+use crate::summary::templates::{self, TemplateVariables};
+use crate::summary::llm_client::{create_provider, LLMProvider};
+use crate::summary::processor::{extract_meeting_name_from_markdown, generate_meeting_summary, ChunkingOptions};
+use crate::summary::provider::SummaryProvider;
 use crate::ollama::metadata::ModelMetadataCache;
 use sqlx::SqlitePool;
 use std::time::{Duration, Instant};
-use tauri::AppHandle;
+use crate::summary::streaming::{SummaryChunk, SummaryDone, SUMMARY_CHUNK_EVENT, SUMMARY_DONE_EVENT};
+use tauri::{AppHandle, Emitter, Runtime};
 use tracing::{error, info, warn};
 use once_cell::sync::Lazy;
 
@@ -25,7 +36,7 @@ impl SummaryService {
     /// the main thread. It updates the database with progress and results.
     ///
     /// # Arguments
-    /// * `_app` - Tauri app handle (for future use)
+    /// * `app` - Tauri app handle, used to stream the summary to the frontend
     /// * `pool` - SQLx connection pool
     /// * `meeting_id` - Unique identifier for the meeting
     /// * `text` - Full transcript text
@@ -33,8 +44,9 @@ impl SummaryService {
     /// * `model_name` - Specific model (e.g., "gpt-4", "llama3.2:latest")
     /// * `custom_prompt` - Optional user-provided context
     /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
+    /// * `chunking` - Map-reduce window settings for long transcripts
     pub async fn process_transcript_background<R: tauri::Runtime>(
-        _app: AppHandle<R>,
+        app: AppHandle<R>,
         pool: SqlitePool,
         meeting_id: String,
         text: String,
@@ -42,6 +54,7 @@ impl SummaryService {
         model_name: String,
         custom_prompt: String,
         template_id: String,
+        chunking: ChunkingOptions,
     ) {
         let start_time = Instant::now();
         info!(
@@ -53,89 +66,59 @@ impl SummaryService {
         let provider = match LLMProvider::from_str(&model_provider) {
             Ok(p) => p,
             Err(e) => {
-                Self::update_process_failed(&pool, &meeting_id, &e).await;
+                Self::update_process_failed(&app, &pool, &meeting_id, &e).await;
                 return;
             }
         };
 
-        // Validate and setup api_key, Flexible for Ollama
-        let api_key = match SettingsRepository::get_api_key(&pool, &model_provider).await {
-            Ok(Some(key)) if !key.is_empty() => key,
-            Ok(None) | Ok(Some(_)) => {
-                if provider != LLMProvider::Ollama {
-                    let err_msg = format!("Api key not found for {}", &model_provider);
-                    Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                    return;
-                }
-                String::new()
-            }
+        let (provider, model_name) = Self::choose_provider(&pool, provider, &model_name).await;
+        let (summary_provider, token_threshold) = match Self::resolve_provider(&pool, provider, &model_name).await {
+            Ok(resolved) => resolved,
             Err(e) => {
-                let err_msg = format!("Failed to retrieve api key for {} : {}", &model_provider, e);
-                Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
+                Self::update_process_failed(&app, &pool, &meeting_id, &e).await;
                 return;
             }
         };
 
-        // Get Ollama endpoint if provider is Ollama
-        let ollama_endpoint = if provider == LLMProvider::Ollama {
-            match SettingsRepository::get_model_config(&pool).await {
-                Ok(Some(config)) => config.ollama_endpoint,
-                Ok(None) => None,
-                Err(e) => {
-                    info!("Failed to retrieve Ollama endpoint: {}, using default", e);
-                    None
-                }
+        // Mask personal data before the transcript leaves for the model
+        let text = match redaction::redact_for_meeting(&pool, &meeting_id, RedactionTarget::Summary, vec![text]).await {
+            Ok(mut redacted) => redacted.pop().unwrap_or_default(),
+            Err(e) => {
+                Self::update_process_failed(&app, &pool, &meeting_id, &e).await;
+                return;
             }
-        } else {
-            None
-        };
-
-        // Dynamically fetch context size for Ollama models
-        let token_threshold = if provider == LLMProvider::Ollama {
-            match METADATA_CACHE.get_or_fetch(&model_name, ollama_endpoint.as_deref()).await {
-                Ok(metadata) => {
-                    // Reserve 300 tokens for prompt overhead
-                    let optimal = metadata.context_size.saturating_sub(300);
-                    info!(
-                        "✓ Using dynamic context for {}: {} tokens (chunk size: {})",
-                        model_name, metadata.context_size, optimal
-                    );
-                    optimal
-                }
-                Err(e) => {
-                    warn!(
-                        "⚠️ Failed to fetch context for {}: {}. Using default 4000",
-                        model_name, e
-                    );
-                    4000  // Fallback to safe default
-                }
-            }
-        } else {
-            // Cloud providers (OpenAI, Claude, Groq) handle large contexts automatically
-            100000  // Effectively unlimited for single-pass processing
         };
 
         // Generate summary
-        let client = reqwest::Client::new();
+        templates::refresh_stored_templates(&pool).await;
+        let variables = Self::template_variables(&pool, &meeting_id).await;
+        let custom_prompt = Self::with_meeting_context(&pool, &meeting_id, &variables, custom_prompt).await;
         let result = generate_meeting_summary(
-            &client,
-            &provider,
-            &model_name,
-            &api_key,
+            summary_provider.as_ref(),
             &text,
             &custom_prompt,
             &template_id,
+            &variables,
             token_threshold,
-            ollama_endpoint.as_deref(),
+            &chunking,
+            &mut |delta: &str| {
+                let chunk = SummaryChunk { meeting_id: meeting_id.clone(), delta: delta.to_string() };
+                if let Err(e) = app.emit(SUMMARY_CHUNK_EVENT, &chunk) {
+                    warn!("Failed to emit {}: {}", SUMMARY_CHUNK_EVENT, e);
+                }
+            },
         )
         .await;
 
         let duration = start_time.elapsed().as_secs_f64();
 
         match result {
-            Ok((mut final_markdown, num_chunks)) => {
+            Ok((mut final_markdown, num_chunks, usage, input)) => {
+                crate::usage::record_llm(&pool, Some(meeting_id.as_str()), "summary", summary_provider.as_ref(), &usage)
+                    .await;
                 if num_chunks == 0 && final_markdown.is_empty() {
                     Self::update_process_failed(
+                        &app,
                         &pool,
                         &meeting_id,
                         "Summary generation failed: No content was processed.",
@@ -148,6 +131,13 @@ impl SummaryService {
                     "✓ Successfully processed {} chunks for meeting_id: {}. Duration: {:.2}s",
                     num_chunks, meeting_id, duration
                 );
+                info!(
+                    "🧮 Token usage for meeting_id {}: {} prompt + {} completion{}",
+                    meeting_id,
+                    usage.prompt_tokens,
+                    usage.completion_tokens,
+                    if usage.estimated { " (estimated)" } else { "" }
+                );
                 info!("final markdown is {}", &final_markdown);
 
                 // Extract and update meeting name if present
@@ -182,11 +172,37 @@ impl SummaryService {
                     }
                 }
 
+                // Plugins may rewrite the summary, so they run before it's saved
+                let final_markdown = crate::plugins::hooks::summary_ready(&app, &pool, &meeting_id, final_markdown).await;
+
                 // Create result JSON with markdown only (summary_json will be added on first edit)
                 let result_json = serde_json::json!({
                     "markdown": final_markdown,
+                    "usage": {
+                        "provider": provider.id(),
+                        "model": model_name,
+                        "prompt_tokens": usage.prompt_tokens,
+                        "completion_tokens": usage.completion_tokens,
+                        "total_tokens": usage.total_tokens(),
+                        "estimated": usage.estimated,
+                    },
+                    "input": input,
                 });
 
+                // Keep this output next to earlier ones so they can be compared
+                if let Err(e) = SummaryVersionsRepository::insert(
+                    &pool,
+                    &meeting_id,
+                    provider.id(),
+                    &model_name,
+                    &template_id,
+                    &result_json,
+                )
+                .await
+                {
+                    warn!("⚠️ Failed to save summary version for {}: {}", meeting_id, e);
+                }
+
                 // Update database with completed status
                 if let Err(e) = SummaryProcessesRepository::update_process_completed(
                     &pool,
@@ -201,26 +217,241 @@ impl SummaryService {
                         "⚠️ Failed to save completed process for {}: {}",
                         meeting_id, e
                     );
+                    Self::emit_done(&app, &meeting_id, "failed", Some(&format!("Failed to save summary: {}", e)));
                 } else {
                     info!(
                         "💾 Summary saved successfully for meeting_id: {}",
                         meeting_id
                     );
+                    Self::emit_done(&app, &meeting_id, "completed", None);
+                    let title = match MeetingsRepository::get_meeting(&pool, &meeting_id).await {
+                        Ok(Some(meeting)) => Some(meeting.title),
+                        _ => None,
+                    };
+                    notify_in_background(&app, Notification::summary_ready(title));
+
+                    // Best effort: the summary is already saved if this fails
+                    match action_items::extract_action_items(&pool, summary_provider.as_ref(), token_threshold, &meeting_id).await {
+                        Ok(_) => action_items::emit_updated(&app, &meeting_id),
+                        Err(e) => warn!("⚠️ Action item extraction failed for {}: {}", meeting_id, e),
+                    }
+                    match key_points::extract_key_points(&pool, summary_provider.as_ref(), token_threshold, &meeting_id).await {
+                        Ok(_) => key_points::emit_updated(&app, &meeting_id),
+                        Err(e) => warn!("⚠️ Key point extraction failed for {}: {}", meeting_id, e),
+                    }
+                    crate::integrations::slack::queue_post(&app, &pool, &meeting_id).await;
+                    crate::integrations::webhooks::dispatch(
+                        &app,
+                        crate::integrations::webhooks::WebhookEvent::SummaryReady,
+                        serde_json::json!({ "meeting_id": meeting_id, "markdown": final_markdown }),
+                    );
+                }
+            }
+            Err(e) => {
+                Self::update_process_failed(&app, &pool, &meeting_id, &e).await;
+            }
+        }
+    }
+
+    /// The provider and model a summary actually runs with
+    ///
+    /// A local Ollama that isn't running yet is started. When the chosen provider still
+    /// can't work (Ollama is missing, or a hosted provider has no API key or is off in
+    /// local-only mode), the built-in
+    /// model takes over if one has been downloaded, so the summary doesn't simply fail.
+    pub async fn choose_provider(pool: &SqlitePool, provider: LLMProvider, model_name: &str) -> (LLMProvider, String) {
+        let unavailable = match provider {
+            LLMProvider::Ollama => {
+                let endpoint = Self::ollama_endpoint(pool).await;
+                match crate::ollama::lifecycle::ensure_running(endpoint.as_deref()).await {
+                    Ok(_) => None,
+                    Err(e) => Some(e),
+                }
+            }
+            _ if !provider.is_local() && crate::network::is_local_only() => {
+                Some(format!("Local-only mode is on, so {} isn't used", provider.display_name()))
+            }
+            _ if provider.requires_api_key() => {
+                match credentials::resolve_api_key(pool, provider.id()).await {
+                    Ok(Some(key)) if !key.is_empty() => None,
+                    _ => Some(format!("No API key for {}", provider.display_name())),
                 }
             }
+            _ => None,
+        };
+
+        if let Some(reason) = unavailable {
+            if let Some(builtin_model) = builtin_llm::ready_model() {
+                warn!("⚠️ {}; using the built-in model {} instead", reason, builtin_model);
+                return (LLMProvider::Builtin, builtin_model.to_string());
+            }
+            warn!("⚠️ {}", reason);
+        }
+        (provider, model_name.to_string())
+    }
+
+    /// Builds the provider for a summary model, along with how many transcript tokens it
+    /// can take in one request before the text has to be split
+    pub async fn resolve_provider(
+        pool: &SqlitePool,
+        provider: LLMProvider,
+        model_name: &str,
+    ) -> Result<(Box<dyn SummaryProvider>, usize), String> {
+        if !provider.is_local() {
+            crate::network::ensure_online(&format!("Summaries with {}", provider.display_name()))?;
+        }
+        // Hosted providers need an API key; local ones don't
+        let api_key = match credentials::resolve_api_key(pool, provider.id()).await {
+            Ok(key) => key.unwrap_or_default(),
+            Err(e) => return Err(format!("Failed to retrieve api key for {} : {}", provider.id(), e)),
+        };
+
+        // Server address of local providers
+        let endpoint = match provider {
+            LLMProvider::Ollama => Self::ollama_endpoint(pool).await,
+            LLMProvider::LlamaCpp => SettingsRepository::get_summary_provider_setting(pool, provider.id())
+                .await
+                .ok()
+                .flatten()
+                .and_then(|setting| setting.endpoint),
+            LLMProvider::Builtin => Some(
+                builtin_llm::ensure_server(model_name)
+                    .await
+                    .map_err(|e| format!("Failed to start the built-in model: {}", e))?,
+            ),
+            _ => None,
+        };
+
+        let token_threshold = Self::token_threshold(provider, model_name, endpoint.as_deref()).await;
+
+        let client = crate::network::client();
+        let summary_provider = create_provider(&client, provider, model_name, &api_key, endpoint.as_deref())
+            .map_err(|e| e.to_string())?;
+        Ok((summary_provider, token_threshold))
+    }
+
+    async fn ollama_endpoint(pool: &SqlitePool) -> Option<String> {
+        match SettingsRepository::get_model_config(pool).await {
+            Ok(Some(config)) => config.ollama_endpoint,
+            Ok(None) => None,
             Err(e) => {
-                Self::update_process_failed(&pool, &meeting_id, &e).await;
+                info!("Failed to retrieve Ollama endpoint: {}, using default", e);
+                None
             }
         }
     }
 
+    /// How many transcript tokens the model can take in one request before the text has to
+    /// be split
+    pub async fn token_threshold(provider: LLMProvider, model_name: &str, endpoint: Option<&str>) -> usize {
+        // Dynamically fetch context size for Ollama models
+        if provider == LLMProvider::Ollama {
+            match METADATA_CACHE.get_or_fetch(model_name, endpoint).await {
+                Ok(metadata) => {
+                    // Reserve 300 tokens for prompt overhead
+                    let optimal = metadata.context_size.saturating_sub(300);
+                    info!(
+                        "✓ Using dynamic context for {}: {} tokens (chunk size: {})",
+                        model_name, metadata.context_size, optimal
+                    );
+                    optimal
+                }
+                Err(e) => {
+                    warn!(
+                        "⚠️ Failed to fetch context for {}: {}. Using default 4000",
+                        model_name, e
+                    );
+                    4000  // Fallback to safe default
+                }
+            }
+        } else if provider == LLMProvider::Builtin {
+            builtin_llm::TOKEN_THRESHOLD
+        } else if provider.is_local() {
+            4000  // llama.cpp context depends on how the server was started
+        } else {
+            // Cloud providers (OpenAI, Claude, Groq) handle large contexts automatically
+            100000  // Effectively unlimited for single-pass processing
+        }
+    }
+
+    /// Meeting facts for template placeholders; whatever isn't known yet is left empty
+    async fn template_variables(pool: &SqlitePool, meeting_id: &str) -> TemplateVariables {
+        let Ok(Some(meeting)) = MeetingsRepository::get_meeting(pool, meeting_id).await else {
+            return TemplateVariables::default();
+        };
+        // Participants entered by hand or copied from a calendar invite, else the speakers
+        let mut participants: Vec<String> = MeetingLibraryRepository::list_participants(pool, Some(meeting_id))
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, participant)| participant.name)
+            .collect();
+        if participants.is_empty() {
+            for speaker in meeting.transcripts.iter().filter_map(|t| t.speaker.as_deref()) {
+                if !participants.iter().any(|p| p == speaker) {
+                    participants.push(speaker.to_string());
+                }
+            }
+        }
+        let duration_seconds = meeting
+            .transcripts
+            .iter()
+            .filter_map(|t| t.audio_end_time)
+            .reduce(f64::max);
+        TemplateVariables {
+            date: chrono::DateTime::parse_from_rfc3339(&meeting.created_at)
+                .ok()
+                .map(|created_at| created_at.format("%Y-%m-%d").to_string()),
+            title: Some(meeting.title),
+            participants,
+            duration_seconds,
+            agenda: crate::calendar::sync::meeting_agenda(pool, meeting_id).await,
+        }
+    }
+
+    /// The user's context followed by the calendar agenda and the moments the user flagged
+    async fn with_meeting_context(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        variables: &TemplateVariables,
+        custom_prompt: String,
+    ) -> String {
+        let mut sections = Vec::new();
+        if let Some(agenda) = variables.agenda.as_deref().filter(|agenda| !custom_prompt.contains(agenda)) {
+            sections.push(format!("Agenda from the calendar invitation:\n{}", agenda));
+        }
+        let markers = MarkersRepository::list_for_meeting(pool, meeting_id).await.unwrap_or_default();
+        if !markers.is_empty() {
+            let transcripts = TranscriptsRepository::list_for_meeting(pool, meeting_id).await.unwrap_or_default();
+            sections.extend(flagged_moments(&markers, &transcripts));
+        }
+        if !custom_prompt.trim().is_empty() {
+            sections.insert(0, custom_prompt);
+        }
+        sections.join("\n\n")
+    }
+
+    /// Tells the frontend the summary stream has ended
+    pub fn emit_done<R: Runtime>(app: &AppHandle<R>, meeting_id: &str, status: &str, error: Option<&str>) {
+        let done = SummaryDone {
+            meeting_id: meeting_id.to_string(),
+            status: status.to_string(),
+            error: error.map(str::to_string),
+        };
+        if let Err(e) = app.emit(SUMMARY_DONE_EVENT, &done) {
+            warn!("Failed to emit {}: {}", SUMMARY_DONE_EVENT, e);
+        }
+    }
+
     /// Updates the summary process status to failed with error message
     ///
     /// # Arguments
+    /// * `app` - Tauri app handle
     /// * `pool` - SQLx connection pool
     /// * `meeting_id` - Meeting identifier
     /// * `error_msg` - Error message to store
-    async fn update_process_failed(pool: &SqlitePool, meeting_id: &str, error_msg: &str) {
+    async fn update_process_failed<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, meeting_id: &str, error_msg: &str) {
+        Self::emit_done(app, meeting_id, "failed", Some(error_msg));
         error!(
             "❌ Processing failed for meeting_id {}: {}",
             meeting_id, error_msg
@@ -0,0 +1,160 @@
+// summary/streaming.rs
+//
+// Incremental summary output. Providers stream their completion as server-sent events;
+// the final report is forwarded to the frontend as `summary-chunk` events while it is
+// generated, followed by one `summary-done` event.
+
+use crate::summary::provider::TokenUsage;
+use serde::{Deserialize, Serialize};
+
+pub const SUMMARY_CHUNK_EVENT: &str = "summary-chunk";
+pub const SUMMARY_DONE_EVENT: &str = "summary-done";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SummaryChunk {
+    pub meeting_id: String,
+    /// Text generated since the previous chunk
+    pub delta: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SummaryDone {
+    pub meeting_id: String,
+    /// "completed", "failed" or "cancelled"
+    pub status: String,
+    pub error: Option<String>,
+}
+
+/// Splits a server-sent event stream into `data:` payloads
+///
+/// Bytes arrive in arbitrary pieces, so incomplete lines are kept until the rest arrives.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: String,
+}
+
+impl SseParser {
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.push_str(&String::from_utf8_lossy(bytes));
+        let mut payloads = Vec::new();
+        while let Some(newline) = self.buffer.find('\n') {
+            let line: String = self.buffer.drain(..=newline).collect();
+            if let Some(data) = line.trim_end_matches(['\r', '\n']).strip_prefix("data:") {
+                let data = data.trim_start();
+                if !data.is_empty() && data != "[DONE]" {
+                    payloads.push(data.to_string());
+                }
+            }
+        }
+        payloads
+    }
+}
+
+#[derive(Deserialize)]
+struct OpenAiStreamChunk {
+    #[serde(default)]
+    choices: Vec<OpenAiStreamChoice>,
+    #[serde(default)]
+    usage: Option<OpenAiStreamUsage>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiStreamChoice {
+    #[serde(default)]
+    delta: OpenAiStreamDelta,
+}
+
+#[derive(Deserialize, Default)]
+struct OpenAiStreamDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiStreamUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+}
+
+/// Text delta and, in the last chunk, token usage of an OpenAI-compatible stream event
+pub fn parse_openai_event(data: &str) -> (Option<String>, Option<TokenUsage>) {
+    let Ok(chunk) = serde_json::from_str::<OpenAiStreamChunk>(data) else {
+        return (None, None);
+    };
+    let delta = chunk
+        .choices
+        .into_iter()
+        .filter_map(|choice| choice.delta.content)
+        .collect::<String>();
+    let usage = chunk.usage.map(|usage| TokenUsage {
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+        estimated: false,
+    });
+    ((!delta.is_empty()).then_some(delta), usage)
+}
+
+/// Anthropic stream event: a text delta, or token counts from `message_start`/`message_delta`
+#[derive(Debug, PartialEq)]
+pub enum AnthropicEvent {
+    Text(String),
+    InputTokens(u64),
+    OutputTokens(u64),
+    Error(String),
+    Other,
+}
+
+pub fn parse_anthropic_event(data: &str) -> AnthropicEvent {
+    let Ok(event) = serde_json::from_str::<serde_json::Value>(data) else {
+        return AnthropicEvent::Other;
+    };
+    match event["type"].as_str() {
+        Some("content_block_delta") => event["delta"]["text"]
+            .as_str()
+            .map_or(AnthropicEvent::Other, |text| AnthropicEvent::Text(text.to_string())),
+        Some("message_start") => event["message"]["usage"]["input_tokens"]
+            .as_u64()
+            .map_or(AnthropicEvent::Other, AnthropicEvent::InputTokens),
+        Some("message_delta") => event["usage"]["output_tokens"]
+            .as_u64()
+            .map_or(AnthropicEvent::Other, AnthropicEvent::OutputTokens),
+        Some("error") => AnthropicEvent::Error(event["error"]["message"].as_str().unwrap_or("Unknown error").to_string()),
+        _ => AnthropicEvent::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_payloads_survive_split_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.feed(b"event: message\ndata: {\"a\":").is_empty());
+        assert_eq!(parser.feed(b"1}\r\n\ndata: [DONE]\n"), vec!["{\"a\":1}"]);
+
+        let (delta, usage) = parse_openai_event(r#"{"choices":[{"delta":{"content":"## Notes"}}]}"#);
+        assert_eq!(delta.as_deref(), Some("## Notes"));
+        assert!(usage.is_none());
+        let (delta, usage) = parse_openai_event(r#"{"choices":[],"usage":{"prompt_tokens":12,"completion_tokens":3}}"#);
+        assert!(delta.is_none());
+        assert_eq!(usage.map(|u| u.total_tokens()), Some(15));
+    }
+
+    #[test]
+    fn test_anthropic_events() {
+        assert_eq!(
+            parse_anthropic_event(r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#),
+            AnthropicEvent::Text("Hi".to_string())
+        );
+        assert_eq!(
+            parse_anthropic_event(r#"{"type":"message_start","message":{"usage":{"input_tokens":42,"output_tokens":1}}}"#),
+            AnthropicEvent::InputTokens(42)
+        );
+        assert_eq!(
+            parse_anthropic_event(r#"{"type":"message_delta","usage":{"output_tokens":7}}"#),
+            AnthropicEvent::OutputTokens(7)
+        );
+        assert_eq!(parse_anthropic_event(r#"{"type":"ping"}"#), AnthropicEvent::Other);
+    }
+}
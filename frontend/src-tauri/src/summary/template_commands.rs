@@ -1,4 +1,6 @@
-use crate::summary::templates;
+use crate::database::repositories::{meeting::MeetingsRepository, template::TemplatesRepository};
+use crate::state::AppState;
+use crate::summary::templates::{self, Template};
 use serde::{Deserialize, Serialize};
 use tauri::Runtime;
 use tracing::{info, warn};
@@ -14,6 +16,10 @@ pub struct TemplateInfo {
 
     /// Brief description of the template's purpose
     pub description: String,
+
+    /// Ships with the app; deleting an edited copy restores the original
+    #[serde(default)]
+    pub builtin: bool,
 }
 
 /// Detailed template structure for preview/debugging
@@ -34,7 +40,8 @@ pub struct TemplateDetails {
 
 /// Lists all available templates
 ///
-/// Returns templates from both built-in (embedded) and custom (user data directory) sources.
+/// Returns templates from built-in (embedded), custom (user data directory) and stored
+/// (created in the app) sources.
 /// Templates are automatically discovered - no code changes needed to add new templates.
 ///
 /// # Returns
@@ -42,14 +49,17 @@ pub struct TemplateDetails {
 #[tauri::command]
 pub async fn api_list_templates<R: Runtime>(
     _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
 ) -> Result<Vec<TemplateInfo>, String> {
     info!("api_list_templates called");
 
-    let templates = templates::list_templates();
+    templates::refresh_stored_templates(state.db_manager.pool()).await;
+    let available = templates::list_templates();
 
-    let template_infos: Vec<TemplateInfo> = templates
+    let template_infos: Vec<TemplateInfo> = available
         .into_iter()
         .map(|(id, name, description)| TemplateInfo {
+            builtin: templates::is_builtin_template(&id),
             id,
             name,
             description,
@@ -123,6 +133,113 @@ pub async fn api_validate_template<R: Runtime>(
     }
 }
 
+/// Gets the full definition of a template, for editing
+#[tauri::command]
+pub async fn api_get_template<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    template_id: String,
+) -> Result<Template, String> {
+    templates::refresh_stored_templates(state.db_manager.pool()).await;
+    templates::get_template(&template_id)
+}
+
+/// Creates or updates a template
+///
+/// Without `template_id` the id is derived from the template name. Saving under the id of
+/// a built-in template stores an edited copy that is used instead of the original.
+///
+/// # Returns
+/// The saved template's metadata
+#[tauri::command]
+pub async fn api_save_template<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    template_id: Option<String>,
+    template_json: String,
+) -> Result<TemplateInfo, String> {
+    let template = templates::validate_and_parse_template(&template_json)?;
+    let id = template_id
+        .map(|id| id.trim().to_string())
+        .unwrap_or_else(|| templates::template_id_from_name(&template.name));
+    if !templates::is_valid_template_id(&id) {
+        return Err(format!(
+            "Invalid template id '{}': use lowercase letters, digits and underscores",
+            id
+        ));
+    }
+
+    // Store the parsed template so the definition is normalized
+    let definition = serde_json::to_string(&template).map_err(|e| e.to_string())?;
+    let pool = state.db_manager.pool();
+    TemplatesRepository::save(pool, &id, &definition)
+        .await
+        .map_err(|e| format!("Failed to save template: {}", e))?;
+    templates::refresh_stored_templates(pool).await;
+
+    info!("💾 Saved template '{}' ({})", template.name, id);
+    Ok(TemplateInfo {
+        builtin: templates::is_builtin_template(&id),
+        id,
+        name: template.name,
+        description: template.description,
+    })
+}
+
+/// Deletes a template created in the app, or restores a built-in one to its original
+///
+/// Returns false if there was no stored template with that id.
+#[tauri::command]
+pub async fn api_delete_template<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    template_id: String,
+) -> Result<bool, String> {
+    let pool = state.db_manager.pool();
+    let deleted = TemplatesRepository::delete(pool, &template_id)
+        .await
+        .map_err(|e| format!("Failed to delete template: {}", e))?;
+    templates::refresh_stored_templates(pool).await;
+    if deleted {
+        info!("🗑️ Deleted stored template '{}'", template_id);
+    }
+    Ok(deleted)
+}
+
+/// Selects the template used for a meeting's summary; None clears the selection
+#[tauri::command]
+pub async fn api_set_meeting_template<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    template_id: Option<String>,
+) -> Result<(), String> {
+    let pool = state.db_manager.pool();
+    if let Some(template_id) = template_id.as_deref() {
+        templates::refresh_stored_templates(pool).await;
+        templates::get_template(template_id)?;
+    }
+    let updated = MeetingsRepository::set_template_id(pool, &meeting_id, template_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to set meeting template: {}", e))?;
+    if !updated {
+        return Err(format!("Meeting not found: {}", meeting_id));
+    }
+    Ok(())
+}
+
+/// Template selected for a meeting, if any
+#[tauri::command]
+pub async fn api_get_meeting_template<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Option<String>, String> {
+    MeetingsRepository::get_template_id(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get meeting template: {}", e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,5 @@
 use super::defaults;
+use super::store;
 use super::types::Template;
 use std::path::PathBuf;
 use tracing::{debug, info, warn};
@@ -18,11 +19,15 @@ pub fn set_bundled_templates_dir(path: PathBuf) {
 
 /// Get the user's custom templates directory path
 ///
-/// Returns the platform-specific application data directory for custom templates:
+/// Returns the `templates/` folder of the active workspace's storage folder, or for the
+/// default workspace the platform-specific application data directory for custom templates:
 /// - macOS: ~/Library/Application Support/Meetily/templates/
 /// - Windows: %APPDATA%\Meetily\templates\
 /// - Linux: ~/.config/Meetily/templates/
-fn get_custom_templates_dir() -> Option<PathBuf> {
+pub fn get_custom_templates_dir() -> Option<PathBuf> {
+    if let Some(path) = crate::workspaces::templates_dir() {
+        return Some(path);
+    }
     let mut path = dirs::data_dir()?;
     path.push("Meetily");
     path.push("templates");
@@ -82,10 +87,11 @@ fn load_custom_template(template_id: &str) -> Option<String> {
 /// Load and parse a template by identifier
 ///
 /// This function implements a fallback strategy:
-/// 1. Check user's custom templates directory
-/// 2. Check bundled resources directory (app templates)
-/// 3. Fall back to built-in embedded templates
-/// 4. Return error if not found in any location
+/// 1. Check templates created or edited in the app (database)
+/// 2. Check user's custom templates directory
+/// 3. Check bundled resources directory (app templates)
+/// 4. Fall back to built-in embedded templates
+/// 5. Return error if not found in any location
 ///
 /// # Arguments
 /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
@@ -95,8 +101,11 @@ fn load_custom_template(template_id: &str) -> Option<String> {
 pub fn get_template(template_id: &str) -> Result<Template, String> {
     info!("Loading template: {}", template_id);
 
-    // Try custom template first, then bundled, then built-in
-    let json_content = if let Some(custom_content) = load_custom_template(template_id) {
+    // Try stored template first, then custom, then bundled, then built-in
+    let json_content = if let Some(stored_content) = store::load_stored_template(template_id) {
+        debug!("Using stored template for '{}'", template_id);
+        stored_content
+    } else if let Some(custom_content) = load_custom_template(template_id) {
         debug!("Using custom template for '{}'", template_id);
         custom_content
     } else if let Some(bundled_content) = load_bundled_template(template_id) {
@@ -139,6 +148,7 @@ pub fn validate_and_parse_template(json_content: &str) -> Result<Template, Strin
 /// - Built-in template IDs
 /// - Bundled template IDs (from app resources)
 /// - Custom template IDs (from user's data directory)
+/// - Template IDs created in the app (database)
 pub fn list_template_ids() -> Vec<String> {
     let mut ids: Vec<String> = defaults::list_builtin_template_ids()
         .into_iter()
@@ -193,10 +203,22 @@ pub fn list_template_ids() -> Vec<String> {
         }
     }
 
+    for id in store::stored_template_ids() {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
     ids.sort();
     ids
 }
 
+/// Whether a template ships with the app (embedded or bundled), so deleting a stored
+/// copy of it restores the original rather than removing it
+pub fn is_builtin_template(template_id: &str) -> bool {
+    defaults::get_builtin_template(template_id).is_some() || load_bundled_template(template_id).is_some()
+}
+
 /// List all available templates with their metadata
 ///
 /// Returns a list of (id, name, description) tuples
@@ -8,7 +8,11 @@
 //!
 //! - **Built-in templates**: JSON files in `frontend/src-tauri/templates/` embedded at compile time
 //! - **Custom templates**: JSON files in platform-specific app data directory
-//! - **Fallback strategy**: Custom templates override built-in templates with the same ID
+//! - **Stored templates**: created or edited in the app, kept in the `summary_templates` table
+//! - **Fallback strategy**: Stored templates override custom templates, which override
+//!   built-in templates with the same ID
+//! - **Variables**: `{title}`, `{date}`, `{participants}` and `{duration}` in a template's
+//!   prompt and instructions are filled in per meeting (`Template::render`)
 //!
 //! # Usage
 //!
@@ -37,16 +41,17 @@
 //!
 //! Custom templates must follow the JSON schema defined in `types::Template`.
 
-mod defaults;
+use meeting_core::summary::templates::{defaults, types};
 mod loader;
-mod types;
+mod store;
 
 // Re-export public API
 pub use loader::{
-    get_template, list_template_ids, list_templates, set_bundled_templates_dir,
-    validate_and_parse_template,
+    get_custom_templates_dir, get_template, is_builtin_template, list_template_ids, list_templates,
+    set_bundled_templates_dir, validate_and_parse_template,
 };
-pub use types::{Template, TemplateSection};
+pub use store::{is_valid_template_id, refresh_stored_templates, template_id_from_name};
+pub use types::{Template, TemplateSection, TemplateVariables};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,71 @@
+use crate::database::repositories::template::TemplatesRepository;
+use once_cell::sync::Lazy;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+use tracing::{info, warn};
+
+// Templates created or edited in the app, cached from the `summary_templates` table so
+// lookups stay synchronous. Refreshed before templates are listed or used, and after
+// every change.
+static STORED_TEMPLATES: Lazy<RwLock<BTreeMap<String, String>>> = Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+/// Reload the stored templates from the database
+pub async fn refresh_stored_templates(pool: &SqlitePool) {
+    match TemplatesRepository::list(pool).await {
+        Ok(rows) => {
+            let templates: BTreeMap<String, String> = rows.into_iter().map(|row| (row.id, row.definition)).collect();
+            if !templates.is_empty() {
+                info!("Loaded {} stored template(s)", templates.len());
+            }
+            if let Ok(mut stored) = STORED_TEMPLATES.write() {
+                *stored = templates;
+            }
+        }
+        Err(e) => warn!("Failed to load stored templates: {}", e),
+    }
+}
+
+/// JSON of a stored template
+pub fn load_stored_template(template_id: &str) -> Option<String> {
+    STORED_TEMPLATES.read().ok()?.get(template_id).cloned()
+}
+
+pub fn stored_template_ids() -> Vec<String> {
+    STORED_TEMPLATES
+        .read()
+        .map(|stored| stored.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Template id for a user-entered name: lowercase letters, digits and underscores
+pub fn template_id_from_name(name: &str) -> String {
+    let mut id = String::new();
+    for c in name.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c);
+        } else if !id.is_empty() && !id.ends_with('_') {
+            id.push('_');
+        }
+    }
+    id.trim_end_matches('_').to_string()
+}
+
+pub fn is_valid_template_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_id_from_name() {
+        assert_eq!(template_id_from_name("Sprint Review"), "sprint_review");
+        assert_eq!(template_id_from_name("  1:1 (Engineering) "), "1_1_engineering");
+        assert_eq!(template_id_from_name("Café"), "caf");
+        assert!(is_valid_template_id("sprint_review"));
+        assert!(!is_valid_template_id("../secrets"));
+        assert!(!is_valid_template_id(""));
+    }
+}
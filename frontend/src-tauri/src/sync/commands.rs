@@ -0,0 +1,124 @@
+// sync/commands.rs
+//
+// Tauri commands for setting up sync, pairing devices and resolving conflicting edits. The
+// sync key is shown as 64 hex characters, to be entered on the user's other devices.
+
+use log::info;
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+
+use super::engine::{self, SyncReport};
+use super::{load_key, load_secret, load_settings, save_settings, store_key_hex, store_secret, SyncSettings};
+use crate::audio::session;
+use crate::database::models::SyncConflict;
+use crate::database::repositories::sync::SyncRepository;
+use crate::encryption;
+use crate::state::AppState;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatus {
+    pub settings: SyncSettings,
+    /// Whether the location's token, secret access key or password is stored
+    pub has_secret: bool,
+    pub has_key: bool,
+}
+
+#[tauri::command]
+pub async fn get_sync_settings<R: Runtime>(app: AppHandle<R>) -> Result<SyncStatus, String> {
+    Ok(SyncStatus {
+        settings: load_settings(&app),
+        has_secret: load_secret()?.is_some(),
+        has_key: load_key()?.is_some(),
+    })
+}
+
+/// Saves the settings, and the location's secret when one is given
+#[tauri::command]
+pub async fn set_sync_settings<R: Runtime>(
+    app: AppHandle<R>,
+    settings: SyncSettings,
+    secret: Option<String>,
+) -> Result<SyncStatus, String> {
+    let settings = settings.normalized();
+    if let Some(target) = &settings.target {
+        target.validate()?;
+    }
+    if settings.enabled && settings.target.is_none() {
+        return Err("Choose a sync location before turning sync on".to_string());
+    }
+    if settings.enabled && load_key()?.is_none() {
+        return Err("Create a sync key, or enter the one from your other device, before turning sync on".to_string());
+    }
+    if let Some(secret) = secret.map(|secret| secret.trim().to_string()).filter(|secret| !secret.is_empty()) {
+        store_secret(&secret)?;
+    }
+    save_settings(&app, &settings)?;
+    info!("🔄 Sync settings saved (enabled: {})", settings.enabled);
+    get_sync_settings(app).await
+}
+
+/// The sync key, to enter on another device; `None` before one is created or entered
+#[tauri::command]
+pub async fn get_sync_key() -> Result<Option<String>, String> {
+    Ok(load_key()?.map(|key| encryption::to_hex(&key)))
+}
+
+/// Creates the sync key on the first device
+#[tauri::command]
+pub async fn create_sync_key() -> Result<String, String> {
+    if load_key()?.is_some() {
+        return Err("A sync key already exists".to_string());
+    }
+    let mut key = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut key);
+    store_key_hex(&key)?;
+    info!("🔄 Created a sync key");
+    Ok(encryption::to_hex(&key))
+}
+
+/// Stores the sync key created on another device, replacing this one's
+#[tauri::command]
+pub async fn set_sync_key(key: String) -> Result<(), String> {
+    let key = encryption::from_hex(&key).ok_or("A sync key is 64 hex characters")?;
+    store_key_hex(&key)?;
+    info!("🔄 Stored the sync key from another device");
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn sync_now<R: Runtime>(app: AppHandle<R>, state: tauri::State<'_, AppState>) -> Result<SyncReport, String> {
+    if session::phase().is_recording() {
+        return Err("Sync can't run while recording".to_string());
+    }
+    engine::run(&app, state.db_manager.pool(), &load_settings(&app)).await
+}
+
+/// Unresolved conflicting edits, of one meeting or of all
+#[tauri::command]
+pub async fn list_sync_conflicts(
+    state: tauri::State<'_, AppState>,
+    meeting_id: Option<String>,
+) -> Result<Vec<SyncConflict>, String> {
+    SyncRepository::list_conflicts(state.db_manager.pool(), meeting_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to list sync conflicts: {}", e))
+}
+
+/// Keeps the merged text, or brings back the other device's edit with `keep_other`; the
+/// choice reaches the other devices on the next sync
+#[tauri::command]
+pub async fn resolve_sync_conflict(
+    state: tauri::State<'_, AppState>,
+    id: String,
+    keep_other: bool,
+) -> Result<(), String> {
+    let pool = state.db_manager.pool();
+    let conflict = SyncRepository::get_conflict(pool, &id)
+        .await
+        .map_err(|e| format!("Failed to load sync conflict: {}", e))?
+        .ok_or_else(|| format!("No sync conflict found with id {}", id))?;
+    let text = keep_other.then_some(conflict.other_text.as_str());
+    SyncRepository::resolve_conflict(pool, &conflict, text)
+        .await
+        .map_err(|e| format!("Failed to resolve sync conflict: {}", e))
+}
@@ -0,0 +1,410 @@
+// sync/engine.rs
+//
+// One sync pass. Every meeting known here, at the sync location or from an earlier pass is
+// merged with the location's copy, which is only downloaded when its tag changed. The merged
+// version is stored here, uploaded when the location's copy differs, and kept as the base of
+// the next merge. A meeting deleted here is uploaded as a tombstone, so the other devices
+// delete it too.
+//
+// Uploads only replace the copy that was merged. When another device uploaded in between, the
+// location refuses the upload; its copy is then downloaded and merged in as well, and the
+// upload tried again.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::Utc;
+use log::{info, warn};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Runtime};
+
+use super::merge::{merge, Merged, SyncedMeeting};
+use super::remote::{self, Precondition, PutOutcome, RemoteStore};
+use super::{load_key, load_secret, open_envelope, seal, seal_envelope, unseal, Envelope, SyncSettings};
+use crate::database::models::SyncState;
+use crate::database::repositories::sync::SyncRepository;
+use crate::encryption::EncryptionKey;
+use crate::library::deletion::{self, DeleteOptions};
+
+const MEETINGS_PREFIX: &str = "meetings/";
+/// A known text encrypted with the sync key, so a device with another key stops before it
+/// writes anything
+const KEY_CHECK: &str = "key-check";
+const KEY_CHECK_TEXT: &[u8] = b"meetily sync key check";
+/// Uploads of one meeting per pass; each refused one means another device uploaded meanwhile
+const MAX_UPLOAD_ATTEMPTS: usize = 5;
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    /// Meetings uploaded
+    pub pushed: usize,
+    /// Meetings created or changed here
+    pub pulled: usize,
+    /// Meetings deleted here because another device deleted them
+    pub deleted: usize,
+    pub conflicts: usize,
+    /// Meetings that couldn't be synced; they're retried on the next pass
+    pub failed: usize,
+}
+
+#[derive(Debug, Default)]
+struct Outcome {
+    pushed: bool,
+    pulled: bool,
+    deleted: bool,
+    conflicts: usize,
+}
+
+/// Clears the running flag however the pass ends
+struct RunGuard;
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+pub async fn run<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, settings: &SyncSettings) -> Result<SyncReport, String> {
+    let target = settings.target.as_ref().ok_or("Choose a sync location first")?;
+    let key = load_key()?.ok_or("Create a sync key, or enter the one from your other device, first")?;
+    let store = remote::open(target, load_secret()?.unwrap_or_default())?;
+
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A sync is already running".to_string());
+    }
+    let _guard = RunGuard;
+
+    let (report, pulled) = sync_meetings(pool, store.as_ref(), &key, &settings.device_name).await?;
+
+    // Pulled transcripts become searchable by meaning once embedded
+    if let Ok(models_dir) = crate::diarization::commands::models_dir(app) {
+        for meeting_id in pulled {
+            crate::qa::index::queue_indexing(app, pool, &models_dir, Some(meeting_id)).await;
+        }
+    }
+    Ok(report)
+}
+
+/// Syncs every meeting; returns the report and the meetings created or changed here
+async fn sync_meetings(
+    pool: &SqlitePool,
+    store: &dyn RemoteStore,
+    key: &EncryptionKey,
+    device: &str,
+) -> Result<(SyncReport, Vec<String>), String> {
+    check_key(store, key).await?;
+    let remote_tags: HashMap<String, Option<String>> = store
+        .list(MEETINGS_PREFIX)
+        .await?
+        .into_iter()
+        .filter_map(|object| Some((object.key.strip_prefix(MEETINGS_PREFIX)?.to_string(), object.tag)))
+        .collect();
+    let states: HashMap<String, SyncState> = SyncRepository::list_states(pool)
+        .await
+        .map_err(|e| format!("Failed to load sync state: {}", e))?
+        .into_iter()
+        .map(|state| (state.meeting_id.clone(), state))
+        .collect();
+    let local_ids = SyncRepository::list_meeting_ids(pool).await.map_err(|e| format!("Failed to list meetings: {}", e))?;
+
+    let ids: BTreeSet<&String> = local_ids.iter().chain(remote_tags.keys()).chain(states.keys()).collect();
+    let mut report = SyncReport::default();
+    let mut pulled = Vec::new();
+    for id in ids {
+        let pass = MeetingPass { pool, store, key, device };
+        match pass.sync(id, remote_tags.get(id), states.get(id)).await {
+            Ok(outcome) => {
+                report.pushed += outcome.pushed as usize;
+                report.pulled += outcome.pulled as usize;
+                report.deleted += outcome.deleted as usize;
+                report.conflicts += outcome.conflicts;
+                if outcome.pulled {
+                    pulled.push(id.clone());
+                }
+            }
+            Err(e) => {
+                warn!("🔄 Failed to sync meeting {}: {}", id, e);
+                report.failed += 1;
+            }
+        }
+    }
+    Ok((report, pulled))
+}
+
+/// Fails when the location holds data written with another sync key; marks a new location
+/// with this one
+async fn check_key(store: &dyn RemoteStore, key: &EncryptionKey) -> Result<(), String> {
+    let sealed = match store.get(KEY_CHECK).await? {
+        Some(fetched) => fetched.body,
+        None => {
+            info!("🔄 Setting up a new sync location");
+            match store.put(KEY_CHECK, seal(key, KEY_CHECK_TEXT)?, Precondition::Absent).await? {
+                PutOutcome::Stored(_) => return Ok(()),
+                // Another device set it up at the same moment
+                PutOutcome::Changed => store.get(KEY_CHECK).await?.ok_or("The sync location lost its key check")?.body,
+            }
+        }
+    };
+    match unseal(key, &sealed) {
+        Ok(text) if text == KEY_CHECK_TEXT => Ok(()),
+        _ => Err("The sync location was set up with a different sync key; enter the key from your other device".to_string()),
+    }
+}
+
+struct MeetingPass<'a> {
+    pool: &'a SqlitePool,
+    store: &'a dyn RemoteStore,
+    key: &'a EncryptionKey,
+    device: &'a str,
+}
+
+impl MeetingPass<'_> {
+    /// `remote_tag` is `None` when the location has no copy, `Some(None)` when it has one
+    /// without a tag
+    async fn sync(&self, id: &str, remote_tag: Option<&Option<String>>, state: Option<&SyncState>) -> Result<Outcome, String> {
+        let object_key = format!("{}{}", MEETINGS_PREFIX, id);
+        let base: Option<SyncedMeeting> = state
+            .map(|state| serde_json::from_str(&state.base))
+            .transpose()
+            .map_err(|e| format!("Invalid sync state: {}", e))?;
+
+        let stored = SyncRepository::load_meeting(self.pool, id).await.map_err(|e| format!("Failed to load meeting: {}", e))?;
+        let mut exists_here = stored.is_some();
+        // Missing here since the last pass: deleted here
+        let mut local = stored
+            .or_else(|| base.as_ref().map(|base| if base.deleted { base.clone() } else { base.tombstone(Utc::now()) }));
+
+        let mut remote_tag = remote_tag.cloned();
+        let mut download = match (&remote_tag, state) {
+            (None, _) => false,
+            (Some(tag), Some(state)) => tag.is_none() || *tag != state.remote_tag,
+            (Some(_), None) => true,
+        };
+        let mut outcome = Outcome::default();
+        for _ in 0..MAX_UPLOAD_ATTEMPTS {
+            let (remote, remote_device) = if download {
+                match self.store.get(&object_key).await? {
+                    Some(fetched) => {
+                        let envelope = open_envelope(self.key, &fetched.body, id)?;
+                        // Some servers only report tags in listings
+                        remote_tag = Some(fetched.tag.or(remote_tag.flatten()));
+                        (Some(envelope.meeting), Some(envelope.device))
+                    }
+                    None => {
+                        remote_tag = None;
+                        (None, None)
+                    }
+                }
+            } else if remote_tag.is_some() {
+                // Unchanged since it was last merged
+                (base.clone(), None)
+            } else {
+                (None, None)
+            };
+
+            let Merged { meeting, conflicts } = match (&local, &remote) {
+                (Some(local), Some(remote)) => merge(base.as_ref(), local, remote),
+                // New on one side, or the location lost its copy
+                (Some(only), None) | (None, Some(only)) => Merged { meeting: only.clone(), conflicts: Vec::new() },
+                (None, None) => return Ok(outcome),
+            };
+
+            outcome.conflicts += conflicts.len();
+            if local.as_ref() != Some(&meeting) {
+                if meeting.deleted {
+                    if exists_here {
+                        // Exports are this device's own, made here on purpose
+                        let options = DeleteOptions { keep_exports: true, ..DeleteOptions::default() };
+                        deletion::delete_meeting(self.pool, id, options).await?;
+                        info!("🔄 Deleted meeting {}, deleted on another device", id);
+                        outcome.deleted = true;
+                        exists_here = false;
+                    }
+                } else {
+                    SyncRepository::apply(self.pool, &meeting).await.map_err(|e| format!("Failed to store meeting: {}", e))?;
+                    outcome.pulled = true;
+                    exists_here = true;
+                }
+            }
+
+            for conflict in &conflicts {
+                let local_text = local
+                    .as_ref()
+                    .and_then(|local| local.segments.iter().find(|segment| segment.id == conflict.segment_id))
+                    .map(|segment| &segment.text);
+                // The device whose edit was dropped
+                let other_device =
+                    if local_text == Some(&conflict.kept) { remote_device.as_deref() } else { Some(self.device) };
+                SyncRepository::record_conflict(self.pool, id, &conflict.segment_id, &conflict.kept, &conflict.other, other_device)
+                    .await
+                    .map_err(|e| format!("Failed to record conflict: {}", e))?;
+            }
+            local = Some(meeting.clone());
+
+            let mut tag = remote_tag.clone().flatten();
+            if remote.as_ref() != Some(&meeting) {
+                let sealed = seal_envelope(self.key, &Envelope::new(self.device, meeting.clone()))?;
+                let precondition = match &remote_tag {
+                    None => Precondition::Absent,
+                    Some(Some(tag)) => Precondition::Matches(tag.clone()),
+                    Some(None) => Precondition::Any,
+                };
+                match self.store.put(&object_key, sealed, precondition).await? {
+                    PutOutcome::Stored(new_tag) => {
+                        tag = new_tag;
+                        outcome.pushed = true;
+                    }
+                    PutOutcome::Changed => {
+                        info!("🔄 Meeting {} was uploaded by another device meanwhile; merging it in", id);
+                        download = true;
+                        continue;
+                    }
+                }
+            }
+
+            if download || outcome.pushed || outcome.pulled || base.as_ref() != Some(&meeting) {
+                let json = serde_json::to_string(&meeting).map_err(|e| e.to_string())?;
+                SyncRepository::save_state(self.pool, id, &json, tag.as_deref())
+                    .await
+                    .map_err(|e| format!("Failed to save sync state: {}", e))?;
+            }
+            return Ok(outcome);
+        }
+        Err(format!("Meeting {} kept changing at the sync location; it's retried on the next pass", id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::integrity::memory_pool;
+    use crate::sync::merge::SyncedSegment;
+    use crate::sync::remote::{Fetched, RemoteObject};
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use std::sync::Mutex;
+
+    /// A sync location in memory that checks upload preconditions like the real ones
+    #[derive(Default)]
+    struct MemoryStore {
+        objects: Mutex<HashMap<String, (Vec<u8>, String)>>,
+        uploads: Mutex<u32>,
+    }
+
+    #[async_trait]
+    impl RemoteStore for MemoryStore {
+        async fn list(&self, prefix: &str) -> Result<Vec<RemoteObject>, String> {
+            let objects = self.objects.lock().unwrap();
+            Ok(objects
+                .iter()
+                .filter(|(key, _)| key.starts_with(prefix))
+                .map(|(key, (_, tag))| RemoteObject { key: key.clone(), tag: Some(tag.clone()) })
+                .collect())
+        }
+
+        async fn get(&self, key: &str) -> Result<Option<Fetched>, String> {
+            let objects = self.objects.lock().unwrap();
+            Ok(objects.get(key).map(|(body, tag)| Fetched { body: body.clone(), tag: Some(tag.clone()) }))
+        }
+
+        async fn put(&self, key: &str, body: Vec<u8>, precondition: Precondition) -> Result<PutOutcome, String> {
+            let mut objects = self.objects.lock().unwrap();
+            let current = objects.get(key).map(|(_, tag)| tag.as_str());
+            let holds = match &precondition {
+                Precondition::Absent => current.is_none(),
+                Precondition::Matches(tag) => current == Some(tag.as_str()),
+                Precondition::Any => true,
+            };
+            if !holds {
+                return Ok(PutOutcome::Changed);
+            }
+            let mut uploads = self.uploads.lock().unwrap();
+            *uploads += 1;
+            let tag = format!("v{}", uploads);
+            objects.insert(key.to_string(), (body, tag.clone()));
+            Ok(PutOutcome::Stored(Some(tag)))
+        }
+    }
+
+    fn segment(id: &str, text: &str, start: f64) -> SyncedSegment {
+        SyncedSegment {
+            id: id.to_string(),
+            text: text.to_string(),
+            timestamp: "00:00".to_string(),
+            start: Some(start),
+            end: None,
+            duration: None,
+            speaker: None,
+            translation: None,
+        }
+    }
+
+    fn texts(meeting: &SyncedMeeting) -> Vec<&str> {
+        meeting.segments.iter().map(|segment| segment.text.as_str()).collect()
+    }
+
+    async fn edit(pool: &SqlitePool, segment_id: &str, text: &str) {
+        let mut meeting = SyncRepository::load_meeting(pool, "meeting-1").await.unwrap().unwrap();
+        meeting.segments.iter_mut().find(|segment| segment.id == segment_id).unwrap().text = text.to_string();
+        meeting.updated_at += Duration::seconds(1);
+        SyncRepository::apply(pool, &meeting).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_two_devices_uploading_at_once_keep_both_edits() {
+        let store = MemoryStore::default();
+        let key = [5u8; 32];
+        let (laptop, desktop) = (memory_pool().await, memory_pool().await);
+        let meeting = SyncedMeeting {
+            id: "meeting-1".to_string(),
+            title: "Planning".to_string(),
+            notes: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted: false,
+            segments: vec![segment("s1", "hello", 0.0), segment("s2", "world", 2.0)],
+            summary: None,
+        };
+        SyncRepository::apply(&laptop, &meeting).await.unwrap();
+
+        let (report, _) = sync_meetings(&laptop, &store, &key, "laptop").await.unwrap();
+        assert_eq!(report.pushed, 1);
+        let (report, pulled) = sync_meetings(&desktop, &store, &key, "desktop").await.unwrap();
+        assert_eq!((report.pulled, pulled), (1, vec!["meeting-1".to_string()]));
+
+        edit(&laptop, "s1", "hello there").await;
+        edit(&desktop, "s2", "wide world").await;
+
+        // The desktop lists the location, then the laptop uploads before the desktop does
+        let listed = store.list(MEETINGS_PREFIX).await.unwrap().remove(0).tag;
+        let (report, _) = sync_meetings(&laptop, &store, &key, "laptop").await.unwrap();
+        assert_eq!(report.pushed, 1);
+
+        let states = SyncRepository::list_states(&desktop).await.unwrap();
+        let pass = MeetingPass { pool: &desktop, store: &store, key: &key, device: "desktop" };
+        let outcome = pass.sync("meeting-1", Some(&listed), states.first()).await.unwrap();
+        assert!(outcome.pushed && outcome.pulled);
+        assert_eq!(outcome.conflicts, 0);
+
+        let desktop_copy = SyncRepository::load_meeting(&desktop, "meeting-1").await.unwrap().unwrap();
+        assert_eq!(texts(&desktop_copy), vec!["hello there", "wide world"]);
+        let sealed = store.get("meetings/meeting-1").await.unwrap().unwrap().body;
+        assert_eq!(texts(&open_envelope(&key, &sealed, "meeting-1").unwrap().meeting), vec!["hello there", "wide world"]);
+
+        let (report, _) = sync_meetings(&laptop, &store, &key, "laptop").await.unwrap();
+        assert_eq!((report.pulled, report.pushed), (1, 0));
+        let laptop_copy = SyncRepository::load_meeting(&laptop, "meeting-1").await.unwrap().unwrap();
+        assert_eq!(texts(&laptop_copy), vec!["hello there", "wide world"]);
+    }
+
+    #[tokio::test]
+    async fn test_location_set_up_with_another_key_is_refused() {
+        let store = MemoryStore::default();
+        check_key(&store, &[1u8; 32]).await.unwrap();
+        check_key(&store, &[1u8; 32]).await.unwrap();
+        assert!(check_key(&store, &[2u8; 32]).await.is_err());
+    }
+}
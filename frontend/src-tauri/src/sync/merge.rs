@@ -0,0 +1,230 @@
+// sync/merge.rs
+//
+// A meeting as it's replicated, and the three-way merge of two devices' versions. The merge
+// base is the version both last agreed on. A field changed on one side only takes that
+// side's value; one changed on both takes the newer meeting's, and for transcript text the
+// losing edit is returned as a conflict so the user can bring it back.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncedMeeting {
+    /// The same on every device
+    pub id: String,
+    pub title: String,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Deleted on some device; kept as a tombstone so the others delete it too
+    #[serde(default)]
+    pub deleted: bool,
+    #[serde(default)]
+    pub segments: Vec<SyncedSegment>,
+    /// The stored summary result, as is
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncedSegment {
+    /// The segment's id, the same on every device
+    pub id: String,
+    pub text: String,
+    pub timestamp: String,
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    pub duration: Option<f64>,
+    pub speaker: Option<String>,
+    pub translation: Option<String>,
+}
+
+impl SyncedMeeting {
+    /// What's left of a meeting deleted here since it was last synced
+    pub fn tombstone(&self, now: DateTime<Utc>) -> Self {
+        Self { deleted: true, segments: Vec::new(), summary: None, notes: None, updated_at: now, ..self.clone() }
+    }
+}
+
+/// A transcript segment whose text was edited differently on two devices
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentConflict {
+    pub segment_id: String,
+    /// The text the merge kept
+    pub kept: String,
+    /// The other device's text
+    pub other: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Merged {
+    pub meeting: SyncedMeeting,
+    pub conflicts: Vec<SegmentConflict>,
+}
+
+/// The merged value of a field: the side that changed it, or the winner when both did
+fn pick<T: PartialEq + Clone>(base: Option<&T>, local: &T, remote: &T, local_wins: bool) -> T {
+    if local == remote || base == Some(remote) {
+        local.clone()
+    } else if base == Some(local) || !local_wins {
+        remote.clone()
+    } else {
+        local.clone()
+    }
+}
+
+/// The merged segment, and a conflict when both sides edited its text
+fn merge_segment(
+    base: Option<&SyncedSegment>,
+    local: &SyncedSegment,
+    remote: &SyncedSegment,
+    local_wins: bool,
+) -> (SyncedSegment, Option<SegmentConflict>) {
+    let text = pick(base.map(|b| &b.text), &local.text, &remote.text, local_wins);
+    let both_edited = local.text != remote.text && !matches!(base, Some(b) if b.text == local.text || b.text == remote.text);
+    let conflict = both_edited.then(|| SegmentConflict {
+        segment_id: local.id.clone(),
+        other: if text == local.text { remote.text.clone() } else { local.text.clone() },
+        kept: text.clone(),
+    });
+    let segment = SyncedSegment {
+        text,
+        speaker: pick(base.map(|b| &b.speaker), &local.speaker, &remote.speaker, local_wins),
+        translation: pick(base.map(|b| &b.translation), &local.translation, &remote.translation, local_wins),
+        ..local.clone()
+    };
+    (segment, conflict)
+}
+
+/// Merges this device's version of a meeting with the sync location's, from the version they
+/// last shared (`None` when they never did). Deleting wins over editing.
+pub fn merge(base: Option<&SyncedMeeting>, local: &SyncedMeeting, remote: &SyncedMeeting) -> Merged {
+    let updated_at = local.updated_at.max(remote.updated_at);
+    if local.deleted || remote.deleted {
+        let deleted = if local.deleted { local } else { remote };
+        return Merged { meeting: SyncedMeeting { updated_at, ..deleted.clone() }, conflicts: Vec::new() };
+    }
+    let local_wins = local.updated_at >= remote.updated_at;
+
+    let index = |meeting: Option<&SyncedMeeting>| -> HashMap<String, SyncedSegment> {
+        meeting.map(|m| m.segments.iter().map(|s| (s.id.clone(), s.clone())).collect()).unwrap_or_default()
+    };
+    let (base_segments, local_segments, remote_segments) = (index(base), index(Some(local)), index(Some(remote)));
+    let ids: BTreeSet<&String> = local_segments.keys().chain(remote_segments.keys()).collect();
+
+    let mut segments = Vec::new();
+    let mut conflicts = Vec::new();
+    for id in ids {
+        let base_segment = base_segments.get(id);
+        match (local_segments.get(id), remote_segments.get(id)) {
+            (Some(l), Some(r)) => {
+                let (segment, conflict) = merge_segment(base_segment, l, r, local_wins);
+                segments.push(segment);
+                conflicts.extend(conflict);
+            }
+            // Gone on the other side: deleted there, unless it was edited here since
+            (Some(only), None) | (None, Some(only)) => {
+                if base_segment != Some(only) {
+                    segments.push(only.clone());
+                }
+            }
+            (None, None) => {}
+        }
+    }
+    segments.sort_by(|a, b| {
+        a.start.unwrap_or(f64::MAX).total_cmp(&b.start.unwrap_or(f64::MAX)).then_with(|| a.timestamp.cmp(&b.timestamp))
+    });
+
+    let meeting = SyncedMeeting {
+        id: local.id.clone(),
+        title: pick(base.map(|b| &b.title), &local.title, &remote.title, local_wins),
+        notes: pick(base.map(|b| &b.notes), &local.notes, &remote.notes, local_wins),
+        created_at: local.created_at.min(remote.created_at),
+        updated_at,
+        deleted: false,
+        segments,
+        summary: pick(base.map(|b| &b.summary), &local.summary, &remote.summary, local_wins),
+    };
+    Merged { meeting, conflicts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn segment(id: &str, start: f64, text: &str) -> SyncedSegment {
+        SyncedSegment {
+            id: id.to_string(),
+            text: text.to_string(),
+            timestamp: format!("[00:{:02}]", start as u32),
+            start: Some(start),
+            end: Some(start + 1.0),
+            duration: Some(1.0),
+            speaker: None,
+            translation: None,
+        }
+    }
+
+    fn meeting(minute: u32, segments: Vec<SyncedSegment>) -> SyncedMeeting {
+        SyncedMeeting {
+            id: "meeting-1".to_string(),
+            title: "Planning".to_string(),
+            notes: None,
+            created_at: Utc.with_ymd_and_hms(2025, 11, 14, 9, 0, 0).unwrap(),
+            updated_at: Utc.with_ymd_and_hms(2025, 11, 14, 10, minute, 0).unwrap(),
+            deleted: false,
+            segments,
+            summary: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_takes_each_sides_changes() {
+        let base = meeting(0, vec![segment("a", 0.0, "hello"), segment("b", 1.0, "world"), segment("c", 2.0, "gone")]);
+        let mut local = meeting(5, vec![segment("a", 0.0, "Hello!"), segment("b", 1.0, "world")]);
+        local.title = "Sprint planning".to_string();
+        let mut remote = meeting(3, base.segments.clone());
+        remote.segments[1].speaker = Some("Bob".to_string());
+        remote.segments.push(segment("d", 3.0, "new"));
+        remote.summary = Some("{}".to_string());
+
+        let merged = merge(Some(&base), &local, &remote);
+        assert!(merged.conflicts.is_empty());
+        let texts: Vec<&str> = merged.meeting.segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["Hello!", "world", "new"]);
+        assert_eq!(merged.meeting.segments[1].speaker.as_deref(), Some("Bob"));
+        assert_eq!(merged.meeting.title, "Sprint planning");
+        assert_eq!(merged.meeting.summary.as_deref(), Some("{}"));
+        assert_eq!(merged.meeting.updated_at, local.updated_at);
+    }
+
+    #[test]
+    fn test_merge_conflicting_edits_keep_the_newer() {
+        let base = meeting(0, vec![segment("a", 0.0, "hello")]);
+        let local = meeting(2, vec![segment("a", 0.0, "hello there")]);
+        let remote = meeting(4, vec![segment("a", 0.0, "hello, all")]);
+
+        let merged = merge(Some(&base), &local, &remote);
+        assert_eq!(merged.meeting.segments[0].text, "hello, all");
+        assert_eq!(
+            merged.conflicts,
+            vec![SegmentConflict { segment_id: "a".to_string(), kept: "hello, all".to_string(), other: "hello there".to_string() }]
+        );
+        // Without a shared version, differing texts are a conflict too
+        assert_eq!(merge(None, &local, &remote).conflicts.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_edit_survives_deletion_and_deletion_wins() {
+        let base = meeting(0, vec![segment("a", 0.0, "hello")]);
+        let local = meeting(1, vec![segment("a", 0.0, "hello!")]);
+        let remote = meeting(2, Vec::new());
+        assert_eq!(merge(Some(&base), &local, &remote).meeting.segments.len(), 1);
+
+        let deleted = base.tombstone(Utc.with_ymd_and_hms(2025, 11, 14, 11, 0, 0).unwrap());
+        let merged = merge(Some(&base), &local, &deleted);
+        assert!(merged.meeting.deleted);
+        assert!(merged.meeting.segments.is_empty());
+    }
+}
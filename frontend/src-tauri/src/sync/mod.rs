@@ -0,0 +1,239 @@
+/// Sync module - replicating meetings between the user's devices
+///
+/// Off by default. Once set up, each meeting's title, notes, transcript and summary are copied
+/// to a sync location the user picks (a relay, an S3-compatible bucket or a WebDAV folder) and
+/// from there to their other devices. Everything is encrypted on the device with a sync key
+/// that never leaves it except when the user copies it to another device, so the location
+/// only holds ciphertext. Recordings aren't synced.
+///
+/// Devices sync every few minutes, or on demand. Changes made on both sides are merged field
+/// by field; when a transcript segment was edited on two devices, the newer edit is kept and
+/// the other is listed as a conflict until the user picks one.
+///
+/// This module contains:
+/// - The synced form of a meeting and the merge (`merge`)
+/// - Sync locations (`remote`)
+/// - One sync pass (`engine`)
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+pub mod engine;
+pub mod merge;
+pub mod remote;
+
+use std::io::Cursor;
+use std::time::Duration;
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::audio::session;
+use crate::encryption::{self, files, EncryptionKey};
+use crate::secrets;
+use crate::state::AppState;
+use merge::SyncedMeeting;
+use remote::SyncTarget;
+
+const STORE_FILE: &str = "sync.json";
+
+const SERVICE: &str = "com.meetily.ai.sync";
+const KEY_ACCOUNT: &str = "sync-key";
+/// The relay token, S3 secret access key or WebDAV password
+const SECRET_ACCOUNT: &str = "location-secret";
+
+/// Short, so edits made on other devices arrive soon after the app opens, but not during startup
+const FIRST_RUN_DELAY: Duration = Duration::from_secs(2 * 60);
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_INTERVAL_MINUTES: u32 = 15;
+const MIN_INTERVAL_MINUTES: u32 = 5;
+const MAX_INTERVAL_MINUTES: u32 = 24 * 60;
+
+const ENVELOPE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncSettings {
+    pub enabled: bool,
+    pub target: Option<SyncTarget>,
+    /// Shown on the other devices next to conflicting edits
+    pub device_name: String,
+    pub interval_minutes: u32,
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        Self { enabled: false, target: None, device_name: default_device_name(), interval_minutes: DEFAULT_INTERVAL_MINUTES }
+    }
+}
+
+impl SyncSettings {
+    pub fn normalized(mut self) -> Self {
+        self.interval_minutes = self.interval_minutes.clamp(MIN_INTERVAL_MINUTES, MAX_INTERVAL_MINUTES);
+        self.device_name = self.device_name.trim().chars().take(100).collect();
+        if self.device_name.is_empty() {
+            self.device_name = default_device_name();
+        }
+        self
+    }
+}
+
+fn default_device_name() -> String {
+    sysinfo::System::host_name().unwrap_or_else(|| "This device".to_string())
+}
+
+/// Each workspace syncs with its own location and key
+fn store_key() -> String {
+    format!("sync_settings.{}", crate::workspaces::active().id)
+}
+
+fn service() -> String {
+    crate::workspaces::scoped_service(SERVICE)
+}
+
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> SyncSettings {
+    let settings = match app.store(STORE_FILE) {
+        Ok(store) => store.get(store_key()).and_then(|value| serde_json::from_value(value).ok()).unwrap_or_default(),
+        Err(e) => {
+            warn!("Failed to open {}: {}", STORE_FILE, e);
+            SyncSettings::default()
+        }
+    };
+    SyncSettings::normalized(settings)
+}
+
+fn save_settings<R: Runtime>(app: &AppHandle<R>, settings: &SyncSettings) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    store.set(store_key(), serde_json::to_value(settings).map_err(|e| e.to_string())?);
+    store.save().map_err(|e| format!("Failed to save {}: {}", STORE_FILE, e))
+}
+
+fn load_secret() -> Result<Option<String>, String> {
+    secrets::load(&service(), SECRET_ACCOUNT).map_err(|e| e.to_string())
+}
+
+fn store_secret(secret: &str) -> Result<(), String> {
+    secrets::store(&service(), SECRET_ACCOUNT, secret).map_err(|e| e.to_string())
+}
+
+pub fn load_key() -> Result<Option<EncryptionKey>, String> {
+    match secrets::load(&service(), KEY_ACCOUNT).map_err(|e| e.to_string())? {
+        Some(hex) => encryption::from_hex(&hex).map(Some).ok_or_else(|| "The stored sync key is damaged".to_string()),
+        None => Ok(None),
+    }
+}
+
+fn store_key_hex(key: &EncryptionKey) -> Result<(), String> {
+    secrets::store(&service(), KEY_ACCOUNT, &encryption::to_hex(key)).map_err(|e| e.to_string())
+}
+
+/// What's stored at the sync location for each meeting, encrypted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Envelope {
+    pub version: u32,
+    /// The device that wrote it
+    pub device: String,
+    pub meeting: SyncedMeeting,
+}
+
+impl Envelope {
+    pub fn new(device: &str, meeting: SyncedMeeting) -> Self {
+        Self { version: ENVELOPE_VERSION, device: device.to_string(), meeting }
+    }
+}
+
+fn seal(key: &EncryptionKey, plain: &[u8]) -> Result<Vec<u8>, String> {
+    let mut sealed = Vec::new();
+    files::encrypt(key, &mut Cursor::new(plain), &mut sealed).map_err(|e| format!("Failed to encrypt: {}", e))?;
+    Ok(sealed)
+}
+
+fn unseal(key: &EncryptionKey, sealed: &[u8]) -> Result<Vec<u8>, String> {
+    let mut plain = Vec::new();
+    files::decrypt(key, &mut Cursor::new(sealed), &mut plain)
+        .map_err(|_| "Can't decrypt the synced data; the devices' sync keys differ".to_string())?;
+    Ok(plain)
+}
+
+pub fn seal_envelope(key: &EncryptionKey, envelope: &Envelope) -> Result<Vec<u8>, String> {
+    seal(key, &serde_json::to_vec(envelope).map_err(|e| e.to_string())?)
+}
+
+/// Decrypts the envelope stored for `meeting_id`; one holding another meeting, moved there by
+/// the location, is refused before it can overwrite that meeting here
+pub fn open_envelope(key: &EncryptionKey, sealed: &[u8], meeting_id: &str) -> Result<Envelope, String> {
+    let envelope: Envelope =
+        serde_json::from_slice(&unseal(key, sealed)?).map_err(|e| format!("Invalid synced meeting: {}", e))?;
+    if envelope.version > ENVELOPE_VERSION {
+        return Err("A device with a newer version of the app wrote this meeting; update the app to sync it".to_string());
+    }
+    if envelope.meeting.id != meeting_id {
+        return Err(format!("The synced copy of meeting {} holds meeting {}", meeting_id, envelope.meeting.id));
+    }
+    Ok(envelope)
+}
+
+/// Syncs every `interval_minutes` while sync is on; skipped while recording
+pub fn start_sync_worker<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_RUN_DELAY).await;
+        let pool = AppState::wait_for_pool(&app).await;
+        let mut last_run: Option<std::time::Instant> = None;
+        loop {
+            let settings = load_settings(&app);
+            let interval = Duration::from_secs(settings.interval_minutes as u64 * 60);
+            let due = !matches!(last_run, Some(at) if at.elapsed() < interval);
+            if settings.enabled && due {
+                if session::phase().is_recording() {
+                    info!("🔄 Sync postponed while recording");
+                } else {
+                    last_run = Some(std::time::Instant::now());
+                    match engine::run(&app, &pool, &settings).await {
+                        Ok(report) => info!("🔄 Synced: {:?}", report),
+                        Err(e) => warn!("🔄 Sync failed: {}", e),
+                    }
+                }
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn meeting() -> SyncedMeeting {
+        SyncedMeeting {
+            id: "meeting-1".to_string(),
+            title: "Planning".to_string(),
+            notes: Some("notes".to_string()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted: false,
+            segments: Vec::new(),
+            summary: None,
+        }
+    }
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let key = [7u8; 32];
+        let envelope = Envelope::new("laptop", meeting());
+        let sealed = seal_envelope(&key, &envelope).unwrap();
+        assert!(!sealed.windows(8).any(|window| window == b"Planning"));
+        assert_eq!(open_envelope(&key, &sealed, "meeting-1").unwrap(), envelope);
+        assert!(open_envelope(&[8u8; 32], &sealed, "meeting-1").is_err());
+        // Stored under another meeting's key
+        assert!(open_envelope(&key, &sealed, "meeting-2").is_err());
+    }
+
+    #[test]
+    fn test_settings_normalized() {
+        let settings = SyncSettings { interval_minutes: 1, device_name: "  ".to_string(), ..SyncSettings::default() }.normalized();
+        assert_eq!(settings.interval_minutes, MIN_INTERVAL_MINUTES);
+        assert!(!settings.device_name.is_empty());
+    }
+}
@@ -1,9 +1,20 @@
+use std::sync::Mutex;
 use tauri::{
+    image::Image,
     menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem},
     tray::TrayIconBuilder,
     AppHandle, Emitter, Manager, Runtime,
 };
 
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::state::AppState;
+
+/// How often the tooltip's elapsed time and the icon are refreshed
+const STATUS_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// The recording state last shown by the icon, to redraw it only on changes
+static SHOWN_STATE: Mutex<Option<&'static str>> = Mutex::new(None);
+
 #[derive(Debug, Clone)]
 pub enum RecordingState {
     Stopped,
@@ -13,6 +24,20 @@ pub enum RecordingState {
     Paused,
     Resuming,
     Stopping,
+    /// Stopped, with the last of the audio still being transcribed
+    Transcribing,
+}
+
+impl RecordingState {
+    /// The state as the icon and tooltip show it
+    fn status(&self) -> &'static str {
+        match self {
+            RecordingState::Stopped => "idle",
+            RecordingState::Starting | RecordingState::Recording | RecordingState::Resuming => "recording",
+            RecordingState::Pausing | RecordingState::Paused => "paused",
+            RecordingState::Stopping | RecordingState::Transcribing => "transcribing",
+        }
+    }
 }
 
 pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
@@ -28,6 +53,7 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
 
     // Update tray menu with actual recording state after creation
     update_tray_menu(app);
+    start_status_updates(app.clone());
 
     Ok(())
 }
@@ -39,6 +65,7 @@ fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, item_id: &str) {
         "resume_recording" => resume_recording_handler(app),
         "stop_recording" => stop_recording_handler(app),
         "open_window" => focus_main_window(app),
+        "open_last_meeting" => open_last_meeting_handler(app),
         "settings" => {
             focus_main_window(app);
             if let Some(window) = app.get_webview_window("main") {
@@ -49,7 +76,7 @@ fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, item_id: &str) {
         _ => {}
     }
 }
-fn toggle_recording_handler<R: Runtime>(app: &AppHandle<R>) {
+pub(crate) fn toggle_recording_handler<R: Runtime>(app: &AppHandle<R>) {
     focus_main_window(app);
     let app_clone = app.clone();
     tauri::async_runtime::spawn(async move {
@@ -78,7 +105,7 @@ fn toggle_recording_handler<R: Runtime>(app: &AppHandle<R>) {
     });
 }
 
-fn pause_recording_handler<R: Runtime>(app: &AppHandle<R>) {
+pub(crate) fn pause_recording_handler<R: Runtime>(app: &AppHandle<R>) {
     // Immediately show pausing state
     set_tray_state(app, RecordingState::Pausing);
 
@@ -95,7 +122,7 @@ fn pause_recording_handler<R: Runtime>(app: &AppHandle<R>) {
     });
 }
 
-fn resume_recording_handler<R: Runtime>(app: &AppHandle<R>) {
+pub(crate) fn resume_recording_handler<R: Runtime>(app: &AppHandle<R>) {
     // Immediately show resuming state
     set_tray_state(app, RecordingState::Resuming);
 
@@ -112,7 +139,7 @@ fn resume_recording_handler<R: Runtime>(app: &AppHandle<R>) {
     });
 }
 
-fn stop_recording_handler<R: Runtime>(app: &AppHandle<R>) {
+pub(crate) fn stop_recording_handler<R: Runtime>(app: &AppHandle<R>) {
     // Immediately show stopping state
     set_tray_state(app, RecordingState::Stopping);
 
@@ -131,6 +158,26 @@ fn stop_recording_handler<R: Runtime>(app: &AppHandle<R>) {
     });
 }
 
+fn open_last_meeting_handler<R: Runtime>(app: &AppHandle<R>) {
+    focus_main_window(app);
+    let app_clone = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Some(state) = app_clone.try_state::<AppState>() else {
+            return;
+        };
+        match MeetingsRepository::get_latest_meeting(state.db_manager.pool()).await {
+            Ok(Some(meeting)) => {
+                if let Some(window) = app_clone.get_webview_window("main") {
+                    let url = serde_json::to_string(&format!("/meeting-details?id={}", meeting.id)).unwrap_or_default();
+                    let _ = window.eval(&format!("window.location.assign({})", url));
+                }
+            }
+            Ok(None) => log::info!("Tray: No meetings to open yet"),
+            Err(e) => log::error!("Tray: Failed to load the last meeting: {}", e),
+        }
+    });
+}
+
 pub fn update_tray_menu<R: Runtime>(app: &AppHandle<R>) {
     // For sync update, spawn async task to get current state
     let app_clone = app.clone();
@@ -156,6 +203,11 @@ pub fn set_tray_state<R: Runtime>(app: &AppHandle<R>, state: RecordingState) {
 }
 
 async fn get_current_recording_state() -> RecordingState {
+    // The recording flag stays set until the remaining audio is transcribed
+    if crate::audio::recording_commands::is_finishing_transcription() {
+        return RecordingState::Transcribing;
+    }
+
     // Check if currently recording
     let is_recording = crate::audio::recording_commands::is_recording().await;
     log::info!("Tray: get_current_recording_state - is_recording: {}", is_recording);
@@ -232,11 +284,15 @@ fn build_menu<R: Runtime>(
         RecordingState::Stopping => {
             builder = builder.item(&MenuItemBuilder::new("⏹ Stopping...").enabled(false).build(app)?);
         }
+        RecordingState::Transcribing => {
+            builder = builder.item(&MenuItemBuilder::new("📝 Finishing Transcript...").enabled(false).build(app)?);
+        }
     }
 
     builder
         .item(&PredefinedMenuItem::separator(app)?)
         .item(&MenuItemBuilder::with_id("open_window", "Open Main Window").build(app)?)
+        .item(&MenuItemBuilder::with_id("open_last_meeting", "Open Last Meeting").build(app)?)
         .item(&MenuItemBuilder::with_id("settings", "Settings").build(app)?)
         .item(&PredefinedMenuItem::separator(app)?)
         .item(&MenuItemBuilder::with_id("quit", "Quit").build(app)?)
@@ -253,3 +309,124 @@ fn focus_main_window<R: Runtime>(app: &AppHandle<R>) {
         log::warn!("Could not find main window");
     }
 }
+
+/// Tooltip text, with the elapsed recording time while recording or paused
+fn tooltip(status: &str, elapsed_secs: Option<f64>) -> String {
+    let elapsed = elapsed_secs.map(|secs| {
+        let total = secs.max(0.0) as u64;
+        if total >= 3600 {
+            format!("{}:{:02}:{:02}", total / 3600, total / 60 % 60, total % 60)
+        } else {
+            format!("{:02}:{:02}", total / 60, total % 60)
+        }
+    });
+    match (status, elapsed) {
+        ("recording", Some(elapsed)) => format!("Meetily - Recording {}", elapsed),
+        ("paused", Some(elapsed)) => format!("Meetily - Paused at {}", elapsed),
+        ("transcribing", _) => "Meetily - Finishing transcript".to_string(),
+        _ => "Meetily".to_string(),
+    }
+}
+
+/// RGBA color of the dot drawn on the icon for a status
+fn status_color(status: &str) -> Option<[u8; 4]> {
+    match status {
+        "recording" => Some([229, 57, 53, 255]),
+        "paused" => Some([251, 192, 45, 255]),
+        "transcribing" => Some([30, 136, 229, 255]),
+        _ => None,
+    }
+}
+
+/// Draws a filled dot, a third of the icon wide, in the bottom-right corner of an RGBA image
+fn draw_status_dot(rgba: &mut [u8], width: u32, height: u32, color: [u8; 4]) {
+    let radius = (width.min(height) as f32 / 6.0).max(2.0);
+    let (cx, cy) = (width as f32 - radius - 0.5, height as f32 - radius - 0.5);
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (x as f32 - cx, y as f32 - cy);
+            if dx * dx + dy * dy <= radius * radius {
+                let i = ((y * width + x) * 4) as usize;
+                if let Some(pixel) = rgba.get_mut(i..i + 4) {
+                    pixel.copy_from_slice(&color);
+                }
+            }
+        }
+    }
+}
+
+fn status_icon<R: Runtime>(app: &AppHandle<R>, status: &str) -> Option<Image<'static>> {
+    let base = app.default_window_icon()?;
+    let mut rgba = base.rgba().to_vec();
+    if let Some(color) = status_color(status) {
+        draw_status_dot(&mut rgba, base.width(), base.height(), color);
+    }
+    Some(Image::new_owned(rgba, base.width(), base.height()))
+}
+
+/// Keeps the tooltip's elapsed time current and the icon matching the recording state
+fn start_status_updates<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(STATUS_INTERVAL).await;
+            let Some(tray) = app.tray_by_id("main-tray") else {
+                continue;
+            };
+            let status = get_current_recording_status().await;
+            let elapsed = crate::audio::recording_commands::active_recording_duration();
+            let _ = tray.set_tooltip(Some(tooltip(status, elapsed)));
+
+            let changed = {
+                let mut shown = SHOWN_STATE.lock().unwrap();
+                let changed = *shown != Some(status);
+                *shown = Some(status);
+                changed
+            };
+            if changed {
+                log::info!("Tray: Recording status is now {}", status);
+                if let Some(icon) = status_icon(&app, status) {
+                    let _ = tray.set_icon(Some(icon));
+                }
+                update_tray_menu_async(&app).await;
+            }
+        }
+    });
+}
+
+async fn get_current_recording_status() -> &'static str {
+    if crate::audio::recording_commands::is_finishing_transcription() {
+        return RecordingState::Transcribing.status();
+    }
+    if !crate::audio::recording_commands::is_recording().await {
+        return RecordingState::Stopped.status();
+    }
+    if crate::audio::recording_commands::is_recording_paused().await {
+        RecordingState::Paused.status()
+    } else {
+        RecordingState::Recording.status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tooltip() {
+        assert_eq!(tooltip("recording", Some(754.2)), "Meetily - Recording 12:34");
+        assert_eq!(tooltip("paused", Some(3725.0)), "Meetily - Paused at 1:02:05");
+        assert_eq!(tooltip("transcribing", None), "Meetily - Finishing transcript");
+        assert_eq!(tooltip("idle", None), "Meetily");
+    }
+
+    #[test]
+    fn test_draw_status_dot() {
+        let (width, height) = (12, 12);
+        let mut rgba = vec![0u8; (width * height * 4) as usize];
+        draw_status_dot(&mut rgba, width, height, [255, 0, 0, 255]);
+        let pixel = |x: u32, y: u32| &rgba[((y * width + x) * 4) as usize..][..4];
+        assert_eq!(pixel(10, 10), [255, 0, 0, 255]);
+        assert_eq!(pixel(0, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(5, 10), [0, 0, 0, 0]);
+    }
+}
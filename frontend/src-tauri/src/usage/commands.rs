@@ -0,0 +1,179 @@
+// usage/commands.rs
+//
+// Tauri command for the usage report: what was sent to each hosted provider and model, with
+// estimated costs, overall and per meeting.
+
+use std::collections::HashMap;
+
+use chrono::{Duration, Utc};
+use serde::Serialize;
+
+use super::pricing::{llm_cost, stt_cost};
+use crate::database::models::ProviderUsage;
+use crate::database::repositories::usage::UsageRepository;
+use crate::state::AppState;
+
+/// Usage of one provider and model
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageLine {
+    pub kind: String, // "llm" or "stt"
+    pub provider: String,
+    pub model: String,
+    pub requests: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub audio_minutes: f64,
+    /// Some token counts were estimated because the provider didn't report them
+    pub estimated_tokens: bool,
+    /// None when the model's price isn't known
+    pub estimated_cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MeetingUsage {
+    pub meeting_id: String,
+    /// None once the meeting has been deleted
+    pub meeting_title: Option<String>,
+    pub estimated_cost_usd: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageReport {
+    pub lines: Vec<UsageLine>,
+    /// Meetings by estimated cost, highest first
+    pub meetings: Vec<MeetingUsage>,
+    pub total_cost_usd: f64,
+    /// Some usage has no price, so the total is too low
+    pub has_unpriced: bool,
+}
+
+fn row_cost(row: &ProviderUsage) -> Option<f64> {
+    match row.kind.as_str() {
+        "stt" => stt_cost(&row.provider, &row.model, row.audio_seconds),
+        _ => llm_cost(&row.provider, &row.model, row.prompt_tokens as u64, row.completion_tokens as u64),
+    }
+}
+
+/// Totals usage rows by provider and model, and costs by meeting
+pub fn build_report(rows: &[ProviderUsage]) -> UsageReport {
+    let mut lines: Vec<UsageLine> = Vec::new();
+    let mut meetings: HashMap<&str, MeetingUsage> = HashMap::new();
+    let mut has_unpriced = false;
+
+    for row in rows {
+        let cost = row_cost(row);
+        has_unpriced |= cost.is_none();
+
+        let index = lines
+            .iter()
+            .position(|line| line.kind == row.kind && line.provider == row.provider && line.model == row.model)
+            .unwrap_or_else(|| {
+                lines.push(UsageLine {
+                    kind: row.kind.clone(),
+                    provider: row.provider.clone(),
+                    model: row.model.clone(),
+                    requests: 0,
+                    prompt_tokens: 0,
+                    completion_tokens: 0,
+                    audio_minutes: 0.0,
+                    estimated_tokens: false,
+                    estimated_cost_usd: Some(0.0),
+                });
+                lines.len() - 1
+            });
+        let line = &mut lines[index];
+        line.requests += row.requests;
+        line.prompt_tokens += row.prompt_tokens;
+        line.completion_tokens += row.completion_tokens;
+        line.audio_minutes += row.audio_seconds / 60.0;
+        line.estimated_tokens |= row.estimated;
+        line.estimated_cost_usd = line.estimated_cost_usd.zip(cost).map(|(total, cost)| total + cost);
+
+        if let (Some(meeting_id), Some(cost)) = (row.meeting_id.as_deref(), cost) {
+            meetings
+                .entry(meeting_id)
+                .or_insert_with(|| MeetingUsage {
+                    meeting_id: meeting_id.to_string(),
+                    meeting_title: row.meeting_title.clone(),
+                    estimated_cost_usd: 0.0,
+                })
+                .estimated_cost_usd += cost;
+        }
+    }
+
+    let total_cost_usd = lines.iter().filter_map(|line| line.estimated_cost_usd).sum();
+    lines.sort_by(|a, b| b.estimated_cost_usd.unwrap_or(0.0).total_cmp(&a.estimated_cost_usd.unwrap_or(0.0)));
+    let mut meetings: Vec<MeetingUsage> = meetings.into_values().collect();
+    meetings.sort_by(|a, b| b.estimated_cost_usd.total_cmp(&a.estimated_cost_usd));
+    UsageReport { lines, meetings, total_cost_usd, has_unpriced }
+}
+
+/// Usage of hosted providers with estimated costs, for one meeting or all of them, and
+/// optionally only for the last `days` days
+#[tauri::command]
+pub async fn get_usage_report(
+    state: tauri::State<'_, AppState>,
+    meeting_id: Option<String>,
+    days: Option<u32>,
+) -> Result<UsageReport, String> {
+    let since = days.map(|days| Utc::now() - Duration::days(days as i64));
+    let rows = UsageRepository::list(state.db_manager.pool(), meeting_id.as_deref(), since)
+        .await
+        .map_err(|e| format!("Failed to load usage: {}", e))?;
+    Ok(build_report(&rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::DateTimeUtc;
+
+    fn row(
+        meeting_id: Option<&str>,
+        kind: &str,
+        provider: &str,
+        model: &str,
+        tokens: (i64, i64),
+        seconds: f64,
+    ) -> ProviderUsage {
+        ProviderUsage {
+            id: "usage".to_string(),
+            meeting_id: meeting_id.map(str::to_string),
+            meeting_title: meeting_id.map(|id| format!("Meeting {}", id)),
+            kind: kind.to_string(),
+            purpose: if kind == "stt" { "transcription" } else { "summary" }.to_string(),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            prompt_tokens: tokens.0,
+            completion_tokens: tokens.1,
+            audio_seconds: seconds,
+            requests: 1,
+            estimated: false,
+            created_at: DateTimeUtc(Utc::now()),
+        }
+    }
+
+    #[test]
+    fn test_report_totals_by_model_and_meeting() {
+        let rows = vec![
+            row(Some("a"), "llm", "openai", "gpt-4o", (100_000, 10_000), 0.0),
+            row(Some("b"), "llm", "openai", "gpt-4o", (100_000, 10_000), 0.0),
+            row(Some("a"), "stt", "openai", "whisper-1", (0, 0), 1200.0),
+            row(None, "llm", "openrouter", "meta-llama/llama-3.3-70b-instruct", (5_000, 500), 0.0),
+        ];
+        let report = build_report(&rows);
+
+        assert_eq!(report.lines.len(), 3);
+        assert_eq!((report.lines[0].model.as_str(), report.lines[0].requests), ("gpt-4o", 2));
+        assert!((report.lines[0].estimated_cost_usd.unwrap() - 0.7).abs() < 1e-9);
+        assert!((report.lines[1].audio_minutes - 20.0).abs() < 1e-9);
+        assert_eq!(report.lines[2].estimated_cost_usd, None);
+        assert!(report.has_unpriced);
+        assert!((report.total_cost_usd - 0.82).abs() < 1e-9);
+
+        assert_eq!(report.meetings.len(), 2);
+        assert_eq!(report.meetings[0].meeting_id, "a");
+        assert!((report.meetings[0].estimated_cost_usd - 0.47).abs() < 1e-9);
+        assert_eq!(report.meetings[0].meeting_title.as_deref(), Some("Meeting a"));
+    }
+}
@@ -0,0 +1,84 @@
+// usage/meter.rs
+//
+// Cloud transcription sends audio a VAD segment at a time, from the recording workers, live
+// captions and file transcription, none of which know which meeting the audio will end up
+// in. The seconds sent are added up here per provider and model, and written to the
+// database when a transcript is saved as a meeting. Audio that never becomes a meeting (a
+// discarded recording, the provider self-test) is written without one when the next
+// recording starts.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use log::warn;
+use once_cell::sync::Lazy;
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::database::repositories::usage::{NewProviderUsage, UsageRepository};
+use crate::state::AppState;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Pending {
+    audio_seconds: f64,
+    requests: u64,
+}
+
+/// Audio sent since the last flush, by (provider id, model)
+static PENDING: Lazy<Mutex<HashMap<(String, String), Pending>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Counts one successful request of `seconds` of audio to a cloud provider
+pub fn record_audio(provider: &str, model: &str, seconds: f64) {
+    let mut pending = PENDING.lock().unwrap();
+    let entry = pending.entry((provider.to_string(), model.to_string())).or_default();
+    entry.audio_seconds += seconds;
+    entry.requests += 1;
+}
+
+fn take_pending() -> Vec<((String, String), Pending)> {
+    PENDING.lock().unwrap().drain().collect()
+}
+
+/// Writes the audio sent since the last flush, against `meeting_id` when it's known
+pub async fn flush(pool: &SqlitePool, meeting_id: Option<&str>) {
+    for ((provider, model), pending) in take_pending() {
+        let row = NewProviderUsage {
+            meeting_id,
+            kind: "stt",
+            purpose: "transcription",
+            provider: &provider,
+            model: &model,
+            audio_seconds: pending.audio_seconds,
+            requests: pending.requests,
+            ..Default::default()
+        };
+        if let Err(e) = UsageRepository::record(pool, &row).await {
+            warn!("Failed to record transcription usage for {}: {}", provider, e);
+        }
+    }
+}
+
+/// Writes audio sent before a new recording starts, so it isn't counted toward its meeting
+pub async fn flush_unattributed<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(state) = app.try_state::<AppState>() {
+        flush(state.db_manager.pool(), None).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_adds_up_per_provider_and_model() {
+        record_audio("test-provider", "a", 1.5);
+        record_audio("test-provider", "a", 2.0);
+        record_audio("test-provider", "b", 4.0);
+        let mut pending: Vec<_> =
+            take_pending().into_iter().filter(|((provider, _), _)| provider == "test-provider").collect();
+        pending.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(pending[0].1, Pending { audio_seconds: 3.5, requests: 2 });
+        assert_eq!(pending[1].1, Pending { audio_seconds: 4.0, requests: 1 });
+        assert!(take_pending().iter().all(|((provider, _), _)| provider != "test-provider"));
+    }
+}
@@ -0,0 +1,57 @@
+/// Usage module - what the app sends to hosted providers, and roughly what it costs
+///
+/// Every call to a hosted summary model (summaries, action item and key point extraction,
+/// Q&A) is recorded with its token counts, and the audio sent to cloud speech-to-text is
+/// metered and recorded against the meeting it was transcribed for. Local providers
+/// (Whisper, Parakeet, Ollama, llama.cpp, the built-in model) cost nothing and aren't
+/// recorded. Costs are estimated from list prices when a report is made, so they aren't
+/// stored and may differ from the provider's bill.
+///
+/// This module contains:
+/// - Metering of cloud transcription audio (`meter`)
+/// - List prices (`pricing`)
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+pub mod meter;
+pub mod pricing;
+
+use log::warn;
+use sqlx::SqlitePool;
+
+use crate::database::repositories::usage::{NewProviderUsage, UsageRepository};
+use crate::summary::llm_client::LLMProvider;
+use crate::summary::provider::{SummaryProvider, TokenUsage};
+
+/// Records a hosted summary model call; `purpose` is "summary", "action_items",
+/// "key_points" or "qa"
+pub async fn record_llm(
+    pool: &SqlitePool,
+    meeting_id: Option<&str>,
+    purpose: &str,
+    provider: &dyn SummaryProvider,
+    usage: &TokenUsage,
+) {
+    if !provider.is_remote() {
+        return;
+    }
+    let provider_id = LLMProvider::ALL
+        .iter()
+        .find(|candidate| candidate.display_name() == provider.provider_name())
+        .map_or(provider.provider_name(), |candidate| candidate.id());
+    let row = NewProviderUsage {
+        meeting_id,
+        kind: "llm",
+        purpose,
+        provider: provider_id,
+        model: provider.model(),
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+        requests: 1,
+        estimated: usage.estimated,
+        ..Default::default()
+    };
+    if let Err(e) = UsageRepository::record(pool, &row).await {
+        warn!("Failed to record {} usage for {}: {}", purpose, provider_id, e);
+    }
+}
@@ -0,0 +1,135 @@
+// usage/pricing.rs
+//
+// Published list prices for the hosted models the app can use, in USD. Models are matched
+// on the longest listed prefix of their name, so dated and `-latest` variants share their
+// family's price; models that aren't listed (new releases, fine-tunes) have no estimate
+// rather than a guess. Prices change and discounts (batch, cached input, committed use)
+// aren't applied, so these are estimates for comparing, not a bill.
+
+/// (provider id, model prefix, input, output) in USD per million tokens
+const LLM_PRICES: &[(&str, &str, f64, f64)] = &[
+    ("openai", "gpt-5", 1.25, 10.0),
+    ("openai", "gpt-5-mini", 0.25, 2.0),
+    ("openai", "gpt-5-nano", 0.05, 0.4),
+    ("openai", "gpt-4.1", 2.0, 8.0),
+    ("openai", "gpt-4.1-mini", 0.4, 1.6),
+    ("openai", "gpt-4.1-nano", 0.1, 0.4),
+    ("openai", "gpt-4o", 2.5, 10.0),
+    ("openai", "gpt-4o-mini", 0.15, 0.6),
+    ("openai", "gpt-4-turbo", 10.0, 30.0),
+    ("openai", "gpt-4", 30.0, 60.0),
+    ("openai", "gpt-3.5-turbo", 0.5, 1.5),
+    ("openai", "o1", 15.0, 60.0),
+    ("openai", "o1-mini", 1.1, 4.4),
+    ("openai", "o3", 2.0, 8.0),
+    ("openai", "o3-mini", 1.1, 4.4),
+    ("openai", "o3-pro", 20.0, 80.0),
+    ("openai", "o4-mini", 1.1, 4.4),
+    ("claude", "claude-opus-4", 15.0, 75.0),
+    ("claude", "claude-opus-4-5", 5.0, 25.0),
+    ("claude", "claude-sonnet-4", 3.0, 15.0),
+    ("claude", "claude-haiku-4-5", 1.0, 5.0),
+    ("claude", "claude-3-opus", 15.0, 75.0),
+    ("claude", "claude-3-7-sonnet", 3.0, 15.0),
+    ("claude", "claude-3-5-sonnet", 3.0, 15.0),
+    ("claude", "claude-3-5-haiku", 0.8, 4.0),
+    ("claude", "claude-3-haiku", 0.25, 1.25),
+    ("groq", "llama-3.3-70b-versatile", 0.59, 0.79),
+    ("groq", "llama-3.1-8b-instant", 0.05, 0.08),
+    ("groq", "gemma2-9b-it", 0.2, 0.2),
+    ("groq", "openai/gpt-oss-120b", 0.15, 0.75),
+    ("groq", "openai/gpt-oss-20b", 0.1, 0.5),
+];
+
+/// (provider id, model prefix, USD per minute of audio)
+const STT_PRICES: &[(&str, &str, f64)] = &[
+    ("openai", "whisper-1", 0.006),
+    ("openai", "gpt-4o-transcribe", 0.006),
+    ("openai", "gpt-4o-mini-transcribe", 0.003),
+    ("deepgram", "nova", 0.0043),
+    ("deepgram", "enhanced", 0.0145),
+    ("deepgram", "base", 0.0125),
+    ("assemblyAI", "best", 0.0062),
+    ("assemblyAI", "universal", 0.0025),
+    ("assemblyAI", "nano", 0.002),
+];
+
+/// Model names compared with dots as dashes, since OpenRouter writes `claude-3.5-sonnet`
+fn normalize(model: &str) -> String {
+    model.to_lowercase().replace('.', "-")
+}
+
+/// The entry whose provider matches and whose prefix is the longest one `model` starts with
+fn longest_match<'a, T>(
+    entries: &'a [T],
+    key: impl Fn(&T) -> (&str, &str),
+    provider: &str,
+    model: &str,
+) -> Option<&'a T> {
+    let model = normalize(model);
+    entries
+        .iter()
+        .filter(|entry| {
+            let (entry_provider, prefix) = key(entry);
+            entry_provider == provider && model.starts_with(&normalize(prefix))
+        })
+        .max_by_key(|entry| key(entry).1.len())
+}
+
+/// (input, output) USD per million tokens for a summary model
+pub fn llm_price(provider: &str, model: &str) -> Option<(f64, f64)> {
+    let find = |provider: &str, model: &str| {
+        longest_match(LLM_PRICES, |(provider, prefix, _, _)| (provider, prefix), provider, model)
+            .map(|(_, _, input, output)| (*input, *output))
+    };
+    if provider != "openrouter" {
+        return find(provider, model);
+    }
+    // OpenRouter passes OpenAI and Anthropic models through at their providers' prices
+    let (vendor, name) = model.split_once('/')?;
+    match vendor {
+        "openai" => find("openai", name),
+        "anthropic" => find("claude", name),
+        _ => None,
+    }
+}
+
+/// Estimated USD for a summary model call, if the model's price is known
+pub fn llm_cost(provider: &str, model: &str, prompt_tokens: u64, completion_tokens: u64) -> Option<f64> {
+    let (input, output) = llm_price(provider, model)?;
+    Some((prompt_tokens as f64 * input + completion_tokens as f64 * output) / 1_000_000.0)
+}
+
+/// Estimated USD for audio sent to a cloud transcription model, if its price is known
+pub fn stt_cost(provider: &str, model: &str, audio_seconds: f64) -> Option<f64> {
+    longest_match(STT_PRICES, |(provider, prefix, _)| (provider, prefix), provider, model)
+        .map(|(_, _, per_minute)| per_minute * audio_seconds / 60.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_llm_price_uses_the_longest_prefix() {
+        assert_eq!(llm_price("openai", "gpt-4o-2024-11-20"), Some((2.5, 10.0)));
+        assert_eq!(llm_price("openai", "gpt-4o-mini"), Some((0.15, 0.6)));
+        assert_eq!(llm_price("openai", "gpt-4.1-mini-2025-04-14"), Some((0.4, 1.6)));
+        assert_eq!(llm_price("claude", "claude-3-5-sonnet-latest"), Some((3.0, 15.0)));
+        assert_eq!(llm_price("claude", "claude-opus-4-5-20251101"), Some((5.0, 25.0)));
+        assert_eq!(llm_price("groq", "openai/gpt-oss-120b"), Some((0.15, 0.75)));
+        assert_eq!(llm_price("openrouter", "anthropic/claude-3.5-sonnet"), Some((3.0, 15.0)));
+        assert_eq!(llm_price("openrouter", "openai/gpt-4o"), Some((2.5, 10.0)));
+        assert_eq!(llm_price("openrouter", "meta-llama/llama-3.3-70b-instruct"), None);
+        assert_eq!(llm_price("claude", "gpt-4o"), None);
+    }
+
+    #[test]
+    fn test_costs() {
+        let cost = llm_cost("openai", "gpt-4o", 10_000, 1_000).unwrap();
+        assert!((cost - 0.035).abs() < 1e-9);
+        assert!((stt_cost("openai", "whisper-1", 600.0).unwrap() - 0.06).abs() < 1e-9);
+        assert!((stt_cost("deepgram", "nova-2", 60.0).unwrap() - 0.0043).abs() < 1e-9);
+        assert_eq!(stt_cost("assemblyAI", "slam-1", 60.0), None);
+    }
+}
@@ -1,3 +1,10 @@
+use std::time::Duration;
+
+use tauri::{AppHandle, Runtime};
+
+/// Lets the command's result reach the UI before the app goes down
+const RESTART_DELAY: Duration = Duration::from_millis(500);
+
 pub fn format_timestamp(seconds: f64) -> String {
     let total_seconds = seconds as u64;
     let hours = total_seconds / 3600;
@@ -6,20 +13,18 @@ pub fn format_timestamp(seconds: f64) -> String {
     format!("{:02}:{:02}:{:02}", hours, minutes, secs)
 }
 
+/// Restarts the app once a command that needs a restart has returned
+pub fn restart_soon<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(RESTART_DELAY).await;
+        app.request_restart();
+    });
+}
+
 /// Opens macOS System Settings to a specific privacy preference pane
 #[cfg(target_os = "macos")]
 #[tauri::command]
 pub async fn open_system_settings(preference_pane: String) -> Result<(), String> {
-    use std::process::Command;
-
-    // Construct the URL for System Settings
-    let url = format!("x-apple.systempreferences:com.apple.preference.security?{}", preference_pane);
-
-    // Use the 'open' command on macOS to open the URL
-    Command::new("open")
-        .arg(&url)
-        .spawn()
-        .map_err(|e| format!("Failed to open system settings: {}", e))?;
-
-    Ok(())
+    // The URL differs between System Settings and the older System Preferences
+    crate::audio::permissions::open_privacy_settings(&preference_pane).map_err(|e| e.to_string())
 } 
\ No newline at end of file
@@ -0,0 +1,220 @@
+// GPU backend selection for local Whisper transcription
+//
+// whisper.cpp backends are chosen at build time (Metal on macOS, CUDA/Vulkan via cargo
+// features, Vulkan by default on Windows), so at runtime the choice is between the GPU
+// backend compiled into this build and the CPU. A backend is only used when it is both
+// compiled in and detected on the machine; otherwise the preference falls back to the
+// best available option and the reason is reported in diagnostics.
+
+use std::sync::RwLock;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::audio::{GpuType, HardwareProfile, PerformanceTier};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptionBackend {
+    /// Best GPU backend available, else CPU
+    #[default]
+    Auto,
+    Cpu,
+    Metal,
+    Cuda,
+    Vulkan,
+}
+
+impl TranscriptionBackend {
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "cpu" => Some(Self::Cpu),
+            "metal" => Some(Self::Metal),
+            "cuda" => Some(Self::Cuda),
+            "vulkan" => Some(Self::Vulkan),
+            _ => None,
+        }
+    }
+
+    fn from_gpu_type(gpu: &GpuType) -> Option<Self> {
+        match gpu {
+            GpuType::Metal => Some(Self::Metal),
+            GpuType::Cuda => Some(Self::Cuda),
+            GpuType::Vulkan => Some(Self::Vulkan),
+            GpuType::OpenCL | GpuType::None => None,
+        }
+    }
+
+    pub fn is_gpu(&self) -> bool {
+        matches!(self, Self::Metal | Self::Cuda | Self::Vulkan)
+    }
+}
+
+/// GPU backends whisper.cpp was built with
+pub fn compiled_backends() -> Vec<TranscriptionBackend> {
+    let mut backends = Vec::new();
+    if cfg!(any(target_os = "macos", feature = "metal")) {
+        backends.push(TranscriptionBackend::Metal);
+    }
+    if cfg!(feature = "cuda") {
+        backends.push(TranscriptionBackend::Cuda);
+    }
+    if cfg!(any(target_os = "windows", feature = "vulkan")) {
+        backends.push(TranscriptionBackend::Vulkan);
+    }
+    backends
+}
+
+/// GPU backends whose runtime is present on this machine
+pub fn detected_backends() -> Vec<TranscriptionBackend> {
+    HardwareProfile::detected_gpu_types()
+        .iter()
+        .filter_map(TranscriptionBackend::from_gpu_type)
+        .collect()
+}
+
+/// Backend a model is loaded with, and why
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResolvedBackend {
+    pub backend: TranscriptionBackend,
+    pub use_gpu: bool,
+    pub flash_attention: bool,
+    /// Set when the preference could not be honoured
+    pub fallback_reason: Option<String>,
+}
+
+/// Pick the backend for a preference given what is compiled in and detected
+pub fn resolve_backend(
+    preference: TranscriptionBackend,
+    compiled: &[TranscriptionBackend],
+    detected: &[TranscriptionBackend],
+    tier: &PerformanceTier,
+) -> ResolvedBackend {
+    let usable: Vec<TranscriptionBackend> = [TranscriptionBackend::Metal, TranscriptionBackend::Cuda, TranscriptionBackend::Vulkan]
+        .into_iter()
+        .filter(|b| compiled.contains(b) && detected.contains(b))
+        .collect();
+
+    let (backend, fallback_reason) = match preference {
+        TranscriptionBackend::Cpu => (TranscriptionBackend::Cpu, None),
+        TranscriptionBackend::Auto => (usable.first().copied().unwrap_or(TranscriptionBackend::Cpu), None),
+        requested if usable.contains(&requested) => (requested, None),
+        requested => {
+            let reason = if !compiled.contains(&requested) {
+                format!("{:?} support is not included in this build", requested)
+            } else {
+                format!("No {:?}-capable GPU was detected", requested)
+            };
+            (usable.first().copied().unwrap_or(TranscriptionBackend::Cpu), Some(reason))
+        }
+    };
+
+    // Flash attention needs mature drivers; only enable it on fast Metal/CUDA machines
+    let flash_attention = matches!(backend, TranscriptionBackend::Metal | TranscriptionBackend::Cuda)
+        && matches!(tier, PerformanceTier::Ultra | PerformanceTier::High);
+
+    ResolvedBackend { backend, use_gpu: backend.is_gpu(), flash_attention, fallback_reason }
+}
+
+static BACKEND_PREFERENCE: Lazy<RwLock<TranscriptionBackend>> = Lazy::new(|| RwLock::new(TranscriptionBackend::default()));
+
+/// Backend the currently loaded model runs on
+static ACTIVE_BACKEND: Lazy<RwLock<Option<ResolvedBackend>>> = Lazy::new(|| RwLock::new(None));
+
+pub fn get_backend_preference() -> TranscriptionBackend {
+    *BACKEND_PREFERENCE.read().unwrap()
+}
+
+/// Applies the next time a model is loaded
+pub fn set_backend_preference(backend: TranscriptionBackend) {
+    *BACKEND_PREFERENCE.write().unwrap() = backend;
+    info!("🖥️ Transcription backend preference set to {:?}", backend);
+}
+
+/// Resolve the current preference for this machine
+pub fn resolve_current_backend() -> ResolvedBackend {
+    let profile = HardwareProfile::detect();
+    let resolved = resolve_backend(
+        get_backend_preference(),
+        &compiled_backends(),
+        &detected_backends(),
+        &profile.performance_tier,
+    );
+    if let Some(reason) = &resolved.fallback_reason {
+        warn!("⚠️ {}; using {:?} for transcription", reason, resolved.backend);
+    }
+    resolved
+}
+
+pub fn set_active_backend(backend: Option<ResolvedBackend>) {
+    *ACTIVE_BACKEND.write().unwrap() = backend;
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendDiagnostics {
+    pub preference: TranscriptionBackend,
+    /// None until a model is loaded
+    pub active: Option<ResolvedBackend>,
+    pub compiled: Vec<TranscriptionBackend>,
+    pub detected: Vec<TranscriptionBackend>,
+    pub model: Option<String>,
+}
+
+#[tauri::command]
+pub async fn get_transcription_backend_diagnostics() -> Result<BackendDiagnostics, String> {
+    let engine = super::commands::WHISPER_ENGINE.lock().unwrap().as_ref().cloned();
+    let model = match engine {
+        Some(engine) => engine.get_current_model().await,
+        None => None,
+    };
+
+    Ok(BackendDiagnostics {
+        preference: get_backend_preference(),
+        active: ACTIVE_BACKEND.read().unwrap().clone(),
+        compiled: compiled_backends(),
+        detected: detected_backends(),
+        model,
+    })
+}
+
+/// Change the backend and reload the active model with it
+#[tauri::command]
+pub async fn set_transcription_backend(backend: String) -> Result<BackendDiagnostics, String> {
+    let backend = TranscriptionBackend::from_string(&backend)
+        .ok_or_else(|| format!("Unknown transcription backend: {}", backend))?;
+    set_backend_preference(backend);
+
+    let engine = super::commands::WHISPER_ENGINE.lock().unwrap().as_ref().cloned();
+    if let Some(engine) = engine {
+        engine
+            .reload_model()
+            .await
+            .map_err(|e| format!("Failed to reload model with the new backend: {}", e))?;
+    }
+    get_transcription_backend_diagnostics().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TranscriptionBackend::*;
+
+    #[test]
+    fn test_auto_prefers_usable_gpu() {
+        let resolved = resolve_backend(Auto, &[Cuda, Vulkan], &[Vulkan], &PerformanceTier::High);
+        assert_eq!(resolved.backend, Vulkan);
+        assert!(resolved.use_gpu && !resolved.flash_attention);
+        assert_eq!(resolve_backend(Auto, &[], &[Cuda], &PerformanceTier::High).backend, Cpu);
+    }
+
+    #[test]
+    fn test_unavailable_request_falls_back_with_reason() {
+        let resolved = resolve_backend(Cuda, &[Vulkan], &[Cuda, Vulkan], &PerformanceTier::Ultra);
+        assert_eq!(resolved.backend, Vulkan);
+        assert!(resolved.fallback_reason.unwrap().contains("not included"));
+
+        let resolved = resolve_backend(Cpu, &[Metal], &[Metal], &PerformanceTier::Ultra);
+        assert!(!resolved.use_gpu && resolved.fallback_reason.is_none());
+    }
+}
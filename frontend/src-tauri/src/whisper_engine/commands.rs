@@ -462,6 +462,83 @@ pub async fn whisper_delete_corrupted_model(model_name: String) -> Result<String
     }
 }
 
+#[command]
+pub async fn whisper_delete_model(model_name: String) -> Result<String, String> {
+    let engine = {
+        let guard = WHISPER_ENGINE.lock().unwrap();
+        guard.as_ref().cloned()
+    };
+
+    if let Some(engine) = engine {
+        engine
+            .delete_model(&model_name)
+            .await
+            .map_err(|e| format!("Failed to delete model: {}", e))
+    } else {
+        Err("Whisper engine not initialized".to_string())
+    }
+}
+
+/// Re-check a downloaded model against its SHA-256; a mismatch marks it corrupted
+#[command]
+pub async fn whisper_verify_model(model_name: String) -> Result<bool, String> {
+    let engine = {
+        let guard = WHISPER_ENGINE.lock().unwrap();
+        guard.as_ref().cloned()
+    };
+
+    if let Some(engine) = engine {
+        engine
+            .verify_model(&model_name)
+            .await
+            .map_err(|e| format!("Failed to verify model: {}", e))
+    } else {
+        Err("Whisper engine not initialized".to_string())
+    }
+}
+
+/// Switch the active model at runtime, including during a recording, and make it the
+/// model used for future meetings
+#[command]
+pub async fn whisper_switch_model<R: Runtime>(
+    app_handle: AppHandle<R>,
+    model_name: String,
+) -> Result<(), String> {
+    let engine = {
+        let guard = WHISPER_ENGINE.lock().unwrap();
+        guard.as_ref().cloned()
+    };
+    let engine = engine.ok_or_else(|| "Whisper engine not initialized".to_string())?;
+
+    if let Err(e) = app_handle.emit("model-loading-started", serde_json::json!({ "modelName": model_name })) {
+        log::error!("Failed to emit model-loading-started event: {}", e);
+    }
+
+    if let Err(e) = engine.switch_model(&model_name).await {
+        let error = format!("Failed to switch model: {}", e);
+        let _ = app_handle.emit(
+            "model-loading-failed",
+            serde_json::json!({ "modelName": model_name, "error": error }),
+        );
+        return Err(error);
+    }
+
+    crate::api::api::api_save_transcript_config(
+        app_handle.clone(),
+        app_handle.state(),
+        "localWhisper".to_string(),
+        model_name.clone(),
+        None,
+        None,
+    )
+    .await?;
+
+    if let Err(e) = app_handle.emit("model-loading-completed", serde_json::json!({ "modelName": model_name })) {
+        log::error!("Failed to emit model-loading-completed event: {}", e);
+    }
+    Ok(())
+}
+
 /// Open the models folder in the system file explorer
 #[command]
 pub async fn open_models_folder() -> Result<(), String> {
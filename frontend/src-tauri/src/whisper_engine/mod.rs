@@ -1,5 +1,7 @@
 pub mod whisper_engine;
 pub mod commands;
+pub use meeting_core::whisper_engine::models;
+pub mod acceleration;
 pub mod system_monitor;
 pub mod parallel_processor;
 pub mod parallel_commands;
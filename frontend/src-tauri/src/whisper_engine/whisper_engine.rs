@@ -11,6 +11,9 @@ use reqwest::Client;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use crate::{perf_debug, perf_trace};
+use crate::audio::transcription::words::{words_from_tokens, TranscriptWord};
+use super::acceleration::{resolve_current_backend, set_active_backend, TranscriptionBackend};
+use super::models::{checksum_path, fetch_expected_sha256, find_model, sha256_file, DownloadHasher, MODEL_CATALOG};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ModelStatus {
@@ -169,27 +172,9 @@ impl WhisperEngine {
     pub async fn discover_models(&self) -> Result<Vec<ModelInfo>> {
         let models_dir = &self.models_dir;
         let mut models = Vec::new();
-                // Using standard ggerganov/whisper.cpp GGML models
-        let model_configs = [
-            // Standard f16 models (full precision)
-            ("tiny", "ggml-tiny.bin", 39, "Decent", "Very Fast", "Fastest processing, good for real-time use"),
-            ("base", "ggml-base.bin", 142, "Good", "Fast", "Good balance of speed and accuracy"),
-            ("small", "ggml-small.bin", 466, "Good", "Medium", "Better accuracy, moderate speed"),
-            ("medium", "ggml-medium.bin", 1420, "High", "Slow", "High accuracy for professional use"),
-            ("large-v3-turbo", "ggml-large-v3-turbo.bin", 809, "High", "Medium", "Best accuracy with improved speed"),
-            ("large-v3", "ggml-large-v3.bin", 2870, "High", "Slow", "Best accuracy, latest large model"),
-
-            // Q5_0 quantized models (balanced speed/accuracy)
-            ("tiny-q5_0", "ggml-tiny-q5_0.bin", 26, "Decent", "Very Fast", "Quantized tiny model, ~50% faster processing"),
-            ("base-q5_0", "ggml-base-q5_0.bin", 85, "Good", "Fast", "Quantized base model, good speed/accuracy balance"),
-            ("small-q5_0", "ggml-small-q5_0.bin", 280, "Good", "Fast", "Quantized small model, faster than f16 version"),
-            ("medium-q5_0", "ggml-medium-q5_0.bin", 852, "High", "Medium", "Quantized medium model, professional quality"),
-            ("large-v3-turbo-q5_0", "ggml-large-v3-turbo-q5_0.bin", 574, "High", "Medium", "Quantized large model, best balance"),
-            ("large-v3-q5_0", "ggml-large-v3-q5_0.bin", 1050, "High", "Slow", "Quantized large model, high accuracy"),
-
-           ];
-        
-        for (name, filename, size_mb, accuracy, speed, description) in model_configs {
+        for spec in MODEL_CATALOG {
+            let (name, filename, size_mb, accuracy, speed, description) =
+                (spec.name, spec.filename, spec.size_mb, spec.accuracy, spec.speed, spec.description);
             let model_path = models_dir.join(filename);
             let status = if model_path.exists() {
                 // Check if file size is reasonable (at least 1MB for a valid model)
@@ -293,55 +278,11 @@ impl WhisperEngine {
                 }
 
                 log::info!("Loading model: {}", model_name);
-
-                // PERFORMANCE OPTIMIZATION: Use comprehensive hardware profile for optimal GPU configuration
-                let hardware_profile = crate::audio::HardwareProfile::detect();
-                let adaptive_config = hardware_profile.get_whisper_config();
-
-                // Enable flash attention for high-end GPUs (Metal on Apple Silicon, CUDA on NVIDIA)
-                // Flash attention provides 20-40% speedup but requires stable GPU drivers
-                let flash_attn_enabled = match (&hardware_profile.gpu_type, &hardware_profile.performance_tier) {
-                    (crate::audio::GpuType::Metal, crate::audio::PerformanceTier::Ultra | crate::audio::PerformanceTier::High) => true,
-                    (crate::audio::GpuType::Cuda, crate::audio::PerformanceTier::Ultra | crate::audio::PerformanceTier::High) => true,
-                    _ => false, // Conservative: disable for other GPU types and lower tiers
-                };
-
-                let context_param = WhisperContextParameters {
-                    use_gpu: adaptive_config.use_gpu,
-                    gpu_device: 0,
-                    flash_attn: flash_attn_enabled,
-                    ..Default::default()
-                };
-
-                // PERFORMANCE: Suppress verbose C library logs during model loading
-                // This hides the excessive Metal/GGML initialization logs in release builds
-                let ctx = {
-                    // let _suppressor = crate::whisper_engine::StderrSuppressor::new();
-
-                    // Load whisper context with hardware-optimized parameters
-                    WhisperContext::new_with_params(&model_info.path.to_string_lossy(), context_param)
-                        .map_err(|e| anyhow!("Failed to load model {}: {}", model_name, e))?
-                    // Suppressor dropped here, stderr restored
-                };
+                let ctx = Self::create_context(model_name, &model_info.path)?;
 
                 // Update current context and model
                 *self.current_context.write().await = Some(ctx);
                 *self.current_model.write().await = Some(model_name.to_string());
-
-                // Enhanced acceleration status reporting
-                let acceleration_status = match (&hardware_profile.gpu_type, flash_attn_enabled) {
-                    (crate::audio::GpuType::Metal, true) => "Metal GPU with Flash Attention (Ultra-Fast)",
-                    (crate::audio::GpuType::Metal, false) => "Metal GPU acceleration",
-                    (crate::audio::GpuType::Cuda, true) => "CUDA GPU with Flash Attention (Ultra-Fast)",
-                    (crate::audio::GpuType::Cuda, false) => "CUDA GPU acceleration",
-                    (crate::audio::GpuType::Vulkan, _) => "Vulkan GPU acceleration",
-                    (crate::audio::GpuType::OpenCL, _) => "OpenCL GPU acceleration",
-                    (crate::audio::GpuType::None, _) => "CPU processing only",
-                };
-
-                log::info!("Successfully loaded model: {} with {} (Performance Tier: {:?}, Beam Size: {}, Threads: {:?})",
-                          model_name, acceleration_status, hardware_profile.performance_tier,
-                          adaptive_config.beam_size, adaptive_config.max_threads);
                 Ok(())
             },
             ModelStatus::Missing => {
@@ -359,6 +300,133 @@ impl WhisperEngine {
         }
     }
 
+    /// Create a whisper context for a model file with hardware-optimized parameters
+    fn create_context(model_name: &str, model_path: &PathBuf) -> Result<WhisperContext> {
+        // PERFORMANCE OPTIMIZATION: Use comprehensive hardware profile for optimal GPU configuration
+        let hardware_profile = crate::audio::HardwareProfile::detect();
+        let adaptive_config = hardware_profile.get_whisper_config();
+
+        // GPU vs CPU follows the user's backend preference, limited to what this build
+        // and machine support; flash attention only on fast Metal/CUDA machines
+        let backend = resolve_current_backend();
+
+        let context_param = WhisperContextParameters {
+            use_gpu: backend.use_gpu,
+            gpu_device: 0,
+            flash_attn: backend.flash_attention,
+            ..Default::default()
+        };
+
+        // PERFORMANCE: Suppress verbose C library logs during model loading
+        // This hides the excessive Metal/GGML initialization logs in release builds
+        let ctx = {
+            // let _suppressor = crate::whisper_engine::StderrSuppressor::new();
+
+            // Load whisper context with hardware-optimized parameters
+            WhisperContext::new_with_params(&model_path.to_string_lossy(), context_param)
+                .map_err(|e| anyhow!("Failed to load model {}: {}", model_name, e))?
+            // Suppressor dropped here, stderr restored
+        };
+
+        // Enhanced acceleration status reporting
+        let acceleration_status = match (backend.backend, backend.flash_attention) {
+            (TranscriptionBackend::Metal, true) => "Metal GPU with Flash Attention (Ultra-Fast)",
+            (TranscriptionBackend::Metal, false) => "Metal GPU acceleration",
+            (TranscriptionBackend::Cuda, true) => "CUDA GPU with Flash Attention (Ultra-Fast)",
+            (TranscriptionBackend::Cuda, false) => "CUDA GPU acceleration",
+            (TranscriptionBackend::Vulkan, _) => "Vulkan GPU acceleration",
+            (TranscriptionBackend::Cpu | TranscriptionBackend::Auto, _) => "CPU processing only",
+        };
+
+        log::info!("Successfully loaded model: {} with {} (Performance Tier: {:?}, Beam Size: {}, Threads: {:?})",
+                  model_name, acceleration_status, hardware_profile.performance_tier,
+                  adaptive_config.beam_size, adaptive_config.max_threads);
+        set_active_backend(Some(backend));
+        Ok(ctx)
+    }
+
+    /// Replace the loaded model without a window where no model is loaded
+    ///
+    /// The new model is loaded next to the current one and swapped in once ready, so a
+    /// recording in progress keeps transcribing with the old model until the switch.
+    pub async fn switch_model(&self, model_name: &str) -> Result<()> {
+        if self.current_model.read().await.as_deref() == Some(model_name) {
+            log::info!("Model {} is already active", model_name);
+            return Ok(());
+        }
+
+        let model_path = {
+            let models = self.available_models.read().await;
+            let model_info = models.get(model_name)
+                .ok_or_else(|| anyhow!("Model {} not found", model_name))?;
+            if !matches!(model_info.status, ModelStatus::Available) {
+                return Err(anyhow!("Model {} is not available ({:?})", model_name, model_info.status));
+            }
+            model_info.path.clone()
+        };
+
+        log::info!("🔄 Hot-swapping to model: {}", model_name);
+        self.swap_in_model(model_name, model_path).await
+    }
+
+    /// Reload the active model, e.g. after the transcription backend changed
+    pub async fn reload_model(&self) -> Result<()> {
+        let Some(model_name) = self.current_model.read().await.clone() else {
+            return Ok(());
+        };
+        let model_path = self.available_models.read().await.get(&model_name)
+            .map(|info| info.path.clone())
+            .ok_or_else(|| anyhow!("Model {} not found", model_name))?;
+
+        log::info!("🔄 Reloading model {} with the current backend settings", model_name);
+        self.swap_in_model(&model_name, model_path).await
+    }
+
+    /// Load a model next to the current one, then replace it
+    async fn swap_in_model(&self, model_name: &str, model_path: PathBuf) -> Result<()> {
+        let name_for_load = model_name.to_string();
+        let ctx = tokio::task::spawn_blocking(move || Self::create_context(&name_for_load, &model_path))
+            .await
+            .map_err(|e| anyhow!("Model loading task failed: {}", e))??;
+
+        // Waits for an in-flight transcription to release the old context
+        let mut ctx_guard = self.current_context.write().await;
+        let mut model_guard = self.current_model.write().await;
+        let previous = model_guard.replace(model_name.to_string());
+        *ctx_guard = Some(ctx);
+        log::info!("✅ Switched model {} → {}", previous.as_deref().unwrap_or("none"), model_name);
+        Ok(())
+    }
+
+    /// Re-hash a downloaded model and compare it with the checksum recorded at download
+    pub async fn verify_model(&self, model_name: &str) -> Result<bool> {
+        let spec = find_model(model_name).ok_or_else(|| anyhow!("Unknown model: {}", model_name))?;
+        let model_path = self.models_dir.join(spec.filename);
+        if !model_path.exists() {
+            return Err(anyhow!("Model {} is not downloaded", model_name));
+        }
+
+        let expected = match fs::read_to_string(checksum_path(&model_path)).await {
+            Ok(hash) => Some(hash.trim().to_string()),
+            Err(_) => fetch_expected_sha256(&spec.download_url()).await,
+        }
+        .ok_or_else(|| anyhow!("No checksum known for {}", model_name))?;
+
+        let actual = sha256_file(&model_path).await?;
+        let valid = actual == expected;
+        if valid {
+            log::info!("✅ Model {} verified", model_name);
+        } else {
+            log::warn!("❌ Model {} checksum mismatch: expected {}, got {}", model_name, expected, actual);
+            let mut models = self.available_models.write().await;
+            if let Some(model_info) = models.get_mut(model_name) {
+                let file_size = std::fs::metadata(&model_path).map(|m| m.len()).unwrap_or(0);
+                model_info.status = ModelStatus::Corrupted { file_size, expected_min_size: file_size };
+            }
+        }
+        Ok(valid)
+    }
+
     pub async fn unload_model(&self) -> bool  {
         let mut ctx_guard = self.current_context.write().await;
         let unloaded = ctx_guard.take().is_some();
@@ -368,6 +436,7 @@ impl WhisperEngine {
 
         let mut model_name_guard = self.current_model.write().await;
         model_name_guard.take();
+        set_active_backend(None);
 
         unloaded
     }
@@ -529,8 +598,63 @@ impl WhisperEngine {
         repeated_words as f32 / total_words
     }
     
+    /// Detect the spoken language of 16kHz mono audio (whisper only looks at the first 30s)
+    ///
+    /// Returns the language code and its probability. Fails for English-only models.
+    pub async fn detect_language(&self, audio_data: &[f32]) -> Result<(String, f32)> {
+        let ctx_lock = self.current_context.read().await;
+        let ctx = ctx_lock.as_ref()
+            .ok_or_else(|| anyhow!("No model loaded. Please load a model first."))?;
+
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get().min(8))
+            .unwrap_or(4);
+
+        let mut state = ctx.create_state()?;
+        state.pcm_to_mel(audio_data, threads)?;
+        let probabilities = state.lang_detect(0, threads)?;
+
+        let (lang_id, probability) = probabilities
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .ok_or_else(|| anyhow!("Language detection returned no results"))?;
+        let code = whisper_rs::get_lang_str(lang_id as i32)
+            .ok_or_else(|| anyhow!("Unknown language id {}", lang_id))?;
+
+        Ok((code.to_string(), *probability))
+    }
+
     /// Transcribe audio with streaming support for partial results and adaptive quality
     pub async fn transcribe_audio_with_confidence(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<(String, f32, bool)> {
+        let (text, confidence, is_partial, _) = self.transcribe_audio_with_words(audio_data, language).await?;
+        Ok((text, confidence, is_partial))
+    }
+
+    /// Like `transcribe_audio_with_confidence`, plus word timings relative to the start of the audio
+    pub async fn transcribe_audio_with_words(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<(String, f32, bool, Vec<TranscriptWord>)> {
+        // If language is "auto" or None, use automatic language detection (pass None)
+        // If language is "auto-translate", enable translation to English
+        // Otherwise, use the specified language code
+        let (language_code, should_translate) = match language.as_deref() {
+            Some("auto") | None => (None, false),
+            Some("auto-translate") => (None, true),
+            Some(lang) => (Some(lang.to_string()), false),
+        };
+        self.run_with_confidence(audio_data, language_code, should_translate).await
+    }
+
+    /// Translate speech to English with Whisper's translate task
+    ///
+    /// `source_language` skips per-segment language detection when the language is known.
+    pub async fn translate_audio(&self, audio_data: Vec<f32>, source_language: Option<String>) -> Result<String> {
+        let source_language = source_language.filter(|l| l != "auto" && l != "auto-translate");
+        let (text, _, _, _) = self.run_with_confidence(audio_data, source_language, true).await?;
+        Ok(text)
+    }
+
+    async fn run_with_confidence(&self, audio_data: Vec<f32>, language_code: Option<String>, should_translate: bool) -> Result<(String, f32, bool, Vec<TranscriptWord>)> {
+        let glossary_prompt = crate::audio::transcription::glossary::initial_prompt();
         let ctx_lock = self.current_context.read().await;
         let ctx = ctx_lock.as_ref()
             .ok_or_else(|| anyhow!("No model loaded. Please load a model first."))?;
@@ -546,17 +670,14 @@ impl WhisperEngine {
         });
 
         // Configure with adaptive settings
-        // If language is "auto" or None, use automatic language detection (pass None)
-        // If language is "auto-translate", enable translation to English
-        // Otherwise, use the specified language code
-        let (language_code, should_translate) = match language.as_deref() {
-            Some("auto") | None => (None, false),
-            Some("auto-translate") => (None, true),
-            Some(lang) => (Some(lang), false),
-        };
-        params.set_language(language_code);
+        params.set_language(language_code.as_deref());
         params.set_translate(should_translate);
 
+        // Glossary terms bias decoding towards the correct spelling of domain words
+        if let Some(prompt) = glossary_prompt.as_deref() {
+            params.set_initial_prompt(prompt);
+        }
+
         // CRITICAL: Disable timestamp tokens to prevent whisper.cpp chunking heuristics
         // The "single timestamp ending - skip entire chunk" optimization incorrectly discards
         // complete, valid transcriptions. Disabling timestamps forces whisper to return ALL text.
@@ -608,6 +729,8 @@ impl WhisperEngine {
         let mut result = String::new();
         let mut total_confidence = 0.0;
         let mut segment_count = 0;
+        let mut tokens: Vec<(String, f64, f64, f32)> = Vec::new();
+        let eot = ctx.token_eot();
 
         let num_segments = num_segments?;
         for i in 0..num_segments {
@@ -626,6 +749,17 @@ impl WhisperEngine {
             total_confidence += segment_confidence;
             segment_count += 1;
 
+            // Token timings (centiseconds) and probabilities, merged into words below
+            for j in 0..state.full_n_tokens(i).unwrap_or(0) {
+                let (Ok(text), Ok(data)) = (state.full_get_token_text_lossy(i, j), state.full_get_token_data(i, j)) else {
+                    continue;
+                };
+                if data.id >= eot || text.starts_with("[_") || text.starts_with("<|") {
+                    continue;
+                }
+                tokens.push((text, data.t0 as f64 / 100.0, data.t1 as f64 / 100.0, data.p));
+            }
+
             let cleaned_text = segment_text.trim();
             if !cleaned_text.is_empty() {
                 if !result.is_empty() {
@@ -644,7 +778,7 @@ impl WhisperEngine {
             0.0
         };
 
-        Ok((cleaned_result, avg_confidence, is_partial))
+        Ok((cleaned_result, avg_confidence, is_partial, words_from_tokens(&tokens)))
     }
 
     pub async fn transcribe_audio(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<String> {
@@ -857,6 +991,11 @@ impl WhisperEngine {
 
         let model_info = model_info.ok_or_else(|| anyhow!("Model '{}' not found", model_name))?;
 
+        if self.current_model.read().await.as_deref() == Some(model_name) {
+            return Err(anyhow!("Model '{}' is in use; switch to another model before deleting it", model_name));
+        }
+        let _ = fs::remove_file(checksum_path(&model_info.path)).await;
+
         // Check if model is corrupted before allowing deletion
         log::info!("Model '{}' has status: {:?}", model_name, model_info.status);
         match &model_info.status {
@@ -936,29 +1075,24 @@ impl WhisperEngine {
         }
 
         // Official ggerganov/whisper.cpp model URLs from Hugging Face
-        let model_url = match model_name {
-            // Standard f16 models
-            "tiny" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin",
-            "base" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin",
-            "small" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin",
-            "medium" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.bin",
-            "large-v3-turbo" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-turbo.bin",
-            "large-v3" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3.bin",
-            
-            "small-q5_0" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small-q5_0.bin",
-            "medium-q5_0" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium-q5_0.bin",
-            "large-v3-turbo-q5_0" => "https://huggingface.co/ggerganov/whisper.cpp/blob/main/ggml-large-v3-turbo-q5_0.bin",
-            "large-v3-q5_0" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-q5_0.bin",
-            // Quantized int8 models
-            
-            _ => return Err(anyhow!("Unsupported model: {}", model_name))
+        let spec = match find_model(model_name) {
+            Some(spec) => spec,
+            None => {
+                self.active_downloads.write().await.remove(model_name);
+                return Err(anyhow!("Unsupported model: {}", model_name));
+            }
         };
-        
+        let model_url = spec.download_url();
+
         log::info!("Model URL for {}: {}", model_name, model_url);
-        
-        // Generate correct filename - all models follow ggml-{model_name}.bin pattern
-        let filename = format!("ggml-{}.bin", model_name);
-        let file_path = self.models_dir.join(&filename);
+
+        let expected_sha256 = fetch_expected_sha256(&model_url).await;
+        match &expected_sha256 {
+            Some(hash) => log::info!("Expected SHA-256 for {}: {}", model_name, hash),
+            None => log::warn!("No checksum available for {}, falling back to header validation", model_name),
+        }
+
+        let file_path = self.models_dir.join(spec.filename);
         
         log::info!("Downloading to file path: {}", file_path.display());
         
@@ -980,7 +1114,7 @@ impl WhisperEngine {
         let client = Client::new();
         
         log::info!("Sending GET request to: {}", model_url);
-        let response = client.get(model_url).send().await
+        let response = client.get(&model_url).send().await
             .map_err(|e| anyhow!("Failed to start download: {}", e))?;
         
         log::info!("Received response with status: {}", response.status());
@@ -1009,6 +1143,7 @@ impl WhisperEngine {
 
         use futures_util::StreamExt;
         let mut stream = response.bytes_stream();
+        let mut hasher = DownloadHasher::new();
         let mut downloaded = 0u64;
         let mut last_progress_report = 0u8;
         let mut last_report_time = std::time::Instant::now();
@@ -1036,6 +1171,7 @@ impl WhisperEngine {
 
             file.write_all(&chunk).await
                 .map_err(|e| anyhow!("Failed to write chunk to file: {}", e))?;
+            hasher.update(&chunk);
 
             downloaded += chunk.len() as u64;
 
@@ -1088,7 +1224,36 @@ impl WhisperEngine {
         
         file.flush().await
             .map_err(|e| anyhow!("Failed to flush file: {}", e))?;
-        
+        drop(file);
+
+        // Verify the download before offering the model
+        let actual_sha256 = hasher.finish();
+        let verification = match &expected_sha256 {
+            Some(expected) if *expected != actual_sha256 => Err(anyhow!(
+                "Checksum mismatch for {}: expected {}, got {}", model_name, expected, actual_sha256
+            )),
+            Some(_) => Ok(()),
+            None => self.validate_model_file(&file_path).await,
+        };
+        if let Err(e) = verification {
+            log::error!("❌ Downloaded model {} failed verification: {}", model_name, e);
+            let _ = fs::remove_file(&file_path).await;
+            {
+                let mut models = self.available_models.write().await;
+                if let Some(model_info) = models.get_mut(model_name) {
+                    model_info.status = ModelStatus::Missing;
+                }
+            }
+            self.active_downloads.write().await.remove(model_name);
+            return Err(e);
+        }
+        if expected_sha256.is_some() {
+            log::info!("✅ Checksum verified for {}", model_name);
+        }
+        if let Err(e) = fs::write(checksum_path(&file_path), &actual_sha256).await {
+            log::warn!("Failed to store checksum for {}: {}", model_name, e);
+        }
+
         log::info!("Download completed for model: {}", model_name);
         
         // Update model status to available
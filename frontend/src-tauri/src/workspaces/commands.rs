@@ -0,0 +1,158 @@
+// workspaces/commands.rs
+//
+// Tauri commands managing workspaces. Removing one only forgets it: its folder is left as it
+// is, and adding the folder again brings the workspace back.
+
+use std::path::{Path, PathBuf};
+
+use log::info;
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Runtime};
+
+use super::storage::{self, StorageLocations, StorageMovePlan};
+use super::{load, save, stored_id, validate_name, write_marker, Workspace};
+use crate::audio::session;
+use crate::state::AppState;
+use crate::utils::restart_soon;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceList {
+    pub workspaces: Vec<Workspace>,
+    /// The open workspace
+    pub active: String,
+}
+
+#[tauri::command]
+pub async fn list_workspaces<R: Runtime>(app: AppHandle<R>) -> Result<WorkspaceList, String> {
+    let (workspaces, _) = load(&app);
+    Ok(WorkspaceList { workspaces, active: super::active().id })
+}
+
+/// Adds a workspace storing its data in `storage_dir`, or in the app's data folder without
+/// one. A folder that already holds a workspace is added back as that workspace.
+#[tauri::command]
+pub async fn create_workspace<R: Runtime>(
+    app: AppHandle<R>,
+    name: String,
+    storage_dir: Option<String>,
+) -> Result<Workspace, String> {
+    let (mut workspaces, active_id) = load(&app);
+    let name = validate_name(&name, &workspaces, None)?;
+
+    let app_data_dir = app.path().app_data_dir().map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let requested = storage_dir.map(|dir| dir.trim().to_string()).filter(|dir| !dir.is_empty()).map(PathBuf::from);
+    if let Some(dir) = &requested {
+        if !dir.is_absolute() {
+            return Err(format!("Storage folder must be a full path: {}", dir.display()));
+        }
+        if dir == &app_data_dir || workspaces.iter().any(|workspace| workspace.storage_dir.as_ref() == Some(dir)) {
+            return Err(format!("{} is already used by another workspace", dir.display()));
+        }
+    }
+
+    let id = requested.as_deref().and_then(stored_id).unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    if workspaces.iter().any(|workspace| workspace.id == id) {
+        return Err("That folder holds a workspace that's already added".to_string());
+    }
+    let storage_dir = requested.unwrap_or_else(|| app_data_dir.join("workspaces").join(&id));
+    std::fs::create_dir_all(&storage_dir).map_err(|e| format!("Failed to create {}: {}", storage_dir.display(), e))?;
+
+    let workspace = Workspace { id, name, storage_dir: Some(storage_dir) };
+    write_marker(&workspace)?;
+    workspaces.push(workspace.clone());
+    save(&app, &workspaces, &active_id)?;
+    info!("Added workspace '{}' at {:?}", workspace.name, workspace.storage_dir);
+    Ok(workspace)
+}
+
+#[tauri::command]
+pub async fn rename_workspace<R: Runtime>(app: AppHandle<R>, id: String, name: String) -> Result<Workspace, String> {
+    let (mut workspaces, active_id) = load(&app);
+    let name = validate_name(&name, &workspaces, Some(&id))?;
+    let workspace = workspaces
+        .iter_mut()
+        .find(|workspace| workspace.id == id)
+        .ok_or_else(|| format!("No workspace found with id {}", id))?;
+    workspace.name = name;
+    let renamed = workspace.clone();
+    write_marker(&renamed)?;
+    save(&app, &workspaces, &active_id)?;
+    if id == super::active().id {
+        *super::ACTIVE.write().unwrap() = renamed.clone();
+    }
+    Ok(renamed)
+}
+
+/// Forgets a workspace other than the default and the open one; its folder isn't touched
+#[tauri::command]
+pub async fn remove_workspace<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    if id == super::DEFAULT_ID {
+        return Err("The default workspace can't be removed".to_string());
+    }
+    if id == super::active().id {
+        return Err("Switch to another workspace before removing this one".to_string());
+    }
+    let (mut workspaces, active_id) = load(&app);
+    let count = workspaces.len();
+    workspaces.retain(|workspace| workspace.id != id);
+    if workspaces.len() == count {
+        return Err(format!("No workspace found with id {}", id));
+    }
+    save(&app, &workspaces, &active_id)
+}
+
+/// Makes `id` the open workspace and restarts the app to open its database
+#[tauri::command]
+pub async fn switch_workspace<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    if session::phase().is_recording() {
+        return Err("Workspaces can't be switched while recording".to_string());
+    }
+    let (workspaces, _) = load(&app);
+    let workspace = workspaces
+        .iter()
+        .find(|workspace| workspace.id == id)
+        .ok_or_else(|| format!("No workspace found with id {}", id))?;
+    if let Some(dir) = workspace.storage_dir.as_ref().filter(|dir| !dir.is_dir()) {
+        return Err(format!("The storage folder of '{}' is missing: {}", workspace.name, dir.display()));
+    }
+    if id == super::active().id {
+        return Ok(());
+    }
+    save(&app, &workspaces, &id)?;
+
+    info!("Switching to workspace '{}', restarting", workspace.name);
+    restart_soon(app);
+    Ok(())
+}
+
+/// Where the open workspace's database, recordings and templates are
+#[tauri::command]
+pub async fn get_storage_locations<R: Runtime>(app: AppHandle<R>) -> Result<StorageLocations, String> {
+    StorageLocations::current(&app)
+}
+
+/// What moving the open workspace's data to `target` involves; fails when it can't go there
+#[tauri::command]
+pub async fn plan_storage_move<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    target: String,
+) -> Result<StorageMovePlan, String> {
+    storage::plan(&app, state.db_manager.pool(), Path::new(target.trim())).await
+}
+
+/// Moves the open workspace's data to `target`, then restarts the app to use it there
+#[tauri::command]
+pub async fn move_storage<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    target: String,
+) -> Result<Workspace, String> {
+    if session::phase().is_recording() {
+        return Err("Storage can't be moved while recording".to_string());
+    }
+    let workspace = storage::move_to(&app, state.db_manager.pool(), Path::new(target.trim())).await?;
+    info!("Moved the storage of workspace '{}', restarting", workspace.name);
+    restart_soon(app);
+    Ok(workspace)
+}
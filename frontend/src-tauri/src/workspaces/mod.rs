@@ -0,0 +1,232 @@
+/// Workspaces module - keeping clients' meetings apart
+///
+/// A workspace (e.g. "Client A", "Internal") has its own database, recordings and custom
+/// templates, all in one storage folder the user picks, so that one client's data can live on
+/// its own drive and never mixes with another's. Provider settings and summary templates are
+/// kept in the database, so they're per workspace too, and API keys are stored in the
+/// credential store under the workspace's own service names. The default workspace keeps the
+/// locations the app has always used until its storage is moved.
+///
+/// The active workspace is chosen before the database opens, so switching restarts the app.
+/// Device settings (microphones, models, shortcuts) are shared by all workspaces.
+///
+/// This module contains:
+/// - Moving a workspace's data to another folder (`storage`)
+/// - Tauri commands for frontend integration
+
+pub mod commands;
+pub mod storage;
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+/// The workspaces have to be known before the database opens, so they aren't in it
+const STORE_FILE: &str = "workspaces.json";
+const WORKSPACES_KEY: &str = "workspaces";
+const ACTIVE_KEY: &str = "active";
+
+pub const DEFAULT_ID: &str = "default";
+const DEFAULT_NAME: &str = "Personal";
+
+/// Written into each storage folder, so a workspace added again keeps its id and, with it, its
+/// API keys
+const MARKER_FILE: &str = "workspace.json";
+const DATABASE_FILE: &str = "meeting_minutes.sqlite";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    /// Holds the database, `recordings/` and `templates/`; `None` for the default workspace
+    /// while it keeps the app's usual locations
+    pub storage_dir: Option<PathBuf>,
+}
+
+impl Workspace {
+    fn default_workspace() -> Self {
+        Self { id: DEFAULT_ID.to_string(), name: DEFAULT_NAME.to_string(), storage_dir: None }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.id == DEFAULT_ID
+    }
+}
+
+static ACTIVE: Lazy<RwLock<Workspace>> = Lazy::new(|| RwLock::new(Workspace::default_workspace()));
+
+/// Loads the active workspace; called at startup before anything reads meeting data. Falls
+/// back to the default workspace when the active one's folder is missing, e.g. on a drive
+/// that isn't connected.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let (workspaces, active_id) = load(app);
+    let mut active = workspaces.into_iter().find(|workspace| workspace.id == active_id).unwrap_or_else(Workspace::default_workspace);
+    if let Some(dir) = active.storage_dir.as_ref().filter(|dir| !dir.is_dir()) {
+        warn!("Storage folder {:?} of workspace '{}' is missing, opening the default workspace", dir, active.name);
+        active = Workspace::default_workspace();
+    }
+    info!("Workspace: {}", active.name);
+    *ACTIVE.write().unwrap() = active;
+}
+
+pub fn active() -> Workspace {
+    ACTIVE.read().unwrap().clone()
+}
+
+/// The stored workspaces, the default one first, and the id of the one to open
+fn load<R: Runtime>(app: &AppHandle<R>) -> (Vec<Workspace>, String) {
+    let store = match app.store(STORE_FILE) {
+        Ok(store) => store,
+        Err(e) => {
+            warn!("Failed to open {}: {}", STORE_FILE, e);
+            return (vec![Workspace::default_workspace()], DEFAULT_ID.to_string());
+        }
+    };
+    let stored: Vec<Workspace> =
+        store.get(WORKSPACES_KEY).and_then(|value| serde_json::from_value(value).ok()).unwrap_or_default();
+    let active = store.get(ACTIVE_KEY).and_then(|value| value.as_str().map(String::from)).unwrap_or_else(|| DEFAULT_ID.to_string());
+    (with_default(stored), active)
+}
+
+fn with_default(mut workspaces: Vec<Workspace>) -> Vec<Workspace> {
+    if !workspaces.iter().any(Workspace::is_default) {
+        workspaces.insert(0, Workspace::default_workspace());
+    }
+    workspaces
+}
+
+fn save<R: Runtime>(app: &AppHandle<R>, workspaces: &[Workspace], active_id: &str) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    store.set(WORKSPACES_KEY, serde_json::to_value(workspaces).map_err(|e| e.to_string())?);
+    store.set(ACTIVE_KEY, active_id);
+    store.save().map_err(|e| format!("Failed to save {}: {}", STORE_FILE, e))
+}
+
+/// Whether workspaces other than the default one exist
+pub fn has_others<R: Runtime>(app: &AppHandle<R>) -> bool {
+    load(app).0.iter().any(|workspace| !workspace.is_default())
+}
+
+/// The folder holding the active workspace's database
+pub fn data_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    match active().storage_dir {
+        Some(dir) => Ok(dir),
+        None => app.path().app_data_dir().map_err(|e| format!("Failed to get app data dir: {}", e)),
+    }
+}
+
+pub fn database_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    Ok(data_dir(app)?.join(DATABASE_FILE))
+}
+
+/// Where the active workspace's recordings go, unless it's the default one
+pub fn recordings_dir() -> Option<PathBuf> {
+    active().storage_dir.map(|dir| dir.join("recordings"))
+}
+
+/// Where the active workspace's template files are read from, unless it's the default one
+pub fn templates_dir() -> Option<PathBuf> {
+    active().storage_dir.map(|dir| dir.join("templates"))
+}
+
+/// The name the active workspace's credentials are kept under in the credential store; the
+/// default workspace's are under `service` itself, where older versions put them
+pub fn scoped_service(service: &str) -> String {
+    let active = ACTIVE.read().unwrap();
+    scoped_service_for(service, &active.id)
+}
+
+fn scoped_service_for(service: &str, workspace_id: &str) -> String {
+    if workspace_id == DEFAULT_ID {
+        service.to_string()
+    } else {
+        format!("{}.workspace.{}", service, workspace_id)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StorageMarker {
+    id: String,
+    name: String,
+}
+
+/// The id recorded in a storage folder that already held a workspace
+fn stored_id(dir: &Path) -> Option<String> {
+    let json = std::fs::read_to_string(dir.join(MARKER_FILE)).ok()?;
+    serde_json::from_str::<StorageMarker>(&json).ok().map(|marker| marker.id)
+}
+
+fn write_marker(workspace: &Workspace) -> Result<(), String> {
+    let Some(dir) = &workspace.storage_dir else {
+        return Ok(());
+    };
+    let marker = StorageMarker { id: workspace.id.clone(), name: workspace.name.clone() };
+    let json = serde_json::to_string_pretty(&marker).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(MARKER_FILE), json).map_err(|e| format!("Failed to write {:?}: {}", dir.join(MARKER_FILE), e))
+}
+
+/// Trims a workspace name and checks it's set and not taken (case-insensitively) by another
+fn validate_name(name: &str, workspaces: &[Workspace], except_id: Option<&str>) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Workspace name can't be empty".to_string());
+    }
+    if name.chars().count() > 100 {
+        return Err("Workspace name is too long".to_string());
+    }
+    let taken = workspaces
+        .iter()
+        .any(|workspace| Some(workspace.id.as_str()) != except_id && workspace.name.to_lowercase() == name.to_lowercase());
+    if taken {
+        return Err(format!("A workspace named '{}' already exists", name));
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(id: &str, name: &str) -> Workspace {
+        Workspace { id: id.to_string(), name: name.to_string(), storage_dir: Some(PathBuf::from("/data").join(id)) }
+    }
+
+    #[test]
+    fn test_with_default() {
+        let workspaces = with_default(vec![workspace("a", "Client A")]);
+        assert_eq!(workspaces[0], Workspace::default_workspace());
+        assert_eq!(workspaces.len(), 2);
+        assert_eq!(with_default(workspaces.clone()), workspaces);
+    }
+
+    #[test]
+    fn test_validate_name() {
+        let workspaces = with_default(vec![workspace("a", "Client A")]);
+        assert_eq!(validate_name("  Internal ", &workspaces, None).unwrap(), "Internal");
+        assert!(validate_name("client a", &workspaces, None).is_err());
+        assert!(validate_name("personal", &workspaces, None).is_err());
+        assert_eq!(validate_name("Client a", &workspaces, Some("a")).unwrap(), "Client a");
+        assert!(validate_name("   ", &workspaces, None).is_err());
+    }
+
+    #[test]
+    fn test_scoped_service() {
+        assert_eq!(scoped_service_for("com.meetily.ai.summary", DEFAULT_ID), "com.meetily.ai.summary");
+        assert_eq!(scoped_service_for("com.meetily.ai.summary", "a1"), "com.meetily.ai.summary.workspace.a1");
+    }
+
+    #[test]
+    fn test_stored_id() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(stored_id(dir.path()), None);
+        let mut client = workspace("a", "Client A");
+        client.storage_dir = Some(dir.path().to_path_buf());
+        write_marker(&client).unwrap();
+        assert_eq!(stored_id(dir.path()).as_deref(), Some("a"));
+    }
+}
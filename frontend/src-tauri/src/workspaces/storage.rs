@@ -0,0 +1,537 @@
+// workspaces/storage.rs
+//
+// Moving the open workspace's database, snapshots, recordings and templates to another
+// folder, e.g. an external drive. Everything is copied while the app runs and checked before
+// anything points at the copy: each file is read back and compared by SHA-256, and the
+// database is exported with its recording paths rebased onto the new folder, then put through
+// SQLite's integrity check. Only then does the workspace take the new folder and the app
+// restart into it; the old copy is deleted on that start, once the database has opened from
+// the new folder.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{SqliteConnection, SqlitePool};
+use tauri::{AppHandle, Emitter, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use super::{load, save, write_marker, Workspace, DATABASE_FILE, MARKER_FILE, STORE_FILE};
+use crate::audio::recording_preferences::get_default_recordings_folder;
+use crate::audio::session;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::encryption::database::key_pragma;
+use crate::summary::templates::get_custom_templates_dir;
+
+pub const STORAGE_MOVE_PROGRESS_EVENT: &str = "storage-move-progress";
+
+/// The old copy of a move, kept until the workspace has opened from its new folder
+const CLEANUP_KEY: &str = "pending_cleanup";
+
+const COPY_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Where the open workspace's data is
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageLocations {
+    pub database: PathBuf,
+    pub recordings: PathBuf,
+    /// Custom templates, when there are any
+    pub templates: Option<PathBuf>,
+    /// Database snapshots, when there are any
+    pub backups: Option<PathBuf>,
+}
+
+impl StorageLocations {
+    pub fn current<R: Runtime>(app: &AppHandle<R>) -> Result<Self, String> {
+        Ok(Self {
+            database: super::database_path(app)?,
+            recordings: get_default_recordings_folder(),
+            templates: get_custom_templates_dir().filter(|dir| dir.is_dir()),
+            backups: Some(super::data_dir(app)?.join(crate::backup::BACKUPS_DIR)).filter(|dir| dir.is_dir()),
+        })
+    }
+
+    /// The database with its write-ahead log and shared memory files
+    fn database_files(&self) -> Vec<PathBuf> {
+        let name = self.database.to_string_lossy();
+        vec![self.database.clone(), PathBuf::from(format!("{}-wal", name)), PathBuf::from(format!("{}-shm", name))]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct FolderSize {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// What moving to `target` involves, shown to the user before anything is copied
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageMovePlan {
+    pub from: StorageLocations,
+    pub target: PathBuf,
+    pub database_bytes: u64,
+    pub recordings: FolderSize,
+    pub templates: FolderSize,
+    pub backups: FolderSize,
+    pub total_bytes: u64,
+    /// Free space where the data is going, when it can be found out
+    pub available_bytes: Option<u64>,
+    /// Meetings whose recordings are in the recordings folder and move with it
+    pub meetings_moved: usize,
+    /// Meetings recorded somewhere else; their recordings stay where they are
+    pub meetings_left: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageMoveProgress {
+    pub stage: &'static str,
+    pub done_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingCleanup {
+    workspace_id: String,
+    moved_to: PathBuf,
+    /// Files and folders deleted with everything in them
+    paths: Vec<PathBuf>,
+    /// Folders deleted only when nothing else is left in them
+    empty_folders: Vec<PathBuf>,
+}
+
+/// `path` moved from under `old_root` to the same place under `new_root`; `None` when it isn't
+/// under `old_root`
+pub fn rebase(path: &Path, old_root: &Path, new_root: &Path) -> Option<PathBuf> {
+    let rest = path.strip_prefix(old_root).ok()?;
+    if rest.as_os_str().is_empty() {
+        return Some(new_root.to_path_buf());
+    }
+    Some(new_root.join(rest))
+}
+
+/// Checks that `target` is a full path to a new or empty folder, not inside one of the folders
+/// being moved or another workspace's
+fn check_target(target: &Path, moving: &[&Path], other_workspaces: &[PathBuf]) -> Result<(), String> {
+    if !target.is_absolute() {
+        return Err(format!("The new folder must be a full path: {}", target.display()));
+    }
+    if let Some(source) = moving.iter().find(|source| target.starts_with(source)) {
+        return Err(format!("{} is inside {}, which is being moved", target.display(), source.display()));
+    }
+    if other_workspaces.iter().any(|dir| target.starts_with(dir)) {
+        return Err(format!("{} is used by another workspace", target.display()));
+    }
+    if target.exists() {
+        if !target.is_dir() {
+            return Err(format!("{} isn't a folder", target.display()));
+        }
+        let mut entries = std::fs::read_dir(target).map_err(|e| format!("Failed to read {}: {}", target.display(), e))?;
+        if entries.next().is_some() {
+            return Err(format!("{} must be empty", target.display()));
+        }
+    }
+    Ok(())
+}
+
+fn folder_size(dir: &Path) -> FolderSize {
+    let mut size = FolderSize::default();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return size;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            let inner = folder_size(&entry.path());
+            size.files += inner.files;
+            size.bytes += inner.bytes;
+        } else if file_type.is_file() {
+            size.files += 1;
+            size.bytes += entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        }
+    }
+    size
+}
+
+fn hash_file(path: &Path) -> Result<Vec<u8>, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            return Ok(hasher.finalize().to_vec());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+/// Copies one file and reads the copy back to check it matches; returns its size
+fn copy_file_verified(from: &Path, to: &Path) -> Result<u64, String> {
+    let mut source = File::open(from).map_err(|e| format!("Failed to open {}: {}", from.display(), e))?;
+    let mut target = File::create(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+    let mut bytes = 0u64;
+    loop {
+        let read = source.read(&mut buffer).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        target.write_all(&buffer[..read]).map_err(|e| format!("Failed to write {}: {}", to.display(), e))?;
+        bytes += read as u64;
+    }
+    target.sync_all().map_err(|e| format!("Failed to write {}: {}", to.display(), e))?;
+    drop(target);
+
+    if hash_file(to)? != hasher.finalize().to_vec() {
+        return Err(format!("The copy of {} doesn't match the original", from.display()));
+    }
+    Ok(bytes)
+}
+
+/// Copies the files under `from` into `to`, checking each copy; `progress` gets the bytes
+/// copied so far after each file
+fn copy_verified(from: &Path, to: &Path, copied: &mut FolderSize, progress: &mut dyn FnMut(u64)) -> Result<(), String> {
+    std::fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+    let entries = std::fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+        let file_type = entry.file_type().map_err(|e| format!("Failed to read {:?}: {}", entry.path(), e))?;
+        let target = to.join(entry.file_name());
+        if file_type.is_dir() {
+            copy_verified(&entry.path(), &target, copied, progress)?;
+        } else if file_type.is_file() {
+            copied.bytes += copy_file_verified(&entry.path(), &target)?;
+            copied.files += 1;
+            progress(copied.bytes);
+        } else {
+            warn!("Not moving {:?}, which isn't a file or folder", entry.path());
+        }
+    }
+    Ok(())
+}
+
+/// Each meeting's recording folder that's under `recordings`, rebased onto `new_recordings`,
+/// and how many meetings are recorded elsewhere
+async fn rebased_folders(
+    pool: &SqlitePool,
+    recordings: &Path,
+    new_recordings: &Path,
+) -> Result<(Vec<(String, PathBuf)>, usize), String> {
+    let meetings = MeetingsRepository::get_meetings(pool)
+        .await
+        .map_err(|e| format!("Failed to load meetings: {}", e))?;
+    let mut rebased = Vec::new();
+    let mut left = 0;
+    for meeting in meetings {
+        let Some(folder) = meeting.folder_path else {
+            continue;
+        };
+        match rebase(Path::new(&folder), recordings, new_recordings) {
+            Some(new_folder) => rebased.push((meeting.id, new_folder)),
+            None => left += 1,
+        }
+    }
+    Ok((rebased, left))
+}
+
+/// Checks `target` and works out what moving there involves
+pub async fn plan<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, target: &Path) -> Result<StorageMovePlan, String> {
+    let from = StorageLocations::current(app)?;
+    let active = super::active();
+    let others: Vec<PathBuf> = load(app)
+        .0
+        .into_iter()
+        .filter(|workspace| workspace.id != active.id)
+        .filter_map(|workspace| workspace.storage_dir)
+        .collect();
+    let mut moving = vec![from.recordings.as_path()];
+    moving.extend(from.templates.as_deref());
+    moving.extend(from.backups.as_deref());
+    check_target(target, &moving, &others)?;
+
+    let database_bytes = from
+        .database_files()
+        .iter()
+        .filter_map(|file| std::fs::metadata(file).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+    let recordings = folder_size(&from.recordings);
+    let templates = from.templates.as_deref().map(folder_size).unwrap_or_default();
+    let backups = from.backups.as_deref().map(folder_size).unwrap_or_default();
+    let (rebased, meetings_left) = rebased_folders(pool, &from.recordings, &target.join("recordings")).await?;
+
+    Ok(StorageMovePlan {
+        target: target.to_path_buf(),
+        database_bytes,
+        recordings,
+        templates,
+        backups,
+        total_bytes: database_bytes + recordings.bytes + templates.bytes + backups.bytes,
+        available_bytes: crate::audio::disk_space::available_space(target),
+        meetings_moved: rebased.len(),
+        meetings_left,
+        from,
+    })
+}
+
+/// Exports the database to `target`, encrypted like the open one, with the recording folders
+/// in `folders`, and checks the export
+async fn export_database(pool: &SqlitePool, target: &Path, folders: &[(String, PathBuf)]) -> Result<(), String> {
+    let mut conn = pool.acquire().await.map_err(|e| format!("Failed to open the database: {}", e))?;
+    let attach = format!(
+        "ATTACH DATABASE '{}' AS moved KEY {}",
+        target.to_string_lossy().replace('\'', "''"),
+        key_pragma(crate::encryption::active_key().as_ref())
+    );
+    sqlx::query(&attach)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to create the database copy: {}", e))?;
+    let exported = fill_export(&mut conn, folders).await;
+    let _ = sqlx::query("DETACH DATABASE moved").execute(&mut *conn).await;
+    exported
+}
+
+async fn fill_export(conn: &mut SqliteConnection, folders: &[(String, PathBuf)]) -> Result<(), String> {
+    sqlx::query("SELECT sqlcipher_export('moved')")
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to copy the database: {}", e))?;
+    for (meeting_id, folder) in folders {
+        sqlx::query("UPDATE moved.meetings SET folder_path = ? WHERE id = ?")
+            .bind(folder.to_string_lossy().to_string())
+            .bind(meeting_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to update recording paths: {}", e))?;
+    }
+
+    let problems: Vec<String> = sqlx::query_scalar::<_, String>("PRAGMA moved.integrity_check")
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to check the database copy: {}", e))?
+        .into_iter()
+        .filter(|line| line != "ok")
+        .collect();
+    if !problems.is_empty() {
+        return Err(format!("The database copy failed its integrity check: {}", problems.join("; ")));
+    }
+    let (original, copied): (i64, i64) =
+        sqlx::query_as("SELECT (SELECT COUNT(*) FROM main.meetings), (SELECT COUNT(*) FROM moved.meetings)")
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to check the database copy: {}", e))?;
+    if original != copied {
+        return Err(format!("The database copy has {} of {} meetings", copied, original));
+    }
+    Ok(())
+}
+
+/// Copies the data and checks the copies
+async fn copy_all<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, plan: &StorageMovePlan) -> Result<(), String> {
+    let emit = |stage: &'static str, done_bytes: u64| {
+        let progress = StorageMoveProgress { stage, done_bytes, total_bytes: plan.total_bytes };
+        let _ = app.emit(STORAGE_MOVE_PROGRESS_EVENT, progress);
+    };
+
+    let mut sources = vec![(plan.from.recordings.clone(), plan.target.join("recordings"))];
+    if let Some(templates) = &plan.from.templates {
+        sources.push((templates.clone(), plan.target.join("templates")));
+    }
+    if let Some(backups) = &plan.from.backups {
+        sources.push((backups.clone(), plan.target.join(crate::backup::BACKUPS_DIR)));
+    }
+    let mut done = 0;
+    let mut copies = Vec::new();
+    for (from, to) in sources {
+        if !from.is_dir() {
+            continue;
+        }
+        let app = app.clone();
+        let total_bytes = plan.total_bytes;
+        let source = from.clone();
+        let copied = tokio::task::spawn_blocking(move || {
+            let mut copied = FolderSize::default();
+            copy_verified(&source, &to, &mut copied, &mut |bytes| {
+                let progress = StorageMoveProgress { stage: "files", done_bytes: done + bytes, total_bytes };
+                let _ = app.emit(STORAGE_MOVE_PROGRESS_EVENT, progress);
+            })
+            .map(|_| copied)
+        })
+        .await
+        .map_err(|e| format!("Failed to copy files: {}", e))??;
+        done += copied.bytes;
+        copies.push((from, copied));
+    }
+
+    emit("database", done);
+    let (rebased, _) = rebased_folders(pool, &plan.from.recordings, &plan.target.join("recordings")).await?;
+    export_database(pool, &plan.target.join(DATABASE_FILE), &rebased).await?;
+
+    // A meeting saved or imported while copying would be in the database but not in the copy
+    if copies.iter().any(|(from, copied)| folder_size(from) != *copied) {
+        return Err("Recordings changed while they were being moved; try again once nothing is being saved".to_string());
+    }
+    emit("done", plan.total_bytes);
+    Ok(())
+}
+
+/// Copies the open workspace's data to `target`, checks it and points the workspace there.
+/// The app has to restart to use it; the old copy is deleted then.
+pub async fn move_to<R: Runtime>(app: &AppHandle<R>, pool: &SqlitePool, target: &Path) -> Result<Workspace, String> {
+    let plan = plan(app, pool, target).await?;
+    if let Some(available) = plan.available_bytes.filter(|available| *available < plan.total_bytes) {
+        return Err(format!(
+            "{} needs {} MB free but has {} MB",
+            target.display(),
+            plan.total_bytes / 1_000_000,
+            available / 1_000_000
+        ));
+    }
+    std::fs::create_dir_all(target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+
+    info!("Moving workspace storage to {:?} ({} bytes)", target, plan.total_bytes);
+    let copied = copy_all(app, pool, &plan).await.and_then(|_| {
+        if session::phase().is_recording() {
+            return Err("A recording started while moving; storage wasn't moved".to_string());
+        }
+        Ok(())
+    });
+    if let Err(e) = copied {
+        // The folder was empty, so everything in it is from this attempt
+        for created in [target.join("recordings"), target.join("templates"), target.join(crate::backup::BACKUPS_DIR)] {
+            let _ = std::fs::remove_dir_all(created);
+        }
+        let _ = std::fs::remove_file(target.join(DATABASE_FILE));
+        return Err(e);
+    }
+
+    let (mut workspaces, active_id) = load(app);
+    let open_id = super::active().id;
+    let moved = {
+        let workspace = workspaces
+            .iter_mut()
+            .find(|workspace| workspace.id == open_id)
+            .ok_or_else(|| "The open workspace isn't stored".to_string())?;
+        workspace.storage_dir = Some(target.to_path_buf());
+        workspace.clone()
+    };
+    write_marker(&moved)?;
+
+    let mut paths = plan.from.database_files();
+    paths.push(plan.from.recordings.clone());
+    paths.extend(plan.from.templates.clone());
+    paths.extend(plan.from.backups.clone());
+    let mut empty_folders = Vec::new();
+    if let Some(old_dir) = super::active().storage_dir {
+        paths.push(old_dir.join(MARKER_FILE));
+        empty_folders.push(old_dir);
+    }
+    let cleanup =
+        PendingCleanup { workspace_id: moved.id.clone(), moved_to: target.to_path_buf(), paths, empty_folders };
+    let store = app.store(STORE_FILE).map_err(|e| format!("Failed to open {}: {}", STORE_FILE, e))?;
+    store.set(CLEANUP_KEY, serde_json::to_value(&cleanup).map_err(|e| e.to_string())?);
+    save(app, &workspaces, &active_id)?;
+
+    info!("Workspace '{}' now stores its data in {:?}", moved.name, target);
+    Ok(moved)
+}
+
+/// Deletes the old copy of a move once the workspace it belongs to has opened its database
+/// from the new folder; called after the database opens
+pub fn delete_old_copy<R: Runtime>(app: &AppHandle<R>) {
+    let Ok(store) = app.store(STORE_FILE) else {
+        return;
+    };
+    let Some(cleanup) = store.get(CLEANUP_KEY).and_then(|value| serde_json::from_value::<PendingCleanup>(value).ok())
+    else {
+        return;
+    };
+    let active = super::active();
+    if active.id != cleanup.workspace_id || active.storage_dir.as_ref() != Some(&cleanup.moved_to) {
+        return;
+    }
+
+    for path in &cleanup.paths {
+        let removed = if path.is_dir() {
+            std::fs::remove_dir_all(path)
+        } else if path.exists() {
+            std::fs::remove_file(path)
+        } else {
+            Ok(())
+        };
+        if let Err(e) = removed {
+            warn!("Failed to delete {:?} after moving storage: {}", path, e);
+        }
+    }
+    for folder in &cleanup.empty_folders {
+        let _ = std::fs::remove_dir(folder);
+    }
+    store.delete(CLEANUP_KEY);
+    if let Err(e) = store.save() {
+        warn!("Failed to save {}: {}", STORE_FILE, e);
+    }
+    info!("Deleted the old copy of workspace '{}'", active.name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebase() {
+        let old = Path::new("/home/me/meetily-recordings");
+        let new = Path::new("/mnt/drive/meetings/recordings");
+        assert_eq!(
+            rebase(Path::new("/home/me/meetily-recordings/Standup_2025"), old, new),
+            Some(PathBuf::from("/mnt/drive/meetings/recordings/Standup_2025"))
+        );
+        assert_eq!(rebase(old, old, new), Some(new.to_path_buf()));
+        assert_eq!(rebase(Path::new("/home/me/meetily-recordings-old/a"), old, new), None);
+        assert_eq!(rebase(Path::new("/tmp/import"), old, new), None);
+    }
+
+    #[test]
+    fn test_check_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let recordings = dir.path().join("recordings");
+        std::fs::create_dir(&recordings).unwrap();
+        std::fs::write(recordings.join("audio.mp4"), "audio").unwrap();
+        let others = vec![dir.path().join("client-a")];
+
+        assert!(check_target(&dir.path().join("new"), &[&recordings], &others).is_ok());
+        assert!(check_target(Path::new("relative/folder"), &[&recordings], &[]).is_err());
+        assert!(check_target(&recordings.join("inside"), &[&recordings], &[]).is_err());
+        assert!(check_target(&others[0].join("x"), &[&recordings], &others).is_err());
+        assert!(check_target(&recordings, &[], &[]).is_err());
+        assert!(check_target(&recordings.join("audio.mp4"), &[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_copy_verified() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("from");
+        std::fs::create_dir_all(from.join("Standup")).unwrap();
+        std::fs::write(from.join("Standup").join("audio.mp4"), vec![7u8; 3 * COPY_BUFFER_SIZE / 2]).unwrap();
+        std::fs::write(from.join("notes.txt"), "notes").unwrap();
+
+        let to = dir.path().join("to");
+        let mut copied = FolderSize::default();
+        let mut reported = Vec::new();
+        copy_verified(&from, &to, &mut copied, &mut |bytes| reported.push(bytes)).unwrap();
+
+        assert_eq!(copied, folder_size(&from));
+        assert_eq!(copied, FolderSize { files: 2, bytes: (3 * COPY_BUFFER_SIZE / 2 + 5) as u64 });
+        assert_eq!(reported.last(), Some(&copied.bytes));
+        let audio = Path::new("Standup").join("audio.mp4");
+        assert_eq!(hash_file(&to.join(&audio)), hash_file(&from.join(&audio)));
+        assert_eq!(std::fs::read_to_string(to.join("notes.txt")).unwrap(), "notes");
+    }
+}
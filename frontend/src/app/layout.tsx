@@ -15,6 +15,7 @@ import { LegacyDatabaseImport } from '@/components/DatabaseImport/LegacyDatabase
 import { TooltipProvider } from '@/components/ui/tooltip'
 import { RecordingStateProvider } from '@/contexts/RecordingStateContext'
 import { OllamaDownloadProvider } from '@/contexts/OllamaDownloadContext'
+import { RecordingRecovery } from '@/components/RecordingRecovery'
 
 const sourceSans3 = Source_Sans_3({
   subsets: ['latin'],
@@ -76,6 +77,7 @@ export default function RootLayout({
                     <Sidebar />
                     <MainContent>{children}</MainContent>
                   </div>
+                  <RecordingRecovery />
                 </TooltipProvider>
               </SidebarProvider>
             </OllamaDownloadProvider>
@@ -35,7 +35,7 @@ import { ButtonGroup } from '@/components/ui/button-group';
 
 
 interface ModelConfig {
-  provider: 'ollama' | 'groq' | 'claude' | 'openrouter';
+  provider: 'builtin' | 'ollama' | 'groq' | 'claude' | 'openrouter';
   model: string;
   whisperModel: string;
 }
@@ -85,6 +85,8 @@ export default function Home() {
   const [errorMessage, setErrorMessage] = useState('');
   const [showChunkDropWarning, setShowChunkDropWarning] = useState(false);
   const [chunkDropMessage, setChunkDropMessage] = useState('');
+  const [recordingWarning, setRecordingWarning] = useState<string | null>(null);
+  const [handsFreeWarning, setHandsFreeWarning] = useState<{ message: string; built_in_microphone: string | null } | null>(null);
   const [isSavingTranscript, setIsSavingTranscript] = useState(false);
   const [isRecordingDisabled, setIsRecordingDisabled] = useState(false);
   const [selectedDevices, setSelectedDevices] = useState<SelectedDevices>({
@@ -107,7 +109,7 @@ export default function Home() {
   });
 
   // Permission check hook
-  const { hasMicrophone, hasSystemAudio, isChecking: isCheckingPermissions, checkPermissions } = usePermissionCheck();
+  const { hasMicrophone, hasSystemAudio, report: preflightReport, isChecking: isCheckingPermissions, checkPermissions } = usePermissionCheck();
 
   // Recording state context - provides backend-synced state
   const recordingState = useRecordingState();
@@ -571,6 +573,46 @@ export default function Home() {
     };
   }, []);
 
+  // Muted or silent microphone while recording; cleared once audio comes back
+  useEffect(() => {
+    const unlisten = listen<{ reason: string; message: string; device_name: string }>('recording-warning', (event) => {
+      console.warn('Recording warning received:', event.payload);
+      setRecordingWarning(event.payload.reason === 'signal_restored' ? null : event.payload.message);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // A Bluetooth headset microphone records low-quality hands-free audio
+  useEffect(() => {
+    const unlisten = listen<{ message: string; built_in_microphone: string | null }>('bluetooth-hands-free', (event) => {
+      console.warn('Hands-free microphone in use:', event.payload);
+      setHandsFreeWarning(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    if (!recordingState.isRecording) {
+      setRecordingWarning(null);
+      setHandsFreeWarning(null);
+    }
+  }, [recordingState.isRecording]);
+
+  const switchToBuiltInMicrophone = async () => {
+    try {
+      const microphone = await invoke<string>('use_built_in_microphone', { remember: true });
+      toast.success(`Recording from ${microphone}`);
+      setHandsFreeWarning(null);
+    } catch (error) {
+      console.error('Failed to switch to the built-in microphone:', error);
+      toast.error(String(error));
+    }
+  };
+
   // Set up recording-stopped listener for meeting navigation
   useEffect(() => {
     let unlistenFn: (() => void) | undefined;
@@ -1650,6 +1692,45 @@ export default function Home() {
           </Alert>
         </div>
       )}
+      {handsFreeWarning && (
+        <div className="fixed top-20 left-1/2 -translate-x-1/2 z-40">
+          <Alert className="border-amber-400 bg-amber-50 shadow-lg">
+            <AlertTitle className="text-amber-900">Bluetooth headset microphone</AlertTitle>
+            <AlertDescription className="text-amber-800">
+              {handsFreeWarning.message}
+              <div className="mt-2 flex gap-3">
+                {handsFreeWarning.built_in_microphone && (
+                  <button onClick={switchToBuiltInMicrophone} className="text-amber-900 font-medium underline">
+                    Use {handsFreeWarning.built_in_microphone}
+                  </button>
+                )}
+                <button
+                  onClick={() => setHandsFreeWarning(null)}
+                  className="text-amber-700 hover:text-amber-900 underline"
+                >
+                  Dismiss
+                </button>
+              </div>
+            </AlertDescription>
+          </Alert>
+        </div>
+      )}
+      {recordingWarning && (
+        <div className="fixed top-4 left-1/2 -translate-x-1/2 z-40">
+          <Alert className="border-amber-400 bg-amber-50 shadow-lg">
+            <AlertTitle className="text-amber-900">Check your microphone</AlertTitle>
+            <AlertDescription className="text-amber-800">
+              {recordingWarning}
+              <button
+                onClick={() => setRecordingWarning(null)}
+                className="ml-2 text-amber-700 hover:text-amber-900 underline"
+              >
+                Dismiss
+              </button>
+            </AlertDescription>
+          </Alert>
+        </div>
+      )}
       <div className="flex flex-1 overflow-hidden">
         {/* Left side - Transcript */}
         <div ref={transcriptContainerRef} className="w-full border-r border-gray-200 bg-white flex flex-col overflow-y-auto">
@@ -1820,6 +1901,7 @@ export default function Home() {
               <PermissionWarning
                 hasMicrophone={hasMicrophone}
                 hasSystemAudio={hasSystemAudio}
+                report={preflightReport}
                 onRecheck={checkPermissions}
                 isRechecking={isCheckingPermissions}
               />
@@ -1,10 +1,48 @@
-import React from "react";
+import React, { useState } from "react";
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import Image from 'next/image';
+import { toast } from 'sonner';
 import AnalyticsConsentSwitch from "./AnalyticsConsentSwitch";
 
 
+type SelfTestStage = 'offline' | 'tone' | 'speech' | 'transcription';
+
+interface SelfTestCheck {
+    name: string;
+    status: 'pass' | 'warn' | 'fail' | 'skipped';
+    detail: string;
+}
+
+interface SelfTestReport {
+    passed: boolean;
+    checks: SelfTestCheck[];
+    input_device: string | null;
+    phrase: string;
+    transcript: string | null;
+    duration_ms: number;
+}
+
+const SELFTEST_STAGE_LABELS: Record<SelfTestStage, string> = {
+    offline: 'Checking the audio pipeline…',
+    tone: 'Playing a test tone…',
+    speech: 'Now read aloud: “The quick brown fox jumps over the lazy dog”',
+    transcription: 'Transcribing…',
+};
+
+const SELFTEST_STATUS_STYLES: Record<SelfTestCheck['status'], string> = {
+    pass: 'text-green-700',
+    warn: 'text-amber-700',
+    fail: 'text-red-700',
+    skipped: 'text-gray-400',
+};
+
 export function About() {
+    const [diagnosticsConsent, setDiagnosticsConsent] = useState(false);
+    const [generatingDiagnostics, setGeneratingDiagnostics] = useState(false);
+    const [selfTestStage, setSelfTestStage] = useState<SelfTestStage | null>(null);
+    const [selfTestReport, setSelfTestReport] = useState<SelfTestReport | null>(null);
+
     const handleContactClick = async () => {
         try {
             await invoke('open_external_url', { url: 'https://meetily.zackriya.com/#about' });
@@ -13,6 +51,34 @@ export function About() {
         }
     };
 
+    const handleGenerateDiagnostics = async () => {
+        setGeneratingDiagnostics(true);
+        try {
+            const path = await invoke<string>('generate_diagnostics', { consent: diagnosticsConsent });
+            toast.success('Diagnostics saved', { description: `Attach ${path} to your bug report.` });
+        } catch (error) {
+            console.error('Failed to generate diagnostics:', error);
+            toast.error('Failed to generate diagnostics', { description: String(error) });
+        } finally {
+            setGeneratingDiagnostics(false);
+        }
+    };
+
+    const handleRunSelfTest = async () => {
+        setSelfTestReport(null);
+        setSelfTestStage('offline');
+        const unlisten = await listen<SelfTestStage>('pipeline-selftest', (event) => setSelfTestStage(event.payload));
+        try {
+            setSelfTestReport(await invoke<SelfTestReport>('run_pipeline_selftest'));
+        } catch (error: any) {
+            console.error('Self-test failed to run:', error);
+            toast.error('Self-test failed to run', { description: error?.message ?? String(error) });
+        } finally {
+            unlisten();
+            setSelfTestStage(null);
+        }
+    };
+
     return (
         <div className="p-4 space-y-4 h-[80vh] overflow-y-auto">
             {/* Compact Header */}
@@ -77,6 +143,57 @@ export function About() {
                 </button>
             </div>
 
+            {/* Diagnostics for bug reports */}
+            <div className="bg-gray-50 rounded p-3 space-y-2">
+                <h3 className="text-sm font-semibold text-gray-800">Report a problem</h3>
+                <p className="text-xs text-gray-600">
+                    Creates a zip with recent logs, audio settings, devices, models and permission states. Paths, names, emails and API keys are removed. Nothing is sent anywhere.
+                </p>
+                <label className="flex items-center gap-2 text-xs text-gray-700">
+                    <input
+                        type="checkbox"
+                        checked={diagnosticsConsent}
+                        onChange={(e) => setDiagnosticsConsent(e.target.checked)}
+                    />
+                    I agree to collect this information
+                </label>
+                <button
+                    onClick={handleGenerateDiagnostics}
+                    disabled={!diagnosticsConsent || generatingDiagnostics}
+                    className="px-3 py-1.5 bg-gray-800 hover:bg-gray-900 disabled:bg-gray-300 text-white text-xs font-medium rounded transition-colors duration-200"
+                >
+                    {generatingDiagnostics ? 'Generating…' : 'Generate diagnostics'}
+                </button>
+                <p className="text-xs text-gray-600 pt-2">
+                    The self-test plays a tone, records you reading a short phrase and transcribes it, to check that each stage works.
+                </p>
+                <button
+                    onClick={handleRunSelfTest}
+                    disabled={selfTestStage !== null}
+                    className="px-3 py-1.5 bg-gray-800 hover:bg-gray-900 disabled:bg-gray-300 text-white text-xs font-medium rounded transition-colors duration-200"
+                >
+                    {selfTestStage ? 'Testing…' : 'Run self-test'}
+                </button>
+                {selfTestStage && (
+                    <p className="text-xs font-medium text-blue-700">{SELFTEST_STAGE_LABELS[selfTestStage]}</p>
+                )}
+                {selfTestReport && (
+                    <div className="text-xs space-y-1">
+                        <p className={selfTestReport.passed ? 'text-green-700 font-medium' : 'text-red-700 font-medium'}>
+                            {selfTestReport.passed ? 'All checks passed' : 'Some checks failed'}
+                        </p>
+                        {selfTestReport.checks.map((check) => (
+                            <p key={check.name} className={SELFTEST_STATUS_STYLES[check.status]}>
+                                <span className="font-mono">{check.name}</span>: {check.detail}
+                            </p>
+                        ))}
+                        {selfTestReport.transcript && (
+                            <p className="text-gray-600">Heard: “{selfTestReport.transcript}”</p>
+                        )}
+                    </div>
+                )}
+            </div>
+
             {/* Footer - Compact */}
             <div className="pt-2 border-t border-gray-200 text-center">
                 <p className="text-xs text-gray-400">
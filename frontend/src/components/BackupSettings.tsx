@@ -0,0 +1,217 @@
+"use client"
+
+import { useEffect, useState } from "react"
+import { Switch } from "./ui/switch"
+import { invoke } from "@tauri-apps/api/core"
+
+interface BackupConfig {
+  enabled: boolean
+  interval_hours: number
+  keep: number
+}
+
+interface Snapshot {
+  path: string
+  created_at: string
+  bytes: number
+}
+
+interface ExportSummary {
+  path: string
+  meetings: number
+  encrypted: boolean
+}
+
+interface RestoreReport {
+  meetings: number
+  relinked: number
+  missing_recordings: number
+  mismatched_files: number
+}
+
+const INTERVALS: { hours: number; label: string }[] = [
+  { hours: 6, label: "Every 6 hours" },
+  { hours: 24, label: "Daily" },
+  { hours: 24 * 7, label: "Weekly" },
+]
+
+const formatSize = (bytes: number) =>
+  bytes >= 1e6 ? `${(bytes / 1e6).toFixed(1)} MB` : `${Math.ceil(bytes / 1e3)} KB`
+
+export function BackupSettings() {
+  const [settings, setSettings] = useState<BackupConfig | null>(null);
+  const [snapshots, setSnapshots] = useState<Snapshot[]>([]);
+  const [exportPath, setExportPath] = useState("");
+  const [restorePath, setRestorePath] = useState("");
+  const [busy, setBusy] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadSnapshots = async () => {
+    try {
+      setSnapshots(await invoke<Snapshot[]>('list_backups'));
+    } catch (loadError) {
+      console.error('Failed to list backups:', loadError);
+    }
+  };
+
+  useEffect(() => {
+    invoke<BackupConfig>('get_backup_settings')
+      .then(setSettings)
+      .catch((loadError) => console.error('Failed to load backup settings:', loadError));
+    loadSnapshots();
+  }, []);
+
+  const save = async (changes: Partial<BackupConfig>) => {
+    if (!settings) return;
+    setError(null);
+    try {
+      setSettings(await invoke<BackupConfig>('set_backup_settings', { settings: { ...settings, ...changes } }));
+    } catch (saveError) {
+      console.error('Failed to save backup settings:', saveError);
+      setError(String(saveError));
+    }
+  };
+
+  const run = async (action: () => Promise<string>) => {
+    setError(null);
+    setMessage(null);
+    setBusy(true);
+    try {
+      setMessage(await action());
+    } catch (actionError) {
+      console.error('Backup failed:', actionError);
+      setError(String(actionError));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const backUpNow = () =>
+    run(async () => {
+      await invoke<Snapshot>('create_backup');
+      await loadSnapshots();
+      return 'Snapshot saved';
+    });
+
+  const exportBackup = () =>
+    run(async () => {
+      const summary = await invoke<ExportSummary>('export_backup', { path: exportPath });
+      return `Exported ${summary.meetings} meetings to ${summary.path}. Copy the recordings folder along with it.${
+        summary.encrypted ? ' The export is encrypted and only opens on this computer.' : ''
+      }`;
+    });
+
+  // The app restarts with the restored database
+  const restore = (path: string) => {
+    if (!window.confirm('Replace all meetings with this backup? The current database is kept as a snapshot.')) return;
+    run(async () => {
+      const report = await invoke<RestoreReport>('restore_backup', { path });
+      return `Restoring ${report.meetings} meetings, restarting...${
+        report.missing_recordings > 0 ? ` ${report.missing_recordings} have no recordings on this computer.` : ''
+      }${report.mismatched_files > 0 ? ` ${report.mismatched_files} recording files are missing or incomplete.` : ''}`;
+    });
+  };
+
+  if (!settings) {
+    return null;
+  }
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <div className="flex items-center justify-between">
+        <div>
+          <h3 className="text-lg font-semibold text-gray-900 mb-2">Backups</h3>
+          <p className="text-sm text-gray-600">
+            Keep snapshots of the meeting database to recover from damage, or export it to move to another computer
+          </p>
+        </div>
+        <Switch checked={settings.enabled} onCheckedChange={(enabled) => save({ enabled })} />
+      </div>
+
+      {settings.enabled && (
+        <div className="mt-4 flex items-center gap-3 text-sm">
+          <select
+            value={settings.interval_hours}
+            onChange={(event) => save({ interval_hours: Number(event.target.value) })}
+            className="px-2 py-1 border rounded-md"
+          >
+            {INTERVALS.map((interval) => (
+              <option key={interval.hours} value={interval.hours}>{interval.label}</option>
+            ))}
+          </select>
+          <label className="flex items-center gap-2 text-gray-600">
+            Keep
+            <input
+              type="number"
+              min={1}
+              value={settings.keep}
+              onChange={(event) => save({ keep: Number(event.target.value) })}
+              className="w-16 px-2 py-1 border rounded-md"
+            />
+            snapshots
+          </label>
+        </div>
+      )}
+
+      <div className="mt-4 border-t pt-4 space-y-2">
+        <div className="flex items-center justify-between">
+          <span className="text-xs font-medium text-gray-500">Snapshots</span>
+          <button onClick={backUpNow} disabled={busy} className="text-xs text-blue-600 hover:underline disabled:text-gray-400">
+            Back up now
+          </button>
+        </div>
+        {snapshots.length === 0 ? (
+          <p className="text-sm text-gray-500">No snapshots yet.</p>
+        ) : (
+          snapshots.map((snapshot) => (
+            <div key={snapshot.path} className="flex items-center justify-between text-sm">
+              <span className="text-gray-900">
+                {new Date(snapshot.created_at).toLocaleString()}
+                <span className="ml-2 text-xs text-gray-500">{formatSize(snapshot.bytes)}</span>
+              </span>
+              <button onClick={() => restore(snapshot.path)} disabled={busy} className="text-xs text-blue-600 hover:underline disabled:text-gray-400">
+                Restore
+              </button>
+            </div>
+          ))
+        )}
+      </div>
+
+      <div className="mt-4 border-t pt-4 space-y-2">
+        <div className="flex gap-2">
+          <input
+            value={exportPath}
+            onChange={(event) => setExportPath(event.target.value)}
+            placeholder="Export to, e.g. /Users/me/meetily-backup.zip"
+            className="flex-1 px-3 py-2 text-sm border rounded-md font-mono"
+          />
+          <button
+            onClick={exportBackup}
+            disabled={busy || !exportPath.trim()}
+            className="px-3 py-2 text-sm border rounded-md hover:bg-gray-50 disabled:text-gray-400"
+          >
+            Export
+          </button>
+        </div>
+        <div className="flex gap-2">
+          <input
+            value={restorePath}
+            onChange={(event) => setRestorePath(event.target.value)}
+            placeholder="Restore from an export (.zip) or snapshot"
+            className="flex-1 px-3 py-2 text-sm border rounded-md font-mono"
+          />
+          <button
+            onClick={() => restore(restorePath)}
+            disabled={busy || !restorePath.trim()}
+            className="px-3 py-2 text-sm border rounded-md hover:bg-gray-50 disabled:text-gray-400"
+          >
+            Restore
+          </button>
+        </div>
+      </div>
+      {message && <p className="mt-3 text-xs text-gray-600">{message}</p>}
+      {error && <p className="mt-3 text-xs text-red-600">{error}</p>}
+    </div>
+  )
+}
@@ -0,0 +1,96 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { Button } from '@/components/ui/button';
+import { Download } from 'lucide-react';
+
+interface BuiltinModel {
+  id: string
+  name: string
+  size_mb: number
+  downloaded: boolean
+}
+
+interface BuiltinLlmStatus {
+  runtime_available: boolean
+  models: BuiltinModel[]
+}
+
+interface BuiltinModelStatusProps {
+  /** The selected built-in model's id */
+  model: string
+}
+
+export function BuiltinModelStatus({ model }: BuiltinModelStatusProps) {
+  const [status, setStatus] = useState<BuiltinLlmStatus | null>(null);
+  const [downloadProgress, setDownloadProgress] = useState<number | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<BuiltinLlmStatus>('get_builtin_llm_status')
+      .then(setStatus)
+      .catch((loadError) => console.error('Failed to check the built-in model:', loadError));
+
+    const unlisten = listen<{ model_id: string; progress: number }>('builtin-llm-download-progress', (event) => {
+      setDownloadProgress(event.payload.progress);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const download = async (modelId: string) => {
+    setError(null);
+    setDownloadProgress(0);
+    try {
+      setStatus(await invoke<BuiltinLlmStatus>('download_builtin_llm_model', { modelId }));
+    } catch (downloadError) {
+      console.error('Failed to download the built-in model:', downloadError);
+      setError(String(downloadError));
+    } finally {
+      setDownloadProgress(null);
+    }
+  };
+
+  if (!status) {
+    return null;
+  }
+
+  const selected = status.models.find((candidate) => candidate.id === model);
+  return (
+    <div className="space-y-1 rounded-md border px-3 py-2 text-sm">
+      <div className="flex items-center justify-between gap-2">
+        <div className="flex items-center gap-2">
+          <span
+            className={`h-2 w-2 rounded-full ${status.runtime_available && selected?.downloaded ? 'bg-green-500' : 'bg-gray-400'}`}
+          />
+          {!status.runtime_available ? (
+            <span className="text-muted-foreground">This build doesn&apos;t include the llama.cpp runtime</span>
+          ) : selected?.downloaded ? (
+            <span>{selected.name} is ready</span>
+          ) : (
+            <span className="text-muted-foreground">
+              {selected ? `${selected.name} isn't downloaded yet` : 'Choose a model'}
+            </span>
+          )}
+        </div>
+        {selected && !selected.downloaded && (
+          <Button
+            type="button"
+            size="sm"
+            variant="outline"
+            onClick={() => download(selected.id)}
+            disabled={downloadProgress !== null}
+          >
+            <Download className="mr-2 h-4 w-4" />
+            {downloadProgress !== null ? `Downloading ${downloadProgress}%` : `Download (${selected.size_mb} MB)`}
+          </Button>
+        )}
+      </div>
+      <p className="text-xs text-muted-foreground">
+        Runs on this computer. Summaries also fall back to it when Ollama isn&apos;t available or a provider has no API key.
+      </p>
+      {error && <p className="text-xs text-red-600">{error}</p>}
+    </div>
+  );
+}
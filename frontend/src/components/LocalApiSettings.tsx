@@ -0,0 +1,134 @@
+"use client"
+
+import { useEffect, useState } from "react"
+import { Switch } from "./ui/switch"
+import { invoke } from "@tauri-apps/api/core"
+
+interface LocalApiSettingsValue {
+  enabled: boolean
+  port: number
+}
+
+interface LocalApiStatus {
+  settings: LocalApiSettingsValue
+  token: string | null
+  running_port: number | null
+}
+
+export function LocalApiSettings() {
+  const [status, setStatus] = useState<LocalApiStatus | null>(null);
+  const [port, setPort] = useState("");
+  const [showToken, setShowToken] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const apply = (loaded: LocalApiStatus) => {
+    setStatus(loaded);
+    setPort(String(loaded.settings.port));
+  };
+
+  useEffect(() => {
+    const load = async () => {
+      try {
+        apply(await invoke<LocalApiStatus>('get_local_api_status'));
+      } catch (loadError) {
+        console.error('Failed to load local API settings:', loadError);
+      }
+    };
+    load();
+  }, []);
+
+  const save = async (settings: LocalApiSettingsValue) => {
+    setError(null);
+    try {
+      apply(await invoke<LocalApiStatus>('set_local_api_settings', { settings }));
+    } catch (saveError) {
+      console.error('Failed to save local API settings:', saveError);
+      setError(String(saveError));
+    }
+  };
+
+  const regenerate = async () => {
+    setError(null);
+    try {
+      apply(await invoke<LocalApiStatus>('regenerate_local_api_token'));
+      setShowToken(true);
+    } catch (tokenError) {
+      console.error('Failed to regenerate local API token:', tokenError);
+      setError(String(tokenError));
+    }
+  };
+
+  if (!status) {
+    return null;
+  }
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <div className="flex items-center justify-between">
+        <div>
+          <h3 className="text-lg font-semibold text-gray-900 mb-2">Local API</h3>
+          <p className="text-sm text-gray-600">
+            Let scripts on this computer list meetings, search them and start or stop recordings over HTTP. Only reachable from this computer, with the token below.
+          </p>
+        </div>
+        <Switch checked={status.settings.enabled} onCheckedChange={(enabled) => save({ ...status.settings, enabled })} />
+      </div>
+
+      {status.settings.enabled && (
+        <div className="mt-4 space-y-3 border-t pt-4">
+          <div className="flex items-center gap-2">
+            <span className="text-sm text-gray-700">Port</span>
+            <input
+              value={port}
+              onChange={(event) => setPort(event.target.value.replace(/\D/g, ''))}
+              className="w-24 px-3 py-2 text-sm border rounded-md"
+            />
+            <button
+              onClick={() => save({ ...status.settings, port: Number(port) })}
+              disabled={!port || Number(port) === status.settings.port}
+              className="px-3 py-2 text-sm border rounded-md hover:bg-gray-50 disabled:text-gray-400"
+            >
+              Apply
+            </button>
+            <span className="text-xs text-gray-500">
+              {status.running_port ? `Listening on http://localhost:${status.running_port}` : 'Not running'}
+            </span>
+          </div>
+          {status.token && (
+            <div className="space-y-1">
+              <div className="flex items-center gap-3">
+                <span className="text-sm text-gray-700">Token</span>
+                <button onClick={() => setShowToken(!showToken)} className="text-xs text-blue-600 hover:underline">
+                  {showToken ? 'Hide' : 'Show'}
+                </button>
+                <button onClick={() => navigator.clipboard.writeText(status.token ?? '')} className="text-xs text-blue-600 hover:underline">
+                  Copy
+                </button>
+                <button onClick={regenerate} className="text-xs text-red-600 hover:underline">
+                  Regenerate
+                </button>
+              </div>
+              {showToken && <p className="text-xs font-mono break-all p-2 bg-gray-50 border rounded-md">{status.token}</p>}
+            </div>
+          )}
+          <p className="text-xs text-gray-500 font-mono break-all">
+            curl -H &quot;Authorization: Bearer $TOKEN&quot; http://localhost:{status.settings.port}/v1/meetings
+          </p>
+          {status.token && (
+            <div className="flex items-center gap-3">
+              <span className="text-sm text-gray-700">Live captions overlay</span>
+              <button
+                onClick={() => navigator.clipboard.writeText(`http://localhost:${status.settings.port}/captions?token=${status.token}`)}
+                className="text-xs text-blue-600 hover:underline"
+              >
+                Copy URL
+              </button>
+              <span className="text-xs text-gray-500">Add it as a Browser source in OBS</span>
+            </div>
+          )}
+        </div>
+      )}
+      {error && <p className="mt-3 text-xs text-red-600">{error}</p>}
+    </div>
+  )
+}
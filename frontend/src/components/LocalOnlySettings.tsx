@@ -0,0 +1,77 @@
+"use client"
+
+import { useEffect, useState } from "react"
+import { Switch } from "./ui/switch"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+
+interface AffectedFeature {
+  feature: string
+  detail: string
+}
+
+interface NetworkStatus {
+  local_only: boolean
+  affected: AffectedFeature[]
+}
+
+export function LocalOnlySettings() {
+  const [status, setStatus] = useState<NetworkStatus | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<NetworkStatus>('get_network_status')
+      .then(setStatus)
+      .catch((loadError) => console.error('Failed to load local-only mode:', loadError));
+
+    const unlisten = listen<NetworkStatus>('network-mode-changed', (event) => {
+      setStatus(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const toggle = async (enabled: boolean) => {
+    setError(null);
+    try {
+      setStatus(await invoke<NetworkStatus>('set_local_only', { enabled }));
+    } catch (saveError) {
+      console.error('Failed to change local-only mode:', saveError);
+      setError(String(saveError));
+    }
+  };
+
+  if (!status) {
+    return null;
+  }
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <div className="flex items-center justify-between">
+        <div>
+          <h3 className="text-lg font-semibold text-gray-900 mb-2">Local-Only Mode</h3>
+          <p className="text-sm text-gray-600">
+            Block everything that would send meeting data off this computer or local network. Model downloads still work.
+          </p>
+        </div>
+        <Switch checked={status.local_only} onCheckedChange={toggle} />
+      </div>
+
+      {status.affected.length > 0 && (
+        <div className="mt-4 space-y-2 border-t pt-4">
+          <p className="text-xs font-medium text-gray-500">
+            {status.local_only ? 'Limited while local-only mode is on' : 'Turning this on limits'}
+          </p>
+          {status.affected.map(({ feature, detail }) => (
+            <div key={feature} className="text-sm">
+              <span className="text-gray-900">{feature}</span>
+              <span className="ml-2 text-gray-600">{detail}</span>
+            </div>
+          ))}
+        </div>
+      )}
+      {error && <p className="mt-3 text-xs text-red-600">{error}</p>}
+    </div>
+  )
+}
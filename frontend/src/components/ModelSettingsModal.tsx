@@ -3,6 +3,8 @@ import { useSidebar } from './Sidebar/SidebarProvider';
 import { invoke } from '@tauri-apps/api/core';
 import { Button } from '@/components/ui/button';
 import { useOllamaDownload } from '@/contexts/OllamaDownloadContext';
+import { OllamaStatus } from './OllamaStatus';
+import { BuiltinModelStatus } from './BuiltinModelStatus';
 import { Input } from '@/components/ui/input';
 import { Label } from '@/components/ui/label';
 import {
@@ -20,7 +22,7 @@ import { cn } from '@/lib/utils';
 import { toast } from 'sonner';
 
 export interface ModelConfig {
-  provider: 'ollama' | 'groq' | 'claude' | 'openai' | 'openrouter';
+  provider: 'builtin' | 'ollama' | 'groq' | 'claude' | 'openai' | 'openrouter';
   model: string;
   whisperModel: string;
   apiKey?: string | null;
@@ -130,6 +132,7 @@ export function ModelSettingsModal({
   }, [modelConfig.apiKey]);
 
   const modelOptions = {
+    builtin: ['qwen2.5-1.5b-instruct', 'qwen2.5-3b-instruct'],
     ollama: models.map((model) => model.name),
     claude: ['claude-3-5-sonnet-latest', 'claude-3-5-sonnet-20241022', 'claude-3-5-sonnet-20240620'],
     groq: ['llama-3.3-70b-versatile'],
@@ -189,7 +192,7 @@ export function ModelSettingsModal({
           setModelConfig(data);
 
           // Fetch API key if not included in response and provider requires it
-          if (data.provider !== 'ollama' && !data.apiKey) {
+          if (data.provider !== 'ollama' && data.provider !== 'builtin' && !data.apiKey) {
             try {
               const apiKeyData = await invoke('api_get_api_key', {
                 provider: data.provider
@@ -507,7 +510,9 @@ export function ModelSettingsModal({
                   provider,
                   model: defaultModel,
                 });
-                fetchApiKey(provider);
+                if (provider !== 'builtin') {
+                  fetchApiKey(provider);
+                }
 
                 // Load OpenRouter models only when OpenRouter is selected
                 if (provider === 'openrouter') {
@@ -519,6 +524,7 @@ export function ModelSettingsModal({
                 <SelectValue placeholder="Select provider" />
               </SelectTrigger>
               <SelectContent className="max-h-64 overflow-y-auto">
+                <SelectItem value="builtin">Built-in (llama.cpp)</SelectItem>
                 <SelectItem value="claude">Claude</SelectItem>
                 <SelectItem value="groq">Groq</SelectItem>
                 <SelectItem value="ollama">Ollama</SelectItem>
@@ -595,6 +601,12 @@ export function ModelSettingsModal({
           </div>
         )}
 
+        {modelConfig.provider === 'builtin' && <BuiltinModelStatus model={modelConfig.model} />}
+
+        {modelConfig.provider === 'ollama' && (
+          <OllamaStatus endpoint={lastFetchedEndpoint.trim() || null} onStarted={() => fetchOllamaModels(true)} />
+        )}
+
         {modelConfig.provider === 'ollama' && (
           <div>
             <div
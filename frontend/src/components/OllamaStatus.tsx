@@ -0,0 +1,100 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Button } from '@/components/ui/button';
+import { RefreshCw, Play, Download } from 'lucide-react';
+
+interface OllamaHealth {
+  reachable: boolean
+  version: string | null
+  latency_ms: number | null
+  error: string | null
+}
+
+interface OllamaStatusValue {
+  binary_path: string | null
+  local: boolean
+  health: OllamaHealth
+}
+
+interface OllamaStatusProps {
+  endpoint: string | null
+  /** Called once Ollama answers after being started, e.g. to load its models */
+  onStarted?: () => void
+}
+
+export function OllamaStatus({ endpoint, onStarted }: OllamaStatusProps) {
+  const [status, setStatus] = useState<OllamaStatusValue | null>(null);
+  const [starting, setStarting] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const check = async () => {
+    try {
+      setStatus(await invoke<OllamaStatusValue>('get_ollama_status', { endpoint }));
+    } catch (checkError) {
+      console.error('Failed to check Ollama:', checkError);
+    }
+  };
+
+  useEffect(() => {
+    check();
+  }, [endpoint]);
+
+  const start = async () => {
+    setStarting(true);
+    setError(null);
+    try {
+      setStatus(await invoke<OllamaStatusValue>('start_ollama', { endpoint }));
+      onStarted?.();
+    } catch (startError) {
+      console.error('Failed to start Ollama:', startError);
+      setError(String(startError));
+    } finally {
+      setStarting(false);
+    }
+  };
+
+  if (!status) {
+    return null;
+  }
+
+  const { health } = status;
+  return (
+    <div className="flex items-center justify-between gap-2 rounded-md border px-3 py-2 text-sm">
+      <div className="flex items-center gap-2">
+        <span className={`h-2 w-2 rounded-full ${health.reachable ? 'bg-green-500' : 'bg-gray-400'}`} />
+        {health.reachable ? (
+          <span>
+            Ollama {health.version ?? ''} is running
+            {health.latency_ms !== null && <span className="text-muted-foreground"> ({health.latency_ms} ms)</span>}
+          </span>
+        ) : status.local && !status.binary_path ? (
+          <span>Ollama isn&apos;t installed</span>
+        ) : (
+          <span className="text-muted-foreground">{error ?? health.error ?? 'Ollama isn\'t running'}</span>
+        )}
+      </div>
+      <div className="flex gap-1 shrink-0">
+        {!health.reachable && status.local && status.binary_path && (
+          <Button type="button" size="sm" variant="outline" onClick={start} disabled={starting}>
+            {starting ? <RefreshCw className="mr-2 h-4 w-4 animate-spin" /> : <Play className="mr-2 h-4 w-4" />}
+            {starting ? 'Starting...' : 'Start Ollama'}
+          </Button>
+        )}
+        {!health.reachable && status.local && !status.binary_path && (
+          <Button
+            type="button"
+            size="sm"
+            variant="outline"
+            onClick={() => invoke('open_external_url', { url: 'https://ollama.com/download' })}
+          >
+            <Download className="mr-2 h-4 w-4" />
+            Download
+          </Button>
+        )}
+        <Button type="button" size="icon" variant="ghost" onClick={check} title="Check again">
+          <RefreshCw className="h-4 w-4" />
+        </Button>
+      </div>
+    </div>
+  );
+}
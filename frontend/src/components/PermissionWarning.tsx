@@ -1,11 +1,13 @@
-import React from 'react';
+import React, { useState } from 'react';
 import { AlertTriangle, Mic, Speaker, RefreshCw } from 'lucide-react';
 import { Alert, AlertDescription, AlertTitle } from '@/components/ui/alert';
 import { invoke } from '@tauri-apps/api/core';
+import type { PreflightItem, PreflightReport } from '@/hooks/usePermissionCheck';
 
 interface PermissionWarningProps {
   hasMicrophone: boolean;
   hasSystemAudio: boolean;
+  report?: PreflightReport | null;
   onRecheck: () => void;
   isRechecking?: boolean;
 }
@@ -13,22 +15,41 @@ interface PermissionWarningProps {
 export function PermissionWarning({
   hasMicrophone,
   hasSystemAudio,
+  report = null,
   onRecheck,
   isRechecking = false
 }: PermissionWarningProps) {
-  // Don't show if both permissions are granted
-  if (hasMicrophone && hasSystemAudio) {
+  const [sandboxHelp, setSandboxHelp] = useState<string | null>(null);
+
+  // Problems outside the permissions that still stop or degrade a recording
+  const otherProblems: PreflightItem[] = report
+    ? [report.disk_space, report.transcription_model].filter((item) => item.status !== 'ok')
+    : [];
+
+  // Don't show if both permissions are granted and nothing else is wrong
+  if (hasMicrophone && hasSystemAudio && otherProblems.length === 0) {
     return null;
   }
 
   const isMacOS = navigator.userAgent.includes('Mac');
+  const isWindows = navigator.userAgent.includes('Windows');
 
   const openMicrophoneSettings = async () => {
-    if (isMacOS) {
-      try {
+    try {
+      if (isMacOS) {
         await invoke('open_system_settings', { preferencePane: 'Privacy_Microphone' });
-      } catch (error) {
-        console.error('Failed to open microphone settings:', error);
+      } else if (isWindows) {
+        // Opens Settings → Privacy & security → Microphone
+        await invoke('request_microphone_permission_command');
+      } else {
+        // A Flatpak or Snap can't grant itself access; the error says what to run
+        await invoke('request_microphone_permission_command');
+        setSandboxHelp(null);
+      }
+    } catch (error) {
+      console.error('Failed to open microphone settings:', error);
+      if (!isMacOS && !isWindows) {
+        setSandboxHelp((error as { message?: string })?.message ?? String(error));
       }
     }
   };
@@ -58,13 +79,13 @@ export function PermissionWarning({
           </AlertTitle>
           {/* Action Buttons */}
           <div className="mt-4 flex flex-wrap gap-2">
-            {isMacOS && !hasMicrophone && (
+            {!hasMicrophone && (
               <button
                 onClick={openMicrophoneSettings}
                 className="inline-flex items-center gap-2 px-4 py-2 text-sm font-medium text-white bg-amber-600 hover:bg-amber-700 rounded-md transition-colors"
               >
                 <Mic className="h-4 w-4" />
-                Open Microphone Settings
+                {isMacOS || isWindows ? 'Open Microphone Settings' : 'How to Allow Microphone Access'}
               </button>
             )}
             {isMacOS && !hasSystemAudio && (
@@ -90,16 +111,23 @@ export function PermissionWarning({
             {!hasMicrophone && (
               <>
                 <p className="mb-3">
-                  Meetily needs access to your microphone to record meetings. No microphone devices were detected.
+                  Meetily needs access to your microphone to record meetings. No microphone it can use was detected.
                 </p>
                 <div className="space-y-2 text-sm mb-4">
                   <p className="font-medium">Please check:</p>
                   <ul className="list-disc list-inside ml-2 space-y-1">
                     <li>Your microphone is connected and powered on</li>
-                    <li>Microphone permission is granted in System Settings</li>
+                    <li>
+                      {isWindows
+                        ? 'Microphone access and "Let desktop apps access your microphone" are on in Settings → Privacy & security → Microphone'
+                        : 'Microphone permission is granted in System Settings'}
+                    </li>
                     <li>No other app is exclusively using the microphone</li>
                   </ul>
                 </div>
+                {sandboxHelp && (
+                  <p className="mb-3 text-sm font-mono break-words">{sandboxHelp}</p>
+                )}
               </>
             )}
 
@@ -128,6 +156,35 @@ export function PermissionWarning({
           </AlertDescription>
         </Alert>
       )}
+
+      {otherProblems.length > 0 && (
+        <Alert variant="destructive" className="border-amber-400 bg-amber-50">
+          <AlertTriangle className="h-5 w-5 text-amber-600" />
+          <AlertTitle className="text-amber-900 font-semibold">
+            {report && !report.ready ? 'Not Ready to Record' : 'Check Before Recording'}
+          </AlertTitle>
+          <AlertDescription className="text-amber-800 mt-2">
+            <ul className="space-y-2 text-sm">
+              {otherProblems.map((item) => (
+                <li key={item.detail}>
+                  <p className={item.status === 'fail' ? 'font-medium text-red-700' : 'font-medium'}>{item.detail}</p>
+                  {item.fix && <p>{item.fix}</p>}
+                </li>
+              ))}
+            </ul>
+            {hasMicrophone && hasSystemAudio && (
+              <button
+                onClick={onRecheck}
+                disabled={isRechecking}
+                className="mt-3 inline-flex items-center gap-2 px-4 py-2 text-sm font-medium text-amber-900 bg-amber-100 hover:bg-amber-200 rounded-md transition-colors disabled:opacity-50"
+              >
+                <RefreshCw className={`h-4 w-4 ${isRechecking ? 'animate-spin' : ''}`} />
+                Recheck
+              </button>
+            )}
+          </AlertDescription>
+        </Alert>
+      )}
     </div>
   );
 }
@@ -0,0 +1,110 @@
+"use client"
+
+import { useEffect, useState } from "react"
+import { FolderOpen } from "lucide-react"
+import { Switch } from "./ui/switch"
+import { invoke } from "@tauri-apps/api/core"
+
+interface PluginManifest {
+  id: string
+  name: string
+  version: string | null
+  description: string | null
+  events: string[]
+}
+
+interface InstalledPlugin {
+  dir: string
+  manifest: PluginManifest | null
+  error: string | null
+  enabled: boolean
+}
+
+export function PluginSettings() {
+  const [plugins, setPlugins] = useState<InstalledPlugin[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = async () => {
+    try {
+      setPlugins(await invoke<InstalledPlugin[]>('list_plugins'));
+    } catch (loadError) {
+      console.error('Failed to load plugins:', loadError);
+    }
+  };
+
+  useEffect(() => {
+    load();
+  }, []);
+
+  const setEnabled = async (pluginId: string, enabled: boolean) => {
+    setError(null);
+    try {
+      setPlugins(await invoke<InstalledPlugin[]>('set_plugin_enabled', { pluginId, enabled }));
+    } catch (saveError) {
+      console.error('Failed to change plugin:', saveError);
+      setError(String(saveError));
+    }
+  };
+
+  const openFolder = async () => {
+    try {
+      await invoke('open_plugins_folder');
+    } catch (openError) {
+      console.error('Failed to open plugins folder:', openError);
+    }
+  };
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <div className="flex items-start justify-between">
+        <div>
+          <h3 className="text-lg font-semibold text-gray-900 mb-2">Plugins</h3>
+          <p className="text-sm text-gray-600">
+            Run your own programs when a transcript or summary is ready, e.g. to create tickets from action items. Plugins can change the title, transcript and summary, so only turn on plugins you trust.
+          </p>
+        </div>
+        <div className="flex gap-2 shrink-0 ml-4">
+          <button onClick={load} className="px-3 py-2 text-sm border rounded-md hover:bg-gray-50">
+            Reload
+          </button>
+          <button onClick={openFolder} className="flex items-center gap-2 px-3 py-2 text-sm border rounded-md hover:bg-gray-50">
+            <FolderOpen className="w-4 h-4" />
+            Open folder
+          </button>
+        </div>
+      </div>
+
+      <div className="mt-4 space-y-3 border-t pt-4">
+        {plugins.length === 0 && (
+          <p className="text-sm text-gray-500">No plugins installed. Copy a plugin&apos;s folder into the plugins folder and reload.</p>
+        )}
+        {plugins.map((plugin) => (
+          <div key={plugin.dir} className="flex items-center justify-between gap-4">
+            {plugin.manifest ? (
+              <>
+                <div>
+                  <p className="text-sm font-medium text-gray-800">
+                    {plugin.manifest.name}
+                    {plugin.manifest.version && <span className="ml-2 text-xs text-gray-500">{plugin.manifest.version}</span>}
+                  </p>
+                  {plugin.manifest.description && <p className="text-xs text-gray-600">{plugin.manifest.description}</p>}
+                  <p className="text-xs text-gray-500">Runs on {plugin.manifest.events.join(', ')}</p>
+                </div>
+                <Switch
+                  checked={plugin.enabled}
+                  onCheckedChange={(enabled) => setEnabled(plugin.manifest!.id, enabled)}
+                />
+              </>
+            ) : (
+              <div>
+                <p className="text-sm font-medium text-gray-800 break-all">{plugin.dir}</p>
+                <p className="text-xs text-red-600">{plugin.error}</p>
+              </div>
+            )}
+          </div>
+        ))}
+        {error && <p className="text-xs text-red-600">{error}</p>}
+      </div>
+    </div>
+  )
+}
@@ -6,6 +6,15 @@ import { FolderOpen } from "lucide-react"
 import { invoke } from "@tauri-apps/api/core"
 import Analytics from "@/lib/analytics"
 import AnalyticsConsentSwitch from "./AnalyticsConsentSwitch"
+import { RedactionSettings } from "./RedactionSettings"
+import { LocalOnlySettings } from "./LocalOnlySettings"
+import { UsageSettings } from "./UsageSettings"
+import { WorkspaceSettings } from "./WorkspaceSettings"
+import { StorageSettings } from "./StorageSettings"
+import { BackupSettings } from "./BackupSettings"
+import { SyncSettings } from "./SyncSettings"
+import { LocalApiSettings } from "./LocalApiSettings"
+import { PluginSettings } from "./PluginSettings"
 
 interface StorageLocations {
   database: string
@@ -13,6 +22,11 @@ interface StorageLocations {
   recordings: string
 }
 
+interface EncryptionStatus {
+  enabled: boolean
+  restart_pending: boolean
+}
+
 interface NotificationSettings {
   recording_notifications: boolean
   time_based_reminders: boolean
@@ -28,6 +42,9 @@ interface NotificationSettings {
     show_recording_paused: boolean
     show_recording_resumed: boolean
     show_transcription_complete: boolean
+    show_summary_ready: boolean
+    show_disk_space_low: boolean
+    show_permission_problems: boolean
     show_meeting_reminders: boolean
     show_system_errors: boolean
     meeting_reminder_minutes: number[]
@@ -41,6 +58,9 @@ export function PreferenceSettings() {
   const [loading, setLoading] = useState(true);
   const [isInitialLoad, setIsInitialLoad] = useState(true);
   const [previousNotificationsEnabled, setPreviousNotificationsEnabled] = useState<boolean | null>(null);
+  const [encryption, setEncryption] = useState<EncryptionStatus | null>(null);
+  const [encryptionSwitching, setEncryptionSwitching] = useState(false);
+  const [encryptionError, setEncryptionError] = useState<string | null>(null);
 
   useEffect(() => {
     const loadPreferences = async () => {
@@ -74,6 +94,12 @@ export function PreferenceSettings() {
           recordings: recordingsDir
         });
 
+        try {
+          setEncryption(await invoke<EncryptionStatus>('get_encryption_status'));
+        } catch (encryptionLoadError) {
+          console.error('Failed to load encryption status:', encryptionLoadError);
+        }
+
         // Track preferences page view
         await Analytics.track('preferences_viewed', {
           notifications_enabled: settings?.notification_preferences.show_recording_started ? 'true' : 'false'
@@ -149,6 +175,21 @@ export function PreferenceSettings() {
     }
   };
 
+  // Both directions rewrite every meeting and then restart the app
+  const handleEncryptionChange = async (enabled: boolean) => {
+    setEncryptionSwitching(true);
+    setEncryptionError(null);
+    try {
+      await invoke(enabled ? 'enable_encryption' : 'disable_encryption');
+      setEncryption({ enabled, restart_pending: true });
+    } catch (error) {
+      console.error('Failed to switch encryption:', error);
+      setEncryptionError(String(error));
+    } finally {
+      setEncryptionSwitching(false);
+    }
+  };
+
   if (loading || notificationsEnabled === null) {
     return <div className="max-w-2xl mx-auto p-6">Loading Preferences...</div>
   }
@@ -227,6 +268,62 @@ export function PreferenceSettings() {
         </div>
       </div>
 
+      {/* Encryption Section */}
+      {encryption && (
+        <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+          <div className="flex items-center justify-between">
+            <div>
+              <h3 className="text-lg font-semibold text-gray-900 mb-2">Encrypt Meetings</h3>
+              <p className="text-sm text-gray-600">
+                Encrypt the database, recordings and transcripts with a key kept in your system keychain
+              </p>
+              <p className="text-xs text-gray-500 mt-1">
+                A recording in progress is encrypted once it is saved. Until then, and after a crash until it is recovered, its audio and transcript are stored unencrypted.
+              </p>
+            </div>
+            <Switch
+              checked={encryption.enabled}
+              disabled={encryptionSwitching || encryption.restart_pending}
+              onCheckedChange={handleEncryptionChange}
+            />
+          </div>
+          {encryptionSwitching && (
+            <p className="mt-3 text-xs text-gray-600">Processing meetings, Meetily restarts when done...</p>
+          )}
+          {encryption.restart_pending && !encryptionSwitching && (
+            <p className="mt-3 text-xs text-gray-600">Meetily restarts to finish.</p>
+          )}
+          {encryptionError && <p className="mt-3 text-xs text-red-600">{encryptionError}</p>}
+        </div>
+      )}
+
+      {/* Local-Only Section */}
+      <LocalOnlySettings />
+
+      {/* Redaction Section */}
+      <RedactionSettings />
+
+      {/* Workspaces Section */}
+      <WorkspaceSettings />
+
+      {/* Storage Location Section */}
+      <StorageSettings />
+
+      {/* Backups Section */}
+      <BackupSettings />
+
+      {/* Sync Section */}
+      <SyncSettings />
+
+      {/* Local API Section */}
+      <LocalApiSettings />
+
+      {/* Plugins Section */}
+      <PluginSettings />
+
+      {/* Provider Usage Section */}
+      <UsageSettings />
+
       {/* Analytics Section */}
       <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
         <AnalyticsConsentSwitch />
@@ -5,6 +5,7 @@ import { appDataDir } from '@tauri-apps/api/path';
 import { useCallback, useEffect, useState, useRef } from 'react';
 import { Play, Pause, Square, Mic, AlertCircle, X } from 'lucide-react';
 import { ProcessRequest, SummaryResponse } from '@/types/summary';
+import { AppErrorCode, isAppError } from '@/types';
 import { listen } from '@tauri-apps/api/event';
 import { Alert, AlertDescription, AlertTitle } from "@/components/ui/alert"
 import { Tooltip, TooltipContent, TooltipProvider, TooltipTrigger } from '@/components/ui/tooltip';
@@ -130,30 +131,20 @@ export const RecordingControls: React.FC<RecordingControlsProps> = ({
       onRecordingStart();
     } catch (error) {
       console.error('Failed to start recording:', error);
-      console.error('Error details:', {
-        message: error instanceof Error ? error.message : String(error),
-        name: error instanceof Error ? error.name : 'Unknown',
-        stack: error instanceof Error ? error.stack : undefined
-      });
-
-      // Parse error message to provide user-friendly feedback
-      const errorMsg = error instanceof Error ? error.message : String(error);
 
-      // Check for device-related errors
-      if (errorMsg.includes('microphone') || errorMsg.includes('mic') || errorMsg.includes('input')) {
-        setDeviceError({
-          title: 'Microphone Not Available',
-          message: 'Unable to access your microphone. Please check that:\n• Your microphone is connected\n• The app has microphone permissions\n• No other app is using the microphone'
-        });
-      } else if (errorMsg.includes('system audio') || errorMsg.includes('speaker') || errorMsg.includes('output')) {
-        setDeviceError({
-          title: 'System Audio Not Available',
-          message: 'Unable to capture system audio. Please check that:\n• A virtual audio device (like BlackHole) is installed\n• The app has screen recording permissions (macOS)\n• System audio is properly configured'
-        });
-      } else if (errorMsg.includes('permission')) {
+      // The backend reports a code for the failures the user can act on
+      if (isAppError(error)) {
+        const titles: Partial<Record<AppErrorCode, string>> = {
+          PermissionDenied: 'Permission Required',
+          DeviceBusy: 'Audio Device Busy',
+          DeviceNotFound: 'Audio Device Not Found',
+          ModelMissing: 'Transcription Model Required',
+          DiskFull: 'Disk Full',
+          AlreadyRecording: 'Already Recording',
+        };
         setDeviceError({
-          title: 'Permission Required',
-          message: 'Recording permissions are required. Please:\n• Grant microphone access in System Settings\n• Grant screen recording access for system audio (macOS)\n• Restart the app after granting permissions'
+          title: titles[error.code] ?? 'Recording Failed',
+          message: error.hint ? `${error.message}\n\n${error.hint}` : error.message
         });
       } else {
         setDeviceError({
@@ -277,6 +268,17 @@ export const RecordingControls: React.FC<RecordingControlsProps> = ({
     };
   }, []);
 
+  // The backend asks to stop and save before the disk fills up
+  useEffect(() => {
+    const unlisten = listen('recording-disk-stop', (event) => {
+      console.warn('recording-disk-stop event received:', event.payload);
+      handleStopRecording();
+    });
+    return () => {
+      unlisten.then(unsubscribe => unsubscribe());
+    };
+  }, [handleStopRecording]);
+
   useEffect(() => {
     console.log('Setting up recording event listeners');
     let unsubscribes: (() => void)[] = [];
@@ -0,0 +1,136 @@
+"use client";
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { toast } from 'sonner';
+import { LifeBuoy } from 'lucide-react';
+import { Alert, AlertDescription, AlertTitle } from '@/components/ui/alert';
+import { Button } from '@/components/ui/button';
+import { useSidebar } from '@/components/Sidebar/SidebarProvider';
+
+interface OrphanedSession {
+  meeting_folder: string;
+  meeting_name: string | null;
+  started_at: string;
+  last_written_at: string;
+  committed_checkpoints: number;
+  pending_seconds: number;
+}
+
+interface RecoveredMeeting {
+  meeting_id: string;
+  audio_path: string;
+  job_id: string;
+}
+
+/** Seconds the incremental saver puts in each checkpoint */
+const CHECKPOINT_SECONDS = 30;
+
+function formatStart(startedAt: string): string {
+  const date = new Date(startedAt);
+  return isNaN(date.getTime()) ? startedAt : date.toLocaleString();
+}
+
+function formatLength(session: OrphanedSession): string {
+  const seconds = Math.round(session.committed_checkpoints * CHECKPOINT_SECONDS + session.pending_seconds);
+  return seconds >= 60 ? `about ${Math.round(seconds / 60)} min` : `${seconds} s`;
+}
+
+/**
+ * Offers recordings interrupted by a crash, force quit or sleep for recovery.
+ *
+ * The list is fetched on mount: the startup scan's `recording-recovery-available` event can
+ * fire before this component listens, so the event only triggers a refresh.
+ */
+export function RecordingRecovery() {
+  const [sessions, setSessions] = useState<OrphanedSession[]>([]);
+  const [busyFolder, setBusyFolder] = useState<string | null>(null);
+  const { refetchMeetings } = useSidebar();
+
+  const loadSessions = useCallback(async () => {
+    try {
+      setSessions(await invoke<OrphanedSession[]>('list_orphaned_recordings'));
+    } catch (error) {
+      console.error('Failed to list interrupted recordings:', error);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadSessions();
+    const unlisten = listen('recording-recovery-available', () => loadSessions());
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [loadSessions]);
+
+  const recover = async (session: OrphanedSession) => {
+    setBusyFolder(session.meeting_folder);
+    try {
+      await invoke<RecoveredMeeting>('recover_interrupted_recording', { meetingFolder: session.meeting_folder });
+      toast.success('Recording recovered', {
+        description: 'It was added to your meetings and is being transcribed.',
+      });
+      await refetchMeetings();
+    } catch (error) {
+      console.error('Failed to recover recording:', error);
+      toast.error('Failed to recover recording', { description: String(error) });
+    } finally {
+      setBusyFolder(null);
+      await loadSessions();
+    }
+  };
+
+  const dismiss = async (session: OrphanedSession) => {
+    setBusyFolder(session.meeting_folder);
+    try {
+      await invoke('dismiss_interrupted_recording', { meetingFolder: session.meeting_folder });
+    } catch (error) {
+      console.error('Failed to dismiss recording:', error);
+      toast.error('Failed to dismiss recording', { description: String(error) });
+    } finally {
+      setBusyFolder(null);
+      await loadSessions();
+    }
+  };
+
+  if (sessions.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="fixed bottom-4 right-4 z-50 flex w-96 flex-col gap-2">
+      {sessions.map((session) => (
+        <Alert
+          key={session.meeting_folder}
+          className="border-blue-500 bg-blue-50 text-blue-900 shadow-lg"
+          role="alert"
+          aria-live="polite"
+        >
+          <LifeBuoy className="h-4 w-4 text-blue-600" />
+          <AlertTitle className="font-semibold">Interrupted recording found</AlertTitle>
+          <AlertDescription className="mt-1 text-blue-800">
+            <strong>{session.meeting_name || 'Untitled recording'}</strong>, started{' '}
+            {formatStart(session.started_at)}, was not saved. {formatLength(session)} of audio can be recovered.
+            <div className="mt-3 flex gap-2">
+              <Button
+                size="sm"
+                onClick={() => recover(session)}
+                disabled={busyFolder !== null}
+              >
+                {busyFolder === session.meeting_folder ? 'Recovering…' : 'Recover'}
+              </Button>
+              <Button
+                size="sm"
+                variant="outline"
+                onClick={() => dismiss(session)}
+                disabled={busyFolder !== null}
+              >
+                Dismiss
+              </Button>
+            </div>
+          </AlertDescription>
+        </Alert>
+      ))}
+    </div>
+  );
+}
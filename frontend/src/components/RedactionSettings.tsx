@@ -0,0 +1,125 @@
+"use client"
+
+import { useEffect, useState } from "react"
+import { Switch } from "./ui/switch"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+
+interface RedactionSettingsValue {
+  enabled: boolean
+  emails: boolean
+  phone_numbers: boolean
+  card_numbers: boolean
+  names: boolean
+  profanity: boolean
+}
+
+interface NerModelStatus {
+  model: string
+  downloaded: boolean
+  path: string
+}
+
+type Category = Exclude<keyof RedactionSettingsValue, "enabled">
+
+const CATEGORIES: { key: Category; label: string }[] = [
+  { key: "emails", label: "Email addresses" },
+  { key: "phone_numbers", label: "Phone numbers" },
+  { key: "card_numbers", label: "Payment card numbers" },
+  { key: "names", label: "People's names" },
+  { key: "profanity", label: "Profanity" },
+]
+
+export function RedactionSettings() {
+  const [settings, setSettings] = useState<RedactionSettingsValue | null>(null);
+  const [model, setModel] = useState<NerModelStatus | null>(null);
+  const [downloadProgress, setDownloadProgress] = useState<number | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const load = async () => {
+      try {
+        setSettings(await invoke<RedactionSettingsValue>('get_redaction_settings'));
+        setModel(await invoke<NerModelStatus>('get_ner_model_status'));
+      } catch (loadError) {
+        console.error('Failed to load redaction settings:', loadError);
+      }
+    };
+    load();
+
+    const unlisten = listen<{ progress: number }>('ner-model-download-progress', (event) => {
+      setDownloadProgress(event.payload.progress);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const save = async (updated: RedactionSettingsValue) => {
+    setError(null);
+    try {
+      setSettings(await invoke<RedactionSettingsValue>('set_redaction_settings', { settings: updated }));
+    } catch (saveError) {
+      console.error('Failed to save redaction settings:', saveError);
+      setError(String(saveError));
+    }
+  };
+
+  const downloadModel = async () => {
+    setError(null);
+    setDownloadProgress(0);
+    try {
+      setModel(await invoke<NerModelStatus>('download_ner_model'));
+    } catch (downloadError) {
+      console.error('Failed to download name detection model:', downloadError);
+      setError(String(downloadError));
+    } finally {
+      setDownloadProgress(null);
+    }
+  };
+
+  if (!settings) {
+    return null;
+  }
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <div className="flex items-center justify-between">
+        <div>
+          <h3 className="text-lg font-semibold text-gray-900 mb-2">Redact Personal Data</h3>
+          <p className="text-sm text-gray-600">
+            Mask personal data in transcripts before they're summarized, and in exports. Saved meetings aren't changed.
+          </p>
+        </div>
+        <Switch checked={settings.enabled} onCheckedChange={(enabled) => save({ ...settings, enabled })} />
+      </div>
+
+      {settings.enabled && (
+        <div className="mt-4 space-y-3 border-t pt-4">
+          {CATEGORIES.map(({ key, label }) => (
+            <div key={key} className="flex items-center justify-between">
+              <span className="text-sm text-gray-700">{label}</span>
+              <div className="flex items-center gap-3">
+                {key === "names" && model && !model.downloaded && (
+                  <button
+                    onClick={downloadModel}
+                    disabled={downloadProgress !== null}
+                    className="text-xs text-blue-600 hover:underline disabled:text-gray-400"
+                  >
+                    {downloadProgress !== null ? `Downloading ${downloadProgress}%` : 'Download model (110 MB)'}
+                  </button>
+                )}
+                <Switch
+                  checked={settings[key]}
+                  disabled={key === "names" && !model?.downloaded}
+                  onCheckedChange={(checked) => save({ ...settings, [key]: checked })}
+                />
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+      {error && <p className="mt-3 text-xs text-red-600">{error}</p>}
+    </div>
+  )
+}
@@ -95,6 +95,7 @@ const Sidebar: React.FC = () => {
 
 
   const [deleteModalState, setDeleteModalState] = useState<{ isOpen: boolean; itemId: string | null }>({ isOpen: false, itemId: null });
+  const [secureWipe, setSecureWipe] = useState(false);
 
   useEffect(() => {
     // Note: Don't set hardcoded defaults - let DB be the source of truth
@@ -317,8 +318,9 @@ const Sidebar: React.FC = () => {
     
     try{
       const { invoke } = await import('@tauri-apps/api/core');
-      await invoke('api_delete_meeting', {
+      const { report } = await invoke<{ report: { secure_wipe: boolean; exports_deleted: number; errors: string[] } }>('api_delete_meeting', {
         meetingId: itemId,
+        options: { secure_wipe: secureWipe },
       });
       console.log('Meeting deleted successfully');
       const updatedMeetings = meetings.filter((m: CurrentMeeting) => m.id !== itemId);
@@ -329,7 +331,9 @@ const Sidebar: React.FC = () => {
 
       // Show success toast
       toast.success("Meeting deleted successfully", {
-        description: "All associated data has been removed"
+        description: report.errors.length > 0
+          ? `Some files couldn't be removed yet and will be deleted on the next start`
+          : `Its recording, transcript${report.exports_deleted > 0 ? `, ${report.exports_deleted} exported files` : ''} and search entries were ${report.secure_wipe ? 'overwritten and ' : ''}removed`
       });
 
       // If deleting the active meeting, navigate to home
@@ -350,6 +354,7 @@ const Sidebar: React.FC = () => {
       handleDelete(deleteModalState.itemId);
     }
     setDeleteModalState({ isOpen: false, itemId: null });
+    setSecureWipe(false);
   };
 
   // Handle modal editing of meeting names
@@ -796,7 +801,19 @@ const Sidebar: React.FC = () => {
         text="Are you sure you want to delete this meeting? This action cannot be undone."
         onConfirm={handleDeleteConfirm}
         onCancel={() => setDeleteModalState({ isOpen: false, itemId: null })}
-      />
+      >
+        <label className="flex items-start gap-2 mb-6 text-sm text-gray-600">
+          <input
+            type="checkbox"
+            checked={secureWipe}
+            onChange={(event) => setSecureWipe(event.target.checked)}
+            className="mt-1"
+          />
+          <span>
+            Securely overwrite the recording and files before deleting them. Slower, and SSDs or earlier backups may still keep a copy.
+          </span>
+        </label>
+      </ConfirmationModal>
 
       {/* Edit Meeting Title Modal */}
       <Dialog open={editModalState.isOpen} onOpenChange={(open) => {
@@ -0,0 +1,179 @@
+"use client"
+
+import { useEffect, useState } from "react"
+import { invoke } from "@tauri-apps/api/core"
+import { listen } from "@tauri-apps/api/event"
+
+interface StorageLocations {
+  database: string
+  recordings: string
+  templates: string | null
+  backups: string | null
+}
+
+interface FolderSize {
+  files: number
+  bytes: number
+}
+
+interface StorageMovePlan {
+  from: StorageLocations
+  target: string
+  database_bytes: number
+  recordings: FolderSize
+  templates: FolderSize
+  backups: FolderSize
+  total_bytes: number
+  available_bytes: number | null
+  meetings_moved: number
+  meetings_left: number
+}
+
+interface StorageMoveProgress {
+  stage: "files" | "database" | "done"
+  done_bytes: number
+  total_bytes: number
+}
+
+const STAGES: Record<StorageMoveProgress["stage"], string> = {
+  files: "Copying and checking files",
+  database: "Copying and checking the database",
+  done: "Restarting",
+}
+
+const formatSize = (bytes: number) => {
+  if (bytes >= 1e9) return `${(bytes / 1e9).toFixed(1)} GB`
+  if (bytes >= 1e6) return `${(bytes / 1e6).toFixed(1)} MB`
+  return `${Math.ceil(bytes / 1e3)} KB`
+}
+
+export function StorageSettings() {
+  const [locations, setLocations] = useState<StorageLocations | null>(null);
+  const [target, setTarget] = useState("");
+  const [plan, setPlan] = useState<StorageMovePlan | null>(null);
+  const [progress, setProgress] = useState<StorageMoveProgress | null>(null);
+  const [moving, setMoving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<StorageLocations>('get_storage_locations')
+      .then(setLocations)
+      .catch((loadError) => console.error('Failed to load storage locations:', loadError));
+
+    const unlisten = listen<StorageMoveProgress>('storage-move-progress', (event) => {
+      setProgress(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const check = async () => {
+    setError(null);
+    setPlan(null);
+    try {
+      setPlan(await invoke<StorageMovePlan>('plan_storage_move', { target }));
+    } catch (planError) {
+      console.error('Failed to check the new storage folder:', planError);
+      setError(String(planError));
+    }
+  };
+
+  // The app restarts from the new folder once everything is copied and checked
+  const move = async () => {
+    if (!plan) return;
+    setError(null);
+    setMoving(true);
+    try {
+      await invoke('move_storage', { target: plan.target });
+    } catch (moveError) {
+      console.error('Failed to move storage:', moveError);
+      setError(String(moveError));
+      setMoving(false);
+      setProgress(null);
+    }
+  };
+
+  if (!locations) {
+    return null;
+  }
+
+  const notEnoughSpace = plan?.available_bytes != null && plan.available_bytes < plan.total_bytes;
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <h3 className="text-lg font-semibold text-gray-900 mb-2">Storage Location</h3>
+      <p className="text-sm text-gray-600">
+        Move this workspace's database, snapshots, recordings and templates to another folder, such as an external drive. The old copy is deleted only after the new one has been checked and opened.
+      </p>
+
+      <div className="mt-4 space-y-1 text-xs">
+        <div><span className="text-gray-500">Database:</span> <span className="font-mono break-all">{locations.database}</span></div>
+        <div><span className="text-gray-500">Recordings:</span> <span className="font-mono break-all">{locations.recordings}</span></div>
+        {locations.templates && (
+          <div><span className="text-gray-500">Templates:</span> <span className="font-mono break-all">{locations.templates}</span></div>
+        )}
+      </div>
+
+      <div className="mt-4 border-t pt-4 space-y-2">
+        <div className="flex gap-2">
+          <input
+            value={target}
+            onChange={(event) => {
+              setTarget(event.target.value);
+              setPlan(null);
+            }}
+            disabled={moving}
+            placeholder="New folder (full path, new or empty)"
+            className="flex-1 px-3 py-2 text-sm border rounded-md font-mono"
+          />
+          <button
+            onClick={check}
+            disabled={!target.trim() || moving}
+            className="px-3 py-2 text-sm border rounded-md hover:bg-gray-50 disabled:text-gray-400"
+          >
+            Check
+          </button>
+        </div>
+
+        {plan && (
+          <div className="p-3 border rounded-lg bg-gray-50 text-sm space-y-1">
+            <div>
+              {formatSize(plan.total_bytes)} to copy: the database ({formatSize(plan.database_bytes)}), {plan.recordings.files} recording files
+              {plan.templates.files > 0 ? `, ${plan.templates.files} templates` : ''}
+              {plan.backups.files > 0 ? `, ${plan.backups.files} snapshots` : ''}
+            </div>
+            <div className="text-gray-600">
+              {plan.available_bytes != null ? `${formatSize(plan.available_bytes)} free in the new folder` : 'Free space in the new folder is unknown'}
+            </div>
+            <div className="text-gray-600">
+              {plan.meetings_moved} meetings move with the recordings folder
+              {plan.meetings_left > 0 ? `; ${plan.meetings_left} recorded elsewhere stay where they are` : ''}
+            </div>
+            {notEnoughSpace && <div className="text-red-600">There isn't enough free space there</div>}
+            <button
+              onClick={move}
+              disabled={moving || notEnoughSpace}
+              className="mt-2 px-3 py-2 text-sm bg-blue-600 text-white rounded-md hover:bg-blue-700 disabled:bg-gray-300"
+            >
+              {moving ? 'Moving...' : 'Move and restart'}
+            </button>
+          </div>
+        )}
+
+        {moving && progress && (
+          <div className="space-y-1">
+            <div className="h-2 bg-gray-200 rounded">
+              <div
+                className="h-2 bg-blue-600 rounded"
+                style={{ width: `${progress.total_bytes > 0 ? Math.min(100, (100 * progress.done_bytes) / progress.total_bytes) : 100}%` }}
+              />
+            </div>
+            <p className="text-xs text-gray-500">{STAGES[progress.stage]}</p>
+          </div>
+        )}
+      </div>
+      {error && <p className="mt-3 text-xs text-red-600">{error}</p>}
+    </div>
+  )
+}
@@ -0,0 +1,328 @@
+"use client"
+
+import { useEffect, useState } from "react"
+import { Switch } from "./ui/switch"
+import { invoke } from "@tauri-apps/api/core"
+
+type SyncTarget =
+  | { kind: "relay"; url: string }
+  | { kind: "s3"; endpoint: string; region: string; bucket: string; access_key_id: string; prefix: string }
+  | { kind: "web_dav"; url: string; username: string }
+
+interface SyncSettingsValue {
+  enabled: boolean
+  target: SyncTarget | null
+  device_name: string
+  interval_minutes: number
+}
+
+interface SyncStatus {
+  settings: SyncSettingsValue
+  has_secret: boolean
+  has_key: boolean
+}
+
+interface SyncReport {
+  pushed: number
+  pulled: number
+  deleted: number
+  conflicts: number
+  failed: number
+}
+
+interface SyncConflict {
+  id: string
+  meeting_id: string
+  segment_id: string
+  kept_text: string
+  other_text: string
+  other_device: string | null
+  created_at: string
+}
+
+const EMPTY_TARGETS: Record<SyncTarget["kind"], SyncTarget> = {
+  relay: { kind: "relay", url: "" },
+  s3: { kind: "s3", endpoint: "", region: "", bucket: "", access_key_id: "", prefix: "" },
+  web_dav: { kind: "web_dav", url: "", username: "" },
+}
+
+const SECRET_LABELS: Record<SyncTarget["kind"], string> = {
+  relay: "Relay token",
+  s3: "Secret access key",
+  web_dav: "Password",
+}
+
+export function SyncSettings() {
+  const [status, setStatus] = useState<SyncStatus | null>(null);
+  const [draft, setDraft] = useState<SyncSettingsValue | null>(null);
+  const [secret, setSecret] = useState("");
+  const [key, setKey] = useState<string | null>(null);
+  const [keyInput, setKeyInput] = useState("");
+  const [syncing, setSyncing] = useState(false);
+  const [report, setReport] = useState<SyncReport | null>(null);
+  const [conflicts, setConflicts] = useState<SyncConflict[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadConflicts = async () => {
+    try {
+      setConflicts(await invoke<SyncConflict[]>('list_sync_conflicts', { meetingId: null }));
+    } catch (loadError) {
+      console.error('Failed to load sync conflicts:', loadError);
+    }
+  };
+
+  useEffect(() => {
+    const load = async () => {
+      try {
+        const loaded = await invoke<SyncStatus>('get_sync_settings');
+        setStatus(loaded);
+        setDraft(loaded.settings);
+      } catch (loadError) {
+        console.error('Failed to load sync settings:', loadError);
+      }
+    };
+    load();
+    loadConflicts();
+  }, []);
+
+  const save = async (settings: SyncSettingsValue) => {
+    setError(null);
+    try {
+      const saved = await invoke<SyncStatus>('set_sync_settings', { settings, secret: secret || null });
+      setStatus(saved);
+      setDraft(saved.settings);
+      setSecret("");
+    } catch (saveError) {
+      console.error('Failed to save sync settings:', saveError);
+      setError(String(saveError));
+    }
+  };
+
+  const createKey = async () => {
+    setError(null);
+    try {
+      setKey(await invoke<string>('create_sync_key'));
+      setStatus((current) => current && { ...current, has_key: true });
+    } catch (keyError) {
+      console.error('Failed to create sync key:', keyError);
+      setError(String(keyError));
+    }
+  };
+
+  const enterKey = async () => {
+    setError(null);
+    try {
+      await invoke('set_sync_key', { key: keyInput });
+      setKeyInput("");
+      setStatus((current) => current && { ...current, has_key: true });
+    } catch (keyError) {
+      console.error('Failed to store sync key:', keyError);
+      setError(String(keyError));
+    }
+  };
+
+  const showKey = async () => {
+    try {
+      setKey(await invoke<string | null>('get_sync_key'));
+    } catch (keyError) {
+      console.error('Failed to read sync key:', keyError);
+      setError(String(keyError));
+    }
+  };
+
+  const syncNow = async () => {
+    setError(null);
+    setSyncing(true);
+    try {
+      setReport(await invoke<SyncReport>('sync_now'));
+      await loadConflicts();
+    } catch (syncError) {
+      console.error('Failed to sync:', syncError);
+      setError(String(syncError));
+    } finally {
+      setSyncing(false);
+    }
+  };
+
+  const resolve = async (id: string, keepOther: boolean) => {
+    try {
+      await invoke('resolve_sync_conflict', { id, keepOther });
+      await loadConflicts();
+    } catch (resolveError) {
+      console.error('Failed to resolve sync conflict:', resolveError);
+      setError(String(resolveError));
+    }
+  };
+
+  if (!status || !draft) {
+    return null;
+  }
+
+  const target = draft.target;
+  const setTargetField = (field: string, value: string) => {
+    if (target) {
+      setDraft({ ...draft, target: { ...target, [field]: value } as SyncTarget });
+    }
+  };
+  const field = (name: string, placeholder: string, value: string) => (
+    <input
+      value={value}
+      onChange={(event) => setTargetField(name, event.target.value)}
+      placeholder={placeholder}
+      className="w-full px-3 py-2 text-sm border rounded-md"
+    />
+  );
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <div className="flex items-center justify-between">
+        <div>
+          <h3 className="text-lg font-semibold text-gray-900 mb-2">Sync Between Devices</h3>
+          <p className="text-sm text-gray-600">
+            Copy meetings, transcripts and summaries to your other devices through a relay, an S3 bucket or a WebDAV folder. Everything is encrypted on this device first. Recordings stay here.
+          </p>
+        </div>
+        <Switch checked={status.settings.enabled} onCheckedChange={(enabled) => save({ ...draft, enabled })} />
+      </div>
+
+      <div className="mt-4 space-y-2 border-t pt-4">
+        <select
+          value={target?.kind ?? ""}
+          onChange={(event) => {
+            const kind = event.target.value as SyncTarget["kind"] | "";
+            setDraft({ ...draft, target: kind ? EMPTY_TARGETS[kind] : null });
+          }}
+          className="w-full px-3 py-2 text-sm border rounded-md"
+        >
+          <option value="">Choose a sync location</option>
+          <option value="relay">Sync relay</option>
+          <option value="s3">S3-compatible bucket</option>
+          <option value="web_dav">WebDAV folder</option>
+        </select>
+        {target?.kind === "relay" && field("url", "Relay URL, e.g. https://sync.example.com", target.url)}
+        {target?.kind === "s3" && (
+          <>
+            {field("endpoint", "Endpoint, e.g. https://s3.eu-west-1.amazonaws.com", target.endpoint)}
+            {field("region", "Region, e.g. eu-west-1", target.region)}
+            {field("bucket", "Bucket", target.bucket)}
+            {field("prefix", "Folder in the bucket (optional)", target.prefix)}
+            {field("access_key_id", "Access key ID", target.access_key_id)}
+          </>
+        )}
+        {target?.kind === "web_dav" && (
+          <>
+            {field("url", "Folder URL, e.g. https://cloud.example.com/remote.php/dav/files/me/meetily", target.url)}
+            {field("username", "Username", target.username)}
+          </>
+        )}
+        {target && (
+          <input
+            type="password"
+            value={secret}
+            onChange={(event) => setSecret(event.target.value)}
+            placeholder={status.has_secret ? `${SECRET_LABELS[target.kind]} (saved)` : SECRET_LABELS[target.kind]}
+            className="w-full px-3 py-2 text-sm border rounded-md"
+          />
+        )}
+        <div className="flex gap-2">
+          <input
+            value={draft.device_name}
+            onChange={(event) => setDraft({ ...draft, device_name: event.target.value })}
+            placeholder="This device's name"
+            className="flex-1 px-3 py-2 text-sm border rounded-md"
+          />
+          <select
+            value={draft.interval_minutes}
+            onChange={(event) => setDraft({ ...draft, interval_minutes: Number(event.target.value) })}
+            className="px-3 py-2 text-sm border rounded-md"
+          >
+            {[5, 15, 30, 60, 240].map((minutes) => (
+              <option key={minutes} value={minutes}>Every {minutes < 60 ? `${minutes} min` : `${minutes / 60} h`}</option>
+            ))}
+          </select>
+        </div>
+        <button
+          onClick={() => save({ ...draft, enabled: status.settings.enabled })}
+          className="px-3 py-2 text-sm bg-blue-600 text-white rounded-md hover:bg-blue-700"
+        >
+          Save location
+        </button>
+      </div>
+
+      <div className="mt-4 space-y-2 border-t pt-4">
+        <div className="text-sm font-medium text-gray-900">Sync key</div>
+        {status.has_key ? (
+          key ? (
+            <p className="text-xs font-mono break-all p-2 bg-gray-50 border rounded-md">{key}</p>
+          ) : (
+            <button onClick={showKey} className="text-xs text-blue-600 hover:underline">
+              Show the key to enter on your other devices
+            </button>
+          )
+        ) : (
+          <button onClick={createKey} className="text-xs text-blue-600 hover:underline">
+            Create a sync key (on your first device)
+          </button>
+        )}
+        <div className="flex gap-2">
+          <input
+            value={keyInput}
+            onChange={(event) => setKeyInput(event.target.value)}
+            placeholder="Or enter the key from another device"
+            className="flex-1 px-3 py-2 text-sm border rounded-md font-mono"
+          />
+          <button
+            onClick={enterKey}
+            disabled={!keyInput.trim()}
+            className="px-3 py-2 text-sm border rounded-md hover:bg-gray-50 disabled:text-gray-400"
+          >
+            Use key
+          </button>
+        </div>
+        <p className="text-xs text-gray-500">Anyone with the key can read your synced meetings. Without it, they can't be recovered.</p>
+      </div>
+
+      {status.settings.enabled && (
+        <div className="mt-4 border-t pt-4">
+          <button
+            onClick={syncNow}
+            disabled={syncing}
+            className="px-3 py-2 text-sm border rounded-md hover:bg-gray-50 disabled:text-gray-400"
+          >
+            {syncing ? 'Syncing...' : 'Sync now'}
+          </button>
+          {report && (
+            <p className="mt-2 text-xs text-gray-600">
+              Sent {report.pushed}, received {report.pulled}, deleted {report.deleted}
+              {report.conflicts > 0 && `, ${report.conflicts} conflicting edit(s)`}
+              {report.failed > 0 && `, ${report.failed} failed`}
+            </p>
+          )}
+        </div>
+      )}
+
+      {conflicts.length > 0 && (
+        <div className="mt-4 space-y-2 border-t pt-4">
+          <div className="text-sm font-medium text-gray-900">Conflicting edits</div>
+          {conflicts.map((conflict) => (
+            <div key={conflict.id} className="p-3 border rounded-lg bg-gray-50 text-sm space-y-1">
+              <div className="text-gray-900">Kept: {conflict.kept_text}</div>
+              <div className="text-gray-600">
+                {conflict.other_device ?? 'Other device'}: {conflict.other_text}
+              </div>
+              <div className="flex gap-3">
+                <button onClick={() => resolve(conflict.id, false)} className="text-xs text-blue-600 hover:underline">
+                  Keep
+                </button>
+                <button onClick={() => resolve(conflict.id, true)} className="text-xs text-blue-600 hover:underline">
+                  Use the other edit
+                </button>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+      {error && <p className="mt-3 text-xs text-red-600">{error}</p>}
+    </div>
+  )
+}
@@ -0,0 +1,111 @@
+"use client"
+
+import { useEffect, useState } from "react"
+import { invoke } from "@tauri-apps/api/core"
+
+interface UsageLine {
+  kind: "llm" | "stt"
+  provider: string
+  model: string
+  requests: number
+  prompt_tokens: number
+  completion_tokens: number
+  audio_minutes: number
+  estimated_tokens: boolean
+  estimated_cost_usd: number | null
+}
+
+interface MeetingUsage {
+  meeting_id: string
+  meeting_title: string | null
+  estimated_cost_usd: number
+}
+
+interface UsageReport {
+  lines: UsageLine[]
+  meetings: MeetingUsage[]
+  total_cost_usd: number
+  has_unpriced: boolean
+}
+
+const PERIODS: { days: number | null; label: string }[] = [
+  { days: 7, label: "7 days" },
+  { days: 30, label: "30 days" },
+  { days: null, label: "All time" },
+]
+
+const formatCost = (usd: number) => (usd > 0 && usd < 0.01 ? "< $0.01" : `$${usd.toFixed(2)}`)
+
+const describeUsage = (line: UsageLine) =>
+  line.kind === "stt"
+    ? `${line.audio_minutes.toFixed(1)} min of audio`
+    : `${(line.prompt_tokens + line.completion_tokens).toLocaleString()} tokens${line.estimated_tokens ? " (estimated)" : ""}`
+
+export function UsageSettings() {
+  const [days, setDays] = useState<number | null>(30);
+  const [report, setReport] = useState<UsageReport | null>(null);
+
+  useEffect(() => {
+    invoke<UsageReport>('get_usage_report', { days })
+      .then(setReport)
+      .catch((loadError) => console.error('Failed to load provider usage:', loadError));
+  }, [days]);
+
+  if (!report) {
+    return null;
+  }
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <div className="flex items-start justify-between">
+        <div>
+          <h3 className="text-lg font-semibold text-gray-900 mb-2">Provider Usage</h3>
+          <p className="text-sm text-gray-600">
+            What was sent to hosted transcription and summary providers, with costs estimated from list prices
+          </p>
+        </div>
+        <div className="flex gap-1">
+          {PERIODS.map((period) => (
+            <button
+              key={period.label}
+              onClick={() => setDays(period.days)}
+              className={`rounded px-2 py-1 text-xs ${days === period.days ? 'bg-gray-900 text-white' : 'text-gray-600 hover:bg-gray-100'}`}
+            >
+              {period.label}
+            </button>
+          ))}
+        </div>
+      </div>
+
+      {report.lines.length === 0 ? (
+        <p className="mt-4 text-sm text-gray-500">No hosted providers were used in this period.</p>
+      ) : (
+        <div className="mt-4 space-y-2 border-t pt-4">
+          {report.lines.map((line) => (
+            <div key={`${line.kind}-${line.provider}-${line.model}`} className="flex items-center justify-between text-sm">
+              <div>
+                <span className="text-gray-900">{line.provider} · {line.model}</span>
+                <span className="ml-2 text-xs text-gray-500">
+                  {describeUsage(line)}, {line.requests} request{line.requests === 1 ? "" : "s"}
+                </span>
+              </div>
+              <span className="text-gray-700">
+                {line.estimated_cost_usd === null ? "Unknown" : formatCost(line.estimated_cost_usd)}
+              </span>
+            </div>
+          ))}
+          <div className="flex items-center justify-between border-t pt-2 text-sm font-medium">
+            <span>Estimated total</span>
+            <span>{formatCost(report.total_cost_usd)}{report.has_unpriced ? " + unpriced models" : ""}</span>
+          </div>
+          {report.meetings.length > 0 && (
+            <p className="text-xs text-gray-500">
+              Most expensive meeting: {report.meetings[0].meeting_title ?? "a deleted meeting"} (
+              {formatCost(report.meetings[0].estimated_cost_usd)})
+            </p>
+          )}
+        </div>
+      )}
+    </div>
+  )
+}
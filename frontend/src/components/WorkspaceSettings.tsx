@@ -0,0 +1,140 @@
+"use client"
+
+import { useEffect, useState } from "react"
+import { invoke } from "@tauri-apps/api/core"
+
+interface Workspace {
+  id: string
+  name: string
+  storage_dir: string | null
+}
+
+interface WorkspaceList {
+  workspaces: Workspace[]
+  active: string
+}
+
+export function WorkspaceSettings() {
+  const [list, setList] = useState<WorkspaceList | null>(null);
+  const [name, setName] = useState("");
+  const [storageDir, setStorageDir] = useState("");
+  const [switching, setSwitching] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = async () => {
+    try {
+      setList(await invoke<WorkspaceList>('list_workspaces'));
+    } catch (loadError) {
+      console.error('Failed to load workspaces:', loadError);
+    }
+  };
+
+  useEffect(() => {
+    load();
+  }, []);
+
+  const create = async () => {
+    setError(null);
+    try {
+      await invoke<Workspace>('create_workspace', { name, storageDir: storageDir || null });
+      setName("");
+      setStorageDir("");
+      await load();
+    } catch (createError) {
+      console.error('Failed to create workspace:', createError);
+      setError(String(createError));
+    }
+  };
+
+  const remove = async (id: string) => {
+    setError(null);
+    try {
+      await invoke('remove_workspace', { id });
+      await load();
+    } catch (removeError) {
+      console.error('Failed to remove workspace:', removeError);
+      setError(String(removeError));
+    }
+  };
+
+  // The app restarts into the other workspace
+  const switchTo = async (id: string) => {
+    setError(null);
+    setSwitching(id);
+    try {
+      await invoke('switch_workspace', { id });
+    } catch (switchError) {
+      console.error('Failed to switch workspace:', switchError);
+      setError(String(switchError));
+      setSwitching(null);
+    }
+  };
+
+  if (!list) {
+    return null;
+  }
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <h3 className="text-lg font-semibold text-gray-900 mb-2">Workspaces</h3>
+      <p className="text-sm text-gray-600">
+        Each workspace has its own meetings, recordings, provider settings, API keys and templates, stored in its own folder. Switching restarts the app.
+      </p>
+
+      <div className="mt-4 space-y-2">
+        {list.workspaces.map((workspace) => (
+          <div key={workspace.id} className="flex items-center justify-between p-3 border rounded-lg bg-gray-50">
+            <div className="min-w-0">
+              <div className="font-medium text-sm text-gray-900">{workspace.name}</div>
+              <div className="text-xs text-gray-500 font-mono break-all">{workspace.storage_dir ?? 'Default locations'}</div>
+            </div>
+            <div className="flex items-center gap-3 shrink-0">
+              {workspace.id === list.active ? (
+                <span className="text-xs text-green-700">Open</span>
+              ) : (
+                <>
+                  <button
+                    onClick={() => switchTo(workspace.id)}
+                    disabled={switching !== null}
+                    className="text-xs text-blue-600 hover:underline disabled:text-gray-400"
+                  >
+                    {switching === workspace.id ? 'Switching...' : 'Switch'}
+                  </button>
+                  {workspace.id !== 'default' && (
+                    <button onClick={() => remove(workspace.id)} className="text-xs text-red-600 hover:underline">
+                      Remove
+                    </button>
+                  )}
+                </>
+              )}
+            </div>
+          </div>
+        ))}
+      </div>
+
+      <div className="mt-4 border-t pt-4 space-y-2">
+        <input
+          value={name}
+          onChange={(event) => setName(event.target.value)}
+          placeholder="Workspace name, e.g. Client A"
+          className="w-full px-3 py-2 text-sm border rounded-md"
+        />
+        <input
+          value={storageDir}
+          onChange={(event) => setStorageDir(event.target.value)}
+          placeholder="Storage folder (optional, full path)"
+          className="w-full px-3 py-2 text-sm border rounded-md font-mono"
+        />
+        <button
+          onClick={create}
+          disabled={!name.trim()}
+          className="px-3 py-2 text-sm bg-blue-600 text-white rounded-md hover:bg-blue-700 disabled:bg-gray-300"
+        >
+          Add workspace
+        </button>
+        <p className="text-xs text-gray-500">Removing a workspace leaves its folder in place; adding the folder again brings it back.</p>
+      </div>
+      {error && <p className="mt-3 text-xs text-red-600">{error}</p>}
+    </div>
+  )
+}
@@ -1,9 +1,38 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+
+export type PreflightStatus = 'ok' | 'warn' | 'fail';
+
+export interface PreflightItem {
+  status: PreflightStatus;
+  detail: string;
+  fix: string | null;
+}
+
+export interface PreflightReport {
+  microphone_permission: PreflightItem;
+  system_audio_permission: PreflightItem;
+  input_device: PreflightItem;
+  output_tap: PreflightItem;
+  disk_space: PreflightItem;
+  transcription_model: PreflightItem;
+  ready: boolean;
+}
+
+export type MicrophonePermission = 'authorized' | 'denied' | 'not_determined' | 'restricted';
+export type SystemAudioPermission = 'authorized' | 'denied' | 'unknown' | 'not_required';
+
+/** Payload of `permission-state-changed`, emitted whenever access is granted or revoked */
+export interface PermissionState {
+  mic: MicrophonePermission;
+  system_audio: SystemAudioPermission;
+}
 
 export interface PermissionStatus {
   hasMicrophone: boolean;
   hasSystemAudio: boolean;
+  report: PreflightReport | null;
   isChecking: boolean;
   error: string | null;
 }
@@ -12,6 +41,7 @@ export function usePermissionCheck() {
   const [status, setStatus] = useState<PermissionStatus>({
     hasMicrophone: false,
     hasSystemAudio: false,
+    report: null,
     isChecking: true,
     error: null,
   });
@@ -20,28 +50,17 @@ export function usePermissionCheck() {
     setStatus(prev => ({ ...prev, isChecking: true, error: null }));
 
     try {
-      // Get audio devices to check for microphone and system audio availability
-      const devices = await invoke<Array<{ name: string; device_type: 'Input' | 'Output' }>>('get_audio_devices');
-
-      // Check for microphone devices (Input)
-      const inputDevices = devices.filter(d => d.device_type === 'Input');
-      const hasMicrophone = inputDevices.length > 0;
+      // One report covers the permissions, the devices, disk space and the transcription model
+      const report = await invoke<PreflightReport>('preflight_check');
+      const hasMicrophone = report.microphone_permission.status !== 'fail' && report.input_device.status !== 'fail';
+      const hasSystemAudio = report.system_audio_permission.status === 'ok' && report.output_tap.status === 'ok';
 
-      // Check for system audio devices (Output)
-      // On macOS, we need ScreenCaptureKit devices for system audio
-      const outputDevices = devices.filter(d => d.device_type === 'Output');
-      const hasSystemAudio = outputDevices.length > 0;
-
-      console.log('Permission check:', {
-        hasMicrophone,
-        hasSystemAudio,
-        inputDevices: inputDevices.length,
-        outputDevices: outputDevices.length
-      });
+      console.log('Preflight check:', report);
 
       setStatus({
         hasMicrophone,
         hasSystemAudio,
+        report,
         isChecking: false,
         error: null,
       });
@@ -52,6 +71,7 @@ export function usePermissionCheck() {
       setStatus({
         hasMicrophone: false,
         hasSystemAudio: false,
+        report: null,
         isChecking: false,
         error: error instanceof Error ? error.message : 'Failed to check permissions',
       });
@@ -61,8 +81,8 @@ export function usePermissionCheck() {
 
   const requestPermissions = async () => {
     try {
-      // Trigger audio permission by trying to access devices
-      await invoke('get_audio_devices');
+      // Shows the system prompt, or opens the settings where access is granted
+      await invoke('request_microphone_permission_command');
 
       // Recheck after triggering
       setTimeout(() => {
@@ -73,9 +93,26 @@ export function usePermissionCheck() {
     }
   };
 
-  // Check permissions on mount
+  // Check permissions on mount, and follow grants and revocations made in the system settings
   useEffect(() => {
     checkPermissions();
+
+    const unlisten = listen<PermissionState>('permission-state-changed', (event) => {
+      const { mic, system_audio } = event.payload;
+      console.log('Permission state changed:', event.payload);
+      setStatus(prev => ({
+        ...prev,
+        hasMicrophone: mic !== 'denied' && mic !== 'restricted' && prev.report?.input_device.status !== 'fail',
+        hasSystemAudio:
+          (system_audio === 'authorized' || system_audio === 'not_required') &&
+          prev.report?.output_tap.status !== 'fail',
+      }));
+      // The report's permission rows and fixes are stale now
+      checkPermissions();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
   return {
@@ -84,3 +84,25 @@ export interface SummaryDataResponse {
   _section_order?: string[];
   [key: string]: any; // For legacy section data
 }
+
+// Error returned by backend commands that report structured errors
+export type AppErrorCode =
+  | 'PermissionDenied'
+  | 'DeviceBusy'
+  | 'DeviceNotFound'
+  | 'ModelMissing'
+  | 'DiskFull'
+  | 'AlreadyRecording'
+  | 'NotRecording'
+  | 'Internal';
+
+export interface AppError {
+  code: AppErrorCode;
+  message: string;
+  recoverable: boolean;
+  hint: string | null;
+}
+
+export function isAppError(error: unknown): error is AppError {
+  return typeof error === 'object' && error !== null && 'code' in error && 'message' in error;
+}